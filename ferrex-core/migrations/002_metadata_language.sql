@@ -0,0 +1,5 @@
+-- Language preference (e.g. 'de-DE,en-US') the TMDB metadata was fetched
+-- with. Rows with a different or missing value are refetched on reuse so a
+-- preference change never keeps serving text in the old language.
+ALTER TABLE ferrex.movie_metadata ADD COLUMN metadata_language text;
+ALTER TABLE ferrex.series_metadata ADD COLUMN metadata_language text;
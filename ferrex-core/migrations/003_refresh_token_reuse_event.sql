@@ -0,0 +1,2 @@
+-- Audit replays of already-rotated refresh tokens.
+ALTER TYPE ferrex.auth_event_type ADD VALUE IF NOT EXISTS 'refresh_token_reuse';
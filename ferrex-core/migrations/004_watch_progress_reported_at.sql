@@ -0,0 +1,11 @@
+-- Client-reported timestamp (unix ms) of the last accepted progress update,
+-- used to reject late reports from other devices.
+ALTER TABLE ferrex.user_watch_progress
+    ADD COLUMN IF NOT EXISTS reported_at bigint;
+
+UPDATE ferrex.user_watch_progress
+SET reported_at = last_watched
+WHERE reported_at IS NULL;
+
+ALTER TABLE ferrex.user_watch_progress
+    ALTER COLUMN reported_at SET NOT NULL;
@@ -0,0 +1,4 @@
+-- Tiny 4x4 PNG shown while a requested image size is still being cached.
+-- Computed once per image from the first size that is downloaded.
+ALTER TABLE ferrex.tmdb_image_variants
+    ADD COLUMN IF NOT EXISTS placeholder bytea;
@@ -0,0 +1,7 @@
+-- TMDB id an operator pinned a movie or series to. Rescans reuse it instead
+-- of matching on the file or folder name again.
+ALTER TABLE ferrex.movie_references
+    ADD COLUMN IF NOT EXISTS tmdb_id_override bigint;
+
+ALTER TABLE ferrex.series
+    ADD COLUMN IF NOT EXISTS tmdb_id_override bigint;
@@ -0,0 +1,8 @@
+-- Keyset pagination for the recently added feed orders by
+-- (discovered_at, id); these cover the unfiltered movie scan and give the
+-- library-filtered scan a deterministic tiebreak.
+CREATE INDEX IF NOT EXISTS idx_movie_refs_discovered_at_id
+    ON ferrex.movie_references USING btree (discovered_at DESC, id DESC);
+
+CREATE INDEX IF NOT EXISTS idx_movie_refs_library_discovered_at_id
+    ON ferrex.movie_references USING btree (library_id, discovered_at DESC, id DESC);
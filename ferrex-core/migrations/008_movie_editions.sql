@@ -0,0 +1,4 @@
+-- A movie can own several files (editions such as a director's cut). They
+-- are looked up by the owning movie id when a movie is loaded.
+CREATE INDEX IF NOT EXISTS idx_media_files_media_id
+    ON ferrex.media_files USING btree (media_id);
@@ -0,0 +1,50 @@
+-- A movie found in several libraries keeps one reference per library. The
+-- copies are grouped by TMDB id; the oldest copy is canonical and owns the
+-- movie's image variants, so artwork is downloaded once for all of them.
+CREATE OR REPLACE VIEW ferrex.movie_copies AS
+SELECT mr.id AS movie_id,
+       mr.library_id,
+       mr.tmdb_id,
+       mr.title,
+       mf.file_path,
+       first_value(mr.id) OVER copies AS canonical_id,
+       count(*) OVER (PARTITION BY mr.tmdb_id) AS copy_count
+FROM ferrex.movie_references mr
+JOIN ferrex.media_files mf ON mf.id = mr.file_id
+WHERE mr.tmdb_id <> 0
+WINDOW copies AS (PARTITION BY mr.tmdb_id ORDER BY mr.discovered_at, mr.id);
+
+-- When the canonical copy is deleted, the next oldest copy takes over its
+-- image variants so the remaining copies keep their artwork.
+CREATE OR REPLACE FUNCTION ferrex.hand_over_movie_images() RETURNS trigger
+    LANGUAGE plpgsql
+    AS $$
+DECLARE
+    successor uuid;
+BEGIN
+    IF OLD.tmdb_id = 0 THEN
+        RETURN OLD;
+    END IF;
+
+    SELECT mr.id
+    INTO successor
+    FROM ferrex.movie_references mr
+    WHERE mr.tmdb_id = OLD.tmdb_id
+      AND mr.id <> OLD.id
+    ORDER BY mr.discovered_at, mr.id
+    LIMIT 1;
+
+    IF successor IS NOT NULL THEN
+        UPDATE ferrex.tmdb_image_variants
+        SET media_id = successor
+        WHERE media_id = OLD.id
+          AND media_type = 'movie';
+    END IF;
+
+    RETURN OLD;
+END;
+$$;
+
+CREATE TRIGGER movie_references_hand_over_images
+    AFTER DELETE ON ferrex.movie_references
+    FOR EACH ROW EXECUTE FUNCTION ferrex.hand_over_movie_images();
@@ -0,0 +1,30 @@
+-- Music libraries hold audio tracks grouped by artist and album. Tracks are
+-- not matched against TMDB, so they live in their own table rather than in
+-- media_files and the media reference tables.
+ALTER TABLE ferrex.libraries
+    DROP CONSTRAINT libraries_library_type_check;
+ALTER TABLE ferrex.libraries
+    ADD CONSTRAINT libraries_library_type_check CHECK (((library_type)::text = ANY ((ARRAY['movies'::character varying, 'tvshows'::character varying, 'music'::character varying])::text[])));
+
+CREATE TABLE ferrex.music_tracks (
+    id uuid NOT NULL,
+    library_id uuid NOT NULL,
+    file_path text NOT NULL,
+    filename text NOT NULL,
+    file_size bigint NOT NULL,
+    artist text NOT NULL,
+    album text,
+    title text NOT NULL,
+    track_number integer,
+    disc_number integer,
+    year integer,
+    discovered_at timestamp with time zone DEFAULT now() NOT NULL,
+    updated_at timestamp with time zone DEFAULT now() NOT NULL,
+    CONSTRAINT music_tracks_pkey PRIMARY KEY (id),
+    CONSTRAINT music_tracks_file_path_key UNIQUE (file_path),
+    CONSTRAINT music_tracks_library_id_fkey FOREIGN KEY (library_id)
+        REFERENCES ferrex.libraries(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_music_tracks_library_artist_album
+    ON ferrex.music_tracks USING btree (library_id, lower(artist), lower(album));
@@ -0,0 +1,50 @@
+-- Named, per-user collections of movies and episodes ("Comfort Movies").
+-- Members reference the media tables directly so that deleting a movie or
+-- episode drops it from every collection while the collection itself stays.
+CREATE TABLE ferrex.user_collections (
+    id uuid DEFAULT ferrex.uuidv7() CONSTRAINT user_collections_pkey PRIMARY KEY,
+    user_id uuid NOT NULL,
+    name character varying(200) NOT NULL,
+    description text,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    updated_at timestamp with time zone DEFAULT now() NOT NULL,
+    CONSTRAINT user_collections_user_id_fkey FOREIGN KEY (user_id)
+        REFERENCES ferrex.users(id) ON DELETE CASCADE,
+    CONSTRAINT user_collections_name_not_blank CHECK (btrim(name) <> '')
+);
+
+CREATE UNIQUE INDEX user_collections_user_name_key
+    ON ferrex.user_collections USING btree (user_id, lower(name));
+
+CREATE TABLE ferrex.user_collection_items (
+    collection_id uuid NOT NULL,
+    movie_id uuid,
+    episode_id uuid,
+    -- Sort key within the collection; gaps are allowed.
+    "position" integer NOT NULL,
+    added_at timestamp with time zone DEFAULT now() NOT NULL,
+    CONSTRAINT user_collection_items_collection_id_fkey FOREIGN KEY (collection_id)
+        REFERENCES ferrex.user_collections(id) ON DELETE CASCADE,
+    CONSTRAINT user_collection_items_movie_id_fkey FOREIGN KEY (movie_id)
+        REFERENCES ferrex.movie_references(id) ON DELETE CASCADE,
+    CONSTRAINT user_collection_items_episode_id_fkey FOREIGN KEY (episode_id)
+        REFERENCES ferrex.episode_references(id) ON DELETE CASCADE,
+    CONSTRAINT user_collection_items_one_media CHECK (
+        (movie_id IS NULL) <> (episode_id IS NULL)
+    )
+);
+
+CREATE UNIQUE INDEX user_collection_items_movie_key
+    ON ferrex.user_collection_items USING btree (collection_id, movie_id)
+    WHERE movie_id IS NOT NULL;
+CREATE UNIQUE INDEX user_collection_items_episode_key
+    ON ferrex.user_collection_items USING btree (collection_id, episode_id)
+    WHERE episode_id IS NOT NULL;
+CREATE INDEX user_collection_items_order_idx
+    ON ferrex.user_collection_items USING btree (collection_id, "position");
+CREATE INDEX user_collection_items_movie_idx
+    ON ferrex.user_collection_items USING btree (movie_id)
+    WHERE movie_id IS NOT NULL;
+CREATE INDEX user_collection_items_episode_idx
+    ON ferrex.user_collection_items USING btree (episode_id)
+    WHERE episode_id IS NOT NULL;
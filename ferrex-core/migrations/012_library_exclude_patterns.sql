@@ -0,0 +1,5 @@
+-- Per-library exclude patterns: globs, or regexes prefixed with `re:`,
+-- matched against paths relative to the library root. Scans never walk
+-- matching paths, and rescans remove items indexed under them.
+ALTER TABLE ferrex.libraries
+    ADD COLUMN exclude_patterns text[] DEFAULT '{}'::text[] NOT NULL;
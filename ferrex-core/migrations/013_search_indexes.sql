@@ -0,0 +1,14 @@
+-- /search matches overviews with full-text search and cast names by
+-- trigram similarity. The expressions must match the repository queries
+-- exactly for the planner to use these indexes.
+CREATE INDEX IF NOT EXISTS idx_movie_metadata_overview_fts
+    ON ferrex.movie_metadata
+    USING gin (to_tsvector('english', COALESCE(overview, '')));
+
+CREATE INDEX IF NOT EXISTS idx_series_metadata_overview_fts
+    ON ferrex.series_metadata
+    USING gin (to_tsvector('english', COALESCE(overview, '')));
+
+CREATE INDEX IF NOT EXISTS idx_persons_name_trgm
+    ON ferrex.persons
+    USING gin (name public.gin_trgm_ops);
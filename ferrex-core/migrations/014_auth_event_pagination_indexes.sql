@@ -0,0 +1,10 @@
+-- Keyset pagination of the auth audit log orders by (created_at, id).
+-- These replace the created_at-only indexes, which they cover.
+CREATE INDEX IF NOT EXISTS idx_auth_events_created_at_id
+    ON ferrex.auth_events USING btree (created_at DESC, id DESC);
+
+CREATE INDEX IF NOT EXISTS idx_auth_events_user_created_at_id
+    ON ferrex.auth_events USING btree (user_id, created_at DESC, id DESC);
+
+DROP INDEX IF EXISTS ferrex.idx_auth_events_created_at;
+DROP INDEX IF EXISTS ferrex.idx_auth_events_user_created_at;
@@ -0,0 +1,28 @@
+-- Scan runs checkpoint into scan_state so a scan that was still running
+-- when the server stopped can be found and resumed on the next start.
+ALTER TABLE ferrex.scan_state
+    ADD COLUMN IF NOT EXISTS correlation_id uuid,
+    ADD COLUMN IF NOT EXISTS origin character varying(10) DEFAULT 'fresh'::character varying NOT NULL,
+    ADD COLUMN IF NOT EXISTS resumed_from uuid;
+
+ALTER TABLE ferrex.scan_state
+    ADD CONSTRAINT scan_state_origin_check CHECK (((origin)::text = ANY ((ARRAY['fresh'::character varying, 'resumed'::character varying])::text[])));
+
+ALTER TABLE ferrex.scan_state
+    ADD CONSTRAINT scan_state_resumed_from_fkey FOREIGN KEY (resumed_from) REFERENCES ferrex.scan_state(id) ON DELETE SET NULL;
+
+-- 'interrupted' marks runs that were unfinished when the server stopped.
+ALTER TABLE ferrex.scan_state DROP CONSTRAINT IF EXISTS scan_state_status_check;
+ALTER TABLE ferrex.scan_state
+    ADD CONSTRAINT scan_state_status_check CHECK (((status)::text = ANY ((ARRAY['pending'::character varying, 'running'::character varying, 'paused'::character varying, 'completed'::character varying, 'failed'::character varying, 'cancelled'::character varying, 'interrupted'::character varying])::text[])));
+
+CREATE INDEX IF NOT EXISTS idx_scan_state_unfinished
+    ON ferrex.scan_state USING btree (status, started_at DESC)
+    WHERE ((status)::text = ANY ((ARRAY['pending'::character varying, 'running'::character varying, 'paused'::character varying, 'interrupted'::character varying])::text[]));
+
+CREATE INDEX IF NOT EXISTS idx_scan_state_resumed_from
+    ON ferrex.scan_state USING btree (resumed_from)
+    WHERE (resumed_from IS NOT NULL);
+
+COMMENT ON COLUMN ferrex.scan_state.origin IS 'fresh for a newly started scan, resumed for one that picked up an interrupted scan';
+COMMENT ON COLUMN ferrex.scan_state.resumed_from IS 'The interrupted scan this scan resumed';
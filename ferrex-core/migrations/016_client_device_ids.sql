@@ -0,0 +1,20 @@
+-- Device sessions remember the X-Device-ID their client logged in with, so
+-- a second client presenting the same id under another user is noticed.
+ALTER TABLE ferrex.auth_device_sessions
+    ADD COLUMN IF NOT EXISTS client_device_id uuid;
+
+CREATE INDEX IF NOT EXISTS idx_auth_device_sessions_client_device_id
+    ON ferrex.auth_device_sessions USING btree (client_device_id)
+    WHERE (client_device_id IS NOT NULL);
+
+COMMENT ON COLUMN ferrex.auth_device_sessions.client_device_id IS 'X-Device-ID the client used when this session last logged in';
+
+-- Ids seen on more than one user's device; rate limiting no longer keys
+-- on them.
+CREATE TABLE IF NOT EXISTS ferrex.auth_contested_device_ids (
+    device_id uuid PRIMARY KEY,
+    first_seen_at timestamp with time zone DEFAULT now() NOT NULL,
+    last_seen_at timestamp with time zone DEFAULT now() NOT NULL
+);
+
+ALTER TYPE ferrex.auth_event_type ADD VALUE IF NOT EXISTS 'device_id_collision';
@@ -0,0 +1,42 @@
+-- Minimum viewer age implied by the stored certification, so listings can
+-- be filtered by content rating across rating systems.
+ALTER TABLE ferrex.movie_metadata
+    ADD COLUMN IF NOT EXISTS certification_age smallint;
+
+ALTER TABLE ferrex.series_metadata
+    ADD COLUMN IF NOT EXISTS certification_age smallint;
+
+CREATE INDEX IF NOT EXISTS idx_movie_metadata_certification_age
+    ON ferrex.movie_metadata USING btree (certification_age);
+
+CREATE INDEX IF NOT EXISTS idx_series_metadata_certification_age
+    ON ferrex.series_metadata USING btree (certification_age);
+
+COMMENT ON COLUMN ferrex.movie_metadata.certification_age IS 'Minimum viewer age of primary_certification; NULL when unrated or unrecognized';
+COMMENT ON COLUMN ferrex.series_metadata.certification_age IS 'Minimum viewer age of primary_content_rating; NULL when unrated or unrecognized';
+
+-- Backfill the common codes; the next metadata refresh writes the rest
+-- with the full mapping.
+UPDATE ferrex.movie_metadata
+SET certification_age = CASE upper(btrim(primary_certification))
+        WHEN 'G' THEN 0
+        WHEN 'U' THEN 0
+        WHEN 'PG' THEN 8
+        WHEN 'PG-13' THEN 13
+        WHEN 'R' THEN 17
+        WHEN 'NC-17' THEN 18
+        ELSE NULL
+    END
+WHERE certification_age IS NULL AND primary_certification IS NOT NULL;
+
+UPDATE ferrex.series_metadata
+SET certification_age = CASE upper(btrim(primary_content_rating))
+        WHEN 'TV-Y' THEN 0
+        WHEN 'TV-G' THEN 0
+        WHEN 'TV-Y7' THEN 7
+        WHEN 'TV-PG' THEN 8
+        WHEN 'TV-14' THEN 14
+        WHEN 'TV-MA' THEN 17
+        ELSE NULL
+    END
+WHERE certification_age IS NULL AND primary_content_rating IS NOT NULL;
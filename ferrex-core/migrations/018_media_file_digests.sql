@@ -0,0 +1,16 @@
+-- Head/tail digests taken by library verification. A later run compares a
+-- file against its digest to catch content that changed while the size and
+-- modification time stayed put.
+CREATE TABLE IF NOT EXISTS ferrex.media_file_digests (
+    file_id uuid CONSTRAINT media_file_digests_pkey PRIMARY KEY,
+    sample_bytes integer NOT NULL,
+    file_size bigint NOT NULL,
+    modified_at timestamp with time zone NOT NULL,
+    digest bytea NOT NULL,
+    computed_at timestamp with time zone DEFAULT now() NOT NULL,
+    CONSTRAINT media_file_digests_file_id_fkey FOREIGN KEY (file_id)
+        REFERENCES ferrex.media_files(id) ON DELETE CASCADE
+);
+
+COMMENT ON COLUMN ferrex.media_file_digests.sample_bytes IS 'Bytes sampled from each end of the file';
+COMMENT ON COLUMN ferrex.media_file_digests.modified_at IS 'Modification time of the file the samples were taken from';
@@ -0,0 +1,727 @@
+//! Typed async client for the v1 HTTP API.
+//!
+//! Methods wrap the routes in [`v1`] and return the same DTOs the server
+//! serializes, so clients and server cannot drift apart. The client keeps
+//! the session from [`FerrexClient::login`], attaches it as a bearer token,
+//! and on a `401` exchanges the refresh token once and retries the request.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), ferrex_core::api::client::ClientError> {
+//! use ferrex_core::api::client::FerrexClient;
+//!
+//! let client = FerrexClient::builder("https://media.example.com").build()?;
+//! client.login("alice", "hunter22", Some("living room")).await?;
+//! for item in client.continue_watching(Some(10)).await? {
+//!     println!("{} at {}s", item.media_id, item.position);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{sync::Arc, time::Duration};
+
+use reqwest::{Method, RequestBuilder, Response, StatusCode, header};
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::sync::{Mutex, RwLock};
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        routes::{utils, v1},
+        types::{
+            AddCollectionItemsRequest, ApiErrorBody, ApiResponse, Collection,
+            CollectionDetail, CreateCollectionRequest, EmbeddedSubtitleTrack,
+            LibraryStats, MediaCopy, MediaCredits, MusicAlbum, MusicTrack,
+            MusicTrackQuery, PersonAppearances, PlaybackTicketResponse,
+            RefreshRequest, ReorderCollectionRequest, StorageUsageReport,
+            Trailer, UpdateCollectionRequest,
+        },
+    },
+    domain::{
+        users::user::{AuthToken, LoginRequest, User},
+        watch::{InProgressItem, UpdateProgressRequest},
+    },
+    types::{Library, LibraryId, MovieID, media_id::MediaID},
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Failure of a client call, by HTTP status where the server answered.
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("invalid server URL `{0}`")]
+    InvalidUrl(String),
+    #[error("request failed: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("not signed in")]
+    NotAuthenticated,
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+    #[error("not found: {0}")]
+    NotFound(String),
+    #[error("conflict: {0}")]
+    Conflict(String),
+    #[error("rate limited")]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("server returned {status}: {message}")]
+    Api { status: StatusCode, message: String },
+    #[error("malformed response: {0}")]
+    Decode(String),
+}
+
+impl ClientError {
+    /// Map an error status and its body to an error. The body may be an
+    /// [`ApiErrorBody`], an [`ApiResponse`] carrying `error`, or plain text.
+    pub fn from_status(
+        status: StatusCode,
+        body: &str,
+        retry_after: Option<Duration>,
+    ) -> Self {
+        let message = serde_json::from_str::<ApiErrorBody>(body)
+            .map(|body| body.error.message)
+            .or_else(|_| {
+                serde_json::from_str::<ApiResponse<serde_json::Value>>(body)
+                    .ok()
+                    .and_then(|response| response.error)
+                    .ok_or(())
+            })
+            .unwrap_or_else(|_| body.trim().to_string());
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized(message),
+            StatusCode::FORBIDDEN => Self::Forbidden(message),
+            StatusCode::NOT_FOUND => Self::NotFound(message),
+            StatusCode::CONFLICT => Self::Conflict(message),
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited { retry_after },
+            status => Self::Api { status, message },
+        }
+    }
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
+
+/// Builder for [`FerrexClient`].
+#[derive(Debug)]
+pub struct FerrexClientBuilder {
+    base_url: String,
+    http: Option<reqwest::Client>,
+    timeout: Duration,
+    token: Option<AuthToken>,
+}
+
+impl FerrexClientBuilder {
+    /// Use a preconfigured HTTP client (TLS roots, proxies); its own
+    /// timeout applies instead of [`Self::timeout`].
+    pub fn http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = Some(http);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Resume a session persisted from an earlier login.
+    pub fn token(mut self, token: AuthToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    pub fn build(self) -> ClientResult<FerrexClient> {
+        let trimmed = self.base_url.trim().trim_end_matches('/');
+        let base = Url::parse(trimmed)
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .ok_or_else(|| ClientError::InvalidUrl(self.base_url.clone()))?;
+        let http = match self.http {
+            Some(http) => http,
+            None => reqwest::Client::builder().timeout(self.timeout).build()?,
+        };
+        Ok(FerrexClient {
+            http,
+            base_url: base.as_str().trim_end_matches('/').to_string(),
+            token: Arc::new(RwLock::new(self.token)),
+            refresh_lock: Arc::new(Mutex::new(())),
+        })
+    }
+}
+
+/// Async client for a Ferrex server; cheap to clone, clones share the
+/// session.
+#[derive(Debug, Clone)]
+pub struct FerrexClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Arc<RwLock<Option<AuthToken>>>,
+    refresh_lock: Arc<Mutex<()>>,
+}
+
+impl FerrexClient {
+    /// Client for the server at `base_url`, e.g. `https://media.example.com`.
+    pub fn builder(base_url: impl Into<String>) -> FerrexClientBuilder {
+        FerrexClientBuilder {
+            base_url: base_url.into(),
+            http: None,
+            timeout: DEFAULT_TIMEOUT,
+            token: None,
+        }
+    }
+
+    /// Absolute URL of a route path such as `/api/v1/libraries`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// The current session, e.g. to persist it across restarts.
+    pub async fn token(&self) -> Option<AuthToken> {
+        self.token.read().await.clone()
+    }
+
+    pub async fn set_token(&self, token: Option<AuthToken>) {
+        *self.token.write().await = token;
+    }
+
+    pub async fn login(
+        &self,
+        username: &str,
+        password: &str,
+        device_name: Option<&str>,
+    ) -> ClientResult<AuthToken> {
+        let request = LoginRequest {
+            username: username.to_string(),
+            password: password.to_string(),
+            device_name: device_name.map(str::to_string),
+        };
+        let token: AuthToken = self
+            .call(Method::POST, v1::auth::LOGIN, Some(&request), false)
+            .await?;
+        self.set_token(Some(token.clone())).await;
+        Ok(token)
+    }
+
+    /// Rotate the session with its refresh token.
+    pub async fn refresh(&self) -> ClientResult<AuthToken> {
+        let current =
+            self.token().await.ok_or(ClientError::NotAuthenticated)?;
+        self.refresh_from(&current).await
+    }
+
+    /// End the session on the server and forget it locally.
+    pub async fn logout(&self) -> ClientResult<()> {
+        let result = self
+            .call_empty(Method::POST, v1::auth::LOGOUT, None::<&()>)
+            .await;
+        self.set_token(None).await;
+        result
+    }
+
+    pub async fn current_user(&self) -> ClientResult<User> {
+        self.call(Method::GET, v1::users::CURRENT, None::<&()>, true)
+            .await
+    }
+
+    /// Every library with its media (the server's rkyv snapshot).
+    pub async fn list_libraries(&self) -> ClientResult<Vec<Library>> {
+        let response = self
+            .send(Method::GET, v1::libraries::COLLECTION, None::<&()>, true)
+            .await?;
+        let bytes = response.bytes().await?;
+        rkyv::from_bytes::<Vec<Library>, rkyv::rancor::Error>(&bytes)
+            .map_err(|e| ClientError::Decode(e.to_string()))
+    }
+
+    pub async fn library_stats(
+        &self,
+        library_id: LibraryId,
+    ) -> ClientResult<LibraryStats> {
+        let path = utils::replace_param(
+            v1::libraries::STATS,
+            "{id}",
+            library_id.to_string(),
+        );
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    /// Disk usage of every library, with growth over the last `days`
+    /// (the server's default window when `None`). Admin only.
+    pub async fn storage_usage(
+        &self,
+        days: Option<u32>,
+    ) -> ClientResult<StorageUsageReport> {
+        let path = match days {
+            Some(days) => format!("{}?days={days}", v1::admin::STORAGE),
+            None => v1::admin::STORAGE.to_string(),
+        };
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    /// Tracks of a music library matching `query`.
+    pub async fn music_tracks(
+        &self,
+        library_id: LibraryId,
+        query: &MusicTrackQuery,
+    ) -> ClientResult<Vec<MusicTrack>> {
+        let mut path = utils::replace_param(
+            v1::libraries::music::TRACKS,
+            "{id}",
+            library_id.to_string(),
+        );
+        let mut params = url::form_urlencoded::Serializer::new(String::new());
+        for (key, value) in [
+            ("artist", &query.artist),
+            ("album", &query.album),
+            ("search", &query.search),
+        ] {
+            if let Some(value) = value {
+                params.append_pair(key, value);
+            }
+        }
+        let params = params.finish();
+        if !params.is_empty() {
+            path = format!("{path}?{params}");
+        }
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    /// Albums of a music library, optionally of one artist.
+    pub async fn music_albums(
+        &self,
+        library_id: LibraryId,
+        artist: Option<&str>,
+    ) -> ClientResult<Vec<MusicAlbum>> {
+        let mut path = utils::replace_param(
+            v1::libraries::music::ALBUMS,
+            "{id}",
+            library_id.to_string(),
+        );
+        if let Some(artist) = artist {
+            let params = url::form_urlencoded::Serializer::new(String::new())
+                .append_pair("artist", artist)
+                .finish();
+            path = format!("{path}?{params}");
+        }
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    /// In-progress items, most recently watched first.
+    pub async fn continue_watching(
+        &self,
+        limit: Option<usize>,
+    ) -> ClientResult<Vec<InProgressItem>> {
+        let limit = limit.map(|limit| limit.to_string());
+        let params: Vec<(&str, &str)> = limit
+            .as_deref()
+            .map(|limit| ("limit", limit))
+            .into_iter()
+            .collect();
+        let path = utils::with_query(v1::watch::CONTINUE, &params);
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    pub async fn update_progress(
+        &self,
+        request: &UpdateProgressRequest,
+    ) -> ClientResult<()> {
+        self.call_empty(Method::POST, v1::watch::UPDATE_PROGRESS, Some(request))
+            .await
+    }
+
+    /// The caller's collections, ordered by name.
+    pub async fn collections(&self) -> ClientResult<Vec<Collection>> {
+        self.call(Method::GET, v1::collections::COLLECTION, None::<&()>, true)
+            .await
+    }
+
+    /// A collection with its items in order.
+    pub async fn collection(&self, id: Uuid) -> ClientResult<CollectionDetail> {
+        let path = collection_path(v1::collections::ITEM, id);
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    pub async fn create_collection(
+        &self,
+        request: &CreateCollectionRequest,
+    ) -> ClientResult<Collection> {
+        self.call(
+            Method::POST,
+            v1::collections::COLLECTION,
+            Some(request),
+            true,
+        )
+        .await
+    }
+
+    pub async fn update_collection(
+        &self,
+        id: Uuid,
+        request: &UpdateCollectionRequest,
+    ) -> ClientResult<Collection> {
+        let path = collection_path(v1::collections::ITEM, id);
+        self.call(Method::PUT, &path, Some(request), true).await
+    }
+
+    pub async fn delete_collection(&self, id: Uuid) -> ClientResult<()> {
+        let path = collection_path(v1::collections::ITEM, id);
+        self.call_empty(Method::DELETE, &path, None::<&()>).await
+    }
+
+    /// Append movies and episodes to a collection.
+    pub async fn add_to_collection(
+        &self,
+        id: Uuid,
+        media: Vec<MediaID>,
+    ) -> ClientResult<()> {
+        let path = collection_path(v1::collections::ITEMS, id);
+        let request = AddCollectionItemsRequest { media };
+        self.call_empty(Method::POST, &path, Some(&request)).await
+    }
+
+    pub async fn remove_from_collection(
+        &self,
+        id: Uuid,
+        media: &MediaID,
+    ) -> ClientResult<()> {
+        let path = utils::replace_param(
+            &collection_path(v1::collections::MEMBER, id),
+            "{media_id}",
+            media.as_uuid().to_string(),
+        );
+        self.call_empty(Method::DELETE, &path, None::<&()>).await
+    }
+
+    /// Set the order of a collection; `media` must list every item once.
+    pub async fn reorder_collection(
+        &self,
+        id: Uuid,
+        media: Vec<MediaID>,
+    ) -> ClientResult<()> {
+        let path = collection_path(v1::collections::ORDER, id);
+        let request = ReorderCollectionRequest { media };
+        self.call_empty(Method::PUT, &path, Some(&request)).await
+    }
+
+    /// Libraries holding a copy of the movie, canonical copy first.
+    pub async fn media_availability(
+        &self,
+        movie_id: MovieID,
+    ) -> ClientResult<Vec<MediaCopy>> {
+        let path = utils::replace_param(
+            v1::media::item::AVAILABILITY,
+            "{id}",
+            MediaID::Movie(movie_id).to_param(),
+        );
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    /// Cast and key crew of a movie, series or episode.
+    pub async fn media_credits(
+        &self,
+        media: &MediaID,
+    ) -> ClientResult<MediaCredits> {
+        let path = utils::replace_param(
+            v1::media::item::CREDITS,
+            "{id}",
+            media.to_param(),
+        );
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    /// Trailers of a movie or series, official ones first.
+    pub async fn media_trailers(
+        &self,
+        media: &MediaID,
+    ) -> ClientResult<Vec<Trailer>> {
+        let path = utils::replace_param(
+            v1::media::item::TRAILERS,
+            "{id}",
+            media.to_param(),
+        );
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    /// Movies and series crediting a person, optionally in one library.
+    pub async fn person_appearances(
+        &self,
+        person_id: Uuid,
+        library_id: Option<LibraryId>,
+    ) -> ClientResult<PersonAppearances> {
+        let mut path = utils::replace_param(
+            v1::people::APPEARANCES,
+            "{id}",
+            person_id.to_string(),
+        );
+        if let Some(library_id) = library_id {
+            path = format!("{path}?library_id={library_id}");
+        }
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    pub async fn playback_ticket(
+        &self,
+        media_file_id: Uuid,
+    ) -> ClientResult<PlaybackTicketResponse> {
+        let path = utils::replace_param(
+            v1::stream::PLAYBACK_TICKET,
+            "{id}",
+            media_file_id.to_string(),
+        );
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    /// Subtitle tracks muxed into a media file. Text tracks are served as
+    /// WebVTT from [`v1::media::subtitles::EMBEDDED_TRACK`].
+    pub async fn embedded_subtitles(
+        &self,
+        media_file_id: Uuid,
+    ) -> ClientResult<Vec<EmbeddedSubtitleTrack>> {
+        let path = utils::replace_param(
+            v1::media::subtitles::EMBEDDED,
+            "{id}",
+            media_file_id.to_string(),
+        );
+        self.call(Method::GET, &path, None::<&()>, true).await
+    }
+
+    /// Direct-play URL for a media file that players can open without
+    /// setting headers; it embeds a fresh playback ticket.
+    pub async fn stream_url(&self, media_file_id: Uuid) -> ClientResult<Url> {
+        let ticket = self.playback_ticket(media_file_id).await?;
+        let path = utils::replace_param(
+            v1::stream::PLAY,
+            "{id}",
+            media_file_id.to_string(),
+        );
+        let mut url = Url::parse(&self.url(&path))
+            .map_err(|_| ClientError::InvalidUrl(self.url(&path)))?;
+        url.query_pairs_mut()
+            .append_pair("access_token", &ticket.access_token);
+        Ok(url)
+    }
+
+    /// Call an endpoint answering with an [`ApiResponse`] envelope.
+    async fn call<B, T>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+        authenticated: bool,
+    ) -> ClientResult<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let response = self.send(method, path, body, authenticated).await?;
+        decode_envelope(response).await
+    }
+
+    /// Call an authenticated endpoint answering with a bare status.
+    async fn call_empty<B>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+    ) -> ClientResult<()>
+    where
+        B: Serialize + ?Sized,
+    {
+        self.send(method, path, body, true).await.map(drop)
+    }
+
+    /// Send a request, refreshing the session once on `401`. Error
+    /// statuses come back as [`ClientError`].
+    async fn send<B>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+        authenticated: bool,
+    ) -> ClientResult<Response>
+    where
+        B: Serialize + ?Sized,
+    {
+        let build = |token: Option<&AuthToken>| -> RequestBuilder {
+            let mut request = self.http.request(method.clone(), self.url(path));
+            if let Some(token) = token {
+                request = request.bearer_auth(&token.access_token);
+            }
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+            request
+        };
+
+        let token = if authenticated {
+            Some(self.token().await.ok_or(ClientError::NotAuthenticated)?)
+        } else {
+            None
+        };
+        let mut response = build(token.as_ref()).send().await?;
+        if let Some(token) = token
+            && response.status() == StatusCode::UNAUTHORIZED
+        {
+            let refreshed = self.refresh_from(&token).await?;
+            response = build(Some(&refreshed)).send().await?;
+        }
+        check_status(response).await
+    }
+
+    /// Exchange `stale`'s refresh token, unless a concurrent call already
+    /// replaced it. A rejected refresh token signs the client out.
+    async fn refresh_from(&self, stale: &AuthToken) -> ClientResult<AuthToken> {
+        let _guard = self.refresh_lock.lock().await;
+        if let Some(current) = self.token().await
+            && current.access_token != stale.access_token
+        {
+            return Ok(current);
+        }
+
+        // Sent directly rather than through `send`, which refreshes on 401.
+        let request = RefreshRequest {
+            refresh_token: stale.refresh_token.clone(),
+        };
+        let refreshed = match self
+            .http
+            .post(self.url(v1::auth::REFRESH))
+            .json(&request)
+            .send()
+            .await
+        {
+            Ok(response) => match check_status(response).await {
+                Ok(response) => decode_envelope::<AuthToken>(response).await,
+                Err(err) => Err(err),
+            },
+            Err(err) => Err(err.into()),
+        };
+        match refreshed {
+            Ok(token) => {
+                self.set_token(Some(token.clone())).await;
+                Ok(token)
+            }
+            Err(err @ ClientError::Unauthorized(_)) => {
+                self.set_token(None).await;
+                Err(err)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn collection_path(route: &str, id: Uuid) -> String {
+    utils::replace_param(route, "{id}", id.to_string())
+}
+
+async fn decode_envelope<T: DeserializeOwned>(
+    response: Response,
+) -> ClientResult<T> {
+    let status = response.status();
+    let envelope: ApiResponse<T> = response
+        .json()
+        .await
+        .map_err(|e| ClientError::Decode(e.to_string()))?;
+    match (envelope.data, envelope.error) {
+        (Some(data), _) => Ok(data),
+        (None, Some(message)) => Err(ClientError::Api { status, message }),
+        (None, None) => {
+            Err(ClientError::Decode("response carried no data".into()))
+        }
+    }
+}
+
+async fn check_status(response: Response) -> ClientResult<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let retry_after = response
+        .headers()
+        .get(header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs);
+    let body = response.text().await.unwrap_or_default();
+    Err(ClientError::from_status(status, &body, retry_after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_bodies_of_every_shape_map_to_typed_errors() {
+        let app_error =
+            r#"{"error":{"message":"Library not found","status":404}}"#;
+        assert!(matches!(
+            ClientError::from_status(StatusCode::NOT_FOUND, app_error, None),
+            ClientError::NotFound(message) if message == "Library not found"
+        ));
+
+        let envelope = r#"{"status":"error","error":"Invalid credentials"}"#;
+        assert!(matches!(
+            ClientError::from_status(StatusCode::UNAUTHORIZED, envelope, None),
+            ClientError::Unauthorized(message) if message == "Invalid credentials"
+        ));
+
+        assert!(matches!(
+            ClientError::from_status(
+                StatusCode::BAD_GATEWAY,
+                "upstream down\n",
+                None
+            ),
+            ClientError::Api { status, message }
+                if status == StatusCode::BAD_GATEWAY && message == "upstream down"
+        ));
+
+        assert!(matches!(
+            ClientError::from_status(
+                StatusCode::TOO_MANY_REQUESTS,
+                "",
+                Some(Duration::from_secs(30))
+            ),
+            ClientError::RateLimited { retry_after: Some(after) }
+                if after == Duration::from_secs(30)
+        ));
+    }
+
+    #[test]
+    fn base_urls_are_validated_and_joined_with_routes() {
+        let client = FerrexClient::builder(" https://media.example.com/ ")
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.url(v1::watch::CONTINUE),
+            "https://media.example.com/api/v1/watch/continue"
+        );
+
+        let proxied = FerrexClient::builder("https://example.com/ferrex")
+            .build()
+            .unwrap();
+        assert_eq!(
+            proxied.url(v1::libraries::COLLECTION),
+            "https://example.com/ferrex/api/v1/libraries"
+        );
+
+        for bad in ["media.example.com", "ftp://example.com", ""] {
+            assert!(matches!(
+                FerrexClient::builder(bad).build(),
+                Err(ClientError::InvalidUrl(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn authenticated_calls_need_a_session() {
+        let client =
+            FerrexClient::builder("http://127.0.0.1:9").build().unwrap();
+        assert!(matches!(
+            client.current_user().await,
+            Err(ClientError::NotAuthenticated)
+        ));
+        assert!(matches!(
+            client.refresh().await,
+            Err(ClientError::NotAuthenticated)
+        ));
+    }
+}
@@ -1,9 +1,10 @@
 //! API boundary for Ferrex Core.
 //!
-//! Groups versioned routes, scan-facing DTOs, and general API data structures
-//! so consumers can depend on a single namespace instead of dozens of root
-//! modules.
+//! Groups versioned routes, scan-facing DTOs, general API data structures,
+//! and a typed client for those routes so consumers can depend on a single
+//! namespace instead of dozens of root modules.
 
+pub mod client;
 pub mod routes;
 pub mod scan;
 pub mod types;
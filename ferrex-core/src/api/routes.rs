@@ -14,6 +14,10 @@ pub mod v1 {
         pub const LOGIN: &str = v1_path!("/auth/login");
         pub const REFRESH: &str = v1_path!("/auth/refresh");
         pub const LOGOUT: &str = v1_path!("/auth/logout");
+        /// Introspect the bearer session token (lifetime, owner, device).
+        pub const SESSION: &str = v1_path!("/auth/session");
+        /// Auth events of the current user, sensitive fields withheld.
+        pub const EVENTS: &str = v1_path!("/auth/events");
 
         pub mod device {
             pub const LOGIN: &str = v1_path!("/auth/device/login");
@@ -56,23 +60,114 @@ pub mod v1 {
         pub const CREATE_ADMIN: &str = v1_path!("/setup/admin");
         pub const CLAIM_START: &str = v1_path!("/setup/claim/start");
         pub const CLAIM_CONFIRM: &str = v1_path!("/setup/claim/confirm");
+        /// Deep link and QR code for a pending claim, given its code.
+        pub const CLAIM_LINK: &str = v1_path!("/setup/claim/link");
     }
 
     pub mod media {
+        /// Delete media by id or file filter (admin only).
+        pub const COLLECTION: &str = v1_path!("/media");
         pub const QUERY: &str = v1_path!("/media/query");
-
+        /// Movies and series newest first; `?limit=&cursor=&library_id=`.
+        pub const RECENTLY_ADDED: &str = v1_path!("/media/recently-added");
+        /// Episodes of a season; `?sort=number|air_date` (default `number`).
+        pub const SEASON_EPISODES: &str =
+            v1_path!("/media/seasons/{id}/episodes");
+        /// Mark every episode of a series or season watched / unwatched.
+        pub const SERIES_WATCHED: &str = v1_path!("/media/series/{id}/watched");
+        pub const SERIES_UNWATCHED: &str =
+            v1_path!("/media/series/{id}/unwatched");
+        pub const SEASON_WATCHED: &str =
+            v1_path!("/media/seasons/{id}/watched");
+        pub const SEASON_UNWATCHED: &str =
+            v1_path!("/media/seasons/{id}/unwatched");
+
+        /// `{id}` is a typed media id (`movie:<uuid>`, see
+        /// `MediaID::to_param`); a bare UUID is still accepted. Ids of a
+        /// kind the route does not serve are refused with `400`.
         pub mod item {
             pub const PROGRESS: &str = v1_path!("/media/{id}/progress");
             pub const COMPLETE: &str = v1_path!("/media/{id}/complete");
             pub const IS_COMPLETED: &str = v1_path!("/media/{id}/is-completed");
+            /// Libraries holding a copy of the same movie.
+            pub const AVAILABILITY: &str = v1_path!("/media/{id}/availability");
+            /// Cast and key crew of a movie, series or episode.
+            pub const CREDITS: &str = v1_path!("/media/{id}/credits");
+            /// Trailers and teasers of a movie or series.
+            pub const TRAILERS: &str = v1_path!("/media/{id}/trailers");
+            /// Re-fetch a movie or series from TMDB, replacing stored
+            /// metadata (admin only).
+            pub const REFRESH_METADATA: &str =
+                v1_path!("/media/{id}/refresh-metadata");
+            /// Re-fetch only the listed metadata fields of a movie or
+            /// series, keeping the rest as stored (admin only).
+            pub const REFRESH_FIELDS: &str =
+                v1_path!("/media/{id}/refresh-fields");
+            /// Pin a movie or series to a specific TMDB id so rescans keep
+            /// the match (admin only).
+            pub const TMDB_MATCH: &str = v1_path!("/media/{id}/tmdb-match");
+            /// Every image linked to a movie or series (admin only).
+            pub const IMAGES: &str = v1_path!("/media/{id}/images");
+            /// Make `{iid}` the primary image of its variant (admin only).
+            pub const PRIMARY_IMAGE: &str =
+                v1_path!("/media/{id}/images/{iid}/primary");
+        }
+
+        /// Adaptive HLS for a media file; `{id}` is the media file id as in
+        /// `/stream/{id}`.
+        pub mod hls {
+            pub const MASTER: &str = v1_path!("/media/{id}/master.m3u8");
+            pub const VARIANT: &str =
+                v1_path!("/media/{id}/hls/{variant}/index.m3u8");
+            pub const SEGMENT: &str =
+                v1_path!("/media/{id}/hls/{variant}/{segment}");
+        }
+
+        /// Subtitle tracks muxed into a media file; `{id}` is the media
+        /// file id as in `/stream/{id}`.
+        pub mod subtitles {
+            pub const EMBEDDED: &str =
+                v1_path!("/media/{id}/subtitle/embedded");
+            /// One embedded track converted to WebVTT.
+            pub const EMBEDDED_TRACK: &str =
+                v1_path!("/media/{id}/subtitle/embedded/{index}");
         }
     }
 
+    pub mod people {
+        /// Movies and series crediting a person; `?library_id=`.
+        pub const APPEARANCES: &str = v1_path!("/people/{id}/appearances");
+    }
+
+    pub mod search {
+        /// Ranked movies, series and episodes;
+        /// `?q=&type=&fields=title,overview,cast&limit=&library_id=`.
+        pub const ROOT: &str = v1_path!("/search");
+    }
+
+    /// Per-user collections of movies and episodes.
+    pub mod collections {
+        /// List (GET) or create (POST) the caller's collections.
+        pub const COLLECTION: &str = v1_path!("/collections");
+        /// A collection with its resolved items (GET), renamed (PUT) or
+        /// deleted (DELETE).
+        pub const ITEM: &str = v1_path!("/collections/{id}");
+        /// Append movies and episodes.
+        pub const ITEMS: &str = v1_path!("/collections/{id}/items");
+        /// Replace the order of all items.
+        pub const ORDER: &str = v1_path!("/collections/{id}/items/order");
+        /// Remove one movie or episode.
+        pub const MEMBER: &str = v1_path!("/collections/{id}/items/{media_id}");
+    }
+
     pub mod watch {
         pub const UPDATE_PROGRESS: &str = v1_path!("/watch/progress");
         pub const STATE: &str = v1_path!("/watch/state");
         pub const CONTINUE: &str = v1_path!("/watch/continue");
         pub const CLEAR_PROGRESS: &str = v1_path!("/watch/progress/{media_id}");
+        /// Portable watch state for backups and server moves.
+        pub const EXPORT: &str = v1_path!("/watch/export");
+        pub const IMPORT: &str = v1_path!("/watch/import");
         // Identity-based TV helpers
         pub const SERIES_STATE: &str =
             v1_path!("/watch/series/{tmdb_series_id}");
@@ -80,6 +175,10 @@ pub mod v1 {
             v1_path!("/watch/series/{tmdb_series_id}/season/{season_number}");
         pub const SERIES_NEXT: &str =
             v1_path!("/watch/series/{tmdb_series_id}/next");
+        /// Mark one episode watched, optionally cascading to earlier ones.
+        pub const EPISODE_WATCHED: &str = v1_path!(
+            "/watch/series/{tmdb_series_id}/season/{season_number}/episode/{episode_number}/watched"
+        );
     }
 
     pub mod folders {
@@ -96,6 +195,25 @@ pub mod v1 {
             v1_path!("/libraries/{id}/indices/sorted");
         pub const FILTERED_INDICES: &str =
             v1_path!("/libraries/{id}/indices/filter");
+        /// Verify on-disk media files against stored sizes (POST) or fetch
+        /// the latest verification report (GET).
+        pub const VERIFY: &str = v1_path!("/libraries/{id}/verify");
+        /// Remove records and cached images of media files deleted from
+        /// disk, without a full scan (POST).
+        pub const RECONCILE: &str = v1_path!("/libraries/{id}/reconcile");
+        /// Aggregate item, size, poster/metadata coverage and missing-file
+        /// counts for a library.
+        pub const STATS: &str = v1_path!("/libraries/{id}/stats");
+        /// Turn filesystem watching for a library on or off (PUT).
+        pub const WATCH: &str = v1_path!("/libraries/{id}/watch");
+
+        /// Listings of music libraries.
+        pub mod music {
+            /// Tracks, filtered by `artist`, `album` or `search`.
+            pub const TRACKS: &str = v1_path!("/libraries/{id}/music/tracks");
+            /// Albums with track counts, filtered by `artist`.
+            pub const ALBUMS: &str = v1_path!("/libraries/{id}/music/albums");
+        }
 
         pub mod movie_batches {
             pub const COLLECTION: &str =
@@ -130,17 +248,27 @@ pub mod v1 {
             pub const PAUSE: &str = v1_path!("/libraries/{id}/scans:pause");
             pub const RESUME: &str = v1_path!("/libraries/{id}/scans:resume");
             pub const CANCEL: &str = v1_path!("/libraries/{id}/scans:cancel");
+            pub const PATH: &str = v1_path!("/libraries/{id}/scans:path");
+            /// Count what a scan would process without running one (GET).
+            pub const PREVIEW: &str = v1_path!("/libraries/{id}/scans:preview");
         }
     }
 
     pub mod scan {
         pub const ACTIVE: &str = v1_path!("/scan/active");
         pub const HISTORY: &str = v1_path!("/scan/history");
+        pub const HISTORY_LATENCY: &str =
+            v1_path!("/scan/history/{id}/latency");
         pub const PROGRESS: &str = v1_path!("/scan/progress");
         pub const EVENTS: &str = v1_path!("/scan/{id}/events");
         pub const PROGRESS_STREAM: &str = v1_path!("/scan/{id}/progress");
         pub const METRICS: &str = v1_path!("/scan/metrics");
         pub const CONFIG: &str = v1_path!("/scan/config");
+        /// Scans left unfinished by a shutdown that can be resumed (GET).
+        pub const INTERRUPTED: &str = v1_path!("/scan/interrupted");
+        /// Resume an interrupted scan (POST).
+        pub const RESUME_INTERRUPTED: &str =
+            v1_path!("/scan/interrupted/{id}/resume");
     }
 
     pub mod events {
@@ -154,7 +282,10 @@ pub mod v1 {
         pub const MANIFEST: &str = v1_path!("/images/manifest");
         /// Immutable, content-addressed image blob (token is hex).
         pub const BLOB_ITEM: &str = v1_path!("/images/blob/{token}");
-        /// SSE stream for image readiness notifications.
+        /// Tiny blurred placeholder for an image whose sizes are still caching.
+        pub const PLACEHOLDER: &str = v1_path!("/images/{iid}/placeholder");
+        /// SSE stream for image readiness notifications; `?media_ids=` and
+        /// `?iids=` (comma-separated) limit it to the images of interest.
         pub const EVENTS: &str = v1_path!("/images/events");
     }
 
@@ -165,6 +296,13 @@ pub mod v1 {
             v1_path!("/stream/{media_type}/{id}/progress");
     }
 
+    pub mod transcode {
+        pub const START: &str = v1_path!("/transcode");
+        pub const STATUS: &str = v1_path!("/transcode/{id}");
+        /// Playlist or segment of a transcode job, e.g. `index.m3u8`.
+        pub const FILE: &str = v1_path!("/transcode/{id}/{file}");
+    }
+
     pub mod sync {
         pub const WEBSOCKET: &str = v1_path!("/sync/ws");
     }
@@ -176,10 +314,18 @@ pub mod v1 {
         pub const USER_SESSIONS: &str = v1_path!("/admin/users/{id}/sessions");
         pub const REVOKE_SESSION: &str =
             v1_path!("/admin/users/{user_id}/sessions/{session_id}");
+        pub const AUTH_EVENTS: &str = v1_path!("/admin/auth-events");
         pub const STATS: &str = v1_path!("/admin/stats");
+        /// Per-library disk usage and growth.
+        pub const STORAGE: &str = v1_path!("/admin/storage");
+        /// Effective configuration with secrets redacted.
+        pub const CONFIG: &str = v1_path!("/admin/config");
 
         pub const MEDIA_ROOT_BROWSER: &str =
             v1_path!("/admin/media/root-browser");
+        /// Dry-run how a filename parses under the configured rules.
+        pub const PARSE_FILENAME: &str =
+            v1_path!("/admin/media/parse-filename");
 
         pub mod dev {
             pub const RESET_CHECK: &str = v1_path!("/admin/dev/reset/check");
@@ -194,6 +340,22 @@ pub mod v1 {
             pub const RESIZE: &str = v1_path!("/admin/demo/resize");
         }
 
+        pub mod maintenance {
+            pub const THEME_COLORS: &str =
+                v1_path!("/admin/maintenance/theme-colors");
+            /// Read (GET) or toggle (PUT) runtime read-only mode.
+            pub const MODE: &str = v1_path!("/admin/maintenance/mode");
+            /// Clear rate limiter buckets for one key or all keys.
+            pub const RATE_LIMIT_RESET: &str =
+                v1_path!("/admin/maintenance/rate-limits/reset");
+            /// Report image blobs on disk without a database record.
+            pub const ORPHANED_IMAGES: &str =
+                v1_path!("/admin/maintenance/orphaned-images");
+            /// Delete those blobs; requires `{"confirm": true}`.
+            pub const ORPHANED_IMAGES_PURGE: &str =
+                v1_path!("/admin/maintenance/orphaned-images/purge");
+        }
+
         pub mod sessions {
             pub const REGISTER: &str = v1_path!("/admin/sessions/register");
             pub const REMOVE: &str = v1_path!("/admin/sessions/{device_id}");
@@ -10,11 +10,27 @@ pub struct ScanQueueDepths {
     pub image_fetch: usize,
 }
 
+/// Activity of the background metadata workers since the server started.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataQueueStats {
+    /// Enrichment and episode-match jobs ready to run.
+    pub queued: usize,
+    pub background_in_flight: usize,
+    /// Fetches requested directly, e.g. an admin metadata refresh.
+    pub on_demand_in_flight: usize,
+    pub completed: u64,
+    pub retried: u64,
+    /// Jobs given up on after a permanent error or their last retry.
+    pub dead_lettered: u64,
+    pub completed_last_minute: usize,
+}
+
 /// Top-level scanner metrics for admin surfaces.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanMetrics {
     pub queue_depths: ScanQueueDepths,
     pub active_scans: usize,
+    pub metadata: MetadataQueueStats,
 }
 
 /// Minimal, feature-agnostic view of orchestrator configuration for admin surfaces.
@@ -63,6 +79,8 @@ pub struct RetryConfigView {
 pub struct MetadataLimitsView {
     pub max_concurrency: usize,
     pub max_qps: u32,
+    pub batch_size: usize,
+    pub batch_delay_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
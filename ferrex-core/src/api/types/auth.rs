@@ -0,0 +1,124 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::users::auth::AuthEventType;
+
+/// Exchange a refresh token for a new session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+/// Short-lived token for embedding in a stream URL (`?access_token=`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaybackTicketResponse {
+    pub access_token: String,
+    /// Seconds until the ticket expires.
+    pub expires_in: i64,
+}
+
+/// Query string of the auth event endpoints.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthEventsQuery {
+    /// Comma-separated event types, e.g. `pin_login_failure,pin_set`
+    #[serde(default)]
+    pub event_types: Option<String>,
+    /// Only events at or after this time
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Only events before this time
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    /// `next_cursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<AuthEventCursor>,
+    /// Page size; the server clamps it
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Restrict to one user; admin endpoint only
+    #[serde(default)]
+    pub user_id: Option<Uuid>,
+}
+
+/// One audit log entry. On a user's own view the session, failure reason
+/// and metadata are withheld.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthEventEntry {
+    pub id: Uuid,
+    pub event_type: AuthEventType,
+    pub occurred_at: DateTime<Utc>,
+    pub success: bool,
+    pub user_id: Option<Uuid>,
+    pub device_id: Option<Uuid>,
+    pub device_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub session_id: Option<Uuid>,
+    pub failure_reason: Option<String>,
+    pub metadata: Option<Value>,
+}
+
+/// One page of auth events, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthEventPage {
+    pub events: Vec<AuthEventEntry>,
+    /// Present when more events follow
+    pub next_cursor: Option<AuthEventCursor>,
+}
+
+/// Opaque position in the audit log: the `(occurred_at, id)` of the last
+/// event on a page. Serialized as a URL-safe string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthEventCursor {
+    pub occurred_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl AuthEventCursor {
+    pub fn after(entry: &AuthEventEntry) -> Self {
+        Self {
+            occurred_at: entry.occurred_at,
+            id: entry.id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}:{}",
+            self.occurred_at.timestamp_micros(),
+            self.id
+        ))
+    }
+
+    pub fn decode(raw: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(raw).ok()?;
+        let text = String::from_utf8(bytes).ok()?;
+        let (micros, id) = text.split_once(':')?;
+        Some(Self {
+            occurred_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+impl Serialize for AuthEventCursor {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.encode())
+    }
+}
+
+impl<'de> Deserialize<'de> for AuthEventCursor {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Self::decode(&raw)
+            .ok_or_else(|| serde::de::Error::custom("invalid cursor"))
+    }
+}
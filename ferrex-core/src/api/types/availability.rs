@@ -0,0 +1,59 @@
+use std::{fmt, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Whether a media item can be played right now, and why not.
+///
+/// `LibraryOffline` is reported instead of `FileMissing` when the library
+/// root the file lives under is itself unreachable, so clients can tell an
+/// unmounted share apart from a deleted file. Files stored behind a URL are
+/// `FileMissing` when the upstream answers `404`/`410` and
+/// `RemoteUnreachable` when it cannot be reached or fails otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum MediaAvailability {
+    Available {
+        path: PathBuf,
+    },
+    /// No media file or track with that id is indexed.
+    NotFound,
+    LibraryOffline {
+        /// Where the file is expected once the library is back.
+        path: PathBuf,
+        library_root: PathBuf,
+    },
+    FileMissing {
+        path: PathBuf,
+    },
+    RemoteUnreachable {
+        url: String,
+        /// Upstream status or connection error.
+        reason: String,
+    },
+}
+
+impl MediaAvailability {
+    pub fn is_available(&self) -> bool {
+        matches!(self, MediaAvailability::Available { .. })
+    }
+}
+
+impl fmt::Display for MediaAvailability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MediaAvailability::Available { path } => {
+                write!(f, "media file {} is available", path.display())
+            }
+            MediaAvailability::NotFound => f.write_str("Media not found"),
+            MediaAvailability::LibraryOffline { library_root, .. } => {
+                write!(f, "library root {} is offline", library_root.display())
+            }
+            MediaAvailability::FileMissing { path } => {
+                write!(f, "media file {} is missing", path.display())
+            }
+            MediaAvailability::RemoteUnreachable { url, reason } => {
+                write!(f, "remote media {url} is unreachable: {reason}")
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! User-curated collections of movies and episodes.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::types::media_id::MediaID;
+
+/// A named, ordered collection owned by one user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collection {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub item_count: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A member of a collection, resolved to something a client can show
+/// without loading the media itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionItem {
+    pub media_id: MediaID,
+    /// Movie title, or the episode's series title.
+    pub title: String,
+    /// `S01E02` for episodes.
+    pub subtitle: Option<String>,
+    pub added_at: DateTime<Utc>,
+    pub watch: CollectionItemWatchState,
+}
+
+/// The requesting user's progress on a collection member.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CollectionItemWatchState {
+    /// Seconds into the media; `None` when never started.
+    pub position: Option<f32>,
+    pub duration: Option<f32>,
+    pub completed: bool,
+}
+
+/// A collection with its members in order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionDetail {
+    pub collection: Collection,
+    pub items: Vec<CollectionItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateCollectionRequest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Fields left out are kept; an empty description clears it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateCollectionRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Movies and episodes to append to a collection. Media already in the
+/// collection keeps its place.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddCollectionItemsRequest {
+    pub media: Vec<MediaID>,
+}
+
+/// The complete new order of a collection's members.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReorderCollectionRequest {
+    pub media: Vec<MediaID>,
+}
@@ -0,0 +1,114 @@
+//! Cast and crew of a movie, series or episode, and the media a person
+//! appears in.
+//!
+//! People are stored once per TMDB person id, so the same `person_id`
+//! shows up in every credit of that person across the library.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::types::{ids::LibraryId, media_id::MediaID};
+
+/// Crew jobs worth showing next to the cast, most important first. The
+/// rest of a crew list is rarely interesting on a details page.
+pub const KEY_CREW_JOBS: &[&str] = &[
+    "Director",
+    "Creator",
+    "Screenplay",
+    "Writer",
+    "Novel",
+    "Story",
+    "Producer",
+    "Executive Producer",
+    "Original Music Composer",
+    "Director of Photography",
+    "Editor",
+];
+
+pub fn is_key_crew_job(job: &str) -> bool {
+    KEY_CREW_JOBS
+        .iter()
+        .any(|key| key.eq_ignore_ascii_case(job.trim()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CastCredit {
+    pub person_id: Uuid,
+    pub tmdb_id: u64,
+    pub name: String,
+    pub character: String,
+    /// Billing order as given by TMDB.
+    pub order: Option<u32>,
+    /// Guest star of a single episode rather than regular cast.
+    #[serde(default)]
+    pub guest: bool,
+    /// Profile image id for the image manifest; the image itself is
+    /// downloaded the first time a client asks for it.
+    pub profile_image_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrewCredit {
+    pub person_id: Uuid,
+    pub tmdb_id: u64,
+    pub name: String,
+    pub department: String,
+    pub job: String,
+    pub profile_image_id: Option<Uuid>,
+}
+
+/// Cast in billing order and key crew in [`KEY_CREW_JOBS`] order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaCredits {
+    pub media_id: MediaID,
+    pub cast: Vec<CastCredit>,
+    pub crew: Vec<CrewCredit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Person {
+    pub id: Uuid,
+    pub tmdb_id: u64,
+    pub name: String,
+    pub known_for_department: Option<String>,
+    pub profile_image_id: Option<Uuid>,
+}
+
+/// A movie or series a person is credited on, with every role they had.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonAppearance {
+    pub media_id: MediaID,
+    pub library_id: LibraryId,
+    pub title: String,
+    pub year: Option<i32>,
+    pub poster_path: Option<String>,
+    pub characters: Vec<String>,
+    pub jobs: Vec<String>,
+}
+
+/// A person and their appearances, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonAppearances {
+    pub person: Person,
+    pub appearances: Vec<PersonAppearance>,
+}
+
+/// Query string of the appearances route.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonAppearancesQuery {
+    /// Only media in this library.
+    #[serde(default)]
+    pub library_id: Option<LibraryId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_crew_jobs_ignore_case_and_padding() {
+        assert!(is_key_crew_job("Director"));
+        assert!(is_key_crew_job(" director of photography "));
+        assert!(!is_key_crew_job("Best Boy Grip"));
+    }
+}
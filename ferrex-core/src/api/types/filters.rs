@@ -6,7 +6,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     domain::watch::WatchStatusFilter,
-    query::types::{MediaTypeFilter, SortBy, SortOrder},
+    query::{
+        filter_expr::FilterExpr,
+        types::{
+            CertificationFilter, MediaTypeFilter, QueryError, SortBy, SortOrder,
+        },
+    },
 };
 
 /// Legacy library filter payload (to be replaced by GraphQL)
@@ -42,6 +47,27 @@ pub struct FilterIndicesRequest {
     pub sort: Option<SortBy>,
     /// Optional sort order ("asc"/"desc")
     pub order: Option<SortOrder>,
+    /// Optional combined predicate, ANDed with the flat filters above
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expr: Option<FilterExpr>,
+    /// Highest allowed certification, as a rating (`PG-13`) or an age (`13`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certification_max: Option<String>,
+    /// Keep movies without a known certification when `certification_max`
+    /// is set
+    #[serde(default)]
+    pub include_unrated: bool,
+}
+
+impl FilterIndicesRequest {
+    pub fn certification(
+        &self,
+    ) -> Result<Option<CertificationFilter>, QueryError> {
+        CertificationFilter::parse(
+            self.certification_max.as_deref(),
+            self.include_unrated,
+        )
+    }
 }
 
 /// Compact response for index-based sorting/filtering
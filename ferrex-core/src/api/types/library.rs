@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
 #[cfg(feature = "rkyv")]
 use rkyv::{
     Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize,
@@ -9,13 +10,15 @@ use uuid::Uuid;
 
 use crate::types::details::LibraryReference;
 use crate::types::ids::LibraryId;
-use crate::types::ids::{MovieBatchId, SeriesID};
+use crate::types::ids::{MovieBatchId, MovieID, SeriesID};
 use crate::types::library::LibraryType;
 use crate::types::media::{
     EpisodeReference, Media, MovieReference, SeasonReference, Series,
 };
 use crate::types::media_id::MediaID;
 
+use super::music::MusicTrack;
+
 /// Lightweight payload of library media used by UI clients
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "rkyv", derive(Archive, RkyvSerialize, RkyvDeserialize))]
@@ -102,6 +105,9 @@ pub enum LibraryMediaCache {
         season_references: HashMap<Uuid, Vec<SeasonReference>>,
         episode_references: HashMap<Uuid, Vec<EpisodeReference>>,
     },
+    Music {
+        tracks: Vec<MusicTrack>,
+    },
 }
 
 impl LibraryMediaCache {
@@ -111,6 +117,7 @@ impl LibraryMediaCache {
             LibraryMediaCache::TvShows {
                 series_references, ..
             } => series_references.is_empty(),
+            LibraryMediaCache::Music { tracks } => tracks.is_empty(),
         }
     }
 
@@ -126,6 +133,9 @@ impl LibraryMediaCache {
                 season_references: HashMap::new(),
                 episode_references: HashMap::new(),
             },
+            LibraryType::Music => {
+                LibraryMediaCache::Music { tracks: Vec::new() }
+            }
         }
     }
 }
@@ -171,6 +181,10 @@ pub struct CreateLibraryRequest {
     pub enabled: bool,
     #[serde(default = "default_movie_ref_batch_size")]
     pub movie_ref_batch_size: u32,
+    /// Globs, or regexes prefixed with `re:`, of paths below the library
+    /// root that scans skip.
+    #[serde(default)]
+    pub exclude_patterns: Vec<String>,
     #[serde(default = "default_start_scan")]
     pub start_scan: bool,
 }
@@ -183,6 +197,133 @@ pub struct UpdateLibraryRequest {
     pub scan_interval_minutes: Option<u32>,
     pub enabled: Option<bool>,
     pub movie_ref_batch_size: Option<u32>,
+    /// Replaces the library's exclude patterns. Items already indexed
+    /// under newly excluded paths are removed on the next scan.
+    #[serde(default)]
+    pub exclude_patterns: Option<Vec<String>>,
+}
+
+/// Aggregate counts for one library, served by `/libraries/{id}/stats`.
+///
+/// Poster and metadata coverage count top-level items only (movies and
+/// series).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryStats {
+    pub library_id: LibraryId,
+    pub movies: u64,
+    pub series: u64,
+    pub seasons: u64,
+    pub episodes: u64,
+    pub total_files: u64,
+    /// Sum of the stored file sizes, in bytes.
+    pub total_size: u64,
+    pub with_poster: u64,
+    pub without_poster: u64,
+    pub with_metadata: u64,
+    pub without_metadata: u64,
+    /// Files the latest integrity verification found missing on disk;
+    /// `None` until the library has been verified.
+    pub missing_files: Option<u64>,
+    /// When the verification behind `missing_files` finished.
+    pub missing_checked_at: Option<DateTime<Utc>>,
+}
+
+/// Growth window used by `/admin/storage` when the query gives none.
+pub const DEFAULT_STORAGE_GROWTH_DAYS: u32 = 30;
+/// Longest growth window `/admin/storage` accepts.
+pub const MAX_STORAGE_GROWTH_DAYS: u32 = 365;
+
+/// Query string of `/admin/storage`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageUsageQuery {
+    /// Days of additions the growth estimate is based on.
+    #[serde(default)]
+    pub days: Option<u32>,
+}
+
+impl StorageUsageQuery {
+    /// The requested window clamped to `1..=MAX_STORAGE_GROWTH_DAYS`.
+    pub fn window_days(&self) -> u32 {
+        self.days
+            .unwrap_or(DEFAULT_STORAGE_GROWTH_DAYS)
+            .clamp(1, MAX_STORAGE_GROWTH_DAYS)
+    }
+}
+
+/// Per-library disk use, served by `/admin/storage`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageUsageReport {
+    pub window_days: u32,
+    pub libraries: Vec<LibraryStorageUsage>,
+}
+
+/// Disk use of one library. File sizes are the ones stored at scan time;
+/// nothing is re-read from disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryStorageUsage {
+    pub library_id: LibraryId,
+    pub library_name: String,
+    pub library_type: LibraryType,
+    pub total_files: u64,
+    /// Sum of the stored file sizes, in bytes.
+    pub total_size: u64,
+    /// Cached images of the library's movies, series, seasons and
+    /// episodes. Profile images are shared between libraries and left out.
+    pub image_cache_size: u64,
+    /// HLS segments of the library's files currently cached. Other
+    /// transcode output is not attributed to a library.
+    pub transcode_cache_size: u64,
+    pub growth: StorageGrowth,
+}
+
+/// Files added over the last `window_days`, by discovery time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageGrowth {
+    pub window_days: u32,
+    pub added_files: u64,
+    pub added_size: u64,
+    /// `added_size` spread over the window.
+    pub bytes_per_day: u64,
+    /// `total_size` after another window at the same pace.
+    pub projected_size: u64,
+}
+
+impl StorageGrowth {
+    pub fn new(
+        window_days: u32,
+        added_files: u64,
+        added_size: u64,
+        total_size: u64,
+    ) -> Self {
+        let window_days = window_days.max(1);
+        Self {
+            window_days,
+            added_files,
+            added_size,
+            bytes_per_day: added_size / u64::from(window_days),
+            projected_size: total_size.saturating_add(added_size),
+        }
+    }
+}
+
+/// One library's copy of a movie, served by `/media/{id}/availability`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaCopy {
+    pub library_id: LibraryId,
+    pub library_name: String,
+    pub movie_id: MovieID,
+    pub file_path: String,
+    /// Owns the images shared by every copy.
+    pub canonical: bool,
+}
+
+/// A movie present in more than one library, keyed by its TMDB id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateMedia {
+    pub tmdb_id: u64,
+    pub title: String,
+    /// Canonical copy first.
+    pub copies: Vec<MediaCopy>,
 }
 
 fn default_scan_interval() -> u32 {
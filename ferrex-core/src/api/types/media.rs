@@ -5,7 +5,7 @@ use rkyv::{
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use ferrex_model::ImageSize;
+use ferrex_model::{ImageSize, LibraryId, Media, MediaID, MetadataField};
 
 /// Wrapper for image binary data to enable rkyv serialization
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,7 +52,114 @@ pub struct ImageManifestResult {
 #[cfg_attr(feature = "rkyv", derive(Archive, RkyvSerialize, RkyvDeserialize))]
 #[cfg_attr(feature = "rkyv", rkyv(derive(Debug, PartialEq, Eq)))]
 pub enum ImageManifestStatus {
-    Ready { token: String, byte_len: u64 },
-    Pending { retry_after_ms: u64 },
-    Missing { reason: String },
+    Ready {
+        token: String,
+        byte_len: u64,
+    },
+    /// The requested size is being cached. `placeholder` carries a tiny
+    /// blurred PNG of the image when any of its sizes was cached before;
+    /// an `ImageReadyEvent` follows once the real size is ready.
+    Pending {
+        retry_after_ms: u64,
+        placeholder: Option<Vec<u8>>,
+    },
+    /// The requested size is not ready and could not be queued right now;
+    /// a smaller cached size is ready instead. Ask again after
+    /// `retry_after_ms` for the requested size.
+    Fallback {
+        imz: ImageSize,
+        token: String,
+        byte_len: u64,
+        retry_after_ms: u64,
+    },
+    Missing {
+        reason: String,
+    },
+}
+
+/// Request body for `DELETE /media`. The ids and the files matched by
+/// `filter` are deleted together.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeleteMediaRequest {
+    #[serde(default)]
+    pub ids: Vec<MediaID>,
+    #[serde(default)]
+    pub filter: Option<DeleteMediaFilter>,
+    /// Also remove the media files from disk. Off unless asked for.
+    #[serde(default)]
+    pub delete_files: bool,
+}
+
+/// Selects the movies and episodes whose files match, like the media file
+/// listing does.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteMediaFilter {
+    pub library_id: LibraryId,
+    #[serde(default)]
+    pub path_prefix: Option<String>,
+}
+
+/// Outcome of deleting one requested id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaDeletionResult {
+    pub id: MediaID,
+    pub deleted: bool,
+    /// Why the id was not deleted, or which of its files could not be
+    /// removed from disk.
+    pub error: Option<String>,
+    /// Files whose records were deleted.
+    pub files: Vec<String>,
+    /// How many of `files` were removed from disk.
+    pub files_removed: u32,
+}
+
+/// One image linked to a movie or series.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaImage {
+    pub iid: Uuid,
+    /// Variant and original width of the image.
+    pub imz: ImageSize,
+    pub path: String,
+    /// Name of the image source serving `path` (`tmdb`, `url`, `local`),
+    /// when one recognizes it.
+    pub source: Option<String>,
+    /// ISO 639-1 language of any text on the image.
+    pub language: Option<String>,
+    pub vote_avg: f32,
+    pub vote_cnt: u32,
+    pub is_primary: bool,
+}
+
+/// Response for `GET /media/{id}/images`: posters first, then backdrops,
+/// each variant's primary image ahead of the rest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaImagesResponse {
+    pub media_id: MediaID,
+    pub images: Vec<MediaImage>,
+}
+
+/// Body of `POST /media/{id}/refresh-fields`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshFieldsRequest {
+    /// Fields to re-fetch from TMDB; must not be empty.
+    pub fields: Vec<MetadataField>,
+}
+
+/// Response for `POST /media/{id}/refresh-fields`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshFieldsResponse {
+    pub media: Media,
+    /// Requested fields whose stored value changed; empty when TMDB had
+    /// nothing new.
+    pub changed: Vec<MetadataField>,
+}
+
+/// Response for `DELETE /media`, one result per distinct requested id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteMediaResponse {
+    pub results: Vec<MediaDeletionResult>,
+    pub deleted: u32,
+    pub failed: u32,
+    /// Cached image sizes dropped along with the deleted media.
+    pub images_invalidated: u64,
 }
@@ -3,20 +3,43 @@
 //! specialized namespaces instead of the entire API layer.
 
 pub mod admin;
+pub mod auth;
+pub mod availability;
+pub mod collections;
+pub mod credits;
 pub mod demo;
 pub mod filters;
 pub mod library;
 pub mod media;
 pub mod media_repo_sync;
+pub mod music;
 pub mod responses;
 pub mod scan;
 pub mod setup;
+pub mod subtitles;
+pub mod trailers;
+pub mod transcode;
 pub mod users_admin;
 
 pub use admin::{
     MediaRootBreadcrumb, MediaRootBrowseRequest, MediaRootBrowseResponse,
     MediaRootEntry, MediaRootEntryKind,
 };
+pub use auth::{
+    AuthEventCursor, AuthEventEntry, AuthEventPage, AuthEventsQuery,
+    PlaybackTicketResponse, RefreshRequest,
+};
+pub use availability::MediaAvailability;
+pub use collections::{
+    AddCollectionItemsRequest, Collection, CollectionDetail, CollectionItem,
+    CollectionItemWatchState, CreateCollectionRequest,
+    ReorderCollectionRequest, UpdateCollectionRequest,
+};
+pub use credits::{
+    CastCredit, CrewCredit, KEY_CREW_JOBS, MediaCredits, Person,
+    PersonAppearance, PersonAppearances, PersonAppearancesQuery,
+    is_key_crew_job,
+};
 pub use demo::{DemoLibraryStatus, DemoResetRequest, DemoStatus};
 pub use filters::{
     FilterIndicesRequest, IndicesResponse, LibraryFilters,
@@ -25,15 +48,18 @@ pub use filters::{
 };
 pub use library::{
     BatchMediaRequest, BatchMediaResponse, CreateLibraryRequest,
-    FetchMediaRequest, LibraryMediaCache, LibraryMediaResponse,
-    ManualMatchRequest, MovieReferenceBatchBlob,
-    MovieReferenceBatchBundleResponse, MovieReferenceBatchResponse,
-    SeriesBundleBlob, SeriesBundleBundleResponse, SeriesBundleResponse,
+    DuplicateMedia, FetchMediaRequest, LibraryMediaCache, LibraryMediaResponse,
+    LibraryStats, LibraryStorageUsage, ManualMatchRequest, MediaCopy,
+    MovieReferenceBatchBlob, MovieReferenceBatchBundleResponse,
+    MovieReferenceBatchResponse, SeriesBundleBlob, SeriesBundleBundleResponse,
+    SeriesBundleResponse, StorageGrowth, StorageUsageQuery, StorageUsageReport,
     UpdateLibraryRequest,
 };
 pub use media::{
-    ImageData, ImageManifestItem, ImageManifestRequest, ImageManifestResponse,
-    ImageManifestResult, ImageManifestStatus,
+    DeleteMediaFilter, DeleteMediaRequest, DeleteMediaResponse, ImageData,
+    ImageManifestItem, ImageManifestRequest, ImageManifestResponse,
+    ImageManifestResult, ImageManifestStatus, MediaDeletionResult, MediaImage,
+    MediaImagesResponse, RefreshFieldsRequest, RefreshFieldsResponse,
 };
 pub use media_repo_sync::{
     MovieBatchFetchRequest, MovieBatchSyncRequest, MovieBatchSyncResponse,
@@ -41,10 +67,24 @@ pub use media_repo_sync::{
     SeriesBundleSyncRequest, SeriesBundleSyncResponse,
     SeriesBundleVersionManifestEntry,
 };
-pub use responses::{ApiResponse, MediaStats, MetadataRequest};
+pub use music::{MusicAlbum, MusicAlbumQuery, MusicTrack, MusicTrackQuery};
+pub use responses::{
+    ApiErrorBody, ApiErrorDetail, ApiResponse, MediaStats, MetadataRequest,
+};
 pub use scan::{
-    ActiveScansResponse, LatestProgressResponse, ScanCommandAcceptedResponse,
-    ScanCommandRequest, ScanLifecycleStatus, ScanSnapshotDto, StartScanRequest,
+    ActiveScansResponse, LatestProgressResponse, LibraryReconcileReport,
+    LibraryVerifyReport, LibraryWatchResponse, MediaFileIntegrity,
+    MediaFileVerifyIssue, PathScanAcceptedResponse, PathScanRequest,
+    ReconcileLibraryRequest, ReconciledFile, ScanCommandAcceptedResponse,
+    ScanCommandRequest, ScanLatencyBreakdown, ScanLifecycleStatus,
+    ScanSnapshotDto, SetLibraryWatchRequest, StartScanRequest,
+    VerifyLibraryRequest,
+};
+pub use subtitles::EmbeddedSubtitleTrack;
+pub use trailers::{TRAILER_KINDS, Trailer, select_trailers, trailer_url};
+pub use transcode::{
+    StartTranscodeRequest, TranscodeAudioCodec, TranscodeContainer,
+    TranscodeProfile,
 };
 pub use users_admin::{AdminUserInfo, CreateUserRequest, UpdateUserRequest};
 
@@ -54,18 +94,37 @@ pub mod player {
         MediaRootBreadcrumb, MediaRootBrowseRequest, MediaRootBrowseResponse,
         MediaRootEntry, MediaRootEntryKind,
     };
+    pub use super::auth::{
+        AuthEventCursor, AuthEventEntry, AuthEventPage, AuthEventsQuery,
+        PlaybackTicketResponse, RefreshRequest,
+    };
+    pub use super::availability::MediaAvailability;
+    pub use super::collections::{
+        AddCollectionItemsRequest, Collection, CollectionDetail,
+        CollectionItem, CollectionItemWatchState, CreateCollectionRequest,
+        ReorderCollectionRequest, UpdateCollectionRequest,
+    };
+    pub use super::credits::{
+        CastCredit, CrewCredit, MediaCredits, Person, PersonAppearance,
+        PersonAppearances, PersonAppearancesQuery,
+    };
     pub use super::demo::{DemoLibraryStatus, DemoResetRequest, DemoStatus};
     pub use super::library::{
         BatchMediaRequest, BatchMediaResponse, CreateLibraryRequest,
-        FetchMediaRequest, LibraryMediaCache, LibraryMediaResponse,
-        ManualMatchRequest, MovieReferenceBatchBlob,
+        DuplicateMedia, FetchMediaRequest, LibraryMediaCache,
+        LibraryMediaResponse, LibraryStats, LibraryStorageUsage,
+        ManualMatchRequest, MediaCopy, MovieReferenceBatchBlob,
         MovieReferenceBatchBundleResponse, MovieReferenceBatchResponse,
         SeriesBundleBlob, SeriesBundleBundleResponse, SeriesBundleResponse,
+        StorageGrowth, StorageUsageQuery, StorageUsageReport,
         UpdateLibraryRequest,
     };
     pub use super::media::{
-        ImageData, ImageManifestItem, ImageManifestRequest,
-        ImageManifestResponse, ImageManifestResult, ImageManifestStatus,
+        DeleteMediaFilter, DeleteMediaRequest, DeleteMediaResponse, ImageData,
+        ImageManifestItem, ImageManifestRequest, ImageManifestResponse,
+        ImageManifestResult, ImageManifestStatus, MediaDeletionResult,
+        MediaImage, MediaImagesResponse, RefreshFieldsRequest,
+        RefreshFieldsResponse,
     };
     pub use super::media_repo_sync::{
         MovieBatchFetchRequest, MovieBatchSyncRequest, MovieBatchSyncResponse,
@@ -73,15 +132,28 @@ pub mod player {
         SeriesBundleSyncRequest, SeriesBundleSyncResponse,
         SeriesBundleVersionManifestEntry,
     };
-    pub use super::responses::ApiResponse;
+    pub use super::music::{
+        MusicAlbum, MusicAlbumQuery, MusicTrack, MusicTrackQuery,
+    };
+    pub use super::responses::{ApiErrorBody, ApiErrorDetail, ApiResponse};
     pub use super::scan::{
-        ActiveScansResponse, LatestProgressResponse,
-        ScanCommandAcceptedResponse, ScanCommandRequest, ScanLifecycleStatus,
-        ScanSnapshotDto, StartScanRequest, events::*,
+        ActiveScansResponse, LatestProgressResponse, LibraryReconcileReport,
+        LibraryVerifyReport, LibraryWatchResponse, MediaFileIntegrity,
+        MediaFileVerifyIssue, PathScanAcceptedResponse, PathScanRequest,
+        ReconcileLibraryRequest, ReconciledFile, ScanCommandAcceptedResponse,
+        ScanCommandRequest, ScanLatencyBreakdown, ScanLifecycleStatus,
+        ScanSnapshotDto, SetLibraryWatchRequest, StartScanRequest,
+        VerifyLibraryRequest, events::*,
     };
     pub use super::setup::{
-        ConfirmClaimRequest, ConfirmClaimResponse, StartClaimRequest,
-        StartClaimResponse,
+        ClaimLinkQuery, ClaimLinkResponse, ConfirmClaimRequest,
+        ConfirmClaimResponse, StartClaimRequest, StartClaimResponse,
+    };
+    pub use super::subtitles::EmbeddedSubtitleTrack;
+    pub use super::trailers::Trailer;
+    pub use super::transcode::{
+        StartTranscodeRequest, TranscodeAudioCodec, TranscodeContainer,
+        TranscodeProfile,
     };
     pub use super::users_admin::{
         AdminUserInfo, CreateUserRequest, UpdateUserRequest,
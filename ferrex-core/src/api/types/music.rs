@@ -0,0 +1,57 @@
+//! Music library listings.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::types::ids::LibraryId;
+
+/// An audio file of a music library. Its id is accepted by the stream
+/// endpoint like a media file id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MusicTrack {
+    pub id: Uuid,
+    pub library_id: LibraryId,
+    pub file_path: String,
+    pub filename: String,
+    pub file_size: u64,
+    pub artist: String,
+    pub album: Option<String>,
+    pub title: String,
+    pub track_number: Option<u16>,
+    pub disc_number: Option<u16>,
+    pub year: Option<u16>,
+    pub discovered_at: DateTime<Utc>,
+}
+
+/// An album of a music library with the number of tracks found for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MusicAlbum {
+    pub artist: String,
+    pub album: String,
+    pub year: Option<u16>,
+    pub track_count: u32,
+}
+
+/// Filters for listing the tracks of a music library. Tracks come back
+/// ordered by artist, album, disc and track number.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MusicTrackQuery {
+    /// Exact artist name, case-insensitively.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    /// Exact album name, case-insensitively.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+    /// Substring of the title, artist or album.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+}
+
+/// Filters for listing the albums of a music library.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MusicAlbumQuery {
+    /// Exact artist name, case-insensitively.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+}
@@ -38,6 +38,29 @@ impl<T> ApiResponse<T> {
     }
 }
 
+/// Body sent with error statuses: `{"error": {"message", "status"}}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiErrorBody {
+    pub error: ApiErrorDetail,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiErrorDetail {
+    pub message: String,
+    pub status: u16,
+}
+
+impl ApiErrorBody {
+    pub fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            error: ApiErrorDetail {
+                message: message.into(),
+                status,
+            },
+        }
+    }
+}
+
 /// Request payload for metadata refresh operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetadataRequest {
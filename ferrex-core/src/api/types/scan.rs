@@ -3,7 +3,7 @@ use std::fmt;
 use uuid::Uuid;
 
 use crate::types::ids::LibraryId;
-use crate::types::media_events::ScanProgressEvent;
+use crate::types::media_events::{ScanProgressEvent, ScanStageLatencySummary};
 
 /// Lifecycle state of a background scan job
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
@@ -37,6 +37,15 @@ pub struct ScanSnapshotDto {
     pub total_items: u64,
     pub retrying_items: u64,
     pub dead_lettered_items: u64,
+    /// Files not on the video extension allowlist.
+    #[serde(default)]
+    pub skipped_by_extension: u64,
+    /// Files under the configured minimum size.
+    #[serde(default)]
+    pub skipped_too_small: u64,
+    /// Files over the configured maximum size.
+    #[serde(default)]
+    pub skipped_too_large: u64,
     pub correlation_id: Uuid,
     pub idempotency_key: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -65,6 +74,9 @@ impl fmt::Debug for ScanSnapshotDto {
             .field("total_items", &self.total_items)
             .field("retrying_items", &self.retrying_items)
             .field("dead_lettered_items", &self.dead_lettered_items)
+            .field("skipped_by_extension", &self.skipped_by_extension)
+            .field("skipped_too_small", &self.skipped_too_small)
+            .field("skipped_too_large", &self.skipped_too_large)
             .field("current_path", &self.current_path)
             .field("started_at", &self.started_at)
             .field("terminal_at", &self.terminal_at)
@@ -97,6 +109,41 @@ pub struct StartScanRequest {
     pub correlation_id: Option<Uuid>,
 }
 
+/// Request body for `/libraries/{id}/scans:path`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PathScanRequest {
+    /// Absolute path of a folder or file within one of the library's roots.
+    pub path: String,
+    #[serde(default)]
+    pub correlation_id: Option<Uuid>,
+}
+
+/// Acknowledge a targeted path scan
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PathScanAcceptedResponse {
+    pub scan_id: Uuid,
+    pub correlation_id: Uuid,
+    /// Media files under the path that were gone from disk and removed.
+    pub removed_files: u64,
+}
+
+/// Request body for `/libraries/{id}/watch`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SetLibraryWatchRequest {
+    pub enabled: bool,
+}
+
+/// Library watch state after a toggle
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LibraryWatchResponse {
+    pub library_id: LibraryId,
+    pub watch_for_changes: bool,
+    /// Scan started to pick up changes made while the library was not
+    /// watched; absent when disabling or when a scan was already running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconcile_scan_id: Option<Uuid>,
+}
+
 /// Request body for scan commands (pause/resume/cancel)
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ScanCommandRequest {
@@ -110,6 +157,129 @@ pub struct ScanCommandAcceptedResponse {
     pub correlation_id: Uuid,
 }
 
+/// Request body for `/libraries/{id}/verify`
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct VerifyLibraryRequest {
+    /// Read this many bytes from the start and end of every file, flag
+    /// samples that are entirely zeroed and compare the samples' digest with
+    /// the one an earlier run recorded for the same version of the file.
+    /// `None` limits the check to sizes.
+    #[serde(default)]
+    pub sample_bytes: Option<u32>,
+    /// Run in the background and return immediately; fetch the report later
+    /// with `GET /libraries/{id}/verify`.
+    #[serde(default)]
+    pub background: bool,
+}
+
+/// Integrity state of a single media file after verification.
+///
+/// `LibraryOffline` is reported instead of `Missing` when the library root the
+/// file lives under is itself unreachable, so clients can tell an unmounted
+/// share apart from a damaged file.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum MediaFileIntegrity {
+    Ok,
+    LibraryOffline,
+    Missing,
+    SizeMismatch {
+        expected_size: u64,
+        actual_size: u64,
+    },
+    Zeroed,
+    /// The head or tail changed since an earlier run sampled the file,
+    /// although its size and modification time did not.
+    DigestMismatch,
+    Unreadable {
+        message: String,
+    },
+}
+
+impl MediaFileIntegrity {
+    /// Whether the file should be picked up again by the next scan.
+    pub fn needs_rescan(&self) -> bool {
+        matches!(
+            self,
+            MediaFileIntegrity::Missing
+                | MediaFileIntegrity::SizeMismatch { .. }
+                | MediaFileIntegrity::Zeroed
+                | MediaFileIntegrity::DigestMismatch
+        )
+    }
+}
+
+/// A media file that did not pass verification.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MediaFileVerifyIssue {
+    pub file_id: Uuid,
+    pub path: String,
+    pub integrity: MediaFileIntegrity,
+}
+
+/// Result of verifying every media file in a library.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LibraryVerifyReport {
+    pub library_id: LibraryId,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub files_checked: u64,
+    pub files_ok: u64,
+    /// Library roots that could not be reached during verification.
+    pub offline_roots: Vec<String>,
+    pub issues: Vec<MediaFileVerifyIssue>,
+    /// Folders whose scan cursor was reset so the next scan revisits them.
+    pub folders_marked_for_rescan: u64,
+}
+
+/// Request body for `/libraries/{id}/reconcile`
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ReconcileLibraryRequest {
+    /// Report what would be removed without deleting anything.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A media file whose record was removed because it is gone from disk.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReconciledFile {
+    pub file_id: Uuid,
+    pub path: String,
+}
+
+/// Result of a deletion reconcile: every indexed file of a library is
+/// checked for existence, without reading contents or fetching metadata.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LibraryReconcileReport {
+    pub library_id: LibraryId,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub dry_run: bool,
+    pub files_checked: u64,
+    /// Files left alone because their root is offline or their existence
+    /// could not be determined.
+    pub files_skipped: u64,
+    /// Roots that are missing, not directories or empty. Nothing below
+    /// them is removed, so an unmounted share does not empty the library.
+    pub offline_roots: Vec<String>,
+    /// Files confirmed gone; removed unless `dry_run` is set.
+    pub removed: Vec<ReconciledFile>,
+    /// Cached image sizes dropped along with the removed media.
+    pub images_invalidated: u64,
+}
+
+/// Response for `/scan/history/{id}/latency`: per-stage job latencies of a
+/// scan, aggregated across every job it ran.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScanLatencyBreakdown {
+    pub scan_id: Uuid,
+    pub library_id: LibraryId,
+    pub p50_stage_latencies_ms: ScanStageLatencySummary,
+    pub p95_stage_latencies_ms: ScanStageLatencySummary,
+    /// Number of completed jobs measured per stage.
+    pub jobs_measured: ScanStageLatencySummary,
+}
+
 /// Re-export media scan SSE payloads for downstream clients
 pub mod events {
     pub use crate::types::media_events::{
@@ -11,6 +11,10 @@ use uuid::Uuid;
 pub struct StartClaimRequest {
     #[serde(default)]
     pub device_name: Option<String>,
+    /// Address other devices should use to reach the server; defaults to
+    /// the one this request was sent to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_url: Option<String>,
 }
 
 /// Response returned when a secure setup claim is started.
@@ -20,12 +24,39 @@ pub struct StartClaimResponse {
     pub claim_code: String,
     pub expires_at: DateTime<Utc>,
     pub lan_only: bool,
+    /// Signed `ferrex://setup/claim?…` deep link for the claim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim_link: Option<String>,
 }
 
-/// Request payload for confirming a setup claim with the generated code.
+/// Request payload for confirming a setup claim with the generated code,
+/// or with its scanned deep link instead.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfirmClaimRequest {
+    #[serde(default)]
+    pub claim_code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim_link: Option<String>,
+}
+
+/// Query for the deep link of a pending claim; the code proves the caller
+/// started it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimLinkQuery {
     pub claim_code: String,
+    #[serde(default)]
+    pub server_url: Option<String>,
+}
+
+/// Deep link of the pending claim, as text and as a QR code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimLinkResponse {
+    pub claim_id: Uuid,
+    pub claim_link: String,
+    pub server_url: String,
+    pub expires_at: DateTime<Utc>,
+    /// PNG rendering of `claim_link` as a QR code, base64 encoded.
+    pub qr_png: String,
 }
 
 /// Response returned when a setup claim is confirmed and a token is issued.
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// A subtitle track muxed into a media file's container.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddedSubtitleTrack {
+    /// Position among the file's subtitle tracks; the `{index}` of the
+    /// embedded subtitle endpoint.
+    pub index: u32,
+    /// ffmpeg codec name, e.g. `subrip`, `ass` or `hdmv_pgs_subtitle`.
+    pub codec: String,
+    /// Language tag as stored in the container (usually ISO 639-2).
+    pub language: Option<String>,
+    pub title: Option<String>,
+    pub forced: bool,
+    pub default: bool,
+    /// Whether the track is text and can be served as WebVTT. Image-based
+    /// tracks (PGS, VobSub, DVB) cannot.
+    pub text_convertible: bool,
+}
@@ -0,0 +1,160 @@
+//! Trailers of a movie or series, picked from the videos stored with its
+//! TMDB metadata.
+
+use std::{cmp::Reverse, collections::HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::details::Video;
+
+/// Video types offered as trailers, preferred first.
+pub const TRAILER_KINDS: &[&str] = &["Trailer", "Teaser"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trailer {
+    /// Hosting site as named by TMDB, e.g. `YouTube` or `Vimeo`.
+    pub provider: String,
+    /// Id of the video on the provider, used to embed it.
+    pub key: String,
+    /// One of [`TRAILER_KINDS`].
+    pub kind: String,
+    pub name: Option<String>,
+    /// Published by the studio or network rather than a fan channel.
+    pub official: bool,
+    pub language: Option<String>,
+    pub published_at: Option<String>,
+    /// Watch page on the provider, when it is one clients can open.
+    pub url: Option<String>,
+}
+
+/// Watch page of `key` on `provider`.
+pub fn trailer_url(provider: &str, key: &str) -> Option<String> {
+    match provider.to_ascii_lowercase().as_str() {
+        "youtube" => Some(format!("https://www.youtube.com/watch?v={key}")),
+        "vimeo" => Some(format!("https://vimeo.com/{key}")),
+        _ => None,
+    }
+}
+
+/// Trailers and teasers among `videos`: official ones first, trailers
+/// before teasers, newest first. A video listed twice, under the same key
+/// or the same name and language, is returned once.
+pub fn select_trailers(videos: &[Video]) -> Vec<Trailer> {
+    let mut candidates: Vec<(usize, Trailer)> = videos
+        .iter()
+        .filter_map(|video| {
+            let kind = video.video_type.as_deref()?;
+            let rank = TRAILER_KINDS
+                .iter()
+                .position(|known| known.eq_ignore_ascii_case(kind))?;
+            Some((
+                rank,
+                Trailer {
+                    provider: video.site.clone(),
+                    key: video.key.clone(),
+                    kind: TRAILER_KINDS[rank].to_string(),
+                    name: video.name.clone(),
+                    official: video.official.unwrap_or(false),
+                    language: video.iso_639_1.clone(),
+                    published_at: video.published_at.clone(),
+                    url: trailer_url(&video.site, &video.key),
+                },
+            ))
+        })
+        .collect();
+    candidates.sort_by(|(a_rank, a), (b_rank, b)| {
+        (!a.official, a_rank, Reverse(&a.published_at)).cmp(&(
+            !b.official,
+            b_rank,
+            Reverse(&b.published_at),
+        ))
+    });
+
+    let mut keys = HashSet::new();
+    let mut names = HashSet::new();
+    candidates
+        .into_iter()
+        .map(|(_, trailer)| trailer)
+        .filter(|trailer| {
+            let new_key = keys.insert((
+                trailer.provider.to_ascii_lowercase(),
+                trailer.key.clone(),
+            ));
+            let new_name = match &trailer.name {
+                Some(name) => names.insert((
+                    name.trim().to_lowercase(),
+                    trailer.language.clone(),
+                )),
+                None => true,
+            };
+            new_key && new_name
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(
+        key: &str,
+        name: &str,
+        kind: &str,
+        official: bool,
+        published_at: &str,
+    ) -> Video {
+        Video {
+            key: key.into(),
+            name: Some(name.into()),
+            site: "YouTube".into(),
+            video_type: Some(kind.into()),
+            official: Some(official),
+            iso_639_1: Some("en".into()),
+            iso_3166_1: Some("US".into()),
+            published_at: Some(published_at.into()),
+            size: Some(1080),
+        }
+    }
+
+    #[test]
+    fn official_trailers_come_first_and_duplicates_are_dropped() {
+        let videos = [
+            video("fan", "Heat Trailer", "Trailer", false, "2020-01-01"),
+            video("teaser", "Teaser", "Teaser", true, "1995-10-01"),
+            video("clip", "Bank Scene", "Clip", true, "1995-12-01"),
+            video("old", "Official Trailer", "Trailer", true, "1995-11-01"),
+            video("new", "4K Trailer", "Trailer", true, "2017-05-01"),
+            video(
+                "reupload",
+                "Official Trailer",
+                "Trailer",
+                false,
+                "2019-01-01",
+            ),
+            video("new", "4K Trailer (copy)", "Trailer", true, "2017-05-01"),
+        ];
+
+        let keys: Vec<String> = select_trailers(&videos)
+            .into_iter()
+            .map(|trailer| trailer.key)
+            .collect();
+        assert_eq!(keys, ["new", "old", "teaser", "fan"]);
+    }
+
+    #[test]
+    fn no_videos_means_no_trailers() {
+        assert!(select_trailers(&[]).is_empty());
+        let trailer = &select_trailers(&[video(
+            "abc",
+            "Trailer",
+            "trailer",
+            true,
+            "2001-01-01",
+        )])[0];
+        assert_eq!(trailer.kind, "Trailer");
+        assert_eq!(
+            trailer.url.as_deref(),
+            Some("https://www.youtube.com/watch?v=abc")
+        );
+    }
+}
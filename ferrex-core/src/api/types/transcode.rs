@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Segment container written by a transcode job.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscodeContainer {
+    /// HLS with MPEG-TS segments.
+    #[default]
+    HlsTs,
+    /// HLS with fragmented MP4 segments.
+    HlsFmp4,
+}
+
+/// Audio codec of the transcoded output.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscodeAudioCodec {
+    #[default]
+    Aac,
+    Opus,
+    Ac3,
+}
+
+/// Output constraints requested by the client.
+///
+/// Two requests with equal profiles for the same media share one job.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct TranscodeProfile {
+    #[serde(default)]
+    pub container: TranscodeContainer,
+    /// Cap on the output height in pixels; the source is never upscaled.
+    #[serde(default)]
+    pub max_height: Option<u32>,
+    #[serde(default)]
+    pub audio_codec: TranscodeAudioCodec,
+    /// Video bitrate ceiling in kbit/s.
+    #[serde(default)]
+    pub max_bitrate_kbps: Option<u32>,
+}
+
+impl TranscodeProfile {
+    /// Named presets used by clients that only pick a quality level.
+    pub fn preset(name: &str) -> Option<Self> {
+        let (max_height, max_bitrate_kbps) = match name {
+            "original" => (None, None),
+            "2160p" | "4k" => (Some(2160), Some(40_000)),
+            "1080p" => (Some(1080), Some(8_000)),
+            "720p" => (Some(720), Some(4_000)),
+            "480p" => (Some(480), Some(1_500)),
+            _ => return None,
+        };
+        Some(Self {
+            max_height,
+            max_bitrate_kbps,
+            ..Self::default()
+        })
+    }
+
+    /// Stable key used to coalesce identical requests.
+    pub fn cache_key(&self) -> String {
+        let container = match self.container {
+            TranscodeContainer::HlsTs => "ts",
+            TranscodeContainer::HlsFmp4 => "fmp4",
+        };
+        let audio = match self.audio_codec {
+            TranscodeAudioCodec::Aac => "aac",
+            TranscodeAudioCodec::Opus => "opus",
+            TranscodeAudioCodec::Ac3 => "ac3",
+        };
+        format!(
+            "{}-h{}-{}-b{}",
+            container,
+            self.max_height.unwrap_or(0),
+            audio,
+            self.max_bitrate_kbps.unwrap_or(0)
+        )
+    }
+}
+
+/// Request body for `POST /transcode`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartTranscodeRequest {
+    /// Media file id as used by `/stream/{id}`.
+    pub media_id: Uuid,
+    #[serde(default)]
+    pub profile: TranscodeProfile,
+}
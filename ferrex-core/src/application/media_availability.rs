@@ -0,0 +1,281 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use reqwest::{StatusCode, header};
+use uuid::Uuid;
+
+use crate::{
+    api::types::MediaAvailability,
+    application::unit_of_work::AppUnitOfWork,
+    error::Result,
+    types::{LibraryId, MediaLocation},
+};
+
+/// How long a remote upstream gets to answer a reachability probe.
+const REMOTE_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+static REMOTE_PROBE_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(REMOTE_PROBE_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Failed to create HTTP client")
+});
+
+/// Tells apart media that is unknown, stored on an offline library, missing
+/// from disk, or stored behind a URL that cannot be reached.
+pub struct MediaAvailabilityService {
+    unit_of_work: Arc<AppUnitOfWork>,
+}
+
+impl fmt::Debug for MediaAvailabilityService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MediaAvailabilityService")
+            .finish_non_exhaustive()
+    }
+}
+
+impl MediaAvailabilityService {
+    pub fn new(unit_of_work: Arc<AppUnitOfWork>) -> Self {
+        Self { unit_of_work }
+    }
+
+    /// Availability of the media file or music track `media_id`.
+    pub async fn media_availability(
+        &self,
+        media_id: Uuid,
+    ) -> Result<MediaAvailability> {
+        let uow = &self.unit_of_work;
+        if let Some(file) = uow.media_files_read.get_by_id(&media_id).await? {
+            return self.file_availability(file.library_id, &file.path).await;
+        }
+        match uow.music_tracks.get_track(media_id).await? {
+            Some(track) => {
+                self.file_availability(
+                    track.library_id,
+                    Path::new(&track.file_path),
+                )
+                .await
+            }
+            None => Ok(MediaAvailability::NotFound),
+        }
+    }
+
+    /// Availability of an indexed file at `path` in library `library_id`,
+    /// for callers that already loaded the file's record.
+    pub async fn file_availability(
+        &self,
+        library_id: LibraryId,
+        path: &Path,
+    ) -> Result<MediaAvailability> {
+        let roots = self
+            .unit_of_work
+            .libraries
+            .get_library(library_id)
+            .await?
+            .map(|library| library.paths)
+            .unwrap_or_default();
+        Ok(check_file(path, &roots).await)
+    }
+}
+
+async fn check_file(
+    path: &Path,
+    library_roots: &[PathBuf],
+) -> MediaAvailability {
+    if let MediaLocation::Remote(url) = MediaLocation::of(path) {
+        return check_remote(url).await;
+    }
+    if is_file(path).await {
+        return MediaAvailability::Available {
+            path: path.to_path_buf(),
+        };
+    }
+    // Prefer the most specific root when libraries nest.
+    let root = library_roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count());
+    if let Some(root) = root
+        && !is_dir(root).await
+    {
+        return MediaAvailability::LibraryOffline {
+            path: path.to_path_buf(),
+            library_root: root.clone(),
+        };
+    }
+    MediaAvailability::FileMissing {
+        path: path.to_path_buf(),
+    }
+}
+
+/// Probe a remote file with `HEAD`, or a one-byte ranged `GET` for
+/// upstreams that do not implement `HEAD`. Redirects are not followed, so
+/// the probe never reaches a host other than the one in `url`.
+async fn check_remote(url: &str) -> MediaAvailability {
+    let client = &*REMOTE_PROBE_CLIENT;
+    let mut probe = client.head(url).send().await;
+    if let Ok(response) = &probe
+        && matches!(
+            response.status(),
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+        )
+    {
+        probe = client
+            .get(url)
+            .header(header::RANGE, "bytes=0-0")
+            .send()
+            .await;
+    }
+
+    let reason = match probe {
+        Ok(response) if response.status().is_success() => {
+            return MediaAvailability::Available {
+                path: PathBuf::from(url),
+            };
+        }
+        Ok(response)
+            if matches!(
+                response.status(),
+                StatusCode::NOT_FOUND | StatusCode::GONE
+            ) =>
+        {
+            return MediaAvailability::FileMissing {
+                path: PathBuf::from(url),
+            };
+        }
+        Ok(response) => format!("upstream answered {}", response.status()),
+        Err(err) => err.to_string(),
+    };
+    MediaAvailability::RemoteUnreachable {
+        url: url.to_string(),
+        reason,
+    }
+}
+
+async fn is_file(path: &Path) -> bool {
+    tokio::fs::metadata(path)
+        .await
+        .is_ok_and(|meta| meta.is_file())
+}
+
+async fn is_dir(path: &Path) -> bool {
+    tokio::fs::metadata(path)
+        .await
+        .is_ok_and(|meta| meta.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn distinguishes_missing_files_from_offline_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("movies");
+        std::fs::create_dir_all(&root).unwrap();
+        let present = root.join("Heat (1995).mkv");
+        std::fs::write(&present, b"x").unwrap();
+        let roots = vec![root.clone()];
+
+        assert_eq!(
+            check_file(&present, &roots).await,
+            MediaAvailability::Available {
+                path: present.clone()
+            }
+        );
+
+        let deleted = root.join("Ronin (1998).mkv");
+        assert_eq!(
+            check_file(&deleted, &roots).await,
+            MediaAvailability::FileMissing {
+                path: deleted.clone()
+            }
+        );
+
+        let unmounted = dir.path().join("share");
+        let expected = unmounted.join("Alien (1979).mkv");
+        assert_eq!(
+            check_file(&expected, &[root, unmounted.clone()]).await,
+            MediaAvailability::LibraryOffline {
+                path: expected.clone(),
+                library_root: unmounted,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn files_outside_every_root_are_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("elsewhere/clip.mp4");
+        let roots = vec![dir.path().join("offline")];
+
+        assert_eq!(
+            check_file(&path, &roots).await,
+            MediaAvailability::FileMissing { path }
+        );
+    }
+
+    /// Answer every connection on a local port with `status`.
+    async fn upstream(status: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener =
+            tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let _ = socket.read(&mut request).await;
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{addr}/movies/Heat.mkv")
+    }
+
+    #[tokio::test]
+    async fn remote_files_are_probed_over_http() {
+        let url = upstream("200 OK").await;
+        assert_eq!(
+            check_file(Path::new(&url), &[]).await,
+            MediaAvailability::Available {
+                path: PathBuf::from(&url)
+            }
+        );
+
+        let url = upstream("404 Not Found").await;
+        assert_eq!(
+            check_file(Path::new(&url), &[]).await,
+            MediaAvailability::FileMissing {
+                path: PathBuf::from(&url)
+            }
+        );
+
+        let url = upstream("500 Internal Server Error").await;
+        assert!(matches!(
+            check_file(Path::new(&url), &[]).await,
+            MediaAvailability::RemoteUnreachable { reason, .. }
+                if reason.contains("500")
+        ));
+    }
+
+    #[tokio::test]
+    async fn unreachable_upstreams_are_not_missing_files() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/clip.mp4", listener.local_addr().unwrap());
+        drop(listener);
+
+        assert!(matches!(
+            check_file(Path::new(&url), &[]).await,
+            MediaAvailability::RemoteUnreachable { url: reported, .. }
+                if reported == url
+        ));
+    }
+}
@@ -1,4 +1,8 @@
 #[cfg(feature = "database")]
+pub mod media_availability;
+#[cfg(feature = "database")]
 pub mod rbac_bootstrap;
 #[cfg(feature = "database")]
 pub mod unit_of_work;
+#[cfg(feature = "database")]
+pub mod watch_status;
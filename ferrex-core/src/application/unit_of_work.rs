@@ -7,10 +7,13 @@ use crate::database::repositories::watch_status::PostgresWatchStatusRepository;
 use crate::database::{
     postgres::PostgresDatabase,
     repositories::{
+        collections::PostgresCollectionsRepository,
+        credits::PostgresCreditsRepository,
         folder_inventory::PostgresFolderInventoryRepository,
         images::PostgresImageRepository, indices::PostgresIndicesRepository,
         library::PostgresLibraryRepository, media::PostgresMediaRepository,
         media_references::PostgresMediaReferencesRepository,
+        music_tracks::PostgresMusicTracksRepository,
         processing_status::PostgresProcessingStatusRepository,
         query::PostgresQueryRepository, rbac::PostgresRbacRepository,
         security_settings::PostgresSecuritySettingsRepository,
@@ -20,10 +23,12 @@ use crate::database::{
         watch_metrics::PostgresWatchMetricsRepository,
     },
     repository_ports::{
+        collections::CollectionsRepository, credits::CreditsRepository,
         folder_inventory::FolderInventoryRepository, images::ImageRepository,
         indices::IndicesRepository, library::LibraryRepository,
         media_files::MediaFilesReadPort, media_files::MediaFilesWritePort,
         media_references::MediaReferencesRepository,
+        music_tracks::MusicTracksRepository,
         processing_status::ProcessingStatusRepositoryTrait,
         query::QueryRepository, rbac::RbacRepository,
         security_settings::SecuritySettingsRepository,
@@ -46,6 +51,7 @@ pub struct AppUnitOfWork {
     pub media_files_write: Arc<dyn MediaFilesWritePort>,
     pub images: Arc<dyn ImageRepository>,
     pub query: Arc<dyn QueryRepository>,
+    pub music_tracks: Arc<dyn MusicTracksRepository>,
 
     pub users: Arc<dyn UsersRepository>,
     pub rbac: Arc<dyn RbacRepository>,
@@ -54,6 +60,8 @@ pub struct AppUnitOfWork {
 
     pub watch_status: Arc<dyn WatchStatusRepository>,
     pub watch_metrics: Arc<dyn WatchMetricsReadPort>,
+    pub collections: Arc<dyn CollectionsRepository>,
+    pub credits: Arc<dyn CreditsRepository>,
 
     pub sync_sessions: Arc<dyn SyncSessionsRepository>,
 
@@ -77,6 +85,10 @@ impl fmt::Debug for AppUnitOfWork {
             )
             .field("images", &type_name_of_val(self.images.as_ref()))
             .field("query", &type_name_of_val(self.query.as_ref()))
+            .field(
+                "music_tracks",
+                &type_name_of_val(self.music_tracks.as_ref()),
+            )
             .field("users", &type_name_of_val(self.users.as_ref()))
             .field("rbac", &type_name_of_val(self.rbac.as_ref()))
             .field(
@@ -95,6 +107,8 @@ impl fmt::Debug for AppUnitOfWork {
                 "watch_metrics",
                 &type_name_of_val(self.watch_metrics.as_ref()),
             )
+            .field("collections", &type_name_of_val(self.collections.as_ref()))
+            .field("credits", &type_name_of_val(self.credits.as_ref()))
             .field(
                 "sync_sessions",
                 &type_name_of_val(self.sync_sessions.as_ref()),
@@ -120,6 +134,7 @@ pub struct AppUnitOfWorkBuilder {
     media_files_write: Option<Arc<dyn MediaFilesWritePort>>,
     images: Option<Arc<dyn ImageRepository>>,
     query: Option<Arc<dyn QueryRepository>>,
+    music_tracks: Option<Arc<dyn MusicTracksRepository>>,
 
     users: Option<Arc<dyn UsersRepository>>,
     rbac: Option<Arc<dyn RbacRepository>>,
@@ -128,6 +143,8 @@ pub struct AppUnitOfWorkBuilder {
 
     watch_status: Option<Arc<dyn WatchStatusRepository>>,
     watch_metrics: Option<Arc<dyn WatchMetricsReadPort>>,
+    collections: Option<Arc<dyn CollectionsRepository>>,
+    credits: Option<Arc<dyn CreditsRepository>>,
 
     sync_sessions: Option<Arc<dyn SyncSessionsRepository>>,
 
@@ -145,12 +162,15 @@ impl fmt::Debug for AppUnitOfWorkBuilder {
             .field("media_files_write", &self.media_files_write.is_some())
             .field("images", &self.images.is_some())
             .field("query", &self.query.is_some())
+            .field("music_tracks", &self.music_tracks.is_some())
             .field("users", &self.users.is_some())
             .field("rbac", &self.rbac.is_some())
             .field("security_settings", &self.security_settings.is_some())
             .field("setup_claims", &self.setup_claims.is_some())
             .field("watch_status", &self.watch_status.is_some())
             .field("watch_metrics", &self.watch_metrics.is_some())
+            .field("collections", &self.collections.is_some())
+            .field("credits", &self.credits.is_some())
             .field("sync_sessions", &self.sync_sessions.is_some())
             .field("folder_inventory", &self.folder_inventory.is_some())
             .field("processing_status", &self.processing_status.is_some())
@@ -197,6 +217,13 @@ impl AppUnitOfWorkBuilder {
         self.query = Some(repo);
         self
     }
+    pub fn with_music_tracks(
+        mut self,
+        repo: Arc<dyn MusicTracksRepository>,
+    ) -> Self {
+        self.music_tracks = Some(repo);
+        self
+    }
     pub fn with_users(mut self, repo: Arc<dyn UsersRepository>) -> Self {
         self.users = Some(repo);
         self
@@ -226,6 +253,17 @@ impl AppUnitOfWorkBuilder {
         self.watch_metrics = Some(repo);
         self
     }
+    pub fn with_collections(
+        mut self,
+        repo: Arc<dyn CollectionsRepository>,
+    ) -> Self {
+        self.collections = Some(repo);
+        self
+    }
+    pub fn with_credits(mut self, repo: Arc<dyn CreditsRepository>) -> Self {
+        self.credits = Some(repo);
+        self
+    }
     pub fn with_sync_sessions(
         mut self,
         repo: Arc<dyn SyncSessionsRepository>,
@@ -274,6 +312,9 @@ impl AppUnitOfWorkBuilder {
             query: self
                 .query
                 .ok_or_else(|| "missing QueryRepository".to_string())?,
+            music_tracks: self
+                .music_tracks
+                .ok_or_else(|| "missing MusicTracksRepository".to_string())?,
             users: self
                 .users
                 .ok_or_else(|| "missing UsersRepository".to_string())?,
@@ -292,6 +333,12 @@ impl AppUnitOfWorkBuilder {
             watch_metrics: self
                 .watch_metrics
                 .ok_or_else(|| "missing WatchMetricsReadPort".to_string())?,
+            collections: self
+                .collections
+                .ok_or_else(|| "missing CollectionsRepository".to_string())?,
+            credits: self
+                .credits
+                .ok_or_else(|| "missing CreditsRepository".to_string())?,
             sync_sessions: self
                 .sync_sessions
                 .ok_or_else(|| "missing SyncSessionsRepository".to_string())?,
@@ -342,6 +389,10 @@ impl AppUnitOfWorkBuilder {
             Arc::new(PostgresQueryRepository::new(pool.clone()));
         self.query = Some(query);
 
+        let music_tracks: Arc<dyn MusicTracksRepository> =
+            Arc::new(PostgresMusicTracksRepository::new(pool.clone()));
+        self.music_tracks = Some(music_tracks);
+
         let users: Arc<dyn UsersRepository> =
             Arc::new(PostgresUsersRepository::new(pool.clone()));
         self.users = Some(users);
@@ -366,6 +417,14 @@ impl AppUnitOfWorkBuilder {
             Arc::new(PostgresWatchMetricsRepository::new(pool.clone()));
         self.watch_metrics = Some(watch_metrics);
 
+        let collections: Arc<dyn CollectionsRepository> =
+            Arc::new(PostgresCollectionsRepository::new(pool.clone()));
+        self.collections = Some(collections);
+
+        let credits: Arc<dyn CreditsRepository> =
+            Arc::new(PostgresCreditsRepository::new(pool.clone()));
+        self.credits = Some(credits);
+
         let sync_sessions: Arc<dyn SyncSessionsRepository> =
             Arc::new(PostgresSyncSessionsRepository::new(pool.clone()));
         self.sync_sessions = Some(sync_sessions);
@@ -385,3 +444,19 @@ impl AppUnitOfWorkBuilder {
         self
     }
 }
+
+#[cfg(feature = "test-utils")]
+impl AppUnitOfWorkBuilder {
+    /// Use the in-memory media file, image and watch stores. The remaining
+    /// ports must still be supplied before [`Self::build`].
+    pub fn with_in_memory(
+        mut self,
+        db: &crate::database::memory::InMemoryDatabase,
+    ) -> Self {
+        self.media_files_read = Some(db.media_files.clone());
+        self.media_files_write = Some(db.media_files.clone());
+        self.images = Some(db.images.clone());
+        self.watch_status = Some(db.watch_status.clone());
+        self
+    }
+}
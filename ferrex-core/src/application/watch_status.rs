@@ -0,0 +1,143 @@
+use std::{fmt, sync::Arc};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    application::unit_of_work::AppUnitOfWork,
+    domain::watch::{
+        BulkWatchUpdate, EpisodeKey, MarkEpisodeWatchedRequest,
+        SeasonWatchStatus, WATCH_STATE_EXPORT_VERSION, WatchScope,
+        WatchStateExport, WatchStateImportReport,
+    },
+    error::{MediaError, Result},
+    types::{SeasonID, SeriesID},
+};
+
+/// Watch-state changes that span several episodes: whole seasons and
+/// series, and single episodes marked with a cascade.
+///
+/// Each call is applied in a single transaction by the watch-status
+/// repository, so a season is never left half marked.
+pub struct WatchStatusService {
+    unit_of_work: Arc<AppUnitOfWork>,
+}
+
+impl fmt::Debug for WatchStatusService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchStatusService").finish_non_exhaustive()
+    }
+}
+
+impl WatchStatusService {
+    pub fn new(unit_of_work: Arc<AppUnitOfWork>) -> Self {
+        Self { unit_of_work }
+    }
+
+    pub async fn mark_series_watched(
+        &self,
+        user_id: Uuid,
+        series_id: SeriesID,
+    ) -> Result<BulkWatchUpdate> {
+        self.set(user_id, WatchScope::Series(series_id), true).await
+    }
+
+    pub async fn mark_series_unwatched(
+        &self,
+        user_id: Uuid,
+        series_id: SeriesID,
+    ) -> Result<BulkWatchUpdate> {
+        self.set(user_id, WatchScope::Series(series_id), false)
+            .await
+    }
+
+    pub async fn mark_season_watched(
+        &self,
+        user_id: Uuid,
+        season_id: SeasonID,
+    ) -> Result<BulkWatchUpdate> {
+        self.set(user_id, WatchScope::Season(season_id), true).await
+    }
+
+    pub async fn mark_season_unwatched(
+        &self,
+        user_id: Uuid,
+        season_id: SeasonID,
+    ) -> Result<BulkWatchUpdate> {
+        self.set(user_id, WatchScope::Season(season_id), false)
+            .await
+    }
+
+    /// Mark one episode watched under the request's policy and return the
+    /// recomputed state of its season.
+    ///
+    /// A cascade stays within the episode's season unless the request is
+    /// explicitly series-wide.
+    pub async fn mark_episode_watched(
+        &self,
+        user_id: Uuid,
+        key: EpisodeKey,
+        request: MarkEpisodeWatchedRequest,
+    ) -> Result<SeasonWatchStatus> {
+        let watch_status = &self.unit_of_work.watch_status;
+        watch_status
+            .mark_episode_watched(user_id, &key, request)
+            .await?;
+        watch_status
+            .get_season_watch_status(
+                user_id,
+                key.tmdb_series_id,
+                key.season_number,
+            )
+            .await
+    }
+
+    /// The user's watch state keyed by stable identity, for backups and
+    /// moving to another server.
+    pub async fn export_watch_state(
+        &self,
+        user_id: Uuid,
+    ) -> Result<WatchStateExport> {
+        let entries = self
+            .unit_of_work
+            .watch_status
+            .export_watch_state(user_id)
+            .await?;
+        Ok(WatchStateExport::new(
+            entries,
+            Utc::now().timestamp_millis(),
+        ))
+    }
+
+    /// Re-map an export onto the media currently in the libraries.
+    pub async fn import_watch_state(
+        &self,
+        user_id: Uuid,
+        export: WatchStateExport,
+    ) -> Result<WatchStateImportReport> {
+        if export.version > WATCH_STATE_EXPORT_VERSION {
+            return Err(MediaError::InvalidMedia(format!(
+                "Unsupported watch state export version {}",
+                export.version
+            )));
+        }
+        // Merging again tolerates hand-edited or concatenated exports.
+        let export = WatchStateExport::new(export.entries, export.exported_at);
+        self.unit_of_work
+            .watch_status
+            .import_watch_state(user_id, &export.entries)
+            .await
+    }
+
+    async fn set(
+        &self,
+        user_id: Uuid,
+        scope: WatchScope,
+        watched: bool,
+    ) -> Result<BulkWatchUpdate> {
+        self.unit_of_work
+            .watch_status
+            .set_scope_watched(user_id, scope, watched)
+            .await
+    }
+}
@@ -0,0 +1,745 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use ferrex_model::{
+    ImageMediaType, ImageSize,
+    image::{ImageDimensions, ImageVariant, SqlxImageSizeVariant},
+};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        repository_ports::images::{
+            CachedImageRef, ImageRepository, ImgDbLookup, ImgInput,
+            ThemeColorCandidate, VarInput,
+        },
+        traits::{ImageRecord, OriginalImage},
+    },
+    error::{MediaError, Result},
+};
+
+/// One `tmdb_image_variants` row.
+#[derive(Debug, Clone)]
+struct Variant {
+    iid: Uuid,
+    variant: ImageVariant,
+    tmdb_path: String,
+    media_id: Uuid,
+    media_type: ImageMediaType,
+    width: i16,
+    iso_lang: String,
+    vote_avg: f32,
+    vote_cnt: u32,
+    is_primary: bool,
+    placeholder: Option<Vec<u8>>,
+}
+
+impl Variant {
+    fn original(&self) -> OriginalImage {
+        let imz = if self.width > 0 {
+            ImageSize::original(self.width as u32, self.variant)
+        } else {
+            ImageSize::original_unknown(self.variant)
+        };
+        OriginalImage {
+            iid: self.iid,
+            media_id: self.media_id,
+            media_type: self.media_type,
+            tmdb_path: self.tmdb_path.clone(),
+            imz,
+            iso_lang: self.iso_lang.clone(),
+            vote_avg: self.vote_avg,
+            vote_cnt: self.vote_cnt,
+            is_primary: self.is_primary,
+        }
+    }
+
+    /// Primary first, then by votes.
+    fn rank(a: &&Variant, b: &&Variant) -> Ordering {
+        b.is_primary
+            .cmp(&a.is_primary)
+            .then_with(|| b.vote_avg.total_cmp(&a.vote_avg))
+            .then_with(|| b.vote_cnt.cmp(&a.vote_cnt))
+    }
+}
+
+/// One `cached_images` row.
+#[derive(Debug, Clone)]
+struct Cached {
+    record: ImageRecord,
+    size_variant: SqlxImageSizeVariant,
+}
+
+#[derive(Debug, Default)]
+struct Images {
+    variants: HashMap<Uuid, Variant>,
+    by_path: HashMap<String, Uuid>,
+    /// Keyed by image id and width, the `cached_images` unique key.
+    cached: HashMap<(Uuid, i16), Cached>,
+}
+
+impl Images {
+    fn variants_of(
+        &self,
+        media_id: Uuid,
+        media_type: ImageMediaType,
+        variant: ImageVariant,
+    ) -> Vec<&Variant> {
+        let mut found: Vec<&Variant> = self
+            .variants
+            .values()
+            .filter(|v| {
+                v.media_id == media_id
+                    && v.media_type == media_type
+                    && v.variant == variant
+            })
+            .collect();
+        found.sort_by(Variant::rank);
+        found
+    }
+
+    /// Most recently written cached size of `iid` accepted by `keep`.
+    fn latest_cached(
+        &self,
+        iid: Uuid,
+        keep: impl Fn(&Cached) -> bool,
+    ) -> Option<ImageRecord> {
+        self.cached
+            .iter()
+            .filter(|((image, _), cached)| *image == iid && keep(cached))
+            .map(|(_, cached)| &cached.record)
+            .max_by_key(|record| record.modified_at)
+            .cloned()
+    }
+}
+
+/// Image variants and cached sizes in process memory.
+///
+/// Theme colors live on movie and series rows, which this store does not
+/// have: [`ImageRepository::list_theme_color_candidates`] finds nothing and
+/// [`ImageRepository::update_media_theme_color`] updates nothing.
+#[derive(Debug, Default)]
+pub struct InMemoryImages {
+    images: RwLock<Images>,
+}
+
+impl InMemoryImages {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&Images) -> T) -> T {
+        f(&self.images.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut Images) -> T) -> T {
+        f(&mut self.images.write().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// The size a cached row reads back as, as the Postgres adapter maps it.
+fn cached_size(
+    size_variant: SqlxImageSizeVariant,
+    width: u32,
+    variant: ImageVariant,
+) -> ImageSize {
+    match size_variant {
+        SqlxImageSizeVariant::Original => ImageSize::original(width, variant),
+        SqlxImageSizeVariant::Resized => ImageSize::custom(width, variant),
+        SqlxImageSizeVariant::Tmdb => {
+            ImageSize::from_size_and_variant(width, variant)
+        }
+    }
+}
+
+/// Position of `variant` in media image listings, matching the enum order
+/// Postgres sorts by.
+fn variant_order(variant: ImageVariant) -> u8 {
+    match variant {
+        ImageVariant::Poster => 0,
+        ImageVariant::Backdrop => 1,
+        ImageVariant::Thumbnail => 2,
+        ImageVariant::Profile => 3,
+    }
+}
+
+fn upsert_variant(images: &mut Images, input: &VarInput) -> OriginalImage {
+    let iid = images
+        .by_path
+        .get(input.tmdb_path)
+        .copied()
+        .unwrap_or_else(Uuid::now_v7);
+    let placeholder = images
+        .variants
+        .get(&iid)
+        .and_then(|existing| existing.placeholder.clone());
+    let variant = Variant {
+        iid,
+        variant: input.imz.image_variant(),
+        tmdb_path: input.tmdb_path.to_string(),
+        media_id: input.media_id,
+        media_type: input.media_type,
+        width: input.width,
+        iso_lang: input.lang.to_string(),
+        vote_avg: input.v_avg,
+        vote_cnt: input.v_cnt,
+        is_primary: input.is_primary,
+        placeholder,
+    };
+    let original = variant.original();
+    images.by_path.insert(variant.tmdb_path.clone(), iid);
+    images.variants.insert(iid, variant);
+    original
+}
+
+#[async_trait]
+impl ImageRepository for InMemoryImages {
+    async fn cleanup_orphaned_images(&self) -> Result<u32> {
+        Ok(self.write(|images| {
+            let before = images.cached.len();
+            let Images {
+                variants, cached, ..
+            } = images;
+            cached.retain(|(iid, _), _| variants.contains_key(iid));
+            (before - cached.len()) as u32
+        }))
+    }
+
+    async fn list_cached_image_refs(&self) -> Result<Vec<CachedImageRef>> {
+        Ok(self.read(|images| {
+            images
+                .cached
+                .values()
+                .map(|cached| CachedImageRef {
+                    cache_key: cached.record.cache_key.clone(),
+                    integrity: cached.record.integrity.clone(),
+                })
+                .collect()
+        }))
+    }
+
+    async fn list_theme_color_candidates(
+        &self,
+        media_type: ImageMediaType,
+        _after: Option<Uuid>,
+        _limit: i64,
+    ) -> Result<Vec<ThemeColorCandidate>> {
+        match media_type {
+            ImageMediaType::Movie | ImageMediaType::Series => Ok(Vec::new()),
+            other => Err(MediaError::InvalidMedia(format!(
+                "Theme colors are only stored for movies and series; got {:?}",
+                other
+            ))),
+        }
+    }
+
+    async fn update_media_theme_color(
+        &self,
+        media_type: ImageMediaType,
+        _media_id: Uuid,
+        _theme_color: &str,
+    ) -> Result<bool> {
+        self.list_theme_color_candidates(media_type, None, 0)
+            .await
+            .map(|_| false)
+    }
+
+    async fn image_placeholder(&self, iid: Uuid) -> Result<Option<Vec<u8>>> {
+        Ok(self.read(|images| {
+            images
+                .variants
+                .get(&iid)
+                .and_then(|variant| variant.placeholder.clone())
+        }))
+    }
+
+    async fn store_image_placeholder(
+        &self,
+        iid: Uuid,
+        placeholder: &[u8],
+    ) -> Result<bool> {
+        Ok(self.write(|images| match images.variants.get_mut(&iid) {
+            Some(variant) if variant.placeholder.is_none() => {
+                variant.placeholder = Some(placeholder.to_vec());
+                true
+            }
+            _ => false,
+        }))
+    }
+
+    async fn delete_cached_images_for_media(
+        &self,
+        media_id: Uuid,
+    ) -> Result<Vec<String>> {
+        Ok(self.write(|images| {
+            let mut cache_keys = Vec::new();
+            let Images {
+                variants, cached, ..
+            } = images;
+            cached.retain(|(iid, _), row| {
+                let owned = variants
+                    .get(iid)
+                    .is_some_and(|variant| variant.media_id == media_id);
+                if owned {
+                    cache_keys.push(row.record.cache_key.clone());
+                }
+                !owned
+            });
+            for variant in variants.values_mut() {
+                if variant.media_id == media_id {
+                    variant.placeholder = None;
+                }
+            }
+            cache_keys
+        }))
+    }
+
+    async fn lookup_original_image<'a>(
+        &self,
+        ctx: &'a ImgDbLookup,
+    ) -> Result<Option<OriginalImage>> {
+        if let Some(iid) = ctx.iid {
+            return self.lookup_variant_by_iid(iid).await;
+        }
+        if let Some(tmdb_path) = ctx.tmdb_path {
+            return self.lookup_variant_by_path(tmdb_path).await;
+        }
+        if let (Some(media_id), Some(media_type)) =
+            (ctx.media_id, ctx.media_type)
+        {
+            return Ok(self.read(|images| {
+                images
+                    .variants_of(media_id, media_type, ctx.imz.image_variant())
+                    .first()
+                    .map(|variant| variant.original())
+            }));
+        }
+        Ok(None)
+    }
+
+    async fn lookup_variants_for_media(
+        &self,
+        media_id: Uuid,
+        media_type: ImageMediaType,
+        imz: ImageSize,
+    ) -> Result<Vec<OriginalImage>> {
+        Ok(self.read(|images| {
+            images
+                .variants_of(media_id, media_type, imz.image_variant())
+                .into_iter()
+                .map(Variant::original)
+                .collect()
+        }))
+    }
+
+    async fn list_media_images(
+        &self,
+        media_id: Uuid,
+    ) -> Result<Vec<OriginalImage>> {
+        Ok(self.read(|images| {
+            let mut found: Vec<&Variant> = images
+                .variants
+                .values()
+                .filter(|v| v.media_id == media_id)
+                .collect();
+            found.sort_by(|a, b| {
+                variant_order(a.variant)
+                    .cmp(&variant_order(b.variant))
+                    .then_with(|| Variant::rank(a, b))
+            });
+            found.into_iter().map(Variant::original).collect()
+        }))
+    }
+
+    async fn set_primary_image(
+        &self,
+        media_id: Uuid,
+        iid: Uuid,
+    ) -> Result<OriginalImage> {
+        self.write(|images| {
+            let chosen = images
+                .variants
+                .get(&iid)
+                .filter(|v| v.media_id == media_id)
+                .map(|v| (v.media_type, v.variant))
+                .ok_or_else(|| {
+                    MediaError::NotFound(format!(
+                        "Image {iid} is not linked to media {media_id}"
+                    ))
+                })?;
+            for variant in images.variants.values_mut() {
+                if variant.media_id == media_id
+                    && (variant.media_type, variant.variant) == chosen
+                {
+                    variant.is_primary = variant.iid == iid;
+                }
+            }
+            Ok(images.variants[&iid].original())
+        })
+    }
+
+    async fn lookup_variant_by_iid(
+        &self,
+        iid: Uuid,
+    ) -> Result<Option<OriginalImage>> {
+        Ok(
+            self.read(|images| {
+                images.variants.get(&iid).map(Variant::original)
+            }),
+        )
+    }
+
+    async fn lookup_variant_by_path(
+        &self,
+        tmdb_path: &str,
+    ) -> Result<Option<OriginalImage>> {
+        Ok(self.read(|images| {
+            images
+                .by_path
+                .get(tmdb_path)
+                .and_then(|iid| images.variants.get(iid))
+                .map(Variant::original)
+        }))
+    }
+
+    async fn lookup_cached_image(
+        &self,
+        iid: Uuid,
+        imz: ImageSize,
+    ) -> Result<Option<ImageRecord>> {
+        if imz.is_original() {
+            self.lookup_original_cached_image(iid).await
+        } else if imz.is_resized() {
+            self.lookup_resized_cached_image(iid, imz.width_unchecked() as i16)
+                .await
+        } else {
+            let width = imz.width_unchecked();
+            Ok(self.read(|images| {
+                images.latest_cached(iid, |cached| {
+                    cached.record.dimensions.0 == width
+                })
+            }))
+        }
+    }
+
+    async fn lookup_original_cached_image(
+        &self,
+        iid: Uuid,
+    ) -> Result<Option<ImageRecord>> {
+        Ok(self.read(|images| {
+            images.latest_cached(iid, |cached| {
+                cached.size_variant == SqlxImageSizeVariant::Original
+            })
+        }))
+    }
+
+    async fn lookup_resized_cached_image(
+        &self,
+        iid: Uuid,
+        width: i16,
+    ) -> Result<Option<ImageRecord>> {
+        Ok(self.read(|images| {
+            images
+                .cached
+                .get(&(iid, width))
+                .filter(|cached| {
+                    cached.size_variant == SqlxImageSizeVariant::Resized
+                })
+                .map(|cached| cached.record.clone())
+        }))
+    }
+
+    async fn lookup_images<'a>(
+        &self,
+        _ctx: &'a [ImgDbLookup],
+    ) -> Result<Vec<ImageRecord>> {
+        Ok(Vec::new())
+    }
+
+    async fn upsert_image<'a>(&self, ctx: &'a ImgInput) -> Result<ImageRecord> {
+        let (width, height) = match ctx.decoded_dimensions {
+            Some(dims) => dims.as_u32_tuple(),
+            None if ctx.imz.has_width() => ctx.imz.dimensions_unchecked(),
+            None => {
+                return Err(MediaError::Internal(
+                    "Provided ImageSize must have a valid width (or decoded_dimensions must be provided)"
+                        .to_string(),
+                ));
+            }
+        };
+        ImageDimensions::try_from((width, height)).map_err(|err| {
+            MediaError::InvalidMedia(format!(
+                "Invalid image dimensions {width}x{height}: {err:?}"
+            ))
+        })?;
+        let key_width = i16::try_from(width).map_err(|_| {
+            MediaError::Internal(format!(
+                "Image width out of range for i16: {width}"
+            ))
+        })?;
+        i16::try_from(height).map_err(|_| {
+            MediaError::Internal(format!(
+                "Image height out of range for i16: {height}"
+            ))
+        })?;
+
+        let size_variant = ctx.imz.sqlx_image_size_variant();
+        Ok(self.write(|images| {
+            let now = Utc::now();
+            let created_at = images
+                .cached
+                .get(&(ctx.iid, key_width))
+                .map_or(now, |existing| existing.record.created_at);
+            let record = ImageRecord {
+                iid: ctx.iid,
+                imz: cached_size(size_variant, width, ctx.imz.image_variant()),
+                theme_color: ctx.theme_color.unwrap_or_default().to_string(),
+                dimensions: (width, height),
+                cache_key: ctx.cache_key.to_string(),
+                integrity: ctx.integrity.to_string(),
+                byte_len: ctx.byte_len,
+                created_at,
+                modified_at: now,
+            };
+            images.cached.insert(
+                (ctx.iid, key_width),
+                Cached {
+                    record: record.clone(),
+                    size_variant,
+                },
+            );
+            record
+        }))
+    }
+
+    async fn upsert_variant<'a>(
+        &self,
+        ctx: &'a VarInput,
+    ) -> Result<OriginalImage> {
+        Ok(self.write(|images| upsert_variant(images, ctx)))
+    }
+
+    async fn upsert_variants<'a>(
+        &self,
+        variants: &'a [VarInput],
+    ) -> Result<Vec<OriginalImage>> {
+        Ok(self.write(|images| {
+            // Apply every input first so duplicate paths resolve to the
+            // last one, as in the Postgres bulk upsert.
+            for input in variants {
+                upsert_variant(images, input);
+            }
+            variants
+                .iter()
+                .map(|input| {
+                    images.variants[&images.by_path[input.tmdb_path]].original()
+                })
+                .collect()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poster<'a>(
+        media_id: Uuid,
+        tmdb_path: &'a str,
+        v_avg: f32,
+        is_primary: bool,
+    ) -> VarInput<'a> {
+        VarInput {
+            media_id,
+            media_type: ImageMediaType::Movie,
+            tmdb_path,
+            imz: ImageSize::poster(),
+            width: 2000,
+            height: 3000,
+            lang: "en",
+            v_avg,
+            v_cnt: 10,
+            is_primary,
+        }
+    }
+
+    fn cached<'a>(iid: Uuid, width: u32, cache_key: &'a str) -> ImgInput<'a> {
+        ImgInput {
+            iid,
+            media_id: None,
+            media_type: None,
+            tmdb_path: None,
+            imz: ImageSize::custom(width, ImageVariant::Poster),
+            decoded_dimensions: Some(
+                ImageDimensions::try_from((width, width * 3 / 2)).unwrap(),
+            ),
+            theme_color: None,
+            cache_key,
+            integrity: "sha256-test",
+            byte_len: 1024,
+        }
+    }
+
+    #[tokio::test]
+    async fn variant_upserts_keep_the_path_id_and_lookups_follow() {
+        let store = InMemoryImages::new();
+        let media_id = Uuid::now_v7();
+
+        let first = store
+            .upsert_variant(&poster(media_id, "/a.jpg", 5.0, false))
+            .await
+            .unwrap();
+        let refreshed = store
+            .upsert_variant(&poster(media_id, "/a.jpg", 6.0, false))
+            .await
+            .unwrap();
+        assert_eq!(refreshed.iid, first.iid);
+        assert_eq!(refreshed.vote_avg, 6.0);
+
+        let primary = store
+            .upsert_variant(&poster(media_id, "/b.jpg", 1.0, true))
+            .await
+            .unwrap();
+
+        let by_iid = store.lookup_variant_by_iid(first.iid).await.unwrap();
+        assert_eq!(by_iid.map(|image| image.vote_avg), Some(6.0));
+        let by_path = store.lookup_variant_by_path("/a.jpg").await.unwrap();
+        assert_eq!(by_path.map(|image| image.iid), Some(first.iid));
+        assert!(
+            store
+                .lookup_variant_by_path("/missing.jpg")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let ranked: Vec<Uuid> = store
+            .lookup_variants_for_media(
+                media_id,
+                ImageMediaType::Movie,
+                ImageSize::poster(),
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|image| image.iid)
+            .collect();
+        assert_eq!(ranked, vec![primary.iid, first.iid]);
+
+        let lookup = ImgDbLookup {
+            imz: ImageSize::poster(),
+            iid: None,
+            media_id: Some(media_id),
+            media_type: Some(ImageMediaType::Movie),
+            tmdb_path: None,
+            lang: None,
+        };
+        let best = store.lookup_original_image(&lookup).await.unwrap();
+        assert_eq!(best.map(|image| image.iid), Some(primary.iid));
+    }
+
+    #[tokio::test]
+    async fn cached_sizes_upsert_in_place_and_look_up_by_width() {
+        let store = InMemoryImages::new();
+        let variant = store
+            .upsert_variant(&poster(Uuid::now_v7(), "/a.jpg", 5.0, true))
+            .await
+            .unwrap();
+
+        let first = store
+            .upsert_image(&cached(variant.iid, 300, "poster-300-v1"))
+            .await
+            .unwrap();
+        let refreshed = store
+            .upsert_image(&cached(variant.iid, 300, "poster-300-v2"))
+            .await
+            .unwrap();
+        assert_eq!(refreshed.created_at, first.created_at);
+        assert_eq!(store.list_cached_image_refs().await.unwrap().len(), 1);
+
+        let found = store
+            .lookup_resized_cached_image(variant.iid, 300)
+            .await
+            .unwrap();
+        assert_eq!(
+            found.map(|record| record.cache_key),
+            Some("poster-300-v2".to_string())
+        );
+        assert!(
+            store
+                .lookup_resized_cached_image(variant.iid, 500)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn deletes_drop_cached_sizes_and_placeholders() {
+        let store = InMemoryImages::new();
+        let media_id = Uuid::now_v7();
+        let kept_media = Uuid::now_v7();
+        let variant = store
+            .upsert_variant(&poster(media_id, "/a.jpg", 5.0, true))
+            .await
+            .unwrap();
+        let kept = store
+            .upsert_variant(&poster(kept_media, "/b.jpg", 5.0, true))
+            .await
+            .unwrap();
+        store
+            .upsert_image(&cached(variant.iid, 300, "a-300"))
+            .await
+            .unwrap();
+        store
+            .upsert_image(&cached(kept.iid, 300, "b-300"))
+            .await
+            .unwrap();
+        assert!(
+            store
+                .store_image_placeholder(variant.iid, b"blur")
+                .await
+                .unwrap()
+        );
+
+        let removed = store
+            .delete_cached_images_for_media(media_id)
+            .await
+            .unwrap();
+        assert_eq!(removed, vec!["a-300".to_string()]);
+        assert!(
+            store
+                .lookup_resized_cached_image(variant.iid, 300)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            store
+                .image_placeholder(variant.iid)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            store
+                .lookup_resized_cached_image(kept.iid, 300)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        // Cached sizes whose variant is gone are orphans.
+        store
+            .upsert_image(&cached(Uuid::now_v7(), 300, "orphan-300"))
+            .await
+            .unwrap();
+        assert_eq!(store.cleanup_orphaned_images().await.unwrap(), 1);
+        assert_eq!(store.list_cached_image_refs().await.unwrap().len(), 1);
+    }
+}
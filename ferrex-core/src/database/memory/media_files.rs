@@ -0,0 +1,539 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    path::PathBuf,
+    sync::{PoisonError, RwLock},
+};
+
+use async_trait::async_trait;
+use ferrex_model::MediaID;
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        repository_ports::media_files::{
+            MediaFileDigest, MediaFileFilter, MediaFileSort,
+            MediaFileSortField, MediaFilesReadPort, MediaFilesWritePort, Page,
+            SortDirection, UpsertOutcome,
+        },
+        traits::MediaStats,
+    },
+    error::Result,
+    types::{files::MediaFile, files::MediaFileMetadata, ids::LibraryId},
+};
+
+#[derive(Debug, Default)]
+struct Files {
+    by_id: HashMap<Uuid, MediaFile>,
+    by_path: HashMap<PathBuf, Uuid>,
+    digests: HashMap<Uuid, MediaFileDigest>,
+}
+
+impl Files {
+    fn remove(&mut self, id: Uuid) -> Option<MediaFile> {
+        let file = self.by_id.remove(&id)?;
+        self.by_path.remove(&file.path);
+        self.digests.remove(&id);
+        Some(file)
+    }
+}
+
+/// `media_files` in process memory. Paths are unique, as in Postgres.
+#[derive(Debug, Default)]
+pub struct InMemoryMediaFiles {
+    files: RwLock<Files>,
+}
+
+impl InMemoryMediaFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&Files) -> T) -> T {
+        f(&self.files.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut Files) -> T) -> T {
+        f(&mut self.files.write().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn matching<'a>(
+        files: &'a Files,
+        filter: &'a MediaFileFilter,
+    ) -> impl Iterator<Item = &'a MediaFile> {
+        files.by_id.values().filter(|file| matches(filter, file))
+    }
+}
+
+fn matches(filter: &MediaFileFilter, file: &MediaFile) -> bool {
+    if filter.library_id.is_some_and(|id| id != file.library_id) {
+        return false;
+    }
+    if let Some(prefix) = &filter.path_prefix
+        && !file.path.to_string_lossy().starts_with(prefix.as_str())
+    {
+        return false;
+    }
+    if !filter.extension_in.is_empty() {
+        let extension = file
+            .filename
+            .rsplit('.')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if !filter.extension_in.iter().any(|wanted| {
+            wanted
+                .trim_start_matches('.')
+                .eq_ignore_ascii_case(&extension)
+        }) {
+            return false;
+        }
+    }
+    filter.min_size.is_none_or(|min| file.size >= min)
+        && filter.max_size.is_none_or(|max| file.size <= max)
+        && filter
+            .discovered_after
+            .is_none_or(|after| file.discovered_at >= after)
+        && filter
+            .discovered_before
+            .is_none_or(|before| file.discovered_at <= before)
+        && filter
+            .created_after
+            .is_none_or(|after| file.created_at >= after)
+        && filter
+            .created_before
+            .is_none_or(|before| file.created_at <= before)
+}
+
+fn compare(sort: MediaFileSort, a: &MediaFile, b: &MediaFile) -> Ordering {
+    let ordering = match sort.field {
+        MediaFileSortField::DiscoveredAt => {
+            a.discovered_at.cmp(&b.discovered_at)
+        }
+        MediaFileSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+        MediaFileSortField::FileSize => a.size.cmp(&b.size),
+        MediaFileSortField::Filename => {
+            a.filename.to_lowercase().cmp(&b.filename.to_lowercase())
+        }
+    };
+    let ordering = match sort.direction {
+        SortDirection::Ascending => ordering,
+        SortDirection::Descending => ordering.reverse(),
+    };
+    // Ties fall back to the id so paging is stable.
+    ordering.then_with(|| a.id.cmp(&b.id))
+}
+
+/// Same grouping as Postgres: `parsed_info.media_type`, else `unknown`.
+fn stats_type(file: &MediaFile) -> String {
+    file.media_file_metadata
+        .as_ref()
+        .and_then(|metadata| metadata.parsed_info.as_ref())
+        .and_then(|parsed| serde_json::to_value(parsed).ok())
+        .and_then(|parsed| {
+            parsed.get("media_type")?.as_str().map(str::to_string)
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[async_trait]
+impl MediaFilesReadPort for InMemoryMediaFiles {
+    async fn get_by_id(&self, id: &Uuid) -> Result<Option<MediaFile>> {
+        Ok(self.read(|files| files.by_id.get(id).cloned()))
+    }
+
+    async fn get_by_media_id(
+        &self,
+        media_id: &MediaID,
+    ) -> Result<Option<MediaFile>> {
+        Ok(self.read(|files| {
+            files
+                .by_id
+                .values()
+                .filter(|file| file.media_id.as_uuid() == media_id.as_uuid())
+                .min_by(|a, b| {
+                    b.discovered_at
+                        .cmp(&a.discovered_at)
+                        .then_with(|| a.id.cmp(&b.id))
+                })
+                .cloned()
+        }))
+    }
+
+    async fn get_by_path(&self, path: &str) -> Result<Option<MediaFile>> {
+        Ok(self.read(|files| {
+            files
+                .by_path
+                .get(&PathBuf::from(path))
+                .and_then(|id| files.by_id.get(id))
+                .cloned()
+        }))
+    }
+
+    async fn exists_by_path(&self, path: &str) -> Result<bool> {
+        Ok(self.read(|files| files.by_path.contains_key(&PathBuf::from(path))))
+    }
+
+    async fn list(
+        &self,
+        filter: MediaFileFilter,
+        sort: MediaFileSort,
+        page: Page,
+    ) -> Result<Vec<MediaFile>> {
+        Ok(self.read(|files| {
+            let mut listed: Vec<&MediaFile> =
+                Self::matching(files, &filter).collect();
+            listed.sort_by(|a, b| compare(sort, a, b));
+            listed
+                .into_iter()
+                .skip(page.offset as usize)
+                .take(page.limit as usize)
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn stats(&self, filter: MediaFileFilter) -> Result<MediaStats> {
+        Ok(self.read(|files| {
+            let mut stats = MediaStats {
+                total_files: 0,
+                total_size: 0,
+                by_type: HashMap::new(),
+            };
+            for file in Self::matching(files, &filter) {
+                stats.total_files += 1;
+                stats.total_size += file.size;
+                *stats.by_type.entry(stats_type(file)).or_default() += 1;
+            }
+            stats
+        }))
+    }
+
+    async fn digests(
+        &self,
+        file_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, MediaFileDigest>> {
+        Ok(self.read(|files| {
+            file_ids
+                .iter()
+                .filter_map(|id| {
+                    files.digests.get(id).map(|digest| (*id, digest.clone()))
+                })
+                .collect()
+        }))
+    }
+}
+
+#[async_trait]
+impl MediaFilesWritePort for InMemoryMediaFiles {
+    async fn upsert(&self, file: MediaFile) -> Result<UpsertOutcome> {
+        Ok(self.write(|files| upsert(files, file)))
+    }
+
+    async fn upsert_batch(
+        &self,
+        batch: Vec<MediaFile>,
+    ) -> Result<Vec<UpsertOutcome>> {
+        Ok(self.write(|files| {
+            batch.into_iter().map(|file| upsert(files, file)).collect()
+        }))
+    }
+
+    async fn delete_by_id(&self, id: Uuid) -> Result<()> {
+        self.write(|files| files.remove(id));
+        Ok(())
+    }
+
+    async fn delete_by_path(
+        &self,
+        library_id: LibraryId,
+        path: &str,
+    ) -> Result<()> {
+        self.write(|files| {
+            let id = files.by_path.get(&PathBuf::from(path)).copied();
+            if let Some(id) = id
+                && files.by_id[&id].library_id == library_id
+            {
+                files.remove(id);
+            }
+        });
+        Ok(())
+    }
+
+    async fn delete_by_path_prefixes(
+        &self,
+        library_id: LibraryId,
+        prefixes: Vec<String>,
+    ) -> Result<u64> {
+        Ok(self.write(|files| {
+            let doomed: Vec<Uuid> = files
+                .by_id
+                .values()
+                .filter(|file| file.library_id == library_id)
+                .filter(|file| {
+                    let path = file.path.to_string_lossy();
+                    prefixes.iter().any(|root| {
+                        path == root.as_str()
+                            || path
+                                .strip_prefix(root.as_str())
+                                .is_some_and(|rest| rest.starts_with('/'))
+                    })
+                })
+                .map(|file| file.id)
+                .collect();
+            for id in &doomed {
+                files.remove(*id);
+            }
+            doomed.len() as u64
+        }))
+    }
+
+    async fn update_technical_metadata(
+        &self,
+        id: Uuid,
+        metadata: &MediaFileMetadata,
+    ) -> Result<()> {
+        self.write(|files| {
+            if let Some(file) = files.by_id.get_mut(&id) {
+                file.media_file_metadata = Some(metadata.clone());
+            }
+        });
+        Ok(())
+    }
+
+    async fn record_digest(&self, digest: MediaFileDigest) -> Result<()> {
+        self.write(|files| {
+            // Digests go with their file, as the foreign key has it.
+            if files.by_id.contains_key(&digest.file_id) {
+                files.digests.insert(digest.file_id, digest);
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Insert `file`, or refresh the file already stored at its path while
+/// keeping that file's id, media and library.
+fn upsert(files: &mut Files, file: MediaFile) -> UpsertOutcome {
+    if let Some(&id) = files.by_path.get(&file.path) {
+        let existing = files.by_id.get_mut(&id).expect("path index in sync");
+        existing.filename = file.filename;
+        existing.size = file.size;
+        existing.media_file_metadata = file.media_file_metadata;
+        return UpsertOutcome { id, created: false };
+    }
+    let id = file.id;
+    files.by_path.insert(file.path.clone(), id);
+    files.by_id.insert(id, file);
+    UpsertOutcome { id, created: true }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Utc;
+    use ferrex_model::MovieID;
+
+    use super::*;
+
+    fn file(library_id: LibraryId, path: &str, size: u64) -> MediaFile {
+        MediaFile {
+            id: Uuid::now_v7(),
+            media_id: MediaID::Movie(MovieID(Uuid::now_v7())),
+            path: PathBuf::from(path),
+            filename: path.rsplit('/').next().unwrap().to_string(),
+            size,
+            discovered_at: Utc::now(),
+            created_at: Utc::now(),
+            media_file_metadata: None,
+            library_id,
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_upserts_of_one_path_keep_a_single_row() {
+        let store = Arc::new(InMemoryMediaFiles::new());
+        let library_id = LibraryId::new();
+
+        let writers = (0..16u64).map(|size| {
+            let store = Arc::clone(&store);
+            tokio::spawn(async move {
+                store
+                    .upsert(file(library_id, "/media/Heat (1995).mkv", size))
+                    .await
+                    .unwrap()
+            })
+        });
+        let mut created = 0;
+        let mut ids = Vec::new();
+        for writer in writers {
+            let outcome = writer.await.unwrap();
+            created += usize::from(outcome.created);
+            ids.push(outcome.id);
+        }
+        assert_eq!(created, 1);
+        ids.dedup();
+        assert_eq!(ids.len(), 1);
+
+        let stats = store.stats(MediaFileFilter::default()).await.unwrap();
+        assert_eq!(stats.total_files, 1);
+    }
+
+    #[tokio::test]
+    async fn prefix_deletes_stop_at_path_boundaries() {
+        let store = InMemoryMediaFiles::new();
+        let library_id = LibraryId::new();
+        store
+            .upsert_batch(vec![
+                file(library_id, "/media/Show/S01E01.mkv", 1),
+                file(library_id, "/media/Show/S01E02.mkv", 2),
+                file(library_id, "/media/Show 2/S01E01.mkv", 3),
+            ])
+            .await
+            .unwrap();
+
+        let removed = store
+            .delete_by_path_prefixes(library_id, vec!["/media/Show".into()])
+            .await
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert!(
+            store
+                .exists_by_path("/media/Show 2/S01E01.mkv")
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn upserts_refresh_the_stored_file_and_lookups_follow() {
+        let store = InMemoryMediaFiles::new();
+        let library_id = LibraryId::new();
+        let first = file(library_id, "/media/Heat (1995).mkv", 10);
+        let media_id = first.media_id;
+        let created = store.upsert(first.clone()).await.unwrap();
+        assert!(created.created);
+        assert_eq!(created.id, first.id);
+
+        // A rescan finds the same path under a fresh id and a new size.
+        let refreshed = store
+            .upsert(file(library_id, "/media/Heat (1995).mkv", 20))
+            .await
+            .unwrap();
+        assert_eq!(refreshed.id, first.id);
+        assert!(!refreshed.created);
+
+        let by_id = store.get_by_id(&first.id).await.unwrap().unwrap();
+        assert_eq!(by_id.size, 20);
+        assert_eq!(by_id.media_id, media_id);
+        let by_path = store
+            .get_by_path("/media/Heat (1995).mkv")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_path.id, first.id);
+        let by_media = store.get_by_media_id(&media_id).await.unwrap().unwrap();
+        assert_eq!(by_media.id, first.id);
+        assert!(
+            store
+                .get_by_path("/media/Ronin.mkv")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn deletes_drop_the_file_its_path_and_its_digest() {
+        let store = InMemoryMediaFiles::new();
+        let library_id = LibraryId::new();
+        let heat = file(library_id, "/media/Heat.mkv", 10);
+        let ronin = file(library_id, "/media/Ronin.mkv", 10);
+        store
+            .upsert_batch(vec![heat.clone(), ronin.clone()])
+            .await
+            .unwrap();
+        let digest = |file_id| MediaFileDigest {
+            file_id,
+            sample_bytes: 4096,
+            file_size: 10,
+            modified_at: Utc::now(),
+            digest: vec![1, 2, 3],
+        };
+        store.record_digest(digest(heat.id)).await.unwrap();
+        // No row, no digest, as the foreign key has it.
+        store.record_digest(digest(Uuid::now_v7())).await.unwrap();
+        assert_eq!(store.digests(&[heat.id, ronin.id]).await.unwrap().len(), 1);
+
+        store.delete_by_id(heat.id).await.unwrap();
+        assert!(store.get_by_id(&heat.id).await.unwrap().is_none());
+        assert!(!store.exists_by_path("/media/Heat.mkv").await.unwrap());
+        assert!(store.digests(&[heat.id]).await.unwrap().is_empty());
+
+        // Paths are only deleted within their own library.
+        store
+            .delete_by_path(LibraryId::new(), "/media/Ronin.mkv")
+            .await
+            .unwrap();
+        assert!(store.exists_by_path("/media/Ronin.mkv").await.unwrap());
+        store
+            .delete_by_path(library_id, "/media/Ronin.mkv")
+            .await
+            .unwrap();
+        assert!(!store.exists_by_path("/media/Ronin.mkv").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn listings_filter_sort_and_page() {
+        let store = InMemoryMediaFiles::new();
+        let library_id = LibraryId::new();
+        store
+            .upsert_batch(vec![
+                file(library_id, "/media/b.mkv", 30),
+                file(library_id, "/media/a.MKV", 10),
+                file(library_id, "/media/c.mp4", 20),
+                file(LibraryId::new(), "/other/d.mkv", 40),
+            ])
+            .await
+            .unwrap();
+
+        let filter = MediaFileFilter {
+            library_id: Some(library_id),
+            extension_in: vec!["mkv".into()],
+            ..MediaFileFilter::default()
+        };
+        let listed = store
+            .list(
+                filter.clone(),
+                MediaFileSort::ascending(MediaFileSortField::Filename),
+                Page::default(),
+            )
+            .await
+            .unwrap();
+        let names: Vec<_> =
+            listed.iter().map(|f| f.filename.as_str()).collect();
+        assert_eq!(names, ["a.MKV", "b.mkv"]);
+
+        let largest = store
+            .list(
+                MediaFileFilter::default(),
+                MediaFileSort {
+                    field: MediaFileSortField::FileSize,
+                    direction: SortDirection::Descending,
+                },
+                Page {
+                    limit: 2,
+                    offset: 1,
+                },
+            )
+            .await
+            .unwrap();
+        let sizes: Vec<_> = largest.iter().map(|f| f.size).collect();
+        assert_eq!(sizes, [30, 20]);
+
+        let stats = store.stats(filter).await.unwrap();
+        assert_eq!(stats.total_files, 2);
+        assert_eq!(stats.total_size, 40);
+    }
+}
@@ -0,0 +1,51 @@
+//! In-memory repository adapters for tests that should not need Postgres.
+//!
+//! Each store keeps its rows behind a single lock, so every port method is
+//! atomic and the stores can be shared freely between tasks. Semantics follow
+//! the Postgres adapters (upsert conflicts, sort tie-breaks, the 95%
+//! completion threshold, stale-progress rejection) so logic exercised here
+//! behaves the same against the real database.
+//!
+//! | Port | Coverage |
+//! | --- | --- |
+//! | [`MediaFilesReadPort`], [`MediaFilesWritePort`] | Implemented, except `move_by_path`, which keeps the trait default like Postgres. Library ids are not checked against a library table. |
+//! | [`ImageRepository`] | Implemented, except theme colors (no candidates, updates are no-ops), `lookup_images`, which finds nothing as in Postgres, and `set_primary_image`, which swaps the primary flags but has no metadata rows to repoint. |
+//! | [`WatchStatusRepository`] | Implemented. Episode identities and season totals come from episodes registered with [`InMemoryWatchStatus::add_episode`]; watch-state export and import resolve movies registered with [`InMemoryWatchStatus::add_movie`]. |
+//!
+//! Query, library, user and the remaining ports have no in-memory adapter;
+//! [`QueryRepository`](crate::database::repository_ports::query::QueryRepository)
+//! compiles filters to SQL and is only meaningful against Postgres. Supply
+//! those ports to the [`AppUnitOfWorkBuilder`] yourself after
+//! [`AppUnitOfWorkBuilder::with_in_memory`].
+//!
+//! [`MediaFilesReadPort`]: crate::database::repository_ports::media_files::MediaFilesReadPort
+//! [`MediaFilesWritePort`]: crate::database::repository_ports::media_files::MediaFilesWritePort
+//! [`ImageRepository`]: crate::database::repository_ports::images::ImageRepository
+//! [`WatchStatusRepository`]: crate::database::repository_ports::watch_status::WatchStatusRepository
+//! [`AppUnitOfWorkBuilder`]: crate::application::unit_of_work::AppUnitOfWorkBuilder
+//! [`AppUnitOfWorkBuilder::with_in_memory`]: crate::application::unit_of_work::AppUnitOfWorkBuilder::with_in_memory
+
+pub mod images;
+pub mod media_files;
+pub mod watch_status;
+
+use std::sync::Arc;
+
+pub use images::InMemoryImages;
+pub use media_files::InMemoryMediaFiles;
+pub use watch_status::{CatalogEpisode, CatalogMovie, InMemoryWatchStatus};
+
+/// The in-memory stores, shareable between a unit of work and the test
+/// that seeds and inspects them.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryDatabase {
+    pub media_files: Arc<InMemoryMediaFiles>,
+    pub images: Arc<InMemoryImages>,
+    pub watch_status: Arc<InMemoryWatchStatus>,
+}
+
+impl InMemoryDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
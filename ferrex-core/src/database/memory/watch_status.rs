@@ -0,0 +1,1088 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::{PoisonError, RwLock},
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use ferrex_model::{SeasonID, SeriesID, VideoMediaType};
+use uuid::Uuid;
+
+use crate::{
+    database::repository_ports::watch_status::WatchStatusRepository,
+    domain::watch::{
+        BulkWatchUpdate, EpisodeKey, EpisodeStatus, InProgressItem,
+        LatestProgress, MarkEpisodeWatchedRequest, NextEpisode, NextReason,
+        PortableMediaKey, SeasonKey, SeasonWatchStatus, SeriesWatchStatus,
+        UpdateProgressRequest, UserWatchState, WatchAdvancePolicy, WatchScope,
+        WatchStateEntry, WatchStateImportReport,
+    },
+    error::{MediaError, Result},
+};
+
+/// An episode file known to the store.
+///
+/// Stands in for `episode_references` and the `episode_metadata` rows that
+/// season and series totals are counted from; register them with
+/// [`InMemoryWatchStatus::add_episode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CatalogEpisode {
+    /// The episode reference id that progress is reported against.
+    pub media_id: Uuid,
+    pub series_id: SeriesID,
+    pub season_id: SeasonID,
+    pub key: EpisodeKey,
+}
+
+/// A movie file known to the store.
+///
+/// Stands in for `movie_references` joined to its `media_files` row, which
+/// watch-state exports resolve through; register them with
+/// [`InMemoryWatchStatus::add_movie`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogMovie {
+    /// The movie reference id that progress is reported against.
+    pub media_id: Uuid,
+    /// `0` for movies that were never matched.
+    pub tmdb_id: u64,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Progress {
+    position: f32,
+    duration: f32,
+    last_watched: i64,
+    reported_at: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EpisodeState {
+    position: f32,
+    duration: f32,
+    last_watched: i64,
+    is_completed: bool,
+    last_media_uuid: Option<Uuid>,
+}
+
+impl EpisodeState {
+    fn completed(now: i64, last_media_uuid: Option<Uuid>) -> Self {
+        Self {
+            position: 1.0,
+            duration: 1.0,
+            last_watched: now,
+            is_completed: true,
+            last_media_uuid,
+        }
+    }
+
+    fn status(&self) -> EpisodeStatus {
+        let (pos, dur) = (self.position, self.duration);
+        if self.is_completed || (dur > 0.0 && pos / dur > 0.95) {
+            EpisodeStatus::Completed
+        } else if pos > 0.0 && dur > 0.0 {
+            EpisodeStatus::InProgress {
+                progress: (pos / dur).clamp(0.0, 1.0),
+            }
+        } else {
+            EpisodeStatus::Unwatched
+        }
+    }
+
+    /// Mark completed, keeping the end position and newest watch time.
+    fn complete(&mut self, now: i64) {
+        self.position = self.duration;
+        self.is_completed = true;
+        self.last_watched = self.last_watched.max(now);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Watch {
+    /// `user_watch_progress`, keyed by user and media.
+    progress: HashMap<(Uuid, Uuid), Progress>,
+    /// `user_completed_media`: completion time per user and media.
+    completed: HashMap<(Uuid, Uuid), i64>,
+    /// `user_episode_state`, keyed by user and episode identity.
+    episodes: HashMap<(Uuid, EpisodeKey), EpisodeState>,
+    /// Episode files in discovery order.
+    catalog: Vec<CatalogEpisode>,
+    /// Movie files in discovery order.
+    movies: Vec<CatalogMovie>,
+}
+
+impl Watch {
+    fn identity_of(&self, media_id: Uuid) -> Option<EpisodeKey> {
+        self.catalog
+            .iter()
+            .find(|episode| episode.media_id == media_id)
+            .map(|episode| episode.key)
+    }
+
+    /// First discovered file of an episode.
+    fn playable(&self, key: &EpisodeKey) -> Option<Uuid> {
+        self.catalog
+            .iter()
+            .find(|episode| episode.key == *key)
+            .map(|episode| episode.media_id)
+    }
+
+    /// Distinct episode identities of a series, in season/episode order.
+    fn identities(&self, tmdb_series_id: u64) -> BTreeSet<(u16, u16)> {
+        self.catalog
+            .iter()
+            .filter(|episode| episode.key.tmdb_series_id == tmdb_series_id)
+            .map(|episode| {
+                (episode.key.season_number, episode.key.episode_number)
+            })
+            .collect()
+    }
+
+    fn status(&self, user_id: Uuid, key: EpisodeKey) -> EpisodeStatus {
+        self.episodes
+            .get(&(user_id, key))
+            .map_or(EpisodeStatus::Unwatched, EpisodeState::status)
+    }
+
+    fn season_status(
+        &self,
+        user_id: Uuid,
+        tmdb_series_id: u64,
+        season_number: u16,
+    ) -> SeasonWatchStatus {
+        let mut season = SeasonWatchStatus {
+            key: SeasonKey {
+                tmdb_series_id,
+                season_number,
+            },
+            total: 0,
+            watched: 0,
+            in_progress: 0,
+            is_completed: false,
+            episodes: HashMap::new(),
+        };
+        for (_, episode_number) in self
+            .identities(tmdb_series_id)
+            .into_iter()
+            .filter(|(season, _)| *season == season_number)
+        {
+            let status = self.status(
+                user_id,
+                EpisodeKey {
+                    tmdb_series_id,
+                    season_number,
+                    episode_number,
+                },
+            );
+            season.total += 1;
+            match status {
+                EpisodeStatus::Completed => season.watched += 1,
+                EpisodeStatus::InProgress { .. } => season.in_progress += 1,
+                EpisodeStatus::Unwatched => {}
+            }
+            season.episodes.insert(episode_number, status);
+        }
+        season.is_completed =
+            season.watched == season.total && season.total > 0;
+        season
+    }
+
+    fn complete_file(&mut self, user_id: Uuid, media_id: Uuid, now: i64) {
+        self.progress.remove(&(user_id, media_id));
+        self.completed.entry((user_id, media_id)).or_insert(now);
+    }
+
+    /// Stable identity of a movie or episode file.
+    fn portable_key(&self, media_id: Uuid) -> Option<PortableMediaKey> {
+        if let Some(movie) =
+            self.movies.iter().find(|movie| movie.media_id == media_id)
+        {
+            return Some(match movie.tmdb_id {
+                0 => PortableMediaKey::File {
+                    path: movie.path.clone(),
+                },
+                tmdb_id => PortableMediaKey::Movie { tmdb_id },
+            });
+        }
+        self.identity_of(media_id).map(PortableMediaKey::from)
+    }
+
+    /// Files an exported item resolves to, in discovery order.
+    fn files_of(&self, item: &PortableMediaKey) -> Vec<Uuid> {
+        match item {
+            PortableMediaKey::Movie { tmdb_id } => self
+                .movies
+                .iter()
+                .filter(|movie| movie.tmdb_id != 0 && movie.tmdb_id == *tmdb_id)
+                .map(|movie| movie.media_id)
+                .collect(),
+            PortableMediaKey::File { path } => self
+                .movies
+                .iter()
+                .filter(|movie| movie.path == *path)
+                .map(|movie| movie.media_id)
+                .collect(),
+            PortableMediaKey::Episode {
+                tmdb_series_id,
+                season_number,
+                episode_number,
+            } => self
+                .catalog
+                .iter()
+                .filter(|episode| {
+                    episode.key
+                        == EpisodeKey {
+                            tmdb_series_id: *tmdb_series_id,
+                            season_number: *season_number,
+                            episode_number: *episode_number,
+                        }
+                })
+                .map(|episode| episode.media_id)
+                .collect(),
+        }
+    }
+
+    /// Apply an imported entry to one file under the max-position rule.
+    fn import_file(
+        &mut self,
+        user_id: Uuid,
+        media_id: Uuid,
+        entry: &WatchStateEntry,
+    ) -> bool {
+        let item = (user_id, media_id);
+        let position = self.progress.get(&item).map(|p| p.position);
+        if !entry.supersedes(self.completed.contains_key(&item), position) {
+            return false;
+        }
+        if entry.is_completed() {
+            self.complete_file(user_id, media_id, entry.last_watched);
+        } else {
+            let previous = self.progress.get(&item).copied();
+            self.progress.insert(
+                item,
+                Progress {
+                    position: entry.position,
+                    duration: entry.duration,
+                    last_watched: previous.map_or(entry.last_watched, |p| {
+                        p.last_watched.max(entry.last_watched)
+                    }),
+                    reported_at: previous.map_or(entry.last_watched, |p| {
+                        p.reported_at.max(entry.last_watched)
+                    }),
+                },
+            );
+        }
+        true
+    }
+
+    /// Apply an imported entry to an episode's identity state.
+    fn import_episode(
+        &mut self,
+        user_id: Uuid,
+        key: EpisodeKey,
+        media_id: Uuid,
+        entry: &WatchStateEntry,
+    ) -> bool {
+        let current = self.episodes.get(&(user_id, key)).copied();
+        if !entry.supersedes(
+            current.is_some_and(|state| state.is_completed),
+            current.map(|state| state.position),
+        ) {
+            return false;
+        }
+        self.episodes.insert(
+            (user_id, key),
+            EpisodeState {
+                position: entry.position,
+                duration: entry.duration,
+                last_watched: current.map_or(entry.last_watched, |state| {
+                    state.last_watched.max(entry.last_watched)
+                }),
+                is_completed: entry.is_completed(),
+                last_media_uuid: current
+                    .and_then(|state| state.last_media_uuid)
+                    .or(Some(media_id)),
+            },
+        );
+        true
+    }
+}
+
+/// Watch progress, completions and identity-based episode state in process
+/// memory.
+///
+/// Every method is implemented and applies its changes under one lock, the
+/// way the Postgres adapter uses one transaction. What Postgres reads from
+/// the media tables (episode identities, season totals, series and season
+/// ids) comes from the episodes registered with [`Self::add_episode`].
+#[derive(Debug, Default)]
+pub struct InMemoryWatchStatus {
+    watch: RwLock<Watch>,
+}
+
+impl InMemoryWatchStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an episode file. Files of the same episode are resolved in
+    /// the order they were added.
+    pub fn add_episode(&self, episode: CatalogEpisode) {
+        self.write(|watch| watch.catalog.push(episode));
+    }
+
+    /// Register a movie file for watch-state export and import.
+    pub fn add_movie(&self, movie: CatalogMovie) {
+        self.write(|watch| watch.movies.push(movie));
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&Watch) -> T) -> T {
+        f(&self.watch.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut Watch) -> T) -> T {
+        f(&mut self.watch.write().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+#[async_trait]
+impl WatchStatusRepository for InMemoryWatchStatus {
+    async fn update_watch_progress(
+        &self,
+        user_id: Uuid,
+        progress: &UpdateProgressRequest,
+    ) -> Result<()> {
+        let now = Utc::now().timestamp_millis();
+        let reported_at = progress.effective_reported_at(now);
+        let item = (user_id, progress.media_id);
+
+        self.write(|watch| {
+            let latest = match watch.progress.get(&item) {
+                Some(current) => Some(LatestProgress::InProgress {
+                    position: current.position,
+                    duration: current.duration,
+                    reported_at: current.reported_at,
+                }),
+                None => watch.completed.get(&item).map(|&reported_at| {
+                    LatestProgress::Completed { reported_at }
+                }),
+            };
+            if let Some(latest) = latest
+                && let Err(stale) = progress.check_against(&latest, now)
+            {
+                return Err(MediaError::Conflict(stale.to_string()));
+            }
+
+            if progress.reset {
+                watch.completed.remove(&item);
+            }
+            watch.progress.insert(
+                item,
+                Progress {
+                    position: progress.position,
+                    duration: progress.duration,
+                    last_watched: now,
+                    reported_at,
+                },
+            );
+
+            let is_completed = progress.position / progress.duration > 0.95;
+            if is_completed {
+                watch.completed.insert(item, reported_at);
+                watch.progress.remove(&item);
+            }
+
+            if matches!(progress.media_type, VideoMediaType::Episode)
+                && let Some(key) = progress
+                    .episode
+                    .or_else(|| watch.identity_of(progress.media_id))
+            {
+                watch.episodes.insert(
+                    (user_id, key),
+                    EpisodeState {
+                        position: progress.position,
+                        duration: progress.duration,
+                        last_watched: now,
+                        is_completed,
+                        last_media_uuid: Some(
+                            progress
+                                .last_media_uuid
+                                .unwrap_or(progress.media_id),
+                        ),
+                    },
+                );
+            }
+            Ok(())
+        })
+    }
+
+    async fn get_user_watch_state(
+        &self,
+        user_id: Uuid,
+    ) -> Result<UserWatchState> {
+        Ok(self.read(|watch| UserWatchState {
+            in_progress: watch
+                .progress
+                .iter()
+                .filter(|((user, _), _)| *user == user_id)
+                .map(|(&(_, media_id), progress)| {
+                    (
+                        media_id,
+                        InProgressItem {
+                            media_id,
+                            position: progress.position,
+                            duration: progress.duration,
+                            last_watched: progress.last_watched,
+                        },
+                    )
+                })
+                .collect(),
+            completed: watch
+                .completed
+                .keys()
+                .filter(|(user, _)| *user == user_id)
+                .map(|&(_, media_id)| media_id)
+                .collect::<HashSet<_>>(),
+        }))
+    }
+
+    async fn get_continue_watching(
+        &self,
+        user_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<InProgressItem>> {
+        let mut items: Vec<InProgressItem> = self
+            .get_user_watch_state(user_id)
+            .await?
+            .in_progress
+            .into_values()
+            .collect();
+        items.sort_by(|a, b| b.last_watched.cmp(&a.last_watched));
+        items.truncate(limit);
+        Ok(items)
+    }
+
+    async fn clear_watch_progress(
+        &self,
+        user_id: Uuid,
+        media_id: &Uuid,
+    ) -> Result<()> {
+        self.write(|watch| {
+            watch.progress.remove(&(user_id, *media_id));
+            watch.completed.remove(&(user_id, *media_id));
+        });
+        Ok(())
+    }
+
+    async fn is_media_completed(
+        &self,
+        user_id: Uuid,
+        media_id: &Uuid,
+    ) -> Result<bool> {
+        Ok(self
+            .read(|watch| watch.completed.contains_key(&(user_id, *media_id))))
+    }
+
+    async fn upsert_episode_identity_progress(
+        &self,
+        user_id: Uuid,
+        key: &EpisodeKey,
+        position: f32,
+        duration: f32,
+        last_media_uuid: Option<Uuid>,
+    ) -> Result<()> {
+        let now = Utc::now().timestamp_millis();
+        self.write(|watch| {
+            let previous = watch
+                .episodes
+                .get(&(user_id, *key))
+                .and_then(|state| state.last_media_uuid);
+            watch.episodes.insert(
+                (user_id, *key),
+                EpisodeState {
+                    position,
+                    duration,
+                    last_watched: now,
+                    is_completed: position / duration > 0.95,
+                    last_media_uuid: last_media_uuid.or(previous),
+                },
+            );
+        });
+        Ok(())
+    }
+
+    async fn get_series_watch_status(
+        &self,
+        user_id: Uuid,
+        tmdb_series_id: u64,
+    ) -> Result<SeriesWatchStatus> {
+        let season_numbers: BTreeSet<u16> = self.read(|watch| {
+            watch
+                .identities(tmdb_series_id)
+                .into_iter()
+                .map(|(season, _)| season)
+                .collect()
+        });
+        let seasons: HashMap<u16, SeasonWatchStatus> = self.read(|watch| {
+            season_numbers
+                .iter()
+                .map(|&season| {
+                    (
+                        season,
+                        watch.season_status(user_id, tmdb_series_id, season),
+                    )
+                })
+                .collect()
+        });
+        let next_episode =
+            self.get_next_episode(user_id, tmdb_series_id).await?;
+
+        Ok(SeriesWatchStatus {
+            tmdb_series_id,
+            total_episodes: seasons.values().map(|season| season.total).sum(),
+            watched: seasons.values().map(|season| season.watched).sum(),
+            in_progress: seasons
+                .values()
+                .map(|season| season.in_progress)
+                .sum(),
+            seasons,
+            next_episode,
+        })
+    }
+
+    async fn get_season_watch_status(
+        &self,
+        user_id: Uuid,
+        tmdb_series_id: u64,
+        season_number: u16,
+    ) -> Result<SeasonWatchStatus> {
+        Ok(self.read(|watch| {
+            watch.season_status(user_id, tmdb_series_id, season_number)
+        }))
+    }
+
+    async fn get_next_episode(
+        &self,
+        user_id: Uuid,
+        tmdb_series_id: u64,
+    ) -> Result<Option<NextEpisode>> {
+        Ok(self.read(|watch| {
+            let resume = watch
+                .episodes
+                .iter()
+                .filter(|((user, key), state)| {
+                    *user == user_id
+                        && key.tmdb_series_id == tmdb_series_id
+                        && matches!(
+                            state.status(),
+                            EpisodeStatus::InProgress { .. }
+                        )
+                })
+                .max_by_key(|(_, state)| state.last_watched);
+            if let Some(((_, key), state)) = resume {
+                return Some(NextEpisode {
+                    key: *key,
+                    playable_media_id: state
+                        .last_media_uuid
+                        .or_else(|| watch.playable(key)),
+                    reason: NextReason::ResumeInProgress,
+                });
+            }
+
+            watch
+                .identities(tmdb_series_id)
+                .into_iter()
+                .map(|(season_number, episode_number)| EpisodeKey {
+                    tmdb_series_id,
+                    season_number,
+                    episode_number,
+                })
+                .find(|key| {
+                    !watch
+                        .episodes
+                        .get(&(user_id, *key))
+                        .is_some_and(|state| state.is_completed)
+                })
+                .map(|key| NextEpisode {
+                    key,
+                    playable_media_id: watch.playable(&key),
+                    reason: NextReason::FirstUnwatched,
+                })
+        }))
+    }
+
+    async fn mark_episode_completed(
+        &self,
+        user_id: Uuid,
+        key: &EpisodeKey,
+    ) -> Result<()> {
+        let now = Utc::now().timestamp_millis();
+        self.write(|watch| {
+            watch
+                .episodes
+                .entry((user_id, *key))
+                .and_modify(|state| {
+                    state.is_completed = true;
+                    state.last_watched = state.last_watched.max(now);
+                })
+                .or_insert_with(|| EpisodeState::completed(now, None));
+        });
+        Ok(())
+    }
+
+    async fn clear_episode_state(
+        &self,
+        user_id: Uuid,
+        key: &EpisodeKey,
+    ) -> Result<()> {
+        self.write(|watch| watch.episodes.remove(&(user_id, *key)));
+        Ok(())
+    }
+
+    async fn mark_episode_watched(
+        &self,
+        user_id: Uuid,
+        key: &EpisodeKey,
+        request: MarkEpisodeWatchedRequest,
+    ) -> Result<u32> {
+        let now = Utc::now().timestamp_millis();
+        let cascade = request.policy == WatchAdvancePolicy::Cascade;
+        let series_wide = cascade && request.series_wide;
+        let reaches = |other: &EpisodeKey| {
+            other.tmdb_series_id == key.tmdb_series_id
+                && ((other.season_number == key.season_number
+                    && (other.episode_number == key.episode_number
+                        || (cascade
+                            && other.episode_number < key.episode_number)))
+                    || (series_wide
+                        && other.season_number > 0
+                        && other.season_number < key.season_number))
+        };
+
+        Ok(self.write(|watch| {
+            let files: Vec<CatalogEpisode> = watch
+                .catalog
+                .iter()
+                .filter(|episode| reaches(&episode.key))
+                .copied()
+                .collect();
+
+            let mut identities: HashMap<EpisodeKey, Option<Uuid>> =
+                HashMap::new();
+            for file in &files {
+                watch.complete_file(user_id, file.media_id, now);
+                identities.entry(file.key).or_insert(Some(file.media_id));
+            }
+            identities.entry(*key).or_insert(None);
+
+            let mut completed = 0;
+            for (identity, media_id) in identities {
+                match watch.episodes.get_mut(&(user_id, identity)) {
+                    Some(state) if state.is_completed => continue,
+                    Some(state) => {
+                        state.complete(now);
+                        state.last_media_uuid =
+                            state.last_media_uuid.or(media_id);
+                    }
+                    None => {
+                        watch.episodes.insert(
+                            (user_id, identity),
+                            EpisodeState::completed(now, media_id),
+                        );
+                    }
+                }
+                completed += 1;
+            }
+            completed
+        }))
+    }
+
+    async fn set_scope_watched(
+        &self,
+        user_id: Uuid,
+        scope: WatchScope,
+        watched: bool,
+    ) -> Result<BulkWatchUpdate> {
+        let now = Utc::now().timestamp_millis();
+        self.write(|watch| {
+            let files: Vec<CatalogEpisode> = watch
+                .catalog
+                .iter()
+                .filter(|episode| match scope {
+                    WatchScope::Series(series_id) => {
+                        episode.series_id == series_id
+                    }
+                    WatchScope::Season(season_id) => {
+                        episode.season_id == season_id
+                    }
+                })
+                .copied()
+                .collect();
+            // Scopes are resolved through their episodes, so a series or
+            // season without any is reported missing.
+            let Some(first) = files.first().copied() else {
+                return Err(MediaError::NotFound(match scope {
+                    WatchScope::Series(series_id) => {
+                        format!("Series {series_id} not found")
+                    }
+                    WatchScope::Season(season_id) => {
+                        format!("Season {season_id} not found")
+                    }
+                }));
+            };
+
+            for file in &files {
+                let item = (user_id, file.media_id);
+                watch.progress.remove(&item);
+                if watched {
+                    watch.completed.insert(item, now);
+                    watch
+                        .episodes
+                        .entry((user_id, file.key))
+                        .and_modify(|state| state.complete(now))
+                        .or_insert_with(|| {
+                            EpisodeState::completed(now, Some(file.media_id))
+                        });
+                } else {
+                    watch.completed.remove(&item);
+                    watch.episodes.remove(&(user_id, file.key));
+                }
+            }
+
+            Ok(BulkWatchUpdate {
+                series_id: first.series_id,
+                season_id: match scope {
+                    WatchScope::Series(_) => None,
+                    WatchScope::Season(season_id) => Some(season_id),
+                },
+                tmdb_series_id: first.key.tmdb_series_id,
+                watched,
+                episodes: files.len() as u32,
+            })
+        })
+    }
+
+    async fn export_watch_state(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<WatchStateEntry>> {
+        Ok(self.read(|watch| {
+            let progress = watch
+                .progress
+                .iter()
+                .filter(|((user, _), _)| *user == user_id)
+                .filter_map(|(&(_, media_id), progress)| {
+                    Some(WatchStateEntry {
+                        item: watch.portable_key(media_id)?,
+                        position: progress.position,
+                        duration: progress.duration,
+                        completed: false,
+                        last_watched: progress.last_watched,
+                    })
+                });
+            let completed = watch
+                .completed
+                .iter()
+                .filter(|((user, _), _)| *user == user_id)
+                .filter_map(|(&(_, media_id), &completed_at)| {
+                    Some(WatchStateEntry {
+                        item: watch.portable_key(media_id)?,
+                        position: 1.0,
+                        duration: 1.0,
+                        completed: true,
+                        last_watched: completed_at,
+                    })
+                });
+            let episodes = watch
+                .episodes
+                .iter()
+                .filter(|((user, _), _)| *user == user_id)
+                .map(|(&(_, key), state)| WatchStateEntry {
+                    item: key.into(),
+                    position: state.position,
+                    duration: state.duration,
+                    completed: state.is_completed,
+                    last_watched: state.last_watched,
+                });
+            progress.chain(completed).chain(episodes).collect()
+        }))
+    }
+
+    async fn import_watch_state(
+        &self,
+        user_id: Uuid,
+        entries: &[WatchStateEntry],
+    ) -> Result<WatchStateImportReport> {
+        Ok(self.write(|watch| {
+            let mut report = WatchStateImportReport::default();
+            for entry in entries {
+                if let Err(reason) = entry.validate() {
+                    report.skip(&entry.item, reason);
+                    continue;
+                }
+                let files = watch.files_of(&entry.item);
+                let Some(&first) = files.first() else {
+                    report.skip(&entry.item, "not found in any library");
+                    continue;
+                };
+
+                let mut changed = false;
+                for media_id in files {
+                    changed |= watch.import_file(user_id, media_id, entry);
+                }
+                if let PortableMediaKey::Episode {
+                    tmdb_series_id,
+                    season_number,
+                    episode_number,
+                } = entry.item
+                {
+                    let key = EpisodeKey {
+                        tmdb_series_id,
+                        season_number,
+                        episode_number,
+                    };
+                    changed |= watch.import_episode(user_id, key, first, entry);
+                }
+                report.record(changed);
+            }
+            report
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::watch::WatchStateExport;
+
+    const SHOW: u64 = 4242;
+
+    fn key(season_number: u16, episode_number: u16) -> EpisodeKey {
+        EpisodeKey {
+            tmdb_series_id: SHOW,
+            season_number,
+            episode_number,
+        }
+    }
+
+    fn seeded() -> (InMemoryWatchStatus, SeriesID, [CatalogEpisode; 3]) {
+        let store = InMemoryWatchStatus::new();
+        let series_id = SeriesID(Uuid::now_v7());
+        let seasons = [SeasonID(Uuid::now_v7()), SeasonID(Uuid::now_v7())];
+        let episodes = [(0, 1, 1), (0, 1, 2), (1, 2, 1)].map(
+            |(season, season_number, episode_number)| CatalogEpisode {
+                media_id: Uuid::now_v7(),
+                series_id,
+                season_id: seasons[season],
+                key: key(season_number, episode_number),
+            },
+        );
+        for episode in episodes {
+            store.add_episode(episode);
+        }
+        (store, series_id, episodes)
+    }
+
+    fn progress(
+        episode: &CatalogEpisode,
+        position: f32,
+        reported_at: i64,
+    ) -> UpdateProgressRequest {
+        UpdateProgressRequest {
+            media_id: episode.media_id,
+            media_type: VideoMediaType::Episode,
+            position,
+            duration: 1200.0,
+            episode: None,
+            last_media_uuid: None,
+            reported_at: Some(reported_at),
+            reset: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn progress_reports_are_ordered_and_complete_at_95_percent() {
+        let (store, _, [first, ..]) = seeded();
+        let user = Uuid::now_v7();
+        let base = Utc::now().timestamp_millis() - 60_000;
+
+        store
+            .update_watch_progress(user, &progress(&first, 600.0, base + 2_000))
+            .await
+            .unwrap();
+        let late = store
+            .update_watch_progress(user, &progress(&first, 300.0, base + 1_000))
+            .await;
+        assert!(matches!(late, Err(MediaError::Conflict(_))));
+
+        let next = store.get_next_episode(user, SHOW).await.unwrap().unwrap();
+        assert_eq!(next.reason, NextReason::ResumeInProgress);
+        assert_eq!(next.playable_media_id, Some(first.media_id));
+
+        store
+            .update_watch_progress(
+                user,
+                &progress(&first, 1190.0, base + 3_000),
+            )
+            .await
+            .unwrap();
+        assert!(
+            store
+                .is_media_completed(user, &first.media_id)
+                .await
+                .unwrap()
+        );
+        let state = store.get_user_watch_state(user).await.unwrap();
+        assert!(state.in_progress.is_empty());
+        let next = store.get_next_episode(user, SHOW).await.unwrap().unwrap();
+        assert_eq!(
+            (next.key, next.reason),
+            (key(1, 2), NextReason::FirstUnwatched)
+        );
+    }
+
+    #[tokio::test]
+    async fn cascades_and_bulk_changes_match_the_postgres_adapter() {
+        let (store, series_id, [first, _, later]) = seeded();
+        let user = Uuid::now_v7();
+        let cascade = MarkEpisodeWatchedRequest {
+            policy: WatchAdvancePolicy::Cascade,
+            series_wide: false,
+        };
+
+        assert_eq!(
+            store
+                .mark_episode_watched(user, &later.key, cascade)
+                .await
+                .unwrap(),
+            1
+        );
+        let season_one =
+            store.get_season_watch_status(user, SHOW, 1).await.unwrap();
+        assert_eq!(season_one.watched, 0);
+
+        let series_wide = MarkEpisodeWatchedRequest {
+            series_wide: true,
+            ..cascade
+        };
+        assert_eq!(
+            store
+                .mark_episode_watched(user, &later.key, series_wide)
+                .await
+                .unwrap(),
+            2
+        );
+        let series = store.get_series_watch_status(user, SHOW).await.unwrap();
+        assert_eq!((series.total_episodes, series.watched), (3, 3));
+        assert!(
+            store
+                .is_media_completed(user, &first.media_id)
+                .await
+                .unwrap()
+        );
+
+        let cleared = store
+            .set_scope_watched(user, WatchScope::Season(later.season_id), false)
+            .await
+            .unwrap();
+        assert_eq!((cleared.series_id, cleared.episodes), (series_id, 1));
+        assert_eq!(
+            store
+                .get_user_watch_state(user)
+                .await
+                .unwrap()
+                .completed
+                .len(),
+            2
+        );
+        let missing = store
+            .set_scope_watched(
+                user,
+                WatchScope::Series(SeriesID(Uuid::now_v7())),
+                true,
+            )
+            .await;
+        assert!(matches!(missing, Err(MediaError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn watch_state_round_trips_onto_new_media_ids() {
+        let (source, _, [first, _, later]) = seeded();
+        let movie = CatalogMovie {
+            media_id: Uuid::now_v7(),
+            tmdb_id: 603,
+            path: "/media/The Matrix (1999).mkv".into(),
+        };
+        source.add_movie(movie.clone());
+        let user = Uuid::now_v7();
+        let base = Utc::now().timestamp_millis() - 60_000;
+        source
+            .update_watch_progress(user, &progress(&first, 600.0, base))
+            .await
+            .unwrap();
+        source
+            .mark_episode_watched(user, &later.key, Default::default())
+            .await
+            .unwrap();
+        source
+            .update_watch_progress(
+                user,
+                &UpdateProgressRequest {
+                    media_id: movie.media_id,
+                    media_type: VideoMediaType::Movie,
+                    ..progress(&first, 900.0, base)
+                },
+            )
+            .await
+            .unwrap();
+        let export = WatchStateExport::new(
+            source.export_watch_state(user).await.unwrap(),
+            base,
+        );
+
+        // A rescan gives everything new ids; the second episode is gone and
+        // the movie already has further progress.
+        let (target, _, [first, _, later]) = seeded();
+        let copy = CatalogMovie {
+            media_id: Uuid::now_v7(),
+            ..movie
+        };
+        target.add_movie(copy.clone());
+        target
+            .update_watch_progress(
+                user,
+                &UpdateProgressRequest {
+                    media_id: copy.media_id,
+                    media_type: VideoMediaType::Movie,
+                    ..progress(&first, 1000.0, base)
+                },
+            )
+            .await
+            .unwrap();
+        let mut entries = export.entries;
+        entries.push(WatchStateEntry {
+            item: PortableMediaKey::Movie { tmdb_id: 11 },
+            position: 10.0,
+            duration: 100.0,
+            completed: false,
+            last_watched: base,
+        });
+
+        let report = target.import_watch_state(user, &entries).await.unwrap();
+        assert_eq!(
+            (report.imported, report.kept_existing, report.skipped.len()),
+            (2, 1, 1)
+        );
+        assert!(
+            target
+                .is_media_completed(user, &later.media_id)
+                .await
+                .unwrap()
+        );
+        let state = target.get_user_watch_state(user).await.unwrap();
+        assert_eq!(state.in_progress[&first.media_id].position, 600.0);
+        assert_eq!(state.in_progress[&copy.media_id].position, 1000.0);
+        let next = target.get_next_episode(user, SHOW).await.unwrap().unwrap();
+        assert_eq!(next.reason, NextReason::ResumeInProgress);
+    }
+}
@@ -1,4 +1,7 @@
 pub mod context;
+#[cfg(feature = "test-utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+pub mod memory;
 pub mod postgres;
 pub mod postgres_ext;
 pub mod repositories;
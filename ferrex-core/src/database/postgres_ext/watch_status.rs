@@ -1,7 +1,10 @@
 use crate::{
     database::PostgresDatabase,
     database::repository_ports::watch_status::WatchStatusRepository,
-    domain::watch::{InProgressItem, UpdateProgressRequest, UserWatchState},
+    domain::watch::{
+        BulkWatchUpdate, InProgressItem, MarkEpisodeWatchedRequest,
+        UpdateProgressRequest, UserWatchState, WatchScope,
+    },
     error::Result,
     types::watch::{
         EpisodeKey, NextEpisode, SeasonWatchStatus, SeriesWatchStatus,
@@ -120,6 +123,17 @@ impl PostgresDatabase {
             .await
     }
 
+    pub async fn mark_episode_watched(
+        &self,
+        user_id: Uuid,
+        key: &EpisodeKey,
+        request: MarkEpisodeWatchedRequest,
+    ) -> Result<u32> {
+        self.watch_status_repository()
+            .mark_episode_watched(user_id, key, request)
+            .await
+    }
+
     pub async fn clear_episode_state(
         &self,
         user_id: Uuid,
@@ -129,4 +143,15 @@ impl PostgresDatabase {
             .clear_episode_state(user_id, key)
             .await
     }
+
+    pub async fn set_scope_watched(
+        &self,
+        user_id: Uuid,
+        scope: WatchScope,
+        watched: bool,
+    ) -> Result<BulkWatchUpdate> {
+        self.watch_status_repository()
+            .set_scope_watched(user_id, scope, watched)
+            .await
+    }
 }
@@ -0,0 +1,545 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::api::types::{
+    Collection, CollectionItem, CollectionItemWatchState,
+    UpdateCollectionRequest,
+};
+use crate::database::repository_ports::collections::CollectionsRepository;
+use crate::error::{MediaError, Result};
+use crate::types::ids::{EpisodeID, MovieID};
+use crate::types::media_id::MediaID;
+
+const COLLECTION_COLUMNS: &str = r#"
+    c.id, c.name, c.description, c.created_at, c.updated_at,
+    (SELECT count(*) FROM user_collection_items i
+     WHERE i.collection_id = c.id) AS item_count
+"#;
+
+#[derive(Clone)]
+pub struct PostgresCollectionsRepository {
+    pool: PgPool,
+}
+
+impl PostgresCollectionsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// Lock the user's collection for the rest of `tx`.
+    async fn lock_collection(
+        tx: &mut Transaction<'_, Postgres>,
+        user_id: Uuid,
+        collection_id: Uuid,
+    ) -> Result<()> {
+        let found: Option<(Uuid,)> = sqlx::query_as(
+            r#"
+            SELECT id FROM user_collections
+            WHERE id = $1 AND user_id = $2
+            FOR UPDATE
+            "#,
+        )
+        .bind(collection_id)
+        .bind(user_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Failed to load collection: {}", e))
+        })?;
+
+        found.map(|_| ()).ok_or_else(|| {
+            MediaError::NotFound(format!(
+                "Collection {} not found",
+                collection_id
+            ))
+        })
+    }
+}
+
+impl fmt::Debug for PostgresCollectionsRepository {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostgresCollectionsRepository")
+            .field("pool_size", &self.pool.size())
+            .field("idle_connections", &self.pool.num_idle())
+            .finish()
+    }
+}
+
+/// Split members into the parallel movie/episode id arrays stored per row.
+fn member_columns(
+    media: &[MediaID],
+) -> Result<(Vec<Option<Uuid>>, Vec<Option<Uuid>>)> {
+    let mut movies = Vec::with_capacity(media.len());
+    let mut episodes = Vec::with_capacity(media.len());
+    for id in media {
+        match id {
+            MediaID::Movie(movie) => {
+                movies.push(Some(movie.to_uuid()));
+                episodes.push(None);
+            }
+            MediaID::Episode(episode) => {
+                movies.push(None);
+                episodes.push(Some(episode.to_uuid()));
+            }
+            other => {
+                return Err(MediaError::InvalidMedia(format!(
+                    "Only movies and episodes can be collected, got {}",
+                    other
+                )));
+            }
+        }
+    }
+    Ok((movies, episodes))
+}
+
+fn map_write_error(context: &str, err: sqlx::Error) -> MediaError {
+    match err.as_database_error() {
+        Some(db_err) if db_err.is_unique_violation() => MediaError::Conflict(
+            "A collection with this name already exists".to_string(),
+        ),
+        Some(db_err) if db_err.is_foreign_key_violation() => {
+            MediaError::NotFound("Media not found".to_string())
+        }
+        Some(db_err) if db_err.is_check_violation() => {
+            MediaError::InvalidMedia("Collection name is required".to_string())
+        }
+        _ => MediaError::Internal(format!("{}: {}", context, err)),
+    }
+}
+
+#[async_trait]
+impl CollectionsRepository for PostgresCollectionsRepository {
+    async fn list_collections(&self, user_id: Uuid) -> Result<Vec<Collection>> {
+        let sql = format!(
+            r#"
+            SELECT {COLLECTION_COLUMNS}
+            FROM user_collections c
+            WHERE c.user_id = $1
+            ORDER BY lower(c.name), c.id
+            "#
+        );
+        let rows = sqlx::query_as::<_, CollectionRow>(&sql)
+            .bind(user_id)
+            .fetch_all(self.pool())
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!(
+                    "Failed to list collections: {}",
+                    e
+                ))
+            })?;
+
+        Ok(rows.into_iter().map(Collection::from).collect())
+    }
+
+    async fn get_collection(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+    ) -> Result<Option<Collection>> {
+        let sql = format!(
+            r#"
+            SELECT {COLLECTION_COLUMNS}
+            FROM user_collections c
+            WHERE c.id = $1 AND c.user_id = $2
+            "#
+        );
+        let row = sqlx::query_as::<_, CollectionRow>(&sql)
+            .bind(collection_id)
+            .bind(user_id)
+            .fetch_optional(self.pool())
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!(
+                    "Failed to load collection: {}",
+                    e
+                ))
+            })?;
+
+        Ok(row.map(Collection::from))
+    }
+
+    async fn create_collection(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<Collection> {
+        let row = sqlx::query_as::<_, CollectionRow>(
+            r#"
+            INSERT INTO user_collections (user_id, name, description)
+            VALUES ($1, btrim($2), NULLIF(btrim($3), ''))
+            RETURNING id, name, description, created_at, updated_at,
+                      0::bigint AS item_count
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(description)
+        .fetch_one(self.pool())
+        .await
+        .map_err(|e| map_write_error("Failed to create collection", e))?;
+
+        Ok(Collection::from(row))
+    }
+
+    async fn update_collection(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+        update: &UpdateCollectionRequest,
+    ) -> Result<Collection> {
+        let sql = format!(
+            r#"
+            WITH updated AS (
+                UPDATE user_collections
+                SET name = COALESCE(btrim($3), name),
+                    description = CASE
+                        WHEN $4::text IS NULL THEN description
+                        ELSE NULLIF(btrim($4), '')
+                    END,
+                    updated_at = now()
+                WHERE id = $1 AND user_id = $2
+                RETURNING *
+            )
+            SELECT {COLLECTION_COLUMNS}
+            FROM updated c
+            "#
+        );
+        let row = sqlx::query_as::<_, CollectionRow>(&sql)
+            .bind(collection_id)
+            .bind(user_id)
+            .bind(update.name.as_deref())
+            .bind(update.description.as_deref())
+            .fetch_optional(self.pool())
+            .await
+            .map_err(|e| map_write_error("Failed to update collection", e))?;
+
+        row.map(Collection::from).ok_or_else(|| {
+            MediaError::NotFound(format!(
+                "Collection {} not found",
+                collection_id
+            ))
+        })
+    }
+
+    async fn delete_collection(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+    ) -> Result<bool> {
+        let deleted = sqlx::query(
+            "DELETE FROM user_collections WHERE id = $1 AND user_id = $2",
+        )
+        .bind(collection_id)
+        .bind(user_id)
+        .execute(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Failed to delete collection: {}", e))
+        })?
+        .rows_affected();
+
+        Ok(deleted > 0)
+    }
+
+    async fn add_items(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+        media: &[MediaID],
+    ) -> Result<u64> {
+        let (movies, episodes) = member_columns(media)?;
+
+        let mut tx = self.pool().begin().await.map_err(|e| {
+            MediaError::Internal(format!("Failed to begin transaction: {}", e))
+        })?;
+        Self::lock_collection(&mut tx, user_id, collection_id).await?;
+
+        let added = sqlx::query(
+            r#"
+            INSERT INTO user_collection_items (
+                collection_id, movie_id, episode_id, "position"
+            )
+            SELECT $1, t.movie_id, t.episode_id,
+                   next.start + t.ord::integer
+            FROM UNNEST($2::uuid[], $3::uuid[])
+                 WITH ORDINALITY AS t(movie_id, episode_id, ord)
+            CROSS JOIN (
+                SELECT COALESCE(max("position"), 0) AS start
+                FROM user_collection_items
+                WHERE collection_id = $1
+            ) next
+            ORDER BY t.ord
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(collection_id)
+        .bind(&movies)
+        .bind(&episodes)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| map_write_error("Failed to add collection items", e))?
+        .rows_affected();
+
+        if added > 0 {
+            sqlx::query(
+                "UPDATE user_collections SET updated_at = now() WHERE id = $1",
+            )
+            .bind(collection_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!(
+                    "Failed to touch collection: {}",
+                    e
+                ))
+            })?;
+        }
+
+        tx.commit().await.map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to commit collection items: {}",
+                e
+            ))
+        })?;
+
+        Ok(added)
+    }
+
+    async fn remove_item(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<bool> {
+        let removed = sqlx::query(
+            r#"
+            DELETE FROM user_collection_items i
+            USING user_collections c
+            WHERE i.collection_id = c.id
+              AND c.id = $1
+              AND c.user_id = $2
+              AND (i.movie_id = $3 OR i.episode_id = $3)
+            "#,
+        )
+        .bind(collection_id)
+        .bind(user_id)
+        .bind(media_id)
+        .execute(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to remove collection item: {}",
+                e
+            ))
+        })?
+        .rows_affected();
+
+        Ok(removed > 0)
+    }
+
+    async fn reorder_items(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+        media: &[MediaID],
+    ) -> Result<()> {
+        let (movies, episodes) = member_columns(media)?;
+
+        let mut tx = self.pool().begin().await.map_err(|e| {
+            MediaError::Internal(format!("Failed to begin transaction: {}", e))
+        })?;
+        Self::lock_collection(&mut tx, user_id, collection_id).await?;
+
+        let members: Vec<(Option<Uuid>, Option<Uuid>)> = sqlx::query_as(
+            r#"
+            SELECT movie_id, episode_id
+            FROM user_collection_items
+            WHERE collection_id = $1
+            "#,
+        )
+        .bind(collection_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to load collection items: {}",
+                e
+            ))
+        })?;
+
+        let current: HashSet<_> = members.into_iter().collect();
+        let requested: HashSet<_> = movies
+            .iter()
+            .copied()
+            .zip(episodes.iter().copied())
+            .collect();
+        if requested.len() != media.len() || requested != current {
+            return Err(MediaError::Conflict(
+                "The new order must list every collection item exactly once"
+                    .to_string(),
+            ));
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE user_collection_items i
+            SET "position" = t.ord::integer
+            FROM UNNEST($2::uuid[], $3::uuid[])
+                 WITH ORDINALITY AS t(movie_id, episode_id, ord)
+            WHERE i.collection_id = $1
+              AND (i.movie_id = t.movie_id OR i.episode_id = t.episode_id)
+            "#,
+        )
+        .bind(collection_id)
+        .bind(&movies)
+        .bind(&episodes)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to reorder collection items: {}",
+                e
+            ))
+        })?;
+
+        sqlx::query(
+            "UPDATE user_collections SET updated_at = now() WHERE id = $1",
+        )
+        .bind(collection_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Failed to touch collection: {}", e))
+        })?;
+
+        tx.commit().await.map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to commit collection order: {}",
+                e
+            ))
+        })
+    }
+
+    async fn list_items(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+    ) -> Result<Vec<CollectionItem>> {
+        if self.get_collection(user_id, collection_id).await?.is_none() {
+            return Err(MediaError::NotFound(format!(
+                "Collection {} not found",
+                collection_id
+            )));
+        }
+
+        let rows = sqlx::query_as::<_, CollectionItemRow>(
+            r#"
+            SELECT i.movie_id, i.episode_id, i.added_at,
+                   COALESCE(m.title, s.title, '') AS title,
+                   e.season_number, e.episode_number,
+                   p."position" AS watch_position,
+                   p.duration AS watch_duration,
+                   (done.media_uuid IS NOT NULL) AS completed
+            FROM user_collection_items i
+            LEFT JOIN movie_references m ON m.id = i.movie_id
+            LEFT JOIN episode_references e ON e.id = i.episode_id
+            LEFT JOIN series s ON s.id = e.series_id
+            LEFT JOIN user_watch_progress p
+                ON p.user_id = $2
+               AND p.media_uuid = COALESCE(i.movie_id, i.episode_id)
+            LEFT JOIN user_completed_media done
+                ON done.user_id = $2
+               AND done.media_uuid = COALESCE(i.movie_id, i.episode_id)
+            WHERE i.collection_id = $1
+            ORDER BY i."position", i.added_at
+            "#,
+        )
+        .bind(collection_id)
+        .bind(user_id)
+        .fetch_all(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to list collection items: {}",
+                e
+            ))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(CollectionItemRow::into_item)
+            .collect())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CollectionRow {
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    item_count: i64,
+}
+
+impl From<CollectionRow> for Collection {
+    fn from(row: CollectionRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            item_count: u32::try_from(row.item_count).unwrap_or(u32::MAX),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CollectionItemRow {
+    movie_id: Option<Uuid>,
+    episode_id: Option<Uuid>,
+    added_at: DateTime<Utc>,
+    title: String,
+    season_number: Option<i16>,
+    episode_number: Option<i16>,
+    watch_position: Option<f32>,
+    watch_duration: Option<f32>,
+    completed: bool,
+}
+
+impl CollectionItemRow {
+    fn into_item(self) -> Option<CollectionItem> {
+        let media_id = match (self.movie_id, self.episode_id) {
+            (Some(id), _) => MediaID::Movie(MovieID(id)),
+            (None, Some(id)) => MediaID::Episode(EpisodeID(id)),
+            (None, None) => return None,
+        };
+        let subtitle = self
+            .season_number
+            .zip(self.episode_number)
+            .map(|(season, episode)| format!("S{season:02}E{episode:02}"));
+        Some(CollectionItem {
+            media_id,
+            title: self.title,
+            subtitle,
+            added_at: self.added_at,
+            watch: CollectionItemWatchState {
+                position: self.watch_position,
+                duration: self.watch_duration,
+                completed: self.completed,
+            },
+        })
+    }
+}
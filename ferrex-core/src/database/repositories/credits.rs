@@ -0,0 +1,352 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::types::{
+    CastCredit, CrewCredit, KEY_CREW_JOBS, MediaCredits, Person,
+    PersonAppearance, PersonAppearances,
+};
+use crate::database::repository_ports::credits::CreditsRepository;
+use crate::error::{MediaError, Result};
+use crate::types::ids::{EpisodeID, LibraryId, MovieID, SeriesID};
+use crate::types::media_id::MediaID;
+
+/// Primary profile image registered for a person, if any. Cast rows carry
+/// their own image id; crew rows fall back to this.
+const PERSON_PROFILE_IMAGE: &str = r#"
+    LEFT JOIN LATERAL (
+        SELECT tiv.id
+        FROM tmdb_image_variants tiv
+        WHERE tiv.media_id = p.id
+          AND tiv.image_variant = 'profile'::image_variant
+        ORDER BY tiv.is_primary DESC
+        LIMIT 1
+    ) profile ON true
+"#;
+
+#[derive(Clone)]
+pub struct PostgresCreditsRepository {
+    pool: PgPool,
+}
+
+impl PostgresCreditsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// The movie, series or episode with this id.
+    async fn resolve_media(&self, id: Uuid) -> Result<Option<MediaID>> {
+        let kind: Option<i32> = sqlx::query_scalar(
+            r#"
+            SELECT CASE
+                WHEN EXISTS (SELECT 1 FROM movie_references WHERE id = $1) THEN 0
+                WHEN EXISTS (SELECT 1 FROM series WHERE id = $1) THEN 1
+                WHEN EXISTS (SELECT 1 FROM episode_references WHERE id = $1) THEN 2
+            END
+            "#,
+        )
+        .bind(id)
+        .fetch_one(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Failed to look up media: {}", e))
+        })?;
+
+        Ok(kind.map(|kind| match kind {
+            0 => MediaID::Movie(MovieID(id)),
+            1 => MediaID::Series(SeriesID(id)),
+            _ => MediaID::Episode(EpisodeID(id)),
+        }))
+    }
+
+    async fn load_cast(&self, media: &MediaID) -> Result<Vec<CastCredit>> {
+        let (table, column) = match media {
+            MediaID::Movie(_) => ("movie_cast", "movie_id"),
+            MediaID::Series(_) => ("series_cast", "series_id"),
+            MediaID::Episode(_) => ("episode_cast", "episode_id"),
+            MediaID::Season(_) => return Ok(Vec::new()),
+        };
+        let guests = if matches!(media, MediaID::Episode(_)) {
+            r#"
+            UNION ALL
+            SELECT g.person_id, g.person_tmdb_id, g."character",
+                   g.order_index, true AS guest, g.profile_image_id
+            FROM episode_guest_stars g
+            WHERE g.episode_id = $1
+            "#
+        } else {
+            ""
+        };
+        let sql = format!(
+            r#"
+            SELECT p.id AS person_id, c.person_tmdb_id, p.name,
+                   c."character", c.order_index, c.guest,
+                   COALESCE(c.profile_image_id, profile.id) AS profile_image_id
+            FROM (
+                SELECT c.person_id, c.person_tmdb_id, c."character",
+                       c.order_index, false AS guest, c.profile_image_id
+                FROM {table} c
+                WHERE c.{column} = $1
+                {guests}
+            ) c
+            JOIN persons p ON p.id = c.person_id
+            {PERSON_PROFILE_IMAGE}
+            ORDER BY c.guest, c.order_index NULLS LAST, p.name
+            "#
+        );
+        let rows = sqlx::query_as::<_, CastRow>(&sql)
+            .bind(media.as_uuid())
+            .fetch_all(self.pool())
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!("Failed to load cast: {}", e))
+            })?;
+
+        Ok(rows.into_iter().map(CastCredit::from).collect())
+    }
+
+    async fn load_key_crew(&self, media: &MediaID) -> Result<Vec<CrewCredit>> {
+        let (table, column) = match media {
+            MediaID::Movie(_) => ("movie_crew", "movie_id"),
+            MediaID::Series(_) => ("series_crew", "series_id"),
+            MediaID::Episode(_) => ("episode_crew", "episode_id"),
+            MediaID::Season(_) => return Ok(Vec::new()),
+        };
+        let sql = format!(
+            r#"
+            SELECT p.id AS person_id, c.person_tmdb_id, p.name,
+                   c.department, c.job, profile.id AS profile_image_id
+            FROM {table} c
+            JOIN persons p ON p.id = c.person_id
+            {PERSON_PROFILE_IMAGE}
+            WHERE c.{column} = $1
+              AND lower(c.job) = ANY($2::text[])
+            ORDER BY array_position($2::text[], lower(c.job)), p.name
+            "#
+        );
+        let jobs: Vec<String> = KEY_CREW_JOBS
+            .iter()
+            .map(|job| job.to_ascii_lowercase())
+            .collect();
+        let rows = sqlx::query_as::<_, CrewRow>(&sql)
+            .bind(media.as_uuid())
+            .bind(&jobs)
+            .fetch_all(self.pool())
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!("Failed to load crew: {}", e))
+            })?;
+
+        Ok(rows.into_iter().map(CrewCredit::from).collect())
+    }
+}
+
+impl fmt::Debug for PostgresCreditsRepository {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostgresCreditsRepository")
+            .field("pool_size", &self.pool.size())
+            .field("idle_connections", &self.pool.num_idle())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl CreditsRepository for PostgresCreditsRepository {
+    async fn media_credits(&self, id: Uuid) -> Result<Option<MediaCredits>> {
+        let Some(media) = self.resolve_media(id).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(MediaCredits {
+            media_id: media,
+            cast: self.load_cast(&media).await?,
+            crew: self.load_key_crew(&media).await?,
+        }))
+    }
+
+    async fn person_appearances(
+        &self,
+        person_id: Uuid,
+        library_id: Option<LibraryId>,
+    ) -> Result<Option<PersonAppearances>> {
+        let person_sql = format!(
+            r#"
+            SELECT p.id, p.tmdb_id, p.name, p.known_for_department,
+                   profile.id AS profile_image_id
+            FROM persons p
+            {PERSON_PROFILE_IMAGE}
+            WHERE p.id = $1
+            "#
+        );
+        let Some(person) = sqlx::query_as::<_, PersonRow>(&person_sql)
+            .bind(person_id)
+            .fetch_optional(self.pool())
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!("Failed to load person: {}", e))
+            })?
+        else {
+            return Ok(None);
+        };
+
+        // One row per movie or series, with every role the person had on
+        // it. Kind 0 is a movie, 1 a series.
+        let rows = sqlx::query_as::<_, AppearanceRow>(
+            r#"
+            WITH credits AS (
+                SELECT movie_id AS id, 0 AS kind,
+                       "character" AS role_character, NULL::text AS job
+                FROM movie_cast WHERE person_id = $1
+                UNION ALL
+                SELECT movie_id, 0, NULL, job
+                FROM movie_crew WHERE person_id = $1
+                UNION ALL
+                SELECT series_id, 1, "character", NULL
+                FROM series_cast WHERE person_id = $1
+                UNION ALL
+                SELECT series_id, 1, NULL, job
+                FROM series_crew WHERE person_id = $1
+            )
+            SELECT c.id, c.kind,
+                   COALESCE(mr.library_id, s.library_id) AS library_id,
+                   COALESCE(mr.title, s.title) AS title,
+                   EXTRACT(YEAR FROM COALESCE(mm.release_date, sm.first_air_date))::int4 AS year,
+                   COALESCE(mm.poster_path, sm.poster_path) AS poster_path,
+                   array_remove(array_agg(DISTINCT NULLIF(c.role_character, '')), NULL) AS characters,
+                   array_remove(array_agg(DISTINCT c.job), NULL) AS jobs
+            FROM credits c
+            LEFT JOIN movie_references mr ON c.kind = 0 AND mr.id = c.id
+            LEFT JOIN movie_metadata mm ON c.kind = 0 AND mm.movie_id = c.id
+            LEFT JOIN series s ON c.kind = 1 AND s.id = c.id
+            LEFT JOIN series_metadata sm ON c.kind = 1 AND sm.series_id = c.id
+            WHERE COALESCE(mr.library_id, s.library_id) IS NOT NULL
+              AND ($2::uuid IS NULL OR COALESCE(mr.library_id, s.library_id) = $2)
+            GROUP BY c.id, c.kind, mr.library_id, s.library_id, mr.title,
+                     s.title, mm.release_date, sm.first_air_date,
+                     mm.poster_path, sm.poster_path
+            ORDER BY year DESC NULLS LAST, title, c.id
+            "#,
+        )
+        .bind(person_id)
+        .bind(library_id.map(|id| id.0))
+        .fetch_all(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to load person appearances: {}",
+                e
+            ))
+        })?;
+
+        Ok(Some(PersonAppearances {
+            person: person.into(),
+            appearances: rows.into_iter().map(PersonAppearance::from).collect(),
+        }))
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CastRow {
+    person_id: Uuid,
+    person_tmdb_id: i64,
+    name: String,
+    character: String,
+    order_index: Option<i32>,
+    guest: bool,
+    profile_image_id: Option<Uuid>,
+}
+
+impl From<CastRow> for CastCredit {
+    fn from(row: CastRow) -> Self {
+        Self {
+            person_id: row.person_id,
+            tmdb_id: row.person_tmdb_id as u64,
+            name: row.name,
+            character: row.character,
+            order: row.order_index.and_then(|o| u32::try_from(o).ok()),
+            guest: row.guest,
+            profile_image_id: row.profile_image_id,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CrewRow {
+    person_id: Uuid,
+    person_tmdb_id: i64,
+    name: String,
+    department: String,
+    job: String,
+    profile_image_id: Option<Uuid>,
+}
+
+impl From<CrewRow> for CrewCredit {
+    fn from(row: CrewRow) -> Self {
+        Self {
+            person_id: row.person_id,
+            tmdb_id: row.person_tmdb_id as u64,
+            name: row.name,
+            department: row.department,
+            job: row.job,
+            profile_image_id: row.profile_image_id,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PersonRow {
+    id: Uuid,
+    tmdb_id: i64,
+    name: String,
+    known_for_department: Option<String>,
+    profile_image_id: Option<Uuid>,
+}
+
+impl From<PersonRow> for Person {
+    fn from(row: PersonRow) -> Self {
+        Self {
+            id: row.id,
+            tmdb_id: row.tmdb_id as u64,
+            name: row.name,
+            known_for_department: row.known_for_department,
+            profile_image_id: row.profile_image_id,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct AppearanceRow {
+    id: Uuid,
+    kind: i32,
+    library_id: Uuid,
+    title: String,
+    year: Option<i32>,
+    poster_path: Option<String>,
+    characters: Vec<String>,
+    jobs: Vec<String>,
+}
+
+impl From<AppearanceRow> for PersonAppearance {
+    fn from(row: AppearanceRow) -> Self {
+        let media_id = if row.kind == 0 {
+            MediaID::Movie(MovieID(row.id))
+        } else {
+            MediaID::Series(SeriesID(row.id))
+        };
+        Self {
+            media_id,
+            library_id: LibraryId(row.library_id),
+            title: row.title,
+            year: row.year,
+            poster_path: row.poster_path,
+            characters: row.characters,
+            jobs: row.jobs,
+        }
+    }
+}
@@ -13,7 +13,8 @@ use uuid::Uuid;
 use crate::{
     database::{
         repository_ports::images::{
-            ImageRepository, ImgDbLookup, ImgInput, VarInput,
+            CachedImageRef, ImageRepository, ImgDbLookup, ImgInput,
+            ThemeColorCandidate, VarInput,
         },
         traits::{ImageRecord, OriginalImage},
     },
@@ -69,6 +70,232 @@ impl ImageRepository for PostgresImageRepository {
         Ok(res.rows_affected() as u32)
     }
 
+    async fn list_cached_image_refs(&self) -> Result<Vec<CachedImageRef>> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT cache_key, integrity FROM cached_images")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| {
+                    MediaError::Internal(format!(
+                        "Failed to list cached images: {}",
+                        e
+                    ))
+                })?;
+        Ok(rows
+            .into_iter()
+            .map(|(cache_key, integrity)| CachedImageRef {
+                cache_key,
+                integrity,
+            })
+            .collect())
+    }
+
+    async fn list_theme_color_candidates(
+        &self,
+        media_type: ImageMediaType,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<ThemeColorCandidate>> {
+        let rows = match media_type {
+            ImageMediaType::Movie => {
+                sqlx::query_as!(
+                    ThemeColorCandidateRow,
+                    r#"
+                    SELECT
+                        mr.id AS "media_id!",
+                        ci.image_id AS "poster_iid!",
+                        ci.cache_key AS "cache_key!"
+                    FROM movie_references mr
+                    JOIN movie_metadata mm ON mm.movie_id = mr.id
+                    JOIN LATERAL (
+                        SELECT c.image_id, c.cache_key
+                        FROM cached_images c
+                        WHERE c.image_id = mm.primary_poster_image_id
+                          AND c.image_variant = 'poster'::image_variant
+                        ORDER BY c.width ASC
+                        LIMIT 1
+                    ) ci ON true
+                    WHERE (mr.theme_color IS NULL OR mr.theme_color = '')
+                      AND ($1::uuid IS NULL OR mr.id > $1)
+                    ORDER BY mr.id
+                    LIMIT $2
+                    "#,
+                    after,
+                    limit
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            ImageMediaType::Series => {
+                sqlx::query_as!(
+                    ThemeColorCandidateRow,
+                    r#"
+                    SELECT
+                        s.id AS "media_id!",
+                        ci.image_id AS "poster_iid!",
+                        ci.cache_key AS "cache_key!"
+                    FROM series s
+                    JOIN series_metadata sm ON sm.series_id = s.id
+                    JOIN LATERAL (
+                        SELECT c.image_id, c.cache_key
+                        FROM cached_images c
+                        WHERE c.image_id = sm.primary_poster_image_id
+                          AND c.image_variant = 'poster'::image_variant
+                        ORDER BY c.width ASC
+                        LIMIT 1
+                    ) ci ON true
+                    WHERE (s.theme_color IS NULL OR s.theme_color = '')
+                      AND ($1::uuid IS NULL OR s.id > $1)
+                    ORDER BY s.id
+                    LIMIT $2
+                    "#,
+                    after,
+                    limit
+                )
+                .fetch_all(&self.pool)
+                .await
+            }
+            other => {
+                return Err(MediaError::InvalidMedia(format!(
+                    "Theme colors are only stored for movies and series; got {:?}",
+                    other
+                )));
+            }
+        }
+        .map_err(MediaError::Database)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ThemeColorCandidate {
+                media_id: r.media_id,
+                poster_iid: r.poster_iid,
+                cache_key: r.cache_key,
+            })
+            .collect())
+    }
+
+    async fn update_media_theme_color(
+        &self,
+        media_type: ImageMediaType,
+        media_id: Uuid,
+        theme_color: &str,
+    ) -> Result<bool> {
+        let result = match media_type {
+            ImageMediaType::Movie => {
+                sqlx::query!(
+                    r#"
+                    UPDATE movie_references
+                    SET theme_color = $2
+                    WHERE id = $1
+                      AND (theme_color IS NULL OR theme_color = '')
+                    "#,
+                    media_id,
+                    theme_color
+                )
+                .execute(&self.pool)
+                .await
+            }
+            ImageMediaType::Series => {
+                sqlx::query!(
+                    r#"
+                    UPDATE series
+                    SET theme_color = $2
+                    WHERE id = $1
+                      AND (theme_color IS NULL OR theme_color = '')
+                    "#,
+                    media_id,
+                    theme_color
+                )
+                .execute(&self.pool)
+                .await
+            }
+            other => {
+                return Err(MediaError::InvalidMedia(format!(
+                    "Theme colors are only stored for movies and series; got {:?}",
+                    other
+                )));
+            }
+        }
+        .map_err(MediaError::Database)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn image_placeholder(&self, iid: Uuid) -> Result<Option<Vec<u8>>> {
+        let placeholder = sqlx::query_scalar!(
+            r#"
+            SELECT placeholder
+            FROM tmdb_image_variants
+            WHERE id = $1
+            "#,
+            iid
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(MediaError::Database)?;
+
+        Ok(placeholder.flatten())
+    }
+
+    async fn store_image_placeholder(
+        &self,
+        iid: Uuid,
+        placeholder: &[u8],
+    ) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE tmdb_image_variants
+            SET placeholder = $2
+            WHERE id = $1
+              AND placeholder IS NULL
+            "#,
+            iid,
+            placeholder
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(MediaError::Database)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_cached_images_for_media(
+        &self,
+        media_id: Uuid,
+    ) -> Result<Vec<String>> {
+        let mut tx = self.pool.begin().await.map_err(MediaError::Database)?;
+
+        let cache_keys = sqlx::query_scalar!(
+            r#"
+            DELETE FROM cached_images ci
+            USING tmdb_image_variants iv
+            WHERE ci.image_id = iv.id
+              AND iv.media_id = $1
+            RETURNING ci.cache_key
+            "#,
+            media_id
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(MediaError::Database)?;
+
+        sqlx::query!(
+            r#"
+            UPDATE tmdb_image_variants
+            SET placeholder = NULL
+            WHERE media_id = $1
+            "#,
+            media_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(MediaError::Database)?;
+
+        tx.commit().await.map_err(MediaError::Database)?;
+
+        Ok(cache_keys)
+    }
+
     async fn lookup_original_image<'a>(
         &self,
         ctx: &'a ImgDbLookup,
@@ -150,6 +377,174 @@ impl ImageRepository for PostgresImageRepository {
         rows.into_iter().map(|r| self.map_variant_row(r)).collect()
     }
 
+    async fn list_media_images(
+        &self,
+        media_id: Uuid,
+    ) -> Result<Vec<OriginalImage>> {
+        let rows: Vec<TmdbVariantRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {VARIANT_COLUMNS}
+            FROM tmdb_image_variants
+            WHERE media_id = $1
+            ORDER BY image_variant, is_primary DESC NULLS LAST,
+                     vote_avg DESC, vote_cnt DESC
+            "#
+        ))
+        .bind(media_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(MediaError::Database)?;
+
+        rows.into_iter().map(|r| self.map_variant_row(r)).collect()
+    }
+
+    async fn set_primary_image(
+        &self,
+        media_id: Uuid,
+        iid: Uuid,
+    ) -> Result<OriginalImage> {
+        let mut tx = self.pool.begin().await.map_err(MediaError::Database)?;
+
+        let row: Option<TmdbVariantRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {VARIANT_COLUMNS}
+            FROM tmdb_image_variants
+            WHERE id = $1 AND media_id = $2
+            FOR UPDATE
+            "#
+        ))
+        .bind(iid)
+        .bind(media_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(MediaError::Database)?;
+        let Some(row) = row else {
+            return Err(MediaError::NotFound(format!(
+                "Image {iid} is not linked to media {media_id}"
+            )));
+        };
+        let (media_type, variant) = (row.media_type, row.image_variant);
+
+        sqlx::query(
+            r#"
+            UPDATE tmdb_image_variants
+            SET is_primary = (id = $1)
+            WHERE media_id = $2
+              AND media_type = $3
+              AND image_variant = $4
+              AND (id = $1 OR is_primary)
+            "#,
+        )
+        .bind(iid)
+        .bind(media_id)
+        .bind(media_type)
+        .bind(variant)
+        .execute(&mut *tx)
+        .await
+        .map_err(MediaError::Database)?;
+
+        // Copies of a movie share the images of one owner, so repoint every
+        // metadata row that showed one of this variant's images, not only
+        // the owner's. The theme color follows the primary poster, and the
+        // propagation triggers only fill an empty one: clear it first.
+        let clear_theme_color = match (media_type, variant) {
+            (ImageMediaType::Movie, ImageVariant::Poster) => Some(
+                r#"
+                UPDATE movie_references mr
+                SET theme_color = NULL
+                FROM movie_metadata mm
+                WHERE mm.movie_id = mr.id
+                  AND mm.primary_poster_image_id <> $2
+                  AND mm.primary_poster_image_id IN (
+                      SELECT id FROM tmdb_image_variants
+                      WHERE media_id = $1 AND image_variant = 'poster'
+                  )
+                "#,
+            ),
+            (ImageMediaType::Series, ImageVariant::Poster) => Some(
+                r#"
+                UPDATE series s
+                SET theme_color = NULL
+                FROM series_metadata sm
+                WHERE sm.series_id = s.id
+                  AND sm.primary_poster_image_id <> $2
+                  AND sm.primary_poster_image_id IN (
+                      SELECT id FROM tmdb_image_variants
+                      WHERE media_id = $1 AND image_variant = 'poster'
+                  )
+                "#,
+            ),
+            _ => None,
+        };
+        if let Some(sql) = clear_theme_color {
+            sqlx::query(sql)
+                .bind(media_id)
+                .bind(iid)
+                .execute(&mut *tx)
+                .await
+                .map_err(MediaError::Database)?;
+        }
+
+        let metadata_columns = match (media_type, variant) {
+            (ImageMediaType::Movie, ImageVariant::Poster) => Some((
+                "movie_metadata",
+                "primary_poster_image_id",
+                "poster_path",
+            )),
+            (ImageMediaType::Movie, ImageVariant::Backdrop) => Some((
+                "movie_metadata",
+                "primary_backdrop_image_id",
+                "backdrop_path",
+            )),
+            (ImageMediaType::Series, ImageVariant::Poster) => Some((
+                "series_metadata",
+                "primary_poster_image_id",
+                "poster_path",
+            )),
+            (ImageMediaType::Series, ImageVariant::Backdrop) => Some((
+                "series_metadata",
+                "primary_backdrop_image_id",
+                "backdrop_path",
+            )),
+            (ImageMediaType::Season, ImageVariant::Poster) => Some((
+                "season_metadata",
+                "primary_poster_image_id",
+                "poster_path",
+            )),
+            (ImageMediaType::Episode, ImageVariant::Thumbnail) => Some((
+                "episode_metadata",
+                "primary_thumbnail_image_id",
+                "still_path",
+            )),
+            _ => None,
+        };
+        if let Some((table, iid_column, path_column)) = metadata_columns {
+            sqlx::query(&format!(
+                r#"
+                UPDATE {table}
+                SET {iid_column} = $2, {path_column} = $3
+                WHERE {iid_column} IN (
+                    SELECT id FROM tmdb_image_variants
+                    WHERE media_id = $1 AND image_variant = $4
+                )
+                "#
+            ))
+            .bind(media_id)
+            .bind(iid)
+            .bind(&row.tmdb_path)
+            .bind(variant)
+            .execute(&mut *tx)
+            .await
+            .map_err(MediaError::Database)?;
+        }
+
+        tx.commit().await.map_err(MediaError::Database)?;
+
+        let mut chosen = self.map_variant_row(row)?;
+        chosen.is_primary = true;
+        Ok(chosen)
+    }
+
     async fn lookup_variant_by_iid(
         &self,
         iid: Uuid,
@@ -671,6 +1066,11 @@ impl ImageRepository for PostgresImageRepository {
     }
 }
 
+/// `tmdb_image_variants` columns in the shape of [`TmdbVariantRow`].
+const VARIANT_COLUMNS: &str = "id, tmdb_path, media_id, image_variant, \
+     media_type, width, iso_lang, vote_avg::real AS vote_avg, vote_cnt, \
+     COALESCE(is_primary, false) AS is_primary";
+
 #[derive(sqlx::FromRow)]
 struct TmdbVariantRow {
     id: Uuid,
@@ -715,6 +1115,13 @@ struct CachedImageRow {
     modified_at: DateTime<Utc>,
 }
 
+#[derive(sqlx::FromRow)]
+struct ThemeColorCandidateRow {
+    media_id: Uuid,
+    poster_iid: Uuid,
+    cache_key: String,
+}
+
 impl PostgresImageRepository {
     fn map_cached_row(&self, r: CachedImageRow) -> ImageRecord {
         let imz = match r.size_variant {
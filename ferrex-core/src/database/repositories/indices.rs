@@ -13,8 +13,14 @@ use crate::{
     database::repository_ports::indices::IndicesRepository,
     domain::watch::WatchStatusFilter,
     error::{MediaError, Result},
-    query::types::{MediaTypeFilter, SortBy, SortOrder},
-    types::ids::LibraryId,
+    query::{
+        filter_expr::{FilterExpr, FilterExprError, FilterField},
+        filtering::{decade_to_year_range, resolution_to_range},
+        types::{
+            CertificationFilter, MediaTypeFilter, QueryError, SortBy, SortOrder,
+        },
+    },
+    types::{filter_types::UiWatchStatus, ids::LibraryId},
 };
 
 #[derive(Clone, Debug)]
@@ -193,6 +199,14 @@ impl<'a> FilteredMovieIndexBuilder<'a> {
             }
         }
 
+        if let Some(expr) = &spec.expr {
+            expr.validate()?;
+            if expr.references(FilterField::WatchStatus) {
+                needs_watch_progress = true;
+                needs_watch_completed = true;
+            }
+        }
+
         if (needs_watch_progress || needs_watch_completed) && user_id.is_none()
         {
             return Err(FilterQueryError::MissingUserContext(
@@ -268,6 +282,15 @@ impl<'a> FilteredMovieIndexBuilder<'a> {
             self.push_resolution_filter(range);
         }
 
+        if let Some(filter) = self.spec.certification().map_err(|err| {
+            FilterQueryError::InvalidCertification(match err {
+                QueryError::InvalidQuery(message) => message,
+                other => other.to_string(),
+            })
+        })? {
+            self.push_certification_filter(filter);
+        }
+
         if let Some(search) = self.spec.search.as_ref() {
             let like = format!("%{}%", search);
             self.qb.push(" AND (mr.title ILIKE ");
@@ -281,9 +304,93 @@ impl<'a> FilteredMovieIndexBuilder<'a> {
             self.apply_watch_status_filter(status)?;
         }
 
+        if let Some(expr) = self.spec.expr.as_ref() {
+            self.qb.push(" AND ");
+            self.push_filter_expr(expr);
+        }
+
         Ok(())
     }
 
+    /// Render a combined predicate as a parenthesized boolean expression.
+    ///
+    /// Every leaf evaluates to TRUE or FALSE (never NULL) so that `NOT`
+    /// behaves like the in-memory evaluator for rows with missing metadata.
+    fn push_filter_expr(&mut self, expr: &'a FilterExpr) {
+        match expr {
+            FilterExpr::Genre(genre) => {
+                self.qb.push(
+                    "EXISTS (SELECT 1 FROM movie_genres mg WHERE mg.movie_id = mr.id AND mg.name = ",
+                );
+                self.qb.push_bind(genre.api_name());
+                self.qb.push(")");
+            }
+            FilterExpr::Decade(decade) => {
+                let range = decade_to_year_range(*decade);
+                self.qb.push(
+                    "COALESCE(EXTRACT(YEAR FROM mm.release_date)::INT BETWEEN ",
+                );
+                self.qb.push_bind(range.min as i32);
+                self.qb.push(" AND ");
+                self.qb.push_bind(range.max as i32);
+                self.qb.push(", FALSE)");
+            }
+            FilterExpr::Resolution(resolution) => {
+                match resolution_to_range(*resolution) {
+                    None => {
+                        self.qb.push("TRUE");
+                    }
+                    Some(range) => {
+                        self.qb.push(
+                            "COALESCE(((mf.technical_metadata->>'height')::INTEGER) BETWEEN ",
+                        );
+                        self.qb.push_bind(range.min as i32);
+                        self.qb.push(" AND ");
+                        self.qb.push_bind(range.max as i32);
+                        self.qb.push(", FALSE)");
+                    }
+                }
+            }
+            FilterExpr::WatchStatus(status) => {
+                self.qb.push(match status {
+                    UiWatchStatus::Any => "TRUE",
+                    UiWatchStatus::Unwatched => {
+                        "(uwp.media_uuid IS NULL AND ucm.media_uuid IS NULL)"
+                    }
+                    UiWatchStatus::InProgress => {
+                        "(uwp.media_uuid IS NOT NULL AND ucm.media_uuid IS NULL)"
+                    }
+                    UiWatchStatus::Completed => "(ucm.media_uuid IS NOT NULL)",
+                });
+            }
+            FilterExpr::And(exprs) | FilterExpr::Or(exprs) => {
+                if exprs.is_empty() {
+                    let identity = matches!(expr, FilterExpr::And(_));
+                    self.qb.push(if identity { "TRUE" } else { "FALSE" });
+                    return;
+                }
+                let joiner = if matches!(expr, FilterExpr::And(_)) {
+                    " AND "
+                } else {
+                    " OR "
+                };
+                self.qb.push("(");
+                for (index, child) in exprs.iter().enumerate() {
+                    if index > 0 {
+                        self.qb.push(joiner);
+                    }
+                    self.push_filter_expr(child);
+                }
+                self.qb.push(")");
+            }
+            FilterExpr::Not(inner) => {
+                self.qb.push("NOT (");
+                self.push_filter_expr(inner);
+                self.qb.push(")");
+            }
+        }
+    }
+
     fn push_year_filter(&mut self, range: crate::api::types::ScalarRange<u16>) {
         self.qb.push(
             " AND mm.release_date IS NOT NULL AND EXTRACT(YEAR FROM mm.release_date)::INT BETWEEN ",
@@ -304,6 +411,15 @@ impl<'a> FilteredMovieIndexBuilder<'a> {
         Ok(())
     }
 
+    fn push_certification_filter(&mut self, filter: CertificationFilter) {
+        self.qb.push(" AND (mm.certification_age <= ");
+        self.qb.push_bind(i16::from(filter.max_age));
+        if filter.include_unrated {
+            self.qb.push(" OR mm.certification_age IS NULL");
+        }
+        self.qb.push(")");
+    }
+
     fn push_resolution_filter(
         &mut self,
         range: crate::api::types::ScalarRange<u16>,
@@ -418,6 +534,10 @@ enum FilterQueryError {
     MissingUserContext(&'static str),
     #[error("unsupported media type {0:?} for filtered indices")]
     UnsupportedMediaType(MediaTypeFilter),
+    #[error(transparent)]
+    InvalidExpression(#[from] FilterExprError),
+    #[error("{0}")]
+    InvalidCertification(String),
 }
 
 fn map_sort_to_column(
@@ -6,6 +6,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
+    api::types::{LibraryStats, LibraryStorageUsage, StorageGrowth},
     database::repository_ports::library::LibraryRepository,
     error::{MediaError, Result},
     types::{
@@ -33,6 +34,7 @@ impl PostgresLibraryRepository {
         match library_type {
             LibraryType::Movies => "movies",
             LibraryType::Series => "tvshows",
+            LibraryType::Music => "music",
         }
     }
 
@@ -40,6 +42,7 @@ impl PostgresLibraryRepository {
         match value {
             "movies" => Some(LibraryType::Movies),
             "tvshows" => Some(LibraryType::Series),
+            "music" => Some(LibraryType::Music),
             _ => None,
         }
     }
@@ -69,9 +72,10 @@ impl LibraryRepository for PostgresLibraryRepository {
                 watch_for_changes,
                 analyze_on_scan,
                 max_retry_attempts,
-                movie_ref_batch_size
+                movie_ref_batch_size,
+                exclude_patterns
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             "#,
             library.id.as_uuid(),
             library.name,
@@ -84,6 +88,7 @@ impl LibraryRepository for PostgresLibraryRepository {
             library.analyze_on_scan,
             library.max_retry_attempts as i32,
             library.movie_ref_batch_size.get() as i32,
+            &library.exclude_patterns,
         )
         .execute(self.pool())
         .await
@@ -110,6 +115,7 @@ impl LibraryRepository for PostgresLibraryRepository {
                 analyze_on_scan,
                 max_retry_attempts,
                 movie_ref_batch_size,
+                exclude_patterns,
                 created_at,
                 updated_at
             FROM libraries
@@ -153,6 +159,7 @@ impl LibraryRepository for PostgresLibraryRepository {
                     row.id, e
                 ))
             })?,
+            exclude_patterns: row.exclude_patterns,
             created_at: row.created_at,
             updated_at: row.updated_at,
             media: None,
@@ -175,6 +182,7 @@ impl LibraryRepository for PostgresLibraryRepository {
                 analyze_on_scan,
                 max_retry_attempts,
                 movie_ref_batch_size,
+                exclude_patterns,
                 created_at,
                 updated_at
             FROM libraries
@@ -215,6 +223,7 @@ impl LibraryRepository for PostgresLibraryRepository {
                         row.id, e
                     ))
                 })?,
+                exclude_patterns: row.exclude_patterns,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
                 media: None,
@@ -251,8 +260,9 @@ impl LibraryRepository for PostgresLibraryRepository {
                 analyze_on_scan = $8,
                 max_retry_attempts = $9,
                 movie_ref_batch_size = $10,
+                exclude_patterns = $11,
                 updated_at = NOW()
-            WHERE id = $11
+            WHERE id = $12
             "#,
             library.name,
             library_type,
@@ -264,6 +274,7 @@ impl LibraryRepository for PostgresLibraryRepository {
             library.analyze_on_scan,
             library.max_retry_attempts as i32,
             library.movie_ref_batch_size.get() as i32,
+            &library.exclude_patterns,
             id.as_uuid(),
         )
         .execute(self.pool())
@@ -354,4 +365,186 @@ impl LibraryRepository for PostgresLibraryRepository {
             paths: row.paths.into_iter().map(PathBuf::from).collect(),
         })
     }
+
+    async fn library_stats(
+        &self,
+        id: LibraryId,
+    ) -> Result<Option<LibraryStats>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM movie_references mr
+                    WHERE mr.library_id = l.id) AS "movies!",
+                (SELECT COUNT(*) FROM series s
+                    WHERE s.library_id = l.id) AS "series!",
+                (SELECT COUNT(*) FROM season_references sr
+                    WHERE sr.library_id = l.id) AS "seasons!",
+                (SELECT COUNT(*) FROM episode_references er
+                    JOIN series s ON s.id = er.series_id
+                    WHERE s.library_id = l.id) AS "episodes!",
+                files.total_files AS "total_files!",
+                files.total_size AS "total_size!",
+                (SELECT COUNT(*) FROM movie_metadata mm
+                    WHERE mm.library_id = l.id)
+                + (SELECT COUNT(*) FROM series_metadata sm
+                    JOIN series s ON s.id = sm.series_id
+                    WHERE s.library_id = l.id) AS "with_metadata!",
+                (SELECT COUNT(*) FROM movie_metadata mm
+                    WHERE mm.library_id = l.id AND mm.poster_path IS NOT NULL)
+                + (SELECT COUNT(*) FROM series_metadata sm
+                    JOIN series s ON s.id = sm.series_id
+                    WHERE s.library_id = l.id AND sm.poster_path IS NOT NULL)
+                    AS "with_poster!"
+            FROM libraries l
+            CROSS JOIN LATERAL (
+                SELECT
+                    COUNT(*) AS total_files,
+                    COALESCE(SUM(mf.file_size), 0)::BIGINT AS total_size
+                FROM media_files mf
+                WHERE mf.library_id = l.id
+            ) files
+            WHERE l.id = $1
+            "#,
+            id.as_uuid()
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Database query failed: {}", e))
+        })?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let movies = row.movies.max(0) as u64;
+        let series = row.series.max(0) as u64;
+        let top_level = movies + series;
+        let with_metadata = (row.with_metadata.max(0) as u64).min(top_level);
+        let with_poster = (row.with_poster.max(0) as u64).min(top_level);
+
+        Ok(Some(LibraryStats {
+            library_id: id,
+            movies,
+            series,
+            seasons: row.seasons.max(0) as u64,
+            episodes: row.episodes.max(0) as u64,
+            total_files: row.total_files.max(0) as u64,
+            total_size: row.total_size.max(0) as u64,
+            with_poster,
+            without_poster: top_level - with_poster,
+            with_metadata,
+            without_metadata: top_level - with_metadata,
+            missing_files: None,
+            missing_checked_at: None,
+        }))
+    }
+
+    async fn storage_usage(
+        &self,
+        window_days: u32,
+    ) -> Result<Vec<LibraryStorageUsage>> {
+        // Music tracks live outside media_files; both count as files.
+        // Images are attributed through the media they belong to, so
+        // profile images of people are left out.
+        let rows = sqlx::query_as::<_, StorageUsageRow>(
+            r#"
+            WITH files AS (
+                SELECT library_id, file_size, discovered_at FROM media_files
+                UNION ALL
+                SELECT library_id, file_size, discovered_at FROM music_tracks
+            ),
+            file_totals AS (
+                SELECT
+                    library_id,
+                    COUNT(*) AS total_files,
+                    COALESCE(SUM(file_size), 0)::BIGINT AS total_size,
+                    COUNT(*) FILTER (
+                        WHERE discovered_at >= now() - make_interval(days => $1)
+                    ) AS added_files,
+                    COALESCE(SUM(file_size) FILTER (
+                        WHERE discovered_at >= now() - make_interval(days => $1)
+                    ), 0)::BIGINT AS added_size
+                FROM files
+                GROUP BY library_id
+            ),
+            image_totals AS (
+                SELECT
+                    COALESCE(mr.library_id, s.library_id, sr.library_id,
+                             es.library_id) AS library_id,
+                    SUM(ci.byte_len)::BIGINT AS image_cache_size
+                FROM cached_images ci
+                JOIN tmdb_image_variants iv ON iv.id = ci.image_id
+                LEFT JOIN movie_references mr
+                    ON iv.media_type = 'movie' AND mr.id = iv.media_id
+                LEFT JOIN series s
+                    ON iv.media_type = 'series' AND s.id = iv.media_id
+                LEFT JOIN season_references sr
+                    ON iv.media_type = 'season' AND sr.id = iv.media_id
+                LEFT JOIN episode_references er
+                    ON iv.media_type = 'episode' AND er.id = iv.media_id
+                LEFT JOIN series es ON es.id = er.series_id
+                WHERE iv.media_type <> 'person'
+                GROUP BY 1
+            )
+            SELECT
+                l.id, l.name, l.library_type,
+                COALESCE(f.total_files, 0) AS total_files,
+                COALESCE(f.total_size, 0) AS total_size,
+                COALESCE(f.added_files, 0) AS added_files,
+                COALESCE(f.added_size, 0) AS added_size,
+                COALESCE(i.image_cache_size, 0) AS image_cache_size
+            FROM libraries l
+            LEFT JOIN file_totals f ON f.library_id = l.id
+            LEFT JOIN image_totals i ON i.library_id = l.id
+            ORDER BY l.name, l.id
+            "#,
+        )
+        .bind(window_days as i32)
+        .fetch_all(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Database query failed: {}", e))
+        })?;
+
+        rows.into_iter()
+            .map(|row| {
+                let library_type = Self::decode_type(&row.library_type)
+                    .ok_or_else(|| {
+                        MediaError::Internal(format!(
+                            "Unknown library type: {}",
+                            row.library_type
+                        ))
+                    })?;
+                let total_size = row.total_size.max(0) as u64;
+                Ok(LibraryStorageUsage {
+                    library_id: LibraryId(row.id),
+                    library_name: row.name,
+                    library_type,
+                    total_files: row.total_files.max(0) as u64,
+                    total_size,
+                    image_cache_size: row.image_cache_size.max(0) as u64,
+                    transcode_cache_size: 0,
+                    growth: StorageGrowth::new(
+                        window_days,
+                        row.added_files.max(0) as u64,
+                        row.added_size.max(0) as u64,
+                        total_size,
+                    ),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StorageUsageRow {
+    id: Uuid,
+    name: String,
+    library_type: String,
+    total_files: i64,
+    total_size: i64,
+    added_files: i64,
+    added_size: i64,
+    image_cache_size: i64,
 }
@@ -9,8 +9,9 @@ use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction, postgres::PgRow};
 use uuid::Uuid;
 
 use crate::database::repository_ports::media_files::{
-    MediaFileFilter, MediaFileSort, MediaFileSortField, MediaFilesReadPort,
-    MediaFilesWritePort, Page, SortDirection, UpsertOutcome,
+    MediaFileDigest, MediaFileFilter, MediaFileSort, MediaFileSortField,
+    MediaFilesReadPort, MediaFilesWritePort, Page, SortDirection,
+    UpsertOutcome,
 };
 use crate::database::traits::{MediaFilters, MediaStats};
 use crate::error::{MediaError, Result};
@@ -55,6 +56,13 @@ impl MediaFilesReadPort for PostgresMediaRepository {
     async fn stats(&self, filter: MediaFileFilter) -> Result<MediaStats> {
         self.stats_with_filter(filter).await
     }
+
+    async fn digests(
+        &self,
+        file_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, MediaFileDigest>> {
+        self.get_media_digests(file_ids).await
+    }
 }
 
 #[async_trait]
@@ -98,6 +106,10 @@ impl MediaFilesWritePort for PostgresMediaRepository {
     ) -> Result<()> {
         self.update_technical_metadata_by_id(id, metadata).await
     }
+
+    async fn record_digest(&self, digest: MediaFileDigest) -> Result<()> {
+        self.record_media_digest(&digest).await
+    }
 }
 
 impl PostgresMediaRepository {
@@ -714,6 +726,73 @@ impl PostgresMediaRepository {
         Ok(())
     }
 
+    pub async fn get_media_digests(
+        &self,
+        file_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, MediaFileDigest>> {
+        if file_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT file_id, sample_bytes, file_size, modified_at, digest
+            FROM media_file_digests
+            WHERE file_id = ANY($1)
+            "#,
+            file_ids
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Database query failed: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let digest = MediaFileDigest {
+                    file_id: row.file_id,
+                    sample_bytes: row.sample_bytes as u32,
+                    file_size: row.file_size as u64,
+                    modified_at: row.modified_at,
+                    digest: row.digest,
+                };
+                (row.file_id, digest)
+            })
+            .collect())
+    }
+
+    pub async fn record_media_digest(
+        &self,
+        digest: &MediaFileDigest,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO media_file_digests (file_id, sample_bytes, file_size, modified_at, digest)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (file_id) DO UPDATE SET
+                sample_bytes = EXCLUDED.sample_bytes,
+                file_size = EXCLUDED.file_size,
+                modified_at = EXCLUDED.modified_at,
+                digest = EXCLUDED.digest,
+                computed_at = NOW()
+            "#,
+            digest.file_id,
+            digest.sample_bytes as i32,
+            digest.file_size as i64,
+            digest.modified_at,
+            &digest.digest
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Failed to record digest: {}", e))
+        })?;
+
+        Ok(())
+    }
+
     async fn upsert_media_in_transaction(
         &self,
         tx: &mut Transaction<'_, Postgres>,
@@ -1,16 +1,18 @@
 use crate::{
+    api::types::{DuplicateMedia, MediaCopy},
     database::{
         postgres_ext::{
             EpisodeReferenceRow, MovieReferenceRow, SeasonReferenceRow,
             SeriesReferenceRow, TmdbMetadataRepository,
         },
         repository_ports::media_references::{
-            MediaReferencesRepository, MovieBatchManifestRecord,
+            MediaReferencesRepository, MediaRemoval, MovieBatchManifestRecord,
             MovieBatchVersionRecord, SeriesBundleVersionRecord,
             TvReferenceOrphanCleanup,
         },
     },
     error::{MediaError, Result},
+    query::types::EpisodeSort,
     types::{
         ids::{EpisodeID, LibraryId, MovieID, SeasonID, SeriesID},
         library::LibraryType,
@@ -25,9 +27,10 @@ use ferrex_model::{MediaID, MovieBatchId};
 use num_bigint::BigUint;
 use rayon::iter::{IntoParallelIterator, ParallelExtend, ParallelIterator};
 use sqlx::{
-    PgPool,
+    Connection, PgConnection, PgPool,
     types::{BigDecimal, Uuid},
 };
+use std::path::PathBuf;
 use tracing::{error, info};
 
 #[derive(Clone, Debug)]
@@ -177,6 +180,9 @@ impl MediaReferencesRepository for PostgresMediaReferencesRepository {
                     }
                 }
             }
+            // Tracks live in `music_tracks`, outside the media reference
+            // tables.
+            LibraryType::Music => {}
         }
 
         Ok(media)
@@ -1512,6 +1518,7 @@ impl MediaReferencesRepository for PostgresMediaReferencesRepository {
     async fn get_season_episodes(
         &self,
         season_id: &SeasonID,
+        sort: EpisodeSort,
     ) -> Result<Vec<EpisodeReference>> {
         let repository = TmdbMetadataRepository::new(&self.pool);
 
@@ -1537,10 +1544,15 @@ impl MediaReferencesRepository for PostgresMediaReferencesRepository {
                 mf.technical_metadata
             FROM episode_references er
             JOIN media_files mf ON er.file_id = mf.id
+            LEFT JOIN episode_metadata em ON em.episode_id = er.id
             WHERE er.season_id = $1
-            ORDER BY er.episode_number
+            ORDER BY
+                CASE WHEN $2 THEN em.air_date END ASC NULLS LAST,
+                er.episode_number,
+                er.id
             "#,
-            season_id.to_uuid()
+            season_id.to_uuid(),
+            sort == EpisodeSort::AirDate
         )
         .fetch_all(&self.pool)
         .await
@@ -1606,9 +1618,9 @@ impl MediaReferencesRepository for PostgresMediaReferencesRepository {
             tmdb_id as i64,
             movie_uuid
         )
-            .execute(&self.pool)
-            .await
-            .map_err(|e| MediaError::Internal(format!("Update failed: {}", e)))?;
+        .execute(&self.pool)
+        .await
+        .map_err(|e| tmdb_id_update_error(e, "movie", tmdb_id))?;
 
         Ok(())
     }
@@ -1627,7 +1639,288 @@ impl MediaReferencesRepository for PostgresMediaReferencesRepository {
         )
         .execute(&self.pool)
         .await
-        .map_err(|e| MediaError::Internal(format!("Update failed: {}", e)))?;
+        .map_err(|e| tmdb_id_update_error(e, "series", tmdb_id))?;
+
+        Ok(())
+    }
+
+    async fn canonical_movie_id(
+        &self,
+        tmdb_id: u64,
+    ) -> Result<Option<MovieID>> {
+        if tmdb_id == 0 {
+            return Ok(None);
+        }
+        let canonical = sqlx::query_scalar!(
+            r#"
+            SELECT id
+            FROM movie_references
+            WHERE tmdb_id = $1
+            ORDER BY discovered_at, id
+            LIMIT 1
+            "#,
+            tmdb_id as i64
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to load canonical movie for TMDB id {}: {}",
+                tmdb_id, e
+            ))
+        })?;
+
+        Ok(canonical.map(MovieID))
+    }
+
+    async fn list_movie_copies(&self, id: &MovieID) -> Result<Vec<MediaCopy>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                c.movie_id AS "movie_id!",
+                c.library_id AS "library_id!",
+                l.name AS library_name,
+                c.file_path AS "file_path!",
+                c.canonical_id AS "canonical_id!"
+            FROM movie_copies c
+            JOIN movie_references mr ON mr.tmdb_id = c.tmdb_id
+            JOIN libraries l ON l.id = c.library_id
+            WHERE mr.id = $1
+            ORDER BY c.movie_id = c.canonical_id DESC, l.name
+            "#,
+            id.to_uuid()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to list copies of movie {}: {}",
+                id, e
+            ))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MediaCopy {
+                library_id: LibraryId(row.library_id),
+                library_name: row.library_name,
+                movie_id: MovieID(row.movie_id),
+                file_path: row.file_path,
+                canonical: row.movie_id == row.canonical_id,
+            })
+            .collect())
+    }
+
+    async fn list_duplicate_movies(
+        &self,
+        library_id: LibraryId,
+    ) -> Result<Vec<DuplicateMedia>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                c.tmdb_id AS "tmdb_id!",
+                c.title AS "title!",
+                c.movie_id AS "movie_id!",
+                c.library_id AS "library_id!",
+                l.name AS library_name,
+                c.file_path AS "file_path!",
+                c.canonical_id AS "canonical_id!"
+            FROM movie_copies c
+            JOIN libraries l ON l.id = c.library_id
+            WHERE c.copy_count > 1
+              AND c.tmdb_id IN (
+                  SELECT tmdb_id FROM movie_references WHERE library_id = $1
+              )
+            ORDER BY c.tmdb_id, c.movie_id = c.canonical_id DESC, l.name
+            "#,
+            library_id.to_uuid()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to list duplicate movies for library {}: {}",
+                library_id, e
+            ))
+        })?;
+
+        let mut duplicates: Vec<DuplicateMedia> = Vec::new();
+        for row in rows {
+            let copy = MediaCopy {
+                library_id: LibraryId(row.library_id),
+                library_name: row.library_name,
+                movie_id: MovieID(row.movie_id),
+                file_path: row.file_path,
+                canonical: row.movie_id == row.canonical_id,
+            };
+            match duplicates.last_mut() {
+                Some(group) if group.tmdb_id == row.tmdb_id as u64 => {
+                    group.copies.push(copy)
+                }
+                _ => duplicates.push(DuplicateMedia {
+                    tmdb_id: row.tmdb_id as u64,
+                    title: row.title,
+                    copies: vec![copy],
+                }),
+            }
+        }
+        Ok(duplicates)
+    }
+
+    async fn get_tmdb_id_override(&self, id: &MediaID) -> Result<Option<u64>> {
+        let tmdb_id = match id {
+            MediaID::Movie(movie_id) => sqlx::query_scalar!(
+                "SELECT tmdb_id_override FROM movie_references WHERE id = $1",
+                movie_id.to_uuid()
+            )
+            .fetch_optional(&self.pool)
+            .await,
+            MediaID::Series(series_id) => {
+                sqlx::query_scalar!(
+                    "SELECT tmdb_id_override FROM series WHERE id = $1",
+                    series_id.to_uuid()
+                )
+                .fetch_optional(&self.pool)
+                .await
+            }
+            other => {
+                return Err(MediaError::InvalidMedia(format!(
+                    "TMDB overrides apply to movies or series, got {:?}",
+                    other
+                )));
+            }
+        }
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to load TMDB id override: {}",
+                e
+            ))
+        })?;
+
+        Ok(tmdb_id.flatten().map(|id| id as u64))
+    }
+
+    async fn set_tmdb_id_override(
+        &self,
+        id: &MediaID,
+        tmdb_id: Option<u64>,
+    ) -> Result<()> {
+        let tmdb_id = tmdb_id.map(|id| id as i64);
+        let result = match id {
+            MediaID::Movie(movie_id) => {
+                sqlx::query!(
+                    "UPDATE movie_references SET tmdb_id_override = $1, updated_at = NOW() WHERE id = $2",
+                    tmdb_id,
+                    movie_id.to_uuid()
+                )
+                .execute(&self.pool)
+                .await
+            }
+            MediaID::Series(series_id) => {
+                sqlx::query!(
+                    "UPDATE series SET tmdb_id_override = $1, updated_at = NOW() WHERE id = $2",
+                    tmdb_id,
+                    series_id.to_uuid()
+                )
+                .execute(&self.pool)
+                .await
+            }
+            other => {
+                return Err(MediaError::InvalidMedia(format!(
+                    "TMDB overrides apply to movies or series, got {:?}",
+                    other
+                )));
+            }
+        }
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to record TMDB id override: {}",
+                e
+            ))
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(MediaError::NotFound(format!("{:?} not found", id)));
+        }
+        Ok(())
+    }
+
+    async fn get_metadata_language(
+        &self,
+        id: &MediaID,
+    ) -> Result<Option<String>> {
+        let language = match id {
+            MediaID::Movie(movie_id) => {
+                sqlx::query_scalar!(
+                    "SELECT metadata_language FROM movie_metadata WHERE movie_id = $1",
+                    movie_id.to_uuid()
+                )
+                .fetch_optional(&self.pool)
+                .await
+            }
+            MediaID::Series(series_id) => {
+                sqlx::query_scalar!(
+                    "SELECT metadata_language FROM series_metadata WHERE series_id = $1",
+                    series_id.to_uuid()
+                )
+                .fetch_optional(&self.pool)
+                .await
+            }
+            other => {
+                return Err(MediaError::InvalidMedia(format!(
+                    "Metadata language is tracked per movie or series, got {:?}",
+                    other
+                )));
+            }
+        }
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to load metadata language: {}",
+                e
+            ))
+        })?;
+
+        Ok(language.flatten())
+    }
+
+    async fn set_metadata_language(
+        &self,
+        id: &MediaID,
+        language: &str,
+    ) -> Result<()> {
+        let result = match id {
+            MediaID::Movie(movie_id) => {
+                sqlx::query!(
+                    "UPDATE movie_metadata SET metadata_language = $1 WHERE movie_id = $2",
+                    language,
+                    movie_id.to_uuid()
+                )
+                .execute(&self.pool)
+                .await
+            }
+            MediaID::Series(series_id) => {
+                sqlx::query!(
+                    "UPDATE series_metadata SET metadata_language = $1 WHERE series_id = $2",
+                    language,
+                    series_id.to_uuid()
+                )
+                .execute(&self.pool)
+                .await
+            }
+            other => {
+                return Err(MediaError::InvalidMedia(format!(
+                    "Metadata language is tracked per movie or series, got {:?}",
+                    other
+                )));
+            }
+        };
+
+        result.map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to record metadata language: {}",
+                e
+            ))
+        })?;
 
         Ok(())
     }
@@ -1690,4 +1983,278 @@ impl MediaReferencesRepository for PostgresMediaReferencesRepository {
             deleted_series,
         })
     }
+
+    async fn delete_media_batch(
+        &self,
+        ids: &[MediaID],
+    ) -> Result<Vec<Result<MediaRemoval>>> {
+        let mut tx = self.pool.begin().await.map_err(MediaError::Database)?;
+        let mut outcomes = Vec::with_capacity(ids.len());
+        for id in ids {
+            // A savepoint per id keeps one failure from aborting the batch.
+            let mut savepoint = Connection::begin(&mut *tx)
+                .await
+                .map_err(MediaError::Database)?;
+            match delete_media(&mut savepoint, *id).await {
+                Ok(removal) => {
+                    savepoint.commit().await.map_err(MediaError::Database)?;
+                    outcomes.push(Ok(removal));
+                }
+                Err(err) => {
+                    savepoint.rollback().await.map_err(MediaError::Database)?;
+                    outcomes.push(Err(err));
+                }
+            }
+        }
+        tx.commit().await.map_err(MediaError::Database)?;
+        Ok(outcomes)
+    }
+}
+
+/// Delete one movie, series, season or episode inside a transaction.
+async fn delete_media(
+    conn: &mut PgConnection,
+    id: MediaID,
+) -> Result<MediaRemoval> {
+    let uuid = *id.as_uuid();
+    let internal = |what: &str, e: sqlx::Error| {
+        MediaError::Internal(format!("Failed to {what} for {uuid}: {e}"))
+    };
+    let not_found = || MediaError::NotFound(format!("{id:?} not found"));
+    let mut removed = vec![id];
+
+    // Files to delete, with the episodes they back. Deleting a file
+    // cascades to the movie or episode reference pointing at it.
+    let (file_ids, episodes) = match id {
+        MediaID::Movie(_) => {
+            let file_id: Uuid = sqlx::query_scalar(
+                "SELECT file_id FROM movie_references WHERE id = $1",
+            )
+            .bind(uuid)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| internal("resolve movie", e))?
+            .ok_or_else(not_found)?;
+            (vec![file_id], Vec::new())
+        }
+        MediaID::Series(_) | MediaID::Season(_) | MediaID::Episode(_) => {
+            let (scope_exists, column) = match id {
+                MediaID::Series(_) => (
+                    "SELECT EXISTS (SELECT 1 FROM series WHERE id = $1)",
+                    "series_id",
+                ),
+                MediaID::Season(_) => (
+                    "SELECT EXISTS (SELECT 1 FROM season_references WHERE id = $1)",
+                    "season_id",
+                ),
+                _ => (
+                    "SELECT EXISTS (SELECT 1 FROM episode_references WHERE id = $1)",
+                    "id",
+                ),
+            };
+            let exists: bool = sqlx::query_scalar(scope_exists)
+                .bind(uuid)
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(|e| internal("resolve media", e))?;
+            if !exists {
+                return Err(not_found());
+            }
+            let episodes: Vec<DeletedEpisodeRow> = sqlx::query_as(&format!(
+                r#"
+                SELECT id, file_id, season_id, series_id, tmdb_series_id,
+                       season_number, episode_number
+                FROM episode_references
+                WHERE {column} = $1
+                "#
+            ))
+            .bind(uuid)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| internal("resolve episodes", e))?;
+            (
+                episodes.iter().map(|episode| episode.file_id).collect(),
+                episodes,
+            )
+        }
+    };
+
+    let files: Vec<String> = sqlx::query_scalar(
+        "DELETE FROM media_files WHERE id = ANY($1) RETURNING file_path",
+    )
+    .bind(&file_ids)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| internal("delete media files", e))?;
+
+    if !matches!(id, MediaID::Episode(_)) {
+        removed.extend(
+            episodes
+                .iter()
+                .map(|episode| MediaID::Episode(EpisodeID(episode.id))),
+        );
+    }
+    match id {
+        MediaID::Series(_) => {
+            let seasons: Vec<Uuid> = sqlx::query_scalar(
+                "DELETE FROM season_references WHERE series_id = $1 RETURNING id",
+            )
+            .bind(uuid)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| internal("delete seasons", e))?;
+            removed.extend(
+                seasons
+                    .into_iter()
+                    .map(|season| MediaID::Season(SeasonID(season))),
+            );
+            sqlx::query("DELETE FROM series WHERE id = $1")
+                .bind(uuid)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| internal("delete series", e))?;
+        }
+        MediaID::Season(_) => {
+            let series_id: Uuid = sqlx::query_scalar(
+                "DELETE FROM season_references WHERE id = $1 RETURNING series_id",
+            )
+            .bind(uuid)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| internal("delete season", e))?;
+            removed.extend(prune_series(conn, series_id).await?);
+        }
+        MediaID::Episode(_) => {
+            if let Some(episode) = episodes.first() {
+                let season: Option<Uuid> = sqlx::query_scalar(
+                    r#"
+                    DELETE FROM season_references sr
+                    WHERE sr.id = $1
+                      AND NOT EXISTS (
+                        SELECT 1 FROM episode_references er
+                        WHERE er.season_id = sr.id
+                      )
+                    RETURNING sr.id
+                    "#,
+                )
+                .bind(episode.season_id)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(|e| internal("prune season", e))?;
+                removed.extend(
+                    season.map(|season| MediaID::Season(SeasonID(season))),
+                );
+                removed.extend(prune_series(conn, episode.series_id).await?);
+            }
+        }
+        MediaID::Movie(_) => {}
+    }
+
+    // Watch state of the removed movies and episodes. Identity-based
+    // episode state survives while another file of the episode remains.
+    let media_uuids: Vec<Uuid> =
+        removed.iter().map(|media| *media.as_uuid()).collect();
+    for table in ["user_watch_progress", "user_completed_media"] {
+        sqlx::query(&format!("DELETE FROM {table} WHERE media_uuid = ANY($1)"))
+            .bind(&media_uuids)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| internal("clear watch state", e))?;
+    }
+    if !episodes.is_empty() {
+        sqlx::query(
+            r#"
+            DELETE FROM user_episode_state ues
+            USING UNNEST($1::bigint[], $2::smallint[], $3::smallint[])
+                AS gone(tmdb_series_id, season_number, episode_number)
+            WHERE ues.tmdb_series_id = gone.tmdb_series_id
+              AND ues.season_number = gone.season_number
+              AND ues.episode_number = gone.episode_number
+              AND NOT EXISTS (
+                SELECT 1 FROM episode_references er
+                WHERE er.tmdb_series_id = gone.tmdb_series_id
+                  AND er.season_number = gone.season_number
+                  AND er.episode_number = gone.episode_number
+              )
+            "#,
+        )
+        .bind(
+            episodes
+                .iter()
+                .map(|e| e.tmdb_series_id)
+                .collect::<Vec<_>>(),
+        )
+        .bind(episodes.iter().map(|e| e.season_number).collect::<Vec<_>>())
+        .bind(
+            episodes
+                .iter()
+                .map(|e| e.episode_number)
+                .collect::<Vec<_>>(),
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| internal("clear episode state", e))?;
+    }
+
+    Ok(MediaRemoval {
+        removed,
+        files: files.into_iter().map(PathBuf::from).collect(),
+    })
+}
+
+/// Delete `series_id` when it has no seasons or episodes left.
+async fn prune_series(
+    conn: &mut PgConnection,
+    series_id: Uuid,
+) -> Result<Option<MediaID>> {
+    let pruned: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        DELETE FROM series s
+        WHERE s.id = $1
+          AND NOT EXISTS (
+            SELECT 1 FROM season_references sr WHERE sr.series_id = s.id
+          )
+          AND NOT EXISTS (
+            SELECT 1 FROM episode_references er WHERE er.series_id = s.id
+          )
+        RETURNING s.id
+        "#,
+    )
+    .bind(series_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| {
+        MediaError::Internal(format!("Failed to prune series {series_id}: {e}"))
+    })?;
+    Ok(pruned.map(|series| MediaID::Series(SeriesID(series))))
+}
+
+#[derive(sqlx::FromRow)]
+struct DeletedEpisodeRow {
+    id: Uuid,
+    file_id: Uuid,
+    season_id: Uuid,
+    series_id: Uuid,
+    tmdb_series_id: i64,
+    season_number: i16,
+    episode_number: i16,
+}
+
+/// Map a failed TMDB id update, reporting the `(tmdb_id, library_id)`
+/// uniqueness clash as a conflict rather than an internal error.
+fn tmdb_id_update_error(
+    err: sqlx::Error,
+    kind: &str,
+    tmdb_id: u64,
+) -> MediaError {
+    let unique_violation = err
+        .as_database_error()
+        .is_some_and(|db_err| db_err.is_unique_violation());
+    if unique_violation {
+        MediaError::Conflict(format!(
+            "TMDB {kind} {tmdb_id} is already matched to another {kind} in this library"
+        ))
+    } else {
+        MediaError::Internal(format!("Update failed: {}", err))
+    }
 }
@@ -1,5 +1,7 @@
 //! PostgreSQL-backed repository implementations.
 
+pub mod collections;
+pub mod credits;
 pub mod file_watch;
 pub mod folder_inventory;
 mod fuzzy_title_search;
@@ -8,6 +10,7 @@ pub mod indices;
 pub mod library;
 pub mod media;
 pub mod media_references;
+pub mod music_tracks;
 pub mod processing_status;
 pub mod query;
 pub mod rbac;
@@ -0,0 +1,290 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::types::{MusicAlbum, MusicTrack, MusicTrackQuery};
+use crate::database::repository_ports::music_tracks::{
+    MusicTrackSync, MusicTracksRepository,
+};
+use crate::error::{MediaError, Result};
+use crate::types::ids::LibraryId;
+
+const TRACK_COLUMNS: &str = r#"
+    id, library_id, file_path, filename, file_size, artist, album, title,
+    track_number, disc_number, year, discovered_at
+"#;
+
+#[derive(Clone)]
+pub struct PostgresMusicTracksRepository {
+    pool: PgPool,
+}
+
+impl PostgresMusicTracksRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+impl fmt::Debug for PostgresMusicTracksRepository {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostgresMusicTracksRepository")
+            .field("pool_size", &self.pool.size())
+            .field("idle_connections", &self.pool.num_idle())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl MusicTracksRepository for PostgresMusicTracksRepository {
+    async fn replace_library_tracks(
+        &self,
+        library_id: LibraryId,
+        tracks: &[MusicTrack],
+    ) -> Result<MusicTrackSync> {
+        let mut ids = Vec::with_capacity(tracks.len());
+        let mut paths = Vec::with_capacity(tracks.len());
+        let mut filenames = Vec::with_capacity(tracks.len());
+        let mut sizes = Vec::with_capacity(tracks.len());
+        let mut artists = Vec::with_capacity(tracks.len());
+        let mut albums = Vec::with_capacity(tracks.len());
+        let mut titles = Vec::with_capacity(tracks.len());
+        let mut track_numbers = Vec::with_capacity(tracks.len());
+        let mut disc_numbers = Vec::with_capacity(tracks.len());
+        let mut years = Vec::with_capacity(tracks.len());
+        for track in tracks {
+            ids.push(track.id);
+            paths.push(track.file_path.clone());
+            filenames.push(track.filename.clone());
+            sizes.push(i64::try_from(track.file_size).unwrap_or(i64::MAX));
+            artists.push(track.artist.clone());
+            albums.push(track.album.clone());
+            titles.push(track.title.clone());
+            track_numbers.push(track.track_number.map(i32::from));
+            disc_numbers.push(track.disc_number.map(i32::from));
+            years.push(track.year.map(i32::from));
+        }
+
+        let mut tx = self.pool().begin().await.map_err(|e| {
+            MediaError::Internal(format!("Failed to begin transaction: {}", e))
+        })?;
+
+        let stored = sqlx::query(
+            r#"
+            INSERT INTO music_tracks (
+                id, library_id, file_path, filename, file_size, artist,
+                album, title, track_number, disc_number, year
+            )
+            SELECT t.id, $1, t.file_path, t.filename, t.file_size, t.artist,
+                   t.album, t.title, t.track_number, t.disc_number, t.year
+            FROM UNNEST(
+                $2::uuid[], $3::text[], $4::text[], $5::bigint[], $6::text[],
+                $7::text[], $8::text[], $9::int[], $10::int[], $11::int[]
+            ) AS t(
+                id, file_path, filename, file_size, artist, album, title,
+                track_number, disc_number, year
+            )
+            ON CONFLICT (file_path) DO UPDATE SET
+                library_id = EXCLUDED.library_id,
+                filename = EXCLUDED.filename,
+                file_size = EXCLUDED.file_size,
+                artist = EXCLUDED.artist,
+                album = EXCLUDED.album,
+                title = EXCLUDED.title,
+                track_number = EXCLUDED.track_number,
+                disc_number = EXCLUDED.disc_number,
+                year = EXCLUDED.year,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(library_id.as_uuid())
+        .bind(&ids)
+        .bind(&paths)
+        .bind(&filenames)
+        .bind(&sizes)
+        .bind(&artists)
+        .bind(&albums)
+        .bind(&titles)
+        .bind(&track_numbers)
+        .bind(&disc_numbers)
+        .bind(&years)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Failed to store music tracks: {}", e))
+        })?
+        .rows_affected();
+
+        let removed = sqlx::query(
+            r#"
+            DELETE FROM music_tracks
+            WHERE library_id = $1
+              AND NOT (file_path = ANY($2::text[]))
+            "#,
+        )
+        .bind(library_id.as_uuid())
+        .bind(&paths)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to remove missing music tracks: {}",
+                e
+            ))
+        })?
+        .rows_affected();
+
+        tx.commit().await.map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to commit music tracks: {}",
+                e
+            ))
+        })?;
+
+        Ok(MusicTrackSync { stored, removed })
+    }
+
+    async fn list_tracks(
+        &self,
+        library_id: LibraryId,
+        query: &MusicTrackQuery,
+    ) -> Result<Vec<MusicTrack>> {
+        let sql = format!(
+            r#"
+            SELECT {TRACK_COLUMNS}
+            FROM music_tracks
+            WHERE library_id = $1
+              AND ($2::text IS NULL OR lower(artist) = lower($2))
+              AND ($3::text IS NULL OR lower(album) = lower($3))
+              AND ($4::text IS NULL
+                   OR strpos(lower(title), lower($4)) > 0
+                   OR strpos(lower(artist), lower($4)) > 0
+                   OR strpos(lower(coalesce(album, '')), lower($4)) > 0)
+            ORDER BY lower(artist), lower(album) NULLS LAST,
+                     disc_number NULLS FIRST, track_number NULLS LAST,
+                     lower(title)
+            "#
+        );
+        let rows = sqlx::query_as::<_, MusicTrackRow>(&sql)
+            .bind(library_id.as_uuid())
+            .bind(query.artist.as_deref())
+            .bind(query.album.as_deref())
+            .bind(query.search.as_deref().filter(|s| !s.trim().is_empty()))
+            .fetch_all(self.pool())
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!(
+                    "Failed to list music tracks: {}",
+                    e
+                ))
+            })?;
+
+        Ok(rows.into_iter().map(MusicTrack::from).collect())
+    }
+
+    async fn list_albums(
+        &self,
+        library_id: LibraryId,
+        artist: Option<&str>,
+    ) -> Result<Vec<MusicAlbum>> {
+        let rows = sqlx::query_as::<_, MusicAlbumRow>(
+            r#"
+            SELECT min(artist) AS artist,
+                   min(album) AS album,
+                   max(year) AS year,
+                   count(*) AS track_count
+            FROM music_tracks
+            WHERE library_id = $1
+              AND album IS NOT NULL
+              AND ($2::text IS NULL OR lower(artist) = lower($2))
+            GROUP BY lower(artist), lower(album)
+            ORDER BY lower(artist), max(year) NULLS LAST, lower(album)
+            "#,
+        )
+        .bind(library_id.as_uuid())
+        .bind(artist)
+        .fetch_all(self.pool())
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Failed to list music albums: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MusicAlbum {
+                artist: row.artist,
+                album: row.album,
+                year: row.year.and_then(|year| u16::try_from(year).ok()),
+                track_count: u32::try_from(row.track_count).unwrap_or(0),
+            })
+            .collect())
+    }
+
+    async fn get_track(&self, id: Uuid) -> Result<Option<MusicTrack>> {
+        let sql =
+            format!("SELECT {TRACK_COLUMNS} FROM music_tracks WHERE id = $1");
+        let row = sqlx::query_as::<_, MusicTrackRow>(&sql)
+            .bind(id)
+            .fetch_optional(self.pool())
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!(
+                    "Failed to load music track: {}",
+                    e
+                ))
+            })?;
+
+        Ok(row.map(MusicTrack::from))
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MusicTrackRow {
+    id: Uuid,
+    library_id: Uuid,
+    file_path: String,
+    filename: String,
+    file_size: i64,
+    artist: String,
+    album: Option<String>,
+    title: String,
+    track_number: Option<i32>,
+    disc_number: Option<i32>,
+    year: Option<i32>,
+    discovered_at: DateTime<Utc>,
+}
+
+impl From<MusicTrackRow> for MusicTrack {
+    fn from(row: MusicTrackRow) -> Self {
+        let small =
+            |value: Option<i32>| value.and_then(|v| u16::try_from(v).ok());
+        Self {
+            id: row.id,
+            library_id: LibraryId(row.library_id),
+            file_path: row.file_path,
+            filename: row.filename,
+            file_size: u64::try_from(row.file_size).unwrap_or(0),
+            artist: row.artist,
+            album: row.album,
+            title: row.title,
+            track_number: small(row.track_number),
+            disc_number: small(row.disc_number),
+            year: small(row.year),
+            discovered_at: row.discovered_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MusicAlbumRow {
+    artist: String,
+    album: String,
+    year: Option<i32>,
+    track_count: i64,
+}
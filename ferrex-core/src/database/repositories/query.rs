@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use async_trait::async_trait;
 use sqlx::Row;
@@ -12,18 +12,51 @@ use crate::domain::watch::{CompletedItem, ItemWatchStatus};
 use crate::{
     api::types::{RATING_DECIMAL_SCALE, RatingValue},
     database::repositories::fuzzy_title_search::{
-        TitleCandidate, rank_title_candidates, supports_title_only_search,
+        RankedTitleCandidate, TitleCandidate, rank_title_candidates,
+        supports_title_only_search,
     },
     database::repository_ports::query::QueryRepository,
     error::{MediaError, Result},
     player_prelude::*,
-    query::types::{MediaQuery, MediaWithStatus},
+    query::types::{
+        CertificationFilter, EpisodeSort, MediaQuery, MediaSearchHit,
+        MediaSearchQuery, MediaWithStatus, QueryError, RecentlyAddedCursor,
+        RecentlyAddedItem, RecentlyAddedPage,
+    },
+    types::LibraryId,
 };
 
 fn rating_bound(value: RatingValue) -> BigDecimal {
     BigDecimal::from(value).with_scale(RATING_DECIMAL_SCALE as i64)
 }
 
+fn certification_filter(
+    filter: std::result::Result<Option<CertificationFilter>, QueryError>,
+) -> Result<Option<CertificationFilter>> {
+    filter.map_err(|err| match err {
+        QueryError::InvalidQuery(message) => MediaError::InvalidMedia(message),
+        other => MediaError::InvalidMedia(other.to_string()),
+    })
+}
+
+/// `column` is the `certification_age` column of the metadata table.
+fn push_certification_filter(
+    sql_builder: &mut QueryBuilder<'_, Postgres>,
+    column: &str,
+    filter: CertificationFilter,
+) {
+    sql_builder.push(" AND (");
+    sql_builder.push(column);
+    sql_builder.push(" <= ");
+    sql_builder.push_bind(i16::from(filter.max_age));
+    if filter.include_unrated {
+        sql_builder.push(" OR ");
+        sql_builder.push(column);
+        sql_builder.push(" IS NULL");
+    }
+    sql_builder.push(")");
+}
+
 #[derive(Clone, Debug)]
 pub struct PostgresQueryRepository {
     pool: PgPool,
@@ -46,12 +79,42 @@ struct CompletedRow {
     media_kind: i32,
 }
 
+#[derive(Debug)]
+struct RecentlyAddedRow {
+    id: Uuid,
+    added_at: chrono::DateTime<chrono::Utc>,
+    media_kind: i32,
+}
+
 #[derive(Debug, sqlx::FromRow)]
 struct TitleCandidateRow {
     id: Uuid,
     title: String,
 }
 
+#[derive(Debug, sqlx::FromRow)]
+struct SearchMatchRow {
+    id: Uuid,
+    /// 0 = movie, 1 = series
+    media_kind: i32,
+    rank: f32,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct SearchCardRow {
+    id: Uuid,
+    library_id: Uuid,
+    title: String,
+    subtitle: Option<String>,
+    year: Option<i32>,
+    poster_path: Option<String>,
+}
+
+/// Overview and cast matches rank below every title match, whose scores
+/// fill `0.5..=1.0`.
+const OVERVIEW_MATCH_WEIGHT: f32 = 0.4;
+const CAST_MATCH_WEIGHT: f32 = 0.45;
+
 impl PostgresQueryRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
@@ -145,7 +208,18 @@ impl PostgresQueryRepository {
             );
         }
 
-        let ranked = rank_title_candidates(search_text, candidates);
+        let mut ranked = rank_title_candidates(search_text, candidates);
+
+        if let Some(filter) =
+            certification_filter(query.filters.certification())?
+        {
+            let ids: Vec<MediaID> =
+                ranked.iter().map(|candidate| candidate.media_id).collect();
+            let allowed = self.certified_ids(&ids, filter).await?;
+            ranked.retain(|candidate| {
+                allowed.contains(candidate.media_id.as_uuid())
+            });
+        }
 
         let start = query.pagination.offset.min(ranked.len());
         let end = (start + query.pagination.limit).min(ranked.len());
@@ -177,6 +251,268 @@ impl PostgresQueryRepository {
         Ok(results)
     }
 
+    /// Movies and series whose overview matches `search_text`, ranked by
+    /// `ts_rank` normalized to `0..1`.
+    async fn fetch_overview_matches(
+        &self,
+        search_text: &str,
+        want_movies: bool,
+        want_series: bool,
+        library_ids: &[Uuid],
+        candidate_limit: i64,
+    ) -> Result<Vec<SearchMatchRow>> {
+        let mut rows = Vec::new();
+        if want_movies {
+            rows.extend(
+                sqlx::query_as::<_, SearchMatchRow>(
+                    r#"
+                    SELECT mr.id, 0::int4 AS media_kind,
+                           ts_rank(to_tsvector('english', COALESCE(mm.overview, '')), q, 32)::float4 AS rank
+                    FROM movie_references mr
+                    JOIN movie_metadata mm ON mm.movie_id = mr.id,
+                         plainto_tsquery('english', $1) q
+                    WHERE to_tsvector('english', COALESCE(mm.overview, '')) @@ q
+                      AND (cardinality($2::uuid[]) = 0 OR mr.library_id = ANY($2))
+                    ORDER BY rank DESC
+                    LIMIT $3
+                    "#,
+                )
+                .bind(search_text)
+                .bind(library_ids)
+                .bind(candidate_limit)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| {
+                    MediaError::Internal(format!(
+                        "Overview search query failed: {}",
+                        e
+                    ))
+                })?,
+            );
+        }
+        if want_series {
+            rows.extend(
+                sqlx::query_as::<_, SearchMatchRow>(
+                    r#"
+                    SELECT s.id, 1::int4 AS media_kind,
+                           ts_rank(to_tsvector('english', COALESCE(sm.overview, '')), q, 32)::float4 AS rank
+                    FROM series s
+                    JOIN series_metadata sm ON sm.series_id = s.id,
+                         plainto_tsquery('english', $1) q
+                    WHERE to_tsvector('english', COALESCE(sm.overview, '')) @@ q
+                      AND (cardinality($2::uuid[]) = 0 OR s.library_id = ANY($2))
+                    ORDER BY rank DESC
+                    LIMIT $3
+                    "#,
+                )
+                .bind(search_text)
+                .bind(library_ids)
+                .bind(candidate_limit)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| {
+                    MediaError::Internal(format!(
+                        "Overview search query failed: {}",
+                        e
+                    ))
+                })?,
+            );
+        }
+        Ok(rows)
+    }
+
+    /// Movies and series with a cast member whose name is similar to
+    /// `search_text`, ranked by the best trigram similarity.
+    async fn fetch_cast_matches(
+        &self,
+        search_text: &str,
+        want_movies: bool,
+        want_series: bool,
+        library_ids: &[Uuid],
+        candidate_limit: i64,
+    ) -> Result<Vec<SearchMatchRow>> {
+        if !want_movies && !want_series {
+            return Ok(Vec::new());
+        }
+        sqlx::query_as::<_, SearchMatchRow>(
+            r#"
+            WITH people AS (
+                SELECT p.id, similarity(p.name, $1) AS rank
+                FROM persons p
+                WHERE p.name % $1
+            )
+            SELECT hits.id, hits.media_kind, MAX(hits.rank)::float4 AS rank
+            FROM (
+                SELECT mc.movie_id AS id, 0::int4 AS media_kind, people.rank
+                FROM people
+                JOIN movie_cast mc ON mc.person_id = people.id
+                WHERE $2 AND (cardinality($4::uuid[]) = 0 OR mc.library_id = ANY($4))
+
+                UNION ALL
+
+                SELECT sc.series_id AS id, 1::int4 AS media_kind, people.rank
+                FROM people
+                JOIN series_cast sc ON sc.person_id = people.id
+                JOIN series s ON s.id = sc.series_id
+                WHERE $3 AND (cardinality($4::uuid[]) = 0 OR s.library_id = ANY($4))
+            ) AS hits
+            GROUP BY hits.id, hits.media_kind
+            ORDER BY rank DESC
+            LIMIT $5
+            "#,
+        )
+        .bind(search_text)
+        .bind(want_movies)
+        .bind(want_series)
+        .bind(library_ids)
+        .bind(candidate_limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Cast search query failed: {}", e))
+        })
+    }
+
+    /// The ids among `ids` whose certification `filter` allows. Seasons
+    /// and episodes are rated by their series.
+    async fn certified_ids(
+        &self,
+        ids: &[MediaID],
+        filter: CertificationFilter,
+    ) -> Result<HashSet<Uuid>> {
+        if ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let uuids: Vec<Uuid> = ids.iter().map(|id| *id.as_uuid()).collect();
+        let rows: Vec<(Uuid, Option<i16>)> = sqlx::query_as(
+            r#"
+            SELECT mr.id, mm.certification_age
+            FROM movie_references mr
+            LEFT JOIN movie_metadata mm ON mm.movie_id = mr.id
+            WHERE mr.id = ANY($1)
+            UNION ALL
+            SELECT s.id, sm.certification_age
+            FROM series s
+            LEFT JOIN series_metadata sm ON sm.series_id = s.id
+            WHERE s.id = ANY($1)
+            UNION ALL
+            SELECT sn.id, sm.certification_age
+            FROM season_references sn
+            LEFT JOIN series_metadata sm ON sm.series_id = sn.series_id
+            WHERE sn.id = ANY($1)
+            UNION ALL
+            SELECT er.id, sm.certification_age
+            FROM episode_references er
+            LEFT JOIN series_metadata sm ON sm.series_id = er.series_id
+            WHERE er.id = ANY($1)
+            "#,
+        )
+        .bind(&uuids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Certification lookup failed: {}", e))
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .filter(|(_, age)| filter.allows(*age))
+            .map(|(id, _)| id)
+            .collect())
+    }
+
+    /// Card fields for ranked hits, in the order given.
+    async fn load_search_cards(
+        &self,
+        ranked: Vec<(MediaID, f32, SearchField)>,
+    ) -> Result<Vec<MediaSearchHit>> {
+        let ids_of = |pick: fn(&MediaID) -> bool| -> Vec<Uuid> {
+            ranked
+                .iter()
+                .filter(|(id, _, _)| pick(id))
+                .map(|(id, _, _)| *id.as_uuid())
+                .collect()
+        };
+        let movie_ids = ids_of(|id| matches!(id, MediaID::Movie(_)));
+        let series_ids = ids_of(|id| matches!(id, MediaID::Series(_)));
+        let episode_ids = ids_of(|id| matches!(id, MediaID::Episode(_)));
+
+        let mut cards: HashMap<Uuid, SearchCardRow> = HashMap::new();
+        let queries = [
+            (
+                movie_ids,
+                r#"
+                SELECT mr.id, mr.library_id, mr.title, NULL::text AS subtitle,
+                       EXTRACT(YEAR FROM mm.release_date)::int4 AS year,
+                       mm.poster_path
+                FROM movie_references mr
+                LEFT JOIN movie_metadata mm ON mm.movie_id = mr.id
+                WHERE mr.id = ANY($1)
+                "#,
+            ),
+            (
+                series_ids,
+                r#"
+                SELECT s.id, s.library_id, s.title, NULL::text AS subtitle,
+                       EXTRACT(YEAR FROM sm.first_air_date)::int4 AS year,
+                       sm.poster_path
+                FROM series s
+                LEFT JOIN series_metadata sm ON sm.series_id = s.id
+                WHERE s.id = ANY($1)
+                "#,
+            ),
+            (
+                episode_ids,
+                r#"
+                SELECT er.id, s.library_id,
+                       COALESCE(em.name, s.title) AS title,
+                       format('%s · S%sE%s', s.title,
+                              lpad(er.season_number::text, 2, '0'),
+                              lpad(er.episode_number::text, 2, '0')) AS subtitle,
+                       EXTRACT(YEAR FROM em.air_date)::int4 AS year,
+                       em.still_path AS poster_path
+                FROM episode_references er
+                JOIN series s ON s.id = er.series_id
+                LEFT JOIN episode_metadata em ON em.episode_id = er.id
+                WHERE er.id = ANY($1)
+                "#,
+            ),
+        ];
+        for (ids, sql) in queries {
+            if ids.is_empty() {
+                continue;
+            }
+            let rows = sqlx::query_as::<_, SearchCardRow>(sql)
+                .bind(&ids)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| {
+                    MediaError::Internal(format!(
+                        "Search result query failed: {}",
+                        e
+                    ))
+                })?;
+            cards.extend(rows.into_iter().map(|row| (row.id, row)));
+        }
+
+        Ok(ranked
+            .into_iter()
+            .filter_map(|(id, score, matched)| {
+                let card = cards.remove(id.as_uuid())?;
+                Some(MediaSearchHit {
+                    id,
+                    library_id: LibraryId(card.library_id),
+                    title: card.title,
+                    subtitle: card.subtitle,
+                    year: card.year,
+                    poster_path: card.poster_path,
+                    score,
+                    matched,
+                })
+            })
+            .collect())
+    }
+
     async fn fetch_movie_title_candidates(
         &self,
         search_text: &str,
@@ -474,9 +810,23 @@ impl QueryRepository for PostgresQueryRepository {
         &self,
         query: &MediaQuery,
     ) -> Result<Vec<MediaWithStatus>> {
+        let certification =
+            certification_filter(query.filters.certification())?;
+
         // Handle watch status filter separately if provided
         if let Some(watch_filter) = &query.filters.watch_status {
-            return self.query_media_by_watch_status(query, watch_filter).await;
+            let mut results = self
+                .query_media_by_watch_status(query, watch_filter)
+                .await?;
+            // Watch lists are paged before this, so a filtered page can
+            // come back short.
+            if let Some(filter) = certification {
+                let ids: Vec<MediaID> =
+                    results.iter().map(|item| item.id).collect();
+                let allowed = self.certified_ids(&ids, filter).await?;
+                results.retain(|item| allowed.contains(item.id.as_uuid()));
+            }
+            return Ok(results);
         }
 
         // Title-only fuzzy search: use Postgres for candidate retrieval, then
@@ -584,6 +934,16 @@ impl QueryRepository for PostgresQueryRepository {
             sql_builder.push_bind(rating_bound(range.max));
         }
 
+        if let Some(filter) =
+            certification_filter(query.filters.certification())?
+        {
+            push_certification_filter(
+                &mut sql_builder,
+                "mm.certification_age",
+                filter,
+            );
+        }
+
         // Add search query if present
         if let Some(search) = &query.search {
             self.add_search_clause(&mut sql_builder, search);
@@ -693,6 +1053,16 @@ impl QueryRepository for PostgresQueryRepository {
             sql_builder.push_bind(rating_bound(range.max));
         }
 
+        if let Some(filter) =
+            certification_filter(query.filters.certification())?
+        {
+            push_certification_filter(
+                &mut sql_builder,
+                "sm.certification_age",
+                filter,
+            );
+        }
+
         if let Some(search) = &query.search {
             self.add_series_search_clause(&mut sql_builder, search);
         }
@@ -738,6 +1108,7 @@ impl QueryRepository for PostgresQueryRepository {
                 ORDER BY season_number, episode_number
             ) ep ON true
             LEFT JOIN media_files mf ON ep.file_id = mf.id
+            LEFT JOIN episode_metadata em ON em.episode_id = ep.id
             "#,
         );
 
@@ -851,6 +1222,117 @@ impl QueryRepository for PostgresQueryRepository {
         Ok(combined)
     }
 
+    async fn search_media(
+        &self,
+        query: &MediaSearchQuery,
+        fields: &[SearchField],
+        limit: usize,
+    ) -> Result<Vec<MediaSearchHit>> {
+        let search_text = query.q.trim();
+        if search_text.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+        let wants = |kind: MediaTypeFilter| {
+            query.media_type.is_none() || query.media_type == Some(kind)
+        };
+        let want_movies = wants(MediaTypeFilter::Movie);
+        let want_series = wants(MediaTypeFilter::Series);
+        let want_episodes = wants(MediaTypeFilter::Episode);
+        let library_ids: Vec<Uuid> =
+            query.library_id.map(|id| id.0).into_iter().collect();
+        let candidate_limit = compute_candidate_limit(limit);
+        let query_len = search_text.chars().count();
+        let searches = |field: SearchField| {
+            fields.contains(&field) || fields.contains(&SearchField::All)
+        };
+
+        let mut title_candidates = Vec::new();
+        if searches(SearchField::Title) {
+            if want_movies {
+                title_candidates.extend(
+                    self.fetch_movie_title_candidates(
+                        search_text,
+                        query_len,
+                        &library_ids,
+                        candidate_limit,
+                    )
+                    .await?,
+                );
+            }
+            if want_series {
+                title_candidates.extend(
+                    self.fetch_series_title_candidates(
+                        search_text,
+                        query_len,
+                        &library_ids,
+                        candidate_limit,
+                    )
+                    .await?,
+                );
+            }
+            if want_episodes {
+                title_candidates.extend(
+                    self.fetch_episode_title_candidates(
+                        search_text,
+                        query_len,
+                        &library_ids,
+                        candidate_limit,
+                    )
+                    .await?,
+                );
+            }
+        }
+        let titles = rank_title_candidates(search_text, title_candidates);
+
+        let mut others = Vec::new();
+        if searches(SearchField::Overview) {
+            let rows = self
+                .fetch_overview_matches(
+                    search_text,
+                    want_movies,
+                    want_series,
+                    &library_ids,
+                    candidate_limit,
+                )
+                .await?;
+            others.extend(rows.into_iter().map(|row| {
+                (
+                    search_match_id(&row),
+                    row.rank * OVERVIEW_MATCH_WEIGHT,
+                    SearchField::Overview,
+                )
+            }));
+        }
+        if searches(SearchField::Cast) && query_len > 2 {
+            let rows = self
+                .fetch_cast_matches(
+                    search_text,
+                    want_movies,
+                    want_series,
+                    &library_ids,
+                    candidate_limit,
+                )
+                .await?;
+            others.extend(rows.into_iter().map(|row| {
+                (
+                    search_match_id(&row),
+                    row.rank * CAST_MATCH_WEIGHT,
+                    SearchField::Cast,
+                )
+            }));
+        }
+
+        let mut ranked = merge_search_matches(&titles, others);
+        if let Some(filter) = certification_filter(query.certification())? {
+            let ids: Vec<MediaID> =
+                ranked.iter().map(|(id, _, _)| *id).collect();
+            let allowed = self.certified_ids(&ids, filter).await?;
+            ranked.retain(|(id, _, _)| allowed.contains(id.as_uuid()));
+        }
+        ranked.truncate(limit);
+        self.load_search_cards(ranked).await
+    }
+
     async fn query_media_by_watch_status(
         &self,
         query: &MediaQuery,
@@ -879,6 +1361,68 @@ impl QueryRepository for PostgresQueryRepository {
         }
     }
 
+    async fn query_recently_added(
+        &self,
+        library_id: Option<LibraryId>,
+        limit: usize,
+        cursor: Option<RecentlyAddedCursor>,
+    ) -> Result<RecentlyAddedPage> {
+        // Fetch one extra row to learn whether another page follows.
+        let rows = sqlx::query_as!(
+            RecentlyAddedRow,
+            r#"
+            SELECT feed.id AS "id!", feed.added_at AS "added_at!", feed.media_kind AS "media_kind!"
+            FROM (
+                SELECT mr.id, mr.library_id, mr.discovered_at AS added_at, 0::int4 AS media_kind
+                FROM movie_references mr
+
+                UNION ALL
+
+                SELECT s.id, s.library_id,
+                       COALESCE(MAX(er.discovered_at), s.discovered_at) AS added_at,
+                       1::int4 AS media_kind
+                FROM series s
+                LEFT JOIN episode_references er ON er.series_id = s.id
+                GROUP BY s.id
+            ) AS feed
+            WHERE ($1::uuid IS NULL OR feed.library_id = $1)
+              AND ($2::timestamptz IS NULL OR (feed.added_at, feed.id) < ($2, $3::uuid))
+            ORDER BY feed.added_at DESC, feed.id DESC
+            LIMIT $4
+            "#,
+            library_id.map(|id| id.0),
+            cursor.map(|c| c.added_at),
+            cursor.map(|c| c.id),
+            limit as i64 + 1
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("Database query failed: {}", e))
+        })?;
+
+        let has_more = rows.len() > limit;
+        let items: Vec<RecentlyAddedItem> = rows
+            .into_iter()
+            .take(limit)
+            .map(|row| RecentlyAddedItem {
+                id: if row.media_kind == 0 {
+                    MediaID::Movie(MovieID(row.id))
+                } else {
+                    MediaID::Series(SeriesID(row.id))
+                },
+                added_at: row.added_at,
+            })
+            .collect();
+        let next_cursor = if has_more {
+            items.last().map(RecentlyAddedCursor::after)
+        } else {
+            None
+        };
+
+        Ok(RecentlyAddedPage { items, next_cursor })
+    }
+
     async fn query_in_progress_media(
         &self,
         user_id: Uuid,
@@ -1191,7 +1735,16 @@ impl QueryRepository for PostgresQueryRepository {
         };
         sql_builder.push(null_position);
 
-        sql_builder.push(", sd.id, sn.season_number, ep.episode_number");
+        sql_builder.push(", sd.id, sn.season_number");
+        match sort.episode_sort {
+            EpisodeSort::Number => {
+                sql_builder.push(", ep.episode_number");
+            }
+            EpisodeSort::AirDate => {
+                sql_builder
+                    .push(", em.air_date ASC NULLS LAST, ep.episode_number");
+            }
+        }
     }
 
     fn add_series_search_clause(
@@ -1480,6 +2033,51 @@ impl QueryRepository for PostgresQueryRepository {
     }
 }
 
+fn search_match_id(row: &SearchMatchRow) -> MediaID {
+    if row.media_kind == 0 {
+        MediaID::Movie(MovieID(row.id))
+    } else {
+        MediaID::Series(SeriesID(row.id))
+    }
+}
+
+/// Combine ranked title matches with overview and cast matches into one
+/// list, best first. Title scores are scaled into `0.5..=1.0` relative to
+/// the best title; other matches are expected to be below `0.5` already.
+/// Media found by several fields keeps its best score.
+fn merge_search_matches(
+    titles: &[RankedTitleCandidate],
+    others: Vec<(MediaID, f32, SearchField)>,
+) -> Vec<(MediaID, f32, SearchField)> {
+    let best = titles.first().map_or(1, |top| top.score.max(1));
+    let scaled_titles = titles.iter().map(|candidate| {
+        let relative = candidate.score.max(0) as f32 / best as f32;
+        (
+            candidate.media_id,
+            0.5 + 0.5 * relative.clamp(0.0, 1.0),
+            SearchField::Title,
+        )
+    });
+
+    let mut best_by_id: HashMap<MediaID, (f32, SearchField)> = HashMap::new();
+    for (id, score, field) in scaled_titles.chain(others) {
+        let entry = best_by_id.entry(id).or_insert((score, field));
+        if score > entry.0 {
+            *entry = (score, field);
+        }
+    }
+
+    let mut merged: Vec<_> = best_by_id
+        .into_iter()
+        .map(|(id, (score, field))| (id, score, field))
+        .collect();
+    merged.sort_by(|a, b| {
+        b.1.total_cmp(&a.1)
+            .then_with(|| a.0.as_uuid().cmp(b.0.as_uuid()))
+    });
+    merged
+}
+
 fn compute_candidate_limit(fetch_limit: usize) -> i64 {
     // Keep this bounded: candidates are scored in Rust to provide fzf/skim-like ordering,
     // while Postgres is used to keep the candidate set reasonable via indexes.
@@ -1538,3 +2136,37 @@ fn first_token_like_pattern(query: &str) -> Option<String> {
     }
     Some(format!("%{}%", clean.to_lowercase()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranked(media_id: MediaID, score: i64) -> RankedTitleCandidate {
+        RankedTitleCandidate {
+            media_id,
+            title: String::new(),
+            title_lower: String::new(),
+            score,
+        }
+    }
+
+    #[test]
+    fn search_matches_rank_titles_first_and_keep_the_best_field() {
+        let heat = MediaID::Movie(MovieID(Uuid::now_v7()));
+        let ronin = MediaID::Movie(MovieID(Uuid::now_v7()));
+        let wire = MediaID::Series(SeriesID(Uuid::now_v7()));
+
+        let merged = merge_search_matches(
+            &[ranked(heat, 200), ranked(ronin, -10)],
+            vec![
+                (heat, 0.3, SearchField::Cast),
+                (wire, 0.4, SearchField::Overview),
+            ],
+        );
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0], (heat, 1.0, SearchField::Title));
+        assert_eq!(merged[1], (ronin, 0.5, SearchField::Title));
+        assert_eq!(merged[2], (wire, 0.4, SearchField::Overview));
+    }
+}
@@ -1,6 +1,11 @@
 use crate::{
     database::repository_ports::watch_status::WatchStatusRepository,
-    domain::watch::{InProgressItem, UpdateProgressRequest, UserWatchState},
+    domain::watch::{
+        BulkWatchUpdate, InProgressItem, LatestProgress,
+        MarkEpisodeWatchedRequest, PortableMediaKey, UpdateProgressRequest,
+        UserWatchState, WatchAdvancePolicy, WatchScope, WatchStateEntry,
+        WatchStateImportReport,
+    },
     error::{MediaError, Result},
     types::watch::{
         EpisodeKey, EpisodeStatus, NextEpisode, NextReason, SeasonKey,
@@ -10,10 +15,10 @@ use crate::{
 
 use async_trait::async_trait;
 use chrono::Utc;
-use ferrex_model::VideoMediaType;
-use sqlx::PgPool;
-use std::collections::{HashMap, HashSet};
-use tracing::info;
+use ferrex_model::{SeriesID, VideoMediaType};
+use sqlx::{PgConnection, PgPool};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::{debug, info};
 use uuid::Uuid;
 
 #[derive(Clone, Debug)]
@@ -39,37 +44,119 @@ impl WatchStatusRepository for PostgresWatchStatusRepository {
         progress: &UpdateProgressRequest,
     ) -> Result<()> {
         let now = Utc::now().timestamp_millis();
+        let reported_at = progress.effective_reported_at(now);
 
         let mut tx = self.pool().begin().await.map_err(|e| {
             MediaError::Internal(format!("Failed to start transaction: {}", e))
         })?;
 
+        // Lock the current row so concurrent reports for the same item are
+        // ordered against each other rather than racing.
+        let current = sqlx::query!(
+            r#"
+            SELECT position, duration, reported_at
+            FROM user_watch_progress
+            WHERE user_id = $1 AND media_uuid = $2
+            FOR UPDATE
+            "#,
+            user_id,
+            progress.media_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!(
+                "Failed to load watch progress: {}",
+                e
+            ))
+        })?;
+
+        let latest = match current {
+            Some(row) => Some(LatestProgress::InProgress {
+                position: row.position,
+                duration: row.duration,
+                reported_at: row.reported_at,
+            }),
+            None => sqlx::query_scalar!(
+                r#"
+                SELECT completed_at
+                FROM user_completed_media
+                WHERE user_id = $1 AND media_uuid = $2
+                "#,
+                user_id,
+                progress.media_id
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!(
+                    "Failed to load completion state: {}",
+                    e
+                ))
+            })?
+            .map(|reported_at| LatestProgress::Completed { reported_at }),
+        };
+
+        if let Some(latest) = latest
+            && let Err(stale) = progress.check_against(&latest, now)
+        {
+            debug!(
+                "Rejected progress for {} from user {}: {}",
+                progress.media_id, user_id, stale
+            );
+            return Err(MediaError::Conflict(stale.to_string()));
+        }
+
+        if progress.reset {
+            // A rewatch starts over: the item is no longer completed.
+            sqlx::query!(
+                r#"
+                DELETE FROM user_completed_media
+                WHERE user_id = $1 AND media_uuid = $2
+                "#,
+                user_id,
+                progress.media_id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!(
+                    "Failed to reset completion state: {}",
+                    e
+                ))
+            })?;
+        }
+
         // Update or insert watch progress
         sqlx::query!(
             r#"
             INSERT INTO user_watch_progress (
-                user_id, media_uuid, media_type, position, duration, last_watched, updated_at
+                user_id, media_uuid, media_type, position, duration, last_watched, updated_at, reported_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $6)
+            VALUES ($1, $2, $3, $4, $5, $6, $6, $7)
             ON CONFLICT (user_id, media_uuid) DO UPDATE SET
                 media_type = EXCLUDED.media_type,
                 position = EXCLUDED.position,
                 duration = EXCLUDED.duration,
                 last_watched = EXCLUDED.last_watched,
-                updated_at = EXCLUDED.updated_at
+                updated_at = EXCLUDED.updated_at,
+                reported_at = EXCLUDED.reported_at
             "#,
             user_id,
             progress.media_id,
             progress.media_type as i16,
             progress.position,
             progress.duration,
-            now
+            now,
+            reported_at
         )
             .execute(&mut *tx)
             .await
             .map_err(|e| MediaError::Internal(format!("Failed to update watch progress: {}", e)))?;
 
-        // Check if we should mark as completed (>95% watched)
+        // Check if we should mark as completed (>95% watched). The row may
+        // already exist via the move_completed_items trigger; stamp it with
+        // the reported time so later reports are ordered against it.
         let completion_ratio = progress.position / progress.duration;
         if completion_ratio > 0.95 {
             info!(
@@ -83,12 +170,13 @@ impl WatchStatusRepository for PostgresWatchStatusRepository {
                 r#"
                 INSERT INTO user_completed_media (user_id, media_uuid, media_type, completed_at)
                 VALUES ($1, $2, $3, $4)
-                ON CONFLICT (user_id, media_uuid) DO NOTHING
+                ON CONFLICT (user_id, media_uuid) DO UPDATE
+                SET completed_at = EXCLUDED.completed_at
                 "#,
                 user_id,
                 progress.media_id,
                 progress.media_type as i16,
-                now
+                reported_at
             )
                 .execute(&mut *tx)
                 .await
@@ -792,9 +880,717 @@ impl WatchStatusRepository for PostgresWatchStatusRepository {
             .map_err(|e| MediaError::Internal(format!("Failed to clear episode state: {}", e)))?;
         Ok(())
     }
+
+    async fn mark_episode_watched(
+        &self,
+        user_id: Uuid,
+        key: &EpisodeKey,
+        request: MarkEpisodeWatchedRequest,
+    ) -> Result<u32> {
+        let now = Utc::now().timestamp_millis();
+        let internal = |what: &str, e: sqlx::Error| {
+            MediaError::Internal(format!("Failed to {what}: {e}"))
+        };
+        let cascade = request.policy == WatchAdvancePolicy::Cascade;
+        let series_wide = cascade && request.series_wide;
+
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(|e| internal("start transaction", e))?;
+
+        // Episode references of the reported episode and of the episodes
+        // the cascade reaches. Earlier seasons are only included
+        // series-wide, and specials never are.
+        let references = sqlx::query!(
+            r#"
+            SELECT id, season_number, episode_number
+            FROM episode_references
+            WHERE tmdb_series_id = $1
+              AND (
+                (season_number = $2 AND episode_number = $3)
+                OR ($4 AND season_number = $2 AND episode_number < $3)
+                OR ($5 AND season_number > 0 AND season_number < $2)
+              )
+            ORDER BY season_number, episode_number, discovered_at
+            "#,
+            key.tmdb_series_id as i64,
+            key.season_number as i16,
+            key.episode_number as i16,
+            cascade,
+            series_wide
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| internal("resolve episodes", e))?;
+
+        let episode_ids: Vec<Uuid> =
+            references.iter().map(|reference| reference.id).collect();
+        let mut identities: BTreeMap<(i16, i16), Option<Uuid>> =
+            BTreeMap::new();
+        for reference in &references {
+            identities
+                .entry((reference.season_number, reference.episode_number))
+                .or_insert(Some(reference.id));
+        }
+        // The reported episode is recorded even without a file on disk.
+        identities
+            .entry((key.season_number as i16, key.episode_number as i16))
+            .or_insert(None);
+
+        sqlx::query!(
+            r#"
+            DELETE FROM user_watch_progress
+            WHERE user_id = $1 AND media_uuid = ANY($2)
+            "#,
+            user_id,
+            &episode_ids
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| internal("clear episode progress", e))?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO user_completed_media (user_id, media_uuid, media_type, completed_at)
+            SELECT $1, id, $3, $4
+            FROM UNNEST($2::uuid[]) AS id
+            ON CONFLICT (user_id, media_uuid) DO NOTHING
+            "#,
+            user_id,
+            &episode_ids,
+            VideoMediaType::Episode as i16,
+            now
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| internal("mark episodes completed", e))?;
+
+        let mut seasons = Vec::with_capacity(identities.len());
+        let mut episodes = Vec::with_capacity(identities.len());
+        let mut media = Vec::with_capacity(identities.len());
+        for ((season, episode), media_uuid) in identities {
+            seasons.push(season);
+            episodes.push(episode);
+            media.push(media_uuid);
+        }
+        let completed = sqlx::query!(
+            r#"
+            INSERT INTO user_episode_state (
+                user_id, tmdb_series_id, season_number, episode_number,
+                position, duration, last_watched, is_completed, last_media_uuid
+            )
+            SELECT $1, $2, season_number, episode_number, 1.0, 1.0, $3, true, media_uuid
+            FROM UNNEST($4::smallint[], $5::smallint[], $6::uuid[])
+                AS t(season_number, episode_number, media_uuid)
+            ON CONFLICT (user_id, tmdb_series_id, season_number, episode_number)
+            DO UPDATE SET
+                position = user_episode_state.duration,
+                is_completed = true,
+                last_watched = GREATEST(user_episode_state.last_watched, EXCLUDED.last_watched),
+                last_media_uuid = COALESCE(user_episode_state.last_media_uuid, EXCLUDED.last_media_uuid)
+            WHERE NOT user_episode_state.is_completed
+            "#,
+            user_id,
+            key.tmdb_series_id as i64,
+            now,
+            &seasons,
+            &episodes,
+            &media as &[Option<Uuid>]
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| internal("mark episode identities completed", e))?
+        .rows_affected();
+
+        tx.commit()
+            .await
+            .map_err(|e| internal("commit transaction", e))?;
+
+        info!(
+            "User {} marked S{:02}E{:02} of series {} watched ({:?}); {} episodes completed",
+            user_id,
+            key.season_number,
+            key.episode_number,
+            key.tmdb_series_id,
+            request.policy,
+            completed
+        );
+
+        Ok(completed as u32)
+    }
+
+    async fn set_scope_watched(
+        &self,
+        user_id: Uuid,
+        scope: WatchScope,
+        watched: bool,
+    ) -> Result<BulkWatchUpdate> {
+        let now = Utc::now().timestamp_millis();
+        let internal = |what: &str, e: sqlx::Error| {
+            MediaError::Internal(format!("Failed to {what}: {e}"))
+        };
+
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(|e| internal("start transaction", e))?;
+
+        let (series_id, season_id, tmdb_series_id) = match scope {
+            WatchScope::Series(series_id) => {
+                let tmdb_id = sqlx::query_scalar!(
+                    r#"SELECT COALESCE(tmdb_id, 0) AS "tmdb_id!" FROM series WHERE id = $1"#,
+                    series_id.to_uuid()
+                )
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| internal("resolve series", e))?
+                .ok_or_else(|| {
+                    MediaError::NotFound(format!("Series {series_id} not found"))
+                })?;
+                (series_id, None, tmdb_id)
+            }
+            WatchScope::Season(season_id) => {
+                let row = sqlx::query!(
+                    r#"
+                    SELECT series_id, tmdb_series_id
+                    FROM season_references
+                    WHERE id = $1
+                    "#,
+                    season_id.to_uuid()
+                )
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| internal("resolve season", e))?
+                .ok_or_else(|| {
+                    MediaError::NotFound(format!(
+                        "Season {season_id} not found"
+                    ))
+                })?;
+                (SeriesID(row.series_id), Some(season_id), row.tmdb_series_id)
+            }
+        };
+        let series_uuid = series_id.to_uuid();
+        let season_uuid = season_id.map(|id| id.to_uuid());
+
+        let episodes = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM episode_references
+            WHERE series_id = $1 AND ($2::uuid IS NULL OR season_id = $2)
+            "#,
+            series_uuid,
+            season_uuid
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| internal("count episodes", e))?;
+
+        // Per-file progress is superseded either way: watched items are
+        // completed and unwatched items start over.
+        sqlx::query!(
+            r#"
+            DELETE FROM user_watch_progress uwp
+            USING episode_references er
+            WHERE uwp.user_id = $1
+              AND uwp.media_uuid = er.id
+              AND er.series_id = $2
+              AND ($3::uuid IS NULL OR er.season_id = $3)
+            "#,
+            user_id,
+            series_uuid,
+            season_uuid
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| internal("clear episode progress", e))?;
+
+        if watched {
+            sqlx::query!(
+                r#"
+                INSERT INTO user_completed_media (user_id, media_uuid, media_type, completed_at)
+                SELECT $1, er.id, $4, $5
+                FROM episode_references er
+                WHERE er.series_id = $2 AND ($3::uuid IS NULL OR er.season_id = $3)
+                ON CONFLICT (user_id, media_uuid) DO UPDATE
+                SET completed_at = EXCLUDED.completed_at
+                "#,
+                user_id,
+                series_uuid,
+                season_uuid,
+                VideoMediaType::Episode as i16,
+                now
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| internal("mark episodes completed", e))?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO user_episode_state (
+                    user_id, tmdb_series_id, season_number, episode_number,
+                    position, duration, last_watched, is_completed, last_media_uuid
+                )
+                SELECT $1, er.tmdb_series_id, er.season_number, er.episode_number,
+                       1.0, 1.0, $4, true, er.id
+                FROM episode_references er
+                WHERE er.series_id = $2 AND ($3::uuid IS NULL OR er.season_id = $3)
+                ON CONFLICT (user_id, tmdb_series_id, season_number, episode_number)
+                DO UPDATE SET
+                    position = user_episode_state.duration,
+                    is_completed = true,
+                    last_watched = GREATEST(user_episode_state.last_watched, EXCLUDED.last_watched)
+                "#,
+                user_id,
+                series_uuid,
+                season_uuid,
+                now
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| internal("mark episode identities completed", e))?;
+        } else {
+            sqlx::query!(
+                r#"
+                DELETE FROM user_completed_media ucm
+                USING episode_references er
+                WHERE ucm.user_id = $1
+                  AND ucm.media_uuid = er.id
+                  AND er.series_id = $2
+                  AND ($3::uuid IS NULL OR er.season_id = $3)
+                "#,
+                user_id,
+                series_uuid,
+                season_uuid
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| internal("clear completed episodes", e))?;
+
+            sqlx::query!(
+                r#"
+                DELETE FROM user_episode_state ues
+                USING episode_references er
+                WHERE ues.user_id = $1
+                  AND ues.tmdb_series_id = er.tmdb_series_id
+                  AND ues.season_number = er.season_number
+                  AND ues.episode_number = er.episode_number
+                  AND er.series_id = $2
+                  AND ($3::uuid IS NULL OR er.season_id = $3)
+                "#,
+                user_id,
+                series_uuid,
+                season_uuid
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| internal("clear episode identities", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| internal("commit transaction", e))?;
+
+        info!(
+            "User {} marked {} episodes of series {} {}",
+            user_id,
+            episodes,
+            series_id,
+            if watched { "watched" } else { "unwatched" }
+        );
+
+        Ok(BulkWatchUpdate {
+            series_id,
+            season_id,
+            tmdb_series_id: tmdb_series_id as u64,
+            watched,
+            episodes: episodes as u32,
+        })
+    }
+
+    async fn export_watch_state(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<WatchStateEntry>> {
+        let internal = |what: &str, e: sqlx::Error| {
+            MediaError::Internal(format!("Failed to {what}: {e}"))
+        };
+
+        // Completions carry no duration, so they export as 1.0 / 1.0 like
+        // the identity rows written by mark-watched.
+        let movies = sqlx::query_as::<_, (i64, String, f32, f32, bool, i64)>(
+            r#"
+            SELECT mr.tmdb_id, mf.file_path, p.position, p.duration, false, p.last_watched
+            FROM user_watch_progress p
+            JOIN movie_references mr ON mr.id = p.media_uuid
+            JOIN media_files mf ON mf.id = mr.file_id
+            WHERE p.user_id = $1
+            UNION ALL
+            SELECT mr.tmdb_id, mf.file_path, 1.0::real, 1.0::real, true, c.completed_at
+            FROM user_completed_media c
+            JOIN movie_references mr ON mr.id = c.media_uuid
+            JOIN media_files mf ON mf.id = mr.file_id
+            WHERE c.user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(self.pool())
+        .await
+        .map_err(|e| internal("export movie watch state", e))?;
+
+        // Identity rows outlive the episode files, so episodes that were
+        // removed since are still exported.
+        let episodes =
+            sqlx::query_as::<_, (i64, i16, i16, f32, f32, bool, i64)>(
+                r#"
+            SELECT er.tmdb_series_id, er.season_number, er.episode_number,
+                   p.position, p.duration, false, p.last_watched
+            FROM user_watch_progress p
+            JOIN episode_references er ON er.id = p.media_uuid
+            WHERE p.user_id = $1
+            UNION ALL
+            SELECT er.tmdb_series_id, er.season_number, er.episode_number,
+                   1.0::real, 1.0::real, true, c.completed_at
+            FROM user_completed_media c
+            JOIN episode_references er ON er.id = c.media_uuid
+            WHERE c.user_id = $1
+            UNION ALL
+            SELECT tmdb_series_id, season_number, episode_number,
+                   position, duration, is_completed, last_watched
+            FROM user_episode_state
+            WHERE user_id = $1
+            "#,
+            )
+            .bind(user_id)
+            .fetch_all(self.pool())
+            .await
+            .map_err(|e| internal("export episode watch state", e))?;
+
+        let movies = movies.into_iter().map(
+            |(tmdb_id, path, position, duration, completed, last_watched)| {
+                WatchStateEntry {
+                    item: if tmdb_id == 0 {
+                        PortableMediaKey::File { path }
+                    } else {
+                        PortableMediaKey::Movie {
+                            tmdb_id: tmdb_id as u64,
+                        }
+                    },
+                    position,
+                    duration,
+                    completed,
+                    last_watched,
+                }
+            },
+        );
+        let episodes = episodes.into_iter().map(
+            |(
+                tmdb_series_id,
+                season_number,
+                episode_number,
+                position,
+                duration,
+                completed,
+                last_watched,
+            )| WatchStateEntry {
+                item: PortableMediaKey::Episode {
+                    tmdb_series_id: tmdb_series_id as u64,
+                    season_number: season_number as u16,
+                    episode_number: episode_number as u16,
+                },
+                position,
+                duration,
+                completed,
+                last_watched,
+            },
+        );
+        Ok(movies.chain(episodes).collect())
+    }
+
+    async fn import_watch_state(
+        &self,
+        user_id: Uuid,
+        entries: &[WatchStateEntry],
+    ) -> Result<WatchStateImportReport> {
+        let internal = |what: &str, e: sqlx::Error| {
+            MediaError::Internal(format!("Failed to {what}: {e}"))
+        };
+        let mut report = WatchStateImportReport::default();
+
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(|e| internal("start transaction", e))?;
+
+        for entry in entries {
+            if let Err(reason) = entry.validate() {
+                report.skip(&entry.item, reason);
+                continue;
+            }
+
+            let (media_type, targets) = match &entry.item {
+                PortableMediaKey::Movie { tmdb_id } => (
+                    VideoMediaType::Movie,
+                    sqlx::query_scalar::<_, Uuid>(
+                        r#"
+                        SELECT id FROM movie_references
+                        WHERE tmdb_id = $1 AND tmdb_id <> 0
+                        ORDER BY discovered_at, id
+                        "#,
+                    )
+                    .bind(*tmdb_id as i64)
+                    .fetch_all(&mut *tx)
+                    .await,
+                ),
+                PortableMediaKey::Episode {
+                    tmdb_series_id,
+                    season_number,
+                    episode_number,
+                } => (
+                    VideoMediaType::Episode,
+                    sqlx::query_scalar::<_, Uuid>(
+                        r#"
+                        SELECT id FROM episode_references
+                        WHERE tmdb_series_id = $1 AND season_number = $2 AND episode_number = $3
+                        ORDER BY discovered_at, id
+                        "#,
+                    )
+                    .bind(*tmdb_series_id as i64)
+                    .bind(*season_number as i16)
+                    .bind(*episode_number as i16)
+                    .fetch_all(&mut *tx)
+                    .await,
+                ),
+                PortableMediaKey::File { path } => (
+                    VideoMediaType::Movie,
+                    sqlx::query_scalar::<_, Uuid>(
+                        r#"
+                        SELECT mr.id
+                        FROM movie_references mr
+                        JOIN media_files mf ON mf.id = mr.file_id
+                        WHERE mf.file_path = $1
+                        "#,
+                    )
+                    .bind(path)
+                    .fetch_all(&mut *tx)
+                    .await,
+                ),
+            };
+            let targets =
+                targets.map_err(|e| internal("resolve imported item", e))?;
+            let Some(&first) = targets.first() else {
+                report.skip(&entry.item, "not found in any library");
+                continue;
+            };
+
+            let mut changed = false;
+            for media_uuid in targets {
+                changed |= Self::import_file_state(
+                    &mut *tx, user_id, media_uuid, media_type, entry,
+                )
+                .await?;
+            }
+            if let PortableMediaKey::Episode {
+                tmdb_series_id,
+                season_number,
+                episode_number,
+            } = entry.item
+            {
+                let key = EpisodeKey {
+                    tmdb_series_id,
+                    season_number,
+                    episode_number,
+                };
+                changed |= Self::import_episode_state(
+                    &mut *tx, user_id, &key, first, entry,
+                )
+                .await?;
+            }
+            report.record(changed);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| internal("commit transaction", e))?;
+
+        info!(
+            "User {} imported watch state: {} imported, {} kept, {} skipped",
+            user_id,
+            report.imported,
+            report.kept_existing,
+            report.skipped.len()
+        );
+
+        Ok(report)
+    }
 }
 
 impl PostgresWatchStatusRepository {
+    /// Apply an imported entry to one file's progress or completion.
+    /// Returns whether the stored state moved.
+    async fn import_file_state(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        media_uuid: Uuid,
+        media_type: VideoMediaType,
+        entry: &WatchStateEntry,
+    ) -> Result<bool> {
+        let internal = |what: &str, e: sqlx::Error| {
+            MediaError::Internal(format!("Failed to {what}: {e}"))
+        };
+
+        let position = sqlx::query_scalar::<_, f32>(
+            r#"
+            SELECT position FROM user_watch_progress
+            WHERE user_id = $1 AND media_uuid = $2
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .bind(media_uuid)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| internal("load watch progress", e))?;
+        let completed = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM user_completed_media
+                WHERE user_id = $1 AND media_uuid = $2
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(media_uuid)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| internal("load completion state", e))?;
+
+        if !entry.supersedes(completed, position) {
+            return Ok(false);
+        }
+
+        if entry.is_completed() {
+            sqlx::query(
+                r#"
+                INSERT INTO user_completed_media (user_id, media_uuid, media_type, completed_at)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (user_id, media_uuid) DO NOTHING
+                "#,
+            )
+            .bind(user_id)
+            .bind(media_uuid)
+            .bind(media_type as i16)
+            .bind(entry.last_watched)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| internal("import completion", e))?;
+            sqlx::query(
+                r#"
+                DELETE FROM user_watch_progress
+                WHERE user_id = $1 AND media_uuid = $2
+                "#,
+            )
+            .bind(user_id)
+            .bind(media_uuid)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| internal("clear imported progress", e))?;
+        } else {
+            // The export's watch time stands in for the report time, so
+            // later reports from players still order against it.
+            sqlx::query(
+                r#"
+                INSERT INTO user_watch_progress (
+                    user_id, media_uuid, media_type, position, duration, last_watched, updated_at, reported_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $6)
+                ON CONFLICT (user_id, media_uuid) DO UPDATE SET
+                    position = EXCLUDED.position,
+                    duration = EXCLUDED.duration,
+                    last_watched = GREATEST(user_watch_progress.last_watched, EXCLUDED.last_watched),
+                    updated_at = EXCLUDED.updated_at,
+                    reported_at = GREATEST(user_watch_progress.reported_at, EXCLUDED.reported_at)
+                "#,
+            )
+            .bind(user_id)
+            .bind(media_uuid)
+            .bind(media_type as i16)
+            .bind(entry.position)
+            .bind(entry.duration)
+            .bind(entry.last_watched)
+            .bind(Utc::now().timestamp_millis())
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| internal("import watch progress", e))?;
+        }
+        Ok(true)
+    }
+
+    /// Apply an imported entry to the identity-based state of an episode.
+    /// Returns whether the stored state moved.
+    async fn import_episode_state(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        key: &EpisodeKey,
+        media_uuid: Uuid,
+        entry: &WatchStateEntry,
+    ) -> Result<bool> {
+        let internal = |what: &str, e: sqlx::Error| {
+            MediaError::Internal(format!("Failed to {what}: {e}"))
+        };
+
+        let current = sqlx::query_as::<_, (f32, bool)>(
+            r#"
+            SELECT position, is_completed FROM user_episode_state
+            WHERE user_id = $1 AND tmdb_series_id = $2 AND season_number = $3 AND episode_number = $4
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .bind(key.tmdb_series_id as i64)
+        .bind(key.season_number as i16)
+        .bind(key.episode_number as i16)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| internal("load episode identity state", e))?;
+        let completed = current.is_some_and(|(_, completed)| completed);
+        if !entry.supersedes(completed, current.map(|(position, _)| position)) {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_episode_state (
+                user_id, tmdb_series_id, season_number, episode_number,
+                position, duration, last_watched, is_completed, last_media_uuid
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9)
+            ON CONFLICT (user_id, tmdb_series_id, season_number, episode_number)
+            DO UPDATE SET
+                position = EXCLUDED.position,
+                duration = EXCLUDED.duration,
+                last_watched = GREATEST(user_episode_state.last_watched, EXCLUDED.last_watched),
+                is_completed = EXCLUDED.is_completed,
+                last_media_uuid = COALESCE(user_episode_state.last_media_uuid, EXCLUDED.last_media_uuid)
+            "#,
+        )
+        .bind(user_id)
+        .bind(key.tmdb_series_id as i64)
+        .bind(key.season_number as i16)
+        .bind(key.episode_number as i16)
+        .bind(entry.position)
+        .bind(entry.duration)
+        .bind(entry.last_watched)
+        .bind(entry.is_completed())
+        .bind(media_uuid)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| internal("import episode identity state", e))?;
+        Ok(true)
+    }
+
     async fn lookup_playable_episode(
         &self,
         key: &EpisodeKey,
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::api::types::{Collection, CollectionItem, UpdateCollectionRequest};
+use crate::error::Result;
+use crate::types::media_id::MediaID;
+
+/// Per-user collections of movies and episodes. Every call is scoped to
+/// `user_id`; another user's collection behaves as if it did not exist.
+#[async_trait]
+pub trait CollectionsRepository: Send + Sync {
+    /// The user's collections ordered by name.
+    async fn list_collections(&self, user_id: Uuid) -> Result<Vec<Collection>>;
+
+    async fn get_collection(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+    ) -> Result<Option<Collection>>;
+
+    /// Fails with `Conflict` when the user already has a collection of that
+    /// name (case-insensitively).
+    async fn create_collection(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<Collection>;
+
+    async fn update_collection(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+        update: &UpdateCollectionRequest,
+    ) -> Result<Collection>;
+
+    /// Returns whether a collection was deleted.
+    async fn delete_collection(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+    ) -> Result<bool>;
+
+    /// Append movies and episodes in the given order, skipping media that
+    /// is already a member. Returns how many were added.
+    async fn add_items(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+        media: &[MediaID],
+    ) -> Result<u64>;
+
+    /// Remove the movie or episode with id `media_id`. Returns whether it
+    /// was a member.
+    async fn remove_item(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<bool>;
+
+    /// Reorder the members to match `media`, which must list every member
+    /// exactly once; anything else fails with `Conflict`.
+    async fn reorder_items(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+        media: &[MediaID],
+    ) -> Result<()>;
+
+    /// The members in order, with titles and the user's watch state.
+    async fn list_items(
+        &self,
+        user_id: Uuid,
+        collection_id: Uuid,
+    ) -> Result<Vec<CollectionItem>>;
+}
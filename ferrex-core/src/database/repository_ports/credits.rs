@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::api::types::{MediaCredits, PersonAppearances};
+use crate::error::Result;
+use crate::types::ids::LibraryId;
+
+/// Read access to the cast and crew stored with TMDB metadata.
+#[async_trait]
+pub trait CreditsRepository: Send + Sync {
+    /// Cast and key crew of the movie, series or episode with this id.
+    /// `None` when there is no such media; media without metadata has
+    /// empty lists.
+    async fn media_credits(&self, id: Uuid) -> Result<Option<MediaCredits>>;
+
+    /// Movies and series crediting the person, optionally limited to one
+    /// library. `None` when the person is unknown.
+    async fn person_appearances(
+        &self,
+        person_id: Uuid,
+        library_id: Option<LibraryId>,
+    ) -> Result<Option<PersonAppearances>>;
+}
@@ -52,6 +52,23 @@ pub struct ImgDbLookup<'a> {
     pub lang: Option<&'a str>,
 }
 
+/// Cache key and content integrity of one `cached_images` row.
+#[derive(Debug, Clone)]
+pub struct CachedImageRef {
+    pub cache_key: String,
+    pub integrity: String,
+}
+
+/// A movie or series without a stored theme color whose primary poster
+/// already has at least one cached size.
+#[derive(Debug, Clone)]
+pub struct ThemeColorCandidate {
+    pub media_id: Uuid,
+    pub poster_iid: Uuid,
+    /// Cache key of the smallest cached poster size.
+    pub cache_key: String,
+}
+
 /// Repository port for image persistence and media-image associations.
 ///
 /// This port intentionally uses typed `ImageType` where appropriate to avoid
@@ -62,6 +79,46 @@ pub trait ImageRepository: Send + Sync {
     // Maintenance
     async fn cleanup_orphaned_images(&self) -> Result<u32>;
 
+    /// Every cached image size the database knows about; blobs on disk not
+    /// covered by this list are orphans.
+    async fn list_cached_image_refs(&self) -> Result<Vec<CachedImageRef>>;
+
+    /// Page through media of `media_type` (movies or series) lacking a
+    /// theme color, ordered by id and starting after `after`.
+    async fn list_theme_color_candidates(
+        &self,
+        media_type: ImageMediaType,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<ThemeColorCandidate>>;
+
+    /// Store `theme_color` on a movie or series unless one is already set.
+    /// Returns whether a row was updated.
+    async fn update_media_theme_color(
+        &self,
+        media_type: ImageMediaType,
+        media_id: Uuid,
+        theme_color: &str,
+    ) -> Result<bool>;
+
+    /// Placeholder bytes stored for an image, if one was computed.
+    async fn image_placeholder(&self, iid: Uuid) -> Result<Option<Vec<u8>>>;
+
+    /// Store `placeholder` on an image unless one is already set.
+    /// Returns whether a row was updated.
+    async fn store_image_placeholder(
+        &self,
+        iid: Uuid,
+        placeholder: &[u8],
+    ) -> Result<bool>;
+
+    /// Delete every cached size of the images belonging to `media_id` and
+    /// clear their placeholders. Returns the cache keys of the removed rows.
+    async fn delete_cached_images_for_media(
+        &self,
+        media_id: Uuid,
+    ) -> Result<Vec<String>>;
+
     // Variant lookups
     /// Unified image variant query from struct of optionals
     async fn lookup_original_image<'a>(
@@ -77,6 +134,22 @@ pub trait ImageRepository: Send + Sync {
         imz: ImageSize,
     ) -> Result<Vec<OriginalImage>>;
 
+    /// Every image linked to `media_id`, of all variants, each variant's
+    /// primary first and the rest by votes.
+    async fn list_media_images(
+        &self,
+        media_id: Uuid,
+    ) -> Result<Vec<OriginalImage>>;
+
+    /// Make `iid` the only primary image of its variant for `media_id` and
+    /// point the media's metadata at it. Fails with `NotFound` when `iid` is
+    /// not linked to `media_id`.
+    async fn set_primary_image(
+        &self,
+        media_id: Uuid,
+        iid: Uuid,
+    ) -> Result<OriginalImage>;
+
     /// Get from image id; preferred method
     async fn lookup_variant_by_iid(
         &self,
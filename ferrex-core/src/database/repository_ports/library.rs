@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 
+use crate::api::types::{LibraryStats, LibraryStorageUsage};
 use crate::error::Result;
 use crate::types::details::LibraryReference;
 use crate::types::ids::LibraryId;
@@ -41,4 +42,24 @@ pub trait LibraryRepository: Send + Sync {
         &self,
         id: uuid::Uuid,
     ) -> Result<LibraryReference>;
+
+    /// Aggregate item, file, poster and metadata counts for a library in a
+    /// single query. Returns `None` if the library does not exist.
+    ///
+    /// `missing_files` is left unset; on-disk state is owned by the
+    /// integrity verifier.
+    async fn library_stats(
+        &self,
+        id: LibraryId,
+    ) -> Result<Option<LibraryStats>>;
+
+    /// Stored file sizes, cached image bytes and the files discovered in
+    /// the last `window_days` for every library.
+    ///
+    /// `transcode_cache_size` is left at zero; transcode output is tracked
+    /// in memory by the server.
+    async fn storage_usage(
+        &self,
+        window_days: u32,
+    ) -> Result<Vec<LibraryStorageUsage>>;
 }
@@ -5,6 +5,7 @@ use crate::{
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::types::files::MediaFile;
@@ -81,6 +82,17 @@ pub struct UpsertOutcome {
     pub created: bool,
 }
 
+/// Digest of the head and tail samples library verification took from a
+/// media file, along with the sample size and file version it describes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaFileDigest {
+    pub file_id: Uuid,
+    pub sample_bytes: u32,
+    pub file_size: u64,
+    pub modified_at: DateTime<Utc>,
+    pub digest: Vec<u8>,
+}
+
 #[async_trait]
 pub trait MediaFilesReadPort: Send + Sync {
     async fn get_by_id(&self, id: &Uuid) -> Result<Option<MediaFile>>;
@@ -97,6 +109,11 @@ pub trait MediaFilesReadPort: Send + Sync {
         page: Page,
     ) -> Result<Vec<MediaFile>>;
     async fn stats(&self, filter: MediaFileFilter) -> Result<MediaStats>;
+    /// Recorded verification digests of the given files, keyed by file id.
+    async fn digests(
+        &self,
+        file_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, MediaFileDigest>>;
 }
 
 #[async_trait]
@@ -128,6 +145,8 @@ pub trait MediaFilesWritePort: Send + Sync {
         id: Uuid,
         metadata: &MediaFileMetadata,
     ) -> Result<()>;
+    /// Record `digest`, replacing the file's previous one.
+    async fn record_digest(&self, digest: MediaFileDigest) -> Result<()>;
     async fn move_by_path(
         &self,
         _library_id: LibraryId,
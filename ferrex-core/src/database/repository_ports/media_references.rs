@@ -1,8 +1,12 @@
+use std::path::PathBuf;
+
 use async_trait::async_trait;
 use ferrex_model::MediaID;
 
 use crate::{
+    api::types::{DuplicateMedia, MediaCopy},
     error::Result,
+    query::types::EpisodeSort,
     types::{
         EpisodeID, EpisodeReference, LibraryId, Media, MovieBatchId, MovieID,
         MovieReference, SeasonID, SeasonReference, Series, SeriesID,
@@ -16,6 +20,16 @@ pub struct TvReferenceOrphanCleanup {
     pub deleted_series: u64,
 }
 
+/// What deleting one movie, series, season or episode removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaRemoval {
+    /// Every reference removed: the requested one, the episodes below it
+    /// and any season or series left without episodes.
+    pub removed: Vec<MediaID>,
+    /// Paths of the media file records that were deleted.
+    pub files: Vec<PathBuf>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MovieBatchVersionRecord {
     pub batch_id: MovieBatchId,
@@ -177,9 +191,11 @@ pub trait MediaReferencesRepository: Send + Sync {
         &self,
         series_id: &SeriesID,
     ) -> Result<Vec<EpisodeReference>>;
+    /// Episodes of a season in the requested order.
     async fn get_season_episodes(
         &self,
         season_id: &SeasonID,
+        sort: EpisodeSort,
     ) -> Result<Vec<EpisodeReference>>;
 
     async fn get_library_media_references(
@@ -251,6 +267,42 @@ pub trait MediaReferencesRepository: Send + Sync {
         tmdb_id: u64,
     ) -> Result<()>;
 
+    /// Oldest movie matched to `tmdb_id` in any library. Copies of a movie
+    /// in other libraries share its image variants.
+    async fn canonical_movie_id(&self, tmdb_id: u64)
+    -> Result<Option<MovieID>>;
+    /// Every library's copy of the movie `id`, canonical copy first.
+    async fn list_movie_copies(&self, id: &MovieID) -> Result<Vec<MediaCopy>>;
+    /// Movies of `library_id` that are also present in another library.
+    async fn list_duplicate_movies(
+        &self,
+        library_id: LibraryId,
+    ) -> Result<Vec<DuplicateMedia>>;
+
+    /// TMDB id an operator pinned a movie or series to, if any.
+    async fn get_tmdb_id_override(&self, id: &MediaID) -> Result<Option<u64>>;
+    /// Pin a movie or series to `tmdb_id` so rescans keep the operator's
+    /// match; `None` returns it to filename matching.
+    async fn set_tmdb_id_override(
+        &self,
+        id: &MediaID,
+        tmdb_id: Option<u64>,
+    ) -> Result<()>;
+
+    /// Language preference key the stored movie or series metadata was
+    /// fetched with, or `None` when it was never recorded.
+    async fn get_metadata_language(
+        &self,
+        id: &MediaID,
+    ) -> Result<Option<String>>;
+    /// Record the language preference key for stored movie or series
+    /// metadata.
+    async fn set_metadata_language(
+        &self,
+        id: &MediaID,
+        language: &str,
+    ) -> Result<()>;
+
     /// Remove orphan series/seasons that no longer have any episode references.
     ///
     /// This is useful when callers perform targeted media file deletions (e.g.
@@ -260,4 +312,14 @@ pub trait MediaReferencesRepository: Send + Sync {
         &self,
         library_id: LibraryId,
     ) -> Result<TvReferenceOrphanCleanup>;
+
+    /// Delete media along with their files' records and their users' watch
+    /// state, in one transaction for the whole batch.
+    ///
+    /// Each id succeeds or fails on its own; a failed id leaves nothing
+    /// behind. A season or series is only removed with its last episode.
+    async fn delete_media_batch(
+        &self,
+        ids: &[MediaID],
+    ) -> Result<Vec<Result<MediaRemoval>>>;
 }
@@ -3,6 +3,8 @@
 //!
 //! Implementations live in the Postgres adapter under `database::infra::postgres`.
 
+pub mod collections;
+pub mod credits;
 pub mod file_watch;
 pub mod folder_inventory;
 pub mod images;
@@ -10,6 +12,7 @@ pub mod indices;
 pub mod library;
 pub mod media_files;
 pub mod media_references;
+pub mod music_tracks;
 pub mod processing_status;
 pub mod query;
 pub mod rbac;
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::api::types::{MusicAlbum, MusicTrack, MusicTrackQuery};
+use crate::error::Result;
+use crate::types::ids::LibraryId;
+
+/// Outcome of storing the tracks found by a music library scan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MusicTrackSync {
+    /// Tracks inserted or refreshed.
+    pub stored: u64,
+    /// Tracks of the library that the scan no longer found.
+    pub removed: u64,
+}
+
+#[async_trait]
+pub trait MusicTracksRepository: Send + Sync {
+    /// Store the tracks found by a full scan of `library_id` and drop the
+    /// library's other tracks. Tracks already known by path keep their id.
+    async fn replace_library_tracks(
+        &self,
+        library_id: LibraryId,
+        tracks: &[MusicTrack],
+    ) -> Result<MusicTrackSync>;
+
+    async fn list_tracks(
+        &self,
+        library_id: LibraryId,
+        query: &MusicTrackQuery,
+    ) -> Result<Vec<MusicTrack>>;
+
+    async fn list_albums(
+        &self,
+        library_id: LibraryId,
+        artist: Option<&str>,
+    ) -> Result<Vec<MusicAlbum>>;
+
+    async fn get_track(&self, id: Uuid) -> Result<Option<MusicTrack>>;
+}
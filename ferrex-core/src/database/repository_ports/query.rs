@@ -3,9 +3,12 @@ use crate::{
     error::Result,
     query::{
         prelude::{SearchQuery, SortCriteria},
-        types::{MediaQuery, MediaWithStatus},
+        types::{
+            MediaQuery, MediaSearchHit, MediaSearchQuery, MediaWithStatus,
+            RecentlyAddedCursor, RecentlyAddedPage, SearchField,
+        },
     },
-    types::{EpisodeID, MovieID},
+    types::{EpisodeID, LibraryId, MovieID},
 };
 
 use async_trait::async_trait;
@@ -54,6 +57,25 @@ pub trait QueryRepository: Send + Sync {
         query: &MediaQuery,
     ) -> Result<Vec<MediaWithStatus>>;
 
+    /// Movies and series newest first by when they entered the database.
+    /// A series is dated by its most recently added episode.
+    async fn query_recently_added(
+        &self,
+        library_id: Option<LibraryId>,
+        limit: usize,
+        cursor: Option<RecentlyAddedCursor>,
+    ) -> Result<RecentlyAddedPage>;
+
+    /// Movies, series and episodes matching `query.q` in `fields`, best
+    /// first. Titles are matched fuzzily; overviews by full-text search
+    /// and cast by trigram similarity of the person's name.
+    async fn search_media(
+        &self,
+        query: &MediaSearchQuery,
+        fields: &[SearchField],
+        limit: usize,
+    ) -> Result<Vec<MediaSearchHit>>;
+
     async fn query_media_by_watch_status(
         &self,
         query: &MediaQuery,
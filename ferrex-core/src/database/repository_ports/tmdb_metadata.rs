@@ -1,10 +1,11 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt,
+    path::Path,
 };
 
 use chrono::{DateTime, Utc};
-use ferrex_model::MediaID;
+use ferrex_model::{MediaID, certification_age};
 use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
@@ -14,7 +15,9 @@ use crate::database::repository_ports::tmdb_metadata_bulk::{
     load_season_details_bulk, load_series_details_bulk,
 };
 use crate::{
+    api::types::is_key_crew_job,
     error::{MediaError, Result},
+    infra::media::metadata::FilenameParser,
     traits::prelude::MediaIDLike,
     types::{
         VideoMediaType,
@@ -32,7 +35,10 @@ use crate::{
             EpisodeID, LibraryId, MovieBatchId, MovieID, SeasonID, SeriesID,
         },
         image::MediaImages,
-        media::{EpisodeReference, MovieReference, SeasonReference, Series},
+        media::{
+            EpisodeReference, MovieEdition, MovieReference, SeasonReference,
+            Series,
+        },
         numbers::{EpisodeNumber, SeasonNumber},
         titles::{MovieTitle, SeriesTitle},
         urls::{EpisodeURL, MovieURL, SeasonURL, SeriesURL, UrlLike},
@@ -209,11 +215,20 @@ impl<'a> TmdbMetadataRepository<'a> {
         })?;
 
         let actual_file_id = store_media_file(&mut tx, &movie.file).await?;
+        let is_labeled_edition = FilenameParser::new()
+            .extract_edition(&movie.file.path)
+            .is_some();
 
         // `movie.id` may be a newly generated UUID; if a row already exists for
-        // `(tmdb_id, library_id)`, Postgres will keep the existing `id`.
-        let canonical =
-            upsert_tmdb_movie_reference(&mut tx, movie, actual_file_id).await?;
+        // `(tmdb_id, library_id)`, Postgres will keep the existing `id` and the
+        // file becomes another edition of that movie.
+        let canonical = upsert_tmdb_movie_reference(
+            &mut tx,
+            movie,
+            actual_file_id,
+            is_labeled_edition,
+        )
+        .await?;
 
         // Ensure the `media_files.media_id` matches the canonical movie reference id.
         // This prevents downstream components (indexing, watch state, etc.) from
@@ -424,6 +439,10 @@ impl<'a> TmdbMetadataRepository<'a> {
         })?;
 
         let details = load_movie_details(self.pool, movie_id).await?;
+        let editions = load_movie_editions(self.pool, &[movie_id])
+            .await?
+            .remove(&movie_id)
+            .unwrap_or_default();
 
         Ok(MovieReference {
             id: MovieID(movie_id),
@@ -440,6 +459,7 @@ impl<'a> TmdbMetadataRepository<'a> {
             endpoint: MovieURL::from_string(format!("/stream/{}", file_id)),
             file: media_file,
             theme_color,
+            editions,
         })
     }
 
@@ -555,6 +575,8 @@ impl<'a> TmdbMetadataRepository<'a> {
         let movie_ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
         let mut details_map =
             load_movie_details_bulk(self.pool, &movie_ids).await?;
+        let mut editions_map =
+            load_movie_editions(self.pool, &movie_ids).await?;
 
         let mut movies = Vec::with_capacity(rows.len());
         for row in rows {
@@ -596,6 +618,7 @@ impl<'a> TmdbMetadataRepository<'a> {
                 endpoint: MovieURL::from_string(format!("/stream/{}", file_id)),
                 file: media_file,
                 theme_color,
+                editions: editions_map.remove(&movie_id).unwrap_or_default(),
             });
         }
 
@@ -617,6 +640,8 @@ impl<'a> TmdbMetadataRepository<'a> {
             self.pool, library_id, batch_id, &movie_ids,
         )
         .await?;
+        let mut editions_map =
+            load_movie_editions(self.pool, &movie_ids).await?;
 
         let mut movies = Vec::with_capacity(rows.len());
         for row in rows {
@@ -664,6 +689,7 @@ impl<'a> TmdbMetadataRepository<'a> {
                 endpoint: MovieURL::from_string(format!("/stream/{}", file_id)),
                 file: media_file,
                 theme_color,
+                editions: editions_map.remove(&movie_id).unwrap_or_default(),
             });
         }
 
@@ -944,6 +970,26 @@ async fn persist_movie_metadata(
     .await
     .map_err(|e| MediaError::Internal(format!("Failed to upsert movie metadata: {}", e)))?;
 
+    sqlx::query(
+        "UPDATE movie_metadata SET certification_age = $2 WHERE movie_id = $1",
+    )
+    .bind(mid.to_uuid())
+    .bind(
+        details
+            .content_rating
+            .as_deref()
+            .and_then(certification_age)
+            .map(i16::from),
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        MediaError::Internal(format!(
+            "Failed to store movie certification age: {}",
+            e
+        ))
+    })?;
+
     sync_movie_child_tables(tx, mid.to_uuid(), library_id, batch_id, details)
         .await
 }
@@ -1045,6 +1091,26 @@ async fn persist_series_metadata(
     .await
     .map_err(|e| MediaError::Internal(format!("Failed to upsert series metadata: {}", e)))?;
 
+    sqlx::query(
+        "UPDATE series_metadata SET certification_age = $2 WHERE series_id = $1",
+    )
+    .bind(series_id)
+    .bind(
+        details
+            .content_rating
+            .as_deref()
+            .and_then(certification_age)
+            .map(i16::from),
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        MediaError::Internal(format!(
+            "Failed to store series certification age: {}",
+            e
+        ))
+    })?;
+
     sync_series_child_tables(tx, series_id, details).await
 }
 
@@ -1956,6 +2022,12 @@ async fn sync_movie_people(
                     member.id
                 ))
             })?;
+        // Key crew is listed with the cast, so their portraits are
+        // registered for lazy download the same way.
+        if is_key_crew_job(&job) {
+            ensure_profile_image_id(tx, &mut profile_cache, person_id, member)
+                .await?;
+        }
         sqlx::query!(
             r#"INSERT INTO movie_crew (
                 movie_id, library_id, batch_id,
@@ -2412,6 +2484,12 @@ async fn sync_series_people(
                     member.id
                 ))
             })?;
+        // Key crew is listed with the cast, so their portraits are
+        // registered for lazy download the same way.
+        if is_key_crew_job(&job) {
+            ensure_profile_image_id(tx, &mut profile_cache, person_id, member)
+                .await?;
+        }
         sqlx::query!(
             r#"INSERT INTO series_crew (
                 series_id, person_tmdb_id, person_id, credit_id, department, job
@@ -2939,10 +3017,14 @@ struct CanonicalMovieReference {
     batch_id: i64,
 }
 
+/// Insert or update the movie row for `(tmdb_id, library_id)`. A labeled
+/// edition (`{Extended}`) joining an existing movie never replaces its
+/// primary file; an unlabeled file takes over as primary.
 async fn upsert_tmdb_movie_reference(
     tx: &mut Transaction<'_, Postgres>,
     movie: &MovieReference,
     file_id: Uuid,
+    is_labeled_edition: bool,
 ) -> Result<CanonicalMovieReference> {
     let canonical = sqlx::query_as!(
         CanonicalMovieReferenceRow,
@@ -2950,7 +3032,10 @@ async fn upsert_tmdb_movie_reference(
         INSERT INTO movie_references (id, library_id, file_id, tmdb_id, title, theme_color)
         VALUES ($1, $2, $3, $4, $5, $6)
         ON CONFLICT (tmdb_id, library_id) DO UPDATE SET
-            file_id = EXCLUDED.file_id,
+            file_id = CASE
+                WHEN $7 THEN movie_references.file_id
+                ELSE EXCLUDED.file_id
+            END,
             title = EXCLUDED.title,
             theme_color = EXCLUDED.theme_color,
             updated_at = NOW()
@@ -2961,7 +3046,8 @@ async fn upsert_tmdb_movie_reference(
         file_id,
         movie.tmdb_id as i64,
         movie.title.as_str(),
-        movie.theme_color.as_deref()
+        movie.theme_color.as_deref(),
+        is_labeled_edition
     )
     .fetch_one(&mut **tx)
     .await
@@ -3477,6 +3563,48 @@ async fn load_series_details(
     Ok(details)
 }
 
+/// Files of each movie that has more than one, keyed by movie id. Labels
+/// are parsed from the stored paths; the unlabeled cut sorts first.
+async fn load_movie_editions(
+    pool: &PgPool,
+    movie_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<MovieEdition>>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, media_id, file_path, filename, file_size
+        FROM media_files
+        WHERE media_type = 'movie' AND media_id = ANY($1)
+        ORDER BY media_id, filename
+        "#,
+        movie_ids
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        MediaError::Internal(format!("Failed to load movie editions: {}", e))
+    })?;
+
+    let parser = FilenameParser::new();
+    let mut editions: HashMap<Uuid, Vec<MovieEdition>> = HashMap::new();
+    for row in rows {
+        editions
+            .entry(row.media_id)
+            .or_default()
+            .push(MovieEdition {
+                file_id: row.id,
+                label: parser.extract_edition(Path::new(&row.file_path)),
+                filename: row.filename,
+                size: row.file_size as u64,
+            });
+    }
+
+    editions.retain(|_, files| files.len() > 1);
+    for files in editions.values_mut() {
+        files.sort_by_key(|edition| edition.label.is_some());
+    }
+    Ok(editions)
+}
+
 async fn load_movie_details(
     pool: &PgPool,
     movie_id: Uuid,
@@ -2,8 +2,9 @@ use async_trait::async_trait;
 use uuid::Uuid;
 
 use crate::domain::watch::{
-    EpisodeKey, InProgressItem, NextEpisode, SeasonWatchStatus,
-    SeriesWatchStatus, UpdateProgressRequest, UserWatchState,
+    BulkWatchUpdate, EpisodeKey, InProgressItem, MarkEpisodeWatchedRequest,
+    NextEpisode, SeasonWatchStatus, SeriesWatchStatus, UpdateProgressRequest,
+    UserWatchState, WatchScope, WatchStateEntry, WatchStateImportReport,
 };
 use crate::error::Result;
 
@@ -74,4 +75,45 @@ pub trait WatchStatusRepository: Send + Sync {
         user_id: Uuid,
         key: &EpisodeKey,
     ) -> Result<()>;
+
+    /// Mark the episode at `key` watched in a single transaction, along
+    /// with the earlier episodes a cascade reaches. Returns how many
+    /// episodes were not completed before.
+    async fn mark_episode_watched(
+        &self,
+        user_id: Uuid,
+        key: &EpisodeKey,
+        request: MarkEpisodeWatchedRequest,
+    ) -> Result<u32>;
+
+    /// Mark every episode in `scope` watched or unwatched in a single
+    /// transaction. Watched episodes become completed with their position
+    /// at the end; unwatched episodes lose all progress.
+    async fn set_scope_watched(
+        &self,
+        user_id: Uuid,
+        scope: WatchScope,
+        watched: bool,
+    ) -> Result<BulkWatchUpdate>;
+
+    /// Every progress row, completion and episode state of the user, keyed
+    /// by stable identity. Items without an identity (media that has since
+    /// been removed) are left out; copies of one item appear once each.
+    async fn export_watch_state(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<WatchStateEntry>>;
+
+    /// Apply exported entries to every matching item in a single
+    /// transaction. Stored state is only moved forward (max-position rule);
+    /// entries without a matching item are skipped and reported. Merge
+    /// duplicate entries first (see [`WatchStateExport::new`]) so each item
+    /// is counted once.
+    ///
+    /// [`WatchStateExport::new`]: crate::domain::watch::WatchStateExport::new
+    async fn import_watch_state(
+        &self,
+        user_id: Uuid,
+        entries: &[WatchStateEntry],
+    ) -> Result<WatchStateImportReport>;
 }
@@ -28,6 +28,68 @@ pub enum ScanStatus {
     Completed,
     Failed,
     Cancelled,
+    /// Still unfinished when the server stopped.
+    Interrupted,
+}
+
+impl ScanStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Paused => "paused",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+            Self::Interrupted => "interrupted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "pending" => Self::Pending,
+            "running" => Self::Running,
+            "paused" => Self::Paused,
+            "completed" => Self::Completed,
+            "failed" => Self::Failed,
+            "cancelled" => Self::Cancelled,
+            "interrupted" => Self::Interrupted,
+            _ => return None,
+        })
+    }
+
+    /// Pending, running or paused.
+    pub fn is_unfinished(&self) -> bool {
+        matches!(self, Self::Pending | Self::Running | Self::Paused)
+    }
+}
+
+/// Whether a scan started from scratch or picked up an interrupted one.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanOrigin {
+    #[default]
+    Fresh,
+    Resumed,
+}
+
+impl ScanOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fresh => "fresh",
+            Self::Resumed => "resumed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "fresh" => Some(Self::Fresh),
+            "resumed" => Some(Self::Resumed),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +109,9 @@ pub struct ScanState {
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub options: serde_json::Value,
+    pub correlation_id: Option<Uuid>,
+    pub origin: ScanOrigin,
+    pub resumed_from: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
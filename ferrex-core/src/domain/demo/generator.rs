@@ -55,6 +55,7 @@ pub async fn generate_plan(
                 .unwrap_or_else(|| match library_option.library_type {
                     LibraryType::Movies => format!("Demo Movies {}", idx + 1),
                     LibraryType::Series => format!("Demo Series {}", idx + 1),
+                    LibraryType::Music => format!("Demo Music {}", idx + 1),
                 });
         let name = uniquify_name(base_name, &mut used_names);
         let root_path = root.join(slug_name(&name));
@@ -104,6 +105,12 @@ pub async fn generate_plan(
                     )
                     .await?
             }
+            LibraryType::Music => {
+                return Err(MediaError::InvalidMedia(
+                    "demo libraries are generated from TMDB; music is not supported"
+                        .into(),
+                ));
+            }
         };
 
         let mut plan = structure_to_demo_plan(
@@ -294,6 +301,7 @@ fn apply_deviations(
     match plan.library_type {
         LibraryType::Movies => apply_movie_deviations(plan, rate),
         LibraryType::Series => apply_series_deviations(plan, rate),
+        LibraryType::Music => {}
     }
 }
 
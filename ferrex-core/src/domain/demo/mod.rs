@@ -169,6 +169,7 @@ pub fn library_from_plan(plan: &DemoLibraryPlan) -> Library {
         analyze_on_scan: false,
         max_retry_attempts: 1,
         movie_ref_batch_size: MovieReferenceBatchSize::default(),
+        exclude_patterns: Vec::new(),
         created_at: Utc::now(),
         updated_at: Utc::now(),
         media: None,
@@ -41,6 +41,7 @@ fn allow_zero_length_is_true_only_for_registered_demo_library() {
         analyze_on_scan: false,
         max_retry_attempts: 1,
         movie_ref_batch_size: MovieReferenceBatchSize::default(),
+        exclude_patterns: Vec::new(),
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         media: None,
@@ -0,0 +1,141 @@
+//! Mapping absolute episode numbers onto TMDB seasons.
+//!
+//! Anime is commonly released as `Show - 137` instead of `S03E12`, while
+//! TMDB splits most of those shows into seasons. The absolute number is
+//! walked through the per-season episode counts to find its season and
+//! episode. Anything that would need a guess (unknown counts, gaps in the
+//! season numbering, numbers past the last known episode) is reported as
+//! ambiguous so the file can be reviewed instead of mismatched.
+
+/// Episode count of one season as TMDB lists it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeasonEpisodeCount {
+    pub season: u16,
+    pub episodes: u16,
+}
+
+/// Where an absolute episode number lands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbsoluteMapping {
+    Resolved {
+        season: u16,
+        episode: u16,
+    },
+    /// The layout does not pin the episode down; the reason is for humans.
+    Ambiguous(String),
+}
+
+/// Map `absolute` (1-based) onto `seasons`. Specials (season 0) never count
+/// towards absolute numbering.
+pub fn map_absolute_episode(
+    absolute: u16,
+    seasons: &[SeasonEpisodeCount],
+) -> AbsoluteMapping {
+    if absolute == 0 {
+        return AbsoluteMapping::Ambiguous("absolute episode 0".into());
+    }
+
+    let mut regular: Vec<SeasonEpisodeCount> =
+        seasons.iter().copied().filter(|s| s.season > 0).collect();
+    regular.sort_by_key(|s| s.season);
+    if regular.is_empty() {
+        return AbsoluteMapping::Ambiguous("no regular seasons".into());
+    }
+
+    let mut remaining = absolute;
+    for (expected, season) in (1u16..).zip(&regular) {
+        if season.season != expected {
+            return AbsoluteMapping::Ambiguous(format!(
+                "season {} is missing from the layout",
+                expected
+            ));
+        }
+        if season.episodes == 0 {
+            return AbsoluteMapping::Ambiguous(format!(
+                "season {} has no episode count",
+                season.season
+            ));
+        }
+        if remaining <= season.episodes {
+            return AbsoluteMapping::Resolved {
+                season: season.season,
+                episode: remaining,
+            };
+        }
+        remaining -= season.episodes;
+    }
+
+    AbsoluteMapping::Ambiguous(format!(
+        "absolute episode {} is past the last known episode ({})",
+        absolute,
+        absolute - remaining
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(counts: &[(u16, u16)]) -> Vec<SeasonEpisodeCount> {
+        counts
+            .iter()
+            .map(|&(season, episodes)| SeasonEpisodeCount { season, episodes })
+            .collect()
+    }
+
+    #[test]
+    fn absolute_numbers_walk_through_season_counts() {
+        let seasons = layout(&[(0, 3), (1, 12), (2, 12), (3, 24)]);
+
+        assert_eq!(
+            map_absolute_episode(7, &seasons),
+            AbsoluteMapping::Resolved {
+                season: 1,
+                episode: 7
+            }
+        );
+        assert_eq!(
+            map_absolute_episode(12, &seasons),
+            AbsoluteMapping::Resolved {
+                season: 1,
+                episode: 12
+            }
+        );
+        assert_eq!(
+            map_absolute_episode(13, &seasons),
+            AbsoluteMapping::Resolved {
+                season: 2,
+                episode: 1
+            }
+        );
+        assert_eq!(
+            map_absolute_episode(48, &seasons),
+            AbsoluteMapping::Resolved {
+                season: 3,
+                episode: 24
+            }
+        );
+    }
+
+    #[test]
+    fn incomplete_layouts_are_ambiguous() {
+        let past_end = map_absolute_episode(49, &layout(&[(1, 24), (2, 24)]));
+        assert!(matches!(past_end, AbsoluteMapping::Ambiguous(_)));
+
+        let gap = map_absolute_episode(30, &layout(&[(1, 24), (3, 24)]));
+        assert_eq!(
+            gap,
+            AbsoluteMapping::Ambiguous(
+                "season 2 is missing from the layout".into()
+            )
+        );
+
+        let unknown = map_absolute_episode(30, &layout(&[(1, 24), (2, 0)]));
+        assert!(matches!(unknown, AbsoluteMapping::Ambiguous(_)));
+
+        assert!(matches!(
+            map_absolute_episode(1, &layout(&[(0, 5)])),
+            AbsoluteMapping::Ambiguous(_)
+        ));
+    }
+}
@@ -1,4 +1,8 @@
 //! Media-domain boundary.
 //!
+pub mod absolute_numbering;
 pub mod extras;
+pub mod music_parser;
+pub mod naming_rules;
+pub mod nfo;
 pub mod tv_parser;
@@ -0,0 +1,159 @@
+//! Track details from the folder layout of a music library.
+//!
+//! Music libraries follow the `Artist/Album (Year)/NN - Title.ext` layout
+//! most taggers and rippers write. Disc folders (`CD1`, `Disc 2`) below an
+//! album are folded into the track's disc number. Paths are read relative
+//! to the library root, so a track sitting directly in an artist folder has
+//! no album, and a loose file falls back to an `Artist - Title` file name.
+
+use std::path::{Component, Path};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::types::files::ParsedTrackInfo;
+
+/// Artist recorded for tracks whose layout names none.
+pub const UNKNOWN_ARTIST: &str = "Unknown Artist";
+
+/// `01 - Title`, `01. Title`, `1-03 Title` (disc 1, track 3).
+static NUMBERED: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(\d{1,2})[-.])?(\d{1,3})(?:\s*[-.]\s*|\s+)(.+)$").unwrap()
+});
+static DISC_FOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^(?:cd|disc|disk)\s*(\d{1,2})$").unwrap());
+/// `Album (1997)`, `Album [1997]`
+static YEAR_SUFFIX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(.+?)\s*[(\[]((?:19|20)\d{2})[)\]]$").unwrap());
+/// `1997 - Album`
+static YEAR_PREFIX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^((?:19|20)\d{2})\s*-\s*(.+)$").unwrap());
+
+/// Parse the track at `relative`, a path below the library root.
+pub fn parse_track(relative: &Path) -> Option<ParsedTrackInfo> {
+    let stem = relative.file_stem()?.to_str()?.trim();
+    if stem.is_empty() {
+        return None;
+    }
+    let mut folders: Vec<&str> = relative
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    let disc_folder = folders
+        .last()
+        .and_then(|name| DISC_FOLDER.captures(name))
+        .and_then(|caps| caps[1].parse::<u16>().ok());
+    if disc_folder.is_some() {
+        folders.pop();
+    }
+
+    let (disc, track, mut title) = match NUMBERED.captures(stem) {
+        Some(caps) => (
+            caps.get(1).and_then(|disc| disc.as_str().parse().ok()),
+            caps[2].parse().ok(),
+            caps[3].trim().to_string(),
+        ),
+        None => (None, None, stem.to_string()),
+    };
+
+    let (artist, album) = match folders.as_slice() {
+        [.., artist, album] => ((*artist).to_string(), Some(*album)),
+        [artist] => ((*artist).to_string(), None),
+        [] => match title.split_once(" - ") {
+            Some((artist, rest)) if !artist.trim().is_empty() => {
+                let artist = artist.trim().to_string();
+                title = rest.trim().to_string();
+                (artist, None)
+            }
+            _ => (UNKNOWN_ARTIST.to_string(), None),
+        },
+    };
+    let (album, year) = match album {
+        Some(album) => {
+            let (album, year) = split_album_year(album);
+            (Some(album), year)
+        }
+        None => (None, None),
+    };
+
+    Some(ParsedTrackInfo {
+        artist,
+        album,
+        title,
+        track,
+        disc: disc.or(disc_folder),
+        year,
+    })
+}
+
+fn split_album_year(folder: &str) -> (String, Option<u16>) {
+    if let Some(caps) = YEAR_SUFFIX.captures(folder) {
+        return (caps[1].trim().to_string(), caps[2].parse().ok());
+    }
+    if let Some(caps) = YEAR_PREFIX.captures(folder) {
+        return (caps[2].trim().to_string(), caps[1].parse().ok());
+    }
+    (folder.trim().to_string(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(path: &str) -> ParsedTrackInfo {
+        parse_track(Path::new(path)).unwrap()
+    }
+
+    #[test]
+    fn artist_and_album_come_from_folders() {
+        let track =
+            parse("Radiohead/OK Computer (1997)/02 - Paranoid Android.flac");
+        assert_eq!(track.artist, "Radiohead");
+        assert_eq!(track.album.as_deref(), Some("OK Computer"));
+        assert_eq!(track.year, Some(1997));
+        assert_eq!(track.track, Some(2));
+        assert_eq!(track.disc, None);
+        assert_eq!(track.title, "Paranoid Android");
+
+        let prefixed = parse("Björk/1995 - Post/01. Army of Me.mp3");
+        assert_eq!(prefixed.album.as_deref(), Some("Post"));
+        assert_eq!(prefixed.year, Some(1995));
+        assert_eq!(prefixed.title, "Army of Me");
+    }
+
+    #[test]
+    fn disc_numbers_come_from_folders_or_prefixes() {
+        let track = parse("Pink Floyd/The Wall/CD2/03 Hey You.flac");
+        assert_eq!(track.album.as_deref(), Some("The Wall"));
+        assert_eq!(track.disc, Some(2));
+        assert_eq!(track.track, Some(3));
+
+        let prefixed = parse("Pink Floyd/The Wall/2-03 Hey You.flac");
+        assert_eq!(prefixed.disc, Some(2));
+        assert_eq!(prefixed.track, Some(3));
+        assert_eq!(prefixed.title, "Hey You");
+    }
+
+    #[test]
+    fn shallow_layouts_keep_what_they_can() {
+        let unsorted = parse("Nina Simone/Feeling Good.m4a");
+        assert_eq!(unsorted.artist, "Nina Simone");
+        assert_eq!(unsorted.album, None);
+        assert_eq!(unsorted.track, None);
+
+        let loose = parse("Massive Attack - Teardrop.opus");
+        assert_eq!(loose.artist, "Massive Attack");
+        assert_eq!(loose.title, "Teardrop");
+
+        let untitled = parse("1979.mp3");
+        assert_eq!(untitled.artist, UNKNOWN_ARTIST);
+        assert_eq!(untitled.title, "1979");
+        assert_eq!(untitled.track, None);
+    }
+}
@@ -0,0 +1,276 @@
+//! Operator-configured filename rules.
+//!
+//! Libraries named in ways the built-in heuristics do not recognize (fansub
+//! releases like `[Group] Show - 01v2`, air-date names like
+//! `Show 2021-05-03`) can be handled with regexes from the config instead of
+//! a code change. Rules are tried in order against the file stem before any
+//! built-in pattern, and the first match wins.
+//!
+//! A rule reports what it found through named groups:
+//!
+//! - `episode` makes the match an episode. `show` names the series (the
+//!   series folder is used when absent) and `season` defaults to the season
+//!   folder the file sits in, else 1. Digits are read ignoring separators,
+//!   so `05-03` captured as the episode gives the same `MMDD` number the
+//!   built-in air-date patterns use.
+//! - Without `episode` the match is a movie and `title` is required.
+//! - `year` is optional for both; `title` on an episode is its title.
+
+use std::{path::Path, sync::OnceLock};
+
+use regex::Regex;
+
+use super::tv_parser::{EpisodeInfo, TvParser};
+use crate::{
+    error::{MediaError, Result},
+    types::files::{ParsedEpisodeInfo, ParsedMediaInfo, ParsedMovieInfo},
+};
+
+/// Named groups a rule may use.
+pub const RULE_GROUPS: [&str; 5] =
+    ["show", "season", "episode", "title", "year"];
+
+static NAMING_RULES: OnceLock<NamingRules> = OnceLock::new();
+
+/// Install the rules used by every scan. Only succeeds once.
+pub fn install_naming_rules(rules: NamingRules) -> Result<()> {
+    NAMING_RULES.set(rules).map_err(|_| {
+        MediaError::Internal("filename rules already installed".into())
+    })
+}
+
+/// Rules installed at startup, if any.
+pub fn naming_rules() -> Option<&'static NamingRules> {
+    NAMING_RULES.get().filter(|rules| !rules.is_empty())
+}
+
+/// A pattern that cannot be used as a rule.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("filename rule #{index} `{pattern}` is invalid: {reason}")]
+pub struct NamingRuleError {
+    /// Position of the rule in the configured list, starting at 0
+    pub index: usize,
+    pub pattern: String,
+    pub reason: String,
+}
+
+/// Ordered, compiled filename rules.
+#[derive(Debug, Clone, Default)]
+pub struct NamingRules {
+    rules: Vec<Regex>,
+}
+
+/// Outcome of parsing one filename with the configured rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatch {
+    /// Index of the rule that matched
+    pub rule: usize,
+    pub info: ParsedMediaInfo,
+}
+
+impl NamingRules {
+    /// Compile `patterns`, rejecting the first one that is not a valid regex,
+    /// uses an unknown group name or captures neither `episode` nor `title`.
+    pub fn compile<S: AsRef<str>>(
+        patterns: &[S],
+    ) -> std::result::Result<Self, NamingRuleError> {
+        let rules = patterns
+            .iter()
+            .enumerate()
+            .map(|(index, pattern)| compile_rule(index, pattern.as_ref()))
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Parse `path` with the first rule that matches its file stem.
+    pub fn parse(&self, path: &Path) -> Option<RuleMatch> {
+        let stem = path.file_stem()?.to_str()?;
+        self.rules.iter().enumerate().find_map(|(rule, regex)| {
+            let captures = regex.captures(stem)?;
+            let text = |name: &str| {
+                captures
+                    .name(name)
+                    .map(|m| tidy(m.as_str()))
+                    .filter(|value| !value.is_empty())
+            };
+            let number = |name: &str| captures.name(name).and_then(digits);
+            let year = number("year");
+
+            let info = if captures.name("episode").is_some() {
+                let show_name = text("show")
+                    .or_else(|| TvParser::extract_series_name(path))?;
+                ParsedMediaInfo::Episode(ParsedEpisodeInfo {
+                    show_name,
+                    season: number("season")
+                        .or_else(|| season_from_folder(path))
+                        .unwrap_or(1),
+                    episode: number("episode")?,
+                    episode_title: text("title"),
+                    year,
+                    resolution: None,
+                    source: None,
+                    release_group: None,
+                    absolute_episode: None,
+                })
+            } else {
+                ParsedMediaInfo::Movie(ParsedMovieInfo {
+                    title: text("title")?,
+                    year,
+                    resolution: None,
+                    source: None,
+                    release_group: None,
+                })
+            };
+            Some(RuleMatch { rule, info })
+        })
+    }
+
+    /// Episode numbering for `path` if a rule parses it as an episode.
+    pub fn parse_episode(&self, path: &Path) -> Option<EpisodeInfo> {
+        match self.parse(path)?.info {
+            ParsedMediaInfo::Episode(episode) => Some(EpisodeInfo {
+                season: episode.season,
+                episode: episode.episode,
+                end_episode: None,
+                year: episode.year,
+                month: None,
+                day: None,
+                absolute_episode: None,
+                is_special: episode.season == 0,
+            }),
+            ParsedMediaInfo::Movie(_) | ParsedMediaInfo::Track(_) => None,
+        }
+    }
+}
+
+fn compile_rule(
+    index: usize,
+    pattern: &str,
+) -> std::result::Result<Regex, NamingRuleError> {
+    let error = |reason: String| NamingRuleError {
+        index,
+        pattern: pattern.to_string(),
+        reason,
+    };
+
+    let regex = Regex::new(pattern).map_err(|e| error(e.to_string()))?;
+    let names: Vec<&str> = regex.capture_names().flatten().collect();
+    if let Some(unknown) = names.iter().find(|name| !RULE_GROUPS.contains(name))
+    {
+        return Err(error(format!(
+            "unknown group `{}` (expected one of {})",
+            unknown,
+            RULE_GROUPS.join(", ")
+        )));
+    }
+    if !names.contains(&"episode") && !names.contains(&"title") {
+        return Err(error(
+            "needs an `episode` group (episodes) or a `title` group (movies)"
+                .into(),
+        ));
+    }
+    Ok(regex)
+}
+
+/// Season number of the folder holding `path` (`Season 02`), if it is one.
+fn season_from_folder(path: &Path) -> Option<u16> {
+    let parent = path.parent()?;
+    let series = parent
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|name| name.to_str());
+    TvParser::parse_season_folder_with_series(
+        parent.file_name()?.to_str()?,
+        series,
+    )
+}
+
+/// Number made of the digits in a capture, ignoring separators.
+fn digits(capture: regex::Match<'_>) -> Option<u16> {
+    let digits: String = capture
+        .as_str()
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Turn `Show.Name_here ` into `Show Name here`.
+fn tidy(raw: &str) -> String {
+    raw.replace(['.', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fansub_and_air_date_names_parse_as_episodes() {
+        let rules = NamingRules::compile(&[
+            r"^\[[^\]]+\]\s*(?P<show>.+?)\s+-\s+(?P<episode>\d+)(?:v\d+)?",
+            r"^(?P<show>.+?)\s+(?P<season>\d{4})-(?P<episode>\d{2}-\d{2})$",
+        ])
+        .unwrap();
+
+        let anime = rules
+            .parse(Path::new("/tv/Frieren/[SubsPlease] Frieren - 07v2.mkv"))
+            .unwrap();
+        assert_eq!(anime.rule, 0);
+        let ParsedMediaInfo::Episode(info) = anime.info else {
+            panic!("expected an episode");
+        };
+        assert_eq!(info.show_name, "Frieren");
+        assert_eq!((info.season, info.episode), (1, 7));
+
+        let daily = rules
+            .parse_episode(Path::new("/tv/Show/Show 2021-05-03.mkv"))
+            .unwrap();
+        assert_eq!((daily.season, daily.episode), (2021, 503));
+    }
+
+    #[test]
+    fn rules_without_episode_are_movies() {
+        let rules =
+            NamingRules::compile(&[r"^(?P<title>.+?)\.(?P<year>\d{4})\.REMUX"])
+                .unwrap();
+        let matched = rules
+            .parse(Path::new("/m/Heat.1995.REMUX.2160p.mkv"))
+            .unwrap();
+        assert_eq!(
+            matched.info,
+            ParsedMediaInfo::Movie(ParsedMovieInfo {
+                title: "Heat".into(),
+                year: Some(1995),
+                resolution: None,
+                source: None,
+                release_group: None,
+            })
+        );
+        assert!(rules.parse(Path::new("/m/Heat (1995).mkv")).is_none());
+    }
+
+    #[test]
+    fn invalid_rules_are_pinpointed() {
+        let err = NamingRules::compile(&[r"(?P<title>.+)", r"(?P<title>[a-"])
+            .unwrap_err();
+        assert_eq!(err.index, 1);
+
+        let err =
+            NamingRules::compile(&[r"(?P<show>.+) (?P<ep>\d+)"]).unwrap_err();
+        assert!(err.reason.contains("unknown group `ep`"), "{err}");
+
+        let err = NamingRules::compile(&[r"(?P<show>.+)"]).unwrap_err();
+        assert!(err.reason.contains("needs an `episode` group"), "{err}");
+    }
+}
@@ -0,0 +1,347 @@
+//! Kodi-style `.nfo` sidecars.
+//!
+//! Libraries curated with Kodi or Jellyfin keep a `movie.nfo` (or
+//! `<file stem>.nfo`) next to the media. Only the fields the catalog stores
+//! are read: titles, year and release date, plot, tagline, runtime, genres,
+//! TMDB/IMDb ids and artwork URLs. NFO files are small, flat XML written by
+//! a handful of tools, so they are read with patterns rather than a full
+//! XML parser; anything without a `<movie>` root and a title is rejected.
+
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Fields of a movie NFO.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NfoMovie {
+    pub title: String,
+    pub original_title: Option<String>,
+    pub year: Option<u16>,
+    /// `YYYY-MM-DD`
+    pub premiered: Option<String>,
+    pub plot: Option<String>,
+    pub tagline: Option<String>,
+    /// Minutes
+    pub runtime: Option<u32>,
+    pub genres: Vec<String>,
+    pub tmdb_id: Option<u64>,
+    pub imdb_id: Option<String>,
+    /// Artwork URLs listed in the NFO, primary first.
+    pub poster_urls: Vec<String>,
+    pub fanart_urls: Vec<String>,
+}
+
+/// Why an NFO could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NfoError {
+    #[error("no <movie> root element")]
+    MissingRoot,
+    #[error("no <title>")]
+    MissingTitle,
+}
+
+static MOVIE_ROOT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<movie(?:\s[^>]*)?>(.*)</movie\s*>").unwrap()
+});
+static OPEN_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<([A-Za-z_][\w.-]*)([^>]*)>").unwrap());
+static ATTRIBUTE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"([A-Za-z_][\w.-]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap()
+});
+static CDATA: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<!\[CDATA\[(.*?)\]\]>").unwrap());
+
+/// Parse the text of a movie NFO.
+pub fn parse_movie_nfo(text: &str) -> Result<NfoMovie, NfoError> {
+    let body = MOVIE_ROOT
+        .captures(text)
+        .and_then(|c| c.get(1))
+        .ok_or(NfoError::MissingRoot)?
+        .as_str();
+
+    let mut movie = NfoMovie::default();
+    let mut uniqueid_default: Option<(String, String)> = None;
+
+    for element in elements(body) {
+        let attrs = element.attrs;
+        let value = text_of(element.inner);
+        if value.is_empty() {
+            continue;
+        }
+
+        match element.name.to_ascii_lowercase().as_str() {
+            "title" if movie.title.is_empty() => movie.title = value,
+            "originaltitle" => movie.original_title = Some(value),
+            "year" => movie.year = value.parse().ok(),
+            "premiered" | "releasedate" => {
+                movie.premiered = valid_date(&value);
+            }
+            "plot" => movie.plot = Some(value),
+            "outline" if movie.plot.is_none() => movie.plot = Some(value),
+            "tagline" => movie.tagline = Some(value),
+            "runtime" => {
+                movie.runtime = value
+                    .split_whitespace()
+                    .next()
+                    .and_then(|m| m.parse().ok());
+            }
+            "genre" => {
+                for genre in value.split(" / ") {
+                    let genre = genre.trim();
+                    if !genre.is_empty()
+                        && !movie.genres.iter().any(|g| g == genre)
+                    {
+                        movie.genres.push(genre.to_string());
+                    }
+                }
+            }
+            "tmdbid" => movie.tmdb_id = value.parse().ok().or(movie.tmdb_id),
+            "imdbid" => movie.imdb_id = imdb_id(&value).or(movie.imdb_id),
+            "id" => {
+                if movie.imdb_id.is_none() {
+                    movie.imdb_id = imdb_id(&value);
+                }
+            }
+            "uniqueid" => {
+                let kind = attribute(attrs, "type").unwrap_or_default();
+                match kind.to_ascii_lowercase().as_str() {
+                    "tmdb" => movie.tmdb_id = value.parse().ok(),
+                    "imdb" => movie.imdb_id = imdb_id(&value),
+                    _ => {}
+                }
+                if attribute(attrs, "default").as_deref() == Some("true") {
+                    uniqueid_default = Some((kind, value));
+                }
+            }
+            "thumb" => {
+                let aspect = attribute(attrs, "aspect").unwrap_or_default();
+                if aspect.is_empty() || aspect.eq_ignore_ascii_case("poster") {
+                    movie.poster_urls.push(value);
+                }
+            }
+            "fanart" => {
+                // <fanart><thumb>url</thumb>...</fanart>
+                movie.fanart_urls.extend(
+                    elements(element.inner)
+                        .into_iter()
+                        .filter(|c| c.name.eq_ignore_ascii_case("thumb"))
+                        .map(|c| text_of(c.inner))
+                        .filter(|url| !url.is_empty()),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    if movie.tmdb_id.is_none()
+        && let Some((kind, value)) = uniqueid_default
+        && kind.eq_ignore_ascii_case("tmdb")
+    {
+        movie.tmdb_id = value.parse().ok();
+    }
+    if movie.year.is_none() {
+        movie.year = movie
+            .premiered
+            .as_deref()
+            .and_then(|d| d.get(..4))
+            .and_then(|y| y.parse().ok());
+    }
+    if movie.title.is_empty() {
+        return Err(NfoError::MissingTitle);
+    }
+    Ok(movie)
+}
+
+/// NFO files that may describe the movie at `media_path`, most specific
+/// first: `<stem>.nfo`, then `movie.nfo` in the same folder.
+pub fn movie_nfo_candidates(media_path: &Path) -> Vec<PathBuf> {
+    let mut candidates = Vec::with_capacity(2);
+    if let Some(dir) = media_path.parent() {
+        candidates.push(media_path.with_extension("nfo"));
+        candidates.push(dir.join("movie.nfo"));
+    }
+    candidates
+}
+
+/// Local artwork files for `media_path` of the given kind (`poster` or
+/// `fanart`), most specific first.
+pub fn local_artwork_candidates(media_path: &Path, kind: &str) -> Vec<PathBuf> {
+    let Some(dir) = media_path.parent() else {
+        return Vec::new();
+    };
+    let stem = media_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let mut names = vec![format!("{stem}-{kind}"), kind.to_string()];
+    match kind {
+        "poster" => names.push("folder".into()),
+        "fanart" => names.push("backdrop".into()),
+        _ => {}
+    }
+    names
+        .iter()
+        .flat_map(|name| {
+            ["jpg", "jpeg", "png", "webp"]
+                .iter()
+                .map(move |ext| dir.join(format!("{name}.{ext}")))
+        })
+        .collect()
+}
+
+/// One child element: `<name attrs>inner</name>`.
+struct Element<'a> {
+    name: &'a str,
+    attrs: &'a str,
+    inner: &'a str,
+}
+
+/// Direct children of `body`. Self-closing and unclosed tags are skipped.
+fn elements(body: &str) -> Vec<Element<'_>> {
+    let mut found = Vec::new();
+    let mut pos = 0;
+    while let Some(open) = OPEN_TAG.captures_at(body, pos) {
+        let whole = open.get(0).expect("match");
+        let name = open.get(1).expect("name").as_str();
+        let attrs = open.get(2).expect("attrs").as_str();
+        pos = whole.end();
+        if attrs.ends_with('/') {
+            continue;
+        }
+        let close = format!("</{name}");
+        let Some(rel) = body[pos..].find(&close) else {
+            continue;
+        };
+        let inner_end = pos + rel;
+        let Some(gt) = body[inner_end..].find('>') else {
+            break;
+        };
+        found.push(Element {
+            name,
+            attrs,
+            inner: &body[pos..inner_end],
+        });
+        pos = inner_end + gt + 1;
+    }
+    found
+}
+
+/// Text content of an element: CDATA unwrapped, nested tags dropped and
+/// entities decoded.
+fn text_of(raw: &str) -> String {
+    let raw = CDATA.replace_all(raw, "$1");
+    let mut text = String::with_capacity(raw.len());
+    let mut in_tag = false;
+    for ch in raw.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    decode_entities(text.trim())
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn attribute(attrs: &str, name: &str) -> Option<String> {
+    ATTRIBUTE
+        .captures_iter(attrs)
+        .find(|c| c[1].eq_ignore_ascii_case(name))
+        .and_then(|c| c.get(2).or_else(|| c.get(3)))
+        .map(|m| decode_entities(m.as_str()))
+}
+
+fn imdb_id(value: &str) -> Option<String> {
+    let value = value.trim();
+    (value.len() > 2
+        && value.starts_with("tt")
+        && value[2..].chars().all(|c| c.is_ascii_digit()))
+    .then(|| value.to_string())
+}
+
+fn valid_date(value: &str) -> Option<String> {
+    chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .ok()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kodi_movie_nfo_is_read() {
+        let nfo = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>
+<movie>
+    <title>Heat</title>
+    <originaltitle>Heat</originaltitle>
+    <year>1995</year>
+    <premiered>1995-12-15</premiered>
+    <plot><![CDATA[Obsessive master thief & a cop.]]></plot>
+    <runtime>170</runtime>
+    <genre>Crime</genre>
+    <genre>Drama / Thriller</genre>
+    <uniqueid type="imdb">tt0113277</uniqueid>
+    <uniqueid type="tmdb" default="true">949</uniqueid>
+    <thumb aspect="poster">https://image.example/heat.jpg</thumb>
+    <thumb aspect="clearlogo">https://image.example/logo.png</thumb>
+    <fanart><thumb>https://image.example/fanart.jpg</thumb></fanart>
+    <actor><name>Al Pacino</name></actor>
+</movie>"#;
+
+        let movie = parse_movie_nfo(nfo).unwrap();
+        assert_eq!(movie.title, "Heat");
+        assert_eq!(movie.year, Some(1995));
+        assert_eq!(movie.premiered.as_deref(), Some("1995-12-15"));
+        assert_eq!(
+            movie.plot.as_deref(),
+            Some("Obsessive master thief & a cop.")
+        );
+        assert_eq!(movie.runtime, Some(170));
+        assert_eq!(movie.genres, ["Crime", "Drama", "Thriller"]);
+        assert_eq!(movie.tmdb_id, Some(949));
+        assert_eq!(movie.imdb_id.as_deref(), Some("tt0113277"));
+        assert_eq!(movie.poster_urls, ["https://image.example/heat.jpg"]);
+        assert_eq!(movie.fanart_urls, ["https://image.example/fanart.jpg"]);
+    }
+
+    #[test]
+    fn malformed_nfo_is_rejected() {
+        assert_eq!(
+            parse_movie_nfo("https://www.themoviedb.org/movie/949"),
+            Err(NfoError::MissingRoot)
+        );
+        assert_eq!(
+            parse_movie_nfo("<movie><year>1995</year></movie>"),
+            Err(NfoError::MissingTitle)
+        );
+        assert_eq!(
+            parse_movie_nfo("<movie><title>Heat</title>"),
+            Err(NfoError::MissingRoot)
+        );
+    }
+
+    #[test]
+    fn sidecar_candidates_prefer_the_file_stem() {
+        let media = Path::new("/m/Heat (1995)/Heat.mkv");
+        assert_eq!(
+            movie_nfo_candidates(media),
+            [
+                PathBuf::from("/m/Heat (1995)/Heat.nfo"),
+                PathBuf::from("/m/Heat (1995)/movie.nfo"),
+            ]
+        );
+        let posters = local_artwork_candidates(media, "poster");
+        assert_eq!(posters[0], PathBuf::from("/m/Heat (1995)/Heat-poster.jpg"));
+        assert!(posters.contains(&PathBuf::from("/m/Heat (1995)/folder.jpg")));
+    }
+}
@@ -3,6 +3,8 @@ use regex::Regex;
 use std::path::Path;
 use tracing::debug;
 
+use super::naming_rules::naming_rules;
+
 /// TV show parsing utilities for Jellyfin-compatible patterns
 #[derive(Debug, Default, Clone, Copy)]
 pub struct TvParser;
@@ -134,6 +136,16 @@ impl TvParser {
 
     /// Extract detailed episode information from a file path
     pub fn parse_episode_info(path: &Path) -> Option<EpisodeInfo> {
+        if let Some(info) =
+            naming_rules().and_then(|rules| rules.parse_episode(path))
+        {
+            debug!(
+                "Parsed episode with a configured filename rule: {:?}",
+                info
+            );
+            return Some(info);
+        }
+
         let filename = path.file_stem()?.to_str()?;
 
         // First, try date-based patterns (common for daily shows)
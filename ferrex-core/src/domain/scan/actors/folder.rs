@@ -6,6 +6,10 @@ use tokio::fs;
 
 use crate::domain::media::tv_parser::TvParser;
 use crate::domain::scan::actors::messages::MediaKindHint;
+use crate::domain::scan::exclusions::{ExcludedPathCounts, library_exclusions};
+use crate::domain::scan::file_filter::{
+    FileVerdict, SkippedFileCounts, scan_file_filter,
+};
 use crate::domain::scan::orchestration::context::{
     FolderScanContext, MovieScanHierarchy, ScanNodeKind, SeasonFolderPath,
     SeasonFolderScanContext, SeasonLink, SeriesFolderScanContext, SeriesHint,
@@ -41,6 +45,12 @@ pub struct FolderListingPlan {
     pub media_files: Vec<PathBuf>,
     pub ancillary_files: Vec<PathBuf>,
     pub generated_listing_hash: String,
+    /// Files turned away by the scan file filter.
+    #[serde(default)]
+    pub skipped: SkippedFileCounts,
+    /// Entries turned away by the library's exclude patterns.
+    #[serde(default)]
+    pub excluded: ExcludedPathCounts,
 }
 
 /// Captures state while the folder scan actor is running.
@@ -109,21 +119,7 @@ pub struct DefaultFolderScanActor;
 /// Shared helper so other actors (e.g., LibraryActor) can apply the
 /// same definition of what constitutes a media file.
 pub fn is_supported_media_ext(ext: &str) -> bool {
-    matches!(
-        ext.to_ascii_lowercase().as_str(),
-        "mkv"
-            | "mp4"
-            | "avi"
-            | "mov"
-            | "webm"
-            | "flv"
-            | "wmv"
-            | "mpg"
-            | "mpeg"
-            | "m4v"
-            | "3gp"
-            | "ts"
-    )
+    scan_file_filter().allows_extension(ext)
 }
 
 pub fn is_media_file_path(path: &Path) -> bool {
@@ -144,10 +140,6 @@ impl DefaultFolderScanActor {
         Self
     }
 
-    fn is_media_file(&self, path: &Path) -> bool {
-        is_media_file_path(path)
-    }
-
     async fn list_directory(&self, path: &Path) -> Result<Vec<ListingEntry>> {
         let mut entries = Vec::new();
         let mut dir = fs::read_dir(path).await.map_err(|e| {
@@ -210,14 +202,51 @@ impl FolderScanActor for DefaultFolderScanActor {
     ) -> Result<FolderListingPlan> {
         let context = &job.context;
         let folder_path = PathBuf::from(context.folder_path_norm());
+        let exclusions = library_exclusions(context.library_id());
+        let excluded_by = |path: &Path| {
+            exclusions
+                .as_deref()
+                .and_then(|ex| ex.excluded_by(path))
+                .map(str::to_string)
+        };
+        let mut excluded = ExcludedPathCounts::default();
+
+        // Folders queued before their pattern was added are dropped here.
+        if let Some(pattern) = excluded_by(&folder_path) {
+            tracing::debug!(
+                target: "scan::jobs",
+                folder = %folder_path.display(),
+                %pattern,
+                "skipping folder matched by an exclude pattern"
+            );
+            excluded.record(&pattern);
+            return Ok(FolderListingPlan {
+                generated_listing_hash: compute_listing_hash(&[]),
+                excluded,
+                ..FolderListingPlan::default()
+            });
+        }
+
         let entries = self.list_directory(&folder_path).await?;
 
         let mut directories = Vec::new();
         let mut media_files = Vec::new();
         let mut ancillary_files = Vec::new();
+        let mut skipped = SkippedFileCounts::default();
+        let filter = scan_file_filter();
 
         for entry in &entries {
             let entry_path = folder_path.join(&entry.name);
+            if let Some(pattern) = excluded_by(&entry_path) {
+                tracing::debug!(
+                    target: "scan::jobs",
+                    path = %entry_path.display(),
+                    %pattern,
+                    "skipping entry matched by an exclude pattern"
+                );
+                excluded.record(&pattern);
+                continue;
+            }
             if entry.is_dir {
                 // Skip hidden/system directories up front
                 if entry.name.starts_with('.') {
@@ -255,8 +284,11 @@ impl FolderScanActor for DefaultFolderScanActor {
                         );
                     }
                 }
-            } else if self.is_media_file(&entry_path) {
-                match context {
+                continue;
+            }
+
+            match filter.check(&entry_path, entry.size) {
+                FileVerdict::Media => match context {
                     FolderScanContext::Season(_)
                     | FolderScanContext::Movie(_) => {
                         media_files.push(entry_path);
@@ -268,9 +300,19 @@ impl FolderScanActor for DefaultFolderScanActor {
                             "ignoring media file directly under series root (expected season folders)"
                         );
                     }
+                },
+                FileVerdict::Sidecar => ancillary_files.push(entry_path),
+                FileVerdict::Skipped(reason) => {
+                    tracing::debug!(
+                        target: "scan::jobs",
+                        file = %entry_path.display(),
+                        size = entry.size,
+                        ?reason,
+                        "skipping file rejected by the scan file filter"
+                    );
+                    skipped.record(reason);
+                    ancillary_files.push(entry_path);
                 }
-            } else {
-                ancillary_files.push(entry_path);
             }
         }
 
@@ -280,6 +322,8 @@ impl FolderScanActor for DefaultFolderScanActor {
             media_files,
             ancillary_files,
             generated_listing_hash,
+            skipped,
+            excluded,
         })
     }
 
@@ -478,6 +522,8 @@ impl FolderScanActor for DefaultFolderScanActor {
             enqueued_subfolders: children.len(),
             listing_hash: plan.generated_listing_hash.clone(),
             completed_at: Utc::now(),
+            skipped: plan.skipped,
+            excluded: plan.excluded.clone(),
         })
     }
 }
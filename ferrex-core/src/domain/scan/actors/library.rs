@@ -3,6 +3,7 @@ use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -12,7 +13,7 @@ use uuid::Uuid;
 
 use crate::domain::scan::MediaCandidate;
 use crate::{
-    error::Result,
+    error::{MediaError, Result},
     types::{ids::LibraryId, prelude::LibraryReference},
 };
 
@@ -64,6 +65,10 @@ pub struct LibraryActorConfig {
     pub library: LibraryReference,
     pub root_paths: Vec<PathBuf>,
     pub max_outstanding_jobs: usize,
+    /// Idle window before a scan of this library is declared complete.
+    /// `None` uses the scan control plane's global window.
+    #[serde(default)]
+    pub quiescence_window: Option<Duration>,
 }
 
 impl LibraryActorConfig {
@@ -101,6 +106,14 @@ pub enum LibraryActorCommand {
         events: Vec<FileSystemEvent>,
         correlation_id: Option<Uuid>,
     },
+    /// Rescan the folders holding `paths` under `root` on request. Unlike
+    /// watcher bursts these are honoured during a bulk scan; the root itself
+    /// rescans all of its folders.
+    ScanPaths {
+        root: LibraryRootsId,
+        paths: Vec<PathBuf>,
+        correlation_id: Option<Uuid>,
+    },
     JobCompleted {
         job_id: JobId,
         dedupe_key: DedupeKey,
@@ -307,6 +320,14 @@ where
                     series_root_path,
                 }))
             }
+            // Music libraries are scanned by `scan::music`, never by the
+            // folder actors.
+            crate::types::library::LibraryType::Music => {
+                Err(MediaError::InvalidMedia(format!(
+                    "library {} is a music library and has no folder scans",
+                    library_id
+                )))
+            }
         }
     }
 
@@ -352,10 +373,10 @@ where
             return Ok(vec![LibraryActorEvent::JobThrottled { dedupe_key }]);
         }
 
-        // For bulk seeding we bypass the in-memory outstanding throttle so we can
-        // enqueue all folders into the persistent queue up-front. The scheduler
-        // will regulate actual execution concurrency.
-        if !matches!(reason, ScanReason::BulkSeed) {
+        // For bulk seeding and explicit requests we bypass the in-memory
+        // outstanding throttle so every folder reaches the persistent queue
+        // up-front. The scheduler will regulate actual execution concurrency.
+        if !matches!(reason, ScanReason::BulkSeed | ScanReason::UserRequested) {
             let outstanding_limit_reached = self.state.outstanding_jobs.len()
                 >= self.config.max_outstanding_jobs
                 && !self.state.outstanding_jobs.contains_key(&dedupe_key);
@@ -606,6 +627,57 @@ where
         Ok(responses)
     }
 
+    async fn handle_scan_paths(
+        &mut self,
+        root: LibraryRootsId,
+        paths: Vec<PathBuf>,
+        correlation_id: Option<Uuid>,
+    ) -> Result<Vec<LibraryActorEvent>> {
+        let Some(root_path) = self.config.root_path(root) else {
+            warn!(
+                target: "scan::events",
+                root_id = root.0,
+                "path scan requested for unknown root id"
+            );
+            return Ok(vec![]);
+        };
+        let root_path_norm = normalize_path(&root_path)?;
+
+        let mut targets: HashSet<String> = HashSet::new();
+        for path in &paths {
+            if *path == root_path {
+                let roots = vec![LibraryRootDescriptor {
+                    root_id: root,
+                    path_norm: root_path_norm.clone(),
+                }];
+                let (folders, _skipped) =
+                    Self::enumerate_first_level_folders(&roots).await;
+                targets.extend(folders.into_iter().map(|(_, folder)| folder));
+            } else if let Some(target) =
+                Self::scan_target_under_root(&root_path, path)
+            {
+                targets.insert(target);
+            }
+        }
+
+        let mut responses = Vec::new();
+        for folder_path_norm in targets {
+            let context = self
+                .build_root_scan_context(&root_path_norm, folder_path_norm)?;
+            let mut issued = self
+                .enqueue_folder_scan(
+                    context,
+                    JobPriority::P0,
+                    ScanReason::UserRequested,
+                    correlation_id,
+                )
+                .await?;
+            responses.append(&mut issued);
+        }
+
+        Ok(responses)
+    }
+
     fn scan_target_under_root(
         root_path: &PathBuf,
         path: &Path,
@@ -709,6 +781,11 @@ where
                     events,
                     correlation_id,
                 } => self.handle_fs_events(root, events, correlation_id).await,
+                LibraryActorCommand::ScanPaths {
+                    root,
+                    paths,
+                    correlation_id,
+                } => self.handle_scan_paths(root, paths, correlation_id).await,
                 LibraryActorCommand::JobCompleted { dedupe_key, .. } => {
                     let _ = self.state.release_job(&dedupe_key);
                     if let DedupeKey::FolderScan {
@@ -952,6 +1029,7 @@ mod tests {
             library: reference,
             root_paths: vec![root],
             max_outstanding_jobs: 8,
+            quiescence_window: None,
         };
         DefaultLibraryActor::new(
             config,
@@ -1174,6 +1252,84 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn path_scan_targets_containing_folder_during_bulk_scan() -> Result<()>
+    {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().to_path_buf();
+        std::fs::create_dir_all(root.join("Heat (1995)/extras")).unwrap();
+        std::fs::create_dir_all(root.join("Ronin (1998)")).unwrap();
+        let queue = Arc::new(RecordingQueue::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+        let mut actor = make_actor(
+            Arc::clone(&queue),
+            root.clone(),
+            Arc::clone(&publisher),
+        );
+
+        let _ = actor
+            .handle_command(LibraryActorCommand::Start {
+                mode: StartMode::Bulk,
+                correlation_id: Some(Uuid::now_v7()),
+            })
+            .await?;
+        // Let the bulk seed's folders drain so the path scan is not throttled.
+        actor.state.active_folder_scans.clear();
+        actor.state.outstanding_jobs.clear();
+
+        let correlation = Uuid::now_v7();
+        let responses = actor
+            .handle_command(LibraryActorCommand::ScanPaths {
+                root: LibraryRootsId(0),
+                paths: vec![root.join("Heat (1995)/extras/Heat.mkv")],
+                correlation_id: Some(correlation),
+            })
+            .await?;
+
+        let enqueued: Vec<_> = responses
+            .iter()
+            .filter_map(|event| match event {
+                LibraryActorEvent::EnqueueFolderScan {
+                    context,
+                    reason,
+                    correlation_id,
+                    ..
+                } => Some((
+                    context.folder_path_norm().to_string(),
+                    *reason,
+                    *correlation_id,
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            enqueued,
+            vec![(
+                normalize_path(&root.join("Heat (1995)"))?,
+                ScanReason::UserRequested,
+                Some(correlation),
+            )]
+        );
+
+        actor.state.active_folder_scans.clear();
+        let responses = actor
+            .handle_command(LibraryActorCommand::ScanPaths {
+                root: LibraryRootsId(0),
+                paths: vec![root.clone()],
+                correlation_id: Some(correlation),
+            })
+            .await?;
+        let folders = responses
+            .iter()
+            .filter(|event| {
+                matches!(event, LibraryActorEvent::EnqueueFolderScan { .. })
+            })
+            .count();
+        assert_eq!(folders, 2);
+
+        Ok(())
+    }
+
     // #[tokio::test]
     // async fn burst_of_events_enqueues_single_scan() -> Result<()> {
     //     let temp = tempfile::tempdir().unwrap();
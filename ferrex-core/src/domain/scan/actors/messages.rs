@@ -1,4 +1,6 @@
 use super::library::MaintenancePartition;
+use crate::domain::scan::exclusions::ExcludedPathCounts;
+use crate::domain::scan::file_filter::SkippedFileCounts;
 use crate::domain::scan::orchestration::context::FolderScanContext;
 use crate::domain::scan::orchestration::{
     DedupeKey, JobId, JobPriority, LibraryRootsId, ScanReason,
@@ -122,6 +124,12 @@ pub struct FolderScanSummary {
     pub enqueued_subfolders: usize,
     pub listing_hash: String,
     pub completed_at: DateTime<Utc>,
+    /// Files in the folder turned away by the scan file filter.
+    #[serde(default)]
+    pub skipped: SkippedFileCounts,
+    /// Entries in the folder turned away by the library's exclude patterns.
+    #[serde(default)]
+    pub excluded: ExcludedPathCounts,
 }
 
 use crate::domain::scan::orchestration::context::ScanNodeKind;
@@ -26,6 +26,10 @@ pub struct MediaReadyForIndex {
     pub prepared_at: DateTime<Utc>,
     #[serde(default)]
     pub image_jobs: Vec<ImageFetchJob>,
+    /// Set in limited metadata mode: only the file was stored, so there
+    /// is no reference to index yet.
+    #[serde(default)]
+    pub metadata_skipped: bool,
 }
 
 #[async_trait]
@@ -34,6 +38,13 @@ pub trait MetadataActor: Send + Sync {
         &self,
         command: MetadataCommand,
     ) -> error::Result<MediaReadyForIndex>;
+
+    /// `false` when metadata cannot be looked up at all (no TMDB API key).
+    /// Series are then left unmatched and episodes are enriched directly
+    /// from their file names.
+    fn lookups_enabled(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Debug)]
@@ -67,6 +78,7 @@ impl MetadataActor for DefaultMetadataActor {
             analyzed: command.analyzed,
             prepared_at: Utc::now(),
             image_jobs: Vec::new(),
+            metadata_skipped: false,
         })
     }
 }
@@ -0,0 +1,282 @@
+//! Metadata providers consulted in priority order.
+//!
+//! The scan pipeline asks a [`MetadataProviderChain`] for metadata. Each
+//! provider is tried in turn and the first success wins; a provider that
+//! fails (no match, network trouble, a kind of media it does not handle)
+//! hands the item to the next one. When every provider fails, the last
+//! error is returned, so a chain holding only TMDB behaves exactly like the
+//! TMDB actor on its own.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ferrex_model::VideoMediaType;
+use tracing::{debug, warn};
+
+use crate::{
+    domain::scan::actors::metadata::{
+        DefaultMetadataActor, MediaReadyForIndex, MetadataActor,
+        MetadataCommand,
+    },
+    error::{MediaError, Result},
+    infra::media::image_service::ImageSource,
+};
+
+/// A source of movie and series metadata for the scan pipeline.
+///
+/// Providers persist what they find the same way the TMDB provider does and
+/// return the item ready for indexing.
+#[async_trait]
+pub trait MetadataProvider: Send + Sync {
+    /// Short stable name for logs (`tmdb`, `nfo`).
+    fn name(&self) -> &'static str;
+
+    async fn fetch_movie(
+        &self,
+        command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex>;
+
+    async fn fetch_series(
+        &self,
+        command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex>;
+
+    /// Episodes are optional; by default they fall through to the next
+    /// provider.
+    async fn fetch_episode(
+        &self,
+        _command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex> {
+        Err(MediaError::NotFound(format!(
+            "{} does not provide episode metadata",
+            self.name()
+        )))
+    }
+
+    /// `false` when the provider cannot look anything up, e.g. TMDB
+    /// without an API key.
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    /// Resolver for the image paths this provider stores, when they are not
+    /// TMDB paths or plain URLs.
+    fn image_source(&self) -> Option<Arc<dyn ImageSource>> {
+        None
+    }
+}
+
+/// Providers tried in order until one succeeds.
+pub struct MetadataProviderChain {
+    providers: Vec<Arc<dyn MetadataProvider>>,
+}
+
+impl std::fmt::Debug for MetadataProviderChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetadataProviderChain")
+            .field("providers", &self.names())
+            .finish()
+    }
+}
+
+impl MetadataProviderChain {
+    pub fn new(providers: Vec<Arc<dyn MetadataProvider>>) -> Self {
+        Self { providers }
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.providers.iter().map(|p| p.name()).collect()
+    }
+
+    pub fn providers(&self) -> &[Arc<dyn MetadataProvider>] {
+        &self.providers
+    }
+}
+
+#[async_trait]
+impl MetadataActor for MetadataProviderChain {
+    async fn enrich(
+        &self,
+        command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex> {
+        let variant = command.job.variant;
+        if variant == VideoMediaType::Season {
+            return DefaultMetadataActor::new().enrich(command).await;
+        }
+
+        let mut last_error = None;
+        for provider in &self.providers {
+            let attempt = match variant {
+                VideoMediaType::Movie => {
+                    provider.fetch_movie(command.clone()).await
+                }
+                VideoMediaType::Series => {
+                    provider.fetch_series(command.clone()).await
+                }
+                _ => provider.fetch_episode(command.clone()).await,
+            };
+            match attempt {
+                Ok(ready) => {
+                    debug!(
+                        provider = provider.name(),
+                        media = ?ready.media_id,
+                        "metadata provided"
+                    );
+                    return Ok(ready);
+                }
+                Err(err @ MediaError::NotFound(_)) => {
+                    debug!(
+                        provider = provider.name(),
+                        error = %err,
+                        "metadata provider has no match"
+                    );
+                    last_error = Some(err);
+                }
+                Err(err) => {
+                    if self.providers.len() > 1 {
+                        warn!(
+                            provider = provider.name(),
+                            error = %err,
+                            "metadata provider failed; trying the next one"
+                        );
+                    }
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            MediaError::Internal("no metadata providers configured".into())
+        }))
+    }
+
+    fn lookups_enabled(&self) -> bool {
+        self.providers
+            .iter()
+            .all(|provider| provider.is_available())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::scan::{
+        AnalyzeScanHierarchy, MediaAnalyzed, MediaFingerprint,
+        MetadataEnrichJob, ScanReason,
+        actors::analyze::AnalysisContext,
+        orchestration::context::{
+            MovieRootPath, MovieScanHierarchy, ScanNodeKind,
+        },
+    };
+    use chrono::Utc;
+    use ferrex_model::{LibraryId, MediaID};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Fixed {
+        name: &'static str,
+        succeed: bool,
+        calls: AtomicUsize,
+    }
+
+    impl Fixed {
+        fn new(name: &'static str, succeed: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                succeed,
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl MetadataProvider for Fixed {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn fetch_movie(
+            &self,
+            command: MetadataCommand,
+        ) -> Result<MediaReadyForIndex> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if !self.succeed {
+                return Err(MediaError::NotFound(self.name.into()));
+            }
+            let mut ready = DefaultMetadataActor::new().enrich(command).await?;
+            ready.normalized_title = Some(self.name.into());
+            Ok(ready)
+        }
+
+        async fn fetch_series(
+            &self,
+            command: MetadataCommand,
+        ) -> Result<MediaReadyForIndex> {
+            self.fetch_movie(command).await
+        }
+    }
+
+    fn movie_command() -> MetadataCommand {
+        let library_id = LibraryId::new();
+        let media_id = MediaID::new(VideoMediaType::Movie);
+        let hierarchy = AnalyzeScanHierarchy::Movie(MovieScanHierarchy {
+            movie_root_path: MovieRootPath::try_new_under_library_root(
+                "/movies",
+                "/movies/Heat (1995)",
+            )
+            .unwrap(),
+            movie_id: None,
+            extra_tag: None,
+        });
+        let path_norm = "/movies/Heat (1995)/Heat.mkv".to_string();
+        let job = MetadataEnrichJob {
+            library_id,
+            media_id,
+            variant: VideoMediaType::Movie,
+            hierarchy: hierarchy.clone(),
+            node: ScanNodeKind::MovieFolder,
+            path_norm: path_norm.clone(),
+            fingerprint: MediaFingerprint::default(),
+            scan_reason: ScanReason::BulkSeed,
+        };
+        let analyzed = MediaAnalyzed {
+            library_id,
+            media_id,
+            variant: VideoMediaType::Movie,
+            hierarchy,
+            node: ScanNodeKind::MovieFolder,
+            path_norm,
+            fingerprint: MediaFingerprint::default(),
+            analyzed_at: Utc::now(),
+            analysis: AnalysisContext::default(),
+            thumbnails: Vec::new(),
+        };
+        MetadataCommand { job, analyzed }
+    }
+
+    #[tokio::test]
+    async fn first_successful_provider_wins() {
+        let nfo = Fixed::new("nfo", false);
+        let tmdb = Fixed::new("tmdb", true);
+        let later = Fixed::new("later", true);
+        let chain = MetadataProviderChain::new(vec![
+            nfo.clone(),
+            tmdb.clone(),
+            later.clone(),
+        ]);
+
+        let ready = chain.enrich(movie_command()).await.unwrap();
+        assert_eq!(ready.normalized_title.as_deref(), Some("tmdb"));
+        assert_eq!(nfo.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(later.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn last_error_is_returned_when_all_fail() {
+        let chain = MetadataProviderChain::new(vec![
+            Fixed::new("nfo", false),
+            Fixed::new("tmdb", false),
+        ]);
+        let err = chain.enrich(movie_command()).await.unwrap_err();
+        assert!(matches!(err, MediaError::NotFound(name) if name == "tmdb"));
+    }
+}
@@ -1,3 +1,7 @@
+pub mod chain;
+pub mod nfo;
 pub mod tmdb;
 mod tmdb_match;
+pub use chain::{MetadataProvider, MetadataProviderChain};
+pub use nfo::NfoMetadataProvider;
 pub use tmdb::*;
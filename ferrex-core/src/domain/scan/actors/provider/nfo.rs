@@ -0,0 +1,415 @@
+//! Metadata from Kodi-style `.nfo` sidecars and local artwork.
+//!
+//! Runs ahead of TMDB so curated NFO data wins. A movie is imported from its
+//! NFO when the NFO names a TMDB id (the catalog keys movies by it) and a
+//! poster is available, either as a local file (`poster.jpg`,
+//! `<stem>-poster.jpg`, `folder.jpg`) or as a URL in the NFO. Local files
+//! are stored as `file://` image paths served by [`LocalFileImageSource`],
+//! so they are cached and resized like TMDB images. Anything else falls
+//! through to TMDB; malformed NFO files are skipped with a warning.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use ferrex_model::{ImageMediaType, ImageSize, MediaID};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use super::chain::MetadataProvider;
+use crate::{
+    database::repository_ports::{
+        images::VarInput, media_files::MediaFilesWritePort,
+        media_references::MediaReferencesRepository,
+    },
+    domain::{
+        media::nfo::{
+            NfoMovie, local_artwork_candidates, movie_nfo_candidates,
+            parse_movie_nfo,
+        },
+        scan::{
+            AnalyzeScanHierarchy,
+            actors::metadata::{MediaReadyForIndex, MetadataCommand},
+            orchestration::job::{ImageFetchJob, ImageFetchPriority},
+        },
+    },
+    error::{MediaError, Result},
+    infra::media::image_service::{
+        ImageService, ImageSource, LocalFileImageSource,
+    },
+    traits::prelude::MediaIDLike,
+    types::{
+        details::{EnhancedMovieDetails, ExternalIds, GenreInfo},
+        files::MediaFile,
+        ids::{LibraryId, MovieID},
+        image::MediaImages,
+        media::MovieReference,
+        titles::MovieTitle,
+        urls::{MovieURL, UrlLike},
+    },
+};
+
+/// TMDB's movie genre ids, so NFO genres filter alongside TMDB ones.
+const TMDB_MOVIE_GENRES: &[(&str, u64)] = &[
+    ("action", 28),
+    ("adventure", 12),
+    ("animation", 16),
+    ("comedy", 35),
+    ("crime", 80),
+    ("documentary", 99),
+    ("drama", 18),
+    ("family", 10751),
+    ("fantasy", 14),
+    ("history", 36),
+    ("horror", 27),
+    ("music", 10402),
+    ("mystery", 9648),
+    ("romance", 10749),
+    ("science fiction", 878),
+    ("sci-fi", 878),
+    ("tv movie", 10770),
+    ("thriller", 53),
+    ("war", 10752),
+    ("western", 37),
+];
+
+pub struct NfoMetadataProvider {
+    media_refs: Arc<dyn MediaReferencesRepository>,
+    media_files_write: Arc<dyn MediaFilesWritePort>,
+    image_service: Arc<ImageService>,
+}
+
+impl fmt::Debug for NfoMetadataProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NfoMetadataProvider")
+            .finish_non_exhaustive()
+    }
+}
+
+impl NfoMetadataProvider {
+    pub fn new(
+        media_refs: Arc<dyn MediaReferencesRepository>,
+        media_files_write: Arc<dyn MediaFilesWritePort>,
+        image_service: Arc<ImageService>,
+    ) -> Self {
+        Self {
+            media_refs,
+            media_files_write,
+            image_service,
+        }
+    }
+
+    /// The first readable NFO next to `media_path`. Malformed files are
+    /// logged and skipped.
+    async fn read_movie_nfo(media_path: &Path) -> Option<NfoMovie> {
+        for candidate in movie_nfo_candidates(media_path) {
+            let text = match tokio::fs::read(&candidate).await {
+                Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                Err(_) => continue,
+            };
+            match parse_movie_nfo(&text) {
+                Ok(movie) => return Some(movie),
+                Err(err) => warn!(
+                    nfo = %candidate.display(),
+                    error = %err,
+                    "skipping malformed NFO"
+                ),
+            }
+        }
+        None
+    }
+
+    /// Stored image path for the artwork of `kind`: a local file when one
+    /// exists, else the first URL from the NFO.
+    async fn artwork_path(
+        media_path: &Path,
+        kind: &str,
+        urls: &[String],
+    ) -> Option<String> {
+        for candidate in local_artwork_candidates(media_path, kind) {
+            if tokio::fs::try_exists(&candidate).await.unwrap_or(false) {
+                return Some(LocalFileImageSource::path_for(&candidate));
+            }
+        }
+        urls.iter()
+            .find(|url| {
+                url.starts_with("https://") || url.starts_with("http://")
+            })
+            .cloned()
+    }
+
+    async fn store_artwork(
+        &self,
+        movie_id: MovieID,
+        path: &str,
+        imz: ImageSize,
+    ) -> Result<Uuid> {
+        let variant = self
+            .image_service
+            .images
+            .upsert_variant(&VarInput {
+                media_id: movie_id.to_uuid(),
+                media_type: ImageMediaType::Movie,
+                tmdb_path: path,
+                imz,
+                width: 0,
+                height: 0,
+                lang: "",
+                v_avg: 0.0,
+                v_cnt: 0,
+                is_primary: true,
+            })
+            .await?;
+        Ok(variant.iid)
+    }
+
+    /// Operator-pinned TMDB matches are left to the TMDB provider.
+    async fn is_pinned(&self, path_norm: &str) -> Result<bool> {
+        let Some(existing) = self
+            .media_refs
+            .get_movie_reference_by_path(path_norm)
+            .await?
+        else {
+            return Ok(false);
+        };
+        Ok(self
+            .media_refs
+            .get_tmdb_id_override(&MediaID::Movie(existing.id))
+            .await?
+            .is_some())
+    }
+
+    fn genres(nfo: &NfoMovie) -> Vec<GenreInfo> {
+        let mut genres: Vec<GenreInfo> = Vec::new();
+        for name in &nfo.genres {
+            let Some((_, id)) = TMDB_MOVIE_GENRES
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(name))
+            else {
+                debug!(genre = %name, "NFO genre has no TMDB equivalent");
+                continue;
+            };
+            if !genres.iter().any(|g| g.id == *id) {
+                genres.push(GenreInfo {
+                    id: *id,
+                    name: name.clone(),
+                });
+            }
+        }
+        genres
+    }
+
+    fn details(
+        nfo: &NfoMovie,
+        tmdb_id: u64,
+        primary_poster_iid: Uuid,
+        primary_backdrop_iid: Option<Uuid>,
+    ) -> EnhancedMovieDetails {
+        EnhancedMovieDetails {
+            id: tmdb_id,
+            title: nfo.title.clone(),
+            original_title: nfo.original_title.clone(),
+            overview: nfo.plot.clone(),
+            release_date: nfo
+                .premiered
+                .clone()
+                .or_else(|| nfo.year.map(|year| format!("{year:04}-01-01"))),
+            runtime: nfo.runtime,
+            vote_average: None,
+            vote_count: None,
+            popularity: None,
+            content_rating: None,
+            content_ratings: Vec::new(),
+            release_dates: Vec::new(),
+            genres: Self::genres(nfo),
+            spoken_languages: Vec::new(),
+            production_companies: Vec::new(),
+            production_countries: Vec::new(),
+            homepage: None,
+            status: None,
+            tagline: nfo.tagline.clone(),
+            budget: None,
+            revenue: None,
+            poster_path: None,
+            backdrop_path: None,
+            logo_path: None,
+            primary_poster_iid: Some(primary_poster_iid),
+            primary_backdrop_iid,
+            images: MediaImages::default(),
+            cast: Vec::new(),
+            crew: Vec::new(),
+            videos: Vec::new(),
+            keywords: Vec::new(),
+            external_ids: ExternalIds {
+                imdb_id: nfo.imdb_id.clone(),
+                ..ExternalIds::default()
+            },
+            alternative_titles: Vec::new(),
+            translations: Vec::new(),
+            collection: None,
+            recommendations: Vec::new(),
+            similar: Vec::new(),
+        }
+    }
+
+    fn image_jobs(
+        library_id: LibraryId,
+        poster_iid: Uuid,
+        backdrop_iid: Option<Uuid>,
+    ) -> Vec<ImageFetchJob> {
+        let mut jobs = vec![ImageFetchJob {
+            library_id,
+            iid: poster_iid,
+            imz: ImageSize::poster(),
+            priority_hint: ImageFetchPriority::Poster,
+        }];
+        if let Some(iid) = backdrop_iid {
+            jobs.push(ImageFetchJob {
+                library_id,
+                iid,
+                imz: ImageSize::backdrop(),
+                priority_hint: ImageFetchPriority::Backdrop,
+            });
+        }
+        jobs
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for NfoMetadataProvider {
+    fn name(&self) -> &'static str {
+        "nfo"
+    }
+
+    async fn fetch_movie(
+        &self,
+        mut command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex> {
+        let path_norm = command.analyzed.path_norm.clone();
+        let media_path = PathBuf::from(&path_norm);
+        let not_found =
+            |why: &str| MediaError::NotFound(format!("{why} for {path_norm}"));
+
+        let Some(nfo) = Self::read_movie_nfo(&media_path).await else {
+            return Err(not_found("no usable NFO"));
+        };
+        let Some(tmdb_id) = nfo.tmdb_id else {
+            debug!(path = %path_norm, "NFO has no TMDB id; leaving to TMDB");
+            return Err(not_found("NFO without a TMDB id"));
+        };
+        if self.is_pinned(&path_norm).await? {
+            return Err(not_found("pinned TMDB match"));
+        }
+        let Some(poster_path) =
+            Self::artwork_path(&media_path, "poster", &nfo.poster_urls).await
+        else {
+            debug!(path = %path_norm, "NFO movie has no poster; leaving to TMDB");
+            return Err(not_found("NFO without a poster"));
+        };
+        let backdrop_path =
+            Self::artwork_path(&media_path, "fanart", &nfo.fanart_urls).await;
+
+        let title = MovieTitle::new(nfo.title.clone()).map_err(|e| {
+            MediaError::InvalidMedia(format!("Invalid NFO movie title: {e}"))
+        })?;
+        let library_id = command.analyzed.library_id;
+        let movie_id = MovieID::new();
+
+        let allow_zero_length = {
+            #[cfg(feature = "demo")]
+            {
+                crate::domain::demo::allow_zero_length_for(&library_id)
+            }
+            #[cfg(not(feature = "demo"))]
+            {
+                false
+            }
+        };
+        let mut media_file = MediaFile::new_with_policy(
+            MediaID::Movie(movie_id),
+            media_path.clone(),
+            library_id,
+            allow_zero_length,
+        )?;
+        media_file.media_file_metadata =
+            command.analyzed.analysis.technical.clone();
+        media_file.id =
+            self.media_files_write.upsert(media_file.clone()).await?.id;
+
+        let poster_iid = self
+            .store_artwork(movie_id, &poster_path, ImageSize::poster())
+            .await?;
+        let backdrop_iid = match backdrop_path {
+            Some(path) => Some(
+                self.store_artwork(movie_id, &path, ImageSize::backdrop())
+                    .await?,
+            ),
+            None => None,
+        };
+
+        let movie_ref = MovieReference {
+            id: movie_id,
+            library_id,
+            batch_id: None,
+            tmdb_id,
+            title,
+            details: Self::details(&nfo, tmdb_id, poster_iid, backdrop_iid),
+            endpoint: MovieURL::from_string(format!(
+                "/stream/{}",
+                media_file.id
+            )),
+            file: media_file,
+            theme_color: None,
+            editions: Vec::new(),
+        };
+        let media_id =
+            self.media_refs.store_movie_reference(&movie_ref).await?;
+        let MediaID::Movie(stored_id) = media_id else {
+            return Err(MediaError::Internal(format!(
+                "Expected movie id after movie store, got {:?}",
+                media_id
+            )));
+        };
+
+        command.analyzed.analysis.tmdb_id_hint = Some(tmdb_id);
+        let AnalyzeScanHierarchy::Movie(ref mut hierarchy) =
+            command.analyzed.hierarchy
+        else {
+            return Err(MediaError::Internal(
+                "nfo movie import requires movie hierarchy".into(),
+            ));
+        };
+        hierarchy.movie_id = Some(stored_id);
+
+        debug!(path = %path_norm, tmdb_id, "movie imported from NFO");
+        Ok(MediaReadyForIndex {
+            library_id,
+            media_id,
+            variant: command.analyzed.variant,
+            hierarchy: command.analyzed.hierarchy.clone(),
+            node: command.analyzed.node.clone(),
+            normalized_title: Some(movie_ref.title.to_string()),
+            analyzed: command.analyzed,
+            prepared_at: Utc::now(),
+            image_jobs: Self::image_jobs(library_id, poster_iid, backdrop_iid),
+            metadata_skipped: false,
+        })
+    }
+
+    async fn fetch_series(
+        &self,
+        _command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex> {
+        Err(MediaError::NotFound(
+            "series NFO files are not imported".into(),
+        ))
+    }
+
+    fn image_source(&self) -> Option<Arc<dyn ImageSource>> {
+        Some(Arc::new(LocalFileImageSource))
+    }
+}
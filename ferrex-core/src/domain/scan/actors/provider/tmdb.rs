@@ -1,9 +1,12 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     fmt,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
 use async_trait::async_trait;
@@ -17,6 +20,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use tracing::{error, warn};
 
+use super::chain::MetadataProvider;
 use super::tmdb_match::{rank_movie_candidates, rank_series_candidates};
 
 use crate::{
@@ -24,21 +28,29 @@ use crate::{
         images::VarInput, media_files::MediaFilesWritePort,
         media_references::MediaReferencesRepository,
     },
-    domain::media::tv_parser::TvParser,
+    domain::media::{
+        absolute_numbering::{
+            AbsoluteMapping, SeasonEpisodeCount, map_absolute_episode,
+        },
+        tv_parser::TvParser,
+    },
     error::{MediaError, Result},
     infra::media::{
-        image_service::ImageService,
-        providers::{ProviderError, TmdbApiProvider},
+        image_service::{
+            CachePolicy, ImageService, ImageSource, TmdbImageSource,
+        },
+        providers::{ProviderError, TmdbApiProvider, TmdbVideos},
     },
     traits::prelude::MediaIDLike,
     types::{
         details::{
             AlternativeTitle, CastMember, CollectionInfo, ContentRating,
             CrewMember, EnhancedMovieDetails, EnhancedSeriesDetails,
-            EpisodeDetails, ExternalIds, GenreInfo, Keyword, NetworkInfo,
-            PersonExternalIds, ProductionCompany, ProductionCountry,
-            RelatedMediaRef, ReleaseDateEntry, ReleaseDatesByCountry,
-            SeasonDetails, SpokenLanguage, Translation, Video,
+            EpisodeDetails, ExternalIds, GenreInfo, Keyword, MetadataField,
+            NetworkInfo, PersonExternalIds, ProductionCompany,
+            ProductionCountry, RelatedMediaRef, ReleaseDateEntry,
+            ReleaseDatesByCountry, SeasonDetails, SpokenLanguage, Translation,
+            Video,
         },
         files::{MediaFile, MediaFileMetadata, ParsedMediaInfo},
         ids::{EpisodeID, LibraryId, MovieID, SeasonID, SeriesID},
@@ -76,7 +88,6 @@ use tmdb_api::{
         images::GetMovieImagesResponse, keywords::Response as KeywordsResponse,
         release_dates::Response as ReleaseDatesResponse,
         translations::Response as TranslationResponse,
-        videos::Response as MovieVideosResponse,
     },
     tvshow::{
         Season as TmdbSeason, aggregate_credits::TVShowAggregateCredits,
@@ -99,6 +110,11 @@ static MOVIE_FILENAME_YEAR_DOT_PATTERN: Lazy<Regex> = Lazy::new(|| {
 });
 const SEASON_NOT_FOUND_PREFIX: &str = "season_not_found";
 const EPISODE_NOT_FOUND_PREFIX: &str = "episode_not_found";
+/// Absolute-numbered episode that could not be placed without guessing;
+/// the dead-lettered job is left for an operator to review.
+const ABSOLUTE_EPISODE_REVIEW_PREFIX: &str = "absolute_episode_needs_review";
+/// Whether limited metadata mode has been reported yet.
+static LIMITED_MODE_REPORTED: AtomicBool = AtomicBool::new(false);
 
 pub struct TmdbMetadataActor {
     media_refs: Arc<dyn MediaReferencesRepository>,
@@ -119,6 +135,9 @@ struct EpisodeContextInfo {
     season_number: u16,
     episode_number: u16,
     episode_title: Option<String>,
+    /// Set when the file is numbered absolutely; `season_number` and
+    /// `episode_number` are placeholders until it is mapped.
+    absolute_episode: Option<u16>,
 }
 
 impl TmdbMetadataActor {
@@ -136,6 +155,514 @@ impl TmdbMetadataActor {
         }
     }
 
+    /// Re-fetch a stored movie from TMDB, ignoring the metadata already on
+    /// record.
+    ///
+    /// `tmdb_override` re-points the movie at another TMDB id to fix a wrong
+    /// match. The movie keeps its id; its cached images are invalidated and
+    /// the new primary poster and backdrop are queued for caching.
+    pub async fn refresh_movie(
+        &self,
+        movie_id: MovieID,
+        tmdb_override: Option<u64>,
+    ) -> Result<MovieReference> {
+        let existing = self.media_refs.get_movie_reference(&movie_id).await?;
+        let tmdb_id = Self::refresh_target(existing.tmdb_id, tmdb_override)?;
+
+        let path_norm = existing.file.path.to_string_lossy().into_owned();
+        let movie_ref = self
+            .build_movie_reference(
+                existing.library_id,
+                movie_id,
+                &path_norm,
+                existing.file.media_file_metadata.as_ref(),
+                tmdb_id,
+            )
+            .await?;
+
+        if tmdb_id != existing.tmdb_id {
+            self.media_refs
+                .update_movie_tmdb_id(&movie_id, tmdb_id)
+                .await?;
+        }
+        self.media_refs.store_movie_reference(&movie_ref).await?;
+        self.media_refs
+            .set_metadata_language(
+                &MediaID::Movie(movie_id),
+                &self.tmdb.language_key(),
+            )
+            .await?;
+
+        // Copies of the same title share the images of the oldest copy.
+        let image_owner = self
+            .media_refs
+            .canonical_movie_id(tmdb_id)
+            .await?
+            .unwrap_or(movie_id);
+        self.relink_primary_images(
+            image_owner.to_uuid(),
+            movie_ref.details.primary_poster_iid,
+            movie_ref.details.primary_backdrop_iid,
+        )
+        .await?;
+
+        self.media_refs.get_movie_reference(&movie_id).await
+    }
+
+    /// Re-fetch a stored series from TMDB, ignoring the metadata already on
+    /// record. See [`Self::refresh_movie`]; seasons and episodes are left
+    /// to the next scan.
+    pub async fn refresh_series(
+        &self,
+        series_id: SeriesID,
+        tmdb_override: Option<u64>,
+    ) -> Result<Series> {
+        let existing = self.media_refs.get_series_reference(&series_id).await?;
+        let tmdb_id = Self::refresh_target(existing.tmdb_id, tmdb_override)?;
+
+        if tmdb_id != existing.tmdb_id
+            && let Some(other) = self
+                .media_refs
+                .get_series_by_tmdb_id(existing.library_id, tmdb_id)
+                .await?
+            && other.id != series_id
+        {
+            return Err(MediaError::Conflict(format!(
+                "TMDB series {} is already matched to series {} in this library",
+                tmdb_id, other.id
+            )));
+        }
+
+        let mut series_ref = self
+            .build_series_reference(existing.library_id, series_id, tmdb_id)
+            .await?;
+        series_ref.created_at = existing.created_at;
+
+        if tmdb_id != existing.tmdb_id {
+            self.media_refs
+                .update_series_tmdb_id(&series_id, tmdb_id)
+                .await?;
+        }
+        self.store_localized_series(&series_ref).await?;
+
+        self.relink_primary_images(
+            series_id.to_uuid(),
+            series_ref.details.primary_poster_iid,
+            series_ref.details.primary_backdrop_iid,
+        )
+        .await?;
+
+        self.media_refs.get_series_reference(&series_id).await
+    }
+
+    /// Re-match a movie to `tmdb_id` chosen by an operator and pin it there,
+    /// so later scans keep the match instead of guessing from the filename.
+    /// Fails with [`MediaError::InvalidMedia`] when TMDB has no movie with
+    /// that id.
+    pub async fn rematch_movie(
+        &self,
+        movie_id: MovieID,
+        tmdb_id: u64,
+    ) -> Result<MovieReference> {
+        if let Err(err) = self.tmdb.get_movie(tmdb_id, None).await {
+            return Err(MediaError::InvalidMedia(format!(
+                "TMDB id {tmdb_id} is not a movie: {err}"
+            )));
+        }
+
+        let movie = self.refresh_movie(movie_id, Some(tmdb_id)).await?;
+        self.media_refs
+            .set_tmdb_id_override(&MediaID::Movie(movie_id), Some(tmdb_id))
+            .await?;
+        Ok(movie)
+    }
+
+    /// Series counterpart of [`Self::rematch_movie`].
+    pub async fn rematch_series(
+        &self,
+        series_id: SeriesID,
+        tmdb_id: u64,
+    ) -> Result<Series> {
+        if let Err(err) = self.tmdb.get_series(tmdb_id, None).await {
+            return Err(MediaError::InvalidMedia(format!(
+                "TMDB id {tmdb_id} is not a series: {err}"
+            )));
+        }
+
+        let series = self.refresh_series(series_id, Some(tmdb_id)).await?;
+        self.media_refs
+            .set_tmdb_id_override(&MediaID::Series(series_id), Some(tmdb_id))
+            .await?;
+        Ok(series)
+    }
+
+    /// Re-fetch only `fields` of a stored movie from TMDB. Everything else
+    /// stays as stored, and a refresh of the images keeps the chosen
+    /// primary poster and backdrop. Returns the movie and the fields whose
+    /// stored value changed.
+    pub async fn refresh_movie_fields(
+        &self,
+        movie_id: MovieID,
+        fields: &BTreeSet<MetadataField>,
+    ) -> Result<(MovieReference, Vec<MetadataField>)> {
+        let existing = self.media_refs.get_movie_reference(&movie_id).await?;
+        let tmdb_id = Self::refresh_target(existing.tmdb_id, None)?;
+        let mut details = existing.details.clone();
+        let fetch_error = |what: &str, err: ProviderError| {
+            MediaError::Internal(format!(
+                "Failed to fetch movie {what} for {tmdb_id}: {err}"
+            ))
+        };
+
+        let wants = |field| fields.contains(&field);
+        if wants(MetadataField::Overview)
+            || wants(MetadataField::Genres)
+            || wants(MetadataField::Rating)
+        {
+            let fetched = self
+                .tmdb
+                .get_movie_localized(tmdb_id)
+                .await
+                .map_err(|err| fetch_error("details", err))?;
+            if wants(MetadataField::Overview) {
+                details.overview = Some(fetched.inner.overview.clone());
+            }
+            if wants(MetadataField::Genres) {
+                details.genres = fetched
+                    .genres
+                    .iter()
+                    .map(|g| GenreInfo {
+                        id: g.id,
+                        name: g.name.clone(),
+                    })
+                    .collect();
+            }
+            if wants(MetadataField::Rating) {
+                let release_dates = self
+                    .tmdb
+                    .get_movie_release_dates(tmdb_id)
+                    .await
+                    .map_err(|err| fetch_error("release dates", err))?;
+                details.vote_average = Some(fetched.inner.vote_average as f32);
+                details.vote_count = Some(fetched.inner.vote_count as u32);
+                details.content_rating = Self::extract_movie_certification(
+                    &release_dates,
+                    self.tmdb.region(),
+                );
+                details.content_ratings =
+                    Self::map_movie_content_ratings(&release_dates);
+            }
+        }
+
+        if wants(MetadataField::Cast) {
+            let credits = self
+                .tmdb
+                .get_movie_credits(tmdb_id, None)
+                .await
+                .map_err(|err| fetch_error("credits", err))?;
+            let mut cast = Self::map_cast(&credits);
+            let mut crew = Self::map_crew(&credits);
+            self.persist_person_profile_variants(&mut cast, &mut crew)
+                .await?;
+            details.cast = cast;
+            details.crew = crew;
+        }
+
+        let mut images_changed = false;
+        if wants(MetadataField::Images) {
+            let images = self
+                .tmdb
+                .get_movie_images(tmdb_id, None)
+                .await
+                .map_err(|err| fetch_error("images", err))?;
+            let owner = self
+                .media_refs
+                .canonical_movie_id(tmdb_id)
+                .await?
+                .unwrap_or(movie_id)
+                .to_uuid();
+            let variants = self
+                .persist_tmdb_variants_movie(
+                    owner,
+                    ImageMediaType::Movie,
+                    &images,
+                )
+                .await?;
+            images_changed = self
+                .refresh_linked_images(
+                    owner,
+                    &variants,
+                    &mut details.primary_poster_iid,
+                    &mut details.primary_backdrop_iid,
+                )
+                .await?;
+        }
+
+        let changed =
+            Self::changed_fields(fields, images_changed, |field| match field {
+                MetadataField::Overview => {
+                    details.overview != existing.details.overview
+                }
+                MetadataField::Genres => {
+                    details.genres != existing.details.genres
+                }
+                MetadataField::Cast => {
+                    details.cast != existing.details.cast
+                        || details.crew != existing.details.crew
+                }
+                MetadataField::Rating => {
+                    details.vote_average != existing.details.vote_average
+                        || details.vote_count != existing.details.vote_count
+                        || details.content_rating
+                            != existing.details.content_rating
+                        || details.content_ratings
+                            != existing.details.content_ratings
+                }
+                MetadataField::Images => {
+                    details.primary_poster_iid
+                        != existing.details.primary_poster_iid
+                        || details.primary_backdrop_iid
+                            != existing.details.primary_backdrop_iid
+                }
+            });
+        if changed.is_empty() {
+            return Ok((existing, changed));
+        }
+
+        let mut movie = existing;
+        movie.details = details;
+        self.media_refs.store_movie_reference(&movie).await?;
+        let movie = self.media_refs.get_movie_reference(&movie_id).await?;
+        Ok((movie, changed))
+    }
+
+    /// Series counterpart of [`Self::refresh_movie_fields`].
+    pub async fn refresh_series_fields(
+        &self,
+        series_id: SeriesID,
+        fields: &BTreeSet<MetadataField>,
+    ) -> Result<(Series, Vec<MetadataField>)> {
+        let existing = self.media_refs.get_series_reference(&series_id).await?;
+        let tmdb_id = Self::refresh_target(existing.tmdb_id, None)?;
+        let mut details = existing.details.clone();
+        let fetch_error = |what: &str, err: ProviderError| {
+            MediaError::Internal(format!(
+                "Failed to fetch series {what} for {tmdb_id}: {err}"
+            ))
+        };
+
+        let wants = |field| fields.contains(&field);
+        if wants(MetadataField::Overview)
+            || wants(MetadataField::Genres)
+            || wants(MetadataField::Rating)
+        {
+            let fetched = self
+                .tmdb
+                .get_series_localized(tmdb_id)
+                .await
+                .map_err(|err| fetch_error("details", err))?;
+            if wants(MetadataField::Overview) {
+                details.overview = fetched.inner.overview.clone();
+            }
+            if wants(MetadataField::Genres) {
+                details.genres = fetched
+                    .genres
+                    .iter()
+                    .map(|g| GenreInfo {
+                        id: g.id,
+                        name: g.name.clone(),
+                    })
+                    .collect();
+            }
+            if wants(MetadataField::Rating) {
+                let ratings =
+                    self.tmdb
+                        .get_tv_content_ratings(tmdb_id)
+                        .await
+                        .map_err(|err| fetch_error("content ratings", err))?;
+                details.vote_average = Some(fetched.inner.vote_average as f32);
+                details.vote_count = Some(fetched.inner.vote_count as u32);
+                details.content_rating = Self::extract_series_content_rating(
+                    &ratings,
+                    self.tmdb.region(),
+                );
+                details.content_ratings =
+                    Self::map_series_content_ratings(&ratings);
+            }
+        }
+
+        if wants(MetadataField::Cast) {
+            let credits = self
+                .tmdb
+                .get_series_credits(tmdb_id, None)
+                .await
+                .map_err(|err| fetch_error("credits", err))?;
+            let mut cast = Self::map_series_cast(&credits);
+            let mut crew = Self::map_series_crew(&credits);
+            self.persist_person_profile_variants(&mut cast, &mut crew)
+                .await?;
+            details.cast = cast;
+            details.crew = crew;
+        }
+
+        let mut images_changed = false;
+        if wants(MetadataField::Images) {
+            let images = self
+                .tmdb
+                .get_series_images(tmdb_id, None)
+                .await
+                .map_err(|err| fetch_error("images", err))?;
+            let owner = series_id.to_uuid();
+            let variants = self
+                .persist_tmdb_variants_series(
+                    owner,
+                    ImageMediaType::Series,
+                    &images,
+                )
+                .await?;
+            images_changed = self
+                .refresh_linked_images(
+                    owner,
+                    &variants,
+                    &mut details.primary_poster_iid,
+                    &mut details.primary_backdrop_iid,
+                )
+                .await?;
+        }
+
+        let changed =
+            Self::changed_fields(fields, images_changed, |field| match field {
+                MetadataField::Overview => {
+                    details.overview != existing.details.overview
+                }
+                MetadataField::Genres => {
+                    details.genres != existing.details.genres
+                }
+                MetadataField::Cast => {
+                    details.cast != existing.details.cast
+                        || details.crew != existing.details.crew
+                }
+                MetadataField::Rating => {
+                    details.vote_average != existing.details.vote_average
+                        || details.vote_count != existing.details.vote_count
+                        || details.content_rating
+                            != existing.details.content_rating
+                        || details.content_ratings
+                            != existing.details.content_ratings
+                }
+                MetadataField::Images => {
+                    details.primary_poster_iid
+                        != existing.details.primary_poster_iid
+                        || details.primary_backdrop_iid
+                            != existing.details.primary_backdrop_iid
+                }
+            });
+        if changed.is_empty() {
+            return Ok((existing, changed));
+        }
+
+        let mut series = existing;
+        series.details = details;
+        self.media_refs.store_series_reference(&series).await?;
+        let series = self.media_refs.get_series_reference(&series_id).await?;
+        Ok((series, changed))
+    }
+
+    /// The requested fields whose stored value `differs`; images also
+    /// count as changed when new ones were linked.
+    fn changed_fields(
+        fields: &BTreeSet<MetadataField>,
+        images_linked: bool,
+        differs: impl Fn(MetadataField) -> bool,
+    ) -> Vec<MetadataField> {
+        fields
+            .iter()
+            .copied()
+            .filter(|field| {
+                differs(*field)
+                    || (*field == MetadataField::Images && images_linked)
+            })
+            .collect()
+    }
+
+    /// Link the TMDB images in `variants` to `owner` without giving up the
+    /// chosen primaries: a poster or backdrop picked before stays primary,
+    /// and TMDB's pick is only used where none was set. Returns whether
+    /// images were linked that were not before.
+    async fn refresh_linked_images(
+        &self,
+        owner: Uuid,
+        variants: &[VarInput<'_>],
+        poster_iid: &mut Option<Uuid>,
+        backdrop_iid: &mut Option<Uuid>,
+    ) -> Result<bool> {
+        let images = &self.image_service.images;
+        let linked_before: HashSet<Uuid> = images
+            .list_media_images(owner)
+            .await?
+            .into_iter()
+            .map(|image| image.iid)
+            .collect();
+
+        // Upserting resets `is_primary` to TMDB's ranking; the chosen
+        // primaries are restored below.
+        let inserted = images.upsert_variants(variants).await?;
+        let tmdb_primary = |kind: fn(&ImageSize) -> bool| {
+            inserted
+                .iter()
+                .find(|image| image.is_primary && kind(&image.imz))
+                .map(|image| image.iid)
+        };
+        let kept_poster = poster_iid.or_else(|| {
+            tmdb_primary(|imz| matches!(imz, ImageSize::Poster(_)))
+        });
+        let kept_backdrop = backdrop_iid.or_else(|| {
+            tmdb_primary(|imz| matches!(imz, ImageSize::Backdrop(_)))
+        });
+        for iid in [kept_poster, kept_backdrop].into_iter().flatten() {
+            images.set_primary_image(owner, iid).await?;
+        }
+        *poster_iid = kept_poster;
+        *backdrop_iid = kept_backdrop;
+
+        let linked_after = images.list_media_images(owner).await?;
+        Ok(linked_after
+            .iter()
+            .any(|image| !linked_before.contains(&image.iid)))
+    }
+
+    fn refresh_target(stored: u64, tmdb_override: Option<u64>) -> Result<u64> {
+        match tmdb_override.unwrap_or(stored) {
+            0 => Err(MediaError::InvalidMedia(
+                "media has no TMDB match; supply a tmdb_id to refresh it"
+                    .into(),
+            )),
+            tmdb_id => Ok(tmdb_id),
+        }
+    }
+
+    /// Drop every cached size of the media's images and queue the new
+    /// primary poster and backdrop, so clients pick up corrected artwork.
+    async fn relink_primary_images(
+        &self,
+        media_id: Uuid,
+        poster_iid: Option<Uuid>,
+        backdrop_iid: Option<Uuid>,
+    ) -> Result<()> {
+        self.image_service.invalidate_all_variants(media_id).await?;
+
+        let primaries = [
+            poster_iid.map(|iid| (iid, ImageSize::poster())),
+            backdrop_iid.map(|iid| (iid, ImageSize::backdrop())),
+        ];
+        for (iid, imz) in primaries.into_iter().flatten() {
+            let _ =
+                self.image_service
+                    .enqueue_cache(iid, imz, CachePolicy::Ensure);
+        }
+        Ok(())
+    }
+
     /// Persist TMDB image variants for a movie into tmdb_image_variants.
     /// Maps posters and backdrops from the TMDB images response into VarInput rows.
     async fn persist_tmdb_variants_movie<'a>(
@@ -411,9 +938,10 @@ impl TmdbMetadataActor {
 
         if let Some(primary) = ranked.first().map(|entry| entry.candidate) {
             let tmdb_id = primary.inner.id;
-            let mut series_ref =
-                self.build_series_reference(library_id, tmdb_id).await?;
-            self.media_refs.store_series_reference(&series_ref).await?;
+            let mut series_ref = self
+                .build_series_reference(library_id, SeriesID::new(), tmdb_id)
+                .await?;
+            self.store_localized_series(&series_ref).await?;
             if let Some(stored) = self
                 .media_refs
                 .get_series_by_tmdb_id(library_id, tmdb_id)
@@ -447,6 +975,9 @@ impl TmdbMetadataActor {
         &self,
         command: MetadataCommand,
     ) -> Result<MediaReadyForIndex> {
+        if !self.tmdb.is_configured() {
+            return self.store_without_tmdb(command).await;
+        }
         let series_id = match command.job.media_id {
             MediaID::Series(id) => id,
             _ => {
@@ -482,6 +1013,7 @@ impl TmdbMetadataActor {
             analyzed: command.analyzed,
             prepared_at: Utc::now(),
             image_jobs: vec![],
+            metadata_skipped: false,
         })
     }
 
@@ -575,6 +1107,7 @@ impl TmdbMetadataActor {
                 season_number: info.season,
                 episode_number: info.episode,
                 episode_title: info.episode_title.clone(),
+                absolute_episode: info.absolute_episode,
             });
         }
 
@@ -586,6 +1119,7 @@ impl TmdbMetadataActor {
                 season_number: info.season,
                 episode_number: info.episode,
                 episode_title,
+                absolute_episode: info.absolute_episode,
             }
         })
     }
@@ -738,6 +1272,7 @@ impl TmdbMetadataActor {
     fn handle_movie_release_dates(
         tmdb_id: u64,
         result: std::result::Result<ReleaseDatesResponse, ProviderError>,
+        region: Option<&str>,
     ) -> Option<(
         Option<String>,
         Vec<ContentRating>,
@@ -745,7 +1280,8 @@ impl TmdbMetadataActor {
     )> {
         match result {
             Ok(data) => {
-                let certification = Self::extract_movie_certification(&data);
+                let certification =
+                    Self::extract_movie_certification(&data, region);
                 let release_dates = Self::map_movie_release_dates(&data);
                 let content_ratings = Self::map_movie_content_ratings(&data);
                 Some((certification, content_ratings, release_dates))
@@ -775,6 +1311,7 @@ impl TmdbMetadataActor {
 
     fn extract_movie_certification(
         data: &ReleaseDatesResponse,
+        region: Option<&str>,
     ) -> Option<String> {
         let preferred = [
             ReleaseDateKind::Theatrical,
@@ -804,7 +1341,7 @@ impl TmdbMetadataActor {
                     .map(|cert| cert.trim().to_string())
             };
 
-        for region in ["US", "GB", "CA", "AU", "NZ", "FR"] {
+        for region in certification_regions(region) {
             if let Some(entry) =
                 data.results.iter().find(|r| r.iso_3166_1 == region)
                 && let Some(cert) = pick_cert(&entry.release_dates)
@@ -875,20 +1412,20 @@ impl TmdbMetadataActor {
             .collect()
     }
 
-    fn map_movie_videos(result: &MovieVideosResponse) -> Vec<Video> {
+    fn map_videos(result: &TmdbVideos) -> Vec<Video> {
         result
             .results
             .iter()
             .map(|video| Video {
                 key: video.key.clone(),
-                name: Some(video.name.clone()),
+                name: video.name.clone(),
                 site: video.site.clone(),
-                video_type: Some(video.kind.clone()),
-                official: None,
-                iso_639_1: Some(video.iso_639_1.clone()),
-                iso_3166_1: Some(video.iso_3166_1.clone()),
-                published_at: Some(video.published_at.to_rfc3339()),
-                size: u32::try_from(video.size).ok(),
+                video_type: video.kind.clone(),
+                official: video.official,
+                iso_639_1: video.iso_639_1.clone(),
+                iso_3166_1: video.iso_3166_1.clone(),
+                published_at: video.published_at.clone(),
+                size: video.size,
             })
             .collect()
     }
@@ -1069,7 +1606,10 @@ impl TmdbMetadataActor {
     ) -> (Option<String>, Vec<ContentRating>) {
         match self.tmdb.get_tv_content_ratings(tmdb_id).await {
             Ok(result) => {
-                let primary = Self::extract_series_content_rating(&result);
+                let primary = Self::extract_series_content_rating(
+                    &result,
+                    self.tmdb.region(),
+                );
                 let ratings = Self::map_series_content_ratings(&result);
                 (primary, ratings)
             }
@@ -1085,9 +1625,9 @@ impl TmdbMetadataActor {
 
     fn extract_series_content_rating(
         data: &SeriesContentRatingResponse,
+        region: Option<&str>,
     ) -> Option<String> {
-        let preferred_regions = ["US", "GB", "CA", "AU", "NZ", "FR"];
-        for region in preferred_regions {
+        for region in certification_regions(region) {
             if let Some(entry) =
                 data.results.iter().find(|r| r.iso_3166_1 == region)
                 && !entry.rating.trim().is_empty()
@@ -1336,8 +1876,28 @@ impl TmdbMetadataActor {
         &self,
         mut command: MetadataCommand,
     ) -> Result<MediaReadyForIndex> {
+        if !self.tmdb.is_configured() {
+            return self.store_without_tmdb(command).await;
+        }
         let metadata =
             Self::extract_technical_metadata(&command.analyzed.analysis);
+
+        // An operator-pinned match wins over anything the filename suggests.
+        if let Some((movie_id, tmdb_id)) =
+            self.pinned_movie(&command.analyzed.path_norm).await?
+        {
+            let movie_ref = self
+                .build_movie_reference(
+                    command.analyzed.library_id,
+                    movie_id,
+                    &command.analyzed.path_norm,
+                    metadata.as_ref(),
+                    tmdb_id,
+                )
+                .await?;
+            return self.store_matched_movie(command, movie_ref).await;
+        }
+
         let path = PathBuf::from(&command.analyzed.path_norm);
         let (title_hint, year_hint) =
             Self::derive_movie_info(metadata.as_ref(), &path);
@@ -1428,6 +1988,7 @@ impl TmdbMetadataActor {
                 let movie_ref = match self
                     .build_movie_reference(
                         command.analyzed.library_id,
+                        MovieID::new(),
                         &command.analyzed.path_norm,
                         metadata.as_ref(),
                         tmdb_id,
@@ -1444,63 +2005,7 @@ impl TmdbMetadataActor {
                     Err(err) => return Err(err),
                 };
 
-                let media_id =
-                    self.media_refs.store_movie_reference(&movie_ref).await?;
-                let movie_id = match media_id {
-                    MediaID::Movie(id) => id,
-                    other => {
-                        return Err(MediaError::Internal(format!(
-                            "Expected movie id after movie store, got {:?}",
-                            other
-                        )));
-                    }
-                };
-                let stored_movie =
-                    self.media_refs.get_movie_reference(&movie_id).await?;
-
-                let library_id = command.job.library_id;
-
-                let primary_poster_iid =
-                    movie_ref.details.primary_poster_iid.ok_or_else(|| {
-                        MediaError::Internal(format!(
-                            "TMDB movie reference missing primary poster iid (tmdb_id={tmdb_id})"
-                        ))
-                    })?;
-
-                let mut image_jobs = Self::movie_primary_image_jobs(
-                    library_id,
-                    primary_poster_iid,
-                    movie_ref.details.primary_backdrop_iid,
-                );
-
-                self.queue_person_profile_jobs(
-                    library_id,
-                    &stored_movie.details.cast,
-                    &mut image_jobs,
-                )
-                .await?;
-
-                command.analyzed.analysis.tmdb_id_hint = Some(tmdb_id);
-                let AnalyzeScanHierarchy::Movie(ref mut hierarchy) =
-                    command.analyzed.hierarchy
-                else {
-                    return Err(MediaError::Internal(
-                        "tmdb movie enrich requires movie hierarchy".into(),
-                    ));
-                };
-                hierarchy.movie_id = Some(movie_id);
-
-                return Ok(MediaReadyForIndex {
-                    library_id: command.job.library_id,
-                    media_id,
-                    variant: command.analyzed.variant,
-                    hierarchy: command.analyzed.hierarchy.clone(),
-                    node: command.analyzed.node.clone(),
-                    normalized_title: Some(movie_ref.title.to_string()),
-                    analyzed: command.analyzed,
-                    prepared_at: Utc::now(),
-                    image_jobs,
-                });
+                return self.store_matched_movie(command, movie_ref).await;
             }
         }
 
@@ -1522,6 +2027,100 @@ impl TmdbMetadataActor {
         )))
     }
 
+    /// Store a matched movie and hand it to indexing along with its primary
+    /// image and cast portrait jobs.
+    async fn store_matched_movie(
+        &self,
+        mut command: MetadataCommand,
+        movie_ref: MovieReference,
+    ) -> Result<MediaReadyForIndex> {
+        let tmdb_id = movie_ref.tmdb_id;
+        let media_id =
+            self.media_refs.store_movie_reference(&movie_ref).await?;
+        let movie_id = match media_id {
+            MediaID::Movie(id) => id,
+            other => {
+                return Err(MediaError::Internal(format!(
+                    "Expected movie id after movie store, got {:?}",
+                    other
+                )));
+            }
+        };
+        self.media_refs
+            .set_metadata_language(
+                &MediaID::Movie(movie_id),
+                &self.tmdb.language_key(),
+            )
+            .await?;
+        let stored_movie =
+            self.media_refs.get_movie_reference(&movie_id).await?;
+
+        let library_id = command.job.library_id;
+
+        let primary_poster_iid =
+            movie_ref.details.primary_poster_iid.ok_or_else(|| {
+                MediaError::Internal(format!(
+                    "TMDB movie reference missing primary poster iid (tmdb_id={tmdb_id})"
+                ))
+            })?;
+
+        let mut image_jobs = Self::movie_primary_image_jobs(
+            library_id,
+            primary_poster_iid,
+            movie_ref.details.primary_backdrop_iid,
+        );
+
+        self.queue_person_profile_jobs(
+            library_id,
+            &stored_movie.details.cast,
+            &mut image_jobs,
+        )
+        .await?;
+
+        command.analyzed.analysis.tmdb_id_hint = Some(tmdb_id);
+        let AnalyzeScanHierarchy::Movie(ref mut hierarchy) =
+            command.analyzed.hierarchy
+        else {
+            return Err(MediaError::Internal(
+                "tmdb movie enrich requires movie hierarchy".into(),
+            ));
+        };
+        hierarchy.movie_id = Some(movie_id);
+
+        Ok(MediaReadyForIndex {
+            library_id: command.job.library_id,
+            media_id,
+            variant: command.analyzed.variant,
+            hierarchy: command.analyzed.hierarchy.clone(),
+            node: command.analyzed.node.clone(),
+            normalized_title: Some(movie_ref.title.to_string()),
+            analyzed: command.analyzed,
+            prepared_at: Utc::now(),
+            image_jobs,
+            metadata_skipped: false,
+        })
+    }
+
+    /// Movie already stored for `path_norm` and the TMDB id an operator
+    /// pinned it to, if any.
+    async fn pinned_movie(
+        &self,
+        path_norm: &str,
+    ) -> Result<Option<(MovieID, u64)>> {
+        let Some(existing) = self
+            .media_refs
+            .get_movie_reference_by_path(path_norm)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let pinned = self
+            .media_refs
+            .get_tmdb_id_override(&MediaID::Movie(existing.id))
+            .await?;
+        Ok(pinned.map(|tmdb_id| (existing.id, tmdb_id)))
+    }
+
     fn movie_primary_image_jobs(
         library_id: LibraryId,
         primary_poster_iid: Uuid,
@@ -1550,13 +2149,13 @@ impl TmdbMetadataActor {
     async fn build_movie_reference(
         &self,
         library_id: LibraryId,
+        movie_id: MovieID,
         path_norm: &str,
         metadata: Option<&MediaFileMetadata>,
         tmdb_id: u64,
     ) -> Result<MovieReference> {
-        // TODO: Passthrough language and region preference
         let tmdb_details =
-            self.tmdb.get_movie(tmdb_id, None).await.map_err(|e| {
+            self.tmdb.get_movie_localized(tmdb_id).await.map_err(|e| {
                 MediaError::Internal(format!(
                     "Failed to fetch movie details: {e}"
                 ))
@@ -1587,8 +2186,12 @@ impl TmdbMetadataActor {
         );
 
         let (certification, content_ratings, release_dates_list) =
-            Self::handle_movie_release_dates(tmdb_id, release_dates_res)
-                .unwrap_or_default();
+            Self::handle_movie_release_dates(
+                tmdb_id,
+                release_dates_res,
+                self.tmdb.region(),
+            )
+            .unwrap_or_default();
 
         let keywords = keywords_res
             .map(|res| Self::map_movie_keywords(&res))
@@ -1601,7 +2204,7 @@ impl TmdbMetadataActor {
             });
 
         let videos = videos_res
-            .map(|res| Self::map_movie_videos(&res))
+            .map(|res| Self::map_videos(&res))
             .unwrap_or_else(|err| {
                 warn!("Failed to fetch movie videos for {}: {}", tmdb_id, err);
                 Vec::new()
@@ -1712,8 +2315,6 @@ impl TmdbMetadataActor {
             }
         };
 
-        let movie_id = MovieID::new();
-
         let mut media_file = MediaFile::new_with_policy(
             MediaID::Movie(movie_id),
             PathBuf::from(path_norm),
@@ -1736,13 +2337,21 @@ impl TmdbMetadataActor {
         self.persist_person_profile_variants(&mut cast, &mut crew)
             .await?;
 
+        // Copies of one title in several libraries share a single set of
+        // image variants, owned by the oldest copy.
+        let image_owner = self
+            .media_refs
+            .canonical_movie_id(tmdb_id)
+            .await?
+            .unwrap_or(movie_id);
+
         let mut primary_poster_iid: Option<Uuid> = None;
         let mut primary_backdrop_iid: Option<Uuid> = None;
         match images_res {
             Ok(images) => {
                 let variants = self
                     .persist_tmdb_variants_movie(
-                        movie_id.to_uuid(),
+                        image_owner.to_uuid(),
                         ImageMediaType::Movie,
                         &images,
                     )
@@ -1805,7 +2414,7 @@ impl TmdbMetadataActor {
                 .image_service
                 .images
                 .upsert_variant(&VarInput {
-                    media_id: movie_id.to_uuid(),
+                    media_id: image_owner.to_uuid(),
                     media_type: ImageMediaType::Movie,
                     tmdb_path: path,
                     imz: ImageSize::poster(),
@@ -1832,7 +2441,7 @@ impl TmdbMetadataActor {
                 .image_service
                 .images
                 .upsert_variant(&VarInput {
-                    media_id: movie_id.to_uuid(),
+                    media_id: image_owner.to_uuid(),
                     media_type: ImageMediaType::Movie,
                     tmdb_path: path,
                     imz: ImageSize::backdrop(),
@@ -1930,93 +2539,106 @@ impl TmdbMetadataActor {
             )),
             file: media_file,
             theme_color: None,
+            editions: Vec::new(),
         };
 
         Ok(movie_ref)
     }
 
-    // async fn store_movie_without_tmdb(
-    //     &self,
-    //     mut command: MetadataCommand,
-    //     metadata: Option<MediaFileMetadata>,
-    // ) -> Result<MediaReadyForIndex> {
-    //     let allow_zero_length = {
-    //         #[cfg(feature = "demo")]
-    //         {
-    //             crate::demo::allow_zero_length_for(&command.analyzed.library_id)
-    //         }
-    //         #[cfg(not(feature = "demo"))]
-    //         {
-    //             false
-    //         }
-    //     };
-
-    //     let mut media_file = MediaFile::new_with_policy(
-    //         PathBuf::from(&command.analyzed.path_norm),
-    //         command.analyzed.library_id,
-    //         allow_zero_length,
-    //     )?;
-    //     if let Some(meta) = metadata.clone() {
-    //         media_file.media_file_metadata = Some(meta);
-    //     }
-
-    //     let title = metadata
-    //         .as_ref()
-    //         .and_then(|meta| match &meta.parsed_info {
-    //             Some(ParsedMediaInfo::Movie(info)) => Some(info.title.clone()),
-    //             _ => None,
-    //         })
-    //         .unwrap_or_else(|| {
-    //             Path::new(&command.analyzed.path_norm)
-    //                 .file_stem()
-    //                 .and_then(|s| s.to_str())
-    //                 .unwrap_or_default()
-    //                 .replace(['.', '_', '-'], " ")
-    //         });
-
-    //     let movie_id = MovieID::new();
-    //     let movie_ref = MovieReference {
-    //         id: movie_id,
-    //         library_id: command.analyzed.library_id,
-    //         tmdb_id: 0,
-    //         title: MovieTitle::new(title.clone()).map_err(|e| {
-    //             MediaError::Internal(format!("Invalid movie title: {e}"))
-    //         })?,
-    //         details: MediaDetailsOption::Endpoint(format!(
-    //             "/movie/lookup/{}",
-    //             media_file.id
-    //         )),
-    //         endpoint: MovieURL::from_string(format!(
-    //             "/stream/{}",
-    //             media_file.id
-    //         )),
-    //         file: media_file,
-    //         theme_color: None,
-    //     };
-
-    //     self.media_refs.store_movie_reference(&movie_ref).await?;
-
-    //     Self::annotate_context(&mut command.analyzed.context, 0);
-
-    //     Ok(MediaReadyForIndex {
-    //         library_id: command.job.library_id,
-    //         logical_id: Some(movie_ref.id.to_string()),
-    //         normalized_title: Some(movie_ref.title.to_string()),
-    //         analyzed: command.analyzed,
-    //         prepared_at: Utc::now(),
-    //         image_jobs: Vec::new(),
-    //     })
-    // }
+    /// Limited metadata mode, used while no TMDB API key is configured.
+    /// Movie and episode files are recorded with their technical metadata
+    /// and the title parsed from their path; nothing is fetched and no
+    /// artwork is queued, and a rescan with a key matches them as usual.
+    /// The missing key is reported once per process.
+    async fn store_without_tmdb(
+        &self,
+        command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex> {
+        if !LIMITED_MODE_REPORTED.swap(true, Ordering::Relaxed) {
+            warn!(
+                "TMDB API key is not configured; indexing media by file name \
+                 without metadata or artwork. Set TMDB_API_KEY and rescan to \
+                 match it."
+            );
+        }
+
+        let metadata =
+            Self::extract_technical_metadata(&command.analyzed.analysis);
+        let path = PathBuf::from(&command.analyzed.path_norm);
+        let normalized_title = match command.job.variant {
+            VideoMediaType::Movie => {
+                Some(Self::derive_movie_info(metadata.as_ref(), &path).0)
+            }
+            VideoMediaType::Episode => {
+                Self::derive_episode_info(metadata.as_ref(), &path).map(
+                    |info| {
+                        format!(
+                            "{} S{:02}E{:02}",
+                            info.series.raw_title,
+                            info.season_number,
+                            info.episode_number
+                        )
+                    },
+                )
+            }
+            VideoMediaType::Series | VideoMediaType::Season => None,
+        };
+
+        if matches!(
+            command.job.variant,
+            VideoMediaType::Movie | VideoMediaType::Episode
+        ) {
+            let allow_zero_length = {
+                #[cfg(feature = "demo")]
+                {
+                    crate::domain::demo::allow_zero_length_for(
+                        &command.job.library_id,
+                    )
+                }
+                #[cfg(not(feature = "demo"))]
+                {
+                    false
+                }
+            };
+            let mut media_file = MediaFile::new_with_policy(
+                command.job.media_id,
+                path,
+                command.job.library_id,
+                allow_zero_length,
+            )?;
+            if let Some(meta) = metadata {
+                media_file.media_file_metadata = Some(meta);
+            }
+            self.media_files_write.upsert(media_file).await?;
+        }
+
+        Ok(MediaReadyForIndex {
+            library_id: command.job.library_id,
+            media_id: command.job.media_id,
+            variant: command.job.variant,
+            hierarchy: command.job.hierarchy.clone(),
+            node: command.job.node.clone(),
+            normalized_title,
+            analyzed: command.analyzed,
+            prepared_at: Utc::now(),
+            image_jobs: Vec::new(),
+            metadata_skipped: true,
+        })
+    }
 
     async fn enrich_episode(
         &self,
         mut command: MetadataCommand,
     ) -> Result<MediaReadyForIndex> {
-        let metadata =
+        if !self.tmdb.is_configured() {
+            return self.store_without_tmdb(command).await;
+        }
+        let mut metadata =
             Self::extract_technical_metadata(&command.analyzed.analysis);
         let path = PathBuf::from(&command.analyzed.path_norm);
 
-        let Some(info) = Self::derive_episode_info(metadata.as_ref(), &path)
+        let Some(mut info) =
+            Self::derive_episode_info(metadata.as_ref(), &path)
         else {
             return DefaultMetadataActor::new().enrich(command).await;
         };
@@ -2049,8 +2671,6 @@ impl TmdbMetadataActor {
             }
         }
 
-        let season_number = info.season_number;
-
         let mut excluded_series = HashSet::new();
         let (series_ref, season_ref) = loop {
             let candidate_series = self
@@ -2062,6 +2682,15 @@ impl TmdbMetadataActor {
                 )
                 .await?;
 
+            if let Some(absolute) = info.absolute_episode {
+                let (season, episode) = self
+                    .map_absolute_episode(&candidate_series, absolute)
+                    .await?;
+                info.season_number = season;
+                info.episode_number = episode;
+            }
+            let season_number = info.season_number;
+
             match self
                 .resolve_season(
                     command.job.library_id,
@@ -2088,6 +2717,16 @@ impl TmdbMetadataActor {
 
         let library_id = command.job.library_id;
 
+        // Keep the absolute number next to the TMDB position it mapped to.
+        if let Some(absolute) = info.absolute_episode
+            && let Some(ParsedMediaInfo::Episode(parsed)) =
+                metadata.as_mut().and_then(|meta| meta.parsed_info.as_mut())
+        {
+            parsed.season = info.season_number;
+            parsed.episode = info.episode_number;
+            parsed.absolute_episode = Some(absolute);
+        }
+
         // self.queue_image_job(
         //     command.job.library_id,
         //     "series",
@@ -2180,6 +2819,7 @@ impl TmdbMetadataActor {
             analyzed: command.analyzed,
             prepared_at: Utc::now(),
             image_jobs,
+            metadata_skipped: false,
         })
     }
 
@@ -2195,7 +2835,7 @@ impl TmdbMetadataActor {
             && (existing.tmdb_id == 0
                 || !excluded_tmdb_ids.contains(&existing.tmdb_id))
         {
-            return Ok(existing);
+            return self.relocalize_series_if_stale(library_id, existing).await;
         }
         let (title, year, region) = if let Some(hint) = hierarchy.series_hint()
         {
@@ -2273,13 +2913,16 @@ impl TmdbMetadataActor {
                 && (existing.tmdb_id == 0
                     || !excluded_tmdb_ids.contains(&existing.tmdb_id))
             {
-                return Ok(existing);
+                return self
+                    .relocalize_series_if_stale(library_id, existing)
+                    .await;
             }
 
-            let mut series_ref =
-                self.build_series_reference(library_id, tmdb_id).await?;
+            let mut series_ref = self
+                .build_series_reference(library_id, SeriesID::new(), tmdb_id)
+                .await?;
 
-            self.media_refs.store_series_reference(&series_ref).await?;
+            self.store_localized_series(&series_ref).await?;
 
             if let Some(stored) = self
                 .media_refs
@@ -2309,14 +2952,83 @@ impl TmdbMetadataActor {
         )))
     }
 
+    /// The previously resolved series, when an operator pinned its TMDB match.
+    async fn pinned_series(
+        &self,
+        library_id: LibraryId,
+        previous: Option<SeriesID>,
+    ) -> Result<Option<Series>> {
+        let Some(series_id) = previous else {
+            return Ok(None);
+        };
+        let pinned = self
+            .media_refs
+            .get_tmdb_id_override(&MediaID::Series(series_id))
+            .await?;
+        if pinned.is_none() {
+            return Ok(None);
+        }
+
+        let existing =
+            match self.media_refs.get_series_reference(&series_id).await {
+                Ok(series) => series,
+                Err(MediaError::NotFound(_)) => return Ok(None),
+                Err(err) => return Err(err),
+            };
+        self.relocalize_series_if_stale(library_id, existing)
+            .await
+            .map(Some)
+    }
+
+    /// Store a freshly built series and record the language preference its
+    /// metadata was fetched with.
+    async fn store_localized_series(&self, series: &Series) -> Result<()> {
+        let media_id = self.media_refs.store_series_reference(series).await?;
+        self.media_refs
+            .set_metadata_language(&media_id, &self.tmdb.language_key())
+            .await
+    }
+
+    /// Reuse a stored series unless its metadata was fetched under another
+    /// language preference, in which case it is refetched in place.
+    async fn relocalize_series_if_stale(
+        &self,
+        library_id: LibraryId,
+        existing: Series,
+    ) -> Result<Series> {
+        if existing.tmdb_id == 0 {
+            return Ok(existing);
+        }
+
+        let language = self.tmdb.language_key();
+        let stored = self
+            .media_refs
+            .get_metadata_language(&MediaID::Series(existing.id))
+            .await?;
+        if stored.as_deref() == Some(language.as_str()) {
+            return Ok(existing);
+        }
+
+        let mut refreshed = self
+            .build_series_reference(library_id, existing.id, existing.tmdb_id)
+            .await?;
+        refreshed.endpoint = existing.endpoint;
+        refreshed.created_at = existing.created_at;
+        if refreshed.theme_color.is_none() {
+            refreshed.theme_color = existing.theme_color;
+        }
+        self.store_localized_series(&refreshed).await?;
+        Ok(refreshed)
+    }
+
     async fn build_series_reference(
         &self,
         library_id: LibraryId,
+        series_id: SeriesID,
         tmdb_id: u64,
     ) -> Result<Series> {
-        // TODO: Thread language through
         let details =
-            self.tmdb.get_series(tmdb_id, None).await.map_err(|e| {
+            self.tmdb.get_series_localized(tmdb_id).await.map_err(|e| {
                 MediaError::Internal(format!(
                     "Failed to fetch series details: {e}"
                 ))
@@ -2324,6 +3036,15 @@ impl TmdbMetadataActor {
 
         let credits = self.tmdb.get_series_credits(tmdb_id, None).await.ok();
         let images = self.tmdb.get_series_images(tmdb_id, None).await.ok();
+        let videos = self
+            .tmdb
+            .get_series_videos(tmdb_id, None)
+            .await
+            .map(|res| Self::map_videos(&res))
+            .unwrap_or_else(|err| {
+                warn!("Failed to fetch series videos for {}: {}", tmdb_id, err);
+                Vec::new()
+            });
         let (content_rating, content_ratings) =
             self.fetch_series_content_rating(tmdb_id).await;
 
@@ -2409,8 +3130,6 @@ impl TmdbMetadataActor {
             Some(details.homepage.clone())
         };
 
-        let series_id = SeriesID::new();
-
         let mut primary_poster_iid: Option<Uuid> = None;
         let mut primary_backdrop_iid: Option<Uuid> = None;
         if let Some(images) = images {
@@ -2552,7 +3271,7 @@ impl TmdbMetadataActor {
             images: MediaImages::default(),
             cast,
             crew,
-            videos: Vec::new(),
+            videos,
             keywords: Vec::new(),
             external_ids: ExternalIds::default(),
             alternative_titles: Vec::new(),
@@ -2583,6 +3302,79 @@ impl TmdbMetadataActor {
         })
     }
 
+    /// Place an absolutely numbered episode in the series' TMDB season
+    /// layout. Stored seasons supply their episode counts; others are
+    /// looked up, stopping once the number is covered.
+    async fn map_absolute_episode(
+        &self,
+        series_ref: &Series,
+        absolute: u16,
+    ) -> Result<(u16, u16)> {
+        let needs_review = |reason: String| {
+            MediaError::InvalidMedia(format!(
+                "{}:{}:{}:{}",
+                ABSOLUTE_EPISODE_REVIEW_PREFIX,
+                series_ref.tmdb_id,
+                absolute,
+                reason
+            ))
+        };
+
+        let season_total = series_ref.details.number_of_seasons.unwrap_or(0);
+        if series_ref.tmdb_id == 0 || season_total == 0 {
+            return Err(needs_review(
+                "series has no TMDB season layout".into(),
+            ));
+        }
+
+        let stored = self.media_refs.get_series_seasons(&series_ref.id).await?;
+        let mut counts = Vec::with_capacity(season_total as usize);
+        let mut covered: u32 = 0;
+        for season in 1..=season_total {
+            let stored_count = stored
+                .iter()
+                .find(|s| s.season_number.value() == season)
+                .map(|s| s.details.episode_count)
+                .filter(|count| *count > 0);
+            let episodes = match stored_count {
+                Some(count) => count,
+                None => match self
+                    .tmdb
+                    .get_season_localized(series_ref.tmdb_id, season)
+                    .await
+                {
+                    Ok(details) => details.episodes.len() as u16,
+                    Err(ProviderError::ApiError(msg))
+                        if msg.contains("404") =>
+                    {
+                        return Err(needs_review(format!(
+                            "season {} is not on TMDB",
+                            season
+                        )));
+                    }
+                    Err(err) => {
+                        return Err(MediaError::Internal(format!(
+                            "Failed to fetch season {} for series {}: {err}",
+                            season, series_ref.tmdb_id
+                        )));
+                    }
+                },
+            };
+            counts.push(SeasonEpisodeCount { season, episodes });
+            covered += u32::from(episodes);
+            if covered >= u32::from(absolute) {
+                break;
+            }
+        }
+
+        match map_absolute_episode(absolute, &counts) {
+            AbsoluteMapping::Resolved { season, episode } => {
+                Ok((season, episode))
+            }
+            AbsoluteMapping::Ambiguous(reason) => Err(needs_review(reason)),
+        }
+    }
+
     async fn resolve_season(
         &self,
         library_id: LibraryId,
@@ -2599,27 +3391,25 @@ impl TmdbMetadataActor {
             return Ok(existing);
         }
 
-        let season_details =
-            // TODO: Thread language through
-            match self
-                .tmdb
-                .get_season(series_ref.tmdb_id, season_number, None)
-                .await
-            {
-                Ok(details) => details,
-                Err(ProviderError::ApiError(msg)) if msg.contains("404") => {
-                    return Err(MediaError::InvalidMedia(format!(
-                        "{}:{}",
-                        SEASON_NOT_FOUND_PREFIX, season_number
-                    )));
-                }
-                Err(err) => {
-                    return Err(MediaError::Internal(format!(
-                        "Failed to fetch season {} for series {}: {err}",
-                        season_number, series_ref.tmdb_id
-                    )));
-                }
-            };
+        let season_details = match self
+            .tmdb
+            .get_season_localized(series_ref.tmdb_id, season_number)
+            .await
+        {
+            Ok(details) => details,
+            Err(ProviderError::ApiError(msg)) if msg.contains("404") => {
+                return Err(MediaError::InvalidMedia(format!(
+                    "{}:{}",
+                    SEASON_NOT_FOUND_PREFIX, season_number
+                )));
+            }
+            Err(err) => {
+                return Err(MediaError::Internal(format!(
+                    "Failed to fetch season {} for series {}: {err}",
+                    season_number, series_ref.tmdb_id
+                )));
+            }
+        };
 
         let mut season_ref = self
             .build_season_reference(
@@ -2770,14 +3560,12 @@ impl TmdbMetadataActor {
 
         // let (episode_details, tmdb_episode_id) = if series_ref.tmdb_id > 0 {
         let (mut details, tmdb_episode_id) = {
-            // TODO: Thread language through
             match self
                 .tmdb
-                .get_episode(
+                .get_episode_localized(
                     series_ref.tmdb_id,
                     season_ref.season_number.value(),
                     info.episode_number,
-                    None,
                 )
                 .await
             {
@@ -3023,6 +3811,46 @@ impl MetadataActor for TmdbMetadataActor {
             VideoMediaType::Episode => self.enrich_episode(command).await,
         }
     }
+
+    fn lookups_enabled(&self) -> bool {
+        self.tmdb.is_configured()
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for TmdbMetadataActor {
+    fn name(&self) -> &'static str {
+        "tmdb"
+    }
+
+    async fn fetch_movie(
+        &self,
+        command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex> {
+        self.enrich_movie(command).await
+    }
+
+    async fn fetch_series(
+        &self,
+        command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex> {
+        self.enrich_series(command).await
+    }
+
+    async fn fetch_episode(
+        &self,
+        command: MetadataCommand,
+    ) -> Result<MediaReadyForIndex> {
+        self.enrich_episode(command).await
+    }
+
+    fn is_available(&self) -> bool {
+        self.tmdb.is_configured()
+    }
+
+    fn image_source(&self) -> Option<Arc<dyn ImageSource>> {
+        Some(Arc::new(TmdbImageSource))
+    }
 }
 
 #[async_trait]
@@ -3033,9 +3861,18 @@ impl SeriesMetadataProvider for TmdbMetadataActor {
         series_root_path: &SeriesRootPath,
         hint: &SeriesHint,
         _folder_name: &str,
+        previous: Option<SeriesID>,
     ) -> Result<SeriesResolution> {
-        let series_ref =
-            self.resolve_series_from_hint(library_id, hint).await?;
+        if !self.tmdb.is_configured() {
+            return Err(MediaError::NotFound(format!(
+                "{}: TMDB API key is not configured",
+                hint.title
+            )));
+        }
+        let series_ref = match self.pinned_series(library_id, previous).await? {
+            Some(pinned) => pinned,
+            None => self.resolve_series_from_hint(library_id, hint).await?,
+        };
         let hierarchy = SeriesScanHierarchy::new(
             SeriesLink::Resolved(SeriesRef {
                 id: series_ref.id,
@@ -3072,6 +3909,7 @@ impl SeriesMetadataProvider for TmdbMetadataActor {
             analyzed,
             prepared_at: Utc::now(),
             image_jobs: vec![],
+            metadata_skipped: false,
         };
 
         Ok(SeriesResolution {
@@ -3085,11 +3923,44 @@ impl SeriesMetadataProvider for TmdbMetadataActor {
     }
 }
 
+/// Countries whose certification is picked first, in order: the configured
+/// region, then the English-speaking markets TMDB covers best.
+fn certification_regions(preferred: Option<&str>) -> Vec<&str> {
+    let mut regions: Vec<&str> = preferred.into_iter().collect();
+    for region in ["US", "GB", "CA", "AU", "NZ", "FR"] {
+        if !regions.contains(&region) {
+            regions.push(region);
+        }
+    }
+    regions
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tmdb_api::tvshow::content_rating::ContentRating as TmdbContentRating;
 
+    #[test]
+    fn only_requested_fields_that_changed_are_reported() {
+        let requested = BTreeSet::from([
+            MetadataField::Overview,
+            MetadataField::Rating,
+            MetadataField::Images,
+        ]);
+        let differs = |field| {
+            matches!(field, MetadataField::Overview | MetadataField::Genres)
+        };
+
+        assert_eq!(
+            TmdbMetadataActor::changed_fields(&requested, false, differs),
+            [MetadataField::Overview]
+        );
+        assert_eq!(
+            TmdbMetadataActor::changed_fields(&requested, true, differs),
+            [MetadataField::Overview, MetadataField::Images]
+        );
+    }
+
     #[test]
     fn movie_primary_image_jobs_skips_backdrop_when_missing() {
         let library_id = LibraryId::new();
@@ -3139,6 +4010,31 @@ mod tests {
         assert_eq!(year, None);
     }
 
+    #[test]
+    fn the_configured_region_wins_the_primary_content_rating() {
+        let rating = |iso: &str, rating: &str| TmdbContentRating {
+            iso_3166_1: iso.to_string(),
+            rating: rating.to_string(),
+            descriptors: vec![],
+        };
+        let data = SeriesContentRatingResponse {
+            id: 7,
+            results: vec![rating("DE", "16"), rating("US", "TV-14")],
+        };
+
+        assert_eq!(
+            TmdbMetadataActor::extract_series_content_rating(&data, None)
+                .as_deref(),
+            Some("TV-14")
+        );
+        assert_eq!(
+            TmdbMetadataActor::extract_series_content_rating(&data, Some("DE"))
+                .as_deref(),
+            Some("16")
+        );
+        assert_eq!(certification_regions(Some("GB"))[..2], ["GB", "US"]);
+    }
+
     #[test]
     fn map_series_content_ratings_normalizes_and_dedupes() {
         let data = SeriesContentRatingResponse {
@@ -0,0 +1,316 @@
+//! Per-library exclude patterns.
+//!
+//! A library may list paths its scans never walk: sample folders, extras,
+//! `@eaDir` thumbnails left by a NAS. Patterns are matched against the
+//! path relative to the library root, so the same list works wherever the
+//! library is mounted. Excluded folders are not descended into, and the
+//! scan counts the entries each pattern turned away.
+//!
+//! Two syntaxes are accepted:
+//!
+//! - globs: `*` and `?` stay within one path segment, `**` spans
+//!   segments. A glob without `/` matches a single segment at any depth
+//!   (`Sample` excludes every `Sample` folder); one with `/` is anchored at
+//!   the root (`Movies/Extras`). Globs ignore case.
+//! - regular expressions prefixed with `re:`, searched anywhere in the
+//!   relative path (`re:(?i)\btrailer\b`).
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, PoisonError, RwLock},
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{MediaError, Result},
+    types::{ids::LibraryId, library::Library},
+};
+
+/// Marks a pattern as a regular expression rather than a glob.
+pub const REGEX_PREFIX: &str = "re:";
+
+static LIBRARY_EXCLUSIONS: LazyLock<
+    RwLock<HashMap<LibraryId, Arc<ScanExclusions>>>,
+> = LazyLock::new(Default::default);
+
+/// Compile `library`'s exclude patterns and use them for its scans from
+/// now on. On error the previous patterns stay in place.
+pub fn install_library_exclusions(library: &Library) -> Result<()> {
+    let exclusions =
+        ScanExclusions::new(&library.paths, &library.exclude_patterns)?;
+    let mut registry = LIBRARY_EXCLUSIONS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if exclusions.is_empty() {
+        registry.remove(&library.id);
+    } else {
+        registry.insert(library.id, Arc::new(exclusions));
+    }
+    Ok(())
+}
+
+/// The exclusions installed for `library_id`, if it has any.
+pub fn library_exclusions(
+    library_id: LibraryId,
+) -> Option<Arc<ScanExclusions>> {
+    LIBRARY_EXCLUSIONS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&library_id)
+        .cloned()
+}
+
+/// Check that every pattern compiles.
+pub fn validate_exclude_patterns<S: AsRef<str>>(patterns: &[S]) -> Result<()> {
+    patterns.iter().try_for_each(|pattern| {
+        ExcludeRule::compile(pattern.as_ref()).map(drop)
+    })
+}
+
+#[derive(Debug, Clone)]
+struct ExcludeRule {
+    pattern: String,
+    regex: Regex,
+}
+
+impl ExcludeRule {
+    fn compile(raw: &str) -> Result<Self> {
+        let pattern = raw.trim();
+        let source = match pattern.strip_prefix(REGEX_PREFIX) {
+            Some(expr) => expr.to_string(),
+            None => glob_to_regex(pattern),
+        };
+        let empty = pattern
+            .strip_prefix(REGEX_PREFIX)
+            .unwrap_or(pattern)
+            .trim_matches('/')
+            .is_empty();
+        if empty {
+            return Err(MediaError::InvalidMedia(format!(
+                "empty exclude pattern `{raw}`"
+            )));
+        }
+        let regex = Regex::new(&source).map_err(|err| {
+            MediaError::InvalidMedia(format!(
+                "invalid exclude pattern `{raw}`: {err}"
+            ))
+        })?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex,
+        })
+    }
+}
+
+/// `*` -> one segment, `**` -> any number of segments, `?` -> one
+/// character within a segment. The match must end on a segment boundary
+/// so `Extras` does not exclude `Extras Edition`.
+fn glob_to_regex(glob: &str) -> String {
+    let anchored = glob.trim_end_matches('/').contains('/');
+    let body = glob.trim_matches('/');
+    let mut out = String::from(if anchored { "(?i)^" } else { "(?i)(?:^|/)" });
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    out.push_str("(?:.*/)?");
+                } else {
+                    out.push_str(".*");
+                }
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            other => {
+                out.push_str(&regex::escape(other.encode_utf8(&mut [0; 4])))
+            }
+        }
+    }
+    out.push_str("(?:/|$)");
+    out
+}
+
+/// One library's compiled exclude patterns and the roots they are
+/// relative to.
+#[derive(Debug, Clone, Default)]
+pub struct ScanExclusions {
+    roots: Vec<PathBuf>,
+    rules: Vec<ExcludeRule>,
+}
+
+impl ScanExclusions {
+    pub fn new<S: AsRef<str>>(
+        roots: &[PathBuf],
+        patterns: &[S],
+    ) -> Result<Self> {
+        let rules = patterns
+            .iter()
+            .map(|pattern| ExcludeRule::compile(pattern.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            roots: roots.to_vec(),
+            rules,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The first pattern excluding `path`. Paths outside every root, and
+    /// the roots themselves, are never excluded.
+    pub fn excluded_by(&self, path: &Path) -> Option<&str> {
+        let relative = self.relative_path(path)?;
+        self.rules
+            .iter()
+            .find(|rule| rule.regex.is_match(&relative))
+            .map(|rule| rule.pattern.as_str())
+    }
+
+    /// `path` below its library root, `/`-separated.
+    fn relative_path(&self, path: &Path) -> Option<String> {
+        let relative = self
+            .roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())?;
+        let segments: Vec<_> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect();
+        if segments.is_empty() {
+            return None;
+        }
+        Some(segments.join("/"))
+    }
+}
+
+/// Entries turned away by each exclude pattern. An excluded folder counts
+/// once; its contents are never listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExcludedPathCounts(BTreeMap<String, u64>);
+
+impl ExcludedPathCounts {
+    pub fn record(&mut self, pattern: &str) {
+        *self.0.entry(pattern.to_string()).or_default() += 1;
+    }
+
+    pub fn add(&mut self, other: &ExcludedPathCounts) {
+        for (pattern, count) in &other.0 {
+            *self.0.entry(pattern.clone()).or_default() += count;
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.0.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// `(pattern, count)` in pattern order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.0
+            .iter()
+            .map(|(pattern, count)| (pattern.as_str(), *count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exclusions(patterns: &[&str]) -> ScanExclusions {
+        ScanExclusions::new(&[PathBuf::from("/media/movies")], patterns)
+            .unwrap()
+    }
+
+    #[test]
+    fn bare_globs_match_a_segment_at_any_depth() {
+        let ex = exclusions(&["sample", "*.part"]);
+        assert_eq!(
+            ex.excluded_by(Path::new("/media/movies/Heat (1995)/Sample")),
+            Some("sample")
+        );
+        assert_eq!(
+            ex.excluded_by(Path::new(
+                "/media/movies/Heat (1995)/heat.mkv.part"
+            )),
+            Some("*.part")
+        );
+        assert_eq!(
+            ex.excluded_by(Path::new("/media/movies/Sample Size (2020)")),
+            None
+        );
+        assert_eq!(
+            ex.excluded_by(Path::new("/media/movies/Heat (1995)/heat.mkv")),
+            None
+        );
+    }
+
+    #[test]
+    fn globs_with_a_slash_are_anchored_at_the_root() {
+        let ex = exclusions(&["Collections/**/Extras", "/@eaDir"]);
+        assert_eq!(
+            ex.excluded_by(Path::new("/media/movies/Collections/Extras")),
+            Some("Collections/**/Extras")
+        );
+        assert_eq!(
+            ex.excluded_by(Path::new(
+                "/media/movies/Collections/Alien/Extras/featurette.mkv"
+            )),
+            Some("Collections/**/Extras")
+        );
+        assert_eq!(
+            ex.excluded_by(Path::new("/media/movies/Heat/Collections/Extras")),
+            None
+        );
+        assert_eq!(
+            ex.excluded_by(Path::new("/media/movies/@eaDir")),
+            Some("/@eaDir")
+        );
+    }
+
+    #[test]
+    fn regexes_search_the_relative_path() {
+        let ex = exclusions(&[r"re:(?i)-trailer\.\w+$"]);
+        assert!(
+            ex.excluded_by(Path::new("/media/movies/Heat/Heat-Trailer.mp4"))
+                .is_some()
+        );
+        // Only the part below the root is matched.
+        let ex = exclusions(&["re:media"]);
+        assert_eq!(ex.excluded_by(Path::new("/media/movies/Heat")), None);
+        assert_eq!(ex.excluded_by(Path::new("/media/movies")), None);
+        assert_eq!(ex.excluded_by(Path::new("/elsewhere/media")), None);
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        assert!(validate_exclude_patterns(&["re:(unclosed"]).is_err());
+        assert!(validate_exclude_patterns(&["  "]).is_err());
+        assert!(validate_exclude_patterns(&["re:"]).is_err());
+        assert!(validate_exclude_patterns(&["Sample", "re:^Extras/"]).is_ok());
+    }
+
+    #[test]
+    fn counts_are_kept_per_pattern() {
+        let mut counts = ExcludedPathCounts::default();
+        counts.record("Sample");
+        let mut other = ExcludedPathCounts::default();
+        other.record("Sample");
+        other.record("*.part");
+        counts.add(&other);
+        assert_eq!(counts.total(), 3);
+        assert_eq!(
+            counts.iter().collect::<Vec<_>>(),
+            [("*.part", 1), ("Sample", 2)]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Which files a scan admits as media.
+//!
+//! A file is media when its extension is on the allowlist and its size is
+//! within the configured bounds. The size bounds keep sample clips and
+//! stray huge files (disk images, archives renamed by mistake) out of the
+//! library. Files turned away are counted per rule so a scan can report
+//! why items did not appear.
+
+use std::{collections::BTreeSet, path::Path, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::scan::scanner::settings::{
+        DEFAULT_AUDIO_FILE_EXTENSIONS, DEFAULT_VIDEO_FILE_EXTENSIONS,
+    },
+    error::{MediaError, Result},
+};
+
+/// Extensions of files expected next to media (subtitles, artwork, NFO,
+/// cue sheets and lyrics). They are not media, but they are not counted as
+/// skipped either.
+const SIDECAR_EXTENSIONS: &[&str] = &[
+    "nfo", "jpg", "jpeg", "png", "webp", "srt", "ass", "ssa", "sub", "idx",
+    "vtt", "txt", "cue", "lrc", "m3u",
+];
+
+static SCAN_FILE_FILTER: OnceLock<ScanFileFilter> = OnceLock::new();
+
+/// Install the filter used by every scan. Only succeeds once.
+pub fn install_scan_file_filter(filter: ScanFileFilter) -> Result<()> {
+    SCAN_FILE_FILTER.set(filter).map_err(|_| {
+        MediaError::Internal("scan file filter already installed".into())
+    })
+}
+
+/// The installed filter, or the built-in allowlist without size bounds.
+pub fn scan_file_filter() -> &'static ScanFileFilter {
+    SCAN_FILE_FILTER.get_or_init(ScanFileFilter::default)
+}
+
+/// Rule that turned a file away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    Extension,
+    TooSmall,
+    TooLarge,
+}
+
+/// Files turned away by each rule.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct SkippedFileCounts {
+    pub extension: u64,
+    pub too_small: u64,
+    pub too_large: u64,
+}
+
+impl SkippedFileCounts {
+    pub fn record(&mut self, reason: SkipReason) {
+        match reason {
+            SkipReason::Extension => self.extension += 1,
+            SkipReason::TooSmall => self.too_small += 1,
+            SkipReason::TooLarge => self.too_large += 1,
+        }
+    }
+
+    pub fn add(&mut self, other: &SkippedFileCounts) {
+        self.extension += other.extension;
+        self.too_small += other.too_small;
+        self.too_large += other.too_large;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.extension + self.too_small + self.too_large
+    }
+}
+
+/// Extension allowlist and size bounds for media files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanFileFilter {
+    extensions: BTreeSet<String>,
+    min_size_bytes: Option<u64>,
+    max_size_bytes: Option<u64>,
+}
+
+impl Default for ScanFileFilter {
+    fn default() -> Self {
+        Self {
+            extensions: DEFAULT_VIDEO_FILE_EXTENSIONS
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+            min_size_bytes: None,
+            max_size_bytes: None,
+        }
+    }
+}
+
+impl ScanFileFilter {
+    /// Build a filter from configured extensions (`.MKV` and `mkv` are the
+    /// same) and optional size bounds in bytes.
+    pub fn new<S: AsRef<str>>(
+        extensions: &[S],
+        min_size_bytes: Option<u64>,
+        max_size_bytes: Option<u64>,
+    ) -> Result<Self> {
+        let extensions = extensions
+            .iter()
+            .map(|ext| normalize_extension(ext.as_ref()))
+            .collect::<Result<BTreeSet<_>>>()?;
+        if extensions.is_empty() {
+            return Err(MediaError::InvalidMedia(
+                "video extension allowlist is empty".into(),
+            ));
+        }
+        if let (Some(min), Some(max)) = (min_size_bytes, max_size_bytes)
+            && min > max
+        {
+            return Err(MediaError::InvalidMedia(format!(
+                "minimum file size ({min} bytes) exceeds maximum ({max} bytes)"
+            )));
+        }
+        Ok(Self {
+            extensions,
+            min_size_bytes,
+            max_size_bytes,
+        })
+    }
+
+    /// The built-in audio allowlist used by music library scans.
+    pub fn audio() -> Self {
+        Self {
+            extensions: DEFAULT_AUDIO_FILE_EXTENSIONS
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+            min_size_bytes: None,
+            max_size_bytes: None,
+        }
+    }
+
+    pub fn extensions(&self) -> impl Iterator<Item = &str> {
+        self.extensions.iter().map(String::as_str)
+    }
+
+    pub fn allows_extension(&self, ext: &str) -> bool {
+        self.extensions.contains(&ext.to_ascii_lowercase())
+    }
+
+    /// Whether `path` has an allowed extension.
+    pub fn allows_path(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.allows_extension(ext))
+    }
+
+    /// Verdict for a file of `size` bytes at `path`. Known sidecar files
+    /// are neither admitted nor counted as skipped.
+    pub fn check(&self, path: &Path, size: u64) -> FileVerdict {
+        if !self.allows_path(path) {
+            let sidecar = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| {
+                    SIDECAR_EXTENSIONS
+                        .contains(&ext.to_ascii_lowercase().as_str())
+                });
+            return if sidecar {
+                FileVerdict::Sidecar
+            } else {
+                FileVerdict::Skipped(SkipReason::Extension)
+            };
+        }
+        if self.min_size_bytes.is_some_and(|min| size < min) {
+            return FileVerdict::Skipped(SkipReason::TooSmall);
+        }
+        if self.max_size_bytes.is_some_and(|max| size > max) {
+            return FileVerdict::Skipped(SkipReason::TooLarge);
+        }
+        FileVerdict::Media
+    }
+}
+
+/// What a scan does with one file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileVerdict {
+    Media,
+    /// Subtitle, artwork or NFO kept alongside media.
+    Sidecar,
+    Skipped(SkipReason),
+}
+
+/// `.MKV` -> `mkv`. Extensions must be non-empty ASCII alphanumerics.
+pub fn normalize_extension(raw: &str) -> Result<String> {
+    let ext = raw.trim().trim_start_matches('.').to_ascii_lowercase();
+    if ext.is_empty() || !ext.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(MediaError::InvalidMedia(format!(
+            "invalid video extension `{raw}`"
+        )));
+    }
+    Ok(ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extensions_are_normalized_and_validated() {
+        let filter =
+            ScanFileFilter::new(&[".MKV", " mp4", "ts"], None, None).unwrap();
+        assert_eq!(
+            filter.extensions().collect::<Vec<_>>(),
+            ["mkv", "mp4", "ts"]
+        );
+        assert!(filter.allows_path(Path::new("/m/Heat.MKV")));
+        assert!(!filter.allows_path(Path::new("/m/Heat.avi")));
+
+        assert!(ScanFileFilter::new(&["m k v"], None, None).is_err());
+        assert!(ScanFileFilter::new(&["."], None, None).is_err());
+        assert!(ScanFileFilter::new::<&str>(&[], None, None).is_err());
+        assert!(ScanFileFilter::new(&["mkv"], Some(10), Some(5)).is_err());
+    }
+
+    #[test]
+    fn files_are_checked_against_each_rule() {
+        let filter =
+            ScanFileFilter::new(&["mkv"], Some(1_000), Some(10_000)).unwrap();
+        let mkv = Path::new("/m/Heat/Heat.mkv");
+
+        assert_eq!(filter.check(mkv, 5_000), FileVerdict::Media);
+        assert_eq!(
+            filter.check(Path::new("/m/Heat/sample.mkv"), 10),
+            FileVerdict::Skipped(SkipReason::TooSmall)
+        );
+        assert_eq!(
+            filter.check(mkv, 20_000),
+            FileVerdict::Skipped(SkipReason::TooLarge)
+        );
+        assert_eq!(
+            filter.check(Path::new("/m/Heat/Heat.iso"), 5_000),
+            FileVerdict::Skipped(SkipReason::Extension)
+        );
+        assert_eq!(
+            filter.check(Path::new("/m/Heat/poster.JPG"), 5_000),
+            FileVerdict::Sidecar
+        );
+    }
+}
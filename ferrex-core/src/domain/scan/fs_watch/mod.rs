@@ -897,13 +897,24 @@ mod tests {
         service
             .register_library(
                 library_id,
-                vec![(LibraryRootsId(0), root)],
+                vec![(LibraryRootsId(0), root.clone())],
                 Arc::clone(&actor),
             )
             .await?;
         assert_eq!(service.watcher_count().await, 1);
         service.unregister_library(library_id).await;
         assert_eq!(service.watcher_count().await, 0);
+
+        // Watching can be turned back on after it was stopped.
+        service
+            .register_library(
+                library_id,
+                vec![(LibraryRootsId(0), root)],
+                Arc::clone(&actor),
+            )
+            .await?;
+        assert_eq!(service.watcher_count().await, 1);
+        service.shutdown().await;
         Ok(())
     }
 }
@@ -6,8 +6,12 @@
 //! root-level shims.
 
 pub mod actors;
+pub mod exclusions;
+pub mod file_filter;
 pub mod fs_watch;
+pub mod music;
 pub mod orchestration;
+pub mod preview;
 pub mod scanner;
 
 // Re-export key surfaces so downstream code can write `crate::scan::*`.
@@ -0,0 +1,238 @@
+//! Scans of music libraries.
+//!
+//! Music libraries skip the folder actors, TMDB matching and image pipeline
+//! that video libraries go through. Every root is walked, audio files are
+//! parsed from their `Artist/Album/NN - Title` layout and the result
+//! replaces the library's stored tracks. Nothing is stored while a root is
+//! unreachable, so an unmounted share does not empty the library.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::{
+    api::types::MusicTrack,
+    database::repository_ports::music_tracks::MusicTracksRepository,
+    domain::{
+        media::music_parser,
+        scan::{
+            exclusions::{ExcludedPathCounts, ScanExclusions},
+            file_filter::{FileVerdict, ScanFileFilter, SkippedFileCounts},
+        },
+    },
+    error::{MediaError, Result},
+    types::library::{Library, LibraryType},
+};
+
+/// What a music library scan found and changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MusicScanOutcome {
+    /// Tracks found on disk.
+    pub tracks: u64,
+    /// Stored tracks no longer found on disk.
+    pub removed: u64,
+    pub skipped_files: SkippedFileCounts,
+    pub excluded_paths: ExcludedPathCounts,
+}
+
+/// Scan every root of the music library `library` into `tracks`.
+pub async fn scan_music_library(
+    library: &Library,
+    tracks: &dyn MusicTracksRepository,
+) -> Result<MusicScanOutcome> {
+    if library.library_type != LibraryType::Music {
+        return Err(MediaError::InvalidMedia(format!(
+            "library {} is not a music library",
+            library.id
+        )));
+    }
+
+    let roots = library.paths.clone();
+    let library_id = library.id;
+    let exclusions =
+        ScanExclusions::new(&library.paths, &library.exclude_patterns)?;
+    let (found, skipped_files, excluded_paths) =
+        tokio::task::spawn_blocking(move || {
+            let filter = ScanFileFilter::audio();
+            let mut found = Vec::new();
+            let mut skipped = SkippedFileCounts::default();
+            let mut excluded = ExcludedPathCounts::default();
+            for root in &roots {
+                if !root.is_dir() {
+                    return Err(MediaError::NotFound(format!(
+                        "music library root {} is unreachable",
+                        root.display()
+                    )));
+                }
+                collect_tracks(
+                    root,
+                    &filter,
+                    &exclusions,
+                    &mut found,
+                    &mut skipped,
+                    &mut excluded,
+                )?;
+            }
+            Ok((found, skipped, excluded))
+        })
+        .await
+        .map_err(|err| {
+            MediaError::Internal(format!("music scan task failed: {err}"))
+        })??;
+
+    let now = Utc::now();
+    let found: Vec<MusicTrack> = found
+        .into_iter()
+        .filter_map(|file| {
+            let info = music_parser::parse_track(&file.relative)?;
+            Some(MusicTrack {
+                id: Uuid::now_v7(),
+                library_id,
+                file_path: file.path.to_string_lossy().into_owned(),
+                filename: file
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                file_size: file.size,
+                artist: info.artist,
+                album: info.album,
+                title: info.title,
+                track_number: info.track,
+                disc_number: info.disc,
+                year: info.year,
+                discovered_at: now,
+            })
+        })
+        .collect();
+
+    let sync = tracks.replace_library_tracks(library_id, &found).await?;
+    info!(
+        library = %library_id,
+        tracks = found.len(),
+        removed = sync.removed,
+        skipped = skipped_files.total(),
+        excluded = excluded_paths.total(),
+        "music library scanned"
+    );
+
+    Ok(MusicScanOutcome {
+        tracks: found.len() as u64,
+        removed: sync.removed,
+        skipped_files,
+        excluded_paths,
+    })
+}
+
+struct FoundFile {
+    path: PathBuf,
+    /// Path below the library root, which carries the artist and album.
+    relative: PathBuf,
+    size: u64,
+}
+
+fn collect_tracks(
+    root: &Path,
+    filter: &ScanFileFilter,
+    exclusions: &ScanExclusions,
+    found: &mut Vec<FoundFile>,
+    skipped: &mut SkippedFileCounts,
+    excluded: &mut ExcludedPathCounts,
+) -> Result<()> {
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if dir != root => {
+                debug!(?dir, error = %err, "skipping unreadable folder");
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if let Some(pattern) = exclusions.excluded_by(&path) {
+                excluded.record(pattern);
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if !hidden {
+                    pending.push(path);
+                }
+                continue;
+            }
+            if hidden || !metadata.is_file() {
+                continue;
+            }
+            match filter.check(&path, metadata.len()) {
+                FileVerdict::Media => {
+                    let relative = path
+                        .strip_prefix(root)
+                        .map(Path::to_path_buf)
+                        .unwrap_or_else(|_| path.clone());
+                    found.push(FoundFile {
+                        path,
+                        relative,
+                        size: metadata.len(),
+                    });
+                }
+                FileVerdict::Sidecar => {}
+                FileVerdict::Skipped(reason) => skipped.record(reason),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_files_are_collected_relative_to_the_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = dir.path().join("Radiohead").join("OK Computer (1997)");
+        std::fs::create_dir_all(&album).unwrap();
+        std::fs::write(album.join("02 - Paranoid Android.flac"), b"fLaC")
+            .unwrap();
+        std::fs::write(album.join("cover.jpg"), b"").unwrap();
+        std::fs::write(album.join("notes.pdf"), b"").unwrap();
+        std::fs::write(album.join(".hidden.mp3"), b"").unwrap();
+        let demos = dir.path().join("Radiohead").join("Demos");
+        std::fs::create_dir_all(&demos).unwrap();
+        std::fs::write(demos.join("01 - Lift.mp3"), b"").unwrap();
+
+        let exclusions =
+            ScanExclusions::new(&[dir.path().to_path_buf()], &["demos"])
+                .unwrap();
+        let mut found = Vec::new();
+        let mut skipped = SkippedFileCounts::default();
+        let mut excluded = ExcludedPathCounts::default();
+        collect_tracks(
+            dir.path(),
+            &ScanFileFilter::audio(),
+            &exclusions,
+            &mut found,
+            &mut skipped,
+            &mut excluded,
+        )
+        .unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].relative,
+            Path::new(
+                "Radiohead/OK Computer (1997)/02 - Paranoid Android.flac"
+            )
+        );
+        assert_eq!(found[0].size, 4);
+        assert_eq!(skipped.extension, 1);
+        assert_eq!(excluded.iter().collect::<Vec<_>>(), [("demos", 1)]);
+    }
+}
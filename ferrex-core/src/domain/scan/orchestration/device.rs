@@ -0,0 +1,261 @@
+//! Per-device concurrency for folder scans.
+//!
+//! Library roots are grouped by the filesystem device that backs them
+//! (`st_dev`), so libraries that live on the same mount share one
+//! `max_parallel_scans_per_device` budget instead of each getting a full one.
+//! Operators can pin roots to a named device through an override map when
+//! auto-detection is misleading (symlink farms, bind mounts, FUSE layers).
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::types::ids::LibraryId;
+
+/// Identity of the device a library root is scanned from.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DeviceKey {
+    /// Resolved from the root's filesystem device id.
+    Filesystem(u64),
+    /// Assigned by an operator override.
+    Named(String),
+    /// The root could not be inspected; it gets a budget of its own.
+    Unresolved(PathBuf),
+}
+
+impl fmt::Display for DeviceKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceKey::Filesystem(dev) => write!(f, "dev:{dev:#x}"),
+            DeviceKey::Named(name) => write!(f, "{name}"),
+            DeviceKey::Unresolved(path) => {
+                write!(f, "unresolved:{}", path.display())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct DeviceState {
+    overrides: Vec<(PathBuf, String)>,
+    roots: HashMap<LibraryId, Vec<(PathBuf, DeviceKey)>>,
+    semaphores: HashMap<DeviceKey, Arc<Semaphore>>,
+}
+
+/// Resolves library roots to devices and hands out per-device scan permits.
+/// Cheap to clone.
+#[derive(Clone, Debug)]
+pub struct DeviceConcurrency {
+    per_device: usize,
+    state: Arc<RwLock<DeviceState>>,
+}
+
+impl DeviceConcurrency {
+    /// A `per_device` limit of zero disables the cap.
+    pub fn new(per_device: usize) -> Self {
+        Self {
+            per_device,
+            state: Arc::new(RwLock::new(DeviceState::default())),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, DeviceState> {
+        self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, DeviceState> {
+        self.state.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn per_device(&self) -> usize {
+        self.per_device
+    }
+
+    /// Replace the operator overrides. Each entry maps a path prefix to a
+    /// device name; roots under the longest matching prefix use that name
+    /// instead of their filesystem device id. Only roots registered after
+    /// this call pick up the new overrides.
+    pub fn set_overrides(&self, overrides: HashMap<PathBuf, String>) {
+        let mut overrides: Vec<(PathBuf, String)> = overrides
+            .into_iter()
+            .filter(|(_, name)| !name.trim().is_empty())
+            .collect();
+        overrides.sort_by(|a, b| {
+            b.0.components().count().cmp(&a.0.components().count())
+        });
+        self.write().overrides = overrides;
+    }
+
+    /// Resolve and remember the device of each library root, replacing any
+    /// roots previously registered for the library.
+    pub fn register_library(&self, library_id: LibraryId, roots: &[PathBuf]) {
+        // Stat the roots without holding the lock; a slow mount must not
+        // stall permit lookups for other devices.
+        let overrides = self.read().overrides.clone();
+        let resolved = roots
+            .iter()
+            .map(|root| (root.clone(), resolve_device(root, &overrides)))
+            .collect();
+        self.write().roots.insert(library_id, resolved);
+    }
+
+    /// Device of the registered root that contains `path`, if any.
+    pub fn device_for(&self, path: &Path) -> Option<DeviceKey> {
+        let state = self.read();
+        state
+            .roots
+            .values()
+            .flatten()
+            .filter(|(root, _)| path.starts_with(root))
+            .max_by_key(|(root, _)| root.components().count())
+            .map(|(_, key)| key.clone())
+    }
+
+    /// Registered roots grouped by device, for diagnostics.
+    pub fn groups(&self) -> BTreeMap<DeviceKey, Vec<(LibraryId, PathBuf)>> {
+        let state = self.read();
+        let mut groups: BTreeMap<DeviceKey, Vec<(LibraryId, PathBuf)>> =
+            BTreeMap::new();
+        for (library_id, roots) in &state.roots {
+            for (root, key) in roots {
+                groups
+                    .entry(key.clone())
+                    .or_default()
+                    .push((*library_id, root.clone()));
+            }
+        }
+        for members in groups.values_mut() {
+            members.sort_by(|a, b| a.1.cmp(&b.1));
+        }
+        groups
+    }
+
+    /// Wait for a scan permit on the device holding `path`. Returns `None`
+    /// when the cap is disabled or the path is outside every registered root.
+    pub async fn acquire(&self, path: &Path) -> Option<OwnedSemaphorePermit> {
+        if self.per_device == 0 {
+            return None;
+        }
+        let key = self.device_for(path)?;
+        let semaphore =
+            {
+                let mut state = self.write();
+                Arc::clone(state.semaphores.entry(key).or_insert_with(|| {
+                    Arc::new(Semaphore::new(self.per_device))
+                }))
+            };
+        semaphore.acquire_owned().await.ok()
+    }
+}
+
+fn resolve_device(root: &Path, overrides: &[(PathBuf, String)]) -> DeviceKey {
+    if let Some((_, name)) = overrides
+        .iter()
+        .find(|(prefix, _)| root.starts_with(prefix))
+    {
+        return DeviceKey::Named(name.clone());
+    }
+    match filesystem_device(root) {
+        Some(dev) => DeviceKey::Filesystem(dev),
+        None => DeviceKey::Unresolved(root.to_path_buf()),
+    }
+}
+
+#[cfg(unix)]
+fn filesystem_device(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    std::fs::metadata(path).ok().map(|meta| meta.dev())
+}
+
+#[cfg(not(unix))]
+fn filesystem_device(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn library() -> LibraryId {
+        LibraryId(Uuid::now_v7())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn roots_on_the_same_mount_share_a_device() {
+        let base = std::env::temp_dir();
+        let a = base.join(format!("ferrex-device-a-{}", Uuid::now_v7()));
+        let b = base.join(format!("ferrex-device-b-{}", Uuid::now_v7()));
+        std::fs::create_dir_all(&a).unwrap();
+        std::fs::create_dir_all(&b).unwrap();
+
+        let devices = DeviceConcurrency::new(2);
+        devices.register_library(library(), std::slice::from_ref(&a));
+        devices.register_library(library(), std::slice::from_ref(&b));
+
+        let key_a = devices.device_for(&a.join("Movie (2020)")).unwrap();
+        let key_b = devices.device_for(&b.join("Show/Season 1")).unwrap();
+        assert_eq!(key_a, key_b);
+        assert_eq!(devices.groups().len(), 1);
+
+        std::fs::remove_dir_all(&a).ok();
+        std::fs::remove_dir_all(&b).ok();
+    }
+
+    #[test]
+    fn overrides_win_over_detection() {
+        let devices = DeviceConcurrency::new(1);
+        devices.set_overrides(HashMap::from([
+            (PathBuf::from("/mnt/farm"), "nas".to_string()),
+            (PathBuf::from("/mnt/farm/local"), "ssd".to_string()),
+        ]));
+        devices.register_library(
+            library(),
+            &[
+                PathBuf::from("/mnt/farm/movies"),
+                PathBuf::from("/mnt/farm/local/tv"),
+            ],
+        );
+
+        assert_eq!(
+            devices.device_for(Path::new("/mnt/farm/movies/Heat (1995)")),
+            Some(DeviceKey::Named("nas".into()))
+        );
+        assert_eq!(
+            devices.device_for(Path::new("/mnt/farm/local/tv/Show")),
+            Some(DeviceKey::Named("ssd".into()))
+        );
+        assert_eq!(devices.device_for(Path::new("/elsewhere")), None);
+    }
+
+    #[tokio::test]
+    async fn permits_are_shared_per_device() {
+        let devices = DeviceConcurrency::new(1);
+        devices.set_overrides(HashMap::from([(
+            PathBuf::from("/nas"),
+            "nas".to_string(),
+        )]));
+        devices.register_library(library(), &[PathBuf::from("/nas/movies")]);
+        devices.register_library(library(), &[PathBuf::from("/nas/tv")]);
+
+        let held = devices.acquire(Path::new("/nas/movies/A")).await;
+        assert!(held.is_some());
+
+        let waiting = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            devices.acquire(Path::new("/nas/tv/B")),
+        )
+        .await;
+        assert!(waiting.is_err(), "second library must wait for the device");
+
+        drop(held);
+        assert!(devices.acquire(Path::new("/nas/tv/B")).await.is_some());
+    }
+}
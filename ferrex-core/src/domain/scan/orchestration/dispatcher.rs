@@ -204,6 +204,12 @@ where
                     DispatchStatus::DeadLetter { error: msg }
                 }
             }
+            MediaError::InsufficientDiskSpace { .. } => {
+                // Space can be freed without a rescan; keep the job around.
+                let msg = err.to_string();
+                warn!(error = %msg, "retrying job due to low disk space");
+                DispatchStatus::Retry { error: msg }
+            }
             #[cfg(feature = "database")]
             MediaError::Database(err) => {
                 let msg = err.to_string();
@@ -300,6 +306,12 @@ where
                 Ok(()) => DispatchStatus::Success,
                 Err(err) => self.handle_media_error(err),
             },
+            Err(MediaError::Cancelled(msg)) => {
+                // The scan was cancelled while this job ran; its own work is
+                // done, the follow-up is simply not scheduled.
+                debug!(reason = %msg, "follow-up job dropped");
+                DispatchStatus::Success
+            }
             Err(err) => self.handle_media_error(err),
         }
     }
@@ -332,6 +344,10 @@ where
                 }
                 DispatchStatus::Success
             }
+            Err(MediaError::Cancelled(msg)) => {
+                debug!(reason = %msg, "follow-up jobs dropped");
+                DispatchStatus::Success
+            }
             Err(err) => self.handle_media_error(err),
         }
     }
@@ -421,7 +437,9 @@ where
                         Err(err) => return self.handle_media_error(err),
                     };
 
-                    if !matches!(state.status, SeriesScanStatus::Resolved) {
+                    if !matches!(state.status, SeriesScanStatus::Resolved)
+                        && self.actors.metadata.lookups_enabled()
+                    {
                         let series_job = SeriesResolveJob {
                             library_id: series_ctx.library_id,
                             series_root_path: series_ctx.series_root_path.clone(),
@@ -519,7 +537,9 @@ where
                     Err(err) => return self.handle_media_error(err),
                 };
 
-                if !matches!(state.status, SeriesScanStatus::Resolved) {
+                if !matches!(state.status, SeriesScanStatus::Resolved)
+                    && self.actors.metadata.lookups_enabled()
+                {
                     let series_job = SeriesResolveJob {
                         library_id: series_ctx.library_id,
                         series_root_path: series_ctx.series_root_path.clone(),
@@ -710,7 +730,11 @@ where
                 };
             };
 
-            if episode_hierarchy.series_id().is_none() {
+            // Without metadata lookups there is no series to wait for; the
+            // episode is enriched from its file name below.
+            if episode_hierarchy.series_id().is_none()
+                && self.actors.metadata.lookups_enabled()
+            {
                 let series_root = episode_hierarchy.series_root_path.clone();
                 let hint = episode_hierarchy.series_hint().cloned();
                 if let Err(err) = self
@@ -932,6 +956,12 @@ where
             return self.handle_media_error(err);
         }
 
+        // Limited metadata mode stores the file only; there is no
+        // reference for the indexer to pick up.
+        if ready.metadata_skipped {
+            return DispatchStatus::Success;
+        }
+
         if !ready.image_jobs.is_empty() {
             let image_requests: Vec<EnqueueRequest> = ready
                 .image_jobs
@@ -1006,6 +1036,7 @@ where
             },
             prepared_at: Utc::now(),
             image_jobs: Vec::new(),
+            metadata_skipped: false,
         };
 
         let outcome = match self
@@ -1172,6 +1203,7 @@ mod tests {
         let library_type = match library_type {
             LibraryType::Movies => "movies",
             LibraryType::Series => "tvshows",
+            LibraryType::Music => "music",
         };
 
         sqlx::query!(
@@ -1315,6 +1347,7 @@ mod tests {
                 analyzed: command.analyzed,
                 prepared_at: Utc::now(),
                 image_jobs: Vec::new(),
+                metadata_skipped: false,
             })
         }
     }
@@ -1372,6 +1405,7 @@ mod tests {
                 analyzed,
                 prepared_at: Utc::now(),
                 image_jobs: vec![],
+                metadata_skipped: false,
             };
 
             let _ = self
@@ -1505,6 +1539,8 @@ mod tests {
                 media_files: vec![PathBuf::from("/library/movie/movie.mkv")],
                 ancillary_files: vec![],
                 generated_listing_hash: unique_hash.clone(),
+                skipped: Default::default(),
+                excluded: Default::default(),
             },
             discovered: vec![MediaFileDiscovered {
                 library_id,
@@ -1531,6 +1567,8 @@ mod tests {
                 enqueued_subfolders: 1,
                 listing_hash: unique_hash,
                 completed_at: Utc::now(),
+                skipped: Default::default(),
+                excluded: Default::default(),
             },
         }) as Arc<dyn FolderScanActor>;
 
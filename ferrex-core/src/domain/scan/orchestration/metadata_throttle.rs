@@ -0,0 +1,285 @@
+//! Pacing of background metadata jobs against on-demand fetches.
+//!
+//! Metadata enrichment and episode matching jobs come from the persistent
+//! queue and can pile up by the thousand after a bulk scan. They share the
+//! provider budget with fetches an operator asks for directly, so each
+//! background job takes a slot of `metadata_limits.max_concurrency` only
+//! while on-demand fetches leave one free, starts no faster than `max_qps`,
+//! and pauses for `batch_delay_ms` after every `batch_size` jobs. On-demand
+//! fetches are never held back. Job outcomes are counted for the scan
+//! metrics.
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use tokio::{sync::Notify, time::Instant};
+
+use super::{config::MetadataLimits, dispatcher::DispatchStatus};
+
+/// Window over which recent completions are counted.
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+struct ThrottleState {
+    background: usize,
+    on_demand: usize,
+    started: u64,
+    next_start: Instant,
+    completed: u64,
+    retried: u64,
+    dead_lettered: u64,
+    recent: VecDeque<Instant>,
+}
+
+impl ThrottleState {
+    fn prune(&mut self, now: Instant) {
+        while self
+            .recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) > THROUGHPUT_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+    }
+}
+
+struct Inner {
+    limits: MetadataLimits,
+    state: Mutex<ThrottleState>,
+    released: Notify,
+}
+
+impl Inner {
+    fn lock(&self) -> MutexGuard<'_, ThrottleState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Start a background job now, or say how long to wait. `None` means
+    /// waiting for a slot to be released.
+    fn try_start(&self, now: Instant) -> Result<(), Option<Duration>> {
+        let mut state = self.lock();
+        let cap = self.limits.max_concurrency;
+        if cap > 0 && state.background + state.on_demand >= cap {
+            return Err(None);
+        }
+        if state.next_start > now {
+            return Err(Some(state.next_start - now));
+        }
+
+        state.background += 1;
+        state.started += 1;
+        let mut next_start = now;
+        if self.limits.max_qps > 0 {
+            next_start += Duration::from_secs(1) / self.limits.max_qps;
+        }
+        let batch = self.limits.batch_size as u64;
+        if batch > 0 && state.started.is_multiple_of(batch) {
+            next_start = next_start
+                .max(now + Duration::from_millis(self.limits.batch_delay_ms));
+        }
+        state.next_start = next_start;
+        Ok(())
+    }
+}
+
+/// Counters of the background metadata workers since startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MetadataThrottleStats {
+    pub background_in_flight: usize,
+    pub on_demand_in_flight: usize,
+    pub completed: u64,
+    pub retried: u64,
+    pub dead_lettered: u64,
+    /// Jobs completed during the last minute.
+    pub completed_last_minute: usize,
+}
+
+/// Shared pacing for metadata work. Cheap to clone.
+#[derive(Clone)]
+pub struct MetadataThrottle {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for MetadataThrottle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetadataThrottle")
+            .field("limits", &self.inner.limits)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl MetadataThrottle {
+    pub fn new(limits: MetadataLimits) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                limits,
+                state: Mutex::new(ThrottleState {
+                    background: 0,
+                    on_demand: 0,
+                    started: 0,
+                    next_start: Instant::now(),
+                    completed: 0,
+                    retried: 0,
+                    dead_lettered: 0,
+                    recent: VecDeque::new(),
+                }),
+                released: Notify::new(),
+            }),
+        }
+    }
+
+    pub fn limits(&self) -> MetadataLimits {
+        self.inner.limits
+    }
+
+    /// Wait until a background job may start.
+    pub async fn background(&self) -> BackgroundPermit {
+        loop {
+            // Registered before checking so a release in between is not
+            // missed.
+            let released = self.inner.released.notified();
+            match self.inner.try_start(Instant::now()) {
+                Ok(()) => {
+                    return BackgroundPermit {
+                        inner: Arc::clone(&self.inner),
+                    };
+                }
+                Err(Some(wait)) => tokio::time::sleep(wait).await,
+                Err(None) => released.await,
+            }
+        }
+    }
+
+    /// Count an on-demand fetch against the budget until the guard drops.
+    pub fn on_demand(&self) -> OnDemandGuard {
+        self.inner.lock().on_demand += 1;
+        OnDemandGuard {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    pub fn stats(&self) -> MetadataThrottleStats {
+        let mut state = self.inner.lock();
+        state.prune(Instant::now());
+        MetadataThrottleStats {
+            background_in_flight: state.background,
+            on_demand_in_flight: state.on_demand,
+            completed: state.completed,
+            retried: state.retried,
+            dead_lettered: state.dead_lettered,
+            completed_last_minute: state.recent.len(),
+        }
+    }
+}
+
+/// Slot of one running background job; released on drop.
+#[must_use]
+pub struct BackgroundPermit {
+    inner: Arc<Inner>,
+}
+
+impl BackgroundPermit {
+    /// Release the slot and count how the job ended.
+    pub fn finish(self, status: &DispatchStatus) {
+        let now = Instant::now();
+        {
+            let mut state = self.inner.lock();
+            match status {
+                DispatchStatus::Success => {
+                    state.completed += 1;
+                    state.recent.push_back(now);
+                    state.prune(now);
+                }
+                DispatchStatus::Retry { .. } => state.retried += 1,
+                DispatchStatus::DeadLetter { .. } => state.dead_lettered += 1,
+            }
+        }
+    }
+}
+
+impl Drop for BackgroundPermit {
+    fn drop(&mut self) {
+        self.inner.lock().background -= 1;
+        self.inner.released.notify_waiters();
+    }
+}
+
+/// Marks an on-demand fetch in flight; released on drop.
+#[must_use]
+pub struct OnDemandGuard {
+    inner: Arc<Inner>,
+}
+
+impl Drop for OnDemandGuard {
+    fn drop(&mut self) {
+        self.inner.lock().on_demand -= 1;
+        self.inner.released.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(max_concurrency: usize) -> MetadataLimits {
+        MetadataLimits {
+            max_concurrency,
+            max_qps: 0,
+            batch_size: 0,
+            batch_delay_ms: 0,
+        }
+    }
+
+    async fn blocked(throttle: &MetadataThrottle) -> bool {
+        tokio::time::timeout(Duration::from_millis(50), throttle.background())
+            .await
+            .is_err()
+    }
+
+    #[tokio::test]
+    async fn on_demand_fetches_take_background_slots() {
+        let throttle = MetadataThrottle::new(limits(2));
+
+        let first = throttle.background().await;
+        let on_demand = throttle.on_demand();
+        assert!(blocked(&throttle).await);
+        assert_eq!(throttle.stats().on_demand_in_flight, 1);
+
+        drop(on_demand);
+        let second = throttle.background().await;
+        assert!(blocked(&throttle).await);
+
+        first.finish(&DispatchStatus::Success);
+        second.finish(&DispatchStatus::DeadLetter {
+            error: "no match".into(),
+        });
+        let stats = throttle.stats();
+        assert_eq!(stats.background_in_flight, 0);
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.dead_lettered, 1);
+        assert_eq!(stats.completed_last_minute, 1);
+    }
+
+    #[tokio::test]
+    async fn batches_pause_between_them() {
+        let throttle = MetadataThrottle::new(MetadataLimits {
+            batch_size: 2,
+            batch_delay_ms: 200,
+            ..limits(0)
+        });
+        let started = Instant::now();
+
+        for _ in 0..2 {
+            throttle.background().await.finish(&DispatchStatus::Success);
+        }
+        assert!(started.elapsed() < Duration::from_millis(200));
+
+        throttle.background().await.finish(&DispatchStatus::Success);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -8,13 +8,16 @@ pub mod budget;
 pub mod config;
 pub mod context;
 pub mod correlation;
+pub mod device;
 pub mod dispatcher;
 pub mod events;
 pub mod job;
 pub mod lease;
+pub mod metadata_throttle;
 pub mod persistence;
 pub mod queue;
 pub mod runtime;
+pub mod scan_checkpoint;
 pub mod scan_cursor;
 pub mod scheduler;
 pub mod series;
@@ -24,13 +27,16 @@ pub use crate::domain::scan::actors::*;
 pub use budget::*;
 pub use config::*;
 pub use correlation::*;
+pub use device::*;
 pub use dispatcher::*;
 pub use events::*;
 pub use job::*;
 pub use lease::*;
+pub use metadata_throttle::*;
 pub use persistence::*;
 pub use queue::*;
 pub use runtime::*;
+pub use scan_checkpoint::*;
 pub use scan_cursor::*;
 pub use scheduler::*;
 pub use series::*;
@@ -7,8 +7,10 @@ use async_trait::async_trait;
 use chrono::Utc;
 use serde_json::from_value;
 use sqlx::PgPool;
+use std::collections::HashSet;
 use std::fmt;
 use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, RwLock};
 use tracing::{debug, info, trace, warn};
 
 use crate::domain::scan::orchestration::{
@@ -30,6 +32,8 @@ use crate::{error::MediaError, types::LibraryId};
 pub struct PostgresQueueService {
     pool: PgPool,
     retry_config: RetryConfig,
+    /// Libraries whose new jobs are refused while a cancelled scan drains.
+    suspended: Arc<RwLock<HashSet<LibraryId>>>,
 }
 
 impl fmt::Debug for PostgresQueueService {
@@ -38,6 +42,10 @@ impl fmt::Debug for PostgresQueueService {
             .field("pool_size", &self.pool.size())
             .field("idle_connections", &self.pool.num_idle())
             .field("retry_config", &self.retry_config)
+            .field(
+                "suspended_libraries",
+                &self.suspended.read().map(|set| set.len()).ok(),
+            )
             .finish()
     }
 }
@@ -101,13 +109,84 @@ impl PostgresQueueService {
             ));
         }
 
-        Ok(Self { pool, retry_config })
+        Ok(Self {
+            pool,
+            retry_config,
+            suspended: Arc::new(RwLock::new(HashSet::new())),
+        })
     }
 
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
 
+    /// Refuse new jobs for `library_id` with [`MediaError::Cancelled`] until
+    /// [`Self::resume_enqueues`] is called. Jobs already leased keep running.
+    pub fn suspend_enqueues(&self, library_id: LibraryId) {
+        if let Ok(mut suspended) = self.suspended.write() {
+            suspended.insert(library_id);
+        }
+    }
+
+    pub fn resume_enqueues(&self, library_id: LibraryId) {
+        if let Ok(mut suspended) = self.suspended.write() {
+            suspended.remove(&library_id);
+        }
+    }
+
+    fn ensure_enqueues_allowed(&self, library_id: LibraryId) -> Result<()> {
+        let suspended = self
+            .suspended
+            .read()
+            .map(|set| set.contains(&library_id))
+            .unwrap_or(false);
+        if suspended {
+            return Err(MediaError::Cancelled(format!(
+                "enqueues suspended for library {library_id}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Delete the library's jobs that have not been leased yet. Returns the
+    /// number of jobs dropped.
+    pub async fn drop_pending_jobs(
+        &self,
+        library_id: LibraryId,
+    ) -> Result<u64> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM orchestrator_jobs
+            WHERE library_id = $1 AND state IN ('ready','deferred')
+            "#,
+            library_id.to_uuid()
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("drop_pending_jobs failed: {e}"))
+        })?;
+        Ok(res.rows_affected())
+    }
+
+    /// Number of the library's jobs currently held by a worker.
+    pub async fn leased_job_count(&self, library_id: LibraryId) -> Result<u64> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*)::bigint AS "count!"
+            FROM orchestrator_jobs
+            WHERE library_id = $1 AND state = 'leased'
+            "#,
+            library_id.to_uuid()
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("leased job count failed: {e}"))
+        })?;
+        Ok(count.max(0) as u64)
+    }
+
     /// Housekeeping: scan for expired leases and resurrect them according to backoff policy.
     /// Returns the number of jobs transitioned back to Ready.
     pub async fn scan_expired_leases(&self) -> Result<u64> {
@@ -437,6 +516,7 @@ impl QueueInstrumentation for PostgresQueueService {
 impl QueueService for PostgresQueueService {
     async fn enqueue(&self, request: EnqueueRequest) -> Result<JobHandle> {
         request.validate()?;
+        self.ensure_enqueues_allowed(request.payload.library_id())?;
         let job_id = crate::domain::scan::orchestration::job::JobId::new();
         let payload_json =
             serde_json::to_value(&request.payload).map_err(|e| {
@@ -713,6 +793,9 @@ impl QueueService for PostgresQueueService {
         if requests.is_empty() {
             return Ok(Vec::new());
         }
+        for request in &requests {
+            self.ensure_enqueues_allowed(request.payload.library_id())?;
+        }
 
         let mut tx = self.pool.begin().await.map_err(|e| {
             MediaError::Internal(format!("begin enqueue_many tx failed: {e}"))
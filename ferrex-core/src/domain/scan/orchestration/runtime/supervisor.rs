@@ -15,6 +15,7 @@ use crate::domain::scan::orchestration::{
     budget::{WorkloadBudget, WorkloadType},
     config::OrchestratorConfig,
     correlation::CorrelationCache,
+    device::DeviceConcurrency,
     dispatcher::{DispatchStatus, JobDispatcher},
     events::{
         JobEvent, JobEventPayload, ScanEvent, ScanEventBus, stable_path_key,
@@ -24,6 +25,7 @@ use crate::domain::scan::orchestration::{
         JobPriority, ScanReason,
     },
     lease::{DequeueRequest, LeaseRenewal, QueueSelector},
+    metadata_throttle::MetadataThrottle,
     queue::{LeaseExpiryScanner, QueueService},
     scheduler::WeightedFairScheduler,
 };
@@ -53,6 +55,8 @@ where
     dispatcher: Arc<dyn JobDispatcher>,
     correlations: CorrelationCache,
     scheduler: WeightedFairScheduler,
+    devices: DeviceConcurrency,
+    metadata: MetadataThrottle,
     library_actors: Arc<RwLock<HashMap<LibraryId, LibraryActorHandle>>>,
     mailbox_tx:
         Arc<Mutex<Option<tokio::sync::mpsc::Sender<OrchestratorCommand>>>>,
@@ -99,6 +103,8 @@ where
             .field("budget_type", &budget_type)
             .field("dispatcher_type", &dispatcher_type)
             .field("scheduler", &self.scheduler)
+            .field("devices", &self.devices)
+            .field("metadata", &self.metadata)
             .field("library_actor_count", &library_actor_count)
             .field("worker_handle_count", &worker_handle_count)
             .field("mailbox_ready", &mailbox_ready)
@@ -126,6 +132,9 @@ where
     ) -> Self {
         let scheduler =
             WeightedFairScheduler::new(&config.queue, config.priority_weights);
+        let devices =
+            DeviceConcurrency::new(config.queue.max_parallel_scans_per_device);
+        let metadata = MetadataThrottle::new(config.metadata_limits);
 
         Self {
             config,
@@ -135,6 +144,8 @@ where
             dispatcher,
             correlations,
             scheduler,
+            devices,
+            metadata,
             library_actors: Arc::new(RwLock::new(HashMap::new())),
             mailbox_tx: Arc::new(Mutex::new(None)),
             shutdown_token: CancellationToken::new(),
@@ -170,6 +181,35 @@ where
         self.scheduler.clone()
     }
 
+    /// Device grouping that bounds concurrent folder scans per mount.
+    pub fn devices(&self) -> DeviceConcurrency {
+        self.devices.clone()
+    }
+
+    /// Pacing shared by background metadata jobs and on-demand fetches.
+    pub fn metadata_throttle(&self) -> MetadataThrottle {
+        self.metadata.clone()
+    }
+
+    fn log_device_groups(&self) {
+        let per_device = self.devices.per_device();
+        for (device, roots) in self.devices.groups() {
+            let libraries: std::collections::BTreeSet<_> =
+                roots.iter().map(|(library_id, _)| *library_id).collect();
+            let roots: Vec<String> = roots
+                .iter()
+                .map(|(_, root)| root.display().to_string())
+                .collect();
+            tracing::info!(
+                device = %device,
+                per_device_cap = per_device,
+                libraries = libraries.len(),
+                roots = ?roots,
+                "scan device group"
+            );
+        }
+    }
+
     pub async fn register_library_actor(
         &self,
         library_id: LibraryId,
@@ -194,6 +234,7 @@ where
     }
 
     pub async fn start(&self) -> Result<()> {
+        self.log_device_groups();
         self.spawn_scheduler_observer();
 
         // Route domain events -> orchestrator actions (e.g., enqueue folder scans)
@@ -576,6 +617,8 @@ where
         let mailbox = Arc::clone(&self.mailbox_tx);
         let correlations = self.correlations.clone();
         let scheduler = self.scheduler.clone();
+        let devices = self.devices.clone();
+        let metadata = self.metadata.clone();
 
         for i in 0..parallelism {
             let worker_id = format!("{}-w{}", worker_group, i);
@@ -587,6 +630,8 @@ where
             let correlation_cache = correlations.clone();
             let shutdown = self.shutdown_token.clone();
             let scheduler = scheduler.clone();
+            let devices = devices.clone();
+            let metadata = metadata.clone();
             let worker_kind = kind;
 
             let handle = tokio::spawn(async move {
@@ -808,7 +853,31 @@ where
                                 }
                             });
 
+                            // Folder scans share a budget per device; wait
+                            // while the lease keeps renewing above.
+                            let device_permit = match &lease.job.payload {
+                                JobPayload::FolderScan(job) => {
+                                    devices
+                                        .acquire(std::path::Path::new(
+                                            job.context.folder_path_norm(),
+                                        ))
+                                        .await
+                                }
+                                _ => None,
+                            };
+                            let metadata_permit = match &lease.job.payload {
+                                JobPayload::MetadataEnrich(_)
+                                | JobPayload::EpisodeMatch(_) => {
+                                    Some(metadata.background().await)
+                                }
+                                _ => None,
+                            };
+
                             let dispatch_status = d.dispatch(&lease).await;
+                            drop(device_permit);
+                            if let Some(permit) = metadata_permit {
+                                permit.finish(&dispatch_status);
+                            }
 
                             // Stop renewer
                             let _ = cancel_tx.try_send(());
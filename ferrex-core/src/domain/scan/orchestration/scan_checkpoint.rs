@@ -0,0 +1,331 @@
+//! Durable checkpoints of scan runs.
+//!
+//! A run writes its status and progress to `scan_state` while it goes, so a
+//! scan that was unfinished when the server stopped is found again on the
+//! next start and can be resumed. A resumed scan reseeds its library:
+//! folders whose listing still matches their
+//! [`ScanCursor`](super::scan_cursor::ScanCursor) are skipped, folders that
+//! changed while the server was down are rescanned.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{
+    database::traits::{ScanOrigin, ScanStatus},
+    error::Result,
+    types::LibraryId,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ScanCheckpoint {
+    pub scan_id: Uuid,
+    pub library_id: LibraryId,
+    pub correlation_id: Uuid,
+    pub origin: ScanOrigin,
+    /// The interrupted scan this one picked up.
+    pub resumed_from: Option<Uuid>,
+    pub status: ScanStatus,
+    pub completed_items: u64,
+    pub total_items: u64,
+    pub current_path: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+pub trait ScanCheckpointRepository: Send + Sync {
+    /// Insert the checkpoint, or overwrite the stored one of its scan.
+    async fn save(&self, checkpoint: &ScanCheckpoint) -> Result<()>;
+
+    async fn get(&self, scan_id: Uuid) -> Result<Option<ScanCheckpoint>>;
+
+    /// Mark every unfinished checkpoint interrupted and return them. Only
+    /// call this at startup, before any scan of this process began.
+    async fn mark_interrupted(&self) -> Result<Vec<ScanCheckpoint>>;
+
+    /// Interrupted scans that no later scan resumed, newest first.
+    async fn list_resumable(&self) -> Result<Vec<ScanCheckpoint>>;
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryScanCheckpointRepository {
+    checkpoints: Arc<Mutex<HashMap<Uuid, ScanCheckpoint>>>,
+}
+
+#[async_trait]
+impl ScanCheckpointRepository for InMemoryScanCheckpointRepository {
+    async fn save(&self, checkpoint: &ScanCheckpoint) -> Result<()> {
+        let mut guard = self.checkpoints.lock().await;
+        guard.insert(checkpoint.scan_id, checkpoint.clone());
+        Ok(())
+    }
+
+    async fn get(&self, scan_id: Uuid) -> Result<Option<ScanCheckpoint>> {
+        let guard = self.checkpoints.lock().await;
+        Ok(guard.get(&scan_id).cloned())
+    }
+
+    async fn mark_interrupted(&self) -> Result<Vec<ScanCheckpoint>> {
+        let mut guard = self.checkpoints.lock().await;
+        let now = Utc::now();
+        let mut interrupted = Vec::new();
+        for checkpoint in guard.values_mut() {
+            if checkpoint.status.is_unfinished() {
+                checkpoint.status = ScanStatus::Interrupted;
+                checkpoint.updated_at = now;
+                interrupted.push(checkpoint.clone());
+            }
+        }
+        interrupted.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        Ok(interrupted)
+    }
+
+    async fn list_resumable(&self) -> Result<Vec<ScanCheckpoint>> {
+        let guard = self.checkpoints.lock().await;
+        let mut resumable: Vec<ScanCheckpoint> = guard
+            .values()
+            .filter(|checkpoint| checkpoint.status == ScanStatus::Interrupted)
+            .filter(|checkpoint| {
+                !guard
+                    .values()
+                    .any(|later| later.resumed_from == Some(checkpoint.scan_id))
+            })
+            .cloned()
+            .collect();
+        resumable.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        Ok(resumable)
+    }
+}
+
+#[cfg(feature = "database")]
+pub struct PostgresScanCheckpointRepository {
+    pool: sqlx::PgPool,
+}
+
+#[cfg(feature = "database")]
+impl std::fmt::Debug for PostgresScanCheckpointRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostgresScanCheckpointRepository")
+            .field("pool_size", &self.pool.size())
+            .field("idle_connections", &self.pool.num_idle())
+            .finish()
+    }
+}
+
+#[cfg(feature = "database")]
+impl PostgresScanCheckpointRepository {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg(feature = "database")]
+#[derive(sqlx::FromRow)]
+struct CheckpointRow {
+    id: Uuid,
+    library_id: Uuid,
+    correlation_id: Option<Uuid>,
+    origin: String,
+    resumed_from: Option<Uuid>,
+    status: String,
+    processed_files: Option<i32>,
+    total_files: Option<i32>,
+    current_path: Option<String>,
+    started_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "database")]
+impl TryFrom<CheckpointRow> for ScanCheckpoint {
+    type Error = crate::error::MediaError;
+
+    fn try_from(row: CheckpointRow) -> Result<Self> {
+        let status = ScanStatus::parse(&row.status).ok_or_else(|| {
+            crate::error::MediaError::Internal(format!(
+                "unknown scan_state status '{}'",
+                row.status
+            ))
+        })?;
+        let origin = ScanOrigin::parse(&row.origin).ok_or_else(|| {
+            crate::error::MediaError::Internal(format!(
+                "unknown scan_state origin '{}'",
+                row.origin
+            ))
+        })?;
+        Ok(ScanCheckpoint {
+            scan_id: row.id,
+            library_id: LibraryId(row.library_id),
+            correlation_id: row.correlation_id.unwrap_or(row.id),
+            origin,
+            resumed_from: row.resumed_from,
+            status,
+            completed_items: row.processed_files.unwrap_or(0).max(0) as u64,
+            total_items: row.total_files.unwrap_or(0).max(0) as u64,
+            current_path: row.current_path,
+            started_at: row.started_at,
+            updated_at: row.updated_at,
+            completed_at: row.completed_at,
+        })
+    }
+}
+
+#[cfg(feature = "database")]
+const CHECKPOINT_COLUMNS: &str = "id, library_id, correlation_id, origin, \
+     resumed_from, status, processed_files, total_files, current_path, \
+     started_at, updated_at, completed_at";
+
+#[cfg(feature = "database")]
+#[async_trait]
+impl ScanCheckpointRepository for PostgresScanCheckpointRepository {
+    async fn save(&self, checkpoint: &ScanCheckpoint) -> Result<()> {
+        // `valid_progress` requires processed <= total.
+        let total = i32::try_from(checkpoint.total_items).unwrap_or(i32::MAX);
+        let processed = i32::try_from(checkpoint.completed_items)
+            .unwrap_or(i32::MAX)
+            .min(total);
+
+        sqlx::query(
+            r#"
+            INSERT INTO scan_state (
+                id, library_id, scan_type, status, total_files,
+                processed_files, current_path, started_at, completed_at,
+                correlation_id, origin, resumed_from
+            )
+            VALUES ($1, $2, 'full', $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (id) DO UPDATE SET
+                status = EXCLUDED.status,
+                total_files = EXCLUDED.total_files,
+                processed_files = EXCLUDED.processed_files,
+                current_path = EXCLUDED.current_path,
+                completed_at = EXCLUDED.completed_at
+            "#,
+        )
+        .bind(checkpoint.scan_id)
+        .bind(checkpoint.library_id.0)
+        .bind(checkpoint.status.as_str())
+        .bind(total)
+        .bind(processed)
+        .bind(checkpoint.current_path.as_deref())
+        .bind(checkpoint.started_at)
+        .bind(checkpoint.completed_at)
+        .bind(checkpoint.correlation_id)
+        .bind(checkpoint.origin.as_str())
+        .bind(checkpoint.resumed_from)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, scan_id: Uuid) -> Result<Option<ScanCheckpoint>> {
+        let row: Option<CheckpointRow> = sqlx::query_as(&format!(
+            "SELECT {CHECKPOINT_COLUMNS} FROM scan_state WHERE id = $1"
+        ))
+        .bind(scan_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(ScanCheckpoint::try_from).transpose()
+    }
+
+    async fn mark_interrupted(&self) -> Result<Vec<ScanCheckpoint>> {
+        let rows: Vec<CheckpointRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE scan_state
+            SET status = 'interrupted'
+            WHERE status IN ('pending', 'running', 'paused')
+            RETURNING {CHECKPOINT_COLUMNS}
+            "#
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut interrupted = rows
+            .into_iter()
+            .map(ScanCheckpoint::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        interrupted.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        Ok(interrupted)
+    }
+
+    async fn list_resumable(&self) -> Result<Vec<ScanCheckpoint>> {
+        let rows: Vec<CheckpointRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {CHECKPOINT_COLUMNS}
+            FROM scan_state s
+            WHERE s.status = 'interrupted'
+              AND NOT EXISTS (
+                  SELECT 1 FROM scan_state r WHERE r.resumed_from = s.id
+              )
+            ORDER BY s.started_at DESC
+            "#
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(ScanCheckpoint::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(status: ScanStatus) -> ScanCheckpoint {
+        let scan_id = Uuid::now_v7();
+        ScanCheckpoint {
+            scan_id,
+            library_id: LibraryId::new(),
+            correlation_id: scan_id,
+            origin: ScanOrigin::Fresh,
+            resumed_from: None,
+            status,
+            completed_items: 3,
+            total_items: 10,
+            current_path: None,
+            started_at: Utc::now(),
+            updated_at: Utc::now(),
+            completed_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn unfinished_scans_become_resumable_until_resumed() {
+        let repo = InMemoryScanCheckpointRepository::default();
+        let running = checkpoint(ScanStatus::Running);
+        let paused = checkpoint(ScanStatus::Paused);
+        let completed = checkpoint(ScanStatus::Completed);
+        for saved in [&running, &paused, &completed] {
+            repo.save(saved).await.unwrap();
+        }
+
+        let interrupted = repo.mark_interrupted().await.unwrap();
+        assert_eq!(interrupted.len(), 2);
+        assert!(
+            interrupted
+                .iter()
+                .all(|entry| entry.status == ScanStatus::Interrupted)
+        );
+        assert_eq!(repo.list_resumable().await.unwrap().len(), 2);
+
+        let mut resumed = checkpoint(ScanStatus::Running);
+        resumed.origin = ScanOrigin::Resumed;
+        resumed.resumed_from = Some(running.scan_id);
+        repo.save(&resumed).await.unwrap();
+
+        let resumable = repo.list_resumable().await.unwrap();
+        assert_eq!(resumable.len(), 1);
+        assert_eq!(resumable[0].scan_id, paused.scan_id);
+        assert_eq!(
+            repo.get(completed.scan_id).await.unwrap().unwrap().status,
+            ScanStatus::Completed
+        );
+    }
+}
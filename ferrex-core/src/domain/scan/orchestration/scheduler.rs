@@ -391,6 +391,17 @@ impl WeightedFairScheduler {
         self.release(library_id).await;
     }
 
+    /// Forget the ready counts of `library_id` after its queued jobs were
+    /// dropped, so workers stop reserving slots for jobs that no longer exist.
+    pub async fn clear_ready(&self, library_id: LibraryId) {
+        let mut state = self.state.lock().await;
+        if let Some(library) = state.libraries.get_mut(&library_id) {
+            for priority_state in library.priorities.values_mut() {
+                priority_state.ready = 0;
+            }
+        }
+    }
+
     #[cfg(test)]
     pub async fn snapshot(&self) -> HashMap<LibraryId, (usize, usize)> {
         let state = self.state.lock().await;
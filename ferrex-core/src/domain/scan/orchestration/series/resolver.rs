@@ -11,7 +11,7 @@ use crate::{
         },
     },
     error::{MediaError, Result},
-    types::ids::LibraryId,
+    types::ids::{LibraryId, SeriesID},
 };
 
 use std::sync::Arc;
@@ -24,12 +24,16 @@ pub struct SeriesResolution {
 
 #[async_trait]
 pub trait SeriesMetadataProvider: Send + Sync {
+    /// Resolve the series rooted at `series_root_path`. `previous` is the
+    /// series this root resolved to last time, if any; providers reuse it
+    /// when an operator pinned its match.
     async fn resolve_series(
         &self,
         library_id: LibraryId,
         series_root_path: &SeriesRootPath,
         hint: &SeriesHint,
         folder_name: &str,
+        previous: Option<SeriesID>,
     ) -> Result<SeriesResolution>;
 }
 
@@ -108,6 +112,12 @@ impl SeriesResolverPort for DefaultSeriesResolver {
                 )
             })?;
 
+        let previous = self
+            .states
+            .get(job.library_id, &job.series_root_path)
+            .await?
+            .and_then(|state| state.series_id);
+
         self.states
             .mark_seeded(
                 job.library_id,
@@ -123,6 +133,7 @@ impl SeriesResolverPort for DefaultSeriesResolver {
                 &job.series_root_path,
                 &hint,
                 &job.folder_name,
+                previous,
             )
             .await?;
 
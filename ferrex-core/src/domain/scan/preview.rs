@@ -0,0 +1,276 @@
+//! What a scan of a library would pick up, without running one.
+//!
+//! A preview walks the library's roots with the exclude patterns and file
+//! filter its scans use and only counts what it finds: nothing is parsed,
+//! matched, downloaded or stored. Media files are reported new or already
+//! indexed by comparing their paths with the library's records.
+//!
+//! Every folder that is not hidden or excluded is descended into, while
+//! scans of video libraries ignore folders they do not support yet (extras
+//! below a movie, anything but seasons below a series), so the counts are
+//! an upper bound.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use crate::{
+    domain::scan::{
+        exclusions::{ExcludedPathCounts, ScanExclusions},
+        file_filter::{
+            FileVerdict, ScanFileFilter, SkippedFileCounts, scan_file_filter,
+        },
+    },
+    error::{MediaError, Result},
+    types::{
+        ids::LibraryId,
+        library::{Library, LibraryType},
+    },
+};
+
+/// Media files with one extension.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct ExtensionPreview {
+    pub files: u64,
+    pub bytes: u64,
+    /// Files not indexed yet.
+    pub new_files: u64,
+}
+
+/// Media files a scan of a library would process.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanPreview {
+    pub library_id: LibraryId,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub media_files: u64,
+    pub total_bytes: u64,
+    /// Media files without a record yet.
+    pub new_files: u64,
+    /// Media files the library already indexes.
+    pub indexed_files: u64,
+    /// Keyed by lowercased extension.
+    pub by_extension: BTreeMap<String, ExtensionPreview>,
+    pub skipped_files: SkippedFileCounts,
+    pub excluded_paths: ExcludedPathCounts,
+    /// Roots that are missing or not directories; nothing was counted
+    /// below them.
+    pub offline_roots: Vec<String>,
+}
+
+/// Counts gathered by one walk.
+#[derive(Debug, Default)]
+struct PreviewCounts {
+    by_extension: BTreeMap<String, ExtensionPreview>,
+    skipped: SkippedFileCounts,
+    excluded: ExcludedPathCounts,
+    offline_roots: Vec<String>,
+}
+
+/// Walk `library`'s roots and count what a scan would pick up.
+/// `indexed` holds the paths the library already has records for.
+///
+/// The walk stops with [`MediaError::Cancelled`] soon after `cancel` fires.
+pub async fn preview_library_scan(
+    library: &Library,
+    indexed: HashSet<PathBuf>,
+    cancel: CancellationToken,
+) -> Result<ScanPreview> {
+    let started_at = Utc::now();
+    let exclusions =
+        ScanExclusions::new(&library.paths, &library.exclude_patterns)?;
+    let filter = match library.library_type {
+        LibraryType::Music => ScanFileFilter::audio(),
+        _ => scan_file_filter().clone(),
+    };
+    let roots = library.paths.clone();
+
+    let counts = tokio::task::spawn_blocking(move || {
+        count_roots(&roots, &filter, &exclusions, &indexed, &cancel)
+    })
+    .await
+    .map_err(|err| {
+        MediaError::Internal(format!("scan preview task failed: {err}"))
+    })??;
+
+    let totals = counts.by_extension.values().fold(
+        ExtensionPreview::default(),
+        |mut total, ext| {
+            total.files += ext.files;
+            total.bytes += ext.bytes;
+            total.new_files += ext.new_files;
+            total
+        },
+    );
+    Ok(ScanPreview {
+        library_id: library.id,
+        started_at,
+        finished_at: Utc::now(),
+        media_files: totals.files,
+        total_bytes: totals.bytes,
+        new_files: totals.new_files,
+        indexed_files: totals.files - totals.new_files,
+        by_extension: counts.by_extension,
+        skipped_files: counts.skipped,
+        excluded_paths: counts.excluded,
+        offline_roots: counts.offline_roots,
+    })
+}
+
+fn count_roots(
+    roots: &[PathBuf],
+    filter: &ScanFileFilter,
+    exclusions: &ScanExclusions,
+    indexed: &HashSet<PathBuf>,
+    cancel: &CancellationToken,
+) -> Result<PreviewCounts> {
+    let mut counts = PreviewCounts::default();
+    for root in roots {
+        if !root.is_dir() {
+            counts.offline_roots.push(root.display().to_string());
+            continue;
+        }
+        let mut pending = vec![root.clone()];
+        while let Some(dir) = pending.pop() {
+            if cancel.is_cancelled() {
+                return Err(MediaError::Cancelled(
+                    "scan preview cancelled".into(),
+                ));
+            }
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(err) => {
+                    debug!(?dir, error = %err, "skipping unreadable folder");
+                    continue;
+                }
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if let Some(pattern) = exclusions.excluded_by(&path) {
+                    counts.excluded.record(pattern);
+                    continue;
+                }
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                if metadata.is_dir() {
+                    if !entry.file_name().to_string_lossy().starts_with('.') {
+                        pending.push(path);
+                    }
+                    continue;
+                }
+                if !metadata.is_file() {
+                    continue;
+                }
+                match filter.check(&path, metadata.len()) {
+                    FileVerdict::Media => {
+                        counts.record(&path, metadata.len(), indexed)
+                    }
+                    FileVerdict::Sidecar => {}
+                    FileVerdict::Skipped(reason) => {
+                        counts.skipped.record(reason)
+                    }
+                }
+            }
+        }
+    }
+    Ok(counts)
+}
+
+impl PreviewCounts {
+    fn record(&mut self, path: &Path, size: u64, indexed: &HashSet<PathBuf>) {
+        let ext = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        let entry = self.by_extension.entry(ext).or_default();
+        entry.files += 1;
+        entry.bytes += size;
+        if !indexed.contains(path) {
+            entry.new_files += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn media_is_counted_per_extension_and_against_the_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let heat = root.join("Heat (1995)");
+        std::fs::create_dir_all(heat.join("Sample")).unwrap();
+        std::fs::write(heat.join("Heat.mkv"), [0u8; 10]).unwrap();
+        std::fs::write(heat.join("Heat.nfo"), b"").unwrap();
+        std::fs::write(heat.join("Sample").join("sample.mkv"), [0u8; 3])
+            .unwrap();
+        let alien = root.join("Alien (1979)");
+        std::fs::create_dir_all(&alien).unwrap();
+        std::fs::write(alien.join("Alien.MP4"), [0u8; 7]).unwrap();
+        std::fs::write(alien.join("Alien.iso"), b"").unwrap();
+        std::fs::create_dir_all(root.join(".trash")).unwrap();
+        std::fs::write(root.join(".trash").join("old.mkv"), b"").unwrap();
+
+        let filter = ScanFileFilter::new(&["mkv", "mp4"], None, None).unwrap();
+        let exclusions =
+            ScanExclusions::new(&[root.clone()], &["sample"]).unwrap();
+        let indexed = HashSet::from([heat.join("Heat.mkv")]);
+        let counts = count_roots(
+            &[root.clone(), root.join("unmounted")],
+            &filter,
+            &exclusions,
+            &indexed,
+            &CancellationToken::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            counts.by_extension.get("mkv"),
+            Some(&ExtensionPreview {
+                files: 1,
+                bytes: 10,
+                new_files: 0
+            })
+        );
+        assert_eq!(
+            counts.by_extension.get("mp4"),
+            Some(&ExtensionPreview {
+                files: 1,
+                bytes: 7,
+                new_files: 1
+            })
+        );
+        assert_eq!(counts.skipped.extension, 1);
+        assert_eq!(counts.excluded.iter().collect::<Vec<_>>(), [("sample", 1)]);
+        assert_eq!(
+            counts.offline_roots,
+            [root.join("unmounted").display().to_string()]
+        );
+    }
+
+    #[test]
+    fn a_cancelled_preview_stops() {
+        let dir = tempfile::tempdir().unwrap();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let result = count_roots(
+            &[dir.path().to_path_buf()],
+            &ScanFileFilter::default(),
+            &ScanExclusions::default(),
+            &HashSet::new(),
+            &cancel,
+        );
+        assert!(matches!(result, Err(MediaError::Cancelled(_))));
+    }
+}
@@ -4,5 +4,6 @@
 //! canonical values live in `ferrex-model` and are re-exported here for compatibility.
 
 pub use crate::types::scan::scanner::settings::{
-    DEFAULT_VIDEO_FILE_EXTENSIONS, default_video_file_extensions_vec,
+    DEFAULT_AUDIO_FILE_EXTENSIONS, DEFAULT_VIDEO_FILE_EXTENSIONS,
+    default_video_file_extensions_vec,
 };
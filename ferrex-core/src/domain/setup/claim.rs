@@ -1,8 +1,10 @@
 use std::{any::type_name_of_val, fmt, net::IpAddr, sync::Arc};
 
 use chrono::{DateTime, Duration, Utc};
+use constant_time_eq::constant_time_eq;
 use rand::{TryRngCore, rngs::OsRng};
 use thiserror::Error;
+use url::Url;
 use uuid::Uuid;
 
 use crate::{
@@ -17,6 +19,14 @@ const DEFAULT_CLAIM_TTL_MINUTES: i64 = 10;
 const CLAIM_CODE_LENGTH: usize = 6;
 const CLAIM_TOKEN_LENGTH: usize = 32;
 
+/// Scheme of claim deep links, e.g.
+/// `ferrex://setup/claim?server=…&claim=…&exp=…&sig=…`.
+pub const CLAIM_LINK_SCHEME: &str = "ferrex";
+const CLAIM_LINK_HOST: &str = "setup";
+const CLAIM_LINK_PATH: &str = "/claim";
+/// Keeps link signatures apart from the token hashes made with the same key.
+const CLAIM_LINK_CONTEXT: &str = "ferrex-setup-claim-link";
+
 /// Provides business logic for the first-run setup claim workflow.
 #[derive(Clone)]
 pub struct SetupClaimService<R>
@@ -100,6 +110,81 @@ where
         })
     }
 
+    /// Start a claim and sign a deep link for it that a player can scan to
+    /// learn `server_url` and confirm the claim without typing the code.
+    pub async fn begin(
+        &self,
+        client_name: Option<String>,
+        client_ip: Option<IpAddr>,
+        server_url: &Url,
+    ) -> Result<(StartedClaim, ClaimLink), SetupClaimError> {
+        let started = self.start_claim(client_name, client_ip).await?;
+        let link =
+            self.sign_link(server_url, started.claim_id, started.expires_at);
+        Ok((started, link))
+    }
+
+    /// Deep link for the pending claim with `code`. Asking for it counts as
+    /// an attempt, since the link is as good as the code.
+    pub async fn claim_link(
+        &self,
+        code: &str,
+        server_url: &Url,
+    ) -> Result<ClaimLink, SetupClaimError> {
+        if code.trim().is_empty() {
+            return Err(SetupClaimError::InvalidCode);
+        }
+
+        let now = Utc::now();
+        let hashed = self.crypto.hash_token(code);
+        let record = self
+            .repository
+            .find_active_by_code_hash(&hashed, now)
+            .await
+            .map_err(SetupClaimError::from)?
+            .ok_or(SetupClaimError::InvalidCode)?;
+
+        self.repository
+            .increment_attempt(record.id, now)
+            .await
+            .map_err(SetupClaimError::from)?;
+
+        Ok(self.sign_link(server_url, record.id, record.expires_at))
+    }
+
+    /// Confirm a claim from a scanned deep link instead of its code.
+    ///
+    /// The link must carry a valid signature, must not have expired, and
+    /// must name the claim that is still awaiting confirmation, so links
+    /// of revoked or already confirmed claims are refused.
+    pub async fn confirm_claim_link(
+        &self,
+        payload: &str,
+    ) -> Result<ConfirmedClaim, SetupClaimError> {
+        let link = self.verify_link(payload)?;
+        let now = Utc::now();
+        if link.expires_at <= now {
+            return Err(SetupClaimError::Expired {
+                expired_at: link.expires_at,
+            });
+        }
+
+        let record = self
+            .repository
+            .get_active(now)
+            .await
+            .map_err(SetupClaimError::from)?
+            .filter(|record| record.id == link.claim_id)
+            .ok_or(SetupClaimError::InvalidLink)?;
+
+        self.repository
+            .increment_attempt(record.id, now)
+            .await
+            .map_err(SetupClaimError::from)?;
+
+        self.issue_claim_token(record.id, now).await
+    }
+
     pub async fn confirm_claim(
         &self,
         code: &str,
@@ -129,11 +214,19 @@ where
             });
         }
 
+        self.issue_claim_token(record.id, now).await
+    }
+
+    async fn issue_claim_token(
+        &self,
+        claim_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<ConfirmedClaim, SetupClaimError> {
         let claim_token = generate_claim_token();
         let token_hash = self.crypto.hash_token(&claim_token);
         let updated = self
             .repository
-            .mark_confirmed(record.id, token_hash, now)
+            .mark_confirmed(claim_id, token_hash, now)
             .await
             .map_err(SetupClaimError::from)?;
 
@@ -144,6 +237,92 @@ where
         })
     }
 
+    fn link_signature(
+        &self,
+        server_url: &str,
+        claim_id: Uuid,
+        expires_at: i64,
+    ) -> String {
+        self.crypto.hash_token(&format!(
+            "{CLAIM_LINK_CONTEXT}\n{server_url}\n{claim_id}\n{expires_at}"
+        ))
+    }
+
+    fn sign_link(
+        &self,
+        server_url: &Url,
+        claim_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> ClaimLink {
+        let server = server_url.as_str().trim_end_matches('/').to_string();
+        let exp = expires_at.timestamp();
+        let sig = self.link_signature(&server, claim_id, exp);
+
+        let mut link = Url::parse(&format!(
+            "{CLAIM_LINK_SCHEME}://{CLAIM_LINK_HOST}{CLAIM_LINK_PATH}"
+        ))
+        .expect("claim link base is a valid URL");
+        link.query_pairs_mut()
+            .append_pair("server", &server)
+            .append_pair("claim", &claim_id.to_string())
+            .append_pair("exp", &exp.to_string())
+            .append_pair("sig", &sig);
+
+        ClaimLink {
+            payload: link.into(),
+            server_url: server,
+            claim_id,
+            expires_at,
+        }
+    }
+
+    /// Parse a deep link and check its signature; expiry and claim state
+    /// are left to the caller.
+    pub fn verify_link(
+        &self,
+        payload: &str,
+    ) -> Result<ClaimLink, SetupClaimError> {
+        let link = Url::parse(payload.trim())
+            .map_err(|_| SetupClaimError::InvalidLink)?;
+        if link.scheme() != CLAIM_LINK_SCHEME
+            || link.host_str() != Some(CLAIM_LINK_HOST)
+            || link.path() != CLAIM_LINK_PATH
+        {
+            return Err(SetupClaimError::InvalidLink);
+        }
+
+        let (mut server, mut claim_id, mut exp, mut sig) =
+            (None, None, None, None);
+        for (key, value) in link.query_pairs() {
+            match key.as_ref() {
+                "server" => server = Some(value.into_owned()),
+                "claim" => claim_id = value.parse::<Uuid>().ok(),
+                "exp" => exp = value.parse::<i64>().ok(),
+                "sig" => sig = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        let (Some(server), Some(claim_id), Some(exp), Some(sig)) =
+            (server, claim_id, exp, sig)
+        else {
+            return Err(SetupClaimError::InvalidLink);
+        };
+
+        let expected = self.link_signature(&server, claim_id, exp);
+        if !constant_time_eq(expected.as_bytes(), sig.as_bytes()) {
+            return Err(SetupClaimError::InvalidLink);
+        }
+        let expires_at = DateTime::from_timestamp(exp, 0)
+            .ok_or(SetupClaimError::InvalidLink)?;
+
+        Ok(ClaimLink {
+            payload: payload.trim().to_string(),
+            server_url: server,
+            claim_id,
+            expires_at,
+        })
+    }
+
     pub async fn validate_claim_token(
         &self,
         token: &str,
@@ -225,6 +404,8 @@ pub enum SetupClaimError {
     InvalidCode,
     #[error("claim token is invalid or expired")]
     InvalidToken,
+    #[error("claim link is invalid or no longer active")]
+    InvalidLink,
     #[error("claim expired at {expired_at}")]
     Expired { expired_at: DateTime<Utc> },
     #[error(transparent)]
@@ -238,6 +419,15 @@ pub struct StartedClaim {
     pub expires_at: DateTime<Utc>,
 }
 
+/// Signed deep link for a claim, suitable for a QR code.
+#[derive(Debug, Clone)]
+pub struct ClaimLink {
+    pub payload: String,
+    pub server_url: String,
+    pub claim_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ConfirmedClaim {
     pub claim_id: Uuid,
@@ -478,4 +668,54 @@ mod tests {
             Err(SetupClaimError::ActiveClaimPending { claim_id, .. }) if claim_id == first.claim_id
         ));
     }
+
+    #[tokio::test]
+    async fn claim_link_confirms_until_revoked() {
+        let repo = Arc::new(InMemoryRepo::new());
+        let service = SetupClaimService::new(repo.clone(), build_crypto());
+        let server = Url::parse("https://ferrex.lan:3000/").unwrap();
+
+        let (started, link) = service
+            .begin(Some("Player".into()), None, &server)
+            .await
+            .expect("begin claim");
+        assert!(link.payload.starts_with("ferrex://setup/claim?"));
+        assert_eq!(link.server_url, "https://ferrex.lan:3000");
+        assert_eq!(link.claim_id, started.claim_id);
+
+        let tampered = link.payload.replace("ferrex.lan", "evil.example");
+        assert!(matches!(
+            service.confirm_claim_link(&tampered).await,
+            Err(SetupClaimError::InvalidLink)
+        ));
+
+        assert!(matches!(
+            service.claim_link("WRONG1", &server).await,
+            Err(SetupClaimError::InvalidCode)
+        ));
+        let regenerated = service
+            .claim_link(&started.claim_code, &server)
+            .await
+            .unwrap();
+        let confirmed = service
+            .confirm_claim_link(&regenerated.payload)
+            .await
+            .expect("link confirms the pending claim");
+        assert_eq!(confirmed.claim_id, started.claim_id);
+
+        // A confirmed claim no longer accepts its link.
+        assert!(matches!(
+            service.confirm_claim_link(&link.payload).await,
+            Err(SetupClaimError::InvalidLink)
+        ));
+
+        // Revoking claims invalidates their outstanding links.
+        service.revoke_all(Some("test")).await.unwrap();
+        let (_, revoked) = service.begin(None, None, &server).await.unwrap();
+        service.revoke_all(Some("test")).await.unwrap();
+        assert!(matches!(
+            service.confirm_claim_link(&revoked.payload).await,
+            Err(SetupClaimError::InvalidLink)
+        ));
+    }
 }
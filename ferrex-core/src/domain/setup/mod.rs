@@ -1,6 +1,6 @@
 pub mod claim;
 
 pub use claim::{
-    ConfirmedClaim, ConsumedClaim, SetupClaimError, SetupClaimService,
-    StartedClaim, ValidatedClaimToken,
+    CLAIM_LINK_SCHEME, ClaimLink, ConfirmedClaim, ConsumedClaim,
+    SetupClaimError, SetupClaimService, StartedClaim, ValidatedClaimToken,
 };
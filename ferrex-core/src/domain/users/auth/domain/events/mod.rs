@@ -79,6 +79,26 @@ pub enum AuthEvent {
         timestamp: DateTime<Utc>,
     },
 
+    /// An already-rotated refresh token was presented again; its family
+    /// has been revoked
+    RefreshTokenReused {
+        family_id: Uuid,
+        user_id: Uuid,
+        device_session_id: Option<Uuid>,
+        generation: u32,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A device id sent by the client is already claimed by another
+    /// user's device
+    DeviceIdCollision {
+        user_id: Uuid,
+        device_id: Uuid,
+        /// Id handed to the client instead, unless the login was refused
+        reissued_device_id: Option<Uuid>,
+        timestamp: DateTime<Utc>,
+    },
+
     /// User authenticated with password
     PasswordAuthenticated {
         user_id: Uuid,
@@ -124,6 +144,8 @@ impl AuthEvent {
             Self::SessionCreated { timestamp, .. } => *timestamp,
             Self::SessionRefreshed { timestamp, .. } => *timestamp,
             Self::AuthenticationFailed { timestamp, .. } => *timestamp,
+            Self::RefreshTokenReused { timestamp, .. } => *timestamp,
+            Self::DeviceIdCollision { timestamp, .. } => *timestamp,
             Self::PasswordAuthenticated { timestamp, .. } => *timestamp,
             Self::PasswordChanged { timestamp, .. } => *timestamp,
             Self::AccountLocked { timestamp, .. } => *timestamp,
@@ -144,6 +166,8 @@ impl AuthEvent {
             Self::SessionCreated { user_id, .. } => *user_id,
             Self::SessionRefreshed { user_id, .. } => *user_id,
             Self::AuthenticationFailed { user_id, .. } => *user_id,
+            Self::RefreshTokenReused { user_id, .. } => *user_id,
+            Self::DeviceIdCollision { user_id, .. } => *user_id,
             Self::PasswordAuthenticated { user_id, .. } => *user_id,
             Self::PasswordChanged { user_id, .. } => *user_id,
             Self::AccountLocked { user_id, .. } => *user_id,
@@ -164,6 +188,8 @@ impl AuthEvent {
             Self::SessionCreated { .. } => "session_created",
             Self::SessionRefreshed { .. } => "session_refreshed",
             Self::AuthenticationFailed { .. } => "authentication_failed",
+            Self::RefreshTokenReused { .. } => "refresh_token_reused",
+            Self::DeviceIdCollision { .. } => "device_id_collision",
             Self::PasswordAuthenticated { .. } => "password_authenticated",
             Self::PasswordChanged { .. } => "password_changed",
             Self::AccountLocked { .. } => "account_locked",
@@ -2,6 +2,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use uuid::Uuid;
 
+use crate::domain::users::auth::AuthEventType;
 use crate::domain::users::auth::domain::aggregates::{
     DeviceSession, UserAuthentication,
 };
@@ -48,6 +49,20 @@ pub trait DeviceSessionRepository: Send + Sync {
         &self,
         fingerprint: &DeviceFingerprint,
     ) -> Result<Vec<DevicePinStatus>>;
+    /// Sessions whose client last logged in with `device_id`.
+    async fn find_by_client_device_id(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Vec<DeviceSession>>;
+    /// Remember the client-supplied device id a session logged in with.
+    async fn set_client_device_id(
+        &self,
+        session_id: Uuid,
+        device_id: Uuid,
+    ) -> Result<()>;
+    /// Flag `device_id` as presented by more than one user's device.
+    async fn mark_device_id_contested(&self, device_id: Uuid) -> Result<()>;
+    async fn is_device_id_contested(&self, device_id: Uuid) -> Result<bool>;
 }
 
 #[derive(Debug, Clone)]
@@ -66,6 +81,8 @@ pub struct RefreshTokenRecord {
     pub revoked: bool,
     pub revoked_reason: Option<String>,
     pub used_count: i32,
+    /// When the token was last presented for rotation.
+    pub used_at: Option<DateTime<Utc>>,
     pub origin_scope: SessionScope,
 }
 
@@ -96,11 +113,25 @@ pub trait RefreshTokenRepository: Send + Sync {
         reason: RevocationReason,
     ) -> Result<()>;
 
+    /// Revoke every live token in the family and return the auth session
+    /// ids any token in the family was ever bound to.
     async fn revoke_family(
         &self,
         family_id: Uuid,
         reason: RevocationReason,
-    ) -> Result<()>;
+    ) -> Result<Vec<Uuid>>;
+
+    /// Revoke the unconsumed successors of `generation` in a family,
+    /// returning the auth session ids they were bound to.
+    ///
+    /// Returns `None` without revoking anything when a successor has
+    /// already been consumed, i.e. the chain has moved on.
+    async fn revoke_unused_successors(
+        &self,
+        family_id: Uuid,
+        generation: i32,
+        reason: RevocationReason,
+    ) -> Result<Option<Vec<Uuid>>>;
 
     async fn revoke_for_user(
         &self,
@@ -233,9 +264,32 @@ pub enum AuthAuditEventKind {
     SessionCreated,
     SessionRevoked,
     AutoLogin,
+    RefreshTokenReuse,
+    DeviceIdCollision,
 }
 
 impl AuthAuditEventKind {
+    pub const ALL: [Self; 13] = [
+        Self::PasswordLoginSuccess,
+        Self::PasswordLoginFailure,
+        Self::PinLoginSuccess,
+        Self::PinLoginFailure,
+        Self::DeviceRegistered,
+        Self::DeviceRevoked,
+        Self::PinSet,
+        Self::PinRemoved,
+        Self::SessionCreated,
+        Self::SessionRevoked,
+        Self::AutoLogin,
+        Self::RefreshTokenReuse,
+        Self::DeviceIdCollision,
+    ];
+
+    /// Inverse of [`Self::as_str`].
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::PasswordLoginSuccess => "password_login_success",
@@ -249,6 +303,42 @@ impl AuthAuditEventKind {
             Self::SessionCreated => "session_created",
             Self::SessionRevoked => "session_revoked",
             Self::AutoLogin => "auto_login",
+            Self::RefreshTokenReuse => "refresh_token_reuse",
+            Self::DeviceIdCollision => "device_id_collision",
+        }
+    }
+}
+
+impl From<AuthAuditEventKind> for AuthEventType {
+    fn from(kind: AuthAuditEventKind) -> Self {
+        match kind {
+            AuthAuditEventKind::PasswordLoginSuccess => {
+                AuthEventType::PasswordLoginSuccess
+            }
+            AuthAuditEventKind::PasswordLoginFailure => {
+                AuthEventType::PasswordLoginFailure
+            }
+            AuthAuditEventKind::PinLoginSuccess => {
+                AuthEventType::PinLoginSuccess
+            }
+            AuthAuditEventKind::PinLoginFailure => {
+                AuthEventType::PinLoginFailure
+            }
+            AuthAuditEventKind::DeviceRegistered => {
+                AuthEventType::DeviceRegistered
+            }
+            AuthAuditEventKind::DeviceRevoked => AuthEventType::DeviceRevoked,
+            AuthAuditEventKind::PinSet => AuthEventType::PinSet,
+            AuthAuditEventKind::PinRemoved => AuthEventType::PinRemoved,
+            AuthAuditEventKind::SessionCreated => AuthEventType::SessionCreated,
+            AuthAuditEventKind::SessionRevoked => AuthEventType::SessionRevoked,
+            AuthAuditEventKind::AutoLogin => AuthEventType::AutoLogin,
+            AuthAuditEventKind::RefreshTokenReuse => {
+                AuthEventType::RefreshTokenReuse
+            }
+            AuthAuditEventKind::DeviceIdCollision => {
+                AuthEventType::DeviceIdCollision
+            }
         }
     }
 }
@@ -274,7 +364,44 @@ impl AuthEventLog {
     }
 }
 
+/// Filters for reading the audit log back, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct AuthEventQuery {
+    pub user_id: Option<Uuid>,
+    /// Any of these types; empty means all
+    pub event_types: Vec<AuthAuditEventKind>,
+    /// Inclusive lower bound on `occurred_at`
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `occurred_at`
+    pub until: Option<DateTime<Utc>>,
+    /// `(occurred_at, id)` of the last event already returned
+    pub after: Option<(DateTime<Utc>, Uuid)>,
+    pub limit: usize,
+}
+
+/// A stored audit event, with the name of its device when known.
+#[derive(Debug, Clone)]
+pub struct AuthEventRecord {
+    pub id: Uuid,
+    pub event_type: AuthAuditEventKind,
+    pub user_id: Option<Uuid>,
+    pub device_session_id: Option<Uuid>,
+    pub device_name: Option<String>,
+    pub session_id: Option<Uuid>,
+    pub success: bool,
+    pub failure_reason: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub metadata: Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
 #[async_trait]
 pub trait AuthEventRepository: Send + Sync {
     async fn record(&self, events: Vec<AuthEventLog>) -> Result<()>;
+    /// Events matching `query`, ordered by `(occurred_at, id)` descending.
+    async fn list(
+        &self,
+        query: &AuthEventQuery,
+    ) -> Result<Vec<AuthEventRecord>>;
 }
@@ -13,9 +13,9 @@ use crate::domain::users::auth::domain::aggregates::{
 };
 use crate::domain::users::auth::domain::events::AuthEvent;
 use crate::domain::users::auth::domain::repositories::{
-    AuthEventRepository, AuthSessionRecord, AuthSessionRepository,
-    DeviceSessionRepository, RefreshTokenRepository,
-    UserAuthenticationRepository,
+    AuthEventQuery, AuthEventRecord, AuthEventRepository, AuthSessionRecord,
+    AuthSessionRepository, DeviceSessionRepository, RefreshTokenRecord,
+    RefreshTokenRepository, UserAuthenticationRepository,
 };
 use crate::domain::users::auth::domain::value_objects::{
     DeviceFingerprint, RefreshToken, RevocationReason, SessionScope,
@@ -24,6 +24,34 @@ use crate::domain::users::auth::domain::value_objects::{
 use base64::Engine as _;
 use base64::engine::general_purpose::STANDARD as BASE64;
 
+/// How long after a rotation the consumed refresh token may be presented
+/// again. Covers clients that retry because the rotation response was lost;
+/// anything later is treated as a replay of a stolen token.
+const REFRESH_RETRY_GRACE_SECS: i64 = 30;
+
+/// Default for [`AuthenticationService::with_clock_skew_leeway`].
+pub const DEFAULT_CLOCK_SKEW_LEEWAY_SECS: i64 = 5;
+
+/// Lifetimes applied to newly issued credentials. Tokens already persisted
+/// keep the expiry they were issued with, so changing these only affects
+/// tokens minted afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenLifetimes {
+    pub session: Duration,
+    pub refresh: Duration,
+    pub device_challenge: Duration,
+}
+
+impl Default for TokenLifetimes {
+    fn default() -> Self {
+        Self {
+            session: Duration::hours(24),
+            refresh: Duration::days(30),
+            device_challenge: Duration::minutes(2),
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum AuthenticationError {
     #[error("Invalid credentials")]
@@ -40,6 +68,8 @@ pub enum AuthenticationError {
     TooManyFailedAttempts,
     #[error("Session expired")]
     SessionExpired,
+    #[error("Refresh token reuse detected; sign in again")]
+    RefreshTokenReused,
     #[error("Database error: {0}")]
     DatabaseError(#[from] anyhow::Error),
 }
@@ -56,6 +86,8 @@ pub struct AuthenticationService {
             dyn crate::domain::users::auth::domain::repositories::DeviceChallengeRepository,
         >,
     >,
+    lifetimes: TokenLifetimes,
+    clock_skew_leeway: Duration,
 }
 
 #[derive(Debug, Clone)]
@@ -145,9 +177,34 @@ impl AuthenticationService {
             crypto,
             event_repo: None,
             challenge_repo: None,
+            lifetimes: TokenLifetimes::default(),
+            clock_skew_leeway: Duration::seconds(
+                DEFAULT_CLOCK_SKEW_LEEWAY_SECS,
+            ),
         }
     }
 
+    pub fn with_token_lifetimes(mut self, lifetimes: TokenLifetimes) -> Self {
+        self.lifetimes = lifetimes;
+        self
+    }
+
+    pub fn token_lifetimes(&self) -> TokenLifetimes {
+        self.lifetimes
+    }
+
+    /// How long past its expiry a session or refresh token is still
+    /// accepted, so a client whose clock runs slightly ahead of ours is
+    /// not signed out early.
+    pub fn with_clock_skew_leeway(mut self, leeway: Duration) -> Self {
+        self.clock_skew_leeway = leeway;
+        self
+    }
+
+    fn has_expired(&self, expires_at: DateTime<Utc>) -> bool {
+        Utc::now() > expires_at + self.clock_skew_leeway
+    }
+
     pub fn with_event_repository(
         mut self,
         event_repo: Arc<dyn AuthEventRepository>,
@@ -289,7 +346,7 @@ impl AuthenticationService {
             return Err(AuthenticationError::SessionExpired);
         }
 
-        if record.expires_at < Utc::now() {
+        if self.has_expired(record.expires_at) {
             return Err(AuthenticationError::SessionExpired);
         }
 
@@ -307,6 +364,33 @@ impl AuthenticationService {
         })
     }
 
+    /// Look up the persisted record behind a session token without touching
+    /// its activity timestamp.
+    ///
+    /// Unknown, revoked, and expired tokens all collapse into
+    /// `SessionExpired` so callers cannot tell whether a token ever existed.
+    pub async fn introspect_session_token(
+        &self,
+        token: &str,
+    ) -> Result<AuthSessionRecord, AuthenticationError> {
+        let token_hash = self.crypto.hash_token(token);
+
+        let record = self
+            .session_store
+            .find_by_hash(&token_hash)
+            .await
+            .map_err(AuthenticationError::from)?;
+
+        match record {
+            Some(record)
+                if !record.revoked && !self.has_expired(record.expires_at) =>
+            {
+                Ok(record)
+            }
+            _ => Err(AuthenticationError::SessionExpired),
+        }
+    }
+
     pub async fn authenticate_user(
         &self,
         username: &str,
@@ -355,7 +439,7 @@ impl AuthenticationService {
     ) -> Result<TokenBundle, AuthenticationError> {
         let user = self.authenticate_user(username, password).await?;
 
-        let session_token = SessionToken::generate(Duration::hours(24))
+        let session_token = SessionToken::generate(self.lifetimes.session)
             .map_err(|_| {
                 AuthenticationError::DatabaseError(anyhow::anyhow!(
                     "failed to generate session token"
@@ -376,7 +460,7 @@ impl AuthenticationService {
             )
             .await?;
 
-        let refresh_token = RefreshToken::generate(Duration::days(30))
+        let refresh_token = RefreshToken::generate(self.lifetimes.refresh)
             .map_err(|_| {
                 AuthenticationError::DatabaseError(anyhow::anyhow!(
                     "failed to generate refresh token"
@@ -460,7 +544,7 @@ impl AuthenticationService {
         let session_token = if verified {
             session.record_pin_success();
             session
-                .issue_pin_session(Duration::hours(24))
+                .issue_pin_session(self.lifetimes.session)
                 .map_err(|_| AuthenticationError::InvalidCredentials)?
         } else {
             let err = session.register_pin_failure(3);
@@ -501,7 +585,7 @@ impl AuthenticationService {
 
         self.publish_events(events, context).await?;
 
-        let refresh_token = RefreshToken::generate(Duration::days(30))
+        let refresh_token = RefreshToken::generate(self.lifetimes.refresh)
             .map_err(|_| {
                 AuthenticationError::DatabaseError(anyhow::anyhow!(
                     "failed to generate refresh token"
@@ -555,25 +639,22 @@ impl AuthenticationService {
             .ok_or(AuthenticationError::SessionExpired)?;
 
         if record.revoked {
-            let reused_after_rotation = record.used_count > 0
+            let rotated = record.used_count > 0
                 && matches!(
                     record.revoked_reason.as_deref(),
                     Some(reason) if reason == RevocationReason::Rotation.as_str()
                 );
 
-            if reused_after_rotation {
-                self.refresh_repo
-                    .revoke_family(
-                        record.token.family_id(),
-                        RevocationReason::ReuseDetected,
-                    )
-                    .await?;
+            if !rotated {
+                return Err(AuthenticationError::SessionExpired);
             }
 
-            return Err(AuthenticationError::SessionExpired);
+            if !self.retire_unreceived_successors(&record).await? {
+                return Err(self.revoke_reused_family(&record).await);
+            }
         }
 
-        if record.token.is_expired() {
+        if self.has_expired(record.token.expires_at()) {
             return Err(AuthenticationError::SessionExpired);
         }
 
@@ -602,9 +683,10 @@ impl AuthenticationService {
                 return Err(AuthenticationError::DeviceNotTrusted);
             }
 
-            let session_token = session
-                .refresh_token(Duration::hours(24))
-                .map_err(|_| AuthenticationError::SessionExpired)?;
+            let session_token =
+                session
+                    .refresh_token(self.lifetimes.session)
+                    .map_err(|_| AuthenticationError::SessionExpired)?;
 
             let persisted_hash = self.crypto.hash_token(session_token.as_str());
             let persisted_token = SessionToken::from_value(
@@ -635,7 +717,7 @@ impl AuthenticationService {
             self.publish_events(events, context).await?;
 
             let refresh_token =
-                record.token.rotate(Duration::days(30)).map_err(|_| {
+                record.token.rotate(self.lifetimes.refresh).map_err(|_| {
                     AuthenticationError::DatabaseError(anyhow::anyhow!(
                         "failed to rotate refresh token"
                     ))
@@ -674,7 +756,7 @@ impl AuthenticationService {
                 scope: SessionScope::Playback,
             })
         } else {
-            let session_token = SessionToken::generate(Duration::hours(24))
+            let session_token = SessionToken::generate(self.lifetimes.session)
                 .map_err(|_| {
                     AuthenticationError::DatabaseError(anyhow::anyhow!(
                         "failed to generate session token"
@@ -695,7 +777,7 @@ impl AuthenticationService {
                 .await?;
 
             let refresh_token =
-                record.token.rotate(Duration::days(30)).map_err(|_| {
+                record.token.rotate(self.lifetimes.refresh).map_err(|_| {
                     AuthenticationError::DatabaseError(anyhow::anyhow!(
                         "failed to rotate refresh token"
                     ))
@@ -819,7 +901,7 @@ impl AuthenticationService {
         let session_token = if verified {
             session.record_pin_success();
             session
-                .issue_pin_session(Duration::hours(24))
+                .issue_pin_session(self.lifetimes.session)
                 .map_err(|_| AuthenticationError::InvalidCredentials)?
         } else {
             let err = session.register_pin_failure(3);
@@ -858,7 +940,7 @@ impl AuthenticationService {
         };
         self.publish_events(events, context).await?;
 
-        let refresh_token = RefreshToken::generate(Duration::days(30))
+        let refresh_token = RefreshToken::generate(self.lifetimes.refresh)
             .map_err(|_| {
                 AuthenticationError::DatabaseError(anyhow::anyhow!(
                     "failed to generate refresh token"
@@ -919,6 +1001,19 @@ impl AuthenticationService {
             .map_err(AuthenticationError::from)
     }
 
+    /// Read back the audit log; needs an event repository.
+    pub async fn list_auth_events(
+        &self,
+        query: &AuthEventQuery,
+    ) -> Result<Vec<AuthEventRecord>, AuthenticationError> {
+        let repo = self.event_repo.as_ref().ok_or_else(|| {
+            AuthenticationError::DatabaseError(anyhow::anyhow!(
+                "auth event repository not configured"
+            ))
+        })?;
+        repo.list(query).await.map_err(AuthenticationError::from)
+    }
+
     pub async fn revoke_session_by_id(
         &self,
         session_id: Uuid,
@@ -1011,6 +1106,93 @@ impl AuthenticationService {
         }
     }
 
+    /// Decide whether a replayed, already-rotated refresh token is a client
+    /// retry. Within the grace window, and only while none of its successors
+    /// has been used, the successors the client never received are revoked
+    /// (with their sessions) so the token can rotate again.
+    async fn retire_unreceived_successors(
+        &self,
+        record: &RefreshTokenRecord,
+    ) -> Result<bool, AuthenticationError> {
+        let within_grace = record.used_count == 1
+            && record.used_at.is_some_and(|used_at| {
+                Utc::now() - used_at
+                    <= Duration::seconds(REFRESH_RETRY_GRACE_SECS)
+            });
+        if !within_grace {
+            return Ok(false);
+        }
+
+        let generation =
+            i32::try_from(record.token.generation()).map_err(|_| {
+                AuthenticationError::DatabaseError(anyhow::anyhow!(
+                    "refresh token generation overflow"
+                ))
+            })?;
+
+        let Some(session_ids) = self
+            .refresh_repo
+            .revoke_unused_successors(
+                record.token.family_id(),
+                generation,
+                RevocationReason::SessionReplaced,
+            )
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        for session_id in session_ids {
+            self.session_store
+                .revoke_by_id(session_id, RevocationReason::SessionReplaced)
+                .await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Treat a replayed refresh token as stolen: revoke its whole family and
+    /// every session it produced, and audit the replay.
+    async fn revoke_reused_family(
+        &self,
+        record: &RefreshTokenRecord,
+    ) -> AuthenticationError {
+        let result: Result<(), AuthenticationError> = async {
+            let session_ids = self
+                .refresh_repo
+                .revoke_family(
+                    record.token.family_id(),
+                    RevocationReason::ReuseDetected,
+                )
+                .await?;
+
+            for session_id in session_ids {
+                self.session_store
+                    .revoke_by_id(session_id, RevocationReason::ReuseDetected)
+                    .await?;
+            }
+
+            let event = AuthEvent::RefreshTokenReused {
+                family_id: record.token.family_id(),
+                user_id: record.user_id,
+                device_session_id: record.device_session_id,
+                generation: record.token.generation(),
+                timestamp: Utc::now(),
+            };
+            let context = AuthEventContext {
+                auth_session_id: record.session_id,
+                ..Default::default()
+            };
+            self.publish_events(vec![event], context).await
+        }
+        .await;
+
+        match result {
+            Ok(()) => AuthenticationError::RefreshTokenReused,
+            Err(err) => err,
+        }
+    }
+
     async fn publish_events(
         &self,
         events: Vec<AuthEvent>,
@@ -1048,11 +1230,11 @@ impl AuthenticationService {
         Ok(user.pin_client_salt().to_vec())
     }
 
-    /// Create a short-lived device possession challenge nonce
+    /// Create a short-lived device possession challenge nonce that expires
+    /// after the configured device challenge lifetime
     pub async fn create_device_challenge(
         &self,
         device_session_id: Uuid,
-        ttl_seconds: i64,
     ) -> Result<(Uuid, Vec<u8>), AuthenticationError> {
         use rand::RngCore;
         let repo = self.challenge_repo.as_ref().ok_or_else(|| {
@@ -1070,7 +1252,7 @@ impl AuthenticationService {
 
         let mut nonce = vec![0u8; 32];
         rand::rng().fill_bytes(&mut nonce);
-        let expires_at = Utc::now() + Duration::seconds(ttl_seconds.max(30));
+        let expires_at = Utc::now() + self.lifetimes.device_challenge;
 
         let id = repo
             .insert_challenge(device_session_id, &nonce, expires_at)
@@ -37,16 +37,36 @@ pub enum DeviceTrustError {
     TooManyDevices { limit: usize },
     #[error("Device not trusted")]
     DeviceNotTrusted,
+    #[error("Device id is already in use by another device")]
+    DeviceIdCollision,
     #[error("Database error: {0}")]
     DatabaseError(#[from] anyhow::Error),
 }
 
+/// What to do when a client logs in with a device id that another user's
+/// device already claims.
+///
+/// Device ids are generated and sent by clients, so a cloned install or a
+/// misbehaving client can present someone else's. Whichever way it is
+/// handled, the id is flagged as contested and rate limiting stops keying
+/// on it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeviceIdCollisionPolicy {
+    /// Let the login through under a fresh, server-assigned device id that
+    /// the client is expected to adopt.
+    #[default]
+    Reissue,
+    /// Refuse the login with [`DeviceTrustError::DeviceIdCollision`].
+    Reject,
+}
+
 pub struct DeviceTrustService {
     user_repo: Arc<dyn UserAuthenticationRepository>,
     session_repo: Arc<dyn DeviceSessionRepository>,
     event_repo: Arc<dyn AuthEventRepository>,
     session_store: Arc<dyn AuthSessionRepository>,
     refresh_repo: Arc<dyn RefreshTokenRepository>,
+    collision_policy: DeviceIdCollisionPolicy,
 }
 
 impl fmt::Debug for DeviceTrustService {
@@ -55,6 +75,7 @@ impl fmt::Debug for DeviceTrustService {
             .field("user_repo_refs", &Arc::strong_count(&self.user_repo))
             .field("session_repo_refs", &Arc::strong_count(&self.session_repo))
             .field("event_repo_refs", &Arc::strong_count(&self.event_repo))
+            .field("collision_policy", &self.collision_policy)
             .finish()
     }
 }
@@ -73,9 +94,18 @@ impl DeviceTrustService {
             event_repo,
             session_store,
             refresh_repo,
+            collision_policy: DeviceIdCollisionPolicy::default(),
         }
     }
 
+    pub fn with_device_id_collision_policy(
+        mut self,
+        policy: DeviceIdCollisionPolicy,
+    ) -> Self {
+        self.collision_policy = policy;
+        self
+    }
+
     /// Register a device for the specified user, creating a pending trust session when necessary.
     pub async fn register_device(
         &self,
@@ -134,6 +164,75 @@ impl DeviceTrustService {
         }
     }
 
+    /// Register the device a client identifies by `device_id`.
+    ///
+    /// The id is client-supplied. When only other users' devices, with
+    /// other fingerprints, already claim it, the collision is audited and
+    /// handled per [`DeviceIdCollisionPolicy`]. `fingerprint` derives
+    /// the device fingerprint from whichever id ends up in use. Returns the
+    /// session along with that id.
+    pub async fn register_client_device<F>(
+        &self,
+        user_id: Uuid,
+        device_id: Uuid,
+        fingerprint: F,
+        device_name: String,
+        context: Option<AuthEventContext>,
+    ) -> Result<(DeviceSession, Uuid), DeviceTrustError>
+    where
+        F: Fn(Uuid) -> DeviceFingerprint + Send,
+    {
+        let ctx = context.unwrap_or_default();
+        let device_id = self
+            .resolve_device_id(
+                user_id,
+                device_id,
+                &fingerprint(device_id),
+                &ctx,
+            )
+            .await?;
+
+        let session = self
+            .register_device(
+                user_id,
+                fingerprint(device_id),
+                device_name,
+                Some(ctx),
+            )
+            .await?;
+        self.session_repo
+            .set_client_device_id(session.id(), device_id)
+            .await?;
+        Ok((session, device_id))
+    }
+
+    /// Whether `device_id` has been presented by more than one user's
+    /// device.
+    pub async fn is_device_id_contested(
+        &self,
+        device_id: Uuid,
+    ) -> Result<bool, DeviceTrustError> {
+        self.session_repo
+            .is_device_id_contested(device_id)
+            .await
+            .map_err(DeviceTrustError::from)
+    }
+
+    /// Whether the device session `session_id` logged in as `device_id` and
+    /// has not been revoked.
+    pub async fn is_session_on_device(
+        &self,
+        session_id: Uuid,
+        device_id: Uuid,
+    ) -> Result<bool, DeviceTrustError> {
+        Ok(self
+            .session_repo
+            .find_by_client_device_id(device_id)
+            .await?
+            .iter()
+            .any(|session| session.id() == session_id && !session.is_revoked()))
+    }
+
     /// Retrieve all device sessions for a user.
     pub async fn list_devices(
         &self,
@@ -279,6 +378,53 @@ impl DeviceTrustService {
         Ok(())
     }
 
+    /// The id `user_id`'s device should use, given that it asked for
+    /// `device_id`. The id is kept when it is unclaimed, already claimed by
+    /// this user, or claimed by a session with the same fingerprint, i.e.
+    /// the same device shared between users.
+    async fn resolve_device_id(
+        &self,
+        user_id: Uuid,
+        device_id: Uuid,
+        fingerprint: &DeviceFingerprint,
+        context: &AuthEventContext,
+    ) -> Result<Uuid, DeviceTrustError> {
+        let claimants = self
+            .session_repo
+            .find_by_client_device_id(device_id)
+            .await?;
+        let active: Vec<_> = claimants
+            .iter()
+            .filter(|session| !session.is_revoked())
+            .collect();
+        let accepted = active.is_empty()
+            || active.iter().any(|session| {
+                session.user_id() == user_id
+                    || session.device_fingerprint().as_str()
+                        == fingerprint.as_str()
+            });
+        if accepted {
+            return Ok(device_id);
+        }
+
+        self.session_repo
+            .mark_device_id_contested(device_id)
+            .await?;
+        let reissued_device_id = match self.collision_policy {
+            DeviceIdCollisionPolicy::Reissue => Some(Uuid::now_v7()),
+            DeviceIdCollisionPolicy::Reject => None,
+        };
+        let event = AuthEvent::DeviceIdCollision {
+            user_id,
+            device_id,
+            reissued_device_id,
+            timestamp: Utc::now(),
+        };
+        self.publish_events(vec![event], context).await?;
+
+        reissued_device_id.ok_or(DeviceTrustError::DeviceIdCollision)
+    }
+
     async fn create_new_session(
         &self,
         user_id: Uuid,
@@ -355,9 +501,9 @@ mod tests {
         DeviceSession, DeviceStatus, UserAuthentication,
     };
     use crate::domain::users::auth::domain::repositories::{
-        AuthAuditEventKind, AuthEventLog, AuthSessionRecord,
-        AuthSessionRepository, DevicePinStatus, RefreshTokenRecord,
-        RefreshTokenRepository,
+        AuthAuditEventKind, AuthEventLog, AuthEventQuery, AuthEventRecord,
+        AuthSessionRecord, AuthSessionRepository, DevicePinStatus,
+        RefreshTokenRecord, RefreshTokenRepository,
     };
     use crate::domain::users::auth::domain::value_objects::RevocationReason;
     use crate::domain::users::auth::domain::value_objects::{
@@ -366,7 +512,7 @@ mod tests {
     use async_trait::async_trait;
     use chrono::{DateTime, Utc};
     use futures::FutureExt;
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
     use std::sync::Mutex;
 
@@ -409,6 +555,8 @@ mod tests {
     #[derive(Debug, Default)]
     struct InMemoryDeviceRepo {
         sessions: Mutex<HashMap<Uuid, DeviceSession>>,
+        client_device_ids: Mutex<HashMap<Uuid, Uuid>>,
+        contested: Mutex<HashSet<Uuid>>,
     }
 
     #[async_trait]
@@ -485,6 +633,46 @@ mod tests {
                 })
                 .collect())
         }
+
+        async fn find_by_client_device_id(
+            &self,
+            device_id: Uuid,
+        ) -> anyhow::Result<Vec<DeviceSession>> {
+            let claims = self.client_device_ids.lock().unwrap();
+            let sessions = self.sessions.lock().unwrap();
+            Ok(claims
+                .iter()
+                .filter(|(_, claimed)| **claimed == device_id)
+                .filter_map(|(session_id, _)| sessions.get(session_id).cloned())
+                .collect())
+        }
+
+        async fn set_client_device_id(
+            &self,
+            session_id: Uuid,
+            device_id: Uuid,
+        ) -> anyhow::Result<()> {
+            self.client_device_ids
+                .lock()
+                .unwrap()
+                .insert(session_id, device_id);
+            Ok(())
+        }
+
+        async fn mark_device_id_contested(
+            &self,
+            device_id: Uuid,
+        ) -> anyhow::Result<()> {
+            self.contested.lock().unwrap().insert(device_id);
+            Ok(())
+        }
+
+        async fn is_device_id_contested(
+            &self,
+            device_id: Uuid,
+        ) -> anyhow::Result<bool> {
+            Ok(self.contested.lock().unwrap().contains(&device_id))
+        }
     }
 
     #[derive(Debug, Default)]
@@ -502,6 +690,13 @@ mod tests {
             storage.extend(events);
             Ok(())
         }
+
+        async fn list(
+            &self,
+            _query: &AuthEventQuery,
+        ) -> anyhow::Result<Vec<AuthEventRecord>> {
+            Ok(Vec::new())
+        }
     }
 
     impl InMemoryEventRepo {
@@ -738,6 +933,211 @@ mod tests {
         )
     }
 
+    /// Service with two users, and a fingerprint for a device of the given
+    /// model that mixes in the device id, as login does.
+    fn build_shared_service(
+        policy: DeviceIdCollisionPolicy,
+    ) -> (DeviceTrustService, Arc<InMemoryEventRepo>, Uuid, Uuid) {
+        let user_repo = Arc::new(InMemoryUserRepo::default());
+        let event_repo = Arc::new(InMemoryEventRepo::default());
+        let first = sample_user(3);
+        let second = sample_user(3);
+        for user in [&first, &second] {
+            user_repo.save(user).now_or_never().unwrap().unwrap();
+        }
+
+        let service = DeviceTrustService::new(
+            user_repo,
+            Arc::new(InMemoryDeviceRepo::default()),
+            event_repo.clone(),
+            Arc::new(RecordingSessionRepo::default()),
+            Arc::new(RecordingRefreshRepo::default()),
+        )
+        .with_device_id_collision_policy(policy);
+        (service, event_repo, first.user_id(), second.user_id())
+    }
+
+    fn model(cpu: &'static str) -> impl Fn(Uuid) -> DeviceFingerprint {
+        move |device_id| {
+            DeviceFingerprint::new(
+                "Linux".to_string(),
+                Some(cpu.to_string()),
+                None,
+                Some(device_id.to_string()),
+                None,
+            )
+            .unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn colliding_device_id_is_reissued_by_default() {
+        let (service, event_repo, first, second) =
+            build_shared_service(DeviceIdCollisionPolicy::default());
+        let device_id = Uuid::now_v7();
+
+        let (_, kept) = service
+            .register_client_device(
+                first,
+                device_id,
+                model("CPU"),
+                "One".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(kept, device_id);
+        assert!(!service.is_device_id_contested(device_id).await.unwrap());
+
+        let (session, reissued) = service
+            .register_client_device(
+                second,
+                device_id,
+                model("CPU2"),
+                "Two".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_ne!(reissued, device_id);
+        assert_eq!(
+            session.device_fingerprint().as_str(),
+            model("CPU2")(reissued).as_str()
+        );
+        assert!(service.is_device_id_contested(device_id).await.unwrap());
+        assert!(!service.is_device_id_contested(reissued).await.unwrap());
+
+        let collision = event_repo
+            .events()
+            .into_iter()
+            .find(|event| {
+                event.event_type == AuthAuditEventKind::DeviceIdCollision
+            })
+            .expect("collision event");
+        assert_eq!(collision.user_id, Some(second));
+        assert!(collision.success);
+        assert_eq!(collision.metadata["reissued_device_id"], json!(reissued));
+    }
+
+    #[tokio::test]
+    async fn strict_mode_rejects_colliding_device_id() {
+        let (service, event_repo, first, second) =
+            build_shared_service(DeviceIdCollisionPolicy::Reject);
+        let device_id = Uuid::now_v7();
+
+        service
+            .register_client_device(
+                first,
+                device_id,
+                model("CPU"),
+                "One".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let result = service
+            .register_client_device(
+                second,
+                device_id,
+                model("CPU2"),
+                "Two".to_string(),
+                None,
+            )
+            .await;
+        assert!(matches!(result, Err(DeviceTrustError::DeviceIdCollision)));
+        assert!(service.is_device_id_contested(device_id).await.unwrap());
+        assert!(service.list_devices(second).await.unwrap().is_empty());
+
+        let collision = event_repo
+            .events()
+            .into_iter()
+            .find(|event| {
+                event.event_type == AuthAuditEventKind::DeviceIdCollision
+            })
+            .expect("collision event");
+        assert!(!collision.success);
+    }
+
+    #[tokio::test]
+    async fn sessions_are_only_on_the_device_they_logged_in_as() {
+        let (service, _, first, second) =
+            build_shared_service(DeviceIdCollisionPolicy::Reject);
+        let device_id = Uuid::now_v7();
+
+        let (session, _) = service
+            .register_client_device(
+                first,
+                device_id,
+                model("CPU"),
+                "One".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        let (other, _) = service
+            .register_client_device(
+                second,
+                Uuid::now_v7(),
+                model("CPU2"),
+                "Two".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert!(
+            service
+                .is_session_on_device(session.id(), device_id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !service
+                .is_session_on_device(other.id(), device_id)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn users_sharing_a_device_keep_its_id() {
+        let (service, event_repo, first, second) =
+            build_shared_service(DeviceIdCollisionPolicy::Reject);
+        let device_id = Uuid::now_v7();
+
+        for user_id in [first, second] {
+            let (_, id) = service
+                .register_client_device(
+                    user_id,
+                    device_id,
+                    model("CPU"),
+                    "Living Room".to_string(),
+                    None,
+                )
+                .await
+                .unwrap();
+            assert_eq!(id, device_id);
+        }
+        // The same user on an updated client is not a collision either.
+        let (_, id) = service
+            .register_client_device(
+                first,
+                device_id,
+                model("CPU2"),
+                "Living Room".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(id, device_id);
+
+        assert!(!service.is_device_id_contested(device_id).await.unwrap());
+        assert!(!event_repo.events().iter().any(|event| {
+            event.event_type == AuthAuditEventKind::DeviceIdCollision
+        }));
+    }
+
     #[tokio::test]
     async fn register_device_creates_pending_session_and_logs_event() {
         let user = sample_user(3);
@@ -176,6 +176,44 @@ fn map_domain_event(
                 context,
             ))
         }
+        AuthEvent::RefreshTokenReused {
+            family_id,
+            device_session_id,
+            generation,
+            ..
+        } => Some(build_log(
+            AuthAuditEventKind::RefreshTokenReuse,
+            user_id,
+            device_session_id,
+            false,
+            Some("refresh_token_reuse".to_string()),
+            Some(json!({
+                "severity": "high",
+                "family_id": family_id,
+                "generation": generation,
+            })),
+            occurred_at,
+            context,
+        )),
+        AuthEvent::DeviceIdCollision {
+            device_id,
+            reissued_device_id,
+            ..
+        } => Some(build_log(
+            AuthAuditEventKind::DeviceIdCollision,
+            user_id,
+            None,
+            reissued_device_id.is_some(),
+            reissued_device_id
+                .is_none()
+                .then(|| "device_id_collision".to_string()),
+            Some(json!({
+                "device_id": device_id,
+                "reissued_device_id": reissued_device_id,
+            })),
+            occurred_at,
+            context,
+        )),
         AuthEvent::PasswordAuthenticated { .. } => Some(build_log(
             AuthAuditEventKind::PasswordLoginSuccess,
             user_id,
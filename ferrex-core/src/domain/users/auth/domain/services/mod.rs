@@ -11,9 +11,11 @@ pub(crate) use event_context::map_domain_events;
 
 pub use authentication_service::{
     AuthenticationError, AuthenticationService, PasswordChangeActor,
-    PasswordChangeRequest, TokenBundle, ValidatedSession,
+    PasswordChangeRequest, TokenBundle, TokenLifetimes, ValidatedSession,
+};
+pub use device_trust_service::{
+    DeviceIdCollisionPolicy, DeviceTrustError, DeviceTrustService,
 };
-pub use device_trust_service::{DeviceTrustError, DeviceTrustService};
 pub use event_context::AuthEventContext;
 pub use pin_management_service::{PinManagementError, PinManagementService};
 
@@ -321,7 +321,8 @@ mod tests {
         DeviceSession, DeviceStatus, UserAuthentication,
     };
     use crate::domain::users::auth::domain::repositories::{
-        AuthAuditEventKind, AuthEventLog, DevicePinStatus,
+        AuthAuditEventKind, AuthEventLog, AuthEventQuery, AuthEventRecord,
+        DevicePinStatus,
     };
     use crate::domain::users::auth::domain::value_objects::{
         DeviceFingerprint, PinPolicy,
@@ -446,6 +447,35 @@ mod tests {
                 })
                 .collect())
         }
+
+        async fn find_by_client_device_id(
+            &self,
+            _device_id: Uuid,
+        ) -> anyhow::Result<Vec<DeviceSession>> {
+            Ok(Vec::new())
+        }
+
+        async fn set_client_device_id(
+            &self,
+            _session_id: Uuid,
+            _device_id: Uuid,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn mark_device_id_contested(
+            &self,
+            _device_id: Uuid,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn is_device_id_contested(
+            &self,
+            _device_id: Uuid,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
     }
 
     #[derive(Debug, Default)]
@@ -463,6 +493,13 @@ mod tests {
             storage.extend(events);
             Ok(())
         }
+
+        async fn list(
+            &self,
+            _query: &AuthEventQuery,
+        ) -> anyhow::Result<Vec<AuthEventRecord>> {
+            Ok(Vec::new())
+        }
     }
 
     impl InMemoryEventRepo {
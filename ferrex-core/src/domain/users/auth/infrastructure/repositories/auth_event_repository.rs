@@ -1,14 +1,18 @@
 use std::fmt;
 use std::net::IpAddr;
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use ipnetwork::IpNetwork;
-use sqlx::PgPool;
+use serde_json::Value;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
 
 use crate::domain::users::auth::AuthEventType;
 use crate::domain::users::auth::domain::repositories::{
-    AuthAuditEventKind, AuthEventLog, AuthEventRepository,
+    AuthAuditEventKind, AuthEventLog, AuthEventQuery, AuthEventRecord,
+    AuthEventRepository,
 };
 
 pub struct PostgresAuthEventRepository {
@@ -27,26 +31,6 @@ impl PostgresAuthEventRepository {
     }
 }
 
-fn map_event_kind(kind: AuthAuditEventKind) -> AuthEventType {
-    match kind {
-        AuthAuditEventKind::PasswordLoginSuccess => {
-            AuthEventType::PasswordLoginSuccess
-        }
-        AuthAuditEventKind::PasswordLoginFailure => {
-            AuthEventType::PasswordLoginFailure
-        }
-        AuthAuditEventKind::PinLoginSuccess => AuthEventType::PinLoginSuccess,
-        AuthAuditEventKind::PinLoginFailure => AuthEventType::PinLoginFailure,
-        AuthAuditEventKind::DeviceRegistered => AuthEventType::DeviceRegistered,
-        AuthAuditEventKind::DeviceRevoked => AuthEventType::DeviceRevoked,
-        AuthAuditEventKind::PinSet => AuthEventType::PinSet,
-        AuthAuditEventKind::PinRemoved => AuthEventType::PinRemoved,
-        AuthAuditEventKind::SessionCreated => AuthEventType::SessionCreated,
-        AuthAuditEventKind::SessionRevoked => AuthEventType::SessionRevoked,
-        AuthAuditEventKind::AutoLogin => AuthEventType::AutoLogin,
-    }
-}
-
 fn parse_ip(value: &Option<String>) -> Option<IpNetwork> {
     value
         .as_deref()
@@ -54,6 +38,47 @@ fn parse_ip(value: &Option<String>) -> Option<IpNetwork> {
         .map(IpNetwork::from)
 }
 
+#[derive(Debug, FromRow)]
+struct AuthEventRow {
+    id: Uuid,
+    event_type: String,
+    user_id: Option<Uuid>,
+    device_session_id: Option<Uuid>,
+    device_name: Option<String>,
+    session_id: Option<Uuid>,
+    success: bool,
+    failure_reason: Option<String>,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+    metadata: Value,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<AuthEventRow> for AuthEventRecord {
+    type Error = anyhow::Error;
+
+    fn try_from(row: AuthEventRow) -> Result<Self> {
+        let event_type = AuthAuditEventKind::parse(&row.event_type)
+            .ok_or_else(|| {
+                anyhow!("unknown auth event type {}", row.event_type)
+            })?;
+        Ok(Self {
+            id: row.id,
+            event_type,
+            user_id: row.user_id,
+            device_session_id: row.device_session_id,
+            device_name: row.device_name,
+            session_id: row.session_id,
+            success: row.success,
+            failure_reason: row.failure_reason,
+            ip_address: row.ip_address,
+            user_agent: row.user_agent,
+            metadata: row.metadata,
+            occurred_at: row.created_at,
+        })
+    }
+}
+
 #[async_trait]
 impl AuthEventRepository for PostgresAuthEventRepository {
     async fn record(&self, events: Vec<AuthEventLog>) -> Result<()> {
@@ -62,7 +87,7 @@ impl AuthEventRepository for PostgresAuthEventRepository {
         }
 
         for event in events {
-            let event_type = map_event_kind(event.event_type);
+            let event_type = AuthEventType::from(event.event_type);
             let ip_address = parse_ip(&event.ip_address);
 
             sqlx::query!(
@@ -100,4 +125,55 @@ impl AuthEventRepository for PostgresAuthEventRepository {
 
         Ok(())
     }
+
+    async fn list(
+        &self,
+        query: &AuthEventQuery,
+    ) -> Result<Vec<AuthEventRecord>> {
+        let event_types: Vec<&str> = query
+            .event_types
+            .iter()
+            .map(AuthAuditEventKind::as_str)
+            .collect();
+        let (after_at, after_id) = query.after.unzip();
+
+        let rows = sqlx::query_as::<_, AuthEventRow>(
+            r#"
+            SELECT e.id,
+                   e.event_type::text AS event_type,
+                   e.user_id,
+                   e.device_session_id,
+                   d.device_name,
+                   e.session_id,
+                   e.success,
+                   e.failure_reason,
+                   host(e.ip_address) AS ip_address,
+                   e.user_agent,
+                   e.metadata,
+                   e.created_at
+            FROM auth_events e
+            LEFT JOIN auth_device_sessions d ON d.id = e.device_session_id
+            WHERE ($1::uuid IS NULL OR e.user_id = $1)
+              AND (cardinality($2::text[]) = 0
+                   OR e.event_type = ANY($2::text[]::auth_event_type[]))
+              AND ($3::timestamptz IS NULL OR e.created_at >= $3)
+              AND ($4::timestamptz IS NULL OR e.created_at < $4)
+              AND ($5::timestamptz IS NULL
+                   OR (e.created_at, e.id) < ($5, $6::uuid))
+            ORDER BY e.created_at DESC, e.id DESC
+            LIMIT $7
+            "#,
+        )
+        .bind(query.user_id)
+        .bind(&event_types)
+        .bind(query.since)
+        .bind(query.until)
+        .bind(after_at)
+        .bind(after_id)
+        .bind(query.limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(AuthEventRecord::try_from).collect()
+    }
 }
@@ -399,6 +399,105 @@ impl DeviceSessionRepository for PostgresDeviceSessionRepository {
 
         Ok(statuses)
     }
+
+    async fn find_by_client_device_id(
+        &self,
+        device_id: Uuid,
+    ) -> Result<Vec<DeviceSession>> {
+        let rows = sqlx::query_as::<_, DeviceSessionRecord>(
+            r#"
+            SELECT
+                ds.id,
+                ds.user_id,
+                ds.device_fingerprint,
+                ds.device_name,
+                ds.device_public_key,
+                ds.device_key_alg::text AS device_key_alg,
+                ds.status::text AS status,
+                (uc.pin_hash IS NOT NULL) AS pin_configured,
+                ds.failed_attempts,
+                ds.created_at,
+                ds.last_activity,
+                sess.session_token_hash,
+                sess.created_at AS session_created_at,
+                sess.expires_at AS session_expires_at
+            FROM auth_device_sessions ds
+            INNER JOIN user_credentials uc ON uc.user_id = ds.user_id
+            LEFT JOIN LATERAL (
+                SELECT
+                    s.session_token_hash,
+                    s.created_at,
+                    s.expires_at
+                FROM auth_sessions s
+                WHERE s.device_session_id = ds.id
+                  AND s.revoked = FALSE
+                  AND s.expires_at > NOW()
+                ORDER BY s.created_at DESC
+                LIMIT 1
+            ) sess ON TRUE
+            WHERE ds.client_device_id = $1
+            ORDER BY ds.created_at DESC
+            "#,
+        )
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| self.hydrate_session(row))
+            .collect()
+    }
+
+    async fn set_client_device_id(
+        &self,
+        session_id: Uuid,
+        device_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE auth_device_sessions
+            SET client_device_id = $2
+            WHERE id = $1
+              AND client_device_id IS DISTINCT FROM $2
+            "#,
+        )
+        .bind(session_id)
+        .bind(device_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn mark_device_id_contested(&self, device_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO auth_contested_device_ids (device_id)
+            VALUES ($1)
+            ON CONFLICT (device_id) DO UPDATE
+            SET last_seen_at = NOW()
+            "#,
+        )
+        .bind(device_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn is_device_id_contested(&self, device_id: Uuid) -> Result<bool> {
+        let contested = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM auth_contested_device_ids
+                WHERE device_id = $1
+            )
+            "#,
+        )
+        .bind(device_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(contested)
+    }
 }
 
 impl PostgresDeviceSessionRepository {
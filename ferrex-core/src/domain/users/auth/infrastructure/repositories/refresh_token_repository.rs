@@ -95,6 +95,7 @@ impl RefreshTokenRepository for PostgresRefreshTokenRepository {
                 family_id,
                 generation AS "generation?",
                 used_count AS "used_count?",
+                used_at,
                 origin_scope
             FROM auth_refresh_tokens
             WHERE token_hash = $1
@@ -143,6 +144,7 @@ impl RefreshTokenRepository for PostgresRefreshTokenRepository {
                     revoked: row.revoked,
                     revoked_reason: row.revoked_reason,
                     used_count: row.used_count.unwrap_or_default(),
+                    used_at: row.used_at,
                     origin_scope,
                 })
             })
@@ -177,7 +179,9 @@ impl RefreshTokenRepository for PostgresRefreshTokenRepository {
         &self,
         family_id: Uuid,
         reason: RevocationReason,
-    ) -> Result<()> {
+    ) -> Result<Vec<Uuid>> {
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query!(
             r#"
             UPDATE auth_refresh_tokens
@@ -189,10 +193,74 @@ impl RefreshTokenRepository for PostgresRefreshTokenRepository {
             family_id,
             reason.as_str()
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(())
+        let session_ids = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT session_id AS "session_id!"
+            FROM auth_refresh_tokens
+            WHERE family_id = $1 AND session_id IS NOT NULL
+            "#,
+            family_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(session_ids)
+    }
+
+    async fn revoke_unused_successors(
+        &self,
+        family_id: Uuid,
+        generation: i32,
+        reason: RevocationReason,
+    ) -> Result<Option<Vec<Uuid>>> {
+        let mut tx = self.pool.begin().await?;
+
+        // Lock the successors so a rotation running concurrently cannot
+        // consume one between this check and the revocation below.
+        let consumed = sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(used_count, 0) > 0 AS "consumed!"
+            FROM auth_refresh_tokens
+            WHERE family_id = $1
+              AND generation > $2
+            FOR UPDATE
+            "#,
+            family_id,
+            generation
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if consumed.into_iter().any(|consumed| consumed) {
+            return Ok(None);
+        }
+
+        let session_ids = sqlx::query_scalar!(
+            r#"
+            UPDATE auth_refresh_tokens
+            SET revoked = TRUE,
+                revoked_at = NOW(),
+                revoked_reason = COALESCE(revoked_reason, $3)
+            WHERE family_id = $1
+              AND generation > $2
+              AND revoked = FALSE
+            RETURNING session_id
+            "#,
+            family_id,
+            generation,
+            reason.as_str()
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(session_ids.into_iter().flatten().collect()))
     }
 
     async fn revoke_for_user(
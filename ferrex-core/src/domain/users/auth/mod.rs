@@ -106,6 +106,10 @@ pub enum AuthEventType {
     SessionRevoked,
     #[serde(rename = "auto_login")]
     AutoLogin,
+    #[serde(rename = "refresh_token_reuse")]
+    RefreshTokenReuse,
+    #[serde(rename = "device_id_collision")]
+    DeviceIdCollision,
 }
 
 impl AuthEventType {
@@ -123,6 +127,8 @@ impl AuthEventType {
             Self::SessionCreated => "session_created",
             Self::SessionRevoked => "session_revoked",
             Self::AutoLogin => "auto_login",
+            Self::RefreshTokenReuse => "refresh_token_reuse",
+            Self::DeviceIdCollision => "device_id_collision",
         }
     }
 
@@ -140,6 +146,8 @@ impl AuthEventType {
             "session_created" => Some(Self::SessionCreated),
             "session_revoked" => Some(Self::SessionRevoked),
             "auto_login" => Some(Self::AutoLogin),
+            "refresh_token_reuse" => Some(Self::RefreshTokenReuse),
+            "device_id_collision" => Some(Self::DeviceIdCollision),
             _ => None,
         }
     }
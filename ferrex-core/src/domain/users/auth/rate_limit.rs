@@ -66,8 +66,13 @@ pub trait RateLimiter: Send + Sync {
         rule: &RateLimitRule,
     ) -> RateLimitResult<RateLimitDecision>;
 
-    /// Reset rate limit for a specific key
-    async fn reset(&self, key: &RateLimitKey) -> RateLimitResult<()>;
+    /// Clear every bucket held for `key`, across all rules. Returns the
+    /// number of buckets cleared.
+    async fn reset(&self, key: &RateLimitKey) -> RateLimitResult<u64>;
+
+    /// Clear every bucket for every key. Returns the number of buckets
+    /// cleared.
+    async fn reset_all(&self) -> RateLimitResult<u64>;
 
     /// Get current state without updating counters
     async fn get_current_state(
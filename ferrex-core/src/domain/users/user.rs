@@ -308,10 +308,94 @@ pub struct AuthToken {
     pub scope: SessionScope,
 }
 
+/// Snapshot of the session behind the presented bearer token.
+///
+/// Returned by the session introspection endpoint so clients can decide
+/// between a silent refresh and a full re-login without performing a
+/// privileged request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionIntrospection {
+    /// Identifier of the persisted session record
+    pub session_id: Uuid,
+    /// User the session belongs to
+    pub user_id: Uuid,
+    /// Trusted device session the token is bound to, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_session_id: Option<Uuid>,
+    /// Scope granted to the session
+    pub scope: SessionScope,
+    /// When the session token was issued
+    pub issued_at: DateTime<Utc>,
+    /// When the session token stops being accepted
+    pub expires_at: DateTime<Utc>,
+    /// Seconds until `expires_at`, clamped at zero
+    pub expires_in: u32,
+    /// True once less than a quarter of the token lifetime remains
+    pub refresh_recommended: bool,
+}
+
+impl SessionIntrospection {
+    /// Derive the remaining lifetime fields relative to `now`.
+    pub fn new(
+        session_id: Uuid,
+        user_id: Uuid,
+        device_session_id: Option<Uuid>,
+        scope: SessionScope,
+        issued_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let remaining = expires_at.signed_duration_since(now);
+        let lifetime = expires_at.signed_duration_since(issued_at);
+        let refresh_recommended = remaining * 4 <= lifetime;
+
+        Self {
+            session_id,
+            user_id,
+            device_session_id,
+            scope,
+            issued_at,
+            expires_at,
+            expires_in: remaining.num_seconds().clamp(0, u32::MAX as i64)
+                as u32,
+            refresh_recommended,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn session_introspection_recommends_refresh_near_expiry() {
+        let issued_at = Utc::now();
+        let expires_at = issued_at + chrono::Duration::hours(24);
+
+        let fresh = SessionIntrospection::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+            SessionScope::Full,
+            issued_at,
+            expires_at,
+            issued_at + chrono::Duration::hours(1),
+        );
+        assert!(!fresh.refresh_recommended);
+        assert_eq!(fresh.expires_in, 23 * 3600);
+
+        let stale = SessionIntrospection::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+            SessionScope::Full,
+            issued_at,
+            expires_at,
+            issued_at + chrono::Duration::hours(20),
+        );
+        assert!(stale.refresh_recommended);
+    }
+
     #[test]
     fn auth_token_scope_defaults_to_full() {
         let raw = r#"{
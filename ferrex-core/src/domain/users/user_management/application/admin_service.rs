@@ -597,7 +597,8 @@ impl From<AuthenticationError> for UserAdminError {
             | AuthenticationError::DeviceNotFound
             | AuthenticationError::DeviceNotTrusted
             | AuthenticationError::TooManyFailedAttempts
-            | AuthenticationError::SessionExpired => {
+            | AuthenticationError::SessionExpired
+            | AuthenticationError::RefreshTokenReused => {
                 UserAdminError::PermissionDenied(err.to_string())
             }
             AuthenticationError::UserNotFound => UserAdminError::UserNotFound,
@@ -6,10 +6,10 @@
 
 // Re-export identity types from model for convenience
 pub use crate::types::watch::{
-    EpisodeKey, EpisodeStatus, NextEpisode, NextReason, SeasonKey,
-    SeasonWatchStatus, SeriesWatchStatus,
+    BulkWatchUpdate, EpisodeKey, EpisodeStatus, NextEpisode, NextReason,
+    SeasonKey, SeasonWatchStatus, SeriesWatchStatus,
 };
-use ferrex_model::{MediaID, VideoMediaType};
+use ferrex_model::{MediaID, SeasonID, SeriesID, VideoMediaType};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     collections::{HashMap, HashSet},
@@ -221,6 +221,46 @@ pub enum WatchStatusFilter {
     },
 }
 
+/// Set of episodes targeted by a bulk watched/unwatched change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatchScope {
+    Series(SeriesID),
+    Season(SeasonID),
+}
+
+/// How marking an episode watched treats the episodes before it
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchAdvancePolicy {
+    /// Only the reported episode changes
+    #[default]
+    Strict,
+    /// Earlier episodes of the season that are not completed yet are
+    /// marked watched as well
+    Cascade,
+}
+
+/// Mark a single episode watched
+///
+/// # Example
+///
+/// ```json
+/// { "policy": "cascade", "series_wide": false }
+/// ```
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct MarkEpisodeWatchedRequest {
+    #[serde(default)]
+    pub policy: WatchAdvancePolicy,
+    /// Let a cascade reach into earlier seasons. Specials (season 0) are
+    /// never cascaded into. Ignored for `Strict`.
+    #[serde(default)]
+    pub series_wide: bool,
+}
+
 /// Progress update request
 ///
 /// Sent by clients to update viewing progress. Progress updates
@@ -247,6 +287,256 @@ pub struct UpdateProgressRequest {
     /// Optional hint of the specific media UUID used for playback (useful for identity rows)
     #[serde(default)]
     pub last_media_uuid: Option<Uuid>,
+    /// Client clock (unix milliseconds) when the position was observed.
+    /// Orders reports from different devices; omitted means "now".
+    #[serde(default)]
+    pub reported_at: Option<i64>,
+    /// Explicit reset/rewatch: lets the position move backwards.
+    #[serde(default)]
+    pub reset: bool,
+}
+
+impl UpdateProgressRequest {
+    /// Timestamp used to order this report. Missing timestamps and clocks
+    /// running ahead of the server are clamped to `now_ms`, so a skewed
+    /// device cannot lock out every other device.
+    pub fn effective_reported_at(&self, now_ms: i64) -> i64 {
+        self.reported_at
+            .map_or(now_ms, |reported| reported.min(now_ms))
+    }
+
+    /// Decide whether this report may replace `latest`.
+    ///
+    /// Reports older than the latest accepted one are always rejected.
+    /// Newer reports may not move an in-progress position backwards unless
+    /// `reset` is set; a completed item accepts any newer report, which is
+    /// how a rewatch starts.
+    pub fn check_against(
+        &self,
+        latest: &LatestProgress,
+        now_ms: i64,
+    ) -> Result<(), StaleProgress> {
+        let reported_at = self.effective_reported_at(now_ms);
+        if reported_at < latest.reported_at() {
+            return Err(StaleProgress::OutOfOrder {
+                reported_at,
+                latest_reported_at: latest.reported_at(),
+            });
+        }
+
+        if let LatestProgress::InProgress {
+            position, duration, ..
+        } = *latest
+            && !self.reset
+            && self.position / self.duration < position / duration
+        {
+            return Err(StaleProgress::Rewind {
+                position: self.position,
+                latest_position: position,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Most recent accepted progress for a media item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LatestProgress {
+    InProgress {
+        position: f32,
+        duration: f32,
+        reported_at: i64,
+    },
+    Completed {
+        reported_at: i64,
+    },
+}
+
+impl LatestProgress {
+    pub fn reported_at(&self) -> i64 {
+        match *self {
+            LatestProgress::InProgress { reported_at, .. }
+            | LatestProgress::Completed { reported_at } => reported_at,
+        }
+    }
+}
+
+/// Why a progress report was not applied.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum StaleProgress {
+    #[error(
+        "stale progress update: reported at {reported_at} but the latest update was reported at {latest_reported_at}"
+    )]
+    OutOfOrder {
+        reported_at: i64,
+        latest_reported_at: i64,
+    },
+    #[error(
+        "progress update would rewind from {latest_position:.1}s to {position:.1}s; set reset to rewatch"
+    )]
+    Rewind { position: f32, latest_position: f32 },
+}
+
+/// Format version written into [`WatchStateExport`].
+pub const WATCH_STATE_EXPORT_VERSION: u32 = 1;
+
+/// Stable identity of a watched item in an export.
+///
+/// Internal ids change when a library is rescanned or moved to another
+/// server, so exports refer to items by TMDB identity. Movies that were
+/// never matched fall back to their file path.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PortableMediaKey {
+    Movie {
+        tmdb_id: u64,
+    },
+    Episode {
+        tmdb_series_id: u64,
+        season_number: u16,
+        episode_number: u16,
+    },
+    /// An unmatched movie, by file path.
+    File {
+        path: String,
+    },
+}
+
+impl From<EpisodeKey> for PortableMediaKey {
+    fn from(key: EpisodeKey) -> Self {
+        PortableMediaKey::Episode {
+            tmdb_series_id: key.tmdb_series_id,
+            season_number: key.season_number,
+            episode_number: key.episode_number,
+        }
+    }
+}
+
+/// Progress or completion of one item in an export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchStateEntry {
+    pub item: PortableMediaKey,
+    /// Playback position in seconds
+    pub position: f32,
+    /// Total duration in seconds
+    pub duration: f32,
+    pub completed: bool,
+    /// Unix milliseconds of the last watch
+    pub last_watched: i64,
+}
+
+impl WatchStateEntry {
+    /// Whether the entry records completion, either explicitly or by
+    /// passing the 95% threshold.
+    pub fn is_completed(&self) -> bool {
+        self.completed || self.position / self.duration > 0.95
+    }
+
+    /// Entries that could not have been written by a player.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.position < 0.0 || self.duration <= 0.0 {
+            return Err("invalid position or duration");
+        }
+        if self.position > self.duration {
+            return Err("position exceeds duration");
+        }
+        Ok(())
+    }
+
+    /// Max-position rule: whether this entry moves an item further than
+    /// the state already stored. Completion is the furthest an item can
+    /// get, so a completed item is never changed.
+    pub fn supersedes(&self, completed: bool, position: Option<f32>) -> bool {
+        if completed {
+            return false;
+        }
+        self.is_completed() || self.position > position.unwrap_or(0.0)
+    }
+
+    /// Fold another entry for the same item into this one, keeping the
+    /// furthest progress and the latest watch time.
+    pub fn merge(&mut self, other: &WatchStateEntry) {
+        if other.supersedes(self.is_completed(), Some(self.position)) {
+            self.position = other.position;
+            self.duration = other.duration;
+            self.completed = other.completed;
+        }
+        self.last_watched = self.last_watched.max(other.last_watched);
+    }
+}
+
+/// A user's watch state in portable form.
+///
+/// Returned by the export endpoint and accepted as is by the import
+/// endpoint, on the same server or another one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchStateExport {
+    pub version: u32,
+    /// Unix milliseconds when the export was taken
+    pub exported_at: i64,
+    pub entries: Vec<WatchStateEntry>,
+}
+
+impl WatchStateExport {
+    /// Wrap `entries`, merging duplicates and ordering them by item.
+    pub fn new(entries: Vec<WatchStateEntry>, exported_at: i64) -> Self {
+        let mut merged: HashMap<PortableMediaKey, WatchStateEntry> =
+            HashMap::with_capacity(entries.len());
+        for entry in entries {
+            match merged.get_mut(&entry.item) {
+                Some(existing) => existing.merge(&entry),
+                None => {
+                    merged.insert(entry.item.clone(), entry);
+                }
+            }
+        }
+        let mut entries: Vec<WatchStateEntry> = merged.into_values().collect();
+        entries.sort_by(|a, b| a.item.cmp(&b.item));
+        Self {
+            version: WATCH_STATE_EXPORT_VERSION,
+            exported_at,
+            entries,
+        }
+    }
+}
+
+/// An export entry that was not imported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedWatchEntry {
+    pub item: PortableMediaKey,
+    pub reason: String,
+}
+
+/// Outcome of importing a [`WatchStateExport`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WatchStateImportReport {
+    /// Entries that moved at least one matching item further
+    pub imported: u32,
+    /// Entries whose items were already as far along
+    pub kept_existing: u32,
+    /// Entries without a matching item in this server's libraries, or
+    /// with invalid values
+    pub skipped: Vec<SkippedWatchEntry>,
+}
+
+impl WatchStateImportReport {
+    pub fn skip(&mut self, item: &PortableMediaKey, reason: impl Into<String>) {
+        self.skipped.push(SkippedWatchEntry {
+            item: item.clone(),
+            reason: reason.into(),
+        });
+    }
+
+    pub fn record(&mut self, changed: bool) {
+        if changed {
+            self.imported += 1;
+        } else {
+            self.kept_existing += 1;
+        }
+    }
 }
 
 /// Watch progress percentage
@@ -363,3 +653,126 @@ impl Default for UserWatchState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(position: f32, reported_at: i64) -> UpdateProgressRequest {
+        UpdateProgressRequest {
+            media_id: Uuid::now_v7(),
+            media_type: VideoMediaType::Movie,
+            position,
+            duration: 7200.0,
+            episode: None,
+            last_media_uuid: None,
+            reported_at: Some(reported_at),
+            reset: false,
+        }
+    }
+
+    fn in_progress(position: f32, reported_at: i64) -> LatestProgress {
+        LatestProgress::InProgress {
+            position,
+            duration: 7200.0,
+            reported_at,
+        }
+    }
+
+    #[test]
+    fn late_reports_from_another_device_are_rejected() {
+        let latest = in_progress(3600.0, 2_000);
+
+        assert_eq!(
+            report(1200.0, 1_000).check_against(&latest, 5_000),
+            Err(StaleProgress::OutOfOrder {
+                reported_at: 1_000,
+                latest_reported_at: 2_000,
+            })
+        );
+        assert!(report(3700.0, 3_000).check_against(&latest, 5_000).is_ok());
+    }
+
+    #[test]
+    fn newer_reports_cannot_rewind_without_reset() {
+        let latest = in_progress(3600.0, 2_000);
+        let mut rewatch = report(60.0, 3_000);
+
+        assert!(matches!(
+            rewatch.check_against(&latest, 5_000),
+            Err(StaleProgress::Rewind { .. })
+        ));
+
+        rewatch.reset = true;
+        assert!(rewatch.check_against(&latest, 5_000).is_ok());
+    }
+
+    #[test]
+    fn stale_resets_are_still_rejected() {
+        let mut reset = report(0.0, 1_000);
+        reset.reset = true;
+
+        assert!(matches!(
+            reset.check_against(&in_progress(3600.0, 2_000), 5_000),
+            Err(StaleProgress::OutOfOrder { .. })
+        ));
+    }
+
+    #[test]
+    fn completed_items_accept_newer_reports() {
+        let latest = LatestProgress::Completed { reported_at: 2_000 };
+
+        assert!(report(60.0, 3_000).check_against(&latest, 5_000).is_ok());
+        assert!(report(60.0, 1_500).check_against(&latest, 5_000).is_err());
+    }
+
+    fn entry(
+        position: f32,
+        completed: bool,
+        last_watched: i64,
+    ) -> WatchStateEntry {
+        WatchStateEntry {
+            item: PortableMediaKey::Movie { tmdb_id: 603 },
+            position,
+            duration: 7200.0,
+            completed,
+            last_watched,
+        }
+    }
+
+    #[test]
+    fn imports_follow_the_max_position_rule() {
+        let entry = entry(3600.0, false, 1_000);
+
+        assert!(entry.supersedes(false, None));
+        assert!(entry.supersedes(false, Some(1200.0)));
+        assert!(!entry.supersedes(false, Some(4000.0)));
+        assert!(!entry.supersedes(true, None));
+        // Past 95% counts as completed even without the flag.
+        assert!(self::entry(7000.0, false, 0).is_completed());
+    }
+
+    #[test]
+    fn exports_merge_copies_of_one_item() {
+        let export = WatchStateExport::new(
+            vec![
+                entry(3600.0, false, 3_000),
+                entry(7200.0, true, 1_000),
+                entry(1200.0, false, 2_000),
+            ],
+            5_000,
+        );
+
+        assert_eq!(export.version, WATCH_STATE_EXPORT_VERSION);
+        assert_eq!(export.entries, vec![entry(7200.0, true, 3_000)]);
+    }
+
+    #[test]
+    fn future_and_missing_timestamps_clamp_to_server_time() {
+        let mut request = report(60.0, i64::MAX);
+        assert_eq!(request.effective_reported_at(5_000), 5_000);
+
+        request.reported_at = None;
+        assert_eq!(request.effective_reported_at(5_000), 5_000);
+    }
+}
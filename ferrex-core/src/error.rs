@@ -38,6 +38,16 @@ pub enum MediaError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    #[error(
+        "Insufficient disk space on {}: {available} bytes free, {required} required",
+        path.display()
+    )]
+    InsufficientDiskSpace {
+        path: std::path::PathBuf,
+        available: u64,
+        required: u64,
+    },
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
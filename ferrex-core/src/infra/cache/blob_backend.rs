@@ -0,0 +1,252 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use super::ImageFileStore;
+use crate::error::{MediaError, Result};
+
+/// A finished blob in an [`ImageFileStore`](super::ImageFileStore).
+#[derive(Debug, Clone)]
+pub struct StoredFileBlob {
+    pub token: String,
+    pub byte_len: u64,
+    pub modified_at: SystemTime,
+}
+
+/// Where a client can fetch a blob from without going through the backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlobLocation {
+    /// A local file that can be streamed directly.
+    Path(PathBuf),
+    /// A URL clients can be redirected to.
+    Url(String),
+    /// Neither; the bytes have to be read through the backend and proxied.
+    Proxied,
+}
+
+/// Storage for immutable, token-addressed blobs.
+///
+/// Backends only move bytes around. Tokens are validated and blobs are
+/// sealed or opened by [`ImageFileStore`](super::ImageFileStore) before
+/// they get here, so every backend stores the same content under the same
+/// token.
+#[async_trait]
+pub trait BlobBackend: Send + Sync + fmt::Debug {
+    /// Store `bytes` under `token` unless a blob is already there. Returns
+    /// whether this call wrote it.
+    async fn put_if_missing(&self, token: &str, bytes: &[u8]) -> Result<bool>;
+
+    /// Stored bytes of a blob, `None` when it does not exist.
+    async fn read(&self, token: &str) -> Result<Option<Vec<u8>>>;
+
+    async fn exists(&self, token: &str) -> Result<bool>;
+
+    /// Remove a blob. Removing one that does not exist is not an error.
+    async fn delete(&self, token: &str) -> Result<()>;
+
+    /// Every finished blob. Writes still in progress are left out.
+    async fn list(&self) -> Result<Vec<StoredFileBlob>>;
+
+    /// How a blob can be served without reading it through the backend.
+    fn path_or_url_for_token(&self, token: &str) -> BlobLocation;
+}
+
+/// Blobs as files in one directory on the local filesystem; the default.
+#[derive(Debug, Clone)]
+pub struct LocalBlobBackend {
+    root: PathBuf,
+}
+
+impl LocalBlobBackend {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, token: &str) -> PathBuf {
+        self.root.join(token)
+    }
+
+    async fn ensure_root(&self) -> Result<()> {
+        tokio::fs::create_dir_all(&self.root).await.map_err(|err| {
+            MediaError::Internal(format!(
+                "failed to create image blob dir {:?}: {err}",
+                self.root
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl BlobBackend for LocalBlobBackend {
+    /// Best-effort atomic write (tmp + rename).
+    async fn put_if_missing(&self, token: &str, bytes: &[u8]) -> Result<bool> {
+        self.ensure_root().await?;
+        let path = self.path(token);
+
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(false);
+        }
+
+        let tmp = self
+            .root
+            .join(format!("{token}.tmp-{}", Uuid::new_v4().simple()));
+
+        let mut file = tokio::fs::File::create(&tmp).await.map_err(|err| {
+            MediaError::Internal(format!(
+                "failed to create temp image blob {:?}: {err}",
+                tmp
+            ))
+        })?;
+        file.write_all(bytes).await.map_err(|err| {
+            MediaError::Internal(format!(
+                "failed to write temp image blob {:?}: {err}",
+                tmp
+            ))
+        })?;
+        file.flush().await.map_err(|err| {
+            MediaError::Internal(format!(
+                "failed to flush temp image blob {:?}: {err}",
+                tmp
+            ))
+        })?;
+        drop(file);
+
+        // If another writer won the race, discard our temp.
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Ok(false);
+        }
+
+        tokio::fs::rename(&tmp, &path).await.map_err(|err| {
+            MediaError::Internal(format!(
+                "failed to move image blob {:?} -> {:?}: {err}",
+                tmp, path
+            ))
+        })?;
+
+        Ok(true)
+    }
+
+    async fn read(&self, token: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path(token);
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(MediaError::Internal(format!(
+                "failed to read image blob {:?}: {err}",
+                path
+            ))),
+        }
+    }
+
+    async fn exists(&self, token: &str) -> Result<bool> {
+        Ok(tokio::fs::try_exists(self.path(token))
+            .await
+            .unwrap_or(false))
+    }
+
+    async fn delete(&self, token: &str) -> Result<()> {
+        let path = self.path(token);
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(MediaError::Internal(format!(
+                "failed to remove image blob {:?}: {err}",
+                path
+            ))),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<StoredFileBlob>> {
+        let mut dir = match tokio::fs::read_dir(&self.root).await {
+            Ok(dir) => dir,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Vec::new());
+            }
+            Err(err) => {
+                return Err(MediaError::Internal(format!(
+                    "failed to read image blob dir {:?}: {err}",
+                    self.root
+                )));
+            }
+        };
+
+        let mut blobs = Vec::new();
+        while let Some(entry) = dir.next_entry().await.map_err(|err| {
+            MediaError::Internal(format!(
+                "failed to read image blob dir {:?}: {err}",
+                self.root
+            ))
+        })? {
+            let Some(token) = entry.file_name().to_str().map(str::to_string)
+            else {
+                continue;
+            };
+            if !ImageFileStore::is_valid_token(&token) {
+                continue;
+            }
+            let Ok(meta) = entry.metadata().await else {
+                continue;
+            };
+            if !meta.is_file() {
+                continue;
+            }
+            blobs.push(StoredFileBlob {
+                token,
+                byte_len: meta.len(),
+                modified_at: meta
+                    .modified()
+                    .unwrap_or_else(|_| SystemTime::now()),
+            });
+        }
+        Ok(blobs)
+    }
+
+    fn path_or_url_for_token(&self, token: &str) -> BlobLocation {
+        BlobLocation::Path(self.path(token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str =
+        "5f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0";
+
+    #[tokio::test]
+    async fn local_blobs_are_written_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LocalBlobBackend::new(dir.path().join("blobs"));
+
+        assert!(!backend.exists(TOKEN).await.unwrap());
+        assert!(backend.put_if_missing(TOKEN, b"first").await.unwrap());
+        assert!(!backend.put_if_missing(TOKEN, b"second").await.unwrap());
+        assert_eq!(
+            backend.read(TOKEN).await.unwrap().as_deref(),
+            Some(&b"first"[..])
+        );
+
+        let listed = backend.list().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].byte_len, 5);
+        assert_eq!(
+            backend.path_or_url_for_token(TOKEN),
+            BlobLocation::Path(dir.path().join("blobs").join(TOKEN))
+        );
+
+        backend.delete(TOKEN).await.unwrap();
+        backend.delete(TOKEN).await.unwrap();
+        assert_eq!(backend.read(TOKEN).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,194 @@
+//! Free-space guard for the cache volume.
+//!
+//! Writers that can grow the cache (scans, image downloads, thumbnail
+//! generation) call [`DiskSpaceGuard::check`] before starting work so a full
+//! volume surfaces as [`MediaError::InsufficientDiskSpace`] up front rather
+//! than as an IO error halfway through a write. Reads never consult the
+//! guard.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::error::{MediaError, Result};
+
+/// Emitted when free space first drops below the threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskSpaceWarning {
+    pub path: PathBuf,
+    pub available_bytes: u64,
+    pub required_bytes: u64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    root: PathBuf,
+    min_free_bytes: u64,
+    low: AtomicBool,
+    warnings: broadcast::Sender<DiskSpaceWarning>,
+}
+
+/// Refuses new cache writes while the cache volume has less than
+/// `min_free_bytes` available. Cheap to clone.
+#[derive(Debug, Clone)]
+pub struct DiskSpaceGuard {
+    inner: Arc<Inner>,
+}
+
+impl DiskSpaceGuard {
+    /// A `min_free_bytes` of zero disables the guard.
+    pub fn new(root: PathBuf, min_free_bytes: u64) -> Self {
+        let (warnings, _) = broadcast::channel(16);
+        Self {
+            inner: Arc::new(Inner {
+                root,
+                min_free_bytes,
+                low: AtomicBool::new(false),
+                warnings,
+            }),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.inner.root
+    }
+
+    pub fn min_free_bytes(&self) -> u64 {
+        self.inner.min_free_bytes
+    }
+
+    /// Receive a [`DiskSpaceWarning`] each time the volume crosses below the
+    /// threshold. Repeated failed checks while already low do not re-emit.
+    pub fn subscribe(&self) -> broadcast::Receiver<DiskSpaceWarning> {
+        self.inner.warnings.subscribe()
+    }
+
+    /// Fail with [`MediaError::InsufficientDiskSpace`] when the cache volume
+    /// is below the threshold. If free space cannot be determined the check
+    /// passes, so an unsupported platform never blocks the cache.
+    pub fn check(&self) -> Result<()> {
+        let required = self.inner.min_free_bytes;
+        if required == 0 {
+            return Ok(());
+        }
+
+        let available = match available_bytes(&self.inner.root) {
+            Ok(available) => available,
+            Err(err) => {
+                warn!(
+                    "Unable to determine free space on {}: {}",
+                    self.inner.root.display(),
+                    err
+                );
+                return Ok(());
+            }
+        };
+
+        if available >= required {
+            self.inner.low.store(false, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        if !self.inner.low.swap(true, Ordering::Relaxed) {
+            warn!(
+                "Cache volume {} is below the free-space threshold ({} of {} bytes available); refusing new cache writes",
+                self.inner.root.display(),
+                available,
+                required
+            );
+            let _ = self.inner.warnings.send(DiskSpaceWarning {
+                path: self.inner.root.clone(),
+                available_bytes: available,
+                required_bytes: required,
+            });
+        }
+
+        Err(MediaError::InsufficientDiskSpace {
+            path: self.inner.root.clone(),
+            available,
+            required,
+        })
+    }
+}
+
+/// Bytes available to unprivileged users on the volume holding `path`.
+///
+/// Walks up to the nearest existing ancestor so a cache directory that has
+/// not been created yet still resolves to its volume.
+pub fn available_bytes(path: &Path) -> io::Result<u64> {
+    let existing = path
+        .ancestors()
+        .find(|candidate| candidate.exists())
+        .unwrap_or(path);
+    statvfs_available(existing)
+}
+
+#[cfg(unix)]
+fn statvfs_available(path: &Path) -> io::Result<u64> {
+    use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stat` points to
+    // writable memory of the right size; it is only read after success.
+    let rc = unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: statvfs returned 0, so the struct was fully initialised.
+    let stat = unsafe { stat.assume_init() };
+    #[allow(clippy::unnecessary_cast)]
+    Ok((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+}
+
+#[cfg(not(unix))]
+fn statvfs_available(_path: &Path) -> io::Result<u64> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "free-space probing is only implemented on unix",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_threshold_disables_the_guard() {
+        let guard = DiskSpaceGuard::new(PathBuf::from("/nonexistent"), 0);
+        assert!(guard.check().is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unreachable_threshold_refuses_and_warns_once() {
+        let guard = DiskSpaceGuard::new(std::env::temp_dir(), u64::MAX);
+        let mut warnings = guard.subscribe();
+
+        assert!(matches!(
+            guard.check(),
+            Err(MediaError::InsufficientDiskSpace { .. })
+        ));
+        assert!(guard.check().is_err());
+
+        let warning = warnings.try_recv().expect("first failure warns");
+        assert_eq!(warning.required_bytes, u64::MAX);
+        assert!(warnings.try_recv().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn missing_directories_resolve_to_their_volume() {
+        let missing = std::env::temp_dir().join("ferrex-missing/cache/images");
+        assert!(available_bytes(&missing).is_ok());
+    }
+}
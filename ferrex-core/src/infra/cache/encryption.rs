@@ -0,0 +1,142 @@
+//! Optional at-rest encryption for cached artifacts.
+//!
+//! When a cache key is configured, image blobs and HLS segments are sealed
+//! with AES-256-GCM before they touch the disk and opened again on read.
+//! Each sealed file is `MAGIC || nonce || ciphertext+tag`, so the size of
+//! the plaintext is always the stored size minus [`CacheCipher::OVERHEAD`].
+//! Without a key nothing changes: files are written exactly as before.
+
+use std::fmt;
+
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::error::{MediaError, Result};
+
+const MAGIC: &[u8; 4] = b"FXC1";
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+/// Domain separation so the cache key never doubles as another key.
+const KEY_CONTEXT: &[u8] = b"ferrex cache encryption v1";
+
+/// Seals and opens cache blobs with a key derived from the configured
+/// cache key. Cheap to share behind an `Arc`.
+#[derive(Clone)]
+pub struct CacheCipher {
+    cipher: Aes256Gcm,
+}
+
+impl fmt::Debug for CacheCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheCipher").finish_non_exhaustive()
+    }
+}
+
+impl CacheCipher {
+    /// Bytes a sealed blob carries on top of its plaintext.
+    pub const OVERHEAD: usize = MAGIC.len() + NONCE_LEN + TAG_LEN;
+
+    /// Derive the AES key from `key_material`, which must not be empty.
+    pub fn new(key_material: impl AsRef<[u8]>) -> Result<Self> {
+        let material = key_material.as_ref();
+        if material.is_empty() {
+            return Err(MediaError::Internal(
+                "cache encryption key must not be empty".into(),
+            ));
+        }
+        let mut mac =
+            <Hmac<Sha256> as Mac>::new_from_slice(material).map_err(|e| {
+                MediaError::Internal(format!("invalid cache key: {e}"))
+            })?;
+        mac.update(KEY_CONTEXT);
+        let derived = mac.finalize().into_bytes();
+        let key = Key::<Aes256Gcm>::from_slice(&derived);
+        Ok(Self {
+            cipher: Aes256Gcm::new(key),
+        })
+    }
+
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext =
+            self.cipher.encrypt(&nonce, plaintext).map_err(|_| {
+                MediaError::Internal("cache blob encryption failed".into())
+            })?;
+        let mut sealed = Vec::with_capacity(Self::OVERHEAD + plaintext.len());
+        sealed.extend_from_slice(MAGIC);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Fails with [`MediaError::InvalidMedia`] for blobs that were not
+    /// sealed with this key or were modified on disk, so callers treat
+    /// them like any other corrupt cache entry.
+    pub fn open(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        if sealed.len() < Self::OVERHEAD || !sealed.starts_with(MAGIC) {
+            return Err(MediaError::InvalidMedia(
+                "cache blob is not encrypted".into(),
+            ));
+        }
+        let (nonce, ciphertext) = sealed[MAGIC.len()..].split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                MediaError::InvalidMedia(
+                    "cache blob failed to decrypt; wrong key or corrupt entry"
+                        .into(),
+                )
+            })
+    }
+
+    /// Plaintext length of a sealed blob of `stored_len` bytes.
+    pub fn plaintext_len(stored_len: u64) -> u64 {
+        stored_len.saturating_sub(Self::OVERHEAD as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_blobs_round_trip_and_hide_the_plaintext() {
+        let cipher =
+            CacheCipher::new("a cache key of reasonable length").unwrap();
+        let plaintext = b"\xff\xd8\xff\xe0 jpeg bytes";
+        let sealed = cipher.seal(plaintext).unwrap();
+
+        assert_eq!(sealed.len(), plaintext.len() + CacheCipher::OVERHEAD);
+        assert!(!sealed.windows(4).any(|w| w == b"jpeg"));
+        assert_eq!(
+            CacheCipher::plaintext_len(sealed.len() as u64),
+            plaintext.len() as u64
+        );
+        assert_eq!(cipher.open(&sealed).unwrap(), plaintext);
+        // A fresh nonce every time.
+        assert_ne!(cipher.seal(plaintext).unwrap(), sealed);
+    }
+
+    #[test]
+    fn wrong_keys_tampering_and_plain_files_are_rejected() {
+        let cipher = CacheCipher::new("first key").unwrap();
+        let sealed = cipher.seal(b"segment").unwrap();
+
+        let other = CacheCipher::new("second key").unwrap();
+        assert!(matches!(
+            other.open(&sealed),
+            Err(MediaError::InvalidMedia(_))
+        ));
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(cipher.open(&tampered).is_err());
+
+        assert!(cipher.open(b"plain old file contents here").is_err());
+        assert!(CacheCipher::new("").is_err());
+    }
+}
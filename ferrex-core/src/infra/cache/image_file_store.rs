@@ -1,28 +1,76 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+};
 
 use sha2::{Digest, Sha256};
-use tokio::io::AsyncWriteExt;
-use uuid::Uuid;
 
+use super::{
+    BlobBackend, BlobLocation, CacheCipher, LocalBlobBackend, StoredFileBlob,
+};
 use crate::error::{MediaError, Result};
 
-/// File-backed, immutable image blobs keyed by a stable, URL-safe token.
+/// Immutable image blobs keyed by a stable, URL-safe token.
 ///
 /// This exists alongside the integrity-checked `cacache` store to support:
 /// - cheap, streamable serving paths (OS page cache friendly)
 /// - immutable, cacheable URLs (token-addressed)
+///
+/// Blobs live in a local directory unless another [`BlobBackend`] is set;
+/// tokens and sealing are handled here, the same for every backend.
+///
+/// With a [`CacheCipher`] set, blobs are sealed at rest and must be read
+/// through [`ImageFileStore::read`] rather than streamed or redirected to.
 #[derive(Clone, Debug)]
 pub struct ImageFileStore {
-    root: PathBuf,
+    local: Arc<dyn BlobBackend>,
+    /// Replaces `local` once set. Shared by every clone, like the cipher.
+    backend: Arc<OnceLock<Arc<dyn BlobBackend>>>,
+    /// Shared by every clone, so setting it reaches running workers too.
+    cipher: Arc<OnceLock<CacheCipher>>,
 }
 
 impl ImageFileStore {
+    /// A store keeping its blobs as files under `root`.
     pub fn new(root: PathBuf) -> Self {
-        Self { root }
+        Self {
+            local: Arc::new(LocalBlobBackend::new(root)),
+            backend: Arc::new(OnceLock::new()),
+            cipher: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Keep blobs in `backend` instead of the local directory. Can be set
+    /// once, before blobs are written; returns `false` when a backend was
+    /// already in place.
+    pub fn set_backend(&self, backend: Arc<dyn BlobBackend>) -> bool {
+        self.backend.set(backend).is_ok()
+    }
+
+    pub fn backend(&self) -> &dyn BlobBackend {
+        self.backend.get().unwrap_or(&self.local).as_ref()
+    }
+
+    /// Encrypt blobs written from now on. Can be set once; returns `false`
+    /// when a cipher was already in place.
+    pub fn set_cipher(&self, cipher: CacheCipher) -> bool {
+        self.cipher.set(cipher).is_ok()
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.get().is_some()
     }
 
-    pub fn root(&self) -> &Path {
-        &self.root
+    /// Plaintext bytes of a finished blob, `None` when it does not exist.
+    pub async fn read(&self, token: &str) -> Result<Option<Vec<u8>>> {
+        Self::check_token(token)?;
+        let Some(stored) = self.backend().read(token).await? else {
+            return Ok(None);
+        };
+        match self.cipher.get() {
+            Some(cipher) => cipher.open(&stored).map(Some),
+            None => Ok(Some(stored)),
+        }
     }
 
     /// Produce a stable, URL-safe token from a `cacache` integrity string.
@@ -39,79 +87,67 @@ impl ImageFileStore {
                 .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
     }
 
-    pub fn path_for_token(&self, token: &str) -> Result<PathBuf> {
-        if !Self::is_valid_token(token) {
-            return Err(MediaError::InvalidMedia(format!(
+    fn check_token(token: &str) -> Result<()> {
+        if Self::is_valid_token(token) {
+            Ok(())
+        } else {
+            Err(MediaError::InvalidMedia(format!(
                 "invalid image blob token: {token}"
-            )));
+            )))
         }
-        Ok(self.root.join(token))
     }
 
-    pub async fn ensure_root(&self) -> Result<()> {
-        tokio::fs::create_dir_all(&self.root).await.map_err(|err| {
-            MediaError::Internal(format!(
-                "failed to create image blob dir {:?}: {err}",
-                self.root
-            ))
-        })
+    /// How a blob can be served: a local file, a URL to redirect to, or
+    /// neither. Clients cannot open sealed blobs, so with a cipher set no
+    /// URL is handed out and local files must still be read through
+    /// [`ImageFileStore::read`].
+    pub fn location_for_token(&self, token: &str) -> Result<BlobLocation> {
+        Self::check_token(token)?;
+        match self.backend().path_or_url_for_token(token) {
+            BlobLocation::Url(_) if self.is_encrypted() => {
+                Ok(BlobLocation::Proxied)
+            }
+            location => Ok(location),
+        }
     }
 
     pub async fn exists(&self, token: &str) -> Result<bool> {
-        let path = self.path_for_token(token)?;
-        Ok(tokio::fs::try_exists(path).await.unwrap_or(false))
+        Self::check_token(token)?;
+        self.backend().exists(token).await
+    }
+
+    /// Every finished blob in the store. Temporary files of writes still in
+    /// progress and unrelated files are left out.
+    pub async fn list_blobs(&self) -> Result<Vec<StoredFileBlob>> {
+        self.backend().list().await
+    }
+
+    pub async fn remove(&self, token: &str) -> Result<()> {
+        Self::check_token(token)?;
+        self.backend().delete(token).await
     }
 
-    /// Best-effort atomic write (tmp + rename). If the blob already exists, this is a no-op.
+    /// Store a blob unless it already exists, sealing it first when a
+    /// cipher is set.
     pub async fn write_if_missing(
         &self,
         token: &str,
         bytes: &[u8],
     ) -> Result<()> {
-        self.ensure_root().await?;
-        let path = self.path_for_token(token)?;
-
-        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+        Self::check_token(token)?;
+        if self.backend().exists(token).await? {
             return Ok(());
         }
 
-        let tmp = self
-            .root
-            .join(format!("{token}.tmp-{}", Uuid::new_v4().simple()));
-
-        let mut file = tokio::fs::File::create(&tmp).await.map_err(|err| {
-            MediaError::Internal(format!(
-                "failed to create temp image blob {:?}: {err}",
-                tmp
-            ))
-        })?;
-        file.write_all(bytes).await.map_err(|err| {
-            MediaError::Internal(format!(
-                "failed to write temp image blob {:?}: {err}",
-                tmp
-            ))
-        })?;
-        file.flush().await.map_err(|err| {
-            MediaError::Internal(format!(
-                "failed to flush temp image blob {:?}: {err}",
-                tmp
-            ))
-        })?;
-        drop(file);
-
-        // If another writer won the race, discard our temp.
-        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
-            let _ = tokio::fs::remove_file(&tmp).await;
-            return Ok(());
-        }
-
-        tokio::fs::rename(&tmp, &path).await.map_err(|err| {
-            MediaError::Internal(format!(
-                "failed to move image blob {:?} -> {:?}: {err}",
-                tmp, path
-            ))
-        })?;
-
+        let sealed;
+        let bytes = match self.cipher.get() {
+            Some(cipher) => {
+                sealed = cipher.seal(bytes)?;
+                sealed.as_slice()
+            }
+            None => bytes,
+        };
+        self.backend().put_if_missing(token, bytes).await?;
         Ok(())
     }
 }
@@ -1,9 +1,11 @@
 use std::{
     fmt,
     path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use super::CacheCipher;
 use crate::error::{MediaError, Result};
 use cacache::Integrity;
 use ferrex_model::image::ImageSize;
@@ -108,21 +110,52 @@ pub struct CachedImageBlobMeta {
     pub written_at: SystemTime,
 }
 
+/// One live entry of the `cacache` index.
+#[derive(Debug, Clone)]
+pub struct ImageBlobIndexEntry {
+    pub key: String,
+    pub integrity: Integrity,
+    pub byte_len: usize,
+    pub written_at: SystemTime,
+}
+
 /// A thin typed wrapper over `cacache` for image blobs.
+///
+/// With a [`CacheCipher`] set, content is sealed before `cacache` sees it;
+/// integrity hashes then cover the sealed bytes while sizes reported by
+/// [`ImageBlobStore::metadata`] stay those of the image itself.
 #[derive(Clone, Debug)]
 pub struct ImageBlobStore {
     root: ImageCacheRoot,
+    /// Shared by every clone, so setting it reaches running workers too.
+    cipher: Arc<OnceLock<CacheCipher>>,
 }
 
 impl ImageBlobStore {
     pub fn new(root: ImageCacheRoot) -> Self {
-        Self { root }
+        Self {
+            root,
+            cipher: Arc::new(OnceLock::new()),
+        }
     }
 
     pub fn root(&self) -> &ImageCacheRoot {
         &self.root
     }
 
+    /// Encrypt content written from now on. Can be set once; returns
+    /// `false` when a cipher was already in place.
+    pub fn set_cipher(&self, cipher: CacheCipher) -> bool {
+        self.cipher.set(cipher).is_ok()
+    }
+
+    fn open(&self, stored: Vec<u8>) -> Result<Vec<u8>> {
+        match self.cipher.get() {
+            Some(cipher) => cipher.open(&stored),
+            None => Ok(stored),
+        }
+    }
+
     pub async fn metadata(
         &self,
         key: &ImageCacheKey,
@@ -137,16 +170,21 @@ impl ImageBlobStore {
             // `cacache` uses unix millis in `time`.
             let millis = u64::try_from(m.time).unwrap_or(u64::MAX);
             let written_at = UNIX_EPOCH + Duration::from_millis(millis);
+            let byte_len = if self.cipher.get().is_some() {
+                CacheCipher::plaintext_len(m.size as u64) as usize
+            } else {
+                m.size
+            };
             CachedImageBlobMeta {
                 integrity: m.integrity,
-                byte_len: m.size,
+                byte_len,
                 written_at,
             }
         }))
     }
 
     pub async fn read(&self, key: &ImageCacheKey) -> Result<Vec<u8>> {
-        cacache::read(self.root.as_path(), key.as_str())
+        let stored = cacache::read(self.root.as_path(), key.as_str())
             .await
             .map_err(|e| match e {
                 cacache::Error::EntryNotFound(_, _) => MediaError::NotFound(
@@ -170,11 +208,12 @@ impl ImageBlobStore {
                 cacache::Error::SerdeError(_, msg) => {
                     MediaError::Internal(format!("cacache read serde error: {msg}"))
                 }
-            })
+            })?;
+        self.open(stored)
     }
 
     pub async fn read_hash(&self, hash: &Integrity) -> Result<Vec<u8>> {
-        cacache::read_hash(self.root.as_path(), hash)
+        let stored = cacache::read_hash(self.root.as_path(), hash)
             .await
             .map_err(|e| match e {
                 cacache::Error::EntryNotFound(_, _) => MediaError::NotFound(
@@ -198,7 +237,8 @@ impl ImageBlobStore {
                 cacache::Error::SerdeError(_, msg) => {
                     MediaError::Internal(format!("cacache read serde error: {msg}"))
                 }
-            })
+            })?;
+        self.open(stored)
     }
 
     pub async fn remove(&self, key: &ImageCacheKey) -> Result<()> {
@@ -211,13 +251,62 @@ impl ImageBlobStore {
             })
     }
 
+    /// Every live index entry. Walks the whole index, so this is meant for
+    /// maintenance jobs rather than request paths.
+    pub async fn list_entries(&self) -> Result<Vec<ImageBlobIndexEntry>> {
+        let root = self.root.as_path().to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            if !root.exists() {
+                return Ok(Vec::new());
+            }
+            cacache::list_sync(&root)
+                .map(|entry| {
+                    let m = entry.map_err(|e| {
+                        MediaError::Internal(format!(
+                            "cacache list failed: {e}"
+                        ))
+                    })?;
+                    let millis = u64::try_from(m.time).unwrap_or(u64::MAX);
+                    Ok(ImageBlobIndexEntry {
+                        key: m.key,
+                        integrity: m.integrity,
+                        byte_len: m.size,
+                        written_at: UNIX_EPOCH + Duration::from_millis(millis),
+                    })
+                })
+                .collect()
+        })
+        .await
+        .map_err(|e| {
+            MediaError::Internal(format!("cacache list task failed: {e}"))
+        })?
+    }
+
+    /// Delete the content addressed by `hash`, regardless of which index
+    /// entries still point at it.
+    pub async fn remove_content(&self, hash: &Integrity) -> Result<()> {
+        cacache::remove_hash(self.root.as_path(), hash)
+            .await
+            .map_err(|e| {
+                MediaError::Internal(format!("cacache remove_hash failed: {e}"))
+            })
+    }
+
     pub async fn write(
         &self,
         key: &ImageCacheKey,
         bytes: &[u8],
     ) -> Result<StoredImageBlob> {
+        let sealed;
+        let stored = match self.cipher.get() {
+            Some(cipher) => {
+                sealed = cipher.seal(bytes)?;
+                sealed.as_slice()
+            }
+            None => bytes,
+        };
         let integrity =
-            cacache::write(self.root.as_path(), key.as_str(), bytes)
+            cacache::write(self.root.as_path(), key.as_str(), stored)
                 .await
                 .map_err(|e| {
                     MediaError::Internal(format!("cacache write failed: {e}"))
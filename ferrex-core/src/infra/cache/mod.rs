@@ -3,10 +3,20 @@
 //! This module provides a typed facade around `cacache` for integrity-checked
 //! blob storage used by the image provider.
 
+pub mod blob_backend;
+pub mod disk_space;
+pub mod encryption;
 pub mod image_file_store;
 pub mod image_store;
 pub mod media_store;
+#[cfg(feature = "s3")]
+pub mod s3_blob_backend;
 
+pub use blob_backend::*;
+pub use disk_space::*;
+pub use encryption::*;
 pub use image_file_store::*;
 pub use image_store::*;
 pub use media_store::*;
+#[cfg(feature = "s3")]
+pub use s3_blob_backend::*;
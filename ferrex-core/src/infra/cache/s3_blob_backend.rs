@@ -0,0 +1,202 @@
+use std::{sync::Arc, time::SystemTime};
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use object_store::{
+    ObjectStore, PutMode, PutOptions, PutPayload,
+    aws::{AmazonS3, AmazonS3Builder},
+    path::Path as ObjectPath,
+};
+
+use super::{BlobBackend, BlobLocation, ImageFileStore, StoredFileBlob};
+use crate::error::{MediaError, Result};
+
+/// Where an [`S3BlobBackend`] keeps its blobs.
+#[derive(Debug, Clone, Default)]
+pub struct S3BlobSettings {
+    pub bucket: String,
+    pub region: Option<String>,
+    /// Custom endpoint for S3-compatible services such as MinIO or R2.
+    pub endpoint: Option<String>,
+    /// Key prefix blobs are stored under, e.g. `ferrex/images`.
+    pub prefix: Option<String>,
+    /// Public base URL of the bucket (or a CDN in front of it). When set,
+    /// clients are redirected there instead of having blobs proxied.
+    pub public_url: Option<String>,
+}
+
+/// Blobs as objects in an S3-compatible bucket.
+///
+/// Credentials come from the standard `AWS_*` environment variables.
+#[derive(Debug, Clone)]
+pub struct S3BlobBackend {
+    store: Arc<AmazonS3>,
+    prefix: Option<String>,
+    public_url: Option<String>,
+}
+
+impl S3BlobBackend {
+    pub fn new(settings: S3BlobSettings) -> Result<Self> {
+        let mut builder =
+            AmazonS3Builder::from_env().with_bucket_name(&settings.bucket);
+        if let Some(region) = &settings.region {
+            builder = builder.with_region(region);
+        }
+        if let Some(endpoint) = &settings.endpoint {
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(endpoint.starts_with("http://"));
+        }
+        let store = builder.build().map_err(|err| {
+            MediaError::Internal(format!(
+                "failed to configure S3 image store for bucket {}: {err}",
+                settings.bucket
+            ))
+        })?;
+
+        Ok(Self {
+            store: Arc::new(store),
+            prefix: settings
+                .prefix
+                .map(|prefix| prefix.trim_matches('/').to_string())
+                .filter(|prefix| !prefix.is_empty()),
+            public_url: settings
+                .public_url
+                .map(|url| url.trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty()),
+        })
+    }
+
+    fn key(&self, token: &str) -> ObjectPath {
+        let token = token.trim_start_matches('/');
+        match &self.prefix {
+            Some(prefix) => ObjectPath::from(format!("{prefix}/{token}")),
+            None => ObjectPath::from(token),
+        }
+    }
+
+    fn error(
+        action: &str,
+        token: &str,
+        err: object_store::Error,
+    ) -> MediaError {
+        MediaError::Internal(format!(
+            "failed to {action} image blob {token} in S3: {err}"
+        ))
+    }
+}
+
+#[async_trait]
+impl BlobBackend for S3BlobBackend {
+    /// Conditional create, so concurrent writers of the same token leave a
+    /// single object behind.
+    async fn put_if_missing(&self, token: &str, bytes: &[u8]) -> Result<bool> {
+        let options = PutOptions {
+            mode: PutMode::Create,
+            ..PutOptions::default()
+        };
+        match self
+            .store
+            .put_opts(
+                &self.key(token),
+                PutPayload::from(bytes.to_vec()),
+                options,
+            )
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::AlreadyExists { .. }) => Ok(false),
+            Err(err) => Err(Self::error("write", token, err)),
+        }
+    }
+
+    async fn read(&self, token: &str) -> Result<Option<Vec<u8>>> {
+        let result = match self.store.get(&self.key(token)).await {
+            Ok(result) => result,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(err) => return Err(Self::error("read", token, err)),
+        };
+        let bytes = result
+            .bytes()
+            .await
+            .map_err(|err| Self::error("read", token, err))?;
+        Ok(Some(bytes.to_vec()))
+    }
+
+    async fn exists(&self, token: &str) -> Result<bool> {
+        match self.store.head(&self.key(token)).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(err) => Err(Self::error("look up", token, err)),
+        }
+    }
+
+    async fn delete(&self, token: &str) -> Result<()> {
+        match self.store.delete(&self.key(token)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(err) => Err(Self::error("remove", token, err)),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<StoredFileBlob>> {
+        let prefix = self.prefix.as_deref().map(ObjectPath::from);
+        let objects: Vec<_> = self
+            .store
+            .list(prefix.as_ref())
+            .try_collect()
+            .await
+            .map_err(|err| {
+                MediaError::Internal(format!(
+                    "failed to list image blobs in S3: {err}"
+                ))
+            })?;
+
+        Ok(objects
+            .into_iter()
+            .filter_map(|meta| {
+                let token = meta.location.filename()?.to_string();
+                ImageFileStore::is_valid_token(&token).then(|| StoredFileBlob {
+                    token,
+                    byte_len: meta.size,
+                    modified_at: SystemTime::from(meta.last_modified),
+                })
+            })
+            .collect())
+    }
+
+    fn path_or_url_for_token(&self, token: &str) -> BlobLocation {
+        match &self.public_url {
+            Some(base) => {
+                BlobLocation::Url(public_object_url(base, &self.key(token)))
+            }
+            None => BlobLocation::Proxied,
+        }
+    }
+}
+
+/// `key` under `base`, joined by exactly one `/`.
+fn public_object_url(base: &str, key: &ObjectPath) -> String {
+    format!(
+        "{}/{}",
+        base.trim_end_matches('/'),
+        key.as_ref().trim_start_matches('/')
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_urls_have_a_single_separator() {
+        let key = ObjectPath::from("ferrex/images/abc");
+        assert_eq!(
+            public_object_url("https://cdn.test/", &key),
+            "https://cdn.test/ferrex/images/abc"
+        );
+        assert_eq!(
+            public_object_url("https://cdn.test", &key),
+            "https://cdn.test/ferrex/images/abc"
+        );
+    }
+}
@@ -1,4 +1,22 @@
+mod orphans;
+mod placeholder;
+mod source;
+mod unavailable;
+
+pub use orphans::{ORPHAN_MIN_AGE, OrphanedImageBlobsReport};
+pub use placeholder::{
+    PLACEHOLDER_CONTENT_TYPE, PLACEHOLDER_EDGE, encode_placeholder,
+};
+pub use source::{
+    DirectUrlImageSource, ImageLocation, ImageSource, LocalFileImageSource,
+    TmdbImageSource,
+};
+pub use unavailable::DEFAULT_UNAVAILABLE_TTL;
+
+use unavailable::{UnavailableImages, is_permanent_image_failure};
+
 use crate::{
+    api::routes::{utils::replace_param, v1},
     database::{
         repository_ports::{
             images::{ImageRepository, ImgDbLookup, ImgInput},
@@ -8,22 +26,22 @@ use crate::{
     },
     error::{MediaError, Result},
     infra::cache::{
-        CachedImageBlobMeta, ImageBlobStore, ImageCacheRoot, ImageFileStore,
-        image_cache_key_for,
+        BlobBackend, BlobLocation, CacheCipher, CachedImageBlobMeta,
+        DiskSpaceGuard, ImageBlobStore, ImageCacheKey, ImageCacheRoot,
+        ImageFileStore, image_cache_key_for,
     },
 };
 
 use ferrex_model::ImageReadyEvent;
 use ferrex_model::{
-    ImageSize,
-    image::{ImageDimensions, ImageVariant},
+    BackdropSize, EpisodeSize, ImageMediaType, ImageSize, PosterSize,
+    ProfileSize,
+    image::{ImageDimensions, ImageVariant, ThumbnailStrategy},
 };
 
 #[cfg(not(feature = "demo"))]
-use ferrex_model::{
-    MediaID,
-    media_type::{ImageMediaType, VideoMediaType},
-};
+use ferrex_model::{MediaID, media_type::VideoMediaType};
+use serde::{Deserialize, Serialize};
 use std::{
     any::type_name_of_val,
     collections::HashSet,
@@ -34,7 +52,10 @@ use std::{
     },
     time::Duration,
 };
-use tokio::sync::{Mutex, Notify, Semaphore, broadcast, mpsc};
+use tokio::sync::{
+    Mutex, Notify, Semaphore, broadcast,
+    mpsc::{self, error::TrySendError},
+};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -53,6 +74,8 @@ pub struct ImageService {
     /// Non-blocking cache fill coordination (server can enqueue without awaiting).
     in_flight: Arc<std::sync::Mutex<HashSet<String>>>,
     cache_fill_tx: mpsc::Sender<CacheFillJob>,
+    /// Re-enqueue attempts for a job that found the queue full.
+    cache_fill_enqueue_retries: usize,
     // Per-variant singleflight to avoid duplicate downloads of the same size
     in_flight_variants:
         Arc<Mutex<std::collections::HashMap<String, Arc<Notify>>>>,
@@ -62,8 +85,21 @@ pub struct ImageService {
     sf_variant_waiters: Arc<AtomicU64>,
     // Diagnostics: cache-fill queue pressure
     cache_fill_enqueued: Arc<AtomicU64>,
+    cache_fill_deduplicated: Arc<AtomicU64>,
+    cache_fill_requeue_attempts: Arc<AtomicU64>,
     cache_fill_dropped: Arc<AtomicU64>,
     image_events: broadcast::Sender<ImageReadyEvent>,
+    /// Shared with the cache-fill workers, which hold their own clones.
+    disk_space: Arc<std::sync::OnceLock<DiskSpaceGuard>>,
+    thumbnail_strategy: Arc<std::sync::OnceLock<ThumbnailStrategy>>,
+    thumbnail_quality: Arc<std::sync::OnceLock<u8>>,
+    /// Sources registered by metadata providers, consulted before the
+    /// built-in TMDB and direct-URL sources.
+    image_sources: Arc<std::sync::RwLock<Vec<Arc<dyn ImageSource>>>>,
+    /// Sizes whose download failed permanently, answered "unavailable"
+    /// until their entry expires or the image is refreshed.
+    unavailable: Arc<UnavailableImages>,
+    unavailable_ttl: Arc<std::sync::OnceLock<Duration>>,
 }
 
 #[derive(Debug, Clone)]
@@ -90,7 +126,7 @@ impl fmt::Debug for ImageService {
             )
             .field("image_repository", &type_name_of_val(self.images.as_ref()))
             .field("image_cache_root", &self.blob_store.root())
-            .field("image_blob_root", &self.file_store.root())
+            .field("image_blob_backend", &self.file_store.backend())
             .field("http_client", &self.http_client)
             .field("in_flight_requests", &in_flight)
             .field("permits_available", &self.permits.available_permits())
@@ -106,10 +142,19 @@ impl fmt::Debug for ImageService {
                 "cache_fill_enqueued",
                 &self.cache_fill_enqueued.load(Ordering::Relaxed),
             )
+            .field(
+                "cache_fill_deduplicated",
+                &self.cache_fill_deduplicated.load(Ordering::Relaxed),
+            )
+            .field(
+                "cache_fill_requeue_attempts",
+                &self.cache_fill_requeue_attempts.load(Ordering::Relaxed),
+            )
             .field(
                 "cache_fill_dropped",
                 &self.cache_fill_dropped.load(Ordering::Relaxed),
             )
+            .field("unavailable_images", &self.unavailable.len())
             .finish()
     }
 }
@@ -186,6 +231,11 @@ impl ImageService {
                 .ok()
                 .and_then(|v| v.parse::<usize>().ok())
                 .unwrap_or(5);
+        let cache_fill_enqueue_retries =
+            std::env::var("IMAGE_CACHE_FILL_ENQUEUE_RETRIES")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(3);
 
         let (cache_fill_tx, cache_fill_rx) =
             mpsc::channel::<CacheFillJob>(cache_fill_queue_size);
@@ -200,6 +250,7 @@ impl ImageService {
             http_client,
             in_flight: Arc::new(std::sync::Mutex::new(HashSet::new())),
             cache_fill_tx,
+            cache_fill_enqueue_retries,
             in_flight_variants: Arc::new(Mutex::new(
                 std::collections::HashMap::new(),
             )),
@@ -208,8 +259,16 @@ impl ImageService {
             sf_variant_leaders: Arc::new(AtomicU64::new(0)),
             sf_variant_waiters: Arc::new(AtomicU64::new(0)),
             cache_fill_enqueued: Arc::new(AtomicU64::new(0)),
+            cache_fill_deduplicated: Arc::new(AtomicU64::new(0)),
+            cache_fill_requeue_attempts: Arc::new(AtomicU64::new(0)),
             cache_fill_dropped: Arc::new(AtomicU64::new(0)),
             image_events,
+            disk_space: Arc::new(std::sync::OnceLock::new()),
+            thumbnail_strategy: Arc::new(std::sync::OnceLock::new()),
+            thumbnail_quality: Arc::new(std::sync::OnceLock::new()),
+            image_sources: Arc::new(std::sync::RwLock::new(Vec::new())),
+            unavailable: Arc::new(UnavailableImages::default()),
+            unavailable_ttl: Arc::new(std::sync::OnceLock::new()),
         };
 
         svc.start_cache_fill_workers(
@@ -218,23 +277,235 @@ impl ImageService {
             cache_fill_max_retries,
         );
         info!(
-            "Image cache-fill queue initialized: workers={}, queue_size={}, max_retries={}",
+            "Image cache-fill queue initialized: workers={}, queue_size={}, max_retries={}, enqueue_retries={}",
             cache_fill_concurrency,
             cache_fill_queue_size,
-            cache_fill_max_retries
+            cache_fill_max_retries,
+            cache_fill_enqueue_retries
         );
 
         svc
     }
 
+    /// Refuse downloads and thumbnail generation while the cache volume is
+    /// below the guard's threshold. Can be set once; later calls are ignored.
+    pub fn set_disk_space_guard(&self, guard: DiskSpaceGuard) {
+        if self.disk_space.set(guard).is_err() {
+            warn!("Image service disk space guard already configured");
+        }
+    }
+
+    /// Choose how episode thumbnail frames are selected. Can be set once;
+    /// later calls are ignored.
+    pub fn set_thumbnail_strategy(&self, strategy: ThumbnailStrategy) {
+        if self.thumbnail_strategy.set(strategy).is_err() {
+            warn!("Image service thumbnail strategy already configured");
+        }
+    }
+
+    /// JPEG quality of generated episode thumbnails. Thumbnails already
+    /// cached keep theirs. Can be set once; later calls are ignored.
+    pub fn set_thumbnail_quality(&self, quality: u8) {
+        if self.thumbnail_quality.set(quality).is_err() {
+            warn!("Image service thumbnail quality already configured");
+        }
+    }
+
+    /// How long a size whose download failed permanently (a 404 from
+    /// TMDB, a missing local file) is answered "unavailable" without
+    /// another attempt. Zero keeps retrying every request. Can be set
+    /// once; later calls are ignored.
+    pub fn set_unavailable_ttl(&self, ttl: Duration) {
+        if self.unavailable_ttl.set(ttl).is_err() {
+            warn!("Image service unavailable-image TTL already configured");
+        }
+    }
+
+    /// Why `iid` at `imz` is not being downloaded, when an earlier
+    /// download failed permanently and the failure has not expired.
+    pub fn unavailable_reason(
+        &self,
+        iid: Uuid,
+        imz: ImageSize,
+    ) -> Option<String> {
+        self.unavailable.reason(iid, imz)
+    }
+
+    /// Encrypt cached image content and blob files written from now on.
+    /// Can be set once; later calls are ignored.
+    pub fn set_cache_cipher(&self, cipher: CacheCipher) {
+        let blobs = self.blob_store.set_cipher(cipher.clone());
+        let files = self.file_store.set_cipher(cipher);
+        if !(blobs && files) {
+            warn!("Image service cache encryption already configured");
+        }
+    }
+
+    /// Keep materialized blobs in `backend` instead of the local blob
+    /// directory. Can be set once, before the first blob is written.
+    pub fn set_blob_backend(&self, backend: Arc<dyn BlobBackend>) {
+        if !self.file_store.set_backend(backend) {
+            warn!("Image service blob backend already configured");
+        }
+    }
+
+    /// Whether blobs are sealed at rest. Encrypted blobs cannot be streamed
+    /// from the path [`ImageService::image_blob_location`] returns; read
+    /// them with [`ImageService::read_image_blob`] instead.
+    pub fn cache_encrypted(&self) -> bool {
+        self.file_store.is_encrypted()
+    }
+
+    /// Let images whose stored path `source` recognizes be downloaded from
+    /// it. Sources registered earlier win; TMDB paths are always handled.
+    pub fn register_image_source(&self, source: Arc<dyn ImageSource>) {
+        if let Ok(mut sources) = self.image_sources.write() {
+            if sources.iter().any(|s| s.name() == source.name()) {
+                warn!("Image source {} already registered", source.name());
+                return;
+            }
+            sources.push(source);
+        }
+    }
+
+    /// Registered sources in registration order, then the builtin ones.
+    fn image_sources(&self) -> Vec<Arc<dyn ImageSource>> {
+        let mut sources = self
+            .image_sources
+            .read()
+            .map(|sources| sources.clone())
+            .unwrap_or_default();
+        sources.push(Arc::new(TmdbImageSource));
+        sources.push(Arc::new(DirectUrlImageSource));
+        sources
+    }
+
+    /// Name of the source that serves `path` at `imz`, if any does.
+    pub fn image_source_name(
+        &self,
+        path: &str,
+        imz: ImageSize,
+    ) -> Option<&'static str> {
+        self.image_sources()
+            .iter()
+            .find(|source| source.locate(path, imz).is_some())
+            .map(|source| source.name())
+    }
+
+    /// Where the bytes for `path` at `imz` live.
+    fn locate_image(
+        &self,
+        path: &str,
+        imz: ImageSize,
+    ) -> Result<ImageLocation> {
+        self.image_sources()
+            .iter()
+            .find_map(|source| source.locate(path, imz))
+            .ok_or_else(|| {
+                MediaError::InvalidMedia(format!(
+                    "No image source recognizes path {path}"
+                ))
+            })
+    }
+
+    /// Bytes of an image, checked against the advertised length for HTTP.
+    async fn fetch_image_bytes(
+        &self,
+        location: &ImageLocation,
+    ) -> Result<Vec<u8>> {
+        let url = match location {
+            ImageLocation::File(path) => {
+                return Ok(tokio::fs::read(path).await?);
+            }
+            ImageLocation::Url(url) => url,
+        };
+
+        let request = self
+            .http_client
+            // Avoid compressed, range-susceptible responses for binary assets
+            .get(url)
+            .header(reqwest::header::USER_AGENT, "Mozilla/5.0")
+            .build()
+            .map_err(|e| {
+                MediaError::Internal(format!(
+                    "Failed to build reqwest Request for image: {}",
+                    e
+                ))
+            })?;
+
+        info!("Reqwest Headers: {:#?}", request.headers());
+
+        let response = self.http_client.execute(request).await?;
+
+        if !response.status().is_success() {
+            return Err(MediaError::HttpStatus {
+                status: response.status(),
+                url: url.clone(),
+            });
+        }
+
+        let expected_len = response.content_length();
+        let bytes = response.bytes().await?;
+
+        if let Some(content_len) = expected_len
+            && bytes.len() as u64 != content_len
+        {
+            return Err(MediaError::Internal(format!(
+                "Image size mismatch: got {} bytes, expected {}",
+                bytes.len(),
+                content_len
+            )));
+        }
+        Ok(bytes.to_vec())
+    }
+
+    fn unavailable_ttl(&self) -> Duration {
+        self.unavailable_ttl
+            .get()
+            .copied()
+            .unwrap_or(DEFAULT_UNAVAILABLE_TTL)
+    }
+
+    fn thumbnail_strategy(&self) -> ThumbnailStrategy {
+        self.thumbnail_strategy.get().copied().unwrap_or_default()
+    }
+
+    fn thumbnail_quality(&self) -> u8 {
+        self.thumbnail_quality
+            .get()
+            .copied()
+            .unwrap_or(DEFAULT_THUMBNAIL_QUALITY)
+    }
+
+    fn ensure_disk_space(&self) -> Result<()> {
+        match self.disk_space.get() {
+            Some(guard) => guard.check(),
+            None => Ok(()),
+        }
+    }
+
     pub fn subscribe_image_events(
         &self,
     ) -> broadcast::Receiver<ImageReadyEvent> {
         self.image_events.subscribe()
     }
 
-    pub fn image_blob_path(&self, token: &str) -> Result<std::path::PathBuf> {
-        self.file_store.path_for_token(token)
+    /// How the blob behind `token` can be served without reading it
+    /// through [`ImageService::read_image_blob`].
+    pub fn image_blob_location(&self, token: &str) -> Result<BlobLocation> {
+        self.file_store.location_for_token(token)
+    }
+
+    pub async fn image_blob_exists(&self, token: &str) -> Result<bool> {
+        self.file_store.exists(token).await
+    }
+
+    /// Plaintext bytes of a materialized blob, `None` when it is missing.
+    pub async fn read_image_blob(
+        &self,
+        token: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        self.file_store.read(token).await
     }
 
     fn start_cache_fill_workers(
@@ -270,13 +541,21 @@ impl ImageService {
     /// Enqueue a best-effort cache fill for `iid + imz` without blocking the caller.
     ///
     /// This is intended for request handlers. It dedupes in-flight work so repeated
-    /// requests do not spawn unbounded background tasks.
+    /// requests do not spawn unbounded background tasks. On
+    /// [`CacheFillOutcome::Dropped`] the caller may fall back to
+    /// [`ImageService::pick_best_available`].
     pub fn enqueue_cache(
         &self,
         iid: Uuid,
         imz: ImageSize,
         policy: CachePolicy,
-    ) {
+    ) -> CacheFillOutcome {
+        if policy == CachePolicy::Ensure
+            && self.unavailable.reason(iid, imz).is_some()
+        {
+            return CacheFillOutcome::Unavailable;
+        }
+
         let key = format!(
             "fill:{}:{}:{}",
             iid.as_hyphenated(),
@@ -285,7 +564,8 @@ impl ImageService {
         );
 
         if !self.try_begin_enqueue(&key) {
-            return;
+            self.cache_fill_deduplicated.fetch_add(1, Ordering::Relaxed);
+            return CacheFillOutcome::Deduplicated;
         }
 
         let job = CacheFillJob {
@@ -298,6 +578,18 @@ impl ImageService {
         match self.cache_fill_tx.try_send(job) {
             Ok(()) => {
                 self.cache_fill_enqueued.fetch_add(1, Ordering::Relaxed);
+                CacheFillOutcome::Enqueued
+            }
+            Err(TrySendError::Full(job))
+                if self.cache_fill_enqueue_retries > 0
+                    && tokio::runtime::Handle::try_current().is_ok() =>
+            {
+                debug!(
+                    "[enqueue_cache_fill] Cache fill queue full, retrying in background: iid={}, imz={:?}",
+                    iid, imz
+                );
+                self.spawn_enqueue_retry(job);
+                CacheFillOutcome::Dropped
             }
             Err(err) => {
                 self.cache_fill_dropped.fetch_add(1, Ordering::Relaxed);
@@ -306,10 +598,71 @@ impl ImageService {
                     iid, imz, err
                 );
                 self.finish_enqueue(&key);
+                CacheFillOutcome::Dropped
             }
         }
     }
 
+    /// Re-offer a job that found the queue full a bounded number of times,
+    /// with jittered backoff. The in-flight key stays held meanwhile so
+    /// repeat requests for the same variant are deduplicated.
+    fn spawn_enqueue_retry(&self, job: CacheFillJob) {
+        let svc = self.clone();
+        tokio::spawn(async move {
+            let mut job = job;
+            for attempt in 1..=svc.cache_fill_enqueue_retries {
+                tokio::time::sleep(enqueue_retry_delay(attempt)).await;
+                svc.cache_fill_requeue_attempts
+                    .fetch_add(1, Ordering::Relaxed);
+                match svc.cache_fill_tx.try_send(job) {
+                    Ok(()) => {
+                        svc.cache_fill_enqueued.fetch_add(1, Ordering::Relaxed);
+                        return;
+                    }
+                    Err(TrySendError::Full(returned)) => job = returned,
+                    Err(TrySendError::Closed(returned)) => {
+                        job = returned;
+                        break;
+                    }
+                }
+            }
+
+            svc.cache_fill_dropped.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                "[enqueue_cache_fill] Dropped cache fill after {} retries (queue full/closed): iid={}, imz={:?}",
+                svc.cache_fill_enqueue_retries, job.iid, job.imz
+            );
+            svc.finish_enqueue(&job.key);
+        });
+    }
+
+    /// Largest standard variant of `iid` smaller than `imz` that is already
+    /// cached and materialized, for serving while `imz` itself is filled.
+    pub async fn pick_best_available(
+        &self,
+        iid: Uuid,
+        imz: ImageSize,
+    ) -> Result<Option<AvailableImage>> {
+        for candidate in smaller_standard_sizes(imz) {
+            let Some(meta) =
+                self.read_cached_meta_by_key(iid, candidate).await?
+            else {
+                continue;
+            };
+            let token = ImageFileStore::token_from_integrity(
+                &meta.integrity.to_string(),
+            );
+            if self.file_store.exists(&token).await? {
+                return Ok(Some(AvailableImage {
+                    imz: candidate,
+                    token,
+                    byte_len: meta.byte_len as u64,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
     fn try_begin_enqueue(&self, key: &str) -> bool {
         let Ok(mut set) = self.in_flight.lock() else {
             return false;
@@ -340,6 +693,19 @@ impl ImageService {
             policy,
         } = job;
 
+        // Refresh always downloads; Ensure only does when the variant is not
+        // cached yet and is refused inside `download_variant` in that case.
+        if policy == CachePolicy::Refresh
+            && let Err(err) = self.ensure_disk_space()
+        {
+            warn!(
+                "[enqueue_cache_fill] Skipping cache fill: worker={}, iid={}, imz={:?}, err={}",
+                worker_id, iid, imz, err
+            );
+            self.finish_enqueue(&key);
+            return;
+        }
+
         let mut attempt = 0usize;
         let mut backoff = Duration::from_millis(200);
         let max_backoff = Duration::from_secs(5);
@@ -416,12 +782,41 @@ impl ImageService {
             self.file_store.write_if_missing(&token, &bytes).await?;
         }
 
+        self.emit_image_ready(record.iid, record.imz, token, None)
+            .await;
+        Ok(())
+    }
+
+    /// Tell subscribers that `iid` at `imz` can be fetched. The owning media
+    /// is looked up when the caller does not know it.
+    async fn emit_image_ready(
+        &self,
+        iid: Uuid,
+        imz: ImageSize,
+        token: String,
+        media_id: Option<Uuid>,
+    ) {
+        if self.image_events.receiver_count() == 0 {
+            return;
+        }
+        let media_id = match media_id {
+            Some(media_id) => Some(media_id),
+            None => self
+                .images
+                .lookup_variant_by_iid(iid)
+                .await
+                .ok()
+                .flatten()
+                .map(|variant| variant.media_id),
+        };
+        let path = replace_param(v1::images::BLOB_ITEM, "{token}", &token);
         let _ = self.image_events.send(ImageReadyEvent {
-            iid: record.iid,
-            imz: record.imz,
+            iid,
+            imz,
             token,
+            media_id,
+            path,
         });
-        Ok(())
     }
 
     /// Read cached image bytes for a database record.
@@ -463,6 +858,7 @@ impl ImageService {
         let tmdb_path = iin.tmdb_path.ok_or(MediaError::Internal(
             "Failed to download, tmdb_path must be passed".to_string(),
         ))?;
+        self.ensure_disk_space()?;
 
         let _permit = self
             .permits
@@ -481,60 +877,19 @@ impl ImageService {
             lang: None,
         };
 
-        let url = format!(
-            "https://image.tmdb.org/t/p/{}{}",
-            iin.imz.to_tmdb_param(),
-            tmdb_path
-        );
+        let location = self.locate_image(tmdb_path, iin.imz)?;
 
         info!(
-            "[download_variant] Fetching image from tmdb for iid={}, media_type={:?}, media_id={:?}, imz={:?}, width={:?}, url={}",
+            "[download_variant] Fetching image for iid={}, media_type={:?}, media_id={:?}, imz={:?}, width={:?}, location={:?}",
             iin.iid,
             iin.media_type,
             iin.media_id,
             iin.imz,
             iin.imz.width(),
-            url
+            location
         );
 
-        // .header(reqwest::header::ACCEPT_ENCODING, "identity")
-
-        let request = self
-            .http_client
-            // Avoid compressed, range-susceptible responses for binary assets
-            .get(&url)
-            .header(reqwest::header::USER_AGENT, "Mozilla/5.0")
-            .build()
-            .map_err(|e| {
-                MediaError::Internal(format!(
-                    "Failed to build reqwest Request for image: {}",
-                    e
-                ))
-            })?;
-
-        info!("Reqwest Headers: {:#?}", request.headers());
-
-        let response = self.http_client.execute(request).await?;
-
-        if !response.status().is_success() {
-            return Err(MediaError::HttpStatus {
-                status: response.status(),
-                url,
-            });
-        }
-
-        let expected_len = response.content_length();
-        let bytes = response.bytes().await?;
-
-        if let Some(content_len) = expected_len
-            && bytes.len() as u64 != content_len
-        {
-            return Err(MediaError::Internal(format!(
-                "Image size mismatch: got {} bytes, expected {}",
-                bytes.len(),
-                content_len
-            )));
-        }
+        let bytes = self.fetch_image_bytes(&location).await?;
 
         // Gather metadata from the freshly downloaded bytes.
         let (width, height) = self.get_image_dimensions(&bytes)?;
@@ -592,6 +947,8 @@ impl ImageService {
             byte_len: stored.byte_len as i32,
         };
 
+        self.ensure_placeholder(iin.iid, &bytes).await;
+
         info!(
             "[download_variant] Prepared upsert context: iid={}, media_type={:?}, media_id={:?}, imz={:?}, width={:?}, tmdb_path={}, cache_key={}, integrity={}, byte_len={}",
             ctx.iid,
@@ -606,11 +963,8 @@ impl ImageService {
         );
 
         let record = self.images.upsert_image(&ctx).await?;
-        let _ = self.image_events.send(ImageReadyEvent {
-            iid: record.iid,
-            imz: record.imz,
-            token,
-        });
+        self.emit_image_ready(record.iid, record.imz, token, ctx.media_id)
+            .await;
         Ok(record)
     }
 
@@ -621,6 +975,7 @@ impl ImageService {
         imz: ImageSize,
     ) -> Result<ImageRecord> {
         self.ensure_ffmpeg_initialized()?;
+        self.ensure_disk_space()?;
 
         let (target_w, target_h) =
             imz.dimensions().ok_or_else(|| MediaError::Internal(
@@ -694,12 +1049,14 @@ impl ImageService {
 
         let video_path = media.path.clone();
         let video_path_string = video_path.to_string_lossy().to_string();
+        let strategy = self.thumbnail_strategy();
+        let quality = self.thumbnail_quality();
         let (src_w, src_h, encoded_jpeg) =
             tokio::task::spawn_blocking(move || {
                 let (src_w, src_h, rgb_bytes) =
-                    extract_frame_at_percentage(&video_path_string, 0.3)?;
+                    extract_thumbnail_frame(&video_path_string, strategy)?;
                 let encoded_jpeg = encode_thumbnail_jpeg_rgb24(
-                    src_w, src_h, rgb_bytes, target_w, target_h, 85,
+                    src_w, src_h, rgb_bytes, target_w, target_h, quality,
                 )?;
                 Ok::<_, MediaError>((src_w, src_h, encoded_jpeg))
             })
@@ -798,6 +1155,8 @@ impl ImageService {
             media_type: None,
         };
 
+        self.ensure_placeholder(iid, &encoded_jpeg).await;
+
         info!(
             "[download_variant] Prepared upsert context: iid={}, media_type={:?}, media_id={:?}, imz={:?}, width={:?}, cache_key={}, integrity={}, byte_len={}",
             ctx.iid,
@@ -811,11 +1170,8 @@ impl ImageService {
         );
 
         let record = self.images.upsert_image(&ctx).await?;
-        let _ = self.image_events.send(ImageReadyEvent {
-            iid: record.iid,
-            imz: record.imz,
-            token,
-        });
+        self.emit_image_ready(record.iid, record.imz, token, ctx.media_id)
+            .await;
         Ok(record)
 
         // if let Some(existing_image) =
@@ -878,6 +1234,24 @@ impl ImageService {
         //     Ok(file_path)
     }
 
+    /// Warm a single variant ahead of demand.
+    ///
+    /// Returns `Ok(false)` without any download attempt when the variant is
+    /// already cached. Downloads share the variant semaphore with on-demand
+    /// requests, so callers should drive prewarm one item at a time.
+    pub async fn prewarm_variant(
+        &self,
+        iid: Uuid,
+        imz: ImageSize,
+    ) -> Result<bool> {
+        if self.images.lookup_cached_image(iid, imz).await?.is_some() {
+            return Ok(false);
+        }
+
+        self.cached_image(iid, imz, CachePolicy::Ensure).await?;
+        Ok(true)
+    }
+
     /// Returns an image from the cache
     /// Either ensures the presence of the image, or force overwrites
     pub async fn cached_image(
@@ -893,7 +1267,11 @@ impl ImageService {
             imz.width()
         );
 
+        let requested = imz;
         let mut imz = imz;
+        if policy == CachePolicy::Refresh {
+            self.unavailable.clear(iid, requested);
+        }
         // 1. Ensure we have a tmdb_image_variants row for this image id.
         debug!("[get_or_download_variant] Looking up variant by iid...");
         let variant: OriginalImage = match self
@@ -932,6 +1310,11 @@ impl ImageService {
             {
                 return Ok(existing);
             }
+            if let Some(reason) = self.unavailable.reason(iid, requested) {
+                return Err(MediaError::NotFound(format!(
+                    "Image {iid} at {imz:?} is unavailable: {reason}"
+                )));
+            }
         }
 
         if matches!(imz.image_variant(), ImageVariant::Thumbnail) {
@@ -982,10 +1365,33 @@ impl ImageService {
             {
                 return Ok(existing);
             }
+            if let Some(reason) = self.unavailable.reason(iid, requested) {
+                return Err(MediaError::NotFound(format!(
+                    "Image {iid} at {imz:?} is unavailable: {reason}"
+                )));
+            }
             // Leader may have failed; fall through and attempt ourselves.
         }
 
         let result = self.download_variant(iin).await;
+        if let Err(err) = &result
+            && is_permanent_image_failure(err)
+        {
+            warn!(
+                "[get_or_download_variant] Download failed permanently, not retrying for {:?}: iid={}, imz={:?}, err={}",
+                self.unavailable_ttl(),
+                iid,
+                imz,
+                err
+            );
+            self.unavailable.record(
+                iid,
+                requested,
+                Some(variant.media_id),
+                err.to_string(),
+                self.unavailable_ttl(),
+            );
+        }
         if is_leader {
             self.complete_variant(&vkey).await;
         }
@@ -1090,6 +1496,34 @@ impl ImageService {
         Ok(img.dimensions())
     }
 
+    /// Placeholder stored for `iid`, if any size of it has been cached.
+    pub async fn placeholder(&self, iid: Uuid) -> Result<Option<Vec<u8>>> {
+        self.images.image_placeholder(iid).await
+    }
+
+    /// Compute and store the placeholder for `iid` from freshly cached
+    /// bytes, unless the image already has one. Failures are logged and
+    /// never fail the surrounding cache write.
+    async fn ensure_placeholder(&self, iid: Uuid, data: &[u8]) {
+        match self.images.image_placeholder(iid).await {
+            Ok(Some(_)) => return,
+            Ok(None) => {}
+            Err(err) => {
+                warn!("Placeholder lookup failed for iid={}: {}", iid, err);
+                return;
+            }
+        }
+
+        let Some(placeholder) = encode_placeholder(data) else {
+            return;
+        };
+        if let Err(err) =
+            self.images.store_image_placeholder(iid, &placeholder).await
+        {
+            warn!("Failed to store placeholder for iid={}: {}", iid, err);
+        }
+    }
+
     /// Extract dominant color from image data
     pub fn extract_theme_color(&self, data: &[u8]) -> Option<String> {
         use image::{GenericImageView, Rgba};
@@ -1206,10 +1640,155 @@ impl ImageService {
         ))
     }
 
+    /// Forget every cached size of the images belonging to `media_id` so the
+    /// next request downloads them again, including sizes remembered as
+    /// unavailable. Blobs already handed out by token stay on disk; only the
+    /// cache index and database rows are dropped. Returns how many cached
+    /// sizes were removed.
+    pub async fn invalidate_all_variants(&self, media_id: Uuid) -> Result<u32> {
+        let cache_keys =
+            self.images.delete_cached_images_for_media(media_id).await?;
+        let forgotten = self.unavailable.clear_media(media_id);
+        if forgotten > 0 {
+            debug!(
+                "Cleared {} unavailable image sizes for media {}",
+                forgotten, media_id
+            );
+        }
+
+        for key in &cache_keys {
+            let key = ImageCacheKey::new(key.clone());
+            if let Err(err) = self.blob_store.remove(&key).await {
+                debug!(
+                    "Cache index entry {} already gone during invalidation: {}",
+                    key.as_str(),
+                    err
+                );
+            }
+        }
+
+        info!(
+            "Invalidated {} cached image sizes for media {}",
+            cache_keys.len(),
+            media_id
+        );
+        Ok(cache_keys.len() as u32)
+    }
+
     /// Clean up orphaned images
     pub async fn cleanup_orphaned(&self) -> Result<u32> {
         self.images.cleanup_orphaned_images().await
     }
+
+    /// Compute theme colors for movies or series imported before theme
+    /// color extraction existed.
+    ///
+    /// Only media whose primary poster is already cached are visited; the
+    /// rest keep relying on the lazy path in [`Self::download_variant`].
+    /// Nothing is downloaded. Work starts after `after` (by media id) and
+    /// stops once `max_items` candidates were visited; pass the returned
+    /// `next_cursor` back in to resume.
+    pub async fn backfill_theme_colors(
+        &self,
+        media_type: ImageMediaType,
+        after: Option<Uuid>,
+        max_items: Option<usize>,
+    ) -> Result<ThemeColorBackfillReport> {
+        const PAGE_SIZE: usize = 200;
+
+        let mut report = ThemeColorBackfillReport::default();
+        let mut cursor = after;
+        let budget = max_items.unwrap_or(usize::MAX);
+
+        while report.visited < budget {
+            let page = PAGE_SIZE.min(budget - report.visited);
+            let candidates = self
+                .images
+                .list_theme_color_candidates(media_type, cursor, page as i64)
+                .await?;
+            let exhausted = candidates.len() < page;
+
+            for candidate in candidates {
+                cursor = Some(candidate.media_id);
+                report.visited += 1;
+
+                let bytes = match self
+                    .blob_store
+                    .read(&candidate.cache_key)
+                    .await
+                {
+                    Ok(bytes) => bytes,
+                    Err(err) => {
+                        // Missing or corrupt blob: the serving path repairs it
+                        // and extracts the color on re-download.
+                        debug!(
+                            "Theme color backfill skipped {} (poster {}): {}",
+                            candidate.media_id, candidate.poster_iid, err
+                        );
+                        report.skipped += 1;
+                        continue;
+                    }
+                };
+
+                let service = self.clone();
+                let color = tokio::task::spawn_blocking(move || {
+                    service.extract_theme_color(&bytes)
+                })
+                .await
+                .ok()
+                .flatten();
+
+                match color {
+                    Some(color) => {
+                        if self
+                            .images
+                            .update_media_theme_color(
+                                media_type,
+                                candidate.media_id,
+                                &color,
+                            )
+                            .await?
+                        {
+                            report.computed += 1;
+                        }
+                    }
+                    None => report.no_color += 1,
+                }
+            }
+
+            if exhausted {
+                cursor = None;
+                break;
+            }
+        }
+
+        report.next_cursor = cursor;
+        info!(
+            "Theme color backfill for {:?}: visited={}, computed={}, skipped={}, no_color={}",
+            media_type,
+            report.visited,
+            report.computed,
+            report.skipped,
+            report.no_color
+        );
+        Ok(report)
+    }
+}
+
+/// Outcome of [`ImageService::backfill_theme_colors`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThemeColorBackfillReport {
+    /// Candidates examined in this run.
+    pub visited: usize,
+    /// Theme colors computed and stored.
+    pub computed: usize,
+    /// Candidates whose cached poster bytes could not be read.
+    pub skipped: usize,
+    /// Posters without a usable dominant color.
+    pub no_color: usize,
+    /// Resume point when the run stopped at `max_items`; `None` once every
+    /// candidate has been visited.
+    pub next_cursor: Option<Uuid>,
 }
 
 fn is_retryable_cache_fill_error(err: &MediaError) -> bool {
@@ -1232,133 +1811,349 @@ pub enum CachePolicy {
     Refresh,
 }
 
+/// What [`ImageService::enqueue_cache`] did with a fill request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[must_use]
+pub enum CacheFillOutcome {
+    /// Queued for a cache-fill worker.
+    Enqueued,
+    /// A fill for the same variant is already queued or running.
+    Deduplicated,
+    /// Not queued. If the queue was full a bounded background retry may
+    /// still queue it, but callers should not count on that.
+    Dropped,
+    /// Not queued: an earlier download of this size failed permanently.
+    /// See [`ImageService::unavailable_reason`].
+    Unavailable,
+}
+
+/// A cached, materialized variant picked by
+/// [`ImageService::pick_best_available`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvailableImage {
+    pub imz: ImageSize,
+    pub token: String,
+    pub byte_len: u64,
+}
+
+/// Standard TMDB sizes of the same variant narrower than `imz`, widest
+/// first. An original without a known width counts as the widest size.
+fn smaller_standard_sizes(imz: ImageSize) -> Vec<ImageSize> {
+    let max = imz.width().unwrap_or(u32::MAX);
+    let mut sizes: Vec<ImageSize> = match imz {
+        ImageSize::Poster(_) => {
+            PosterSize::ALL.into_iter().map(ImageSize::Poster).collect()
+        }
+        ImageSize::Backdrop(_) => BackdropSize::ALL
+            .into_iter()
+            .map(ImageSize::Backdrop)
+            .collect(),
+        ImageSize::Thumbnail(_) => EpisodeSize::ALL
+            .into_iter()
+            .map(ImageSize::Thumbnail)
+            .collect(),
+        ImageSize::Profile(_) => ProfileSize::ALL
+            .into_iter()
+            .map(ImageSize::Profile)
+            .collect(),
+    };
+    sizes.retain(|size| size.width().is_some_and(|w| w < max));
+    sizes.sort_by_key(|size| std::cmp::Reverse(size.width()));
+    sizes
+}
+
+/// Exponential backoff starting at 50ms with up to 100% random jitter, so
+/// requests dropped together do not all retry in the same instant.
+fn enqueue_retry_delay(attempt: usize) -> Duration {
+    use rand::Rng;
+
+    let base_ms = 50u64 << attempt.saturating_sub(1).min(6);
+    let jitter_ms = rand::rng().random_range(0..=base_ms);
+    Duration::from_millis(base_ms + jitter_ms)
+}
+
+/// JPEG quality of episode thumbnails unless configured otherwise.
+const DEFAULT_THUMBNAIL_QUALITY: u8 = 85;
+
+/// `AVFormatContext::duration` and seek targets are in `AV_TIME_BASE`
+/// units (microseconds).
 #[cfg(feature = "ffmpeg")]
-fn extract_frame_at_percentage(
+const AV_TIME_BASE: f64 = 1_000_000.0;
+
+/// Decode one RGB24 frame for an episode thumbnail using `strategy`.
+///
+/// `BestFrame` decodes at most `MAX_BEST_FRAME_CANDIDATES` frames and falls
+/// back to the default percentage when no candidate can be decoded.
+#[cfg(feature = "ffmpeg")]
+fn extract_thumbnail_frame(
     input_path: &str,
-    percentage: f64,
+    strategy: ThumbnailStrategy,
 ) -> Result<(u32, u32, Vec<u8>)> {
-    use ffmpeg::codec::context::Context as CodecContext;
+    let fallback = || {
+        extract_frame_at_fraction(
+            input_path,
+            ThumbnailStrategy::DEFAULT_FRACTION,
+        )
+    };
+    match strategy {
+        ThumbnailStrategy::Percentage { fraction } => {
+            extract_frame_at_fraction(input_path, fraction)
+        }
+        ThumbnailStrategy::Timestamp { seconds } => {
+            let mut video = VideoFrameSource::open(input_path)?;
+            let target = (seconds * AV_TIME_BASE) as i64;
+            let target = match video.duration() {
+                Some(duration) => target.min(duration.saturating_sub(1)),
+                None => target,
+            };
+            video.frame_at(Some(target))
+        }
+        ThumbnailStrategy::BestFrame { candidates } => {
+            match best_frame(input_path, candidates) {
+                Ok(Some(frame)) => Ok(frame),
+                Ok(None) => {
+                    warn!(
+                        "No thumbnail candidates decoded for {}; using percentage strategy",
+                        input_path
+                    );
+                    fallback()
+                }
+                Err(err) => {
+                    warn!(
+                        "Best-frame thumbnail selection failed for {}: {}; using percentage strategy",
+                        input_path, err
+                    );
+                    fallback()
+                }
+            }
+        }
+    }
+}
 
-    let mut input_ctx = ffmpeg::format::input(&input_path).map_err(|e| {
-        MediaError::InvalidMedia(format!("Failed to open video file: {e}"))
-    })?;
+#[cfg(feature = "ffmpeg")]
+fn extract_frame_at_fraction(
+    input_path: &str,
+    fraction: f64,
+) -> Result<(u32, u32, Vec<u8>)> {
+    let mut video = VideoFrameSource::open(input_path)?;
+    let target = video
+        .duration()
+        .filter(|_| fraction > 0.0)
+        .map(|duration| (duration as f64 * fraction) as i64);
+    video.frame_at(target)
+}
 
-    let video_stream = input_ctx
-        .streams()
-        .best(ffmpeg::media::Type::Video)
-        .ok_or_else(|| {
-            MediaError::InvalidMedia("No video stream found".into())
+#[cfg(feature = "ffmpeg")]
+fn best_frame(
+    input_path: &str,
+    candidates: u8,
+) -> Result<Option<(u32, u32, Vec<u8>)>> {
+    let mut video = VideoFrameSource::open(input_path)?;
+    let Some(duration) = video.duration() else {
+        return Ok(None);
+    };
+
+    let mut best: Option<(f64, (u32, u32, Vec<u8>))> = None;
+    for fraction in ThumbnailStrategy::candidate_fractions(candidates) {
+        let target = (duration as f64 * fraction) as i64;
+        let frame = match video.frame_at(Some(target)) {
+            Ok(frame) => frame,
+            Err(err) => {
+                debug!(
+                    "Skipping thumbnail candidate at {:.0}%: {err}",
+                    fraction * 100.0
+                );
+                continue;
+            }
+        };
+        let score = frame_score(&frame.2);
+        if best
+            .as_ref()
+            .is_none_or(|(best_score, _)| score > *best_score)
+        {
+            best = Some((score, frame));
+        }
+    }
+    Ok(best.map(|(_, frame)| frame))
+}
+
+/// An opened video stream that can be repeatedly seeked and decoded.
+#[cfg(feature = "ffmpeg")]
+struct VideoFrameSource {
+    input_ctx: ffmpeg::format::context::Input,
+    decoder: ffmpeg::decoder::Video,
+    stream_index: usize,
+}
+
+#[cfg(feature = "ffmpeg")]
+impl VideoFrameSource {
+    fn open(input_path: &str) -> Result<Self> {
+        use ffmpeg::codec::context::Context as CodecContext;
+
+        let input_ctx = ffmpeg::format::input(&input_path).map_err(|e| {
+            MediaError::InvalidMedia(format!("Failed to open video file: {e}"))
         })?;
 
-    let video_stream_index = video_stream.index();
-    let codec_params = video_stream.parameters();
+        let video_stream = input_ctx
+            .streams()
+            .best(ffmpeg::media::Type::Video)
+            .ok_or_else(|| {
+                MediaError::InvalidMedia("No video stream found".into())
+            })?;
+
+        let stream_index = video_stream.index();
+        let codec_params = video_stream.parameters();
 
-    let codec_ctx =
-        CodecContext::from_parameters(codec_params).map_err(|e| {
+        let codec_ctx =
+            CodecContext::from_parameters(codec_params).map_err(|e| {
+                MediaError::InvalidMedia(format!(
+                    "Failed to create codec context: {e}"
+                ))
+            })?;
+        let decoder = codec_ctx.decoder().video().map_err(|e| {
             MediaError::InvalidMedia(format!(
-                "Failed to create codec context: {e}"
+                "Failed to create video decoder: {e}"
             ))
         })?;
-    let mut decoder = codec_ctx.decoder().video().map_err(|e| {
-        MediaError::InvalidMedia(format!("Failed to create video decoder: {e}"))
-    })?;
 
-    let duration = input_ctx.duration();
-    if duration > 0 && percentage > 0.0 {
-        let target_position = (duration as f64 * percentage) as i64;
-        input_ctx.seek(target_position, ..).map_err(|e| {
-            MediaError::InvalidMedia(format!("Failed to seek: {e}"))
-        })?;
+        Ok(Self {
+            input_ctx,
+            decoder,
+            stream_index,
+        })
     }
 
-    let mut received_frame = None;
-    for (stream, packet) in input_ctx.packets() {
-        if stream.index() != video_stream_index {
-            continue;
-        }
+    /// Container duration in `AV_TIME_BASE` units, when known.
+    fn duration(&self) -> Option<i64> {
+        let duration = self.input_ctx.duration();
+        (duration > 0).then_some(duration)
+    }
 
-        decoder.send_packet(&packet).map_err(|e| {
-            MediaError::InvalidMedia(format!("Failed to send packet: {e}"))
-        })?;
+    /// Decode the first frame at or after `target` (from the start when
+    /// `None`) and convert it to tightly packed RGB24.
+    fn frame_at(&mut self, target: Option<i64>) -> Result<(u32, u32, Vec<u8>)> {
+        if let Some(target) = target {
+            self.input_ctx.seek(target, ..).map_err(|e| {
+                MediaError::InvalidMedia(format!("Failed to seek: {e}"))
+            })?;
+            self.decoder.flush();
+        }
 
-        let mut frame = ffmpeg::frame::Video::empty();
-        match decoder.receive_frame(&mut frame) {
-            Ok(_) => {
-                received_frame = Some(frame);
-                break;
-            }
-            Err(err) => {
-                debug!("Skipping packet during thumbnail extraction: {err}");
+        let mut received_frame = None;
+        for (stream, packet) in self.input_ctx.packets() {
+            if stream.index() != self.stream_index {
                 continue;
             }
-        }
-    }
 
-    let frame = received_frame.ok_or_else(|| {
-        MediaError::InvalidMedia(
-            "Unable to decode frame for thumbnail generation".into(),
-        )
-    })?;
+            self.decoder.send_packet(&packet).map_err(|e| {
+                MediaError::InvalidMedia(format!("Failed to send packet: {e}"))
+            })?;
 
-    let mut scaler = ffmpeg::software::scaling::Context::get(
-        decoder.format(),
-        decoder.width(),
-        decoder.height(),
-        ffmpeg::format::Pixel::RGB24,
-        decoder.width(),
-        decoder.height(),
-        ffmpeg::software::scaling::flag::Flags::BILINEAR,
-    )
-    .map_err(|e| {
-        MediaError::InvalidMedia(format!("Failed to create scaler: {e}"))
-    })?;
+            let mut frame = ffmpeg::frame::Video::empty();
+            match self.decoder.receive_frame(&mut frame) {
+                Ok(_) => {
+                    received_frame = Some(frame);
+                    break;
+                }
+                Err(err) => {
+                    debug!(
+                        "Skipping packet during thumbnail extraction: {err}"
+                    );
+                    continue;
+                }
+            }
+        }
 
-    let mut rgb_frame = ffmpeg::frame::Video::empty();
-    scaler.run(&frame, &mut rgb_frame).map_err(|e| {
-        MediaError::InvalidMedia(format!("Failed to scale frame: {e}"))
-    })?;
+        let frame = received_frame.ok_or_else(|| {
+            MediaError::InvalidMedia(
+                "Unable to decode frame for thumbnail generation".into(),
+            )
+        })?;
 
-    let width = rgb_frame.width();
-    let height = rgb_frame.height();
-    let data = rgb_frame.data(0);
-    let stride = rgb_frame.stride(0);
+        let decoder = &self.decoder;
+        let mut scaler = ffmpeg::software::scaling::Context::get(
+            decoder.format(),
+            decoder.width(),
+            decoder.height(),
+            ffmpeg::format::Pixel::RGB24,
+            decoder.width(),
+            decoder.height(),
+            ffmpeg::software::scaling::flag::Flags::BILINEAR,
+        )
+        .map_err(|e| {
+            MediaError::InvalidMedia(format!("Failed to create scaler: {e}"))
+        })?;
 
-    let row_len = (width as usize).checked_mul(3).ok_or_else(|| {
-        MediaError::InvalidMedia("RGB row length overflow".into())
-    })?;
-    let expected = (height as usize).checked_mul(row_len).ok_or_else(|| {
-        MediaError::InvalidMedia("RGB buffer length overflow".into())
-    })?;
+        let mut rgb_frame = ffmpeg::frame::Video::empty();
+        scaler.run(&frame, &mut rgb_frame).map_err(|e| {
+            MediaError::InvalidMedia(format!("Failed to scale frame: {e}"))
+        })?;
 
-    let mut rgb = vec![0u8; expected];
-    for y in 0..height as usize {
-        let src_off = y * stride;
-        let dst_off = y * row_len;
+        let width = rgb_frame.width();
+        let height = rgb_frame.height();
+        let data = rgb_frame.data(0);
+        let stride = rgb_frame.stride(0);
 
-        let src = data.get(src_off..src_off + row_len).ok_or_else(|| {
-            MediaError::InvalidMedia(
-                "FFmpeg RGB frame buffer shorter than expected".into(),
-            )
+        let row_len = (width as usize).checked_mul(3).ok_or_else(|| {
+            MediaError::InvalidMedia("RGB row length overflow".into())
         })?;
-        rgb[dst_off..dst_off + row_len].copy_from_slice(src);
-    }
+        let expected =
+            (height as usize).checked_mul(row_len).ok_or_else(|| {
+                MediaError::InvalidMedia("RGB buffer length overflow".into())
+            })?;
+
+        let mut rgb = vec![0u8; expected];
+        for y in 0..height as usize {
+            let src_off = y * stride;
+            let dst_off = y * row_len;
+
+            let src =
+                data.get(src_off..src_off + row_len).ok_or_else(|| {
+                    MediaError::InvalidMedia(
+                        "FFmpeg RGB frame buffer shorter than expected".into(),
+                    )
+                })?;
+            rgb[dst_off..dst_off + row_len].copy_from_slice(src);
+        }
 
-    Ok((width, height, rgb))
-    // // Encode as JPEG and write atomically: write to temp file, fsync, then rename
-    // atomic_write_jpeg_rgb8(output_path, width, height, buffer.into_raw())
-    //     .map_err(|e| MediaError::Io(std::io::Error::other(e)))
+        Ok((width, height, rgb))
+    }
 }
 
 #[cfg(not(feature = "ffmpeg"))]
-fn extract_frame_at_percentage(
+fn extract_thumbnail_frame(
     _input_path: &str,
-    _percentage: f64,
+    _strategy: ThumbnailStrategy,
 ) -> Result<(u32, u32, Vec<u8>)> {
     Err(MediaError::Internal(
         "FFmpeg support is required for thumbnail generation".into(),
     ))
 }
 
+/// Higher for brighter, more detailed frames: mean luma plus its standard
+/// deviation, sampled on every 16th pixel. Black and flat fade frames
+/// score near zero.
+#[cfg_attr(not(feature = "ffmpeg"), allow(dead_code))]
+fn frame_score(rgb: &[u8]) -> f64 {
+    let mut count = 0f64;
+    let mut sum = 0f64;
+    let mut sum_sq = 0f64;
+    for pixel in rgb.chunks_exact(3).step_by(16) {
+        let luma = 0.299 * f64::from(pixel[0])
+            + 0.587 * f64::from(pixel[1])
+            + 0.114 * f64::from(pixel[2]);
+        count += 1.0;
+        sum += luma;
+        sum_sq += luma * luma;
+    }
+    if count == 0.0 {
+        return 0.0;
+    }
+    let mean = sum / count;
+    let variance = (sum_sq / count - mean * mean).max(0.0);
+    mean + variance.sqrt()
+}
+
 fn encode_thumbnail_jpeg_rgb24(
     src_w: u32,
     src_h: u32,
@@ -1446,7 +2241,55 @@ fn encode_thumbnail_jpeg_rgb24(
 
 #[cfg(test)]
 mod tests {
-    use super::encode_thumbnail_jpeg_rgb24;
+    use super::{
+        BackdropSize, ImageSize, PosterSize, encode_thumbnail_jpeg_rgb24,
+        enqueue_retry_delay, frame_score, smaller_standard_sizes,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn fallback_sizes_are_smaller_and_widest_first() {
+        assert_eq!(
+            smaller_standard_sizes(ImageSize::Poster(PosterSize::W342)),
+            vec![
+                ImageSize::Poster(PosterSize::W185),
+                ImageSize::Poster(PosterSize::W154),
+                ImageSize::Poster(PosterSize::W92),
+            ]
+        );
+        assert_eq!(
+            smaller_standard_sizes(ImageSize::Backdrop(
+                BackdropSize::Original(None)
+            ))
+            .first(),
+            Some(&ImageSize::Backdrop(BackdropSize::W1280))
+        );
+        assert!(
+            smaller_standard_sizes(ImageSize::Poster(PosterSize::W92))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn frame_score_prefers_bright_detailed_frames() {
+        let black = vec![0u8; 64 * 64 * 3];
+        let flat_grey = vec![128u8; 64 * 64 * 3];
+        let detailed: Vec<u8> =
+            (0..64 * 64 * 3).map(|i| (i * 7 % 256) as u8).collect();
+
+        assert_eq!(frame_score(&black), 0.0);
+        assert!(frame_score(&flat_grey) > frame_score(&black));
+        assert!(frame_score(&detailed) > frame_score(&flat_grey));
+    }
+
+    #[test]
+    fn enqueue_retry_delay_grows_within_jitter_bounds() {
+        for attempt in 1..=4 {
+            let base = Duration::from_millis(50 << (attempt - 1));
+            let delay = enqueue_retry_delay(attempt);
+            assert!(delay >= base && delay <= base * 2, "{delay:?}");
+        }
+    }
 
     #[test]
     fn rgb24_thumbnail_encoder_produces_valid_jpeg_with_expected_dimensions() {
@@ -0,0 +1,337 @@
+//! Image blobs left on disk without a `cached_images` row.
+//!
+//! A download writes the `cacache` entry and the token-addressed file before
+//! it records the size in `cached_images`, so a crash in between leaves
+//! blobs that nothing points at. These are found by diffing both stores
+//! against the database. Blobs of images that are being downloaded right
+//! now, and blobs younger than [`ORPHAN_MIN_AGE`], are never reported: the
+//! database row of a fresh download may simply not be written yet.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime},
+};
+
+use cacache::Integrity;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::ImageService;
+use crate::{
+    database::repository_ports::images::CachedImageRef,
+    error::Result,
+    infra::cache::{
+        ImageBlobIndexEntry, ImageCacheKey, ImageFileStore, StoredFileBlob,
+    },
+};
+
+/// Blobs written more recently than this are never treated as orphans.
+pub const ORPHAN_MIN_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// Outcome of [`ImageService::find_orphaned_blobs`] and
+/// [`ImageService::purge_orphaned_blobs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrphanedImageBlobsReport {
+    /// Blob files without a database reference, across both stores.
+    pub orphaned_blobs: usize,
+    /// Total size of `orphaned_blobs`.
+    pub orphaned_bytes: u64,
+    /// `cacache` index entries whose key has no `cached_images` row.
+    pub stale_index_entries: usize,
+    /// Candidates left alone because their image is being downloaded or
+    /// they were written within [`ORPHAN_MIN_AGE`].
+    pub skipped_in_flight: usize,
+    /// Blobs deleted; always zero when only listing.
+    pub removed_blobs: usize,
+    /// Bytes freed by `removed_blobs`.
+    pub reclaimed_bytes: u64,
+    /// Blobs or index entries that could not be deleted.
+    pub failed: usize,
+}
+
+/// What a purge would delete.
+#[derive(Debug, Default)]
+struct OrphanPlan {
+    stale_entries: Vec<String>,
+    content: Vec<(Integrity, u64)>,
+    files: Vec<(String, u64)>,
+    skipped: usize,
+}
+
+impl OrphanPlan {
+    fn report(&self) -> OrphanedImageBlobsReport {
+        OrphanedImageBlobsReport {
+            orphaned_blobs: self.content.len() + self.files.len(),
+            orphaned_bytes: self
+                .content
+                .iter()
+                .map(|(_, bytes)| bytes)
+                .chain(self.files.iter().map(|(_, bytes)| bytes))
+                .sum(),
+            stale_index_entries: self.stale_entries.len(),
+            skipped_in_flight: self.skipped,
+            ..Default::default()
+        }
+    }
+}
+
+impl ImageService {
+    /// Count the image blobs on disk that no database row references.
+    pub async fn find_orphaned_blobs(
+        &self,
+    ) -> Result<OrphanedImageBlobsReport> {
+        Ok(self.plan_orphaned_blobs().await?.report())
+    }
+
+    /// Delete the image blobs on disk that no database row references and
+    /// report how much space was reclaimed.
+    pub async fn purge_orphaned_blobs(
+        &self,
+    ) -> Result<OrphanedImageBlobsReport> {
+        let plan = self.plan_orphaned_blobs().await?;
+        let mut report = plan.report();
+
+        for key in plan.stale_entries {
+            let key = ImageCacheKey::new(key);
+            if let Err(err) = self.blob_store.remove(&key).await {
+                warn!("Failed to remove stale image index entry {key}: {err}");
+                report.failed += 1;
+            }
+        }
+        for (hash, bytes) in plan.content {
+            match self.blob_store.remove_content(&hash).await {
+                Ok(()) => {
+                    report.removed_blobs += 1;
+                    report.reclaimed_bytes += bytes;
+                }
+                Err(err) => {
+                    warn!("Failed to remove orphaned image blob {hash}: {err}");
+                    report.failed += 1;
+                }
+            }
+        }
+        for (token, bytes) in plan.files {
+            match self.file_store.remove(&token).await {
+                Ok(()) => {
+                    report.removed_blobs += 1;
+                    report.reclaimed_bytes += bytes;
+                }
+                Err(err) => {
+                    warn!(
+                        "Failed to remove orphaned image file {token}: {err}"
+                    );
+                    report.failed += 1;
+                }
+            }
+        }
+
+        info!(
+            "Purged {} orphaned image blobs ({} bytes), {} failed",
+            report.removed_blobs, report.reclaimed_bytes, report.failed
+        );
+        Ok(report)
+    }
+
+    async fn plan_orphaned_blobs(&self) -> Result<OrphanPlan> {
+        // Read the database first: anything written after this snapshot is
+        // younger than the cutoff and therefore kept.
+        let refs = self.images.list_cached_image_refs().await?;
+        let cutoff = SystemTime::now()
+            .checked_sub(ORPHAN_MIN_AGE)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let entries = self.blob_store.list_entries().await?;
+        let files = self.file_store.list_blobs().await?;
+        let in_flight = self.in_flight_image_ids().await;
+
+        Ok(plan_orphans(&refs, &entries, &files, &in_flight, cutoff))
+    }
+
+    /// Images with a singleflight download or a queued cache fill.
+    async fn in_flight_image_ids(&self) -> HashSet<Uuid> {
+        let mut ids: HashSet<Uuid> = self
+            .in_flight_variants
+            .lock()
+            .await
+            .keys()
+            .filter_map(|key| image_id_of_flight_key(key))
+            .collect();
+        if let Ok(fills) = self.in_flight.lock() {
+            ids.extend(fills.iter().filter_map(|key| {
+                image_id_of_flight_key(key.trim_start_matches("fill:"))
+            }));
+        }
+        ids
+    }
+}
+
+/// The image id leading a singleflight (`<iid>:<size>`) key.
+fn image_id_of_flight_key(key: &str) -> Option<Uuid> {
+    key.split(':').next()?.parse().ok()
+}
+
+/// The image id in an `images/v2/iid/<iid>/...` cache key.
+fn image_id_of_cache_key(key: &str) -> Option<Uuid> {
+    key.strip_prefix("images/v2/iid/")?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn plan_orphans(
+    refs: &[CachedImageRef],
+    entries: &[ImageBlobIndexEntry],
+    files: &[StoredFileBlob],
+    in_flight: &HashSet<Uuid>,
+    cutoff: SystemTime,
+) -> OrphanPlan {
+    let referenced_keys: HashSet<&str> =
+        refs.iter().map(|r| r.cache_key.as_str()).collect();
+    // Integrity strings whose content must stay.
+    let mut kept: HashSet<String> =
+        refs.iter().map(|r| r.integrity.clone()).collect();
+    // Integrity strings kept only because their download is not finished.
+    let mut protected: HashSet<String> = HashSet::new();
+
+    let mut plan = OrphanPlan::default();
+    let mut stale = Vec::new();
+    for entry in entries {
+        if referenced_keys.contains(entry.key.as_str()) {
+            kept.insert(entry.integrity.to_string());
+            continue;
+        }
+        let downloading = image_id_of_cache_key(&entry.key)
+            .is_some_and(|iid| in_flight.contains(&iid));
+        if downloading || entry.written_at > cutoff {
+            plan.skipped += 1;
+            kept.insert(entry.integrity.to_string());
+            protected.insert(entry.integrity.to_string());
+            continue;
+        }
+        stale.push(entry);
+    }
+
+    let mut content: HashMap<String, (Integrity, u64)> = HashMap::new();
+    for entry in &stale {
+        plan.stale_entries.push(entry.key.clone());
+        let integrity = entry.integrity.to_string();
+        if !kept.contains(&integrity) {
+            content.entry(integrity).or_insert_with(|| {
+                (entry.integrity.clone(), entry.byte_len as u64)
+            });
+        }
+    }
+    plan.content = content.into_values().collect();
+
+    let kept_tokens: HashSet<String> = kept
+        .iter()
+        .map(|integrity| ImageFileStore::token_from_integrity(integrity))
+        .collect();
+    let protected_tokens: HashSet<String> = protected
+        .iter()
+        .map(|integrity| ImageFileStore::token_from_integrity(integrity))
+        .collect();
+    for file in files {
+        if protected_tokens.contains(&file.token) || file.modified_at > cutoff {
+            plan.skipped += 1;
+        } else if !kept_tokens.contains(&file.token) {
+            plan.files.push((file.token.clone(), file.byte_len));
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IID: &str = "01234567-89ab-cdef-0123-456789abcdef";
+
+    fn entry(key: &str, bytes: &[u8], age: Duration) -> ImageBlobIndexEntry {
+        ImageBlobIndexEntry {
+            key: key.to_string(),
+            integrity: Integrity::from(bytes),
+            byte_len: bytes.len(),
+            written_at: SystemTime::now() - age,
+        }
+    }
+
+    fn file(bytes: &[u8], age: Duration) -> StoredFileBlob {
+        StoredFileBlob {
+            token: ImageFileStore::token_from_integrity(
+                &Integrity::from(bytes).to_string(),
+            ),
+            byte_len: bytes.len() as u64,
+            modified_at: SystemTime::now() - age,
+        }
+    }
+
+    fn cutoff() -> SystemTime {
+        SystemTime::now() - ORPHAN_MIN_AGE
+    }
+
+    #[test]
+    fn unreferenced_blobs_in_both_stores_are_orphans() {
+        let old = Duration::from_secs(3600);
+        let kept_key = format!("images/v2/iid/{IID}/poster/w185");
+        let refs = vec![CachedImageRef {
+            cache_key: kept_key.clone(),
+            integrity: Integrity::from(b"kept").to_string(),
+        }];
+        let entries = vec![
+            entry(&kept_key, b"kept", old),
+            entry("images/v2/iid/x/poster/w342", b"lost", old),
+        ];
+        let files = vec![file(b"kept", old), file(b"stray file", old)];
+
+        let plan =
+            plan_orphans(&refs, &entries, &files, &HashSet::new(), cutoff());
+        let report = plan.report();
+        assert_eq!(report.stale_index_entries, 1);
+        assert_eq!(report.orphaned_blobs, 2);
+        assert_eq!(report.orphaned_bytes, (b"lost".len() + 10) as u64);
+        assert_eq!(report.skipped_in_flight, 0);
+    }
+
+    #[test]
+    fn shared_content_is_kept_for_the_referenced_key() {
+        let old = Duration::from_secs(3600);
+        let kept_key = format!("images/v2/iid/{IID}/poster/w185");
+        let refs = vec![CachedImageRef {
+            cache_key: kept_key.clone(),
+            integrity: Integrity::from(b"same").to_string(),
+        }];
+        let entries = vec![
+            entry(&kept_key, b"same", old),
+            entry("images/v2/iid/x/poster/w92", b"same", old),
+        ];
+
+        let plan =
+            plan_orphans(&refs, &entries, &[], &HashSet::new(), cutoff());
+        assert_eq!(plan.stale_entries.len(), 1);
+        assert!(plan.content.is_empty());
+    }
+
+    #[test]
+    fn downloads_in_flight_and_fresh_blobs_are_skipped() {
+        let old = Duration::from_secs(3600);
+        let downloading = format!("images/v2/iid/{IID}/backdrop/w780");
+        let entries = vec![
+            entry(&downloading, b"partial", old),
+            entry("images/v2/iid/x/poster/w92", b"new", Duration::ZERO),
+        ];
+        let files = vec![file(b"partial", old), file(b"fresh", Duration::ZERO)];
+        let in_flight =
+            HashSet::from([
+                image_id_of_flight_key(&format!("{IID}:w780")).unwrap()
+            ]);
+
+        let report =
+            plan_orphans(&[], &entries, &files, &in_flight, cutoff()).report();
+        assert_eq!(report.orphaned_blobs, 0);
+        assert_eq!(report.stale_index_entries, 0);
+        assert_eq!(report.skipped_in_flight, 4);
+    }
+}
@@ -0,0 +1,75 @@
+//! Tiny blurred placeholders for images that are still being cached.
+//!
+//! A placeholder is a 4x4 PNG of the image's average colors (well under
+//! 200 bytes). It is computed once per image from whichever size is cached
+//! first and stored on the image row, so clients can paint a blur while the
+//! size they asked for is still downloading.
+
+use std::io::Cursor;
+
+use image::{ImageFormat, imageops::FilterType};
+use tracing::warn;
+
+/// Width and height of a placeholder in pixels.
+pub const PLACEHOLDER_EDGE: u32 = 4;
+
+/// Media type of placeholder bytes.
+pub const PLACEHOLDER_CONTENT_TYPE: &str = "image/png";
+
+/// Downscale encoded image bytes to a [`PLACEHOLDER_EDGE`]-square PNG.
+/// Returns `None` when the bytes cannot be decoded.
+pub fn encode_placeholder(data: &[u8]) -> Option<Vec<u8>> {
+    let img = match image::load_from_memory(data) {
+        Ok(img) => img,
+        Err(err) => {
+            warn!("Failed to decode image for placeholder: {}", err);
+            return None;
+        }
+    };
+
+    // Triangle filtering over the whole image averages each cell instead of
+    // sampling a single pixel from it.
+    let small = img
+        .resize_exact(PLACEHOLDER_EDGE, PLACEHOLDER_EDGE, FilterType::Triangle)
+        .to_rgb8();
+
+    let mut out = Cursor::new(Vec::with_capacity(128));
+    if let Err(err) = small.write_to(&mut out, ImageFormat::Png) {
+        warn!("Failed to encode image placeholder: {}", err);
+        return None;
+    }
+    Some(out.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+
+    #[test]
+    fn placeholder_is_a_tiny_png_of_average_colors() {
+        let source = ImageBuffer::from_fn(64, 96, |x, _| {
+            if x < 32 {
+                Rgb([200u8, 0, 0])
+            } else {
+                Rgb([0u8, 0, 200])
+            }
+        });
+        let mut encoded = Cursor::new(Vec::new());
+        source.write_to(&mut encoded, ImageFormat::Png).unwrap();
+
+        let placeholder =
+            encode_placeholder(encoded.get_ref()).expect("placeholder");
+        assert!(placeholder.len() < 200);
+
+        let decoded = image::load_from_memory(&placeholder).unwrap().to_rgb8();
+        assert_eq!(decoded.dimensions(), (PLACEHOLDER_EDGE, PLACEHOLDER_EDGE));
+        assert!(decoded.get_pixel(0, 0)[0] > decoded.get_pixel(0, 0)[2]);
+        assert!(decoded.get_pixel(3, 3)[2] > decoded.get_pixel(3, 3)[0]);
+    }
+
+    #[test]
+    fn undecodable_bytes_have_no_placeholder() {
+        assert!(encode_placeholder(b"not an image").is_none());
+    }
+}
@@ -0,0 +1,130 @@
+//! Where the bytes behind a stored image path come from.
+//!
+//! Image rows keep the path their metadata provider handed out (the
+//! `tmdb_path` column). TMDB paths look like `/abc.jpg` and are served from
+//! its CDN at the requested width; other providers register an
+//! [`ImageSource`] that recognizes their own paths. Sources are consulted
+//! in registration order and the TMDB source comes last, so registered
+//! sources can claim any path shape.
+
+use std::path::PathBuf;
+
+use ferrex_model::ImageSize;
+
+/// Location of the bytes for one image at one size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageLocation {
+    /// Fetched over HTTP(S).
+    Url(String),
+    /// Read from a local file, e.g. artwork next to the media.
+    File(PathBuf),
+}
+
+/// Resolves stored image paths of one provider to a location.
+pub trait ImageSource: Send + Sync {
+    /// Short stable name for logs (`tmdb`, `nfo`).
+    fn name(&self) -> &'static str;
+
+    /// Location of `path` at `size`, or `None` when `path` is not one of
+    /// this source's.
+    fn locate(&self, path: &str, size: ImageSize) -> Option<ImageLocation>;
+}
+
+/// TMDB's image CDN; claims the `/`-rooted paths TMDB returns.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TmdbImageSource;
+
+impl ImageSource for TmdbImageSource {
+    fn name(&self) -> &'static str {
+        "tmdb"
+    }
+
+    fn locate(&self, path: &str, size: ImageSize) -> Option<ImageLocation> {
+        path.starts_with('/').then(|| {
+            ImageLocation::Url(format!(
+                "https://image.tmdb.org/t/p/{}{}",
+                size.to_tmdb_param(),
+                path
+            ))
+        })
+    }
+}
+
+/// Absolute `http(s)://` URLs, fetched as-is at every size.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectUrlImageSource;
+
+impl ImageSource for DirectUrlImageSource {
+    fn name(&self) -> &'static str {
+        "url"
+    }
+
+    fn locate(&self, path: &str, _size: ImageSize) -> Option<ImageLocation> {
+        (path.starts_with("https://") || path.starts_with("http://"))
+            .then(|| ImageLocation::Url(path.to_string()))
+    }
+}
+
+/// Artwork stored next to the media, recorded as `file://` paths by
+/// providers that import local images. The file is the same at every size;
+/// the variant pipeline resizes it like any other original.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalFileImageSource;
+
+impl LocalFileImageSource {
+    pub const SCHEME: &'static str = "file://";
+
+    /// Stored path for the local file at `path`.
+    pub fn path_for(path: &std::path::Path) -> String {
+        format!("{}{}", Self::SCHEME, path.display())
+    }
+}
+
+impl ImageSource for LocalFileImageSource {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn locate(&self, path: &str, _size: ImageSize) -> Option<ImageLocation> {
+        path.strip_prefix(Self::SCHEME)
+            .filter(|file| file.starts_with('/'))
+            .map(|file| ImageLocation::File(PathBuf::from(file)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrex_model::PosterSize;
+
+    #[test]
+    fn sources_claim_only_their_own_paths() {
+        let size = ImageSize::Poster(PosterSize::W342);
+
+        assert_eq!(
+            TmdbImageSource.locate("/abc.jpg", size),
+            Some(ImageLocation::Url(format!(
+                "https://image.tmdb.org/t/p/{}/abc.jpg",
+                size.to_tmdb_param()
+            )))
+        );
+        assert_eq!(
+            TmdbImageSource.locate("https://cdn.example/a.jpg", size),
+            None
+        );
+        assert_eq!(
+            DirectUrlImageSource.locate("https://cdn.example/a.jpg", size),
+            Some(ImageLocation::Url("https://cdn.example/a.jpg".into()))
+        );
+        assert_eq!(DirectUrlImageSource.locate("/abc.jpg", size), None);
+
+        let poster = std::path::Path::new("/m/Heat (1995)/poster.jpg");
+        let stored = LocalFileImageSource::path_for(poster);
+        assert_eq!(
+            LocalFileImageSource.locate(&stored, size),
+            Some(ImageLocation::File(poster.to_path_buf()))
+        );
+        assert_eq!(TmdbImageSource.locate(&stored, size), None);
+        assert_eq!(LocalFileImageSource.locate("/abc.jpg", size), None);
+    }
+}
@@ -0,0 +1,171 @@
+//! Negative cache of image sizes whose download failed permanently.
+//!
+//! A poster path TMDB answers with 404 fails the same way every time, so
+//! the failure is remembered for a while and requests for that size are
+//! answered "unavailable" instead of queueing the download again.
+//! Transient failures are never recorded.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use ferrex_model::ImageSize;
+use uuid::Uuid;
+
+use crate::error::MediaError;
+
+/// How long a permanent failure is remembered when nothing else is set.
+pub const DEFAULT_UNAVAILABLE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug)]
+struct UnavailableEntry {
+    media_id: Option<Uuid>,
+    reason: String,
+    expires_at: Instant,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct UnavailableImages {
+    entries: Mutex<HashMap<(Uuid, ImageSize), UnavailableEntry>>,
+}
+
+impl UnavailableImages {
+    /// Remember that `iid` at `imz` cannot be fetched for `ttl`. A zero
+    /// TTL records nothing.
+    pub(super) fn record(
+        &self,
+        iid: Uuid,
+        imz: ImageSize,
+        media_id: Option<Uuid>,
+        reason: String,
+        ttl: Duration,
+    ) {
+        if ttl.is_zero() {
+            return;
+        }
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                (iid, imz),
+                UnavailableEntry {
+                    media_id,
+                    reason,
+                    expires_at: Instant::now() + ttl,
+                },
+            );
+        }
+    }
+
+    /// Why `iid` at `imz` is unavailable, while the entry has not expired.
+    pub(super) fn reason(&self, iid: Uuid, imz: ImageSize) -> Option<String> {
+        let mut entries = self.entries.lock().ok()?;
+        let key = (iid, imz);
+        match entries.get(&key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                Some(entry.reason.clone())
+            }
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(super) fn clear(&self, iid: Uuid, imz: ImageSize) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(&(iid, imz));
+        }
+    }
+
+    /// Forget every recorded failure of the images of `media_id`.
+    pub(super) fn clear_media(&self, media_id: Uuid) -> usize {
+        let Ok(mut entries) = self.entries.lock() else {
+            return 0;
+        };
+        let before = entries.len();
+        entries.retain(|_, entry| entry.media_id != Some(media_id));
+        before - entries.len()
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or(0)
+    }
+}
+
+/// Whether retrying `err` can never succeed: the source answered with a
+/// client error other than a timeout or rate limit, or a local image file
+/// does not exist.
+pub(super) fn is_permanent_image_failure(err: &MediaError) -> bool {
+    match err {
+        MediaError::HttpStatus { status, .. } => {
+            status.is_client_error() && !matches!(status.as_u16(), 408 | 429)
+        }
+        MediaError::Io(io) => io.kind() == std::io::ErrorKind::NotFound,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrex_model::{BackdropSize, PosterSize};
+
+    fn status(code: u16) -> MediaError {
+        MediaError::HttpStatus {
+            status: reqwest::StatusCode::from_u16(code).unwrap(),
+            url: "https://image.tmdb.org/t/p/w342/missing.jpg".into(),
+        }
+    }
+
+    #[test]
+    fn only_client_errors_and_missing_files_are_permanent() {
+        assert!(is_permanent_image_failure(&status(404)));
+        assert!(is_permanent_image_failure(&status(410)));
+        assert!(!is_permanent_image_failure(&status(429)));
+        assert!(!is_permanent_image_failure(&status(408)));
+        assert!(!is_permanent_image_failure(&status(503)));
+        assert!(is_permanent_image_failure(&MediaError::Io(
+            std::io::ErrorKind::NotFound.into()
+        )));
+        assert!(!is_permanent_image_failure(&MediaError::Internal(
+            "size mismatch".into()
+        )));
+    }
+
+    #[test]
+    fn entries_expire_and_clear_per_size_or_media() {
+        let cache = UnavailableImages::default();
+        let media = Uuid::now_v7();
+        let poster = Uuid::now_v7();
+        let backdrop = Uuid::now_v7();
+        let w342 = ImageSize::Poster(PosterSize::W342);
+        let w1280 = ImageSize::Backdrop(BackdropSize::W1280);
+
+        cache.record(poster, w342, Some(media), "404".into(), Duration::ZERO);
+        assert_eq!(cache.reason(poster, w342), None);
+
+        let hour = Duration::from_secs(3600);
+        cache.record(poster, w342, Some(media), "404".into(), hour);
+        cache.record(backdrop, w1280, Some(media), "410".into(), hour);
+        assert_eq!(cache.reason(poster, w342).as_deref(), Some("404"));
+        assert_eq!(
+            cache.reason(poster, ImageSize::Poster(PosterSize::W185)),
+            None
+        );
+
+        cache.clear(poster, w342);
+        assert_eq!(cache.reason(poster, w342), None);
+        assert_eq!(cache.clear_media(media), 1);
+        assert_eq!(cache.len(), 0);
+
+        cache.record(poster, w342, None, "404".into(), Duration::from_nanos(1));
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(cache.reason(poster, w342), None);
+        assert_eq!(cache.len(), 0);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
     domain::media::extras::ExtrasParser,
+    domain::media::music_parser,
+    domain::media::naming_rules::naming_rules,
     domain::media::tv_parser::TvParser,
     types::{
         files::{ParsedEpisodeInfo, ParsedMediaInfo, ParsedMovieInfo},
@@ -7,9 +9,12 @@ use crate::{
     },
 };
 use regex::Regex;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
+/// Brace tags naming a database id (`{tmdb-603}`) rather than an edition.
+const ID_TAG_PREFIXES: [&str; 3] = ["tmdb-", "imdb-", "tvdb-"];
+
 #[derive(Debug, Clone, Default)]
 pub struct FilenameParser {
     library_type: Option<LibraryType>,
@@ -37,20 +42,74 @@ impl FilenameParser {
     ) -> Option<ParsedMediaInfo> {
         let file_path = file_path.as_ref();
 
+        if self.library_type == Some(LibraryType::Music) {
+            return self.try_parse_track(file_path);
+        }
+
         if ExtrasParser::is_extra(file_path) {
             return None;
         }
 
+        if let Some(matched) =
+            naming_rules().and_then(|rules| rules.parse(file_path))
+        {
+            return Some(self.with_release_details(matched.info, file_path));
+        }
+
         let episode = self.try_parse_episode(file_path);
         let movie = self.try_parse_movie(file_path);
 
         match self.library_type {
             Some(LibraryType::Series) => episode.or(movie),
-            Some(LibraryType::Movies) => movie.or(episode),
+            Some(LibraryType::Movies) | Some(LibraryType::Music) => {
+                movie.or(episode)
+            }
             None => episode.or(movie),
         }
     }
 
+    /// Fill the resolution, source and release group a configured rule
+    /// does not capture.
+    fn with_release_details(
+        &self,
+        info: ParsedMediaInfo,
+        file_path: &Path,
+    ) -> ParsedMediaInfo {
+        let filename = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        match info {
+            ParsedMediaInfo::Movie(movie) => {
+                ParsedMediaInfo::Movie(ParsedMovieInfo {
+                    resolution: self.extract_resolution(filename),
+                    source: self.extract_source(filename),
+                    release_group: self.extract_release_group(filename),
+                    ..movie
+                })
+            }
+            ParsedMediaInfo::Episode(episode) => {
+                ParsedMediaInfo::Episode(ParsedEpisodeInfo {
+                    resolution: self.extract_resolution(filename),
+                    source: self.extract_source(filename),
+                    release_group: self.extract_release_group(filename),
+                    ..episode
+                })
+            }
+            track @ ParsedMediaInfo::Track(_) => track,
+        }
+    }
+
+    /// Without the library root at hand, the artist and album are taken
+    /// from the two folders above the file.
+    fn try_parse_track(&self, file_path: &Path) -> Option<ParsedMediaInfo> {
+        let components: Vec<_> = file_path.components().collect();
+        let tail: PathBuf = components[components.len().saturating_sub(3)..]
+            .iter()
+            .collect();
+        music_parser::parse_track(&tail).map(ParsedMediaInfo::Track)
+    }
+
     fn try_parse_episode(&self, file_path: &Path) -> Option<ParsedMediaInfo> {
         let filename = file_path.file_stem()?.to_str()?;
         let info = TvParser::parse_episode_info(file_path)?;
@@ -71,6 +130,7 @@ impl FilenameParser {
             resolution: self.extract_resolution(filename),
             source: self.extract_source(filename),
             release_group: self.extract_release_group(filename),
+            absolute_episode: info.absolute_episode,
         }))
     }
 
@@ -162,11 +222,12 @@ impl FilenameParser {
             && let Some(folder_str) = folder_name.to_str()
         {
             info!("Trying to parse movie from folder name: {}", folder_str);
+            let folder_str = strip_brace_tags(folder_str);
 
             // Try to match "movie_name (year)" pattern in folder name
             let folder_regex = Regex::new(r"^(.+?)\s*\((\d{4})\)\s*$").ok();
             if let Some(regex) = folder_regex
-                && let Some(captures) = regex.captures(folder_str)
+                && let Some(captures) = regex.captures(&folder_str)
                 && let (Some(title_match), Some(year_match)) =
                     (captures.get(1), captures.get(2))
             {
@@ -250,6 +311,9 @@ impl FilenameParser {
                 .replace(&cleaned, "")
                 .to_string();
 
+        // Edition and id tags in braces never belong to the title
+        cleaned = strip_brace_tags(&cleaned);
+
         // First pass: Remove everything in square brackets
         cleaned = Regex::new(r"\[.*?\]")
             .unwrap()
@@ -375,6 +439,23 @@ impl FilenameParser {
         None
     }
 
+    /// Extract an edition label from a `{Extended}` or `{edition-Directors.Cut}`
+    /// tag in the filename, falling back to the parent folder
+    /// (`Movie (1999) {Extended}/movie.mkv`).
+    pub fn extract_edition(&self, file_path: &Path) -> Option<String> {
+        let from_name = file_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(edition_tag);
+        from_name.or_else(|| {
+            file_path
+                .parent()
+                .and_then(Path::file_name)
+                .and_then(|name| name.to_str())
+                .and_then(edition_tag)
+        })
+    }
+
     /// Clean filename by removing common artifacts
     pub fn clean_filename(&self, filename: &str) -> String {
         let mut cleaned = filename.to_string();
@@ -419,6 +500,36 @@ impl FilenameParser {
     }
 }
 
+/// First brace tag in `name` that is not an id tag, as a readable label.
+fn edition_tag(name: &str) -> Option<String> {
+    let tag_regex = Regex::new(r"\{([^{}]+)\}").ok()?;
+    tag_regex.captures_iter(name).find_map(|captures| {
+        let raw = captures.get(1)?.as_str().trim();
+        let lower = raw.to_ascii_lowercase();
+        if ID_TAG_PREFIXES
+            .iter()
+            .any(|prefix| lower.starts_with(prefix))
+        {
+            return None;
+        }
+        let raw = if lower.starts_with("edition-") {
+            &raw["edition-".len()..]
+        } else {
+            raw
+        };
+        let label = raw.replace(['.', '_'], " ");
+        let label = label.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!label.is_empty()).then_some(label)
+    })
+}
+
+/// Remove every `{...}` tag so editions of one movie parse to the same title.
+fn strip_brace_tags(name: &str) -> String {
+    Regex::new(r"\s*\{[^{}]*\}")
+        .map(|regex| regex.replace_all(name, "").trim().to_string())
+        .unwrap_or_else(|_| name.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -499,4 +610,46 @@ mod tests {
             panic!("Expected Movie variant");
         }
     }
+
+    #[test]
+    fn test_extract_edition() {
+        let parser = FilenameParser::new();
+
+        assert_eq!(
+            parser.extract_edition(Path::new(
+                "/m/Blade Runner (1982)/Blade Runner (1982) {Directors.Cut}.mkv"
+            )),
+            Some("Directors Cut".to_string())
+        );
+        assert_eq!(
+            parser.extract_edition(Path::new(
+                "/m/Aliens (1986) {edition-Special Edition}/Aliens.mkv"
+            )),
+            Some("Special Edition".to_string())
+        );
+        assert_eq!(
+            parser.extract_edition(Path::new(
+                "/m/The Matrix (1999) {tmdb-603}/The Matrix (1999).mkv"
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_editions_parse_to_the_same_title() {
+        let parser = FilenameParser::new();
+
+        for filename in [
+            "Blade.Runner.1982.1080p.mkv",
+            "Blade.Runner.1982.{Directors.Cut}.1080p.mkv",
+        ] {
+            let Some(ParsedMediaInfo::Movie(info)) =
+                parser.parse_as_movie(filename, Path::new(""))
+            else {
+                panic!("Expected Movie variant");
+            };
+            assert_eq!(info.title, "Blade Runner");
+            assert_eq!(info.year, Some(1982));
+        }
+    }
 }
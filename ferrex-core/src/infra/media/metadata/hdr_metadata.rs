@@ -2,6 +2,7 @@ use std::path::Path;
 use std::process::Command;
 
 use crate::error::{MediaError, Result};
+use crate::types::files::HdrFormat;
 
 #[derive(Debug, Default)]
 pub struct HdrInfo {
@@ -9,6 +10,25 @@ pub struct HdrInfo {
     pub color_primaries: Option<String>,
     pub color_transfer: Option<String>,
     pub color_space: Option<String>,
+    pub dolby_vision_profile: Option<u8>,
+}
+
+impl HdrInfo {
+    /// Classify the stream; SDR (and anything unrecognised) yields `None`.
+    ///
+    /// Dolby Vision wins over the base-layer transfer, since profile 8 streams
+    /// also carry a PQ or HLG base layer.
+    pub fn hdr_format(&self) -> Option<HdrFormat> {
+        if self.dolby_vision_profile.is_some() {
+            return Some(HdrFormat::DolbyVision);
+        }
+
+        match self.color_transfer.as_deref() {
+            Some("smpte2084") => Some(HdrFormat::Hdr10),
+            Some("arib-std-b67") => Some(HdrFormat::Hlg),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -59,60 +79,111 @@ impl HdrMetadataExtractor {
         if let Some(streams) = json["streams"].as_array()
             && let Some(stream) = streams.first()
         {
-            // Bit depth from pix_fmt (e.g., yuv420p10le -> 10 bit)
-            if let Some(pix_fmt) = stream["pix_fmt"].as_str() {
-                if pix_fmt.contains("p10")
-                    || pix_fmt.contains("10le")
-                    || pix_fmt.contains("10be")
-                {
-                    hdr_info.bit_depth = Some(10);
-                } else if pix_fmt.contains("p12")
-                    || pix_fmt.contains("12le")
-                    || pix_fmt.contains("12be")
-                {
-                    hdr_info.bit_depth = Some(12);
-                } else {
-                    hdr_info.bit_depth = Some(8);
-                }
-            }
+            hdr_info = Self::hdr_info_from_stream(stream);
+        }
 
-            // Color information
-            if let Some(color_primaries) = stream["color_primaries"].as_str() {
-                hdr_info.color_primaries = Some(color_primaries.to_string());
-            }
+        Ok(hdr_info)
+    }
 
-            if let Some(color_transfer) = stream["color_transfer"].as_str() {
-                hdr_info.color_transfer = Some(color_transfer.to_string());
-            }
+    /// Read HDR details from a single ffprobe stream object.
+    fn hdr_info_from_stream(stream: &serde_json::Value) -> HdrInfo {
+        let mut hdr_info = HdrInfo::default();
 
-            if let Some(color_space) = stream["color_space"].as_str() {
-                hdr_info.color_space = Some(color_space.to_string());
+        // Bit depth from pix_fmt (e.g., yuv420p10le -> 10 bit)
+        if let Some(pix_fmt) = stream["pix_fmt"].as_str() {
+            if pix_fmt.contains("p10")
+                || pix_fmt.contains("10le")
+                || pix_fmt.contains("10be")
+            {
+                hdr_info.bit_depth = Some(10);
+            } else if pix_fmt.contains("p12")
+                || pix_fmt.contains("12le")
+                || pix_fmt.contains("12be")
+            {
+                hdr_info.bit_depth = Some(12);
+            } else {
+                hdr_info.bit_depth = Some(8);
             }
+        }
+
+        // Color information
+        if let Some(color_primaries) = stream["color_primaries"].as_str() {
+            hdr_info.color_primaries = Some(color_primaries.to_string());
+        }
+
+        if let Some(color_transfer) = stream["color_transfer"].as_str() {
+            hdr_info.color_transfer = Some(color_transfer.to_string());
+        }
+
+        if let Some(color_space) = stream["color_space"].as_str() {
+            hdr_info.color_space = Some(color_space.to_string());
+        }
 
-            // Also check side_data_list for HDR metadata
-            if let Some(side_data_list) = stream["side_data_list"].as_array() {
-                for side_data in side_data_list {
-                    if let Some(side_data_type) =
-                        side_data["side_data_type"].as_str()
-                        && (side_data_type
-                            .contains("Mastering display metadata")
-                            || side_data_type
-                                .contains("Content light level metadata"))
-                    {
-                        // This indicates HDR content
-                        if hdr_info.color_transfer.is_none() {
-                            hdr_info.color_transfer =
-                                Some("smpte2084".to_string());
-                        }
-                        if hdr_info.color_primaries.is_none() {
-                            hdr_info.color_primaries =
-                                Some("bt2020".to_string());
-                        }
+        // Also check side_data_list for HDR metadata
+        if let Some(side_data_list) = stream["side_data_list"].as_array() {
+            for side_data in side_data_list {
+                let side_data_type =
+                    side_data["side_data_type"].as_str().unwrap_or("");
+
+                if side_data_type.contains("DOVI configuration record") {
+                    hdr_info.dolby_vision_profile = side_data["dv_profile"]
+                        .as_u64()
+                        .and_then(|profile| u8::try_from(profile).ok());
+                }
+
+                if side_data_type.contains("Mastering display metadata")
+                    || side_data_type.contains("Content light level metadata")
+                {
+                    // This indicates HDR content
+                    if hdr_info.color_transfer.is_none() {
+                        hdr_info.color_transfer = Some("smpte2084".to_string());
+                    }
+                    if hdr_info.color_primaries.is_none() {
+                        hdr_info.color_primaries = Some("bt2020".to_string());
                     }
                 }
             }
         }
 
-        Ok(hdr_info)
+        hdr_info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn classifies_sdr_hdr10_hlg_and_dolby_vision() {
+        let sdr = HdrMetadataExtractor::hdr_info_from_stream(&json!({
+            "pix_fmt": "yuv420p",
+            "color_transfer": "bt709",
+            "color_primaries": "bt709"
+        }));
+        assert_eq!(sdr.hdr_format(), None);
+        assert_eq!(sdr.dolby_vision_profile, None);
+
+        let hdr10 = HdrMetadataExtractor::hdr_info_from_stream(&json!({
+            "pix_fmt": "yuv420p10le",
+            "color_transfer": "smpte2084",
+            "color_primaries": "bt2020"
+        }));
+        assert_eq!(hdr10.hdr_format(), Some(HdrFormat::Hdr10));
+        assert_eq!(hdr10.bit_depth, Some(10));
+
+        let hlg = HdrMetadataExtractor::hdr_info_from_stream(&json!({
+            "color_transfer": "arib-std-b67"
+        }));
+        assert_eq!(hlg.hdr_format(), Some(HdrFormat::Hlg));
+
+        let dv = HdrMetadataExtractor::hdr_info_from_stream(&json!({
+            "color_transfer": "smpte2084",
+            "side_data_list": [
+                { "side_data_type": "DOVI configuration record", "dv_profile": 8 }
+            ]
+        }));
+        assert_eq!(dv.hdr_format(), Some(HdrFormat::DolbyVision));
+        assert_eq!(dv.dolby_vision_profile, Some(8));
     }
 }
@@ -122,10 +122,13 @@ impl MetadataExtractor {
                         color_primaries: None,
                         color_transfer: None,
                         color_space: None,
+                        dolby_vision_profile: None,
                     }
                 }
             };
 
+        let hdr_format = hdr_metadata.hdr_format();
+
         Ok(MediaFileMetadata {
             duration: technical_metadata.duration,
             width: technical_metadata.width,
@@ -146,6 +149,8 @@ impl MetadataExtractor {
                 .color_space
                 .or(technical_metadata.color_space),
             bit_depth: hdr_metadata.bit_depth.or(technical_metadata.bit_depth),
+            hdr_format,
+            dolby_vision_profile: hdr_metadata.dolby_vision_profile,
             parsed_info,
         })
     }
@@ -1,5 +1,7 @@
 pub mod tmdb_api_provider;
 pub mod tmdb_discover;
+pub mod tmdb_videos;
 
 pub use tmdb_api_provider::{ProviderError, TmdbApiProvider};
 pub use tmdb_discover::{DiscoverMovieItem, DiscoverPage, DiscoverTvItem};
+pub use tmdb_videos::{TmdbVideo, TmdbVideos};
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, future::Future};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ProviderError {
@@ -14,6 +14,10 @@ pub enum ProviderError {
     #[error("Invalid API key")]
     InvalidApiKey,
 
+    /// No API key was given; every TMDB request is skipped.
+    #[error("TMDB API key is not configured")]
+    NotConfigured,
+
     #[error("Network error: {0}")]
     NetworkError(#[from] reqwest::Error),
 
@@ -25,6 +29,7 @@ use super::tmdb_discover::{
     DiscoverMovieItem, DiscoverMovieQuery, DiscoverPage, DiscoverTvItem,
     DiscoverTvQuery,
 };
+use super::tmdb_videos::{TmdbVideos, VideoLanguageQuery};
 use ferrex_model::ImageSize;
 use serde::Deserialize;
 use serde::Serialize;
@@ -33,7 +38,7 @@ use tmdb_api::{
     client::{Client, reqwest::Client as ReqwestClient},
     common::{
         EntityResults, LanguagePageParams, LanguageParams, PaginatedResult,
-        release_date::LocatedReleaseDates, video::Video,
+        release_date::LocatedReleaseDates,
     },
     genre::Response as GenreResponse,
     movie::{
@@ -57,7 +62,7 @@ use tmdb_api::{
         search::Params as SeriesSearchParams,
     },
 };
-use tracing::error;
+use tracing::{error, warn};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Media {
@@ -67,19 +72,129 @@ pub enum Media {
 
 const TMDB_IMAGE_BASE: &str = "https://image.tmdb.org/t/p";
 const TMDB_V3_BASE: &str = "https://api.themoviedb.org/3";
+/// TMDB's own default when no language is requested.
+const DEFAULT_LANGUAGE: &str = "en-US";
+
+#[derive(Serialize)]
+struct ImageLanguageQuery<'a> {
+    api_key: &'a str,
+    include_image_language: &'a str,
+}
+
+/// Split a comma-separated language list such as `de-DE, en-US`.
+fn parse_language_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn normalize_languages(languages: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(languages.len());
+    for language in languages {
+        let language = language.trim();
+        if !language.is_empty() && !out.iter().any(|l| l == language) {
+            out.push(language.to_string());
+        }
+    }
+    if out.is_empty() {
+        out.push(DEFAULT_LANGUAGE.to_string());
+    }
+    out
+}
+
+fn is_blank(value: &str) -> bool {
+    value.trim().is_empty()
+}
+
+fn fill_string(target: &mut String, fallback: String) {
+    if is_blank(target) && !is_blank(&fallback) {
+        *target = fallback;
+    }
+}
+
+fn fill_option(target: &mut Option<String>, fallback: Option<String>) {
+    if target.as_deref().is_none_or(is_blank)
+        && fallback.as_deref().is_some_and(|value| !is_blank(value))
+    {
+        *target = fallback;
+    }
+}
+
+/// Text fields TMDB may leave empty when a translation is missing.
+trait LocalizedText {
+    fn has_missing_text(&self) -> bool;
+    fn fill_missing_text(&mut self, fallback: Self);
+}
+
+impl LocalizedText for TmdbMovieDetails {
+    fn has_missing_text(&self) -> bool {
+        is_blank(&self.inner.overview)
+            || self.tagline.as_deref().is_none_or(is_blank)
+    }
+
+    fn fill_missing_text(&mut self, fallback: Self) {
+        fill_string(&mut self.inner.overview, fallback.inner.overview);
+        fill_option(&mut self.tagline, fallback.tagline);
+    }
+}
+
+impl LocalizedText for tmdb_api::tvshow::TVShow {
+    fn has_missing_text(&self) -> bool {
+        self.inner.overview.as_deref().is_none_or(is_blank)
+            || self.tagline.as_deref().is_none_or(is_blank)
+    }
+
+    fn fill_missing_text(&mut self, fallback: Self) {
+        fill_option(&mut self.inner.overview, fallback.inner.overview);
+        fill_option(&mut self.tagline, fallback.tagline);
+    }
+}
+
+impl LocalizedText for tmdb_api::tvshow::Season {
+    fn has_missing_text(&self) -> bool {
+        is_blank(&self.inner.name)
+            || self.inner.overview.as_deref().is_none_or(is_blank)
+    }
+
+    fn fill_missing_text(&mut self, fallback: Self) {
+        fill_string(&mut self.inner.name, fallback.inner.name);
+        fill_option(&mut self.inner.overview, fallback.inner.overview);
+    }
+}
+
+impl LocalizedText for tmdb_api::tvshow::Episode {
+    fn has_missing_text(&self) -> bool {
+        is_blank(&self.inner.name)
+            || self.inner.overview.as_deref().is_none_or(is_blank)
+    }
+
+    fn fill_missing_text(&mut self, fallback: Self) {
+        fill_string(&mut self.inner.name, fallback.inner.name);
+        fill_option(&mut self.inner.overview, fallback.inner.overview);
+    }
+}
 
 pub struct TmdbApiProvider {
     client: Client<ReqwestClient>,
     http: reqwest::Client,
     api_key: String,
-    env_language: Option<String>,
-    env_region: Option<String>,
+    /// Whether `api_key` is non-blank. Requests fail with
+    /// [`ProviderError::NotConfigured`] otherwise.
+    configured: bool,
+    /// Preferred metadata languages, most preferred first. Never empty.
+    languages: Vec<String>,
+    /// Region sent with requests that take one; see [`Self::with_region`].
+    region: Option<String>,
 }
 
 impl fmt::Debug for TmdbApiProvider {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("TmdbApiProvider")
             .field("client", &"tmdb_api::Client<ReqwestExecutor>")
+            .field("configured", &self.configured)
+            .field("languages", &self.languages)
             .finish()
     }
 }
@@ -92,20 +207,145 @@ impl Default for TmdbApiProvider {
 
 impl TmdbApiProvider {
     pub fn new() -> Self {
-        let api_key =
-            std::env::var("TMDB_API_KEY").unwrap_or_else(|_| String::new());
-        let env_language = std::env::var("TMDB_LANG").ok();
-        let env_region = std::env::var("TMDB_REGION").ok();
+        let api_key = Self::api_key_from_env().unwrap_or_default();
+        let languages = std::env::var("TMDB_LANG")
+            .map(|raw| parse_language_list(&raw))
+            .unwrap_or_default();
+        let region = std::env::var("TMDB_REGION").ok();
 
         let client = Client::<ReqwestClient>::new(api_key.clone());
 
         Self {
             client,
             http: reqwest::Client::new(),
+            configured: !is_blank(&api_key),
             api_key,
-            env_language,
-            env_region,
+            languages: normalize_languages(languages),
+            region,
+        }
+    }
+
+    /// `TMDB_API_KEY`, unless unset or blank.
+    pub fn api_key_from_env() -> Option<String> {
+        std::env::var("TMDB_API_KEY")
+            .ok()
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+    }
+
+    /// Replace the API key read from the environment.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        let api_key = api_key.into().trim().to_string();
+        self.client = Client::<ReqwestClient>::new(api_key.clone());
+        self.configured = !api_key.is_empty();
+        self.api_key = api_key;
+        self
+    }
+
+    /// Whether an API key is set. Without one the server runs in limited
+    /// metadata mode: files are indexed under their parsed names and no
+    /// request is sent to TMDB.
+    pub fn is_configured(&self) -> bool {
+        self.configured
+    }
+
+    fn ensure_configured(&self) -> Result<(), ProviderError> {
+        if self.configured {
+            Ok(())
+        } else {
+            Err(ProviderError::NotConfigured)
+        }
+    }
+
+    /// Override the preferred metadata languages (e.g. `["de-DE", "en-US"]`).
+    ///
+    /// Localized requests use the first entry and fall back down the list for
+    /// text fields TMDB leaves empty. An empty list keeps the current value.
+    pub fn with_preferred_languages(mut self, languages: Vec<String>) -> Self {
+        if !languages.is_empty() {
+            self.languages = normalize_languages(languages);
+        }
+        self
+    }
+
+    pub fn preferred_languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// Override the region read from `TMDB_REGION` (ISO 3166-1, e.g. `DE`).
+    ///
+    /// It is sent with requests that take a region and its release dates and
+    /// certifications are preferred over other countries'. `None` keeps the
+    /// current value.
+    pub fn with_region(mut self, region: Option<String>) -> Self {
+        if let Some(region) = region.filter(|region| !is_blank(region)) {
+            self.region = Some(region.trim().to_ascii_uppercase());
+        }
+        self
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /// Stable key identifying the language preference that stored metadata
+    /// was fetched with; metadata stored under a different key is stale.
+    pub fn language_key(&self) -> String {
+        self.languages.join(",")
+    }
+
+    fn primary_language(&self) -> Option<&str> {
+        self.languages.first().map(String::as_str)
+    }
+
+    /// `include_image_language` value covering every preferred language plus
+    /// language-neutral artwork, e.g. `de,en,null`.
+    fn image_languages(&self) -> String {
+        let mut codes: Vec<&str> = Vec::new();
+        for language in &self.languages {
+            let code = language.split('-').next().unwrap_or(language);
+            if !codes.contains(&code) {
+                codes.push(code);
+            }
         }
+        codes.push("null");
+        codes.join(",")
+    }
+
+    /// Fetch `fetch` in the primary language, then in each fallback language
+    /// while `T` still has untranslated text fields.
+    async fn fetch_localized<T, F, Fut>(
+        &self,
+        fetch: F,
+    ) -> Result<T, ProviderError>
+    where
+        T: LocalizedText,
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<T, ProviderError>>,
+    {
+        let mut languages = self.languages.iter();
+        let primary = languages
+            .next()
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+        let mut details = fetch(primary).await?;
+
+        for language in languages {
+            if !details.has_missing_text() {
+                break;
+            }
+            match fetch(language.clone()).await {
+                Ok(fallback) => details.fill_missing_text(fallback),
+                Err(err) => {
+                    warn!(
+                        "Fallback TMDB fetch in {} failed: {}",
+                        language, err
+                    );
+                }
+            }
+        }
+
+        Ok(details)
     }
 
     async fn get_tmdb_json<Q, T>(
@@ -154,6 +394,7 @@ impl TmdbApiProvider {
         language: Option<&str>,
         region: Option<&str>,
     ) -> Result<DiscoverPage<DiscoverMovieItem>, ProviderError> {
+        self.ensure_configured()?;
         let query = DiscoverMovieQuery {
             api_key: &self.api_key,
             sort_by: "popularity.desc",
@@ -161,8 +402,8 @@ impl TmdbApiProvider {
             include_video: false,
             page: page.max(1),
             primary_release_year: year,
-            language: language.or(self.env_language.as_deref()),
-            region: region.or(self.env_region.as_deref()),
+            language: language.or(self.primary_language()),
+            region: region.or(self.region.as_deref()),
         };
 
         self.get_tmdb_json(&format!("{TMDB_V3_BASE}/discover/movie"), &query)
@@ -175,13 +416,14 @@ impl TmdbApiProvider {
         page: u32,
         language: Option<&str>,
     ) -> Result<DiscoverPage<DiscoverTvItem>, ProviderError> {
+        self.ensure_configured()?;
         let query = DiscoverTvQuery {
             api_key: &self.api_key,
             sort_by: "popularity.desc",
             include_adult: false,
             page: page.max(1),
             first_air_date_year: year,
-            language: language.or(self.env_language.as_deref()),
+            language: language.or(self.primary_language()),
         };
 
         self.get_tmdb_json(&format!("{TMDB_V3_BASE}/discover/tv"), &query)
@@ -195,10 +437,11 @@ impl TmdbApiProvider {
         language: Option<&str>,
         region: Option<&str>,
     ) -> Result<PaginatedResult<MovieShort>, ProviderError> {
+        self.ensure_configured()?;
         let params = Params {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
             page,
-            region: region.or(self.env_region.as_deref()).map(Into::into),
+            region: region.or(self.region.as_deref()).map(Into::into),
         };
 
         let popular_movies_cmd = self.client.list_popular_movies(&params).await;
@@ -212,8 +455,9 @@ impl TmdbApiProvider {
         page: Option<u32>,
         language: Option<&str>,
     ) -> Result<PaginatedResult<TVShowShort>, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguagePageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
             page,
         };
 
@@ -231,10 +475,11 @@ impl TmdbApiProvider {
         language: Option<&str>,
         region: Option<&str>,
     ) -> Result<PaginatedResult<MovieShort>, ProviderError> {
+        self.ensure_configured()?;
         let params = MovieSearchParams {
             year,
-            language: language.or(self.env_language.as_deref()).map(Into::into),
-            region: region.or(self.env_region.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
+            region: region.or(self.region.as_deref()).map(Into::into),
             ..Default::default()
         };
 
@@ -251,10 +496,11 @@ impl TmdbApiProvider {
         language: Option<&str>,
         region: Option<&str>,
     ) -> Result<PaginatedResult<TVShowShort>, ProviderError> {
+        self.ensure_configured()?;
         let params = SeriesSearchParams {
             first_air_date_year: year,
-            language: language.or(self.env_language.as_deref()).map(Into::into),
-            region: region.or(self.env_region.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
+            region: region.or(self.region.as_deref()).map(Into::into),
             ..Default::default()
         };
 
@@ -269,8 +515,9 @@ impl TmdbApiProvider {
         id: u64,
         language: Option<&str>,
     ) -> Result<TmdbMovieDetails, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
         };
 
         let details = self.client.get_movie_details(id, &params).await;
@@ -283,6 +530,7 @@ impl TmdbApiProvider {
         &self,
         id: u64,
     ) -> Result<EntityResults<Vec<LocatedReleaseDates>>, ProviderError> {
+        self.ensure_configured()?;
         self.client
             .get_movie_release_dates(id)
             .await
@@ -294,26 +542,50 @@ impl TmdbApiProvider {
         &self,
         id: u64,
     ) -> Result<KeywordsResponse, ProviderError> {
+        self.ensure_configured()?;
         self.client
             .get_movie_keywords(id)
             .await
             .map_err(|e| ProviderError::ApiError(e.to_string()))
     }
 
-    /// Get movie videos (trailers, clips, etc.)
+    /// Get movie videos (trailers, clips, etc.) in every preferred
+    /// language plus untagged ones
     pub async fn get_movie_videos(
         &self,
         id: u64,
         language: Option<&str>,
-    ) -> Result<EntityResults<Vec<Video>>, ProviderError> {
-        let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
-        };
+    ) -> Result<TmdbVideos, ProviderError> {
+        self.get_videos(&format!("{TMDB_V3_BASE}/movie/{id}/videos"), language)
+            .await
+    }
 
-        self.client
-            .get_movie_videos(id, &params)
+    /// Get TV series videos (trailers, clips, etc.)
+    pub async fn get_series_videos(
+        &self,
+        id: u64,
+        language: Option<&str>,
+    ) -> Result<TmdbVideos, ProviderError> {
+        self.get_videos(&format!("{TMDB_V3_BASE}/tv/{id}/videos"), language)
             .await
-            .map_err(|e| ProviderError::ApiError(e.to_string()))
+    }
+
+    async fn get_videos(
+        &self,
+        url: &str,
+        language: Option<&str>,
+    ) -> Result<TmdbVideos, ProviderError> {
+        self.ensure_configured()?;
+        let include_video_language = match language {
+            Some(language) => format!("{},null", language),
+            None => self.image_languages(),
+        };
+        let query = VideoLanguageQuery {
+            api_key: &self.api_key,
+            include_video_language: &include_video_language,
+        };
+
+        self.get_tmdb_json(url, &query).await
     }
 
     /// Get movie translations
@@ -321,6 +593,7 @@ impl TmdbApiProvider {
         &self,
         id: u64,
     ) -> Result<TranslationResponse, ProviderError> {
+        self.ensure_configured()?;
         self.client
             .get_movie_translations(id)
             .await
@@ -333,8 +606,9 @@ impl TmdbApiProvider {
         id: u64,
         country: Option<&str>,
     ) -> Result<MovieAltTitleResponse, ProviderError> {
+        self.ensure_configured()?;
         let params = CountryParams {
-            country: country.or(self.env_region.as_deref()).map(Into::into),
+            country: country.or(self.region.as_deref()).map(Into::into),
         };
 
         self.client
@@ -350,8 +624,9 @@ impl TmdbApiProvider {
         language: Option<&str>,
         page: Option<u32>,
     ) -> Result<PaginatedResult<MovieShort>, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguagePageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
             page,
         };
 
@@ -368,8 +643,9 @@ impl TmdbApiProvider {
         language: Option<&str>,
         page: Option<u32>,
     ) -> Result<PaginatedResult<MovieShort>, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguagePageParams {
-            language: language.map(|l| l.into()),
+            language: language.or(self.primary_language()).map(Into::into),
             page,
         };
 
@@ -384,28 +660,33 @@ impl TmdbApiProvider {
         &self,
         id: u64,
     ) -> Result<MovieExternalIds, ProviderError> {
+        self.ensure_configured()?;
         self.client
             .get_movie_external_ids(id)
             .await
             .map_err(|e| ProviderError::ApiError(e.to_string()))
     }
 
-    /// Get movie images
+    /// Get movie images in every preferred language plus untagged artwork
     pub async fn get_movie_images(
         &self,
         id: u64,
         language: Option<&str>,
     ) -> Result<GetMovieImagesResponse, ProviderError> {
-        let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+        self.ensure_configured()?;
+        let include_image_language = match language {
+            Some(language) => format!("{},null", language),
+            None => self.image_languages(),
+        };
+        let query = ImageLanguageQuery {
+            api_key: &self.api_key,
+            include_image_language: &include_image_language,
         };
 
-        self.client
-            .get_movie_images(id, &params)
+        self.get_tmdb_json(&format!("{TMDB_V3_BASE}/movie/{id}/images"), &query)
             .await
-            .map_err(|e| {
+            .inspect_err(|_| {
                 error!("Failed to map movie images for id {}", id);
-                ProviderError::ApiError(e.to_string())
             })
     }
 
@@ -415,8 +696,9 @@ impl TmdbApiProvider {
         id: u64,
         language: Option<&str>,
     ) -> Result<GetMovieCreditsResponse, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
         };
 
         self.client
@@ -431,8 +713,9 @@ impl TmdbApiProvider {
         id: u64,
         language: Option<&str>,
     ) -> Result<tmdb_api::tvshow::TVShow, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
         };
 
         self.client
@@ -446,6 +729,7 @@ impl TmdbApiProvider {
         &self,
         id: u64,
     ) -> Result<SeriesContentRatingResponse, ProviderError> {
+        self.ensure_configured()?;
         self.client
             .get_tvshow_content_ratings(id)
             .await
@@ -458,10 +742,15 @@ impl TmdbApiProvider {
         id: u64,
         language: Option<&str>,
     ) -> Result<GetTVshowImagesResponse, ProviderError> {
+        self.ensure_configured()?;
         let params = SeriesImageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
             include_image_language: Some(
-                format!("{},null", language.unwrap_or("en")).into(),
+                match language {
+                    Some(language) => format!("{},null", language),
+                    None => self.image_languages(),
+                }
+                .into(),
             ),
         };
 
@@ -477,8 +766,9 @@ impl TmdbApiProvider {
         id: u64,
         language: Option<&str>,
     ) -> Result<TVShowAggregateCredits, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
         };
 
         self.client
@@ -494,8 +784,9 @@ impl TmdbApiProvider {
         season_number: u16,
         language: Option<&str>,
     ) -> Result<tmdb_api::tvshow::Season, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
         };
 
         self.client
@@ -512,8 +803,9 @@ impl TmdbApiProvider {
         episode_number: u16,
         language: Option<&str>,
     ) -> Result<tmdb_api::tvshow::Episode, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
         };
 
         self.client
@@ -527,13 +819,76 @@ impl TmdbApiProvider {
             .map_err(|e| ProviderError::ApiError(e.to_string()))
     }
 
+    /// Movie details in the preferred languages, falling back down the list
+    /// for an empty overview or tagline.
+    pub async fn get_movie_localized(
+        &self,
+        id: u64,
+    ) -> Result<TmdbMovieDetails, ProviderError> {
+        self.ensure_configured()?;
+        self.fetch_localized(|language| async move {
+            self.get_movie(id, Some(&language)).await
+        })
+        .await
+    }
+
+    /// Series details in the preferred languages, falling back down the list
+    /// for an empty overview or tagline.
+    pub async fn get_series_localized(
+        &self,
+        id: u64,
+    ) -> Result<tmdb_api::tvshow::TVShow, ProviderError> {
+        self.ensure_configured()?;
+        self.fetch_localized(|language| async move {
+            self.get_series(id, Some(&language)).await
+        })
+        .await
+    }
+
+    /// Season details in the preferred languages, falling back down the list
+    /// for an empty name or overview.
+    pub async fn get_season_localized(
+        &self,
+        series_id: u64,
+        season_number: u16,
+    ) -> Result<tmdb_api::tvshow::Season, ProviderError> {
+        self.ensure_configured()?;
+        self.fetch_localized(|language| async move {
+            self.get_season(series_id, season_number, Some(&language))
+                .await
+        })
+        .await
+    }
+
+    /// Episode details in the preferred languages, falling back down the list
+    /// for an empty name or overview.
+    pub async fn get_episode_localized(
+        &self,
+        series_id: u64,
+        season_number: u16,
+        episode_number: u16,
+    ) -> Result<tmdb_api::tvshow::Episode, ProviderError> {
+        self.ensure_configured()?;
+        self.fetch_localized(|language| async move {
+            self.get_episode(
+                series_id,
+                season_number,
+                episode_number,
+                Some(&language),
+            )
+            .await
+        })
+        .await
+    }
+
     /// Get all movie genres
     pub async fn get_movie_genres(
         &self,
         language: Option<&str>,
     ) -> Result<GenreResponse, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
         };
 
         self.client
@@ -547,8 +902,9 @@ impl TmdbApiProvider {
         &self,
         language: Option<&str>,
     ) -> Result<GenreResponse, ProviderError> {
+        self.ensure_configured()?;
         let params = LanguageParams {
-            language: language.or(self.env_language.as_deref()).map(Into::into),
+            language: language.or(self.primary_language()).map(Into::into),
         };
 
         self.client
@@ -562,3 +918,55 @@ impl TmdbApiProvider {
         format!("{}/{}{}", TMDB_IMAGE_BASE, size.to_tmdb_param(), path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_lists_are_trimmed_deduplicated_and_never_empty() {
+        assert_eq!(
+            normalize_languages(parse_language_list(" de-DE, en-US ,,de-DE")),
+            vec!["de-DE".to_string(), "en-US".to_string()]
+        );
+        assert_eq!(normalize_languages(Vec::new()), vec!["en-US".to_string()]);
+    }
+
+    #[test]
+    fn image_languages_cover_each_base_language_and_untagged_art() {
+        let provider = TmdbApiProvider::new().with_preferred_languages(vec![
+            "de-DE".into(),
+            "de-AT".into(),
+            "en-US".into(),
+        ]);
+        assert_eq!(provider.image_languages(), "de,en,null");
+        assert_eq!(provider.language_key(), "de-DE,de-AT,en-US");
+    }
+
+    #[tokio::test]
+    async fn blank_api_key_fails_fast_with_not_configured() {
+        let provider = TmdbApiProvider::new().with_api_key("  ");
+        assert!(!provider.is_configured());
+        assert!(matches!(
+            provider.search_movies("Heat", None, None, None).await,
+            Err(ProviderError::NotConfigured)
+        ));
+        assert!(matches!(
+            provider.get_movie_localized(949).await,
+            Err(ProviderError::NotConfigured)
+        ));
+
+        assert!(TmdbApiProvider::new().with_api_key("abc").is_configured());
+    }
+
+    #[test]
+    fn fallback_only_fills_blank_fields() {
+        let mut overview = Some("  ".to_string());
+        fill_option(&mut overview, Some("Translated".into()));
+        assert_eq!(overview.as_deref(), Some("Translated"));
+
+        let mut name = "Staffel 1".to_string();
+        fill_string(&mut name, "Season 1".into());
+        assert_eq!(name, "Staffel 1");
+    }
+}
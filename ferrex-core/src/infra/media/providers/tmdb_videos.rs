@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// Response of TMDB's `/movie/{id}/videos` and `/tv/{id}/videos`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TmdbVideos {
+    #[serde(default)]
+    pub results: Vec<TmdbVideo>,
+}
+
+/// A trailer, teaser, clip or featurette hosted on a video site.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TmdbVideo {
+    pub key: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Hosting site, e.g. `YouTube` or `Vimeo`.
+    pub site: String,
+    /// `Trailer`, `Teaser`, `Clip`, `Featurette`, ...
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    /// Published by the studio or network rather than a fan channel.
+    #[serde(default)]
+    pub official: Option<bool>,
+    #[serde(default)]
+    pub iso_639_1: Option<String>,
+    #[serde(default)]
+    pub iso_3166_1: Option<String>,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub size: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VideoLanguageQuery<'a> {
+    pub api_key: &'a str,
+    pub include_video_language: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tmdb_video_list() {
+        let videos: TmdbVideos = serde_json::from_str(
+            r#"{
+                "id": 949,
+                "results": [{
+                    "iso_639_1": "en",
+                    "iso_3166_1": "US",
+                    "name": "Official Trailer",
+                    "key": "2GfZl4kuVNI",
+                    "site": "YouTube",
+                    "size": 1080,
+                    "type": "Trailer",
+                    "official": true,
+                    "published_at": "2016-09-06T17:00:03.000Z",
+                    "id": "57cef6c2c3a3687cd5002e3e"
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let video = &videos.results[0];
+        assert_eq!(video.kind.as_deref(), Some("Trailer"));
+        assert_eq!(video.official, Some(true));
+        assert_eq!(video.size, Some(1080));
+    }
+}
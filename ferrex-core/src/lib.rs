@@ -19,7 +19,7 @@
 //!
 //! - `database`: Enables database functionality (PostgreSQL/SQLx support)
 //! - `ffmpeg`: Enables FFmpeg-based metadata extraction
-//! - `test-utils`: Provides utilities for testing
+//! - `test-utils`: In-memory media file, image and watch repositories for tests
 //!
 //! ## Architecture
 //!
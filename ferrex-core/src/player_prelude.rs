@@ -26,8 +26,8 @@ pub use crate::types::watch::{
 pub use crate::domain::users::rbac::{Permission, Role, UserPermissions};
 pub use crate::domain::users::user::{
     AuthToken, LoginRequest, PlaybackPreferences, PlaybackQuality,
-    RegisterRequest, ResumeBehavior, SubtitlePreferences, ThemePreference,
-    UiPreferences, User, UserPreferences, UserScale,
+    RegisterRequest, ResumeBehavior, SessionIntrospection, SubtitlePreferences,
+    ThemePreference, UiPreferences, User, UserPreferences, UserScale,
 };
 
 pub use crate::types::media_events::{
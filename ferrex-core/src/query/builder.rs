@@ -86,6 +86,18 @@ impl MediaQueryBuilder {
         self
     }
 
+    /// Exclude media rated above `max` (`PG`, `12`); unrated media is kept
+    /// only with `include_unrated`
+    pub fn certification_max(
+        mut self,
+        max: impl Into<String>,
+        include_unrated: bool,
+    ) -> Self {
+        self.query.filters.certification_max = Some(max.into());
+        self.query.filters.include_unrated = include_unrated;
+        self
+    }
+
     /// Filter by library
     pub fn in_library(mut self, library_id: LibraryId) -> Self {
         self.query.filters.library_ids.push(library_id.to_uuid());
@@ -149,6 +161,12 @@ impl MediaQueryBuilder {
         self
     }
 
+    /// Order episodes within each season
+    pub fn episode_sort(mut self, sort: EpisodeSort) -> Self {
+        self.query.sort.episode_sort = sort;
+        self
+    }
+
     // === Pagination methods ===
 
     /// Set result limit
@@ -251,7 +251,7 @@ mod tests {
     use crate::query::prelude::{
         MediaFilters, Pagination, SearchField, SearchQuery, SortCriteria,
     };
-    use crate::query::types::SortOrder;
+    use crate::query::types::{EpisodeSort, SortOrder};
     use uuid::Uuid;
 
     #[test]
@@ -263,6 +263,7 @@ mod tests {
                 primary: SortBy::Title,
                 order: SortOrder::Ascending,
                 secondary: None,
+                episode_sort: EpisodeSort::Number,
             },
             pagination: Pagination {
                 offset: 0,
@@ -302,6 +303,7 @@ mod tests {
                 primary: SortBy::LastWatched,
                 order: SortOrder::Descending,
                 secondary: Some(SortBy::Rating),
+                episode_sort: EpisodeSort::Number,
             },
             search: Some(SearchQuery {
                 text: "test".to_string(),
@@ -368,6 +370,7 @@ mod tests {
                 primary: SortBy::Title,
                 order: SortOrder::Ascending,
                 secondary: Some(SortBy::Rating),
+                episode_sort: EpisodeSort::Number,
             },
             search: Some(SearchQuery {
                 text: "test".to_string(),
@@ -0,0 +1,443 @@
+//! Composable filter expressions over the UI filter vocabulary.
+//!
+//! A [`FilterExpr`] combines genre, decade, resolution and watch-status
+//! predicates with AND/OR/NOT, e.g.
+//! `(genre: sci-fi OR action) AND decade: 2010s AND NOT watch_status: completed`.
+//! Expressions travel inside `FilterIndicesRequest::expr` and are rendered into
+//! a single parameterized `WHERE` clause by the database adapter. Backends that
+//! cannot translate every predicate use [`FilterExpr::split_for_pushdown`] and
+//! evaluate the residual in memory with [`FilterExpr::matches`].
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    query::filtering::resolution_to_range,
+    types::filter_types::{UiDecade, UiGenre, UiResolution, UiWatchStatus},
+};
+
+/// Upper bound on the number of nodes in a single expression so that client
+/// supplied filters cannot produce unbounded SQL.
+pub const MAX_FILTER_EXPR_NODES: usize = 64;
+
+/// Predicate tree over [`UiGenre`], [`UiDecade`], [`UiResolution`] and
+/// [`UiWatchStatus`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterExpr {
+    Genre(UiGenre),
+    Decade(UiDecade),
+    Resolution(UiResolution),
+    WatchStatus(UiWatchStatus),
+    And(Vec<FilterExpr>),
+    Or(Vec<FilterExpr>),
+    Not(Box<FilterExpr>),
+}
+
+/// Predicate families a backend may or may not be able to push down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterField {
+    Genre,
+    Decade,
+    Resolution,
+    WatchStatus,
+}
+
+impl fmt::Display for FilterField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FilterField::Genre => "genre",
+            FilterField::Decade => "decade",
+            FilterField::Resolution => "resolution",
+            FilterField::WatchStatus => "watch_status",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FilterExprError {
+    #[error("unknown {field} value '{value}'")]
+    UnknownValue { field: FilterField, value: String },
+    #[error("filter expression has {0} nodes (max {MAX_FILTER_EXPR_NODES})")]
+    TooComplex(usize),
+}
+
+/// Item view used by the in-memory evaluator.
+pub trait FilterSubject {
+    fn has_genre(&self, genre: UiGenre) -> bool;
+    fn release_year(&self) -> Option<u16>;
+    fn resolution_height(&self) -> Option<u16>;
+    fn watch_status(&self) -> UiWatchStatus;
+}
+
+impl FilterExpr {
+    /// Genre predicate from a user supplied name such as `"sci-fi"`.
+    pub fn genre(name: &str) -> Result<Self, FilterExprError> {
+        parse_genre(name)
+            .map(FilterExpr::Genre)
+            .ok_or_else(|| unknown(FilterField::Genre, name))
+    }
+
+    /// Decade predicate from `"2010s"` or `"2010"`.
+    pub fn decade(label: &str) -> Result<Self, FilterExprError> {
+        parse_decade(label)
+            .map(FilterExpr::Decade)
+            .ok_or_else(|| unknown(FilterField::Decade, label))
+    }
+
+    /// Resolution predicate from `"1080p"`, `"4k"`, `"sd"`, ...
+    pub fn resolution(label: &str) -> Result<Self, FilterExprError> {
+        parse_resolution(label)
+            .map(FilterExpr::Resolution)
+            .ok_or_else(|| unknown(FilterField::Resolution, label))
+    }
+
+    /// Watch-status predicate from `"unwatched"`, `"in_progress"`,
+    /// `"completed"` (alias `"watched"`) or `"any"`.
+    pub fn watch_status(label: &str) -> Result<Self, FilterExprError> {
+        parse_watch_status(label)
+            .map(FilterExpr::WatchStatus)
+            .ok_or_else(|| unknown(FilterField::WatchStatus, label))
+    }
+
+    /// Conjunction of `exprs`; an empty list matches everything.
+    pub fn all(exprs: impl IntoIterator<Item = FilterExpr>) -> Self {
+        FilterExpr::And(exprs.into_iter().collect())
+    }
+
+    /// Disjunction of `exprs`; an empty list matches nothing.
+    pub fn any(exprs: impl IntoIterator<Item = FilterExpr>) -> Self {
+        FilterExpr::Or(exprs.into_iter().collect())
+    }
+
+    pub fn and(self, other: FilterExpr) -> Self {
+        match self {
+            FilterExpr::And(mut exprs) => {
+                exprs.push(other);
+                FilterExpr::And(exprs)
+            }
+            expr => FilterExpr::And(vec![expr, other]),
+        }
+    }
+
+    pub fn or(self, other: FilterExpr) -> Self {
+        match self {
+            FilterExpr::Or(mut exprs) => {
+                exprs.push(other);
+                FilterExpr::Or(exprs)
+            }
+            expr => FilterExpr::Or(vec![expr, other]),
+        }
+    }
+
+    pub fn negate(self) -> Self {
+        match self {
+            FilterExpr::Not(inner) => *inner,
+            expr => FilterExpr::Not(Box::new(expr)),
+        }
+    }
+
+    /// Total number of nodes in the tree.
+    pub fn node_count(&self) -> usize {
+        match self {
+            FilterExpr::And(exprs) | FilterExpr::Or(exprs) => {
+                1 + exprs.iter().map(FilterExpr::node_count).sum::<usize>()
+            }
+            FilterExpr::Not(inner) => 1 + inner.node_count(),
+            _ => 1,
+        }
+    }
+
+    /// Reject expressions too large to translate safely.
+    pub fn validate(&self) -> Result<(), FilterExprError> {
+        let nodes = self.node_count();
+        if nodes > MAX_FILTER_EXPR_NODES {
+            return Err(FilterExprError::TooComplex(nodes));
+        }
+        Ok(())
+    }
+
+    /// Whether any leaf uses `field`.
+    pub fn references(&self, field: FilterField) -> bool {
+        match self {
+            FilterExpr::And(exprs) | FilterExpr::Or(exprs) => {
+                exprs.iter().any(|expr| expr.references(field))
+            }
+            FilterExpr::Not(inner) => inner.references(field),
+            leaf => leaf.field() == Some(field),
+        }
+    }
+
+    /// Field of a leaf predicate, `None` for combinators.
+    pub fn field(&self) -> Option<FilterField> {
+        match self {
+            FilterExpr::Genre(_) => Some(FilterField::Genre),
+            FilterExpr::Decade(_) => Some(FilterField::Decade),
+            FilterExpr::Resolution(_) => Some(FilterField::Resolution),
+            FilterExpr::WatchStatus(_) => Some(FilterField::WatchStatus),
+            FilterExpr::And(_) | FilterExpr::Or(_) | FilterExpr::Not(_) => None,
+        }
+    }
+
+    /// Split into a part the backend can push down and a residual to
+    /// evaluate in memory.
+    ///
+    /// Only top-level conjuncts are separated: an OR or NOT subtree is pushed
+    /// down whole or not at all, which keeps `pushed AND residual`
+    /// equivalent to the original expression.
+    pub fn split_for_pushdown(
+        self,
+        supports: impl Fn(FilterField) -> bool,
+    ) -> (Option<FilterExpr>, Option<FilterExpr>) {
+        let supported = |expr: &FilterExpr| {
+            [
+                FilterField::Genre,
+                FilterField::Decade,
+                FilterField::Resolution,
+                FilterField::WatchStatus,
+            ]
+            .into_iter()
+            .all(|field| !expr.references(field) || supports(field))
+        };
+
+        let conjuncts = match self {
+            FilterExpr::And(exprs) => exprs,
+            expr => vec![expr],
+        };
+
+        let (pushed, residual): (Vec<_>, Vec<_>) =
+            conjuncts.into_iter().partition(supported);
+
+        let collapse = |mut exprs: Vec<FilterExpr>| match exprs.len() {
+            0 => None,
+            1 => exprs.pop(),
+            _ => Some(FilterExpr::And(exprs)),
+        };
+
+        (collapse(pushed), collapse(residual))
+    }
+
+    /// Evaluate the expression against a single item.
+    pub fn matches<S: FilterSubject + ?Sized>(&self, subject: &S) -> bool {
+        match self {
+            FilterExpr::Genre(genre) => subject.has_genre(*genre),
+            FilterExpr::Decade(decade) => {
+                let start = decade.start_year();
+                subject
+                    .release_year()
+                    .is_some_and(|year| (start..=start + 9).contains(&year))
+            }
+            FilterExpr::Resolution(resolution) => {
+                match resolution_to_range(*resolution) {
+                    None => true,
+                    Some(range) => {
+                        subject.resolution_height().is_some_and(|height| {
+                            (range.min..=range.max).contains(&height)
+                        })
+                    }
+                }
+            }
+            FilterExpr::WatchStatus(UiWatchStatus::Any) => true,
+            FilterExpr::WatchStatus(status) => {
+                subject.watch_status() == *status
+            }
+            FilterExpr::And(exprs) => {
+                exprs.iter().all(|expr| expr.matches(subject))
+            }
+            FilterExpr::Or(exprs) => {
+                exprs.iter().any(|expr| expr.matches(subject))
+            }
+            FilterExpr::Not(inner) => !inner.matches(subject),
+        }
+    }
+}
+
+fn unknown(field: FilterField, value: &str) -> FilterExprError {
+    FilterExprError::UnknownValue {
+        field,
+        value: value.to_string(),
+    }
+}
+
+/// Lowercase and drop separators so `"Sci-Fi"`, `"sci fi"` and `"scifi"`
+/// compare equal.
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn parse_genre(name: &str) -> Option<UiGenre> {
+    let key = normalize(name);
+    if key == "scifi" {
+        return Some(UiGenre::ScienceFiction);
+    }
+    UiGenre::all()
+        .iter()
+        .copied()
+        .find(|genre| normalize(genre.api_name()) == key)
+}
+
+fn parse_decade(label: &str) -> Option<UiDecade> {
+    let key = normalize(label);
+    let year = key.strip_suffix('s').unwrap_or(&key);
+    let year: u16 = year.parse().ok()?;
+    UiDecade::all()
+        .iter()
+        .copied()
+        .find(|decade| decade.start_year() == year)
+}
+
+fn parse_resolution(label: &str) -> Option<UiResolution> {
+    use UiResolution::*;
+    match normalize(label).as_str() {
+        "any" => Some(Any),
+        "sd" | "480p" | "576p" => Some(SD),
+        "720p" | "hd" => Some(HD720),
+        "1080p" | "fhd" => Some(FHD1080),
+        "1440p" | "qhd" => Some(QHD1440),
+        "4k" | "2160p" | "uhd" => Some(UHD4K),
+        "8k" | "4320p" => Some(UHD8K),
+        _ => None,
+    }
+}
+
+fn parse_watch_status(label: &str) -> Option<UiWatchStatus> {
+    use UiWatchStatus::*;
+    match normalize(label).as_str() {
+        "any" => Some(Any),
+        "unwatched" => Some(Unwatched),
+        "inprogress" => Some(InProgress),
+        "completed" | "watched" => Some(Completed),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item {
+        genres: Vec<UiGenre>,
+        year: Option<u16>,
+        height: Option<u16>,
+        status: UiWatchStatus,
+    }
+
+    impl FilterSubject for Item {
+        fn has_genre(&self, genre: UiGenre) -> bool {
+            self.genres.contains(&genre)
+        }
+        fn release_year(&self) -> Option<u16> {
+            self.year
+        }
+        fn resolution_height(&self) -> Option<u16> {
+            self.height
+        }
+        fn watch_status(&self) -> UiWatchStatus {
+            self.status
+        }
+    }
+
+    fn example() -> FilterExpr {
+        FilterExpr::genre("sci-fi")
+            .unwrap()
+            .or(FilterExpr::genre("action").unwrap())
+            .and(FilterExpr::decade("2010s").unwrap())
+            .and(FilterExpr::watch_status("watched").unwrap().negate())
+    }
+
+    #[test]
+    fn builder_rejects_unknown_values() {
+        assert_eq!(
+            FilterExpr::genre("space opera"),
+            Err(FilterExprError::UnknownValue {
+                field: FilterField::Genre,
+                value: "space opera".into(),
+            })
+        );
+        assert!(FilterExpr::decade("1940s").is_err());
+        assert!(FilterExpr::resolution("900p").is_err());
+        assert!(FilterExpr::watch_status("maybe").is_err());
+        assert_eq!(
+            FilterExpr::genre("Science Fiction").unwrap(),
+            FilterExpr::Genre(UiGenre::ScienceFiction)
+        );
+    }
+
+    #[test]
+    fn evaluates_combined_predicates() {
+        let expr = example();
+        let mut item = Item {
+            genres: vec![UiGenre::Action, UiGenre::Thriller],
+            year: Some(2014),
+            height: Some(1080),
+            status: UiWatchStatus::InProgress,
+        };
+        assert!(expr.matches(&item));
+
+        item.status = UiWatchStatus::Completed;
+        assert!(!expr.matches(&item));
+
+        item.status = UiWatchStatus::Unwatched;
+        item.year = Some(2021);
+        assert!(!expr.matches(&item));
+
+        item.year = None;
+        assert!(!expr.matches(&item));
+    }
+
+    #[test]
+    fn split_keeps_unsupported_conjuncts_as_residual() {
+        let expr = example();
+        let (pushed, residual) = expr
+            .clone()
+            .split_for_pushdown(|field| field != FilterField::WatchStatus);
+
+        assert_eq!(
+            pushed,
+            Some(FilterExpr::And(vec![
+                FilterExpr::Or(vec![
+                    FilterExpr::Genre(UiGenre::ScienceFiction),
+                    FilterExpr::Genre(UiGenre::Action),
+                ]),
+                FilterExpr::Decade(UiDecade::Y2010s),
+            ]))
+        );
+        assert_eq!(
+            residual,
+            Some(FilterExpr::Not(Box::new(FilterExpr::WatchStatus(
+                UiWatchStatus::Completed
+            ))))
+        );
+
+        let (pushed, residual) = expr.split_for_pushdown(|_| true);
+        assert!(pushed.is_some());
+        assert!(residual.is_none());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let expr = example();
+        let json = serde_json::to_value(&expr).unwrap();
+        assert_eq!(json["and"][1]["decade"], "2010s");
+        let back: FilterExpr = serde_json::from_value(json).unwrap();
+        assert_eq!(back, expr);
+
+        let unknown = serde_json::json!({ "genre": "space_opera" });
+        assert!(serde_json::from_value::<FilterExpr>(unknown).is_err());
+    }
+
+    #[test]
+    fn validate_caps_expression_size() {
+        let wide = FilterExpr::any(std::iter::repeat_n(
+            FilterExpr::Genre(UiGenre::Drama),
+            80,
+        ));
+        assert_eq!(wide.validate(), Err(FilterExprError::TooComplex(81)));
+        assert!(example().validate().is_ok());
+    }
+}
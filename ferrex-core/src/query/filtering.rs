@@ -41,6 +41,8 @@ pub struct FilterRequestParams<'a> {
     pub search: Option<&'a str>,
     pub sort: SortBy,
     pub order: SortOrder,
+    pub certification_max: Option<&'a str>,
+    pub include_unrated: bool,
 }
 
 impl<'a> FilterRequestParams<'a> {
@@ -71,6 +73,9 @@ impl<'a> FilterRequestParams<'a> {
             search,
             sort: Some(self.sort),
             order: Some(self.order),
+            expr: None,
+            certification_max: self.certification_max.map(str::to_string),
+            include_unrated: self.include_unrated,
         }
     }
 }
@@ -138,6 +143,9 @@ pub fn hash_filter_spec(spec: &FilterIndicesRequest) -> u64 {
 
     spec.sort.hash(&mut hasher);
     spec.order.hash(&mut hasher);
+    spec.expr.hash(&mut hasher);
+    spec.certification_max.hash(&mut hasher);
+    spec.include_unrated.hash(&mut hasher);
 
     hasher.finish()
 }
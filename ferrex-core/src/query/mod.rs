@@ -1,6 +1,7 @@
 pub mod builder;
 pub mod complexity_guard;
 pub mod decision_engine;
+pub mod filter_expr;
 pub mod filtering;
 pub mod prelude;
 pub mod sorting;
@@ -8,3 +9,6 @@ pub mod types;
 
 pub use builder::MediaQueryBuilder;
 pub use complexity_guard::{ComplexityConfig, QueryComplexityGuard};
+pub use filter_expr::{
+    FilterExpr, FilterExprError, FilterField, FilterSubject,
+};
@@ -328,6 +328,7 @@ mod tests {
                 library_id: LibraryId::new(),
             },
             theme_color: None,
+            editions: Vec::new(),
         }
     }
 
@@ -2,7 +2,9 @@ use crate::{
     api::types::{RatingValue, ScalarRange},
     domain::watch::{ItemWatchStatus, WatchStatusFilter},
 };
-use ferrex_model::MediaID;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use ferrex_model::{LibraryId, MediaID, certification_age};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -27,6 +29,59 @@ pub struct MediaFilters {
     pub rating_range: Option<ScalarRange<RatingValue>>,
     pub resolution_range: Option<ScalarRange<u16>>,
     pub library_ids: Vec<Uuid>,
+    /// Highest allowed certification, as a rating (`PG-13`) or an age (`13`)
+    #[serde(default)]
+    pub certification_max: Option<String>,
+    /// Keep media without a known certification when `certification_max`
+    /// is set
+    #[serde(default)]
+    pub include_unrated: bool,
+}
+
+impl MediaFilters {
+    pub fn certification(
+        &self,
+    ) -> Result<Option<CertificationFilter>, QueryError> {
+        CertificationFilter::parse(
+            self.certification_max.as_deref(),
+            self.include_unrated,
+        )
+    }
+}
+
+/// A `certification_max` filter resolved to an age threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CertificationFilter {
+    pub max_age: u8,
+    pub include_unrated: bool,
+}
+
+impl CertificationFilter {
+    /// `Ok(None)` without a maximum; an error for a code no rating system
+    /// uses.
+    pub fn parse(
+        max: Option<&str>,
+        include_unrated: bool,
+    ) -> Result<Option<Self>, QueryError> {
+        let Some(max) = max.map(str::trim).filter(|max| !max.is_empty()) else {
+            return Ok(None);
+        };
+        let max_age = certification_age(max).ok_or_else(|| {
+            QueryError::InvalidQuery(format!("unknown certification `{max}`"))
+        })?;
+        Ok(Some(Self {
+            max_age,
+            include_unrated,
+        }))
+    }
+
+    /// Whether media whose certification implies `age` passes.
+    pub fn allows(&self, age: Option<i16>) -> bool {
+        match age {
+            Some(age) => age <= i16::from(self.max_age),
+            None => self.include_unrated,
+        }
+    }
 }
 
 /// Filter by media type
@@ -45,6 +100,9 @@ pub struct SortCriteria {
     pub primary: SortBy,
     pub order: SortOrder,
     pub secondary: Option<SortBy>, // For stable sorting
+    /// Order of episodes within a season
+    #[serde(default)]
+    pub episode_sort: EpisodeSort,
 }
 
 impl Default for SortCriteria {
@@ -53,10 +111,25 @@ impl Default for SortCriteria {
             primary: SortBy::Title,
             order: SortOrder::Ascending,
             secondary: None,
+            episode_sort: EpisodeSort::Number,
         }
     }
 }
 
+/// Order of episodes within a season
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash, Default,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum EpisodeSort {
+    /// By episode number
+    #[default]
+    Number,
+    /// By air date, oldest first; episodes without an air date come last,
+    /// ordered by number
+    AirDate,
+}
+
 /// Fields available for sorting
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "snake_case")]
@@ -161,6 +234,178 @@ impl MediaWithStatus {
     }
 }
 
+/// Request for the recently added feed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecentlyAddedQuery {
+    /// Page size; the server clamps it
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<RecentlyAddedCursor>,
+    #[serde(default)]
+    pub library_id: Option<LibraryId>,
+}
+
+/// A movie, or a series stamped with its most recently added episode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentlyAddedItem {
+    pub id: MediaID,
+    pub added_at: DateTime<Utc>,
+}
+
+/// One page of the recently added feed, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentlyAddedPage {
+    pub items: Vec<RecentlyAddedItem>,
+    /// Present when more items follow
+    pub next_cursor: Option<RecentlyAddedCursor>,
+}
+
+/// Opaque position in the recently added feed: the `(added_at, id)` of the
+/// last item on a page. Serialized as a URL-safe string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecentlyAddedCursor {
+    pub added_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl RecentlyAddedCursor {
+    pub fn after(item: &RecentlyAddedItem) -> Self {
+        Self {
+            added_at: item.added_at,
+            id: *item.id.as_uuid(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}:{}",
+            self.added_at.timestamp_micros(),
+            self.id
+        ))
+    }
+
+    pub fn decode(raw: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(raw).ok()?;
+        let text = String::from_utf8(bytes).ok()?;
+        let (micros, id) = text.split_once(':')?;
+        Some(Self {
+            added_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+impl Serialize for RecentlyAddedCursor {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.encode())
+    }
+}
+
+impl<'de> Deserialize<'de> for RecentlyAddedCursor {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Self::decode(&raw)
+            .ok_or_else(|| serde::de::Error::custom("invalid cursor"))
+    }
+}
+
+/// Query string of the `/search` endpoint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MediaSearchQuery {
+    /// Text to look for
+    pub q: String,
+    /// Only return this kind of media; seasons are not searchable
+    #[serde(default, rename = "type")]
+    pub media_type: Option<MediaTypeFilter>,
+    /// Comma-separated `title`, `overview`, `cast` or `all`; titles only
+    /// by default
+    #[serde(default)]
+    pub fields: Option<String>,
+    /// Number of hits; the server clamps it
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub library_id: Option<LibraryId>,
+    /// Highest allowed certification, as a rating (`PG-13`) or an age (`13`)
+    #[serde(default)]
+    pub certification_max: Option<String>,
+    /// Keep unrated hits when `certification_max` is set
+    #[serde(default)]
+    pub include_unrated: bool,
+}
+
+impl MediaSearchQuery {
+    pub fn certification(
+        &self,
+    ) -> Result<Option<CertificationFilter>, QueryError> {
+        CertificationFilter::parse(
+            self.certification_max.as_deref(),
+            self.include_unrated,
+        )
+    }
+
+    /// The fields to search, with `all` expanded.
+    pub fn search_fields(&self) -> Result<Vec<SearchField>, QueryError> {
+        let Some(raw) = self.fields.as_deref() else {
+            return Ok(vec![SearchField::Title]);
+        };
+        let mut fields = Vec::new();
+        for name in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let field = match name.to_ascii_lowercase().as_str() {
+                "title" => SearchField::Title,
+                "overview" => SearchField::Overview,
+                "cast" => SearchField::Cast,
+                "all" => {
+                    return Ok(vec![
+                        SearchField::Title,
+                        SearchField::Overview,
+                        SearchField::Cast,
+                    ]);
+                }
+                _ => {
+                    return Err(QueryError::InvalidQuery(format!(
+                        "unknown search field `{name}`"
+                    )));
+                }
+            };
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        if fields.is_empty() {
+            fields.push(SearchField::Title);
+        }
+        Ok(fields)
+    }
+}
+
+/// One ranked `/search` result, with what a client needs to draw a card
+/// and open the media
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaSearchHit {
+    pub id: MediaID,
+    pub library_id: LibraryId,
+    /// Movie or series title, or the episode name
+    pub title: String,
+    /// `Series · S01E02` for episodes
+    pub subtitle: Option<String>,
+    pub year: Option<i32>,
+    /// TMDB poster path; the episode still for episodes
+    pub poster_path: Option<String>,
+    /// Relevance in `0.0..=1.0`, higher first. Title matches rank above
+    /// overview and cast matches.
+    pub score: f32,
+    /// The field the best match was found in
+    pub matched: SearchField,
+}
+
 /// Query execution error
 #[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
 pub enum QueryError {
@@ -176,3 +421,73 @@ pub enum QueryError {
     #[error("Deserialization error")]
     DeserializationError,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrex_model::SeriesID;
+
+    #[test]
+    fn recently_added_cursor_round_trips_as_an_opaque_string() {
+        let item = RecentlyAddedItem {
+            id: MediaID::Series(SeriesID(Uuid::new_v4())),
+            added_at: DateTime::from_timestamp_micros(1_760_000_000_123_456)
+                .unwrap(),
+        };
+        let cursor = RecentlyAddedCursor::after(&item);
+
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert!(!json.contains(':'), "cursor should be opaque: {json}");
+        let decoded: RecentlyAddedCursor = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, cursor);
+
+        assert!(RecentlyAddedCursor::decode("not-a-cursor").is_none());
+    }
+
+    #[test]
+    fn search_fields_default_to_title_and_expand_all() {
+        let mut query = MediaSearchQuery {
+            q: "heat".into(),
+            ..MediaSearchQuery::default()
+        };
+        assert_eq!(query.search_fields().unwrap(), [SearchField::Title]);
+
+        query.fields = Some("Cast, title,cast".into());
+        assert_eq!(
+            query.search_fields().unwrap(),
+            [SearchField::Cast, SearchField::Title]
+        );
+
+        query.fields = Some("all".into());
+        assert_eq!(query.search_fields().unwrap().len(), 3);
+
+        query.fields = Some("genre".into());
+        assert!(query.search_fields().is_err());
+    }
+
+    #[test]
+    fn certification_filter_takes_ratings_or_ages_and_gates_unrated() {
+        assert_eq!(CertificationFilter::parse(None, true).unwrap(), None);
+        assert_eq!(CertificationFilter::parse(Some(" "), true).unwrap(), None);
+        assert!(CertificationFilter::parse(Some("family"), false).is_err());
+
+        let pg13 = CertificationFilter::parse(Some("PG-13"), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            CertificationFilter::parse(Some("13"), false).unwrap(),
+            Some(pg13)
+        );
+        assert!(pg13.allows(Some(0)));
+        assert!(pg13.allows(Some(13)));
+        assert!(!pg13.allows(Some(17)));
+        assert!(!pg13.allows(None));
+
+        let with_unrated = CertificationFilter {
+            include_unrated: true,
+            ..pg13
+        };
+        assert!(with_unrated.allows(None));
+        assert!(!with_unrated.allows(Some(18)));
+    }
+}
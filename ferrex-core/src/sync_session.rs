@@ -62,6 +62,15 @@ pub enum SyncMessage {
     Pong { timestamp: i64 },
 }
 
+/// WebSocket close code sent when the auth session behind a sync connection
+/// is revoked. Clients should sign in again rather than reconnect with the
+/// same token.
+pub const WS_CLOSE_SESSION_REVOKED: u16 = 4001;
+
+/// WebSocket close code sent when the auth session behind a sync connection
+/// expires. Clients should refresh their token before reconnecting.
+pub const WS_CLOSE_SESSION_EXPIRED: u16 = 4002;
+
 /// Request to create a sync session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSyncSessionRequest {
@@ -58,6 +58,8 @@ fn sample_movie() -> SampleMovie {
             color_transfer: Some("bt709".into()),
             color_space: Some("bt709".into()),
             bit_depth: Some(8),
+            hdr_format: None,
+            dolby_vision_profile: None,
             parsed_info: None,
         }),
         library_id,
@@ -127,6 +129,7 @@ fn sample_movie() -> SampleMovie {
         endpoint: MovieURL::from_string("/movies/inception".into()),
         file: media_file,
         theme_color: Some("#0a0f24".into()),
+        editions: Vec::new(),
     };
 
     SampleMovie {
@@ -190,6 +193,7 @@ fn archived_library_round_trip_retains_members() {
         analyze_on_scan: false,
         max_retry_attempts: 3,
         movie_ref_batch_size: MovieReferenceBatchSize::default(),
+        exclude_patterns: Vec::new(),
         created_at: timestamp,
         updated_at: timestamp,
         media: Some(vec![movie.clone()]),
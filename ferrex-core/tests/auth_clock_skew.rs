@@ -0,0 +1,81 @@
+//! Session tokens stay valid for the configured clock skew leeway past
+//! their expiry, and no longer.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Duration;
+use ferrex_core::domain::users::auth::{
+    AuthCrypto,
+    domain::services::{AuthenticationError, create_authentication_service},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const TEST_USERNAME: &str = "skewuser";
+const TEST_PASSWORD: &str = "CorrectHorseBattery1!";
+
+async fn seed_user(pool: &PgPool, crypto: &AuthCrypto) -> Result<()> {
+    let user_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO users (id, username, display_name) VALUES ($1, $2, $2)",
+    )
+    .bind(user_id)
+    .bind(TEST_USERNAME)
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "INSERT INTO user_credentials (user_id, password_hash) VALUES ($1, $2)",
+    )
+    .bind(user_id)
+    .bind(crypto.hash_password(TEST_PASSWORD)?)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn expire_session(
+    pool: &PgPool,
+    session_id: Uuid,
+    ago: Duration,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE auth_sessions SET expires_at = NOW() - $1 WHERE id = $2",
+    )
+    .bind(ago)
+    .bind(session_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn expiry_is_checked_with_leeway(pool: PgPool) -> Result<()> {
+    let crypto = Arc::new(AuthCrypto::new("test-pepper", "test-token-key")?);
+    seed_user(&pool, &crypto).await?;
+    let service = create_authentication_service(pool.clone(), crypto)
+        .with_clock_skew_leeway(Duration::seconds(30));
+
+    let bundle = service
+        .authenticate_with_password(TEST_USERNAME, TEST_PASSWORD)
+        .await?;
+    let token = bundle.session_token.as_str();
+
+    expire_session(&pool, bundle.session_record_id, Duration::seconds(5))
+        .await?;
+    service.validate_session_token(token).await?;
+    service.introspect_session_token(token).await?;
+
+    expire_session(&pool, bundle.session_record_id, Duration::minutes(5))
+        .await?;
+    assert!(matches!(
+        service.validate_session_token(token).await,
+        Err(AuthenticationError::SessionExpired)
+    ));
+    assert!(matches!(
+        service.introspect_session_token(token).await,
+        Err(AuthenticationError::SessionExpired)
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Client-supplied device ids claimed by another user's device, against
+//! the Postgres repositories.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use ferrex_core::domain::users::auth::{
+    AuthCrypto,
+    domain::{
+        services::{
+            DeviceIdCollisionPolicy, DeviceTrustError, DeviceTrustService,
+        },
+        value_objects::DeviceFingerprint,
+    },
+    infrastructure::repositories::{
+        PostgresAuthEventRepository, PostgresAuthSessionRepository,
+        PostgresDeviceSessionRepository, PostgresRefreshTokenRepository,
+        PostgresUserAuthRepository,
+    },
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::auth::TestAuthHarness;
+
+fn build_service(
+    pool: &PgPool,
+    policy: DeviceIdCollisionPolicy,
+) -> Result<DeviceTrustService> {
+    let crypto = Arc::new(AuthCrypto::new("test-pepper", "test-token-key")?);
+    Ok(DeviceTrustService::new(
+        Arc::new(PostgresUserAuthRepository::new(pool.clone())),
+        Arc::new(PostgresDeviceSessionRepository::new(pool.clone(), crypto)),
+        Arc::new(PostgresAuthEventRepository::new(pool.clone())),
+        Arc::new(PostgresAuthSessionRepository::new(pool.clone())),
+        Arc::new(PostgresRefreshTokenRepository::new(pool.clone())),
+    )
+    .with_device_id_collision_policy(policy))
+}
+
+fn model(cpu: &'static str) -> impl Fn(Uuid) -> DeviceFingerprint {
+    move |device_id| {
+        DeviceFingerprint::new(
+            "Linux".to_string(),
+            Some(cpu.to_string()),
+            None,
+            Some(device_id.to_string()),
+            None,
+        )
+        .expect("valid fingerprint")
+    }
+}
+
+async fn collision_events(pool: &PgPool, user_id: Uuid) -> Result<i64> {
+    let count = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM auth_events
+        WHERE user_id = $1 AND event_type = 'device_id_collision'
+        "#,
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn collisions_are_reissued_or_rejected(pool: PgPool) -> Result<()> {
+    let harness = TestAuthHarness::new(pool.clone())?;
+    let owner = harness.create_user("owner", "StrongPassword123!").await?;
+    let other = harness.create_user("other", "StrongPassword123!").await?;
+    let device_id = Uuid::now_v7();
+
+    let strict = build_service(&pool, DeviceIdCollisionPolicy::Reject)?;
+    let (_, kept) = strict
+        .register_client_device(
+            owner,
+            device_id,
+            model("CPU"),
+            "Owner".to_string(),
+            None,
+        )
+        .await?;
+    assert_eq!(kept, device_id);
+
+    let rejected = strict
+        .register_client_device(
+            other,
+            device_id,
+            model("CPU2"),
+            "Other".to_string(),
+            None,
+        )
+        .await;
+    assert!(matches!(rejected, Err(DeviceTrustError::DeviceIdCollision)));
+    assert!(strict.is_device_id_contested(device_id).await?);
+    assert!(strict.list_devices(other).await?.is_empty());
+
+    let lenient = build_service(&pool, DeviceIdCollisionPolicy::Reissue)?;
+    let (session, reissued) = lenient
+        .register_client_device(
+            other,
+            device_id,
+            model("CPU2"),
+            "Other".to_string(),
+            None,
+        )
+        .await?;
+    assert_ne!(reissued, device_id);
+    assert!(!lenient.is_device_id_contested(reissued).await?);
+
+    let stored: Option<Uuid> = sqlx::query_scalar(
+        "SELECT client_device_id FROM auth_device_sessions WHERE id = $1",
+    )
+    .bind(session.id())
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(stored, Some(reissued));
+    assert_eq!(collision_events(&pool, other).await?, 2);
+    assert_eq!(collision_events(&pool, owner).await?, 0);
+
+    Ok(())
+}
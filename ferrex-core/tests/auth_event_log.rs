@@ -0,0 +1,125 @@
+//! Reading the auth audit log back: keyset paging and filters.
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use ferrex_core::domain::users::auth::{
+    domain::repositories::{
+        AuthAuditEventKind, AuthEventLog, AuthEventQuery, AuthEventRepository,
+    },
+    infrastructure::repositories::PostgresAuthEventRepository,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn seed_user(pool: &PgPool, username: &str) -> Result<Uuid> {
+    let user_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO users (id, username, display_name) VALUES ($1, $2, $2)",
+    )
+    .bind(user_id)
+    .bind(username)
+    .execute(pool)
+    .await?;
+    Ok(user_id)
+}
+
+fn event(
+    kind: AuthAuditEventKind,
+    user_id: Uuid,
+    minutes_ago: i64,
+) -> AuthEventLog {
+    AuthEventLog {
+        event_type: kind,
+        user_id: Some(user_id),
+        device_session_id: None,
+        session_id: None,
+        success: !matches!(kind, AuthAuditEventKind::PinLoginFailure),
+        failure_reason: None,
+        ip_address: Some("198.51.100.4".into()),
+        user_agent: Some("ferrex-tests".into()),
+        metadata: json!({}),
+        occurred_at: Utc::now() - Duration::minutes(minutes_ago),
+    }
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn pages_follow_the_cursor_newest_first(pool: PgPool) -> Result<()> {
+    let repo = PostgresAuthEventRepository::new(pool.clone());
+    let alice = seed_user(&pool, "alice").await?;
+    let bob = seed_user(&pool, "bob").await?;
+
+    let mut logs: Vec<AuthEventLog> = (0..5)
+        .map(|i| event(AuthAuditEventKind::SessionCreated, alice, i))
+        .collect();
+    logs.push(event(AuthAuditEventKind::SessionCreated, bob, 0));
+    repo.record(logs).await?;
+
+    let mut query = AuthEventQuery {
+        user_id: Some(alice),
+        limit: 2,
+        ..AuthEventQuery::default()
+    };
+    let mut seen = Vec::new();
+    loop {
+        let page = repo.list(&query).await?;
+        let Some(last) = page.last() else { break };
+        query.after = Some((last.occurred_at, last.id));
+        seen.extend(page);
+    }
+
+    assert_eq!(seen.len(), 5);
+    assert!(seen.iter().all(|event| event.user_id == Some(alice)));
+    assert!(
+        seen.windows(2)
+            .all(|pair| pair[0].occurred_at >= pair[1].occurred_at)
+    );
+    assert_eq!(seen[0].ip_address.as_deref(), Some("198.51.100.4"));
+    Ok(())
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn filters_by_type_and_time_range(pool: PgPool) -> Result<()> {
+    let repo = PostgresAuthEventRepository::new(pool.clone());
+    let user = seed_user(&pool, "carol").await?;
+    repo.record(vec![
+        event(AuthAuditEventKind::PinLoginFailure, user, 90),
+        event(AuthAuditEventKind::PinLoginFailure, user, 10),
+        event(AuthAuditEventKind::PinSet, user, 5),
+        event(AuthAuditEventKind::SessionCreated, user, 1),
+    ])
+    .await?;
+
+    let failures = repo
+        .list(&AuthEventQuery {
+            event_types: vec![AuthAuditEventKind::PinLoginFailure],
+            limit: 10,
+            ..AuthEventQuery::default()
+        })
+        .await?;
+    assert_eq!(failures.len(), 2);
+    assert!(failures.iter().all(|event| !event.success));
+
+    let last_hour = repo
+        .list(&AuthEventQuery {
+            event_types: vec![
+                AuthAuditEventKind::PinLoginFailure,
+                AuthAuditEventKind::PinSet,
+            ],
+            since: Some(Utc::now() - Duration::hours(1)),
+            until: Some(Utc::now() - Duration::minutes(2)),
+            limit: 10,
+            ..AuthEventQuery::default()
+        })
+        .await?;
+    let kinds: Vec<_> =
+        last_hour.iter().map(|event| event.event_type).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            AuthAuditEventKind::PinSet,
+            AuthAuditEventKind::PinLoginFailure
+        ]
+    );
+    Ok(())
+}
@@ -1,4 +1,5 @@
-//! Guards refresh-token reuse semantics (family revocation on reuse detection).
+//! Guards refresh-token reuse semantics (family revocation on reuse
+//! detection, and the retry grace window for legitimate clients).
 
 use std::sync::Arc;
 
@@ -9,6 +10,7 @@ use ferrex_core::domain::users::auth::{
         AuthenticationError, AuthenticationService,
         create_authentication_service,
     },
+    infrastructure::repositories::PostgresAuthEventRepository,
 };
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -20,7 +22,9 @@ fn build_service(
     pool: PgPool,
 ) -> Result<(AuthenticationService, Arc<AuthCrypto>)> {
     let crypto = Arc::new(AuthCrypto::new("test-pepper", "test-token-key")?);
-    let service = create_authentication_service(pool, crypto.clone());
+    let event_repo = Arc::new(PostgresAuthEventRepository::new(pool.clone()));
+    let service = create_authentication_service(pool, crypto.clone())
+        .with_event_repository(event_repo);
     Ok((service, crypto))
 }
 
@@ -54,6 +58,26 @@ async fn seed_user(pool: &PgPool, crypto: &AuthCrypto) -> Result<Uuid> {
     Ok(user_id)
 }
 
+/// Push the rotation of `token` outside the retry grace window.
+async fn expire_retry_grace(
+    pool: &PgPool,
+    crypto: &AuthCrypto,
+    token: &str,
+) -> Result<()> {
+    let token_hash = crypto.hash_token(token);
+    sqlx::query!(
+        r#"
+        UPDATE auth_refresh_tokens
+        SET used_at = NOW() - INTERVAL '10 minutes'
+        WHERE token_hash = $1
+        "#,
+        token_hash
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 #[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
 async fn refresh_reuse_revokes_family(pool: PgPool) -> Result<()> {
     let (service, crypto) = build_service(pool.clone())?;
@@ -68,9 +92,13 @@ async fn refresh_reuse_revokes_family(pool: PgPool) -> Result<()> {
 
     let rotated_bundle =
         service.refresh_session(initial_token.as_str()).await?;
+    expire_retry_grace(&pool, &crypto, initial_token.as_str()).await?;
 
     let reused = service.refresh_session(initial_token.as_str()).await;
-    assert!(matches!(reused, Err(AuthenticationError::SessionExpired)));
+    assert!(matches!(
+        reused,
+        Err(AuthenticationError::RefreshTokenReused)
+    ));
 
     let rotated_hash = crypto.hash_token(rotated_bundle.refresh_token.as_str());
     let rotated_record = sqlx::query!(
@@ -110,3 +138,156 @@ async fn refresh_reuse_revokes_family(pool: PgPool) -> Result<()> {
 
     Ok(())
 }
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn refresh_reuse_revokes_sessions_and_audits(pool: PgPool) -> Result<()> {
+    let (service, crypto) = build_service(pool.clone())?;
+    let user_id = seed_user(&pool, &crypto).await?;
+
+    let initial_bundle = service
+        .authenticate_with_password(TEST_USERNAME, TEST_PASSWORD)
+        .await?;
+    let initial_token = initial_bundle.refresh_token.clone();
+
+    let rotated_bundle =
+        service.refresh_session(initial_token.as_str()).await?;
+    expire_retry_grace(&pool, &crypto, initial_token.as_str()).await?;
+
+    let reused = service.refresh_session(initial_token.as_str()).await;
+    assert!(matches!(
+        reused,
+        Err(AuthenticationError::RefreshTokenReused)
+    ));
+
+    for session_id in [
+        initial_bundle.session_record_id,
+        rotated_bundle.session_record_id,
+    ] {
+        let revoked: bool = sqlx::query_scalar!(
+            "SELECT revoked FROM auth_sessions WHERE id = $1",
+            session_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert!(revoked, "sessions of a reused family must be revoked");
+    }
+
+    let rotated_session = service
+        .validate_session_token(rotated_bundle.session_token.as_str())
+        .await;
+    assert!(matches!(
+        rotated_session,
+        Err(AuthenticationError::SessionExpired)
+    ));
+
+    let audit = sqlx::query!(
+        r#"
+        SELECT success, metadata
+        FROM auth_events
+        WHERE user_id = $1 AND event_type = 'refresh_token_reuse'
+        "#,
+        user_id
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert!(!audit.success);
+    assert_eq!(audit.metadata["severity"], "high");
+
+    // The stolen successor cannot be used to re-enter either.
+    let successor = service
+        .refresh_session(rotated_bundle.refresh_token.as_str())
+        .await;
+    assert!(successor.is_err());
+
+    Ok(())
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn refresh_retry_within_grace_window_rotates_again(
+    pool: PgPool,
+) -> Result<()> {
+    let (service, crypto) = build_service(pool.clone())?;
+    seed_user(&pool, &crypto).await?;
+
+    let initial_bundle = service
+        .authenticate_with_password(TEST_USERNAME, TEST_PASSWORD)
+        .await?;
+    let initial_token = initial_bundle.refresh_token.clone();
+
+    // First response is "lost"; the client retries with the same token.
+    let lost_bundle = service.refresh_session(initial_token.as_str()).await?;
+    let retried_bundle =
+        service.refresh_session(initial_token.as_str()).await?;
+
+    assert_eq!(
+        retried_bundle.refresh_token.family_id(),
+        initial_token.family_id()
+    );
+
+    let lost = service
+        .refresh_session(lost_bundle.refresh_token.as_str())
+        .await;
+    assert!(
+        matches!(lost, Err(AuthenticationError::SessionExpired)),
+        "the unreceived successor is retired, not treated as reuse"
+    );
+    let lost_session = service
+        .validate_session_token(lost_bundle.session_token.as_str())
+        .await;
+    assert!(lost_session.is_err());
+
+    // The retried chain keeps working.
+    service
+        .refresh_session(retried_bundle.refresh_token.as_str())
+        .await?;
+
+    // A second replay of the original token is no longer a retry.
+    let replay = service.refresh_session(initial_token.as_str()).await;
+    assert!(matches!(
+        replay,
+        Err(AuthenticationError::RefreshTokenReused)
+    ));
+
+    Ok(())
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn refresh_replay_after_successor_used_is_reuse(
+    pool: PgPool,
+) -> Result<()> {
+    let (service, crypto) = build_service(pool.clone())?;
+    seed_user(&pool, &crypto).await?;
+
+    let initial_bundle = service
+        .authenticate_with_password(TEST_USERNAME, TEST_PASSWORD)
+        .await?;
+    let initial_token = initial_bundle.refresh_token.clone();
+
+    let rotated_bundle =
+        service.refresh_session(initial_token.as_str()).await?;
+    service
+        .refresh_session(rotated_bundle.refresh_token.as_str())
+        .await?;
+
+    // Still inside the grace window, but the chain has already moved on.
+    let replay = service.refresh_session(initial_token.as_str()).await;
+    assert!(matches!(
+        replay,
+        Err(AuthenticationError::RefreshTokenReused)
+    ));
+
+    let remaining_active: i64 = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*)
+        FROM auth_refresh_tokens
+        WHERE family_id = $1 AND revoked = FALSE
+        "#,
+        initial_token.family_id()
+    )
+    .fetch_one(&pool)
+    .await?
+    .unwrap_or(0);
+    assert_eq!(remaining_active, 0);
+
+    Ok(())
+}
@@ -0,0 +1,237 @@
+//! Content-rating filters over listings, indices and search.
+
+use ferrex_core::api::types::FilterIndicesRequest;
+use ferrex_core::database::repositories::indices::PostgresIndicesRepository;
+use ferrex_core::database::repositories::query::PostgresQueryRepository;
+use ferrex_core::database::repository_ports::indices::IndicesRepository;
+use ferrex_core::database::repository_ports::query::QueryRepository;
+use ferrex_core::player_prelude::*;
+use ferrex_core::query::MediaQueryBuilder;
+use ferrex_core::types::certification_age;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn seed_library(pool: &PgPool, id: Uuid) {
+    sqlx::query(
+        r#"
+        INSERT INTO libraries (id, name, library_type, paths)
+        VALUES ($1, 'family', 'movies', ARRAY['/tmp'])
+        "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .expect("insert library");
+}
+
+/// A movie rated `certification`, stored the way the metadata pipeline
+/// stores it.
+async fn seed_movie(
+    pool: &PgPool,
+    library_id: Uuid,
+    tmdb_id: i64,
+    title: &str,
+    certification: Option<&str>,
+) -> Uuid {
+    let movie_id = Uuid::new_v4();
+    let file_id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO media_files (id, library_id, media_id, media_type, file_path, filename, file_size)
+        VALUES ($1, $2, $3, 'movie', $4, $5, 123)
+        "#,
+    )
+    .bind(file_id)
+    .bind(library_id)
+    .bind(movie_id)
+    .bind(format!("/tmp/{movie_id}.mkv"))
+    .bind(format!("{movie_id}.mkv"))
+    .execute(pool)
+    .await
+    .expect("insert media_file");
+
+    sqlx::query(
+        r#"
+        INSERT INTO movie_references (id, library_id, file_id, tmdb_id, title, batch_id)
+        VALUES ($1, $2, $3, $4, $5, 1)
+        "#,
+    )
+    .bind(movie_id)
+    .bind(library_id)
+    .bind(file_id)
+    .bind(tmdb_id)
+    .bind(title)
+    .execute(pool)
+    .await
+    .expect("insert movie_reference");
+
+    sqlx::query(
+        r#"
+        INSERT INTO movie_metadata (
+            movie_id, library_id, batch_id, tmdb_id, title,
+            primary_certification, certification_age
+        )
+        VALUES ($1, $2, 1, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(movie_id)
+    .bind(library_id)
+    .bind(tmdb_id)
+    .bind(title)
+    .bind(certification)
+    .bind(certification.and_then(certification_age).map(i16::from))
+    .execute(pool)
+    .await
+    .expect("insert movie_metadata");
+
+    movie_id
+}
+
+struct Seeded {
+    library_id: Uuid,
+    toy_story: Uuid,
+    home_movie: Uuid,
+}
+
+async fn seed_family_library(pool: &PgPool) -> Seeded {
+    let library_id = Uuid::new_v4();
+    seed_library(pool, library_id).await;
+    let toy_story =
+        seed_movie(pool, library_id, 862, "Toy Story", Some("G")).await;
+    seed_movie(pool, library_id, 949, "Heat", Some("R")).await;
+    let home_movie =
+        seed_movie(pool, library_id, 1, "Home Movie", Some("NR")).await;
+    Seeded {
+        library_id,
+        toy_story,
+        home_movie,
+    }
+}
+
+fn movie_ids(results: &[MediaWithStatus]) -> Vec<Uuid> {
+    let mut ids: Vec<Uuid> =
+        results.iter().map(|item| *item.id.as_uuid()).collect();
+    ids.sort();
+    ids
+}
+
+fn sorted(mut ids: Vec<Uuid>) -> Vec<Uuid> {
+    ids.sort();
+    ids
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn listings_exclude_ratings_above_the_maximum(pool: PgPool) {
+    let seeded = seed_family_library(&pool).await;
+    let repo = PostgresQueryRepository::new(pool);
+
+    let rated_only = repo
+        .query_media(
+            &MediaQueryBuilder::new()
+                .movies_only()
+                .certification_max("PG", false)
+                .build(),
+        )
+        .await
+        .expect("query_media");
+    assert_eq!(movie_ids(&rated_only), [seeded.toy_story]);
+
+    // A bare age works as the threshold too.
+    let by_age = repo
+        .query_media(
+            &MediaQueryBuilder::new()
+                .movies_only()
+                .certification_max("10", false)
+                .build(),
+        )
+        .await
+        .expect("query_media");
+    assert_eq!(movie_ids(&by_age), [seeded.toy_story]);
+
+    let err = repo
+        .query_media(
+            &MediaQueryBuilder::new()
+                .movies_only()
+                .certification_max("family friendly", false)
+                .build(),
+        )
+        .await
+        .expect_err("unknown threshold");
+    assert!(err.to_string().contains("certification"), "{err}");
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn unrated_media_is_kept_only_when_asked_for(pool: PgPool) {
+    let seeded = seed_family_library(&pool).await;
+    let repo = PostgresQueryRepository::new(pool.clone());
+
+    let listing = repo
+        .query_media(
+            &MediaQueryBuilder::new()
+                .movies_only()
+                .certification_max("PG", true)
+                .build(),
+        )
+        .await
+        .expect("query_media");
+    assert_eq!(
+        movie_ids(&listing),
+        sorted(vec![seeded.toy_story, seeded.home_movie])
+    );
+
+    let indices = PostgresIndicesRepository::new(pool.clone());
+    indices
+        .rebuild_movie_sort_positions(LibraryId(seeded.library_id))
+        .await
+        .expect("rebuild sort positions");
+    let mut spec = FilterIndicesRequest {
+        media_type: Some(MediaTypeFilter::Movie),
+        genres: Vec::new(),
+        year_range: None,
+        rating_range: None,
+        resolution_range: None,
+        watch_status: None,
+        search: None,
+        sort: Some(SortBy::Title),
+        order: Some(SortOrder::Ascending),
+        expr: None,
+        certification_max: Some("PG".into()),
+        include_unrated: false,
+    };
+    let excluded = indices
+        .fetch_filtered_movie_indices(LibraryId(seeded.library_id), &spec, None)
+        .await
+        .expect("filtered indices");
+    assert_eq!(excluded.len(), 1);
+    spec.include_unrated = true;
+    let included = indices
+        .fetch_filtered_movie_indices(LibraryId(seeded.library_id), &spec, None)
+        .await
+        .expect("filtered indices");
+    assert_eq!(included.len(), 2);
+
+    let mut query = MediaSearchQuery {
+        q: "home movie".into(),
+        certification_max: Some("PG".into()),
+        ..MediaSearchQuery::default()
+    };
+    let fields = query.search_fields().expect("fields");
+    let hits = repo
+        .search_media(&query, &fields, 10)
+        .await
+        .expect("search_media");
+    assert!(
+        hits.iter()
+            .all(|hit| *hit.id.as_uuid() != seeded.home_movie)
+    );
+
+    query.include_unrated = true;
+    let hits = repo
+        .search_media(&query, &fields, 10)
+        .await
+        .expect("search_media");
+    assert!(
+        hits.iter()
+            .any(|hit| *hit.id.as_uuid() == seeded.home_movie)
+    );
+}
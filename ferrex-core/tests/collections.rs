@@ -0,0 +1,191 @@
+//! Integration coverage for per-user collections.
+
+use ferrex_core::api::types::UpdateCollectionRequest;
+use ferrex_core::database::repositories::collections::PostgresCollectionsRepository;
+use ferrex_core::database::repository_ports::collections::CollectionsRepository;
+use ferrex_core::error::MediaError;
+use ferrex_core::types::ids::MovieID;
+use ferrex_core::types::media_id::MediaID;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn seed_user(pool: &PgPool, username: &str) -> Uuid {
+    let id = Uuid::now_v7();
+    sqlx::query(
+        "INSERT INTO users (id, username, display_name) VALUES ($1, $2, $2)",
+    )
+    .bind(id)
+    .bind(username)
+    .execute(pool)
+    .await
+    .expect("insert user");
+    id
+}
+
+async fn seed_library(pool: &PgPool) -> Uuid {
+    let id = Uuid::now_v7();
+    sqlx::query(
+        r#"
+        INSERT INTO libraries (id, name, library_type, paths)
+        VALUES ($1, 'collections-movies', 'movies', ARRAY['/movies'])
+        "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .expect("insert library");
+    id
+}
+
+async fn seed_movie(
+    pool: &PgPool,
+    library_id: Uuid,
+    tmdb_id: i64,
+    title: &str,
+) -> MediaID {
+    let movie_id = Uuid::now_v7();
+    let file_id = Uuid::now_v7();
+    sqlx::query(
+        r#"
+        INSERT INTO media_files (
+            id, library_id, media_id, media_type, file_path, filename, file_size
+        )
+        VALUES ($1, $2, $3, 'movie', $4, $5, 1024)
+        "#,
+    )
+    .bind(file_id)
+    .bind(library_id)
+    .bind(movie_id)
+    .bind(format!("/movies/{title}.mkv"))
+    .bind(format!("{title}.mkv"))
+    .execute(pool)
+    .await
+    .expect("insert media file");
+    sqlx::query(
+        r#"
+        INSERT INTO movie_references (id, library_id, file_id, tmdb_id, title)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(movie_id)
+    .bind(library_id)
+    .bind(file_id)
+    .bind(tmdb_id)
+    .bind(title)
+    .execute(pool)
+    .await
+    .expect("insert movie reference");
+    MediaID::Movie(MovieID(movie_id))
+}
+
+fn titles(items: &[ferrex_core::api::types::CollectionItem]) -> Vec<&str> {
+    items.iter().map(|item| item.title.as_str()).collect()
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn collections_keep_order_and_follow_media_deletion(pool: PgPool) {
+    let user = seed_user(&pool, "collector").await;
+    let library = seed_library(&pool).await;
+    let heat = seed_movie(&pool, library, 949, "Heat").await;
+    let ronin = seed_movie(&pool, library, 8195, "Ronin").await;
+    let thief = seed_movie(&pool, library, 11_524, "Thief").await;
+    let repo = PostgresCollectionsRepository::new(pool.clone());
+
+    let collection = repo
+        .create_collection(user, "  Comfort Movies ", Some(""))
+        .await
+        .expect("create collection");
+    assert_eq!(collection.name, "Comfort Movies");
+    assert_eq!(collection.description, None);
+
+    let added = repo
+        .add_items(user, collection.id, &[ronin, heat, ronin])
+        .await
+        .expect("add items");
+    assert_eq!(added, 2);
+    repo.add_items(user, collection.id, &[thief, heat])
+        .await
+        .expect("append items");
+
+    let items = repo.list_items(user, collection.id).await.expect("items");
+    assert_eq!(titles(&items), ["Ronin", "Heat", "Thief"]);
+    assert!(!items[0].watch.completed);
+
+    repo.reorder_items(user, collection.id, &[thief, ronin, heat])
+        .await
+        .expect("reorder");
+    let items = repo.list_items(user, collection.id).await.expect("items");
+    assert_eq!(titles(&items), ["Thief", "Ronin", "Heat"]);
+
+    let partial = repo.reorder_items(user, collection.id, &[thief]).await;
+    assert!(matches!(partial, Err(MediaError::Conflict(_))));
+
+    // Deleting a movie drops it from the collection, not the collection.
+    sqlx::query("DELETE FROM movie_references WHERE id = $1")
+        .bind(ronin.as_uuid())
+        .execute(&pool)
+        .await
+        .expect("delete movie");
+    let items = repo.list_items(user, collection.id).await.expect("items");
+    assert_eq!(titles(&items), ["Thief", "Heat"]);
+    let collection = repo
+        .get_collection(user, collection.id)
+        .await
+        .expect("load collection")
+        .expect("collection survives");
+    assert_eq!(collection.item_count, 2);
+
+    assert!(
+        repo.remove_item(user, collection.id, *heat.as_uuid())
+            .await
+            .expect("remove item")
+    );
+    assert!(
+        !repo
+            .remove_item(user, collection.id, *heat.as_uuid())
+            .await
+            .expect("remove item again")
+    );
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn collections_are_private_to_their_owner(pool: PgPool) {
+    let owner = seed_user(&pool, "owner").await;
+    let other = seed_user(&pool, "other").await;
+    let library = seed_library(&pool).await;
+    let heat = seed_movie(&pool, library, 949, "Heat").await;
+    let repo = PostgresCollectionsRepository::new(pool.clone());
+
+    let collection = repo
+        .create_collection(owner, "Crime", None)
+        .await
+        .expect("create collection");
+    let duplicate = repo.create_collection(owner, "crime", None).await;
+    assert!(matches!(duplicate, Err(MediaError::Conflict(_))));
+    repo.create_collection(other, "Crime", None)
+        .await
+        .expect("names are per user");
+
+    assert!(repo.list_collections(other).await.unwrap().len() == 1);
+    assert!(
+        repo.get_collection(other, collection.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let foreign = repo.add_items(other, collection.id, &[heat]).await;
+    assert!(matches!(foreign, Err(MediaError::NotFound(_))));
+    let rename = repo
+        .update_collection(
+            other,
+            collection.id,
+            &UpdateCollectionRequest {
+                name: Some("Mine now".into()),
+                description: None,
+            },
+        )
+        .await;
+    assert!(matches!(rename, Err(MediaError::NotFound(_))));
+    assert!(!repo.delete_collection(other, collection.id).await.unwrap());
+    assert!(repo.delete_collection(owner, collection.id).await.unwrap());
+}
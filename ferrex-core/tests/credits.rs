@@ -0,0 +1,207 @@
+//! Integration coverage for cast/crew lookups and person appearances.
+
+use ferrex_core::database::repositories::credits::PostgresCreditsRepository;
+use ferrex_core::database::repository_ports::credits::CreditsRepository;
+use ferrex_core::types::ids::{LibraryId, MovieID};
+use ferrex_core::types::media_id::MediaID;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn seed_library(pool: &PgPool, name: &str) -> Uuid {
+    let id = Uuid::now_v7();
+    sqlx::query(
+        r#"
+        INSERT INTO libraries (id, name, library_type, paths)
+        VALUES ($1, $2, 'movies', ARRAY['/movies'])
+        "#,
+    )
+    .bind(id)
+    .bind(name)
+    .execute(pool)
+    .await
+    .expect("insert library");
+    id
+}
+
+async fn seed_movie(
+    pool: &PgPool,
+    library_id: Uuid,
+    tmdb_id: i64,
+    title: &str,
+) -> Uuid {
+    let movie_id = Uuid::now_v7();
+    let file_id = Uuid::now_v7();
+    sqlx::query(
+        r#"
+        INSERT INTO media_files (
+            id, library_id, media_id, media_type, file_path, filename, file_size
+        )
+        VALUES ($1, $2, $3, 'movie', $4, $5, 1024)
+        "#,
+    )
+    .bind(file_id)
+    .bind(library_id)
+    .bind(movie_id)
+    .bind(format!("/movies/{movie_id}.mkv"))
+    .bind(format!("{movie_id}.mkv"))
+    .execute(pool)
+    .await
+    .expect("insert media file");
+    sqlx::query(
+        r#"
+        INSERT INTO movie_references (id, library_id, file_id, tmdb_id, title)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(movie_id)
+    .bind(library_id)
+    .bind(file_id)
+    .bind(tmdb_id)
+    .bind(title)
+    .execute(pool)
+    .await
+    .expect("insert movie reference");
+    movie_id
+}
+
+async fn seed_person(pool: &PgPool, tmdb_id: i64, name: &str) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO persons (tmdb_id, name) VALUES ($1, $2) RETURNING id",
+    )
+    .bind(tmdb_id)
+    .bind(name)
+    .fetch_one(pool)
+    .await
+    .expect("insert person")
+}
+
+async fn seed_cast(
+    pool: &PgPool,
+    movie_id: Uuid,
+    library_id: Uuid,
+    person_id: Uuid,
+    character: &str,
+    order: i32,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO movie_cast (
+            movie_id, library_id, batch_id, person_tmdb_id, person_id,
+            "character", order_index
+        )
+        SELECT $1, $2, 1, p.tmdb_id, p.id, $4, $5 FROM persons p WHERE p.id = $3
+        "#,
+    )
+    .bind(movie_id)
+    .bind(library_id)
+    .bind(person_id)
+    .bind(character)
+    .bind(order)
+    .execute(pool)
+    .await
+    .expect("insert cast");
+}
+
+async fn seed_crew(
+    pool: &PgPool,
+    movie_id: Uuid,
+    library_id: Uuid,
+    person_id: Uuid,
+    department: &str,
+    job: &str,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO movie_crew (
+            movie_id, library_id, batch_id, person_tmdb_id, person_id,
+            department, job
+        )
+        SELECT $1, $2, 1, p.tmdb_id, p.id, $4, $5 FROM persons p WHERE p.id = $3
+        "#,
+    )
+    .bind(movie_id)
+    .bind(library_id)
+    .bind(person_id)
+    .bind(department)
+    .bind(job)
+    .execute(pool)
+    .await
+    .expect("insert crew");
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn credits_list_cast_in_order_and_key_crew_only(pool: PgPool) {
+    let library = seed_library(&pool, "credits-movies").await;
+    let heat = seed_movie(&pool, library, 949, "Heat").await;
+    let pacino = seed_person(&pool, 1158, "Al Pacino").await;
+    let de_niro = seed_person(&pool, 380, "Robert De Niro").await;
+    let mann = seed_person(&pool, 638, "Michael Mann").await;
+    let grip = seed_person(&pool, 9999, "Key Grip").await;
+    seed_cast(&pool, heat, library, de_niro, "Neil McCauley", 1).await;
+    seed_cast(&pool, heat, library, pacino, "Vincent Hanna", 0).await;
+    seed_crew(&pool, heat, library, mann, "Writing", "Screenplay").await;
+    seed_crew(&pool, heat, library, mann, "Directing", "Director").await;
+    seed_crew(&pool, heat, library, grip, "Crew", "Key Grip").await;
+    let repo = PostgresCreditsRepository::new(pool.clone());
+
+    let credits = repo
+        .media_credits(heat)
+        .await
+        .expect("credits")
+        .expect("movie exists");
+    assert_eq!(credits.media_id, MediaID::Movie(MovieID(heat)));
+    let cast: Vec<_> = credits.cast.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(cast, ["Al Pacino", "Robert De Niro"]);
+    let crew: Vec<_> = credits.crew.iter().map(|c| c.job.as_str()).collect();
+    assert_eq!(crew, ["Director", "Screenplay"]);
+
+    assert!(
+        repo.media_credits(Uuid::now_v7())
+            .await
+            .expect("credits")
+            .is_none()
+    );
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn appearances_group_roles_and_filter_by_library(pool: PgPool) {
+    let movies = seed_library(&pool, "credits-movies").await;
+    let classics = seed_library(&pool, "credits-classics").await;
+    let heat = seed_movie(&pool, movies, 949, "Heat").await;
+    let godfather = seed_movie(&pool, classics, 238, "The Godfather").await;
+    let pacino = seed_person(&pool, 1158, "Al Pacino").await;
+    seed_cast(&pool, heat, movies, pacino, "Vincent Hanna", 0).await;
+    seed_crew(&pool, heat, movies, pacino, "Production", "Producer").await;
+    seed_cast(&pool, godfather, classics, pacino, "Michael Corleone", 2).await;
+    let repo = PostgresCreditsRepository::new(pool.clone());
+
+    let all = repo
+        .person_appearances(pacino, None)
+        .await
+        .expect("appearances")
+        .expect("person exists");
+    assert_eq!(all.person.tmdb_id, 1158);
+    assert_eq!(all.appearances.len(), 2);
+    let heat_entry = all
+        .appearances
+        .iter()
+        .find(|a| a.media_id == MediaID::Movie(MovieID(heat)))
+        .expect("heat listed once");
+    assert_eq!(heat_entry.characters, ["Vincent Hanna"]);
+    assert_eq!(heat_entry.jobs, ["Producer"]);
+
+    let classics_only = repo
+        .person_appearances(pacino, Some(LibraryId(classics)))
+        .await
+        .expect("appearances")
+        .expect("person exists");
+    assert_eq!(classics_only.appearances.len(), 1);
+    assert_eq!(classics_only.appearances[0].title, "The Godfather");
+
+    assert!(
+        repo.person_appearances(Uuid::now_v7(), None)
+            .await
+            .expect("appearances")
+            .is_none()
+    );
+}
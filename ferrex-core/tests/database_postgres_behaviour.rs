@@ -6,12 +6,26 @@ use anyhow::Result;
 use chrono::{Duration, Utc};
 use ferrex_core::database::postgres::PostgresDatabase;
 use ferrex_core::database::repositories::folder_inventory::PostgresFolderInventoryRepository;
+use ferrex_core::database::repositories::library::PostgresLibraryRepository;
+use ferrex_core::database::repositories::media_references::PostgresMediaReferencesRepository;
+use ferrex_core::database::repositories::watch_status::PostgresWatchStatusRepository;
 use ferrex_core::database::repository_ports::folder_inventory::FolderInventoryRepository;
+use ferrex_core::database::repository_ports::library::LibraryRepository;
+use ferrex_core::database::repository_ports::media_references::MediaReferencesRepository;
 use ferrex_core::database::repository_ports::processing_status::ProcessingStatusRepository;
+use ferrex_core::database::repository_ports::watch_status::WatchStatusRepository;
 use ferrex_core::database::traits::{
     FolderProcessingStatus, FolderScanFilters, MediaProcessingStatus,
 };
-use ferrex_core::types::LibraryId;
+use ferrex_core::domain::watch::{
+    EpisodeKey, EpisodeStatus, MarkEpisodeWatchedRequest, PortableMediaKey,
+    UpdateProgressRequest, WatchAdvancePolicy, WatchScope, WatchStateEntry,
+    WatchStateExport,
+};
+use ferrex_core::error::MediaError;
+use ferrex_core::types::{
+    EpisodeID, LibraryId, MediaID, SeasonID, SeriesID, VideoMediaType,
+};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -281,3 +295,586 @@ async fn folder_inventory_filters_are_bound(pool: PgPool) -> Result<()> {
 
     Ok(())
 }
+
+#[sqlx::test(
+    migrator = "ferrex_core::MIGRATOR",
+    fixtures(
+        path = "../fixtures",
+        scripts("test_libraries", "media_processing_base")
+    )
+)]
+async fn library_stats_aggregates_library_contents(pool: PgPool) -> Result<()> {
+    let repo = PostgresLibraryRepository::new(pool);
+
+    let stats = repo
+        .library_stats(fixture_library_id())
+        .await?
+        .expect("fixture library exists");
+    assert_eq!(stats.movies, 1);
+    assert_eq!(stats.series, 0);
+    assert_eq!(stats.episodes, 0);
+    assert_eq!(stats.total_files, 3);
+    assert_eq!(stats.total_size, 6);
+    assert_eq!(stats.with_metadata, 0);
+    assert_eq!(stats.without_metadata, 1);
+    assert_eq!(stats.without_poster, 1);
+    assert_eq!(stats.missing_files, None);
+
+    let empty = repo
+        .library_stats(LibraryId(
+            Uuid::parse_str("cccccccc-cccc-cccc-cccc-cccccccccccc").unwrap(),
+        ))
+        .await?
+        .expect("fixture library exists");
+    assert_eq!(empty.total_files, 0);
+    assert_eq!(empty.total_size, 0);
+
+    assert!(
+        repo.library_stats(LibraryId(Uuid::now_v7()))
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[sqlx::test(
+    migrator = "ferrex_core::MIGRATOR",
+    fixtures(
+        path = "../fixtures",
+        scripts("test_libraries", "media_processing_base")
+    )
+)]
+async fn storage_usage_reports_sizes_and_recent_growth(
+    pool: PgPool,
+) -> Result<()> {
+    // The third file was discovered well before the growth window.
+    sqlx::query(
+        "UPDATE media_files SET discovered_at = now() - interval '90 days' \
+         WHERE id = $1",
+    )
+    .bind(fixture_media_file("33333333-3333-3333-3333-333333333333"))
+    .execute(&pool)
+    .await?;
+
+    let repo = PostgresLibraryRepository::new(pool);
+    let usage = repo.storage_usage(30).await?;
+    assert_eq!(usage.len(), 3, "every library is reported");
+
+    let library = usage
+        .iter()
+        .find(|usage| usage.library_id == fixture_library_id())
+        .expect("fixture library reported");
+    assert_eq!(library.total_files, 3);
+    assert_eq!(library.total_size, 6);
+    assert_eq!(library.image_cache_size, 0);
+    assert_eq!(library.growth.window_days, 30);
+    assert_eq!(library.growth.added_files, 2);
+    assert_eq!(library.growth.added_size, 3);
+    assert_eq!(library.growth.bytes_per_day, 0);
+    assert_eq!(library.growth.projected_size, 9);
+
+    let empty = usage
+        .iter()
+        .find(|usage| usage.library_name == "Fixture Library C")
+        .expect("empty library reported");
+    assert_eq!(empty.total_files, 0);
+    assert_eq!(empty.growth.added_size, 0);
+
+    Ok(())
+}
+
+async fn seed_watch_user(pool: &PgPool) -> Result<Uuid> {
+    let user_id = Uuid::now_v7();
+    sqlx::query!(
+        r#"
+        INSERT INTO users (id, username, display_name)
+        VALUES ($1, $2, $3)
+        "#,
+        user_id,
+        format!("viewer-{}", user_id.simple()),
+        "Viewer"
+    )
+    .execute(pool)
+    .await?;
+    Ok(user_id)
+}
+
+fn movie_progress(
+    media_id: Uuid,
+    position: f32,
+    reported_at: i64,
+) -> UpdateProgressRequest {
+    UpdateProgressRequest {
+        media_id,
+        media_type: VideoMediaType::Movie,
+        position,
+        duration: 7200.0,
+        episode: None,
+        last_media_uuid: Some(media_id),
+        reported_at: Some(reported_at),
+        reset: false,
+    }
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn watch_progress_rejects_out_of_order_reports(
+    pool: PgPool,
+) -> Result<()> {
+    let user_id = seed_watch_user(&pool).await?;
+    let repo = PostgresWatchStatusRepository::new(pool);
+    let media_id = Uuid::now_v7();
+    let base = Utc::now().timestamp_millis() - 60_000;
+
+    // Device A watched further and synced first; device B reports an older
+    // position observed before A's report.
+    repo.update_watch_progress(
+        user_id,
+        &movie_progress(media_id, 3600.0, base + 2_000),
+    )
+    .await?;
+    let late = repo
+        .update_watch_progress(
+            user_id,
+            &movie_progress(media_id, 1200.0, base + 1_000),
+        )
+        .await;
+    assert!(matches!(late, Err(MediaError::Conflict(_))));
+
+    // A newer report that would rewind is also refused without reset.
+    let rewind = repo
+        .update_watch_progress(
+            user_id,
+            &movie_progress(media_id, 1800.0, base + 3_000),
+        )
+        .await;
+    assert!(matches!(rewind, Err(MediaError::Conflict(_))));
+
+    repo.update_watch_progress(
+        user_id,
+        &movie_progress(media_id, 4000.0, base + 4_000),
+    )
+    .await?;
+
+    let state = repo.get_user_watch_state(user_id).await?;
+    assert_eq!(state.in_progress[&media_id].position, 4000.0);
+
+    Ok(())
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn watch_progress_reset_starts_a_rewatch(pool: PgPool) -> Result<()> {
+    let user_id = seed_watch_user(&pool).await?;
+    let repo = PostgresWatchStatusRepository::new(pool);
+    let media_id = Uuid::now_v7();
+    let base = Utc::now().timestamp_millis() - 60_000;
+
+    repo.update_watch_progress(
+        user_id,
+        &movie_progress(media_id, 7100.0, base + 1_000),
+    )
+    .await?;
+    assert!(repo.is_media_completed(user_id, &media_id).await?);
+
+    // A report captured before completion must not resurrect progress.
+    let stale = repo
+        .update_watch_progress(
+            user_id,
+            &movie_progress(media_id, 3000.0, base + 500),
+        )
+        .await;
+    assert!(matches!(stale, Err(MediaError::Conflict(_))));
+
+    let mut rewatch = movie_progress(media_id, 30.0, base + 2_000);
+    rewatch.reset = true;
+    repo.update_watch_progress(user_id, &rewatch).await?;
+
+    assert!(!repo.is_media_completed(user_id, &media_id).await?);
+    let state = repo.get_user_watch_state(user_id).await?;
+    assert_eq!(state.in_progress[&media_id].position, 30.0);
+
+    // Rewinding an in-progress rewatch also needs an explicit reset.
+    let mut restart = movie_progress(media_id, 0.5, base + 3_000);
+    assert!(matches!(
+        repo.update_watch_progress(user_id, &restart).await,
+        Err(MediaError::Conflict(_))
+    ));
+    restart.reset = true;
+    repo.update_watch_progress(user_id, &restart).await?;
+
+    Ok(())
+}
+
+/// Seeds one series with two seasons; the fixture media files back the
+/// episodes. Returns the series id and the season ids in order.
+async fn seed_watch_series(pool: &PgPool) -> Result<(SeriesID, [SeasonID; 2])> {
+    let library_id = fixture_library_id().to_uuid();
+    let series_id = Uuid::now_v7();
+    let seasons = [Uuid::now_v7(), Uuid::now_v7()];
+
+    sqlx::query!(
+        "INSERT INTO series (id, library_id, tmdb_id, title) VALUES ($1, $2, 4242, 'Fixture Show')",
+        series_id,
+        library_id
+    )
+    .execute(pool)
+    .await?;
+    for (index, season_id) in seasons.iter().enumerate() {
+        sqlx::query!(
+            r#"
+            INSERT INTO season_references (id, series_id, season_number, tmdb_series_id, library_id)
+            VALUES ($1, $2, $3, 4242, $4)
+            "#,
+            season_id,
+            series_id,
+            index as i16 + 1,
+            library_id
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    let episodes = [
+        (
+            seasons[0],
+            1_i16,
+            1_i16,
+            "11111111-1111-1111-1111-111111111111",
+        ),
+        (seasons[0], 1, 2, "22222222-2222-2222-2222-222222222222"),
+        (seasons[1], 2, 1, "33333333-3333-3333-3333-333333333333"),
+    ];
+    for (season_id, season_number, episode_number, file_id) in episodes {
+        sqlx::query!(
+            r#"
+            INSERT INTO episode_references (
+                series_id, season_id, file_id, season_number, episode_number, tmdb_series_id
+            )
+            VALUES ($1, $2, $3, $4, $5, 4242)
+            "#,
+            series_id,
+            season_id,
+            fixture_media_file(file_id),
+            season_number,
+            episode_number
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    Ok((SeriesID(series_id), seasons.map(SeasonID)))
+}
+
+async fn completed_identities(pool: &PgPool, user_id: Uuid) -> Result<i64> {
+    Ok(sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM user_episode_state
+        WHERE user_id = $1 AND tmdb_series_id = 4242 AND is_completed
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?)
+}
+
+#[sqlx::test(
+    migrator = "ferrex_core::MIGRATOR",
+    fixtures(
+        path = "../fixtures",
+        scripts("test_libraries", "media_processing_base")
+    )
+)]
+async fn bulk_watch_marks_seasons_and_series(pool: PgPool) -> Result<()> {
+    let user_id = seed_watch_user(&pool).await?;
+    let (series_id, [season_one, season_two]) =
+        seed_watch_series(&pool).await?;
+    let repo = PostgresWatchStatusRepository::new(pool.clone());
+
+    // An in-progress episode is superseded by the bulk change.
+    repo.update_watch_progress(
+        user_id,
+        &UpdateProgressRequest {
+            media_type: VideoMediaType::Episode,
+            ..movie_progress(
+                fixture_media_file("11111111-1111-1111-1111-111111111111"),
+                600.0,
+                Utc::now().timestamp_millis() - 60_000,
+            )
+        },
+    )
+    .await?;
+
+    let update = repo
+        .set_scope_watched(user_id, WatchScope::Season(season_one), true)
+        .await?;
+    assert_eq!(update.series_id, series_id);
+    assert_eq!(update.season_id, Some(season_one));
+    assert_eq!(update.tmdb_series_id, 4242);
+    assert_eq!(update.episodes, 2);
+
+    let state = repo.get_user_watch_state(user_id).await?;
+    assert!(state.in_progress.is_empty());
+    assert_eq!(state.completed.len(), 2);
+    assert_eq!(completed_identities(&pool, user_id).await?, 2);
+
+    let series = repo
+        .set_scope_watched(user_id, WatchScope::Series(series_id), true)
+        .await?;
+    assert_eq!(series.season_id, None);
+    assert_eq!(series.episodes, 3);
+    assert_eq!(completed_identities(&pool, user_id).await?, 3);
+
+    repo.set_scope_watched(user_id, WatchScope::Season(season_two), false)
+        .await?;
+    assert_eq!(repo.get_user_watch_state(user_id).await?.completed.len(), 2);
+    assert_eq!(completed_identities(&pool, user_id).await?, 2);
+    assert!(
+        !repo
+            .is_media_completed(
+                user_id,
+                &fixture_media_file("33333333-3333-3333-3333-333333333333"),
+            )
+            .await?
+    );
+
+    let missing = repo
+        .set_scope_watched(
+            user_id,
+            WatchScope::Season(SeasonID(Uuid::now_v7())),
+            true,
+        )
+        .await;
+    assert!(matches!(missing, Err(MediaError::NotFound(_))));
+
+    Ok(())
+}
+
+/// Episode reference backed by a fixture media file; completions are
+/// recorded against it.
+async fn episode_reference(pool: &PgPool, file_id: &str) -> Result<Uuid> {
+    Ok(sqlx::query_scalar!(
+        "SELECT id FROM episode_references WHERE file_id = $1",
+        fixture_media_file(file_id)
+    )
+    .fetch_one(pool)
+    .await?)
+}
+
+fn episode(season_number: u16, episode_number: u16) -> EpisodeKey {
+    EpisodeKey {
+        tmdb_series_id: 4242,
+        season_number,
+        episode_number,
+    }
+}
+
+#[sqlx::test(
+    migrator = "ferrex_core::MIGRATOR",
+    fixtures(
+        path = "../fixtures",
+        scripts("test_libraries", "media_processing_base")
+    )
+)]
+async fn cascading_episode_watch_stays_within_the_season(
+    pool: PgPool,
+) -> Result<()> {
+    let user_id = seed_watch_user(&pool).await?;
+    seed_watch_series(&pool).await?;
+    // Season state counts the episodes that have metadata.
+    sqlx::query!(
+        r#"
+        INSERT INTO episode_metadata (episode_id, tmdb_id, series_tmdb_id, season_number, episode_number)
+        SELECT id, 0, tmdb_series_id, season_number, episode_number
+        FROM episode_references
+        WHERE tmdb_series_id = 4242
+        "#
+    )
+    .execute(&pool)
+    .await?;
+    let repo = PostgresWatchStatusRepository::new(pool.clone());
+    let strict = MarkEpisodeWatchedRequest::default();
+    let cascade = MarkEpisodeWatchedRequest {
+        policy: WatchAdvancePolicy::Cascade,
+        series_wide: false,
+    };
+
+    // Strict only touches the reported episode.
+    assert_eq!(
+        repo.mark_episode_watched(user_id, &episode(1, 2), strict)
+            .await?,
+        1
+    );
+    let season_one = repo.get_season_watch_status(user_id, 4242, 1).await?;
+    assert_eq!((season_one.total, season_one.watched), (2, 1));
+    assert_eq!(season_one.episodes[&1], EpisodeStatus::Unwatched);
+    assert!(
+        repo.is_media_completed(
+            user_id,
+            &episode_reference(&pool, "22222222-2222-2222-2222-222222222222")
+                .await?,
+        )
+        .await?
+    );
+
+    // A cascade does not leave the season without the series-wide flag.
+    assert_eq!(
+        repo.mark_episode_watched(user_id, &episode(2, 1), cascade)
+            .await?,
+        1
+    );
+    assert_eq!(completed_identities(&pool, user_id).await?, 2);
+
+    let series_wide = MarkEpisodeWatchedRequest {
+        series_wide: true,
+        ..cascade
+    };
+    assert_eq!(
+        repo.mark_episode_watched(user_id, &episode(2, 1), series_wide)
+            .await?,
+        1
+    );
+    assert_eq!(completed_identities(&pool, user_id).await?, 3);
+    let season_one = repo.get_season_watch_status(user_id, 4242, 1).await?;
+    assert!(season_one.is_completed);
+    assert!(
+        repo.is_media_completed(
+            user_id,
+            &episode_reference(&pool, "11111111-1111-1111-1111-111111111111")
+                .await?,
+        )
+        .await?
+    );
+
+    Ok(())
+}
+
+#[sqlx::test(
+    migrator = "ferrex_core::MIGRATOR",
+    fixtures(
+        path = "../fixtures",
+        scripts("test_libraries", "media_processing_base")
+    )
+)]
+async fn deleting_episodes_keeps_series_with_remaining_episodes(
+    pool: PgPool,
+) -> Result<()> {
+    let user_id = seed_watch_user(&pool).await?;
+    let (series_id, [season_one, season_two]) =
+        seed_watch_series(&pool).await?;
+    let watch = PostgresWatchStatusRepository::new(pool.clone());
+    let repo = PostgresMediaReferencesRepository::new(pool.clone());
+    let first =
+        episode_reference(&pool, "11111111-1111-1111-1111-111111111111")
+            .await?;
+    let last = episode_reference(&pool, "33333333-3333-3333-3333-333333333333")
+        .await?;
+    watch
+        .mark_episode_watched(user_id, &episode(2, 1), Default::default())
+        .await?;
+
+    let missing = MediaID::Episode(EpisodeID(Uuid::now_v7()));
+    let outcomes = repo
+        .delete_media_batch(&[MediaID::Episode(EpisodeID(last)), missing])
+        .await?;
+    let removal = outcomes[0].as_ref().expect("episode deleted");
+    assert_eq!(
+        removal.removed,
+        vec![
+            MediaID::Episode(EpisodeID(last)),
+            MediaID::Season(season_two)
+        ]
+    );
+    assert_eq!(removal.files.len(), 1);
+    assert!(matches!(outcomes[1], Err(MediaError::NotFound(_))));
+    assert!(!watch.is_media_completed(user_id, &last).await?);
+    assert_eq!(completed_identities(&pool, user_id).await?, 0);
+
+    // The series keeps its first season, which still has episodes.
+    let remaining = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM episode_references WHERE series_id = $1"#,
+        series_id.to_uuid()
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(remaining, 2);
+
+    let outcomes = repo
+        .delete_media_batch(&[MediaID::Season(season_one)])
+        .await?;
+    let removal = outcomes[0].as_ref().expect("season deleted");
+    assert!(
+        removal
+            .removed
+            .contains(&MediaID::Episode(EpisodeID(first)))
+    );
+    assert!(removal.removed.contains(&MediaID::Series(series_id)));
+    let series_left = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM series WHERE id = $1"#,
+        series_id.to_uuid()
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(series_left, 0);
+
+    Ok(())
+}
+
+#[sqlx::test(
+    migrator = "ferrex_core::MIGRATOR",
+    fixtures(
+        path = "../fixtures",
+        scripts("test_libraries", "media_processing_base")
+    )
+)]
+async fn watch_state_export_imports_by_identity(pool: PgPool) -> Result<()> {
+    let source = seed_watch_user(&pool).await?;
+    let target = seed_watch_user(&pool).await?;
+    seed_watch_series(&pool).await?;
+    let repo = PostgresWatchStatusRepository::new(pool.clone());
+    let first =
+        episode_reference(&pool, "11111111-1111-1111-1111-111111111111")
+            .await?;
+    let second =
+        episode_reference(&pool, "22222222-2222-2222-2222-222222222222")
+            .await?;
+    let base = Utc::now().timestamp_millis() - 60_000;
+
+    let episode_progress = |media_id, position| UpdateProgressRequest {
+        media_type: VideoMediaType::Episode,
+        ..movie_progress(media_id, position, base)
+    };
+    repo.update_watch_progress(source, &episode_progress(first, 600.0))
+        .await?;
+    repo.mark_episode_watched(source, &episode(1, 2), Default::default())
+        .await?;
+    // The target already watched further into the first episode.
+    repo.update_watch_progress(target, &episode_progress(first, 1200.0))
+        .await?;
+
+    let export =
+        WatchStateExport::new(repo.export_watch_state(source).await?, base);
+    assert_eq!(export.entries.len(), 2);
+    let mut entries = export.entries;
+    entries.push(WatchStateEntry {
+        item: PortableMediaKey::Movie { tmdb_id: 999_999 },
+        position: 10.0,
+        duration: 100.0,
+        completed: false,
+        last_watched: base,
+    });
+
+    let report = repo.import_watch_state(target, &entries).await?;
+    assert_eq!((report.imported, report.kept_existing), (1, 1));
+    assert_eq!(
+        report.skipped.first().map(|skipped| &skipped.item),
+        Some(&PortableMediaKey::Movie { tmdb_id: 999_999 })
+    );
+
+    let state = repo.get_user_watch_state(target).await?;
+    assert_eq!(state.in_progress[&first].position, 1200.0);
+    assert!(state.completed.contains(&second));
+    assert_eq!(completed_identities(&pool, target).await?, 1);
+
+    Ok(())
+}
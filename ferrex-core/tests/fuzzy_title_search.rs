@@ -180,3 +180,69 @@ async fn title_search_prefers_intuitive_fuzzy_hits(pool: PgPool) {
         "expected other 'star' items to be present in the top results"
     );
 }
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn search_endpoint_ranks_hits_and_honours_type_and_limit(pool: PgPool) {
+    let movies_lib = Uuid::new_v4();
+    let tv_lib = Uuid::new_v4();
+    seed_library(&pool, movies_lib, "movies").await;
+    seed_library(&pool, tv_lib, "tvshows").await;
+
+    let star_wars = Uuid::new_v4();
+    seed_movie(
+        &pool,
+        movies_lib,
+        star_wars,
+        Uuid::new_v4(),
+        11,
+        "Star Wars",
+    )
+    .await;
+    let star_is_born = Uuid::new_v4();
+    seed_movie(
+        &pool,
+        movies_lib,
+        star_is_born,
+        Uuid::new_v4(),
+        12,
+        "A Star Is Born",
+    )
+    .await;
+    seed_series(&pool, tv_lib, Uuid::new_v4(), 21, "Star Trek").await;
+
+    let repo = PostgresQueryRepository::new(pool);
+    let query = MediaSearchQuery {
+        q: "star wars".into(),
+        ..MediaSearchQuery::default()
+    };
+    let fields = query.search_fields().expect("fields");
+
+    let hits = repo
+        .search_media(&query, &fields, 10)
+        .await
+        .expect("search_media");
+    assert_eq!(hits[0].id, MediaID::Movie(MovieID(star_wars)));
+    assert_eq!(hits[0].title, "Star Wars");
+    assert_eq!(hits[0].library_id, LibraryId(movies_lib));
+    assert_eq!(hits[0].matched, SearchField::Title);
+    assert!(hits.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    assert!(hits.iter().all(|hit| (0.0..=1.0).contains(&hit.score)));
+
+    let limited = repo
+        .search_media(&query, &fields, 1)
+        .await
+        .expect("search_media");
+    assert_eq!(limited.len(), 1);
+
+    // Unfinalized series are not searchable, and a series filter must not
+    // return movies.
+    let series_only = MediaSearchQuery {
+        media_type: Some(MediaTypeFilter::Series),
+        ..query.clone()
+    };
+    let hits = repo
+        .search_media(&series_only, &fields, 10)
+        .await
+        .expect("search_media");
+    assert!(hits.iter().all(|hit| matches!(hit.id, MediaID::Series(_))));
+}
@@ -100,6 +100,7 @@ impl MetadataActor for StubMetadata {
             analyzed: command.analyzed,
             prepared_at: Utc::now(),
             image_jobs: Vec::new(),
+            metadata_skipped: false,
         })
     }
 }
@@ -139,6 +140,7 @@ impl SeriesMetadataProvider for StubSeriesProvider {
         series_root_path: &SeriesRootPath,
         hint: &SeriesHint,
         _folder_name: &str,
+        _previous: Option<SeriesID>,
     ) -> Result<SeriesResolution> {
         let series_id = SeriesID(Uuid::now_v7());
         let series_ref = SeriesRef {
@@ -174,6 +176,7 @@ impl SeriesMetadataProvider for StubSeriesProvider {
             analyzed,
             prepared_at: Utc::now(),
             image_jobs: Vec::new(),
+            metadata_skipped: false,
         };
         Ok(SeriesResolution { series_ref, ready })
     }
@@ -211,6 +214,7 @@ async fn bulk_seed_depth1_and_recursive_followups(pool: PgPool) -> Result<()> {
         library: library.clone(),
         root_paths: vec![root.clone()],
         max_outstanding_jobs: 10_000,
+        quiescence_window: None,
     };
     let mut actor = DefaultLibraryActor::new(
         config,
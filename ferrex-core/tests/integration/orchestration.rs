@@ -659,6 +659,57 @@ async fn bench_stub_latency_logging(pool: PgPool) {
     );
 }
 
+#[sqlx::test]
+async fn suspended_library_keeps_leases_and_drops_pending(pool: PgPool) {
+    let svc = PostgresQueueService::new(pool.clone())
+        .await
+        .expect("svc init");
+    let lib_id = ferrex_core::LibraryID(seed_library(&pool).await);
+    let folder_job = |i: usize| {
+        EnqueueRequest::new(
+            JobPriority::P1,
+            JobPayload::FolderScan(FolderScanJob {
+                library_id: lib_id,
+                folder_path_norm: format!("/cancel/path/{}", i),
+                hierarchy: ScanHierarchy::default(),
+                scan_reason: ScanReason::UserRequested,
+                enqueue_time: Utc::now(),
+                device_id: None,
+            }),
+        )
+    };
+
+    for i in 0..3 {
+        svc.enqueue(folder_job(i)).await.expect("enqueue");
+    }
+    let lease = svc
+        .dequeue(DequeueRequest {
+            kind: JobKind::FolderScan,
+            worker_id: "cancel-w".into(),
+            lease_ttl: chrono::Duration::seconds(30),
+            selector: None,
+        })
+        .await
+        .expect("dequeue")
+        .expect("lease");
+
+    svc.suspend_enqueues(lib_id);
+    let refused = svc.enqueue(folder_job(3)).await;
+    assert!(matches!(
+        refused,
+        Err(ferrex_core::error::MediaError::Cancelled(_))
+    ));
+    assert_eq!(svc.drop_pending_jobs(lib_id).await.expect("drop"), 2);
+    assert_eq!(svc.leased_job_count(lib_id).await.expect("count"), 1);
+
+    svc.complete(lease.lease_id).await.expect("complete");
+    assert_eq!(svc.leased_job_count(lib_id).await.expect("count"), 0);
+
+    svc.resume_enqueues(lib_id);
+    let handle = svc.enqueue(folder_job(4)).await.expect("enqueue");
+    assert!(handle.accepted);
+}
+
 async fn seed_library(pool: &PgPool) -> uuid::Uuid {
     let id = uuid::Uuid::now_v7();
     let name = format!("Integration Test Library {}", id);
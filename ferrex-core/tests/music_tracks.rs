@@ -0,0 +1,113 @@
+//! Integration coverage for music track storage and album grouping.
+
+use chrono::Utc;
+use ferrex_core::api::types::{MusicTrack, MusicTrackQuery};
+use ferrex_core::database::repositories::music_tracks::PostgresMusicTracksRepository;
+use ferrex_core::database::repository_ports::music_tracks::MusicTracksRepository;
+use ferrex_core::types::ids::LibraryId;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn seed_music_library(pool: &PgPool) -> LibraryId {
+    let id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO libraries (id, name, library_type, paths)
+        VALUES ($1, 'test-music', 'music', ARRAY['/music'])
+        "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .expect("insert library");
+    LibraryId(id)
+}
+
+fn track(
+    library_id: LibraryId,
+    artist: &str,
+    album: &str,
+    number: u16,
+    title: &str,
+) -> MusicTrack {
+    MusicTrack {
+        id: Uuid::now_v7(),
+        library_id,
+        file_path: format!(
+            "/music/{artist}/{album}/{number:02} - {title}.flac"
+        ),
+        filename: format!("{number:02} - {title}.flac"),
+        file_size: 1024,
+        artist: artist.to_string(),
+        album: Some(album.to_string()),
+        title: title.to_string(),
+        track_number: Some(number),
+        disc_number: None,
+        year: Some(1997),
+        discovered_at: Utc::now(),
+    }
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn rescans_replace_tracks_and_group_albums(pool: PgPool) {
+    let library_id = seed_music_library(&pool).await;
+    let repo = PostgresMusicTracksRepository::new(pool.clone());
+
+    let first = vec![
+        track(
+            library_id,
+            "Radiohead",
+            "OK Computer",
+            2,
+            "Paranoid Android",
+        ),
+        track(library_id, "Radiohead", "OK Computer", 1, "Airbag"),
+        track(library_id, "Portishead", "Dummy", 1, "Mysterons"),
+    ];
+    let sync = repo
+        .replace_library_tracks(library_id, &first)
+        .await
+        .expect("store tracks");
+    assert_eq!(sync.stored, 3);
+    assert_eq!(sync.removed, 0);
+
+    let albums = repo
+        .list_albums(library_id, Some("radiohead"))
+        .await
+        .expect("list albums");
+    assert_eq!(albums.len(), 1);
+    assert_eq!(albums[0].album, "OK Computer");
+    assert_eq!(albums[0].track_count, 2);
+
+    let ordered = repo
+        .list_tracks(
+            library_id,
+            &MusicTrackQuery {
+                album: Some("ok computer".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list tracks");
+    let titles: Vec<_> = ordered.iter().map(|t| t.title.as_str()).collect();
+    assert_eq!(titles, ["Airbag", "Paranoid Android"]);
+
+    // The Portishead album disappeared from disk.
+    let sync = repo
+        .replace_library_tracks(library_id, &first[..2])
+        .await
+        .expect("rescan");
+    assert_eq!(sync.removed, 1);
+
+    let search = repo
+        .list_tracks(
+            library_id,
+            &MusicTrackQuery {
+                search: Some("myster".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("search tracks");
+    assert!(search.is_empty());
+}
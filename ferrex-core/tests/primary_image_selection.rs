@@ -0,0 +1,182 @@
+//! Choosing which linked poster of a movie is primary.
+
+use ferrex_core::database::repositories::images::PostgresImageRepository;
+use ferrex_core::database::repositories::media_references::PostgresMediaReferencesRepository;
+use ferrex_core::database::repository_ports::images::ImageRepository;
+use ferrex_core::{
+    error::{MediaError, Result},
+    types::{MovieID, ids::LibraryId},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+struct SeededMovie {
+    movie_id: MovieID,
+    english_poster: Uuid,
+    german_poster: Uuid,
+    backdrop: Uuid,
+}
+
+async fn seed_movie(pool: &PgPool) -> SeededMovie {
+    let library_id = LibraryId(Uuid::now_v7());
+    sqlx::query(
+        r#"
+        INSERT INTO libraries (id, name, paths, library_type)
+        VALUES ($1, 'Primary image selection', ARRAY['/test/movies'], 'movies')
+        "#,
+    )
+    .bind(library_id.to_uuid())
+    .execute(pool)
+    .await
+    .expect("insert library");
+
+    let movie_uuid = Uuid::now_v7();
+    let file_id = Uuid::now_v7();
+    sqlx::query(
+        r#"
+        INSERT INTO media_files (
+            id, library_id, media_id, media_type, file_path, filename, file_size
+        )
+        VALUES ($1, $2, $3, 'movie', '/test/movies/heat.mkv', 'heat.mkv', 1)
+        "#,
+    )
+    .bind(file_id)
+    .bind(library_id.to_uuid())
+    .bind(movie_uuid)
+    .execute(pool)
+    .await
+    .expect("insert media_files");
+
+    sqlx::query(
+        r#"
+        INSERT INTO movie_references (
+            id, library_id, file_id, tmdb_id, title, theme_color
+        )
+        VALUES ($1, $2, $3, 949, 'Heat', '#aa0000')
+        "#,
+    )
+    .bind(movie_uuid)
+    .bind(library_id.to_uuid())
+    .bind(file_id)
+    .execute(pool)
+    .await
+    .expect("insert movie_references");
+
+    let english_poster = Uuid::now_v7();
+    let german_poster = Uuid::now_v7();
+    let backdrop = Uuid::now_v7();
+    sqlx::query(
+        r#"
+        INSERT INTO tmdb_image_variants (
+            id, tmdb_path, media_id, image_variant, media_type, width, height,
+            iso_lang, vote_avg, vote_cnt, is_primary
+        )
+        VALUES
+            ($1, '/heat-en.jpg', $4, 'poster', 'movie', 500, 750, 'en', 5.5, 10, true),
+            ($2, '/heat-de.jpg', $4, 'poster', 'movie', 500, 750, 'de', 5.1, 4, false),
+            ($3, '/heat-bd.jpg', $4, 'backdrop', 'movie', 1280, 720, NULL, 5.0, 3, true)
+        "#,
+    )
+    .bind(english_poster)
+    .bind(german_poster)
+    .bind(backdrop)
+    .bind(movie_uuid)
+    .execute(pool)
+    .await
+    .expect("insert tmdb_image_variants");
+
+    sqlx::query(
+        r#"
+        INSERT INTO movie_metadata (
+            movie_id, library_id, batch_id, tmdb_id, title, poster_path,
+            primary_poster_image_id, primary_backdrop_image_id
+        )
+        VALUES ($1, $2, 1, 949, 'Heat', '/heat-en.jpg', $3, $4)
+        "#,
+    )
+    .bind(movie_uuid)
+    .bind(library_id.to_uuid())
+    .bind(english_poster)
+    .bind(backdrop)
+    .execute(pool)
+    .await
+    .expect("insert movie_metadata");
+
+    SeededMovie {
+        movie_id: MovieID(movie_uuid),
+        english_poster,
+        german_poster,
+        backdrop,
+    }
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn chosen_poster_becomes_the_only_primary(pool: PgPool) -> Result<()> {
+    let seeded = seed_movie(&pool).await;
+    let images = PostgresImageRepository::new(pool.clone());
+    let media_refs = PostgresMediaReferencesRepository::new(pool.clone());
+    let media_id = seeded.movie_id.to_uuid();
+
+    let listed = images.list_media_images(media_id).await?;
+    let order: Vec<Uuid> = listed.iter().map(|image| image.iid).collect();
+    assert_eq!(
+        order,
+        [seeded.english_poster, seeded.german_poster, seeded.backdrop]
+    );
+
+    let chosen = images
+        .set_primary_image(media_id, seeded.german_poster)
+        .await?;
+    assert!(chosen.is_primary);
+    assert_eq!(chosen.iso_lang, "de");
+
+    let listed = images.list_media_images(media_id).await?;
+    let primaries: Vec<Uuid> = listed
+        .iter()
+        .filter(|image| image.is_primary)
+        .map(|image| image.iid)
+        .collect();
+    assert_eq!(primaries, [seeded.german_poster, seeded.backdrop]);
+
+    let movie = media_refs.get_movie(&seeded.movie_id).await?;
+    assert_eq!(movie.details.primary_poster_iid, Some(seeded.german_poster));
+    assert_eq!(movie.details.primary_backdrop_iid, Some(seeded.backdrop));
+
+    // The old poster's color no longer applies; nothing is cached for the
+    // new one yet, so the color stays empty until it is.
+    let theme_color: Option<String> = sqlx::query_scalar(
+        "SELECT theme_color FROM movie_references WHERE id = $1",
+    )
+    .bind(media_id)
+    .fetch_one(&pool)
+    .await
+    .expect("read theme color");
+    assert_eq!(theme_color, None);
+
+    Ok(())
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn unlinked_image_is_not_found_and_changes_nothing(
+    pool: PgPool,
+) -> Result<()> {
+    let seeded = seed_movie(&pool).await;
+    let images = PostgresImageRepository::new(pool.clone());
+    let media_id = seeded.movie_id.to_uuid();
+
+    let missing = images.set_primary_image(media_id, Uuid::now_v7()).await;
+    assert!(matches!(missing, Err(MediaError::NotFound(_))));
+
+    let other_media = images
+        .set_primary_image(Uuid::now_v7(), seeded.german_poster)
+        .await;
+    assert!(matches!(other_media, Err(MediaError::NotFound(_))));
+
+    let primary = images
+        .lookup_variant_by_iid(seeded.english_poster)
+        .await?
+        .expect("english poster");
+    assert!(primary.is_primary);
+
+    Ok(())
+}
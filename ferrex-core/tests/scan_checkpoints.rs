@@ -0,0 +1,76 @@
+//! Integration coverage for scan checkpoints stored in `scan_state`.
+
+use chrono::Utc;
+use ferrex_core::database::traits::{ScanOrigin, ScanStatus};
+use ferrex_core::domain::scan::orchestration::scan_checkpoint::{
+    PostgresScanCheckpointRepository, ScanCheckpoint, ScanCheckpointRepository,
+};
+use ferrex_core::types::ids::LibraryId;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn seed_library(pool: &PgPool) -> LibraryId {
+    let id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO libraries (id, name, library_type, paths)
+        VALUES ($1, 'test-movies', 'movies', ARRAY['/movies'])
+        "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .expect("insert library");
+    LibraryId(id)
+}
+
+fn checkpoint(library_id: LibraryId, status: ScanStatus) -> ScanCheckpoint {
+    let scan_id = Uuid::now_v7();
+    ScanCheckpoint {
+        scan_id,
+        library_id,
+        correlation_id: scan_id,
+        origin: ScanOrigin::Fresh,
+        resumed_from: None,
+        status,
+        completed_items: 40,
+        total_items: 120,
+        current_path: Some("/movies/Heat (1995)".into()),
+        started_at: Utc::now(),
+        updated_at: Utc::now(),
+        completed_at: None,
+    }
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn interrupted_scans_stay_resumable_until_resumed(pool: PgPool) {
+    let library_id = seed_library(&pool).await;
+    let repo = PostgresScanCheckpointRepository::new(pool.clone());
+
+    let running = checkpoint(library_id, ScanStatus::Running);
+    let mut finished = checkpoint(library_id, ScanStatus::Running);
+    repo.save(&running).await.unwrap();
+    repo.save(&finished).await.unwrap();
+
+    finished.status = ScanStatus::Completed;
+    finished.completed_items = finished.total_items;
+    finished.completed_at = Some(Utc::now());
+    repo.save(&finished).await.unwrap();
+
+    let interrupted = repo.mark_interrupted().await.unwrap();
+    assert_eq!(interrupted.len(), 1);
+    assert_eq!(interrupted[0].scan_id, running.scan_id);
+    assert_eq!(interrupted[0].completed_items, 40);
+    assert_eq!(interrupted[0].status, ScanStatus::Interrupted);
+
+    let mut resumed = checkpoint(library_id, ScanStatus::Running);
+    resumed.origin = ScanOrigin::Resumed;
+    resumed.resumed_from = Some(running.scan_id);
+    assert_eq!(repo.list_resumable().await.unwrap().len(), 1);
+    repo.save(&resumed).await.unwrap();
+
+    assert!(repo.list_resumable().await.unwrap().is_empty());
+    let stored = repo.get(resumed.scan_id).await.unwrap().unwrap();
+    assert_eq!(stored.origin, ScanOrigin::Resumed);
+    assert_eq!(stored.resumed_from, Some(running.scan_id));
+}
@@ -107,6 +107,7 @@ fn build_movie_reference(
             library_id,
         },
         theme_color: None,
+        editions: Vec::new(),
     }
 }
 
@@ -0,0 +1,71 @@
+//! Content ratings on a common scale.
+//!
+//! Every rating system has its own codes, so filters compare the minimum
+//! viewer age a rating implies instead of the codes themselves. Ratings
+//! that only advise (`PG`, `TV-PG`) get the age the advice usually targets.
+
+/// Minimum viewer age implied by a certification or content rating such as
+/// `PG-13`, `TV-MA`, `12A` or `FSK 16`. A bare age (`12`, `16+`) is taken
+/// as is. `None` for empty, unrated and unrecognized codes.
+pub fn certification_age(code: &str) -> Option<u8> {
+    let code = code.trim().to_ascii_uppercase();
+    let named = match code.as_str() {
+        "" | "NR" | "UR" | "NOT RATED" | "UNRATED" | "E" | "EXEMPT" => {
+            return None;
+        }
+        "G" | "U" | "TV-Y" | "TV-G" | "AL" | "ALL" | "TP" | "BTL" => 0,
+        "TV-Y7" | "TV-Y7-FV" => 7,
+        "PG" | "TV-PG" => 8,
+        "PG-13" => 13,
+        "TV-14" => 14,
+        "M" => 15,
+        "R" | "TV-MA" => 17,
+        "NC-17" | "X" => 18,
+        _ => return leading_age(&code),
+    };
+    Some(named)
+}
+
+/// The first number in `code` when it reads as an age (`FSK 16`, `MA15+`,
+/// `R18`, `-12`).
+fn leading_age(code: &str) -> Option<u8> {
+    let digits: String = code
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok().filter(|age| *age <= 21)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratings_from_different_systems_share_one_age_scale() {
+        assert_eq!(certification_age("G"), Some(0));
+        assert_eq!(certification_age("tv-y7"), Some(7));
+        assert_eq!(certification_age(" PG "), Some(8));
+        assert_eq!(certification_age("PG-13"), Some(13));
+        assert_eq!(certification_age("12A"), Some(12));
+        assert_eq!(certification_age("FSK 16"), Some(16));
+        assert_eq!(certification_age("MA15+"), Some(15));
+        assert_eq!(certification_age("R"), Some(17));
+        assert_eq!(certification_age("R18"), Some(18));
+        assert_eq!(certification_age("NC-17"), Some(18));
+        assert_eq!(certification_age("0"), Some(0));
+        assert_eq!(certification_age("13"), Some(13));
+
+        assert!(certification_age("PG-13") > certification_age("PG"));
+        assert!(certification_age("TV-MA") > certification_age("TV-14"));
+    }
+
+    #[test]
+    fn unrated_and_unknown_codes_have_no_age() {
+        assert_eq!(certification_age(""), None);
+        assert_eq!(certification_age("NR"), None);
+        assert_eq!(certification_age("Unrated"), None);
+        assert_eq!(certification_age("Banned"), None);
+        assert_eq!(certification_age("1999"), None);
+    }
+}
@@ -669,3 +669,19 @@ pub struct LibraryReference {
     #[cfg_attr(feature = "rkyv", rkyv(with = crate::rkyv_wrappers::VecPathBuf))]
     pub paths: Vec<PathBuf>,
 }
+
+/// Part of a movie's or series' metadata that can be refreshed on its own,
+/// leaving the rest of the stored metadata untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MetadataField {
+    Overview,
+    Genres,
+    /// Linked posters and backdrops; the chosen primary of each is kept.
+    Images,
+    /// Cast and crew.
+    Cast,
+    /// TMDB vote average and count, and the content rating.
+    Rating,
+}
@@ -82,6 +82,13 @@ pub enum MediaSseEventType {
     SeasonUpdated,
     EpisodeUpdated,
     MediaDeleted,
+    PosterPrewarmProgress,
+    DiskSpaceLow,
+    WatchStateUpdated,
+    /// Control event: the server could not replay every event the client
+    /// missed, so the client must reload library state. Carries no
+    /// `MediaEvent` payload; the `id:` field holds the latest sequence.
+    ResyncRequired,
     Scan(ScanSseEventType),
 }
 
@@ -99,6 +106,10 @@ impl MediaSseEventType {
             Self::SeasonUpdated => "media.season_updated",
             Self::EpisodeUpdated => "media.episode_updated",
             Self::MediaDeleted => "media.deleted",
+            Self::PosterPrewarmProgress => "media.poster_prewarm_progress",
+            Self::DiskSpaceLow => "media.disk_space_low",
+            Self::WatchStateUpdated => "media.watch_state_updated",
+            Self::ResyncRequired => "media.resync_required",
             Self::Scan(kind) => kind.event_name(),
         }
     }
@@ -147,6 +158,10 @@ impl FromStr for MediaSseEventType {
             "media.season_updated" => Ok(Self::SeasonUpdated),
             "media.episode_updated" => Ok(Self::EpisodeUpdated),
             "media.deleted" => Ok(Self::MediaDeleted),
+            "media.poster_prewarm_progress" => Ok(Self::PosterPrewarmProgress),
+            "media.disk_space_low" => Ok(Self::DiskSpaceLow),
+            "media.watch_state_updated" => Ok(Self::WatchStateUpdated),
+            "media.resync_required" => Ok(Self::ResyncRequired),
             other => match ScanSseEventType::from_str(other) {
                 Ok(kind) => Ok(Self::Scan(kind)),
                 Err(_) => Err(ParseMediaSseEventTypeError::new(other)),
@@ -171,6 +186,13 @@ impl MediaEvent {
                 MediaSseEventType::SeriesUpdated
             }
             MediaEvent::MediaDeleted { .. } => MediaSseEventType::MediaDeleted,
+            MediaEvent::PosterPrewarmProgress { .. } => {
+                MediaSseEventType::PosterPrewarmProgress
+            }
+            MediaEvent::DiskSpaceLow { .. } => MediaSseEventType::DiskSpaceLow,
+            MediaEvent::WatchStateUpdated { .. } => {
+                MediaSseEventType::WatchStateUpdated
+            }
             MediaEvent::ScanStarted { .. } => {
                 MediaSseEventType::Scan(ScanSseEventType::Started)
             }
@@ -279,6 +301,7 @@ mod tests {
             ("media.season_updated", MediaSseEventType::SeasonUpdated),
             ("media.episode_updated", MediaSseEventType::EpisodeUpdated),
             ("media.deleted", MediaSseEventType::MediaDeleted),
+            ("media.disk_space_low", MediaSseEventType::DiskSpaceLow),
             (
                 "scan.started",
                 MediaSseEventType::Scan(ScanSseEventType::Started),
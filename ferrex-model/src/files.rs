@@ -3,7 +3,7 @@ use crate::{
     error::{ModelError as MediaError, Result},
 };
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 use super::LibraryId;
@@ -54,6 +54,12 @@ pub struct MediaFileMetadata {
     pub color_transfer: Option<String>,
     pub color_space: Option<String>,
     pub bit_depth: Option<u32>,
+    /// Dynamic range format of the video stream; `None` for SDR.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hdr_format: Option<HdrFormat>,
+    /// Dolby Vision profile from the stream's configuration record.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dolby_vision_profile: Option<u8>,
 
     // Parsed from filename
     pub parsed_info: Option<ParsedMediaInfo>,
@@ -75,12 +81,52 @@ impl fmt::Debug for MediaFile {
     }
 }
 
+impl MediaFile {
+    /// Where the file's bytes live.
+    pub fn location(&self) -> MediaLocation<'_> {
+        MediaLocation::of(&self.path)
+    }
+}
+
+/// Where a media file is stored.
+///
+/// Files usually sit below a library root on local disk. A file whose
+/// recorded path is an `http://` or `https://` URL is stored remotely, for
+/// example in an object store, and is fetched from there instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaLocation<'a> {
+    Local(&'a Path),
+    Remote(&'a str),
+}
+
+impl<'a> MediaLocation<'a> {
+    /// Classify a recorded media path.
+    pub fn of(path: &'a Path) -> Self {
+        match path.to_str() {
+            Some(url) if is_remote_url(url) => MediaLocation::Remote(url),
+            _ => MediaLocation::Local(path),
+        }
+    }
+
+    pub fn is_remote(&self) -> bool {
+        matches!(self, MediaLocation::Remote(_))
+    }
+}
+
+fn is_remote_url(path: &str) -> bool {
+    ["http://", "https://"].iter().any(|scheme| {
+        path.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    })
+}
+
 impl fmt::Debug for MediaFileMetadata {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let resolution = self.width.zip(self.height);
         let parsed_kind = self.parsed_info.as_ref().map(|info| match info {
             ParsedMediaInfo::Movie(_) => "Movie",
             ParsedMediaInfo::Episode(_) => "Episode",
+            ParsedMediaInfo::Track(_) => "Track",
         });
 
         f.debug_struct("MediaFileMetadata")
@@ -98,12 +144,42 @@ impl fmt::Debug for MediaFileMetadata {
                     &self.color_transfer,
                     &self.color_space,
                     &self.bit_depth,
+                    &self.hdr_format,
+                    &self.dolby_vision_profile,
                 ),
             )
             .field("parsed_info_kind", &parsed_kind)
             .finish()
     }
 }
+/// High dynamic range format detected from a video stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "rkyv", rkyv(derive(Debug, PartialEq, Eq)))]
+pub enum HdrFormat {
+    /// PQ (SMPTE ST 2084) transfer with static metadata.
+    Hdr10,
+    /// Hybrid log-gamma (ARIB STD-B67) transfer.
+    Hlg,
+    DolbyVision,
+}
+
+impl HdrFormat {
+    /// Short label suitable for a UI badge.
+    pub const fn label(self) -> &'static str {
+        match self {
+            HdrFormat::Hdr10 => "HDR10",
+            HdrFormat::Hlg => "HLG",
+            HdrFormat::DolbyVision => "Dolby Vision",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -114,6 +190,7 @@ impl fmt::Debug for MediaFileMetadata {
 pub enum ParsedMediaInfo {
     Movie(ParsedMovieInfo),
     Episode(ParsedEpisodeInfo),
+    Track(ParsedTrackInfo),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -147,6 +224,30 @@ pub struct ParsedEpisodeInfo {
     pub resolution: Option<String>,
     pub source: Option<String>,
     pub release_group: Option<String>,
+    /// Absolute episode number the file is named with (common for anime).
+    /// Once mapped onto TMDB's layout, `season`/`episode` hold the result.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub absolute_episode: Option<u16>,
+}
+
+/// An audio track, parsed from an `Artist/Album (Year)/NN - Title` layout.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "rkyv", rkyv(derive(Debug, PartialEq, Eq)))]
+pub struct ParsedTrackInfo {
+    pub artist: String,
+    pub album: Option<String>,
+    pub title: String,
+    pub track: Option<u16>,
+    pub disc: Option<u16>,
+    pub year: Option<u16>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -273,3 +374,27 @@ impl MediaFile {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_are_remote_locations() {
+        let url = Path::new("HTTPS://media.example.com/movies/Heat.mkv");
+        assert_eq!(
+            MediaLocation::of(url),
+            MediaLocation::Remote("HTTPS://media.example.com/movies/Heat.mkv")
+        );
+        assert!(
+            MediaLocation::of(Path::new("http://nas:8080/a.mp4")).is_remote()
+        );
+
+        let local = Path::new("/srv/media/https:/Heat.mkv");
+        assert_eq!(MediaLocation::of(local), MediaLocation::Local(local));
+        assert_eq!(
+            MediaLocation::of(Path::new("http")),
+            MediaLocation::Local(Path::new("http"))
+        );
+    }
+}
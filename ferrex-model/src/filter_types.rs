@@ -1,6 +1,8 @@
 use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum UiGenre {
     Action,
     Adventure,
@@ -77,15 +79,24 @@ impl fmt::Display for UiGenre {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UiDecade {
+    #[cfg_attr(feature = "serde", serde(rename = "2020s"))]
     Y2020s,
+    #[cfg_attr(feature = "serde", serde(rename = "2010s"))]
     Y2010s,
+    #[cfg_attr(feature = "serde", serde(rename = "2000s"))]
     Y2000s,
+    #[cfg_attr(feature = "serde", serde(rename = "1990s"))]
     Y1990s,
+    #[cfg_attr(feature = "serde", serde(rename = "1980s"))]
     Y1980s,
+    #[cfg_attr(feature = "serde", serde(rename = "1970s"))]
     Y1970s,
+    #[cfg_attr(feature = "serde", serde(rename = "1960s"))]
     Y1960s,
+    #[cfg_attr(feature = "serde", serde(rename = "1950s"))]
     Y1950s,
 }
 
@@ -128,15 +139,23 @@ impl fmt::Display for UiDecade {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UiResolution {
+    #[cfg_attr(feature = "serde", serde(rename = "any"))]
     Any,
-    SD,      // <= 576
-    HD720,   // 720p
+    #[cfg_attr(feature = "serde", serde(rename = "sd"))]
+    SD, // <= 576
+    #[cfg_attr(feature = "serde", serde(rename = "720p"))]
+    HD720, // 720p
+    #[cfg_attr(feature = "serde", serde(rename = "1080p"))]
     FHD1080, // 1080p
+    #[cfg_attr(feature = "serde", serde(rename = "1440p"))]
     QHD1440, // 1440p
-    UHD4K,   // 2160p
-    UHD8K,   // 4320p
+    #[cfg_attr(feature = "serde", serde(rename = "4k"))]
+    UHD4K, // 2160p
+    #[cfg_attr(feature = "serde", serde(rename = "8k"))]
+    UHD8K, // 4320p
 }
 
 impl UiResolution {
@@ -163,7 +182,9 @@ impl fmt::Display for UiResolution {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum UiWatchStatus {
     Any,
     Unwatched,
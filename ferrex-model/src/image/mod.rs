@@ -4,6 +4,7 @@ pub mod metadata;
 pub mod query;
 pub mod request;
 pub mod sizes;
+pub mod thumbnail;
 
 pub use dimensions::*;
 pub use fetch::*;
@@ -11,3 +12,4 @@ pub use metadata::*;
 pub use query::*;
 pub use request::*;
 pub use sizes::*;
+pub use thumbnail::*;
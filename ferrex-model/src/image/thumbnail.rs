@@ -0,0 +1,159 @@
+use std::{fmt, str::FromStr};
+
+/// Upper bound on frames decoded by [`ThumbnailStrategy::BestFrame`].
+pub const MAX_BEST_FRAME_CANDIDATES: u8 = 8;
+
+/// How the frame for a generated episode thumbnail is chosen.
+///
+/// The string form used in environment variables is `percentage:<0..1>`,
+/// `timestamp:<seconds>` or `best_frame[:<candidates>]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "mode", rename_all = "snake_case"))]
+pub enum ThumbnailStrategy {
+    /// Frame at a fraction of the duration (`0.3` is 30%).
+    Percentage { fraction: f64 },
+    /// Frame at a fixed offset, clamped to the duration.
+    Timestamp { seconds: f64 },
+    /// Decode a few frames spread over the video and keep the brightest,
+    /// most detailed one, avoiding black and fade frames.
+    BestFrame { candidates: u8 },
+}
+
+impl ThumbnailStrategy {
+    pub const DEFAULT_FRACTION: f64 = 0.3;
+    pub const DEFAULT_CANDIDATES: u8 = 5;
+
+    /// Positions, as fractions of the duration, sampled by `BestFrame`.
+    /// Candidates are spread evenly between 10% and 90% to skip intros and
+    /// credits, and capped at [`MAX_BEST_FRAME_CANDIDATES`].
+    pub fn candidate_fractions(candidates: u8) -> Vec<f64> {
+        let count = candidates.clamp(1, MAX_BEST_FRAME_CANDIDATES);
+        (1..=count)
+            .map(|i| 0.1 + 0.8 * f64::from(i) / f64::from(count + 1))
+            .collect()
+    }
+}
+
+impl Default for ThumbnailStrategy {
+    fn default() -> Self {
+        Self::Percentage {
+            fraction: Self::DEFAULT_FRACTION,
+        }
+    }
+}
+
+impl fmt::Display for ThumbnailStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Percentage { fraction } => write!(f, "percentage:{fraction}"),
+            Self::Timestamp { seconds } => write!(f, "timestamp:{seconds}"),
+            Self::BestFrame { candidates } => {
+                write!(f, "best_frame:{candidates}")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseThumbnailStrategyError {
+    invalid_value: String,
+}
+
+impl fmt::Display for ParseThumbnailStrategyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid thumbnail strategy '{}' (expected percentage:<0..1>, timestamp:<seconds> or best_frame[:<n>])",
+            self.invalid_value
+        )
+    }
+}
+
+impl std::error::Error for ParseThumbnailStrategyError {}
+
+impl FromStr for ThumbnailStrategy {
+    type Err = ParseThumbnailStrategyError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseThumbnailStrategyError {
+            invalid_value: value.to_string(),
+        };
+        let trimmed = value.trim();
+        let (mode, arg) = match trimmed.split_once(':') {
+            Some((mode, arg)) => (mode.trim(), Some(arg.trim())),
+            None => (trimmed, None),
+        };
+
+        match (mode.to_ascii_lowercase().as_str(), arg) {
+            ("percentage", Some(arg)) => {
+                let fraction: f64 = arg.parse().map_err(|_| invalid())?;
+                if !(0.0..=1.0).contains(&fraction) {
+                    return Err(invalid());
+                }
+                Ok(Self::Percentage { fraction })
+            }
+            ("timestamp", Some(arg)) => {
+                let seconds: f64 = arg.parse().map_err(|_| invalid())?;
+                if !seconds.is_finite() || seconds < 0.0 {
+                    return Err(invalid());
+                }
+                Ok(Self::Timestamp { seconds })
+            }
+            ("best_frame", None) => Ok(Self::BestFrame {
+                candidates: Self::DEFAULT_CANDIDATES,
+            }),
+            ("best_frame", Some(arg)) => {
+                let candidates: u8 = arg.parse().map_err(|_| invalid())?;
+                if candidates == 0 {
+                    return Err(invalid());
+                }
+                Ok(Self::BestFrame {
+                    candidates: candidates.min(MAX_BEST_FRAME_CANDIDATES),
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_each_mode() {
+        assert_eq!(
+            "percentage:0.5".parse(),
+            Ok(ThumbnailStrategy::Percentage { fraction: 0.5 })
+        );
+        assert_eq!(
+            "timestamp:90".parse(),
+            Ok(ThumbnailStrategy::Timestamp { seconds: 90.0 })
+        );
+        assert_eq!(
+            "best_frame".parse(),
+            Ok(ThumbnailStrategy::BestFrame { candidates: 5 })
+        );
+        assert_eq!(
+            "best_frame:50".parse(),
+            Ok(ThumbnailStrategy::BestFrame {
+                candidates: MAX_BEST_FRAME_CANDIDATES
+            })
+        );
+        assert!("percentage:1.5".parse::<ThumbnailStrategy>().is_err());
+        assert!("middle".parse::<ThumbnailStrategy>().is_err());
+    }
+
+    #[test]
+    fn candidates_stay_inside_the_trimmed_range() {
+        let fractions = ThumbnailStrategy::candidate_fractions(3);
+        assert_eq!(fractions.len(), 3);
+        assert!(fractions.iter().all(|f| (0.1..0.9).contains(f)));
+        assert!(fractions.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(
+            ThumbnailStrategy::candidate_fractions(200).len(),
+            MAX_BEST_FRAME_CANDIDATES as usize
+        );
+    }
+}
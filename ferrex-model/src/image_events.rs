@@ -14,4 +14,9 @@ pub struct ImageReadyEvent {
     pub imz: ImageSize,
     /// Stable, hex-encoded token for the immutable blob URL.
     pub token: String,
+    /// Movie, series, season, episode or person the image belongs to, when
+    /// known.
+    pub media_id: Option<Uuid>,
+    /// Server-relative path of the blob, ready to fetch.
+    pub path: String,
 }
@@ -9,6 +9,7 @@ pub mod chrono_stub;
 #[cfg(not(feature = "chrono"))]
 pub use chrono_stub as chrono;
 
+pub mod certification;
 pub mod details;
 pub mod error;
 pub mod events;
@@ -35,15 +36,19 @@ pub mod urls;
 pub mod watch;
 
 // Intentionally curated re-exports for downstream consumers.
+pub use certification::certification_age;
 #[cfg(feature = "rkyv")]
 pub use details::ArchivedCastMember;
 pub use details::{
     EnhancedMovieDetails, EnhancedSeriesDetails, EpisodeDetails, GenreInfo,
-    LibraryReference, NetworkInfo, ProductionCompany, ProductionCountry,
-    SeasonDetails, SpokenLanguage, TmdbDetails,
+    LibraryReference, MetadataField, NetworkInfo, ProductionCompany,
+    ProductionCountry, SeasonDetails, SpokenLanguage, TmdbDetails,
 };
 pub use error::{ModelError, Result as ModelResult};
-pub use files::{MediaFile, MediaFileMetadata, ParsedMediaInfo};
+pub use files::{
+    HdrFormat, MediaFile, MediaFileMetadata, MediaLocation, ParsedMediaInfo,
+    ParsedTrackInfo,
+};
 pub use filter_types::{UiDecade, UiGenre, UiResolution, UiWatchStatus};
 pub use ids::{
     EpisodeID, LibraryId, MovieBatchId, MovieID, MovieReferenceBatchSize,
@@ -63,14 +68,15 @@ pub use media::{
     ArchivedSeasonReference, ArchivedSeries,
 };
 pub use media::{
-    EpisodeReference, Media, MovieReference, SeasonReference, Series,
+    EpisodeReference, Media, MovieEdition, MovieReference, SeasonReference,
+    Series,
 };
 pub use media_events::{
     MediaEvent, ScanEventMetadata, ScanProgressEvent, ScanStageLatencySummary,
 };
 #[cfg(feature = "rkyv")]
 pub use media_id::ArchivedMediaID;
-pub use media_id::MediaID;
+pub use media_id::{MediaID, ParseMediaIdError};
 pub use media_type::ImageMediaType;
 pub use media_type::VideoMediaType;
 pub use rate_limit::{
@@ -82,6 +88,6 @@ pub use transcoding::{
     TranscodingJobResponse, TranscodingProgressDetails, TranscodingStatus,
 };
 pub use watch::{
-    EpisodeKey, EpisodeStatus, NextEpisode, NextReason, SeasonKey,
-    SeasonWatchStatus, SeriesWatchStatus,
+    BulkWatchUpdate, EpisodeKey, EpisodeStatus, NextEpisode, NextReason,
+    SeasonKey, SeasonWatchStatus, SeriesWatchStatus,
 };
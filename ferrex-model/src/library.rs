@@ -65,6 +65,10 @@ pub struct Library {
     pub analyze_on_scan: bool,
     pub max_retry_attempts: u32,
     pub movie_ref_batch_size: MovieReferenceBatchSize,
+    /// Globs, or regexes prefixed with `re:`, matched against paths
+    /// relative to the library root; matching paths are never scanned.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exclude_patterns: Vec<String>,
     #[cfg_attr(feature = "rkyv", rkyv(with = crate::rkyv_wrappers::DateTimeWrapper))]
     pub created_at: DateTime<Utc>,
     #[cfg_attr(feature = "rkyv", rkyv(with = crate::rkyv_wrappers::DateTimeWrapper))]
@@ -84,6 +88,8 @@ pub struct Library {
 pub enum LibraryType {
     Movies,
     Series,
+    /// Audio tracks grouped by artist and album; not matched against TMDB.
+    Music,
 }
 
 impl std::fmt::Display for LibraryType {
@@ -91,6 +97,7 @@ impl std::fmt::Display for LibraryType {
         match self {
             LibraryType::Movies => write!(f, "Movies"),
             LibraryType::Series => write!(f, "TV Shows"),
+            LibraryType::Music => write!(f, "Music"),
         }
     }
 }
@@ -191,6 +198,7 @@ impl LibraryLikeMut for Library {
             analyze_on_scan: false,
             max_retry_attempts: 3,
             movie_ref_batch_size: MovieReferenceBatchSize::default(),
+            exclude_patterns: Vec::new(),
             created_at: now,
             updated_at: now,
             media: None,
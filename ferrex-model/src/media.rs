@@ -6,6 +6,7 @@ use super::{
     urls::{EpisodeURL, MovieURL, SeasonURL, SeriesURL},
 };
 use std::fmt;
+use uuid::Uuid;
 
 #[cfg(feature = "rkyv")]
 use crate::media_id::ArchivedMediaID;
@@ -60,6 +61,55 @@ pub struct MovieReference {
         serde(skip_serializing_if = "Option::is_none")
     )]
     pub theme_color: Option<String>, // Hex color e.g. "#2C3E50"
+    /// Every file of this movie when there is more than one (e.g. an
+    /// extended cut next to the theatrical release), `file` included.
+    /// Empty for single-file movies.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    pub editions: Vec<MovieEdition>,
+}
+
+/// One file of a movie that has several editions
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "rkyv", rkyv(derive(Debug, PartialEq, Eq)))]
+pub struct MovieEdition {
+    pub file_id: Uuid,
+    /// Label from the filename or folder (`{Extended}`); `None` for the
+    /// unlabeled cut
+    pub label: Option<String>,
+    pub filename: String,
+    pub size: u64,
+}
+
+impl MovieReference {
+    /// Edition whose label matches `selector` (case-insensitive, ignoring
+    /// spaces, dots and underscores).
+    pub fn edition(&self, selector: &str) -> Option<&MovieEdition> {
+        let wanted = normalize_edition_label(selector);
+        self.editions.iter().find(|edition| {
+            edition
+                .label
+                .as_deref()
+                .is_some_and(|label| normalize_edition_label(label) == wanted)
+        })
+    }
+}
+
+/// Comparison key for edition labels, so `directors-cut`, `Directors.Cut`
+/// and `Director's Cut` select the same edition.
+pub fn normalize_edition_label(label: &str) -> String {
+    label
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
 }
 
 /// Lightweight series reference for lists/collections
@@ -183,6 +233,7 @@ impl fmt::Debug for MovieReference {
             .field("theme_color", &self.theme_color)
             .field("details", &self.details)
             .field("file", &self.file)
+            .field("editions", &self.editions)
             .finish()
     }
 }
@@ -1,7 +1,7 @@
 use super::{LibraryId, Media, MediaID, MovieBatchId, MovieReference, Series};
 
 use crate::{
-    SeriesID, SubjectKey,
+    SeasonID, SeriesID, SubjectKey,
     chrono::{DateTime, Utc},
 };
 
@@ -11,7 +11,7 @@ use uuid::Uuid;
 #[cfg(feature = "rkyv")]
 use crate::rkyv_wrappers::DateTimeWrapper;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
     feature = "rkyv",
@@ -19,9 +19,19 @@ use crate::rkyv_wrappers::DateTimeWrapper;
 )]
 #[cfg_attr(feature = "rkyv", rkyv(derive(Debug, PartialEq, Eq)))]
 pub struct ScanStageLatencySummary {
+    /// Folder discovery (`FolderScan` jobs).
     pub scan: u64,
+    /// Probing and matching (`MediaAnalyze`, `SeriesResolve`,
+    /// `EpisodeMatch` jobs).
     pub analyze: u64,
+    /// Database writes (`IndexUpsert` jobs).
     pub index: u64,
+    /// Metadata provider lookups (`MetadataEnrich` jobs).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: u64,
+    /// Image downloads (`ImageFetch` jobs).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub image: u64,
 }
 
 #[derive(Clone, PartialEq)]
@@ -64,6 +74,26 @@ pub struct ScanProgressEvent {
         serde(skip_serializing_if = "Option::is_none")
     )]
     pub dead_lettered_items: Option<u64>,
+    /// Share of the items discovered so far that were processed, from `0`
+    /// to `100`. Absent while the scan is still discovering folders.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none")
+    )]
+    pub percent_complete: Option<f32>,
+    /// Smoothed recent throughput, in items per second.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none")
+    )]
+    pub items_per_second: Option<f32>,
+    /// Estimated seconds until every discovered item is processed. Absent
+    /// while discovering or before enough items finished to measure a rate.
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none")
+    )]
+    pub eta_seconds: Option<u64>,
 }
 
 impl fmt::Debug for ScanProgressEvent {
@@ -78,6 +108,8 @@ impl fmt::Debug for ScanProgressEvent {
             .field("current_path", &self.current_path)
             .field("retrying_items", &self.retrying_items)
             .field("dead_lettered_items", &self.dead_lettered_items)
+            .field("percent_complete", &self.percent_complete)
+            .field("eta_seconds", &self.eta_seconds)
             .field("correlation_id", &self.correlation_id)
             .field("idempotency_key", &self.idempotency_key)
             .field("p95_stage_latencies_ms", &self.p95_stage_latencies_ms)
@@ -151,6 +183,34 @@ pub enum MediaEvent {
         error: String,
         metadata: ScanEventMetadata,
     },
+    /// Progress of the post-scan poster prewarm pass for a library.
+    PosterPrewarmProgress {
+        library_id: LibraryId,
+        scan_id: Uuid,
+        /// Posters downloaded during this pass.
+        warmed: u64,
+        /// Posters that were already cached and skipped without a download.
+        skipped: u64,
+        /// Posters whose download failed.
+        failed: u64,
+        total: u64,
+    },
+    /// The cache volume dropped below its configured free-space threshold;
+    /// scans and image downloads are refused until space is freed.
+    DiskSpaceLow {
+        path: String,
+        available_bytes: u64,
+        required_bytes: u64,
+    },
+    /// A user marked a whole season or series watched or unwatched. Sent
+    /// once per bulk change and only delivered to that user's streams.
+    WatchStateUpdated {
+        user_id: Uuid,
+        series_id: SeriesID,
+        season_id: Option<SeasonID>,
+        watched: bool,
+        episodes: u32,
+    },
 }
 
 impl MediaEvent {
@@ -170,7 +230,21 @@ impl MediaEvent {
             | MediaEvent::ScanStarted { .. }
             | MediaEvent::ScanProgress { .. }
             | MediaEvent::ScanCompleted { .. }
-            | MediaEvent::ScanFailed { .. } => None,
+            | MediaEvent::ScanFailed { .. }
+            | MediaEvent::PosterPrewarmProgress { .. }
+            | MediaEvent::DiskSpaceLow { .. }
+            | MediaEvent::WatchStateUpdated { .. } => None,
+        }
+    }
+
+    /// Whether a stream authenticated as `user_id` may receive this event.
+    /// Per-user watch changes stay private; everything else is shared.
+    pub fn visible_to(&self, user_id: Uuid) -> bool {
+        match self {
+            MediaEvent::WatchStateUpdated { user_id: owner, .. } => {
+                *owner == user_id
+            }
+            _ => true,
         }
     }
 }
@@ -2,6 +2,7 @@ use crate::{
     ids::{EpisodeID, MovieID, SeasonID, SeriesID},
     media_type::VideoMediaType,
 };
+use std::{fmt, str::FromStr};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
@@ -56,6 +57,18 @@ impl MediaID {
         }
     }
 
+    /// Typed form used in URLs, `movie:<uuid>`, `series:<uuid>`,
+    /// `season:<uuid>` or `episode:<uuid>`; parsed back by [`FromStr`].
+    pub fn to_param(&self) -> String {
+        let kind = match self {
+            MediaID::Movie(_) => "movie",
+            MediaID::Series(_) => "series",
+            MediaID::Season(_) => "season",
+            MediaID::Episode(_) => "episode",
+        };
+        format!("{kind}:{}", self.as_uuid())
+    }
+
     pub fn eq_movie(&self, other: &MovieID) -> bool {
         match (self, other) {
             (MediaID::Movie(MovieID(a)), MovieID(b)) => a == b,
@@ -86,6 +99,43 @@ impl std::fmt::Display for MediaID {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMediaIdError {
+    invalid_value: String,
+}
+
+impl fmt::Display for ParseMediaIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid media id '{}' (expected movie:, series:, season: or episode: followed by a UUID)",
+            self.invalid_value
+        )
+    }
+}
+
+impl std::error::Error for ParseMediaIdError {}
+
+impl FromStr for MediaID {
+    type Err = ParseMediaIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseMediaIdError {
+            invalid_value: value.to_string(),
+        };
+        let (kind, id) = value.split_once(':').ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+        let media_type = match kind.to_ascii_lowercase().as_str() {
+            "movie" => VideoMediaType::Movie,
+            "series" => VideoMediaType::Series,
+            "season" => VideoMediaType::Season,
+            "episode" => VideoMediaType::Episode,
+            _ => return Err(invalid()),
+        };
+        Ok(MediaID::from((id, media_type)))
+    }
+}
+
 impl From<MovieID> for MediaID {
     fn from(id: MovieID) -> Self {
         MediaID::Movie(id)
@@ -218,3 +268,39 @@ mod archived {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_param_roundtrip() {
+        let id = Uuid::now_v7();
+        for media in [
+            MediaID::Movie(MovieID(id)),
+            MediaID::Series(SeriesID(id)),
+            MediaID::Season(SeasonID(id)),
+            MediaID::Episode(EpisodeID(id)),
+        ] {
+            assert_eq!(MediaID::from_str(&media.to_param()).unwrap(), media);
+        }
+        assert_eq!(
+            MediaID::from_str(&format!("Movie:{id}")).unwrap(),
+            MediaID::Movie(MovieID(id))
+        );
+    }
+
+    #[test]
+    fn malformed_params_are_rejected() {
+        let id = Uuid::now_v7();
+        for value in [
+            id.to_string(),
+            format!("person:{id}"),
+            "movie:not-a-uuid".to_string(),
+            format!("movie:{id}:extra"),
+            String::new(),
+        ] {
+            assert!(MediaID::from_str(&value).is_err(), "{value}");
+        }
+    }
+}
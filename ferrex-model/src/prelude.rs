@@ -9,7 +9,9 @@ pub use super::details::{
     LibraryReference, NetworkInfo, ProductionCompany, ProductionCountry,
     SeasonDetails, SpokenLanguage, TmdbDetails,
 };
-pub use super::files::{MediaFile, MediaFileMetadata, ParsedMediaInfo};
+pub use super::files::{
+    MediaFile, MediaFileMetadata, MediaLocation, ParsedMediaInfo,
+};
 pub use super::filter_types::{UiDecade, UiGenre, UiResolution, UiWatchStatus};
 pub use super::ids::{
     EpisodeID, LibraryId, MovieBatchId, MovieID, MovieReferenceBatchSize,
@@ -30,7 +32,8 @@ pub use super::media::{
     ArchivedSeasonReference, ArchivedSeries,
 };
 pub use super::media::{
-    EpisodeReference, Media, MovieReference, SeasonReference, Series,
+    EpisodeReference, Media, MovieEdition, MovieReference, SeasonReference,
+    Series,
 };
 #[cfg(feature = "rkyv")]
 pub use super::media_id::ArchivedMediaID;
@@ -96,7 +96,13 @@ pub mod scanner {
         /// Default file extensions treated as video assets by the scanner.
         pub const DEFAULT_VIDEO_FILE_EXTENSIONS: &[&str] = &[
             "mp4", "mkv", "avi", "mov", "webm", "flv", "wmv", "m4v", "mpg",
-            "mpeg",
+            "mpeg", "3gp", "ts",
+        ];
+
+        /// Default file extensions treated as tracks in music libraries.
+        pub const DEFAULT_AUDIO_FILE_EXTENSIONS: &[&str] = &[
+            "mp3", "flac", "m4a", "aac", "ogg", "oga", "opus", "wav", "wma",
+            "mka",
         ];
 
         /// Convenience helper for consumers that work with owned strings.
@@ -326,18 +332,33 @@ pub mod orchestration {
             }
         }
 
+        /// Pacing of background metadata jobs (enrichment and episode
+        /// matching). On-demand fetches, such as an admin refreshing one
+        /// title, are never held back but count against `max_concurrency`.
         #[derive(Clone, Copy, Debug)]
         #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
         pub struct MetadataLimits {
+            /// Metadata fetches in flight at once, background and on-demand
+            /// together. Zero removes the cap.
             pub max_concurrency: usize,
+            /// Background jobs started per second. Zero removes the limit.
             pub max_qps: u32,
+            /// Background jobs started between two `batch_delay_ms` pauses.
+            /// Zero disables batching.
+            #[cfg_attr(feature = "serde", serde(default))]
+            pub batch_size: usize,
+            /// Pause (milliseconds) after every `batch_size` background jobs.
+            #[cfg_attr(feature = "serde", serde(default))]
+            pub batch_delay_ms: u64,
         }
 
         impl Default for MetadataLimits {
             fn default() -> Self {
                 Self {
-                    max_concurrency: 2,
+                    max_concurrency: 8,
                     max_qps: 100,
+                    batch_size: 0,
+                    batch_delay_ms: 0,
                 }
             }
         }
@@ -1,5 +1,7 @@
 use uuid::Uuid;
 
+use crate::{SeasonID, SeriesID};
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -79,3 +81,16 @@ pub struct SeriesWatchStatus {
     pub seasons: std::collections::HashMap<u16, SeasonWatchStatus>,
     pub next_episode: Option<NextEpisode>,
 }
+
+/// Outcome of marking a whole season or series watched or unwatched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BulkWatchUpdate {
+    pub series_id: SeriesID,
+    /// Set when only one season was changed
+    pub season_id: Option<SeasonID>,
+    pub tmdb_series_id: u64,
+    pub watched: bool,
+    /// Number of episodes whose watch state was written
+    pub episodes: u32,
+}
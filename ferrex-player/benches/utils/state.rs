@@ -75,6 +75,7 @@ pub fn get_or_initialize_global_state() -> Arc<BenchmarkGlobalState> {
                                 real_series
                                     .extend(series_references_sorted.clone());
                             }
+                            LibraryMediaCache::Music { .. } => {}
                         }
                     }
 
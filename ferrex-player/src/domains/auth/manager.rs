@@ -13,8 +13,8 @@ use ferrex_core::domain::users::auth::{
     device::DeviceInfo, domain::value_objects::SessionScope,
 };
 use ferrex_core::player_prelude::{
-    ApiResponse, AuthToken, LoginRequest, Platform, RegisterRequest, User,
-    UserPermissions,
+    ApiResponse, AuthToken, LoginRequest, Platform, RegisterRequest,
+    SessionIntrospection, User, UserPermissions,
 };
 use log::{error, info, warn};
 use reqwest::StatusCode;
@@ -352,6 +352,14 @@ impl AuthManager {
         }
     }
 
+    /// Ask the server how much lifetime the current session token has left.
+    ///
+    /// Used at startup to choose between a silent refresh and re-login
+    /// without performing a privileged request.
+    pub async fn introspect_session(&self) -> AuthResult<SessionIntrospection> {
+        self.fetch_api_data(v1::auth::SESSION).await
+    }
+
     async fn fetch_user_and_permissions(
         &self,
     ) -> AuthResult<(User, UserPermissions)> {
@@ -0,0 +1,37 @@
+//! The current user's collections, shown as their own section of the
+//! library.
+
+use ferrex_core::player_prelude::{Collection, CollectionDetail};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    pub collections: Vec<Collection>,
+    /// The open collection, once its items have loaded.
+    pub open: Option<CollectionDetail>,
+    /// Collection whose items are being fetched.
+    pub opening: Option<Uuid>,
+    pub is_loading: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Load,
+    Loaded(Result<Vec<Collection>, String>),
+    Open(Uuid),
+    Opened(Uuid, Result<CollectionDetail, String>),
+    Close,
+}
+
+impl Message {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Load => "Library::Collections::Load",
+            Self::Loaded(_) => "Library::Collections::Loaded",
+            Self::Open(_) => "Library::Collections::Open",
+            Self::Opened(_, _) => "Library::Collections::Opened",
+            Self::Close => "Library::Collections::Close",
+        }
+    }
+}
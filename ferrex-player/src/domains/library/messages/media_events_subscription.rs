@@ -86,6 +86,9 @@ struct MediaEventState {
     retry_count: u32,
     max_retries: u32,
     api_service: Arc<dyn ApiService>,
+    /// Sequence of the last event received, sent as `Last-Event-ID` on
+    /// reconnect so the server can replay what was missed.
+    last_event_id: Option<u64>,
 }
 
 impl MediaEventState {
@@ -97,6 +100,7 @@ impl MediaEventState {
             retry_count: 0,
             max_retries: 10,
             api_service,
+            last_event_id: None,
         }
     }
 
@@ -179,6 +183,7 @@ impl MediaEventState {
         self.event_receiver = Some(rx);
 
         let api = Arc::clone(&self.api_service);
+        let last_event_id = self.last_event_id;
         // Spawn task to handle EventSource
         let task_handle = tokio::spawn(async move {
             let client = reqwest::Client::new();
@@ -186,6 +191,9 @@ impl MediaEventState {
             if let Some(token) = api.get_token().await {
                 request = request.bearer_auth(token.access_token);
             }
+            if let Some(sequence) = last_event_id {
+                request = request.header("Last-Event-ID", sequence.to_string());
+            }
 
             match reqwest_eventsource::EventSource::new(request) {
                 Ok(mut event_source) => {
@@ -228,6 +236,10 @@ impl MediaEventState {
             return None;
         }
 
+        if let Ok(sequence) = msg.id.parse::<u64>() {
+            self.last_event_id = Some(sequence);
+        }
+
         let declared_event =
             match MediaSseEventType::from_str(msg.event.as_str()) {
                 Ok(event_type) => event_type,
@@ -242,6 +254,14 @@ impl MediaEventState {
                 }
             };
 
+        if declared_event == MediaSseEventType::ResyncRequired {
+            log::warn!(
+                "Media event history expired; reloading libraries from sequence {}",
+                msg.data
+            );
+            return Some(LibraryMessage::LoadLibraries);
+        }
+
         log::debug!(
             "Received media event '{}' with payload of {} bytes",
             declared_event.event_name(),
@@ -387,6 +407,53 @@ impl MediaEventState {
                 // Could emit a scan failed message if needed
                 None
             }
+            MediaEvent::PosterPrewarmProgress {
+                library_id,
+                warmed,
+                skipped,
+                failed,
+                total,
+                ..
+            } => {
+                log::debug!(
+                    "Poster prewarm for library {}: {} warmed, {} skipped, {} failed of {}",
+                    library_id,
+                    warmed,
+                    skipped,
+                    failed,
+                    total
+                );
+                None
+            }
+            MediaEvent::DiskSpaceLow {
+                path,
+                available_bytes,
+                required_bytes,
+            } => {
+                log::warn!(
+                    "Server cache volume {} is low on space ({} of {} bytes free)",
+                    path,
+                    available_bytes,
+                    required_bytes
+                );
+                None
+            }
+            MediaEvent::WatchStateUpdated {
+                series_id,
+                season_id,
+                watched,
+                episodes,
+                ..
+            } => {
+                log::info!(
+                    "Watch state updated for series {} (season {:?}): {} episodes marked {}",
+                    series_id,
+                    season_id,
+                    episodes,
+                    if watched { "watched" } else { "unwatched" }
+                );
+                None
+            }
         }
     }
 }
@@ -2,8 +2,8 @@ pub mod media_events_subscription;
 pub mod scan_subscription;
 pub mod subscriptions;
 
-use crate::domains::library::media_root_browser;
 use crate::domains::library::types::LibrariesBootstrapPayload;
+use crate::domains::library::{collections, media_root_browser};
 use crate::infra::api_types::{Library as ApiLibrary, Media, MediaID};
 use ferrex_core::player_prelude::Library as CoreLibrary;
 use ferrex_core::player_prelude::{
@@ -47,6 +47,9 @@ pub enum LibraryMessage {
     SubmitLibraryForm,
     MediaRootBrowser(media_root_browser::Message),
 
+    // Collections
+    Collections(collections::Message),
+
     // Scanning
     ScanStarted {
         library_id: LibraryId,
@@ -167,6 +170,7 @@ impl LibraryMessage {
             }
             Self::SubmitLibraryForm => "Library::SubmitLibraryForm",
             Self::MediaRootBrowser(msg) => msg.name(),
+            Self::Collections(msg) => msg.name(),
 
             // Scanning
             Self::ScanLibrary(_) => "Library::ScanLibrary",
@@ -328,6 +332,9 @@ impl std::fmt::Debug for LibraryMessage {
             Self::MediaRootBrowser(inner) => {
                 write!(f, "Library::MediaRootBrowser({:?})", inner)
             }
+            Self::Collections(inner) => {
+                write!(f, "Library::Collections({:?})", inner)
+            }
 
             // Scanning
             Self::ScanLibrary(_) => write!(f, "Library::ScanLibrary"),
@@ -284,12 +284,17 @@ mod tests {
                 scan: 1,
                 analyze: 2,
                 index: 3,
+                metadata: 4,
+                image: 5,
             },
             correlation_id: Uuid::now_v7(),
             idempotency_key: "idem".to_string(),
             emitted_at: Utc::now(),
             retrying_items: Some(1),
             dead_lettered_items: Some(2),
+            percent_complete: Some(10.0),
+            items_per_second: Some(2.5),
+            eta_seconds: Some(36),
         }
     }
 
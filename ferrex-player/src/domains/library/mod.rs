@@ -2,6 +2,7 @@
 //!
 //! Contains all library-related state and logic moved from the monolithic State
 
+pub mod collections;
 pub mod media_root_browser;
 pub mod messages;
 pub mod repo_snapshot;
@@ -11,6 +12,7 @@ pub mod update;
 pub mod update_handlers;
 
 use self::{
+    collections::State as CollectionsState,
     media_root_browser::State as MediaRootBrowserState, types::LibraryFormData,
 };
 use crate::common::messages::{CrossDomainEvent, DomainMessage};
@@ -46,6 +48,7 @@ pub struct LibraryDomainState {
     pub scan_metrics: Option<ScanMetrics>,
     pub scan_config: Option<ScanConfig>,
     pub media_root_browser: MediaRootBrowserState,
+    pub collections: CollectionsState,
 
     pub api_service: Option<Arc<dyn ApiService>>,
 
@@ -88,6 +91,7 @@ impl LibraryDomainState {
             scan_metrics: None,
             scan_config: None,
             media_root_browser: MediaRootBrowserState::default(),
+            collections: CollectionsState::default(),
             api_service,
             libraries: Vec::new(),
             repo_accessor,
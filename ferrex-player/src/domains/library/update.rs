@@ -667,6 +667,13 @@ pub fn update_library(
             DomainUpdateResult::task(task.map(DomainMessage::Library))
         }
 
+        LibraryMessage::Collections(inner) => {
+            let task = super::update_handlers::collections::update_collections(
+                state, inner,
+            );
+            DomainUpdateResult::task(task.map(DomainMessage::Library))
+        }
+
         // Scanning - duplicate handler removed
         // Already handled above
         LibraryMessage::ScanStarted {
@@ -691,6 +698,9 @@ pub fn update_library(
                     total_items: 0,
                     retrying_items: 0,
                     dead_lettered_items: 0,
+                    skipped_by_extension: 0,
+                    skipped_too_small: 0,
+                    skipped_too_large: 0,
                     correlation_id,
                     idempotency_key: String::new(),
                     current_path: None,
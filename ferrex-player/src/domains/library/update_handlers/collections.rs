@@ -0,0 +1,124 @@
+use ferrex_core::player_prelude::{Collection, CollectionDetail};
+use iced::Task;
+use uuid::Uuid;
+
+use crate::{
+    domains::library::{
+        collections::{
+            Message as CollectionsMessage, State as CollectionsState,
+        },
+        messages::LibraryMessage,
+    },
+    state::State,
+};
+
+pub fn update_collections(
+    state: &mut State,
+    message: CollectionsMessage,
+) -> Task<LibraryMessage> {
+    match message {
+        CollectionsMessage::Load => handle_load(state),
+        CollectionsMessage::Loaded(result) => handle_loaded(state, result),
+        CollectionsMessage::Open(id) => handle_open(state, id),
+        CollectionsMessage::Opened(id, result) => {
+            handle_opened(state, id, result)
+        }
+        CollectionsMessage::Close => {
+            let collections = collections_state(state);
+            collections.open = None;
+            collections.opening = None;
+            Task::none()
+        }
+    }
+}
+
+fn collections_state(state: &mut State) -> &mut CollectionsState {
+    &mut state.domains.library.state.collections
+}
+
+fn handle_load(state: &mut State) -> Task<LibraryMessage> {
+    let Some(api) = state.domains.library.state.api_service.clone() else {
+        let collections = collections_state(state);
+        collections.error = Some(
+            "Server connection not available; cannot load collections.".into(),
+        );
+        collections.is_loading = false;
+        return Task::none();
+    };
+
+    let collections = collections_state(state);
+    collections.is_loading = true;
+    collections.error = None;
+
+    Task::perform(
+        async move { api.list_collections().await.map_err(|e| e.to_string()) },
+        |result| {
+            LibraryMessage::Collections(CollectionsMessage::Loaded(result))
+        },
+    )
+}
+
+fn handle_loaded(
+    state: &mut State,
+    result: Result<Vec<Collection>, String>,
+) -> Task<LibraryMessage> {
+    let collections = collections_state(state);
+    collections.is_loading = false;
+    match result {
+        Ok(list) => {
+            let open_id = collections.open.as_ref().map(|o| o.collection.id);
+            collections.collections = list;
+            if let Some(id) = open_id {
+                // Refresh the open collection's items and watch state, or
+                // drop it if it was deleted elsewhere.
+                if collections.collections.iter().any(|c| c.id == id) {
+                    return handle_open(state, id);
+                }
+                collections.open = None;
+            }
+        }
+        Err(err) => {
+            log::error!("Failed to load collections: {}", err);
+            collections.error = Some(err);
+        }
+    }
+    Task::none()
+}
+
+fn handle_open(state: &mut State, id: Uuid) -> Task<LibraryMessage> {
+    let Some(api) = state.domains.library.state.api_service.clone() else {
+        return Task::none();
+    };
+
+    let collections = collections_state(state);
+    collections.opening = Some(id);
+    collections.error = None;
+
+    Task::perform(
+        async move { api.fetch_collection(id).await.map_err(|e| e.to_string()) },
+        move |result| {
+            LibraryMessage::Collections(CollectionsMessage::Opened(id, result))
+        },
+    )
+}
+
+fn handle_opened(
+    state: &mut State,
+    id: Uuid,
+    result: Result<CollectionDetail, String>,
+) -> Task<LibraryMessage> {
+    let collections = collections_state(state);
+    // A later Open or Close superseded this response.
+    if collections.opening != Some(id) {
+        return Task::none();
+    }
+    collections.opening = None;
+    match result {
+        Ok(detail) => collections.open = Some(detail),
+        Err(err) => {
+            log::error!("Failed to load collection {}: {}", id, err);
+            collections.error = Some(err);
+        }
+    }
+    Task::none()
+}
@@ -28,6 +28,7 @@ pub fn handle_create_library(
         scan_interval_minutes: library.scan_interval_minutes,
         enabled: library.enabled,
         movie_ref_batch_size: library.movie_ref_batch_size.get(),
+        exclude_patterns: library.exclude_patterns.clone(),
         start_scan,
     };
 
@@ -98,6 +99,7 @@ pub fn handle_update_library(
         scan_interval_minutes: Some(library.scan_interval_minutes),
         enabled: Some(library.enabled),
         movie_ref_batch_size: None,
+        exclude_patterns: Some(library.exclude_patterns.clone()),
     };
 
     let api = state.api_service.clone();
@@ -217,6 +219,7 @@ pub fn handle_show_library_form(
                 library_type: match lib.library_type {
                     LibraryType::Movies => "Movies".to_string(),
                     LibraryType::Series => "TvShows".to_string(),
+                    LibraryType::Music => "Music".to_string(),
                 },
                 paths: lib
                     .paths
@@ -309,6 +312,7 @@ pub fn handle_reset_library(
                 scan_interval_minutes: lib.scan_interval_minutes,
                 enabled: lib.enabled,
                 movie_ref_batch_size: lib.movie_ref_batch_size.get(),
+                exclude_patterns: lib.exclude_patterns.clone(),
                 start_scan: true,
             };
             let _id = create_api
@@ -436,6 +440,7 @@ pub fn handle_submit_library_form(state: &mut State) -> Task<LibraryMessage> {
         let library_type = match form_data.library_type.as_str() {
             "Movies" => crate::infra::api_types::LibraryType::Movies,
             "TvShows" => crate::infra::api_types::LibraryType::Series,
+            "Music" => crate::infra::api_types::LibraryType::Music,
             _ => {
                 state
                     .domains
@@ -474,6 +479,7 @@ pub fn handle_submit_library_form(state: &mut State) -> Task<LibraryMessage> {
             analyze_on_scan: true,
             max_retry_attempts: 3,
             movie_ref_batch_size: MovieReferenceBatchSize::default(),
+            exclude_patterns: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -499,6 +505,9 @@ pub fn handle_submit_library_form(state: &mut State) -> Task<LibraryMessage> {
                         ),
                         enabled: Some(library.enabled),
                         movie_ref_batch_size: None,
+                        exclude_patterns: Some(
+                            library.exclude_patterns.clone(),
+                        ),
                     };
                     api.update_library(library.id, req).await.map(|_| library)
                 },
@@ -530,6 +539,7 @@ pub fn handle_submit_library_form(state: &mut State) -> Task<LibraryMessage> {
                         movie_ref_batch_size: library
                             .movie_ref_batch_size
                             .get(),
+                        exclude_patterns: library.exclude_patterns.clone(),
                         start_scan,
                     };
                     api.create_library(req).await.map(|_| library)
@@ -2,6 +2,7 @@
 //!
 //! Contains specific update logic for library-related messages
 
+pub mod collections;
 #[cfg(feature = "demo")]
 pub mod demo_controls;
 pub mod library_loaded;
@@ -15,6 +16,7 @@ pub mod select_library;
 pub mod series_bundles;
 
 // Re-export update functions
+pub use collections::*;
 #[cfg(feature = "demo")]
 pub use demo_controls::*;
 pub use library_loaded::*;
@@ -198,6 +198,16 @@ pub fn update_media(
                         _ => None,
                     };
 
+                    // Restarting, resuming behind the stored position or
+                    // seeking back is an explicit rewind; the server
+                    // otherwise refuses to move progress backwards.
+                    let reset = rewinds_progress(
+                        state.user_watch_state.as_ref(),
+                        &media_id,
+                        position,
+                        duration,
+                    );
+
                     DomainUpdateResult::task(Task::perform(
                         async move {
                             let request = UpdateProgressRequest {
@@ -207,6 +217,10 @@ pub fn update_media(
                                 duration: duration as f32,
                                 episode: episode_key_opt,
                                 last_media_uuid: Some(media_id.to_uuid()),
+                                reported_at: Some(
+                                    chrono::Utc::now().timestamp_millis(),
+                                ),
+                                reset,
                             };
                             api_service
                                 .update_progress(&request)
@@ -244,3 +258,60 @@ pub fn update_media(
         }
     }
 }
+
+/// Whether a report at `position` of `duration` is behind the progress
+/// stored for `media_id`. The stored progress is what the server last
+/// accepted, including reports from other devices, so this covers playback
+/// started from the beginning or behind the resume point as well as
+/// seeking back. Compares progress ratios, as the server does.
+fn rewinds_progress(
+    watch_state: Option<&UserWatchState>,
+    media_id: &MediaID,
+    position: f64,
+    duration: f64,
+) -> bool {
+    watch_state
+        .and_then(|watch_state| watch_state.get_by_media_id(media_id.as_uuid()))
+        .is_some_and(|stored| {
+            stored.duration > 0.0
+                && position / duration
+                    < f64::from(stored.position) / f64::from(stored.duration)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrex_core::player_prelude::MovieID;
+    use uuid::Uuid;
+
+    #[test]
+    fn reports_behind_the_stored_position_rewind() {
+        let media_id = MediaID::Movie(MovieID(Uuid::now_v7()));
+        let mut watch_state = UserWatchState::new();
+        watch_state.update_progress(media_id.to_uuid(), 3000.0, 6000.0);
+
+        // Play from start, or resume on a device that fell behind.
+        assert!(rewinds_progress(
+            Some(&watch_state),
+            &media_id,
+            12.0,
+            6000.0
+        ));
+        assert!(!rewinds_progress(
+            Some(&watch_state),
+            &media_id,
+            3010.0,
+            6000.0
+        ));
+
+        let unwatched = MediaID::Movie(MovieID(Uuid::now_v7()));
+        assert!(!rewinds_progress(
+            Some(&watch_state),
+            &unwatched,
+            12.0,
+            6000.0
+        ));
+        assert!(!rewinds_progress(None, &media_id, 12.0, 6000.0));
+    }
+}
@@ -11,6 +11,7 @@ use std::sync::{
 };
 use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
 
 use crate::infra::constants::image::{
     IMAGE_MAX_RETRY_ATTEMPTS, IMAGE_RETRY_THROTTLE,
@@ -57,6 +58,9 @@ pub struct UnifiedImageService {
     // Ready immutable blob tokens keyed by request (v2 image pipeline).
     ready_tokens: Arc<DashMap<ImageRequest, String>>,
 
+    // Blurred stand-ins keyed by image id, shown while a size is pending.
+    placeholders: Arc<DashMap<Uuid, Handle>>,
+
     // Priority queue for pending loads (using u8 priority, higher is better)
     queue: Arc<Mutex<PriorityQueue<ImageRequest, u8>>>,
 
@@ -101,6 +105,7 @@ impl UnifiedImageService {
         let service = Self {
             cache: Arc::new(DashMap::new()),
             ready_tokens: Arc::new(DashMap::new()),
+            placeholders: Arc::new(DashMap::new()),
             queue: Arc::new(Mutex::new(PriorityQueue::new())),
             loading: Arc::new(DashMap::new()),
             load_sender,
@@ -135,6 +140,21 @@ impl UnifiedImageService {
         self.ready_tokens.remove(request);
     }
 
+    /// Remember the server-provided placeholder for an image. The first one
+    /// wins; every size of the image shares it.
+    pub fn set_placeholder(&self, iid: Uuid, bytes: Vec<u8>) {
+        self.placeholders
+            .entry(iid)
+            .or_insert_with(|| Handle::from_bytes(bytes));
+    }
+
+    /// Blurred stand-in to draw until `request` finishes loading.
+    pub fn placeholder(&self, request: &ImageRequest) -> Option<Handle> {
+        self.placeholders
+            .get(&request.iid)
+            .map(|h| h.value().clone())
+    }
+
     /// Maximum allowed concurrent loads for the unified image service.
     pub fn max_concurrent(&self) -> usize {
         self.max_concurrent.load(Ordering::SeqCst)
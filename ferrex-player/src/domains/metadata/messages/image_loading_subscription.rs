@@ -279,7 +279,18 @@ fn image_loader_stream_concurrent(
                                                                     svc.request_image(req);
                                                                 }
                                                             }
-                                                            ImageManifestStatus::Pending { .. } => {
+                                                            ImageManifestStatus::Pending { placeholder, .. } => {
+                                                                if let Some(bytes) = placeholder {
+                                                                    svc.set_placeholder(req.iid, bytes);
+                                                                }
+                                                                svc.mark_pending(&req);
+                                                            }
+                                                            ImageManifestStatus::Fallback { imz, token, .. } => {
+                                                                // Load the smaller cached size under its own
+                                                                // key; the requested size stays pending.
+                                                                let fallback = ImageRequest::new(result.iid, imz);
+                                                                svc.set_ready_token(&fallback, token);
+                                                                svc.request_image(fallback);
                                                                 svc.mark_pending(&req);
                                                             }
                                                             ImageManifestStatus::Missing { reason } => {
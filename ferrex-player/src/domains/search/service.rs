@@ -10,15 +10,33 @@ use super::metrics::SearchPerformanceMetrics;
 use super::types::{SearchResponse, SearchStrategy};
 use chrono::Datelike;
 use ferrex_core::player_prelude::{
-    ArchivedModel, MediaWithStatus, SearchField,
+    ArchivedModel, MediaSearchQuery, SearchField,
 };
-use ferrex_core::query::MediaQueryBuilder;
 use ferrex_model::{LibraryId, Media, MediaID};
 use log::warn;
 use std::time::Instant;
 
 const SERVER_SEARCH_LIMIT: usize = 50;
 
+/// The `fields` parameter of the search endpoint. It understands titles,
+/// overviews and cast; other fields are dropped and an empty selection
+/// falls back to the server's default (titles).
+fn server_search_fields(fields: &[SearchField]) -> Option<String> {
+    if fields.contains(&SearchField::All) {
+        return Some("all".to_string());
+    }
+    let names: Vec<&str> = fields
+        .iter()
+        .filter_map(|field| match field {
+            SearchField::Title => Some("title"),
+            SearchField::Overview => Some("overview"),
+            SearchField::Cast => Some("cast"),
+            _ => None,
+        })
+        .collect();
+    (!names.is_empty()).then(|| names.join(","))
+}
+
 /// Service for executing searches
 #[derive(Debug)]
 pub struct SearchService {
@@ -387,45 +405,23 @@ impl SearchService {
             )
         })?;
 
-        // Build MediaQuery for server
-        let mut query_builder = MediaQueryBuilder::new();
-
         // Global search only: ignore any library filter for now
+        let search_query = MediaSearchQuery {
+            q: query.to_string(),
+            fields: server_search_fields(fields),
+            limit: Some(SERVER_SEARCH_LIMIT),
+            ..MediaSearchQuery::default()
+        };
 
-        // Always use fuzzy search (which includes exact matches)
-        // NOTE: the server-side fuzzy path is currently title-only; treat
-        // `All` as "Title" until multi-field fuzzy search is implemented.
-        let effective_fields =
-            if fields.is_empty() || fields.contains(&SearchField::All) {
-                vec![SearchField::Title]
-            } else {
-                fields.to_vec()
-            };
-
-        query_builder = query_builder.search_in(query, effective_fields);
-
-        let media_query = query_builder.limit(SERVER_SEARCH_LIMIT).build();
-
-        // Log the query being sent for debugging
         log::debug!(
-            "Sending search query to server: text='{}', fuzzy={}, fields={:?}",
+            "Sending search query to server: text='{}', fields={:?}",
             query,
-            media_query
-                .search
-                .as_ref()
-                .map(|s| s.fuzzy)
-                .unwrap_or(false),
-            media_query
-                .search
-                .as_ref()
-                .map(|s| &s.fields)
-                .unwrap_or(&vec![])
+            search_query.fields
         );
 
-        // Execute server query via API endpoint
-        let response = match api_service.query_media(media_query.clone()).await
-        {
-            Ok(response) => response,
+        // Execute server query via the ranked search endpoint
+        let hits = match api_service.search_media(search_query).await {
+            Ok(hits) => hits,
             Err(e) => {
                 log::warn!(
                     "Server search failed for query '{}', with error {:?}",
@@ -436,9 +432,19 @@ impl SearchService {
             }
         };
 
-        // Convert server results to SearchResponse
-        self.convert_api_results_from_status(response, query)
-            .map(|opt_vec| opt_vec.into_iter().flatten().collect())
+        // Keep the server's ranking; the card itself comes from the local
+        // repository so it can be opened directly.
+        let mut results = Vec::with_capacity(hits.len());
+        for hit in hits {
+            if let Some(mut result) =
+                self.convert_media_ref_to_result(hit.id, query)?
+            {
+                result.match_score = hit.score;
+                result.match_field = hit.matched;
+                results.push(result);
+            }
+        }
+        Ok(results)
     }
 
     async fn search_hybrid(
@@ -676,26 +682,7 @@ impl SearchService {
     //     }
     // }
 
-    /// Convert API response with status to SearchResult format
-    #[cfg_attr(
-        any(
-            feature = "profile-with-puffin",
-            feature = "profile-with-tracy",
-            feature = "profile-with-tracing"
-        ),
-        profiling::function
-    )]
-    fn convert_api_results_from_status(
-        &self,
-        response: Vec<MediaWithStatus>,
-        query: &str,
-    ) -> anyhow::Result<Vec<Option<SearchResponse>>, SearchError> {
-        response
-            .into_iter()
-            .map(|item| self.convert_media_ref_to_result(item.id, query))
-            .collect()
-    }
-
+    /// Build a search result card from the locally cached media
     #[cfg_attr(
         any(
             feature = "profile-with-puffin",
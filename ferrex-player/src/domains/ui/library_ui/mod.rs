@@ -1,6 +1,6 @@
 pub mod update;
 
-use crate::domains::ui::messages::UiMessage;
+use crate::domains::{library::collections, ui::messages::UiMessage};
 pub use update::update_library_ui;
 
 use ferrex_core::player_prelude::{
@@ -25,6 +25,7 @@ pub enum LibraryUiMessage {
     ApplyFilters, // Build spec from UI inputs and request filtered positions
     ClearFilters, // Clear UI inputs and reset filters
     SortedIndexFailed(String), // Report fetch failure
+    Collections(collections::Message), // Proxy for collection actions
 }
 
 impl From<LibraryUiMessage> for UiMessage {
@@ -52,6 +53,7 @@ impl LibraryUiMessage {
             Self::ApplyFilters => "UI::ApplyFilters",
             Self::ClearFilters => "UI::ClearFilters",
             Self::SortedIndexFailed(_) => "UI::SortedIndexFailed",
+            Self::Collections(msg) => msg.name(),
         }
     }
 }
@@ -86,6 +88,7 @@ impl std::fmt::Debug for LibraryUiMessage {
             Self::ApplyFilters => write!(f, "UI::ApplyFilters"),
             Self::ClearFilters => write!(f, "UI::ClearFilters"),
             Self::SortedIndexFailed(_) => write!(f, "UI::SortedIndexFailed"),
+            Self::Collections(msg) => write!(f, "UI::Collections({msg:?})"),
         }
     }
 }
@@ -3,6 +3,7 @@ use iced::Task;
 use crate::{
     common::messages::{DomainMessage, DomainUpdateResult},
     domains::{
+        library::messages::LibraryMessage,
         metadata::demand_planner::DemandSnapshot,
         ui::{
             library_ui::LibraryUiMessage,
@@ -349,6 +350,8 @@ pub fn update_library_ui(
                         search,
                         sort: core_sort,
                         order: core_order,
+                        certification_max: None,
+                        include_unrated: false,
                     };
                     let spec = build_filter_indices_request(params);
                     let spec_hash = hash_filter_spec(&spec);
@@ -490,5 +493,10 @@ pub fn update_library_ui(
                 Some(format!("Unable to apply sort/filter: {}", err));
             DomainUpdateResult::task(Task::none())
         }
+        LibraryUiMessage::Collections(message) => {
+            DomainUpdateResult::task(Task::done(DomainMessage::Library(
+                LibraryMessage::Collections(message),
+            )))
+        }
     }
 }
@@ -153,6 +153,9 @@ impl ViewIdentifier for ViewState {
             ViewState::UserSettings => {
                 ScrollPositionManager::generate_key("user_settings", None, None)
             }
+            ViewState::Collections => {
+                ScrollPositionManager::generate_key("collections", None, None)
+            }
             ViewState::Player
             | ViewState::LoadingVideo { .. }
             | ViewState::VideoError { .. } => {
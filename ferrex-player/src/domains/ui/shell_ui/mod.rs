@@ -46,6 +46,7 @@ pub enum UiShellMessage {
     ViewTvShow(SeriesID),
     ViewSeason(SeriesID, SeasonID),
     ViewEpisode(EpisodeID),
+    ShowCollections,
 
     // Header navigation
     NavigateHome,
@@ -89,6 +90,7 @@ impl UiShellMessage {
             Self::ViewTvShow(_) => "UI::ViewTvShow",
             Self::ViewSeason(_, _) => "UI::ViewSeason",
             Self::ViewEpisode(_) => "UI::ViewEpisode",
+            Self::ShowCollections => "UI::ShowCollections",
 
             // Header navigation
             Self::NavigateHome => "UI::NavigateHome",
@@ -137,6 +139,9 @@ impl std::fmt::Debug for UiShellMessage {
             UiShellMessage::ViewEpisode(id) => {
                 write!(f, "UI::ViewEpisode({})", id)
             }
+            UiShellMessage::ShowCollections => {
+                write!(f, "UI::ShowCollections")
+            }
             UiShellMessage::NavigateHome => write!(f, "UI::NavigateHome"),
             UiShellMessage::NavigateBack => write!(f, "UI::NavigateBack"),
             UiShellMessage::MainWindowOpened(id) => {
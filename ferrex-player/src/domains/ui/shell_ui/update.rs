@@ -1,6 +1,7 @@
 use crate::{
     common::messages::{CrossDomainEvent, DomainMessage, DomainUpdateResult},
     domains::{
+        library::{collections, messages::LibraryMessage},
         metadata::demand_planner::DemandSnapshot,
         ui::{
             search_surface,
@@ -395,6 +396,20 @@ pub fn update_shell_ui(
                 navigation_updates::handle_view_episode(state, episode_id);
             DomainUpdateResult::task(task.map(DomainMessage::Ui))
         }
+        UiShellMessage::ShowCollections => {
+            if !matches!(state.domains.ui.state.view, ViewState::Collections) {
+                state
+                    .domains
+                    .ui
+                    .state
+                    .navigation_history
+                    .push(state.domains.ui.state.view.clone());
+                state.domains.ui.state.view = ViewState::Collections;
+            }
+            DomainUpdateResult::task(Task::done(DomainMessage::Library(
+                LibraryMessage::Collections(collections::Message::Load),
+            )))
+        }
         UiShellMessage::NavigateHome => {
             // Clear navigation history when going home
             state.domains.ui.state.navigation_history.clear();
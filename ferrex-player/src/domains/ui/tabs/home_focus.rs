@@ -77,6 +77,7 @@ impl HomeFocusState {
                     self.ordered_keys
                         .push(CarouselKey::LibrarySeries(lib_id.to_uuid()));
                 }
+                LibraryType::Music => {}
             }
         }
 
@@ -159,6 +160,7 @@ pub fn ordered_keys_for_home(state: &State) -> Vec<CarouselKey> {
             LibraryType::Series => {
                 keys.push(CarouselKey::LibrarySeries(lib_id.to_uuid()))
             }
+            LibraryType::Music => {}
         }
     }
 
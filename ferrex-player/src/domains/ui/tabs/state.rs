@@ -305,6 +305,7 @@ mod tests {
             analyze_on_scan: false,
             max_retry_attempts: 3,
             movie_ref_batch_size: MovieReferenceBatchSize::default(),
+            exclude_patterns: Vec::new(),
             created_at: ferrex_model::chrono::Utc::now(),
             updated_at: ferrex_model::chrono::Utc::now(),
             media: None,
@@ -344,6 +345,7 @@ mod tests {
                 library_id,
             },
             theme_color: None,
+            editions: Vec::new(),
         };
 
         let movie_b = MovieReference {
@@ -366,6 +368,7 @@ mod tests {
                 library_id,
             },
             theme_color: None,
+            editions: Vec::new(),
         };
 
         let batch = MovieReferenceBatchResponse {
@@ -519,8 +522,11 @@ impl LibraryTabState {
         library_type: LibraryType,
         accessor: Accessor<ReadOnly>,
     ) -> Self {
+        // Music libraries have no grid media; their cache stays empty.
         let cached_media = match library_type {
-            LibraryType::Movies => CachedMedia::Movies(Vec::new()),
+            LibraryType::Movies | LibraryType::Music => {
+                CachedMedia::Movies(Vec::new())
+            }
             LibraryType::Series => CachedMedia::TvShows(Vec::new()),
         };
 
@@ -739,7 +745,9 @@ impl LibraryTabState {
         self.filtered_indices = None;
 
         self.cached_media = match self.library_type {
-            LibraryType::Movies => CachedMedia::Movies(Vec::new()),
+            LibraryType::Movies | LibraryType::Music => {
+                CachedMedia::Movies(Vec::new())
+            }
             LibraryType::Series => CachedMedia::TvShows(Vec::new()),
         };
 
@@ -766,10 +774,11 @@ impl LibraryTabState {
         }
     }
 
-    fn media_id_for_uuid(&self, id: Uuid) -> MediaID {
+    fn media_id_for_uuid(&self, id: Uuid) -> Option<MediaID> {
         match self.library_type {
-            LibraryType::Movies => MediaID::Movie(MovieID(id)),
-            LibraryType::Series => MediaID::Series(SeriesID(id)),
+            LibraryType::Movies => Some(MediaID::Movie(MovieID(id))),
+            LibraryType::Series => Some(MediaID::Series(SeriesID(id))),
+            LibraryType::Music => None,
         }
     }
 
@@ -817,7 +826,7 @@ impl LibraryTabState {
                 }
                 LibraryType::Series => {
                     let existing_lookup_id =
-                        self.media_id_for_uuid(existing_id);
+                        self.media_id_for_uuid(existing_id)?;
                     let existing_yoke = match self
                         .accessor
                         .get_media_yoke(&existing_lookup_id)
@@ -844,6 +853,7 @@ impl LibraryTabState {
                         }
                     }
                 }
+                LibraryType::Music => return None,
             };
 
             let ord = self.compare_media_with_fallback(
@@ -998,6 +1008,7 @@ impl LibraryTabState {
                 .iter()
                 .filter(|m| matches!(m, ArchivedMedia::Series(_)))
                 .collect(),
+            LibraryType::Music => Vec::new(),
         };
 
         filtered
@@ -1042,6 +1053,7 @@ impl LibraryTabState {
                 .iter()
                 .filter(|m| matches!(m, ArchivedMedia::Series(_)))
                 .collect(),
+            LibraryType::Music => Vec::new(),
         };
 
         let mut preload: Vec<ArchivedMediaID> = filtered
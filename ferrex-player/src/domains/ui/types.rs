@@ -35,6 +35,7 @@ pub enum ViewState {
         backdrop_handle: Option<iced::widget::image::Handle>, // Cached backdrop handle
     },
     UserSettings, // User settings and preferences view
+    Collections,  // The user's collections and the items of the open one
 }
 
 #[cfg_attr(
@@ -55,6 +56,7 @@ impl ViewState {
                 | ViewState::AdminDashboard
                 | ViewState::AdminUsers
                 | ViewState::UserSettings
+                | ViewState::Collections
                 | ViewState::MovieDetail { .. }
                 | ViewState::SeriesDetail { .. }
                 | ViewState::SeasonDetail { .. }
@@ -83,7 +85,8 @@ impl ViewState {
                 ViewState::LibraryManagement
                 | ViewState::AdminDashboard
                 | ViewState::AdminUsers
-                | ViewState::UserSettings => {
+                | ViewState::UserSettings
+                | ViewState::Collections => {
                     Some(crate::infra::constants::layout::header::HEIGHT)
                 } // Same header height
                 _ => None,
@@ -61,6 +61,7 @@ fn fetch_media_for_uuid(
             .repo_accessor
             .get(&MediaID::Series(SeriesID(id)))
             .ok(),
+        LibraryType::Music => None,
     }
 }
 
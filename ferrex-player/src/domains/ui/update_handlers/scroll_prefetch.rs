@@ -217,6 +217,7 @@ pub fn maybe_run_grid_scroll_prefetch(state: &mut State, now: Instant) {
                     prefetched += 1;
                 }
             }
+            crate::infra::api_types::LibraryType::Music => {}
         }
     }
 }
@@ -49,6 +49,7 @@ pub fn init_all_tab_virtual_carousels(state: &mut State) {
                 LibraryType::Series => {
                     CarouselKey::LibrarySeries(lib_id.to_uuid())
                 }
+                LibraryType::Music => continue,
             };
             let scale = state.domains.ui.state.scaled_layout.scale;
             state.domains.ui.state.carousel_registry.ensure_default(
@@ -148,6 +149,7 @@ pub fn emit_initial_all_tab_snapshots(state: &mut State) {
                     LibraryType::Series => {
                         CarouselKey::LibrarySeries(lib_id.to_uuid())
                     }
+                    LibraryType::Music => continue,
                 };
                 emit_snapshot_for_carousel_simple(
                     state_ref,
@@ -45,6 +45,7 @@ pub fn primary_poster_iid_for_library_media(
                 Media::Series(sr) => sr.details.primary_poster_iid,
                 _ => None,
             }),
+        LibraryType::Music => None,
     }
 }
 
@@ -96,6 +97,7 @@ pub fn primary_poster_iid_for_library_media_cached(
                 )
             }
         }
+        LibraryType::Music => None,
     }
 }
 
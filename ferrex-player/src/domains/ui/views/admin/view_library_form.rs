@@ -139,6 +139,18 @@ pub fn view_library_form<'a>(
                         .into()
                     }
                 ),
+                Space::new().width(Length::Fixed(30.0)),
+                radio(
+                    "Music",
+                    "Music",
+                    Some(form_data.library_type.as_str()),
+                    |value| {
+                        SettingsUiMessage::UpdateLibraryFormType(
+                            value.to_string(),
+                        )
+                        .into()
+                    }
+                ),
             ]
             .spacing(20)
         ]
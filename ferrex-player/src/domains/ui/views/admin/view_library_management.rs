@@ -163,6 +163,7 @@ fn create_library_card<'a>(
         let library_type_icon = match library.library_type {
             ArchivedLibraryType::Movies => "🎬",
             ArchivedLibraryType::Series => "📺",
+            ArchivedLibraryType::Music => "🎵",
         };
 
         let status_text = if library.enabled {
@@ -427,10 +428,34 @@ fn scan_status_panel(state: &State) -> Element<'_, UiMessage> {
                 )
             };
 
-            let percent = if total_items > 0 {
-                (completed_items as f32 / total_items as f32 * 100.0).round()
-            } else {
-                0.0
+            let percent = match progress
+                .as_ref()
+                .and_then(|event| event.percent_complete)
+            {
+                Some(percent) => percent.round(),
+                None if total_items > 0 => {
+                    (completed_items as f32 / total_items as f32 * 100.0)
+                        .round()
+                }
+                None => 0.0,
+            };
+
+            let eta_text = match &progress {
+                Some(event) if event.status == "discovering" => {
+                    Some("Discovering…".to_string())
+                }
+                Some(event)
+                    if matches!(
+                        snapshot.status,
+                        ScanLifecycleStatus::Running
+                    ) =>
+                {
+                    Some(match event.eta_seconds {
+                        Some(seconds) => format!("ETA {}", format_eta(seconds)),
+                        None => "Estimating…".to_string(),
+                    })
+                }
+                _ => None,
             };
 
             let status_label = match snapshot.status {
@@ -486,6 +511,10 @@ fn scan_status_panel(state: &State) -> Element<'_, UiMessage> {
                     .size(13)
                     .color(theme::MediaServerTheme::TEXT_SECONDARY),
                 Space::new().width(20),
+                text(eta_text.unwrap_or_default())
+                    .size(13)
+                    .color(theme::MediaServerTheme::TEXT_SECONDARY),
+                Space::new().width(20),
                 text(path_text)
                     .size(13)
                     .color(theme::MediaServerTheme::TEXT_SECONDARY),
@@ -612,6 +641,14 @@ fn scan_status_panel(state: &State) -> Element<'_, UiMessage> {
         .into()
 }
 
+fn format_eta(seconds: u64) -> String {
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
 fn truncate_path(path: &str) -> String {
     const MAX_LEN: usize = 48;
     if path.len() <= MAX_LEN {
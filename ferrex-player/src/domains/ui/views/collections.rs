@@ -0,0 +1,214 @@
+//! The current user's collections: a list of collections on the left and
+//! the items of the open one on the right.
+
+use crate::domains::library::collections::{self, State as CollectionsState};
+use crate::domains::ui::library_ui::LibraryUiMessage;
+use crate::domains::ui::messages::UiMessage;
+use crate::domains::ui::shell_ui::UiShellMessage;
+use crate::domains::ui::theme;
+use crate::state::State;
+use ferrex_core::player_prelude::{Collection, CollectionItem, MediaID};
+use iced::widget::{Space, button, column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+fn collections_message(message: collections::Message) -> UiMessage {
+    LibraryUiMessage::Collections(message).into()
+}
+
+pub fn view_collections(state: &State) -> Element<'_, UiMessage> {
+    let fonts = &state.domains.ui.state.size_provider.font;
+    let collections = &state.domains.library.state.collections;
+
+    let mut list = column![].spacing(6);
+    if collections.collections.is_empty() && !collections.is_loading {
+        list = list.push(
+            text("No collections yet.")
+                .size(fonts.body)
+                .color(theme::MediaServerTheme::TEXT_SECONDARY),
+        );
+    }
+    for collection in &collections.collections {
+        list = list.push(collection_entry(state, collections, collection));
+    }
+
+    let sidebar = container(scrollable(list.padding(10)).height(Length::Fill))
+        .style(theme::Container::Card.style())
+        .width(Length::Fixed(280.0))
+        .height(Length::Fill);
+
+    let mut body = column![].spacing(12);
+    if let Some(error) = &collections.error {
+        body = body.push(
+            container(text(error).size(fonts.caption))
+                .padding(12)
+                .width(Length::Fill)
+                .style(theme::Container::ErrorBox.style()),
+        );
+    }
+    body = body.push(open_collection(state, collections));
+
+    container(
+        row![sidebar, body.width(Length::Fill)]
+            .spacing(20)
+            .padding(20),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn collection_entry<'a>(
+    state: &'a State,
+    collections: &'a CollectionsState,
+    collection: &'a Collection,
+) -> Element<'a, UiMessage> {
+    let fonts = &state.domains.ui.state.size_provider.font;
+    let is_open = collections
+        .open
+        .as_ref()
+        .is_some_and(|open| open.collection.id == collection.id)
+        || collections.opening == Some(collection.id);
+
+    button(
+        column![
+            text(&collection.name).size(fonts.body),
+            text(item_count_label(collection.item_count))
+                .size(fonts.caption)
+                .color(theme::MediaServerTheme::TEXT_SECONDARY),
+        ]
+        .spacing(2),
+    )
+    .on_press(collections_message(collections::Message::Open(
+        collection.id,
+    )))
+    .style(if is_open {
+        theme::Button::Primary.style()
+    } else {
+        theme::Button::Secondary.style()
+    })
+    .padding([8, 12])
+    .width(Length::Fill)
+    .into()
+}
+
+fn open_collection<'a>(
+    state: &'a State,
+    collections: &'a CollectionsState,
+) -> Element<'a, UiMessage> {
+    let fonts = &state.domains.ui.state.size_provider.font;
+
+    let Some(open) = &collections.open else {
+        let hint = if collections.opening.is_some() || collections.is_loading {
+            "Loading…"
+        } else {
+            "Select a collection to see its items."
+        };
+        return container(
+            text(hint)
+                .size(fonts.body)
+                .color(theme::MediaServerTheme::TEXT_SECONDARY),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into();
+    };
+
+    let mut header = column![
+        text(&open.collection.name)
+            .size(fonts.title)
+            .color(theme::MediaServerTheme::TEXT_PRIMARY)
+    ]
+    .spacing(4);
+    if let Some(description) = &open.collection.description {
+        header = header.push(
+            text(description)
+                .size(fonts.body)
+                .color(theme::MediaServerTheme::TEXT_SECONDARY),
+        );
+    }
+
+    let mut items = column![].spacing(8);
+    if open.items.is_empty() {
+        items = items.push(
+            text("This collection is empty.")
+                .size(fonts.body)
+                .color(theme::MediaServerTheme::TEXT_SECONDARY),
+        );
+    }
+    for item in &open.items {
+        items = items.push(collection_item(state, item));
+    }
+
+    column![
+        container(header)
+            .style(theme::Container::Card.style())
+            .padding(16)
+            .width(Length::Fill),
+        scrollable(
+            container(items.padding(10))
+                .style(theme::Container::Card.style())
+                .width(Length::Fill),
+        )
+        .height(Length::Fill),
+    ]
+    .spacing(12)
+    .into()
+}
+
+fn collection_item<'a>(
+    state: &'a State,
+    item: &'a CollectionItem,
+) -> Element<'a, UiMessage> {
+    let fonts = &state.domains.ui.state.size_provider.font;
+
+    let mut title = row![text(&item.title).size(fonts.body)].spacing(10);
+    if let Some(subtitle) = &item.subtitle {
+        title = title.push(
+            text(subtitle)
+                .size(fonts.caption)
+                .color(theme::MediaServerTheme::TEXT_SECONDARY),
+        );
+    }
+
+    let open = match item.media_id {
+        MediaID::Movie(id) => Some(UiShellMessage::ViewMovieDetails(id)),
+        MediaID::Episode(id) => Some(UiShellMessage::ViewEpisode(id)),
+        _ => None,
+    };
+
+    button(
+        row![
+            title,
+            Space::new().width(Length::Fill),
+            text(watch_label(item))
+                .size(fonts.caption)
+                .color(theme::MediaServerTheme::TEXT_SECONDARY),
+        ]
+        .align_y(iced::Alignment::Center),
+    )
+    .on_press_maybe(open.map(Into::into))
+    .style(theme::Button::Secondary.style())
+    .padding([8, 12])
+    .width(Length::Fill)
+    .into()
+}
+
+fn item_count_label(count: u32) -> String {
+    match count {
+        1 => "1 item".to_string(),
+        n => format!("{n} items"),
+    }
+}
+
+fn watch_label(item: &CollectionItem) -> String {
+    let watch = &item.watch;
+    if watch.completed {
+        return "Watched".to_string();
+    }
+    match (watch.position, watch.duration) {
+        (Some(position), Some(duration)) if duration > 0.0 => {
+            format!("{:.0}% watched", position / duration * 100.0)
+        }
+        _ => String::new(),
+    }
+}
@@ -98,8 +98,19 @@ pub fn view_header<'a>(state: &'a State) -> Element<'a, UiMessage> {
             .width(Length::Fixed(HEIGHT))
             .height(HEIGHT);
 
+            let collections_button = button(
+                container(icon_text_with_size(Icon::List, 16.0))
+                    .center_x(Length::Fill)
+                    .center_y(Length::Fill),
+            )
+            .on_press(UiShellMessage::ShowCollections.into())
+            .style(theme::Button::HeaderIcon.style())
+            .width(Length::Fixed(HEIGHT))
+            .height(HEIGHT);
+
             let mut right_section = row![
                 search_button,
+                collections_button,
                 button(
                     container(icon_text_with_size(
                         if state.is_fullscreen {
@@ -667,6 +678,52 @@ pub fn view_header<'a>(state: &'a State) -> Element<'a, UiMessage> {
                 .height(HEIGHT)
                 .into()
         }
+        ViewState::Collections => {
+            let left_section = row![
+                button(
+                    container(icon_text_with_size(Icon::House, 16.0))
+                        .center_x(Length::Fill)
+                        .center_y(Length::Fill),
+                )
+                .on_press(UiShellMessage::NavigateHome.into())
+                .style(theme::Button::HeaderIcon.style())
+                .width(Length::Fixed(HEIGHT))
+                .height(HEIGHT),
+                button(
+                    container(icon_text_with_size(Icon::ChevronLeft, 16.0))
+                        .center_x(Length::Fill)
+                        .center_y(Length::Fill),
+                )
+                .on_press(UiShellMessage::NavigateBack.into())
+                .style(theme::Button::HeaderIcon.style())
+                .width(Length::Fixed(HEIGHT))
+                .height(HEIGHT),
+            ]
+            .align_y(iced::Alignment::Center);
+
+            Stack::new()
+                .push(
+                    container(
+                        text("Collections")
+                            .size(fonts.subtitle)
+                            .color(theme::MediaServerTheme::TEXT_PRIMARY),
+                    )
+                    .width(Length::Fill)
+                    .height(HEIGHT)
+                    .align_x(iced::alignment::Horizontal::Center)
+                    .align_y(iced::alignment::Vertical::Center),
+                )
+                .push(
+                    container(left_section)
+                        .width(Length::Fill)
+                        .height(HEIGHT)
+                        .align_x(iced::alignment::Horizontal::Left)
+                        .align_y(iced::alignment::Vertical::Center),
+                )
+                .width(Length::Fill)
+                .height(HEIGHT)
+                .into()
+        }
         _ => {
             // No header for other views
             Space::new().height(0).into()
@@ -539,6 +539,7 @@ pub fn view_home_content<'a>(state: &'a State) -> Element<'a, UiMessage> {
                     }
                 }
             }
+            LibraryType::Music => {}
         }
     }
 
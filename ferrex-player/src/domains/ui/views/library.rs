@@ -219,6 +219,15 @@ pub fn view_library(state: &State) -> Element<'_, UiMessage> {
                                     state,
                                 )
                             }
+                            LibraryType::Music => container(
+                                text("Music libraries are not browsable yet")
+                                    .size(fonts.body_lg)
+                                    .color(
+                                        theme::MediaServerTheme::TEXT_SECONDARY,
+                                    ),
+                            )
+                            .center(Length::Fill)
+                            .into(),
                         },
                         TabState::Home(_all_state) => {
                             // Use the AllViewModel from all_state
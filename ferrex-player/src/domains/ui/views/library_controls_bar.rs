@@ -76,6 +76,8 @@ pub fn view_library_controls_bar<'a>(
             ret.push_str(" Episodes");
             ret
         }
+        // Music libraries are browsed through their own endpoints.
+        LibraryType::Music => String::new(),
     };
 
     let active_filter_count = ui_state.selected_genres.len()
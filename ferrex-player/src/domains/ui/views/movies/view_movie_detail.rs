@@ -16,7 +16,10 @@ use crate::{
 use ferrex_contracts::prelude::MovieLike;
 use ferrex_core::{traits::id::MediaIDLike, types::ids::MovieID};
 
-use ferrex_model::{EnhancedMovieDetails, ImageSize, Priority};
+use ferrex_model::{
+    EnhancedMovieDetails, HdrFormat, ImageSize, Priority,
+    files::ArchivedHdrFormat,
+};
 use iced::{
     Element, Length,
     widget::{Space, Stack, column, container, row, scrollable, text},
@@ -376,8 +379,23 @@ pub fn view_movie_detail<'a>(
                 tech_row = tech_row.push(size_card);
 
                 // HDR info - enhanced with bit depth
-                let mut hdr_info = None;
-                if let ArchivedOption::Some(transfer) = &metadata.color_transfer
+                let mut hdr_info = match &metadata.hdr_format {
+                    ArchivedOption::Some(ArchivedHdrFormat::DolbyVision) => {
+                        Some(HdrFormat::DolbyVision.label())
+                    }
+                    ArchivedOption::Some(ArchivedHdrFormat::Hdr10) => {
+                        Some(HdrFormat::Hdr10.label())
+                    }
+                    ArchivedOption::Some(ArchivedHdrFormat::Hlg) => {
+                        Some(HdrFormat::Hlg.label())
+                    }
+                    ArchivedOption::None => None,
+                };
+                // Files analyzed before HDR classification only carry the
+                // raw transfer characteristic.
+                if hdr_info.is_none()
+                    && let ArchivedOption::Some(transfer) =
+                        &metadata.color_transfer
                 {
                     if transfer.contains("2084") {
                         hdr_info = Some("HDR10");
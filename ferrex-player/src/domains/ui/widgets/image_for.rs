@@ -538,6 +538,20 @@ impl<'a> From<ImageFor> for Element<'a, UiMessage> {
                         image.media_id,
                         image.carousel_key.clone(),
                     );
+                    // Paint the server's blurred stand-in while the real
+                    // size downloads; the loaded texture replaces it once
+                    // the image-ready event triggers the fetch.
+                    if let Some(blur) = image_service.placeholder(&request) {
+                        return create_blurred_placeholder(
+                            blur,
+                            bounds,
+                            image.radius,
+                            instance_hash,
+                            instance_key,
+                            image.face.unwrap_or(PosterFace::Front),
+                            image.rotation_y,
+                        );
+                    }
                     create_loading_placeholder(
                         bounds,
                         image.radius,
@@ -690,6 +704,30 @@ fn create_loading_placeholder<'a>(
     poster.into()
 }
 
+fn create_blurred_placeholder<'a>(
+    blur: Handle,
+    bounds: AnimatedPosterBounds,
+    radius: f32,
+    instance_hash: u64,
+    instance_key: PosterInstanceKey,
+    face: PosterFace,
+    rotation_override: Option<f32>,
+) -> Element<'a, UiMessage> {
+    let mut poster = poster(blur, Some(instance_hash))
+        .radius(radius)
+        .with_animated_bounds(bounds)
+        .with_animation(PosterAnimationType::None)
+        .is_hovered(false)
+        .menu_target(instance_key)
+        .face(face);
+
+    if let Some(rot) = rotation_override {
+        poster = poster.rotation_y(rot);
+    }
+
+    poster.into()
+}
+
 /// Extension trait for creating image widgets from media references
 pub trait ImageForExt {
     fn image_for(&self) -> ImageFor;
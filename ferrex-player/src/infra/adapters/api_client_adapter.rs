@@ -13,12 +13,13 @@ use crate::infra::{
 };
 
 use ferrex_core::player_prelude::{
-    ActiveScansResponse, AuthToken, AuthenticatedDevice, CreateLibraryRequest,
-    FilterIndicesRequest, ImageManifestRequest, ImageManifestResponse,
-    IndicesResponse, LatestProgressResponse, Library, LibraryId,
-    LibraryMediaResponse, Media, MediaID, MediaQuery, MediaRootBrowseResponse,
-    MediaWithStatus, MovieBatchFetchRequest, MovieBatchId,
-    MovieBatchSyncRequest, MovieBatchSyncResponse, NextEpisode,
+    ActiveScansResponse, AuthToken, AuthenticatedDevice, Collection,
+    CollectionDetail, CreateLibraryRequest, FilterIndicesRequest,
+    ImageManifestRequest, ImageManifestResponse, IndicesResponse,
+    LatestProgressResponse, Library, LibraryId, LibraryMediaResponse,
+    LibraryStats, Media, MediaID, MediaQuery, MediaRootBrowseResponse,
+    MediaSearchHit, MediaSearchQuery, MediaWithStatus, MovieBatchFetchRequest,
+    MovieBatchId, MovieBatchSyncRequest, MovieBatchSyncResponse, NextEpisode,
     ScanCommandAcceptedResponse, ScanCommandRequest, ScanConfig, ScanMetrics,
     SeasonWatchStatus, SeriesBundleFetchRequest, SeriesBundleSyncRequest,
     SeriesBundleSyncResponse, SeriesID, SeriesWatchStatus, SortBy, SortOrder,
@@ -578,6 +579,28 @@ impl ApiService for ApiClientAdapter {
             .map_err(|e| RepositoryError::QueryFailed(e.to_string()))
     }
 
+    async fn list_collections(&self) -> RepositoryResult<Vec<Collection>> {
+        self.client
+            .get(v1::collections::COLLECTION)
+            .await
+            .map_err(|e| RepositoryError::QueryFailed(e.to_string()))
+    }
+
+    async fn fetch_collection(
+        &self,
+        collection_id: Uuid,
+    ) -> RepositoryResult<CollectionDetail> {
+        let path = replace_param(
+            v1::collections::ITEM,
+            "{id}",
+            collection_id.to_string(),
+        );
+        self.client
+            .get(&path)
+            .await
+            .map_err(|e| RepositoryError::QueryFailed(e.to_string()))
+    }
+
     async fn list_user_devices(
         &self,
     ) -> RepositoryResult<Vec<AuthenticatedDevice>> {
@@ -604,6 +627,16 @@ impl ApiService for ApiClientAdapter {
             .map_err(|e| RepositoryError::QueryFailed(e.to_string()))
     }
 
+    async fn search_media(
+        &self,
+        query: MediaSearchQuery,
+    ) -> RepositoryResult<Vec<MediaSearchHit>> {
+        self.client
+            .search_media(&query)
+            .await
+            .map_err(|e| RepositoryError::QueryFailed(e.to_string()))
+    }
+
     async fn fetch_filtered_indices(
         &self,
         library_id: Uuid,
@@ -765,6 +798,21 @@ impl ApiService for ApiClientAdapter {
         Ok(())
     }
 
+    async fn fetch_library_stats(
+        &self,
+        id: LibraryId,
+    ) -> RepositoryResult<LibraryStats> {
+        let path = replace_param(
+            v1::libraries::STATS,
+            "{id}",
+            id.as_uuid().to_string(),
+        );
+        self.client
+            .get(&path)
+            .await
+            .map_err(|e| RepositoryError::QueryFailed(e.to_string()))
+    }
+
     async fn start_library_scan(
         &self,
         library_id: LibraryId,
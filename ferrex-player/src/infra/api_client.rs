@@ -2,8 +2,9 @@ use ferrex_core::{
     api::routes::v1,
     player_prelude::{
         ApiResponse, AuthToken, AuthenticatedDevice, ConfirmClaimRequest,
-        ConfirmClaimResponse, MediaQuery, MediaWithStatus, StartClaimRequest,
-        StartClaimResponse, UpdateProgressRequest, UserWatchState,
+        ConfirmClaimResponse, MediaQuery, MediaSearchHit, MediaSearchQuery,
+        MediaWithStatus, StartClaimRequest, StartClaimResponse,
+        UpdateProgressRequest, UserWatchState,
     },
 };
 
@@ -817,6 +818,21 @@ impl ApiClient {
         self.execute_request(request).await
     }
 
+    /// GET request with authentication and a serialized query string
+    pub async fn get_with_query<Q: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<T> {
+        let url = self.build_url(path);
+
+        log::debug!("GET request to: {}", url);
+
+        let request = self.client.get(&url).query(query);
+        let request = self.build_request(request).await;
+        self.execute_request(request).await
+    }
+
     /// GET request for public endpoints (no authentication)
     pub async fn get_public<T: DeserializeOwned>(
         &self,
@@ -904,7 +920,10 @@ impl ApiClient {
         &self,
         device_name: Option<String>,
     ) -> Result<StartClaimResponse> {
-        let request = StartClaimRequest { device_name };
+        let request = StartClaimRequest {
+            device_name,
+            server_url: None,
+        };
         self.post(v1::setup::CLAIM_START, &request).await
     }
 
@@ -915,6 +934,7 @@ impl ApiClient {
     ) -> Result<ConfirmClaimResponse> {
         let request = ConfirmClaimRequest {
             claim_code: claim_code.to_string(),
+            claim_link: None,
         };
         self.post(v1::setup::CLAIM_CONFIRM, &request).await
     }
@@ -954,6 +974,14 @@ impl ApiClient {
     ) -> Result<Vec<MediaWithStatus>> {
         self.post(v1::media::QUERY, &query).await
     }
+
+    /// Ranked search across movies, series and episodes
+    pub async fn search_media(
+        &self,
+        query: &MediaSearchQuery,
+    ) -> Result<Vec<MediaSearchHit>> {
+        self.get_with_query(v1::search::ROOT, query).await
+    }
 }
 
 /// Server setup status
@@ -10,17 +10,18 @@ use async_trait::async_trait;
 use ferrex_core::{
     api::types::setup::{ConfirmClaimResponse, StartClaimResponse},
     player_prelude::{
-        ActiveScansResponse, AuthToken, AuthenticatedDevice,
-        CreateLibraryRequest, FilterIndicesRequest, ImageManifestRequest,
-        ImageManifestResponse, LatestProgressResponse, Library, LibraryId,
-        Media, MediaQuery, MediaRootBrowseResponse, MediaWithStatus,
-        MovieBatchFetchRequest, MovieBatchId, MovieBatchSyncRequest,
-        MovieBatchSyncResponse, NextEpisode, ScanCommandAcceptedResponse,
-        ScanCommandRequest, ScanConfig, ScanMetrics, SeasonWatchStatus,
-        SeriesBundleFetchRequest, SeriesBundleSyncRequest,
-        SeriesBundleSyncResponse, SeriesID, SeriesWatchStatus,
-        StartScanRequest, UpdateLibraryRequest, UpdateProgressRequest, User,
-        UserPermissions, UserWatchState,
+        ActiveScansResponse, AuthToken, AuthenticatedDevice, Collection,
+        CollectionDetail, CreateLibraryRequest, FilterIndicesRequest,
+        ImageManifestRequest, ImageManifestResponse, LatestProgressResponse,
+        Library, LibraryId, LibraryStats, Media, MediaQuery,
+        MediaRootBrowseResponse, MediaSearchHit, MediaSearchQuery,
+        MediaWithStatus, MovieBatchFetchRequest, MovieBatchId,
+        MovieBatchSyncRequest, MovieBatchSyncResponse, NextEpisode,
+        ScanCommandAcceptedResponse, ScanCommandRequest, ScanConfig,
+        ScanMetrics, SeasonWatchStatus, SeriesBundleFetchRequest,
+        SeriesBundleSyncRequest, SeriesBundleSyncResponse, SeriesID,
+        SeriesWatchStatus, StartScanRequest, UpdateLibraryRequest,
+        UpdateProgressRequest, User, UserPermissions, UserWatchState,
     },
 };
 use ferrex_model::image::ImageQuery;
@@ -151,6 +152,11 @@ pub trait ApiService: Send + Sync + Debug {
     ) -> RepositoryResult<()>;
     /// Delete a library on the server
     async fn delete_library(&self, id: LibraryId) -> RepositoryResult<()>;
+    /// Fetch aggregate counts for a library overview
+    async fn fetch_library_stats(
+        &self,
+        id: LibraryId,
+    ) -> RepositoryResult<LibraryStats>;
 
     /// Start a library scan
     async fn start_library_scan(
@@ -250,6 +256,15 @@ pub trait ApiService: Send + Sync + Debug {
         tmdb_series_id: u64,
     ) -> RepositoryResult<Option<NextEpisode>>;
 
+    /// List the current user's collections
+    async fn list_collections(&self) -> RepositoryResult<Vec<Collection>>;
+
+    /// Fetch a collection with its items and their watch state
+    async fn fetch_collection(
+        &self,
+        collection_id: Uuid,
+    ) -> RepositoryResult<CollectionDetail>;
+
     /// List authenticated devices for current user
     async fn list_user_devices(
         &self,
@@ -264,6 +279,12 @@ pub trait ApiService: Send + Sync + Debug {
         query: MediaQuery,
     ) -> RepositoryResult<Vec<MediaWithStatus>>;
 
+    /// Ranked search across movies, series and episodes
+    async fn search_media(
+        &self,
+        query: MediaSearchQuery,
+    ) -> RepositoryResult<Vec<MediaSearchHit>>;
+
     /// Fetch filtered index positions for a library based on the provided filter spec
     async fn fetch_filtered_indices(
         &self,
@@ -1,8 +1,13 @@
 // Streaming service trait and adapter for the RUS-136 pilot
 
 use anyhow::Result;
+use anyhow::anyhow;
 use async_trait::async_trait;
 use ferrex_core::api::routes::{utils, v1};
+use ferrex_core::api::types::{StartTranscodeRequest, TranscodeProfile};
+use ferrex_core::player_prelude::{
+    TranscodingJobResponse, TranscodingStatus as JobStatus,
+};
 use std::sync::Arc;
 
 use crate::infra::api_client::ApiClient;
@@ -47,20 +52,42 @@ impl StreamingApiService for StreamingApiAdapter {
         media_id: &str,
         profile: &str,
     ) -> Result<String> {
-        // Transcoding provider is temporarily unavailable; signal cached job
-        let _ = profile; // profile selection is ignored for direct streaming
-        Ok(format!("cached_{}", media_id))
+        let request = StartTranscodeRequest {
+            media_id: media_id.parse()?,
+            profile: TranscodeProfile::preset(profile).ok_or_else(|| {
+                anyhow!("unknown transcode profile '{}'", profile)
+            })?,
+        };
+        let job: TranscodingJobResponse =
+            self.client.post(v1::transcode::START, &request).await?;
+        Ok(job.id)
     }
 
     async fn check_transcoding_status(
         &self,
         job_id: &str,
     ) -> Result<TranscodingStatus> {
+        let path = utils::replace_param(v1::transcode::STATUS, "{id}", job_id);
+        let job: TranscodingJobResponse = self.client.get(&path).await?;
+
+        let (state, progress, message) = match job.status {
+            JobStatus::Pending => ("pending", None, None),
+            JobStatus::Queued => ("queued", None, None),
+            JobStatus::Processing { progress } => {
+                ("processing", Some(progress), None)
+            }
+            JobStatus::Completed => ("completed", Some(1.0), None),
+            JobStatus::Failed { error } => ("failed", None, Some(error)),
+            JobStatus::Cancelled => {
+                ("failed", None, Some("Transcoding was cancelled".into()))
+            }
+        };
+
         Ok(TranscodingStatus {
-            job_id: job_id.to_string(),
-            state: "completed".to_string(),
-            progress: Some(1.0),
-            message: Some("Direct streaming available".to_string()),
+            job_id: job.id,
+            state: state.to_string(),
+            progress,
+            message,
         })
     }
 
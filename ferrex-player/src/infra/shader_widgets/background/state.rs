@@ -364,7 +364,8 @@ impl BackgroundShaderState {
             ViewState::Library
             | ViewState::LibraryManagement
             | ViewState::AdminDashboard
-            | ViewState::UserSettings => {
+            | ViewState::UserSettings
+            | ViewState::Collections => {
                 // All these views use library default colors
                 self.reset_to_library_colors();
             }
@@ -8,17 +8,18 @@ use ferrex_core::domain::users::auth::{
     device::AuthDeviceStatus, domain::value_objects::SessionScope,
 };
 use ferrex_core::player_prelude::{
-    ActiveScansResponse, AuthToken, AuthenticatedDevice, ConfirmClaimResponse,
-    CreateLibraryRequest, FilterIndicesRequest, ImageManifestRequest,
-    ImageManifestResponse, LatestProgressResponse, Library, LibraryId,
-    LibraryType, Media, MediaQuery, MediaRootBrowseResponse, MediaWithStatus,
-    MovieBatchFetchRequest, MovieBatchId, MovieBatchSyncRequest,
-    MovieBatchSyncResponse, Platform, Role, ScanCommandAcceptedResponse,
-    ScanCommandRequest, ScanConfig, ScanMetrics, SeriesBundleFetchRequest,
-    SeriesBundleSyncRequest, SeriesBundleSyncResponse, SeriesID,
-    StartClaimResponse, StartScanRequest, UpdateLibraryRequest,
-    UpdateProgressRequest, User, UserPermissions, UserPreferences,
-    UserWatchState,
+    ActiveScansResponse, AuthToken, AuthenticatedDevice, Collection,
+    CollectionDetail, ConfirmClaimResponse, CreateLibraryRequest,
+    FilterIndicesRequest, ImageManifestRequest, ImageManifestResponse,
+    LatestProgressResponse, Library, LibraryId, LibraryStats, LibraryType,
+    Media, MediaQuery, MediaRootBrowseResponse, MediaSearchHit,
+    MediaSearchQuery, MediaWithStatus, MovieBatchFetchRequest, MovieBatchId,
+    MovieBatchSyncRequest, MovieBatchSyncResponse, Platform, Role,
+    ScanCommandAcceptedResponse, ScanCommandRequest, ScanConfig, ScanMetrics,
+    SeriesBundleFetchRequest, SeriesBundleSyncRequest,
+    SeriesBundleSyncResponse, SeriesID, StartClaimResponse, StartScanRequest,
+    UpdateLibraryRequest, UpdateProgressRequest, User, UserPermissions,
+    UserPreferences, UserWatchState,
 };
 use ferrex_model::MovieReferenceBatchSize;
 use ferrex_model::image::ImageQuery;
@@ -264,6 +265,7 @@ impl ApiService for TestApiService {
             scan_interval_minutes,
             enabled,
             movie_ref_batch_size,
+            exclude_patterns,
             start_scan,
         } = request;
 
@@ -284,6 +286,7 @@ impl ApiService for TestApiService {
                 movie_ref_batch_size,
             )
             .expect("movie_ref_batch_size must be valid"),
+            exclude_patterns,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             media: None,
@@ -310,6 +313,9 @@ impl ApiService for TestApiService {
                     ferrex_model::MovieReferenceBatchSize::new(size)
                         .expect("movie_ref_batch_size must be valid");
             }
+            if let Some(patterns) = request.exclude_patterns {
+                library.exclude_patterns = patterns;
+            }
             library.updated_at = Utc::now();
             Ok(())
         } else {
@@ -326,6 +332,34 @@ impl ApiService for TestApiService {
         Ok(())
     }
 
+    async fn fetch_library_stats(
+        &self,
+        id: LibraryId,
+    ) -> RepositoryResult<LibraryStats> {
+        let guard = self.inner.read().expect("lock poisoned");
+        if !guard.libraries.iter().any(|library| library.id == id) {
+            return Err(RepositoryError::NotFound {
+                entity_type: "Library".to_string(),
+                id: id.to_string(),
+            });
+        }
+        Ok(LibraryStats {
+            library_id: id,
+            movies: 0,
+            series: 0,
+            seasons: 0,
+            episodes: 0,
+            total_files: 0,
+            total_size: 0,
+            with_poster: 0,
+            without_poster: 0,
+            with_metadata: 0,
+            without_metadata: 0,
+            missing_files: None,
+            missing_checked_at: None,
+        })
+    }
+
     async fn start_library_scan(
         &self,
         _library_id: LibraryId,
@@ -490,6 +524,20 @@ impl ApiService for TestApiService {
         Ok(None)
     }
 
+    async fn list_collections(&self) -> RepositoryResult<Vec<Collection>> {
+        Ok(Vec::new())
+    }
+
+    async fn fetch_collection(
+        &self,
+        collection_id: Uuid,
+    ) -> RepositoryResult<CollectionDetail> {
+        Err(RepositoryError::NotFound {
+            entity_type: "Collection".to_string(),
+            id: collection_id.to_string(),
+        })
+    }
+
     async fn list_user_devices(
         &self,
     ) -> RepositoryResult<Vec<AuthenticatedDevice>> {
@@ -510,6 +558,13 @@ impl ApiService for TestApiService {
         Ok(Vec::new())
     }
 
+    async fn search_media(
+        &self,
+        _query: MediaSearchQuery,
+    ) -> RepositoryResult<Vec<MediaSearchHit>> {
+        Ok(Vec::new())
+    }
+
     async fn fetch_filtered_indices(
         &self,
         _library_id: Uuid,
@@ -590,6 +645,7 @@ impl ApiService for TestApiService {
             claim_code: "123456".into(),
             expires_at: Utc::now() + Duration::minutes(5),
             lan_only: true,
+            claim_link: None,
         };
 
         if let Ok(mut guard) = self.inner.write() {
@@ -689,6 +745,7 @@ fn sample_library(name: &str) -> Library {
         analyze_on_scan: false,
         max_retry_attempts: 3,
         movie_ref_batch_size: MovieReferenceBatchSize::default(),
+        exclude_patterns: Vec::new(),
         created_at: Utc::now() - Duration::days(1),
         updated_at: Utc::now(),
         media: None,
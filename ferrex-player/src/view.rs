@@ -8,6 +8,7 @@ use crate::domains::ui::views::admin::{
     view_admin_dashboard, view_admin_users, view_library_management,
 };
 use crate::domains::ui::views::auth::view_auth;
+use crate::domains::ui::views::collections::view_collections;
 use crate::domains::ui::views::header::view_header;
 use crate::domains::ui::views::library::view_library;
 use crate::domains::ui::views::library_controls_bar::view_library_controls_bar;
@@ -117,6 +118,9 @@ pub fn view(
         ViewState::UserSettings => {
             view_unified_settings(state).map(DomainMessage::from)
         }
+        ViewState::Collections => {
+            view_collections(state).map(DomainMessage::from)
+        }
     };
 
     // Add header if the view needs it
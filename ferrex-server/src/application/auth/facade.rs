@@ -7,7 +7,9 @@ use ferrex_core::{
     domain::users::{
         auth::domain::{
             aggregates::DeviceSession,
-            repositories::AuthSessionRecord,
+            repositories::{
+                AuthEventQuery, AuthEventRecord, AuthSessionRecord,
+            },
             services::{
                 AuthEventContext, AuthenticationError, AuthenticationService,
                 DeviceTrustError, DeviceTrustService, PasswordChangeActor,
@@ -96,30 +98,38 @@ impl AuthApplicationFacade {
         Ok(salt)
     }
 
-    pub async fn device_password_login(
+    /// Password login from the device the client calls `device_id`. Also
+    /// returns the device id the client should use from now on, which
+    /// differs from `device_id` when that was reissued after a collision.
+    pub async fn device_password_login<F>(
         &self,
         username: &str,
         password: &str,
-        fingerprint: DeviceFingerprint,
+        device_id: Uuid,
+        fingerprint: F,
         device_name: String,
         context: AuthEventContext,
-    ) -> Result<(TokenBundle, DeviceSession), AuthFacadeError> {
+    ) -> Result<(TokenBundle, DeviceSession, Uuid), AuthFacadeError>
+    where
+        F: Fn(Uuid) -> DeviceFingerprint + Send,
+    {
         let bundle = self
             .auth_service
             .authenticate_with_password(username, password)
             .await?;
 
-        let session = self
+        let (session, device_id) = self
             .device_trust_service
-            .register_device(
+            .register_client_device(
                 bundle.user_id,
+                device_id,
                 fingerprint,
                 device_name,
                 Some(context),
             )
             .await?;
 
-        Ok((bundle, session))
+        Ok((bundle, session, device_id))
     }
 
     pub async fn get_user_by_id(
@@ -257,6 +267,13 @@ impl AuthApplicationFacade {
         Ok(records.into_iter().map(Self::map_session_record).collect())
     }
 
+    pub async fn list_auth_events(
+        &self,
+        query: &AuthEventQuery,
+    ) -> Result<Vec<AuthEventRecord>, AuthFacadeError> {
+        Ok(self.auth_service.list_auth_events(query).await?)
+    }
+
     pub async fn revoke_user_session(
         &self,
         user_id: Uuid,
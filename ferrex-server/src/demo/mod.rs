@@ -195,10 +195,7 @@ impl DemoCoordinator {
         config: &mut Config,
         tmdb: Arc<TmdbApiProvider>,
     ) -> Result<Self> {
-        if std::env::var("TMDB_API_KEY")
-            .map(|value| value.trim().is_empty())
-            .unwrap_or(true)
-        {
+        if !tmdb.is_configured() {
             return Err(anyhow!(
                 "TMDB_API_KEY must be configured to materialise demo media. Supply a key or stub the provider before enabling demo mode."
             ));
@@ -301,6 +298,7 @@ impl DemoCoordinator {
                         library.series_count = Some(count.max(1));
                     }
                 }
+                LibraryType::Music => {}
             }
         }
     }
@@ -440,6 +438,8 @@ impl DemoCoordinator {
                 LibraryType::Series => {
                     opts_lib.series_count.unwrap_or(3).max(1)
                 }
+                // Demo plans are generated from TMDB and never hold music.
+                LibraryType::Music => continue,
             };
 
             let current_items = primary_item_paths_on_disk(
@@ -533,6 +533,7 @@ impl DemoCoordinator {
                             )
                             .await?
                     }
+                    LibraryType::Music => continue,
                 };
 
                 let (dirs, files) = structure_nodes_to_paths(&structure);
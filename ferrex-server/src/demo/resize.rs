@@ -130,7 +130,9 @@ pub fn primary_item_paths_on_disk(
 
             primary_item_roots_on_disk(library_root)
         }
-        LibraryType::Series => primary_item_roots_on_disk(library_root),
+        LibraryType::Series | LibraryType::Music => {
+            primary_item_roots_on_disk(library_root)
+        }
     }
 }
 
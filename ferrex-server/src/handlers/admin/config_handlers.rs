@@ -0,0 +1,39 @@
+//! Read-only view of the configuration the server is running with, for
+//! working out why a setting did not take effect.
+
+use axum::{extract::State, response::Json};
+use ferrex_core::api::types::ApiResponse;
+use serde::Serialize;
+
+use crate::infra::{
+    app_state::AppState,
+    config::{
+        Config, ConfigWarning,
+        schema::{KeySource, value_sources},
+    },
+    errors::AppResult,
+};
+
+#[derive(Debug, Serialize)]
+pub struct EffectiveConfigReport {
+    /// The configuration in effect, including changes applied by a reload.
+    /// Secrets are reduced to whether they are set and their length.
+    pub config: Config,
+    /// Where each key's value came from: default, environment, overlay
+    /// fragment or secret file.
+    pub sources: Vec<KeySource>,
+    /// Warnings raised while loading `config`.
+    pub warnings: Vec<ConfigWarning>,
+}
+
+/// Return the effective configuration with secrets redacted.
+pub async fn get_effective_config(
+    State(state): State<AppState>,
+) -> AppResult<Json<ApiResponse<EffectiveConfigReport>>> {
+    let effective = state.live_settings().effective();
+    Ok(Json(ApiResponse::success(EffectiveConfigReport {
+        sources: value_sources(&effective.config.metadata),
+        config: effective.config.clone(),
+        warnings: effective.warnings.items.clone(),
+    })))
+}
@@ -383,6 +383,7 @@ pub async fn seed_database(
             analyze_on_scan: true,
             max_retry_attempts: 3,
             movie_ref_batch_size: MovieReferenceBatchSize::default(),
+            exclude_patterns: Vec::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             media: None,
@@ -0,0 +1,70 @@
+//! Dry run of filename parsing, for checking configured filename rules
+//! before a scan relies on them.
+
+use std::path::PathBuf;
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use ferrex_core::{
+    api::types::ApiResponse,
+    domain::media::naming_rules::naming_rules,
+    infra::media::metadata::FilenameParser,
+    types::{files::ParsedMediaInfo, library::LibraryType},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::infra::{
+    app_state::AppState,
+    errors::{AppError, AppResult},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct FilenameParseQuery {
+    /// File path (or bare filename) to parse; nothing is read from disk
+    pub path: String,
+    /// Parse as this library type would; both are tried when absent
+    #[serde(default)]
+    pub library_type: Option<LibraryType>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FilenameParsePreview {
+    /// Number of configured filename rules
+    pub configured_rules: usize,
+    /// Configured rule that matched; `None` when the built-in heuristics
+    /// were used
+    pub matched_rule: Option<usize>,
+    /// What a scan would record, or `None` if the file would be skipped
+    pub parsed: Option<ParsedMediaInfo>,
+}
+
+/// Show how `path` parses under the configured filename rules and the
+/// built-in heuristics.
+pub async fn preview_filename_parse(
+    State(state): State<AppState>,
+    Query(query): Query<FilenameParseQuery>,
+) -> AppResult<Json<ApiResponse<FilenameParsePreview>>> {
+    if query.path.trim().is_empty() {
+        return Err(AppError::bad_request("path must not be empty"));
+    }
+    let path = PathBuf::from(&query.path);
+
+    let mut parser = FilenameParser::new();
+    parser.set_library_type(query.library_type);
+    let parsed = parser.parse_filename_with_type(&path);
+
+    // Extras are skipped before any rule is tried
+    let matched_rule = parsed
+        .as_ref()
+        .and(naming_rules())
+        .and_then(|rules| rules.parse(&path))
+        .map(|matched| matched.rule);
+
+    Ok(Json(ApiResponse::success(FilenameParsePreview {
+        configured_rules: state.config().media.filename_rules.len(),
+        matched_rule,
+        parsed,
+    })))
+}
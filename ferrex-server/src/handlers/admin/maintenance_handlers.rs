@@ -0,0 +1,178 @@
+//! Admin maintenance routines for libraries imported by older releases.
+
+use axum::{Extension, Json, extract::State};
+use ferrex_core::{
+    api::types::ApiResponse,
+    domain::users::{auth::rate_limit::RateLimitKey, user::User},
+    infra::media::image_service::{
+        OrphanedImageBlobsReport, ThemeColorBackfillReport,
+    },
+};
+use ferrex_model::ImageMediaType;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::infra::{
+    app_state::AppState,
+    errors::{AppError, AppResult},
+    maintenance::MaintenanceStatus,
+};
+
+/// Request to backfill theme colors
+#[derive(Debug, Deserialize)]
+pub struct ThemeColorBackfillRequest {
+    /// `Movie` or `Series`
+    pub media_type: ImageMediaType,
+    /// Resume after this media id (the `next_cursor` of a previous run)
+    #[serde(default)]
+    pub after: Option<Uuid>,
+    /// Stop after visiting this many media items
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Compute missing theme colors from posters that are already cached.
+pub async fn backfill_theme_colors(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Json(request): Json<ThemeColorBackfillRequest>,
+) -> AppResult<Json<ApiResponse<ThemeColorBackfillReport>>> {
+    if !matches!(
+        request.media_type,
+        ImageMediaType::Movie | ImageMediaType::Series
+    ) {
+        return Err(AppError::bad_request(
+            "Theme colors can only be backfilled for movies and series",
+        ));
+    }
+
+    info!(
+        "Admin {} started theme color backfill for {:?}",
+        admin.username, request.media_type
+    );
+    let report = state
+        .image_service()
+        .backfill_theme_colors(request.media_type, request.after, request.limit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Count image blobs on disk that no database record references.
+pub async fn list_orphaned_images(
+    State(state): State<AppState>,
+) -> AppResult<Json<ApiResponse<OrphanedImageBlobsReport>>> {
+    let report = state.image_service().find_orphaned_blobs().await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Request to delete orphaned image blobs
+#[derive(Debug, Deserialize)]
+pub struct OrphanedImagesPurgeRequest {
+    /// Must be `true`; guards against an accidental empty POST
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Delete image blobs on disk that no database record references. Blobs of
+/// images still being downloaded are left alone.
+pub async fn purge_orphaned_images(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Json(request): Json<OrphanedImagesPurgeRequest>,
+) -> AppResult<Json<ApiResponse<OrphanedImageBlobsReport>>> {
+    if !request.confirm {
+        return Err(AppError::bad_request(
+            "Purging orphaned images requires `confirm: true`",
+        ));
+    }
+
+    warn!("Admin {} purging orphaned image blobs", admin.username);
+    let report = state.image_service().purge_orphaned_blobs().await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Request to toggle runtime read-only mode
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceModeRequest {
+    pub enabled: bool,
+    /// Seconds advertised in `Retry-After` while enabled
+    #[serde(default)]
+    pub retry_after_secs: Option<u64>,
+    /// Note included in 503 responses
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Current maintenance mode status.
+pub async fn get_maintenance_mode(
+    State(state): State<AppState>,
+) -> Json<ApiResponse<MaintenanceStatus>> {
+    Json(ApiResponse::success(state.maintenance().status()))
+}
+
+/// Enable or disable maintenance mode without restarting the server.
+pub async fn set_maintenance_mode(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Json(request): Json<MaintenanceModeRequest>,
+) -> Json<ApiResponse<MaintenanceStatus>> {
+    let status = state.maintenance().set(
+        request.enabled,
+        request.retry_after_secs,
+        request.reason,
+        &admin.username,
+    );
+    Json(ApiResponse::success(status))
+}
+
+/// Request to clear rate limiter buckets; exactly one of `key` or `all`
+#[derive(Debug, Deserialize)]
+pub struct RateLimitResetRequest {
+    /// e.g. `{"IpAddress": "203.0.113.7"}` or `{"DeviceId": "<uuid>"}`
+    #[serde(default)]
+    pub key: Option<RateLimitKey>,
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RateLimitResetResponse {
+    pub cleared: u64,
+}
+
+/// Clear rate limiter state for one key (a client throttled by mistake) or
+/// for every key.
+pub async fn reset_rate_limits(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Json(request): Json<RateLimitResetRequest>,
+) -> AppResult<Json<ApiResponse<RateLimitResetResponse>>> {
+    let limiter = state.rate_limiter().ok_or_else(|| {
+        AppError::bad_request("Rate limiting is not enabled on this server")
+    })?;
+
+    let result = match (request.key, request.all) {
+        (Some(key), false) => {
+            warn!("Admin {} reset rate limits for {:?}", admin.username, key);
+            limiter.reset(&key).await
+        }
+        (None, true) => {
+            warn!("Admin {} reset all rate limits", admin.username);
+            limiter.reset_all().await
+        }
+        _ => {
+            return Err(AppError::bad_request(
+                "Provide either `key` or `all: true`",
+            ));
+        }
+    };
+    let cleared = result.map_err(|err| {
+        AppError::internal(format!("Failed to reset rate limits: {err}"))
+    })?;
+
+    Ok(Json(ApiResponse::success(RateLimitResetResponse {
+        cleared,
+    })))
+}
@@ -1,4 +1,8 @@
+pub mod config_handlers;
 #[cfg(feature = "demo")]
 pub mod demo_handlers;
 pub mod dev_handlers;
+pub mod filename_rules;
+pub mod maintenance_handlers;
 pub mod media_root;
+pub mod storage_handlers;
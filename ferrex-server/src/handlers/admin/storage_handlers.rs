@@ -0,0 +1,71 @@
+//! Per-library disk usage for capacity planning.
+
+use std::collections::HashMap;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use ferrex_core::{
+    api::types::{ApiResponse, StorageUsageQuery, StorageUsageReport},
+    types::ids::LibraryId,
+};
+use tracing::warn;
+
+use crate::infra::{app_state::AppState, demo_mode, errors::AppResult};
+
+/// Stored file sizes, cache use and recent growth of every library.
+///
+/// Sizes come from the database, so this never touches the media roots.
+/// Transcode usage counts the HLS segments currently cached for each
+/// library's files.
+pub async fn get_storage_usage(
+    State(state): State<AppState>,
+    Query(query): Query<StorageUsageQuery>,
+) -> AppResult<Json<ApiResponse<StorageUsageReport>>> {
+    let window_days = query.window_days();
+    let mut libraries = state
+        .unit_of_work()
+        .libraries
+        .storage_usage(window_days)
+        .await?;
+    if demo_mode::is_demo_mode(&state) {
+        libraries.retain(|usage| demo_mode::is_demo_library(&usage.library_id));
+    }
+
+    let transcode = transcode_bytes_by_library(&state).await;
+    for usage in &mut libraries {
+        usage.transcode_cache_size =
+            transcode.get(&usage.library_id).copied().unwrap_or(0);
+    }
+
+    Ok(Json(ApiResponse::success(StorageUsageReport {
+        window_days,
+        libraries,
+    })))
+}
+
+/// HLS cache bytes summed per library of the media file they were cut
+/// from. Files deleted since are skipped.
+async fn transcode_bytes_by_library(
+    state: &AppState,
+) -> HashMap<LibraryId, u64> {
+    let mut totals = HashMap::new();
+    for (file_id, bytes) in state.hls_segmenter().cached_bytes_by_media() {
+        match state
+            .unit_of_work()
+            .media_files_read
+            .get_by_id(&file_id)
+            .await
+        {
+            Ok(Some(file)) => {
+                *totals.entry(file.library_id).or_default() += bytes;
+            }
+            Ok(None) => {}
+            Err(err) => {
+                warn!(%file_id, error = %err, "failed to attribute HLS cache");
+            }
+        }
+    }
+    totals
+}
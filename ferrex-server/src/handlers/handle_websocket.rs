@@ -1,38 +1,60 @@
 use axum::{
     extract::{
         Extension, State,
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
     },
     response::Response,
 };
+use chrono::Utc;
+use ferrex_core::domain::users::auth::domain::services::ValidatedSession;
 use ferrex_core::domain::users::user::User;
 use ferrex_core::sync_session::SyncMessage;
 use futures_util::{SinkExt, StreamExt};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::infra::{
     app_state::AppState,
-    websocket::{Connection, messages},
+    websocket::{CloseReason, Connection, messages},
 };
 
+/// How often a live connection re-checks that its auth session still exists
+/// and has not been revoked.
+const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
 /// Handle WebSocket upgrade request
+///
+/// The route sits behind `auth_middleware`, so the upgrade only succeeds
+/// with a valid session token. The connection is tied to that session and
+/// closed with a `WS_CLOSE_SESSION_*` code once it is revoked or expires.
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     State(state): State<AppState>,
     Extension(user): Extension<User>,
+    Extension(session): Extension<ValidatedSession>,
 ) -> Response {
-    ws.on_upgrade(move |socket| handle_socket(socket, state, user))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, user, session))
 }
 
 /// Handle an individual WebSocket connection
-async fn handle_socket(socket: WebSocket, state: AppState, user: User) {
+async fn handle_socket(
+    socket: WebSocket,
+    state: AppState,
+    user: User,
+    session: ValidatedSession,
+) {
     let (ws_sender, mut ws_receiver) = socket.split();
     let (tx, mut rx) = mpsc::channel::<SyncMessage>(100);
 
     // Create connection
-    let connection = Arc::new(Connection::new(user.clone(), tx));
+    let connection = Arc::new(Connection::new(
+        user.clone(),
+        session.session_id,
+        session.expires_at,
+        tx,
+    ));
     let conn_id = connection.id;
 
     // Register connection
@@ -40,20 +62,49 @@ async fn handle_socket(socket: WebSocket, state: AppState, user: User) {
         .websocket_manager()
         .add_connection(conn_id, connection.clone());
 
-    // Spawn task to handle outgoing messages
+    // Spawn task to handle outgoing messages and server-initiated closes
     let mut ws_sender = ws_sender;
+    let mut outgoing_close = connection.close_signal();
     tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            if let Ok(ws_msg) = messages::sync_to_websocket(&msg)
-                && ws_sender.send(ws_msg).await.is_err()
-            {
-                break;
+        loop {
+            tokio::select! {
+                msg = rx.recv() => {
+                    let Some(msg) = msg else { break };
+                    if let Ok(ws_msg) = messages::sync_to_websocket(&msg)
+                        && ws_sender.send(ws_msg).await.is_err()
+                    {
+                        break;
+                    }
+                }
+                changed = outgoing_close.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    let reason = *outgoing_close.borrow_and_update();
+                    if let Some(reason) = reason {
+                        let frame = CloseFrame {
+                            code: reason.code(),
+                            reason: reason.message().into(),
+                        };
+                        let _ = ws_sender.send(Message::Close(Some(frame))).await;
+                        break;
+                    }
+                }
             }
         }
     });
 
+    let watchdog =
+        tokio::spawn(watch_session(state.clone(), connection.clone()));
+
     // Handle incoming messages
-    while let Some(msg) = ws_receiver.next().await {
+    let mut incoming_close = connection.close_signal();
+    loop {
+        let msg = tokio::select! {
+            msg = ws_receiver.next() => msg,
+            _ = incoming_close.changed() => break,
+        };
+        let Some(msg) = msg else { break };
         match msg {
             Ok(Message::Text(text)) => {
                 if let Ok(sync_msg) =
@@ -94,10 +145,46 @@ async fn handle_socket(socket: WebSocket, state: AppState, user: User) {
         }
     }
 
+    watchdog.abort();
+
     // Clean up on disconnect
     handle_disconnect(&state, conn_id, &user).await;
 }
 
+/// Close the connection once its auth session expires or is revoked.
+async fn watch_session(state: AppState, connection: Arc<Connection>) {
+    loop {
+        let until_expiry = (connection.session_expires_at - Utc::now())
+            .to_std()
+            .unwrap_or_default();
+        if until_expiry.is_zero() {
+            connection.close(CloseReason::SessionExpired);
+            return;
+        }
+
+        tokio::time::sleep(until_expiry.min(SESSION_CHECK_INTERVAL)).await;
+
+        match state
+            .auth_service()
+            .find_session_by_id(connection.session_id)
+            .await
+        {
+            Ok(Some(record)) if !record.revoked => {}
+            Ok(_) => {
+                connection.close(CloseReason::SessionRevoked);
+                return;
+            }
+            Err(err) => {
+                tracing::warn!(
+                    connection = %connection.id,
+                    "failed to re-check websocket session: {}",
+                    err
+                );
+            }
+        }
+    }
+}
+
 /// Handle host command - verify sender is host and update state
 async fn handle_host_command<F>(
     state: &AppState,
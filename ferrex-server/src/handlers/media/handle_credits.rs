@@ -0,0 +1,70 @@
+//! Cast and crew, and the other media a person appears in.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use ferrex_core::{
+    api::types::{
+        ApiResponse, MediaCredits, PersonAppearances, PersonAppearancesQuery,
+    },
+    types::VideoMediaType,
+};
+use uuid::Uuid;
+
+use crate::{
+    handlers::media::media_id_param::MediaIdParam,
+    infra::{
+        app_state::AppState,
+        errors::{AppError, AppResult},
+    },
+};
+
+/// Cast in billing order and key crew of a movie, series or episode.
+/// Profile image ids can be passed to the image manifest; portraits are
+/// fetched from TMDB the first time they are requested.
+///
+/// # Response
+///
+/// - `200 OK` with the credits; empty lists before metadata is fetched
+/// - `400 Bad Request` for a season id
+/// - `404 Not Found` when no movie, series or episode has this id
+pub async fn get_media_credits_handler(
+    State(state): State<AppState>,
+    Path(id): Path<MediaIdParam>,
+) -> AppResult<Json<ApiResponse<MediaCredits>>> {
+    let id = id
+        .accept(&[
+            VideoMediaType::Movie,
+            VideoMediaType::Series,
+            VideoMediaType::Episode,
+        ])?
+        .uuid();
+    let credits = state
+        .unit_of_work()
+        .credits
+        .media_credits(id)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found(format!("No movie, series or episode {id}"))
+        })?;
+
+    Ok(Json(ApiResponse::success(credits)))
+}
+
+/// Movies and series crediting a person, newest first, with every role
+/// they had on each.
+pub async fn get_person_appearances_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PersonAppearancesQuery>,
+) -> AppResult<Json<ApiResponse<PersonAppearances>>> {
+    let appearances = state
+        .unit_of_work()
+        .credits
+        .person_appearances(id, query.library_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Person not found"))?;
+
+    Ok(Json(ApiResponse::success(appearances)))
+}
@@ -1,8 +1,8 @@
 use axum::{
     body::{Body, Bytes},
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{HeaderMap, StatusCode, header},
-    response::{IntoResponse, Response, Sse},
+    response::{IntoResponse, Redirect, Response, Sse},
 };
 use base64::{
     Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD,
@@ -12,24 +12,41 @@ use ferrex_core::{
         ImageManifestRequest, ImageManifestResponse, ImageManifestResult,
         ImageManifestStatus,
     },
-    infra::{cache::ImageFileStore, image_service::CachePolicy},
+    infra::{
+        cache::{BlobLocation, ImageFileStore},
+        image_service::{
+            CacheFillOutcome, CachePolicy, PLACEHOLDER_CONTENT_TYPE,
+        },
+    },
 };
-use ferrex_model::events::ImageSseEventType;
+use ferrex_model::{ImageReadyEvent, ImageSize, events::ImageSseEventType};
 use httpdate::{fmt_http_date, parse_http_date};
 use rkyv::util::AlignedVec;
 use rkyv::{from_bytes, rancor::Error as RkyvError, to_bytes};
-use std::{convert::Infallible, time::Duration};
+use serde::Deserialize;
+use std::{collections::HashSet, convert::Infallible, time::Duration};
 use tokio_stream::{StreamExt, wrappers::BroadcastStream};
 use tokio_util::io::ReaderStream;
 use tracing::{error, warn};
 use uuid::Uuid;
 
 use crate::{
-    handlers::media::image_validation::validate_magic_bytes,
-    infra::app_state::AppState,
+    handlers::{
+        media::image_validation::validate_magic_bytes,
+        stream::stream_handlers::without_body,
+    },
+    infra::{
+        app_state::AppState,
+        ranges::{self, RangeOutcome},
+    },
 };
 
 const BLOB_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+const PENDING_RETRY_AFTER_MS: u64 = 1_000;
+/// Placeholders never change once stored, but are only useful briefly.
+const PLACEHOLDER_CACHE_CONTROL: &str = "private, max-age=3600";
+/// Marks a response as a placeholder; the real image is still pending.
+const PLACEHOLDER_HEADER: &str = "X-Image-Placeholder";
 
 /// POST /api/v1/images/manifest - Batch image readiness lookup (rkyv request/response).
 pub async fn post_image_manifest_handler(
@@ -95,42 +112,25 @@ pub async fn post_image_manifest_handler(
                     "image manifest meta lookup failed: iid={}, imz={:?}, err={}",
                     iid, imz, err
                 );
-                results.push(ImageManifestResult {
-                    iid,
-                    imz,
-                    status: ImageManifestStatus::Pending {
-                        retry_after_ms: 1_000,
-                    },
-                });
-                state.image_service().enqueue_cache(
-                    iid,
-                    imz,
-                    CachePolicy::Ensure,
-                );
+                let status = pending_status(&state, iid, imz).await;
+                results.push(ImageManifestResult { iid, imz, status });
                 continue;
             }
         };
 
         let Some(meta) = meta else {
-            results.push(ImageManifestResult {
-                iid,
-                imz,
-                status: ImageManifestStatus::Pending {
-                    retry_after_ms: 1_000,
-                },
-            });
-            state
-                .image_service()
-                .enqueue_cache(iid, imz, CachePolicy::Ensure);
+            let status = pending_status(&state, iid, imz).await;
+            results.push(ImageManifestResult { iid, imz, status });
             continue;
         };
 
         let token =
             ImageFileStore::token_from_integrity(&meta.integrity.to_string());
-        let blob_exists = match state.image_service().image_blob_path(&token) {
-            Ok(path) => tokio::fs::try_exists(path).await.unwrap_or(false),
-            Err(_) => false,
-        };
+        let blob_exists = state
+            .image_service()
+            .image_blob_exists(&token)
+            .await
+            .unwrap_or(false);
 
         if blob_exists {
             results.push(ImageManifestResult {
@@ -145,16 +145,8 @@ pub async fn post_image_manifest_handler(
         }
 
         // Cached in `cacache`, but not yet materialized for the immutable blob path.
-        state
-            .image_service()
-            .enqueue_cache(iid, imz, CachePolicy::Ensure);
-        results.push(ImageManifestResult {
-            iid,
-            imz,
-            status: ImageManifestStatus::Pending {
-                retry_after_ms: 1_000,
-            },
-        });
+        let status = pending_status(&state, iid, imz).await;
+        results.push(ImageManifestResult { iid, imz, status });
     }
 
     let response = ImageManifestResponse { results };
@@ -173,6 +165,104 @@ pub async fn post_image_manifest_handler(
         .into_response()
 }
 
+/// Queue a cache fill for a variant that is not ready yet. When the fill
+/// queue is saturated, point the client at a smaller variant that is
+/// already cached rather than leaving it with nothing to show. A variant
+/// whose download failed permanently is reported missing until the
+/// failure expires or the media's images are refreshed.
+async fn pending_status(
+    state: &AppState,
+    iid: Uuid,
+    imz: ImageSize,
+) -> ImageManifestStatus {
+    let images = state.image_service();
+    let outcome = images.enqueue_cache(iid, imz, CachePolicy::Ensure);
+    if let CacheFillOutcome::Unavailable = outcome {
+        return ImageManifestStatus::Missing {
+            reason: images
+                .unavailable_reason(iid, imz)
+                .unwrap_or_else(|| "image is unavailable".to_string()),
+        };
+    }
+    if let CacheFillOutcome::Dropped = outcome {
+        match images.pick_best_available(iid, imz).await {
+            Ok(Some(available)) => {
+                return ImageManifestStatus::Fallback {
+                    imz: available.imz,
+                    token: available.token,
+                    byte_len: available.byte_len,
+                    retry_after_ms: PENDING_RETRY_AFTER_MS,
+                };
+            }
+            Ok(None) => {}
+            Err(err) => {
+                warn!(
+                    "image manifest fallback lookup failed: iid={}, imz={:?}, err={}",
+                    iid, imz, err
+                );
+            }
+        }
+    }
+
+    let placeholder = images.placeholder(iid).await.unwrap_or_else(|err| {
+        warn!("image placeholder lookup failed: iid={}, err={}", iid, err);
+        None
+    });
+    ImageManifestStatus::Pending {
+        retry_after_ms: PENDING_RETRY_AFTER_MS,
+        placeholder,
+    }
+}
+
+/// GET /api/v1/images/{iid}/placeholder - Tiny blurred stand-in shown while
+/// the requested size of an image is still being cached.
+pub async fn get_image_placeholder_handler(
+    State(state): State<AppState>,
+    Path(iid): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.image_service().placeholder(iid).await {
+        Ok(Some(bytes)) => Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, PLACEHOLDER_CONTENT_TYPE)
+            .header(header::CONTENT_LENGTH, bytes.len().to_string())
+            .header(header::CACHE_CONTROL, PLACEHOLDER_CACHE_CONTROL)
+            .header(PLACEHOLDER_HEADER, "pending")
+            .body(Body::from(bytes))
+            .unwrap(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(err) => {
+            error!("image placeholder lookup failed: iid={}, err={}", iid, err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// HEAD /api/v1/images/{iid}/placeholder - Headers of the placeholder.
+pub async fn head_image_placeholder_handler(
+    state: State<AppState>,
+    iid: Path<Uuid>,
+) -> Response {
+    without_body(
+        get_image_placeholder_handler(state, iid)
+            .await
+            .into_response(),
+    )
+}
+
+/// HEAD /api/v1/images/blob/{token} - Status, size, type and validators of
+/// a blob, including `304` and `206` answers, without reading it.
+pub async fn head_image_blob_handler(
+    headers: HeaderMap,
+    state: State<AppState>,
+    token: Path<String>,
+) -> Response {
+    without_body(
+        get_image_blob_handler(headers, state, token)
+            .await
+            .into_response(),
+    )
+}
+
 /// GET /api/v1/images/blob/{token} - Content-addressed immutable image blob.
 pub async fn get_image_blob_handler(
     headers: HeaderMap,
@@ -194,8 +284,18 @@ pub async fn get_image_blob_handler(
             .unwrap();
     }
 
-    let path = match state.image_service().image_blob_path(&token) {
-        Ok(path) => path,
+    let path = match state.image_service().image_blob_location(&token) {
+        Ok(BlobLocation::Path(path)) => path,
+        // Blobs in remote storage have no local file to stream: plaintext
+        // ones are fetched by the client from their public URL, the rest
+        // are read through the server.
+        Ok(BlobLocation::Url(url)) => {
+            return Redirect::temporary(&url).into_response();
+        }
+        Ok(BlobLocation::Proxied) => {
+            return serve_blob_bytes(&state, &headers, &token, etag, None)
+                .await;
+        }
         Err(_) => return StatusCode::BAD_REQUEST.into_response(),
     };
 
@@ -222,6 +322,17 @@ pub async fn get_image_blob_handler(
             .unwrap();
     }
 
+    if state.image_service().cache_encrypted() {
+        return serve_blob_bytes(
+            &state,
+            &headers,
+            &token,
+            etag,
+            Some(last_modified),
+        )
+        .await;
+    }
+
     // Sniff content type via magic bytes (small read) without loading the whole file.
     let content_type = match tokio::fs::File::open(&path).await {
         Ok(mut f) => {
@@ -238,34 +349,187 @@ pub async fn get_image_blob_handler(
         Ok(f) => f,
         Err(_) => return StatusCode::NOT_FOUND.into_response(),
     };
-    let stream = ReaderStream::new(file);
 
-    Response::builder()
-        .status(StatusCode::OK)
+    let file_size = meta.len();
+    let served =
+        requested_range(&headers, &etag, file_size).response(file_size);
+
+    let builder = served
+        .apply(Response::builder())
         .header(header::CONTENT_TYPE, content_type)
-        .header(header::CONTENT_LENGTH, meta.len().to_string())
         .header(header::ETAG, etag)
         .header(header::LAST_MODIFIED, last_modified)
-        .header(header::CACHE_CONTROL, BLOB_CACHE_CONTROL)
-        .body(Body::from_stream(stream))
+        .header(header::CACHE_CONTROL, BLOB_CACHE_CONTROL);
+
+    if served.is_unsatisfiable() {
+        return builder.body(Body::empty()).unwrap();
+    }
+
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+    let mut file = file;
+    if served.offset > 0
+        && let Err(err) =
+            file.seek(std::io::SeekFrom::Start(served.offset)).await
+    {
+        warn!("image blob seek failed: token={}, err={}", token, err);
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    builder
+        .body(Body::from_stream(ReaderStream::new(
+            file.take(served.length),
+        )))
         .unwrap()
 }
 
+/// A range only applies to the representation named by `If-Range`; blobs
+/// are immutable, so the ETag is the only validator compared.
+fn requested_range(headers: &HeaderMap, etag: &str, size: u64) -> RangeOutcome {
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .filter(|_| {
+            headers
+                .get(header::IF_RANGE)
+                .and_then(|v| v.to_str().ok())
+                .is_none_or(|if_range| if_range.trim() == etag)
+        });
+    ranges::parse_ranges(range, size)
+}
+
+/// Blobs sealed at rest, or kept where the client cannot be sent, are
+/// read whole through the image service; ranges are cut from the
+/// plaintext in memory.
+async fn serve_blob_bytes(
+    state: &AppState,
+    headers: &HeaderMap,
+    token: &str,
+    etag: String,
+    last_modified: Option<String>,
+) -> Response {
+    let bytes = match state.image_service().read_image_blob(token).await {
+        Ok(Some(bytes)) => bytes,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(err) => {
+            warn!(
+                "image blob could not be opened: token={}, err={}",
+                token, err
+            );
+            return StatusCode::NOT_FOUND.into_response();
+        }
+    };
+
+    let content_type =
+        validate_magic_bytes(&bytes).unwrap_or("application/octet-stream");
+    let size = bytes.len() as u64;
+    let served = requested_range(headers, &etag, size).response(size);
+
+    let mut builder = served
+        .apply(Response::builder())
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::ETAG, etag)
+        .header(header::CACHE_CONTROL, BLOB_CACHE_CONTROL);
+    if let Some(last_modified) = last_modified {
+        builder = builder.header(header::LAST_MODIFIED, last_modified);
+    }
+
+    let start = served.offset as usize;
+    let end = start + served.length as usize;
+    let body = if start == 0 && end == bytes.len() {
+        bytes
+    } else {
+        bytes[start..end].to_vec()
+    };
+    builder.body(Body::from(body)).unwrap()
+}
+
+/// Most ids a single image events subscription may filter on.
+const MAX_IMAGE_EVENT_FILTER_IDS: usize = 1_024;
+
+/// Comma-separated ids limiting which images an events stream reports.
+#[derive(Debug, Default, Deserialize)]
+pub struct ImageEventsQuery {
+    #[serde(default)]
+    pub media_ids: Option<String>,
+    #[serde(default)]
+    pub iids: Option<String>,
+}
+
+/// Images a subscriber asked about; empty means every image.
+#[derive(Debug, Default)]
+struct ImageEventFilter {
+    media_ids: HashSet<Uuid>,
+    iids: HashSet<Uuid>,
+}
+
+impl ImageEventFilter {
+    fn parse(query: &ImageEventsQuery) -> Result<Self, String> {
+        fn ids(list: Option<&str>) -> Result<HashSet<Uuid>, String> {
+            list.into_iter()
+                .flat_map(|list| list.split(','))
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(|id| {
+                    Uuid::parse_str(id).map_err(|_| format!("invalid id {id}"))
+                })
+                .collect()
+        }
+        let filter = Self {
+            media_ids: ids(query.media_ids.as_deref())?,
+            iids: ids(query.iids.as_deref())?,
+        };
+        if filter.media_ids.len() + filter.iids.len()
+            > MAX_IMAGE_EVENT_FILTER_IDS
+        {
+            return Err(format!(
+                "at most {MAX_IMAGE_EVENT_FILTER_IDS} ids may be filtered on"
+            ));
+        }
+        Ok(filter)
+    }
+
+    fn matches(&self, event: &ImageReadyEvent) -> bool {
+        (self.media_ids.is_empty() && self.iids.is_empty())
+            || self.iids.contains(&event.iid)
+            || event
+                .media_id
+                .is_some_and(|media_id| self.media_ids.contains(&media_id))
+    }
+}
+
 /// GET /api/v1/images/events - SSE stream for image readiness notifications.
+///
+/// `?media_ids=` and `?iids=` narrow the stream to the images a client is
+/// waiting for. Open streams are capped; the slot is released when the
+/// client disconnects.
 pub async fn image_events_sse_handler(
     State(state): State<AppState>,
-) -> Sse<
-    impl tokio_stream::Stream<Item = Result<axum::response::sse::Event, Infallible>>,
-> {
+    Query(query): Query<ImageEventsQuery>,
+) -> Response {
     use axum::response::sse::{Event, KeepAlive};
 
+    let filter = match ImageEventFilter::parse(&query) {
+        Ok(filter) => filter,
+        Err(message) => {
+            return (StatusCode::BAD_REQUEST, message).into_response();
+        }
+    };
+    let limiter = state.image_subscriptions();
+    let Some(permit) = limiter.try_acquire() else {
+        return limiter.busy_response();
+    };
+
     let receiver = state.image_service().subscribe_image_events();
 
     let stream = async_stream::stream! {
+        // Held by the stream, so dropped with it when the client leaves.
+        let _permit = permit;
         let mut live = BroadcastStream::new(receiver);
         while let Some(item) = live.next().await {
             match item {
                 Ok(evt) => {
+                    if !filter.matches(&evt) {
+                        continue;
+                    }
                     let Ok(bytes) = to_bytes::<RkyvError>(&evt) else {
                         continue;
                     };
@@ -283,9 +547,62 @@ pub async fn image_events_sse_handler(
         }
     };
 
-    Sse::new(stream).keep_alive(
-        KeepAlive::new()
-            .interval(Duration::from_secs(15))
-            .text("keep-alive"),
-    )
+    Sse::new(stream)
+        .keep_alive(
+            KeepAlive::new()
+                .interval(Duration::from_secs(15))
+                .text("keep-alive"),
+        )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ready(iid: Uuid, media_id: Option<Uuid>) -> ImageReadyEvent {
+        ImageReadyEvent {
+            iid,
+            imz: ImageSize::Poster(ferrex_model::PosterSize::W185),
+            token: "ab".into(),
+            media_id,
+            path: "/api/v1/images/blob/ab".into(),
+        }
+    }
+
+    #[test]
+    fn filters_match_by_media_or_image_id() {
+        let (poster, movie, other) =
+            (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let everything = ImageEventFilter::default();
+        assert!(everything.matches(&ready(other, None)));
+
+        let filter = ImageEventFilter::parse(&ImageEventsQuery {
+            media_ids: Some(format!("{movie}, ")),
+            iids: Some(poster.to_string()),
+        })
+        .unwrap();
+        assert!(filter.matches(&ready(other, Some(movie))));
+        assert!(filter.matches(&ready(poster, None)));
+        assert!(!filter.matches(&ready(other, Some(other))));
+        assert!(!filter.matches(&ready(other, None)));
+    }
+
+    #[test]
+    fn malformed_or_oversized_filters_are_rejected() {
+        let malformed = ImageEventsQuery {
+            media_ids: Some("not-a-uuid".into()),
+            iids: None,
+        };
+        assert!(ImageEventFilter::parse(&malformed).is_err());
+
+        let ids: Vec<String> = (0..=MAX_IMAGE_EVENT_FILTER_IDS)
+            .map(|_| Uuid::now_v7().to_string())
+            .collect();
+        let oversized = ImageEventsQuery {
+            media_ids: None,
+            iids: Some(ids.join(",")),
+        };
+        assert!(ImageEventFilter::parse(&oversized).is_err());
+    }
 }
@@ -3,22 +3,24 @@ use axum::{
     extract::{Extension, Path, Query, State},
     http::StatusCode,
     http::header,
-    response::{IntoResponse, Json},
+    response::{IntoResponse, Json, Response},
 };
 use ferrex_core::domain::users::user::User;
+use ferrex_core::domain::watch::WatchStatusFilter;
 use ferrex_core::error::MediaError;
 use ferrex_core::query::{
     filtering::hash_filter_spec,
     types::{SortBy, SortOrder},
 };
 use ferrex_core::types::{
-    Library, LibraryId, LibraryReference, Media, MediaID,
+    Library, LibraryId, LibraryReference, Media, MediaID, MovieID,
+    VideoMediaType,
 };
 use ferrex_core::{
     api::types::{
         ApiResponse, CreateLibraryRequest, FetchMediaRequest,
         FilterIndicesRequest, IndicesResponse, LibraryMediaResponse,
-        UpdateLibraryRequest,
+        LibraryStats, MediaCopy, MediaFileIntegrity, UpdateLibraryRequest,
     },
     types::LibraryType,
 };
@@ -31,9 +33,15 @@ use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use crate::handlers::media::media_id_param::MediaIdParam;
 use crate::infra::app_state::AppState;
+use crate::infra::config::MediaConfig;
 use crate::infra::demo_mode;
+use crate::infra::errors::AppResult;
 
+use ferrex_core::domain::scan::exclusions::{
+    install_library_exclusions, validate_exclude_patterns,
+};
 use ferrex_core::domain::scan::orchestration::LibraryActorConfig;
 use futures::{StreamExt, TryStreamExt, stream};
 use once_cell::sync::Lazy;
@@ -164,12 +172,15 @@ pub async fn get_libraries_with_media_handler(
                         })?;
 
                     // Movie and series libraries are bootstrapped via dedicated
-                    // snapshot endpoints (`movie-batches` and `series-bundles`).
+                    // snapshot endpoints (`movie-batches` and `series-bundles`),
+                    // music libraries via the `music` listings.
                     // Keep `/libraries` focused on library metadata so the
                     // snapshot stays small and fast to fetch.
                     if matches!(
                         library_ref.library_type,
-                        LibraryType::Movies | LibraryType::Series
+                        LibraryType::Movies
+                            | LibraryType::Series
+                            | LibraryType::Music
                     ) {
                         return Ok::<_, StatusCode>(library.map(|mut l| {
                             l.media = None;
@@ -245,14 +256,94 @@ pub async fn get_libraries_with_media_handler(
     ))
 }
 
+/// Response header naming the library type's default order
+/// (`date_added:desc`), so clients can reflect it in their sort controls.
+pub const DEFAULT_SORT_HEADER: &str = "x-default-sort";
+/// Response header naming the library type's default watch-status filter;
+/// absent when there is none.
+pub const DEFAULT_FILTER_HEADER: &str = "x-default-filter";
+
 #[derive(Debug, Deserialize)]
 pub struct SortedIdsQuery {
     pub sort: Option<String>,
     pub order: Option<String>,
+    /// Watch-status filter; `all` drops the configured default.
+    pub filter: Option<String>,
     pub offset: Option<usize>,
     pub limit: Option<usize>,
 }
 
+/// Order and filter a listing falls back to when the client leaves them
+/// out, from `media.movie_defaults` / `media.series_defaults`. Values are
+/// validated when the config loads; anything unparsable here falls back to
+/// title ascending, unfiltered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ListingDefaults {
+    sort: SortBy,
+    order: SortOrder,
+    watch_status: Option<WatchStatusFilter>,
+}
+
+impl ListingDefaults {
+    fn for_library(media: &MediaConfig, library_type: LibraryType) -> Self {
+        let configured = match library_type {
+            LibraryType::Movies => Some(&media.movie_defaults),
+            LibraryType::Series => Some(&media.series_defaults),
+            LibraryType::Music => None,
+        };
+        let (sort, order) = configured
+            .and_then(|defaults| defaults.sort_parts())
+            .map(|(field, order)| {
+                (parse_sort_field(field), order.and_then(parse_sort_order))
+            })
+            .unwrap_or_default();
+        Self {
+            sort: sort.unwrap_or(SortBy::Title),
+            order: order.unwrap_or(SortOrder::Ascending),
+            watch_status: configured
+                .and_then(|defaults| defaults.filter.as_deref())
+                .and_then(parse_watch_filter),
+        }
+    }
+
+    /// Attach [`DEFAULT_SORT_HEADER`] and [`DEFAULT_FILTER_HEADER`].
+    fn annotate(&self, response: &mut Response) {
+        let sort = serde_json::to_value(self.sort)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_owned))
+            .unwrap_or_default();
+        let order = match self.order {
+            SortOrder::Ascending => "asc",
+            SortOrder::Descending => "desc",
+        };
+        let headers = response.headers_mut();
+        if let Ok(value) = format!("{sort}:{order}").parse() {
+            headers.insert(DEFAULT_SORT_HEADER, value);
+        }
+        let filter = match self.watch_status {
+            Some(WatchStatusFilter::Unwatched) => Some("unwatched"),
+            Some(WatchStatusFilter::InProgress) => Some("in_progress"),
+            Some(WatchStatusFilter::Completed) => Some("completed"),
+            Some(WatchStatusFilter::RecentlyWatched { .. }) | None => None,
+        };
+        if let Some(filter) = filter {
+            headers.insert(
+                DEFAULT_FILTER_HEADER,
+                header::HeaderValue::from_static(filter),
+            );
+        }
+    }
+}
+
+fn parse_watch_filter(s: &str) -> Option<WatchStatusFilter> {
+    match s.to_lowercase().as_str() {
+        "unwatched" => Some(WatchStatusFilter::Unwatched),
+        "in_progress" => Some(WatchStatusFilter::InProgress),
+        "completed" | "watched" => Some(WatchStatusFilter::Completed),
+        _ => None,
+    }
+}
+
 fn parse_sort_field(s: &str) -> Option<SortBy> {
     match s.to_lowercase().as_str() {
         "title" => Some(SortBy::Title),
@@ -277,10 +368,17 @@ fn parse_sort_order(s: &str) -> Option<SortOrder> {
     }
 }
 
-/// Get presorted media indices for a library (movie libraries supported)
+/// Get presorted media indices for a library (movie libraries supported).
+///
+/// `sort`, `order` and `filter` default to the library type's configured
+/// listing defaults, which are echoed in [`DEFAULT_SORT_HEADER`] and
+/// [`DEFAULT_FILTER_HEADER`].
+///
+/// Watch filters need a user: an explicit one is answered with `401` for
+/// anonymous callers, and a configured default one is skipped for them.
 pub async fn get_library_sorted_indices_handler(
     State(state): State<AppState>,
-    Extension(_user): Extension<User>,
+    user: Option<Extension<User>>,
     Path(library_id): Path<Uuid>,
     Query(params): Query<SortedIdsQuery>,
 ) -> impl IntoResponse {
@@ -300,17 +398,34 @@ pub async fn get_library_sorted_indices_handler(
         }
     };
 
-    // Map sort and order with sensible defaults (default: title asc)
+    // Fall back to the configured defaults for this library type
+    let defaults = ListingDefaults::for_library(
+        &state.config().media,
+        library_ref.library_type,
+    );
     let sort_field = params
         .sort
         .as_deref()
         .and_then(parse_sort_field)
-        .unwrap_or(SortBy::Title);
+        .unwrap_or(defaults.sort);
     let sort_order = params
         .order
         .as_deref()
         .and_then(parse_sort_order)
-        .unwrap_or(SortOrder::Ascending);
+        .unwrap_or(defaults.order);
+    let user_id = user.map(|Extension(user)| user.id);
+    let watch_status = match params.filter.as_deref() {
+        None => defaults.watch_status.clone().filter(|_| user_id.is_some()),
+        Some("all") => None,
+        Some(filter) => {
+            let status =
+                parse_watch_filter(filter).ok_or(StatusCode::BAD_REQUEST)?;
+            if user_id.is_none() {
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+            Some(status)
+        }
+    };
 
     let _offset = params.offset.unwrap_or(0);
     let _limit = params.limit.unwrap_or(60).min(500);
@@ -325,18 +440,51 @@ pub async fn get_library_sorted_indices_handler(
         return Err(StatusCode::NOT_IMPLEMENTED);
     }
 
-    let indices = match state
-        .unit_of_work()
-        .indices
-        .fetch_sorted_movie_indices(
-            library_ref.id,
-            sort_field,
-            sort_order,
-            params.offset,
-            params.limit,
-        )
-        .await
-    {
+    let indices = match watch_status {
+        // A watch filter needs the per-user query; page its result here.
+        Some(watch_status) => {
+            let spec = FilterIndicesRequest {
+                media_type: None,
+                genres: Vec::new(),
+                year_range: None,
+                rating_range: None,
+                resolution_range: None,
+                watch_status: Some(watch_status),
+                search: None,
+                sort: Some(sort_field),
+                order: Some(sort_order),
+                expr: None,
+                certification_max: None,
+                include_unrated: false,
+            };
+            state
+                .unit_of_work()
+                .indices
+                .fetch_filtered_movie_indices(library_ref.id, &spec, user_id)
+                .await
+                .map(|indices| {
+                    indices
+                        .into_iter()
+                        .skip(params.offset.unwrap_or(0))
+                        .take(params.limit.unwrap_or(usize::MAX))
+                        .collect()
+                })
+        }
+        None => {
+            state
+                .unit_of_work()
+                .indices
+                .fetch_sorted_movie_indices(
+                    library_ref.id,
+                    sort_field,
+                    sort_order,
+                    params.offset,
+                    params.limit,
+                )
+                .await
+        }
+    };
+    let indices = match indices {
         Ok(indices) => indices,
         Err(err) => {
             error!(
@@ -347,14 +495,18 @@ pub async fn get_library_sorted_indices_handler(
         }
     };
 
-    respond_with_indices(indices)
+    respond_with_indices(indices, &defaults)
 }
 
+/// Indices of a library's movies matching `spec`. A spec without `sort` or
+/// `order` takes the library type's configured default; the spec itself is
+/// the filter, so the default filter does not apply. A spec that filters
+/// or sorts on watch state is answered with `401` for anonymous callers.
 pub async fn post_library_filtered_indices_handler(
     State(state): State<AppState>,
-    Extension(user): Extension<User>,
+    user: Option<Extension<User>>,
     Path(library_id): Path<Uuid>,
-    Json(spec): Json<FilterIndicesRequest>,
+    Json(mut spec): Json<FilterIndicesRequest>,
 ) -> impl IntoResponse {
     info!("Getting filtered indices for library: {}", library_id);
 
@@ -376,9 +528,21 @@ pub async fn post_library_filtered_indices_handler(
         return Err(StatusCode::NOT_IMPLEMENTED);
     }
 
+    let defaults = ListingDefaults::for_library(
+        &state.config().media,
+        library_ref.library_type,
+    );
+    spec.sort.get_or_insert(defaults.sort);
+    spec.order.get_or_insert(defaults.order);
+
     let library_uuid = library_ref.id.to_uuid();
 
-    let user_scope = requires_user_scope(&spec).then_some(user.id);
+    let user_id = user.map(|Extension(user)| user.id);
+    let user_scope = if requires_user_scope(&spec) {
+        Some(user_id.ok_or(StatusCode::UNAUTHORIZED)?)
+    } else {
+        None
+    };
 
     // Check short-lived in-process cache first
     let cache_key = FilterCacheKey {
@@ -387,13 +551,13 @@ pub async fn post_library_filtered_indices_handler(
         user_id: user_scope,
     };
     if let Some(indices) = get_cached_indices(&cache_key) {
-        return respond_with_indices(indices);
+        return respond_with_indices(indices, &defaults);
     }
 
     let indices = match state
         .unit_of_work()
         .indices
-        .fetch_filtered_movie_indices(library_ref.id, &spec, Some(user.id))
+        .fetch_filtered_movie_indices(library_ref.id, &spec, user_id)
         .await
     {
         Ok(indices) => indices,
@@ -411,7 +575,7 @@ pub async fn post_library_filtered_indices_handler(
     };
 
     insert_cached_indices(cache_key, indices.clone());
-    respond_with_indices(indices)
+    respond_with_indices(indices, &defaults)
 }
 
 fn get_cached_indices(key: &FilterCacheKey) -> Option<Vec<u32>> {
@@ -438,20 +602,26 @@ fn insert_cached_indices(key: FilterCacheKey, indices: Vec<u32>) {
 
 fn respond_with_indices(
     indices: Vec<u32>,
-) -> Result<
-    ([(axum::http::header::HeaderName, &'static str); 1], Bytes),
-    StatusCode,
-> {
+    defaults: &ListingDefaults,
+) -> Result<Response, StatusCode> {
     let response = IndicesResponse {
         content_version: 1,
         indices,
     };
 
     match rkyv::to_bytes::<RkyvError>(&response) {
-        Ok(bytes) => Ok((
-            [(axum::http::header::CONTENT_TYPE, "application/octet-stream")],
-            Bytes::from(bytes.into_vec()),
-        )),
+        Ok(bytes) => {
+            let mut response = (
+                [(
+                    axum::http::header::CONTENT_TYPE,
+                    "application/octet-stream",
+                )],
+                Bytes::from(bytes.into_vec()),
+            )
+                .into_response();
+            defaults.annotate(&mut response);
+            Ok(response)
+        }
         Err(e) => {
             error!("Failed to serialize indices response: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -672,6 +842,76 @@ pub async fn get_library_handler(
     }
 }
 
+/// Aggregate counts for a library overview.
+///
+/// Item, size and coverage counts come from one aggregate query; the
+/// missing-file count is taken from the latest integrity verification so
+/// this endpoint never touches the media roots.
+pub async fn get_library_stats_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<LibraryStats>>, StatusCode> {
+    let library_id = LibraryId(id);
+    if demo_mode::is_demo_mode(&state)
+        && !demo_mode::is_demo_library(&library_id)
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let mut stats = state
+        .unit_of_work()
+        .libraries
+        .library_stats(library_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to load library stats for {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if let Some(report) = state
+        .scan_control()
+        .integrity()
+        .latest_report(library_id)
+        .await
+    {
+        let missing = report
+            .issues
+            .iter()
+            .filter(|issue| {
+                matches!(issue.integrity, MediaFileIntegrity::Missing)
+            })
+            .count();
+        stats.missing_files = Some(missing as u64);
+        stats.missing_checked_at = Some(report.finished_at);
+    }
+
+    Ok(Json(ApiResponse::success(stats)))
+}
+
+/// Every library holding a copy of a movie, the canonical copy first.
+///
+/// Copies are matched by TMDB id; a movie only present in one library
+/// returns just itself.
+pub async fn get_media_availability_handler(
+    State(state): State<AppState>,
+    Path(id): Path<MediaIdParam>,
+) -> AppResult<Json<ApiResponse<Vec<MediaCopy>>>> {
+    let media_id = id.resolve(&state, &[VideoMediaType::Movie]).await?;
+    let movie_id = MovieID(*media_id.as_uuid());
+
+    let mut copies = state
+        .unit_of_work()
+        .media_refs
+        .list_movie_copies(&movie_id)
+        .await?;
+    if demo_mode::is_demo_mode(&state) {
+        copies.retain(|copy| demo_mode::is_demo_library(&copy.library_id));
+    }
+
+    Ok(Json(ApiResponse::success(copies)))
+}
+
 /// Create a new library
 pub async fn create_library_handler(
     State(state): State<AppState>,
@@ -700,6 +940,9 @@ pub async fn create_library_handler(
                 ))));
             }
         };
+    if let Err(e) = validate_exclude_patterns(&request.exclude_patterns) {
+        return Ok(Json(ApiResponse::error(e.to_string())));
+    }
 
     let library = Library {
         id: library_id,
@@ -717,6 +960,7 @@ pub async fn create_library_handler(
         analyze_on_scan: false,
         max_retry_attempts: 3,
         movie_ref_batch_size,
+        exclude_patterns: request.exclude_patterns,
     };
 
     info!(
@@ -730,6 +974,12 @@ pub async fn create_library_handler(
     match libraries_repo.create_library(library.clone()).await {
         Ok(id) => {
             info!("Library successfully created in database with ID: {}", id);
+            if let Err(err) = install_library_exclusions(&library) {
+                warn!(
+                    "Failed to install exclude patterns for library {}: {}",
+                    library.id, err
+                );
+            }
 
             let actor_config = LibraryActorConfig {
                 library: LibraryReference {
@@ -740,6 +990,13 @@ pub async fn create_library_handler(
                 },
                 root_paths: library.paths.clone(),
                 max_outstanding_jobs: 8,
+                quiescence_window: state
+                    .config()
+                    .scanner
+                    .library_quiescence_window(
+                        &library.id.to_string(),
+                        &library.name,
+                    ),
             };
 
             if let Err(err) = orchestrator
@@ -857,14 +1114,26 @@ pub async fn update_library_handler(
             }
         }
     }
+    if let Some(patterns) = request.exclude_patterns {
+        if let Err(e) = validate_exclude_patterns(&patterns) {
+            return Ok(Json(ApiResponse::error(e.to_string())));
+        }
+        library.exclude_patterns = patterns;
+    }
     library.updated_at = chrono::Utc::now();
 
     match libraries_repo
-        .update_library(LibraryId(uuid), library)
+        .update_library(LibraryId(uuid), library.clone())
         .await
     {
         Ok(_) => {
             info!("Library updated: {}", id);
+            if let Err(err) = install_library_exclusions(&library) {
+                warn!(
+                    "Failed to install exclude patterns for library {}: {}",
+                    id, err
+                );
+            }
             Ok(Json(ApiResponse::success("Library updated".to_string())))
         }
         Err(e) => {
@@ -906,3 +1175,48 @@ pub async fn delete_library_handler(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::config::LibraryListingDefaults;
+
+    fn media(sort: Option<&str>, filter: Option<&str>) -> MediaConfig {
+        MediaConfig {
+            root: None,
+            metadata_languages: Vec::new(),
+            filename_rules: Vec::new(),
+            movie_defaults: LibraryListingDefaults {
+                sort: sort.map(str::to_string),
+                filter: filter.map(str::to_string),
+            },
+            series_defaults: LibraryListingDefaults::default(),
+        }
+    }
+
+    #[test]
+    fn listing_defaults_follow_the_library_type() {
+        let media = media(Some("date_added:desc"), Some("unwatched"));
+        let movies = ListingDefaults::for_library(&media, LibraryType::Movies);
+        assert_eq!(movies.sort, SortBy::DateAdded);
+        assert_eq!(movies.order, SortOrder::Descending);
+        assert_eq!(movies.watch_status, Some(WatchStatusFilter::Unwatched));
+
+        let series = ListingDefaults::for_library(&media, LibraryType::Series);
+        assert_eq!(series.sort, SortBy::Title);
+        assert_eq!(series.order, SortOrder::Ascending);
+        assert_eq!(series.watch_status, None);
+    }
+
+    #[test]
+    fn listing_defaults_are_reported_in_headers() {
+        let defaults = ListingDefaults::for_library(
+            &media(Some("rating"), Some("in_progress")),
+            LibraryType::Movies,
+        );
+        let mut response = Response::new(axum::body::Body::empty());
+        defaults.annotate(&mut response);
+        assert_eq!(response.headers()[DEFAULT_SORT_HEADER], "rating:asc");
+        assert_eq!(response.headers()[DEFAULT_FILTER_HEADER], "in_progress");
+    }
+}
@@ -0,0 +1,170 @@
+//! Bulk deletion of movies, series, seasons and episodes.
+
+use std::collections::HashSet;
+
+use axum::{Extension, Json, extract::State};
+use ferrex_core::{
+    api::types::{
+        ApiResponse, DeleteMediaFilter, DeleteMediaRequest,
+        DeleteMediaResponse, MediaDeletionResult,
+    },
+    database::repository_ports::media_files::{
+        MediaFileFilter, MediaFileSort, MediaFileSortField, Page,
+    },
+    domain::users::user::User,
+    types::{MediaEvent, MediaID},
+};
+use tracing::{info, warn};
+
+use crate::infra::{
+    app_state::AppState,
+    errors::{AppError, AppResult},
+};
+
+/// Ids deleted per transaction.
+const DELETE_BATCH_SIZE: usize = 100;
+const FILTER_PAGE_SIZE: u32 = 500;
+
+/// Delete media by id or by file filter.
+///
+/// Each batch of ids is deleted in one transaction, together with the file
+/// records, watch state and cached images of everything removed. A season
+/// or series goes only with its last episode. Files stay on disk unless
+/// `delete_files` is set.
+pub async fn delete_media_handler(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Json(request): Json<DeleteMediaRequest>,
+) -> AppResult<Json<ApiResponse<DeleteMediaResponse>>> {
+    if request.ids.is_empty() && request.filter.is_none() {
+        return Err(AppError::bad_request("Provide ids or a filter to delete"));
+    }
+
+    let mut ids = request.ids.clone();
+    if let Some(filter) = &request.filter {
+        ids.extend(filtered_media(&state, filter).await?);
+    }
+    let mut seen = HashSet::new();
+    ids.retain(|id| seen.insert(*id));
+
+    info!(
+        "Admin {} deleting {} media (delete_files: {})",
+        admin.username,
+        ids.len(),
+        request.delete_files
+    );
+
+    let media_refs = state.unit_of_work().media_refs.clone();
+    let images = state.image_service();
+    let mut response = DeleteMediaResponse {
+        results: Vec::with_capacity(ids.len()),
+        deleted: 0,
+        failed: 0,
+        images_invalidated: 0,
+    };
+
+    for batch in ids.chunks(DELETE_BATCH_SIZE) {
+        let outcomes = media_refs.delete_media_batch(batch).await?;
+        for (id, outcome) in batch.iter().zip(outcomes) {
+            let removal = match outcome {
+                Ok(removal) => removal,
+                Err(err) => {
+                    response.failed += 1;
+                    response.results.push(MediaDeletionResult {
+                        id: *id,
+                        deleted: false,
+                        error: Some(err.to_string()),
+                        files: Vec::new(),
+                        files_removed: 0,
+                    });
+                    continue;
+                }
+            };
+
+            for media in &removal.removed {
+                match images.invalidate_all_variants(*media.as_uuid()).await {
+                    Ok(count) => {
+                        response.images_invalidated += u64::from(count)
+                    }
+                    Err(err) => warn!(
+                        media = %media.as_uuid(),
+                        error = %err,
+                        "failed to drop cached images of deleted media"
+                    ),
+                }
+                state.scan_control().publish_media_event(
+                    MediaEvent::MediaDeleted { id: *media },
+                );
+            }
+
+            let mut files_removed = 0;
+            let mut failures = Vec::new();
+            if request.delete_files {
+                for path in &removal.files {
+                    match tokio::fs::remove_file(path).await {
+                        Ok(()) => files_removed += 1,
+                        Err(err)
+                            if err.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(err) => failures.push(format!(
+                            "{}: {}",
+                            path.display(),
+                            err
+                        )),
+                    }
+                }
+            }
+
+            response.deleted += 1;
+            response.results.push(MediaDeletionResult {
+                id: *id,
+                deleted: true,
+                error: (!failures.is_empty()).then(|| {
+                    format!("Failed to remove files: {}", failures.join("; "))
+                }),
+                files: removal
+                    .files
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect(),
+                files_removed,
+            });
+        }
+    }
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// Movies and episodes whose files match `filter`.
+async fn filtered_media(
+    state: &AppState,
+    filter: &DeleteMediaFilter,
+) -> AppResult<Vec<MediaID>> {
+    let files = state.unit_of_work().media_files_read.clone();
+    let filter = MediaFileFilter {
+        library_id: Some(filter.library_id),
+        path_prefix: filter.path_prefix.clone(),
+        ..MediaFileFilter::default()
+    };
+    let sort = MediaFileSort::ascending(MediaFileSortField::DiscoveredAt);
+
+    let mut media = Vec::new();
+    let mut offset = 0u32;
+    loop {
+        let page = files
+            .list(
+                filter.clone(),
+                sort,
+                Page {
+                    limit: FILTER_PAGE_SIZE,
+                    offset,
+                },
+            )
+            .await?;
+        let page_len = page.len() as u32;
+        media.extend(page.into_iter().map(|file| file.media_id));
+        if page_len < FILTER_PAGE_SIZE {
+            return Ok(media);
+        }
+        offset = offset.saturating_add(page_len);
+    }
+}
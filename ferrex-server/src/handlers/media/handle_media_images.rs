@@ -0,0 +1,131 @@
+//! Listing the images linked to a movie or series and choosing which one
+//! of each variant is primary.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+};
+use ferrex_core::{
+    api::types::{ApiResponse, MediaImage, MediaImagesResponse},
+    database::traits::OriginalImage,
+    domain::users::user::User,
+    infra::image_service::CachePolicy,
+    types::{MediaEvent, MediaID, SeriesID},
+};
+use ferrex_model::{ImageSize, image::ImageVariant};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    handlers::media::media_id_param::{MOVIE_OR_SERIES, MediaIdParam},
+    infra::{app_state::AppState, errors::AppResult},
+};
+
+/// Every image linked to a movie or series, with its variant, source and
+/// whether it is the primary one.
+pub async fn list_media_images_handler(
+    State(state): State<AppState>,
+    Path(id): Path<MediaIdParam>,
+) -> AppResult<Json<ApiResponse<MediaImagesResponse>>> {
+    let media_id = id.resolve(&state, MOVIE_OR_SERIES).await?;
+    let owner = image_owner(&state, media_id).await?;
+
+    let images = state
+        .unit_of_work()
+        .images
+        .list_media_images(owner)
+        .await?
+        .into_iter()
+        .map(|image| media_image(&state, image))
+        .collect();
+
+    Ok(Json(ApiResponse::success(MediaImagesResponse {
+        media_id,
+        images,
+    })))
+}
+
+/// Make one linked image the primary of its variant. The previous primary
+/// keeps its link, the library listing shows the new image and clients are
+/// told through the media event stream.
+pub async fn set_primary_image_handler(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Path((id, iid)): Path<(MediaIdParam, Uuid)>,
+) -> AppResult<Json<ApiResponse<MediaImage>>> {
+    let media_id = id.resolve(&state, MOVIE_OR_SERIES).await?;
+    let owner = image_owner(&state, media_id).await?;
+
+    // NotFound when the image is unknown or belongs to other media.
+    let image = state
+        .unit_of_work()
+        .images
+        .set_primary_image(owner, iid)
+        .await?;
+    info!(
+        "Admin {} set image {} as primary {} of {:?}",
+        admin.username,
+        iid,
+        image.imz.image_variant_str(),
+        media_id
+    );
+
+    let display_size = match image.imz.image_variant() {
+        ImageVariant::Poster => ImageSize::poster(),
+        ImageVariant::Backdrop => ImageSize::backdrop(),
+        ImageVariant::Thumbnail => ImageSize::thumbnail(),
+        ImageVariant::Profile => ImageSize::profile(),
+    };
+    let _ = state.image_service().enqueue_cache(
+        iid,
+        display_size,
+        CachePolicy::Ensure,
+    );
+
+    let media_refs = state.unit_of_work().media_refs.clone();
+    let event = match media_id {
+        MediaID::Movie(movie_id) => MediaEvent::MovieUpdated {
+            movie: media_refs.get_movie_reference(&movie_id).await?,
+        },
+        _ => MediaEvent::SeriesUpdated {
+            series: media_refs
+                .get_series_reference(&SeriesID(*media_id.as_uuid()))
+                .await?,
+        },
+    };
+    state.scan_control().publish_media_event(event);
+
+    Ok(Json(ApiResponse::success(media_image(&state, image))))
+}
+
+/// The id the images of `media_id` are linked to. Copies of a movie share
+/// the images of the oldest copy.
+async fn image_owner(state: &AppState, media_id: MediaID) -> AppResult<Uuid> {
+    let MediaID::Movie(movie_id) = media_id else {
+        return Ok(*media_id.as_uuid());
+    };
+    let media_refs = &state.unit_of_work().media_refs;
+    let movie = media_refs.get_movie_reference(&movie_id).await?;
+    let owner = media_refs
+        .canonical_movie_id(movie.tmdb_id)
+        .await?
+        .unwrap_or(movie_id);
+    Ok(owner.to_uuid())
+}
+
+fn media_image(state: &AppState, image: OriginalImage) -> MediaImage {
+    let source = state
+        .image_service()
+        .image_source_name(&image.tmdb_path, image.imz)
+        .map(str::to_string);
+    MediaImage {
+        iid: image.iid,
+        imz: image.imz,
+        path: image.tmdb_path,
+        source,
+        language: (!image.iso_lang.is_empty()).then_some(image.iso_lang),
+        vote_avg: image.vote_avg,
+        vote_cnt: image.vote_cnt,
+        is_primary: image.is_primary,
+    }
+}
@@ -0,0 +1,250 @@
+//! Forced metadata refresh, partial refresh of selected fields and manual
+//! TMDB matching for a single movie or series.
+
+use std::collections::BTreeSet;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use ferrex_core::{
+    api::types::{ApiResponse, RefreshFieldsRequest, RefreshFieldsResponse},
+    domain::users::user::User,
+    error::MediaError,
+    types::{Media, MediaEvent, MediaID, SeriesID, VideoMediaType},
+};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{
+    handlers::media::media_id_param::{MOVIE_OR_SERIES, MediaIdParam},
+    infra::{
+        app_state::AppState,
+        errors::{AppError, AppResult},
+    },
+};
+
+/// Optional body for a forced metadata refresh
+#[derive(Debug, Default, Deserialize)]
+pub struct RefreshMetadataRequest {
+    /// Re-match against this TMDB id instead of the stored one
+    #[serde(default)]
+    pub tmdb_id: Option<u64>,
+}
+
+/// Body for pinning a movie or series to a TMDB id
+#[derive(Debug, Deserialize)]
+pub struct SetTmdbMatchRequest {
+    pub tmdb_id: u64,
+    /// Must agree with the stored record (`Movie` or `Series`)
+    pub media_type: VideoMediaType,
+}
+
+/// Re-fetch a movie or series from TMDB regardless of the metadata already
+/// stored, invalidate its cached images and broadcast the updated record.
+///
+/// Repeated calls for the same id and override while a refresh is running
+/// (or just finished) share that refresh instead of starting another.
+pub async fn refresh_metadata_handler(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Path(id): Path<MediaIdParam>,
+    body: Option<Json<RefreshMetadataRequest>>,
+) -> AppResult<Json<ApiResponse<Media>>> {
+    let tmdb_override = body.and_then(|Json(request)| request.tmdb_id);
+    if tmdb_override == Some(0) {
+        return Err(AppError::bad_request("tmdb_id must be non-zero"));
+    }
+
+    let media_id = id.resolve(&state, MOVIE_OR_SERIES).await?;
+    info!(
+        "Admin {} requested metadata refresh for {:?} (tmdb override: {:?})",
+        admin.username, media_id, tmdb_override
+    );
+
+    let actor = state.scan_control().orchestrator().actors().tmdb_actor();
+    let scan_control = state.scan_control();
+    let throttle = scan_control.orchestrator().metadata_throttle();
+    let refresh = async move {
+        // Background enrichment backs off while this fetch runs.
+        let _on_demand = throttle.on_demand();
+        let (media, event) = match media_id {
+            MediaID::Movie(movie_id) => {
+                let movie = actor
+                    .refresh_movie(movie_id, tmdb_override)
+                    .await
+                    .map_err(refresh_error)?;
+                (
+                    Media::Movie(Box::new(movie.clone())),
+                    MediaEvent::MovieUpdated { movie },
+                )
+            }
+            MediaID::Series(series_id) => {
+                let series = actor
+                    .refresh_series(series_id, tmdb_override)
+                    .await
+                    .map_err(refresh_error)?;
+                (
+                    Media::Series(Box::new(series.clone())),
+                    MediaEvent::SeriesUpdated { series },
+                )
+            }
+            other => {
+                return Err(AppError::bad_request(format!(
+                    "Metadata refresh is supported for movies and series, got {:?}",
+                    other
+                )));
+            }
+        };
+        scan_control.publish_media_event(event);
+        Ok(media)
+    };
+
+    let media = state
+        .metadata_refreshes()
+        .run((*media_id.as_uuid(), tmdb_override), refresh)
+        .await?;
+
+    Ok(Json(ApiResponse::success(media)))
+}
+
+/// Re-fetch only the requested metadata fields of a movie or series. Other
+/// fields, including manual edits and a hand-picked primary image, are
+/// left as stored; the response lists the fields that actually changed.
+pub async fn refresh_metadata_fields_handler(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Path(id): Path<MediaIdParam>,
+    Json(request): Json<RefreshFieldsRequest>,
+) -> AppResult<Json<ApiResponse<RefreshFieldsResponse>>> {
+    let fields: BTreeSet<_> = request.fields.into_iter().collect();
+    if fields.is_empty() {
+        return Err(AppError::bad_request(
+            "fields must name at least one of overview, genres, images, cast, rating",
+        ));
+    }
+
+    let media_id = id.resolve(&state, MOVIE_OR_SERIES).await?;
+    info!(
+        "Admin {} requested a refresh of {:?} for {:?}",
+        admin.username, fields, media_id
+    );
+
+    let actor = state.scan_control().orchestrator().actors().tmdb_actor();
+    let _on_demand = state
+        .scan_control()
+        .orchestrator()
+        .metadata_throttle()
+        .on_demand();
+    let (media, changed) = match media_id {
+        MediaID::Movie(movie_id) => {
+            let (movie, changed) = actor
+                .refresh_movie_fields(movie_id, &fields)
+                .await
+                .map_err(refresh_error)?;
+            if !changed.is_empty() {
+                state.scan_control().publish_media_event(
+                    MediaEvent::MovieUpdated {
+                        movie: movie.clone(),
+                    },
+                );
+            }
+            (Media::Movie(Box::new(movie)), changed)
+        }
+        _ => {
+            let (series, changed) = actor
+                .refresh_series_fields(SeriesID(*media_id.as_uuid()), &fields)
+                .await
+                .map_err(refresh_error)?;
+            if !changed.is_empty() {
+                state.scan_control().publish_media_event(
+                    MediaEvent::SeriesUpdated {
+                        series: series.clone(),
+                    },
+                );
+            }
+            (Media::Series(Box::new(series)), changed)
+        }
+    };
+
+    Ok(Json(ApiResponse::success(RefreshFieldsResponse {
+        media,
+        changed,
+    })))
+}
+
+/// Re-match a movie or series against an operator-chosen TMDB id. The
+/// metadata and images are replaced and the id is pinned on the record, so
+/// later scans keep this match instead of searching again.
+pub async fn set_tmdb_match_handler(
+    State(state): State<AppState>,
+    Extension(admin): Extension<User>,
+    Path(id): Path<MediaIdParam>,
+    Json(request): Json<SetTmdbMatchRequest>,
+) -> AppResult<Json<ApiResponse<Media>>> {
+    if request.tmdb_id == 0 {
+        return Err(AppError::bad_request("tmdb_id must be non-zero"));
+    }
+
+    let media_id = id.resolve(&state, MOVIE_OR_SERIES).await?;
+    let stored_type = match media_id {
+        MediaID::Movie(_) => VideoMediaType::Movie,
+        _ => VideoMediaType::Series,
+    };
+    if request.media_type != stored_type {
+        return Err(AppError::bad_request(format!(
+            "{} is a {}, not a {}",
+            media_id.as_uuid(),
+            stored_type,
+            request.media_type
+        )));
+    }
+    info!(
+        "Admin {} pinned {:?} to TMDB id {}",
+        admin.username, media_id, request.tmdb_id
+    );
+
+    let actor = state.scan_control().orchestrator().actors().tmdb_actor();
+    let _on_demand = state
+        .scan_control()
+        .orchestrator()
+        .metadata_throttle()
+        .on_demand();
+    let (media, event) = match media_id {
+        MediaID::Movie(movie_id) => {
+            let movie = actor
+                .rematch_movie(movie_id, request.tmdb_id)
+                .await
+                .map_err(refresh_error)?;
+            (
+                Media::Movie(Box::new(movie.clone())),
+                MediaEvent::MovieUpdated { movie },
+            )
+        }
+        _ => {
+            let series = actor
+                .rematch_series(SeriesID(*media_id.as_uuid()), request.tmdb_id)
+                .await
+                .map_err(refresh_error)?;
+            (
+                Media::Series(Box::new(series.clone())),
+                MediaEvent::SeriesUpdated { series },
+            )
+        }
+    };
+    state.scan_control().publish_media_event(event);
+
+    Ok(Json(ApiResponse::success(media)))
+}
+
+/// TMDB had no usable match for the requested id (e.g. no poster); report
+/// it as unprocessable rather than a server fault.
+fn refresh_error(err: MediaError) -> AppError {
+    match err {
+        MediaError::InvalidMedia(msg) => {
+            AppError::new(StatusCode::UNPROCESSABLE_ENTITY, msg)
+        }
+        other => other.into(),
+    }
+}
@@ -0,0 +1,68 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use ferrex_core::{
+    api::ApiResponse,
+    api::types::{MusicAlbum, MusicAlbumQuery, MusicTrack, MusicTrackQuery},
+    types::{LibraryId, library::LibraryType},
+};
+use uuid::Uuid;
+
+use crate::infra::{
+    app_state::AppState,
+    demo_mode,
+    errors::{AppError, AppResult},
+};
+
+/// Tracks of a music library, optionally narrowed to an artist, an album
+/// or a search term.
+pub async fn list_music_tracks_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<MusicTrackQuery>,
+) -> AppResult<Json<ApiResponse<Vec<MusicTrack>>>> {
+    let library_id = music_library(&state, id).await?;
+    let tracks = state
+        .unit_of_work()
+        .music_tracks
+        .list_tracks(library_id, &query)
+        .await?;
+    Ok(Json(ApiResponse::success(tracks)))
+}
+
+/// Albums of a music library, optionally of one artist.
+pub async fn list_music_albums_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<MusicAlbumQuery>,
+) -> AppResult<Json<ApiResponse<Vec<MusicAlbum>>>> {
+    let library_id = music_library(&state, id).await?;
+    let albums = state
+        .unit_of_work()
+        .music_tracks
+        .list_albums(library_id, query.artist.as_deref())
+        .await?;
+    Ok(Json(ApiResponse::success(albums)))
+}
+
+async fn music_library(state: &AppState, id: Uuid) -> AppResult<LibraryId> {
+    let library_id = LibraryId(id);
+    if demo_mode::is_demo_mode(state)
+        && !demo_mode::is_demo_library(&library_id)
+    {
+        return Err(AppError::not_found("Library not found"));
+    }
+    let library = state
+        .unit_of_work()
+        .libraries
+        .get_library_reference(id)
+        .await?;
+    if library.library_type != LibraryType::Music {
+        return Err(AppError::bad_request(format!(
+            "library {} is not a music library",
+            id
+        )));
+    }
+    Ok(library_id)
+}
@@ -1,22 +1,38 @@
-use axum::{Extension, Json, extract::State};
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
 use ferrex_core::{
     api::ApiResponse,
-    player_prelude::{MediaQuery, MediaWithStatus, User},
+    player_prelude::{MediaQuery, MediaTypeFilter, MediaWithStatus, User},
+    query::types::{
+        MediaSearchHit, MediaSearchQuery, QueryError, RecentlyAddedPage,
+        RecentlyAddedQuery,
+    },
 };
 
-use crate::infra::{app_state::AppState, errors::AppResult};
+use crate::infra::{
+    app_state::AppState,
+    errors::{AppError, AppResult},
+};
 
 const DEFAULT_SEARCH_LIMIT: usize = 50;
 const MAX_SEARCH_LIMIT: usize = 100;
 
-/// Execute a media query
+/// Execute a media query. Anonymous callers, allowed when browsing does
+/// not require auth, get results without watch status.
 pub async fn query_media_handler(
     State(state): State<AppState>,
-    Extension(user): Extension<User>,
+    user: Option<Extension<User>>,
     Json(mut query): Json<MediaQuery>,
 ) -> AppResult<Json<ApiResponse<Vec<MediaWithStatus>>>> {
+    query
+        .filters
+        .certification()
+        .map_err(invalid_query_parameters)?;
+
     // Add user context to the query
-    query.user_context = Some(user.id);
+    query.user_context = user.map(|Extension(user)| user.id);
 
     clamp_query_limit(&mut query);
 
@@ -26,17 +42,66 @@ pub async fn query_media_handler(
     Ok(Json(ApiResponse::success(results)))
 }
 
-/// Execute a media query without authentication (public)
-pub async fn query_media_public_handler(
+/// Search titles, and optionally overviews and cast, across movies, series
+/// and episodes. Hits come back best first with a relevance score.
+///
+/// # Response
+///
+/// - `200 OK` with the ranked hits
+/// - `400 Bad Request` for an empty `q`, `type=season`, an unknown field
+///   or an unknown `certification_max`
+pub async fn search_media_handler(
     State(state): State<AppState>,
-    Json(mut query): Json<MediaQuery>,
-) -> AppResult<Json<ApiResponse<Vec<MediaWithStatus>>>> {
-    // Execute the query without user context
-    clamp_query_limit(&mut query);
-    clamp_query_limit(&mut query);
-    let results = state.unit_of_work().query.query_media(&query).await?;
+    Query(query): Query<MediaSearchQuery>,
+) -> AppResult<Json<ApiResponse<Vec<MediaSearchHit>>>> {
+    if query.q.trim().is_empty() {
+        return Err(AppError::bad_request("Search text `q` is required"));
+    }
+    if query.media_type == Some(MediaTypeFilter::Season) {
+        return Err(AppError::bad_request(
+            "Seasons are not searchable; use movie, series or episode",
+        ));
+    }
+    let fields = query.search_fields().map_err(invalid_query_parameters)?;
+    query.certification().map_err(invalid_query_parameters)?;
+    let limit = match query.limit {
+        None | Some(0) => DEFAULT_SEARCH_LIMIT,
+        Some(limit) => limit.min(MAX_SEARCH_LIMIT),
+    };
 
-    Ok(Json(ApiResponse::success(results)))
+    let hits = state
+        .unit_of_work()
+        .query
+        .search_media(&query, &fields, limit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(hits)))
+}
+
+/// Movies and series newest first, one entry per series
+pub async fn recently_added_handler(
+    State(state): State<AppState>,
+    Query(query): Query<RecentlyAddedQuery>,
+) -> AppResult<Json<ApiResponse<RecentlyAddedPage>>> {
+    let limit = match query.limit {
+        None | Some(0) => DEFAULT_SEARCH_LIMIT,
+        Some(limit) => limit.min(MAX_SEARCH_LIMIT),
+    };
+
+    let page = state
+        .unit_of_work()
+        .query
+        .query_recently_added(query.library_id, limit, query.cursor)
+        .await?;
+
+    Ok(Json(ApiResponse::success(page)))
+}
+
+fn invalid_query_parameters(err: QueryError) -> AppError {
+    match err {
+        QueryError::InvalidQuery(message) => AppError::bad_request(message),
+        other => AppError::bad_request(other.to_string()),
+    }
 }
 
 fn clamp_query_limit(query: &mut MediaQuery) {
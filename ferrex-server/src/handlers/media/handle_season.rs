@@ -0,0 +1,38 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use ferrex_core::{
+    api::ApiResponse,
+    query::types::EpisodeSort,
+    types::{EpisodeReference, SeasonID},
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::infra::{app_state::AppState, errors::AppResult};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SeasonEpisodesQuery {
+    /// `number` (default) or `air_date`
+    #[serde(default)]
+    pub sort: EpisodeSort,
+}
+
+/// Episodes of a season, ordered by number or by air date.
+pub async fn get_season_episodes_handler(
+    State(state): State<AppState>,
+    Path(season_id): Path<Uuid>,
+    Query(query): Query<SeasonEpisodesQuery>,
+) -> AppResult<Json<ApiResponse<Vec<EpisodeReference>>>> {
+    let season_id = SeasonID(season_id);
+    let media_refs = &state.unit_of_work().media_refs;
+
+    // 404 for unknown seasons rather than an empty list.
+    media_refs.get_season_reference(&season_id).await?;
+    let episodes = media_refs
+        .get_season_episodes(&season_id, query.sort)
+        .await?;
+
+    Ok(Json(ApiResponse::success(episodes)))
+}
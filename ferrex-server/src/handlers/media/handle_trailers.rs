@@ -0,0 +1,37 @@
+//! Trailers of a movie or series for the player to embed or link to.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use ferrex_core::{
+    api::types::{ApiResponse, Trailer, select_trailers},
+    types::Media,
+};
+
+use crate::{
+    handlers::media::media_id_param::{MOVIE_OR_SERIES, MediaIdParam},
+    infra::{app_state::AppState, errors::AppResult},
+};
+
+/// Trailers and teasers stored with the TMDB metadata of a movie or
+/// series, official ones first and each video once.
+///
+/// # Response
+///
+/// - `200 OK` with the trailers; empty when TMDB lists none or metadata
+///   has not been fetched yet
+/// - `400 Bad Request` for a season or episode id
+/// - `404 Not Found` when no movie or series has this id
+pub async fn get_media_trailers_handler(
+    State(state): State<AppState>,
+    Path(id): Path<MediaIdParam>,
+) -> AppResult<Json<ApiResponse<Vec<Trailer>>>> {
+    let trailers = match id.load(&state, MOVIE_OR_SERIES).await? {
+        Media::Movie(movie) => select_trailers(&movie.details.videos),
+        Media::Series(series) => select_trailers(&series.details.videos),
+        _ => Vec::new(),
+    };
+
+    Ok(Json(ApiResponse::success(trailers)))
+}
@@ -0,0 +1,182 @@
+//! The `{id}` of `/media/{id}/...` routes.
+//!
+//! Clients send typed ids (`movie:<uuid>`, see [`MediaID::to_param`]) so a
+//! route can turn away the wrong kind of media with `400` before touching
+//! the database. A bare UUID from an older client is still accepted; its
+//! kind is then looked up among the kinds the route serves.
+
+use std::{fmt, str::FromStr};
+
+use ferrex_core::{
+    error::MediaError,
+    traits::prelude::MediaOps,
+    types::{Media, MediaID, ParseMediaIdError, VideoMediaType},
+};
+use serde::{Deserialize, Deserializer, de};
+use uuid::Uuid;
+
+use crate::infra::{
+    app_state::AppState,
+    errors::{AppError, AppResult},
+};
+
+/// Routes about a movie or a series as a whole.
+pub const MOVIE_OR_SERIES: &[VideoMediaType] =
+    &[VideoMediaType::Movie, VideoMediaType::Series];
+
+/// Routes about something that can be played and watched.
+pub const PLAYABLE: &[VideoMediaType] =
+    &[VideoMediaType::Movie, VideoMediaType::Episode];
+
+/// Path parameter naming a movie, series, season or episode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaIdParam {
+    Typed(MediaID),
+    /// Bare UUID; the kind is unknown until looked up.
+    Untyped(Uuid),
+}
+
+impl MediaIdParam {
+    pub fn uuid(&self) -> Uuid {
+        match self {
+            MediaIdParam::Typed(id) => *id.as_uuid(),
+            MediaIdParam::Untyped(uuid) => *uuid,
+        }
+    }
+
+    /// Refuse a typed id whose kind is not in `kinds`. Bare UUIDs pass.
+    pub fn accept(
+        self,
+        kinds: &[VideoMediaType],
+    ) -> Result<Self, WrongMediaKind> {
+        match self {
+            MediaIdParam::Typed(id) => {
+                let kind = VideoMediaType::from(id);
+                if kinds.contains(&kind) {
+                    Ok(self)
+                } else {
+                    Err(WrongMediaKind { kind })
+                }
+            }
+            MediaIdParam::Untyped(_) => Ok(self),
+        }
+    }
+
+    /// The id of the media this parameter names, which must exist and be
+    /// one of `kinds`. Bare UUIDs are tried as each kind in order.
+    pub async fn resolve(
+        self,
+        state: &AppState,
+        kinds: &[VideoMediaType],
+    ) -> AppResult<MediaID> {
+        self.load(state, kinds).await.map(|media| media.media_id())
+    }
+
+    /// Like [`Self::resolve`], returning the stored reference itself.
+    pub async fn load(
+        self,
+        state: &AppState,
+        kinds: &[VideoMediaType],
+    ) -> AppResult<Media> {
+        let candidates = match self.accept(kinds)? {
+            MediaIdParam::Typed(id) => vec![id],
+            MediaIdParam::Untyped(uuid) => kinds
+                .iter()
+                .map(|kind| MediaID::from((uuid, *kind)))
+                .collect(),
+        };
+
+        let media_refs = &state.unit_of_work().media_refs;
+        for id in candidates {
+            match media_refs.get_media_reference(&id).await {
+                Ok(media) => return Ok(media),
+                Err(MediaError::NotFound(_)) => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let kinds = kinds
+            .iter()
+            .map(|kind| kind.to_string().to_lowercase())
+            .collect::<Vec<_>>()
+            .join(" or ");
+        Err(AppError::not_found(format!(
+            "No {kinds} with id {}",
+            self.uuid()
+        )))
+    }
+}
+
+impl FromStr for MediaIdParam {
+    type Err = ParseMediaIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match Uuid::parse_str(value) {
+            Ok(uuid) => Ok(MediaIdParam::Untyped(uuid)),
+            Err(_) => value.parse().map(MediaIdParam::Typed),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MediaIdParam {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
+
+/// A typed id of a kind the route does not serve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WrongMediaKind {
+    kind: VideoMediaType,
+}
+
+impl fmt::Display for WrongMediaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ids are not accepted by this endpoint", self.kind)
+    }
+}
+
+impl From<WrongMediaKind> for AppError {
+    fn from(err: WrongMediaKind) -> Self {
+        AppError::bad_request(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrex_core::types::{EpisodeID, MovieID, SeriesID};
+
+    #[test]
+    fn typed_and_bare_ids_parse() {
+        let uuid = Uuid::now_v7();
+        assert_eq!(
+            uuid.to_string().parse::<MediaIdParam>().unwrap(),
+            MediaIdParam::Untyped(uuid)
+        );
+        assert_eq!(
+            format!("episode:{uuid}").parse::<MediaIdParam>().unwrap(),
+            MediaIdParam::Typed(MediaID::Episode(EpisodeID(uuid)))
+        );
+        assert!("media:42".parse::<MediaIdParam>().is_err());
+        assert!("movie:".parse::<MediaIdParam>().is_err());
+    }
+
+    #[test]
+    fn typed_ids_of_other_kinds_are_refused() {
+        let uuid = Uuid::now_v7();
+        let movie = MediaIdParam::Typed(MediaID::Movie(MovieID(uuid)));
+        let series = MediaIdParam::Typed(MediaID::Series(SeriesID(uuid)));
+
+        assert!(movie.accept(PLAYABLE).is_ok());
+        assert_eq!(
+            series.accept(PLAYABLE).unwrap_err().to_string(),
+            "Series ids are not accepted by this endpoint"
+        );
+        assert!(MediaIdParam::Untyped(uuid).accept(PLAYABLE).is_ok());
+    }
+}
@@ -1,6 +1,14 @@
+pub mod handle_credits;
 pub mod handle_image;
 pub mod handle_library;
+pub mod handle_media_deletion;
+pub mod handle_media_images;
+pub mod handle_metadata_refresh;
 pub mod handle_movie_batches;
+pub mod handle_music;
 pub mod handle_search;
+pub mod handle_season;
 pub mod handle_series_bundles;
+pub mod handle_trailers;
 pub mod image_validation;
+pub mod media_id_param;
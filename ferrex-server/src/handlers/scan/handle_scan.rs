@@ -1,5 +1,6 @@
 use axum::response::sse::{Event, KeepAlive};
 use axum::{
+    Extension,
     extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
     response::{IntoResponse, Json, Sse},
@@ -10,20 +11,36 @@ use base64::{
 use ferrex_core::api::ScanQueueDepths;
 use ferrex_core::api::types::{
     ActiveScansResponse, ApiResponse, LatestProgressResponse,
-    ScanCommandAcceptedResponse, ScanCommandRequest, ScanSnapshotDto,
-    StartScanRequest,
+    LibraryReconcileReport, LibraryVerifyReport, LibraryWatchResponse,
+    PathScanAcceptedResponse, PathScanRequest, ReconcileLibraryRequest,
+    ScanCommandAcceptedResponse, ScanCommandRequest, ScanLatencyBreakdown,
+    ScanSnapshotDto, SetLibraryWatchRequest, StartScanRequest,
+    VerifyLibraryRequest,
 };
+use ferrex_core::database::repository_ports::media_files::{
+    MediaFileFilter, MediaFileSort, MediaFileSortField, Page,
+};
+use ferrex_core::domain::scan::preview::{ScanPreview, preview_library_scan};
+use ferrex_core::domain::users::user::User;
 use ferrex_core::error::MediaError;
-use ferrex_core::types::{LibraryId, MediaEvent, ScanProgressEvent};
+use ferrex_core::types::{
+    Library, LibraryId, MediaEvent, ScanProgressEvent,
+    events::MediaSseEventType, library::LibraryType,
+};
 use rkyv::{rancor::Error as RkyvError, to_bytes};
 use serde::{Deserialize, Serialize};
-use std::{convert::Infallible, pin::Pin, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet, convert::Infallible, path::PathBuf, pin::Pin,
+    sync::Arc, time::Duration,
+};
 use tokio_stream::{StreamExt, wrappers::BroadcastStream};
+use tokio_util::sync::CancellationToken;
 use tracing::warn;
 use uuid::Uuid;
 
 use crate::infra::app_state::AppState;
 use crate::infra::demo_mode;
+use crate::infra::scan::media_event_bus::MediaEventReplay;
 use crate::infra::scan::scan_manager::{
     ScanBroadcastFrame, ScanControlError, ScanControlPlane, ScanHistoryEntry,
 };
@@ -32,9 +49,11 @@ use ferrex_core::api::scan::{
     OrchestratorConfigView, QueueConfigView, RetryConfigView, ScanConfig,
     ScanMetrics, WatchConfigView,
 };
+use ferrex_core::domain::scan::orchestration::scan_checkpoint::ScanCheckpoint;
 
 const LAST_EVENT_ID_HEADER: &str = "last-event-id";
 const MEDIA_EVENT_REPLAY_WINDOW: Duration = Duration::from_secs(10 * 60);
+const INDEXED_PATHS_PAGE_SIZE: u32 = 1_000;
 
 #[derive(Debug)]
 pub struct ScanHttpError {
@@ -78,6 +97,18 @@ pub struct ScanHistoryResponse {
     pub count: usize,
 }
 
+#[derive(Debug, Serialize)]
+pub struct InterruptedScansResponse {
+    pub scans: Vec<ScanCheckpoint>,
+    pub count: usize,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ResumeInterruptedScanRequest {
+    #[serde(default)]
+    pub correlation_id: Option<Uuid>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ScanEventsResponse {
     pub scan_id: Uuid,
@@ -112,6 +143,235 @@ pub async fn start_scan_handler(
     ))
 }
 
+/// Rescan a single folder or file of a library.
+///
+/// Records under the path whose files are gone are removed first; paths
+/// outside the library's roots are refused with `403 Forbidden`.
+pub async fn path_scan_handler(
+    State(state): State<AppState>,
+    Path(library_id): Path<Uuid>,
+    Json(request): Json<PathScanRequest>,
+) -> Result<impl IntoResponse, ScanHttpError> {
+    if demo_mode::is_demo_mode(&state)
+        && !demo_mode::is_demo_library(&LibraryId(library_id))
+    {
+        return Err(ScanControlError::LibraryNotFound.into());
+    }
+
+    let accepted = state
+        .scan_control()
+        .start_path_scan(
+            LibraryId(library_id),
+            request.path.into(),
+            request.correlation_id,
+        )
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiResponse::success(PathScanAcceptedResponse {
+            scan_id: accepted.scan_id,
+            correlation_id: accepted.correlation_id,
+            removed_files: accepted.removed_files,
+        })),
+    ))
+}
+
+/// Turn filesystem watching for a library on or off without a restart.
+///
+/// Enabling also starts a reconcile scan unless one is already running.
+pub async fn set_library_watch_handler(
+    State(state): State<AppState>,
+    Path(library_id): Path<Uuid>,
+    Json(request): Json<SetLibraryWatchRequest>,
+) -> Result<Json<ApiResponse<LibraryWatchResponse>>, ScanHttpError> {
+    let library_id = LibraryId(library_id);
+    if demo_mode::is_demo_mode(&state)
+        && !demo_mode::is_demo_library(&library_id)
+    {
+        return Err(ScanControlError::LibraryNotFound.into());
+    }
+
+    let toggled = state
+        .scan_control()
+        .set_library_watch(library_id, request.enabled)
+        .await?;
+
+    Ok(Json(ApiResponse::success(LibraryWatchResponse {
+        library_id,
+        watch_for_changes: request.enabled,
+        reconcile_scan_id: toggled.reconcile_scan_id,
+    })))
+}
+
+/// Verify the media files of a library against their recorded sizes.
+///
+/// Runs inline and returns the report, or answers `202 Accepted` right away
+/// when `background` is set.
+pub async fn verify_library_handler(
+    State(state): State<AppState>,
+    Path(library_id): Path<Uuid>,
+    Json(request): Json<VerifyLibraryRequest>,
+) -> Result<axum::response::Response, ScanHttpError> {
+    let library_id = LibraryId(library_id);
+    if demo_mode::is_demo_mode(&state)
+        && !demo_mode::is_demo_library(&library_id)
+    {
+        return Err(ScanControlError::LibraryNotFound.into());
+    }
+
+    let library = state
+        .unit_of_work()
+        .libraries
+        .get_library(library_id)
+        .await
+        .map_err(|err| ScanControlError::Internal(err.to_string()))?
+        .ok_or(ScanControlError::LibraryNotFound)?;
+
+    let integrity = state.scan_control().integrity();
+    if request.background {
+        integrity.spawn(library, request.sample_bytes).await?;
+        return Ok((
+            StatusCode::ACCEPTED,
+            Json(ApiResponse::success("verification_started".to_string())),
+        )
+            .into_response());
+    }
+
+    let report = integrity.verify(&library, request.sample_bytes).await?;
+    Ok(Json(ApiResponse::success(report)).into_response())
+}
+
+/// Remove the records and cached images of media files deleted from disk
+/// without running a full scan. Nothing below an offline root is touched.
+pub async fn reconcile_library_handler(
+    State(state): State<AppState>,
+    Path(library_id): Path<Uuid>,
+    Json(request): Json<ReconcileLibraryRequest>,
+) -> Result<Json<ApiResponse<LibraryReconcileReport>>, ScanHttpError> {
+    let library_id = LibraryId(library_id);
+    if demo_mode::is_demo_mode(&state)
+        && !demo_mode::is_demo_library(&library_id)
+    {
+        return Err(ScanControlError::LibraryNotFound.into());
+    }
+
+    let library = state
+        .unit_of_work()
+        .libraries
+        .get_library(library_id)
+        .await
+        .map_err(|err| ScanControlError::Internal(err.to_string()))?
+        .ok_or(ScanControlError::LibraryNotFound)?;
+    if library.library_type == LibraryType::Music {
+        return Err(ScanControlError::NotSupportedForMusic.into());
+    }
+
+    let report = state
+        .scan_control()
+        .deletion_reconciler()
+        .reconcile(&library, request.dry_run)
+        .await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+/// Count the files a scan of a library would process, by extension and by
+/// whether they are indexed already, without running the scan. Nothing is
+/// stored or fetched, and the walk stops when the client goes away.
+pub async fn preview_scan_handler(
+    State(state): State<AppState>,
+    Path(library_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ScanPreview>>, ScanHttpError> {
+    let library_id = LibraryId(library_id);
+    if demo_mode::is_demo_mode(&state)
+        && !demo_mode::is_demo_library(&library_id)
+    {
+        return Err(ScanControlError::LibraryNotFound.into());
+    }
+
+    let library = state
+        .unit_of_work()
+        .libraries
+        .get_library(library_id)
+        .await
+        .map_err(|err| ScanControlError::Internal(err.to_string()))?
+        .ok_or(ScanControlError::LibraryNotFound)?;
+
+    let indexed = indexed_paths(&state, &library)
+        .await
+        .map_err(|err| ScanControlError::Internal(err.to_string()))?;
+    // Dropping the handler future, as axum does on disconnect, cancels.
+    let cancel = CancellationToken::new();
+    let _cancel_on_drop = cancel.clone().drop_guard();
+    let preview = preview_library_scan(&library, indexed, cancel)
+        .await
+        .map_err(|err| ScanControlError::Internal(err.to_string()))?;
+    Ok(Json(ApiResponse::success(preview)))
+}
+
+/// Paths of every file `library` has a record for.
+async fn indexed_paths(
+    state: &AppState,
+    library: &Library,
+) -> Result<HashSet<PathBuf>, MediaError> {
+    let unit_of_work = state.unit_of_work();
+    if library.library_type == LibraryType::Music {
+        let tracks = unit_of_work
+            .music_tracks
+            .list_tracks(library.id, &Default::default())
+            .await?;
+        return Ok(tracks
+            .into_iter()
+            .map(|track| PathBuf::from(track.file_path))
+            .collect());
+    }
+
+    let filter = MediaFileFilter {
+        library_id: Some(library.id),
+        ..MediaFileFilter::default()
+    };
+    let sort = MediaFileSort::ascending(MediaFileSortField::DiscoveredAt);
+    let mut paths = HashSet::new();
+    let mut offset = 0u32;
+    loop {
+        let page = unit_of_work
+            .media_files_read
+            .list(
+                filter.clone(),
+                sort,
+                Page {
+                    limit: INDEXED_PATHS_PAGE_SIZE,
+                    offset,
+                },
+            )
+            .await?;
+        let page_len = page.len() as u32;
+        paths.extend(page.into_iter().map(|file| file.path));
+        if page_len < INDEXED_PATHS_PAGE_SIZE {
+            return Ok(paths);
+        }
+        offset = offset.saturating_add(page_len);
+    }
+}
+
+/// Latest verification report for a library.
+pub async fn latest_verify_report_handler(
+    State(state): State<AppState>,
+    Path(library_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<LibraryVerifyReport>>, ScanHttpError> {
+    let report = state
+        .scan_control()
+        .integrity()
+        .latest_report(LibraryId(library_id))
+        .await
+        .ok_or_else(|| ScanHttpError {
+            status: StatusCode::NOT_FOUND,
+            message: "verification_report_not_found".to_string(),
+        })?;
+
+    Ok(Json(ApiResponse::success(report)))
+}
+
 pub async fn pause_scan_handler(
     State(state): State<AppState>,
     Path((_library_id,)): Path<(Uuid,)>,
@@ -160,6 +420,40 @@ pub async fn cancel_scan_handler(
     ))
 }
 
+/// Scans a shutdown left unfinished that were not resumed yet.
+pub async fn interrupted_scans_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<InterruptedScansResponse>>, ScanHttpError> {
+    let scans = state.scan_control().resumable_scans().await?;
+    let count = scans.len();
+    Ok(Json(ApiResponse::success(InterruptedScansResponse {
+        scans,
+        count,
+    })))
+}
+
+/// Resume an interrupted scan as a new scan of its library. Folders that
+/// did not change since the interrupted scan visited them are skipped.
+pub async fn resume_interrupted_scan_handler(
+    State(state): State<AppState>,
+    Path(scan_id): Path<Uuid>,
+    request: Option<Json<ResumeInterruptedScanRequest>>,
+) -> Result<impl IntoResponse, ScanHttpError> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let accepted = state
+        .scan_control()
+        .resume_interrupted_scan(scan_id, request.correlation_id)
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiResponse::success(ScanCommandAcceptedResponse {
+            scan_id: accepted.scan_id,
+            correlation_id: accepted.correlation_id,
+        })),
+    ))
+}
+
 pub async fn active_scans_handler(
     State(state): State<AppState>,
 ) -> Result<Json<ApiResponse<ActiveScansResponse>>, ScanHttpError> {
@@ -188,6 +482,14 @@ pub async fn scan_history_handler(
     })))
 }
 
+pub async fn scan_latency_handler(
+    State(state): State<AppState>,
+    Path(scan_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ScanLatencyBreakdown>>, ScanHttpError> {
+    let breakdown = state.scan_control().latency_breakdown(&scan_id).await?;
+    Ok(Json(ApiResponse::success(breakdown)))
+}
+
 pub async fn latest_progress_handler(
     State(state): State<AppState>,
     Query(query): Query<ProgressQuery>,
@@ -245,10 +547,20 @@ pub async fn scan_metrics_handler(
             status: StatusCode::INTERNAL_SERVER_ERROR,
             message: e.to_string(),
         })?;
+    let metadata = state
+        .scan_control()
+        .orchestrator()
+        .metadata_queue_stats()
+        .await
+        .map_err(|e: MediaError| ScanHttpError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: e.to_string(),
+        })?;
     let active = state.scan_control().active_scans().await.len();
     Ok(Json(ApiResponse::success(ScanMetrics {
         queue_depths: depths,
         active_scans: active,
+        metadata,
     })))
 }
 
@@ -288,6 +600,8 @@ pub async fn scan_config_handler(
         metadata_limits: MetadataLimitsView {
             max_concurrency: cfg.metadata_limits.max_concurrency,
             max_qps: cfg.metadata_limits.max_qps,
+            batch_size: cfg.metadata_limits.batch_size,
+            batch_delay_ms: cfg.metadata_limits.batch_delay_ms,
         },
         bulk_mode: BulkModeView {
             speedup_factor: cfg.bulk_mode.speedup_factor,
@@ -372,6 +686,7 @@ pub async fn build_scan_progress_stream(
 
 pub async fn media_events_sse_handler(
     State(state): State<AppState>,
+    Extension(user): Extension<User>,
     Query(query): Query<MediaEventsQuery>,
     headers: HeaderMap,
 ) -> Sse<impl tokio_stream::Stream<Item = Result<Event, Infallible>>> {
@@ -385,10 +700,20 @@ pub async fn media_events_sse_handler(
     let scan_control = state.scan_control();
     let receiver = scan_control.subscribe_media_events();
 
+    let mut history_last_sequence = resume_from.unwrap_or(0);
+    let mut history_events = Vec::new();
     let history = match resume_from {
-        Some(sequence) => {
-            scan_control.media_event_history_since_sequence(sequence)
-        }
+        Some(sequence) => match scan_control.media_event_replay_since(sequence)
+        {
+            MediaEventReplay::Frames(frames) => frames,
+            MediaEventReplay::Expired { latest_sequence } => {
+                // The gap cannot be filled; have the client reload and carry
+                // on from the newest sequence.
+                history_last_sequence = latest_sequence;
+                history_events.push(media_resync_event(latest_sequence));
+                Vec::new()
+            }
+        },
         None => {
             let now = std::time::Instant::now();
             let cutoff =
@@ -397,16 +722,13 @@ pub async fn media_events_sse_handler(
         }
     };
 
-    let mut history_last_sequence = resume_from.unwrap_or(0);
-    let history_events = history
-        .into_iter()
-        .filter_map(|frame| {
-            history_last_sequence = history_last_sequence.max(frame.sequence);
-            media_frame_to_sse(frame)
-        })
-        .map(Ok::<Event, Infallible>)
-        .collect::<Vec<_>>();
-    let history_stream = tokio_stream::iter(history_events);
+    history_events.extend(history.into_iter().filter_map(|frame| {
+        history_last_sequence = history_last_sequence.max(frame.sequence);
+        media_frame_to_sse(frame)
+    }));
+    let history_stream = tokio_stream::iter(
+        history_events.into_iter().map(Ok::<Event, Infallible>),
+    );
 
     // Stream media events, but ensure primary poster availability for new movies/series
     let stream = async_stream::stream! {
@@ -421,13 +743,20 @@ pub async fn media_events_sse_handler(
                         continue;
                     }
                     last_seen_sequence = frame.sequence;
+                    if !frame.event.visible_to(user.id) {
+                        continue;
+                    }
                     //let event = maybe_prepare_and_refresh(&state, event).await;
                     if let Some(sse) = media_frame_to_sse(frame) {
                         yield Ok::<Event, Infallible>(sse);
                     }
                 }
                 Err(err) => {
+                    // A lagged receiver skipped frames; ask the client to
+                    // reload rather than silently dropping updates.
                     warn!("media event broadcast error: {err}");
+                    last_seen_sequence = scan_control.media_event_latest_sequence();
+                    yield Ok::<Event, Infallible>(media_resync_event(last_seen_sequence));
                 }
             }
         }
@@ -447,6 +776,13 @@ fn scan_frame_to_event(frame: ScanBroadcastFrame) -> Option<Event> {
     })
 }
 
+fn media_resync_event(latest_sequence: u64) -> Event {
+    Event::default()
+        .event(MediaSseEventType::ResyncRequired.event_name())
+        .id(latest_sequence.to_string())
+        .data(latest_sequence.to_string())
+}
+
 fn media_frame_to_sse(
     frame: crate::infra::scan::media_event_bus::MediaEventFrame,
 ) -> Option<Event> {
@@ -0,0 +1,132 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::Response,
+};
+use ferrex_core::types::MediaFile;
+use uuid::Uuid;
+
+use crate::handlers::stream::stream_handlers::{
+    StreamAuthQuery, authorize_playback, ensure_available,
+};
+use crate::handlers::stream::transcode_handlers::with_segment_token;
+use crate::infra::app_state::AppState;
+use crate::infra::hls::{self, HlsError, HlsSegmenter};
+
+const PLAYLIST_CONTENT_TYPE: &str = "application/vnd.apple.mpegurl";
+
+fn hls_error(error: HlsError) -> (StatusCode, String) {
+    let status = match error {
+        HlsError::FfmpegUnavailable => StatusCode::NOT_IMPLEMENTED,
+        HlsError::VariantNotFound | HlsError::SegmentNotFound => {
+            StatusCode::NOT_FOUND
+        }
+        HlsError::DurationUnknown => StatusCode::UNPROCESSABLE_ENTITY,
+        HlsError::Encode(_) | HlsError::Io(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, error.to_string())
+}
+
+pub(crate) async fn load_media_file(
+    state: &AppState,
+    media_id: Uuid,
+) -> Result<MediaFile, (StatusCode, String)> {
+    let media_file = state
+        .unit_of_work()
+        .media_files_read
+        .get_by_id(&media_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Media not found".into()))?;
+
+    ensure_available(state, media_file.library_id, &media_file.path).await?;
+    Ok(media_file)
+}
+
+fn playlist_response(
+    playlist: &str,
+    query: &StreamAuthQuery,
+) -> Result<Response, (StatusCode, String)> {
+    // Relative URIs lose the query string, so carry the token forward.
+    let body = match query.access_token.as_deref() {
+        Some(token) => with_segment_token(playlist, token),
+        None => playlist.to_string(),
+    };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, PLAYLIST_CONTENT_TYPE)
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(Body::from(body))
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Master playlist with one variant per ladder rung at or below the source
+/// resolution.
+pub async fn hls_master_handler(
+    State(state): State<AppState>,
+    Path(media_id): Path<Uuid>,
+    headers: HeaderMap,
+    Query(query): Query<StreamAuthQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    authorize_playback(&state, &headers, &query).await?;
+    state
+        .hls_segmenter()
+        .ensure_ffmpeg()
+        .await
+        .map_err(hls_error)?;
+
+    let media_file = load_media_file(&state, media_id).await?;
+    HlsSegmenter::duration(&media_file).map_err(hls_error)?;
+    let variants = HlsSegmenter::variants(&media_file);
+    playlist_response(&hls::master_playlist(&variants), &query)
+}
+
+/// Media playlist of one variant; segments are listed up front and encoded
+/// when first requested.
+pub async fn hls_variant_handler(
+    State(state): State<AppState>,
+    Path((media_id, variant)): Path<(Uuid, String)>,
+    headers: HeaderMap,
+    Query(query): Query<StreamAuthQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    authorize_playback(&state, &headers, &query).await?;
+    state
+        .hls_segmenter()
+        .ensure_ffmpeg()
+        .await
+        .map_err(hls_error)?;
+
+    let media_file = load_media_file(&state, media_id).await?;
+    HlsSegmenter::variant(&media_file, &variant).map_err(hls_error)?;
+    let duration = HlsSegmenter::duration(&media_file).map_err(hls_error)?;
+    playlist_response(&hls::media_playlist(duration), &query)
+}
+
+/// One MPEG-TS segment, served from the HLS cache or encoded on demand.
+pub async fn hls_segment_handler(
+    State(state): State<AppState>,
+    Path((media_id, variant, segment)): Path<(Uuid, String, String)>,
+    headers: HeaderMap,
+    Query(query): Query<StreamAuthQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    authorize_playback(&state, &headers, &query).await?;
+
+    let media_file = load_media_file(&state, media_id).await?;
+    let variant =
+        HlsSegmenter::variant(&media_file, &variant).map_err(hls_error)?;
+    let bytes = state
+        .hls_segmenter()
+        .segment(&media_file, &variant, &segment)
+        .await
+        .map_err(hls_error)?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "video/mp2t")
+        .header(header::CACHE_CONTROL, "private, max-age=3600")
+        .body(Body::from(bytes))
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
@@ -1,2 +1,5 @@
 pub mod handle_sync;
+pub mod hls_handlers;
 pub mod stream_handlers;
+pub mod subtitle_handlers;
+pub mod transcode_handlers;
@@ -1,40 +1,107 @@
 use axum::{
     Extension, Json,
     extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode, header},
-    response::Response,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
 };
 use chrono::Utc;
-use ferrex_core::api::types::ApiResponse;
+use ferrex_core::api::types::{
+    ApiResponse, MediaAvailability, PlaybackTicketResponse,
+};
+use ferrex_core::application::media_availability::MediaAvailabilityService;
 use ferrex_core::domain::users::auth::domain::value_objects::SessionScope;
+use ferrex_core::domain::users::rbac;
 use ferrex_core::domain::{users::user::User, watch::UpdateProgressRequest};
-use ferrex_model::VideoMediaType;
+use ferrex_core::error::MediaError;
+use ferrex_model::{LibraryId, MediaID, MediaLocation, VideoMediaType};
 use serde::Deserialize;
-use serde::Serialize;
+use std::path::PathBuf;
 use tokio_util::io::ReaderStream;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::handlers::users::watch_status_handlers::progress_update_error;
 use crate::infra::app_state::AppState;
+use crate::infra::ranges;
+use crate::infra::remote_media;
+use crate::infra::stream_compat::{
+    self, CAPABILITIES_HEADER, ClientCapabilities, SourceFormat,
+    TRANSCODE_HEADER, TranscodePlan,
+};
+use crate::infra::stream_pacing::paced_body;
+use crate::infra::stream_seek::{self, SEEK_HEADER, SeekPlan};
 
 #[derive(Debug, Deserialize)]
 pub struct ProgressReport {
     pub position: f32,
     pub duration: f32,
+    #[serde(default)]
+    pub reported_at: Option<i64>,
+    #[serde(default)]
+    pub reset: bool,
 }
 
-/// Stream media with automatic progress tracking.
 #[derive(Debug, Deserialize)]
 pub struct StreamAuthQuery {
     #[serde(default)]
-    access_token: Option<String>,
+    pub access_token: Option<String>,
+}
+
+/// Query accepted by the direct-play stream endpoint.
+#[derive(Debug, Deserialize)]
+pub struct StreamQuery {
+    #[serde(default)]
+    pub access_token: Option<String>,
+    /// Start playback at this many seconds; see [`stream_seek`].
+    #[serde(default)]
+    pub t: Option<f64>,
+    /// Play this edition (`Extended`, `directors-cut`) of the requested
+    /// file's movie instead of the file itself.
+    #[serde(default)]
+    pub edition: Option<String>,
+    /// Capability hint for players that cannot send
+    /// `X-Client-Capabilities`; see [`stream_compat`].
+    #[serde(default)]
+    pub caps: Option<String>,
+    /// Bandwidth cap of this stream in kilobits per second, replacing the
+    /// configured one; `0` lifts it. Admins only; see
+    /// [`crate::infra::stream_pacing`].
+    #[serde(default)]
+    pub max_kbps: Option<u64>,
+}
+
+/// Validate a playback token from the `Authorization` header or the
+/// `access_token` query parameter, for endpoints fetched directly by media
+/// players that cannot attach headers.
+pub(crate) async fn authorize_playback(
+    state: &AppState,
+    headers: &HeaderMap,
+    query: &StreamAuthQuery,
+) -> Result<(), (StatusCode, String)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|s| s.to_string())
+        .or_else(|| query.access_token.clone())
+        .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Missing token".into()))?;
+
+    match state.auth_service().validate_session_token(&token).await {
+        Ok(validated) => match validated.scope {
+            SessionScope::Full | SessionScope::Playback => Ok(()),
+        },
+        Err(err) => {
+            warn!("Playback token validation failed: {:?}", err);
+            Err((StatusCode::UNAUTHORIZED, "Invalid token".into()))
+        }
+    }
 }
 
 pub async fn stream_with_progress_handler(
     State(state): State<AppState>,
     Path(media_id): Path<Uuid>,
     headers: HeaderMap,
-    Query(query): Query<StreamAuthQuery>,
+    Query(query): Query<StreamQuery>,
 ) -> Result<Response, (StatusCode, String)> {
     debug!("stream request");
     debug!("Requested media ID: {}", media_id);
@@ -48,81 +115,174 @@ pub async fn stream_with_progress_handler(
         .map(|s| s.to_string())
         .or_else(|| query.access_token.clone());
 
-    if let Some(token) = token_opt {
-        // Validate token; reject unauthorized/expired sessions and enforce scope
+    let Some(token) = token_opt else {
+        return Err((StatusCode::UNAUTHORIZED, "Missing token".into()));
+    };
+    // Validate token; reject unauthorized/expired sessions and enforce scope
+    let user_id =
         match state.auth_service().validate_session_token(&token).await {
             Ok(validated) => match validated.scope {
-                SessionScope::Full | SessionScope::Playback => {}
+                SessionScope::Full | SessionScope::Playback => {
+                    validated.user_id
+                }
             },
             Err(err) => {
                 warn!("Stream token validation failed: {:?}", err);
                 return Err((StatusCode::UNAUTHORIZED, "Invalid token".into()));
             }
-        }
-    } else {
-        return Err((StatusCode::UNAUTHORIZED, "Missing token".into()));
-    }
+        };
+    let bandwidth = stream_bandwidth(&state, user_id, query.max_kbps).await?;
+
+    // Range requests and whole-file streams both take a slot, held by the
+    // response body until the transfer ends or the client goes away.
+    let Some(permit) = state.streams().try_acquire() else {
+        warn!("Refusing stream of {}: stream limit reached", media_id);
+        return Ok(state.streams().busy_response());
+    };
+    let response =
+        serve_stream(&state, media_id, &headers, &query, bandwidth, false)
+            .await?;
+    Ok(permit.hold_for(response))
+}
 
-    // Fetch media metadata
-    let media_file = state
+/// Bandwidth cap of a stream for `user_id`: the configured default, or
+/// `requested` when the user may change server settings.
+async fn stream_bandwidth(
+    state: &AppState,
+    user_id: Uuid,
+    requested: Option<u64>,
+) -> Result<Option<u64>, (StatusCode, String)> {
+    let configured = state.config().server.stream_bandwidth_limit_kbps;
+    let Some(requested) = requested else {
+        return Ok(configured);
+    };
+    let permissions = state
         .unit_of_work()
-        .media_files_read
-        .get_by_id(&media_id)
+        .rbac
+        .get_user_permissions(user_id)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Database error retrieving media {}: {}", media_id, e),
-            )
-        })?
-        .ok_or_else(|| {
-            (StatusCode::NOT_FOUND, "Media not found".to_string())
-        })?;
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !permissions.has_permission(rbac::permissions::SERVER_UPDATE_SETTINGS) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only administrators can override the stream bandwidth cap"
+                .to_string(),
+        ));
+    }
+    Ok(Some(requested).filter(|kbps| *kbps > 0))
+}
+
+/// `HEAD` for the stream route: the status and headers a `GET` would answer
+/// with (`Content-Length`, `Content-Type`, `Accept-Ranges`, range and
+/// availability errors) without a body. No stream slot is held and ffmpeg
+/// is not started, but a full limiter still answers `503`.
+pub async fn stream_head_handler(
+    State(state): State<AppState>,
+    Path(media_id): Path<Uuid>,
+    headers: HeaderMap,
+    Query(query): Query<StreamQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let auth = StreamAuthQuery {
+        access_token: query.access_token.clone(),
+    };
+    authorize_playback(&state, &headers, &auth).await?;
+
+    if state.streams().try_acquire().is_none() {
+        return Ok(without_body(state.streams().busy_response()));
+    }
+    let response =
+        serve_stream(&state, media_id, &headers, &query, None, true).await?;
+    Ok(without_body(response))
+}
+
+/// Drop the body of a response answering a `HEAD`, keeping its headers.
+pub(crate) fn without_body(response: Response) -> Response {
+    response.map(|_| axum::body::Body::empty())
+}
+
+/// Serve `media_id` paced to `bandwidth` kbps, or with `head_only` build
+/// the same response without starting ffmpeg or holding a transcode slot.
+async fn serve_stream(
+    state: &AppState,
+    media_id: Uuid,
+    headers: &HeaderMap,
+    query: &StreamQuery,
+    bandwidth: Option<u64>,
+    head_only: bool,
+) -> Result<Response, (StatusCode, String)> {
+    let media_id = match query.edition.as_deref() {
+        Some(selector) => {
+            resolve_edition_file(state, media_id, selector).await?
+        }
+        None => media_id,
+    };
+
+    let Some(media_file) = load_stream_source(state, media_id).await? else {
+        return Ok(unavailable_response(&MediaAvailability::NotFound));
+    };
 
     debug!(
         "Found media file: {:?} (path: {:?})",
         media_file.filename, media_file.path
     );
 
-    if !media_file.path.exists() {
-        warn!("Media file not found on disk: {:?}", media_file.path);
+    // Remote files are not probed first: a relayed upstream that fails
+    // already answers 502, and a redirected player gets the upstream's own
+    // answer.
+    if let MediaLocation::Remote(url) = MediaLocation::of(&media_file.path) {
+        let mut response = remote_media::serve(
+            url,
+            headers,
+            &state.config().server.remote_media_redirect_hosts,
+            bandwidth,
+            head_only,
+        )
+        .await?;
+        if query.t.is_some_and(|t| t != 0.0) {
+            response.headers_mut().insert(
+                SEEK_HEADER,
+                HeaderValue::from_static(SeekPlan::Unsupported.header_value()),
+            );
+        }
+        return Ok(response);
+    }
+
+    let availability =
+        file_availability(state, media_file.library_id, &media_file.path)
+            .await?;
+    if !availability.is_available() {
+        warn!("Media {} cannot be streamed: {}", media_id, availability);
+        return Ok(unavailable_response(&availability));
+    }
 
-        if let Some(media_root) = state.config().media.root.as_ref()
-            && !media_root.exists()
+    if let Some(plan) = transcode_plan(headers, query, &media_file) {
+        let Some(permit) = state.transcodes().try_acquire() else {
+            warn!(
+                "Refusing transcode of {}: transcode limit reached",
+                media_id
+            );
+            return Ok(state.transcodes().busy_response());
+        };
+        if let Some(response) = spawn_transcode(
+            state,
+            &media_file.path,
+            query.t,
+            plan,
+            bandwidth,
+            head_only,
+        )
+        .await
         {
-            warn!("Media library root is offline: {:?}", media_root);
-            return Ok(Response::builder()
-                .status(StatusCode::SERVICE_UNAVAILABLE)
-                .header("X-Media-Error", "library-offline")
-                .body(axum::body::Body::empty())
-                .expect("failed to build SERVICE_UNAVAILABLE response"));
+            return Ok(permit.hold_for(response));
         }
-
-        return Ok(Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .header("X-Media-Error", "file-missing")
-            .body(axum::body::Body::empty())
-            .expect("failed to build NOT_FOUND response"));
     }
 
     let file_size = media_file.size;
     let extension = media_file.path.extension().and_then(|ext| ext.to_str());
-    let content_type = match extension {
-        Some("mp4") => "video/mp4",
-        Some("mkv") => "video/x-matroska",
-        Some("avi") => "video/x-msvideo",
-        Some("mov") => "video/quicktime",
-        Some("webm") => "video/webm",
-        Some("flv") => "video/x-flv",
-        Some("wmv") => "video/x-ms-wmv",
-        Some("m4v") => "video/x-m4v",
-        Some("mpg") | Some("mpeg") => "video/mpeg",
-        Some("3gp") => "video/3gpp",
-        Some("ogv") => "video/ogg",
-        Some("ts") => "video/mp2t",
-        Some("mts") | Some("m2ts") => "video/mp2t",
-        _ => "application/octet-stream",
-    };
+    let content_type = state
+        .stream_content_types()
+        .resolve(media_id, &media_file.path, file_size)
+        .await;
     debug!("Content-Type: {}", content_type);
 
     let file = tokio::fs::File::open(&media_file.path).await.map_err(|e| {
@@ -133,15 +293,78 @@ pub async fn stream_with_progress_handler(
         )
     })?;
 
-    if let Some(range_header) = headers.get(header::RANGE)
-        && let Ok(range_str) = range_header.to_str()
-        && let Some(range) = parse_range_header(range_str, file_size)
-    {
+    // A timestamp seek applies unless the client asked for a specific
+    // byte range; `bytes=0-` is what most players send by default and is
+    // treated as no range at all.
+    let range_header = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+    let explicit_range =
+        range_header.is_some_and(|range| range.trim() != "bytes=0-");
+    let seek = query.t.filter(|t| *t != 0.0).map(|t| {
+        if explicit_range {
+            return SeekPlan::Unsupported;
+        }
+        stream_seek::plan_seek(extension, file_size, media_file.duration, t)
+    });
+
+    match seek {
+        Some(SeekPlan::ByteOffset(offset)) => {
+            return serve_from_offset(
+                file,
+                offset,
+                file_size,
+                content_type,
+                media_id,
+                bandwidth,
+            )
+            .await;
+        }
+        Some(SeekPlan::Remux { start, format }) => {
+            match spawn_remux(
+                state,
+                &media_file.path,
+                start,
+                format,
+                bandwidth,
+                head_only,
+            )
+            .await
+            {
+                Some(response) => return Ok(response),
+                None => {
+                    return Ok(whole_file_response(
+                        file,
+                        file_size,
+                        content_type,
+                        Some(SeekPlan::Unsupported.header_value()),
+                        bandwidth,
+                    ));
+                }
+            }
+        }
+        _ => {}
+    }
+    let seek_note = seek.as_ref().map(SeekPlan::header_value);
+
+    let served =
+        ranges::parse_ranges(range_header, file_size).response(file_size);
+    if served.is_unsatisfiable() {
+        debug!(
+            "Unsatisfiable range {:?} for media {} ({} bytes)",
+            range_header, media_id, file_size
+        );
+        return Ok(served
+            .apply(Response::builder())
+            .body(axum::body::Body::empty())
+            .expect("failed to build RANGE_NOT_SATISFIABLE response"));
+    }
+    if served.status == StatusCode::PARTIAL_CONTENT {
         use tokio::io::{AsyncReadExt, AsyncSeekExt};
 
-        debug!("Range request: {}-{}/{}", range.start, range.end, file_size);
         let mut file = file;
-        if let Err(e) = file.seek(std::io::SeekFrom::Start(range.start)).await {
+        if let Err(e) = file.seek(std::io::SeekFrom::Start(served.offset)).await
+        {
             warn!("Failed to seek in file: {}", e);
             return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -149,27 +372,24 @@ pub async fn stream_with_progress_handler(
             ));
         }
 
-        let content_length = range.end - range.start + 1;
         info!(
-            "Serving range {}-{} ({} bytes) for media {}",
-            range.start, range.end, content_length, media_id
+            "Serving {} bytes from offset {} for media {}",
+            served.length, served.offset, media_id
         );
 
-        let limited_file = file.take(content_length);
-        let stream = ReaderStream::new(limited_file);
+        let limited_file = file.take(served.length);
+        let body = paced_body(ReaderStream::new(limited_file), bandwidth);
 
-        return Ok(Response::builder()
-            .status(StatusCode::PARTIAL_CONTENT)
+        let mut builder = served
+            .apply(Response::builder())
             .header(header::CONTENT_TYPE, content_type)
-            .header(header::CONTENT_LENGTH, content_length.to_string())
-            .header(
-                header::CONTENT_RANGE,
-                format!("bytes {}-{}/{}", range.start, range.end, file_size),
-            )
-            .header(header::ACCEPT_RANGES, "bytes")
             .header("Cache-Control", "private, no-store")
-            .header("Connection", "keep-alive")
-            .body(axum::body::Body::from_stream(stream))
+            .header("Connection", "keep-alive");
+        if let Some(note) = seek_note {
+            builder = builder.header(SEEK_HEADER, note);
+        }
+        return Ok(builder
+            .body(body)
             .expect("failed to build PARTIAL_CONTENT response"));
     }
 
@@ -178,22 +398,380 @@ pub async fn stream_with_progress_handler(
         media_file.filename, file_size
     );
 
-    let stream = ReaderStream::new(file);
-    Ok(Response::builder()
+    Ok(whole_file_response(
+        file,
+        file_size,
+        content_type,
+        seek_note,
+        bandwidth,
+    ))
+}
+
+/// The file behind a stream id: a media file, or a track of a music
+/// library.
+struct StreamSource {
+    library_id: LibraryId,
+    path: PathBuf,
+    filename: String,
+    size: u64,
+    duration: Option<f64>,
+    /// Probed codecs of a video file; `None` for music, which is always
+    /// direct-played.
+    format: Option<SourceFormat>,
+}
+
+async fn load_stream_source(
+    state: &AppState,
+    id: Uuid,
+) -> Result<Option<StreamSource>, (StatusCode, String)> {
+    let db_error = |e: MediaError| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Database error retrieving media {}: {}", id, e),
+        )
+    };
+    let uow = state.unit_of_work();
+    if let Some(file) = uow
+        .media_files_read
+        .get_by_id(&id)
+        .await
+        .map_err(db_error)?
+    {
+        let meta = file.media_file_metadata.as_ref();
+        return Ok(Some(StreamSource {
+            duration: meta.and_then(|meta| meta.duration),
+            format: Some(SourceFormat::new(
+                &file.path,
+                meta.and_then(|meta| meta.video_codec.as_deref()),
+                meta.and_then(|meta| meta.audio_codec.as_deref()),
+                meta.and_then(|meta| meta.bit_depth),
+            )),
+            library_id: file.library_id,
+            path: file.path,
+            filename: file.filename,
+            size: file.size,
+        }));
+    }
+    let track = uow.music_tracks.get_track(id).await.map_err(db_error)?;
+    Ok(track.map(|track| StreamSource {
+        library_id: track.library_id,
+        path: PathBuf::from(track.file_path),
+        filename: track.filename,
+        size: track.file_size,
+        duration: None,
+        format: None,
+    }))
+}
+
+async fn file_availability(
+    state: &AppState,
+    library_id: LibraryId,
+    path: &std::path::Path,
+) -> Result<MediaAvailability, (StatusCode, String)> {
+    MediaAvailabilityService::new(state.unit_of_work())
+        .file_availability(library_id, path)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// `404` for unknown media or a deleted file, `503` while the library the
+/// file lives on is offline, `502` when a remote file's upstream fails.
+fn availability_status(availability: &MediaAvailability) -> StatusCode {
+    match availability {
+        MediaAvailability::Available { .. } => StatusCode::OK,
+        MediaAvailability::LibraryOffline { .. } => {
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+        MediaAvailability::RemoteUnreachable { .. } => StatusCode::BAD_GATEWAY,
+        MediaAvailability::NotFound | MediaAvailability::FileMissing { .. } => {
+            StatusCode::NOT_FOUND
+        }
+    }
+}
+
+/// Fail with the availability as error message unless the indexed file at
+/// `path` can be served.
+pub(crate) async fn ensure_available(
+    state: &AppState,
+    library_id: LibraryId,
+    path: &std::path::Path,
+) -> Result<(), (StatusCode, String)> {
+    let availability = file_availability(state, library_id, path).await?;
+    if availability.is_available() {
+        Ok(())
+    } else {
+        Err((availability_status(&availability), availability.to_string()))
+    }
+}
+
+/// The availability as JSON, with the reason repeated in `X-Media-Error`
+/// for players that only look at headers.
+fn unavailable_response(availability: &MediaAvailability) -> Response {
+    let reason = match availability {
+        MediaAvailability::LibraryOffline { .. } => "library-offline",
+        MediaAvailability::FileMissing { .. } => "file-missing",
+        MediaAvailability::RemoteUnreachable { .. } => "remote-unreachable",
+        MediaAvailability::NotFound | MediaAvailability::Available { .. } => {
+            "not-found"
+        }
+    };
+    let mut response =
+        (availability_status(availability), Json(availability)).into_response();
+    response
+        .headers_mut()
+        .insert("X-Media-Error", HeaderValue::from_static(reason));
+    response
+}
+
+/// Id of the `selector` edition of the movie that `file_id` belongs to.
+async fn resolve_edition_file(
+    state: &AppState,
+    file_id: Uuid,
+    selector: &str,
+) -> Result<Uuid, (StatusCode, String)> {
+    let unit_of_work = state.unit_of_work();
+    let file = unit_of_work
+        .media_files_read
+        .get_by_id(&file_id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error retrieving media {}: {}", file_id, e),
+            )
+        })?
+        .ok_or_else(|| {
+            (StatusCode::NOT_FOUND, "Media not found".to_string())
+        })?;
+
+    let MediaID::Movie(movie_id) = file.media_id else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Editions are only available for movies".to_string(),
+        ));
+    };
+    let movie = unit_of_work
+        .media_refs
+        .get_movie_reference(&movie_id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error retrieving movie {}: {}", movie_id, e),
+            )
+        })?;
+
+    movie
+        .edition(selector)
+        .map(|edition| edition.file_id)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("Movie {} has no '{}' edition", movie_id, selector),
+            )
+        })
+}
+
+fn whole_file_response(
+    file: tokio::fs::File,
+    file_size: u64,
+    content_type: &str,
+    seek_note: Option<&'static str>,
+    bandwidth: Option<u64>,
+) -> Response {
+    let mut builder = Response::builder()
         .status(StatusCode::OK)
         .header(header::CONTENT_TYPE, content_type)
         .header(header::CONTENT_LENGTH, file_size.to_string())
         .header(header::ACCEPT_RANGES, "bytes")
         .header("Cache-Control", "private, no-store")
+        .header("Connection", "keep-alive");
+    if let Some(note) = seek_note {
+        builder = builder.header(SEEK_HEADER, note);
+    }
+    builder
+        .body(paced_body(ReaderStream::new(file), bandwidth))
+        .expect("failed to build OK response")
+}
+
+/// Serve a resynchronizing stream (MPEG-TS/PS) from an estimated offset.
+async fn serve_from_offset(
+    file: tokio::fs::File,
+    offset: u64,
+    file_size: u64,
+    content_type: &str,
+    media_id: Uuid,
+    bandwidth: Option<u64>,
+) -> Result<Response, (StatusCode, String)> {
+    use tokio::io::AsyncSeekExt;
+
+    let mut file = file;
+    if let Err(e) = file.seek(std::io::SeekFrom::Start(offset)).await {
+        warn!("Failed to seek in file: {}", e);
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to seek in media file".to_string(),
+        ));
+    }
+
+    let content_length = file_size - offset;
+    info!(
+        "Serving media {} from estimated offset {} ({} bytes)",
+        media_id, offset, content_length
+    );
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CONTENT_LENGTH, content_length.to_string())
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header("Cache-Control", "private, no-store")
         .header("Connection", "keep-alive")
-        .body(axum::body::Body::from_stream(stream))
+        .header(SEEK_HEADER, SeekPlan::ByteOffset(offset).header_value())
+        .body(paced_body(ReaderStream::new(file), bandwidth))
         .expect("failed to build OK response"))
 }
 
-#[derive(Debug, Serialize)]
-pub struct PlaybackTicketResponse {
-    pub access_token: String,
-    pub expires_in: i64,
+/// Stream-copy the file from the keyframe at or before `start` through
+/// ffmpeg. Returns `None` when ffmpeg is unavailable so the caller can fall
+/// back to the whole file; with `head_only` ffmpeg is not started.
+async fn spawn_remux(
+    state: &AppState,
+    path: &std::path::Path,
+    start: f64,
+    format: stream_seek::RemuxFormat,
+    bandwidth: Option<u64>,
+    head_only: bool,
+) -> Option<Response> {
+    use futures::StreamExt;
+    use std::process::Stdio;
+
+    if state.hls_segmenter().ensure_ffmpeg().await.is_err() {
+        warn!("ffmpeg unavailable; ignoring seek to {:.1}s", start);
+        return None;
+    }
+    if head_only {
+        return Some(remux_response(start, format, axum::body::Body::empty()));
+    }
+
+    let mut child =
+        tokio::process::Command::new(&state.config().ffmpeg.ffmpeg_path)
+            .args(stream_seek::remux_args(path, start, format))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| warn!("Failed to start ffmpeg for seek: {}", err))
+            .ok()?;
+    let stdout = child.stdout.take()?;
+
+    info!("Remuxing {:?} from {:.1}s for seek", path, start);
+
+    // The child is moved into the stream so ffmpeg is killed as soon as the
+    // client goes away.
+    let stream = ReaderStream::new(stdout).map(move |chunk| {
+        let _ = &child;
+        chunk
+    });
+
+    Some(remux_response(start, format, paced_body(stream, bandwidth)))
+}
+
+fn remux_response(
+    start: f64,
+    format: stream_seek::RemuxFormat,
+    body: axum::body::Body,
+) -> Response {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(header::ACCEPT_RANGES, "none")
+        .header("Cache-Control", "private, no-store")
+        .header(
+            SEEK_HEADER,
+            SeekPlan::Remux { start, format }.header_value(),
+        )
+        .body(body)
+        .expect("failed to build OK response")
+}
+
+/// How to convert `source` for the requesting player, from its
+/// `X-Client-Capabilities` header or `caps` query; `None` for direct play.
+fn transcode_plan(
+    headers: &HeaderMap,
+    query: &StreamQuery,
+    source: &StreamSource,
+) -> Option<TranscodePlan> {
+    let format = source.format.as_ref()?;
+    let hint = headers
+        .get(CAPABILITIES_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .or(query.caps.as_deref())?;
+    let caps = ClientCapabilities::parse(hint)?;
+    stream_compat::plan_transcode(&caps, format)
+}
+
+/// Convert the file for a player that cannot play it as stored, starting
+/// at `t` seconds; ranges are not honored on a live transcode. Returns
+/// `None` when ffmpeg is unavailable so the caller can fall back to direct
+/// play; with `head_only` ffmpeg is not started.
+async fn spawn_transcode(
+    state: &AppState,
+    path: &std::path::Path,
+    t: Option<f64>,
+    plan: TranscodePlan,
+    bandwidth: Option<u64>,
+    head_only: bool,
+) -> Option<Response> {
+    use futures::StreamExt;
+    use std::process::Stdio;
+
+    if state.hls_segmenter().ensure_ffmpeg().await.is_err() {
+        warn!("ffmpeg unavailable; direct-playing {:?} instead", path);
+        return None;
+    }
+    if head_only {
+        return Some(transcode_response(plan, axum::body::Body::empty()));
+    }
+
+    let mut child =
+        tokio::process::Command::new(&state.config().ffmpeg.ffmpeg_path)
+            .args(plan.ffmpeg_args(path, t))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| {
+                warn!("Failed to start ffmpeg for transcode: {}", err)
+            })
+            .ok()?;
+    let stdout = child.stdout.take()?;
+
+    info!(
+        "Transcoding {:?} ({}) for an incompatible client",
+        path,
+        plan.header_value()
+    );
+
+    let stream = ReaderStream::new(stdout).map(move |chunk| {
+        let _ = &child;
+        chunk
+    });
+
+    Some(transcode_response(plan, paced_body(stream, bandwidth)))
+}
+
+fn transcode_response(plan: TranscodePlan, body: axum::body::Body) -> Response {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, plan.output.content_type())
+        .header(header::ACCEPT_RANGES, "none")
+        .header("Cache-Control", "private, no-store")
+        .header(TRANSCODE_HEADER, plan.header_value())
+        .body(body)
+        .expect("failed to build OK response")
 }
 
 /// Issue a short-lived playback token suitable for query-string embedding.
@@ -205,14 +783,7 @@ pub async fn playback_ticket_handler(
 ) -> Result<axum::Json<ApiResponse<PlaybackTicketResponse>>, (StatusCode, String)>
 {
     // Optionally ensure the requested media exists to avoid issuing tokens for unknown items
-    if state
-        .unit_of_work()
-        .media_files_read
-        .get_by_id(&media_id)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .is_none()
-    {
+    if load_stream_source(&state, media_id).await?.is_none() {
         return Err((StatusCode::NOT_FOUND, "Media not found".into()));
     }
     // Lifetime: 6 hours — long enough for extended playback/seeks
@@ -247,6 +818,8 @@ pub async fn report_progress_handler(
         duration: progress.duration,
         episode: None,
         last_media_uuid: Some(media_id),
+        reported_at: progress.reported_at,
+        reset: progress.reset,
     };
 
     // Update progress
@@ -255,56 +828,7 @@ pub async fn report_progress_handler(
         .watch_status
         .update_watch_progress(user.id, &request)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to update progress: {}", e),
-            )
-        })?;
+        .map_err(|e| progress_update_error("Failed to update progress", e))?;
 
     Ok(StatusCode::NO_CONTENT)
 }
-
-#[derive(Debug)]
-struct ByteRange {
-    start: u64,
-    end: u64,
-}
-
-fn parse_range_header(range_str: &str, file_size: u64) -> Option<ByteRange> {
-    if !range_str.starts_with("bytes=") {
-        return None;
-    }
-
-    let range_part = &range_str[6..];
-    let parts: Vec<&str> = range_part.split('-').collect();
-    if parts.len() != 2 {
-        return None;
-    }
-
-    let start = if parts[0].is_empty() {
-        if let Ok(suffix_len) = parts[1].parse::<u64>() {
-            file_size.saturating_sub(suffix_len)
-        } else {
-            return None;
-        }
-    } else if let Ok(start) = parts[0].parse::<u64>() {
-        start
-    } else {
-        return None;
-    };
-
-    let end = if parts[1].is_empty() {
-        file_size.saturating_sub(1)
-    } else if let Ok(end) = parts[1].parse::<u64>() {
-        std::cmp::min(end, file_size.saturating_sub(1))
-    } else {
-        return None;
-    };
-
-    if start <= end && start < file_size {
-        Some(ByteRange { start, end })
-    } else {
-        None
-    }
-}
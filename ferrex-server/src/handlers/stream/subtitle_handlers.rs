@@ -0,0 +1,74 @@
+use axum::{
+    Json,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::Response,
+};
+use ferrex_core::api::types::{ApiResponse, EmbeddedSubtitleTrack};
+use uuid::Uuid;
+
+use crate::handlers::stream::hls_handlers::load_media_file;
+use crate::handlers::stream::stream_handlers::{
+    StreamAuthQuery, authorize_playback,
+};
+use crate::infra::app_state::AppState;
+use crate::infra::subtitles::SubtitleError;
+
+fn subtitle_error(error: SubtitleError) -> (StatusCode, String) {
+    let status = match error {
+        SubtitleError::FfmpegUnavailable => StatusCode::NOT_IMPLEMENTED,
+        SubtitleError::TrackNotFound(_) => StatusCode::NOT_FOUND,
+        SubtitleError::NotTextConvertible { .. } => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+        SubtitleError::Probe(_)
+        | SubtitleError::Extract(_)
+        | SubtitleError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, error.to_string())
+}
+
+/// Subtitle tracks muxed into the media file, with language and forced
+/// flags and whether each can be served as WebVTT.
+pub async fn list_embedded_subtitles_handler(
+    State(state): State<AppState>,
+    Path(media_id): Path<Uuid>,
+    headers: HeaderMap,
+    Query(query): Query<StreamAuthQuery>,
+) -> Result<Json<ApiResponse<Vec<EmbeddedSubtitleTrack>>>, (StatusCode, String)>
+{
+    authorize_playback(&state, &headers, &query).await?;
+
+    let media_file = load_media_file(&state, media_id).await?;
+    let tracks = state
+        .subtitle_extractor()
+        .tracks(&media_file.path)
+        .await
+        .map_err(subtitle_error)?;
+    Ok(Json(ApiResponse::success(tracks)))
+}
+
+/// One embedded subtitle track as WebVTT, converted on first request.
+pub async fn embedded_subtitle_handler(
+    State(state): State<AppState>,
+    Path((media_id, index)): Path<(Uuid, u32)>,
+    headers: HeaderMap,
+    Query(query): Query<StreamAuthQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    authorize_playback(&state, &headers, &query).await?;
+
+    let media_file = load_media_file(&state, media_id).await?;
+    let vtt = state
+        .subtitle_extractor()
+        .webvtt(media_id, &media_file.path, index)
+        .await
+        .map_err(subtitle_error)?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/vtt; charset=utf-8")
+        .header(header::CACHE_CONTROL, "private, max-age=3600")
+        .body(Body::from(vtt))
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
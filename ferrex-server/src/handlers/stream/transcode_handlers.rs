@@ -0,0 +1,145 @@
+use axum::{
+    Json,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::Response,
+};
+use ferrex_core::api::types::{ApiResponse, StartTranscodeRequest};
+use ferrex_core::types::TranscodingJobResponse;
+
+use crate::handlers::stream::hls_handlers::load_media_file;
+use crate::handlers::stream::stream_handlers::{
+    StreamAuthQuery, authorize_playback,
+};
+use crate::infra::app_state::AppState;
+use crate::infra::transcode::{PLAYLIST_FILE, TranscodeError};
+
+fn transcode_error(error: TranscodeError) -> (StatusCode, String) {
+    let status = match error {
+        TranscodeError::JobNotFound => StatusCode::NOT_FOUND,
+        TranscodeError::InvalidFileName => StatusCode::BAD_REQUEST,
+        // ffmpeg missing or not executable
+        TranscodeError::Spawn(_) => StatusCode::NOT_IMPLEMENTED,
+        TranscodeError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, error.to_string())
+}
+
+/// Start (or join) a transcode job for a media file.
+pub async fn start_transcode_handler(
+    State(state): State<AppState>,
+    Json(request): Json<StartTranscodeRequest>,
+) -> Result<Json<ApiResponse<TranscodingJobResponse>>, (StatusCode, String)> {
+    let media_file = load_media_file(&state, request.media_id).await?;
+
+    let job = state
+        .transcode_manager()
+        .start(&media_file, request.profile)
+        .await
+        .map_err(transcode_error)?;
+    Ok(Json(ApiResponse::success(job)))
+}
+
+/// Poll a transcode job.
+pub async fn transcode_status_handler(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<Json<ApiResponse<TranscodingJobResponse>>, (StatusCode, String)> {
+    let job = state
+        .transcode_manager()
+        .status(&job_id)
+        .await
+        .map_err(transcode_error)?;
+    Ok(Json(ApiResponse::success(job)))
+}
+
+/// Serve the playlist or a segment of a transcode job.
+///
+/// Like `/stream/{id}`, this accepts the token as `access_token` query
+/// parameter. Segment URIs in the playlist are rewritten to carry the same
+/// token because HLS clients drop the query when resolving relative URIs.
+pub async fn transcode_file_handler(
+    State(state): State<AppState>,
+    Path((job_id, file)): Path<(String, String)>,
+    headers: HeaderMap,
+    Query(query): Query<StreamAuthQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    authorize_playback(&state, &headers, &query).await?;
+
+    let path = state
+        .transcode_manager()
+        .output_file(&job_id, &file)
+        .await
+        .map_err(transcode_error)?
+        .ok_or_else(|| {
+            (StatusCode::NOT_FOUND, "Segment not produced yet".into())
+        })?;
+
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let (content_type, body) = if file == PLAYLIST_FILE {
+        let playlist = String::from_utf8_lossy(&bytes);
+        let body = match query.access_token.as_deref() {
+            Some(token) => with_segment_token(&playlist, token),
+            None => playlist.into_owned(),
+        };
+        ("application/vnd.apple.mpegurl", Body::from(body))
+    } else if file.ends_with(".m4s") || file.ends_with(".mp4") {
+        ("video/mp4", Body::from(bytes))
+    } else {
+        ("video/mp2t", Body::from(bytes))
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(body)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Append `access_token` to every URI line (and `EXT-X-MAP` URI) of a
+/// playlist.
+pub(crate) fn with_segment_token(playlist: &str, token: &str) -> String {
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("access_token", token)
+        .finish();
+    let mut out = String::with_capacity(playlist.len());
+    for line in playlist.lines() {
+        if !line.is_empty() && !line.starts_with('#') {
+            out.push_str(line);
+            out.push_str(if line.contains('?') { "&" } else { "?" });
+            out.push_str(&query);
+        } else if let Some(rest) = line.strip_prefix("#EXT-X-MAP:URI=\"")
+            && let Some((uri, tail)) = rest.split_once('"')
+        {
+            out.push_str(&format!(
+                "#EXT-X-MAP:URI=\"{}?{}\"{}",
+                uri, query, tail
+            ));
+        } else {
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playlist_segments_carry_the_access_token() {
+        let playlist = "#EXTM3U\n#EXT-X-MAP:URI=\"init.mp4\"\n#EXTINF:6.0,\nsegment_00000.m4s\n";
+        let rewritten = with_segment_token(playlist, "a b");
+        assert!(rewritten.contains("segment_00000.m4s?access_token=a+b\n"));
+        assert!(
+            rewritten.contains("#EXT-X-MAP:URI=\"init.mp4?access_token=a+b\"")
+        );
+        assert!(rewritten.starts_with("#EXTM3U\n"));
+    }
+}
@@ -390,7 +390,8 @@ fn map_auth_error(err: AuthenticationError) -> AppError {
         AuthenticationError::TooManyFailedAttempts => {
             AppError::rate_limited("Too many failed attempts".to_string())
         }
-        AuthenticationError::SessionExpired => {
+        AuthenticationError::SessionExpired
+        | AuthenticationError::RefreshTokenReused => {
             AppError::unauthorized("Session expired".to_string())
         }
         AuthenticationError::DatabaseError(e) => {
@@ -1,6 +1,12 @@
 //! Device-aware authentication handlers built on the new auth domain services.
 
-use axum::{Extension, Json, extract::State, http::HeaderMap};
+use std::net::SocketAddr;
+
+use axum::{
+    Extension, Json,
+    extract::{ConnectInfo, State},
+    http::HeaderMap,
+};
 use base64::Engine as _;
 use chrono::Utc;
 use ferrex_core::{
@@ -37,6 +43,7 @@ use crate::{
     infra::{
         app_state::AppState,
         errors::{AppError, AppResult},
+        middleware::ProxyTrust,
     },
 };
 use ferrex_core::domain::users::auth::domain::services::AuthenticationError as CoreAuthError;
@@ -106,30 +113,43 @@ pub struct ChangePinRequest {
 
 pub async fn device_login(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
     Json(request): Json<DeviceLoginRequest>,
 ) -> AppResult<Json<ApiResponse<AuthResult>>> {
-    let device_info = extract_device_info(&headers, request.device_info);
-    let fingerprint = generate_device_fingerprint(&device_info, &headers)
-        .map_err(AppError::bad_request)?;
+    let mut device_info = extract_device_info(&headers, request.device_info);
 
-    let mut context = build_event_context(&headers);
+    let proxy_trust = ProxyTrust::from_config(&state.config().security);
+    let mut context = build_event_context(&proxy_trust, addr, &headers);
     context
         .insert_metadata("device_name", json!(device_info.device_name.clone()));
     context.insert_metadata("remember_device", json!(request.remember_device));
 
     let facade = state.auth_facade().clone();
 
-    let (bundle, mut session) = facade
+    let (bundle, mut session, device_id) = facade
         .device_password_login(
             &request.username,
             &request.password,
-            fingerprint,
+            device_info.device_id,
+            |device_id| {
+                generate_device_fingerprint(&device_info, device_id, &headers)
+            },
             device_info.device_name.clone(),
             context,
         )
         .await
         .map_err(map_facade_error)?;
+    if device_id != device_info.device_id {
+        warn!(
+            user_id = %bundle.user_id,
+            requested = %device_info.device_id,
+            reissued = %device_id,
+            "device id already claimed by another user's device; reissued"
+        );
+        // Returned in the device registration for the client to adopt.
+        device_info.device_id = device_id;
+    }
 
     // Persist device public key if provided (validate base64 and algorithm)
     if let Some(pk_b64) = request.device_public_key.as_ref() {
@@ -284,10 +304,10 @@ pub async fn pin_challenge(
         .get_pin_client_salt(user_id)
         .await
         .map_err(map_auth_facade_error)?;
-    // 2 minute TTL
-    let (id, nonce) = state
-        .auth_service()
-        .create_device_challenge(request.device_id, 120)
+    let auth_service = state.auth_service();
+    let challenge_ttl = auth_service.token_lifetimes().device_challenge;
+    let (id, nonce) = auth_service
+        .create_device_challenge(request.device_id)
         .await
         .map_err(map_authentication_error)?;
     let nonce_b64 = base64::engine::general_purpose::STANDARD.encode(nonce);
@@ -296,7 +316,7 @@ pub async fn pin_challenge(
     Ok(Json(ApiResponse::success(PinChallengeResponse {
         challenge_id: id,
         nonce: nonce_b64,
-        expires_in_secs: 120,
+        expires_in_secs: challenge_ttl.num_seconds(),
         pin_salt: pin_salt_b64,
     })))
 }
@@ -550,10 +570,13 @@ fn map_core_auth_error(err: CoreAuthError) -> AppError {
     }
 }
 
+/// Fingerprint of the device described by `device_info` and the request
+/// headers, identified by `device_id`.
 fn generate_device_fingerprint(
     device_info: &DeviceInfo,
+    device_id: Uuid,
     headers: &HeaderMap,
-) -> Result<DeviceFingerprint, String> {
+) -> DeviceFingerprint {
     let user_agent = headers
         .get("user-agent")
         .and_then(|h| h.to_str().ok())
@@ -565,24 +588,28 @@ fn generate_device_fingerprint(
     if let Some(hw_id) = &device_info.hardware_id {
         hasher.update(hw_id.as_bytes());
     }
-    hasher.update(device_info.device_id.as_bytes());
+    hasher.update(device_id.as_bytes());
 
     let hash = format!("{:x}", hasher.finalize());
     DeviceFingerprint::from_hash(hash)
-        .map_err(|_| "Invalid device fingerprint".to_string())
+        .expect("a hex SHA-256 digest is 64 characters")
 }
 
-fn build_event_context(headers: &HeaderMap) -> AuthEventContext {
+/// Audit context for a login. The address is the peer's unless it is a
+/// trusted proxy, in which case it is the client the proxy forwarded for.
+fn build_event_context(
+    proxy_trust: &ProxyTrust,
+    peer: SocketAddr,
+    headers: &HeaderMap,
+) -> AuthEventContext {
     let user_agent = headers
         .get("user-agent")
         .and_then(|h| h.to_str().ok())
         .map(|s| s.to_string());
 
-    let ip_address = headers
-        .get("x-forwarded-for")
-        .or_else(|| headers.get("x-real-ip"))
-        .and_then(|h| h.to_str().ok())
-        .map(|s| s.to_string());
+    let ip_address = proxy_trust
+        .client_ip(Some(peer.ip()), headers)
+        .map(|ip| ip.to_string());
 
     AuthEventContext {
         ip_address,
@@ -664,6 +691,9 @@ fn map_authentication_error(err: AuthenticationError) -> AppError {
         AuthenticationError::SessionExpired => {
             AppError::unauthorized(AuthError::SessionExpired.to_string())
         }
+        err @ AuthenticationError::RefreshTokenReused => {
+            AppError::unauthorized(err.to_string())
+        }
         AuthenticationError::DeviceNotFound
         | AuthenticationError::DeviceNotTrusted => AppError::forbidden(
             "Device not eligible for authentication".to_string(),
@@ -696,6 +726,7 @@ fn map_device_trust_error(err: DeviceTrustError) -> AppError {
         E::TooManyDevices { .. } => {
             AppError::conflict("Too many devices registered".to_string())
         }
+        err @ E::DeviceIdCollision => AppError::conflict(err.to_string()),
         E::DeviceNotTrusted => {
             AppError::forbidden("Device is not trusted".to_string())
         }
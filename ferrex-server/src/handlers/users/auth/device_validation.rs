@@ -267,6 +267,9 @@ fn map_authentication_error(err: AuthenticationError) -> AppError {
         AuthenticationError::SessionExpired => {
             AppError::unauthorized(AuthError::SessionExpired.to_string())
         }
+        err @ AuthenticationError::RefreshTokenReused => {
+            AppError::unauthorized(err.to_string())
+        }
         AuthenticationError::DeviceNotFound
         | AuthenticationError::DeviceNotTrusted => AppError::forbidden(
             "Device not eligible for authentication".to_string(),
@@ -299,6 +302,7 @@ fn map_device_trust_error(err: DeviceTrustError) -> AppError {
         E::TooManyDevices { .. } => {
             AppError::conflict("Too many devices registered".to_string())
         }
+        err @ E::DeviceIdCollision => AppError::conflict(err.to_string()),
         E::DeviceNotTrusted => {
             AppError::forbidden("Device is not trusted".to_string())
         }
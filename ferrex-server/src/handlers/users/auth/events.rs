@@ -0,0 +1,237 @@
+//! Paginated reads of the auth audit log.
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
+use ferrex_core::{
+    api::types::{
+        ApiResponse, AuthEventCursor, AuthEventEntry, AuthEventPage,
+        AuthEventsQuery,
+    },
+    domain::users::{
+        auth::{
+            AuthEventType,
+            domain::repositories::{
+                AuthAuditEventKind, AuthEventQuery, AuthEventRecord,
+            },
+        },
+        user::User,
+    },
+};
+
+use crate::{
+    handlers::users::map_auth_facade_error,
+    infra::{
+        app_state::AppState,
+        errors::{AppError, AppResult},
+    },
+};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventView {
+    Admin,
+    /// A user's own events: session ids, failure reasons and metadata
+    /// (refresh token families, revocation details) are withheld.
+    Own,
+}
+
+/// Auth events of the calling user, newest first.
+pub async fn list_own_auth_events(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Query(query): Query<AuthEventsQuery>,
+) -> AppResult<Json<ApiResponse<AuthEventPage>>> {
+    if query.user_id.is_some_and(|id| id != user.id) {
+        return Err(AppError::forbidden("Cannot list another user's events"));
+    }
+    let mut filter = event_query(&query)?;
+    filter.user_id = Some(user.id);
+
+    let page = load_page(&state, filter, EventView::Own).await?;
+    Ok(Json(ApiResponse::success(page)))
+}
+
+/// Auth events of all users, or of `user_id`, newest first (admin only).
+pub async fn list_auth_events_admin(
+    State(state): State<AppState>,
+    Extension(_admin): Extension<User>,
+    Query(query): Query<AuthEventsQuery>,
+) -> AppResult<Json<ApiResponse<AuthEventPage>>> {
+    let mut filter = event_query(&query)?;
+    filter.user_id = query.user_id;
+
+    let page = load_page(&state, filter, EventView::Admin).await?;
+    Ok(Json(ApiResponse::success(page)))
+}
+
+fn event_query(query: &AuthEventsQuery) -> AppResult<AuthEventQuery> {
+    let mut event_types = Vec::new();
+    for name in query
+        .event_types
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let kind = AuthAuditEventKind::parse(name).ok_or_else(|| {
+            AppError::bad_request(format!("Unknown event type: {name}"))
+        })?;
+        if !event_types.contains(&kind) {
+            event_types.push(kind);
+        }
+    }
+
+    if let (Some(since), Some(until)) = (query.since, query.until)
+        && since >= until
+    {
+        return Err(AppError::bad_request("since must be before until"));
+    }
+
+    Ok(AuthEventQuery {
+        user_id: None,
+        event_types,
+        since: query.since,
+        until: query.until,
+        after: query.cursor.map(|cursor| (cursor.occurred_at, cursor.id)),
+        limit: query
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE),
+    })
+}
+
+async fn load_page(
+    state: &AppState,
+    mut filter: AuthEventQuery,
+    view: EventView,
+) -> AppResult<AuthEventPage> {
+    // Fetch one extra row to learn whether another page follows.
+    let limit = filter.limit;
+    filter.limit = limit + 1;
+    let records = state
+        .auth_facade()
+        .list_auth_events(&filter)
+        .await
+        .map_err(map_auth_facade_error)?;
+
+    let has_more = records.len() > limit;
+    let events: Vec<AuthEventEntry> = records
+        .into_iter()
+        .take(limit)
+        .map(|record| entry(record, view))
+        .collect();
+    let next_cursor = if has_more {
+        events.last().map(AuthEventCursor::after)
+    } else {
+        None
+    };
+
+    Ok(AuthEventPage {
+        events,
+        next_cursor,
+    })
+}
+
+fn entry(record: AuthEventRecord, view: EventView) -> AuthEventEntry {
+    let entry = AuthEventEntry {
+        id: record.id,
+        event_type: AuthEventType::from(record.event_type),
+        occurred_at: record.occurred_at,
+        success: record.success,
+        user_id: record.user_id,
+        device_id: record.device_session_id,
+        device_name: record.device_name,
+        ip_address: record.ip_address,
+        user_agent: record.user_agent,
+        session_id: record.session_id,
+        failure_reason: record.failure_reason,
+        metadata: Some(record.metadata),
+    };
+    match view {
+        EventView::Admin => entry,
+        EventView::Own => AuthEventEntry {
+            session_id: None,
+            failure_reason: None,
+            metadata: None,
+            ..entry
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn event_types_are_parsed_and_limits_clamped() {
+        let query = AuthEventsQuery {
+            event_types: Some("pin_set, pin_login_failure,pin_set".into()),
+            limit: Some(10_000),
+            ..AuthEventsQuery::default()
+        };
+        let filter = event_query(&query).unwrap();
+        assert_eq!(
+            filter.event_types,
+            vec![
+                AuthAuditEventKind::PinSet,
+                AuthAuditEventKind::PinLoginFailure
+            ]
+        );
+        assert_eq!(filter.limit, MAX_PAGE_SIZE);
+
+        let unknown = AuthEventsQuery {
+            event_types: Some("password_reset".into()),
+            ..AuthEventsQuery::default()
+        };
+        assert!(event_query(&unknown).is_err());
+
+        let now = Utc::now();
+        let inverted = AuthEventsQuery {
+            since: Some(now),
+            until: Some(now - Duration::hours(1)),
+            ..AuthEventsQuery::default()
+        };
+        assert!(event_query(&inverted).is_err());
+    }
+
+    #[test]
+    fn own_view_withholds_sensitive_fields() {
+        let record = AuthEventRecord {
+            id: Uuid::now_v7(),
+            event_type: AuthAuditEventKind::RefreshTokenReuse,
+            user_id: Some(Uuid::now_v7()),
+            device_session_id: Some(Uuid::now_v7()),
+            device_name: Some("Living room".into()),
+            session_id: Some(Uuid::now_v7()),
+            success: false,
+            failure_reason: Some("refresh_token_reuse".into()),
+            ip_address: Some("203.0.113.7".into()),
+            user_agent: Some("ferrex-player".into()),
+            metadata: json!({ "family_id": Uuid::now_v7() }),
+            occurred_at: Utc::now(),
+        };
+
+        let admin = entry(record.clone(), EventView::Admin);
+        assert!(admin.session_id.is_some());
+        assert!(admin.failure_reason.is_some());
+        assert!(admin.metadata.is_some());
+
+        let own = entry(record, EventView::Own);
+        assert_eq!(own.event_type, AuthEventType::RefreshTokenReuse);
+        assert!(!own.success);
+        assert_eq!(own.device_name.as_deref(), Some("Living room"));
+        assert_eq!(own.ip_address.as_deref(), Some("203.0.113.7"));
+        assert!(own.session_id.is_none());
+        assert!(own.failure_reason.is_none());
+        assert!(own.metadata.is_none());
+    }
+}
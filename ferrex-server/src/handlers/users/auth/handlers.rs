@@ -1,26 +1,26 @@
-use crate::handlers::users::map_auth_facade_error;
-use axum::{Extension, Json, extract::State, http::StatusCode};
+use crate::handlers::users::{
+    auth::middleware::bearer_token_from_headers, map_auth_facade_error,
+};
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
 use chrono::Utc;
 use ferrex_core::{
-    api::types::ApiResponse,
+    api::types::{ApiResponse, RefreshRequest},
     domain::users::{
         auth::{
             domain::services::{AuthenticationError, TokenBundle},
             policy::PasswordPolicyRule,
         },
         user::{
-            AuthError, AuthToken, LoginRequest, RegisterRequest, User,
-            ValidationError,
+            AuthError, AuthToken, LoginRequest, RegisterRequest,
+            SessionIntrospection, User, ValidationError,
         },
     },
     error::MediaError,
 };
-use serde::Deserialize;
-
-#[derive(Debug, Deserialize)]
-pub struct RefreshRequest {
-    pub refresh_token: String,
-}
 use uuid::Uuid;
 
 use crate::infra::{
@@ -180,6 +180,42 @@ pub async fn logout(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Report the lifetime of the presented session token.
+///
+/// Every rejection (missing header, unknown, revoked, or expired token)
+/// produces the same `401` body so the response never reveals whether a
+/// token was ever issued.
+pub async fn introspect_session(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> AppResult<Json<ApiResponse<SessionIntrospection>>> {
+    let rejected =
+        || AppError::unauthorized(AuthError::TokenInvalid.to_string());
+
+    let token = bearer_token_from_headers(&headers).ok_or_else(rejected)?;
+
+    let record = state
+        .auth_service()
+        .introspect_session_token(token)
+        .await
+        .map_err(|err| match err {
+            AuthenticationError::DatabaseError(e) => {
+                AppError::internal(format!("Authentication failed: {e}"))
+            }
+            _ => rejected(),
+        })?;
+
+    Ok(Json(ApiResponse::success(SessionIntrospection::new(
+        record.id,
+        record.user_id,
+        record.device_session_id,
+        record.scope,
+        record.created_at,
+        record.expires_at,
+        Utc::now(),
+    ))))
+}
+
 pub async fn get_current_user(
     Extension(user): Extension<User>,
 ) -> AppResult<Json<ApiResponse<User>>> {
@@ -217,6 +253,9 @@ fn map_auth_error(err: AuthenticationError) -> AppError {
         AuthenticationError::SessionExpired => {
             AppError::unauthorized(AuthError::TokenInvalid.to_string())
         }
+        err @ AuthenticationError::RefreshTokenReused => {
+            AppError::unauthorized(err.to_string())
+        }
         AuthenticationError::DeviceNotFound
         | AuthenticationError::DeviceNotTrusted => AppError::forbidden(
             "Device not eligible for authentication".to_string(),
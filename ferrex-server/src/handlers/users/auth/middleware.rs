@@ -1,6 +1,6 @@
 use axum::{
     extract::{Request, State},
-    http::{StatusCode, header},
+    http::{HeaderMap, StatusCode, header},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -15,6 +15,9 @@ use ferrex_core::{
     },
 };
 
+use tracing::warn;
+use uuid::Uuid;
+
 use crate::infra::app_state::AppState;
 
 pub async fn auth_middleware(
@@ -49,6 +52,7 @@ pub async fn auth_middleware(
     request.extensions_mut().insert(permissions);
     request.extensions_mut().insert(session.device_session_id);
     request.extensions_mut().insert(session.scope);
+    request.extensions_mut().insert(session);
 
     Ok(next.run(request).await)
 }
@@ -78,6 +82,7 @@ pub async fn optional_auth_middleware(
         request.extensions_mut().insert(user);
         request.extensions_mut().insert(session.device_session_id);
         request.extensions_mut().insert(session.scope);
+        request.extensions_mut().insert(session);
     }
 
     next.run(request).await
@@ -135,17 +140,47 @@ pub async fn admin_middleware(request: Request, next: Next) -> Response {
 }
 
 fn extract_bearer_token(request: &Request) -> Result<String, StatusCode> {
-    let auth_header = request
-        .headers()
-        .get(header::AUTHORIZATION)
-        .and_then(|value| value.to_str().ok())
-        .ok_or(StatusCode::UNAUTHORIZED)?;
+    bearer_token_from_headers(request.headers())
+        .map(str::to_string)
+        .ok_or(StatusCode::UNAUTHORIZED)
+}
 
-    if !auth_header.starts_with("Bearer ") {
-        return Err(StatusCode::UNAUTHORIZED);
-    }
+/// Whether `headers` carry a live session whose device logged in as
+/// `device_id`. `X-Device-ID` alone is whatever the client claims, so
+/// anything granted per device id should be backed by this check.
+pub async fn session_is_on_device(
+    state: &AppState,
+    headers: &HeaderMap,
+    device_id: Uuid,
+) -> bool {
+    let Some(token) = bearer_token_from_headers(headers) else {
+        return false;
+    };
+    let Ok(session) =
+        state.auth_service().introspect_session_token(token).await
+    else {
+        return false;
+    };
+    let Some(device_session_id) = session.device_session_id else {
+        return false;
+    };
+    state
+        .auth_facade()
+        .device_trust_service()
+        .is_session_on_device(device_session_id, device_id)
+        .await
+        .unwrap_or_else(|err| {
+            warn!("device session lookup failed: {err}");
+            false
+        })
+}
 
-    Ok(auth_header[7..].to_string())
+/// Pull the opaque session secret out of an `Authorization: Bearer` header.
+pub(crate) fn bearer_token_from_headers(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
 }
 
 fn map_authentication_error_to_status(err: AuthenticationError) -> StatusCode {
@@ -156,6 +191,7 @@ fn map_authentication_error_to_status(err: AuthenticationError) -> StatusCode {
         | AuthenticationError::DeviceNotFound
         | AuthenticationError::DeviceNotTrusted
         | AuthenticationError::SessionExpired
+        | AuthenticationError::RefreshTokenReused
         | AuthenticationError::UserNotFound => StatusCode::UNAUTHORIZED,
         AuthenticationError::DatabaseError(_) => {
             StatusCode::INTERNAL_SERVER_ERROR
@@ -1,5 +1,6 @@
 pub mod device_handlers;
 pub mod device_validation;
+pub mod events;
 pub mod handlers;
 pub mod middleware;
 pub mod permission_middleware;
@@ -8,6 +9,7 @@ pub mod user_preferences;
 
 pub use middleware::{
     admin_middleware, auth_middleware, optional_auth_middleware,
+    session_is_on_device,
 };
 pub use permission_middleware::{
     permission_layer, require_any_permission, require_permission,
@@ -0,0 +1,242 @@
+//! Per-user collections of movies and episodes.
+//!
+//! Every handler is scoped to the requesting user: a collection owned by
+//! somebody else answers `404 Not Found` exactly like a missing one.
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use ferrex_core::{
+    api::types::{
+        AddCollectionItemsRequest, ApiResponse, Collection, CollectionDetail,
+        CreateCollectionRequest, ReorderCollectionRequest,
+        UpdateCollectionRequest,
+    },
+    domain::users::user::User,
+    types::media_id::MediaID,
+};
+use uuid::Uuid;
+
+use crate::infra::{
+    app_state::AppState,
+    errors::{AppError, AppResult},
+};
+
+/// Longest accepted collection name, matching the database column.
+const MAX_NAME_LEN: usize = 200;
+
+fn validate_name(name: &str) -> AppResult<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::bad_request("Collection name is required"));
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(AppError::bad_request(format!(
+            "Collection name must be at most {MAX_NAME_LEN} characters"
+        )));
+    }
+    Ok(())
+}
+
+/// Collections hold playable media only.
+fn validate_members(media: &[MediaID]) -> AppResult<()> {
+    match media
+        .iter()
+        .find(|id| !matches!(id, MediaID::Movie(_) | MediaID::Episode(_)))
+    {
+        Some(other) => Err(AppError::bad_request(format!(
+            "Only movies and episodes can be added to a collection, got {other}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// List the caller's collections, ordered by name.
+pub async fn list_collections_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+) -> AppResult<Json<ApiResponse<Vec<Collection>>>> {
+    let collections = state
+        .unit_of_work()
+        .collections
+        .list_collections(user.id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(collections)))
+}
+
+/// Create an empty collection.
+///
+/// # Response
+///
+/// - `201 Created` with the new collection
+/// - `400 Bad Request` for a blank or overlong name
+/// - `409 Conflict` when the caller already has a collection of that name
+pub async fn create_collection_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Json(request): Json<CreateCollectionRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<Collection>>)> {
+    validate_name(&request.name)?;
+
+    let collection = state
+        .unit_of_work()
+        .collections
+        .create_collection(
+            user.id,
+            &request.name,
+            request.description.as_deref(),
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(collection))))
+}
+
+/// A collection with its members in order, each resolved to a title and
+/// the caller's watch state.
+pub async fn get_collection_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<CollectionDetail>>> {
+    let collections = &state.unit_of_work().collections;
+    let collection = collections
+        .get_collection(user.id, id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Collection not found"))?;
+    let items = collections.list_items(user.id, id).await?;
+
+    Ok(Json(ApiResponse::success(CollectionDetail {
+        collection,
+        items,
+    })))
+}
+
+/// Rename a collection or change its description.
+pub async fn update_collection_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateCollectionRequest>,
+) -> AppResult<Json<ApiResponse<Collection>>> {
+    if let Some(name) = &request.name {
+        validate_name(name)?;
+    }
+
+    let collection = state
+        .unit_of_work()
+        .collections
+        .update_collection(user.id, id, &request)
+        .await?;
+
+    Ok(Json(ApiResponse::success(collection)))
+}
+
+/// Delete a collection. The media in it is not touched.
+pub async fn delete_collection_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let deleted = state
+        .unit_of_work()
+        .collections
+        .delete_collection(user.id, id)
+        .await?;
+
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::not_found("Collection not found"))
+    }
+}
+
+/// Append movies and episodes to the end of a collection, in the order
+/// given. Media already in the collection keeps its place.
+///
+/// # Response
+///
+/// - `204 No Content` on success
+/// - `400 Bad Request` when the list holds series or seasons
+/// - `404 Not Found` for an unknown collection or media id
+pub async fn add_collection_items_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<AddCollectionItemsRequest>,
+) -> AppResult<StatusCode> {
+    validate_members(&request.media)?;
+
+    state
+        .unit_of_work()
+        .collections
+        .add_items(user.id, id, &request.media)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Remove one movie or episode from a collection.
+pub async fn remove_collection_item_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path((id, media_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<StatusCode> {
+    let removed = state
+        .unit_of_work()
+        .collections
+        .remove_item(user.id, id, media_id)
+        .await?;
+
+    if removed {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::not_found("Collection item not found"))
+    }
+}
+
+/// Replace the order of a collection's members.
+///
+/// # Response
+///
+/// - `204 No Content` on success
+/// - `409 Conflict` unless the list names every member exactly once
+pub async fn reorder_collection_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ReorderCollectionRequest>,
+) -> AppResult<StatusCode> {
+    validate_members(&request.media)?;
+
+    state
+        .unit_of_work()
+        .collections
+        .reorder_items(user.id, id, &request.media)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrex_core::types::ids::{MovieID, SeriesID};
+
+    #[test]
+    fn names_must_be_present_and_short() {
+        assert!(validate_name("Comfort Movies").is_ok());
+        assert!(validate_name("   ").is_err());
+        assert!(validate_name(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn only_movies_and_episodes_are_members() {
+        let movie = MediaID::Movie(MovieID(Uuid::now_v7()));
+        let series = MediaID::Series(SeriesID(Uuid::now_v7()));
+        assert!(validate_members(&[movie]).is_ok());
+        assert!(validate_members(&[movie, series]).is_err());
+    }
+}
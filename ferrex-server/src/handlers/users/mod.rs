@@ -1,6 +1,7 @@
 pub mod admin_handlers;
 pub mod admin_user_management;
 pub mod auth;
+pub mod collections_handlers;
 pub mod role_handlers;
 pub mod security_settings_handlers;
 pub mod session_handlers;
@@ -13,6 +13,7 @@ use crate::application::auth::AuthFacadeError;
 use crate::infra::{
     app_state::AppState,
     errors::{AppError, AppResult},
+    websocket::CloseReason,
 };
 
 /// Get all active sessions for the current user
@@ -40,6 +41,9 @@ pub async fn delete_session_handler(
         .revoke_user_session(user.id, session_id)
         .await
         .map_err(map_facade_error)?;
+    state
+        .websocket_manager()
+        .close_session(session_id, CloseReason::SessionRevoked);
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -66,6 +70,9 @@ pub async fn delete_all_sessions_handler(
         .revoke_all_user_sessions(user.id)
         .await
         .map_err(map_facade_error)?;
+    state
+        .websocket_manager()
+        .close_user(user.id, CloseReason::SessionRevoked);
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -101,7 +108,8 @@ fn map_auth_error(err: AuthenticationError) -> AppError {
         AuthenticationError::TooManyFailedAttempts => {
             AppError::rate_limited("Too many failed attempts".to_string())
         }
-        AuthenticationError::SessionExpired => {
+        AuthenticationError::SessionExpired
+        | AuthenticationError::RefreshTokenReused => {
             AppError::unauthorized("Session expired".to_string())
         }
         AuthenticationError::DatabaseError(e) => {
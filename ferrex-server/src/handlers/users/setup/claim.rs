@@ -1,28 +1,44 @@
+use std::io::Cursor;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 use crate::handlers::users::UserService;
 use crate::infra::{
     app_state::AppState,
     errors::{AppError, AppResult},
+    middleware::ProxyTrust,
 };
 use axum::{
     Json,
-    extract::{ConnectInfo, State},
+    extract::{ConnectInfo, Query, State},
+    http::{HeaderMap, Uri, header::HOST},
+};
+use base64::{
+    Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD,
 };
 use ferrex_core::api::types::{
     ApiResponse,
     setup::{
-        ConfirmClaimRequest, ConfirmClaimResponse, StartClaimRequest,
-        StartClaimResponse,
+        ClaimLinkQuery, ClaimLinkResponse, ConfirmClaimRequest,
+        ConfirmClaimResponse, StartClaimRequest, StartClaimResponse,
     },
 };
 use ferrex_core::domain::setup::{
-    ConfirmedClaim, SetupClaimError, StartedClaim,
+    ClaimLink, ConfirmedClaim, SetupClaimError, StartedClaim,
 };
+use image::{GrayImage, ImageFormat, Luma};
+use qrcode::{Color, QrCode};
+use url::Url;
+
+/// Pixels per QR module in the rendered PNG.
+const QR_MODULE_PX: usize = 8;
+/// Blank modules around the code, as the QR spec asks for.
+const QR_QUIET_ZONE: usize = 4;
 
 pub async fn start_secure_claim(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    uri: Uri,
     Json(request): Json<StartClaimRequest>,
 ) -> AppResult<Json<ApiResponse<StartClaimResponse>>> {
     let client_ip = addr.ip();
@@ -50,20 +66,32 @@ pub async fn start_secure_claim(
         return Err(AppError::gone("Setup has already been completed"));
     }
 
+    let server_url = resolve_server_url(
+        &state,
+        client_ip,
+        &headers,
+        &uri,
+        request.server_url.as_deref(),
+    )?;
+
     let claim_service = state.setup_claim_service();
     match claim_service
-        .start_claim(validated_name, Some(client_ip))
+        .begin(validated_name, Some(client_ip), &server_url)
         .await
     {
-        Ok(StartedClaim {
-            claim_id,
-            claim_code,
-            expires_at,
-        }) => Ok(Json(ApiResponse::success(StartClaimResponse {
+        Ok((
+            StartedClaim {
+                claim_id,
+                claim_code,
+                expires_at,
+            },
+            link,
+        )) => Ok(Json(ApiResponse::success(StartClaimResponse {
             claim_id,
             claim_code,
             expires_at,
             lan_only: true,
+            claim_link: Some(link.payload),
         }))),
         Err(SetupClaimError::ActiveClaimPending { expires_at, .. }) => {
             Err(AppError::conflict(format!(
@@ -83,7 +111,12 @@ pub async fn confirm_secure_claim(
     let client_ip = addr.ip();
     require_lan(client_ip)?;
 
-    if request.claim_code.trim().is_empty() {
+    let claim_link = request
+        .claim_link
+        .as_deref()
+        .map(str::trim)
+        .filter(|link| !link.is_empty());
+    if claim_link.is_none() && request.claim_code.trim().is_empty() {
         return Err(AppError::bad_request("Claim code cannot be empty"));
     }
 
@@ -95,7 +128,11 @@ pub async fn confirm_secure_claim(
     }
 
     let claim_service = state.setup_claim_service();
-    match claim_service.confirm_claim(&request.claim_code).await {
+    let confirmed = match claim_link {
+        Some(link) => claim_service.confirm_claim_link(link).await,
+        None => claim_service.confirm_claim(&request.claim_code).await,
+    };
+    match confirmed {
         Ok(ConfirmedClaim {
             claim_id,
             claim_token,
@@ -109,6 +146,123 @@ pub async fn confirm_secure_claim(
     }
 }
 
+/// Deep link and QR code for a pending claim, so the device that started it
+/// can show it on screen for a player to scan.
+pub async fn secure_claim_link(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    uri: Uri,
+    Query(query): Query<ClaimLinkQuery>,
+) -> AppResult<Json<ApiResponse<ClaimLinkResponse>>> {
+    let client_ip = addr.ip();
+    require_lan(client_ip)?;
+
+    if query.claim_code.trim().is_empty() {
+        return Err(AppError::bad_request("Claim code cannot be empty"));
+    }
+
+    let user_service = UserService::new(&state);
+    if !user_service.needs_setup().await? {
+        return Err(AppError::gone("Setup has already been completed"));
+    }
+
+    let server_url = resolve_server_url(
+        &state,
+        client_ip,
+        &headers,
+        &uri,
+        query.server_url.as_deref(),
+    )?;
+    let ClaimLink {
+        payload,
+        server_url,
+        claim_id,
+        expires_at,
+    } = state
+        .setup_claim_service()
+        .claim_link(&query.claim_code, &server_url)
+        .await
+        .map_err(map_claim_error)?;
+    let qr_png = BASE64_STANDARD.encode(render_qr_png(&payload)?);
+
+    Ok(Json(ApiResponse::success(ClaimLinkResponse {
+        claim_id,
+        claim_link: payload,
+        server_url,
+        expires_at,
+        qr_png,
+    })))
+}
+
+/// The server address a claim link points players at: the one the caller
+/// asked for, else the scheme and host this request reached us on.
+fn resolve_server_url(
+    state: &AppState,
+    client_ip: IpAddr,
+    headers: &HeaderMap,
+    uri: &Uri,
+    requested: Option<&str>,
+) -> AppResult<Url> {
+    if let Some(requested) = requested.map(str::trim).filter(|s| !s.is_empty())
+    {
+        return Url::parse(requested)
+            .ok()
+            .filter(|url| {
+                matches!(url.scheme(), "http" | "https")
+                    && url.host_str().is_some()
+            })
+            .ok_or_else(|| {
+                AppError::bad_request("server_url must be an http(s) URL")
+            });
+    }
+
+    let host = headers
+        .get(HOST)
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| uri.authority().map(|authority| authority.as_str()))
+        .ok_or_else(|| {
+            AppError::bad_request(
+                "Cannot tell the server address; pass server_url",
+            )
+        })?;
+    let https = ProxyTrust::from_config(&state.config().security)
+        .is_https_parts(Some(client_ip), headers, uri);
+    let scheme = if https { "https" } else { "http" };
+    Url::parse(&format!("{scheme}://{host}"))
+        .map_err(|_| AppError::bad_request("Invalid Host header"))
+}
+
+fn render_qr_png(payload: &str) -> AppResult<Vec<u8>> {
+    let code = QrCode::new(payload.as_bytes()).map_err(|err| {
+        AppError::internal(format!("Failed to encode claim link: {err}"))
+    })?;
+    let width = code.width();
+    let colors = code.to_colors();
+    let module = |px: u32| {
+        (px as usize / QR_MODULE_PX)
+            .checked_sub(QR_QUIET_ZONE)
+            .filter(|m| *m < width)
+    };
+
+    let size = ((width + 2 * QR_QUIET_ZONE) * QR_MODULE_PX) as u32;
+    let image = GrayImage::from_fn(size, size, |x, y| {
+        let dark = matches!(
+            (module(x), module(y)),
+            (Some(mx), Some(my)) if colors[my * width + mx] == Color::Dark
+        );
+        Luma([if dark { 0 } else { 255 }])
+    });
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|err| {
+            AppError::internal(format!("Failed to render QR code: {err}"))
+        })?;
+    Ok(png)
+}
+
 fn require_lan(ip: IpAddr) -> AppResult<()> {
     if is_lan_ip(ip) {
         Ok(())
@@ -145,6 +299,9 @@ fn map_claim_error(error: SetupClaimError) -> AppError {
         SetupClaimError::InvalidToken => {
             AppError::forbidden("Invalid claim token supplied")
         }
+        SetupClaimError::InvalidLink => {
+            AppError::bad_request("Invalid or inactive claim link supplied")
+        }
         SetupClaimError::Expired { .. } => {
             AppError::gone("Claim secret has expired")
         }
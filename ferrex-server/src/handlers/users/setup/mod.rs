@@ -44,6 +44,7 @@ use tracing::{info, warn};
 use crate::handlers::users::{UserService, user_service::CreateUserParams};
 use crate::infra::{
     app_state::AppState,
+    config::LocaleConfig,
     demo_mode,
     errors::{AppError, AppResult},
 };
@@ -148,6 +149,26 @@ pub struct SetupStatus {
     pub admin_password_policy: PasswordPolicyResponse,
     /// Current password policy for regular users
     pub user_password_policy: PasswordPolicyResponse,
+    /// Deployment-wide language and region, for clients to format dates
+    /// with when the user has not picked a language of their own
+    pub locale: LocaleResponse,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LocaleResponse {
+    /// ISO 639-1 language code
+    pub language: String,
+    /// ISO 3166-1 alpha-2 region code, if one is configured
+    pub region: Option<String>,
+}
+
+impl From<&LocaleConfig> for LocaleResponse {
+    fn from(locale: &LocaleConfig) -> Self {
+        Self {
+            language: locale.language.clone(),
+            region: locale.region.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -275,6 +296,7 @@ pub async fn check_setup_status(
         user_password_policy: PasswordPolicyResponse::from(
             &security_settings.user_password_policy,
         ),
+        locale: LocaleResponse::from(&state.config().locale),
     };
 
     Ok(Json(ApiResponse::success(status)))
@@ -514,6 +536,7 @@ async fn check_setup_status_internal(
         user_password_policy: PasswordPolicyResponse::from(
             &security_settings.user_password_policy,
         ),
+        locale: LocaleResponse::from(&state.config().locale),
     })
 }
 
@@ -526,7 +549,8 @@ fn auth_error_to_app(err: AuthenticationError) -> AppError {
         AuthenticationError::TooManyFailedAttempts => AppError::rate_limited(
             "Too many failed authentication attempts".to_string(),
         ),
-        AuthenticationError::SessionExpired => {
+        AuthenticationError::SessionExpired
+        | AuthenticationError::RefreshTokenReused => {
             AppError::unauthorized("Session expired".to_string())
         }
         AuthenticationError::DeviceNotFound
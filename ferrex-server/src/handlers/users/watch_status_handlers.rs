@@ -3,18 +3,25 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
 };
+use ferrex_core::application::watch_status::WatchStatusService;
 use ferrex_core::types::watch::{
-    NextEpisode, SeasonWatchStatus, SeriesWatchStatus,
+    BulkWatchUpdate, EpisodeKey, NextEpisode, SeasonWatchStatus,
+    SeriesWatchStatus,
 };
 use ferrex_core::{
     api::types::ApiResponse,
     domain::users::user::User,
-    domain::watch::{InProgressItem, UpdateProgressRequest, UserWatchState},
+    domain::watch::{
+        InProgressItem, MarkEpisodeWatchedRequest, UpdateProgressRequest,
+        UserWatchState, WatchStateExport, WatchStateImportReport,
+    },
+    error::MediaError,
 };
-use ferrex_model::VideoMediaType;
+use ferrex_model::{MediaEvent, SeasonID, SeriesID, VideoMediaType};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::handlers::media::media_id_param::{MediaIdParam, PLAYABLE};
 use crate::infra::app_state::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -36,6 +43,28 @@ pub struct ProgressResponse {
     pub is_completed: bool,
 }
 
+/// Map a failed progress write to a response. Stale reports surface as
+/// `409 Conflict` with the reason so clients can drop them.
+pub(crate) fn progress_update_error(
+    context: &str,
+    err: MediaError,
+) -> (StatusCode, String) {
+    match err {
+        MediaError::Conflict(reason) => (StatusCode::CONFLICT, reason),
+        other => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("{context}: {other}"),
+        ),
+    }
+}
+
+/// Id of a movie or episode; other typed ids are refused.
+fn playable_uuid(id: MediaIdParam) -> Result<Uuid, (StatusCode, String)> {
+    id.accept(PLAYABLE)
+        .map(|id| id.uuid())
+        .map_err(|err| (StatusCode::BAD_REQUEST, err.to_string()))
+}
+
 /// Update watch progress for a media item
 ///
 /// Updates the user's viewing progress for a specific media item.
@@ -47,7 +76,8 @@ pub struct ProgressResponse {
 /// {
 ///   "media_id": "movie:550e8400-e29b-41d4-a716-446655440000",
 ///   "position": 1800.0,
-///   "duration": 7200.0
+///   "duration": 7200.0,
+///   "reported_at": 1704067200000
 /// }
 /// ```
 ///
@@ -55,9 +85,14 @@ pub struct ProgressResponse {
 ///
 /// - `204 No Content` on success
 /// - `400 Bad Request` if validation fails
+/// - `409 Conflict` if the report is older than the stored progress, or
+///   would move the position backwards without `reset`
 ///
 /// # Behavior
 ///
+/// - Reports from several devices are ordered by `reported_at`; a device
+///   that syncs late cannot rewind progress made elsewhere
+/// - `reset: true` allows rewinding and clears a completed mark (rewatch)
 /// - Progress > 95% automatically marks the item as completed
 /// - Position of 0 does not create a progress entry
 /// - Limited to 50 in-progress items per user (oldest are removed)
@@ -87,12 +122,7 @@ pub async fn update_progress_handler(
         .watch_status
         .update_watch_progress(user.id, &request)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to update progress: {}", e),
-            )
-        })?;
+        .map_err(|e| progress_update_error("Failed to update progress", e))?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -141,6 +171,79 @@ pub async fn get_watch_state_handler(
     Ok(Json(ApiResponse::success(watch_state)))
 }
 
+/// Export the current user's watch state
+///
+/// Progress and completions are keyed by TMDB identity (movie id, or series
+/// id with season and episode numbers), so the export survives a rescan or
+/// a move to another server. Unmatched movies are keyed by file path.
+///
+/// # Response
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "exported_at": 1704067200000,
+///   "entries": [
+///     {
+///       "item": { "kind": "movie", "tmdb_id": 603 },
+///       "position": 3600.0,
+///       "duration": 8160.0,
+///       "completed": false,
+///       "last_watched": 1704067200000
+///     }
+///   ]
+/// }
+/// ```
+pub async fn export_watch_state_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+) -> Result<Json<ApiResponse<WatchStateExport>>, (StatusCode, String)> {
+    let export = WatchStatusService::new(state.unit_of_work())
+        .export_watch_state(user.id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to export watch state: {}", e),
+            )
+        })?;
+    Ok(Json(ApiResponse::success(export)))
+}
+
+/// Import a watch-state export into the current user's watch state
+///
+/// Takes the `data` of an export response as is. Entries are matched to the
+/// media currently in the libraries and applied in one transaction.
+///
+/// # Response
+///
+/// - `200 OK` with a [`WatchStateImportReport`] counting imported entries
+///   and entries that were already as far along, and listing the entries
+///   with no matching media
+/// - `400 Bad Request` if the export version is not supported
+///
+/// # Behavior
+///
+/// - Conflicts keep the furthest position; a completed item stays completed
+/// - Every copy of a movie and every file of an episode is updated
+pub async fn import_watch_state_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Json(export): Json<WatchStateExport>,
+) -> Result<Json<ApiResponse<WatchStateImportReport>>, (StatusCode, String)> {
+    let report = WatchStatusService::new(state.unit_of_work())
+        .import_watch_state(user.id, export)
+        .await
+        .map_err(|e| match e {
+            MediaError::InvalidMedia(msg) => (StatusCode::BAD_REQUEST, msg),
+            other => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to import watch state: {}", other),
+            ),
+        })?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
 /// Get continue watching list for the current user
 pub async fn get_continue_watching_handler(
     State(state): State<AppState>,
@@ -187,8 +290,10 @@ pub async fn clear_progress_handler(
 pub async fn get_media_progress_handler(
     State(state): State<AppState>,
     Extension(user): Extension<User>,
-    Path(media_id): Path<Uuid>,
+    Path(id): Path<MediaIdParam>,
 ) -> Result<Json<ApiResponse<Option<ProgressResponse>>>, (StatusCode, String)> {
+    let media_id = playable_uuid(id)?;
+
     // Get user's watch state
     let watch_state = state
         .unit_of_work()
@@ -240,9 +345,15 @@ pub async fn get_media_progress_handler(
 pub async fn mark_completed_handler(
     State(state): State<AppState>,
     Extension(user): Extension<User>,
-    Path(media_id): Path<Uuid>,
-    Path(media_type): Path<VideoMediaType>,
+    Path(id): Path<MediaIdParam>,
 ) -> Result<StatusCode, (StatusCode, String)> {
+    let media = id
+        .resolve(&state, PLAYABLE)
+        .await
+        .map_err(|err| (err.status, err.message))?;
+    let media_id = *media.as_uuid();
+    let media_type = VideoMediaType::from(media);
+
     // Create a progress update request with 100% completion
     let request = UpdateProgressRequest {
         media_id,
@@ -251,6 +362,8 @@ pub async fn mark_completed_handler(
         duration: 1.0, // Dummy duration to ensure 100% completion
         episode: None,
         last_media_uuid: Some(media_id),
+        reported_at: None,
+        reset: false,
     };
 
     // Update progress to mark as completed
@@ -259,22 +372,144 @@ pub async fn mark_completed_handler(
         .watch_status
         .update_watch_progress(user.id, &request)
         .await
+        .map_err(|e| progress_update_error("Failed to mark as completed", e))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Mark every episode of a series watched
+///
+/// Applied in one transaction; a single `media.watch_state_updated` event is
+/// sent to the user's media event streams.
+///
+/// # Response
+///
+/// - `200 OK` with the [`BulkWatchUpdate`] summary
+/// - `404 Not Found` if the series does not exist
+pub async fn mark_series_watched_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(series_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<BulkWatchUpdate>>, (StatusCode, String)> {
+    let result = WatchStatusService::new(state.unit_of_work())
+        .mark_series_watched(user.id, SeriesID(series_id))
+        .await;
+    bulk_watch_response(&state, user.id, result)
+}
+
+/// Clear watch state for every episode of a series
+pub async fn mark_series_unwatched_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(series_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<BulkWatchUpdate>>, (StatusCode, String)> {
+    let result = WatchStatusService::new(state.unit_of_work())
+        .mark_series_unwatched(user.id, SeriesID(series_id))
+        .await;
+    bulk_watch_response(&state, user.id, result)
+}
+
+/// Mark every episode of a season watched
+pub async fn mark_season_watched_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(season_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<BulkWatchUpdate>>, (StatusCode, String)> {
+    let result = WatchStatusService::new(state.unit_of_work())
+        .mark_season_watched(user.id, SeasonID(season_id))
+        .await;
+    bulk_watch_response(&state, user.id, result)
+}
+
+/// Clear watch state for every episode of a season
+pub async fn mark_season_unwatched_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path(season_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<BulkWatchUpdate>>, (StatusCode, String)> {
+    let result = WatchStatusService::new(state.unit_of_work())
+        .mark_season_unwatched(user.id, SeasonID(season_id))
+        .await;
+    bulk_watch_response(&state, user.id, result)
+}
+
+/// Mark one episode watched (identity-based)
+///
+/// # Request
+///
+/// ```json
+/// { "policy": "cascade", "series_wide": false }
+/// ```
+///
+/// The body is optional. `policy` is `strict` (default: only this episode
+/// changes) or `cascade` (earlier episodes of the season that are not
+/// completed yet are marked watched too). `series_wide: true` lets a
+/// cascade reach earlier seasons as well.
+///
+/// # Response
+///
+/// - `200 OK` with the recomputed [`SeasonWatchStatus`]
+pub async fn mark_episode_watched_handler(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Path((tmdb_series_id, season_number, episode_number)): Path<(
+        u64,
+        u16,
+        u16,
+    )>,
+    request: Option<Json<MarkEpisodeWatchedRequest>>,
+) -> Result<Json<ApiResponse<SeasonWatchStatus>>, (StatusCode, String)> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let key = EpisodeKey {
+        tmdb_series_id,
+        season_number,
+        episode_number,
+    };
+    let status = WatchStatusService::new(state.unit_of_work())
+        .mark_episode_watched(user.id, key, request)
+        .await
         .map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to mark as completed: {}", e),
+                format!("Failed to mark episode watched: {}", e),
             )
         })?;
+    Ok(Json(ApiResponse::success(status)))
+}
 
-    Ok(StatusCode::NO_CONTENT)
+fn bulk_watch_response(
+    state: &AppState,
+    user_id: Uuid,
+    result: Result<BulkWatchUpdate, MediaError>,
+) -> Result<Json<ApiResponse<BulkWatchUpdate>>, (StatusCode, String)> {
+    let update = result.map_err(|e| match e {
+        MediaError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+        other => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to update watch state: {}", other),
+        ),
+    })?;
+
+    state
+        .scan_control()
+        .publish_media_event(MediaEvent::WatchStateUpdated {
+            user_id,
+            series_id: update.series_id,
+            season_id: update.season_id,
+            watched: update.watched,
+            episodes: update.episodes,
+        });
+
+    Ok(Json(ApiResponse::success(update)))
 }
 
 /// Check if a media item is completed
 pub async fn is_completed_handler(
     State(state): State<AppState>,
     Extension(user): Extension<User>,
-    Path(media_id): Path<Uuid>,
+    Path(id): Path<MediaIdParam>,
 ) -> Result<Json<bool>, (StatusCode, String)> {
+    let media_id = playable_uuid(id)?;
     let is_completed = state
         .unit_of_work()
         .watch_status
@@ -2,7 +2,12 @@ use std::{fmt, sync::Arc};
 
 #[cfg(feature = "demo")]
 use crate::demo::DemoCoordinator;
+use crate::infra::hls::HlsSegmenter;
+use crate::infra::idempotency::{IdempotencyStore, RedisIdempotencyStore};
+use crate::infra::redis_pool::RedisPool;
+use crate::infra::subtitles::SubtitleExtractor;
 use crate::infra::thumbnail_service::ThumbnailService;
+use crate::infra::transcode::TranscodeManager;
 use crate::{
     application::auth::AuthApplicationFacade,
     infra::{
@@ -17,8 +22,9 @@ use ferrex_core::{
         PostgresDatabase, repository_ports::setup_claims::SetupClaimsRepository,
     },
     domain::users::auth::AuthCrypto,
-    infra::media::image_service::ImageService,
+    infra::{cache::CacheCipher, media::image_service::ImageService},
 };
+use tracing::warn;
 
 #[derive(Clone)]
 pub struct AppContext {
@@ -27,12 +33,16 @@ pub struct AppContext {
     postgres: Arc<PostgresDatabase>,
     scan_control: Arc<ScanControlPlane>,
     thumbnail_service: Arc<ThumbnailService>,
+    transcode_manager: Arc<TranscodeManager>,
+    hls_segmenter: Arc<HlsSegmenter>,
+    subtitle_extractor: Arc<SubtitleExtractor>,
     image_service: Arc<ImageService>,
     websocket_manager: Arc<ConnectionManager>,
     auth_facade: Arc<AuthApplicationFacade>,
     auth_crypto: Arc<AuthCrypto>,
     setup_claim_service: Arc<SetupClaimService<dyn SetupClaimsRepository>>,
-    cache_enabled: bool,
+    redis: Option<RedisPool>,
+    idempotency: Option<Arc<dyn IdempotencyStore>>,
     #[cfg(feature = "demo")]
     demo: Option<Arc<DemoCoordinator>>,
 }
@@ -56,26 +66,65 @@ impl AppContext {
         auth_facade: Arc<AuthApplicationFacade>,
         auth_crypto: Arc<AuthCrypto>,
         setup_claim_service: Arc<SetupClaimService<dyn SetupClaimsRepository>>,
-        cache_enabled: bool,
+        redis: Option<RedisPool>,
         #[cfg(feature = "demo")] demo: Option<Arc<DemoCoordinator>>,
     ) -> Self {
+        let transcode_manager = Arc::new(TranscodeManager::new(
+            config.ffmpeg.ffmpeg_path.clone(),
+            config.transcode_cache_dir().to_path_buf(),
+        ));
+        let mut hls_segmenter = HlsSegmenter::new(
+            config.ffmpeg.ffmpeg_path.clone(),
+            config.transcode_cache_dir(),
+            config.cache.hls_max_bytes,
+        );
+        if let Some(key) = config.cache_encryption_key() {
+            match CacheCipher::new(key) {
+                Ok(cipher) => hls_segmenter = hls_segmenter.with_cipher(cipher),
+                Err(err) => warn!("HLS segment encryption disabled: {err}"),
+            }
+        }
+        let hls_segmenter = Arc::new(hls_segmenter);
+        let idempotency = redis.clone().map(|redis| {
+            Arc::new(RedisIdempotencyStore::new(redis))
+                as Arc<dyn IdempotencyStore>
+        });
+        let subtitle_extractor = Arc::new(SubtitleExtractor::new(
+            config.ffmpeg.ffmpeg_path.clone(),
+            config.ffmpeg.ffprobe_path.clone(),
+            config.transcode_cache_dir(),
+        ));
         Self {
             config,
             unit_of_work,
             postgres,
             scan_control,
             thumbnail_service,
+            transcode_manager,
+            hls_segmenter,
+            subtitle_extractor,
             image_service,
             websocket_manager,
             auth_facade,
             auth_crypto,
             setup_claim_service,
-            cache_enabled,
+            redis,
+            idempotency,
             #[cfg(feature = "demo")]
             demo,
         }
     }
 
+    /// Replace the `Idempotency-Key` record store, which otherwise lives in
+    /// Redis when it is configured.
+    pub fn with_idempotency_store(
+        mut self,
+        store: Arc<dyn IdempotencyStore>,
+    ) -> Self {
+        self.idempotency = Some(store);
+        self
+    }
+
     pub fn config(&self) -> &Config {
         self.config.as_ref()
     }
@@ -85,7 +134,19 @@ impl AppContext {
     }
 
     pub fn cache_enabled(&self) -> bool {
-        self.cache_enabled
+        self.redis.is_some()
+    }
+
+    /// Redis connections; `None` when `REDIS_URL` is not configured or
+    /// Redis was unreachable at startup under the fail-open policy.
+    pub fn redis(&self) -> Option<&RedisPool> {
+        self.redis.as_ref()
+    }
+
+    /// Where `Idempotency-Key` records are kept; `None` without Redis, in
+    /// which case keys are ignored.
+    pub fn idempotency_store(&self) -> Option<Arc<dyn IdempotencyStore>> {
+        self.idempotency.clone()
     }
 
     pub fn unit_of_work(&self) -> Arc<AppUnitOfWork> {
@@ -104,6 +165,18 @@ impl AppContext {
         Arc::clone(&self.thumbnail_service)
     }
 
+    pub fn transcode_manager(&self) -> Arc<TranscodeManager> {
+        Arc::clone(&self.transcode_manager)
+    }
+
+    pub fn hls_segmenter(&self) -> Arc<HlsSegmenter> {
+        Arc::clone(&self.hls_segmenter)
+    }
+
+    pub fn subtitle_extractor(&self) -> Arc<SubtitleExtractor> {
+        Arc::clone(&self.subtitle_extractor)
+    }
+
     pub fn image_service(&self) -> Arc<ImageService> {
         Arc::clone(&self.image_service)
     }
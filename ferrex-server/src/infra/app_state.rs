@@ -1,4 +1,8 @@
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, OnceLock},
+};
 
 use anyhow::anyhow;
 use chrono::{DateTime, Duration, Utc};
@@ -10,8 +14,19 @@ use crate::application::auth::AuthApplicationFacade;
 use crate::infra::app_context::AppContext;
 use crate::infra::cache::{MovieBatchesCache, SeriesBundlesCache};
 use crate::infra::config::Config;
+use crate::infra::config_reload::LiveSettings;
+use crate::infra::hls::HlsSegmenter;
+use crate::infra::idempotency::IdempotencyStore;
+use crate::infra::maintenance::MaintenanceMode;
+use crate::infra::metadata_refresh::RefreshCoalescer;
+use crate::infra::readiness::Readiness;
+use crate::infra::redis_pool::RedisPool;
 use crate::infra::scan::scan_manager::ScanControlPlane;
+use crate::infra::stream_content_type::ContentTypeCache;
+use crate::infra::stream_limit::StreamLimiter;
+use crate::infra::subtitles::SubtitleExtractor;
 use crate::infra::thumbnail_service::ThumbnailService;
+use crate::infra::transcode::TranscodeManager;
 use crate::infra::websocket::ConnectionManager;
 use ferrex_core::application::unit_of_work::AppUnitOfWork;
 use ferrex_core::database::PostgresDatabase;
@@ -25,8 +40,10 @@ use ferrex_core::domain::users::auth::{
         },
         value_objects::SessionScope,
     },
+    rate_limit::RateLimiter,
 };
 use ferrex_core::infra::media::image_service::ImageService;
+use ferrex_core::types::Media;
 
 #[cfg(feature = "demo")]
 use crate::demo::DemoCoordinator;
@@ -38,8 +55,20 @@ pub struct AppState {
     pub admin_sessions: Arc<Mutex<HashMap<Uuid, AdminSessionInfo>>>,
     pub series_bundles_cache: Arc<SeriesBundlesCache>,
     pub movie_batches_cache: Arc<MovieBatchesCache>,
+    readiness: Readiness,
+    maintenance: MaintenanceMode,
+    metadata_refreshes: RefreshCoalescer<(Uuid, Option<u64>), Media>,
+    stream_content_types: ContentTypeCache,
+    streams: StreamLimiter,
+    transcodes: StreamLimiter,
+    image_subscriptions: StreamLimiter,
+    rate_limiter: Arc<OnceLock<Arc<dyn RateLimiter>>>,
+    live_settings: LiveSettings,
 }
 
+/// Open image readiness streams allowed at once, across all clients.
+const MAX_IMAGE_EVENT_SUBSCRIPTIONS: usize = 256;
+
 impl fmt::Debug for AppState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("AppState").finish_non_exhaustive()
@@ -79,14 +108,86 @@ impl AppState {
         series_bundles_cache: Arc<SeriesBundlesCache>,
         movie_batches_cache: Arc<MovieBatchesCache>,
     ) -> Self {
+        let streams =
+            StreamLimiter::new(context.config().server.max_concurrent_streams);
+        let transcodes = StreamLimiter::transcodes(
+            context.config().server.max_concurrent_transcodes,
+        );
+        let live_settings = LiveSettings::new(context.config());
         Self {
             context,
             admin_sessions,
             series_bundles_cache,
             movie_batches_cache,
+            readiness: Readiness::new(),
+            maintenance: MaintenanceMode::new(),
+            metadata_refreshes: RefreshCoalescer::new(),
+            stream_content_types: ContentTypeCache::new(),
+            streams,
+            transcodes,
+            image_subscriptions: StreamLimiter::image_subscriptions(Some(
+                MAX_IMAGE_EVENT_SUBSCRIPTIONS,
+            )),
+            rate_limiter: Arc::new(OnceLock::new()),
+            live_settings,
         }
     }
 
+    /// Readiness flag reported by `/ready`; shared by every clone.
+    pub fn readiness(&self) -> &Readiness {
+        &self.readiness
+    }
+
+    /// Runtime read-only toggle; shared by every clone.
+    pub fn maintenance(&self) -> &MaintenanceMode {
+        &self.maintenance
+    }
+
+    /// Coalesces forced metadata refreshes by `(media id, TMDB override)`.
+    pub fn metadata_refreshes(
+        &self,
+    ) -> &RefreshCoalescer<(Uuid, Option<u64>), Media> {
+        &self.metadata_refreshes
+    }
+
+    /// Sniffed content types of streamed files with unknown extensions.
+    pub fn stream_content_types(&self) -> &ContentTypeCache {
+        &self.stream_content_types
+    }
+
+    /// Cap on simultaneous direct-play streams; shared by every clone.
+    pub fn streams(&self) -> &StreamLimiter {
+        &self.streams
+    }
+
+    /// Cap on simultaneous live transcodes, taken on top of a stream slot.
+    pub fn transcodes(&self) -> &StreamLimiter {
+        &self.transcodes
+    }
+
+    /// Cap on open image readiness streams; shared by every clone.
+    pub fn image_subscriptions(&self) -> &StreamLimiter {
+        &self.image_subscriptions
+    }
+
+    /// Limiter guarding the auth endpoints; `None` when rate limiting is
+    /// not configured.
+    pub fn rate_limiter(&self) -> Option<Arc<dyn RateLimiter>> {
+        self.rate_limiter.get().cloned()
+    }
+
+    /// Share the limiter built by the router with every clone. Only the
+    /// first call has an effect.
+    pub fn install_rate_limiter(&self, limiter: Arc<dyn RateLimiter>) {
+        let _ = self.rate_limiter.set(limiter);
+    }
+
+    /// Settings a configuration reload can change; read them here rather
+    /// than from [`Self::config`] where reloads should take effect.
+    pub fn live_settings(&self) -> &LiveSettings {
+        &self.live_settings
+    }
+
     pub fn context(&self) -> &AppContext {
         &self.context
     }
@@ -107,6 +208,14 @@ impl AppState {
         self.context.cache_enabled()
     }
 
+    pub fn redis(&self) -> Option<&RedisPool> {
+        self.context.redis()
+    }
+
+    pub fn idempotency_store(&self) -> Option<Arc<dyn IdempotencyStore>> {
+        self.context.idempotency_store()
+    }
+
     pub fn unit_of_work(&self) -> Arc<AppUnitOfWork> {
         self.context.unit_of_work()
     }
@@ -123,6 +232,18 @@ impl AppState {
         self.context.thumbnail_service()
     }
 
+    pub fn transcode_manager(&self) -> Arc<TranscodeManager> {
+        self.context.transcode_manager()
+    }
+
+    pub fn hls_segmenter(&self) -> Arc<HlsSegmenter> {
+        self.context.hls_segmenter()
+    }
+
+    pub fn subtitle_extractor(&self) -> Arc<SubtitleExtractor> {
+        self.context.subtitle_extractor()
+    }
+
     pub fn image_service(&self) -> Arc<ImageService> {
         self.context.image_service()
     }
@@ -1,9 +1,11 @@
 pub use ferrexctl::{
     AuthConfig, CacheConfig, Config, ConfigLoad, ConfigLoadError, ConfigLoader,
-    ConfigMetadata, ConfigWarnings, CorsConfig, DatabaseConfig, FfmpegConfig,
-    HstsLayerConfig, HstsSettings, MediaConfig, RateLimitSource, RateLimitSpec,
-    RateLimiterConfig, RateLimiterSettings, RedisConfig, ScannerConfig,
-    SecurityConfig, ServerConfig, cli, loader, models,
+    ConfigMetadata, ConfigWarning, ConfigWarnings, CorsConfig, DatabaseConfig,
+    FfmpegConfig, HstsLayerConfig, HstsSettings, ImageStoreBackend, IpRange,
+    LibraryListingDefaults, LocaleConfig, LogFormat, MediaConfig,
+    RateLimitAllowlist, RateLimitSource, RateLimitSpec, RateLimiterConfig,
+    RateLimiterSettings, RedisConfig, RedisOutagePolicy, S3StoreConfig,
+    ScannerConfig, SecurityConfig, ServerConfig, cli, loader, models,
     models::{rate_limits, scanner, sources},
-    validation,
+    schema, validation,
 };
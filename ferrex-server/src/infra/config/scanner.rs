@@ -46,6 +46,15 @@ pub struct ScannerConfig {
     /// can flow through without diverging behaviour.
     #[serde(default = "default_video_extensions")]
     pub video_extensions: Vec<String>,
+    /// Download the primary poster of every newly added movie and series once
+    /// a scan completes, so the first library view is not a grid of blanks.
+    /// Off by default; the pass fetches one poster at a time to leave the
+    /// shared image download permits free for on-demand requests.
+    pub prewarm_posters: bool,
+    /// Library media events kept in memory so reconnecting players can replay
+    /// what they missed via `Last-Event-ID`. Players that fall further behind
+    /// are told to reload the library instead.
+    pub media_event_history: usize,
 }
 
 impl Default for ScannerConfig {
@@ -64,6 +73,8 @@ impl Default for ScannerConfig {
             library_actor_max_outstanding_jobs: 32,
             quiescence_window_ms: 5_000,
             video_extensions: default_video_extensions(),
+            prewarm_posters: false,
+            media_event_history: 512,
         }
     }
 }
@@ -0,0 +1,475 @@
+//! Configuration reloads triggered by `SIGHUP`.
+//!
+//! Only settings consulted per request can change under a running server:
+//! the log level, the CORS allowed origins, and the rate limiter's endpoint
+//! limits and allowlist. Everything else (listen address, database, Redis,
+//! cache directories, ...) is baked into resources built at startup, so a
+//! reload reports changes to it as deferred until the next restart.
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+};
+
+use ferrex_model::rate_limit::EndpointLimits;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+
+use crate::infra::{
+    config::{Config, ConfigWarnings},
+    middleware::RateLimitBypass,
+};
+
+/// Handle swapping the log filter installed at startup.
+pub type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// The part of the configuration a reload can change.
+#[derive(Debug, Clone)]
+pub struct HotSettings {
+    pub cors_origins: Vec<String>,
+    /// Endpoint limits of the rate limiter; `None` without one.
+    pub rate_limits: Option<EndpointLimits>,
+    pub rate_limit_bypass: RateLimitBypass,
+}
+
+impl HotSettings {
+    pub fn from_config(config: &Config) -> Self {
+        let rate_limiter = config.rate_limiter.as_ref();
+        Self {
+            cors_origins: config.cors.allowed_origins.clone(),
+            rate_limits: rate_limiter
+                .map(|settings| settings.config.endpoint_limits.clone()),
+            rate_limit_bypass: rate_limiter
+                .map(|settings| {
+                    RateLimitBypass::from_config(&settings.config.allowlist)
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether browsers may call the API from `origin`. An empty list
+    /// allows every origin unless credentials are sent.
+    pub fn allows_origin(&self, origin: &[u8], credentials: bool) -> bool {
+        if self.cors_origins.is_empty() {
+            return !credentials;
+        }
+        self.cors_origins
+            .iter()
+            .any(|allowed| allowed.as_bytes() == origin)
+    }
+}
+
+/// The configuration in effect and the warnings raised while loading it.
+#[derive(Debug, Clone)]
+pub struct EffectiveConfig {
+    pub config: Config,
+    pub warnings: ConfigWarnings,
+}
+
+/// Current [`HotSettings`] and [`EffectiveConfig`], shared by the
+/// middleware, the admin API and the reloader.
+#[derive(Debug, Clone)]
+pub struct LiveSettings {
+    current: Arc<RwLock<Arc<HotSettings>>>,
+    effective: Arc<RwLock<Arc<EffectiveConfig>>>,
+}
+
+impl LiveSettings {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(HotSettings::from_config(
+                config,
+            )))),
+            effective: Arc::new(RwLock::new(Arc::new(EffectiveConfig {
+                config: config.clone(),
+                warnings: ConfigWarnings::default(),
+            }))),
+        }
+    }
+
+    pub fn current(&self) -> Arc<HotSettings> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    pub fn effective(&self) -> Arc<EffectiveConfig> {
+        Arc::clone(
+            &self
+                .effective
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    /// Keep the warnings of the startup load for the admin API.
+    pub fn record_warnings(&self, warnings: ConfigWarnings) {
+        let mut effective = self
+            .effective
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        *effective = Arc::new(EffectiveConfig {
+            config: effective.config.clone(),
+            warnings,
+        });
+    }
+
+    fn replace(&self, active: &Config, warnings: ConfigWarnings) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) =
+            Arc::new(HotSettings::from_config(active));
+        *self
+            .effective
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) =
+            Arc::new(EffectiveConfig {
+                config: active.clone(),
+                warnings,
+            });
+    }
+}
+
+/// What one reload changed.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReloadSummary {
+    /// Changes in effect as of this reload.
+    pub applied: Vec<String>,
+    /// Changes that wait for a restart.
+    pub deferred: Vec<String>,
+}
+
+impl ReloadSummary {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.deferred.is_empty()
+    }
+}
+
+/// Applies reloaded configurations to the running server.
+pub struct ConfigReloader {
+    /// The configuration in effect: the startup one with every applied
+    /// change merged in, so deferred changes are reported again until the
+    /// server restarts.
+    active: Mutex<Config>,
+    live: LiveSettings,
+    /// `None` when `RUST_LOG` overrides the configured level.
+    log_filter: Option<LogFilterHandle>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        active: Config,
+        live: LiveSettings,
+        log_filter: Option<LogFilterHandle>,
+    ) -> Self {
+        Self {
+            active: Mutex::new(active),
+            live,
+            log_filter,
+        }
+    }
+
+    /// The configuration in effect, including changes applied by reloads.
+    pub fn active(&self) -> Config {
+        self.active
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Apply the settings of `loaded` that can change at runtime. `loaded`
+    /// must already have passed validation; `warnings` are the ones raised
+    /// while loading it.
+    pub fn apply(
+        &self,
+        loaded: &Config,
+        warnings: ConfigWarnings,
+    ) -> ReloadSummary {
+        let mut active =
+            self.active.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut summary = merge_hot_settings(&mut active, loaded);
+
+        if let Some(index) = summary
+            .applied
+            .iter()
+            .position(|change| change.starts_with(LOG_LEVEL))
+        {
+            let level = active
+                .server
+                .log_level
+                .as_deref()
+                .unwrap_or(crate::infra::constants::DEFAULT_LOG_FILTER);
+            let swapped = match &self.log_filter {
+                Some(handle) => EnvFilter::try_new(level)
+                    .map_err(|err| err.to_string())
+                    .and_then(|filter| {
+                        handle.reload(filter).map_err(|err| err.to_string())
+                    }),
+                None => Err("RUST_LOG is set and takes precedence".into()),
+            };
+            if let Err(reason) = swapped {
+                let change = summary.applied.remove(index);
+                summary.deferred.push(format!("{change} ({reason})"));
+            }
+        }
+
+        self.live.replace(&active, warnings);
+        summary
+    }
+}
+
+const LOG_LEVEL: &str = "log level";
+
+/// Copy the runtime-changeable settings of `loaded` into `active` and list
+/// every difference between the two.
+pub fn merge_hot_settings(
+    active: &mut Config,
+    loaded: &Config,
+) -> ReloadSummary {
+    let mut summary = ReloadSummary::default();
+
+    if active.server.log_level != loaded.server.log_level {
+        summary.applied.push(format!(
+            "{LOG_LEVEL}: {} -> {}",
+            active.server.log_level.as_deref().unwrap_or("default"),
+            loaded.server.log_level.as_deref().unwrap_or("default")
+        ));
+        active.server.log_level = loaded.server.log_level.clone();
+    }
+
+    if active.cors.allowed_origins != loaded.cors.allowed_origins {
+        summary.applied.push(format!(
+            "CORS allowed origins: {:?} -> {:?}",
+            active.cors.allowed_origins, loaded.cors.allowed_origins
+        ));
+        active.cors.allowed_origins = loaded.cors.allowed_origins.clone();
+    }
+
+    match (active.rate_limiter.as_mut(), loaded.rate_limiter.as_ref()) {
+        (Some(active), Some(loaded)) => {
+            let (active, loaded) = (&mut active.config, &loaded.config);
+            if differs(&active.endpoint_limits, &loaded.endpoint_limits) {
+                summary.applied.push("rate limit endpoint limits".into());
+                active.endpoint_limits = loaded.endpoint_limits.clone();
+            }
+            if active.allowlist != loaded.allowlist {
+                summary.applied.push("rate limit allowlist".into());
+                active.allowlist = loaded.allowlist.clone();
+            }
+        }
+        (None, None) => {}
+        (active, _) => {
+            let change = if active.is_some() {
+                "disabled"
+            } else {
+                "enabled"
+            };
+            summary.deferred.push(format!("rate limiter {change}"));
+        }
+    }
+
+    let sections: [(&str, bool); 13] = [
+        ("server", differs(&active.server, &loaded.server)),
+        ("database", differs(&active.database, &loaded.database)),
+        ("redis", differs(&active.redis, &loaded.redis)),
+        ("media", differs(&active.media, &loaded.media)),
+        ("locale", active.locale != loaded.locale),
+        ("cache", differs(&active.cache, &loaded.cache)),
+        ("ffmpeg", differs(&active.ffmpeg, &loaded.ffmpeg)),
+        ("cors", differs(&active.cors, &loaded.cors)),
+        ("security", differs(&active.security, &loaded.security)),
+        ("dev_mode", active.dev_mode != loaded.dev_mode),
+        ("auth", differs(&active.auth, &loaded.auth)),
+        (
+            "scanner",
+            serde_json::to_value(&active.scanner).ok()
+                != serde_json::to_value(&loaded.scanner).ok(),
+        ),
+        (
+            "rate_limiter",
+            match (&active.rate_limiter, &loaded.rate_limiter) {
+                (Some(active), Some(loaded)) => {
+                    differs(&active.config, &loaded.config)
+                }
+                _ => false,
+            },
+        ),
+    ];
+    summary.deferred.extend(
+        sections
+            .into_iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(section, _)| format!("{section} settings")),
+    );
+
+    summary
+}
+
+/// The config types do not implement `PartialEq`; their `Debug` output
+/// covers every field. The scanner config holds maps whose `Debug` order
+/// varies, so it is compared through its serialized form instead.
+fn differs<T: Debug>(active: &T, loaded: &T) -> bool {
+    format!("{active:?}") != format!("{loaded:?}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::infra::config::{
+        AuthConfig, CacheConfig, ConfigMetadata, CorsConfig, DatabaseConfig,
+        FfmpegConfig, HstsSettings, LocaleConfig, MediaConfig, RateLimitSource,
+        RateLimiterConfig, RateLimiterSettings, ScannerConfig, SecurityConfig,
+        ServerConfig,
+    };
+
+    fn config() -> Config {
+        Config {
+            server: ServerConfig {
+                host: "0.0.0.0".into(),
+                port: 3000,
+                request_id_header: "x-request-id".into(),
+                request_timeout: None,
+                slow_request_timeout: None,
+                max_concurrent_streams: None,
+                max_concurrent_transcodes: None,
+                stream_bandwidth_limit_kbps: None,
+                remote_media_redirect_hosts: Vec::new(),
+                compression_enabled: true,
+                compression_min_bytes: 1024,
+                log_format: Default::default(),
+                log_level: Some("info".into()),
+            },
+            database: DatabaseConfig {
+                primary_url: Some("postgres://ferrex@db/ferrex".into()),
+            },
+            redis: None,
+            media: MediaConfig {
+                root: None,
+                metadata_languages: Vec::new(),
+                filename_rules: Vec::new(),
+                movie_defaults: Default::default(),
+                series_defaults: Default::default(),
+            },
+            locale: LocaleConfig::default(),
+            cache: CacheConfig {
+                root: "/cache".into(),
+                images: "/cache/images".into(),
+                transcode: "/cache/transcode".into(),
+                thumbnails: "/cache/thumbnails".into(),
+                hls_max_bytes: 0,
+                min_free_bytes: 0,
+                image_failure_ttl_secs: 0,
+                encrypt_at_rest: false,
+                encryption_key: None,
+                image_store: Default::default(),
+                image_store_s3: None,
+            },
+            ffmpeg: FfmpegConfig {
+                ffmpeg_path: "ffmpeg".into(),
+                ffprobe_path: "ffprobe".into(),
+                thumbnail_strategy: Default::default(),
+                thumbnail_width: 320,
+                thumbnail_max_height: None,
+                thumbnail_quality: 85,
+            },
+            cors: CorsConfig {
+                allowed_origins: vec!["https://ferrex.example".into()],
+                allowed_methods: vec!["GET".into()],
+                allowed_headers: vec!["authorization".into()],
+                allow_credentials: true,
+            },
+            security: SecurityConfig {
+                enforce_https: false,
+                trust_proxy_headers: false,
+                trusted_proxies: Vec::new(),
+                require_auth_for_browsing: true,
+                hsts: HstsSettings {
+                    max_age: 31_536_000,
+                    include_subdomains: false,
+                    preload: false,
+                },
+            },
+            dev_mode: false,
+            auth: AuthConfig {
+                password_pepper: "pepper".into(),
+                token_key: "key".into(),
+                setup_token: None,
+                session_ttl: Duration::from_secs(3_600),
+                refresh_ttl: Duration::from_secs(86_400),
+                device_challenge_ttl: Duration::from_secs(120),
+                clock_skew_leeway: Duration::from_secs(5),
+                strict_device_ids: false,
+            },
+            scanner: ScannerConfig::default(),
+            rate_limiter: Some(RateLimiterSettings {
+                config: RateLimiterConfig::default(),
+                source: RateLimitSource::EnvInline,
+            }),
+            metadata: ConfigMetadata::default(),
+        }
+    }
+
+    #[test]
+    fn identical_configs_change_nothing() {
+        let mut active = config();
+        assert!(merge_hot_settings(&mut active, &config()).is_empty());
+    }
+
+    #[test]
+    fn hot_settings_apply_and_structural_ones_wait() {
+        let mut active = config();
+        let mut loaded = config();
+        loaded.server.log_level = Some("debug".into());
+        loaded.server.port = 4000;
+        loaded
+            .cors
+            .allowed_origins
+            .push("https://tv.example".into());
+        loaded.database.primary_url = Some("postgres://ferrex@db2/f".into());
+        let limits = &mut loaded.rate_limiter.as_mut().unwrap().config;
+        limits.endpoint_limits.login.limit += 5;
+        limits.allowlist.ip_ranges.push("10.0.0.0/8".into());
+
+        let summary = merge_hot_settings(&mut active, &loaded);
+        assert_eq!(
+            summary.applied,
+            [
+                "log level: info -> debug".to_string(),
+                "CORS allowed origins: [\"https://ferrex.example\"] -> \
+                 [\"https://ferrex.example\", \"https://tv.example\"]"
+                    .to_string(),
+                "rate limit endpoint limits".to_string(),
+                "rate limit allowlist".to_string(),
+            ]
+        );
+        assert_eq!(summary.deferred, ["server settings", "database settings"]);
+
+        // Applied values are now active; the deferred ones are not.
+        assert_eq!(active.server.log_level.as_deref(), Some("debug"));
+        assert_eq!(active.cors.allowed_origins.len(), 2);
+        assert_eq!(active.server.port, 3000);
+        let again = merge_hot_settings(&mut active, &loaded);
+        assert!(again.applied.is_empty());
+        assert_eq!(again.deferred, ["server settings", "database settings"]);
+    }
+
+    #[test]
+    fn toggling_the_rate_limiter_needs_a_restart() {
+        let mut active = config();
+        let mut loaded = config();
+        loaded.rate_limiter = None;
+
+        let summary = merge_hot_settings(&mut active, &loaded);
+        assert!(summary.applied.is_empty());
+        assert_eq!(summary.deferred, ["rate limiter disabled"]);
+        assert!(active.rate_limiter.is_some());
+    }
+
+    #[test]
+    fn empty_origin_list_allows_anyone_without_credentials() {
+        let mut settings = HotSettings::from_config(&config());
+        assert!(settings.allows_origin(b"https://ferrex.example", true));
+        assert!(!settings.allows_origin(b"https://evil.example", true));
+
+        settings.cors_origins.clear();
+        assert!(settings.allows_origin(b"https://evil.example", false));
+        assert!(!settings.allows_origin(b"https://evil.example", true));
+    }
+}
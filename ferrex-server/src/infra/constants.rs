@@ -1,4 +1,8 @@
 //! Shared constants for infra modules.
 
+/// Log filter used when neither `RUST_LOG` nor `LOG_LEVEL` is set.
+pub const DEFAULT_LOG_FILTER: &str =
+    "info,scan::summary=info,scan::queue=info,scan::seed=info,tower_http=warn";
+
 pub const DEFAULT_PASSWORD_PEPPER: &str = "change-me-password-pepper";
 pub const DEFAULT_TOKEN_KEY: &str = "change-me-hmac-key";
@@ -3,14 +3,13 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
-use serde_json::json;
 use std::fmt;
 
-use ferrex_core::error::MediaError;
+use ferrex_core::{api::types::ApiErrorBody, error::MediaError};
 
 pub type AppResult<T> = Result<T, AppError>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AppError {
     pub status: StatusCode,
     pub message: String,
@@ -67,12 +66,7 @@ impl std::error::Error for AppError {}
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let body = Json(json!({
-            "error": {
-                "message": self.message,
-                "status": self.status.as_u16(),
-            }
-        }));
+        let body = Json(ApiErrorBody::new(self.status.as_u16(), self.message));
 
         (self.status, body).into_response()
     }
@@ -83,7 +77,11 @@ impl From<MediaError> for AppError {
     fn from(err: MediaError) -> Self {
         match err {
             MediaError::NotFound(msg) => Self::not_found(msg),
+            MediaError::Conflict(msg) => Self::conflict(msg),
             MediaError::Internal(msg) => Self::internal(msg),
+            MediaError::InsufficientDiskSpace { .. } => {
+                Self::new(StatusCode::INSUFFICIENT_STORAGE, err.to_string())
+            }
             _ => Self::internal(err.to_string()),
         }
     }
@@ -0,0 +1,696 @@
+//! Adaptive HLS built from on-demand segments.
+//!
+//! Unlike [`TranscodeManager`](super::transcode::TranscodeManager), nothing
+//! is encoded up front: playlists are synthesized from the probed duration
+//! and each segment is cut by its own short ffmpeg run the first time a
+//! client asks for it. Finished segments are kept under
+//! `<transcode cache>/hls/<media id>/<variant>/` and evicted least recently
+//! used first once the cache exceeds its byte bound.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::{Arc, Mutex as StdMutex},
+};
+
+use ferrex_core::{infra::cache::CacheCipher, types::MediaFile};
+use tokio::{
+    process::Command,
+    sync::{Mutex, OnceCell},
+};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Playlist name used for every variant.
+pub const VARIANT_PLAYLIST: &str = "index.m3u8";
+const SEGMENT_SECONDS: f64 = 6.0;
+const AUDIO_KBPS: u32 = 128;
+
+/// Ladder rungs as `(height, video kbit/s)`, highest first.
+const LADDER: [(u32, u32); 5] = [
+    (2160, 16_000),
+    (1080, 6_000),
+    (720, 3_000),
+    (480, 1_200),
+    (360, 700),
+];
+
+/// Cap applied when the source height was never probed.
+const UNKNOWN_SOURCE_CAP: u32 = 1080;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HlsError {
+    #[error("ffmpeg is not available on this server")]
+    FfmpegUnavailable,
+    #[error("unknown HLS variant")]
+    VariantNotFound,
+    #[error("segment not found")]
+    SegmentNotFound,
+    #[error("media duration is unknown; rescan the library to probe it")]
+    DurationUnknown,
+    #[error("segment encode failed: {0}")]
+    Encode(String),
+    #[error("HLS cache error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// One rendition offered in the master playlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HlsVariant {
+    /// Path component, e.g. `720p`.
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub video_kbps: u32,
+}
+
+impl HlsVariant {
+    fn bandwidth(&self) -> u64 {
+        u64::from(self.video_kbps + AUDIO_KBPS) * 1000
+    }
+
+    /// H.264 high profile; level 5.1 above 1080p, 4.0 otherwise.
+    fn level(&self) -> &'static str {
+        if self.height > 1080 { "5.1" } else { "4.0" }
+    }
+
+    fn codecs(&self) -> &'static str {
+        if self.height > 1080 {
+            "avc1.640033,mp4a.40.2"
+        } else {
+            "avc1.640028,mp4a.40.2"
+        }
+    }
+}
+
+/// Variants for a source of the given size, highest first.
+///
+/// Only rungs at or below the source height are offered so the source is
+/// never upscaled. A source smaller than the lowest rung gets a single
+/// variant at its own height.
+pub fn variant_ladder(
+    source_width: Option<u32>,
+    source_height: Option<u32>,
+) -> Vec<HlsVariant> {
+    let cap = source_height.unwrap_or(UNKNOWN_SOURCE_CAP);
+    let aspect = match (source_width, source_height) {
+        (Some(w), Some(h)) if w > 0 && h > 0 => f64::from(w) / f64::from(h),
+        _ => 16.0 / 9.0,
+    };
+    let variant = |height: u32, video_kbps: u32| {
+        // Encoders need even dimensions.
+        let width = ((f64::from(height) * aspect / 2.0).round() as u32) * 2;
+        HlsVariant {
+            name: format!("{}p", height),
+            width,
+            height,
+            video_kbps,
+        }
+    };
+
+    let mut variants: Vec<_> = LADDER
+        .iter()
+        .filter(|(height, _)| *height <= cap)
+        .map(|&(height, kbps)| variant(height, kbps))
+        .collect();
+    if variants.is_empty() {
+        let (_, lowest_kbps) = LADDER[LADDER.len() - 1];
+        variants.push(variant(cap - cap % 2, lowest_kbps));
+    }
+    variants
+}
+
+/// Master playlist listing `variants`; URIs are relative to the master.
+pub fn master_playlist(variants: &[HlsVariant]) -> String {
+    let mut out = String::from("#EXTM3U\n#EXT-X-VERSION:3\n");
+    for variant in variants {
+        out.push_str(&format!(
+            "#EXT-X-STREAM-INF:BANDWIDTH={},RESOLUTION={}x{},CODECS=\"{}\"\n",
+            variant.bandwidth(),
+            variant.width,
+            variant.height,
+            variant.codecs()
+        ));
+        out.push_str(&format!("hls/{}/{}\n", variant.name, VARIANT_PLAYLIST));
+    }
+    out
+}
+
+/// VOD media playlist covering `duration` seconds in fixed-size segments.
+pub fn media_playlist(duration: f64) -> String {
+    let count = segment_count(duration);
+    let mut out = format!(
+        "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:{}\n\
+         #EXT-X-MEDIA-SEQUENCE:0\n#EXT-X-PLAYLIST-TYPE:VOD\n",
+        SEGMENT_SECONDS.ceil() as u32
+    );
+    for index in 0..count {
+        let length = segment_length(duration, index);
+        out.push_str(&format!(
+            "#EXTINF:{:.3},\n{}\n",
+            length,
+            segment_name(index)
+        ));
+    }
+    out.push_str("#EXT-X-ENDLIST\n");
+    out
+}
+
+fn segment_count(duration: f64) -> u32 {
+    (duration / SEGMENT_SECONDS).ceil().max(0.0) as u32
+}
+
+fn segment_length(duration: f64, index: u32) -> f64 {
+    (duration - f64::from(index) * SEGMENT_SECONDS).min(SEGMENT_SECONDS)
+}
+
+fn segment_name(index: u32) -> String {
+    format!("{:05}.ts", index)
+}
+
+/// Parse `00012.ts` back into its index.
+fn parse_segment_name(name: &str) -> Option<u32> {
+    let digits = name.strip_suffix(".ts")?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Byte-bounded LRU over cached segment files.
+#[derive(Debug, Default)]
+struct SegmentLru {
+    entries: HashMap<PathBuf, (u64, u64)>,
+    order: BTreeMap<u64, PathBuf>,
+    total_bytes: u64,
+    tick: u64,
+}
+
+impl SegmentLru {
+    fn touch(&mut self, path: &Path) {
+        if let Some((_, last_used)) = self.entries.get_mut(path) {
+            self.order.remove(last_used);
+            self.tick += 1;
+            *last_used = self.tick;
+            self.order.insert(self.tick, path.to_path_buf());
+        }
+    }
+
+    /// Record a new segment and return the files to delete so the cache
+    /// fits in `max_bytes`. The new segment itself is never evicted.
+    fn insert(
+        &mut self,
+        path: PathBuf,
+        size: u64,
+        max_bytes: u64,
+    ) -> Vec<PathBuf> {
+        self.tick += 1;
+        if let Some((old_size, old_tick)) =
+            self.entries.insert(path.clone(), (size, self.tick))
+        {
+            self.order.remove(&old_tick);
+            self.total_bytes -= old_size;
+        }
+        self.order.insert(self.tick, path.clone());
+        self.total_bytes += size;
+
+        let mut evicted = Vec::new();
+        while self.total_bytes > max_bytes {
+            let Some((&oldest, victim)) = self.order.iter().next() else {
+                break;
+            };
+            if *victim == path {
+                break;
+            }
+            let victim = victim.clone();
+            self.order.remove(&oldest);
+            if let Some((victim_size, _)) = self.entries.remove(&victim) {
+                self.total_bytes -= victim_size;
+            }
+            evicted.push(victim);
+        }
+        evicted
+    }
+
+    /// Cached bytes per media file, keyed by the directory directly below
+    /// `cache_dir`.
+    fn bytes_by_media(&self, cache_dir: &Path) -> HashMap<Uuid, u64> {
+        let mut totals = HashMap::new();
+        for (path, (size, _)) in &self.entries {
+            let media_id = path
+                .strip_prefix(cache_dir)
+                .ok()
+                .and_then(|relative| relative.components().next())
+                .and_then(|dir| dir.as_os_str().to_str())
+                .and_then(|dir| Uuid::parse_str(dir).ok());
+            if let Some(media_id) = media_id {
+                *totals.entry(media_id).or_default() += size;
+            }
+        }
+        totals
+    }
+}
+
+/// Produces and caches HLS segments on demand.
+pub struct HlsSegmenter {
+    ffmpeg_path: String,
+    cache_dir: PathBuf,
+    max_bytes: u64,
+    ffmpeg_available: OnceCell<bool>,
+    in_flight: Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>,
+    lru: StdMutex<SegmentLru>,
+    cipher: Option<CacheCipher>,
+}
+
+impl std::fmt::Debug for HlsSegmenter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cached_bytes = self.lru.lock().ok().map(|lru| lru.total_bytes);
+        f.debug_struct("HlsSegmenter")
+            .field("ffmpeg_path", &self.ffmpeg_path)
+            .field("cache_dir", &self.cache_dir)
+            .field("max_bytes", &self.max_bytes)
+            .field("cached_bytes", &cached_bytes)
+            .field("encrypted", &self.cipher.is_some())
+            .finish()
+    }
+}
+
+impl HlsSegmenter {
+    /// Segments left over from a previous run are not tracked by the LRU,
+    /// so the cache directory starts out empty.
+    pub fn new(
+        ffmpeg_path: String,
+        transcode_cache_dir: &Path,
+        max_bytes: u64,
+    ) -> Self {
+        let cache_dir = transcode_cache_dir.join("hls");
+        if let Err(err) = std::fs::remove_dir_all(&cache_dir)
+            && err.kind() != std::io::ErrorKind::NotFound
+        {
+            warn!(dir = %cache_dir.display(), error = %err, "failed to clear HLS cache");
+        }
+        Self {
+            ffmpeg_path,
+            cache_dir,
+            max_bytes,
+            ffmpeg_available: OnceCell::new(),
+            in_flight: Mutex::new(HashMap::new()),
+            lru: StdMutex::new(SegmentLru::default()),
+            cipher: None,
+        }
+    }
+
+    /// Seal segments on disk with `cipher`. The byte budget counts the
+    /// sealed size.
+    pub fn with_cipher(mut self, cipher: CacheCipher) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Bytes of cached segments per media file id.
+    pub fn cached_bytes_by_media(&self) -> HashMap<Uuid, u64> {
+        self.lru
+            .lock()
+            .map(|lru| lru.bytes_by_media(&self.cache_dir))
+            .unwrap_or_default()
+    }
+
+    /// Fail with [`HlsError::FfmpegUnavailable`] unless `ffmpeg -version`
+    /// runs. Probed once per process.
+    pub async fn ensure_ffmpeg(&self) -> Result<(), HlsError> {
+        let available = *self
+            .ffmpeg_available
+            .get_or_init(|| async {
+                Command::new(&self.ffmpeg_path)
+                    .arg("-version")
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status()
+                    .await
+                    .is_ok_and(|status| status.success())
+            })
+            .await;
+        if available {
+            Ok(())
+        } else {
+            Err(HlsError::FfmpegUnavailable)
+        }
+    }
+
+    /// Ladder offered for `media_file`.
+    pub fn variants(media_file: &MediaFile) -> Vec<HlsVariant> {
+        let meta = media_file.media_file_metadata.as_ref();
+        variant_ladder(
+            meta.and_then(|meta| meta.width),
+            meta.and_then(|meta| meta.height),
+        )
+    }
+
+    /// Look up a variant of `media_file` by its path component.
+    pub fn variant(
+        media_file: &MediaFile,
+        name: &str,
+    ) -> Result<HlsVariant, HlsError> {
+        Self::variants(media_file)
+            .into_iter()
+            .find(|variant| variant.name == name)
+            .ok_or(HlsError::VariantNotFound)
+    }
+
+    pub fn duration(media_file: &MediaFile) -> Result<f64, HlsError> {
+        media_file
+            .media_file_metadata
+            .as_ref()
+            .and_then(|meta| meta.duration)
+            .filter(|duration| *duration > 0.0)
+            .ok_or(HlsError::DurationUnknown)
+    }
+
+    /// Bytes of segment `segment` (e.g. `00003.ts`) of `variant`, encoding
+    /// it first if it is not cached. Concurrent requests for the same
+    /// segment share one ffmpeg run.
+    pub async fn segment(
+        &self,
+        media_file: &MediaFile,
+        variant: &HlsVariant,
+        segment: &str,
+    ) -> Result<Vec<u8>, HlsError> {
+        let duration = Self::duration(media_file)?;
+        let index = parse_segment_name(segment)
+            .filter(|index| *index < segment_count(duration))
+            .ok_or(HlsError::SegmentNotFound)?;
+        let path = self.segment_path(media_file.id, variant, index);
+
+        if let Some(bytes) = self.read_cached(&path).await? {
+            return Ok(bytes);
+        }
+
+        self.ensure_ffmpeg().await?;
+        let lock = Arc::clone(
+            self.in_flight.lock().await.entry(path.clone()).or_default(),
+        );
+        let guard = lock.lock().await;
+
+        // Another request may have produced it while we waited.
+        if let Some(bytes) = self.read_cached(&path).await? {
+            return Ok(bytes);
+        }
+
+        let result = self
+            .encode(media_file, variant, index, duration, &path)
+            .await;
+        drop(guard);
+        self.in_flight.lock().await.remove(&path);
+        result?;
+
+        let stored = tokio::fs::read(&path).await?;
+        let stored_len = stored.len() as u64;
+        let bytes = self.open(stored)?;
+        let evicted = self
+            .lru
+            .lock()
+            .map(|mut lru| lru.insert(path.clone(), stored_len, self.max_bytes))
+            .unwrap_or_default();
+        for victim in evicted {
+            debug!(path = %victim.display(), "evicting HLS segment");
+            if let Err(err) = tokio::fs::remove_file(&victim).await
+                && err.kind() != std::io::ErrorKind::NotFound
+            {
+                warn!(path = %victim.display(), error = %err, "failed to evict HLS segment");
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn segment_path(
+        &self,
+        media_id: Uuid,
+        variant: &HlsVariant,
+        index: u32,
+    ) -> PathBuf {
+        self.cache_dir
+            .join(media_id.to_string())
+            .join(&variant.name)
+            .join(segment_name(index))
+    }
+
+    async fn read_cached(
+        &self,
+        path: &Path,
+    ) -> Result<Option<Vec<u8>>, HlsError> {
+        match tokio::fs::read(path).await {
+            Ok(stored) => {
+                let Ok(bytes) = self.open(stored) else {
+                    // Sealed with another key; encode it again.
+                    warn!(path = %path.display(), "discarding unreadable HLS segment");
+                    let _ = tokio::fs::remove_file(path).await;
+                    return Ok(None);
+                };
+                if let Ok(mut lru) = self.lru.lock() {
+                    lru.touch(path);
+                }
+                Ok(Some(bytes))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn open(&self, stored: Vec<u8>) -> Result<Vec<u8>, HlsError> {
+        match &self.cipher {
+            Some(cipher) => cipher
+                .open(&stored)
+                .map_err(|err| HlsError::Encode(err.to_string())),
+            None => Ok(stored),
+        }
+    }
+
+    async fn encode(
+        &self,
+        media_file: &MediaFile,
+        variant: &HlsVariant,
+        index: u32,
+        duration: f64,
+        path: &Path,
+    ) -> Result<(), HlsError> {
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        // Write to a temporary name so a half-written segment is never
+        // served from the cache.
+        let partial = path.with_extension("ts.part");
+        let source_height = media_file
+            .media_file_metadata
+            .as_ref()
+            .and_then(|meta| meta.height);
+        let args = segment_args(
+            &media_file.path,
+            &partial,
+            variant,
+            source_height,
+            f64::from(index) * SEGMENT_SECONDS,
+            segment_length(duration, index),
+        );
+
+        let output = Command::new(&self.ffmpeg_path)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|_| HlsError::FfmpegUnavailable)?;
+
+        if !output.status.success() {
+            let _ = tokio::fs::remove_file(&partial).await;
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.lines().last().unwrap_or_default().to_string();
+            warn!(
+                media = %media_file.id,
+                variant = %variant.name,
+                segment = index,
+                %reason,
+                "HLS segment encode failed"
+            );
+            return Err(HlsError::Encode(reason));
+        }
+        if let Some(cipher) = &self.cipher {
+            let plain = tokio::fs::read(&partial).await?;
+            let sealed = cipher
+                .seal(&plain)
+                .map_err(|err| HlsError::Encode(err.to_string()))?;
+            tokio::fs::write(&partial, sealed).await?;
+        }
+        tokio::fs::rename(&partial, path).await?;
+        Ok(())
+    }
+}
+
+fn segment_args(
+    input: &Path,
+    output: &Path,
+    variant: &HlsVariant,
+    source_height: Option<u32>,
+    start: f64,
+    length: f64,
+) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        "-hide_banner".into(),
+        "-nostats".into(),
+        "-loglevel".into(),
+        "error".into(),
+        "-y".into(),
+        "-ss".into(),
+        format!("{:.3}", start),
+        "-t".into(),
+        format!("{:.3}", length),
+        "-i".into(),
+        input.display().to_string(),
+        "-map".into(),
+        "0:v:0".into(),
+        "-map".into(),
+        "0:a:0?".into(),
+        "-c:v".into(),
+        "libx264".into(),
+        "-preset".into(),
+        "veryfast".into(),
+        "-profile:v".into(),
+        "high".into(),
+        "-level:v".into(),
+        variant.level().into(),
+        "-pix_fmt".into(),
+        "yuv420p".into(),
+        // Each segment must open on a keyframe to be independently playable.
+        "-force_key_frames".into(),
+        "expr:eq(n,0)".into(),
+    ];
+
+    if source_height.is_none_or(|height| height > variant.height) {
+        args.push("-vf".into());
+        args.push(format!("scale=-2:{}", variant.height));
+    }
+
+    args.extend([
+        "-b:v".into(),
+        format!("{}k", variant.video_kbps),
+        "-maxrate".into(),
+        format!("{}k", variant.video_kbps),
+        "-bufsize".into(),
+        format!("{}k", variant.video_kbps.saturating_mul(2)),
+        "-c:a".into(),
+        "aac".into(),
+        "-b:a".into(),
+        format!("{}k", AUDIO_KBPS),
+        "-ac".into(),
+        "2".into(),
+        // Keep timestamps continuous across independently encoded segments.
+        "-output_ts_offset".into(),
+        format!("{:.3}", start),
+        "-muxdelay".into(),
+        "0".into(),
+        "-f".into(),
+        "mpegts".into(),
+        output.display().to_string(),
+    ]);
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ladder_never_exceeds_the_source() {
+        let names = |variants: Vec<HlsVariant>| {
+            variants.into_iter().map(|v| v.name).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(variant_ladder(Some(1920), Some(1080))),
+            ["1080p", "720p", "480p", "360p"]
+        );
+        assert_eq!(
+            names(variant_ladder(None, None)),
+            ["1080p", "720p", "480p", "360p"]
+        );
+
+        let tiny = variant_ladder(Some(320), Some(240));
+        assert_eq!(names(tiny.clone()), ["240p"]);
+        assert_eq!(tiny[0].width, 320);
+    }
+
+    #[test]
+    fn master_playlist_lists_each_variant() {
+        let variants = variant_ladder(Some(3840), Some(2160));
+        let master = master_playlist(&variants);
+
+        assert!(master.starts_with("#EXTM3U\n"));
+        assert!(master.contains(
+            "BANDWIDTH=16128000,RESOLUTION=3840x2160,CODECS=\"avc1.640033,mp4a.40.2\""
+        ));
+        assert!(master.contains("\nhls/720p/index.m3u8\n"));
+        assert_eq!(master.matches("#EXT-X-STREAM-INF").count(), 5);
+    }
+
+    #[test]
+    fn media_playlist_covers_the_whole_duration() {
+        let playlist = media_playlist(13.5);
+
+        assert!(playlist.contains("#EXTINF:6.000,\n00000.ts\n"));
+        assert!(playlist.contains("#EXTINF:1.500,\n00002.ts\n"));
+        assert!(!playlist.contains("00003.ts"));
+        assert!(playlist.ends_with("#EXT-X-ENDLIST\n"));
+    }
+
+    #[test]
+    fn segment_names_round_trip() {
+        assert_eq!(parse_segment_name(&segment_name(42)), Some(42));
+        assert_eq!(parse_segment_name("../00001.ts"), None);
+        assert_eq!(parse_segment_name("00001.m4s"), None);
+        assert_eq!(parse_segment_name(".ts"), None);
+    }
+
+    #[test]
+    fn lru_evicts_least_recently_used_segments() {
+        let mut lru = SegmentLru::default();
+        let a = PathBuf::from("a.ts");
+        let b = PathBuf::from("b.ts");
+        let c = PathBuf::from("c.ts");
+
+        assert!(lru.insert(a.clone(), 40, 100).is_empty());
+        assert!(lru.insert(b.clone(), 40, 100).is_empty());
+        lru.touch(&a);
+
+        assert_eq!(lru.insert(c.clone(), 40, 100), vec![b]);
+        assert_eq!(lru.total_bytes, 80);
+
+        // A single segment larger than the bound is still kept.
+        assert_eq!(lru.insert(PathBuf::from("d.ts"), 500, 100), vec![a, c]);
+        assert_eq!(lru.total_bytes, 500);
+    }
+
+    #[test]
+    fn cached_bytes_are_grouped_per_media_file() {
+        let cache_dir = PathBuf::from("/cache/hls");
+        let first = Uuid::now_v7();
+        let second = Uuid::now_v7();
+        let segment = |media: Uuid, name: &str| {
+            cache_dir.join(media.to_string()).join("720p").join(name)
+        };
+
+        let mut lru = SegmentLru::default();
+        lru.insert(segment(first, "00000.ts"), 10, 1_000);
+        lru.insert(segment(first, "00001.ts"), 15, 1_000);
+        lru.insert(segment(second, "00000.ts"), 7, 1_000);
+        lru.insert(PathBuf::from("/elsewhere/00000.ts"), 3, 1_000);
+
+        let totals = lru.bytes_by_media(&cache_dir);
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[&first], 25);
+        assert_eq!(totals[&second], 7);
+    }
+}
@@ -0,0 +1,329 @@
+//! Replay records for retried POSTs.
+//!
+//! A request carrying `Idempotency-Key` claims a record before it runs and
+//! stores its response afterwards; a retry with the same key gets that
+//! response back instead of running again. Records are keyed by user,
+//! method, path and key, so reusing a key on another endpoint starts a new
+//! record, and they expire after [`REPLAY_WINDOW`]. A claim that never
+//! finishes expires after [`PENDING_TTL`] so a crashed instance cannot
+//! block a key for the whole replay window.
+//!
+//! Records live in Redis. Without Redis there is no store and requests run
+//! normally, as if no key had been sent.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+
+use crate::infra::redis_pool::RedisPool;
+
+/// How long a finished request is replayed for.
+pub const REPLAY_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// How long a claim lasts before its request stores a response. Matches the
+/// default limit of slow requests; claims dropped earlier are released.
+pub const PENDING_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// What a retry gets back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// Outcome of claiming a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Claim {
+    /// First use of the key: run the request, then `complete` or `release`.
+    Acquired,
+    /// An earlier request with the key has not finished yet.
+    InFlight,
+    /// The key was first used with a different request body.
+    Mismatch,
+    /// The key's request already finished with this response.
+    Completed(StoredResponse),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("idempotency store unavailable: {0}")]
+pub struct StoreError(String);
+
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync + fmt::Debug {
+    /// Claim `key` for a request whose body hashes to `fingerprint`, or
+    /// report what an earlier request with the key left behind.
+    async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<Claim, StoreError>;
+
+    /// Store the response of a request that claimed `key`.
+    async fn complete(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        response: StoredResponse,
+    ) -> Result<(), StoreError>;
+
+    /// Forget `key` so a retry runs the request again.
+    async fn release(&self, key: &str) -> Result<(), StoreError>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+enum Record {
+    Pending {
+        fingerprint: String,
+    },
+    Completed {
+        fingerprint: String,
+        status: u16,
+        content_type: Option<String>,
+        /// Base64 of the response body.
+        body: String,
+    },
+}
+
+impl Record {
+    fn completed(fingerprint: &str, response: StoredResponse) -> Self {
+        Record::Completed {
+            fingerprint: fingerprint.to_string(),
+            status: response.status,
+            content_type: response.content_type,
+            body: STANDARD.encode(response.body),
+        }
+    }
+
+    fn claim(self, fingerprint: &str) -> Result<Claim, StoreError> {
+        match self {
+            Record::Pending {
+                fingerprint: stored,
+            }
+            | Record::Completed {
+                fingerprint: stored,
+                ..
+            } if stored != fingerprint => Ok(Claim::Mismatch),
+            Record::Pending { .. } => Ok(Claim::InFlight),
+            Record::Completed {
+                status,
+                content_type,
+                body,
+                ..
+            } => {
+                let body = STANDARD.decode(body).map_err(|err| {
+                    StoreError(format!("corrupt replay record: {err}"))
+                })?;
+                Ok(Claim::Completed(StoredResponse {
+                    status,
+                    content_type,
+                    body,
+                }))
+            }
+        }
+    }
+}
+
+/// Records in Redis, shared by every server instance.
+#[derive(Debug, Clone)]
+pub struct RedisIdempotencyStore {
+    redis: RedisPool,
+}
+
+impl RedisIdempotencyStore {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+}
+
+fn encode(record: &Record) -> Result<String, StoreError> {
+    serde_json::to_string(record).map_err(|err| StoreError(err.to_string()))
+}
+
+fn redis_error(err: redis::RedisError) -> StoreError {
+    StoreError(err.to_string())
+}
+
+#[async_trait]
+impl IdempotencyStore for RedisIdempotencyStore {
+    async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<Claim, StoreError> {
+        let pending = encode(&Record::Pending {
+            fingerprint: fingerprint.to_string(),
+        })?;
+        let mut connection = self.redis.connection();
+        let claimed: Option<String> = self
+            .redis
+            .timed(
+                redis::cmd("SET")
+                    .arg(key)
+                    .arg(pending)
+                    .arg("NX")
+                    .arg("EX")
+                    .arg(PENDING_TTL.as_secs())
+                    .query_async(&mut connection),
+            )
+            .await
+            .map_err(redis_error)?;
+        if claimed.is_some() {
+            return Ok(Claim::Acquired);
+        }
+
+        let existing: Option<String> = self
+            .redis
+            .timed(connection.get(key))
+            .await
+            .map_err(redis_error)?;
+        match existing {
+            Some(raw) => serde_json::from_str::<Record>(&raw)
+                .map_err(|err| StoreError(err.to_string()))?
+                .claim(fingerprint),
+            // Expired between the two commands; the client can retry.
+            None => Ok(Claim::InFlight),
+        }
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        response: StoredResponse,
+    ) -> Result<(), StoreError> {
+        let record = encode(&Record::completed(fingerprint, response))?;
+        let mut connection = self.redis.connection();
+        self.redis
+            .timed(connection.set_ex::<_, _, ()>(
+                key,
+                record,
+                REPLAY_WINDOW.as_secs(),
+            ))
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn release(&self, key: &str) -> Result<(), StoreError> {
+        let mut connection = self.redis.connection();
+        self.redis
+            .timed(connection.del::<_, ()>(key))
+            .await
+            .map_err(redis_error)
+    }
+}
+
+/// Records in process memory, for tests and single-process tooling.
+#[derive(Debug, Default)]
+pub struct MemoryIdempotencyStore {
+    records: Mutex<HashMap<String, (Record, Instant)>>,
+}
+
+impl MemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for MemoryIdempotencyStore {
+    async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<Claim, StoreError> {
+        let mut records = self.records.lock().expect("idempotency records");
+        let now = Instant::now();
+        records.retain(|_, (_, expires)| *expires > now);
+        match records.get(key) {
+            Some((record, _)) => record.clone().claim(fingerprint),
+            None => {
+                records.insert(
+                    key.to_string(),
+                    (
+                        Record::Pending {
+                            fingerprint: fingerprint.to_string(),
+                        },
+                        now + PENDING_TTL,
+                    ),
+                );
+                Ok(Claim::Acquired)
+            }
+        }
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        response: StoredResponse,
+    ) -> Result<(), StoreError> {
+        self.records.lock().expect("idempotency records").insert(
+            key.to_string(),
+            (
+                Record::completed(fingerprint, response),
+                Instant::now() + REPLAY_WINDOW,
+            ),
+        );
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), StoreError> {
+        self.records
+            .lock()
+            .expect("idempotency records")
+            .remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accepted() -> StoredResponse {
+        StoredResponse {
+            status: 202,
+            content_type: Some("application/json".into()),
+            body: br#"{"scan_id":"1"}"#.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn finished_requests_are_replayed_until_released() {
+        let store = MemoryIdempotencyStore::new();
+        assert_eq!(store.begin("k", "body").await.unwrap(), Claim::Acquired);
+        assert_eq!(store.begin("k", "body").await.unwrap(), Claim::InFlight);
+
+        store.complete("k", "body", accepted()).await.unwrap();
+        assert_eq!(
+            store.begin("k", "body").await.unwrap(),
+            Claim::Completed(accepted())
+        );
+        assert_eq!(store.begin("k", "other").await.unwrap(), Claim::Mismatch);
+
+        store.release("k").await.unwrap();
+        assert_eq!(store.begin("k", "other").await.unwrap(), Claim::Acquired);
+    }
+
+    #[test]
+    fn records_round_trip_through_json() {
+        let record = Record::completed("body", accepted());
+        let decoded: Record =
+            serde_json::from_str(&serde_json::to_string(&record).unwrap())
+                .unwrap();
+        assert_eq!(
+            decoded.claim("body").unwrap(),
+            Claim::Completed(accepted())
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Runtime read-only (maintenance) mode.
+//!
+//! While enabled, the maintenance middleware answers mutating requests with
+//! `503 Service Unavailable` and a `Retry-After` header. Browsing, streaming
+//! and sign-in keep working, so clients stay usable while an operator runs
+//! migrations, backups or storage work. The flag lives in memory and resets
+//! to disabled on restart.
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use ferrex_core::api::routes::v1;
+use serde::Serialize;
+use tracing::{info, warn};
+
+/// `Retry-After` advertised when the operator does not pick one.
+pub const DEFAULT_RETRY_AFTER_SECS: u64 = 300;
+
+/// POST routes that do not modify server state (queries, batch fetches,
+/// transcode sessions for playback) plus the sign-in flows, the toggle
+/// itself and rate limiter recovery. Every other mutating route is refused while maintenance is on.
+const ALLOWED_MUTATIONS: &[&str] = &[
+    v1::auth::LOGIN,
+    v1::auth::REFRESH,
+    v1::auth::device::LOGIN,
+    v1::auth::device::PIN_LOGIN,
+    v1::auth::device::PIN_CHALLENGE,
+    v1::media::QUERY,
+    v1::libraries::FILTERED_INDICES,
+    v1::libraries::movie_batches::SYNC,
+    v1::libraries::movie_batches::FETCH,
+    v1::libraries::series_bundles::SYNC,
+    v1::libraries::series_bundles::FETCH,
+    v1::images::MANIFEST,
+    v1::transcode::START,
+    v1::admin::maintenance::MODE,
+    v1::admin::maintenance::RATE_LIMIT_RESET,
+];
+
+/// Snapshot of the maintenance flag, as reported by `/health` and the admin
+/// endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+    pub retry_after_secs: u64,
+    /// When maintenance was last enabled; `None` while disabled.
+    pub since: Option<DateTime<Utc>>,
+    /// Optional operator note surfaced to clients in the 503 body.
+    pub reason: Option<String>,
+}
+
+impl Default for MaintenanceStatus {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retry_after_secs: DEFAULT_RETRY_AFTER_SECS,
+            since: None,
+            reason: None,
+        }
+    }
+}
+
+/// Shared maintenance flag. Cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceMode {
+    status: Arc<RwLock<MaintenanceStatus>>,
+}
+
+impl MaintenanceMode {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(&self) -> MaintenanceStatus {
+        self.status
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.status
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .enabled
+    }
+
+    /// Enable or disable maintenance mode, logging the transition. A zero
+    /// `retry_after_secs` falls back to [`DEFAULT_RETRY_AFTER_SECS`].
+    pub fn set(
+        &self,
+        enabled: bool,
+        retry_after_secs: Option<u64>,
+        reason: Option<String>,
+        actor: &str,
+    ) -> MaintenanceStatus {
+        let mut status =
+            self.status.write().unwrap_or_else(PoisonError::into_inner);
+        let was_enabled = status.enabled;
+
+        status.enabled = enabled;
+        status.retry_after_secs = retry_after_secs
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+        status.reason = reason.filter(|r| !r.trim().is_empty());
+        status.since = match (was_enabled, enabled) {
+            (false, true) => Some(Utc::now()),
+            (true, true) => status.since,
+            (_, false) => None,
+        };
+
+        match (was_enabled, enabled) {
+            (false, true) => warn!(
+                "Maintenance mode enabled by {}; mutating requests now return 503 (Retry-After {}s)",
+                actor, status.retry_after_secs
+            ),
+            (true, false) => {
+                info!("Maintenance mode disabled by {}", actor)
+            }
+            _ => info!(
+                "Maintenance mode settings updated by {} (enabled: {}, Retry-After {}s)",
+                actor, status.enabled, status.retry_after_secs
+            ),
+        }
+
+        status.clone()
+    }
+}
+
+/// Whether a request to `matched_path` is refused while maintenance mode is
+/// enabled. Safe methods always pass.
+pub fn blocks(method: &Method, matched_path: &str) -> bool {
+    let mutating = matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    mutating && !ALLOWED_MUTATIONS.contains(&matched_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_state_changing_routes_are_blocked() {
+        assert!(blocks(&Method::POST, v1::libraries::scans::START));
+        assert!(blocks(&Method::POST, v1::watch::UPDATE_PROGRESS));
+        assert!(blocks(&Method::PUT, v1::users::CHANGE_PASSWORD));
+        assert!(blocks(&Method::POST, v1::auth::LOGOUT));
+
+        assert!(!blocks(&Method::POST, v1::auth::LOGIN));
+        assert!(!blocks(&Method::POST, v1::media::QUERY));
+        assert!(!blocks(&Method::GET, v1::stream::PLAY));
+        assert!(!blocks(&Method::GET, v1::libraries::COLLECTION));
+        assert!(!blocks(&Method::PUT, v1::admin::maintenance::MODE));
+    }
+
+    #[test]
+    fn toggling_tracks_since_and_defaults() {
+        let mode = MaintenanceMode::new();
+        let shared = mode.clone();
+
+        let status = mode.set(true, Some(0), Some("backup".into()), "test");
+        assert!(shared.is_enabled());
+        assert_eq!(status.retry_after_secs, DEFAULT_RETRY_AFTER_SECS);
+        let since = status.since.expect("enabled sets since");
+
+        let status = mode.set(true, Some(60), None, "test");
+        assert_eq!(status.since, Some(since));
+        assert_eq!(status.retry_after_secs, 60);
+
+        let status = mode.set(false, None, None, "test");
+        assert!(!status.enabled);
+        assert_eq!(status.since, None);
+    }
+}
@@ -0,0 +1,191 @@
+//! Coalescing for forced metadata refreshes.
+//!
+//! A refresh re-fetches a movie or series from TMDB and re-downloads its
+//! artwork, so repeated clicks must not fan out into parallel TMDB bursts
+//! for the same item. A caller asking to refresh an item that is already
+//! refreshing joins the running refresh and shares its outcome. Successful
+//! outcomes stay shared for a short window so quick repeats are answered
+//! without another round trip; failures are forgotten immediately so a
+//! corrected retry runs right away.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    hash::Hash,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use futures::{
+    FutureExt,
+    future::{BoxFuture, Shared},
+};
+use tracing::debug;
+
+use crate::infra::errors::AppResult;
+
+/// How long a finished refresh keeps answering repeat requests.
+pub const COALESCE_WINDOW: Duration = Duration::from_secs(10);
+
+type SharedRefresh<T> = Shared<BoxFuture<'static, AppResult<T>>>;
+
+/// Per-key singleflight for refreshes. Cheap to clone.
+pub struct RefreshCoalescer<K, T> {
+    in_flight: Arc<Mutex<HashMap<K, SharedRefresh<T>>>>,
+    window: Duration,
+}
+
+impl<K, T> Clone for RefreshCoalescer<K, T> {
+    fn clone(&self) -> Self {
+        Self {
+            in_flight: Arc::clone(&self.in_flight),
+            window: self.window,
+        }
+    }
+}
+
+impl<K, T> fmt::Debug for RefreshCoalescer<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RefreshCoalescer")
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, T> Default for RefreshCoalescer<K, T> {
+    fn default() -> Self {
+        Self::with_window(COALESCE_WINDOW)
+    }
+}
+
+impl<K, T> RefreshCoalescer<K, T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_window(window: Duration) -> Self {
+        Self {
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            window,
+        }
+    }
+}
+
+impl<K, T> RefreshCoalescer<K, T>
+where
+    K: Eq + Hash + Clone + fmt::Debug + Send + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    /// Run `refresh` for `key`, or join the refresh already running (or
+    /// recently finished) for it. The refresh runs to completion on its own
+    /// task even if every caller goes away.
+    pub async fn run<F>(&self, key: K, refresh: F) -> AppResult<T>
+    where
+        F: Future<Output = AppResult<T>> + Send + 'static,
+    {
+        let (shared, started) = {
+            let mut in_flight = self
+                .in_flight
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            match in_flight.get(&key) {
+                Some(existing) => {
+                    debug!("Joining in-flight refresh for {:?}", key);
+                    (existing.clone(), false)
+                }
+                None => {
+                    let shared = self.settle(key.clone(), refresh);
+                    in_flight.insert(key, shared.clone());
+                    (shared, true)
+                }
+            }
+        };
+
+        if started {
+            tokio::spawn(shared.clone());
+        }
+        shared.await
+    }
+
+    /// Wrap `refresh` so its entry is released once the outcome is known:
+    /// after the coalescing window on success, straight away on failure.
+    fn settle<F>(&self, key: K, refresh: F) -> SharedRefresh<T>
+    where
+        F: Future<Output = AppResult<T>> + Send + 'static,
+    {
+        let in_flight = Arc::clone(&self.in_flight);
+        let window = self.window;
+        async move {
+            let outcome = refresh.await;
+            let linger = if outcome.is_ok() {
+                window
+            } else {
+                Duration::ZERO
+            };
+            tokio::spawn(async move {
+                if !linger.is_zero() {
+                    tokio::time::sleep(linger).await;
+                }
+                in_flight
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .remove(&key);
+            });
+            outcome
+        }
+        .boxed()
+        .shared()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::errors::AppError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counted(
+        calls: &Arc<AtomicUsize>,
+        outcome: AppResult<usize>,
+    ) -> impl Future<Output = AppResult<usize>> + Send + 'static {
+        let calls = Arc::clone(calls);
+        async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            outcome
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_refreshes_for_one_key_run_once() {
+        let coalescer = RefreshCoalescer::<u32, usize>::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let (a, b) = tokio::join!(
+            coalescer.run(1, counted(&calls, Ok(7))),
+            coalescer.run(1, counted(&calls, Ok(8))),
+        );
+        assert_eq!(a.unwrap(), 7);
+        assert_eq!(b.unwrap(), 7);
+
+        coalescer.run(2, counted(&calls, Ok(9))).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn failures_are_not_reused() {
+        let coalescer = RefreshCoalescer::<u32, usize>::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let failed = coalescer
+            .run(1, counted(&calls, Err(AppError::internal("tmdb down"))))
+            .await;
+        assert!(failed.is_err());
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let retried = coalescer.run(1, counted(&calls, Ok(3))).await;
+        assert_eq!(retried.unwrap(), 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
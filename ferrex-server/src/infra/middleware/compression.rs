@@ -0,0 +1,181 @@
+//! gzip/deflate compression of JSON and plain-text API responses.
+//!
+//! Only bodies whose content type is JSON or plain text are compressed, and
+//! only when the client's `Accept-Encoding` allows it. Media streams, HLS,
+//! images, subtitles and event streams are either already compressed or
+//! latency sensitive, and are passed through untouched together with any
+//! partial (`206`/`Content-Range`) response, so their `Content-Length` and
+//! `Accept-Ranges` headers stay intact.
+
+use axum::{
+    body::HttpBody,
+    http::{
+        HeaderMap, Response, StatusCode,
+        header::{CONTENT_ENCODING, CONTENT_RANGE, CONTENT_TYPE},
+    },
+};
+use tower_http::compression::{
+    CompressionLayer, Predicate, predicate::SizeAbove,
+};
+
+use crate::infra::config::ServerConfig;
+
+/// Content types (without parameters) worth compressing.
+const COMPRESSIBLE_TYPES: &[&str] = &[
+    "application/json",
+    "application/problem+json",
+    "text/plain",
+    "text/html",
+    "text/csv",
+];
+
+/// Compresses JSON and plain-text responses; see the module docs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonOrText;
+
+impl Predicate for JsonOrText {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: HttpBody,
+    {
+        is_compressible(response.status(), response.headers())
+    }
+}
+
+fn is_compressible(status: StatusCode, headers: &HeaderMap) -> bool {
+    if status == StatusCode::PARTIAL_CONTENT
+        || headers.contains_key(CONTENT_RANGE)
+        || headers.contains_key(CONTENT_ENCODING)
+    {
+        return false;
+    }
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(str::trim)
+        .is_some_and(|essence| {
+            COMPRESSIBLE_TYPES
+                .iter()
+                .any(|ty| essence.eq_ignore_ascii_case(ty))
+        })
+}
+
+/// The compression layer for `server`, or `None` when it is turned off.
+pub fn compression_layer(
+    server: &ServerConfig,
+) -> Option<CompressionLayer<impl Predicate + Clone>> {
+    server.compression_enabled.then(|| {
+        CompressionLayer::new()
+            .gzip(true)
+            .deflate(true)
+            .compress_when(
+                SizeAbove::new(server.compression_min_bytes).and(JsonOrText),
+            )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, header},
+        response::IntoResponse,
+        routing::get,
+    };
+    use tower::ServiceExt;
+
+    fn headers(pairs: &[(header::HeaderName, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.clone(), value.parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn only_json_and_text_bodies_qualify() {
+        let ok = StatusCode::OK;
+        assert!(is_compressible(
+            ok,
+            &headers(&[(CONTENT_TYPE, "application/json; charset=utf-8")])
+        ));
+        assert!(is_compressible(
+            ok,
+            &headers(&[(CONTENT_TYPE, "text/plain")])
+        ));
+        for media in [
+            "video/x-matroska",
+            "image/webp",
+            "text/vtt",
+            "text/event-stream",
+            "application/vnd.apple.mpegurl",
+            "application/octet-stream",
+        ] {
+            assert!(!is_compressible(ok, &headers(&[(CONTENT_TYPE, media)])));
+        }
+        assert!(!is_compressible(
+            StatusCode::PARTIAL_CONTENT,
+            &headers(&[(CONTENT_TYPE, "application/json")])
+        ));
+    }
+
+    #[tokio::test]
+    async fn range_responses_keep_their_length_headers() {
+        let server = ServerConfig {
+            host: "0.0.0.0".into(),
+            port: 0,
+            request_id_header: "x-request-id".into(),
+            request_timeout: None,
+            slow_request_timeout: None,
+            max_concurrent_streams: None,
+            max_concurrent_transcodes: None,
+            stream_bandwidth_limit_kbps: None,
+            remote_media_redirect_hosts: Vec::new(),
+            compression_enabled: true,
+            compression_min_bytes: 16,
+            log_format: Default::default(),
+            log_level: None,
+        };
+        let app = Router::new()
+            .route(
+                "/json",
+                get(|| async {
+                    axum::Json(serde_json::json!({ "items": vec![7; 512] }))
+                }),
+            )
+            .route(
+                "/stream",
+                get(|| async {
+                    (
+                        StatusCode::PARTIAL_CONTENT,
+                        [
+                            (header::CONTENT_TYPE, "application/json"),
+                            (header::CONTENT_LENGTH, "1024"),
+                            (header::CONTENT_RANGE, "bytes 0-1023/4096"),
+                            (header::ACCEPT_RANGES, "bytes"),
+                        ],
+                        vec![b'{'; 1024],
+                    )
+                        .into_response()
+                }),
+            )
+            .layer(compression_layer(&server).unwrap());
+        let request = |uri: &str| {
+            Request::get(uri)
+                .header(header::ACCEPT_ENCODING, "gzip")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let json = app.clone().oneshot(request("/json")).await.unwrap();
+        assert_eq!(json.headers()[CONTENT_ENCODING], "gzip");
+
+        let range = app.oneshot(request("/stream")).await.unwrap();
+        assert_eq!(range.status(), StatusCode::PARTIAL_CONTENT);
+        assert!(range.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(range.headers()[header::CONTENT_LENGTH], "1024");
+        assert_eq!(range.headers()[header::ACCEPT_RANGES], "bytes");
+    }
+}
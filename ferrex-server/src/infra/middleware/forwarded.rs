@@ -0,0 +1,186 @@
+//! Which `X-Forwarded-*` headers to believe.
+//!
+//! Forwarded headers are only honoured when `trust_proxy_headers` is set and
+//! the immediate peer (the socket address from `ConnectInfo`) lies within a
+//! configured trusted proxy range. Anyone else could simply send the headers
+//! themselves, so for them the socket address and request scheme are used.
+
+use std::{net::IpAddr, net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::ConnectInfo,
+    http::{HeaderMap, Request, Uri},
+};
+
+use crate::infra::config::{IpRange, SecurityConfig};
+
+/// Trust policy for forwarded headers; cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct ProxyTrust {
+    enabled: bool,
+    ranges: Arc<[IpRange]>,
+}
+
+impl ProxyTrust {
+    pub fn new(enabled: bool, ranges: Vec<IpRange>) -> Self {
+        Self {
+            enabled,
+            ranges: ranges.into(),
+        }
+    }
+
+    pub fn from_config(security: &SecurityConfig) -> Self {
+        Self::new(
+            security.trust_proxy_headers,
+            security.trusted_proxy_ranges(),
+        )
+    }
+
+    /// Address of the immediate peer, when the server recorded it.
+    pub fn peer_ip<B>(req: &Request<B>) -> Option<IpAddr> {
+        req.extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip())
+    }
+
+    fn is_trusted(&self, addr: IpAddr) -> bool {
+        self.ranges.iter().any(|range| range.contains(addr))
+    }
+
+    /// Whether forwarded headers from `peer` are honoured.
+    pub fn trusts_peer(&self, peer: Option<IpAddr>) -> bool {
+        self.enabled && peer.is_some_and(|peer| self.is_trusted(peer))
+    }
+
+    /// The originating client. `X-Forwarded-For` is read right to left,
+    /// skipping trusted proxies, so entries a client prepends cannot
+    /// replace the address the proxy appended.
+    pub fn client_ip(
+        &self,
+        peer: Option<IpAddr>,
+        headers: &HeaderMap,
+    ) -> Option<IpAddr> {
+        if !self.trusts_peer(peer) {
+            return peer;
+        }
+        let mut client = peer;
+        for hop in headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+        {
+            let Ok(addr) = hop.trim().parse::<IpAddr>() else {
+                break;
+            };
+            client = Some(addr);
+            if !self.is_trusted(addr) {
+                break;
+            }
+        }
+        client
+    }
+
+    /// Whether the client connected over HTTPS, either directly or (for a
+    /// trusted peer) according to `X-Forwarded-Proto`.
+    pub fn is_https<B>(&self, req: &Request<B>) -> bool {
+        self.is_https_parts(Self::peer_ip(req), req.headers(), req.uri())
+    }
+
+    /// [`Self::is_https`] for handlers that only hold the request's parts.
+    pub fn is_https_parts(
+        &self,
+        peer: Option<IpAddr>,
+        headers: &HeaderMap,
+        uri: &Uri,
+    ) -> bool {
+        if self.trusts_peer(peer) {
+            return headers
+                .get("x-forwarded-proto")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .is_some_and(|v| v.trim().eq_ignore_ascii_case("https"));
+        }
+        uri.scheme()
+            .is_some_and(|s| s.as_str().eq_ignore_ascii_case("https"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    fn trust(ranges: &[&str]) -> ProxyTrust {
+        ProxyTrust::new(
+            true,
+            ranges.iter().map(|range| range.parse().unwrap()).collect(),
+        )
+    }
+
+    fn request(peer: &str, headers: &[(&str, &str)]) -> Request<Body> {
+        let mut builder = Request::builder().uri("http://ferrex.lan/api");
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        let mut req = builder.body(Body::empty()).unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 443)));
+        req
+    }
+
+    fn client(trust: &ProxyTrust, req: &Request<Body>) -> Option<String> {
+        trust
+            .client_ip(ProxyTrust::peer_ip(req), req.headers())
+            .map(|ip| ip.to_string())
+    }
+
+    #[test]
+    fn forwarded_for_is_only_read_from_trusted_peers() {
+        let trust = trust(&["10.0.0.0/8"]);
+        let headers = [("x-forwarded-for", "198.51.100.4")];
+
+        let proxied = request("10.0.0.2", &headers);
+        assert_eq!(client(&trust, &proxied).as_deref(), Some("198.51.100.4"));
+
+        let direct = request("203.0.113.9", &headers);
+        assert_eq!(client(&trust, &direct).as_deref(), Some("203.0.113.9"));
+
+        let disabled =
+            ProxyTrust::new(false, vec!["10.0.0.0/8".parse().unwrap()]);
+        assert_eq!(client(&disabled, &proxied).as_deref(), Some("10.0.0.2"));
+    }
+
+    #[test]
+    fn spoofed_hops_before_the_proxy_are_ignored() {
+        let trust = trust(&["10.0.0.0/8"]);
+        let req = request(
+            "10.0.0.2",
+            &[("x-forwarded-for", "1.2.3.4, 198.51.100.4, 10.0.0.7")],
+        );
+        assert_eq!(client(&trust, &req).as_deref(), Some("198.51.100.4"));
+
+        let garbage = request("10.0.0.2", &[("x-forwarded-for", "not-an-ip")]);
+        assert_eq!(client(&trust, &garbage).as_deref(), Some("10.0.0.2"));
+    }
+
+    #[test]
+    fn forwarded_proto_needs_a_trusted_peer() {
+        let trust = trust(&["10.0.0.0/8"]);
+        let headers = [("x-forwarded-proto", "https")];
+        assert!(trust.is_https(&request("10.0.0.2", &headers)));
+        assert!(!trust.is_https(&request("203.0.113.9", &headers)));
+
+        let mut unrecorded = Request::builder()
+            .uri("http://ferrex.lan/api")
+            .header("x-forwarded-proto", "https")
+            .body(Body::empty())
+            .unwrap();
+        assert!(!trust.is_https(&unrecorded));
+        *unrecorded.uri_mut() = "https://ferrex.lan/api".parse().unwrap();
+        assert!(trust.is_https(&unrecorded));
+    }
+}
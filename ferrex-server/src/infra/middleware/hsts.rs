@@ -1,7 +1,8 @@
 //! HSTS (HTTP Strict Transport Security) middleware for Ferrex media server
 //!
 //! This module provides a tower middleware that:
-//! - Adds Strict-Transport-Security header to HTTPS responses only
+//! - Adds Strict-Transport-Security header to HTTPS responses only, taking
+//!   forwarded headers into account only from peers [`ProxyTrust`] trusts
 //! - Configurable max-age (default 1 year)
 //! - Optional includeSubDomains directive
 //! - Optional preload directive
@@ -21,6 +22,8 @@ use std::{
 use tower::{Layer, Service};
 use tracing::debug;
 
+use super::ProxyTrust;
+
 /// Configuration for HSTS middleware
 #[derive(Clone, Debug)]
 pub struct HstsConfig {
@@ -69,28 +72,33 @@ impl HstsConfig {
 pub struct HstsLayer {
     config: HstsConfig,
     header_value: HeaderValue,
+    proxy_trust: ProxyTrust,
 }
 
 impl HstsLayer {
-    /// Create a new HSTS layer with default configuration
+    /// Create a new HSTS layer with default configuration that trusts no
+    /// proxy
     pub fn new() -> Self {
-        let config = HstsConfig::default();
-        let header_value = Self::build_header_value(&config);
-        Self {
-            config,
-            header_value,
-        }
+        Self::with_config(HstsConfig::default())
     }
 
-    /// Create a new HSTS layer with custom configuration
+    /// Create a new HSTS layer with custom configuration that trusts no
+    /// proxy
     pub fn with_config(config: HstsConfig) -> Self {
         let header_value = Self::build_header_value(&config);
         Self {
             config,
             header_value,
+            proxy_trust: ProxyTrust::default(),
         }
     }
 
+    /// Believe forwarded headers as far as `proxy_trust` does
+    pub fn with_proxy_trust(mut self, proxy_trust: ProxyTrust) -> Self {
+        self.proxy_trust = proxy_trust;
+        self
+    }
+
     /// Build the HSTS header value from configuration
     /// Uses HeaderValue::from_static for common configurations for performance
     fn build_header_value(config: &HstsConfig) -> HeaderValue {
@@ -155,6 +163,7 @@ impl<S> Layer<S> for HstsLayer {
         HstsMiddleware {
             inner,
             header_value: self.header_value.clone(),
+            proxy_trust: self.proxy_trust.clone(),
         }
     }
 }
@@ -164,32 +173,7 @@ impl<S> Layer<S> for HstsLayer {
 pub struct HstsMiddleware<S> {
     inner: S,
     header_value: HeaderValue,
-}
-
-impl<S> HstsMiddleware<S> {
-    /// Check if the request is using HTTPS
-    fn is_https(&self, req: &Request<Body>) -> bool {
-        // Check direct connection scheme
-        if req.uri().scheme_str() == Some("https") {
-            return true;
-        }
-
-        // Check X-Forwarded-Proto header for proxy scenarios
-        if let Some(proto) = req.headers().get("x-forwarded-proto")
-            && let Ok(proto_str) = proto.to_str()
-        {
-            return proto_str.eq_ignore_ascii_case("https");
-        }
-
-        // Check X-Forwarded-Ssl header (used by some load balancers)
-        if let Some(ssl) = req.headers().get("x-forwarded-ssl")
-            && let Ok(ssl_str) = ssl.to_str()
-        {
-            return ssl_str.eq_ignore_ascii_case("on");
-        }
-
-        false
-    }
+    proxy_trust: ProxyTrust,
 }
 
 impl<S> Service<Request<Body>> for HstsMiddleware<S>
@@ -215,7 +199,7 @@ where
     }
 
     fn call(&mut self, req: Request<Body>) -> Self::Future {
-        let is_https = self.is_https(&req);
+        let is_https = self.proxy_trust.is_https(&req);
 
         // Clone the service and header value for use in the async block
         let mut inner = self.inner.clone();
@@ -3,7 +3,7 @@
 //! This module provides a focused HTTP to HTTPS redirect middleware that:
 //! - Checks if request is HTTP (not HTTPS)
 //! - Returns 301 redirect to HTTPS version
-//! - Handles X-Forwarded-Proto header for reverse proxies
+//! - Honors X-Forwarded-Proto only from peers [`ProxyTrust`] trusts
 //! - Implements tower::Service trait for axum compatibility
 
 use axum::{
@@ -18,9 +18,13 @@ use std::{
 };
 use tower::{Layer, Service};
 
+use super::ProxyTrust;
+
 /// Layer for HTTPS redirect middleware
 #[derive(Clone, Debug)]
-pub struct HttpsRedirectLayer;
+pub struct HttpsRedirectLayer {
+    proxy_trust: ProxyTrust,
+}
 
 impl Default for HttpsRedirectLayer {
     fn default() -> Self {
@@ -29,9 +33,17 @@ impl Default for HttpsRedirectLayer {
 }
 
 impl HttpsRedirectLayer {
-    /// Create a new HTTPS redirect layer
+    /// Create a new HTTPS redirect layer that trusts no proxy
     pub fn new() -> Self {
-        Self
+        Self {
+            proxy_trust: ProxyTrust::default(),
+        }
+    }
+
+    /// Believe forwarded headers as far as `proxy_trust` does
+    pub fn with_proxy_trust(mut self, proxy_trust: ProxyTrust) -> Self {
+        self.proxy_trust = proxy_trust;
+        self
     }
 }
 
@@ -39,7 +51,10 @@ impl<S> Layer<S> for HttpsRedirectLayer {
     type Service = HttpsRedirectMiddleware<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        HttpsRedirectMiddleware { inner }
+        HttpsRedirectMiddleware {
+            inner,
+            proxy_trust: self.proxy_trust.clone(),
+        }
     }
 }
 
@@ -47,26 +62,10 @@ impl<S> Layer<S> for HttpsRedirectLayer {
 #[derive(Clone, Debug)]
 pub struct HttpsRedirectMiddleware<S> {
     inner: S,
+    proxy_trust: ProxyTrust,
 }
 
 impl<S> HttpsRedirectMiddleware<S> {
-    /// Check if the request is using HTTPS
-    fn is_https(&self, req: &Request<Body>) -> bool {
-        // Check direct connection scheme
-        if req.uri().scheme_str() == Some("https") {
-            return true;
-        }
-
-        // Check X-Forwarded-Proto header for reverse proxies
-        if let Some(proto) = req.headers().get("x-forwarded-proto")
-            && let Ok(proto_str) = proto.to_str()
-        {
-            return proto_str.eq_ignore_ascii_case("https");
-        }
-
-        false
-    }
-
     /// Build HTTPS redirect URL preserving path and query
     fn build_https_url(
         &self,
@@ -112,7 +111,7 @@ where
 
     fn call(&mut self, req: Request<Body>) -> Self::Future {
         // Check if request is HTTP (not HTTPS)
-        if !self.is_https(&req) {
+        if !self.proxy_trust.is_https(&req) {
             // Build redirect URL
             match self.build_https_url(&req) {
                 Ok(https_url) => {
@@ -162,9 +161,25 @@ pub use HttpsRedirectMiddleware as HttpsEnforcementMiddleware;
 mod tests {
     use super::*;
     use axum::body::Body;
+    use axum::extract::ConnectInfo;
     use axum::http::{Request, Response, StatusCode};
+    use std::net::SocketAddr;
     use tower::{ServiceBuilder, ServiceExt};
 
+    fn trusting(range: &str) -> HttpsRedirectLayer {
+        HttpsRedirectLayer::new().with_proxy_trust(ProxyTrust::new(
+            true,
+            vec![range.parse().unwrap()],
+        ))
+    }
+
+    fn from_peer(mut request: Request<Body>, peer: &str) -> Request<Body> {
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 443)));
+        request
+    }
+
     #[tokio::test]
     async fn test_http_to_https_redirect() {
         let service = ServiceBuilder::new()
@@ -214,7 +229,7 @@ mod tests {
     #[tokio::test]
     async fn test_proxy_forwarded_proto_https() {
         let service = ServiceBuilder::new()
-            .layer(HttpsRedirectLayer::new())
+            .layer(trusting("10.0.0.0/8"))
             .service_fn(|_req: Request<Body>| async {
                 Ok::<_, Box<dyn std::error::Error + Send + Sync>>(
                     Response::new(Body::from("Hello")),
@@ -228,16 +243,45 @@ mod tests {
             .body(Body::empty())
             .unwrap();
 
-        let response = service.oneshot(request).await.unwrap();
+        let response = service
+            .oneshot(from_peer(request, "10.0.0.2"))
+            .await
+            .unwrap();
 
-        // Should not redirect because X-Forwarded-Proto says it's HTTPS
+        // Should not redirect because the trusted proxy says it's HTTPS
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_untrusted_forwarded_proto_is_ignored() {
+        let service = ServiceBuilder::new()
+            .layer(trusting("10.0.0.0/8"))
+            .service_fn(|_req: Request<Body>| async {
+                Ok::<_, Box<dyn std::error::Error + Send + Sync>>(
+                    Response::new(Body::from("Hello")),
+                )
+            });
+
+        let request = Request::builder()
+            .uri("http://example.com/test")
+            .header("Host", "example.com")
+            .header("X-Forwarded-Proto", "https")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = service
+            .oneshot(from_peer(request, "203.0.113.9"))
+            .await
+            .unwrap();
+
+        // Anyone can send the header; only a trusted proxy is believed
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+    }
+
     #[tokio::test]
     async fn test_proxy_forwarded_proto_http() {
         let service = ServiceBuilder::new()
-            .layer(HttpsRedirectLayer::new())
+            .layer(trusting("10.0.0.0/8"))
             .service_fn(|_req: Request<Body>| async {
                 Ok::<_, Box<dyn std::error::Error + Send + Sync>>(
                     Response::new(Body::from("Hello")),
@@ -251,7 +295,10 @@ mod tests {
             .body(Body::empty())
             .unwrap();
 
-        let response = service.oneshot(request).await.unwrap();
+        let response = service
+            .oneshot(from_peer(request, "10.0.0.2"))
+            .await
+            .unwrap();
 
         // Should redirect because X-Forwarded-Proto says it's HTTP
         assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
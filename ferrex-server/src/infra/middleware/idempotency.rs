@@ -0,0 +1,296 @@
+//! `Idempotency-Key` handling for mutating routes.
+//!
+//! Layered onto individual routes, inside their auth layer, so the record
+//! can be scoped to the caller. A retry of a finished request gets the
+//! stored response with `Idempotent-Replayed: true`; a retry that arrives
+//! while the first attempt still runs gets `409 Conflict`. Only successful
+//! responses are stored, so a failed attempt can simply be retried, and
+//! the claim of an attempt that is dropped mid-flight (a timeout or a
+//! client disconnect) is released the same way. When no store is available
+//! (no Redis) or the store errors, requests run normally without replay
+//! protection.
+
+use std::{future::Future, sync::Arc};
+
+use axum::{
+    body::{Body, HttpBody, to_bytes},
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header::CONTENT_TYPE},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use ferrex_core::domain::users::user::User;
+use sha2::Digest;
+use tracing::warn;
+
+use crate::infra::{
+    app_state::AppState,
+    errors::AppError,
+    idempotency::{Claim, IdempotencyStore, StoredResponse},
+};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const MAX_KEY_LEN: usize = 255;
+/// Request bodies are buffered to fingerprint them.
+const MAX_REQUEST_BODY_BYTES: usize = 1024 * 1024;
+/// Larger responses are passed through without being stored.
+const MAX_STORED_BODY_BYTES: u64 = 256 * 1024;
+
+pub async fn idempotency_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(key) = idempotency_key(request.headers()) else {
+        return next.run(request).await;
+    };
+    let key = match key {
+        Ok(key) => key,
+        Err(err) => return err.into_response(),
+    };
+    let Some(store) = state.idempotency_store() else {
+        return next.run(request).await;
+    };
+
+    let user = request
+        .extensions()
+        .get::<User>()
+        .map(|user| user.id.to_string())
+        .unwrap_or_else(|| "anonymous".to_string());
+    let record_key = format!(
+        "idempotency:{user}:{}:{}:{key}",
+        request.method(),
+        request.uri().path()
+    );
+
+    let (parts, body) = request.into_parts();
+    let Ok(body) = to_bytes(body, MAX_REQUEST_BODY_BYTES).await else {
+        return AppError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Request body too large for an idempotent request",
+        )
+        .into_response();
+    };
+    let fingerprint = format!("{:x}", sha2::Sha256::digest(&body));
+    let request = Request::from_parts(parts, Body::from(body));
+
+    match store.begin(&record_key, &fingerprint).await {
+        Ok(Claim::Acquired) => {}
+        Ok(Claim::Completed(stored)) => return replay(stored),
+        Ok(Claim::InFlight) => {
+            return AppError::conflict(
+                "A request with this Idempotency-Key is still in progress",
+            )
+            .into_response();
+        }
+        Ok(Claim::Mismatch) => {
+            return AppError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Idempotency-Key was already used with a different request body",
+            )
+            .into_response();
+        }
+        Err(err) => {
+            warn!(error = %err, "running request without replay protection");
+            return next.run(request).await;
+        }
+    }
+
+    run_claimed(store, record_key, fingerprint, next.run(request)).await
+}
+
+/// Run the request holding a claim on `key` and store its response. The
+/// claim is released if the future is dropped before the response is
+/// stored.
+async fn run_claimed(
+    store: Arc<dyn IdempotencyStore>,
+    key: String,
+    fingerprint: String,
+    run: impl Future<Output = Response>,
+) -> Response {
+    let mut guard = ClaimGuard {
+        store: Some(Arc::clone(&store)),
+        key: key.clone(),
+    };
+    let response = run.await;
+    let response = remember(store.as_ref(), &key, &fingerprint, response).await;
+    guard.store = None;
+    response
+}
+
+/// Releases a claim whose request never got to store a response.
+struct ClaimGuard {
+    store: Option<Arc<dyn IdempotencyStore>>,
+    key: String,
+}
+
+impl Drop for ClaimGuard {
+    fn drop(&mut self) {
+        let Some(store) = self.store.take() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let key = std::mem::take(&mut self.key);
+        runtime.spawn(async move {
+            if let Err(err) = store.release(&key).await {
+                warn!(error = %err, "failed to release idempotency key");
+            }
+        });
+    }
+}
+
+/// The trimmed key, `None` without the header, or a `400` for keys that
+/// are empty, too long or not visible ASCII.
+fn idempotency_key(headers: &HeaderMap) -> Option<Result<String, AppError>> {
+    let value = headers.get(IDEMPOTENCY_KEY_HEADER)?;
+    let key = value.to_str().map(str::trim).unwrap_or_default();
+    let valid = !key.is_empty()
+        && key.len() <= MAX_KEY_LEN
+        && key.bytes().all(|b| b.is_ascii_graphic());
+    Some(if valid {
+        Ok(key.to_string())
+    } else {
+        Err(AppError::bad_request(
+            "Idempotency-Key must be 1-255 visible ASCII characters",
+        ))
+    })
+}
+
+async fn remember(
+    store: &dyn IdempotencyStore,
+    key: &str,
+    fingerprint: &str,
+    response: Response,
+) -> Response {
+    let storable = response.status().is_success()
+        && response
+            .body()
+            .size_hint()
+            .upper()
+            .is_some_and(|len| len <= MAX_STORED_BODY_BYTES);
+    if !storable {
+        if let Err(err) = store.release(key).await {
+            warn!(error = %err, "failed to release idempotency key");
+        }
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_STORED_BODY_BYTES as usize).await {
+        Ok(body) => body,
+        Err(err) => {
+            if let Err(err) = store.release(key).await {
+                warn!(error = %err, "failed to release idempotency key");
+            }
+            return AppError::internal(format!(
+                "Failed to read response body: {err}"
+            ))
+            .into_response();
+        }
+    };
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        content_type: parts
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        body: body.to_vec(),
+    };
+    if let Err(err) = store.complete(key, fingerprint, stored).await {
+        warn!(error = %err, "failed to store response for replay");
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut response = (status, stored.body).into_response();
+    let headers = response.headers_mut();
+    match stored
+        .content_type
+        .and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        Some(content_type) => {
+            headers.insert(CONTENT_TYPE, content_type);
+        }
+        None => {
+            headers.remove(CONTENT_TYPE);
+        }
+    }
+    headers
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::infra::idempotency::MemoryIdempotencyStore;
+
+    fn headers(key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            HeaderValue::from_str(key).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn keys_must_be_short_visible_ascii() {
+        assert!(idempotency_key(&HeaderMap::new()).is_none());
+        assert_eq!(
+            idempotency_key(&headers(" 3f1c-retry ")).unwrap().unwrap(),
+            "3f1c-retry"
+        );
+        assert!(idempotency_key(&headers("two words")).unwrap().is_err());
+        assert!(idempotency_key(&headers("")).unwrap().is_err());
+        assert!(
+            idempotency_key(&headers(&"k".repeat(MAX_KEY_LEN + 1)))
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn replays_keep_status_and_content_type() {
+        let response = replay(StoredResponse {
+            status: 202,
+            content_type: Some("application/json".into()),
+            body: b"{}".to_vec(),
+        });
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(response.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+    }
+
+    #[tokio::test]
+    async fn dropped_requests_release_their_claim() {
+        let store: Arc<dyn IdempotencyStore> =
+            Arc::new(MemoryIdempotencyStore::new());
+        assert_eq!(store.begin("k", "body").await.unwrap(), Claim::Acquired);
+
+        // What the timeout layer does to a request that overruns.
+        let dropped = tokio::time::timeout(
+            Duration::from_millis(10),
+            run_claimed(
+                Arc::clone(&store),
+                "k".into(),
+                "body".into(),
+                std::future::pending(),
+            ),
+        )
+        .await;
+        assert!(dropped.is_err());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(store.begin("k", "body").await.unwrap(), Claim::Acquired);
+    }
+}
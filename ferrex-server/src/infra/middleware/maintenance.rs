@@ -0,0 +1,59 @@
+//! Maintenance-mode gate for the v1 API.
+//!
+//! Applied once over the whole v1 router so handlers never have to check the
+//! flag themselves. See [`crate::infra::maintenance`] for which routes stay
+//! open while the server is read-only.
+
+use axum::{
+    Json,
+    extract::{MatchedPath, Request, State},
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+
+use crate::infra::{app_state::AppState, maintenance};
+
+pub async fn maintenance_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let maintenance = state.maintenance();
+    if !maintenance.is_enabled() {
+        return next.run(request).await;
+    }
+
+    let blocked =
+        request
+            .extensions()
+            .get::<MatchedPath>()
+            .is_some_and(|path| {
+                maintenance::blocks(request.method(), path.as_str())
+            });
+    if !blocked {
+        return next.run(request).await;
+    }
+
+    let status = maintenance.status();
+    let message = match status.reason.as_deref() {
+        Some(reason) => {
+            format!("Server is in maintenance mode (read-only): {reason}")
+        }
+        None => "Server is in maintenance mode (read-only)".to_string(),
+    };
+    let body = Json(json!({
+        "error": {
+            "message": message,
+            "status": StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+        },
+        "maintenance": status,
+    }));
+
+    let mut response = (StatusCode::SERVICE_UNAVAILABLE, body).into_response();
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(status.retry_after_secs));
+    response
+}
@@ -1,4 +1,6 @@
+pub mod compression;
 pub mod csrf;
+pub mod forwarded;
 pub mod hsts;
 /// Middleware modules for the Ferrex media server
 ///
@@ -7,17 +9,35 @@ pub mod hsts;
 /// - HSTS (HTTP Strict Transport Security) headers
 /// - Request/response logging
 /// - Rate limiting
+/// - Trusted reverse-proxy headers
+/// - Request correlation ids
+/// - Idempotency-Key replay for retried POSTs
+/// - Per-route-class request timeouts
+/// - JSON/text response compression
 /// - Security headers
 pub mod https;
+pub mod idempotency;
+pub mod maintenance;
 pub mod rate_limit;
+pub mod request_id;
+pub mod timeout;
 
+pub use compression::{JsonOrText, compression_layer};
 pub use csrf::{
     CsrfLayer, CsrfMiddleware, ValidateCsrf, create_csrf_cookie,
     extract_csrf_from_cookies, generate_token, hash_token,
 };
+pub use forwarded::ProxyTrust;
 pub use hsts::{HstsConfig, HstsLayer, HstsMiddleware};
 pub use https::{
     HttpsEnforcementLayer, HttpsEnforcementMiddleware, HttpsRedirectLayer,
     HttpsRedirectMiddleware,
 };
-pub use rate_limit::{RateLimiterConfig, create_rate_limiter};
+pub use idempotency::idempotency_middleware;
+pub use maintenance::maintenance_middleware;
+pub use rate_limit::{
+    RateLimitBypass, RateLimiterConfig, create_rate_limiter,
+    insert_unlimited_headers,
+};
+pub use request_id::{RequestId, request_id_middleware};
+pub use timeout::{RequestTimeouts, RouteClass, request_timeout_middleware};
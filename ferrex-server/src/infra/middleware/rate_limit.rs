@@ -3,20 +3,21 @@
 //! This module implements Redis-backed rate limiting with support for
 //! multiple algorithms and dynamic configuration updates.
 
-use anyhow::{Context, Result};
 use async_trait::async_trait;
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
 use ferrex_core::domain::users::auth::rate_limit::{
     EndpointLimits, RateLimitAlgorithm, RateLimitDecision, RateLimitError,
     RateLimitKey, RateLimitResult, RateLimitRule, RateLimiter, TrustedSources,
     backoff,
 };
 pub use ferrexctl::RateLimiterConfig;
+use ferrexctl::{IpRange, RateLimitAllowlist};
 use redis::AsyncCommands;
-use redis::aio::ConnectionManager;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
+    net::IpAddr,
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -25,6 +26,9 @@ use tokio::{
     time::interval,
 };
 use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::infra::redis_pool::RedisPool;
 
 /// Redis scripts for atomic operations
 mod scripts {
@@ -93,10 +97,13 @@ mod scripts {
     }
 }
 
+/// Appended to a bucket key to count its consecutive violations.
+const VIOLATIONS_SUFFIX: &str = ":violations";
+
 /// Redis-backed distributed rate limiter
 pub struct RedisRateLimiter {
-    /// Redis connection manager
-    redis: ConnectionManager,
+    /// Shared Redis connections
+    redis: RedisPool,
 
     /// In-memory cache for hot paths
     cache: Arc<RwLock<HashMap<String, CachedDecision>>>,
@@ -161,18 +168,8 @@ struct RateLimitMetrics {
 }
 
 impl RedisRateLimiter {
-    /// Create a new Redis-backed rate limiter
-    pub async fn new(
-        redis_url: &str,
-        config: RateLimiterConfig,
-    ) -> Result<Self> {
-        let client = redis::Client::open(redis_url)
-            .context("Failed to create Redis client")?;
-
-        let redis = ConnectionManager::new(client)
-            .await
-            .context("Failed to create Redis connection manager")?;
-
+    /// Create a rate limiter on top of `redis`
+    pub fn new(redis: RedisPool, config: RateLimiterConfig) -> Self {
         let (update_tx, _) = broadcast::channel(100);
 
         let limiter = Self {
@@ -186,7 +183,7 @@ impl RedisRateLimiter {
         // Start background tasks
         limiter.start_background_tasks();
 
-        Ok(limiter)
+        limiter
     }
 
     /// Start background maintenance tasks
@@ -293,6 +290,49 @@ impl RedisRateLimiter {
         )
     }
 
+    /// All keys matching `pattern`, collected with `SCAN` so a large
+    /// keyspace never blocks Redis.
+    async fn scan_keys(&self, pattern: &str) -> RateLimitResult<Vec<String>> {
+        let mut conn = self.redis.connection();
+        let mut found = Vec::new();
+        let mut cursor = 0u64;
+        loop {
+            let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(100)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| RateLimitError::BackendError(e.into()))?;
+            found.extend(keys);
+            cursor = next;
+            if cursor == 0 {
+                break;
+            }
+        }
+        Ok(found)
+    }
+
+    /// Delete counters and their violation tallies; returns how many
+    /// counters (buckets) were removed.
+    async fn delete_buckets(&self, keys: Vec<String>) -> RateLimitResult<u64> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+        let buckets = keys
+            .iter()
+            .filter(|key| !key.ends_with(VIOLATIONS_SUFFIX))
+            .count() as u64;
+
+        let mut conn = self.redis.connection();
+        conn.del::<_, ()>(keys)
+            .await
+            .map_err(|e| RateLimitError::BackendError(e.into()))?;
+        Ok(buckets)
+    }
+
     /// Check cache for decision
     async fn check_cache(&self, cache_key: &str) -> Option<RateLimitDecision> {
         let cache_guard = self.cache.read().await;
@@ -395,7 +435,7 @@ impl RateLimiter for RedisRateLimiter {
             .unwrap()
             .as_secs();
 
-        let mut conn = self.redis.clone();
+        let mut conn = self.redis.connection();
 
         let result = match rule.algorithm {
             RateLimitAlgorithm::SlidingWindowLog => {
@@ -462,7 +502,7 @@ impl RateLimiter for RedisRateLimiter {
 
         // Check violations for exponential backoff
         let violation_count = if !allowed {
-            let violation_key = format!("{}:violations", redis_key);
+            let violation_key = format!("{}{}", redis_key, VIOLATIONS_SUFFIX);
             let count: u32 = conn
                 .incr(&violation_key, 1)
                 .await
@@ -533,31 +573,38 @@ impl RateLimiter for RedisRateLimiter {
         Ok(decision)
     }
 
-    async fn reset(&self, key: &RateLimitKey) -> RateLimitResult<()> {
-        let config = self.config.read().await;
-        let pattern =
-            format!("{}:*:{}", config.key_prefix, key.to_cache_key("*"));
+    async fn reset(&self, key: &RateLimitKey) -> RateLimitResult<u64> {
+        let prefix = self.config.read().await.key_prefix.clone();
+
+        // Rule names and key values can contain glob metacharacters, so scan
+        // the whole namespace and match exactly instead of trusting a pattern.
+        let keys: Vec<String> = self
+            .scan_keys(&format!("{}:*", prefix))
+            .await?
+            .into_iter()
+            .filter(|stored| is_bucket_for(stored, &prefix, key))
+            .collect();
+        let cleared = self.delete_buckets(keys).await?;
+
+        self.cache
+            .write()
+            .await
+            .retain(|cached, _| !is_bucket_for(cached, &prefix, key));
 
-        let mut conn = self.redis.clone();
+        info!("Cleared {} rate limit bucket(s) for {:?}", cleared, key);
+        Ok(cleared)
+    }
 
-        // Find all keys matching the pattern
-        let keys: Vec<String> = conn
-            .keys(&pattern)
-            .await
-            .map_err(|e| RateLimitError::BackendError(e.into()))?;
+    async fn reset_all(&self) -> RateLimitResult<u64> {
+        let prefix = self.config.read().await.key_prefix.clone();
 
-        // Delete all matching keys
-        if !keys.is_empty() {
-            conn.del::<_, ()>(keys)
-                .await
-                .map_err(|e| RateLimitError::BackendError(e.into()))?;
-        }
+        let keys = self.scan_keys(&format!("{}:*", prefix)).await?;
+        let cleared = self.delete_buckets(keys).await?;
 
-        // Clear from cache
-        let mut cache_guard = self.cache.write().await;
-        cache_guard.retain(|k, _| !k.contains(&key.to_cache_key("")));
+        self.cache.write().await.clear();
 
-        Ok(())
+        info!("Cleared all {} rate limit bucket(s)", cleared);
+        Ok(cleared)
     }
 
     async fn get_current_state(
@@ -574,7 +621,7 @@ impl RateLimiter for RedisRateLimiter {
 
         // Query Redis without updating
         let redis_key = cache_key.clone();
-        let mut conn = self.redis.clone();
+        let mut conn = self.redis.connection();
 
         let current_count: u32 = match rule.algorithm {
             RateLimitAlgorithm::SlidingWindowLog => {
@@ -631,7 +678,7 @@ impl RateLimiter for RedisRateLimiter {
         let config = self.config.read().await;
         let pattern = format!("{}:*", config.key_prefix);
 
-        let mut conn = self.redis.clone();
+        let mut conn = self.redis.connection();
         let mut cleaned = 0u64;
 
         // Scan for keys (use SCAN in production, not KEYS)
@@ -673,6 +720,24 @@ impl RateLimiter for RedisRateLimiter {
     }
 }
 
+/// Whether `stored` is a bucket (or its violation tally) that
+/// [`RedisRateLimiter::get_cache_key`] produced for `key` under any rule.
+/// Keys are laid out as `<prefix>:<rule>:<key.to_cache_key(rule)>`.
+fn is_bucket_for(stored: &str, prefix: &str, key: &RateLimitKey) -> bool {
+    let Some(rest) = stored
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix(':'))
+    else {
+        return false;
+    };
+    let Some((rule, bucket)) = rest.split_once(':') else {
+        return false;
+    };
+    let expected = key.to_cache_key(rule);
+    bucket == expected
+        || bucket.strip_suffix(VIOLATIONS_SUFFIX) == Some(expected.as_str())
+}
+
 fn apply_dynamic_rule(
     limits: &mut EndpointLimits,
     endpoint: &str,
@@ -689,15 +754,115 @@ fn apply_dynamic_rule(
     }
 }
 
+/// [`RateLimitAllowlist`] parsed once for the middleware; cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitBypass {
+    device_ids: Arc<HashSet<Uuid>>,
+    ranges: Arc<[IpRange]>,
+}
+
+impl RateLimitBypass {
+    pub fn from_config(allowlist: &RateLimitAllowlist) -> Self {
+        Self {
+            device_ids: Arc::new(
+                allowlist.device_uuids().into_iter().collect(),
+            ),
+            ranges: allowlist.ranges().into(),
+        }
+    }
+
+    /// Whether `device_id` is on the allowlist.
+    pub fn lists_device(&self, device_id: Uuid) -> bool {
+        self.device_ids.contains(&device_id)
+    }
+
+    /// Whether a request from `session_device_id` at `client_ip` skips rate
+    /// limiting. Either one matching is enough. Pass a device id only once
+    /// the request's session is known to belong to that device; the
+    /// `X-Device-ID` header by itself proves nothing.
+    pub fn allows(
+        &self,
+        session_device_id: Option<Uuid>,
+        client_ip: Option<IpAddr>,
+    ) -> bool {
+        session_device_id.is_some_and(|id| self.lists_device(id))
+            || client_ip
+                .is_some_and(|ip| self.ranges.iter().any(|r| r.contains(ip)))
+    }
+}
+
+/// Rate-limit headers for an allowlisted request, so clients can tell
+/// they are exempt rather than merely under the limit.
+pub fn insert_unlimited_headers(headers: &mut HeaderMap) {
+    for name in ["x-ratelimit-limit", "x-ratelimit-remaining"] {
+        headers.insert(
+            HeaderName::from_static(name),
+            HeaderValue::from_static("unlimited"),
+        );
+    }
+}
+
 /// Create endpoint-specific rate limiter
 pub fn create_rate_limiter(
-    redis_url: &str,
+    redis: RedisPool,
     config: RateLimiterConfig,
-) -> Result<Arc<dyn RateLimiter>> {
-    tokio::task::block_in_place(|| {
-        tokio::runtime::Handle::current().block_on(async {
-            let limiter = RedisRateLimiter::new(redis_url, config).await?;
-            Ok(Arc::new(limiter) as Arc<dyn RateLimiter>)
-        })
-    })
+) -> Arc<dyn RateLimiter> {
+    Arc::new(RedisRateLimiter::new(redis, config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn reset_matches_only_the_targeted_key() {
+        let ip = RateLimitKey::IpAddress("10.0.0.1".into());
+        let bucket = format!("ferrex:login:{}", ip.to_cache_key("login"));
+
+        assert!(is_bucket_for(&bucket, "ferrex", &ip));
+        assert!(is_bucket_for(
+            &format!("{bucket}{VIOLATIONS_SUFFIX}"),
+            "ferrex",
+            &ip
+        ));
+
+        let neighbour = RateLimitKey::IpAddress("10.0.0.10".into());
+        assert!(!is_bucket_for(&bucket, "ferrex", &neighbour));
+        assert!(!is_bucket_for(&bucket, "other", &ip));
+
+        let device = RateLimitKey::DeviceId(Uuid::nil());
+        let combined = RateLimitKey::Combined {
+            ip: Some("10.0.0.1".into()),
+            user_id: None,
+            device_id: Some(Uuid::nil()),
+        };
+        let combined_bucket =
+            format!("ferrex:pin:{}", combined.to_cache_key("pin"));
+        assert!(!is_bucket_for(&combined_bucket, "ferrex", &ip));
+        assert!(!is_bucket_for(&combined_bucket, "ferrex", &device));
+        assert!(is_bucket_for(&combined_bucket, "ferrex", &combined));
+    }
+
+    #[test]
+    fn allowlist_matches_device_ids_and_ranges() {
+        let dashboard = Uuid::now_v7();
+        let bypass = RateLimitBypass::from_config(&RateLimitAllowlist {
+            device_ids: vec![dashboard.to_string()],
+            ip_ranges: vec!["192.168.10.0/24".into()],
+        });
+        let ip = |raw: &str| raw.parse::<IpAddr>().ok();
+
+        assert!(bypass.lists_device(dashboard));
+        assert!(!bypass.lists_device(Uuid::now_v7()));
+        assert!(bypass.allows(Some(dashboard), None));
+        assert!(bypass.allows(Some(Uuid::now_v7()), ip("192.168.10.7")));
+        assert!(!bypass.allows(Some(Uuid::now_v7()), ip("192.168.11.7")));
+        assert!(!bypass.allows(None, None));
+        assert!(!RateLimitBypass::default().allows(Some(dashboard), None));
+
+        let mut headers = HeaderMap::new();
+        insert_unlimited_headers(&mut headers);
+        assert_eq!(headers["x-ratelimit-remaining"], "unlimited");
+    }
 }
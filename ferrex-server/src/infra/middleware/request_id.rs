@@ -0,0 +1,104 @@
+//! Per-request correlation ids.
+//!
+//! Every request runs inside a `request` tracing span carrying its id, so
+//! log lines from the rate limiter, auth and handlers can be grepped
+//! together. A well-formed inbound id (from a proxy or client) is kept,
+//! otherwise a fresh one is generated; either way it is echoed back in the
+//! response under the same header.
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::{Instrument, info_span};
+use uuid::Uuid;
+
+/// Inbound ids longer than this are replaced rather than trusted.
+const MAX_INBOUND_LEN: usize = 64;
+
+/// Length of generated ids, in hex characters.
+const GENERATED_LEN: usize = 12;
+
+/// Correlation id of the current request, available to handlers as an
+/// extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Short random id: the first [`GENERATED_LEN`] hex digits of a v4 uuid.
+pub fn generate_request_id() -> String {
+    let mut id = Uuid::new_v4().simple().to_string();
+    id.truncate(GENERATED_LEN);
+    id
+}
+
+/// Accept an inbound id only if it is short and limited to characters that
+/// cannot break a log line or a header.
+fn sanitize_inbound(value: &HeaderValue) -> Option<String> {
+    let raw = value.to_str().ok()?.trim();
+    let well_formed = !raw.is_empty()
+        && raw.len() <= MAX_INBOUND_LEN
+        && raw
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-_.:".contains(&b));
+    well_formed.then(|| raw.to_string())
+}
+
+/// Tag the request with a correlation id read from (and echoed back in)
+/// the configured header.
+pub async fn request_id_middleware(
+    State(header): State<HeaderName>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let id = request
+        .headers()
+        .get(&header)
+        .and_then(sanitize_inbound)
+        .unwrap_or_else(generate_request_id);
+
+    let span = info_span!(
+        "request",
+        request_id = %id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    request.extensions_mut().insert(RequestId(id.clone()));
+
+    let mut response = next.run(request).instrument(span).await;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(header, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_ids_are_short_hex() {
+        let id = generate_request_id();
+        assert_eq!(id.len(), GENERATED_LEN);
+        assert!(id.bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_ne!(id, generate_request_id());
+    }
+
+    #[test]
+    fn inbound_ids_are_kept_only_when_well_formed() {
+        let keep = HeaderValue::from_static("lb-7f3a:42");
+        assert_eq!(sanitize_inbound(&keep).as_deref(), Some("lb-7f3a:42"));
+
+        for bad in ["", "has space", "quote\"d", &"a".repeat(65)] {
+            let value = HeaderValue::from_str(bad).unwrap();
+            assert_eq!(sanitize_inbound(&value), None, "{bad:?}");
+        }
+    }
+}
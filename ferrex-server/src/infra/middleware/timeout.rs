@@ -0,0 +1,215 @@
+//! Time limits for v1 API requests.
+//!
+//! Routes fall into classes by their matched path: ordinary requests get
+//! `server.request_timeout_secs`, admin, scan and other heavy requests get
+//! `server.slow_request_timeout_secs`, and streaming, SSE and WebSocket
+//! routes are never limited since they are meant to stay open. A request
+//! that overruns its limit is dropped and answered with
+//! `504 Gateway Timeout`.
+
+use std::time::{Duration, Instant};
+
+use axum::{
+    Json,
+    extract::{MatchedPath, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use ferrex_core::api::{routes::v1, types::ApiErrorBody};
+use tracing::warn;
+
+use crate::infra::config::ServerConfig;
+
+/// Long-lived responses: media streams, HLS, SSE and the sync socket.
+const UNLIMITED: &[&str] = &[
+    v1::stream::PLAY,
+    v1::transcode::FILE,
+    v1::media::hls::MASTER,
+    v1::media::hls::VARIANT,
+    v1::media::hls::SEGMENT,
+    v1::scan::EVENTS,
+    v1::scan::PROGRESS_STREAM,
+    v1::events::MEDIA,
+    v1::images::EVENTS,
+    v1::sync::WEBSOCKET,
+];
+
+/// Requests that legitimately walk a library, the disk or TMDB inline.
+const SLOW: &[&str] = &[
+    v1::libraries::VERIFY,
+    v1::libraries::RECONCILE,
+    v1::libraries::WATCH,
+    v1::libraries::scans::START,
+    v1::libraries::scans::PATH,
+    v1::libraries::scans::PREVIEW,
+    v1::images::REFRESH,
+    v1::media::item::TMDB_MATCH,
+    // ffmpeg reads the whole container to pull out one subtitle track
+    v1::media::subtitles::EMBEDDED_TRACK,
+];
+
+/// Prefix of the admin routes, which all count as slow.
+const ADMIN_PREFIX: &str = "/api/v1/admin/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteClass {
+    Standard,
+    Slow,
+    Unlimited,
+}
+
+impl RouteClass {
+    pub fn of(matched_path: &str) -> Self {
+        if UNLIMITED.contains(&matched_path) {
+            Self::Unlimited
+        } else if SLOW.contains(&matched_path)
+            || matched_path.starts_with(ADMIN_PREFIX)
+        {
+            Self::Slow
+        } else {
+            Self::Standard
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Slow => "slow",
+            Self::Unlimited => "unlimited",
+        }
+    }
+}
+
+/// Configured limit per route class; `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestTimeouts {
+    pub standard: Option<Duration>,
+    pub slow: Option<Duration>,
+}
+
+impl RequestTimeouts {
+    pub fn from_config(server: &ServerConfig) -> Self {
+        Self {
+            standard: server.request_timeout,
+            slow: server.slow_request_timeout,
+        }
+    }
+
+    pub fn limit(&self, class: RouteClass) -> Option<Duration> {
+        match class {
+            RouteClass::Standard => self.standard,
+            RouteClass::Slow => self.slow,
+            RouteClass::Unlimited => None,
+        }
+    }
+}
+
+pub async fn request_timeout_middleware(
+    State(timeouts): State<RequestTimeouts>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(path) = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+    else {
+        return next.run(request).await;
+    };
+    let class = RouteClass::of(&path);
+    let Some(limit) = timeouts.limit(class) else {
+        return next.run(request).await;
+    };
+
+    let method = request.method().clone();
+    let started = Instant::now();
+    match tokio::time::timeout(limit, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            let elapsed = started.elapsed();
+            warn!(
+                %method,
+                path = %path,
+                class = class.as_str(),
+                elapsed_ms = elapsed.as_millis() as u64,
+                limit_ms = limit.as_millis() as u64,
+                "request timed out"
+            );
+            let status = StatusCode::GATEWAY_TIMEOUT;
+            let body = Json(ApiErrorBody::new(
+                status.as_u16(),
+                format!("Request timed out after {}s", limit.as_secs()),
+            ));
+            (status, body).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, middleware, routing::get};
+    use tower::ServiceExt;
+
+    #[test]
+    fn streaming_routes_are_never_limited() {
+        assert_eq!(RouteClass::of(v1::stream::PLAY), RouteClass::Unlimited);
+        assert_eq!(RouteClass::of(v1::events::MEDIA), RouteClass::Unlimited);
+        assert_eq!(RouteClass::of(v1::sync::WEBSOCKET), RouteClass::Unlimited);
+        assert_eq!(RouteClass::of(v1::admin::STATS), RouteClass::Slow);
+        assert_eq!(
+            RouteClass::of(v1::libraries::scans::START),
+            RouteClass::Slow
+        );
+        assert_eq!(
+            RouteClass::of(v1::libraries::scans::PREVIEW),
+            RouteClass::Slow
+        );
+        assert_eq!(
+            RouteClass::of(v1::libraries::COLLECTION),
+            RouteClass::Standard
+        );
+
+        let timeouts = RequestTimeouts {
+            standard: Some(Duration::from_secs(1)),
+            slow: None,
+        };
+        assert_eq!(timeouts.limit(RouteClass::Unlimited), None);
+        assert_eq!(timeouts.limit(RouteClass::Slow), None);
+    }
+
+    #[tokio::test]
+    async fn overrunning_requests_get_504() {
+        let timeouts = RequestTimeouts {
+            standard: Some(Duration::from_millis(50)),
+            slow: None,
+        };
+        let app = Router::new()
+            .route(
+                v1::libraries::COLLECTION,
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    "late"
+                }),
+            )
+            .route(v1::watch::STATE, get(|| async { "prompt" }))
+            .layer(middleware::from_fn_with_state(
+                timeouts,
+                request_timeout_middleware,
+            ));
+
+        let request = |uri: &str| {
+            axum::http::Request::get(uri).body(Body::empty()).unwrap()
+        };
+        let slow = app
+            .clone()
+            .oneshot(request(v1::libraries::COLLECTION))
+            .await
+            .unwrap();
+        assert_eq!(slow.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let fast = app.oneshot(request(v1::watch::STATE)).await.unwrap();
+        assert_eq!(fast.status(), StatusCode::OK);
+    }
+}
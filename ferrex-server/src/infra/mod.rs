@@ -2,13 +2,29 @@ pub mod app_context;
 pub mod app_state;
 pub mod cache;
 pub mod config;
+pub mod config_reload;
 pub mod constants;
 pub mod demo_mode;
 pub mod errors;
+pub mod hls;
+pub mod idempotency;
+pub mod maintenance;
+pub mod metadata_refresh;
 pub mod middleware;
 pub mod orchestration;
 pub mod postgres_tuning;
+pub mod ranges;
+pub mod readiness;
+pub mod redis_pool;
+pub mod remote_media;
 pub mod scan;
 pub mod startup;
+pub mod stream_compat;
+pub mod stream_content_type;
+pub mod stream_limit;
+pub mod stream_pacing;
+pub mod stream_seek;
+pub mod subtitles;
 pub mod thumbnail_service;
+pub mod transcode;
 pub mod websocket;
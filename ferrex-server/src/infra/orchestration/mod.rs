@@ -4,12 +4,17 @@
 //! together so the REST server can enqueue work, observe progress, and drive
 //! follow-up automation using the same runtime that production nodes execute.
 
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+    collections::HashMap, fmt, path::PathBuf, sync::Arc, time::Duration,
+};
 
 use ferrex_core::api::ScanQueueDepths;
+use ferrex_core::api::scan::MetadataQueueStats;
 use ferrex_core::application::unit_of_work::AppUnitOfWork;
 use ferrex_core::database::PostgresDatabase;
-use ferrex_core::domain::scan::actors::provider::TmdbMetadataActor;
+use ferrex_core::domain::scan::actors::provider::{
+    MetadataProvider, MetadataProviderChain, TmdbMetadataActor,
+};
 use ferrex_core::domain::scan::actors::{
     DefaultFolderScanActor, DefaultLibraryActor, LibraryActorCommand,
     LibraryActorConfig, LibraryRootsId, NoopActorObserver,
@@ -32,6 +37,7 @@ use ferrex_core::domain::scan::orchestration::{
     },
     job::{EnqueueRequest, JobHandle, JobKind, JobPriority},
     lease::{DequeueRequest, JobLease},
+    metadata_throttle::MetadataThrottle,
     queue::QueueService,
     runtime::{
         InProcJobEventBus, LibraryActorHandle, OrchestratorRuntime,
@@ -51,8 +57,8 @@ use ferrex_core::error::{MediaError, Result};
 use ferrex_core::infra::media::{
     image_service::ImageService, providers::TmdbApiProvider,
 };
-use ferrex_core::types::LibraryId;
-use tokio::sync::Mutex;
+use ferrex_core::types::{LibraryId, library::LibraryType};
+use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, info, instrument};
 
 pub struct ScanOrchestrator {
@@ -68,6 +74,8 @@ pub struct ScanOrchestrator {
     events: Arc<InProcJobEventBus>,
     watchers: Arc<FsWatchService>,
     correlations: CorrelationCache,
+    /// Quiescence windows of libraries registered with their own.
+    quiescence: RwLock<HashMap<LibraryId, Duration>>,
 }
 
 impl fmt::Debug for ScanOrchestrator {
@@ -80,6 +88,7 @@ impl ScanOrchestrator {
     pub fn new(
         config: OrchestratorConfig,
         tmdb: Arc<TmdbApiProvider>,
+        metadata_providers: Vec<Arc<dyn MetadataProvider>>,
         image_service: Arc<ImageService>,
         unit_of_work: Arc<AppUnitOfWork>,
         queue: Arc<PostgresQueueService>,
@@ -90,6 +99,7 @@ impl ScanOrchestrator {
         let correlations = CorrelationCache::default();
         let actors = Arc::new(ActorSystem::new(
             Arc::clone(&tmdb),
+            metadata_providers,
             Arc::clone(&image_service),
             Arc::clone(&unit_of_work),
             Arc::clone(&events),
@@ -147,6 +157,7 @@ impl ScanOrchestrator {
             events,
             watchers,
             correlations,
+            quiescence: RwLock::new(HashMap::new()),
         })
     }
 
@@ -196,6 +207,12 @@ impl ScanOrchestrator {
         Arc::clone(&self.cursors)
     }
 
+    /// Pin library roots to named devices for the per-device scan cap.
+    /// Call before registering libraries.
+    pub fn set_device_overrides(&self, overrides: HashMap<PathBuf, String>) {
+        self.runtime.devices().set_overrides(overrides);
+    }
+
     #[instrument(
         name = "scan_orchestrator.register_library",
         skip(self, config),
@@ -207,20 +224,32 @@ impl ScanOrchestrator {
         config: LibraryActorConfig,
         watch_for_changes: bool,
     ) -> Result<()> {
+        if config.library.library_type == LibraryType::Music {
+            debug!(library_id = %config.library.id, "music library is scanned outside the orchestrator");
+            return Ok(());
+        }
+        self.runtime
+            .devices()
+            .register_library(config.library.id, &config.root_paths);
+        {
+            let mut windows = self.quiescence.write().await;
+            match config.quiescence_window {
+                Some(window) => windows.insert(config.library.id, window),
+                None => windows.remove(&config.library.id),
+            };
+        }
         let queue = self.runtime.queue();
         let actor = self.actors.make_library_actor(config.clone(), queue);
         self.runtime
             .register_library_actor(config.library.id, Arc::clone(&actor))
             .await?;
         if watch_for_changes {
-            let roots = config
-                .root_paths
-                .iter()
-                .enumerate()
-                .map(|(idx, path)| (LibraryRootsId(idx as u16), path.clone()))
-                .collect();
             self.watchers
-                .register_library(config.library.id, roots, actor)
+                .register_library(
+                    config.library.id,
+                    watch_roots(&config.root_paths),
+                    actor,
+                )
                 .await?;
         } else {
             debug!(library_id = %config.library.id, "skipping watcher registration (disabled)");
@@ -228,6 +257,42 @@ impl ScanOrchestrator {
         Ok(())
     }
 
+    /// The quiescence window a library was registered with, if it has its
+    /// own rather than the global one.
+    pub async fn quiescence_window(
+        &self,
+        library_id: LibraryId,
+    ) -> Option<Duration> {
+        self.quiescence.read().await.get(&library_id).copied()
+    }
+
+    /// Start or stop watching an already registered library's roots for
+    /// changes. Both directions are idempotent.
+    pub async fn set_library_watch(
+        &self,
+        library_id: LibraryId,
+        root_paths: &[PathBuf],
+        enabled: bool,
+    ) -> Result<()> {
+        if !enabled {
+            self.watchers.unregister_library(library_id).await;
+            info!(library_id = %library_id, "stopped watching library");
+            return Ok(());
+        }
+
+        let actor =
+            self.runtime.library_actor(library_id).await.ok_or_else(|| {
+                MediaError::NotFound(format!(
+                    "library {library_id} is not registered with the orchestrator"
+                ))
+            })?;
+        self.watchers
+            .register_library(library_id, watch_roots(root_paths), actor)
+            .await?;
+        info!(library_id = %library_id, "started watching library");
+        Ok(())
+    }
+
     pub async fn start(&self) -> Result<()> {
         self.prime_ready_jobs().await?;
         self.runtime.start().await
@@ -398,6 +463,44 @@ impl ScanOrchestrator {
         Ok(lease)
     }
 
+    /// Stop scheduling work for `library_id`: new jobs are refused and the
+    /// ones not leased yet are dropped. Returns the number of jobs dropped.
+    /// Jobs already running are left alone; see [`Self::drain_library`].
+    pub async fn suspend_library(&self, library_id: LibraryId) -> Result<u64> {
+        let queue = self.runtime.queue();
+        queue.suspend_enqueues(library_id);
+        let dropped = queue.drop_pending_jobs(library_id).await;
+        self.runtime.scheduler().clear_ready(library_id).await;
+        dropped
+    }
+
+    /// Wait until no job of a suspended library is leased, then accept new
+    /// jobs for it again. Jobs that become ready meanwhile (an expired lease
+    /// going back to the queue) are dropped as well. Returns the number of
+    /// jobs dropped while waiting.
+    pub async fn drain_library(
+        &self,
+        library_id: LibraryId,
+        poll: Duration,
+    ) -> Result<u64> {
+        let queue = self.runtime.queue();
+        let drained = async {
+            let mut dropped = 0;
+            loop {
+                dropped += queue.drop_pending_jobs(library_id).await?;
+                if queue.leased_job_count(library_id).await? == 0 {
+                    break;
+                }
+                tokio::time::sleep(poll).await;
+            }
+            self.runtime.scheduler().clear_ready(library_id).await;
+            Ok(dropped)
+        }
+        .await;
+        queue.resume_enqueues(library_id);
+        drained
+    }
+
     /// Return ready-queue depths for each job kind to aid diagnostics.
     pub async fn queue_depths(&self) -> Result<ScanQueueDepths> {
         let queue = self.runtime.queue();
@@ -409,6 +512,28 @@ impl ScanOrchestrator {
             image_fetch: queue.queue_depth(JobKind::ImageFetch).await?,
         })
     }
+
+    /// Pacing shared by background metadata jobs and on-demand fetches.
+    pub fn metadata_throttle(&self) -> MetadataThrottle {
+        self.runtime.metadata_throttle()
+    }
+
+    /// Queue depth and outcome counters of the background metadata workers.
+    pub async fn metadata_queue_stats(&self) -> Result<MetadataQueueStats> {
+        let queue = self.runtime.queue();
+        let queued = queue.queue_depth(JobKind::MetadataEnrich).await?
+            + queue.queue_depth(JobKind::EpisodeMatch).await?;
+        let stats = self.runtime.metadata_throttle().stats();
+        Ok(MetadataQueueStats {
+            queued,
+            background_in_flight: stats.background_in_flight,
+            on_demand_in_flight: stats.on_demand_in_flight,
+            completed: stats.completed,
+            retried: stats.retried,
+            dead_lettered: stats.dead_lettered,
+            completed_last_minute: stats.completed_last_minute,
+        })
+    }
 }
 
 impl ScanOrchestrator {
@@ -416,6 +541,7 @@ impl ScanOrchestrator {
         config: OrchestratorConfig,
         postgres: Arc<PostgresDatabase>,
         tmdb: Arc<TmdbApiProvider>,
+        metadata_providers: Vec<Arc<dyn MetadataProvider>>,
         image_service: Arc<ImageService>,
         unit_of_work: Arc<AppUnitOfWork>,
     ) -> Result<Self> {
@@ -430,6 +556,7 @@ impl ScanOrchestrator {
         Self::new(
             config,
             tmdb,
+            metadata_providers,
             image_service,
             unit_of_work,
             queue,
@@ -439,12 +566,22 @@ impl ScanOrchestrator {
     }
 }
 
+/// Watch roots keyed by their position in the library's path list.
+fn watch_roots(root_paths: &[PathBuf]) -> Vec<(LibraryRootsId, PathBuf)> {
+    root_paths
+        .iter()
+        .enumerate()
+        .map(|(idx, path)| (LibraryRootsId(idx as u16), path.clone()))
+        .collect()
+}
+
 pub struct ActorSystem {
     observer: Arc<NoopActorObserver>,
     folder_actor: Arc<dyn FolderScanActor>,
     analyze_actor: Arc<dyn MediaAnalyzeActor>,
     metadata_actor: Arc<dyn MetadataActor>,
     series_provider: Arc<dyn SeriesMetadataProvider>,
+    tmdb_actor: Arc<TmdbMetadataActor>,
     indexer_actor: Arc<dyn IndexerActor>,
     image_actor: Arc<dyn ImageFetchActor>,
     events: Arc<InProcJobEventBus>,
@@ -458,8 +595,11 @@ impl fmt::Debug for ActorSystem {
 }
 
 impl ActorSystem {
+    /// `metadata_providers` are tried in order before TMDB, which always
+    /// comes last.
     pub fn new(
         tmdb: Arc<TmdbApiProvider>,
+        metadata_providers: Vec<Arc<dyn MetadataProvider>>,
         image_service: Arc<ImageService>,
         unit_of_work: Arc<AppUnitOfWork>,
         events: Arc<InProcJobEventBus>,
@@ -473,7 +613,16 @@ impl ActorSystem {
             tmdb,
             Arc::clone(&image_service),
         ));
-        let metadata_actor: Arc<dyn MetadataActor> = tmdb_actor.clone();
+        for provider in &metadata_providers {
+            if let Some(source) = provider.image_source() {
+                image_service.register_image_source(source);
+            }
+        }
+        let mut providers = metadata_providers;
+        providers.push(tmdb_actor.clone());
+        let chain = MetadataProviderChain::new(providers);
+        info!(providers = ?chain.names(), "metadata providers configured");
+        let metadata_actor: Arc<dyn MetadataActor> = Arc::new(chain);
         let series_provider: Arc<dyn SeriesMetadataProvider> =
             tmdb_actor.clone();
 
@@ -483,6 +632,7 @@ impl ActorSystem {
             analyze_actor: Arc::new(DefaultMediaAnalyzeActor::new()),
             metadata_actor,
             series_provider,
+            tmdb_actor,
             indexer_actor: Arc::new(DefaultIndexerActor::new(
                 unit_of_work.media_refs.clone(),
             )),
@@ -522,6 +672,12 @@ impl ActorSystem {
         Arc::clone(&self.series_provider)
     }
 
+    /// Concrete TMDB actor, for on-demand work outside the job pipeline
+    /// such as forced metadata refreshes.
+    pub fn tmdb_actor(&self) -> Arc<TmdbMetadataActor> {
+        Arc::clone(&self.tmdb_actor)
+    }
+
     pub fn indexer_actor(&self) -> Arc<dyn IndexerActor> {
         Arc::clone(&self.indexer_actor)
     }
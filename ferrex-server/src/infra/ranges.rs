@@ -0,0 +1,320 @@
+//! `Range: bytes=` requests (RFC 9110 §14).
+//!
+//! [`parse_ranges`] turns the header into a [`RangeOutcome`] and
+//! [`RangeOutcome::response`] decides what to send for it, so every handler
+//! serving byte ranges answers alike: `206` with `Content-Range` for one
+//! range, `416` with `Content-Range: bytes */<size>` when no range is
+//! satisfiable, and `200` with the whole body otherwise. Several ranges are
+//! answered with the whole body rather than `multipart/byteranges`, which
+//! the RFC allows and no player needs.
+
+use axum::http::{StatusCode, header, response::Builder};
+
+/// Headers listing more ranges than this are ignored.
+const MAX_RANGES: usize = 16;
+
+/// Inclusive byte range, clamped to the body it applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Number of bytes in the range; never zero.
+    pub fn length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// What a `Range` header asks of a body of known size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeOutcome {
+    /// No header, another unit, or a malformed header: send everything.
+    Full,
+    Single(ByteRange),
+    /// Sorted, non-overlapping ranges; adjacent ones are merged.
+    Multi(Vec<ByteRange>),
+    /// Well formed, but no range overlaps the body.
+    Unsatisfiable,
+}
+
+/// Parse a `Range` header value for a body of `total` bytes.
+pub fn parse_ranges(header: Option<&str>, total: u64) -> RangeOutcome {
+    let Some(specs) = header.map(str::trim).and_then(|value| {
+        value
+            .get(..6)
+            .filter(|unit| unit.eq_ignore_ascii_case("bytes="))
+            .map(|_| &value[6..])
+    }) else {
+        return RangeOutcome::Full;
+    };
+
+    let mut requested = 0;
+    let mut ranges = Vec::new();
+    for spec in specs.split(',').map(str::trim) {
+        if spec.is_empty() {
+            continue;
+        }
+        requested += 1;
+        if requested > MAX_RANGES {
+            return RangeOutcome::Full;
+        }
+        let Some((first, last)) = spec.split_once('-') else {
+            return RangeOutcome::Full;
+        };
+        let (first, last) = (first.trim(), last.trim());
+
+        let range = if first.is_empty() {
+            // `-N`: the last N bytes.
+            let Some(suffix) = position(last) else {
+                return RangeOutcome::Full;
+            };
+            (suffix > 0 && total > 0).then(|| ByteRange {
+                start: total.saturating_sub(suffix),
+                end: total - 1,
+            })
+        } else {
+            let Some(start) = position(first) else {
+                return RangeOutcome::Full;
+            };
+            let end = if last.is_empty() {
+                None
+            } else {
+                match position(last) {
+                    Some(end) if end >= start => Some(end),
+                    _ => return RangeOutcome::Full,
+                }
+            };
+            (start < total).then(|| ByteRange {
+                start,
+                end: end.map_or(total - 1, |end| end.min(total - 1)),
+            })
+        };
+        ranges.extend(range);
+    }
+    if requested == 0 {
+        return RangeOutcome::Full;
+    }
+
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<ByteRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(1) => {
+                last.end = last.end.max(range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+    match merged.len() {
+        0 => RangeOutcome::Unsatisfiable,
+        1 => RangeOutcome::Single(merged[0]),
+        _ => RangeOutcome::Multi(merged),
+    }
+}
+
+/// Digits only; `u64::from_str` would also take a leading `+`.
+fn position(value: &str) -> Option<u64> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+impl RangeOutcome {
+    /// What to send for this outcome from a body of `total` bytes.
+    pub fn response(&self, total: u64) -> RangeResponse {
+        match self {
+            RangeOutcome::Single(range) => RangeResponse {
+                status: StatusCode::PARTIAL_CONTENT,
+                offset: range.start,
+                length: range.length(),
+                content_range: Some(format!(
+                    "bytes {}-{}/{}",
+                    range.start, range.end, total
+                )),
+            },
+            RangeOutcome::Unsatisfiable => RangeResponse {
+                status: StatusCode::RANGE_NOT_SATISFIABLE,
+                offset: 0,
+                length: 0,
+                content_range: Some(format!("bytes */{total}")),
+            },
+            RangeOutcome::Full | RangeOutcome::Multi(_) => RangeResponse {
+                status: StatusCode::OK,
+                offset: 0,
+                length: total,
+                content_range: None,
+            },
+        }
+    }
+}
+
+/// Status and slice of the body to answer a range request with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeResponse {
+    pub status: StatusCode,
+    /// First byte of the body to send.
+    pub offset: u64,
+    /// Bytes to send from `offset`.
+    pub length: u64,
+    content_range: Option<String>,
+}
+
+impl RangeResponse {
+    pub fn is_unsatisfiable(&self) -> bool {
+        self.status == StatusCode::RANGE_NOT_SATISFIABLE
+    }
+
+    /// Set the status, `Content-Length`, `Accept-Ranges` and, for partial
+    /// and unsatisfiable answers, `Content-Range`.
+    pub fn apply(&self, builder: Builder) -> Builder {
+        let builder = builder
+            .status(self.status)
+            .header(header::CONTENT_LENGTH, self.length.to_string())
+            .header(header::ACCEPT_RANGES, "bytes");
+        match &self.content_range {
+            Some(content_range) => {
+                builder.header(header::CONTENT_RANGE, content_range)
+            }
+            None => builder,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u64, end: u64) -> ByteRange {
+        ByteRange { start, end }
+    }
+
+    fn parse(header: &str, total: u64) -> RangeOutcome {
+        parse_ranges(Some(header), total)
+    }
+
+    #[test]
+    fn single_ranges_are_clamped_to_the_body() {
+        assert_eq!(
+            parse("bytes=0-99", 1000),
+            RangeOutcome::Single(range(0, 99))
+        );
+        assert_eq!(
+            parse("bytes=900-5000", 1000),
+            RangeOutcome::Single(range(900, 999))
+        );
+        assert_eq!(parse("Bytes=5-5", 10), RangeOutcome::Single(range(5, 5)));
+    }
+
+    #[test]
+    fn open_ended_ranges_run_to_the_end() {
+        assert_eq!(
+            parse("bytes=0-", 1000),
+            RangeOutcome::Single(range(0, 999))
+        );
+        assert_eq!(
+            parse("bytes=999-", 1000),
+            RangeOutcome::Single(range(999, 999))
+        );
+        assert_eq!(parse("bytes=1000-", 1000), RangeOutcome::Unsatisfiable);
+    }
+
+    #[test]
+    fn suffix_ranges_count_from_the_end() {
+        assert_eq!(
+            parse("bytes=-100", 1000),
+            RangeOutcome::Single(range(900, 999))
+        );
+        // A suffix longer than the body means the whole body.
+        assert_eq!(
+            parse("bytes=-5000", 1000),
+            RangeOutcome::Single(range(0, 999))
+        );
+        assert_eq!(parse("bytes=-0", 1000), RangeOutcome::Unsatisfiable);
+    }
+
+    #[test]
+    fn zero_length_bodies_satisfy_no_range() {
+        assert_eq!(parse("bytes=0-", 0), RangeOutcome::Unsatisfiable);
+        assert_eq!(parse("bytes=-10", 0), RangeOutcome::Unsatisfiable);
+        assert_eq!(parse_ranges(None, 0), RangeOutcome::Full);
+        assert_eq!(
+            RangeOutcome::Unsatisfiable
+                .response(0)
+                .content_range
+                .as_deref(),
+            Some("bytes */0")
+        );
+    }
+
+    #[test]
+    fn several_ranges_are_sorted_and_merged() {
+        assert_eq!(
+            parse("bytes=500-599, 0-99", 1000),
+            RangeOutcome::Multi(vec![range(0, 99), range(500, 599)])
+        );
+        assert_eq!(
+            parse("bytes=0-99,100-199,150-250", 1000),
+            RangeOutcome::Single(range(0, 250))
+        );
+        // Unsatisfiable members are dropped when another one fits.
+        assert_eq!(
+            parse("bytes=2000-3000,0-9", 1000),
+            RangeOutcome::Single(range(0, 9))
+        );
+        assert_eq!(
+            parse("bytes=2000-3000,-0", 1000),
+            RangeOutcome::Unsatisfiable
+        );
+    }
+
+    #[test]
+    fn malformed_headers_are_ignored() {
+        for header in [
+            "bytes=",
+            "bytes=abc-def",
+            "bytes=10-5",
+            "bytes=5",
+            "bytes=+5-10",
+            "bytes=-",
+            "items=0-10",
+            "0-10",
+        ] {
+            assert_eq!(parse(header, 1000), RangeOutcome::Full, "{header}");
+        }
+        let too_many =
+            format!("bytes={}", vec!["0-0"; MAX_RANGES + 1].join(","));
+        assert_eq!(parse(&too_many, 1000), RangeOutcome::Full);
+    }
+
+    #[test]
+    fn responses_carry_the_matching_status_and_headers() {
+        let partial = RangeOutcome::Single(range(10, 19)).response(100);
+        assert_eq!(partial.status, StatusCode::PARTIAL_CONTENT);
+        assert_eq!((partial.offset, partial.length), (10, 10));
+        let built = partial
+            .apply(axum::http::Response::builder())
+            .body(())
+            .unwrap();
+        assert_eq!(built.headers()[header::CONTENT_RANGE], "bytes 10-19/100");
+        assert_eq!(built.headers()[header::CONTENT_LENGTH], "10");
+
+        let unsatisfiable = RangeOutcome::Unsatisfiable.response(100);
+        assert!(unsatisfiable.is_unsatisfiable());
+        let built = unsatisfiable
+            .apply(axum::http::Response::builder())
+            .body(())
+            .unwrap();
+        assert_eq!(built.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(built.headers()[header::CONTENT_RANGE], "bytes */100");
+        assert_eq!(built.headers()[header::CONTENT_LENGTH], "0");
+
+        let multi =
+            RangeOutcome::Multi(vec![range(0, 1), range(5, 6)]).response(100);
+        assert_eq!(multi.status, StatusCode::OK);
+        assert_eq!((multi.offset, multi.length), (0, 100));
+        assert!(multi.content_range.is_none());
+    }
+}
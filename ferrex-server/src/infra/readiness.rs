@@ -0,0 +1,93 @@
+//! Readiness flag backing the `/ready` probe.
+//!
+//! `/health` answers "is the process alive"; readiness answers "should this
+//! instance receive traffic". The flag starts unset, is raised once startup
+//! has initialized the schema and started the scan orchestrator, and is
+//! lowered again when graceful shutdown begins draining connections.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicU8, Ordering},
+};
+
+const STARTING: u8 = 0;
+const READY: u8 = 1;
+const DRAINING: u8 = 2;
+
+/// Lifecycle phase reported by `/ready`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadinessPhase {
+    Starting,
+    Ready,
+    Draining,
+}
+
+impl ReadinessPhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReadinessPhase::Starting => "starting",
+            ReadinessPhase::Ready => "ready",
+            ReadinessPhase::Draining => "draining",
+        }
+    }
+}
+
+/// Shared readiness state. Cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct Readiness {
+    phase: Arc<AtomicU8>,
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark startup as complete. Has no effect once draining has begun, so a
+    /// late startup step cannot re-admit traffic during shutdown.
+    pub fn mark_ready(&self) {
+        let _ = self.phase.compare_exchange(
+            STARTING,
+            READY,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+    }
+
+    /// Stop advertising readiness ahead of graceful shutdown.
+    pub fn mark_draining(&self) {
+        self.phase.store(DRAINING, Ordering::Release);
+    }
+
+    pub fn phase(&self) -> ReadinessPhase {
+        match self.phase.load(Ordering::Acquire) {
+            READY => ReadinessPhase::Ready,
+            DRAINING => ReadinessPhase::Draining,
+            _ => ReadinessPhase::Starting,
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.phase() == ReadinessPhase::Ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readiness_moves_forward_only() {
+        let readiness = Readiness::new();
+        assert_eq!(readiness.phase(), ReadinessPhase::Starting);
+
+        let shared = readiness.clone();
+        shared.mark_ready();
+        assert!(readiness.is_ready());
+
+        readiness.mark_draining();
+        readiness.mark_ready();
+        assert_eq!(shared.phase(), ReadinessPhase::Draining);
+        assert!(!shared.is_ready());
+    }
+}
@@ -0,0 +1,153 @@
+//! Pool of multiplexed Redis connections.
+//!
+//! Each [`ConnectionManager`] pipelines concurrent commands over one socket
+//! and reconnects on its own; the pool opens `REDIS_POOL_SIZE` of them so a
+//! slow reply on one socket does not hold up every rate-limit check.
+
+use std::{
+    fmt, io,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, anyhow};
+use redis::{RedisError, RedisResult, aio::ConnectionManager};
+use serde::Serialize;
+
+use crate::infra::config::{RedisConfig, RedisOutagePolicy};
+
+/// Result of the Redis probe reported by `/health`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RedisHealth {
+    pub status: &'static str,
+    pub pool_size: usize,
+    pub outage_policy: RedisOutagePolicy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RedisHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+struct Inner {
+    connections: Vec<ConnectionManager>,
+    next: AtomicUsize,
+    command_timeout: Duration,
+    outage_policy: RedisOutagePolicy,
+}
+
+/// Shared Redis connections. Cheap to clone.
+#[derive(Clone)]
+pub struct RedisPool {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for RedisPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisPool")
+            .field("size", &self.inner.connections.len())
+            .field("command_timeout", &self.inner.command_timeout)
+            .field("outage_policy", &self.inner.outage_policy)
+            .finish()
+    }
+}
+
+impl RedisPool {
+    /// Open every connection of the pool, failing if any of them cannot be
+    /// established within the connect timeout.
+    pub async fn connect(config: &RedisConfig) -> Result<Self> {
+        let client = redis::Client::open(config.url.as_str())
+            .context("Failed to create Redis client")?;
+
+        let mut connections = Vec::with_capacity(config.pool_size);
+        for _ in 0..config.pool_size.max(1) {
+            let connection = tokio::time::timeout(
+                config.connect_timeout,
+                ConnectionManager::new(client.clone()),
+            )
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "Timed out after {:?} connecting to Redis",
+                    config.connect_timeout
+                )
+            })?
+            .context("Failed to create Redis connection manager")?;
+            connections.push(connection);
+        }
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                connections,
+                next: AtomicUsize::new(0),
+                command_timeout: config.command_timeout,
+                outage_policy: config.outage_policy,
+            }),
+        })
+    }
+
+    /// The next connection in round-robin order.
+    pub fn connection(&self) -> ConnectionManager {
+        let connections = &self.inner.connections;
+        let index =
+            self.inner.next.fetch_add(1, Ordering::Relaxed) % connections.len();
+        connections[index].clone()
+    }
+
+    pub fn size(&self) -> usize {
+        self.inner.connections.len()
+    }
+
+    pub fn command_timeout(&self) -> Duration {
+        self.inner.command_timeout
+    }
+
+    pub fn outage_policy(&self) -> RedisOutagePolicy {
+        self.inner.outage_policy
+    }
+
+    /// Run `command`, failing with a timeout error once it takes longer
+    /// than the configured command timeout.
+    pub async fn timed<T>(
+        &self,
+        command: impl Future<Output = RedisResult<T>>,
+    ) -> RedisResult<T> {
+        tokio::time::timeout(self.inner.command_timeout, command)
+            .await
+            .unwrap_or_else(|_| Err(command_timed_out()))
+    }
+
+    /// `PING` Redis over the next connection.
+    pub async fn health(&self) -> RedisHealth {
+        let started = Instant::now();
+        let mut connection = self.connection();
+        let result = self
+            .timed(redis::cmd("PING").query_async::<String>(&mut connection))
+            .await;
+        let (status, latency_ms, error) = match result {
+            Ok(_) => {
+                ("healthy", Some(started.elapsed().as_millis() as u64), None)
+            }
+            Err(err) => ("unhealthy", None, Some(err.to_string())),
+        };
+        RedisHealth {
+            status,
+            pool_size: self.size(),
+            outage_policy: self.outage_policy(),
+            latency_ms,
+            error,
+        }
+    }
+}
+
+fn command_timed_out() -> RedisError {
+    io::Error::new(io::ErrorKind::TimedOut, "Redis command timed out").into()
+}
@@ -0,0 +1,150 @@
+//! Streams of media files stored behind a URL.
+//!
+//! A remote file is never opened locally. When the upstream's host is listed
+//! in `REMOTE_MEDIA_REDIRECT_HOSTS` the client is redirected to it and
+//! fetches the file directly; otherwise the upstream's answer is relayed.
+//! Relayed requests forward `Range` and `If-Range` and pass `Content-Range`
+//! and the caching validators back, so players seek in remote files the
+//! same way they do in local ones. The upstream's own redirects are not
+//! followed, so a relayed request cannot be bounced to another host.
+//! Timestamp seeks and transcodes need a local file and are not offered for
+//! remote ones.
+
+use std::{io, sync::LazyLock, time::Duration};
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, HeaderName, StatusCode, header},
+    response::{IntoResponse, Redirect, Response},
+};
+use futures::TryStreamExt;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::infra::stream_pacing::paced_body;
+
+/// Only connecting is bounded; a relayed stream lasts as long as playback.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+const FORWARDED_REQUEST_HEADERS: [HeaderName; 2] =
+    [header::RANGE, header::IF_RANGE];
+
+const RELAYED_RESPONSE_HEADERS: [HeaderName; 6] = [
+    header::CONTENT_TYPE,
+    header::CONTENT_LENGTH,
+    header::CONTENT_RANGE,
+    header::ACCEPT_RANGES,
+    header::ETAG,
+    header::LAST_MODIFIED,
+];
+
+static UPSTREAM_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Failed to create HTTP client")
+});
+
+/// Whether clients may be sent to `url` directly: its host is one of
+/// `allowed_hosts`, compared case-insensitively.
+pub fn may_redirect(url: &str, allowed_hosts: &[String]) -> bool {
+    let Some(host) = Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+    else {
+        return false;
+    };
+    allowed_hosts
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(&host))
+}
+
+/// Answer a stream request for the remote file at `url`, by redirect when
+/// policy allows it and by relaying the upstream otherwise. Relayed bodies
+/// are paced to `bandwidth` kbps; redirected ones cannot be.
+pub async fn serve(
+    url: &str,
+    headers: &HeaderMap,
+    allowed_hosts: &[String],
+    bandwidth: Option<u64>,
+    head_only: bool,
+) -> Result<Response, (StatusCode, String)> {
+    if may_redirect(url, allowed_hosts) {
+        debug!("Redirecting stream to {}", url);
+        return Ok(Redirect::temporary(url).into_response());
+    }
+    proxy(url, headers, bandwidth, head_only).await
+}
+
+async fn proxy(
+    url: &str,
+    headers: &HeaderMap,
+    bandwidth: Option<u64>,
+    head_only: bool,
+) -> Result<Response, (StatusCode, String)> {
+    let method = if head_only {
+        reqwest::Method::HEAD
+    } else {
+        reqwest::Method::GET
+    };
+    let mut request = UPSTREAM_CLIENT.request(method, url);
+    for name in FORWARDED_REQUEST_HEADERS {
+        if let Some(value) = headers.get(&name) {
+            request = request.header(name, value.clone());
+        }
+    }
+
+    let upstream = request.send().await.map_err(|e| {
+        warn!("Remote media {} unreachable: {}", url, e);
+        (
+            StatusCode::BAD_GATEWAY,
+            "Remote media upstream unreachable".to_string(),
+        )
+    })?;
+    let status = upstream.status();
+    if !status.is_success() && status != StatusCode::RANGE_NOT_SATISFIABLE {
+        warn!("Remote media {} answered {}", url, status);
+        return Err((
+            StatusCode::BAD_GATEWAY,
+            format!("Remote media upstream answered {status}"),
+        ));
+    }
+
+    let mut builder = Response::builder()
+        .status(status)
+        .header("Cache-Control", "private, no-store");
+    for name in RELAYED_RESPONSE_HEADERS {
+        if let Some(value) = upstream.headers().get(&name) {
+            builder = builder.header(name, value.clone());
+        }
+    }
+    let body = if head_only {
+        Body::empty()
+    } else {
+        paced_body(upstream.bytes_stream().map_err(io::Error::other), bandwidth)
+    };
+    builder.body(body).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to build remote media response: {e}"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_listed_hosts_are_redirected_to() {
+        let hosts = vec!["cdn.example.com".to_string()];
+
+        assert!(may_redirect("https://CDN.example.com/a/Heat.mkv", &hosts));
+        assert!(may_redirect("http://cdn.example.com:8080/b.mp4", &hosts));
+        assert!(!may_redirect("https://media.example.com/a.mkv", &hosts));
+        assert!(!may_redirect("https://cdn.example.com.evil/a.mkv", &hosts));
+        assert!(!may_redirect("not a url", &hosts));
+        assert!(!may_redirect("https://cdn.example.com/a.mkv", &[]));
+    }
+}
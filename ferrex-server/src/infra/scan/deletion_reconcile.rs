@@ -0,0 +1,313 @@
+//! Deletion reconcile.
+//!
+//! Files deleted from disk keep their records and cached images until the
+//! next full scan notices. A reconcile only stats the files a library
+//! already indexes, so it finishes in a fraction of a scan's time: records
+//! of files confirmed gone are deleted along with the cached sizes of their
+//! images, and [`MediaEvent::MediaDeleted`] is published for each.
+//!
+//! An unmounted share looks exactly like a mass deletion, so nothing is
+//! removed below a root that is missing, not a directory or empty (mount
+//! points usually stay behind as empty directories). A root under which
+//! every one of a sizeable number of files is gone is treated the same way:
+//! it most likely points at a different volume now.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::Utc;
+use ferrex_core::{
+    api::types::{LibraryReconcileReport, ReconciledFile},
+    application::unit_of_work::AppUnitOfWork,
+    database::repository_ports::media_files::{
+        MediaFileFilter, MediaFileSort, MediaFileSortField, Page,
+    },
+    infra::media::image_service::ImageService,
+    types::{Library, LibraryId, MediaEvent, MediaFile, library::LibraryType},
+};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use super::{media_event_bus::MediaEventBus, scan_manager::ScanControlError};
+
+const RECONCILE_PAGE_SIZE: u32 = 500;
+/// Roots indexing at least this many files are left alone when all of them
+/// are missing.
+const WHOLE_ROOT_GUARD_MIN_FILES: usize = 20;
+
+/// Runs deletion reconciles, one at a time per library.
+pub struct DeletionReconciler {
+    unit_of_work: Arc<AppUnitOfWork>,
+    media_bus: Arc<MediaEventBus>,
+    images: Option<Arc<ImageService>>,
+    running: Mutex<HashSet<LibraryId>>,
+}
+
+impl DeletionReconciler {
+    pub fn new(
+        unit_of_work: Arc<AppUnitOfWork>,
+        media_bus: Arc<MediaEventBus>,
+        images: Option<Arc<ImageService>>,
+    ) -> Self {
+        Self {
+            unit_of_work,
+            media_bus,
+            images,
+            running: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Reconcile `library` and wait for the report.
+    pub async fn reconcile(
+        &self,
+        library: &Library,
+        dry_run: bool,
+    ) -> Result<LibraryReconcileReport, ScanControlError> {
+        if !self.running.lock().await.insert(library.id) {
+            return Err(ScanControlError::ReconcileInProgress);
+        }
+        let result = self.run(library, dry_run).await;
+        self.running.lock().await.remove(&library.id);
+        result
+    }
+
+    /// Reconcile every enabled video library, logging failures.
+    pub async fn reconcile_all(&self) {
+        let libraries = match self.unit_of_work.libraries.list_libraries().await
+        {
+            Ok(libraries) => libraries,
+            Err(err) => {
+                warn!(error = %err, "deletion reconcile could not list libraries");
+                return;
+            }
+        };
+        for library in libraries.iter().filter(|library| {
+            library.enabled && library.library_type != LibraryType::Music
+        }) {
+            if let Err(err) = self.reconcile(library, false).await {
+                warn!(
+                    library = %library.id,
+                    error = %err,
+                    "scheduled deletion reconcile failed"
+                );
+            }
+        }
+    }
+
+    async fn run(
+        &self,
+        library: &Library,
+        dry_run: bool,
+    ) -> Result<LibraryReconcileReport, ScanControlError> {
+        let started_at = Utc::now();
+
+        let mut offline_roots: HashSet<PathBuf> = HashSet::new();
+        for root in &library.paths {
+            if !root_online(root).await {
+                offline_roots.insert(root.clone());
+            }
+        }
+
+        let mut files_checked = 0u64;
+        let mut files_skipped = 0u64;
+        let mut indexed_per_root: HashMap<PathBuf, usize> = HashMap::new();
+        let mut missing: Vec<(PathBuf, MediaFile)> = Vec::new();
+
+        let filter = MediaFileFilter {
+            library_id: Some(library.id),
+            ..MediaFileFilter::default()
+        };
+        let sort = MediaFileSort::ascending(MediaFileSortField::DiscoveredAt);
+        let mut offset = 0u32;
+        loop {
+            let page = self
+                .unit_of_work
+                .media_files_read
+                .list(
+                    filter.clone(),
+                    sort,
+                    Page {
+                        limit: RECONCILE_PAGE_SIZE,
+                        offset,
+                    },
+                )
+                .await
+                .map_err(|err| ScanControlError::Internal(err.to_string()))?;
+            let page_len = page.len() as u32;
+
+            for file in page {
+                files_checked += 1;
+                let Some(root) = owning_root(&library.paths, &file.path) else {
+                    files_skipped += 1;
+                    continue;
+                };
+                if offline_roots.contains(root) {
+                    files_skipped += 1;
+                    continue;
+                }
+                *indexed_per_root.entry(root.clone()).or_default() += 1;
+                match tokio::fs::try_exists(&file.path).await {
+                    Ok(true) => {}
+                    Ok(false) => missing.push((root.clone(), file)),
+                    // Permission problems and the like prove nothing.
+                    Err(_) => files_skipped += 1,
+                }
+            }
+
+            if page_len < RECONCILE_PAGE_SIZE {
+                break;
+            }
+            offset = offset.saturating_add(page_len);
+        }
+
+        let mut missing_per_root: HashMap<&Path, usize> = HashMap::new();
+        for (root, _) in &missing {
+            *missing_per_root.entry(root.as_path()).or_default() += 1;
+        }
+        let emptied: HashSet<PathBuf> = missing_per_root
+            .into_iter()
+            .filter(|(root, count)| {
+                *count >= WHOLE_ROOT_GUARD_MIN_FILES
+                    && indexed_per_root.get(*root) == Some(count)
+            })
+            .map(|(root, _)| root.to_path_buf())
+            .collect();
+        for root in &emptied {
+            warn!(
+                library = %library.id,
+                root = %root.display(),
+                "every indexed file under the root is missing; leaving it alone"
+            );
+        }
+
+        let mut removed = Vec::new();
+        let mut images_invalidated = 0u64;
+        for (root, file) in missing {
+            if emptied.contains(&root) {
+                files_skipped += 1;
+                continue;
+            }
+            if !dry_run {
+                images_invalidated += self.remove(library.id, &file).await?;
+            }
+            removed.push(ReconciledFile {
+                file_id: file.id,
+                path: file.path.display().to_string(),
+            });
+        }
+
+        offline_roots.extend(emptied);
+        let mut offline_roots: Vec<String> = offline_roots
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect();
+        offline_roots.sort();
+
+        info!(
+            library = %library.id,
+            dry_run,
+            files_checked,
+            files_skipped,
+            removed = removed.len(),
+            images_invalidated,
+            offline_roots = offline_roots.len(),
+            "deletion reconcile finished"
+        );
+
+        Ok(LibraryReconcileReport {
+            library_id: library.id,
+            started_at,
+            finished_at: Utc::now(),
+            dry_run,
+            files_checked,
+            files_skipped,
+            offline_roots,
+            removed,
+            images_invalidated,
+        })
+    }
+
+    /// Drop the cached images of `file`'s media, then its record.
+    async fn remove(
+        &self,
+        library_id: LibraryId,
+        file: &MediaFile,
+    ) -> Result<u64, ScanControlError> {
+        let mut images_invalidated = 0;
+        if let Some(images) = &self.images {
+            match images
+                .invalidate_all_variants(*file.media_id.as_uuid())
+                .await
+            {
+                Ok(count) => images_invalidated = u64::from(count),
+                Err(err) => warn!(
+                    media = %file.media_id.as_uuid(),
+                    error = %err,
+                    "failed to drop cached images of deleted media"
+                ),
+            }
+        }
+
+        self.unit_of_work
+            .media_files_write
+            .delete_by_path(library_id, &file.path.to_string_lossy())
+            .await
+            .map_err(|err| ScanControlError::Internal(err.to_string()))?;
+        self.media_bus
+            .publish(MediaEvent::MediaDeleted { id: file.media_id });
+        Ok(images_invalidated)
+    }
+}
+
+/// The most specific root `path` lives under, when libraries nest.
+fn owning_root<'a>(roots: &'a [PathBuf], path: &Path) -> Option<&'a PathBuf> {
+    roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count())
+}
+
+/// A root counts as online when it is a directory with at least one entry.
+async fn root_online(root: &Path) -> bool {
+    match tokio::fs::read_dir(root).await {
+        Ok(mut entries) => matches!(entries.next_entry().await, Ok(Some(_))),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn missing_and_empty_roots_are_offline() {
+        let temp = tempfile::tempdir().unwrap();
+        let mounted = temp.path().join("mounted");
+        let empty = temp.path().join("empty");
+        std::fs::create_dir_all(&mounted).unwrap();
+        std::fs::create_dir_all(&empty).unwrap();
+        std::fs::write(mounted.join("Heat (1995).mkv"), b"").unwrap();
+
+        assert!(root_online(&mounted).await);
+        assert!(!root_online(&empty).await);
+        assert!(!root_online(&temp.path().join("unmounted")).await);
+        assert!(!root_online(&mounted.join("Heat (1995).mkv")).await);
+    }
+
+    #[test]
+    fn files_belong_to_their_most_specific_root() {
+        let roots = vec![PathBuf::from("/media"), PathBuf::from("/media/tv")];
+        assert_eq!(
+            owning_root(&roots, Path::new("/media/tv/Show/S01E01.mkv")),
+            Some(&roots[1])
+        );
+        assert_eq!(
+            owning_root(&roots, Path::new("/media/movies/Heat.mkv")),
+            Some(&roots[0])
+        );
+        assert_eq!(owning_root(&roots, Path::new("/elsewhere/x.mkv")), None);
+    }
+}
@@ -17,12 +17,25 @@ pub struct MediaEventFrame {
     pub event: MediaEvent,
 }
 
+/// Outcome of replaying history for a reconnecting subscriber.
+#[derive(Debug)]
+pub enum MediaEventReplay {
+    /// Every retained frame newer than the requested sequence.
+    Frames(Vec<MediaEventFrame>),
+    /// Frames newer than the requested sequence were evicted, or the sequence
+    /// belongs to a previous server run; the subscriber must reload.
+    Expired { latest_sequence: u64 },
+}
+
 #[derive(Debug)]
 pub struct MediaEventBus {
     tx: broadcast::Sender<MediaEventFrame>,
     history: Mutex<VecDeque<MediaEventFrame>>,
     history_capacity: usize,
     sequence: AtomicU64,
+    /// Sequence of the newest frame dropped from history; updated under the
+    /// history lock.
+    evicted_through: AtomicU64,
 }
 
 impl MediaEventBus {
@@ -35,6 +48,7 @@ impl MediaEventBus {
             history: Mutex::new(VecDeque::with_capacity(history_capacity)),
             history_capacity,
             sequence: AtomicU64::new(0),
+            evicted_through: AtomicU64::new(0),
         }
     }
 
@@ -59,8 +73,11 @@ impl MediaEventBus {
                 .history
                 .lock()
                 .expect("media event history mutex poisoned");
-            if guard.len() == self.history_capacity {
-                guard.pop_front();
+            if guard.len() == self.history_capacity
+                && let Some(evicted) = guard.pop_front()
+            {
+                self.evicted_through
+                    .store(evicted.sequence, Ordering::Relaxed);
             }
             guard.push_back(frame.clone());
         }
@@ -84,6 +101,33 @@ impl MediaEventBus {
             .collect()
     }
 
+    pub fn latest_sequence(&self) -> u64 {
+        self.sequence.load(Ordering::Relaxed)
+    }
+
+    /// Replay retained frames after `sequence`, or report that the gap can no
+    /// longer be filled from history.
+    pub fn replay_since(&self, sequence: u64) -> MediaEventReplay {
+        let guard = self
+            .history
+            .lock()
+            .expect("media event history mutex poisoned");
+        let latest_sequence = self.sequence.load(Ordering::Relaxed);
+        let evicted_through = self.evicted_through.load(Ordering::Relaxed);
+
+        if sequence > latest_sequence || sequence < evicted_through {
+            return MediaEventReplay::Expired { latest_sequence };
+        }
+
+        MediaEventReplay::Frames(
+            guard
+                .iter()
+                .filter(|frame| frame.sequence > sequence)
+                .cloned()
+                .collect(),
+        )
+    }
+
     pub fn history_since_instant(
         &self,
         since: Instant,
@@ -102,7 +146,11 @@ impl MediaEventBus {
     fn should_record_history(event: &MediaEvent) -> bool {
         matches!(
             event,
-            MediaEvent::MovieBatchFinalized { .. }
+            MediaEvent::MovieAdded { .. }
+                | MediaEvent::MovieUpdated { .. }
+                | MediaEvent::MovieBatchFinalized { .. }
+                | MediaEvent::SeriesAdded { .. }
+                | MediaEvent::SeriesUpdated { .. }
                 | MediaEvent::SeriesBundleFinalized { .. }
                 | MediaEvent::MediaDeleted { .. }
         )
@@ -111,7 +159,7 @@ impl MediaEventBus {
 
 #[cfg(test)]
 mod tests {
-    use super::MediaEventBus;
+    use super::{MediaEventBus, MediaEventReplay};
     use ferrex_core::types::{
         LibraryId, MediaEvent, MovieBatchId, ScanEventMetadata, SeriesID,
     };
@@ -176,4 +224,35 @@ mod tests {
             MediaEvent::SeriesBundleFinalized { .. }
         ));
     }
+
+    #[test]
+    fn replay_reports_expired_once_history_is_evicted() {
+        let bus = MediaEventBus::new(2, 8);
+        let library_id = LibraryId(Uuid::from_u128(1));
+
+        for batch in 1..=3 {
+            bus.publish(MediaEvent::MovieBatchFinalized {
+                library_id,
+                batch_id: MovieBatchId(batch),
+            });
+        }
+
+        match bus.replay_since(1) {
+            MediaEventReplay::Frames(frames) => {
+                let sequences: Vec<u64> =
+                    frames.iter().map(|frame| frame.sequence).collect();
+                assert_eq!(sequences, vec![2, 3]);
+            }
+            other => panic!("expected frames, got {other:?}"),
+        }
+
+        assert!(matches!(
+            bus.replay_since(0),
+            MediaEventReplay::Expired { latest_sequence: 3 }
+        ));
+        assert!(matches!(
+            bus.replay_since(42),
+            MediaEventReplay::Expired { latest_sequence: 3 }
+        ));
+    }
 }
@@ -0,0 +1,543 @@
+//! Media-file integrity verification.
+//!
+//! Flaky network mounts occasionally serve truncated or zero-filled files and
+//! nothing notices until playback fails. Verification re-stats every media
+//! file of a library against the size recorded at scan time and, when asked,
+//! samples the head and tail of each file. Zeroed samples fail outright, and
+//! a digest of the samples is recorded so later runs notice content that
+//! changed while the size and modification time stayed put. Files that fail
+//! have the scan cursor of their folder reset so the next scan revisits them.
+//! Files stored behind a URL are not verified.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use chrono::{DateTime, SubsecRound, Utc};
+use ferrex_core::{
+    api::types::{
+        LibraryVerifyReport, MediaFileIntegrity, MediaFileVerifyIssue,
+    },
+    application::unit_of_work::AppUnitOfWork,
+    database::repository_ports::media_files::{
+        MediaFileDigest, MediaFileFilter, MediaFileSort, MediaFileSortField,
+        Page,
+    },
+    domain::scan::orchestration::scan_cursor::{
+        ScanCursorId, ScanCursorRepository, normalize_path,
+    },
+    error::MediaError,
+    types::{Library, LibraryId},
+};
+use sha2::{Digest, Sha256};
+use tokio::{
+    io::{AsyncReadExt, AsyncSeekExt, SeekFrom},
+    sync::RwLock,
+};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::scan_manager::ScanControlError;
+
+const VERIFY_PAGE_SIZE: u32 = 500;
+/// Upper bound for the per-end sample so a typo cannot read whole files.
+const MAX_SAMPLE_BYTES: u32 = 4 * 1024 * 1024;
+
+/// Runs library verification and keeps the latest report per library.
+pub struct MediaIntegrityVerifier {
+    unit_of_work: Arc<AppUnitOfWork>,
+    cursors: Arc<dyn ScanCursorRepository>,
+    reports: RwLock<HashMap<LibraryId, LibraryVerifyReport>>,
+    running: Arc<Mutex<HashSet<LibraryId>>>,
+}
+
+impl MediaIntegrityVerifier {
+    pub fn new(
+        unit_of_work: Arc<AppUnitOfWork>,
+        cursors: Arc<dyn ScanCursorRepository>,
+    ) -> Self {
+        Self {
+            unit_of_work,
+            cursors,
+            reports: RwLock::new(HashMap::new()),
+            running: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    pub async fn latest_report(
+        &self,
+        library_id: LibraryId,
+    ) -> Option<LibraryVerifyReport> {
+        self.reports.read().await.get(&library_id).cloned()
+    }
+
+    /// Verify `library` and wait for the report.
+    pub async fn verify(
+        &self,
+        library: &Library,
+        sample_bytes: Option<u32>,
+    ) -> Result<LibraryVerifyReport, ScanControlError> {
+        let _claim = RunningClaim::acquire(&self.running, library.id)?;
+        self.run(library, sample_bytes).await
+    }
+
+    /// Verify `library` in the background; the report becomes available via
+    /// [`Self::latest_report`] once the run finishes.
+    pub async fn spawn(
+        self: &Arc<Self>,
+        library: Library,
+        sample_bytes: Option<u32>,
+    ) -> Result<(), ScanControlError> {
+        let claim = RunningClaim::acquire(&self.running, library.id)?;
+
+        let this = Arc::clone(self);
+        tokio::spawn(async move {
+            // Owned by the task so a panic still releases the library.
+            let _claim = claim;
+            if let Err(err) = this.run(&library, sample_bytes).await {
+                warn!(
+                    library = %library.id,
+                    error = %err,
+                    "background library verification failed"
+                );
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn run(
+        &self,
+        library: &Library,
+        sample_bytes: Option<u32>,
+    ) -> Result<LibraryVerifyReport, ScanControlError> {
+        let started_at = Utc::now();
+        let sample_bytes = sample_bytes
+            .filter(|bytes| *bytes > 0)
+            .map(|bytes| bytes.min(MAX_SAMPLE_BYTES));
+
+        let mut offline_roots = Vec::new();
+        for root in &library.paths {
+            let online = tokio::fs::metadata(root)
+                .await
+                .map(|meta| meta.is_dir())
+                .unwrap_or(false);
+            if !online {
+                offline_roots.push(root.clone());
+            }
+        }
+
+        let mut files_checked = 0u64;
+        let mut files_ok = 0u64;
+        let mut issues = Vec::new();
+        let mut rescan_folders: HashSet<PathBuf> = HashSet::new();
+
+        let filter = MediaFileFilter {
+            library_id: Some(library.id),
+            ..MediaFileFilter::default()
+        };
+        let sort = MediaFileSort::ascending(MediaFileSortField::DiscoveredAt);
+        let mut offset = 0u32;
+
+        loop {
+            let page = self
+                .unit_of_work
+                .media_files_read
+                .list(
+                    filter.clone(),
+                    sort,
+                    Page {
+                        limit: VERIFY_PAGE_SIZE,
+                        offset,
+                    },
+                )
+                .await
+                .map_err(|err| ScanControlError::Internal(err.to_string()))?;
+            let page_len = page.len() as u32;
+            let recorded = match sample_bytes {
+                Some(_) => {
+                    let ids: Vec<Uuid> =
+                        page.iter().map(|file| file.id).collect();
+                    self.unit_of_work
+                        .media_files_read
+                        .digests(&ids)
+                        .await
+                        .map_err(|err| {
+                            ScanControlError::Internal(err.to_string())
+                        })?
+                }
+                None => HashMap::new(),
+            };
+
+            for file in page {
+                if file.location().is_remote() {
+                    continue;
+                }
+                files_checked += 1;
+
+                let root_offline = offline_roots
+                    .iter()
+                    .any(|root| file.path.starts_with(root));
+                let (integrity, digest) = if root_offline {
+                    (MediaFileIntegrity::LibraryOffline, None)
+                } else {
+                    check_media_file(
+                        file.id,
+                        &file.path,
+                        file.size,
+                        sample_bytes,
+                        recorded.get(&file.id),
+                    )
+                    .await
+                };
+
+                if integrity == MediaFileIntegrity::Ok {
+                    files_ok += 1;
+                    if let Some(digest) = digest
+                        && recorded.get(&file.id) != Some(&digest)
+                        && let Err(err) = self
+                            .unit_of_work
+                            .media_files_write
+                            .record_digest(digest)
+                            .await
+                    {
+                        warn!(
+                            file = %file.id,
+                            error = %err,
+                            "failed to record media file digest"
+                        );
+                    }
+                    continue;
+                }
+
+                if integrity.needs_rescan()
+                    && let Some(parent) = file.path.parent()
+                {
+                    rescan_folders.insert(parent.to_path_buf());
+                }
+
+                issues.push(MediaFileVerifyIssue {
+                    file_id: file.id,
+                    path: file.path.display().to_string(),
+                    integrity,
+                });
+            }
+
+            if page_len < VERIFY_PAGE_SIZE {
+                break;
+            }
+            offset = offset.saturating_add(page_len);
+        }
+
+        let mut folders_marked_for_rescan = 0u64;
+        for folder in rescan_folders {
+            match self.mark_folder_for_rescan(library.id, &folder).await {
+                Ok(true) => folders_marked_for_rescan += 1,
+                Ok(false) => {}
+                Err(err) => warn!(
+                    library = %library.id,
+                    folder = %folder.display(),
+                    error = %err,
+                    "failed to mark folder for rescan"
+                ),
+            }
+        }
+
+        let report = LibraryVerifyReport {
+            library_id: library.id,
+            started_at,
+            finished_at: Utc::now(),
+            files_checked,
+            files_ok,
+            offline_roots: offline_roots
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect(),
+            issues,
+            folders_marked_for_rescan,
+        };
+
+        info!(
+            library = %library.id,
+            files_checked,
+            files_ok,
+            issues = report.issues.len(),
+            offline_roots = report.offline_roots.len(),
+            folders_marked_for_rescan,
+            "library verification finished"
+        );
+
+        self.reports
+            .write()
+            .await
+            .insert(library.id, report.clone());
+
+        Ok(report)
+    }
+
+    /// Clear the listing hash of the folder's cursor so the next scan does
+    /// not short-circuit it as unchanged.
+    async fn mark_folder_for_rescan(
+        &self,
+        library_id: LibraryId,
+        folder: &Path,
+    ) -> Result<bool, MediaError> {
+        let folder_norm = normalize_path(folder)?;
+        let cursor_id =
+            ScanCursorId::new(library_id, &vec![PathBuf::from(&folder_norm)]);
+
+        let Some(mut cursor) = self.cursors.get(&cursor_id).await? else {
+            return Ok(false);
+        };
+        if cursor.listing_hash.is_empty() {
+            return Ok(true);
+        }
+
+        cursor.listing_hash.clear();
+        self.cursors.upsert(cursor).await?;
+        Ok(true)
+    }
+}
+
+/// Marks a library as being verified until dropped, so the claim is
+/// released however the run ends, panics and cancellation included.
+struct RunningClaim {
+    running: Arc<Mutex<HashSet<LibraryId>>>,
+    library_id: LibraryId,
+}
+
+impl RunningClaim {
+    fn acquire(
+        running: &Arc<Mutex<HashSet<LibraryId>>>,
+        library_id: LibraryId,
+    ) -> Result<Self, ScanControlError> {
+        if running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(library_id)
+        {
+            Ok(Self {
+                running: Arc::clone(running),
+                library_id,
+            })
+        } else {
+            Err(ScanControlError::VerificationInProgress)
+        }
+    }
+}
+
+impl Drop for RunningClaim {
+    fn drop(&mut self) {
+        self.running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.library_id);
+    }
+}
+
+/// Check a single media file against its recorded size and, when
+/// `sample_bytes` is set, against zero-filled head or tail regions and the
+/// digest `recorded` by an earlier run. The digest is only compared when it
+/// was taken with the same sample size from the same version of the file.
+///
+/// Returns the digest of this run's samples alongside the outcome, so a
+/// file that passed can have it recorded.
+pub async fn check_media_file(
+    file_id: Uuid,
+    path: &Path,
+    expected_size: u64,
+    sample_bytes: Option<u32>,
+    recorded: Option<&MediaFileDigest>,
+) -> (MediaFileIntegrity, Option<MediaFileDigest>) {
+    let metadata = match tokio::fs::metadata(path).await {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return (MediaFileIntegrity::Missing, None);
+        }
+        Err(err) => {
+            return (unreadable(err), None);
+        }
+    };
+
+    let actual_size = metadata.len();
+    if actual_size != expected_size {
+        let integrity = MediaFileIntegrity::SizeMismatch {
+            expected_size,
+            actual_size,
+        };
+        return (integrity, None);
+    }
+
+    let Some(sample_bytes) = sample_bytes else {
+        return (MediaFileIntegrity::Ok, None);
+    };
+    // Postgres keeps microseconds; finer precision would never compare equal.
+    let modified_at = match metadata.modified() {
+        Ok(modified) => DateTime::<Utc>::from(modified).trunc_subsecs(6),
+        Err(err) => return (unreadable(err), None),
+    };
+
+    let digest = match sample_digest(path, actual_size, sample_bytes).await {
+        Ok(Some(digest)) => digest,
+        Ok(None) => return (MediaFileIntegrity::Zeroed, None),
+        Err(err) => return (unreadable(err), None),
+    };
+    let digest = MediaFileDigest {
+        file_id,
+        sample_bytes,
+        file_size: actual_size,
+        modified_at,
+        digest,
+    };
+
+    let changed = recorded.is_some_and(|recorded| {
+        recorded.sample_bytes == digest.sample_bytes
+            && recorded.file_size == digest.file_size
+            && recorded.modified_at == digest.modified_at
+            && recorded.digest != digest.digest
+    });
+    if changed {
+        return (MediaFileIntegrity::DigestMismatch, None);
+    }
+    (MediaFileIntegrity::Ok, Some(digest))
+}
+
+fn unreadable(err: io::Error) -> MediaFileIntegrity {
+    MediaFileIntegrity::Unreadable {
+        message: err.to_string(),
+    }
+}
+
+/// SHA-256 over the first and last `sample_bytes` of the file, or `None`
+/// when either sample is entirely zeroed.
+async fn sample_digest(
+    path: &Path,
+    size: u64,
+    sample_bytes: u32,
+) -> io::Result<Option<Vec<u8>>> {
+    let len = u64::from(sample_bytes).min(size);
+    if len == 0 {
+        return Ok(None);
+    }
+
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut buf = vec![0u8; len as usize];
+    let mut hasher = Sha256::new();
+
+    file.read_exact(&mut buf).await?;
+    if buf.iter().all(|byte| *byte == 0) {
+        return Ok(None);
+    }
+    hasher.update(&buf);
+
+    file.seek(SeekFrom::Start(size - len)).await?;
+    file.read_exact(&mut buf).await?;
+    if buf.iter().all(|byte| *byte == 0) {
+        return Ok(None);
+    }
+    hasher.update(&buf);
+
+    Ok(Some(hasher.finalize().to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn check_media_file_flags_size_and_zeroed_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("movie.mkv");
+        let id = Uuid::now_v7();
+
+        let mut bytes = vec![0x1a_u8; 64];
+        bytes.extend(std::iter::repeat_n(0u8, 64));
+        tokio::fs::write(&path, &bytes).await.unwrap();
+
+        assert_eq!(
+            check_media_file(id, &path, 128, None, None).await,
+            (MediaFileIntegrity::Ok, None)
+        );
+        assert_eq!(
+            check_media_file(id, &path, 256, None, None).await.0,
+            MediaFileIntegrity::SizeMismatch {
+                expected_size: 256,
+                actual_size: 128,
+            }
+        );
+        assert_eq!(
+            check_media_file(id, &path, 128, Some(32), None).await.0,
+            MediaFileIntegrity::Zeroed
+        );
+        assert_eq!(
+            check_media_file(id, &dir.path().join("gone.mkv"), 128, None, None)
+                .await
+                .0,
+            MediaFileIntegrity::Missing
+        );
+    }
+
+    #[tokio::test]
+    async fn check_media_file_compares_digests_of_the_same_file_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("movie.mkv");
+        let id = Uuid::now_v7();
+
+        tokio::fs::write(&path, vec![0x1a_u8; 128]).await.unwrap();
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        let (integrity, recorded) =
+            check_media_file(id, &path, 128, Some(32), None).await;
+        assert_eq!(integrity, MediaFileIntegrity::Ok);
+        let recorded = recorded.expect("first sample yields a digest");
+
+        // Same bytes: the digest matches.
+        assert_eq!(
+            check_media_file(id, &path, 128, Some(32), Some(&recorded)).await,
+            (MediaFileIntegrity::Ok, Some(recorded.clone()))
+        );
+
+        // Silently changed content under an unchanged size and mtime.
+        let mut bytes = vec![0x1a_u8; 128];
+        bytes[127] = 0x2b;
+        std::fs::write(&path, &bytes).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_eq!(
+            check_media_file(id, &path, 128, Some(32), Some(&recorded)).await,
+            (MediaFileIntegrity::DigestMismatch, None)
+        );
+
+        // A different sample size is a fresh baseline, not a mismatch.
+        let (integrity, digest) =
+            check_media_file(id, &path, 128, Some(16), Some(&recorded)).await;
+        assert_eq!(integrity, MediaFileIntegrity::Ok);
+        assert_eq!(digest.map(|digest| digest.sample_bytes), Some(16));
+    }
+
+    #[tokio::test]
+    async fn running_claim_is_released_when_the_run_panics() {
+        let running = Arc::new(Mutex::new(HashSet::new()));
+        let library_id = LibraryId::new();
+
+        let claim = RunningClaim::acquire(&running, library_id).unwrap();
+        assert!(matches!(
+            RunningClaim::acquire(&running, library_id),
+            Err(ScanControlError::VerificationInProgress)
+        ));
+
+        let run = tokio::spawn(async move {
+            let _claim = claim;
+            panic!("verification blew up");
+        });
+        assert!(run.await.unwrap_err().is_panic());
+
+        assert!(RunningClaim::acquire(&running, library_id).is_ok());
+    }
+}
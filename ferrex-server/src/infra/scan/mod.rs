@@ -1,5 +1,10 @@
+pub mod deletion_reconcile;
 pub mod folder_inventory;
 pub mod media_event_bus;
+pub mod media_integrity;
 pub mod movie_batch_notifier;
+pub mod poster_prewarm;
 pub mod scan_manager;
 pub mod series_bundle_tracker;
+pub mod stage_latency;
+pub mod throughput;
@@ -0,0 +1,152 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use ferrex_core::{
+    infra::media::image_service::ImageService,
+    types::{LibraryId, Media, MediaEvent},
+};
+use ferrex_model::ImageSize;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::media_event_bus::MediaEventBus;
+
+/// Emit a progress event after this many posters have been processed.
+const PROGRESS_EVERY: u64 = 25;
+
+/// Collects the primary posters of media created during a scan and warms the
+/// poster variant once the scan completes.
+///
+/// Items are processed one at a time so the pass never holds more than a
+/// single image download permit; on-demand requests keep the rest.
+pub struct PosterPrewarm {
+    image_service: Arc<ImageService>,
+    media_bus: Arc<MediaEventBus>,
+    pending: Mutex<HashMap<LibraryId, HashSet<Uuid>>>,
+}
+
+impl PosterPrewarm {
+    pub fn new(
+        image_service: Arc<ImageService>,
+        media_bus: Arc<MediaEventBus>,
+    ) -> Self {
+        Self {
+            image_service,
+            media_bus,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remember the primary poster of a newly added movie or series.
+    pub async fn record_added(&self, library_id: LibraryId, media: &Media) {
+        let poster_iid = match media {
+            Media::Movie(movie) => movie.details.primary_poster_iid,
+            Media::Series(series) => series.details.primary_poster_iid,
+            Media::Season(_) | Media::Episode(_) => None,
+        };
+
+        if let Some(iid) = poster_iid {
+            let mut pending = self.pending.lock().await;
+            pending.entry(library_id).or_default().insert(iid);
+        }
+    }
+
+    /// Drain the posters recorded for `library_id` and warm them in the
+    /// background.
+    pub async fn spawn_for_run(
+        self: &Arc<Self>,
+        library_id: LibraryId,
+        scan_id: Uuid,
+    ) {
+        let iids = {
+            let mut pending = self.pending.lock().await;
+            pending.remove(&library_id).unwrap_or_default()
+        };
+
+        if iids.is_empty() {
+            return;
+        }
+
+        let this = Arc::clone(self);
+        tokio::spawn(async move {
+            this.run(library_id, scan_id, iids.into_iter().collect())
+                .await;
+        });
+    }
+
+    async fn run(&self, library_id: LibraryId, scan_id: Uuid, iids: Vec<Uuid>) {
+        let total = iids.len() as u64;
+        let mut warmed = 0u64;
+        let mut skipped = 0u64;
+        let mut failed = 0u64;
+
+        info!(
+            library = %library_id,
+            scan = %scan_id,
+            total,
+            "poster prewarm started"
+        );
+
+        for (index, iid) in iids.into_iter().enumerate() {
+            match self
+                .image_service
+                .prewarm_variant(iid, ImageSize::poster())
+                .await
+            {
+                Ok(true) => warmed += 1,
+                Ok(false) => skipped += 1,
+                Err(err) => {
+                    failed += 1;
+                    warn!(
+                        library = %library_id,
+                        image = %iid,
+                        error = %err,
+                        "poster prewarm failed"
+                    );
+                }
+            }
+
+            let processed = index as u64 + 1;
+            if processed % PROGRESS_EVERY == 0 && processed < total {
+                self.publish_progress(
+                    library_id, scan_id, warmed, skipped, failed, total,
+                );
+            }
+        }
+
+        self.publish_progress(
+            library_id, scan_id, warmed, skipped, failed, total,
+        );
+
+        info!(
+            library = %library_id,
+            scan = %scan_id,
+            warmed,
+            skipped,
+            failed,
+            "poster prewarm finished"
+        );
+    }
+
+    fn publish_progress(
+        &self,
+        library_id: LibraryId,
+        scan_id: Uuid,
+        warmed: u64,
+        skipped: u64,
+        failed: u64,
+        total: u64,
+    ) {
+        self.media_bus.publish(MediaEvent::PosterPrewarmProgress {
+            library_id,
+            scan_id,
+            warmed,
+            skipped,
+            failed,
+            total,
+        });
+    }
+}
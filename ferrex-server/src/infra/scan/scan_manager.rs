@@ -2,37 +2,57 @@ use ferrex_model::{MediaID, SubjectKey};
 
 use ferrex_core::{
     api::types::{
+        DuplicateMedia, ScanLatencyBreakdown,
         ScanLifecycleStatus as ApiScanLifecycleStatus, ScanSnapshotDto,
         SeriesBundleResponse,
     },
     application::unit_of_work::AppUnitOfWork,
+    database::{
+        repository_ports::media_files::{
+            MediaFileFilter, MediaFileSort, MediaFileSortField, Page,
+        },
+        traits::{ScanOrigin, ScanStatus},
+    },
     domain::scan::{
         actors::{
             FileSystemEvent, FileSystemEventKind, LibraryRootsId,
             index::{IndexingChange, IndexingOutcome},
         },
+        exclusions::{
+            ExcludedPathCounts, ScanExclusions, install_library_exclusions,
+        },
+        file_filter::SkippedFileCounts,
+        music::scan_music_library,
         orchestration::{
             JobEvent, LibraryActorCommand, StartMode,
             events::{JobEventPayload, ScanEvent},
             job::{JobId, JobKind},
+            scan_checkpoint::{ScanCheckpoint, ScanCheckpointRepository},
             scan_cursor::{ScanCursor, ScanCursorRepository, normalize_path},
         },
     },
     error::MediaError,
+    infra::{cache::DiskSpaceGuard, media::image_service::ImageService},
     player_prelude::MediaIDLike,
     types::{
         LibraryId, Media, MediaEvent, ScanEventMetadata, ScanProgressEvent,
-        ScanStageLatencySummary, events::ScanSseEventType,
+        events::ScanSseEventType,
+        library::{Library, LibraryType},
     },
 };
 
 use crate::infra::{
     orchestration::ScanOrchestrator,
-    scan::media_event_bus::{MediaEventBus, MediaEventFrame},
+    scan::deletion_reconcile::DeletionReconciler,
+    scan::media_event_bus::{MediaEventBus, MediaEventFrame, MediaEventReplay},
+    scan::media_integrity::MediaIntegrityVerifier,
     scan::movie_batch_notifier::MovieBatchFinalizationNotifiers,
+    scan::poster_prewarm::PosterPrewarm,
     scan::series_bundle_tracker::{
         SeriesBundleFinalization, SeriesBundleTracker,
     },
+    scan::stage_latency::StageLatencyTracker,
+    scan::throughput::{ScanEstimate, ThroughputEstimator},
 };
 
 use axum::http::StatusCode;
@@ -43,6 +63,7 @@ use sha2::Digest;
 use std::{
     collections::{HashMap, HashSet, VecDeque, hash_map::Entry},
     fmt,
+    path::{Component, Path, PathBuf},
     sync::{Arc, Weak},
     time::{Duration, Instant},
 };
@@ -61,15 +82,14 @@ const HISTORY_CAPACITY: usize = 256;
 const EVENT_HISTORY_CAPACITY: usize = 512;
 const MEDIA_EVENT_HISTORY_CAPACITY: usize = 512;
 const MEDIA_EVENT_BROADCAST_CAPACITY: usize = 512;
-const DEFAULT_LATENCIES: ScanStageLatencySummary = ScanStageLatencySummary {
-    scan: 12,
-    analyze: 210,
-    index: 44,
-};
 const DEFAULT_QUIESCENCE: Duration = Duration::from_secs(3);
 const STALLED_SCAN_TIMEOUT_MULTIPLIER: u32 = 5;
 const SERIES_BUNDLE_TRACKER_IDLE_TTL: Duration = Duration::from_secs(10 * 60);
 const SERIES_BUNDLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const CANCEL_DRAIN_POLL: Duration = Duration::from_millis(250);
+const PATH_SCAN_PAGE_SIZE: u32 = 500;
+/// Minimum gap between two progress checkpoints of one scan.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5);
 
 fn subject_key_path(key: &SubjectKey) -> Option<&str> {
     match key {
@@ -82,6 +102,32 @@ fn subject_key_path_owned(key: &SubjectKey) -> Option<String> {
     subject_key_path(key).map(str::to_string)
 }
 
+/// Relay low-disk warnings to library event subscribers.
+fn forward_disk_space_warnings(
+    guard: &DiskSpaceGuard,
+    media_bus: Arc<MediaEventBus>,
+) {
+    if tokio::runtime::Handle::try_current().is_err() {
+        warn!("Disk space warnings not forwarded (no Tokio runtime available)");
+        return;
+    }
+
+    let mut warnings = guard.subscribe();
+    spawn(async move {
+        loop {
+            match warnings.recv().await {
+                Ok(warning) => media_bus.publish(MediaEvent::DiskSpaceLow {
+                    path: warning.path.display().to_string(),
+                    available_bytes: warning.available_bytes,
+                    required_bytes: warning.required_bytes,
+                }),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
 /// Command dispatcher + read model for scan orchestration state.
 #[derive(Clone)]
 pub struct ScanControlPlane {
@@ -107,6 +153,41 @@ impl fmt::Debug for ScanControlPlane {
     }
 }
 
+/// Tunables for [`ScanControlPlane::with_options`].
+#[derive(Clone)]
+pub struct ScanControlPlaneOptions {
+    /// Idle window before a drained scan is declared complete, for
+    /// libraries registered without their own window.
+    pub quiescence: Duration,
+    /// Number of library media events retained for `Last-Event-ID` replay.
+    pub media_event_history: usize,
+    /// Warm the primary poster of newly added media after each completed
+    /// scan when set.
+    pub poster_prewarm: Option<Arc<ImageService>>,
+    /// Refuse to start scans while the cache volume is low on space, and
+    /// forward the guard's warnings as [`MediaEvent::DiskSpaceLow`].
+    pub disk_space: Option<DiskSpaceGuard>,
+    /// Drop the cached images of media whose files a deletion reconcile
+    /// removes.
+    pub image_cache: Option<Arc<ImageService>>,
+    /// Checkpoint scan progress here so scans interrupted by a shutdown
+    /// can be resumed. Without it scans are only tracked in memory.
+    pub checkpoints: Option<Arc<dyn ScanCheckpointRepository>>,
+}
+
+impl Default for ScanControlPlaneOptions {
+    fn default() -> Self {
+        Self {
+            quiescence: DEFAULT_QUIESCENCE,
+            media_event_history: MEDIA_EVENT_HISTORY_CAPACITY,
+            poster_prewarm: None,
+            disk_space: None,
+            image_cache: None,
+            checkpoints: None,
+        }
+    }
+}
+
 struct ScanControlPlaneInner {
     unit_of_work: Arc<AppUnitOfWork>,
     orchestrator: Arc<ScanOrchestrator>,
@@ -115,6 +196,14 @@ struct ScanControlPlaneInner {
     media_bus: Arc<MediaEventBus>,
     aggregator: ScanRunAggregator,
     movie_batch_notifiers: MovieBatchFinalizationNotifiers,
+    integrity: Arc<MediaIntegrityVerifier>,
+    deletion_reconciler: Arc<DeletionReconciler>,
+    disk_space: Option<DiskSpaceGuard>,
+    checkpoints: Option<Arc<dyn ScanCheckpointRepository>>,
+    quiescence: Duration,
+    /// Serializes watch toggles so the stored flag and the registered
+    /// watcher cannot disagree.
+    watch_toggles: Mutex<()>,
 }
 
 impl ScanControlPlane {
@@ -133,17 +222,47 @@ impl ScanControlPlane {
         unit_of_work: Arc<AppUnitOfWork>,
         orchestrator: Arc<ScanOrchestrator>,
         quiescence: Duration,
+    ) -> Self {
+        Self::with_options(
+            unit_of_work,
+            orchestrator,
+            ScanControlPlaneOptions {
+                quiescence,
+                ..ScanControlPlaneOptions::default()
+            },
+        )
+    }
+
+    pub fn with_options(
+        unit_of_work: Arc<AppUnitOfWork>,
+        orchestrator: Arc<ScanOrchestrator>,
+        options: ScanControlPlaneOptions,
     ) -> Self {
         let media_bus = Arc::new(MediaEventBus::new(
-            MEDIA_EVENT_HISTORY_CAPACITY,
+            options.media_event_history,
             MEDIA_EVENT_BROADCAST_CAPACITY,
         ));
+        let poster_prewarm = options.poster_prewarm.map(|images| {
+            Arc::new(PosterPrewarm::new(images, Arc::clone(&media_bus)))
+        });
         let aggregator = ScanRunAggregator::new(
             Arc::clone(&orchestrator),
-            quiescence,
             Arc::clone(&media_bus),
             unit_of_work.clone(),
+            poster_prewarm,
         );
+        let integrity = Arc::new(MediaIntegrityVerifier::new(
+            unit_of_work.clone(),
+            orchestrator.cursor_repository(),
+        ));
+        let deletion_reconciler = Arc::new(DeletionReconciler::new(
+            unit_of_work.clone(),
+            Arc::clone(&media_bus),
+            options.image_cache,
+        ));
+        if let Some(guard) = options.disk_space.as_ref() {
+            forward_disk_space_warnings(guard, Arc::clone(&media_bus));
+        }
 
         Self {
             inner: Arc::new(ScanControlPlaneInner {
@@ -154,6 +273,12 @@ impl ScanControlPlane {
                 media_bus,
                 aggregator,
                 movie_batch_notifiers: MovieBatchFinalizationNotifiers::new(),
+                integrity,
+                deletion_reconciler,
+                disk_space: options.disk_space,
+                checkpoints: options.checkpoints,
+                quiescence: options.quiescence,
+                watch_toggles: Mutex::new(()),
             }),
         }
     }
@@ -162,6 +287,14 @@ impl ScanControlPlane {
         Arc::clone(&self.inner.orchestrator)
     }
 
+    pub fn deletion_reconciler(&self) -> Arc<DeletionReconciler> {
+        Arc::clone(&self.inner.deletion_reconciler)
+    }
+
+    pub fn integrity(&self) -> Arc<MediaIntegrityVerifier> {
+        Arc::clone(&self.inner.integrity)
+    }
+
     pub fn subscribe_media_events(
         &self,
     ) -> broadcast::Receiver<MediaEventFrame> {
@@ -172,11 +305,12 @@ impl ScanControlPlane {
         self.inner.media_bus.publish(event);
     }
 
-    pub fn media_event_history_since_sequence(
-        &self,
-        sequence: u64,
-    ) -> Vec<MediaEventFrame> {
-        self.inner.media_bus.history_since_sequence(sequence)
+    pub fn media_event_replay_since(&self, sequence: u64) -> MediaEventReplay {
+        self.inner.media_bus.replay_since(sequence)
+    }
+
+    pub fn media_event_latest_sequence(&self) -> u64 {
+        self.inner.media_bus.latest_sequence()
     }
 
     pub fn media_event_history_since_instant(
@@ -216,7 +350,24 @@ impl ScanControlPlane {
         if !library.enabled {
             return Err(ScanControlError::LibraryDisabled);
         }
+        if library.library_type == LibraryType::Music {
+            return Ok(self.start_music_scan(library, correlation_id));
+        }
+        self.start_bulk_run(&library, correlation_id, None).await
+    }
 
+    /// Seed a bulk scan of `library`. `resumed_from` names the interrupted
+    /// scan this one picks up.
+    async fn start_bulk_run(
+        &self,
+        library: &Library,
+        correlation_id: Option<Uuid>,
+        resumed_from: Option<Uuid>,
+    ) -> Result<ScanCommandAccepted, ScanControlError> {
+        self.ensure_disk_space()?;
+        self.refresh_exclusions(library).await?;
+
+        let library_id = library.id;
         let correlation_id = correlation_id.unwrap_or_else(Uuid::now_v7);
         let scan_id = correlation_id;
         let run = ScanRun::new(
@@ -225,6 +376,8 @@ impl ScanControlPlane {
             library_id,
             correlation_id,
             StartMode::Bulk,
+            resumed_from,
+            self.inner.quiescence_for(library_id).await,
         );
 
         self.inner.register_run(run.clone()).await;
@@ -252,6 +405,321 @@ impl ScanControlPlane {
         })
     }
 
+    /// Music libraries bypass the orchestrator: the whole library is walked
+    /// in the background and the run is reported in the scan history once
+    /// it ends. Music scans are short and cannot be paused or canceled.
+    fn start_music_scan(
+        &self,
+        library: Library,
+        correlation_id: Option<Uuid>,
+    ) -> ScanCommandAccepted {
+        let correlation_id = correlation_id.unwrap_or_else(Uuid::now_v7);
+        let scan_id = correlation_id;
+        let inner = Arc::clone(&self.inner);
+        spawn(async move {
+            let started_at = Utc::now();
+            let outcome = scan_music_library(
+                &library,
+                inner.unit_of_work.music_tracks.as_ref(),
+            )
+            .await;
+            let (status, tracks, skipped_files, excluded_paths) = match outcome
+            {
+                Ok(outcome) => (
+                    ScanLifecycleStatus::Completed,
+                    outcome.tracks,
+                    outcome.skipped_files,
+                    outcome.excluded_paths,
+                ),
+                Err(err) => {
+                    warn!(
+                        scan = %scan_id,
+                        library = %library.id,
+                        error = %err,
+                        "music library scan failed"
+                    );
+                    (
+                        ScanLifecycleStatus::Failed,
+                        0,
+                        SkippedFileCounts::default(),
+                        ExcludedPathCounts::default(),
+                    )
+                }
+            };
+            inner
+                .record_history(ScanHistoryEntry {
+                    scan_id,
+                    library_id: library.id,
+                    status,
+                    completed_items: tracks,
+                    total_items: tracks,
+                    skipped_files,
+                    excluded_paths,
+                    started_at,
+                    terminal_at: Utc::now(),
+                    stage_latencies: None,
+                    duplicates: Vec::new(),
+                    origin: ScanOrigin::Fresh,
+                    resumed_from: None,
+                })
+                .await;
+        });
+
+        ScanCommandAccepted {
+            scan_id,
+            correlation_id,
+        }
+    }
+
+    /// Rescan one path of a library instead of the whole library.
+    ///
+    /// Media files recorded under `path` that are gone from disk are removed
+    /// before the scan starts; the folders holding `path` are then rescanned
+    /// and reported like a full scan. `path` must lie within one of the
+    /// library's roots.
+    #[instrument(skip(self))]
+    pub async fn start_path_scan(
+        &self,
+        library_id: LibraryId,
+        path: PathBuf,
+        correlation_id: Option<Uuid>,
+    ) -> Result<PathScanAccepted, ScanControlError> {
+        let library = self
+            .inner
+            .unit_of_work
+            .libraries
+            .get_library(library_id)
+            .await
+            .map_err(|err| ScanControlError::internal(err.to_string()))?
+            .ok_or(ScanControlError::LibraryNotFound)?;
+
+        if !library.enabled {
+            return Err(ScanControlError::LibraryDisabled);
+        }
+        if library.library_type == LibraryType::Music {
+            return Err(ScanControlError::NotSupportedForMusic);
+        }
+        self.ensure_disk_space()?;
+        self.refresh_exclusions(&library).await?;
+
+        let (root_id, root_path) =
+            locate_under_roots(&library.paths, &path).await?;
+        let removed_files = self
+            .remove_missing_files(library_id, &root_path, &path)
+            .await?;
+
+        let correlation_id = correlation_id.unwrap_or_else(Uuid::now_v7);
+        let scan_id = correlation_id;
+        let run = ScanRun::new(
+            Arc::clone(&self.inner),
+            scan_id,
+            library_id,
+            correlation_id,
+            StartMode::Maintenance,
+            None,
+            self.inner.quiescence_for(library_id).await,
+        );
+
+        self.inner.register_run(run.clone()).await;
+        run.begin().await;
+
+        if let Err(err) = self
+            .inner
+            .orchestrator
+            .command_library(
+                library_id,
+                LibraryActorCommand::ScanPaths {
+                    root: root_id,
+                    paths: vec![path],
+                    correlation_id: Some(correlation_id),
+                },
+            )
+            .await
+        {
+            run.fail_with_reason("start_command_failed").await;
+            return Err(ScanControlError::internal(err.to_string()));
+        }
+
+        Ok(PathScanAccepted {
+            scan_id,
+            correlation_id,
+            removed_files,
+        })
+    }
+
+    /// Delete the records of media files under `path` that no longer exist.
+    /// Nothing is removed while the root itself is unreachable, so an
+    /// unmounted share does not empty the library.
+    async fn remove_missing_files(
+        &self,
+        library_id: LibraryId,
+        root_path: &Path,
+        path: &Path,
+    ) -> Result<u64, ScanControlError> {
+        if !tokio::fs::try_exists(root_path).await.unwrap_or(false) {
+            warn!(
+                library = %library_id,
+                root = %root_path.display(),
+                "library root offline; skipping deletion reconcile"
+            );
+            return Ok(0);
+        }
+
+        let filter = MediaFileFilter {
+            library_id: Some(library_id),
+            path_prefix: Some(path.to_string_lossy().into_owned()),
+            ..MediaFileFilter::default()
+        };
+        let sort = MediaFileSort::ascending(MediaFileSortField::DiscoveredAt);
+        let mut missing = Vec::new();
+        let mut offset = 0u32;
+        loop {
+            let page = self
+                .inner
+                .unit_of_work
+                .media_files_read
+                .list(
+                    filter.clone(),
+                    sort,
+                    Page {
+                        limit: PATH_SCAN_PAGE_SIZE,
+                        offset,
+                    },
+                )
+                .await
+                .map_err(|err| ScanControlError::internal(err.to_string()))?;
+            let page_len = page.len() as u32;
+
+            for file in page {
+                // The prefix match is textual; `/m/Heat` also matches
+                // `/m/Heat 2`.
+                if file.path.starts_with(path)
+                    && matches!(
+                        tokio::fs::try_exists(&file.path).await,
+                        Ok(false)
+                    )
+                {
+                    missing.push(file);
+                }
+            }
+
+            if page_len < PATH_SCAN_PAGE_SIZE {
+                break;
+            }
+            offset += page_len;
+        }
+
+        for file in &missing {
+            self.inner
+                .unit_of_work
+                .media_files_write
+                .delete_by_path(library_id, &file.path.to_string_lossy())
+                .await
+                .map_err(|err| ScanControlError::internal(err.to_string()))?;
+            self.inner
+                .media_bus
+                .publish(MediaEvent::MediaDeleted { id: file.media_id });
+        }
+
+        if !missing.is_empty() {
+            info!(
+                library = %library_id,
+                path = %path.display(),
+                removed = missing.len(),
+                "removed media files missing from disk"
+            );
+        }
+        Ok(missing.len() as u64)
+    }
+
+    /// Install `library`'s current exclude patterns and delete the records
+    /// of media files they now exclude, so narrowing a library takes
+    /// effect on its next scan.
+    async fn refresh_exclusions(
+        &self,
+        library: &Library,
+    ) -> Result<(), ScanControlError> {
+        install_library_exclusions(library)
+            .map_err(|err| ScanControlError::internal(err.to_string()))?;
+        if library.exclude_patterns.is_empty() {
+            return Ok(());
+        }
+        let exclusions =
+            ScanExclusions::new(&library.paths, &library.exclude_patterns)
+                .map_err(|err| ScanControlError::internal(err.to_string()))?;
+
+        let filter = MediaFileFilter {
+            library_id: Some(library.id),
+            ..MediaFileFilter::default()
+        };
+        let sort = MediaFileSort::ascending(MediaFileSortField::DiscoveredAt);
+        let mut excluded = Vec::new();
+        let mut offset = 0u32;
+        loop {
+            let page = self
+                .inner
+                .unit_of_work
+                .media_files_read
+                .list(
+                    filter.clone(),
+                    sort,
+                    Page {
+                        limit: PATH_SCAN_PAGE_SIZE,
+                        offset,
+                    },
+                )
+                .await
+                .map_err(|err| ScanControlError::internal(err.to_string()))?;
+            let page_len = page.len() as u32;
+            excluded.extend(
+                page.into_iter().filter(|file| {
+                    exclusions.excluded_by(&file.path).is_some()
+                }),
+            );
+            if page_len < PATH_SCAN_PAGE_SIZE {
+                break;
+            }
+            offset += page_len;
+        }
+
+        for file in &excluded {
+            self.inner
+                .unit_of_work
+                .media_files_write
+                .delete_by_path(library.id, &file.path.to_string_lossy())
+                .await
+                .map_err(|err| ScanControlError::internal(err.to_string()))?;
+            self.inner
+                .media_bus
+                .publish(MediaEvent::MediaDeleted { id: file.media_id });
+        }
+
+        if !excluded.is_empty() {
+            info!(
+                library = %library.id,
+                removed = excluded.len(),
+                "removed media files matched by exclude patterns"
+            );
+        }
+        Ok(())
+    }
+
+    fn ensure_disk_space(&self) -> Result<(), ScanControlError> {
+        if let Some(guard) = self.inner.disk_space.as_ref()
+            && let Err(MediaError::InsufficientDiskSpace {
+                available,
+                required,
+                ..
+            }) = guard.check()
+        {
+            return Err(ScanControlError::InsufficientDiskSpace {
+                available,
+                required,
+            });
+        }
+        Ok(())
+    }
+
     pub async fn inject_created_folders(
         &self,
         library_id: LibraryId,
@@ -370,19 +838,231 @@ impl ScanControlPlane {
         })
     }
 
+    /// Cancel a scan. Work not started yet is dropped and nothing new is
+    /// scheduled; jobs already running finish their writes before the scan
+    /// reports its partial counts and ends as canceled. Cancelling a scan
+    /// that already finished succeeds without doing anything.
     pub async fn cancel_scan(
         &self,
         scan_id: &Uuid,
     ) -> Result<ScanCommandAccepted, ScanControlError> {
-        let run = self.inner.lookup(scan_id).await?;
         let correlation_id = Uuid::now_v7();
-        run.cancel(correlation_id).await?;
+        let run = match self.inner.lookup(scan_id).await {
+            Ok(run) => run,
+            Err(ScanControlError::ScanNotFound)
+                if self.inner.in_history(scan_id).await =>
+            {
+                return Ok(ScanCommandAccepted {
+                    scan_id: *scan_id,
+                    correlation_id,
+                });
+            }
+            Err(err) => return Err(err),
+        };
+        run.cancel(correlation_id).await;
         Ok(ScanCommandAccepted {
             scan_id: *scan_id,
             correlation_id,
         })
     }
 
+    /// Mark the scans left unfinished by the previous process as
+    /// interrupted and return them. Call once at startup, before any scan
+    /// starts.
+    pub async fn recover_interrupted_scans(&self) -> Vec<ScanCheckpoint> {
+        let Some(checkpoints) = self.inner.checkpoints.as_ref() else {
+            return Vec::new();
+        };
+        let interrupted = match checkpoints.mark_interrupted().await {
+            Ok(interrupted) => interrupted,
+            Err(err) => {
+                warn!(error = %err, "failed to look up interrupted scans");
+                return Vec::new();
+            }
+        };
+        for checkpoint in &interrupted {
+            info!(
+                scan = %checkpoint.scan_id,
+                library = %checkpoint.library_id,
+                completed = checkpoint.completed_items,
+                total = checkpoint.total_items,
+                "scan was interrupted by a shutdown and can be resumed"
+            );
+        }
+        interrupted
+    }
+
+    /// Interrupted scans that were not resumed yet, newest first.
+    pub async fn resumable_scans(
+        &self,
+    ) -> Result<Vec<ScanCheckpoint>, ScanControlError> {
+        let Some(checkpoints) = self.inner.checkpoints.as_ref() else {
+            return Ok(Vec::new());
+        };
+        checkpoints
+            .list_resumable()
+            .await
+            .map_err(|err| ScanControlError::internal(err.to_string()))
+    }
+
+    /// Resume a scan that was unfinished when the server stopped.
+    ///
+    /// The library is reseeded by a new scan, recorded in the history as
+    /// resumed from `scan_id`. Folders unchanged since the interrupted scan
+    /// visited them are skipped through their scan cursors, folders that
+    /// changed are rescanned, and a deletion reconcile drops the records of
+    /// files removed while the server was down.
+    #[instrument(skip(self))]
+    pub async fn resume_interrupted_scan(
+        &self,
+        scan_id: Uuid,
+        correlation_id: Option<Uuid>,
+    ) -> Result<ScanCommandAccepted, ScanControlError> {
+        let checkpoints = self
+            .inner
+            .checkpoints
+            .as_ref()
+            .ok_or(ScanControlError::ScanNotFound)?;
+        let resumable = checkpoints
+            .list_resumable()
+            .await
+            .map_err(|err| ScanControlError::internal(err.to_string()))?;
+        let Some(interrupted) =
+            resumable.into_iter().find(|entry| entry.scan_id == scan_id)
+        else {
+            let known = checkpoints
+                .get(scan_id)
+                .await
+                .map_err(|err| ScanControlError::internal(err.to_string()))?;
+            return Err(match known {
+                Some(_) => ScanControlError::ScanNotInterrupted,
+                None => ScanControlError::ScanNotFound,
+            });
+        };
+
+        let library = self
+            .inner
+            .unit_of_work
+            .libraries
+            .get_library(interrupted.library_id)
+            .await
+            .map_err(|err| ScanControlError::internal(err.to_string()))?
+            .ok_or(ScanControlError::LibraryNotFound)?;
+        if !library.enabled {
+            return Err(ScanControlError::LibraryDisabled);
+        }
+
+        let accepted = self
+            .start_bulk_run(&library, correlation_id, Some(scan_id))
+            .await?;
+        info!(
+            scan = %accepted.scan_id,
+            resumed_from = %scan_id,
+            library = %library.id,
+            "resuming interrupted scan"
+        );
+
+        let reconciler = Arc::clone(&self.inner.deletion_reconciler);
+        spawn(async move {
+            if let Err(err) = reconciler.reconcile(&library, false).await {
+                warn!(
+                    library = %library.id,
+                    error = %err,
+                    "deletion reconcile of resumed scan failed"
+                );
+            }
+        });
+
+        Ok(accepted)
+    }
+
+    /// Turn filesystem watching for a library on or off without a restart.
+    ///
+    /// The flag is stored first and the watcher registered or torn down
+    /// afterwards. Enabling starts a reconcile scan to pick up changes made
+    /// while nothing was watching, unless a scan of the library is already
+    /// running (it covers the same ground) or the library is disabled.
+    #[instrument(skip(self))]
+    pub async fn set_library_watch(
+        &self,
+        library_id: LibraryId,
+        enabled: bool,
+    ) -> Result<LibraryWatchToggled, ScanControlError> {
+        let _toggle = self.inner.watch_toggles.lock().await;
+
+        let mut library = self
+            .inner
+            .unit_of_work
+            .libraries
+            .get_library(library_id)
+            .await
+            .map_err(|err| ScanControlError::internal(err.to_string()))?
+            .ok_or(ScanControlError::LibraryNotFound)?;
+        if library.library_type == LibraryType::Music {
+            return Err(ScanControlError::NotSupportedForMusic);
+        }
+
+        if library.watch_for_changes != enabled {
+            library.watch_for_changes = enabled;
+            library.updated_at = Utc::now();
+            self.inner
+                .unit_of_work
+                .libraries
+                .update_library(library_id, library.clone())
+                .await
+                .map_err(|err| ScanControlError::internal(err.to_string()))?;
+        }
+
+        // Applied even when the flag was already set, so a watcher that
+        // failed to register earlier gets another chance.
+        self.inner
+            .orchestrator
+            .set_library_watch(library_id, &library.paths, enabled)
+            .await
+            .map_err(|err| ScanControlError::internal(err.to_string()))?;
+
+        if !enabled || !library.enabled {
+            return Ok(LibraryWatchToggled {
+                reconcile_scan_id: None,
+            });
+        }
+
+        let running = self
+            .inner
+            .active
+            .read()
+            .await
+            .values()
+            .find(|run| run.library_id() == library_id)
+            .map(|run| run.scan_id());
+        if let Some(scan_id) = running {
+            info!(
+                library_id = %library_id,
+                %scan_id,
+                "watch enabled during a running scan; skipping reconcile scan"
+            );
+            return Ok(LibraryWatchToggled {
+                reconcile_scan_id: None,
+            });
+        }
+
+        // The watcher is already running; a reconcile scan that cannot start
+        // now is left to the next manual or scheduled scan.
+        let reconcile_scan_id =
+            match self.start_library_scan(library_id, None).await {
+                Ok(accepted) => Some(accepted.scan_id),
+                Err(err) => {
+                    warn!(
+                        library_id = %library_id,
+                        error = %err.message(),
+                        "could not start reconcile scan after enabling watch"
+                    );
+                    None
+                }
+            };
+        Ok(LibraryWatchToggled { reconcile_scan_id })
+    }
+
     pub async fn active_scans(&self) -> Vec<ScanSnapshot> {
         let guard = self.inner.active.read().await;
         let runs: Vec<_> = guard.values().cloned().collect();
@@ -402,6 +1082,26 @@ impl ScanControlPlane {
         guard.iter().rev().take(limit).cloned().collect()
     }
 
+    /// Per-stage job latencies of a finished scan, or live figures while the
+    /// scan is still running.
+    pub async fn latency_breakdown(
+        &self,
+        scan_id: &Uuid,
+    ) -> Result<ScanLatencyBreakdown, ScanControlError> {
+        let run = self.inner.active.read().await.get(scan_id).cloned();
+        if let Some(run) = run {
+            return Ok(run.latency_breakdown().await);
+        }
+
+        let history = self.inner.history.read().await;
+        history
+            .iter()
+            .rev()
+            .find(|entry| entry.scan_id == *scan_id)
+            .and_then(|entry| entry.stage_latencies.clone())
+            .ok_or(ScanControlError::ScanNotFound)
+    }
+
     pub async fn snapshot(&self, scan_id: &Uuid) -> Option<ScanSnapshot> {
         let guard = self.inner.active.read().await;
         let run = guard.get(scan_id).cloned();
@@ -423,6 +1123,14 @@ impl ScanControlPlane {
 }
 
 impl ScanControlPlaneInner {
+    /// The library's own quiescence window, else the global one.
+    async fn quiescence_for(&self, library_id: LibraryId) -> Duration {
+        self.orchestrator
+            .quiescence_window(library_id)
+            .await
+            .unwrap_or(self.quiescence)
+    }
+
     async fn register_run(&self, run: Arc<ScanRun>) {
         {
             let mut guard = self.active.write().await;
@@ -444,7 +1152,7 @@ impl ScanControlPlaneInner {
         &self,
         scan_id: Uuid,
         correlation_id: Uuid,
-        snapshot: ScanHistoryEntry,
+        mut snapshot: ScanHistoryEntry,
     ) {
         {
             let mut guard = self.active.write().await;
@@ -455,11 +1163,33 @@ impl ScanControlPlaneInner {
             .await;
         self.aggregator.drop(&correlation_id).await;
 
-        let mut history = self.history.write().await;
-        if history.len() == HISTORY_CAPACITY {
-            history.pop_front();
+        if snapshot.status == ScanLifecycleStatus::Completed {
+            match self
+                .unit_of_work
+                .media_refs
+                .list_duplicate_movies(snapshot.library_id)
+                .await
+            {
+                Ok(duplicates) => {
+                    if !duplicates.is_empty() {
+                        info!(
+                            scan = %scan_id,
+                            library = %snapshot.library_id,
+                            duplicates = duplicates.len(),
+                            "library shares titles with other libraries"
+                        );
+                    }
+                    snapshot.duplicates = duplicates;
+                }
+                Err(err) => warn!(
+                    scan = %scan_id,
+                    error = %err,
+                    "failed to collect duplicate media for scan report"
+                ),
+            }
         }
-        history.push_back(snapshot.clone());
+
+        self.record_history(snapshot.clone()).await;
 
         // Rebuild precomputed sort positions for the completed library scan
         if snapshot.status == ScanLifecycleStatus::Completed {
@@ -484,6 +1214,14 @@ impl ScanControlPlaneInner {
         }
     }
 
+    async fn record_history(&self, entry: ScanHistoryEntry) {
+        let mut history = self.history.write().await;
+        if history.len() == HISTORY_CAPACITY {
+            history.pop_front();
+        }
+        history.push_back(entry);
+    }
+
     async fn lookup(
         &self,
         scan_id: &Uuid,
@@ -494,6 +1232,11 @@ impl ScanControlPlaneInner {
             .cloned()
             .ok_or(ScanControlError::ScanNotFound)
     }
+
+    async fn in_history(&self, scan_id: &Uuid) -> bool {
+        let history = self.history.read().await;
+        history.iter().any(|entry| entry.scan_id == *scan_id)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -502,6 +1245,52 @@ pub struct ScanCommandAccepted {
     pub correlation_id: Uuid,
 }
 
+#[derive(Debug, Clone)]
+pub struct LibraryWatchToggled {
+    /// Scan started to catch up on changes made while unwatched.
+    pub reconcile_scan_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PathScanAccepted {
+    pub scan_id: Uuid,
+    pub correlation_id: Uuid,
+    /// Media files under the path that were gone from disk and removed.
+    pub removed_files: u64,
+}
+
+/// The root of `roots` holding `path`. Relative paths and `..` components
+/// are refused, and existing paths are resolved through symlinks so a link
+/// cannot lead outside the library.
+async fn locate_under_roots(
+    roots: &[PathBuf],
+    path: &Path,
+) -> Result<(LibraryRootsId, PathBuf), ScanControlError> {
+    if !path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::ParentDir))
+    {
+        return Err(ScanControlError::PathOutsideLibrary);
+    }
+
+    let (idx, root) = roots
+        .iter()
+        .enumerate()
+        .find(|(_, root)| path.starts_with(root))
+        .ok_or(ScanControlError::PathOutsideLibrary)?;
+
+    if let (Ok(real_root), Ok(real_path)) = (
+        tokio::fs::canonicalize(root).await,
+        tokio::fs::canonicalize(path).await,
+    ) && !real_path.starts_with(&real_root)
+    {
+        return Err(ScanControlError::PathOutsideLibrary);
+    }
+
+    Ok((LibraryRootsId(idx as u16), root.clone()))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ScanLifecycleStatus {
@@ -524,6 +1313,17 @@ impl ScanLifecycleStatus {
             ScanLifecycleStatus::Canceled => "canceled",
         }
     }
+
+    fn checkpoint_status(&self) -> ScanStatus {
+        match self {
+            ScanLifecycleStatus::Pending => ScanStatus::Pending,
+            ScanLifecycleStatus::Running => ScanStatus::Running,
+            ScanLifecycleStatus::Paused => ScanStatus::Paused,
+            ScanLifecycleStatus::Completed => ScanStatus::Completed,
+            ScanLifecycleStatus::Failed => ScanStatus::Failed,
+            ScanLifecycleStatus::Canceled => ScanStatus::Cancelled,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -564,6 +1364,13 @@ struct ScanRun {
     events: Mutex<VecDeque<ScanBroadcastFrame>>,
     start_mode: StartMode,
     log: Mutex<ScanLogWatermark>,
+    origin: ScanOrigin,
+    resumed_from: Option<Uuid>,
+    checkpointed_at: Mutex<Option<Instant>>,
+    /// Idle time after the last item before the run completes.
+    quiescence: ChronoDuration,
+    /// Time without progress after which the run is declared stalled.
+    stall_timeout: ChronoDuration,
 }
 
 #[derive(Debug)]
@@ -587,9 +1394,18 @@ struct ScanRunState {
     last_activity_at: Option<DateTime<Utc>>,
     quiescence_started_at: Option<DateTime<Utc>>,
     last_error: Option<String>,
+    /// Set once a cancel is accepted; the run only moves to `Canceled` from
+    /// here, after the library's running jobs drained.
+    cancel_requested: bool,
+    /// Files the scan file filter turned away, per rule.
+    skipped_files: SkippedFileCounts,
+    /// Entries the library's exclude patterns turned away, per pattern.
+    excluded_paths: ExcludedPathCounts,
     item_states: HashMap<String, ScanItemState>,
     // Count of successful indexed media per folder path
     index_successes_by_folder: HashMap<String, u32>,
+    stage_latencies: StageLatencyTracker,
+    throughput: ThroughputEstimator,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -625,9 +1441,14 @@ impl ScanPhase {
 enum ScanStateEvent {
     RunStarted,
     NewItemFound,
+    /// A targeted scan whose folders were all already queued or running
+    /// saw none of its own jobs.
+    NothingEnqueued,
     AllItemsProcessed,
     QuiescenceComplete,
-    Stalled { reason: String },
+    Stalled {
+        reason: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -680,8 +1501,13 @@ impl ScanRun {
         library_id: LibraryId,
         correlation_id: Uuid,
         mode: StartMode,
+        resumed_from: Option<Uuid>,
+        quiescence: Duration,
     ) -> Arc<Self> {
         let (tx, _rx) = broadcast::channel(1024);
+        let stall_timeout = quiescence
+            .checked_mul(STALLED_SCAN_TIMEOUT_MULTIPLIER)
+            .unwrap_or(Duration::from_secs(60));
         Arc::new(ScanRun {
             scan_id,
             library_id,
@@ -706,14 +1532,30 @@ impl ScanRun {
                 last_activity_at: None,
                 quiescence_started_at: None,
                 last_error: None,
+                cancel_requested: false,
+                skipped_files: SkippedFileCounts::default(),
+                excluded_paths: ExcludedPathCounts::default(),
                 item_states: HashMap::new(),
                 index_successes_by_folder: HashMap::new(),
+                stage_latencies: StageLatencyTracker::default(),
+                throughput: ThroughputEstimator::default(),
             }),
             tx,
             inner: Arc::downgrade(&inner),
             events: Mutex::new(VecDeque::with_capacity(EVENT_HISTORY_CAPACITY)),
             start_mode: mode,
             log: Mutex::new(ScanLogWatermark::default()),
+            origin: if resumed_from.is_some() {
+                ScanOrigin::Resumed
+            } else {
+                ScanOrigin::Fresh
+            },
+            resumed_from,
+            checkpointed_at: Mutex::new(None),
+            quiescence: ChronoDuration::from_std(quiescence)
+                .unwrap_or_else(|_| ChronoDuration::seconds(3)),
+            stall_timeout: ChronoDuration::from_std(stall_timeout)
+                .unwrap_or_else(|_| ChronoDuration::seconds(60)),
         })
     }
 
@@ -745,9 +1587,64 @@ impl ScanRun {
             state.handle_state_event(ScanStateEvent::RunStarted, Utc::now());
             state.build_payload()
         };
+        self.save_checkpoint(None).await;
         self.emit_frame(ScanEventKind::Started, emitted).await;
     }
 
+    /// Checkpoint progress unless the last checkpoint is younger than
+    /// [`CHECKPOINT_INTERVAL`].
+    async fn checkpoint_progress(&self) {
+        let due = self
+            .checkpointed_at
+            .lock()
+            .await
+            .is_none_or(|at| at.elapsed() >= CHECKPOINT_INTERVAL);
+        if due {
+            self.save_checkpoint(None).await;
+        }
+    }
+
+    /// Persist the run's status and progress so it can be resumed after a
+    /// crash. `terminal` overrides the status of a run being finalized.
+    async fn save_checkpoint(&self, terminal: Option<ScanLifecycleStatus>) {
+        let Some(checkpoints) = self
+            .inner
+            .upgrade()
+            .and_then(|inner| inner.checkpoints.clone())
+        else {
+            return;
+        };
+        *self.checkpointed_at.lock().await = Some(Instant::now());
+
+        let checkpoint = {
+            let state = self.state.lock().await;
+            let status = terminal.as_ref().unwrap_or(&state.status);
+            ScanCheckpoint {
+                scan_id: self.scan_id,
+                library_id: self.library_id,
+                correlation_id: self.correlation_id,
+                origin: self.origin,
+                resumed_from: self.resumed_from,
+                status: status.checkpoint_status(),
+                completed_items: state.completed_items,
+                total_items: state.total_items,
+                current_path: state.current_path.clone(),
+                started_at: state.started_at,
+                updated_at: Utc::now(),
+                completed_at: terminal
+                    .is_some()
+                    .then(|| state.terminal_at.unwrap_or_else(Utc::now)),
+            }
+        };
+        if let Err(err) = checkpoints.save(&checkpoint).await {
+            warn!(
+                scan = %self.scan_id,
+                error = %err,
+                "failed to checkpoint scan progress"
+            );
+        }
+    }
+
     async fn rehydrate_from_cursors(&self) {
         let Some(inner) = self.inner.upgrade() else {
             return;
@@ -783,6 +1680,22 @@ impl ScanRun {
     /// Record an index outcome (success/failure) for a given media file path.
     /// Successful outcomes are attributed to the parent folder of the file and
     /// used to verify folder-level scan completion reflects actual matches.
+    async fn record_skipped_files(&self, skipped: &SkippedFileCounts) {
+        if skipped.total() == 0 {
+            return;
+        }
+        let mut state = self.state.lock().await;
+        state.skipped_files.add(skipped);
+    }
+
+    async fn record_excluded_paths(&self, excluded: &ExcludedPathCounts) {
+        if excluded.is_empty() {
+            return;
+        }
+        let mut state = self.state.lock().await;
+        state.excluded_paths.add(excluded);
+    }
+
     async fn record_index_outcome(&self, file_path_norm: &str, success: bool) {
         if !success {
             return;
@@ -929,6 +1842,7 @@ impl ScanRun {
                 }
             }
         };
+        self.save_checkpoint(None).await;
         self.emit_frame(ScanEventKind::Progress, payload).await;
         Ok(())
     }
@@ -956,31 +1870,79 @@ impl ScanRun {
                 }
             }
         };
+        self.save_checkpoint(None).await;
         self.emit_frame(ScanEventKind::Progress, payload).await;
         Ok(())
     }
 
-    async fn cancel(
-        &self,
-        correlation_id: Uuid,
-    ) -> Result<(), ScanControlError> {
-        let frame = {
+    /// Suspend the library's queue and finish the run once its running jobs
+    /// drained. No-op when the run already ended or is being cancelled.
+    async fn cancel(self: &Arc<Self>, correlation_id: Uuid) {
+        {
             let mut state = self.state.lock().await;
-            if state.is_terminal() {
-                return Err(ScanControlError::ScanTerminal);
+            if state.is_terminal() || state.cancel_requested {
+                return;
             }
+            state.cancel_requested = true;
             state.correlation_id = correlation_id;
+        }
+
+        let Some(inner) = self.inner.upgrade() else {
+            self.finish_cancel().await;
+            return;
+        };
+        let orchestrator = Arc::clone(&inner.orchestrator);
+        let run = Arc::clone(self);
+        spawn(async move {
+            match orchestrator.suspend_library(run.library_id).await {
+                Ok(dropped) => info!(
+                    scan = %run.scan_id,
+                    library = %run.library_id,
+                    dropped,
+                    "scan cancelled; dropped queued jobs"
+                ),
+                Err(err) => warn!(
+                    scan = %run.scan_id,
+                    library = %run.library_id,
+                    error = %err,
+                    "failed to drop queued jobs of cancelled scan"
+                ),
+            }
+            if let Err(err) = orchestrator
+                .drain_library(run.library_id, CANCEL_DRAIN_POLL)
+                .await
+            {
+                warn!(
+                    scan = %run.scan_id,
+                    library = %run.library_id,
+                    error = %err,
+                    "failed to drain running jobs of cancelled scan"
+                );
+            }
+            run.finish_cancel().await;
+        });
+    }
+
+    /// Report the partial counts and move the run to `Canceled`.
+    async fn finish_cancel(&self) {
+        let (progress, terminal) = {
+            let mut state = self.state.lock().await;
+            if state.is_terminal() {
+                return;
+            }
             state.last_error = Some("scan_cancelled".to_string());
-            state
+            let progress = state.build_payload();
+            let terminal = state
                 .transition(ScanPhase::Canceled, Utc::now())
                 .unwrap_or_else(|| QueuedFrame {
                     event: ScanEventKind::Failed,
                     payload: state.build_payload(),
-                })
+                });
+            (progress, terminal)
         };
-        self.emit_frame(frame.event, frame.payload).await;
+        self.emit_frame(ScanEventKind::Progress, progress).await;
+        self.emit_frame(terminal.event, terminal.payload).await;
         self.finalize_history(ScanLifecycleStatus::Canceled).await;
-        Ok(())
     }
 
     async fn snapshot(&self) -> Result<ScanSnapshot, ScanControlError> {
@@ -993,6 +1955,8 @@ impl ScanRun {
             total_items: state.total_items,
             retrying_items: state.retrying_items,
             dead_lettered_items: state.dead_lettered_items,
+            skipped_files: state.skipped_files,
+            excluded_paths: state.excluded_paths.clone(),
             correlation_id: state.correlation_id,
             idempotency_key: state.current_idempotency_key(),
             current_path: state.current_path.clone(),
@@ -1160,6 +2124,28 @@ impl ScanRun {
         // overwriting listing_hash with a placeholder and breaking incremental diffs.
     }
 
+    async fn record_job_timing(&self, payload: &JobEventPayload) {
+        let mut state = self.state.lock().await;
+        let tracker = &mut state.stage_latencies;
+        match payload {
+            JobEventPayload::Dequeued { job_id, kind, .. } => {
+                tracker.job_started(*job_id, *kind);
+            }
+            JobEventPayload::Completed { job_id, .. } => {
+                tracker.job_finished(*job_id, true);
+            }
+            JobEventPayload::Failed { job_id, .. }
+            | JobEventPayload::DeadLettered { job_id, .. } => {
+                tracker.job_finished(*job_id, false);
+            }
+            _ => {}
+        }
+    }
+
+    async fn latency_breakdown(&self) -> ScanLatencyBreakdown {
+        self.state.lock().await.latency_breakdown()
+    }
+
     async fn record_folder_lease_renewed(
         &self,
         idempotency_key: &str,
@@ -1354,15 +2340,29 @@ impl ScanRun {
         }
     }
 
-    async fn try_complete(
-        &self,
-        completion_quiescence: ChronoDuration,
-        stall_timeout: ChronoDuration,
-    ) -> bool {
+    async fn try_complete(&self) -> bool {
+        let completion_quiescence = self.quiescence;
+        let stall_timeout = self.stall_timeout;
         let (maybe_frame, finalize_status) = {
             let mut state = self.state.lock().await;
-            if state.is_terminal() || state.total_items == 0 {
+            if state.is_terminal() {
                 (None, None)
+            } else if state.total_items == 0 {
+                // Bulk scans always find work; a targeted scan may not if its
+                // folders were already queued. Close it after a stall window.
+                let now = Utc::now();
+                let frame = (self.start_mode == StartMode::Maintenance
+                    && now - state.started_at >= stall_timeout)
+                    .then(|| {
+                        state.handle_state_event(
+                            ScanStateEvent::NothingEnqueued,
+                            now,
+                        )
+                    })
+                    .flatten();
+                let finalize =
+                    frame.as_ref().map(|_| ScanLifecycleStatus::Completed);
+                (frame, finalize)
             } else {
                 let now = Utc::now();
                 let mut frame: Option<QueuedFrame> = None;
@@ -1492,7 +2492,11 @@ impl ScanRun {
             None
         };
         self.maybe_log_summary(&event, &payload).await;
+        let progress = matches!(event, ScanEventKind::Progress);
         self.emit_media_event(event, payload, error);
+        if progress {
+            self.checkpoint_progress().await;
+        }
     }
 
     fn progress_pct(completed: u64, dead: u64, total: u64) -> u8 {
@@ -1610,10 +2614,37 @@ impl ScanRun {
                 status: terminal.clone(),
                 completed_items: state.completed_items,
                 total_items: state.total_items,
+                skipped_files: state.skipped_files,
+                excluded_paths: state.excluded_paths.clone(),
                 started_at: state.started_at,
                 terminal_at: state.terminal_at.unwrap_or_else(Utc::now),
+                stage_latencies: Some(state.latency_breakdown()),
+                duplicates: Vec::new(),
+                origin: self.origin,
+                resumed_from: self.resumed_from,
             }
         };
+        self.save_checkpoint(Some(terminal.clone())).await;
+
+        if snapshot.skipped_files.total() > 0 {
+            info!(
+                scan = %self.scan_id,
+                library = %snapshot.library_id,
+                by_extension = snapshot.skipped_files.extension,
+                too_small = snapshot.skipped_files.too_small,
+                too_large = snapshot.skipped_files.too_large,
+                "files skipped by the scan file filter"
+            );
+        }
+        for (pattern, count) in snapshot.excluded_paths.iter() {
+            info!(
+                scan = %self.scan_id,
+                library = %snapshot.library_id,
+                %pattern,
+                count,
+                "paths skipped by an exclude pattern"
+            );
+        }
 
         if let Some(inner) = self.inner.upgrade() {
             inner
@@ -1770,6 +2801,13 @@ impl ScanRunState {
                     None
                 }
             }
+            ScanStateEvent::NothingEnqueued => {
+                if self.can_transition_to(ScanPhase::Completed) {
+                    self.transition(ScanPhase::Completed, now)
+                } else {
+                    None
+                }
+            }
             ScanStateEvent::AllItemsProcessed => {
                 if self.can_transition_to(ScanPhase::Quiescing) {
                     self.transition(ScanPhase::Quiescing, now)
@@ -1802,6 +2840,9 @@ impl ScanRunState {
         if self.phase.is_terminal() {
             return false;
         }
+        if self.cancel_requested && next != ScanPhase::Canceled {
+            return false;
+        }
 
         match next {
             ScanPhase::Initializing => false,
@@ -1820,11 +2861,14 @@ impl ScanRunState {
                     ScanPhase::Processing | ScanPhase::Discovering
                 ) && self.can_enter_quiescing()
             }
-            ScanPhase::Completed => {
-                matches!(self.phase, ScanPhase::Quiescing)
-                    && self.completed_items + self.dead_lettered_items
+            ScanPhase::Completed => match self.phase {
+                ScanPhase::Quiescing => {
+                    self.completed_items + self.dead_lettered_items
                         == self.total_items
-            }
+                }
+                ScanPhase::Discovering => self.total_items == 0,
+                _ => false,
+            },
             ScanPhase::Failed | ScanPhase::Canceled => {
                 !self.phase.is_terminal()
             }
@@ -1984,11 +3028,22 @@ impl ScanRunState {
         changed
     }
 
+    fn latency_breakdown(&self) -> ScanLatencyBreakdown {
+        ScanLatencyBreakdown {
+            scan_id: self.scan_id,
+            library_id: self.library_id,
+            p50_stage_latencies_ms: self.stage_latencies.percentile(50),
+            p95_stage_latencies_ms: self.stage_latencies.percentile(95),
+            jobs_measured: self.stage_latencies.jobs_measured(),
+        }
+    }
+
     fn build_payload(&mut self) -> ScanProgressEvent {
         self.event_sequence += 1;
         let idempotency_key =
             format!("{}{}", self.idempotency_prefix, self.event_sequence);
         self.last_idempotency_key = idempotency_key.clone();
+        let estimate = self.estimate();
         ScanProgressEvent {
             version: EVENT_VERSION.to_string(),
             scan_id: self.scan_id,
@@ -1999,7 +3054,7 @@ impl ScanRunState {
             sequence: self.event_sequence,
             current_path: self.current_path.clone(),
             path_key: self.path_key.clone(),
-            p95_stage_latencies_ms: DEFAULT_LATENCIES,
+            p95_stage_latencies_ms: self.stage_latencies.percentile(95),
             correlation_id: self.correlation_id,
             idempotency_key,
             emitted_at: Utc::now(),
@@ -2007,9 +3062,26 @@ impl ScanRunState {
                 .then_some(self.retrying_items),
             dead_lettered_items: (self.dead_lettered_items > 0)
                 .then_some(self.dead_lettered_items),
+            percent_complete: estimate.map(|e| e.percent_complete),
+            items_per_second: estimate.and_then(|e| e.items_per_second),
+            eta_seconds: estimate.and_then(|e| e.eta_seconds),
         }
     }
 
+    /// Progress estimate, or `None` while folders are still being
+    /// discovered and the total is not meaningful yet.
+    fn estimate(&mut self) -> Option<ScanEstimate> {
+        let processed = self.completed_items + self.dead_lettered_items;
+        self.throughput.record(Instant::now(), processed);
+        if matches!(
+            self.phase,
+            ScanPhase::Initializing | ScanPhase::Discovering
+        ) {
+            return None;
+        }
+        Some(self.throughput.estimate(processed, self.total_items))
+    }
+
     fn build_payload_if(
         &mut self,
         condition: bool,
@@ -2200,12 +3272,11 @@ struct ScanRunAggregator {
 struct ScanRunAggregatorInner {
     orchestrator: Arc<ScanOrchestrator>,
     runs: RwLock<HashMap<Uuid, Arc<ScanRun>>>,
-    quiescence_chrono: ChronoDuration,
-    stall_timeout: ChronoDuration,
     media_bus: Arc<MediaEventBus>,
     unit_of_work: Arc<AppUnitOfWork>,
     seen_media: Mutex<HashSet<Uuid>>,
     series_bundles: Mutex<HashMap<LibraryId, SeriesBundleTrackerEntry>>,
+    poster_prewarm: Option<Arc<PosterPrewarm>>,
 }
 
 #[derive(Debug)]
@@ -2232,26 +3303,18 @@ impl SeriesBundleTrackerEntry {
 impl ScanRunAggregator {
     fn new(
         orchestrator: Arc<ScanOrchestrator>,
-        quiescence: Duration,
         media_bus: Arc<MediaEventBus>,
         unit_of_work: Arc<AppUnitOfWork>,
+        poster_prewarm: Option<Arc<PosterPrewarm>>,
     ) -> Self {
-        let chrono_window = ChronoDuration::from_std(quiescence)
-            .unwrap_or_else(|_| ChronoDuration::seconds(3));
-        let stall_std = quiescence
-            .checked_mul(STALLED_SCAN_TIMEOUT_MULTIPLIER)
-            .unwrap_or(Duration::from_secs(60));
-        let stall_window = ChronoDuration::from_std(stall_std)
-            .unwrap_or_else(|_| ChronoDuration::seconds(60));
         let inner = Arc::new(ScanRunAggregatorInner {
             orchestrator,
             runs: RwLock::new(HashMap::new()),
-            quiescence_chrono: chrono_window,
-            stall_timeout: stall_window,
             media_bus,
             unit_of_work,
             seen_media: Mutex::new(HashSet::new()),
             series_bundles: Mutex::new(HashMap::new()),
+            poster_prewarm,
         });
 
         let aggregator = Self {
@@ -2322,10 +3385,7 @@ impl ScanRunAggregatorInner {
         };
 
         for run in runs {
-            if run
-                .try_complete(self.quiescence_chrono, self.stall_timeout)
-                .await
-            {
+            if run.try_complete().await {
                 self.on_run_completed(run.clone()).await;
             }
         }
@@ -2389,6 +3449,7 @@ impl ScanRunAggregatorInner {
         self.observe_series_bundle_job_event(&event).await;
 
         if let Some(run) = run {
+            run.record_job_timing(&event.payload).await;
             let completed = match event.payload {
                 JobEventPayload::Enqueued { kind, job_id, .. } => {
                     if kind == JobKind::FolderScan {
@@ -2409,11 +3470,7 @@ impl ScanRunAggregatorInner {
                             event.meta.path_key.clone(),
                         )
                         .await;
-                        run.try_complete(
-                            self.quiescence_chrono,
-                            self.stall_timeout,
-                        )
-                        .await
+                        run.try_complete().await
                     } else {
                         false
                     }
@@ -2435,11 +3492,7 @@ impl ScanRunAggregatorInner {
                         .await;
 
                         if !retryable {
-                            run.try_complete(
-                                self.quiescence_chrono,
-                                self.stall_timeout,
-                            )
-                            .await
+                            run.try_complete().await
                         } else {
                             false
                         }
@@ -2456,11 +3509,7 @@ impl ScanRunAggregatorInner {
                             event.meta.path_key.clone(),
                         )
                         .await;
-                        run.try_complete(
-                            self.quiescence_chrono,
-                            self.stall_timeout,
-                        )
-                        .await
+                        run.try_complete().await
                     } else {
                         false
                     }
@@ -2519,6 +3568,20 @@ impl ScanRunAggregatorInner {
                 self.observe_series_bundle_media_discovered(&event).await;
             }
             ScanEvent::FolderScanCompleted(summary) => {
+                let library_id = summary.context.library_id();
+                let runs: Vec<Arc<ScanRun>> = {
+                    let guard = self.runs.read().await;
+                    guard
+                        .values()
+                        .filter(|r| r.library_id() == library_id)
+                        .cloned()
+                        .collect()
+                };
+                for run in runs {
+                    run.record_skipped_files(&summary.skipped).await;
+                    run.record_excluded_paths(&summary.excluded).await;
+                }
+
                 self.observe_series_bundle_folder_completed(&summary).await;
             }
             ScanEvent::Indexed(outcome) => {
@@ -2808,6 +3871,12 @@ impl ScanRunAggregatorInner {
             _ => IndexingChange::Updated,
         };
 
+        if matches!(change, IndexingChange::Created)
+            && let Some(prewarm) = &self.poster_prewarm
+        {
+            prewarm.record_added(outcome.library_id, &media).await;
+        }
+
         let event = match (media, change) {
             (Media::Movie(movie), IndexingChange::Created) => {
                 MediaEvent::MovieAdded { movie: *movie }
@@ -2883,6 +3952,10 @@ impl ScanRunAggregatorInner {
     }
 
     async fn on_run_completed(&self, run: Arc<ScanRun>) {
+        if let Some(prewarm) = &self.poster_prewarm {
+            prewarm.spawn_for_run(run.library_id(), run.scan_id()).await;
+        }
+
         if run.start_mode() != StartMode::Bulk {
             return;
         }
@@ -2983,8 +4056,25 @@ pub struct ScanHistoryEntry {
     pub status: ScanLifecycleStatus,
     pub completed_items: u64,
     pub total_items: u64,
+    #[serde(default)]
+    pub skipped_files: SkippedFileCounts,
+    /// Entries turned away by the library's exclude patterns, per pattern.
+    #[serde(default, skip_serializing_if = "ExcludedPathCounts::is_empty")]
+    pub excluded_paths: ExcludedPathCounts,
     pub started_at: DateTime<Utc>,
     pub terminal_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stage_latencies: Option<ScanLatencyBreakdown>,
+    /// Titles in this library that also exist in another library; only
+    /// collected for completed scans.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DuplicateMedia>,
+    /// `resumed` when this scan picked up one interrupted by a shutdown.
+    #[serde(default)]
+    pub origin: ScanOrigin,
+    /// The interrupted scan this one resumed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resumed_from: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2996,6 +4086,8 @@ pub struct ScanSnapshot {
     pub total_items: u64,
     pub retrying_items: u64,
     pub dead_lettered_items: u64,
+    pub skipped_files: SkippedFileCounts,
+    pub excluded_paths: ExcludedPathCounts,
     pub correlation_id: Uuid,
     pub idempotency_key: String,
     pub current_path: Option<String>,
@@ -3014,6 +4106,9 @@ impl From<ScanSnapshot> for ScanSnapshotDto {
             total_items: snapshot.total_items,
             retrying_items: snapshot.retrying_items,
             dead_lettered_items: snapshot.dead_lettered_items,
+            skipped_by_extension: snapshot.skipped_files.extension,
+            skipped_too_small: snapshot.skipped_files.too_small,
+            skipped_too_large: snapshot.skipped_files.too_large,
             correlation_id: snapshot.correlation_id,
             idempotency_key: snapshot.idempotency_key,
             current_path: snapshot.current_path,
@@ -3041,9 +4136,23 @@ impl From<ScanLifecycleStatus> for ApiScanLifecycleStatus {
 pub enum ScanControlError {
     LibraryNotFound,
     LibraryDisabled,
+    /// A targeted scan named a path outside every root of the library.
+    PathOutsideLibrary,
     ScanNotFound,
     ScanNotRunning,
     ScanTerminal,
+    /// Only scans interrupted by a shutdown, and not resumed yet, can be
+    /// resumed.
+    ScanNotInterrupted,
+    VerificationInProgress,
+    ReconcileInProgress,
+    /// Path scans and filesystem watching do not apply to music libraries.
+    NotSupportedForMusic,
+    /// The cache volume is below its configured free-space threshold.
+    InsufficientDiskSpace {
+        available: u64,
+        required: u64,
+    },
     Internal(String),
 }
 
@@ -3052,9 +4161,19 @@ impl ScanControlError {
         match self {
             ScanControlError::LibraryNotFound => StatusCode::NOT_FOUND,
             ScanControlError::LibraryDisabled => StatusCode::CONFLICT,
+            ScanControlError::PathOutsideLibrary => StatusCode::FORBIDDEN,
             ScanControlError::ScanNotFound => StatusCode::NOT_FOUND,
             ScanControlError::ScanNotRunning => StatusCode::CONFLICT,
             ScanControlError::ScanTerminal => StatusCode::GONE,
+            ScanControlError::ScanNotInterrupted => StatusCode::CONFLICT,
+            ScanControlError::VerificationInProgress => StatusCode::CONFLICT,
+            ScanControlError::ReconcileInProgress => StatusCode::CONFLICT,
+            ScanControlError::NotSupportedForMusic => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            ScanControlError::InsufficientDiskSpace { .. } => {
+                StatusCode::INSUFFICIENT_STORAGE
+            }
             ScanControlError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -3063,9 +4182,33 @@ impl ScanControlError {
         match self {
             ScanControlError::LibraryNotFound => "library_not_found".into(),
             ScanControlError::LibraryDisabled => "library_disabled".into(),
+            ScanControlError::PathOutsideLibrary => {
+                "path_outside_library".into()
+            }
             ScanControlError::ScanNotFound => "scan_not_found".into(),
             ScanControlError::ScanNotRunning => "scan_not_running".into(),
             ScanControlError::ScanTerminal => "scan_already_terminal".into(),
+            ScanControlError::ScanNotInterrupted => {
+                "scan_not_interrupted".into()
+            }
+            ScanControlError::VerificationInProgress => {
+                "verification_in_progress".into()
+            }
+            ScanControlError::ReconcileInProgress => {
+                "reconcile_in_progress".into()
+            }
+            ScanControlError::NotSupportedForMusic => {
+                "not_supported_for_music_libraries".into()
+            }
+            ScanControlError::InsufficientDiskSpace {
+                available,
+                required,
+            } => {
+                format!(
+                    "insufficient_disk_space: {} bytes free, {} required",
+                    available, required
+                )
+            }
             ScanControlError::Internal(reason) => reason.clone(),
         }
     }
@@ -3082,3 +4225,45 @@ impl fmt::Display for ScanControlError {
 }
 
 impl std::error::Error for ScanControlError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn targeted_paths_must_lie_under_a_root() {
+        let temp = tempfile::tempdir().unwrap();
+        let movies = temp.path().join("movies");
+        let shows = temp.path().join("shows");
+        std::fs::create_dir_all(movies.join("Heat (1995)")).unwrap();
+        std::fs::create_dir_all(&shows).unwrap();
+        let roots = vec![movies.clone(), shows.clone()];
+
+        let (root_id, root) =
+            locate_under_roots(&roots, &shows.join("Gone/S01E01.mkv"))
+                .await
+                .unwrap();
+        assert_eq!((root_id, root), (LibraryRootsId(1), shows.clone()));
+
+        for outside in [
+            temp.path().join("elsewhere"),
+            movies.join("../shows"),
+            PathBuf::from("movies/Heat (1995)"),
+        ] {
+            assert!(matches!(
+                locate_under_roots(&roots, &outside).await,
+                Err(ScanControlError::PathOutsideLibrary)
+            ));
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(temp.path(), movies.join("escape"))
+                .unwrap();
+            assert!(matches!(
+                locate_under_roots(&roots, &movies.join("escape")).await,
+                Err(ScanControlError::PathOutsideLibrary)
+            ));
+        }
+    }
+}
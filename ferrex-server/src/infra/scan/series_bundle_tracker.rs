@@ -513,6 +513,8 @@ mod tests {
             enqueued_subfolders: 0,
             listing_hash: "abc".into(),
             completed_at: Utc::now(),
+            skipped: Default::default(),
+            excluded: Default::default(),
         });
         tracker.observe_folder_scan_completed(&FolderScanSummary {
             context: FolderScanContext::Series(SeriesFolderScanContext {
@@ -523,6 +525,8 @@ mod tests {
             enqueued_subfolders: 1,
             listing_hash: "def".into(),
             completed_at: Utc::now(),
+            skipped: Default::default(),
+            excluded: Default::default(),
         });
 
         let series_id = SeriesID(Uuid::from_u128(3));
@@ -0,0 +1,148 @@
+use std::{collections::HashMap, time::Instant};
+
+use ferrex_core::{
+    domain::scan::orchestration::job::{JobId, JobKind},
+    types::ScanStageLatencySummary,
+};
+
+/// Samples kept per stage; once full the oldest sample is overwritten so
+/// percentiles on very large scans reflect the most recent jobs.
+const MAX_SAMPLES_PER_STAGE: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Scan,
+    Analyze,
+    Index,
+    Metadata,
+    Image,
+}
+
+impl Stage {
+    const COUNT: usize = 5;
+
+    fn of(kind: JobKind) -> Self {
+        match kind {
+            JobKind::FolderScan => Stage::Scan,
+            JobKind::MediaAnalyze
+            | JobKind::SeriesResolve
+            | JobKind::EpisodeMatch => Stage::Analyze,
+            JobKind::IndexUpsert => Stage::Index,
+            JobKind::MetadataEnrich => Stage::Metadata,
+            JobKind::ImageFetch => Stage::Image,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct StageSamples {
+    samples: Vec<u64>,
+    seen: u64,
+}
+
+impl StageSamples {
+    fn push(&mut self, value_ms: u64) {
+        if self.samples.len() < MAX_SAMPLES_PER_STAGE {
+            self.samples.push(value_ms);
+        } else {
+            let slot = (self.seen % MAX_SAMPLES_PER_STAGE as u64) as usize;
+            self.samples[slot] = value_ms;
+        }
+        self.seen += 1;
+    }
+
+    /// Nearest-rank percentile; `0` when no jobs completed.
+    fn percentile(&self, pct: u8) -> u64 {
+        if self.samples.is_empty() {
+            return 0;
+        }
+        let mut scratch = self.samples.clone();
+        let rank = (usize::from(pct) * scratch.len()).div_ceil(100);
+        let index = rank.saturating_sub(1).min(scratch.len() - 1);
+        *scratch.select_nth_unstable(index).1
+    }
+}
+
+/// Measures dequeue-to-completion time of the jobs belonging to one scan.
+#[derive(Debug, Default)]
+pub struct StageLatencyTracker {
+    in_flight: HashMap<JobId, (Stage, Instant)>,
+    stages: [StageSamples; Stage::COUNT],
+}
+
+impl StageLatencyTracker {
+    pub fn job_started(&mut self, job_id: JobId, kind: JobKind) {
+        self.in_flight
+            .insert(job_id, (Stage::of(kind), Instant::now()));
+    }
+
+    /// Stop timing `job_id`. Only successful jobs contribute a sample;
+    /// failed attempts are dropped so retries do not skew the stage.
+    pub fn job_finished(&mut self, job_id: JobId, succeeded: bool) {
+        let Some((stage, started)) = self.in_flight.remove(&job_id) else {
+            return;
+        };
+        if succeeded {
+            let elapsed = started.elapsed().as_millis() as u64;
+            self.stages[stage as usize].push(elapsed);
+        }
+    }
+
+    pub fn percentile(&self, pct: u8) -> ScanStageLatencySummary {
+        self.summary(|samples| samples.percentile(pct))
+    }
+
+    pub fn jobs_measured(&self) -> ScanStageLatencySummary {
+        self.summary(|samples| samples.seen)
+    }
+
+    fn summary(
+        &self,
+        value: impl Fn(&StageSamples) -> u64,
+    ) -> ScanStageLatencySummary {
+        let stage = |stage: Stage| value(&self.stages[stage as usize]);
+        ScanStageLatencySummary {
+            scan: stage(Stage::Scan),
+            analyze: stage(Stage::Analyze),
+            index: stage(Stage::Index),
+            metadata: stage(Stage::Metadata),
+            image: stage(Stage::Image),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn percentiles_use_nearest_rank_per_stage() {
+        let mut tracker = StageLatencyTracker::default();
+        let samples = &mut tracker.stages[Stage::Metadata as usize];
+        for value in 1..=100 {
+            samples.push(value);
+        }
+
+        assert_eq!(tracker.percentile(50).metadata, 50);
+        assert_eq!(tracker.percentile(95).metadata, 95);
+        assert_eq!(tracker.percentile(95).scan, 0);
+        assert_eq!(tracker.jobs_measured().metadata, 100);
+    }
+
+    #[test]
+    fn failed_jobs_do_not_record_samples() {
+        let mut tracker = StageLatencyTracker::default();
+        let ok = JobId(Uuid::now_v7());
+        let failed = JobId(Uuid::now_v7());
+
+        tracker.job_started(ok, JobKind::ImageFetch);
+        tracker.job_started(failed, JobKind::ImageFetch);
+        tracker.job_finished(ok, true);
+        tracker.job_finished(failed, false);
+        tracker.job_finished(JobId(Uuid::now_v7()), true);
+
+        assert_eq!(tracker.jobs_measured().image, 1);
+        assert!(tracker.in_flight.is_empty());
+    }
+}
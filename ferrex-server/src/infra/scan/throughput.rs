@@ -0,0 +1,187 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Span of processed-item samples the raw throughput is measured over.
+const WINDOW: Duration = Duration::from_secs(30);
+
+/// Time constant of the exponential smoothing applied to the raw rate.
+/// Folder discovery finishes much faster per item than metadata and image
+/// jobs, so the raw rate drops sharply when a scan moves between stages;
+/// smoothing spreads that drop over roughly this long instead of making the
+/// ETA jump.
+const SMOOTHING: Duration = Duration::from_secs(20);
+
+/// Shortest span of samples worth deriving a rate from.
+const MIN_SPAN: Duration = Duration::from_secs(2);
+
+/// Upper bound on the samples kept, for scans that report very often.
+const MAX_SAMPLES: usize = 512;
+
+/// Estimated progress of a scan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScanEstimate {
+    pub percent_complete: f32,
+    pub items_per_second: Option<f32>,
+    pub eta_seconds: Option<u64>,
+}
+
+/// Rolling items-per-second throughput of one scan, used to estimate its
+/// remaining time.
+#[derive(Debug, Default)]
+pub struct ThroughputEstimator {
+    samples: VecDeque<(Instant, u64)>,
+    smoothed: Option<f64>,
+    smoothed_at: Option<Instant>,
+}
+
+impl ThroughputEstimator {
+    /// Record that `processed` items were finished at `now`.
+    pub fn record(&mut self, now: Instant, processed: u64) {
+        if self
+            .samples
+            .back()
+            .is_some_and(|&(_, last)| last == processed)
+            && self.samples.len() > 1
+        {
+            // Keep the newest timestamp so stalls lower the rate.
+            self.samples.pop_back();
+        }
+        self.samples.push_back((now, processed));
+
+        // Drop samples older than the window, but keep one at or before its
+        // start so the window stays fully covered.
+        while self.samples.len() > 2
+            && self.samples.get(1).is_some_and(|&(at, _)| {
+                now.saturating_duration_since(at) >= WINDOW
+            })
+        {
+            self.samples.pop_front();
+        }
+        while self.samples.len() > MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+
+        if let Some(raw) = self.raw_rate() {
+            self.smooth(now, raw);
+        }
+    }
+
+    /// Estimate for a scan that processed `processed` of the `total` items
+    /// discovered so far.
+    pub fn estimate(&self, processed: u64, total: u64) -> ScanEstimate {
+        let percent_complete = if total == 0 {
+            0.0
+        } else {
+            (processed.min(total) as f64 / total as f64 * 100.0) as f32
+        };
+        let rate = self.smoothed.filter(|rate| *rate > f64::EPSILON);
+        let remaining = total.saturating_sub(processed);
+        ScanEstimate {
+            percent_complete,
+            items_per_second: rate.map(|rate| rate as f32),
+            eta_seconds: rate
+                .map(|rate| (remaining as f64 / rate).ceil() as u64),
+        }
+    }
+
+    fn raw_rate(&self) -> Option<f64> {
+        let (&(first_at, first), &(last_at, last)) =
+            (self.samples.front()?, self.samples.back()?);
+        let span = last_at.saturating_duration_since(first_at);
+        if span < MIN_SPAN {
+            return None;
+        }
+        Some(last.saturating_sub(first) as f64 / span.as_secs_f64())
+    }
+
+    fn smooth(&mut self, now: Instant, raw: f64) {
+        let next = match (self.smoothed, self.smoothed_at) {
+            (Some(previous), Some(at)) => {
+                let elapsed = now.saturating_duration_since(at).as_secs_f64();
+                let alpha = 1.0 - (-elapsed / SMOOTHING.as_secs_f64()).exp();
+                previous + alpha * (raw - previous)
+            }
+            _ => raw,
+        };
+        self.smoothed = Some(next);
+        self.smoothed_at = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(
+        estimator: &mut ThroughputEstimator,
+        start: Instant,
+        seconds: std::ops::Range<u64>,
+        per_second: u64,
+        mut processed: u64,
+    ) -> u64 {
+        for second in seconds {
+            processed += per_second;
+            estimator.record(start + Duration::from_secs(second), processed);
+        }
+        processed
+    }
+
+    #[test]
+    fn no_eta_until_a_rate_is_known() {
+        let mut estimator = ThroughputEstimator::default();
+        let start = Instant::now();
+        estimator.record(start, 0);
+        estimator.record(start + Duration::from_secs(1), 10);
+
+        let estimate = estimator.estimate(10, 40);
+        assert_eq!(estimate.percent_complete, 25.0);
+        assert_eq!(estimate.eta_seconds, None);
+    }
+
+    #[test]
+    fn steady_throughput_gives_a_linear_eta() {
+        let mut estimator = ThroughputEstimator::default();
+        let start = Instant::now();
+        estimator.record(start, 0);
+        let processed = feed(&mut estimator, start, 1..61, 10, 0);
+
+        let estimate = estimator.estimate(processed, 1_200);
+        assert_eq!(estimate.percent_complete, 50.0);
+        assert_eq!(estimate.items_per_second, Some(10.0));
+        assert_eq!(estimate.eta_seconds, Some(60));
+    }
+
+    #[test]
+    fn stage_slowdown_is_smoothed() {
+        let mut estimator = ThroughputEstimator::default();
+        let start = Instant::now();
+        estimator.record(start, 0);
+        // Fast discovery, then much slower metadata work.
+        let processed = feed(&mut estimator, start, 1..61, 100, 0);
+        let processed = feed(&mut estimator, start, 61..66, 1, processed);
+
+        let rate = estimator.estimate(processed, processed).items_per_second;
+        let rate = rate.unwrap();
+        assert!(rate < 100.0, "rate should start falling, got {rate}");
+        assert!(rate > 30.0, "rate should not collapse at once, got {rate}");
+
+        let processed = feed(&mut estimator, start, 66..300, 1, processed);
+        let rate = estimator.estimate(processed, processed).items_per_second;
+        assert!((rate.unwrap() - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn stalls_lower_the_rate() {
+        let mut estimator = ThroughputEstimator::default();
+        let start = Instant::now();
+        estimator.record(start, 0);
+        let processed = feed(&mut estimator, start, 1..31, 10, 0);
+        let before = estimator.estimate(processed, 1_000).eta_seconds;
+
+        estimator.record(start + Duration::from_secs(60), processed);
+        let after = estimator.estimate(processed, 1_000).eta_seconds;
+        assert!(after > before);
+    }
+}
@@ -54,6 +54,26 @@ impl StartupHooks for ProdStartupHooks {
             }
         });
 
+        if let Some(secs) = state
+            .config()
+            .scanner
+            .deletion_reconcile_interval_secs
+            .filter(|secs| *secs > 0)
+        {
+            let reconciler = state.scan_control().deletion_reconciler();
+            tokio::spawn(async move {
+                let mut interval =
+                    tokio::time::interval(Duration::from_secs(secs));
+                // The first tick fires at once; leave startup to the scans.
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    reconciler.reconcile_all().await;
+                }
+            });
+            info!("Deletion reconcile scheduled every {secs}s");
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,404 @@
+//! Live transcoding for clients that cannot play a file as stored.
+//!
+//! A player may describe what it can decode in the `X-Client-Capabilities`
+//! header or the `caps` query parameter:
+//!
+//! ```text
+//! video=h264,hevc;audio=aac,opus;container=mp4,mkv;bitdepth=8
+//! ```
+//!
+//! Every key is optional and an omitted key places no restriction. When the
+//! probed codecs, bit depth or container of the source fall outside the
+//! hint, the stream is piped through ffmpeg instead of served directly:
+//! compatible tracks are copied and the rest re-encoded to H.264/AAC.
+//! Without a hint, or when nothing in the hint rules the source out, the
+//! file is direct-played exactly as before. Facts the probe did not record
+//! are assumed to be playable.
+
+use std::path::Path;
+
+/// Request header carrying the client's capability hint.
+pub const CAPABILITIES_HEADER: &str = "x-client-capabilities";
+
+/// Response header present on transcoded streams, naming what was
+/// re-encoded (`video`, `audio`, `video+audio` or `remux`).
+pub const TRANSCODE_HEADER: &str = "x-stream-transcode";
+
+/// What a client says it can play. Empty lists mean "not stated".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientCapabilities {
+    pub video_codecs: Vec<String>,
+    pub audio_codecs: Vec<String>,
+    pub containers: Vec<String>,
+    pub max_bit_depth: Option<u32>,
+}
+
+impl ClientCapabilities {
+    /// Parse a capability hint. Unknown keys are ignored; `None` when the
+    /// hint states nothing usable.
+    pub fn parse(hint: &str) -> Option<Self> {
+        let mut caps = ClientCapabilities::default();
+        for entry in hint.split(';') {
+            let Some((key, values)) = entry.split_once('=') else {
+                continue;
+            };
+            let values = values
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty());
+            match key.trim().to_ascii_lowercase().as_str() {
+                "video" | "vcodec" => {
+                    caps.video_codecs = values.map(normalize_codec).collect()
+                }
+                "audio" | "acodec" => {
+                    caps.audio_codecs = values.map(normalize_codec).collect()
+                }
+                "container" | "containers" => {
+                    caps.containers = values.map(normalize_container).collect()
+                }
+                "bitdepth" | "bit_depth" => {
+                    caps.max_bit_depth =
+                        values.filter_map(|v| v.parse().ok()).max()
+                }
+                _ => {}
+            }
+        }
+        (caps != ClientCapabilities::default()).then_some(caps)
+    }
+
+    fn plays_video(&self, codec: &str) -> bool {
+        self.video_codecs.is_empty()
+            || self.video_codecs.iter().any(|c| c == codec)
+    }
+
+    fn plays_audio(&self, codec: &str) -> bool {
+        self.audio_codecs.is_empty()
+            || self.audio_codecs.iter().any(|c| c == codec)
+    }
+
+    fn plays_container(&self, container: &str) -> bool {
+        self.containers.is_empty()
+            || self.containers.iter().any(|c| c == container)
+    }
+}
+
+/// The probed format of a file about to be streamed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceFormat {
+    pub container: Option<String>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub bit_depth: Option<u32>,
+}
+
+impl SourceFormat {
+    pub fn new(
+        path: &Path,
+        video_codec: Option<&str>,
+        audio_codec: Option<&str>,
+        bit_depth: Option<u32>,
+    ) -> Self {
+        Self {
+            container: path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(normalize_container),
+            video_codec: video_codec.map(normalize_codec),
+            audio_codec: audio_codec.map(normalize_codec),
+            bit_depth,
+        }
+    }
+}
+
+/// Output container of a live transcode; all three can be written to a
+/// pipe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscodeOutput {
+    /// Fragmented MP4.
+    Mp4,
+    Matroska,
+    MpegTs,
+}
+
+impl TranscodeOutput {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            TranscodeOutput::Mp4 => "video/mp4",
+            TranscodeOutput::Matroska => "video/x-matroska",
+            TranscodeOutput::MpegTs => "video/mp2t",
+        }
+    }
+
+    fn muxer_args(self) -> &'static [&'static str] {
+        match self {
+            TranscodeOutput::Mp4 => &[
+                "-movflags",
+                "frag_keyframe+empty_moov+default_base_moof",
+                "-f",
+                "mp4",
+            ],
+            TranscodeOutput::Matroska => &["-f", "matroska"],
+            TranscodeOutput::MpegTs => &["-f", "mpegts"],
+        }
+    }
+
+    /// Whether a stream-copied track of `codec` fits this container.
+    fn carries(self, codec: &str) -> bool {
+        match self {
+            TranscodeOutput::Matroska => true,
+            TranscodeOutput::Mp4 => matches!(
+                codec,
+                "h264"
+                    | "hevc"
+                    | "av1"
+                    | "vp9"
+                    | "aac"
+                    | "ac3"
+                    | "eac3"
+                    | "mp3"
+                    | "opus"
+                    | "flac"
+            ),
+            TranscodeOutput::MpegTs => matches!(
+                codec,
+                "h264" | "hevc" | "aac" | "ac3" | "eac3" | "mp3"
+            ),
+        }
+    }
+}
+
+/// How an incompatible source is converted for the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranscodePlan {
+    pub copy_video: bool,
+    pub copy_audio: bool,
+    pub output: TranscodeOutput,
+}
+
+impl TranscodePlan {
+    /// Value reported in [`TRANSCODE_HEADER`].
+    pub fn header_value(&self) -> &'static str {
+        match (self.copy_video, self.copy_audio) {
+            (false, false) => "video+audio",
+            (false, true) => "video",
+            (true, false) => "audio",
+            (true, true) => "remux",
+        }
+    }
+
+    /// ffmpeg arguments converting `path` from `start` seconds to stdout.
+    pub fn ffmpeg_args(&self, path: &Path, start: Option<f64>) -> Vec<String> {
+        let mut args: Vec<String> =
+            ["-hide_banner", "-loglevel", "error", "-nostdin"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+        if let Some(start) = start.filter(|t| t.is_finite() && *t > 0.0) {
+            args.push("-ss".into());
+            args.push(format!("{start:.3}"));
+        }
+        args.push("-i".into());
+        args.push(path.display().to_string());
+        args.extend(
+            ["-map", "0:v:0?", "-map", "0:a:0?"]
+                .iter()
+                .map(|s| s.to_string()),
+        );
+
+        if self.copy_video {
+            args.extend(["-c:v".into(), "copy".into()]);
+        } else {
+            args.extend(
+                [
+                    "-c:v",
+                    "libx264",
+                    "-preset",
+                    "veryfast",
+                    "-tune",
+                    "zerolatency",
+                    "-pix_fmt",
+                    "yuv420p",
+                ]
+                .iter()
+                .map(|s| s.to_string()),
+            );
+        }
+        if self.copy_audio {
+            args.extend(["-c:a".into(), "copy".into()]);
+        } else {
+            args.extend(
+                ["-c:a", "aac", "-ac", "2"].iter().map(|s| s.to_string()),
+            );
+        }
+
+        args.extend(
+            ["-avoid_negative_ts", "make_zero"]
+                .iter()
+                .map(|s| s.to_string()),
+        );
+        args.extend(self.output.muxer_args().iter().map(|s| s.to_string()));
+        args.push("pipe:1".into());
+        args
+    }
+}
+
+/// Decide whether `source` needs converting for a client with `caps`.
+/// `None` means direct play.
+pub fn plan_transcode(
+    caps: &ClientCapabilities,
+    source: &SourceFormat,
+) -> Option<TranscodePlan> {
+    let video_ok = source
+        .video_codec
+        .as_deref()
+        .is_none_or(|codec| caps.plays_video(codec))
+        && match (caps.max_bit_depth, source.bit_depth) {
+            (Some(max), Some(depth)) => depth <= max,
+            _ => true,
+        };
+    let audio_ok = source
+        .audio_codec
+        .as_deref()
+        .is_none_or(|codec| caps.plays_audio(codec));
+    let container_ok = source
+        .container
+        .as_deref()
+        .is_none_or(|container| caps.plays_container(container));
+
+    if video_ok && audio_ok && container_ok {
+        return None;
+    }
+
+    let output = if caps.plays_container("mp4") {
+        TranscodeOutput::Mp4
+    } else if caps.plays_container("matroska") {
+        TranscodeOutput::Matroska
+    } else if caps.plays_container("mpegts") {
+        TranscodeOutput::MpegTs
+    } else {
+        TranscodeOutput::Mp4
+    };
+    // A track of unknown codec cannot be checked against the container, so
+    // it is re-encoded rather than risk a muxer failure mid-stream.
+    let fits = |codec: Option<&str>| codec.is_some_and(|c| output.carries(c));
+
+    Some(TranscodePlan {
+        copy_video: video_ok && fits(source.video_codec.as_deref()),
+        copy_audio: audio_ok && fits(source.audio_codec.as_deref()),
+        output,
+    })
+}
+
+/// Canonical codec name for the spellings used by players and ffprobe.
+fn normalize_codec(codec: &str) -> String {
+    let codec = codec.trim().to_ascii_lowercase();
+    match codec.as_str() {
+        "avc" | "avc1" | "h.264" | "x264" => "h264".into(),
+        "h265" | "h.265" | "hvc1" | "hev1" | "x265" => "hevc".into(),
+        "av01" => "av1".into(),
+        "mp4a" => "aac".into(),
+        "ac-3" => "ac3".into(),
+        "e-ac3" | "ec-3" | "ec3" => "eac3".into(),
+        _ => codec,
+    }
+}
+
+/// Canonical container name for a file extension or format name.
+fn normalize_container(container: &str) -> String {
+    let container = container.trim().to_ascii_lowercase();
+    match container.as_str() {
+        "mkv" | "mka" => "matroska".into(),
+        "m4v" | "mov" | "fmp4" => "mp4".into(),
+        "ts" | "m2ts" | "mts" => "mpegts".into(),
+        _ => container,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hevc_10bit_mkv() -> SourceFormat {
+        SourceFormat::new(
+            Path::new("/movies/Heat.mkv"),
+            Some("hevc"),
+            Some("eac3"),
+            Some(10),
+        )
+    }
+
+    #[test]
+    fn hints_are_normalized_and_empty_hints_ignored() {
+        let caps = ClientCapabilities::parse(
+            "video=H.264, h265;audio=AAC;container=mkv,M4V;bitdepth=8;x=1",
+        )
+        .expect("hint");
+        assert_eq!(caps.video_codecs, ["h264", "hevc"]);
+        assert_eq!(caps.audio_codecs, ["aac"]);
+        assert_eq!(caps.containers, ["matroska", "mp4"]);
+        assert_eq!(caps.max_bit_depth, Some(8));
+
+        assert_eq!(ClientCapabilities::parse(""), None);
+        assert_eq!(ClientCapabilities::parse("colour=blue"), None);
+    }
+
+    #[test]
+    fn compatible_sources_direct_play() {
+        let caps = ClientCapabilities::parse(
+            "video=hevc,h264;audio=eac3,aac;container=mkv,mp4;bitdepth=10",
+        )
+        .unwrap();
+        assert_eq!(plan_transcode(&caps, &hevc_10bit_mkv()), None);
+
+        // Nothing probed, nothing to object to.
+        let unknown = SourceFormat::new(Path::new("/a"), None, None, None);
+        assert_eq!(plan_transcode(&caps, &unknown), None);
+    }
+
+    #[test]
+    fn deep_color_hevc_is_reencoded_and_audio_kept() {
+        let caps = ClientCapabilities::parse(
+            "video=h264,hevc;audio=eac3;container=mp4;bitdepth=8",
+        )
+        .unwrap();
+        let plan = plan_transcode(&caps, &hevc_10bit_mkv()).expect("plan");
+        assert_eq!(
+            plan,
+            TranscodePlan {
+                copy_video: false,
+                copy_audio: true,
+                output: TranscodeOutput::Mp4,
+            }
+        );
+        assert_eq!(plan.header_value(), "video");
+
+        let args = plan.ffmpeg_args(Path::new("/movies/Heat.mkv"), Some(90.0));
+        let ss = args.iter().position(|a| a == "-ss").unwrap();
+        let input = args.iter().position(|a| a == "-i").unwrap();
+        assert!(ss < input);
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx264"]));
+        assert!(args.windows(2).any(|w| w == ["-c:a", "copy"]));
+        assert_eq!(args.last().unwrap(), "pipe:1");
+    }
+
+    #[test]
+    fn container_mismatch_remuxes_and_unfit_tracks_are_encoded() {
+        let caps = ClientCapabilities::parse("container=ts").unwrap();
+        let source = SourceFormat::new(
+            Path::new("/movies/a.mkv"),
+            Some("h264"),
+            Some("opus"),
+            None,
+        );
+        let plan = plan_transcode(&caps, &source).expect("plan");
+        assert_eq!(plan.output, TranscodeOutput::MpegTs);
+        assert!(plan.copy_video);
+        // Opus does not go into MPEG-TS here, so it is converted to AAC.
+        assert!(!plan.copy_audio);
+        assert!(
+            plan.ffmpeg_args(Path::new("/a"), None)
+                .iter()
+                .all(|a| a != "-ss")
+        );
+    }
+}
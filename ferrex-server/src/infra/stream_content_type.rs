@@ -0,0 +1,296 @@
+//! `Content-Type` for direct-play streams.
+//!
+//! Known video and audio extensions map straight to a MIME type. Files with an
+//! unknown or generic extension (`.bin`, `.dat`, none at all) are sniffed
+//! from their first bytes so mislabeled files still play; the result is
+//! cached per media file and reused until the file's size changes.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use tokio::io::AsyncReadExt;
+use tracing::debug;
+use uuid::Uuid;
+
+/// Served when neither the extension nor the contents identify the file.
+pub const FALLBACK_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Bytes read from the start of a file to identify its container. Two
+/// MPEG-TS packets and the EBML header of Matroska fit comfortably.
+const SNIFF_LEN: usize = 4096;
+
+/// Probed files remembered before the cache starts over.
+const MAX_CACHED: usize = 4096;
+
+/// MIME type for a known video or audio extension, case-insensitively.
+pub fn content_type_for_extension(ext: &str) -> Option<&'static str> {
+    let content_type = match ext.to_ascii_lowercase().as_str() {
+        "mp4" => "video/mp4",
+        "mkv" => "video/x-matroska",
+        "avi" => "video/x-msvideo",
+        "mov" => "video/quicktime",
+        "webm" => "video/webm",
+        "flv" => "video/x-flv",
+        "wmv" => "video/x-ms-wmv",
+        "m4v" => "video/x-m4v",
+        "mpg" | "mpeg" => "video/mpeg",
+        "3gp" => "video/3gpp",
+        "ogv" => "video/ogg",
+        "ts" | "mts" | "m2ts" => "video/mp2t",
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "m4a" => "audio/mp4",
+        "aac" => "audio/aac",
+        "ogg" | "oga" | "opus" => "audio/ogg",
+        "wav" => "audio/wav",
+        "wma" => "audio/x-ms-wma",
+        "mka" => "audio/x-matroska",
+        _ => return None,
+    };
+    Some(content_type)
+}
+
+/// MIME type of the container starting with `head`, from its magic bytes.
+pub fn sniff_content_type(head: &[u8]) -> Option<&'static str> {
+    if head.len() >= 12 && &head[4..8] == b"ftyp" {
+        let brand = &head[8..12];
+        return Some(if brand == b"qt  " {
+            "video/quicktime"
+        } else if brand == b"M4A " || brand == b"M4B " {
+            "audio/mp4"
+        } else if brand.starts_with(b"3g") {
+            "video/3gpp"
+        } else if brand == b"M4V " {
+            "video/x-m4v"
+        } else {
+            "video/mp4"
+        });
+    }
+    if head.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        // The EBML header names the doctype within its first few dozen
+        // bytes.
+        let header = &head[..head.len().min(64)];
+        let webm = header.windows(4).any(|w| w == b"webm");
+        return Some(if webm {
+            "video/webm"
+        } else {
+            "video/x-matroska"
+        });
+    }
+    if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"AVI " {
+        return Some("video/x-msvideo");
+    }
+    if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"WAVE" {
+        return Some("audio/wav");
+    }
+    if head.starts_with(b"fLaC") {
+        return Some("audio/flac");
+    }
+    if head.starts_with(b"ID3") {
+        return Some("audio/mpeg");
+    }
+    if head.starts_with(b"FLV\x01") {
+        return Some("video/x-flv");
+    }
+    if head.starts_with(&[0x30, 0x26, 0xB2, 0x75, 0x8E, 0x66, 0xCF, 0x11]) {
+        return Some("video/x-ms-wmv");
+    }
+    if head.starts_with(b"OggS") {
+        // The first page carries the identification header of the first
+        // stream; without a video codec the file is audio.
+        let page = &head[..head.len().min(128)];
+        let video = page.windows(6).any(|w| w == b"theora");
+        return Some(if video { "video/ogg" } else { "audio/ogg" });
+    }
+    if head.starts_with(&[0x00, 0x00, 0x01, 0xBA]) {
+        return Some("video/mpeg");
+    }
+    // Bare MPEG audio frames: 11 sync bits, then the layer decides between
+    // ADTS AAC (layer 0) and MP3.
+    if head.len() >= 2 && head[0] == 0xFF && head[1] & 0xE0 == 0xE0 {
+        return match head[1] & 0x06 {
+            0x00 => Some("audio/aac"),
+            _ => Some("audio/mpeg"),
+        };
+    }
+    // Transport streams carry no header; require the sync byte on several
+    // consecutive packets, plain (188) or timestamped M2TS (192).
+    for (packet, offset) in [(188, 0), (192, 4)] {
+        let syncs = (0..3)
+            .map(|i| offset + i * packet)
+            .take_while(|&at| at < head.len())
+            .filter(|&at| head[at] == 0x47)
+            .count();
+        if syncs == 3 {
+            return Some("video/mp2t");
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Probed {
+    size: u64,
+    content_type: &'static str,
+}
+
+/// Sniffed content types of media files with unknown extensions; shared by
+/// every clone.
+#[derive(Debug, Clone, Default)]
+pub struct ContentTypeCache {
+    probed: Arc<RwLock<HashMap<Uuid, Probed>>>,
+}
+
+impl ContentTypeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Content type for media file `id` at `path` of `size` bytes. Known
+    /// extensions never touch the file.
+    pub async fn resolve(
+        &self,
+        id: Uuid,
+        path: &Path,
+        size: u64,
+    ) -> &'static str {
+        if let Some(content_type) = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(content_type_for_extension)
+        {
+            return content_type;
+        }
+
+        if let Some(probed) = self
+            .probed
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .filter(|probed| probed.size == size)
+        {
+            return probed.content_type;
+        }
+
+        let content_type = match read_head(path).await {
+            Ok(head) => sniff_content_type(&head),
+            Err(err) => {
+                debug!(
+                    ?path,
+                    error = %err,
+                    "could not read file to sniff its container"
+                );
+                // Not cached: the file may be readable on the next request.
+                return FALLBACK_CONTENT_TYPE;
+            }
+        }
+        .unwrap_or(FALLBACK_CONTENT_TYPE);
+        debug!(?path, content_type, "sniffed stream content type");
+
+        let mut probed =
+            self.probed.write().unwrap_or_else(PoisonError::into_inner);
+        if probed.len() >= MAX_CACHED {
+            probed.clear();
+        }
+        probed.insert(id, Probed { size, content_type });
+        content_type
+    }
+}
+
+async fn read_head(path: &Path) -> std::io::Result<Vec<u8>> {
+    let file = tokio::fs::File::open(path).await?;
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    file.take(SNIFF_LEN as u64).read_to_end(&mut head).await?;
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(packet: usize, offset: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; packet * 4];
+        for i in 0..4 {
+            bytes[offset + i * packet] = 0x47;
+        }
+        bytes
+    }
+
+    #[test]
+    fn containers_are_recognized_by_magic_bytes() {
+        let mut mp4 = b"\0\0\0\x20ftypisom".to_vec();
+        mp4.resize(64, 0);
+        assert_eq!(sniff_content_type(&mp4), Some("video/mp4"));
+        assert_eq!(
+            sniff_content_type(b"\0\0\0\x14ftypqt  \0\0\0\0"),
+            Some("video/quicktime")
+        );
+
+        let mut mkv = vec![0x1A, 0x45, 0xDF, 0xA3, 0x9F, 0x42, 0x82, 0x88];
+        mkv.extend_from_slice(b"matroska");
+        assert_eq!(sniff_content_type(&mkv), Some("video/x-matroska"));
+        let mut webm = vec![0x1A, 0x45, 0xDF, 0xA3, 0x9F, 0x42, 0x82, 0x84];
+        webm.extend_from_slice(b"webm");
+        assert_eq!(sniff_content_type(&webm), Some("video/webm"));
+
+        assert_eq!(
+            sniff_content_type(b"RIFF\0\0\0\0AVI LIST"),
+            Some("video/x-msvideo")
+        );
+        assert_eq!(sniff_content_type(&ts(188, 0)), Some("video/mp2t"));
+        assert_eq!(sniff_content_type(&ts(192, 4)), Some("video/mp2t"));
+        assert_eq!(sniff_content_type(b"\x47not a stream"), None);
+
+        assert_eq!(sniff_content_type(b"ID3\x04\0\0"), Some("audio/mpeg"));
+        assert_eq!(sniff_content_type(&[0xFF, 0xFB, 0x90]), Some("audio/mpeg"));
+        assert_eq!(sniff_content_type(&[0xFF, 0xF1, 0x50]), Some("audio/aac"));
+        assert_eq!(sniff_content_type(b"fLaC\0\0\0\x22"), Some("audio/flac"));
+        assert_eq!(
+            sniff_content_type(b"RIFF\0\0\0\0WAVEfmt "),
+            Some("audio/wav")
+        );
+        assert_eq!(
+            sniff_content_type(b"\0\0\0\x20ftypM4A \0\0\0\0"),
+            Some("audio/mp4")
+        );
+        assert_eq!(
+            sniff_content_type(b"OggS\0\x02\0\0\0\0\0\0\0\0OpusHead"),
+            Some("audio/ogg")
+        );
+        assert_eq!(
+            sniff_content_type(b"OggS\0\x02\0\0\0\0\0\0\0\0\x80theora"),
+            Some("video/ogg")
+        );
+        assert_eq!(content_type_for_extension("FLAC"), Some("audio/flac"));
+        assert_eq!(content_type_for_extension("opus"), Some("audio/ogg"));
+        assert_eq!(sniff_content_type(b"plain text"), None);
+    }
+
+    #[tokio::test]
+    async fn unknown_extensions_are_sniffed_once_per_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Heat.bin");
+        let mut mkv = vec![0x1A, 0x45, 0xDF, 0xA3, 0x9F, 0x42, 0x82, 0x88];
+        mkv.extend_from_slice(b"matroska");
+        std::fs::write(&path, &mkv).unwrap();
+
+        let cache = ContentTypeCache::new();
+        let id = Uuid::new_v4();
+        let size = mkv.len() as u64;
+        assert_eq!(cache.resolve(id, &path, size).await, "video/x-matroska");
+
+        // Cached: the contents are not read again for the same size.
+        std::fs::write(&path, b"plain text").unwrap();
+        assert_eq!(cache.resolve(id, &path, size).await, "video/x-matroska");
+        assert_eq!(cache.resolve(id, &path, 10).await, FALLBACK_CONTENT_TYPE);
+
+        let named = dir.path().join("Heat.MKV");
+        assert_eq!(
+            cache.resolve(Uuid::new_v4(), &named, 0).await,
+            "video/x-matroska"
+        );
+    }
+}
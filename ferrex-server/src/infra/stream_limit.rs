@@ -0,0 +1,221 @@
+//! Cap on simultaneous direct-play streams.
+//!
+//! Every stream response, whether a range request or the whole file, holds
+//! a [`StreamPermit`] inside its body. The permit is released when the body
+//! is dropped, which happens when the transfer finishes or the client
+//! disconnects. Once the cap is reached new streams are refused with
+//! `503 Service Unavailable` and a `Retry-After` header instead of queueing
+//! more open files on a small server.
+//!
+//! Live transcodes take a slot from a second, smaller limiter on top of
+//! their stream slot, since each one keeps an ffmpeg encoder busy. Image
+//! readiness streams are counted by a third limiter the same way.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use axum::{
+    Json,
+    body::Body,
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
+    response::{IntoResponse, Response},
+};
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// `Retry-After` sent when every stream slot is taken.
+pub const STREAM_RETRY_AFTER_SECS: u64 = 5;
+
+/// Active streams and the configured cap, as reported by `/health`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StreamLimitStatus {
+    pub active: usize,
+    /// `None` when streams are not capped.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    /// Plural noun used in the busy response ("streams", "transcodes",
+    /// "subscriptions").
+    kind: &'static str,
+    semaphore: Option<Arc<Semaphore>>,
+    limit: Option<usize>,
+    active: AtomicUsize,
+}
+
+/// Shared stream limiter. Cheap to clone.
+#[derive(Debug, Clone)]
+pub struct StreamLimiter {
+    inner: Arc<Inner>,
+}
+
+impl StreamLimiter {
+    /// A limiter admitting `limit` streams at once; `None` only counts them.
+    pub fn new(limit: Option<usize>) -> Self {
+        Self::for_kind("streams", limit)
+    }
+
+    /// A limiter for live transcodes, reported as such when busy.
+    pub fn transcodes(limit: Option<usize>) -> Self {
+        Self::for_kind("transcodes", limit)
+    }
+
+    /// A limiter for image readiness subscriptions.
+    pub fn image_subscriptions(limit: Option<usize>) -> Self {
+        Self::for_kind("subscriptions", limit)
+    }
+
+    fn for_kind(kind: &'static str, limit: Option<usize>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                kind,
+                semaphore: limit.map(|limit| Arc::new(Semaphore::new(limit))),
+                limit,
+                active: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Claim a stream slot, or `None` when every slot is taken.
+    pub fn try_acquire(&self) -> Option<StreamPermit> {
+        let permit = match &self.inner.semaphore {
+            Some(semaphore) => {
+                Some(semaphore.clone().try_acquire_owned().ok()?)
+            }
+            None => None,
+        };
+        self.inner.active.fetch_add(1, Ordering::AcqRel);
+        Some(StreamPermit {
+            _permit: permit,
+            inner: Arc::clone(&self.inner),
+        })
+    }
+
+    pub fn status(&self) -> StreamLimitStatus {
+        StreamLimitStatus {
+            active: self.inner.active.load(Ordering::Acquire),
+            limit: self.inner.limit,
+        }
+    }
+
+    /// The `503` answer for a stream refused by [`Self::try_acquire`].
+    pub fn busy_response(&self) -> Response {
+        let kind = self.inner.kind;
+        let mut body = json!({
+            "error": {
+                "message": format!("Too many active {kind}; try again shortly"),
+                "status": StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+            },
+        });
+        body[kind] = json!(self.status());
+        let body = Json(body);
+        let mut response =
+            (StatusCode::SERVICE_UNAVAILABLE, body).into_response();
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(STREAM_RETRY_AFTER_SECS));
+        response
+    }
+}
+
+/// One stream slot; released on drop.
+#[derive(Debug)]
+pub struct StreamPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+    inner: Arc<Inner>,
+}
+
+impl StreamPermit {
+    /// Move the permit into `response`'s body so the slot stays taken until
+    /// the body is finished or dropped.
+    pub fn hold_for(self, response: Response) -> Response {
+        response.map(|body| {
+            let stream = body.into_data_stream().map(move |chunk| {
+                let _ = &self;
+                chunk
+            });
+            Body::from_stream(stream)
+        })
+    }
+}
+
+impl Drop for StreamPermit {
+    fn drop(&mut self) {
+        self.inner.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permits_are_capped_and_released_on_drop() {
+        let limiter = StreamLimiter::new(Some(2));
+        let first = limiter.try_acquire().expect("first slot");
+        let _second = limiter.try_acquire().expect("second slot");
+        assert!(limiter.try_acquire().is_none());
+        assert_eq!(
+            limiter.status(),
+            StreamLimitStatus {
+                active: 2,
+                limit: Some(2)
+            }
+        );
+
+        drop(first);
+        assert_eq!(limiter.status().active, 1);
+        assert!(limiter.try_acquire().is_some());
+    }
+
+    #[test]
+    fn uncapped_limiter_still_counts() {
+        let limiter = StreamLimiter::new(None);
+        let permits: Vec<_> =
+            (0..100).filter_map(|_| limiter.try_acquire()).collect();
+        assert_eq!(limiter.status().active, 100);
+        assert_eq!(limiter.status().limit, None);
+        drop(permits);
+        assert_eq!(limiter.status().active, 0);
+    }
+
+    #[tokio::test]
+    async fn permit_lives_as_long_as_the_body() {
+        let limiter = StreamLimiter::new(Some(1));
+        let permit = limiter.try_acquire().unwrap();
+        let response = permit.hold_for(Response::new(Body::from("chunk")));
+        assert!(limiter.try_acquire().is_none());
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&bytes[..], b"chunk");
+        assert_eq!(limiter.status().active, 0);
+        assert_eq!(limiter.busy_response().status(), 503);
+    }
+
+    #[tokio::test]
+    async fn busy_response_names_the_exhausted_kind() {
+        let limiter = StreamLimiter::transcodes(Some(0));
+        assert!(limiter.try_acquire().is_none());
+
+        let response = limiter.busy_response();
+        assert_eq!(response.headers()[RETRY_AFTER], "5");
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["transcodes"]["limit"], 0);
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("transcodes")
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Optional bandwidth cap on streams (`STREAM_BANDWIDTH_LIMIT_KBPS`).
+//!
+//! A capped response paces its own body: each chunk is held back until the
+//! bytes before it fit the target rate. The wait is a timer on the task
+//! driving that connection, so a capped stream only slows itself down.
+//! This shapes what is handed to the socket and nothing more; kernel
+//! buffers, proxies and other traffic on the link are not accounted for.
+
+use std::{io, time::Duration};
+
+use axum::body::{Body, Bytes};
+use futures::{Stream, StreamExt};
+use tokio::time::Instant;
+
+/// How far a stream may fall behind its schedule, e.g. while the client
+/// stopped reading, and still catch up at full speed afterwards.
+const MAX_CATCH_UP: Duration = Duration::from_secs(1);
+
+/// Schedule of one capped stream.
+#[derive(Debug)]
+struct Pacer {
+    bytes_per_sec: u64,
+    /// When the next chunk may be sent; unset until the first one.
+    next: Option<Instant>,
+}
+
+impl Pacer {
+    fn new(kbps: u64) -> Self {
+        Self {
+            bytes_per_sec: (kbps.saturating_mul(1000) / 8).max(1),
+            next: None,
+        }
+    }
+
+    /// How long to hold a chunk of `len` bytes arriving at `now`.
+    fn wait(&mut self, len: usize, now: Instant) -> Duration {
+        let earliest = now.checked_sub(MAX_CATCH_UP).unwrap_or(now);
+        let slot = self.next.map_or(now, |next| next.max(earliest));
+        let airtime = Duration::from_nanos(
+            (len as u64).saturating_mul(1_000_000_000) / self.bytes_per_sec,
+        );
+        self.next = Some(slot + airtime);
+        slot.saturating_duration_since(now)
+    }
+}
+
+/// Response body of `stream`, paced to `limit_kbps` kilobits per second
+/// when set. `None` and `Some(0)` leave it unthrottled.
+pub fn paced_body<S>(stream: S, limit_kbps: Option<u64>) -> Body
+where
+    S: Stream<Item = io::Result<Bytes>> + Send + 'static,
+{
+    let Some(kbps) = limit_kbps.filter(|kbps| *kbps > 0) else {
+        return Body::from_stream(stream);
+    };
+    let mut pacer = Pacer::new(kbps);
+    Body::from_stream(stream.then(move |chunk| {
+        let wait = match &chunk {
+            Ok(bytes) => pacer.wait(bytes.len(), Instant::now()),
+            Err(_) => Duration::ZERO,
+        };
+        async move {
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+            chunk
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_are_spaced_by_their_size() {
+        // 8000 kbps is 1 MB/s, so 100 kB takes 100 ms on the wire.
+        let mut pacer = Pacer::new(8_000);
+        let start = Instant::now();
+
+        assert_eq!(pacer.wait(100_000, start), Duration::ZERO);
+        assert_eq!(pacer.wait(100_000, start), Duration::from_millis(100));
+        assert_eq!(
+            pacer.wait(100_000, start + Duration::from_millis(150)),
+            Duration::from_millis(50)
+        );
+    }
+
+    #[test]
+    fn a_stalled_reader_catches_up_at_most_one_second() {
+        let mut pacer = Pacer::new(8_000);
+        let start = Instant::now();
+        pacer.wait(100_000, start);
+
+        let resumed = start + Duration::from_secs(10);
+        let mut sent = 0;
+        while pacer.wait(100_000, resumed).is_zero() {
+            sent += 100_000;
+        }
+        // A second's worth of backlog plus the chunk due right now.
+        assert_eq!(sent, 1_100_000);
+    }
+}
@@ -0,0 +1,214 @@
+//! Timestamp seeking (`?t=<seconds>`) for direct-play streams.
+//!
+//! Players that cannot do byte-range math ask to start at a time instead.
+//! How that is honored depends on the container:
+//!
+//! - MPEG program and transport streams resynchronize at any packet, so the
+//!   byte offset is estimated from duration and size and the file is served
+//!   from there.
+//! - Indexed containers (MP4, Matroska, WebM) are remuxed by ffmpeg starting
+//!   at the keyframe at or before the requested time, without re-encoding.
+//! - Anything else is served from the start and the response says the seek
+//!   was not honored.
+
+use std::path::Path;
+
+/// Response header reporting how a `t` parameter was handled.
+pub const SEEK_HEADER: &str = "x-stream-seek";
+
+/// MPEG-TS packet size; estimated offsets are aligned down to it.
+const TS_PACKET: u64 = 188;
+/// MPEG-PS pack alignment used by DVD-style program streams.
+const PS_PACK: u64 = 2048;
+
+/// How a requested start time will be served.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeekPlan {
+    /// Serve the file from this byte offset.
+    ByteOffset(u64),
+    /// Remux from the keyframe at or before `start` seconds.
+    Remux { start: f64, format: RemuxFormat },
+    /// Ignore the seek and serve the whole file.
+    Unsupported,
+}
+
+impl SeekPlan {
+    /// Value reported in [`SEEK_HEADER`].
+    pub fn header_value(&self) -> &'static str {
+        match self {
+            SeekPlan::ByteOffset(_) => "byte-estimate",
+            SeekPlan::Remux { .. } => "keyframe",
+            SeekPlan::Unsupported => "not-honored",
+        }
+    }
+}
+
+/// Output container for a keyframe remux.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemuxFormat {
+    Mp4,
+    Matroska,
+    WebM,
+}
+
+impl RemuxFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            RemuxFormat::Mp4 => "video/mp4",
+            RemuxFormat::Matroska => "video/x-matroska",
+            RemuxFormat::WebM => "video/webm",
+        }
+    }
+
+    /// Muxer arguments. MP4 is fragmented so it can be written to a pipe.
+    fn muxer_args(self) -> &'static [&'static str] {
+        match self {
+            RemuxFormat::Mp4 => &[
+                "-map",
+                "0:v?",
+                "-map",
+                "0:a?",
+                "-movflags",
+                "frag_keyframe+empty_moov+default_base_moof",
+                "-f",
+                "mp4",
+            ],
+            RemuxFormat::Matroska => &[
+                "-map", "0:v?", "-map", "0:a?", "-map", "0:s?", "-f",
+                "matroska",
+            ],
+            RemuxFormat::WebM => {
+                &["-map", "0:v?", "-map", "0:a?", "-f", "webm"]
+            }
+        }
+    }
+}
+
+/// Decide how to start a stream of a file with `extension` at `t` seconds.
+/// `duration` is the probed runtime; without it nothing can be estimated.
+pub fn plan_seek(
+    extension: Option<&str>,
+    file_size: u64,
+    duration: Option<f64>,
+    t: f64,
+) -> SeekPlan {
+    if !t.is_finite() || t <= 0.0 {
+        return SeekPlan::Unsupported;
+    }
+    let Some(duration) = duration.filter(|d| d.is_finite() && *d > 0.0) else {
+        return SeekPlan::Unsupported;
+    };
+    let t = t.min(duration);
+    let extension = extension.map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("ts" | "mts" | "m2ts") => SeekPlan::ByteOffset(estimate_offset(
+            file_size, duration, t, TS_PACKET,
+        )),
+        Some("mpg" | "mpeg" | "vob") => SeekPlan::ByteOffset(estimate_offset(
+            file_size, duration, t, PS_PACK,
+        )),
+        Some("mp4" | "m4v" | "mov") => SeekPlan::Remux {
+            start: t,
+            format: RemuxFormat::Mp4,
+        },
+        Some("mkv") => SeekPlan::Remux {
+            start: t,
+            format: RemuxFormat::Matroska,
+        },
+        Some("webm") => SeekPlan::Remux {
+            start: t,
+            format: RemuxFormat::WebM,
+        },
+        _ => SeekPlan::Unsupported,
+    }
+}
+
+/// Constant-bitrate estimate of where `t` falls, aligned down to `align`.
+fn estimate_offset(file_size: u64, duration: f64, t: f64, align: u64) -> u64 {
+    let raw = (file_size as f64 * (t / duration)) as u64;
+    let aligned = raw - raw % align;
+    aligned.min(file_size.saturating_sub(1) / align * align)
+}
+
+/// ffmpeg arguments that stream-copy `path` from `start` to stdout.
+pub fn remux_args(path: &Path, start: f64, format: RemuxFormat) -> Vec<String> {
+    let mut args: Vec<String> =
+        ["-hide_banner", "-loglevel", "error", "-nostdin", "-ss"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+    args.push(format!("{start:.3}"));
+    args.push("-i".into());
+    args.push(path.display().to_string());
+    args.extend(
+        ["-c", "copy", "-avoid_negative_ts", "make_zero"]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    args.extend(format.muxer_args().iter().map(|s| s.to_string()));
+    args.push("pipe:1".into());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_streams_seek_by_aligned_estimate() {
+        let size = 188 * 10_000;
+        match plan_seek(Some("ts"), size, Some(100.0), 50.0) {
+            SeekPlan::ByteOffset(offset) => {
+                assert_eq!(offset % TS_PACKET, 0);
+                assert_eq!(offset, size / 2);
+            }
+            other => panic!("unexpected plan {other:?}"),
+        }
+
+        match plan_seek(Some("M2TS"), size, Some(100.0), 500.0) {
+            SeekPlan::ByteOffset(offset) => {
+                assert!(offset < size);
+                assert_eq!(offset % TS_PACKET, 0);
+            }
+            other => panic!("unexpected plan {other:?}"),
+        }
+    }
+
+    #[test]
+    fn indexed_containers_remux_from_a_keyframe() {
+        assert_eq!(
+            plan_seek(Some("mkv"), 1, Some(3600.0), 120.0),
+            SeekPlan::Remux {
+                start: 120.0,
+                format: RemuxFormat::Matroska
+            }
+        );
+        let args = remux_args(Path::new("/m/a.mp4"), 120.0, RemuxFormat::Mp4);
+        let ss = args.iter().position(|a| a == "-ss").unwrap();
+        let input = args.iter().position(|a| a == "-i").unwrap();
+        assert!(ss < input, "-ss before -i seeks to a keyframe");
+        assert_eq!(args[ss + 1], "120.000");
+        assert_eq!(args.last().unwrap(), "pipe:1");
+    }
+
+    #[test]
+    fn unknown_containers_and_bad_input_are_not_honored() {
+        assert_eq!(
+            plan_seek(Some("avi"), 1_000, Some(60.0), 10.0),
+            SeekPlan::Unsupported
+        );
+        assert_eq!(
+            plan_seek(Some("ts"), 1_000, None, 10.0),
+            SeekPlan::Unsupported
+        );
+        assert_eq!(
+            plan_seek(Some("mp4"), 1_000, Some(60.0), f64::NAN),
+            SeekPlan::Unsupported
+        );
+        assert_eq!(
+            plan_seek(Some("mp4"), 1_000, Some(60.0), 0.0),
+            SeekPlan::Unsupported
+        );
+    }
+}
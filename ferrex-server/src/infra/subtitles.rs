@@ -0,0 +1,317 @@
+//! Subtitle tracks embedded in media containers.
+//!
+//! Tracks are listed with ffprobe and converted to WebVTT by ffmpeg the
+//! first time a client asks for one. Converted tracks are cached under
+//! `<transcode cache>/subtitles/<media id>/<index>.vtt` and reused until the
+//! source file changes. Image-based formats (PGS, VobSub, DVB) carry bitmaps
+//! rather than text and are refused instead of being converted to garbage.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+};
+
+use ferrex_core::api::types::EmbeddedSubtitleTrack;
+use tokio::{process::Command, sync::Mutex};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Subtitle codecs that hold bitmaps and cannot become WebVTT.
+const IMAGE_CODECS: &[&str] = &[
+    "hdmv_pgs_subtitle",
+    "dvd_subtitle",
+    "dvb_subtitle",
+    "dvb_teletext",
+    "xsub",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum SubtitleError {
+    #[error("ffmpeg is not available on this server")]
+    FfmpegUnavailable,
+    #[error("media file has no embedded subtitle track {0}")]
+    TrackNotFound(u32),
+    #[error(
+        "subtitle track {index} is image-based ({codec}) and not text-convertible"
+    )]
+    NotTextConvertible { index: u32, codec: String },
+    #[error("failed to read subtitle tracks: {0}")]
+    Probe(String),
+    #[error("subtitle extraction failed: {0}")]
+    Extract(String),
+    #[error("subtitle cache error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Lists embedded subtitle tracks and extracts them as WebVTT.
+#[derive(Debug)]
+pub struct SubtitleExtractor {
+    ffmpeg_path: String,
+    ffprobe_path: String,
+    cache_dir: PathBuf,
+    in_flight: Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>,
+}
+
+impl SubtitleExtractor {
+    pub fn new(
+        ffmpeg_path: String,
+        ffprobe_path: String,
+        transcode_cache_dir: &Path,
+    ) -> Self {
+        Self {
+            ffmpeg_path,
+            ffprobe_path,
+            cache_dir: transcode_cache_dir.join("subtitles"),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Subtitle tracks of the file at `source`, in container order.
+    pub async fn tracks(
+        &self,
+        source: &Path,
+    ) -> Result<Vec<EmbeddedSubtitleTrack>, SubtitleError> {
+        let output = Command::new(&self.ffprobe_path)
+            .args(["-v", "quiet", "-print_format", "json", "-show_streams"])
+            .args(["-select_streams", "s"])
+            .arg(source)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|_| SubtitleError::FfmpegUnavailable)?;
+        if !output.status.success() {
+            return Err(SubtitleError::Probe(format!(
+                "ffprobe exited with {}",
+                output.status
+            )));
+        }
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+            .map_err(|err| SubtitleError::Probe(err.to_string()))?;
+        Ok(parse_subtitle_tracks(&json))
+    }
+
+    /// WebVTT for subtitle track `index` of `media_id`, converting it first
+    /// if there is no cached copy newer than `source`.
+    pub async fn webvtt(
+        &self,
+        media_id: Uuid,
+        source: &Path,
+        index: u32,
+    ) -> Result<Vec<u8>, SubtitleError> {
+        let path = self
+            .cache_dir
+            .join(media_id.to_string())
+            .join(format!("{index}.vtt"));
+        if let Some(bytes) = read_fresh(&path, source).await? {
+            return Ok(bytes);
+        }
+
+        let track = self
+            .tracks(source)
+            .await?
+            .into_iter()
+            .find(|track| track.index == index)
+            .ok_or(SubtitleError::TrackNotFound(index))?;
+        if !track.text_convertible {
+            return Err(SubtitleError::NotTextConvertible {
+                index,
+                codec: track.codec,
+            });
+        }
+
+        let lock = Arc::clone(
+            self.in_flight.lock().await.entry(path.clone()).or_default(),
+        );
+        let guard = lock.lock().await;
+        // Another request may have converted it while we waited.
+        if let Some(bytes) = read_fresh(&path, source).await? {
+            return Ok(bytes);
+        }
+        let result = self.extract(source, index, &path).await;
+        drop(guard);
+        self.in_flight.lock().await.remove(&path);
+        result?;
+
+        Ok(tokio::fs::read(&path).await?)
+    }
+
+    async fn extract(
+        &self,
+        source: &Path,
+        index: u32,
+        path: &Path,
+    ) -> Result<(), SubtitleError> {
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        // Write to a temporary name so a half-written track is never served
+        // from the cache.
+        let partial = path.with_extension("vtt.part");
+        let output = Command::new(&self.ffmpeg_path)
+            .args(extract_args(source, index, &partial))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|_| SubtitleError::FfmpegUnavailable)?;
+
+        if !output.status.success() {
+            let _ = tokio::fs::remove_file(&partial).await;
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.lines().last().unwrap_or_default().to_string();
+            warn!(source = %source.display(), index, %reason, "subtitle extraction failed");
+            return Err(SubtitleError::Extract(reason));
+        }
+        tokio::fs::rename(&partial, path).await?;
+        Ok(())
+    }
+}
+
+/// The cached track at `path`, unless it is missing or older than `source`.
+async fn read_fresh(
+    path: &Path,
+    source: &Path,
+) -> Result<Option<Vec<u8>>, SubtitleError> {
+    let cached = match tokio::fs::metadata(path).await {
+        Ok(meta) => meta.modified().ok(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(None);
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let source_modified = tokio::fs::metadata(source)
+        .await
+        .ok()
+        .and_then(|meta| meta.modified().ok());
+    if let (Some(cached), Some(source)) = (cached, source_modified)
+        && source > cached
+    {
+        return Ok(None);
+    }
+    Ok(Some(tokio::fs::read(path).await?))
+}
+
+fn extract_args(input: &Path, index: u32, output: &Path) -> Vec<String> {
+    vec![
+        "-hide_banner".into(),
+        "-nostats".into(),
+        "-loglevel".into(),
+        "error".into(),
+        "-y".into(),
+        "-i".into(),
+        input.display().to_string(),
+        "-map".into(),
+        format!("0:s:{index}"),
+        "-c:s".into(),
+        "webvtt".into(),
+        "-f".into(),
+        "webvtt".into(),
+        output.display().to_string(),
+    ]
+}
+
+/// Tracks from `ffprobe -show_streams -select_streams s` JSON output.
+fn parse_subtitle_tracks(
+    json: &serde_json::Value,
+) -> Vec<EmbeddedSubtitleTrack> {
+    let Some(streams) = json["streams"].as_array() else {
+        return Vec::new();
+    };
+    streams
+        .iter()
+        .filter(|stream| stream["codec_type"].as_str() == Some("subtitle"))
+        .enumerate()
+        .map(|(index, stream)| {
+            let codec = stream["codec_name"]
+                .as_str()
+                .unwrap_or("unknown")
+                .to_string();
+            let tag = |name: &str| {
+                stream["tags"][name]
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+            };
+            let flag =
+                |name: &str| stream["disposition"][name].as_i64() == Some(1);
+            EmbeddedSubtitleTrack {
+                index: index as u32,
+                text_convertible: !IMAGE_CODECS.contains(&codec.as_str()),
+                codec,
+                language: tag("language").filter(|lang| lang != "und"),
+                title: tag("title"),
+                forced: flag("forced"),
+                default: flag("default"),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn tracks_are_indexed_among_subtitle_streams() {
+        let probe = json!({
+            "streams": [
+                {
+                    "index": 2,
+                    "codec_type": "subtitle",
+                    "codec_name": "subrip",
+                    "tags": { "language": "eng", "title": "English" },
+                    "disposition": { "default": 1, "forced": 0 }
+                },
+                {
+                    "index": 3,
+                    "codec_type": "subtitle",
+                    "codec_name": "hdmv_pgs_subtitle",
+                    "tags": { "language": "ger" },
+                    "disposition": { "default": 0, "forced": 1 }
+                },
+                {
+                    "index": 4,
+                    "codec_type": "subtitle",
+                    "codec_name": "ass",
+                    "tags": { "language": "und" }
+                }
+            ]
+        });
+
+        let tracks = parse_subtitle_tracks(&probe);
+        assert_eq!(tracks.len(), 3);
+
+        assert_eq!(tracks[0].index, 0);
+        assert_eq!(tracks[0].language.as_deref(), Some("eng"));
+        assert_eq!(tracks[0].title.as_deref(), Some("English"));
+        assert!(tracks[0].default && !tracks[0].forced);
+        assert!(tracks[0].text_convertible);
+
+        assert_eq!(tracks[1].index, 1);
+        assert!(tracks[1].forced);
+        assert!(!tracks[1].text_convertible);
+
+        assert_eq!(tracks[2].language, None);
+        assert!(tracks[2].text_convertible);
+    }
+
+    #[test]
+    fn extraction_maps_the_relative_subtitle_index() {
+        let args = extract_args(
+            Path::new("/media/film.mkv"),
+            2,
+            Path::new("/cache/2.vtt.part"),
+        );
+        let map = args.iter().position(|arg| arg == "-map").unwrap();
+        assert_eq!(args[map + 1], "0:s:2");
+        assert_eq!(args.last().unwrap(), "/cache/2.vtt.part");
+    }
+}
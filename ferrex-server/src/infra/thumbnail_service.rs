@@ -1,3 +1,4 @@
+use crate::infra::config::FfmpegConfig;
 use anyhow::{Context, Result};
 use ferrex_core::database::repository_ports::media_files::MediaFilesReadPort;
 use ffmpeg_next as ffmpeg;
@@ -10,6 +11,43 @@ use uuid::Uuid;
 pub struct ThumbnailService {
     cache_dir: PathBuf,
     media_files: Arc<dyn MediaFilesReadPort>,
+    settings: ThumbnailSettings,
+}
+
+/// Output size and encoding of extracted thumbnails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThumbnailSettings {
+    pub width: u32,
+    pub max_height: Option<u32>,
+    pub quality: u8,
+}
+
+impl From<&FfmpegConfig> for ThumbnailSettings {
+    fn from(config: &FfmpegConfig) -> Self {
+        Self {
+            width: config.thumbnail_width,
+            max_height: config.thumbnail_max_height,
+            quality: config.thumbnail_quality,
+        }
+    }
+}
+
+impl ThumbnailSettings {
+    /// Scaled size of a `src_w`x`src_h` frame: `width` wide unless the
+    /// source is narrower, then shrunk to fit `max_height`. The aspect
+    /// ratio is kept.
+    fn target_dimensions(&self, src_w: u32, src_h: u32) -> (u32, u32) {
+        let aspect = src_w as f64 / src_h as f64;
+        let width = self.width.min(src_w).max(1);
+        let height = (width as f64 / aspect).round().max(1.0) as u32;
+        match self.max_height {
+            Some(max_height) if height > max_height => {
+                let width = (max_height as f64 * aspect).round().max(1.0);
+                (width as u32, max_height)
+            }
+            _ => (width, height),
+        }
+    }
 }
 
 impl fmt::Debug for ThumbnailService {
@@ -17,6 +55,7 @@ impl fmt::Debug for ThumbnailService {
         f.debug_struct("ThumbnailService")
             .field("cache_dir", &self.cache_dir)
             .field("media_files_repo", &"dyn MediaFilesReadPort")
+            .field("settings", &self.settings)
             .finish()
     }
 }
@@ -25,6 +64,7 @@ impl ThumbnailService {
     pub fn new(
         cache_dir: PathBuf,
         media_files: Arc<dyn MediaFilesReadPort>,
+        settings: ThumbnailSettings,
     ) -> Result<Self> {
         // Initialize ffmpeg unless explicitly disabled (useful for tests)
         let ffmpeg_disabled = env::var("FERREX_DISABLE_FFMPEG")
@@ -38,6 +78,7 @@ impl ThumbnailService {
         Ok(Self {
             cache_dir,
             media_files,
+            settings,
         })
     }
 
@@ -53,7 +94,8 @@ impl ThumbnailService {
         self.get_thumbnail_path(media_id).exists()
     }
 
-    /// Extract and cache a thumbnail from a video file
+    /// Extract and cache a thumbnail from a video file. A cached thumbnail
+    /// is returned as is, even if it was made with other settings.
     pub async fn extract_thumbnail(
         &self,
         media_id: &Uuid,
@@ -84,11 +126,17 @@ impl ThumbnailService {
         // Run extraction in blocking task since ffmpeg is not async
         let video_path = video_path.to_string();
         let thumbnail_path_clone = thumbnail_path.clone();
+        let settings = self.settings;
 
         tokio::task::spawn_blocking(move || {
             // Catch panics that might occur from FFmpeg operations
             match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                extract_frame_at_percentage(&video_path, &thumbnail_path_clone, 0.1)
+                extract_frame_at_percentage(
+                    &video_path,
+                    &thumbnail_path_clone,
+                    0.1,
+                    settings,
+                )
             })) {
                 Ok(result) => result,
                 Err(_) => Err(anyhow::anyhow!(
@@ -134,6 +182,7 @@ fn extract_frame_at_percentage(
     input_path: &str,
     output_path: &Path,
     percentage: f64,
+    settings: ThumbnailSettings,
 ) -> Result<()> {
     tracing::info!(
         "Extracting thumbnail from {} at {}% to {:?}",
@@ -290,19 +339,9 @@ fn extract_frame_at_percentage(
         pixel_format
     );
 
-    // Calculate target dimensions preserving aspect ratio
-    // Base target: max width 320px, max height 180px
-    let (target_width, target_height) = if aspect_ratio > (320.0 / 180.0) {
-        // Video is wider than target aspect ratio, constrain by width
-        (320, (320.0 / aspect_ratio).round() as u32)
-    } else {
-        // Video is taller than target aspect ratio, constrain by height
-        ((180.0 * aspect_ratio).round() as u32, 180)
-    };
-
-    // Ensure minimum dimensions
-    let target_width = target_width.max(120);
-    let target_height = target_height.max(90);
+    // Scale in swscale so the full-resolution frame is never encoded.
+    let (target_width, target_height) =
+        settings.target_dimensions(original_width, original_height);
 
     tracing::info!("Thumbnail dimensions: {}x{}", target_width, target_height);
 
@@ -480,9 +519,11 @@ fn extract_frame_at_percentage(
                         }
 
                         // Save as JPEG
-                        if let Err(e) =
-                            save_frame_as_jpeg(&scaled_frame, output_path)
-                        {
+                        if let Err(e) = save_frame_as_jpeg(
+                            &scaled_frame,
+                            output_path,
+                            settings.quality,
+                        ) {
                             tracing::error!("Failed to save thumbnail: {}", e);
                             return Err(e);
                         }
@@ -516,6 +557,7 @@ fn extract_frame_at_percentage(
 fn save_frame_as_jpeg(
     frame: &ffmpeg::util::frame::video::Video,
     output_path: &Path,
+    quality: u8,
 ) -> Result<()> {
     use image::{ImageBuffer, Rgb};
 
@@ -557,7 +599,7 @@ fn save_frame_as_jpeg(
             )
         })?;
 
-    // Save as JPEG with quality 85 using an atomic write pattern
+    // Save as JPEG using an atomic write pattern
     use image::ColorType;
     use image::codecs::jpeg::JpegEncoder;
     use std::fs::{self, File};
@@ -570,7 +612,7 @@ fn save_frame_as_jpeg(
         let mut file = File::create(&tmp_path).with_context(|| {
             format!("Failed to create temp file for {:?}", tmp_path)
         })?;
-        let mut encoder = JpegEncoder::new_with_quality(&mut file, 85);
+        let mut encoder = JpegEncoder::new_with_quality(&mut file, quality);
         encoder
             .encode(img.as_raw(), width, height, ColorType::Rgb8.into())
             .context("Failed to encode JPEG")?;
@@ -600,3 +642,45 @@ fn save_frame_as_jpeg(
     tracing::debug!("Thumbnail saved to {:?}", output_path);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(width: u32, max_height: Option<u32>) -> ThumbnailSettings {
+        ThumbnailSettings {
+            width,
+            max_height,
+            quality: 85,
+        }
+    }
+
+    #[test]
+    fn target_dimensions_keep_the_aspect_ratio() {
+        assert_eq!(
+            settings(320, None).target_dimensions(3840, 2160),
+            (320, 180)
+        );
+        assert_eq!(
+            settings(480, None).target_dimensions(1440, 1080),
+            (480, 360)
+        );
+    }
+
+    #[test]
+    fn small_sources_are_not_upscaled() {
+        assert_eq!(settings(640, None).target_dimensions(480, 270), (480, 270));
+    }
+
+    #[test]
+    fn portrait_sources_respect_the_height_cap() {
+        assert_eq!(
+            settings(320, Some(240)).target_dimensions(1080, 1920),
+            (135, 240)
+        );
+        assert_eq!(
+            settings(320, Some(240)).target_dimensions(1920, 1080),
+            (320, 180)
+        );
+    }
+}
@@ -0,0 +1,497 @@
+//! Server-side transcoding into segmented HLS output.
+//!
+//! Each job runs one ffmpeg process writing `index.m3u8` and its segments into
+//! `<transcode cache>/<job id>/`. Job ids are derived from the media id and
+//! the requested [`TranscodeProfile`], so an identical request coalesces onto
+//! the job that is already running (or finished) instead of spawning another.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+    time::Instant,
+};
+
+use chrono::Utc;
+use ferrex_core::{
+    api::routes::{utils::replace_params, v1},
+    api::types::{TranscodeAudioCodec, TranscodeContainer, TranscodeProfile},
+    types::{
+        MediaFile, TranscodingJobResponse, TranscodingProgressDetails,
+        TranscodingStatus,
+    },
+};
+use sha2::{Digest, Sha256};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::Command,
+    sync::RwLock,
+};
+use tracing::{info, warn};
+
+/// Playlist written by every job; segments live next to it.
+pub const PLAYLIST_FILE: &str = "index.m3u8";
+const SEGMENT_SECONDS: u32 = 6;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TranscodeError {
+    #[error("transcode job not found")]
+    JobNotFound,
+    #[error("invalid transcode file name")]
+    InvalidFileName,
+    #[error("failed to start ffmpeg: {0}")]
+    Spawn(String),
+    #[error("transcode cache error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+struct TranscodeJob {
+    response: TranscodingJobResponse,
+    output_dir: PathBuf,
+}
+
+/// Tracks transcode jobs and the ffmpeg processes behind them.
+pub struct TranscodeManager {
+    ffmpeg_path: String,
+    cache_dir: PathBuf,
+    jobs: RwLock<HashMap<String, TranscodeJob>>,
+}
+
+impl std::fmt::Debug for TranscodeManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let jobs = self.jobs.try_read().ok().map(|guard| guard.len());
+        f.debug_struct("TranscodeManager")
+            .field("ffmpeg_path", &self.ffmpeg_path)
+            .field("cache_dir", &self.cache_dir)
+            .field("jobs", &jobs)
+            .finish()
+    }
+}
+
+impl TranscodeManager {
+    pub fn new(ffmpeg_path: String, cache_dir: PathBuf) -> Self {
+        Self {
+            ffmpeg_path,
+            cache_dir,
+            jobs: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Deterministic job id for `media_id` transcoded with `profile`.
+    pub fn job_id(media_id: uuid::Uuid, profile: &TranscodeProfile) -> String {
+        let digest = Sha256::digest(
+            format!("{}:{}", media_id, profile.cache_key()).as_bytes(),
+        );
+        format!("{:x}", digest)[..32].to_string()
+    }
+
+    /// Start transcoding `media_file`, or return the existing job for the
+    /// same media and profile. Failed or cancelled jobs are restarted.
+    pub async fn start(
+        self: &Arc<Self>,
+        media_file: &MediaFile,
+        profile: TranscodeProfile,
+    ) -> Result<TranscodingJobResponse, TranscodeError> {
+        let job_id = Self::job_id(media_file.id, &profile);
+        let mut jobs = self.jobs.write().await;
+
+        if let Some(job) = jobs.get(&job_id)
+            && !matches!(
+                job.response.status,
+                TranscodingStatus::Failed { .. } | TranscodingStatus::Cancelled
+            )
+        {
+            return Ok(job.response.clone());
+        }
+
+        let output_dir = self.cache_dir.join(&job_id);
+        if tokio::fs::try_exists(&output_dir).await? {
+            tokio::fs::remove_dir_all(&output_dir).await?;
+        }
+        tokio::fs::create_dir_all(&output_dir).await?;
+
+        let duration = media_file
+            .media_file_metadata
+            .as_ref()
+            .and_then(|meta| meta.duration);
+        let source_height = media_file
+            .media_file_metadata
+            .as_ref()
+            .and_then(|meta| meta.height);
+
+        let args =
+            ffmpeg_args(&media_file.path, &output_dir, &profile, source_height);
+        let child = Command::new(&self.ffmpeg_path)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| TranscodeError::Spawn(err.to_string()))?;
+
+        let response = TranscodingJobResponse {
+            id: job_id.clone(),
+            media_id: media_file.id.to_string(),
+            media_path: media_file.path.display().to_string(),
+            profile: profile.cache_key(),
+            status: TranscodingStatus::Pending,
+            created_at: Utc::now().timestamp().max(0) as u64,
+            output_path: Some(output_dir.display().to_string()),
+            playlist_path: None,
+            error: None,
+            progress_details: None,
+            duration,
+        };
+        jobs.insert(
+            job_id.clone(),
+            TranscodeJob {
+                response: response.clone(),
+                output_dir,
+            },
+        );
+        drop(jobs);
+
+        info!(
+            job = %job_id,
+            media = %media_file.id,
+            profile = %profile.cache_key(),
+            "transcode job started"
+        );
+
+        let this = Arc::clone(self);
+        tokio::spawn(async move {
+            this.supervise(job_id, child, duration).await;
+        });
+
+        Ok(response)
+    }
+
+    pub async fn status(
+        &self,
+        job_id: &str,
+    ) -> Result<TranscodingJobResponse, TranscodeError> {
+        self.jobs
+            .read()
+            .await
+            .get(job_id)
+            .map(|job| job.response.clone())
+            .ok_or(TranscodeError::JobNotFound)
+    }
+
+    /// Resolve a playlist or segment of `job_id` inside its output
+    /// directory. Returns `None` while the file has not been written yet.
+    pub async fn output_file(
+        &self,
+        job_id: &str,
+        file: &str,
+    ) -> Result<Option<PathBuf>, TranscodeError> {
+        let valid_name = !file.is_empty()
+            && file.chars().all(|c| {
+                c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')
+            })
+            && !file.starts_with('.');
+        if !valid_name {
+            return Err(TranscodeError::InvalidFileName);
+        }
+
+        let jobs = self.jobs.read().await;
+        let job = jobs.get(job_id).ok_or(TranscodeError::JobNotFound)?;
+        let path = job.output_dir.join(file);
+        Ok(tokio::fs::try_exists(&path).await?.then_some(path))
+    }
+
+    async fn supervise(
+        &self,
+        job_id: String,
+        mut child: tokio::process::Child,
+        duration: Option<f64>,
+    ) {
+        let started = Instant::now();
+
+        if let Some(stdout) = child.stdout.take() {
+            let mut lines = BufReader::new(stdout).lines();
+            let mut progress = FfmpegProgress::default();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if progress.apply(&line) {
+                    let details = progress.details(duration, started);
+                    self.update(&job_id, |job| {
+                        job.status = TranscodingStatus::Processing {
+                            progress: details.percentage / 100.0,
+                        };
+                        job.progress_details = Some(details);
+                    })
+                    .await;
+                }
+            }
+        }
+
+        let outcome = child.wait().await;
+        let playlist_written = self
+            .output_file(&job_id, PLAYLIST_FILE)
+            .await
+            .ok()
+            .flatten()
+            .is_some();
+
+        self.update(&job_id, |job| match &outcome {
+            Ok(status) if status.success() && playlist_written => {
+                job.status = TranscodingStatus::Completed;
+                if let Some(details) = job.progress_details.as_mut() {
+                    details.percentage = 100.0;
+                    details.estimated_time_remaining = Some(0.0);
+                }
+            }
+            Ok(status) => {
+                let error = format!("ffmpeg exited with {}", status);
+                job.status = TranscodingStatus::Failed {
+                    error: error.clone(),
+                };
+                job.error = Some(error);
+            }
+            Err(err) => {
+                let error = format!("failed to wait for ffmpeg: {}", err);
+                job.status = TranscodingStatus::Failed {
+                    error: error.clone(),
+                };
+                job.error = Some(error);
+            }
+        })
+        .await;
+
+        match outcome {
+            Ok(status) if status.success() => {
+                info!(job = %job_id, "transcode job completed")
+            }
+            Ok(status) => {
+                warn!(job = %job_id, %status, "transcode job failed")
+            }
+            Err(err) => {
+                warn!(job = %job_id, error = %err, "transcode job failed")
+            }
+        }
+    }
+
+    async fn update(
+        &self,
+        job_id: &str,
+        apply: impl FnOnce(&mut TranscodingJobResponse),
+    ) {
+        let mut jobs = self.jobs.write().await;
+        let Some(job) = jobs.get_mut(job_id) else {
+            return;
+        };
+        apply(&mut job.response);
+
+        // Expose the playlist as soon as ffmpeg has written it so players
+        // can start while later segments are still being produced.
+        if job.response.playlist_path.is_none()
+            && job.output_dir.join(PLAYLIST_FILE).exists()
+        {
+            job.response.playlist_path = Some(playlist_url(job_id));
+        }
+    }
+}
+
+/// Playable URL of a job's HLS playlist, relative to the server origin.
+pub fn playlist_url(job_id: &str) -> String {
+    replace_params(
+        v1::transcode::FILE,
+        &[("{id}", job_id), ("{file}", PLAYLIST_FILE)],
+    )
+}
+
+fn ffmpeg_args(
+    input: &Path,
+    output_dir: &Path,
+    profile: &TranscodeProfile,
+    source_height: Option<u32>,
+) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        "-hide_banner".into(),
+        "-nostats".into(),
+        "-y".into(),
+        "-i".into(),
+        input.display().to_string(),
+        "-map".into(),
+        "0:v:0".into(),
+        "-map".into(),
+        "0:a:0?".into(),
+        "-c:v".into(),
+        "libx264".into(),
+        "-preset".into(),
+        "veryfast".into(),
+        "-pix_fmt".into(),
+        "yuv420p".into(),
+    ];
+
+    // Never upscale: only add a scaler when the source is taller than the
+    // requested cap (or its height is unknown).
+    if let Some(max_height) = profile.max_height
+        && source_height.is_none_or(|height| height > max_height)
+    {
+        args.push("-vf".into());
+        args.push(format!("scale=-2:{}", max_height));
+    }
+
+    if let Some(kbps) = profile.max_bitrate_kbps {
+        args.extend([
+            "-maxrate".into(),
+            format!("{}k", kbps),
+            "-bufsize".into(),
+            format!("{}k", kbps.saturating_mul(2)),
+        ]);
+    }
+
+    let audio = match profile.audio_codec {
+        TranscodeAudioCodec::Aac => "aac",
+        TranscodeAudioCodec::Opus => "libopus",
+        TranscodeAudioCodec::Ac3 => "ac3",
+    };
+    args.extend([
+        "-c:a".into(),
+        audio.into(),
+        "-ac".into(),
+        "2".into(),
+        "-f".into(),
+        "hls".into(),
+        "-hls_time".into(),
+        SEGMENT_SECONDS.to_string(),
+        "-hls_playlist_type".into(),
+        "event".into(),
+    ]);
+
+    let segment_pattern = match profile.container {
+        TranscodeContainer::HlsTs => "segment_%05d.ts",
+        TranscodeContainer::HlsFmp4 => {
+            args.extend(["-hls_segment_type".into(), "fmp4".into()]);
+            "segment_%05d.m4s"
+        }
+    };
+    args.extend([
+        "-hls_segment_filename".into(),
+        output_dir.join(segment_pattern).display().to_string(),
+        "-progress".into(),
+        "pipe:1".into(),
+        output_dir.join(PLAYLIST_FILE).display().to_string(),
+    ]);
+
+    args
+}
+
+/// Accumulates `-progress pipe:1` key/value output between `progress=` lines.
+#[derive(Debug, Default)]
+struct FfmpegProgress {
+    out_time_us: Option<u64>,
+    frame: Option<u64>,
+    fps: Option<f64>,
+    bitrate_kbps: Option<u64>,
+}
+
+impl FfmpegProgress {
+    /// Record one output line; returns true when a progress block ended.
+    fn apply(&mut self, line: &str) -> bool {
+        let Some((key, value)) = line.trim().split_once('=') else {
+            return false;
+        };
+        match key {
+            "out_time_us" | "out_time_ms" => {
+                // Both keys carry microseconds in current ffmpeg releases.
+                self.out_time_us = value.parse().ok();
+            }
+            "frame" => self.frame = value.parse().ok(),
+            "fps" => self.fps = value.parse().ok(),
+            "bitrate" => {
+                self.bitrate_kbps = value
+                    .trim_end_matches("kbits/s")
+                    .parse::<f64>()
+                    .ok()
+                    .map(|kbps| kbps.round() as u64);
+            }
+            "progress" => return true,
+            _ => {}
+        }
+        false
+    }
+
+    fn details(
+        &self,
+        duration: Option<f64>,
+        started: Instant,
+    ) -> TranscodingProgressDetails {
+        let elapsed = started.elapsed().as_secs_f64();
+        let encoded = self.out_time_us.map(|us| us as f64 / 1_000_000.0);
+        let fraction = match (encoded, duration) {
+            (Some(encoded), Some(total)) if total > 0.0 => {
+                (encoded / total).clamp(0.0, 1.0)
+            }
+            _ => 0.0,
+        };
+        let remaining = (fraction > 0.0)
+            .then(|| elapsed / fraction - elapsed)
+            .filter(|secs| secs.is_finite());
+
+        TranscodingProgressDetails {
+            percentage: (fraction * 100.0) as f32,
+            time_elapsed: Some(elapsed),
+            estimated_time_remaining: remaining,
+            frames_processed: self.frame,
+            current_fps: self.fps,
+            current_bitrate: self.bitrate_kbps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_profiles_share_a_job_id() {
+        let media = uuid::Uuid::now_v7();
+        let profile = TranscodeProfile::preset("720p").unwrap();
+        assert_eq!(
+            TranscodeManager::job_id(media, &profile),
+            TranscodeManager::job_id(media, &profile)
+        );
+
+        let other = TranscodeProfile {
+            audio_codec: TranscodeAudioCodec::Opus,
+            ..profile
+        };
+        assert_ne!(
+            TranscodeManager::job_id(media, &profile),
+            TranscodeManager::job_id(media, &other)
+        );
+    }
+
+    #[test]
+    fn scaler_is_skipped_when_source_is_smaller() {
+        let profile = TranscodeProfile::preset("1080p").unwrap();
+        let input = Path::new("/media/movie.mkv");
+        let out = Path::new("/cache/job");
+
+        let args = ffmpeg_args(input, out, &profile, Some(720));
+        assert!(!args.iter().any(|arg| arg == "-vf"));
+
+        let args = ffmpeg_args(input, out, &profile, Some(2160));
+        assert!(args.iter().any(|arg| arg == "-vf"));
+        assert!(args.iter().any(|arg| arg == "8000k"));
+    }
+
+    #[test]
+    fn progress_block_reports_percentage() {
+        let mut progress = FfmpegProgress::default();
+        for line in ["frame=240", "fps=48.0", "bitrate=2048.3kbits/s"] {
+            assert!(!progress.apply(line));
+        }
+        assert!(!progress.apply("out_time_us=30000000"));
+        assert!(progress.apply("progress=continue"));
+
+        let details = progress.details(Some(120.0), Instant::now());
+        assert_eq!(details.percentage, 25.0);
+        assert_eq!(details.frames_processed, Some(240));
+        assert_eq!(details.current_bitrate, Some(2048));
+    }
+}
@@ -1,22 +1,55 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use ferrex_core::domain::users::user::User;
-use ferrex_core::sync_session::SyncMessage;
+use ferrex_core::sync_session::{
+    SyncMessage, WS_CLOSE_SESSION_EXPIRED, WS_CLOSE_SESSION_REVOKED,
+};
 use std::{fmt, sync::Arc};
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{RwLock, mpsc, watch};
 use uuid::Uuid;
 
+/// Why the server is closing a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseReason {
+    SessionRevoked,
+    SessionExpired,
+}
+
+impl CloseReason {
+    /// WebSocket close code sent to the client.
+    pub fn code(self) -> u16 {
+        match self {
+            CloseReason::SessionRevoked => WS_CLOSE_SESSION_REVOKED,
+            CloseReason::SessionExpired => WS_CLOSE_SESSION_EXPIRED,
+        }
+    }
+
+    pub fn message(self) -> &'static str {
+        match self {
+            CloseReason::SessionRevoked => "session revoked",
+            CloseReason::SessionExpired => "session expired",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Connection {
     /// Unique connection ID
     pub id: Uuid,
     /// User associated with this connection
     pub user: Arc<User>,
+    /// Auth session the connection was opened with
+    pub session_id: Uuid,
+    /// When that auth session expires
+    pub session_expires_at: DateTime<Utc>,
     /// Current room code (if in a sync session)
     pub room_code: Arc<RwLock<Option<String>>>,
     /// Channel to send messages to this connection
     sender: mpsc::Sender<SyncMessage>,
     /// Last ping timestamp for connection health
     pub last_ping: Arc<RwLock<i64>>,
+    /// Set once the server decides to close the connection
+    close_tx: Arc<watch::Sender<Option<CloseReason>>>,
 }
 
 impl fmt::Debug for Connection {
@@ -32,24 +65,53 @@ impl fmt::Debug for Connection {
             .field("id", &self.id)
             .field("user_id", &self.user.id)
             .field("username", &self.user.username)
+            .field("session_id", &self.session_id)
+            .field("session_expires_at", &self.session_expires_at)
             .field("room_code", &room_code)
             .field("channel_closed", &self.sender.is_closed())
             .field("last_ping", &last_ping)
+            .field("close_reason", &*self.close_tx.borrow())
             .finish()
     }
 }
 
 impl Connection {
-    pub fn new(user: User, sender: mpsc::Sender<SyncMessage>) -> Self {
+    pub fn new(
+        user: User,
+        session_id: Uuid,
+        session_expires_at: DateTime<Utc>,
+        sender: mpsc::Sender<SyncMessage>,
+    ) -> Self {
+        let (close_tx, _) = watch::channel(None);
         Self {
             id: Uuid::now_v7(),
             user: Arc::new(user),
+            session_id,
+            session_expires_at,
             room_code: Arc::new(RwLock::new(None)),
             sender,
             last_ping: Arc::new(RwLock::new(chrono::Utc::now().timestamp())),
+            close_tx: Arc::new(close_tx),
         }
     }
 
+    /// Ask the socket task to send a close frame and disconnect. Only the
+    /// first reason is kept.
+    pub fn close(&self, reason: CloseReason) {
+        self.close_tx.send_if_modified(|current| {
+            if current.is_some() {
+                return false;
+            }
+            *current = Some(reason);
+            true
+        });
+    }
+
+    /// Watch for a server-initiated close.
+    pub fn close_signal(&self) -> watch::Receiver<Option<CloseReason>> {
+        self.close_tx.subscribe()
+    }
+
     /// Send a message to this connection
     pub async fn send_message(&self, message: SyncMessage) -> Result<()> {
         self.sender.send(message).await.map_err(|_| {
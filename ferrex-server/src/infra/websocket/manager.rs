@@ -1,4 +1,4 @@
-use crate::infra::websocket::connection::Connection;
+use crate::infra::websocket::connection::{CloseReason, Connection};
 use dashmap::DashMap;
 use ferrex_core::sync_session::SyncMessage;
 use std::{fmt, sync::Arc};
@@ -111,6 +111,36 @@ impl ConnectionManager {
         self.connections.get(conn_id).map(|c| c.clone())
     }
 
+    /// Close every connection opened with the given auth session. Returns
+    /// how many were closed.
+    pub fn close_session(
+        &self,
+        session_id: Uuid,
+        reason: CloseReason,
+    ) -> usize {
+        self.close_where(reason, |conn| conn.session_id == session_id)
+    }
+
+    /// Close every connection belonging to a user.
+    pub fn close_user(&self, user_id: Uuid, reason: CloseReason) -> usize {
+        self.close_where(reason, |conn| conn.user.id == user_id)
+    }
+
+    fn close_where(
+        &self,
+        reason: CloseReason,
+        predicate: impl Fn(&Connection) -> bool,
+    ) -> usize {
+        let mut closed = 0;
+        for entry in self.connections.iter() {
+            if predicate(entry.value()) {
+                entry.value().close(reason);
+                closed += 1;
+            }
+        }
+        closed
+    }
+
     /// Subscribe to broadcast messages
     pub fn subscribe(&self) -> broadcast::Receiver<(String, SyncMessage)> {
         self.broadcast.subscribe()
@@ -26,6 +26,9 @@ use ferrex_core::{
         PostgresDatabase, context::DatabaseContext,
         repository_ports::media_files::MediaFileFilter,
     },
+    domain::media::naming_rules::{NamingRules, install_naming_rules},
+    domain::scan::actors::provider::{MetadataProvider, NfoMetadataProvider},
+    domain::scan::file_filter::{ScanFileFilter, install_scan_file_filter},
     domain::users::auth::{
         AuthCrypto,
         domain::{
@@ -35,7 +38,8 @@ use ferrex_core::{
                 RefreshTokenRepository, UserAuthenticationRepository,
             },
             services::{
-                AuthenticationService, DeviceTrustService, PinManagementService,
+                AuthenticationService, DeviceIdCollisionPolicy,
+                DeviceTrustService, PinManagementService, TokenLifetimes,
             },
         },
         infrastructure::repositories::{
@@ -44,7 +48,10 @@ use ferrex_core::{
             PostgresRefreshTokenRepository, PostgresUserAuthRepository,
         },
     },
-    infra::media::{image_service::ImageService, providers::TmdbApiProvider},
+    infra::{
+        cache::{CacheCipher, DiskSpaceGuard},
+        media::{image_service::ImageService, providers::TmdbApiProvider},
+    },
     types::LibraryReference,
 };
 
@@ -56,14 +63,20 @@ use ferrex_server::{
         app_state::AppState,
         cache::{MovieBatchesCache, SeriesBundlesCache},
         config::{
-            Config, ConfigLoad, ConfigLoader, HstsSettings, RateLimitSource,
+            Config, ConfigLoad, ConfigLoader, ConfigWarnings, HstsSettings,
+            LogFormat, RateLimitSource, RedisOutagePolicy,
             loader::db_url::{
                 DatabaseUrlSource, resolve_effective_database_url_with_source,
             },
         },
+        config_reload::{ConfigReloader, LogFilterHandle},
+        constants::DEFAULT_LOG_FILTER,
+        middleware::ProxyTrust,
         orchestration::ScanOrchestrator,
         postgres_tuning,
-        scan::scan_manager::ScanControlPlane,
+        readiness::Readiness,
+        redis_pool::RedisPool,
+        scan::scan_manager::{ScanControlPlane, ScanControlPlaneOptions},
         startup::{ProdStartupHooks, StartupHooks},
         websocket,
     },
@@ -79,17 +92,22 @@ use axum::{
     body::Body,
     extract::{Request, State},
     http::{HeaderValue, Method, StatusCode},
-    response::{Json, Response},
+    response::{IntoResponse, Json, Response},
     routing::get,
 };
 use chrono::Utc;
 use clap::{Args as ClapArgs, Parser, Subcommand};
-use ferrex_core::domain::scan::orchestration::LibraryActorConfig;
+use ferrex_core::domain::scan::exclusions::install_library_exclusions;
+use ferrex_core::domain::scan::orchestration::{
+    LibraryActorConfig, PostgresScanCheckpointRepository,
+};
 use ferrex_core::domain::setup::SetupClaimService;
 use ferrex_server::handlers::users::auth::tls::{
     TlsCertConfig, create_tls_acceptor,
 };
-use ferrex_server::infra::thumbnail_service::ThumbnailService;
+use ferrex_server::infra::thumbnail_service::{
+    ThumbnailService, ThumbnailSettings,
+};
 use serde_json::{Value, json};
 use sqlx::postgres::PgConnectOptions;
 use std::{
@@ -101,7 +119,7 @@ use tower_http::{
     cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer},
     trace::TraceLayer,
 };
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use uuid::Uuid;
 
@@ -233,7 +251,9 @@ struct ConfigBootstrap {
     config: Arc<Config>,
     tmdb_provider: Arc<TmdbApiProvider>,
     database_url: String,
-    with_cache: bool,
+    config_warnings: ConfigWarnings,
+    /// Swaps the log filter on reload; `None` when `RUST_LOG` is set.
+    log_filter: Option<LogFilterHandle>,
     #[cfg(feature = "demo")]
     demo_coordinator: Option<Arc<DemoCoordinator>>,
 }
@@ -259,20 +279,60 @@ async fn load_runtime_config(
     #[cfg(feature = "demo")]
     let mut demo_coordinator: Option<Arc<DemoCoordinator>> = None;
 
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-                // Quieter defaults with focused scan summaries. Override via RUST_LOG.
-                "info,scan::summary=info,scan::queue=info,scan::seed=info,tower_http=warn".into()
-            }),
+    // RUST_LOG wins over the configured level, which wins over the quieter
+    // built-in default with focused scan summaries. Only the configured
+    // level can be swapped by a reload.
+    let rust_log = tracing_subscriber::EnvFilter::try_from_default_env().ok();
+    let level_from_config = rust_log.is_none();
+    let env_filter = rust_log.unwrap_or_else(|| {
+        tracing_subscriber::EnvFilter::new(
+            config
+                .server
+                .log_level
+                .as_deref()
+                .unwrap_or(DEFAULT_LOG_FILTER),
         )
-        .with(tracing_subscriber::fmt::layer())
+    });
+    let (env_filter, log_filter) =
+        tracing_subscriber::reload::Layer::new(env_filter);
+    let log_filter = level_from_config.then_some(log_filter);
+    let (pretty_layer, json_layer) = match config.server.log_format {
+        LogFormat::Pretty => (Some(tracing_subscriber::fmt::layer()), None),
+        LogFormat::Json => (
+            None,
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_current_span(true)
+                    .with_span_list(true),
+            ),
+        ),
+    };
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(pretty_layer)
+        .with(json_layer)
         .init();
 
     if config.metadata.env_file_loaded {
         info!("loaded .env file");
     }
 
+    if let Some(dir) = config.metadata.config_dir.as_ref() {
+        info!(
+            dir = %dir.display(),
+            keys = config.metadata.fragment_sources.len(),
+            "applied config overlay fragments"
+        );
+        for (key, source) in &config.metadata.fragment_sources {
+            debug!(key = %key, source = %source.display(), "config value set by fragment");
+        }
+    }
+
+    for (key, path) in &config.metadata.secret_files {
+        info!(key = %key, file = %path.display(), "secret read from file");
+    }
+
     if let Some(source) = config.metadata.rate_limit_source.as_ref() {
         match source {
             RateLimitSource::EnvPath(path) => {
@@ -290,18 +350,7 @@ async fn load_runtime_config(
         }
     }
 
-    if !config_warnings.is_empty() {
-        for warning in &config_warnings.items {
-            match &warning.hint {
-                Some(hint) => {
-                    warn!(message = %warning.message, hint = %hint, "configuration warning")
-                }
-                None => {
-                    warn!(message = %warning.message, "configuration warning")
-                }
-            }
-        }
-    }
+    log_config_warnings(&config_warnings);
 
     let queue_cfg = &config.scanner.orchestrator.queue;
     let budget_cfg = &config.scanner.orchestrator.budget;
@@ -313,8 +362,28 @@ async fn load_runtime_config(
         scanner.actor_outstanding_cap =
             config.scanner.library_actor_max_outstanding_jobs,
         scanner.quiescence_ms = config.scanner.quiescence_window_ms,
+        scanner.prewarm_posters = config.scanner.prewarm_posters,
+        scanner.media_event_history = config.scanner.media_event_history,
         "scanner configuration in effect"
     );
+    if !config.media.filename_rules.is_empty() {
+        let rules = NamingRules::compile(&config.media.filename_rules)?;
+        info!(count = rules.len(), "custom filename rules loaded");
+        install_naming_rules(rules)?;
+    }
+    let file_filter = ScanFileFilter::new(
+        &config.scanner.effective_video_extensions(),
+        config.scanner.min_file_size_bytes,
+        config.scanner.max_file_size_bytes,
+    )?;
+    info!(
+        extensions = ?file_filter.extensions().collect::<Vec<_>>(),
+        min_bytes = ?config.scanner.min_file_size_bytes,
+        max_bytes = ?config.scanner.max_file_size_bytes,
+        "scan file filter"
+    );
+    install_scan_file_filter(file_filter)?;
+
     if let Some(media_root) = &config.media.root {
         info!("Media root: {}", media_root.display());
     } else {
@@ -330,7 +399,11 @@ async fn load_runtime_config(
         "cache directories prepared"
     );
 
-    let tmdb_provider = Arc::new(TmdbApiProvider::new());
+    let tmdb_provider = Arc::new(
+        TmdbApiProvider::new()
+            .with_preferred_languages(config.metadata_languages())
+            .with_region(config.locale.region.clone()),
+    );
 
     #[cfg(feature = "demo")]
     if args.demo {
@@ -378,18 +451,94 @@ async fn load_runtime_config(
     config.database.primary_url = Some(database_url.clone());
 
     let config = Arc::new(config);
-    let with_cache = config.redis.is_some();
 
     Ok(ConfigBootstrap {
         config,
         tmdb_provider,
         database_url,
-        with_cache,
+        config_warnings,
+        log_filter,
         #[cfg(feature = "demo")]
         demo_coordinator,
     })
 }
 
+fn log_config_warnings(warnings: &ConfigWarnings) {
+    for warning in &warnings.items {
+        match &warning.hint {
+            Some(hint) => {
+                warn!(message = %warning.message, hint = %hint, "configuration warning")
+            }
+            None => {
+                warn!(message = %warning.message, "configuration warning")
+            }
+        }
+    }
+}
+
+/// Reload the configuration on every `SIGHUP`. A configuration that fails
+/// validation is rejected as a whole; otherwise the settings that can
+/// change at runtime are applied and the rest are reported as waiting for
+/// a restart.
+#[cfg(unix)]
+fn spawn_config_reloader(reloader: ConfigReloader, args: &ServeArgs) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(err) => {
+            warn!(error = %err, "Failed to listen for SIGHUP; configuration reload disabled");
+            return;
+        }
+    };
+    let (host, port) = (args.host.clone(), args.port);
+
+    tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            info!("SIGHUP received; reloading configuration");
+            let active = reloader.active();
+            let ConfigLoad {
+                mut config,
+                warnings,
+            } = match ConfigLoader::new().reload(&active.metadata) {
+                Ok(load) => load,
+                Err(err) => {
+                    error!(error = %err, "configuration reload rejected; keeping the active configuration");
+                    continue;
+                }
+            };
+            log_config_warnings(&warnings);
+
+            // Apply what startup applied on top of the loaded values, so
+            // they do not show up as changes.
+            if let Some(port) = port {
+                config.server.port = port;
+            }
+            if let Some(host) = host.clone() {
+                config.server.host = host;
+            }
+            if let Some((url, _)) =
+                resolve_effective_database_url_with_source(&config)
+            {
+                config.database.primary_url = Some(url);
+            }
+
+            let summary = reloader.apply(&config, warnings);
+            for change in &summary.applied {
+                info!(change = %change, "configuration change applied");
+            }
+            for change in &summary.deferred {
+                warn!(change = %change, "configuration change needs a restart");
+            }
+            info!(
+                applied = summary.applied.len(),
+                deferred = summary.deferred.len(),
+                "configuration reloaded"
+            );
+        }
+    });
+}
+
 struct ResourceBootstrap {
     context: Arc<AppContext>,
     state: AppState,
@@ -401,7 +550,6 @@ async fn wire_app_resources(
     database_url: &str,
     tuning_statements: Option<Vec<String>>,
     tmdb_provider: Arc<TmdbApiProvider>,
-    with_cache: bool,
     #[cfg(feature = "demo")] demo_coordinator: Option<Arc<DemoCoordinator>>,
 ) -> anyhow::Result<ResourceBootstrap> {
     let db_context = match DatabaseContext::connect_postgres(
@@ -450,18 +598,20 @@ async fn wire_app_resources(
         );
     }
 
-    let tmdb_api_key = std::env::var("TMDB_API_KEY").ok();
-    match &tmdb_api_key {
-        Some(key) => info!("TMDB API key configured (length: {})", key.len()),
-        None => {
-            warn!("TMDB_API_KEY not set - metadata fetching will be limited")
-        }
+    if tmdb_provider.is_configured() {
+        info!("TMDB API key configured");
+    } else {
+        warn!(
+            "TMDB_API_KEY not set - running in limited metadata mode; media \
+             is indexed by file name without posters or details"
+        );
     }
 
     let thumbnail_service = Arc::new(
         ThumbnailService::new(
             config.cache_root().to_path_buf(),
             unit_of_work.media_files_read.clone(),
+            ThumbnailSettings::from(&config.ffmpeg),
         )
         .expect("Failed to initialize thumbnail service"),
     );
@@ -476,17 +626,42 @@ async fn wire_app_resources(
         config.image_cache_dir().to_path_buf(),
         download_concurrency,
     ));
+    let disk_space = DiskSpaceGuard::new(
+        config.cache_root().to_path_buf(),
+        config.cache.min_free_bytes,
+    );
+    image_service.set_disk_space_guard(disk_space.clone());
+    image_service.set_thumbnail_strategy(config.ffmpeg.thumbnail_strategy);
+    image_service.set_thumbnail_quality(config.ffmpeg.thumbnail_quality);
+    image_service.set_unavailable_ttl(Duration::from_secs(
+        config.cache.image_failure_ttl_secs,
+    ));
+    if let Some(key) = config.cache_encryption_key() {
+        image_service.set_cache_cipher(CacheCipher::new(key)?);
+        info!("Cached images and HLS segments are encrypted at rest");
+    }
+    configure_image_store(&image_service, &config)?;
+
+    // Local NFO sidecars take precedence over TMDB.
+    let metadata_providers: Vec<Arc<dyn MetadataProvider>> =
+        vec![Arc::new(NfoMetadataProvider::new(
+            unit_of_work.media_refs.clone(),
+            unit_of_work.media_files_write.clone(),
+            image_service.clone(),
+        ))];
 
     let orchestrator = Arc::new(
         ScanOrchestrator::postgres(
             config.scanner.orchestrator.clone(),
             postgres_backend.clone(),
             tmdb_provider.clone(),
+            metadata_providers,
             image_service.clone(),
             unit_of_work.clone(),
         )
         .await?,
     );
+    orchestrator.set_device_overrides(config.scanner.device_overrides.clone());
 
     let libraries = unit_of_work
         .libraries
@@ -506,11 +681,31 @@ async fn wire_app_resources(
         libraries
     };
 
+    let quiescence =
+        Duration::from_millis(config.scanner.quiescence_window_ms.max(1));
+    for key in config.scanner.library_quiescence_window_ms.keys() {
+        if !libraries.iter().any(|library| {
+            library.name == *key || library.id.to_string() == *key
+        }) {
+            warn!(
+                library = %key,
+                "scanner.library_quiescence_window_ms names no library; ignoring"
+            );
+        }
+    }
+
     let mut watch_enabled = 0usize;
     for library in &libraries {
         if library.watch_for_changes {
             watch_enabled += 1;
         }
+        if let Err(err) = install_library_exclusions(library) {
+            warn!(
+                library = %library.name,
+                error = %err,
+                "ignoring invalid exclude patterns"
+            );
+        }
 
         let actor_config = LibraryActorConfig {
             library: LibraryReference {
@@ -523,7 +718,21 @@ async fn wire_app_resources(
             max_outstanding_jobs: config
                 .scanner
                 .library_actor_max_outstanding_jobs,
+            quiescence_window: config.scanner.library_quiescence_window(
+                &library.id.to_string(),
+                &library.name,
+            ),
         };
+        info!(
+            library = %library.name,
+            library_id = %library.id,
+            quiescence_ms = actor_config
+                .quiescence_window
+                .unwrap_or(quiescence)
+                .as_millis() as u64,
+            overridden = actor_config.quiescence_window.is_some(),
+            "effective scan quiescence window"
+        );
 
         orchestrator
             .register_library(actor_config, library.watch_for_changes)
@@ -542,13 +751,31 @@ async fn wire_app_resources(
 
     orchestrator.start().await?;
 
-    let quiescence =
-        Duration::from_millis(config.scanner.quiescence_window_ms.max(1));
-    let scan_control = Arc::new(ScanControlPlane::with_quiescence_window(
+    let scan_control = Arc::new(ScanControlPlane::with_options(
         unit_of_work.clone(),
         orchestrator,
-        quiescence,
+        ScanControlPlaneOptions {
+            quiescence,
+            media_event_history: config.scanner.media_event_history,
+            poster_prewarm: config
+                .scanner
+                .prewarm_posters
+                .then(|| Arc::clone(&image_service)),
+            disk_space: Some(disk_space),
+            image_cache: Some(Arc::clone(&image_service)),
+            checkpoints: Some(Arc::new(PostgresScanCheckpointRepository::new(
+                postgres_pool.clone(),
+            ))),
+        },
     ));
+    let interrupted = scan_control.recover_interrupted_scans().await;
+    if !interrupted.is_empty() {
+        info!(
+            count = interrupted.len(),
+            "found scans interrupted by the last shutdown; resume them via {}",
+            ferrex_core::api::routes::v1::scan::RESUME_INTERRUPTED
+        );
+    }
 
     let websocket_manager = Arc::new(websocket::ConnectionManager::new());
 
@@ -577,6 +804,16 @@ async fn wire_app_resources(
         PostgresDeviceChallengeRepository::new(postgres_pool.clone()),
     );
 
+    let token_lifetime = |ttl: Duration| {
+        chrono::Duration::from_std(ttl)
+            .context("configured token lifetime is out of range")
+    };
+    let token_lifetimes = TokenLifetimes {
+        session: token_lifetime(config.auth.session_ttl)?,
+        refresh: token_lifetime(config.auth.refresh_ttl)?,
+        device_challenge: token_lifetime(config.auth.device_challenge_ttl)?,
+    };
+
     let auth_service = Arc::new(
         AuthenticationService::new(
             user_auth_repository.clone(),
@@ -586,16 +823,27 @@ async fn wire_app_resources(
             auth_crypto.clone(),
         )
         .with_event_repository(auth_event_repo.clone())
-        .with_challenge_repository(device_challenges.clone()),
+        .with_challenge_repository(device_challenges.clone())
+        .with_token_lifetimes(token_lifetimes)
+        .with_clock_skew_leeway(token_lifetime(config.auth.clock_skew_leeway)?),
     );
 
-    let device_trust_service = Arc::new(DeviceTrustService::new(
-        user_auth_repository.clone(),
-        device_sessions.clone(),
-        auth_event_repo.clone(),
-        auth_sessions.clone(),
-        refresh_tokens.clone(),
-    ));
+    let device_trust_service = Arc::new(
+        DeviceTrustService::new(
+            user_auth_repository.clone(),
+            device_sessions.clone(),
+            auth_event_repo.clone(),
+            auth_sessions.clone(),
+            refresh_tokens.clone(),
+        )
+        .with_device_id_collision_policy(
+            if config.auth.strict_device_ids {
+                DeviceIdCollisionPolicy::Reject
+            } else {
+                DeviceIdCollisionPolicy::Reissue
+            },
+        ),
+    );
 
     let pin_management_service = Arc::new(PinManagementService::new(
         user_auth_repository.clone(),
@@ -616,6 +864,8 @@ async fn wire_app_resources(
         auth_crypto.clone(),
     ));
 
+    let redis = connect_redis(&config).await?;
+
     let admin_sessions = Arc::new(Mutex::new(HashMap::new()));
     let app_context = Arc::new(AppContext::new(
         Arc::clone(&config),
@@ -628,7 +878,7 @@ async fn wire_app_resources(
         Arc::clone(&auth_facade),
         auth_crypto,
         setup_claim_service,
-        with_cache,
+        redis,
         #[cfg(feature = "demo")]
         demo_coordinator,
     ));
@@ -639,6 +889,8 @@ async fn wire_app_resources(
         Arc::new(SeriesBundlesCache::new()),
         Arc::new(MovieBatchesCache::new()),
     );
+    state.readiness().mark_ready();
+    info!("Startup complete; readiness probe now reports ready");
 
     Ok(ResourceBootstrap {
         context: app_context,
@@ -646,6 +898,65 @@ async fn wire_app_resources(
     })
 }
 
+/// Keep image blobs in the configured store; local files need no setup.
+fn configure_image_store(
+    image_service: &ImageService,
+    config: &Config,
+) -> anyhow::Result<()> {
+    #[cfg(feature = "s3")]
+    if let Some(s3) = &config.cache.image_store_s3 {
+        use ferrex_core::infra::cache::{S3BlobBackend, S3BlobSettings};
+
+        let backend = S3BlobBackend::new(S3BlobSettings {
+            bucket: s3.bucket.clone(),
+            region: s3.region.clone(),
+            endpoint: s3.endpoint.clone(),
+            prefix: s3.prefix.clone(),
+            public_url: s3.public_url.clone(),
+        })?;
+        image_service.set_blob_backend(Arc::new(backend));
+        info!(bucket = %s3.bucket, "Image blobs are stored in S3");
+    }
+
+    #[cfg(not(feature = "s3"))]
+    if config.cache.image_store_s3.is_some() {
+        let _ = image_service;
+        return Err(anyhow::anyhow!(
+            "IMAGE_STORE=s3 needs a server built with the `s3` feature"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Open the Redis pool when `REDIS_URL` is set. If Redis is unreachable,
+/// startup continues without it under the fail-open policy and stops under
+/// fail-closed, since the auth endpoints would otherwise refuse every
+/// request.
+async fn connect_redis(config: &Config) -> anyhow::Result<Option<RedisPool>> {
+    let Some(redis) = config.redis.as_ref() else {
+        return Ok(None);
+    };
+    match RedisPool::connect(redis).await {
+        Ok(pool) => {
+            info!(pool_size = pool.size(), "Connected to Redis");
+            Ok(Some(pool))
+        }
+        Err(err) => match redis.outage_policy {
+            RedisOutagePolicy::FailOpen => {
+                warn!(
+                    error = %err,
+                    "Redis is unreachable; continuing without rate limiting (REDIS_OUTAGE_POLICY=fail_open)"
+                );
+                Ok(None)
+            }
+            RedisOutagePolicy::FailClosed => Err(err.context(
+                "Redis is unreachable and REDIS_OUTAGE_POLICY=fail_closed",
+            )),
+        },
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 struct ResolvedTlsPaths {
     cert: Option<PathBuf>,
@@ -744,7 +1055,8 @@ where
         config,
         tmdb_provider,
         database_url,
-        with_cache,
+        config_warnings,
+        log_filter,
         #[cfg(feature = "demo")]
         demo_coordinator,
     } = load_runtime_config(&args).await?;
@@ -757,7 +1069,6 @@ where
         &database_url,
         tuning_statements,
         tmdb_provider,
-        with_cache,
         #[cfg(feature = "demo")]
         demo_coordinator.clone(),
     )
@@ -797,12 +1108,27 @@ where
     {
         anyhow::bail!(
             "ENFORCE_HTTPS=true but TLS is not configured locally and TRUST_PROXY_HEADERS=false. \
-            If TLS terminates at your reverse proxy, set TRUST_PROXY_HEADERS=true so the server can \
-            honor X-Forwarded-Proto and enforce HTTPS correctly. Alternatively, configure TLS_CERT_PATH \
+            If TLS terminates at your reverse proxy, set TRUST_PROXY_HEADERS=true (and TRUSTED_PROXIES \
+            to the proxy's address range) so the server can honor X-Forwarded-Proto and enforce HTTPS correctly. Alternatively, configure TLS_CERT_PATH \
             and TLS_KEY_PATH to enable HTTPS directly."
         );
     }
 
+    state.live_settings().record_warnings(config_warnings);
+    #[cfg(unix)]
+    spawn_config_reloader(
+        ConfigReloader::new(
+            (*config).clone(),
+            state.live_settings().clone(),
+            log_filter,
+        ),
+        &args,
+    );
+    #[cfg(not(unix))]
+    drop(log_filter);
+
+    let readiness = state.readiness().clone();
+    let drain_delay = shutdown_drain_delay();
     let ServerSetup { router, mode } =
         build_server_setup(state, Arc::clone(&config), &args);
 
@@ -816,7 +1142,14 @@ where
                 config.server.host, config.server.port
             );
             let rustls_config = create_tls_acceptor(tls).await?;
+            let handle = axum_server::Handle::new();
+            let shutdown_handle = handle.clone();
+            tokio::spawn(async move {
+                drain_on_shutdown_signal(readiness, drain_delay).await;
+                shutdown_handle.graceful_shutdown(Some(SHUTDOWN_GRACE_PERIOD));
+            });
             axum_server::bind_rustls(addr, rustls_config)
+                .handle(handle)
                 .serve(
                     router.into_make_service_with_connect_info::<SocketAddr>(),
                 )
@@ -834,13 +1167,85 @@ where
             let listener = tokio::net::TcpListener::bind(addr).await?;
             let make_service =
                 router.into_make_service_with_connect_info::<SocketAddr>();
-            axum::serve(listener, make_service).await?;
+            axum::serve(listener, make_service)
+                .with_graceful_shutdown(drain_on_shutdown_signal(
+                    readiness,
+                    drain_delay,
+                ))
+                .await?;
         }
     }
 
+    info!("Server stopped");
     Ok(())
 }
 
+/// Upper bound on how long in-flight HTTPS connections may drain.
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// How long `/ready` reports 503 before the listener closes, unless
+/// `FERREX_SHUTDOWN_DRAIN_SECS` says otherwise.
+const DEFAULT_SHUTDOWN_DRAIN: Duration = Duration::from_secs(5);
+
+fn shutdown_drain_delay() -> Duration {
+    match std::env::var("FERREX_SHUTDOWN_DRAIN_SECS") {
+        Ok(raw) => raw.trim().parse().map(Duration::from_secs).unwrap_or_else(
+            |_| {
+                warn!(
+                    "Ignoring invalid FERREX_SHUTDOWN_DRAIN_SECS={raw:?}; using {}s",
+                    DEFAULT_SHUTDOWN_DRAIN.as_secs()
+                );
+                DEFAULT_SHUTDOWN_DRAIN
+            },
+        ),
+        Err(_) => DEFAULT_SHUTDOWN_DRAIN,
+    }
+}
+
+/// Resolve on Ctrl+C or SIGTERM once `/ready` has reported 503 for
+/// `drain_delay`, so load balancers stop routing new traffic before the
+/// listener closes and open connections finish.
+async fn drain_on_shutdown_signal(readiness: Readiness, drain_delay: Duration) {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            error!(error = %err, "Failed to listen for Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+
+    readiness.mark_draining();
+    if !drain_delay.is_zero() {
+        info!(
+            "Shutdown signal received; reporting not ready for {}s before draining",
+            drain_delay.as_secs()
+        );
+        tokio::time::sleep(drain_delay).await;
+    }
+    info!("Draining connections");
+}
+
 async fn resolve_postgres_tuning_statements(
     database_url: &str,
 ) -> Option<Vec<String>> {
@@ -979,81 +1384,272 @@ pub fn create_app(state: AppState, https_terminates_here: bool) -> Router {
     // Create versioned API routes
     let versioned_api = routes::create_api_router(state.clone());
 
+    // Forwarded headers are only believed from configured proxy ranges
+    let proxy_trust = ProxyTrust::from_config(&state.config().security);
+
     // Global rate limiting layer using MatchedPath classification
     let rate_limit_layer = {
-        use axum::extract::{ConnectInfo, MatchedPath};
+        use axum::extract::MatchedPath;
         use axum::http::header::{HeaderName, HeaderValue, RETRY_AFTER};
         use ferrex_core::api::routes::v1;
-        use ferrex_core::domain::users::auth::rate_limit::RateLimitKey;
-        use ferrex_server::infra::middleware::create_rate_limiter;
-        use std::net::SocketAddr;
+        use ferrex_core::domain::users::auth::rate_limit::{
+            RateLimitError, RateLimitKey,
+        };
+        use ferrex_server::handlers::users::auth::session_is_on_device;
+        use ferrex_server::infra::middleware::{
+            create_rate_limiter, insert_unlimited_headers,
+        };
         use std::time::{SystemTime, UNIX_EPOCH};
 
         let config = state.config_handle();
 
-        match (config.rate_limiter.as_ref(), config.redis.as_ref()) {
+        match (config.rate_limiter.as_ref(), state.redis().cloned()) {
             (Some(settings), Some(redis)) => {
-                let configured_limits = settings.config.endpoint_limits.clone();
-                match create_rate_limiter(&redis.url, settings.config.clone()) {
-                    Ok(limiter) => {
+                let limiter =
+                    create_rate_limiter(redis.clone(), settings.config.clone());
+                state.install_rate_limiter(limiter.clone());
+                let proxy_trust = proxy_trust.clone();
+                // Limits and allowlist are read per request so a reload
+                // takes effect immediately.
+                let live = state.live_settings().clone();
+                let device_trust = state.auth_facade().device_trust_service();
+                let state = state.clone();
+                Some(axum::middleware::from_fn(
+                    move |req: Request<Body>, next: axum::middleware::Next| {
+                        let state = state.clone();
                         let limiter = limiter.clone();
-                        Some(axum::middleware::from_fn(move |req: Request<Body>, next: axum::middleware::Next| {
-                            let limiter = limiter.clone();
-                            let configured_limits = configured_limits.clone();
-                            async move {
-                                let matched = req
-                                    .extensions()
-                                    .get::<MatchedPath>()
-                                    .map(|m: &MatchedPath| m.as_str().to_string());
-                                let limits = configured_limits.clone();
-                                let rule_opt = matched.as_deref().and_then(|p| {
-                                    if p == v1::auth::LOGIN || p == v1::auth::device::LOGIN { Some(limits.login) } else if p == v1::auth::REGISTER { Some(limits.register) } else if p == v1::auth::REFRESH { Some(limits.token_refresh) } else if p == v1::auth::device::PIN_LOGIN || p == v1::auth::device::PIN_CHALLENGE { Some(limits.pin_auth) } else if p == v1::setup::CLAIM_START { Some(limits.setup_start) } else if p == v1::setup::CLAIM_CONFIRM { Some(limits.setup_confirm) } else if p == v1::setup::CREATE_ADMIN { Some(limits.setup_create_admin) } else { None }
-                                });
+                        let hot = live.current();
+                        let proxy_trust = proxy_trust.clone();
+                        let redis = redis.clone();
+                        let device_trust = device_trust.clone();
+                        async move {
+                            let Some(limits) = hot.rate_limits.clone() else {
+                                return Ok::<_, StatusCode>(
+                                    next.run(req).await,
+                                );
+                            };
+                            let matched = req
+                                .extensions()
+                                .get::<MatchedPath>()
+                                .map(|m: &MatchedPath| m.as_str().to_string());
+                            let rule_opt = matched.as_deref().and_then(|p| {
+                                if p == v1::auth::LOGIN
+                                    || p == v1::auth::device::LOGIN
+                                {
+                                    Some(limits.login)
+                                } else if p == v1::auth::REGISTER {
+                                    Some(limits.register)
+                                } else if p == v1::auth::REFRESH {
+                                    Some(limits.token_refresh)
+                                } else if p == v1::auth::device::PIN_LOGIN
+                                    || p == v1::auth::device::PIN_CHALLENGE
+                                {
+                                    Some(limits.pin_auth)
+                                } else if p == v1::setup::CLAIM_START {
+                                    Some(limits.setup_start)
+                                } else if p == v1::setup::CLAIM_CONFIRM
+                                    || p == v1::setup::CLAIM_LINK
+                                {
+                                    Some(limits.setup_confirm)
+                                } else if p == v1::setup::CREATE_ADMIN {
+                                    Some(limits.setup_create_admin)
+                                } else {
+                                    None
+                                }
+                            });
+
+                            let Some(rule) = rule_opt else {
+                                return Ok::<_, StatusCode>(
+                                    next.run(req).await,
+                                );
+                            };
+
+                            let device_id = req
+                                .headers()
+                                .get("X-Device-ID")
+                                .and_then(|v| v.to_str().ok())
+                                .and_then(|s| Uuid::parse_str(s).ok());
+                            // An id seen on more than one user's device
+                            // does not tell clients apart; key those
+                            // requests by address instead.
+                            let device_id = match device_id {
+                                Some(id) => match device_trust
+                                    .is_device_id_contested(id)
+                                    .await
+                                {
+                                    Ok(contested) => (!contested).then_some(id),
+                                    Err(err) => {
+                                        warn!(
+                                            "device id lookup failed, keying rate limit by address: {err}"
+                                        );
+                                        None
+                                    }
+                                },
+                                None => None,
+                            };
+                            let client_ip = proxy_trust.client_ip(
+                                ProxyTrust::peer_ip(&req),
+                                req.headers(),
+                            );
+
+                            // A listed device id only counts when a live
+                            // session on the request logged in as that
+                            // device; the header alone is the client's say.
+                            let session_device_id = match device_id {
+                                Some(id)
+                                    if hot
+                                        .rate_limit_bypass
+                                        .lists_device(id)
+                                        && session_is_on_device(
+                                            &state,
+                                            req.headers(),
+                                            id,
+                                        )
+                                        .await =>
+                                {
+                                    Some(id)
+                                }
+                                _ => None,
+                            };
+
+                            // Allowlisted clients never touch a counter.
+                            if hot
+                                .rate_limit_bypass
+                                .allows(session_device_id, client_ip)
+                            {
+                                let mut response = next.run(req).await;
+                                insert_unlimited_headers(
+                                    response.headers_mut(),
+                                );
+                                return Ok::<_, StatusCode>(response);
+                            }
 
-                                let Some(rule) = rule_opt else { return Ok::<_, StatusCode>(next.run(req).await); };
+                            let key = device_id
+                                .map(RateLimitKey::DeviceId)
+                                .or_else(|| {
+                                    client_ip.map(|ip| {
+                                        RateLimitKey::IpAddress(ip.to_string())
+                                    })
+                                })
+                                .unwrap_or_else(|| {
+                                    RateLimitKey::Custom("unknown".to_string())
+                                });
 
-                                let mut key = None;
-                                if let Some(dev_id) = req.headers().get("X-Device-ID").and_then(|v| v.to_str().ok()).and_then(|s| Uuid::parse_str(s).ok()) {
-                                    key = Some(RateLimitKey::DeviceId(dev_id));
+                            // A check runs a few round trips; bound it so an
+                            // unresponsive Redis counts as an outage.
+                            let checked = tokio::time::timeout(
+                                redis.command_timeout() * 4,
+                                limiter.check_and_update(&key, &rule),
+                            )
+                            .await
+                            .unwrap_or_else(|_| {
+                                Err(RateLimitError::BackendError(
+                                    anyhow::anyhow!(
+                                        "rate limit check timed out"
+                                    ),
+                                ))
+                            });
+                            match checked {
+                                Ok(decision) if decision.allowed => {
+                                    let mut response = next.run(req).await;
+                                    let headers = response.headers_mut();
+                                    headers.insert(
+                                        HeaderName::from_static(
+                                            "x-ratelimit-limit",
+                                        ),
+                                        HeaderValue::from_str(
+                                            &decision.limit.to_string(),
+                                        )
+                                        .unwrap_or(HeaderValue::from_static(
+                                            "0",
+                                        )),
+                                    );
+                                    headers.insert(
+                                        HeaderName::from_static(
+                                            "x-ratelimit-remaining",
+                                        ),
+                                        HeaderValue::from_str(
+                                            &decision
+                                                .limit
+                                                .saturating_sub(
+                                                    decision.current_count,
+                                                )
+                                                .to_string(),
+                                        )
+                                        .unwrap_or(HeaderValue::from_static(
+                                            "0",
+                                        )),
+                                    );
+                                    let reset = SystemTime::now()
+                                        .checked_add(decision.reset_after)
+                                        .unwrap_or(SystemTime::now())
+                                        .duration_since(UNIX_EPOCH)
+                                        .unwrap_or_default()
+                                        .as_secs();
+                                    headers.insert(
+                                        HeaderName::from_static(
+                                            "x-ratelimit-reset",
+                                        ),
+                                        HeaderValue::from_str(
+                                            &reset.to_string(),
+                                        )
+                                        .unwrap_or(HeaderValue::from_static(
+                                            "0",
+                                        )),
+                                    );
+                                    Ok::<_, StatusCode>(response)
                                 }
-                                if key.is_none() {
-                                    if let Some(ConnectInfo(addr)) = req.extensions().get::<ConnectInfo<SocketAddr>>() {
-                                        key = Some(RateLimitKey::IpAddress(addr.ip().to_string()));
-                                    } else if let Some(forwarded) = req.headers().get("X-Forwarded-For").and_then(|v| v.to_str().ok()).and_then(|s| s.split(',').next()) {
-                                        key = Some(RateLimitKey::IpAddress(forwarded.trim().to_string()));
-                                    }
+                                Ok(_) => {
+                                    let response = Response::builder()
+                                        .status(StatusCode::TOO_MANY_REQUESTS)
+                                        .body(Body::empty())
+                                        .unwrap();
+                                    Ok::<_, StatusCode>(response)
                                 }
-                                let key = key.unwrap_or_else(|| RateLimitKey::Custom("unknown".to_string()));
-
-                                match limiter.check_and_update(&key, &rule).await {
-                                    Ok(decision) if decision.allowed => {
-                                        let mut response = next.run(req).await;
-                                        let headers = response.headers_mut();
-                                        headers.insert(HeaderName::from_static("x-ratelimit-limit"), HeaderValue::from_str(&decision.limit.to_string()).unwrap_or(HeaderValue::from_static("0")));
-                                        headers.insert(HeaderName::from_static("x-ratelimit-remaining"), HeaderValue::from_str(&decision.limit.saturating_sub(decision.current_count).to_string()).unwrap_or(HeaderValue::from_static("0")));
-                                        let reset = SystemTime::now().checked_add(decision.reset_after).unwrap_or(SystemTime::now()).duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
-                                        headers.insert(HeaderName::from_static("x-ratelimit-reset"), HeaderValue::from_str(&reset.to_string()).unwrap_or(HeaderValue::from_static("0")));
-                                        Ok::<_, StatusCode>(response)
-                                    }
-                                    Ok(_) => {
-                                        let response = Response::builder().status(StatusCode::TOO_MANY_REQUESTS).body(Body::empty()).unwrap();
-                                        Ok::<_, StatusCode>(response)
-                                    }
-                                    Err(ferrex_core::domain::users::auth::rate_limit::RateLimitError::RateLimitExceeded { retry_after, .. }) => {
-                                        let response = Response::builder()
-                                            .status(StatusCode::TOO_MANY_REQUESTS)
-                                            .header(RETRY_AFTER, HeaderValue::from_str(&retry_after.as_secs().to_string()).unwrap_or(HeaderValue::from_static("60")))
-                                            .body(Body::empty())
-                                            .unwrap();
-                                        Ok::<_, StatusCode>(response)
+                                Err(RateLimitError::RateLimitExceeded {
+                                    retry_after,
+                                    ..
+                                }) => {
+                                    let response = Response::builder()
+                                        .status(StatusCode::TOO_MANY_REQUESTS)
+                                        .header(
+                                            RETRY_AFTER,
+                                            HeaderValue::from_str(
+                                                &retry_after
+                                                    .as_secs()
+                                                    .to_string(),
+                                            )
+                                            .unwrap_or(
+                                                HeaderValue::from_static("60"),
+                                            ),
+                                        )
+                                        .body(Body::empty())
+                                        .unwrap();
+                                    Ok::<_, StatusCode>(response)
+                                }
+                                Err(err) => {
+                                    match redis.outage_policy() {
+                                        RedisOutagePolicy::FailOpen => {
+                                            warn!(error = %err, "Rate limiter unavailable; allowing request (fail_open)");
+                                            Ok::<_, StatusCode>(
+                                                next.run(req).await,
+                                            )
+                                        }
+                                        RedisOutagePolicy::FailClosed => {
+                                            warn!(error = %err, "Rate limiter unavailable; refusing request (fail_closed)");
+                                            let response = Response::builder()
+                                                .status(StatusCode::SERVICE_UNAVAILABLE)
+                                                .header(RETRY_AFTER, HeaderValue::from_static("5"))
+                                                .body(Body::empty())
+                                                .unwrap();
+                                            Ok::<_, StatusCode>(response)
+                                        }
                                     }
-                                    Err(_) => Ok::<_, StatusCode>(next.run(req).await),
                                 }
                             }
-                        }))
-                    }
-                    Err(_) => None,
-                }
+                        }
+                    },
+                ))
             }
             _ => None,
         }
@@ -1063,18 +1659,12 @@ pub fn create_app(state: AppState, https_terminates_here: bool) -> Router {
     let cors_layer = if state.config().dev_mode {
         CorsLayer::permissive()
     } else {
-        let origins: Vec<axum::http::HeaderValue> = state
-            .config()
-            .cors
-            .allowed_origins
-            .iter()
-            .filter_map(|s| axum::http::HeaderValue::from_str(s).ok())
-            .collect();
-        let allow_origin = if origins.is_empty() {
-            AllowOrigin::any()
-        } else {
-            AllowOrigin::list(origins)
-        };
+        // Origins are checked per request so a reload can change them.
+        let live = state.live_settings().clone();
+        let credentials = state.config().cors.allow_credentials;
+        let allow_origin = AllowOrigin::predicate(move |origin, _| {
+            live.current().allows_origin(origin.as_bytes(), credentials)
+        });
 
         let methods: Vec<Method> = state
             .config()
@@ -1105,7 +1695,7 @@ pub fn create_app(state: AppState, https_terminates_here: bool) -> Router {
             .allow_methods(allow_methods)
             .allow_headers(allow_headers);
 
-        if state.config().cors.allow_credentials {
+        if credentials {
             layer = layer.allow_credentials(true);
         }
 
@@ -1113,26 +1703,14 @@ pub fn create_app(state: AppState, https_terminates_here: bool) -> Router {
     };
 
     let hsts_header_value = build_hsts_header(&state.config().security.hsts);
-    let trust_proxy_for_hsts = state.config().security.trust_proxy_headers;
+    let proxy_trust_for_hsts = proxy_trust.clone();
     let hsts_layer = axum::middleware::from_fn(
         move |req: Request<Body>, next: axum::middleware::Next| {
             let header_value = hsts_header_value.clone();
+            let is_https = proxy_trust_for_hsts.is_https(&req);
             async move {
                 use axum::http::header;
 
-                let is_https = if trust_proxy_for_hsts {
-                    req.headers()
-                        .get("x-forwarded-proto")
-                        .and_then(|v| v.to_str().ok())
-                        .map(|v| v.eq_ignore_ascii_case("https"))
-                        .unwrap_or(false)
-                } else {
-                    req.uri()
-                        .scheme()
-                        .map(|s| s.as_str().eq_ignore_ascii_case("https"))
-                        .unwrap_or(false)
-                };
-
                 let mut response: Response<Body> = next.run(req).await;
                 if is_https && let Some(value) = &header_value {
                     response.headers_mut().insert(
@@ -1150,6 +1728,7 @@ pub fn create_app(state: AppState, https_terminates_here: bool) -> Router {
     let mut app = Router::new()
         .route("/ping", get(ping_handler))
         .route("/health", get(health_handler))
+        .route("/ready", get(ready_handler))
         // Add versioned API routes
         .merge(versioned_api)
         // Add middleware layers in correct order (outer to inner):
@@ -1164,7 +1743,10 @@ pub fn create_app(state: AppState, https_terminates_here: bool) -> Router {
             state.clone(),
             move |State(app_state): State<AppState>,
                   req: Request<Body>,
-                  next: axum::middleware::Next| async move {
+                  next: axum::middleware::Next| {
+                // Check if request is HTTPS
+                let is_https = proxy_trust.is_https(&req);
+                async move {
                 use axum::http::header;
                 use std::convert::Infallible;
 
@@ -1174,22 +1756,6 @@ pub fn create_app(state: AppState, https_terminates_here: bool) -> Router {
                     return Ok::<_, Infallible>(next.run(req).await);
                 }
 
-                // Check if request is HTTPS
-                let trust_proxy = app_state.config().security.trust_proxy_headers;
-
-                let is_https = if trust_proxy {
-                    req.headers()
-                        .get("x-forwarded-proto")
-                        .and_then(|v| v.to_str().ok())
-                        .map(|v| v.eq_ignore_ascii_case("https"))
-                        .unwrap_or(false)
-                } else {
-                    req.uri()
-                        .scheme()
-                        .map(|s| s.as_str().eq_ignore_ascii_case("https"))
-                        .unwrap_or(false)
-                };
-
                 if !is_https {
                     // Build HTTPS URL
                     let uri = req.uri();
@@ -1217,6 +1783,7 @@ pub fn create_app(state: AppState, https_terminates_here: bool) -> Router {
 
                 // Pass through HTTPS requests
                 Ok::<_, Infallible>(next.run(req).await)
+                }
             },
         ))
         // 4. Rate limiting (before auth to protect auth endpoints)
@@ -1226,6 +1793,24 @@ pub fn create_app(state: AppState, https_terminates_here: bool) -> Router {
         app = app.layer(layer);
     }
 
+    // JSON and plain-text bodies only; media, images and partial
+    // responses pass through untouched.
+    if let Some(layer) = ferrex_server::infra::middleware::compression_layer(
+        &state.config().server,
+    ) {
+        app = app.layer(layer);
+    }
+
+    // Outermost, so the request span covers every layer above.
+    let request_id_header = axum::http::HeaderName::from_bytes(
+        state.config().server.request_id_header.as_bytes(),
+    )
+    .expect("request id header validated at config load");
+    app = app.layer(axum::middleware::from_fn_with_state(
+        request_id_header,
+        ferrex_server::infra::middleware::request_id_middleware,
+    ));
+
     app.with_state(state)
 }
 
@@ -1239,6 +1824,23 @@ async fn ping_handler() -> Result<Json<Value>, StatusCode> {
     })))
 }
 
+/// Readiness probe: 200 once startup has completed, 503 while starting or
+/// draining for shutdown. Unlike `/health` it never touches the database.
+async fn ready_handler(State(state): State<AppState>) -> Response<Body> {
+    let readiness = state.readiness();
+    let status = if readiness.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = Json(json!({
+        "status": readiness.phase().as_str(),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "version": env!("CARGO_PKG_VERSION")
+    }));
+    (status, body).into_response()
+}
+
 async fn health_handler(
     State(state): State<AppState>,
 ) -> Result<Json<Value>, StatusCode> {
@@ -1246,6 +1848,10 @@ async fn health_handler(
         "status": "healthy",
         "timestamp": chrono::Utc::now().to_rfc3339(),
         "version": env!("CARGO_PKG_VERSION"),
+        "maintenance_mode": state.maintenance().status(),
+        "streams": state.streams().status(),
+        "transcodes": state.transcodes().status(),
+        "metadata": metadata_status(),
         "checks": {}
     });
 
@@ -1274,12 +1880,25 @@ async fn health_handler(
         }
     }
 
-    // Check cache if available
-    if state.cache_enabled() {
-        health_status["checks"]["cache"] = json!({
-            "status": "healthy",
-            "type": "redis"
+    // Probe Redis if configured. Losing it only degrades the server under
+    // fail-open; under fail-closed the auth endpoints stop working.
+    if let Some(redis) = state.redis() {
+        let probe = redis.health().await;
+        if !probe.is_healthy() {
+            match probe.outage_policy {
+                RedisOutagePolicy::FailOpen => {
+                    health_status["status"] = json!("degraded");
+                }
+                RedisOutagePolicy::FailClosed => is_unhealthy = true,
+            }
+        }
+        health_status["checks"]["redis"] = json!(probe);
+    } else if state.config().redis.is_some() {
+        health_status["checks"]["redis"] = json!({
+            "status": "unavailable",
+            "error": "Redis was unreachable at startup"
         });
+        health_status["status"] = json!("degraded");
     }
 
     // Check disk space for cache directories
@@ -1298,6 +1917,20 @@ async fn health_handler(
     }
 }
 
+/// `full` when TMDB lookups are possible, `limited` when media is indexed
+/// by file name only because no API key is configured.
+fn metadata_status() -> Value {
+    if TmdbApiProvider::api_key_from_env().is_some() {
+        json!({ "mode": "full", "provider": "tmdb" })
+    } else {
+        json!({
+            "mode": "limited",
+            "provider": "tmdb",
+            "reason": "TMDB_API_KEY is not set"
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
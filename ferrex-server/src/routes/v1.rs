@@ -8,54 +8,89 @@ use ferrex_core::api::routes::v1::admin::MEDIA_ROOT_BROWSER;
 
 #[cfg(feature = "demo")]
 use crate::handlers::admin::demo_handlers;
-use crate::handlers::stream::stream_handlers;
+use crate::handlers::stream::{
+    hls_handlers, stream_handlers, subtitle_handlers, transcode_handlers,
+};
 use crate::handlers::users::admin_user_management;
 use crate::handlers::users::{
-    admin_handlers, auth, role_handlers, security_settings_handlers,
+    admin_handlers, auth, collections_handlers, role_handlers,
+    security_settings_handlers,
     setup::{
-        claim::{confirm_secure_claim, start_secure_claim},
+        claim::{confirm_secure_claim, secure_claim_link, start_secure_claim},
         {check_setup_status, create_initial_admin},
     },
     user_handlers, user_management, watch_status_handlers,
 };
 use crate::{
     handlers::{
-        admin::{dev_handlers, media_root},
+        admin::{
+            config_handlers, dev_handlers, filename_rules,
+            maintenance_handlers, media_root, storage_handlers,
+        },
         handle_websocket::websocket_handler,
         media::{
+            handle_credits::{
+                get_media_credits_handler, get_person_appearances_handler,
+            },
             handle_image::{
-                get_image_blob_handler, image_events_sse_handler,
-                post_image_manifest_handler,
+                get_image_blob_handler, get_image_placeholder_handler,
+                head_image_blob_handler, head_image_placeholder_handler,
+                image_events_sse_handler, post_image_manifest_handler,
             },
             handle_library::{
                 create_library_handler, delete_library_handler,
                 get_libraries_with_media_handler, get_library_handler,
                 get_library_media_handler, get_library_sorted_indices_handler,
+                get_library_stats_handler, get_media_availability_handler,
                 post_library_filtered_indices_handler, update_library_handler,
             },
+            handle_media_deletion::delete_media_handler,
+            handle_media_images::{
+                list_media_images_handler, set_primary_image_handler,
+            },
+            handle_metadata_refresh::{
+                refresh_metadata_fields_handler, refresh_metadata_handler,
+                set_tmdb_match_handler,
+            },
             handle_movie_batches::{
                 get_movie_reference_batch_bundle_handler,
                 get_movie_reference_batch_handler,
                 post_movie_reference_batch_fetch_handler,
                 post_movie_reference_batch_sync_handler,
             },
-            handle_search::query_media_handler,
+            handle_music::{
+                list_music_albums_handler, list_music_tracks_handler,
+            },
+            handle_search::{
+                query_media_handler, recently_added_handler,
+                search_media_handler,
+            },
+            handle_season::get_season_episodes_handler,
             handle_series_bundles::{
                 get_series_bundle_bundle_handler, get_series_bundle_handler,
                 post_series_bundle_fetch_handler,
                 post_series_bundle_sync_handler,
             },
+            handle_trailers::get_media_trailers_handler,
         },
         scan::handle_scan::{
-            active_scans_handler, cancel_scan_handler, latest_progress_handler,
-            media_events_sse_handler, pause_scan_handler, resume_scan_handler,
-            scan_config_handler, scan_events_handler, scan_history_handler,
-            scan_metrics_handler, scan_progress_sse_handler,
-            start_scan_handler,
+            active_scans_handler, cancel_scan_handler,
+            interrupted_scans_handler, latest_progress_handler,
+            latest_verify_report_handler, media_events_sse_handler,
+            path_scan_handler, pause_scan_handler, preview_scan_handler,
+            reconcile_library_handler, resume_interrupted_scan_handler,
+            resume_scan_handler, scan_config_handler, scan_events_handler,
+            scan_history_handler, scan_latency_handler, scan_metrics_handler,
+            scan_progress_sse_handler, set_library_watch_handler,
+            start_scan_handler, verify_library_handler,
         },
     },
     infra::{
         app_state::AppState,
+        middleware::{
+            RequestTimeouts, idempotency_middleware, maintenance_middleware,
+            request_timeout_middleware,
+        },
         scan::folder_inventory::{get_folder_inventory, get_scan_progress},
     },
 };
@@ -68,6 +103,7 @@ pub fn create_v1_router(state: AppState) -> Router<AppState> {
         .route(v1::auth::REGISTER, post(auth::handlers::register))
         .route(v1::auth::LOGIN, post(auth::handlers::login))
         .route(v1::auth::REFRESH, post(auth::handlers::refresh))
+        .route(v1::auth::SESSION, get(auth::handlers::introspect_session))
         // Device authentication endpoints
         .route(
             v1::auth::device::LOGIN,
@@ -86,20 +122,56 @@ pub fn create_v1_router(state: AppState) -> Router<AppState> {
         .route(v1::setup::CREATE_ADMIN, post(create_initial_admin))
         .route(v1::setup::CLAIM_START, post(start_secure_claim))
         .route(v1::setup::CLAIM_CONFIRM, post(confirm_secure_claim))
+        .route(v1::setup::CLAIM_LINK, get(secure_claim_link))
         .route(
             v1::stream::PLAY,
-            get(stream_handlers::stream_with_progress_handler),
+            get(stream_handlers::stream_with_progress_handler)
+                .head(stream_handlers::stream_head_handler),
+        )
+        .route(
+            v1::transcode::FILE,
+            get(transcode_handlers::transcode_file_handler),
+        )
+        .route(
+            v1::media::hls::MASTER,
+            get(hls_handlers::hls_master_handler),
+        )
+        .route(
+            v1::media::hls::VARIANT,
+            get(hls_handlers::hls_variant_handler),
+        )
+        .route(
+            v1::media::hls::SEGMENT,
+            get(hls_handlers::hls_segment_handler),
+        )
+        .route(
+            v1::media::subtitles::EMBEDDED,
+            get(subtitle_handlers::list_embedded_subtitles_handler),
+        )
+        .route(
+            v1::media::subtitles::EMBEDDED_TRACK,
+            get(subtitle_handlers::embedded_subtitle_handler),
         )
         //
+        .merge(create_browse_routes(state.clone()))
         .merge(create_libraries_routes(state.clone()))
         .merge(create_scan_routes(state.clone()))
-        .merge(create_image_routes(state.clone()))
         // Merge protected routes
         .merge(create_protected_routes(state.clone()))
         // Merge admin routes
         .merge(create_admin_routes(state.clone()))
         // Merge role routes
-        .merge(create_role_routes(state))
+        .merge(create_role_routes(state.clone()))
+        // Read-only mode gate; runs before each route's auth layer
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            maintenance_middleware,
+        ))
+        // Per-route-class time limits; streaming routes are exempt
+        .layer(middleware::from_fn_with_state(
+            RequestTimeouts::from_config(&state.config().server),
+            request_timeout_middleware,
+        ))
 }
 
 /// Create protected routes that require authentication
@@ -120,6 +192,7 @@ fn create_protected_routes(state: AppState) -> Router<AppState> {
             v1::auth::device::LIST,
             get(auth::device_handlers::list_user_devices),
         )
+        .route(v1::auth::EVENTS, get(auth::events::list_own_auth_events))
         .route(
             v1::auth::device::REVOKE,
             post(auth::device_handlers::revoke_device),
@@ -178,7 +251,12 @@ fn create_protected_routes(state: AppState) -> Router<AppState> {
         //
         .route(
             v1::watch::UPDATE_PROGRESS,
-            post(watch_status_handlers::update_progress_handler),
+            post(watch_status_handlers::update_progress_handler).layer(
+                middleware::from_fn_with_state(
+                    state.clone(),
+                    idempotency_middleware,
+                ),
+            ),
         )
         .route(
             v1::watch::STATE,
@@ -194,6 +272,41 @@ fn create_protected_routes(state: AppState) -> Router<AppState> {
                 watch_status_handlers::clear_progress_handler,
             ),
         )
+        .route(
+            v1::watch::EXPORT,
+            get(watch_status_handlers::export_watch_state_handler),
+        )
+        .route(
+            v1::watch::IMPORT,
+            post(watch_status_handlers::import_watch_state_handler),
+        )
+        // Collections
+        //
+        .route(
+            v1::collections::COLLECTION,
+            get(collections_handlers::list_collections_handler)
+                .post(collections_handlers::create_collection_handler),
+        )
+        .route(
+            v1::collections::ITEM,
+            get(collections_handlers::get_collection_handler)
+                .put(collections_handlers::update_collection_handler)
+                .delete(collections_handlers::delete_collection_handler),
+        )
+        .route(
+            v1::collections::ITEMS,
+            post(collections_handlers::add_collection_items_handler),
+        )
+        .route(
+            v1::collections::ORDER,
+            put(collections_handlers::reorder_collection_handler),
+        )
+        .route(
+            v1::collections::MEMBER,
+            axum::routing::delete(
+                collections_handlers::remove_collection_item_handler,
+            ),
+        )
         // TV identity-based watch helpers
         .route(
             v1::watch::SERIES_STATE,
@@ -207,6 +320,10 @@ fn create_protected_routes(state: AppState) -> Router<AppState> {
             v1::watch::SERIES_NEXT,
             get(watch_status_handlers::get_series_next_episode_handler),
         )
+        .route(
+            v1::watch::EPISODE_WATCHED,
+            post(watch_status_handlers::mark_episode_watched_handler),
+        )
         // Media endpoints
         //
         .route(
@@ -221,6 +338,26 @@ fn create_protected_routes(state: AppState) -> Router<AppState> {
             v1::media::item::IS_COMPLETED,
             get(watch_status_handlers::is_completed_handler),
         )
+        .route(
+            v1::media::item::AVAILABILITY,
+            get(get_media_availability_handler),
+        )
+        .route(
+            v1::media::SERIES_WATCHED,
+            post(watch_status_handlers::mark_series_watched_handler),
+        )
+        .route(
+            v1::media::SERIES_UNWATCHED,
+            post(watch_status_handlers::mark_series_unwatched_handler),
+        )
+        .route(
+            v1::media::SEASON_WATCHED,
+            post(watch_status_handlers::mark_season_watched_handler),
+        )
+        .route(
+            v1::media::SEASON_UNWATCHED,
+            post(watch_status_handlers::mark_season_unwatched_handler),
+        )
         // Folder inventory monitoring and control
         .route(v1::folders::INVENTORY, get(get_folder_inventory))
         .route(v1::folders::PROGRESS, get(get_scan_progress))
@@ -228,8 +365,6 @@ fn create_protected_routes(state: AppState) -> Router<AppState> {
         //    "/folders/rescan/{folder_id}",
         //    post(trigger_folder_rescan),
         //)
-        // Query system
-        .route(v1::media::QUERY, post(query_media_handler))
         // Scanning: pending-based triggers and counts
         //.route(
         //    "/libraries/{id}/scan/pending",
@@ -252,12 +387,25 @@ fn create_protected_routes(state: AppState) -> Router<AppState> {
         //
         .route(
             v1::stream::REPORT_PROGRESS,
-            post(stream_handlers::report_progress_handler),
+            post(stream_handlers::report_progress_handler).layer(
+                middleware::from_fn_with_state(
+                    state.clone(),
+                    idempotency_middleware,
+                ),
+            ),
         )
         .route(
             v1::stream::PLAYBACK_TICKET,
             get(stream_handlers::playback_ticket_handler),
         )
+        .route(
+            v1::transcode::START,
+            post(transcode_handlers::start_transcode_handler),
+        )
+        .route(
+            v1::transcode::STATUS,
+            get(transcode_handlers::transcode_status_handler),
+        )
         // Sync session endpoints
         // Unimplemented
         //.route(
@@ -283,24 +431,20 @@ fn create_protected_routes(state: AppState) -> Router<AppState> {
         ))
 }
 
-/// Create libraries routes
-fn create_libraries_routes(state: AppState) -> Router<AppState> {
-    Router::new()
-        //.route("/library/events/sse", get(media_events_sse_handler))
+/// Read-only library, query and image routes. They sit behind session
+/// auth unless `security.require_auth_for_browsing` is off, in which case
+/// a valid session is still picked up but not required.
+fn create_browse_routes(state: AppState) -> Router<AppState> {
+    let router = Router::new()
         .route(
             v1::libraries::COLLECTION,
-            get(get_libraries_with_media_handler).post(create_library_handler),
+            get(get_libraries_with_media_handler),
         )
         .route(v1::libraries::ITEM, get(get_library_handler))
-        .route(
-            v1::libraries::ITEM,
-            axum::routing::put(update_library_handler),
-        )
-        .route(
-            v1::libraries::ITEM,
-            axum::routing::delete(delete_library_handler),
-        )
         .route(v1::libraries::MEDIA, get(get_library_media_handler))
+        .route(v1::libraries::STATS, get(get_library_stats_handler))
+        .route(v1::libraries::music::TRACKS, get(list_music_tracks_handler))
+        .route(v1::libraries::music::ALBUMS, get(list_music_albums_handler))
         .route(
             v1::libraries::movie_batches::COLLECTION,
             get(get_movie_reference_batch_bundle_handler),
@@ -341,6 +485,59 @@ fn create_libraries_routes(state: AppState) -> Router<AppState> {
             v1::libraries::FILTERED_INDICES,
             post(post_library_filtered_indices_handler),
         )
+        // Query system
+        .route(v1::media::QUERY, post(query_media_handler))
+        .route(v1::media::RECENTLY_ADDED, get(recently_added_handler))
+        .route(v1::search::ROOT, get(search_media_handler))
+        .route(v1::media::SEASON_EPISODES, get(get_season_episodes_handler))
+        .route(v1::media::item::CREDITS, get(get_media_credits_handler))
+        .route(v1::media::item::TRAILERS, get(get_media_trailers_handler))
+        .route(v1::people::APPEARANCES, get(get_person_appearances_handler))
+        // Images
+        .route(v1::images::MANIFEST, post(post_image_manifest_handler))
+        .route(
+            v1::images::BLOB_ITEM,
+            get(get_image_blob_handler).head(head_image_blob_handler),
+        )
+        .route(
+            v1::images::PLACEHOLDER,
+            get(get_image_placeholder_handler)
+                .head(head_image_placeholder_handler),
+        )
+        .route(v1::images::EVENTS, get(image_events_sse_handler));
+
+    if state.config().security.require_auth_for_browsing {
+        router.route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::middleware::auth_middleware,
+        ))
+    } else {
+        router.route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::middleware::optional_auth_middleware,
+        ))
+    }
+}
+
+/// Library management routes
+fn create_libraries_routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        //.route("/library/events/sse", get(media_events_sse_handler))
+        .route(
+            v1::libraries::COLLECTION,
+            post(create_library_handler).layer(middleware::from_fn_with_state(
+                state.clone(),
+                idempotency_middleware,
+            )),
+        )
+        .route(
+            v1::libraries::ITEM,
+            axum::routing::put(update_library_handler),
+        )
+        .route(
+            v1::libraries::ITEM,
+            axum::routing::delete(delete_library_handler),
+        )
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
             auth::middleware::auth_middleware,
@@ -349,17 +546,38 @@ fn create_libraries_routes(state: AppState) -> Router<AppState> {
 
 fn create_scan_routes(state: AppState) -> Router<AppState> {
     Router::new()
-        .route(v1::libraries::scans::START, post(start_scan_handler))
+        // Layers on a method router run inside the auth route layer, so
+        // idempotency records are scoped to the authenticated caller.
+        .route(
+            v1::libraries::scans::START,
+            post(start_scan_handler).layer(middleware::from_fn_with_state(
+                state.clone(),
+                idempotency_middleware,
+            )),
+        )
         .route(v1::libraries::scans::PAUSE, post(pause_scan_handler))
         .route(v1::libraries::scans::RESUME, post(resume_scan_handler))
         .route(v1::libraries::scans::CANCEL, post(cancel_scan_handler))
+        .route(v1::libraries::scans::PATH, post(path_scan_handler))
+        .route(v1::libraries::scans::PREVIEW, get(preview_scan_handler))
+        .route(
+            v1::libraries::VERIFY,
+            get(latest_verify_report_handler).post(verify_library_handler),
+        )
+        .route(v1::libraries::WATCH, put(set_library_watch_handler))
         .route(v1::scan::ACTIVE, get(active_scans_handler))
         .route(v1::scan::HISTORY, get(scan_history_handler))
+        .route(v1::scan::HISTORY_LATENCY, get(scan_latency_handler))
         .route(v1::scan::PROGRESS, get(latest_progress_handler))
         .route(v1::scan::EVENTS, get(scan_events_handler))
         .route(v1::scan::PROGRESS_STREAM, get(scan_progress_sse_handler))
         .route(v1::scan::METRICS, get(scan_metrics_handler))
         .route(v1::scan::CONFIG, get(scan_config_handler))
+        .route(v1::scan::INTERRUPTED, get(interrupted_scans_handler))
+        .route(
+            v1::scan::RESUME_INTERRUPTED,
+            post(resume_interrupted_scan_handler),
+        )
         .route(v1::events::MEDIA, get(media_events_sse_handler))
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
@@ -367,17 +585,6 @@ fn create_scan_routes(state: AppState) -> Router<AppState> {
         ))
 }
 
-fn create_image_routes(state: AppState) -> Router<AppState> {
-    Router::new()
-        .route(v1::images::MANIFEST, post(post_image_manifest_handler))
-        .route(v1::images::BLOB_ITEM, get(get_image_blob_handler))
-        .route(v1::images::EVENTS, get(image_events_sse_handler))
-        .route_layer(middleware::from_fn_with_state(
-            state.clone(),
-            auth::middleware::auth_middleware,
-        ))
-}
-
 /// Create admin routes that require admin role
 fn create_admin_routes(state: AppState) -> Router<AppState> {
     let router = Router::new()
@@ -406,7 +613,57 @@ fn create_admin_routes(state: AppState) -> Router<AppState> {
             v1::admin::REVOKE_SESSION,
             axum::routing::delete(admin_handlers::revoke_user_session_admin),
         )
+        .route(
+            v1::admin::AUTH_EVENTS,
+            get(auth::events::list_auth_events_admin),
+        )
         .route(v1::admin::STATS, get(admin_handlers::get_admin_stats))
+        .route(v1::admin::STORAGE, get(storage_handlers::get_storage_usage))
+        .route(
+            v1::admin::CONFIG,
+            get(config_handlers::get_effective_config),
+        )
+        .route(
+            v1::media::COLLECTION,
+            axum::routing::delete(delete_media_handler),
+        )
+        // Reconcile deletes media rows, so it sits with the bulk delete
+        .route(v1::libraries::RECONCILE, post(reconcile_library_handler))
+        .route(
+            v1::media::item::REFRESH_METADATA,
+            post(refresh_metadata_handler),
+        )
+        .route(
+            v1::media::item::REFRESH_FIELDS,
+            post(refresh_metadata_fields_handler),
+        )
+        .route(v1::media::item::TMDB_MATCH, put(set_tmdb_match_handler))
+        .route(v1::media::item::IMAGES, get(list_media_images_handler))
+        .route(
+            v1::media::item::PRIMARY_IMAGE,
+            put(set_primary_image_handler),
+        )
+        .route(
+            v1::admin::maintenance::THEME_COLORS,
+            post(maintenance_handlers::backfill_theme_colors),
+        )
+        .route(
+            v1::admin::maintenance::MODE,
+            get(maintenance_handlers::get_maintenance_mode)
+                .put(maintenance_handlers::set_maintenance_mode),
+        )
+        .route(
+            v1::admin::maintenance::RATE_LIMIT_RESET,
+            post(maintenance_handlers::reset_rate_limits),
+        )
+        .route(
+            v1::admin::maintenance::ORPHANED_IMAGES,
+            get(maintenance_handlers::list_orphaned_images),
+        )
+        .route(
+            v1::admin::maintenance::ORPHANED_IMAGES_PURGE,
+            post(maintenance_handlers::purge_orphaned_images),
+        )
         // Development/reset endpoints (admin only)
         .route(
             v1::admin::dev::RESET_CHECK,
@@ -417,6 +674,10 @@ fn create_admin_routes(state: AppState) -> Router<AppState> {
             post(dev_handlers::reset_database),
         )
         .route(MEDIA_ROOT_BROWSER, get(media_root::browse_media_root))
+        .route(
+            v1::admin::PARSE_FILENAME,
+            get(filename_rules::preview_filename_parse),
+        )
         // Admin session management for PIN authentication
         .route(
             v1::admin::sessions::REGISTER,
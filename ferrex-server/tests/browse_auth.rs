@@ -0,0 +1,132 @@
+use anyhow::Result;
+use axum::Router;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::json;
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use uuid::Uuid;
+
+use ferrex_core::api::routes::{utils, v1};
+use ferrex_server::infra::startup::NoopStartupHooks;
+
+mod common;
+use common::build_test_app_with_config;
+
+async fn server_with_browse_auth(
+    pool: PgPool,
+    require_auth_for_browsing: bool,
+) -> Result<TestServer> {
+    let app = build_test_app_with_config(pool, &NoopStartupHooks, |config| {
+        config.security.require_auth_for_browsing = require_auth_for_browsing;
+    })
+    .await?;
+    let (router, state, _tempdir) = app.into_parts();
+    let router: Router<()> = router.with_state(state);
+    let make_service =
+        router.into_make_service_with_connect_info::<SocketAddr>();
+    TestServer::builder()
+        .http_transport()
+        .build(make_service)
+        .map_err(|err| anyhow::anyhow!(err.to_string()))
+}
+
+async fn register(server: &TestServer) -> String {
+    let response = server
+        .post(v1::auth::REGISTER)
+        .json(&json!({
+            "username": "browse_user",
+            "display_name": "Browse",
+            "password": "Password#123"
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    body["data"]["access_token"]
+        .as_str()
+        .expect("access_token present")
+        .to_string()
+}
+
+fn stream_path() -> String {
+    utils::replace_param(v1::stream::PLAY, "{id}", Uuid::now_v7().to_string())
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn browsing_requires_a_session_when_enabled(pool: PgPool) -> Result<()> {
+    let server = server_with_browse_auth(pool, true).await?;
+
+    server
+        .get(v1::libraries::COLLECTION)
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .post(v1::media::QUERY)
+        .json(&json!({}))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .get(v1::search::ROOT)
+        .add_query_param("q", "heat")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    // Setup and auth stay reachable.
+    server.get(v1::setup::STATUS).await.assert_status_ok();
+    let token = register(&server).await;
+
+    server
+        .get(v1::libraries::COLLECTION)
+        .add_header("Authorization", format!("Bearer {token}"))
+        .await
+        .assert_status_ok();
+
+    server
+        .get(&stream_path())
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn anonymous_browsing_leaves_playback_and_management_behind_auth(
+    pool: PgPool,
+) -> Result<()> {
+    let server = server_with_browse_auth(pool, false).await?;
+
+    server
+        .get(v1::libraries::COLLECTION)
+        .await
+        .assert_status_ok();
+    server
+        .get(v1::search::ROOT)
+        .add_query_param("q", "heat")
+        .await
+        .assert_status_ok();
+
+    // Playback and library management still need a session.
+    server
+        .get(&stream_path())
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .post(v1::libraries::COLLECTION)
+        .json(&json!({
+            "name": "Movies",
+            "library_type": "Movies",
+            "paths": ["/media/movies"]
+        }))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    server.get(v1::setup::STATUS).await.assert_status_ok();
+    let token = register(&server).await;
+    server
+        .get(v1::libraries::COLLECTION)
+        .add_header("Authorization", format!("Bearer {token}"))
+        .await
+        .assert_status_ok();
+
+    Ok(())
+}
@@ -16,13 +16,14 @@ use ferrex_server::{
         cache::{MovieBatchesCache, SeriesBundlesCache},
         config::{
             AuthConfig, CacheConfig, Config, ConfigMetadata, CorsConfig,
-            DatabaseConfig, FfmpegConfig, HstsSettings, MediaConfig,
-            ScannerConfig, SecurityConfig, ServerConfig,
+            DatabaseConfig, FfmpegConfig, HstsSettings, LocaleConfig,
+            MediaConfig, ScannerConfig, SecurityConfig, ServerConfig,
         },
+        idempotency::MemoryIdempotencyStore,
         orchestration::ScanOrchestrator,
         scan::scan_manager::ScanControlPlane,
         startup::StartupHooks,
-        thumbnail_service::ThumbnailService,
+        thumbnail_service::{ThumbnailService, ThumbnailSettings},
         websocket::ConnectionManager,
     },
     routes::create_api_router,
@@ -74,6 +75,17 @@ impl TestApp {
 pub async fn build_test_app_with_hooks<H: StartupHooks>(
     pool: PgPool,
     hooks: &H,
+) -> Result<TestApp> {
+    build_test_app_with_config(pool, hooks, |_| {}).await
+}
+
+/// Like [`build_test_app_with_hooks`], with `configure` applied to the
+/// test config before the app is assembled.
+#[allow(unused)]
+pub async fn build_test_app_with_config<H: StartupHooks>(
+    pool: PgPool,
+    hooks: &H,
+    configure: impl FnOnce(&mut Config),
 ) -> Result<TestApp> {
     // SAFETY: tests run in isolation and set the env var before any child threads read it.
     unsafe {
@@ -100,19 +112,50 @@ pub async fn build_test_app_with_hooks<H: StartupHooks>(
         server: ServerConfig {
             host: "127.0.0.1".into(),
             port: 0,
+            request_id_header: ferrexctl::constants::DEFAULT_REQUEST_ID_HEADER
+                .into(),
+            request_timeout: None,
+            slow_request_timeout: None,
+            max_concurrent_streams: None,
+            max_concurrent_transcodes: None,
+            stream_bandwidth_limit_kbps: None,
+            remote_media_redirect_hosts: Vec::new(),
+            compression_enabled: false,
+            compression_min_bytes: 0,
+            log_format: Default::default(),
+            log_level: None,
         },
         database: DatabaseConfig { primary_url: None },
         redis: None,
-        media: MediaConfig { root: None },
+        media: MediaConfig {
+            root: None,
+            metadata_languages: Vec::new(),
+            filename_rules: Vec::new(),
+            movie_defaults: Default::default(),
+            series_defaults: Default::default(),
+        },
+        locale: LocaleConfig::default(),
         cache: CacheConfig {
             root: cache_root.clone(),
             images: image_cache_dir.clone(),
             transcode: transcode_cache_dir.clone(),
             thumbnails: thumbnail_cache_dir.clone(),
+            hls_max_bytes: ferrexctl::constants::DEFAULT_HLS_CACHE_MAX_BYTES,
+            min_free_bytes: 0,
+            image_failure_ttl_secs:
+                ferrexctl::constants::DEFAULT_IMAGE_FAILURE_TTL_SECS,
+            encrypt_at_rest: false,
+            encryption_key: None,
+            image_store: Default::default(),
+            image_store_s3: None,
         },
         ffmpeg: FfmpegConfig {
             ffmpeg_path: "ffmpeg".into(),
             ffprobe_path: "ffprobe".into(),
+            thumbnail_strategy: Default::default(),
+            thumbnail_width: ferrexctl::constants::DEFAULT_THUMBNAIL_WIDTH,
+            thumbnail_max_height: None,
+            thumbnail_quality: ferrexctl::constants::DEFAULT_THUMBNAIL_QUALITY,
         },
         cors: CorsConfig {
             allowed_origins: vec![],
@@ -123,6 +166,8 @@ pub async fn build_test_app_with_hooks<H: StartupHooks>(
         security: SecurityConfig {
             enforce_https: false,
             trust_proxy_headers: false,
+            trusted_proxies: Vec::new(),
+            require_auth_for_browsing: true,
             hsts: HstsSettings {
                 max_age: 31_536_000,
                 include_subdomains: false,
@@ -134,12 +179,19 @@ pub async fn build_test_app_with_hooks<H: StartupHooks>(
             password_pepper: "test-pepper".into(),
             token_key: "test-token-key".into(),
             setup_token: None,
+            session_ttl: Duration::from_secs(24 * 60 * 60),
+            refresh_ttl: Duration::from_secs(30 * 24 * 60 * 60),
+            device_challenge_ttl: Duration::from_secs(120),
+            clock_skew_leeway: Duration::from_secs(5),
+            strict_device_ids: false,
         },
         scanner: ScannerConfig::default(),
         rate_limiter: None,
         metadata: ConfigMetadata::default(),
     };
 
+    configure(&mut config);
+
     config
         .ensure_directories()
         .context("failed to prepare cache directories for test config")?;
@@ -163,11 +215,15 @@ pub async fn build_test_app_with_hooks<H: StartupHooks>(
         ThumbnailService::new(
             cache_root.clone(),
             unit_of_work.media_files_read.clone(),
+            ThumbnailSettings::from(&config.ffmpeg),
         )
         .context("failed to construct thumbnail service")?,
     );
 
-    let tmdb_provider = Arc::new(TmdbApiProvider::new());
+    let tmdb_provider = Arc::new(
+        TmdbApiProvider::new()
+            .with_preferred_languages(config.media.metadata_languages.clone()),
+    );
 
     let queue_service: Arc<PostgresQueueService> = Arc::new(
         PostgresQueueService::new(pool.clone())
@@ -183,6 +239,7 @@ pub async fn build_test_app_with_hooks<H: StartupHooks>(
         ScanOrchestrator::new(
             orchestrator_config,
             tmdb_provider.clone(),
+            Vec::new(),
             image_service.clone(),
             unit_of_work.clone(),
             queue_service.clone(),
@@ -262,21 +319,24 @@ pub async fn build_test_app_with_hooks<H: StartupHooks>(
     let config_arc = Arc::new(config);
     let websocket_manager = Arc::new(ConnectionManager::new());
     let admin_sessions = Arc::new(Mutex::new(HashMap::new()));
-    let app_context = Arc::new(AppContext::new(
-        Arc::clone(&config_arc),
-        unit_of_work.clone(),
-        postgres.clone(),
-        scan_control.clone(),
-        thumbnail_service.clone(),
-        image_service.clone(),
-        Arc::clone(&websocket_manager),
-        Arc::clone(&auth_facade),
-        auth_crypto.clone(),
-        setup_claim_service.clone(),
-        false,
-        #[cfg(feature = "demo")]
-        None,
-    ));
+    let app_context = Arc::new(
+        AppContext::new(
+            Arc::clone(&config_arc),
+            unit_of_work.clone(),
+            postgres.clone(),
+            scan_control.clone(),
+            thumbnail_service.clone(),
+            image_service.clone(),
+            Arc::clone(&websocket_manager),
+            Arc::clone(&auth_facade),
+            auth_crypto.clone(),
+            setup_claim_service.clone(),
+            None,
+            #[cfg(feature = "demo")]
+            None,
+        )
+        .with_idempotency_store(Arc::new(MemoryIdempotencyStore::new())),
+    );
 
     let state = AppState::new(
         app_context,
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use axum::Router;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use std::net::SocketAddr;
+
+use ferrex_core::api::routes::{utils, v1};
+use ferrex_server::infra::startup::NoopStartupHooks;
+
+mod common;
+use common::build_test_app_with_hooks;
+
+async fn server(pool: PgPool) -> Result<TestServer> {
+    let app = build_test_app_with_hooks(pool, &NoopStartupHooks).await?;
+    let (router, state, _tempdir) = app.into_parts();
+    let router: Router<()> = router.with_state(state);
+    let make_service =
+        router.into_make_service_with_connect_info::<SocketAddr>();
+    TestServer::builder()
+        .http_transport()
+        .build(make_service)
+        .map_err(|err| anyhow::anyhow!(err.to_string()))
+}
+
+async fn register(server: &TestServer) -> String {
+    let response = server
+        .post(v1::auth::REGISTER)
+        .json(&json!({
+            "username": "retry_user",
+            "display_name": "Retry",
+            "password": "Password#123"
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: Value = response.json();
+    format!(
+        "Bearer {}",
+        body["data"]["access_token"]
+            .as_str()
+            .expect("access_token present")
+    )
+}
+
+async fn create_library(
+    server: &TestServer,
+    auth: &str,
+    root: &std::path::Path,
+    key: &str,
+) -> Value {
+    let response = server
+        .post(v1::libraries::COLLECTION)
+        .add_header("Authorization", auth)
+        .add_header("Idempotency-Key", key)
+        .json(&json!({
+            "name": "Movies",
+            "library_type": "Movies",
+            "paths": [root.display().to_string()],
+            "start_scan": false
+        }))
+        .await;
+    response.assert_status_ok();
+    response.json()
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn double_submitted_scan_start_runs_once(pool: PgPool) -> Result<()> {
+    let server = server(pool).await?;
+    let auth = register(&server).await;
+    let media = tempfile::tempdir()?;
+    std::fs::write(media.path().join("Heat (1995).mkv"), b"")?;
+
+    let library = create_library(&server, &auth, media.path(), "lib-1").await;
+    let library_id = library["data"].as_str().expect("library id").to_string();
+    let start = utils::replace_param(
+        v1::libraries::scans::START,
+        "{id}",
+        library_id.as_str(),
+    );
+
+    let first = server
+        .post(&start)
+        .add_header("Authorization", &auth)
+        .add_header("Idempotency-Key", "retry-1")
+        .json(&json!({}))
+        .await;
+    first.assert_status(StatusCode::ACCEPTED);
+    assert!(first.headers().get("Idempotent-Replayed").is_none());
+
+    let retry = server
+        .post(&start)
+        .add_header("Authorization", &auth)
+        .add_header("Idempotency-Key", "retry-1")
+        .json(&json!({}))
+        .await;
+    retry.assert_status(StatusCode::ACCEPTED);
+    assert_eq!(retry.header("Idempotent-Replayed"), "true");
+    let first: Value = first.json();
+    let retry: Value = retry.json();
+    assert_eq!(retry["data"]["scan_id"], first["data"]["scan_id"]);
+
+    // Reusing the key with another body is refused rather than replayed.
+    server
+        .post(&start)
+        .add_header("Authorization", &auth)
+        .add_header("Idempotency-Key", "retry-1")
+        .json(&json!({ "correlation_id": uuid::Uuid::now_v7() }))
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+
+    // The same key on another route is a separate record.
+    let second = create_library(&server, &auth, media.path(), "retry-1").await;
+    assert_ne!(second["data"], library["data"]);
+    let replayed = create_library(&server, &auth, media.path(), "lib-1").await;
+    assert_eq!(replayed["data"], library["data"]);
+
+    Ok(())
+}
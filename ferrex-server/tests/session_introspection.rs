@@ -0,0 +1,89 @@
+use anyhow::Result;
+use axum::Router;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use ferrex_core::api::routes::v1;
+use ferrex_server::infra::startup::NoopStartupHooks;
+use serde_json::json;
+use sqlx::PgPool;
+use std::net::SocketAddr;
+
+mod common;
+use common::build_test_app_with_hooks;
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn session_introspection_reports_lifetime_and_hides_revocation(
+    pool: PgPool,
+) -> Result<()> {
+    let app = build_test_app_with_hooks(pool, &NoopStartupHooks).await?;
+    let (router, state, _tempdir) = app.into_parts();
+    let router: Router<()> = router.with_state(state.clone());
+    let make_service =
+        router.into_make_service_with_connect_info::<SocketAddr>();
+    let server = TestServer::builder()
+        .http_transport()
+        .build(make_service)
+        .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+
+    let response = server
+        .post(v1::auth::REGISTER)
+        .json(&json!({
+            "username": "introspect_user",
+            "display_name": "Introspect",
+            "password": "Password#123"
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let access_token = body["data"]["access_token"]
+        .as_str()
+        .expect("access_token present")
+        .to_string();
+    let user_id = body["data"]["user_id"]
+        .as_str()
+        .expect("user_id present")
+        .to_string();
+
+    let introspect = server
+        .get(v1::auth::SESSION)
+        .add_header("Authorization", bearer(&access_token))
+        .await;
+    introspect.assert_status_ok();
+    let session: serde_json::Value = introspect.json();
+    assert_eq!(session["data"]["user_id"], json!(user_id));
+    assert_eq!(session["data"]["refresh_recommended"], json!(false));
+    assert!(session["data"]["expires_in"].as_u64().unwrap_or(0) > 0);
+
+    let unknown = server
+        .get(v1::auth::SESSION)
+        .add_header("Authorization", bearer("not-a-real-token"))
+        .await;
+    unknown.assert_status(StatusCode::UNAUTHORIZED);
+    let unknown_body: serde_json::Value = unknown.json();
+
+    let logout = server
+        .post(v1::auth::LOGOUT)
+        .add_header("Authorization", bearer(&access_token))
+        .await;
+    logout.assert_status(StatusCode::NO_CONTENT);
+
+    let revoked = server
+        .get(v1::auth::SESSION)
+        .add_header("Authorization", bearer(&access_token))
+        .await;
+    revoked.assert_status(StatusCode::UNAUTHORIZED);
+    let revoked_body: serde_json::Value = revoked.json();
+    assert_eq!(
+        revoked_body, unknown_body,
+        "revoked and unknown tokens must be indistinguishable"
+    );
+
+    let missing = server.get(v1::auth::SESSION).await;
+    missing.assert_status(StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
@@ -141,3 +141,72 @@ async fn claim_reset_revokes_pending_codes(pool: PgPool) -> Result<()> {
 
     Ok(())
 }
+
+#[sqlx::test(migrator = "ferrex_core::MIGRATOR")]
+async fn claim_link_confirms_pending_claim(pool: PgPool) -> Result<()> {
+    reset_claim_rate_limiter_for_tests().await;
+    let app = build_test_app_with_hooks(pool, &NoopStartupHooks).await?;
+    let (router, state, _tempdir) = app.into_parts();
+    let router: Router<()> = router.with_state(state.clone());
+    let make_service =
+        router.into_make_service_with_connect_info::<SocketAddr>();
+    let server = TestServer::builder()
+        .http_transport()
+        .build(make_service)
+        .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+
+    let start = server
+        .post(v1::setup::CLAIM_START)
+        .json(&json!({
+            "device_name": "Living Room",
+            "server_url": "http://ferrex.lan:3000"
+        }))
+        .await;
+    start.assert_status_ok();
+    let start_body: serde_json::Value = start.json();
+    let claim_code = start_body["data"]["claim_code"]
+        .as_str()
+        .expect("claim code present")
+        .to_string();
+    assert!(
+        start_body["data"]["claim_link"]
+            .as_str()
+            .expect("claim link present")
+            .starts_with("ferrex://setup/claim?")
+    );
+
+    let link = server
+        .get(v1::setup::CLAIM_LINK)
+        .add_query_param("claim_code", &claim_code)
+        .add_query_param("server_url", "http://ferrex.lan:3000")
+        .await;
+    link.assert_status_ok();
+    let link_body: serde_json::Value = link.json();
+    assert_eq!(link_body["data"]["server_url"], "http://ferrex.lan:3000");
+    assert!(
+        !link_body["data"]["qr_png"]
+            .as_str()
+            .expect("qr code present")
+            .is_empty()
+    );
+    let claim_link = link_body["data"]["claim_link"]
+        .as_str()
+        .expect("claim link present")
+        .to_string();
+
+    let confirm = server
+        .post(v1::setup::CLAIM_CONFIRM)
+        .json(&json!({"claim_link": claim_link}))
+        .await;
+    confirm.assert_status_ok();
+    let confirm_body: serde_json::Value = confirm.json();
+    assert!(confirm_body["data"]["claim_token"].is_string());
+
+    let reuse = server
+        .post(v1::setup::CLAIM_CONFIRM)
+        .json(&json!({"claim_link": claim_link}))
+        .await;
+    reuse.assert_status(StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -21,6 +21,7 @@ use ferrexctl::{
         write_env_atomically,
     },
     runner::{self, Runner, RunnerChoice},
+    schema::config_schema,
 };
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -71,6 +72,8 @@ enum Command {
         #[arg(long)]
         tls_key: Option<PathBuf>,
     },
+    /// Print every configuration key with its type, default and flags as JSON
+    Schema,
     /// Show FERREX_SETUP_TOKEN from an env file
     ShowToken {
         #[arg(long, default_value = ".env")]
@@ -628,6 +631,9 @@ async fn main() -> Result<()> {
             };
             cli::run_config_check(&opts).await?;
         }
+        Command::Schema => {
+            println!("{}", serde_json::to_string_pretty(&config_schema())?);
+        }
         Command::ShowToken { env_file } => {
             match load_env_value(&env_file, "FERREX_SETUP_TOKEN")? {
                 Some(token) if !token.trim().is_empty() => println!("{token}"),
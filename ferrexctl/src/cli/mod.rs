@@ -760,6 +760,7 @@ pub async fn run_config_check(opts: &CheckOptions) -> Result<()> {
     let loader = ConfigLoader::with_options(ConfigLoaderOptions {
         config_path: None,
         env_file: opts.env_file.clone(),
+        config_dir: None,
     });
 
     let ConfigLoad { config, warnings } = loader.load()?;
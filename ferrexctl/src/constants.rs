@@ -2,6 +2,86 @@
 
 pub const DEFAULT_PASSWORD_PEPPER: &str = "change-me-password-pepper";
 pub const DEFAULT_TOKEN_KEY: &str = "change-me-hmac-key";
+pub const DEFAULT_SERVER_HOST: &str = "0.0.0.0";
+pub const DEFAULT_SERVER_PORT: u16 = 3000;
+/// Port assumed when `DATABASE_URL` is assembled from its parts.
+pub const DEFAULT_DATABASE_PORT: u16 = 5432;
+pub const DEFAULT_CACHE_DIR: &str = "./cache";
+pub const DEFAULT_FFMPEG_PATH: &str = "ffmpeg";
+pub const DEFAULT_FFPROBE_PATH: &str = "ffprobe";
+/// Default width of extracted video thumbnails.
+pub const DEFAULT_THUMBNAIL_WIDTH: u32 = 320;
+/// Default JPEG quality of generated thumbnails.
+pub const DEFAULT_THUMBNAIL_QUALITY: u8 = 85;
+/// Deployment language used when `DEFAULT_LANGUAGE` is not set.
+pub const DEFAULT_LOCALE_LANGUAGE: &str = "en";
+/// Default `Strict-Transport-Security` max-age (one year).
+pub const DEFAULT_HSTS_MAX_AGE_SECS: u64 = 31_536_000;
+/// Default lifetime of an access (session) token (24 hours).
+pub const DEFAULT_SESSION_TTL_SECS: u64 = 24 * 60 * 60;
+/// Default lifetime of a refresh token (30 days).
+pub const DEFAULT_REFRESH_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+/// Default lifetime of a device PIN challenge nonce (2 minutes).
+pub const DEFAULT_DEVICE_CHALLENGE_TTL_SECS: u64 = 120;
+/// Default allowance for client clock drift when checking token expiry.
+pub const DEFAULT_CLOCK_SKEW_LEEWAY_SECS: u64 = 5;
+/// Default header carrying a request's correlation id, inbound and outbound.
+pub const DEFAULT_REQUEST_ID_HEADER: &str = "x-request-id";
+/// Default time limit for an ordinary API request (30 seconds).
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Default time limit for admin, scan and other heavy requests (5 minutes).
+pub const DEFAULT_SLOW_REQUEST_TIMEOUT_SECS: u64 = 5 * 60;
+/// Default cap on simultaneous direct-play streams.
+pub const DEFAULT_MAX_CONCURRENT_STREAMS: usize = 64;
+/// Default cap on simultaneous live transcodes for incompatible clients.
+pub const DEFAULT_MAX_CONCURRENT_TRANSCODES: usize = 4;
+/// Smallest JSON/text response body compressed by default.
+pub const DEFAULT_COMPRESSION_MIN_BYTES: u16 = 1024;
+/// Default number of multiplexed Redis connections.
+pub const DEFAULT_REDIS_POOL_SIZE: usize = 4;
+/// Largest accepted `REDIS_POOL_SIZE`.
+pub const MAX_REDIS_POOL_SIZE: usize = 64;
+/// Default time allowed for establishing a Redis connection (5 seconds).
+pub const DEFAULT_REDIS_CONNECT_TIMEOUT_SECS: u64 = 5;
+/// Default time allowed for a single Redis command (500 milliseconds).
+pub const DEFAULT_REDIS_COMMAND_TIMEOUT_MS: u64 = 500;
+/// Sort fields accepted as a library type's default listing order.
+pub const LIBRARY_SORT_FIELDS: &[&str] = &[
+    "title",
+    "date_added",
+    "created_at",
+    "release_date",
+    "rating",
+    "popularity",
+    "runtime",
+    "file_size",
+    "resolution",
+    "bitrate",
+];
+/// Watch-status filters accepted as a library type's default listing filter.
+pub const LIBRARY_LISTING_FILTERS: &[&str] =
+    &["unwatched", "in_progress", "completed"];
+/// Named groups a filename rule may capture.
+pub const FILENAME_RULE_GROUPS: &[&str] =
+    &["show", "season", "episode", "title", "year"];
+/// Secrets whose value may instead be read from the file named by
+/// `<KEY>_FILE`.
+pub const SECRET_FILE_KEYS: &[&str] = &[
+    "AUTH_PASSWORD_PEPPER",
+    "AUTH_TOKEN_KEY",
+    "FERREX_SETUP_TOKEN",
+    "DATABASE_URL",
+    "DATABASE_PASSWORD",
+    "REDIS_URL",
+    "CACHE_ENCRYPTION_KEY",
+];
+/// Default bound for the on-demand HLS segment cache (8 GiB).
+pub const DEFAULT_HLS_CACHE_MAX_BYTES: u64 = 8 * 1024 * 1024 * 1024;
+/// Default free space kept on the cache volume (1 GiB).
+pub const DEFAULT_CACHE_MIN_FREE_BYTES: u64 = 1024 * 1024 * 1024;
+/// Default time an image that failed to download permanently is reported
+/// unavailable before another attempt (6 hours).
+pub const DEFAULT_IMAGE_FAILURE_TTL_SECS: u64 = 6 * 60 * 60;
 
 /// Keys the init tool owns and is allowed to overwrite.
 pub const MANAGED_KEYS: &[&str] = &[
@@ -13,21 +13,29 @@ pub mod loader;
 pub mod models;
 pub mod packaging_config;
 pub mod runner;
+pub mod schema;
 pub mod util;
 pub mod validation;
 
 pub use loader::{ConfigLoad, ConfigLoader, error::ConfigLoadError};
+pub use models::locale::LocaleConfig;
 pub use models::rate_limits::{
-    RateLimitSource, RateLimitSpec, RateLimiterConfig,
+    RateLimitAllowlist, RateLimitSource, RateLimitSpec, RateLimiterConfig,
 };
 pub use models::scanner::{ScannerConfig, ScannerConfigSource};
+pub use models::trusted_proxies::IpRange;
 pub use models::{
     AuthConfig, CacheConfig, Config, ConfigMetadata, CorsConfig,
-    DatabaseConfig, FfmpegConfig, HstsLayerConfig, HstsSettings, MediaConfig,
-    RateLimiterSettings, RedisConfig, SecurityConfig, ServerConfig,
+    DatabaseConfig, FfmpegConfig, HstsLayerConfig, HstsSettings,
+    ImageStoreBackend, LibraryListingDefaults, LogFormat, MediaConfig,
+    RateLimiterSettings, RedisConfig, RedisOutagePolicy, S3StoreConfig,
+    SecurityConfig, ServerConfig,
 };
 pub use packaging_config::{
     FlatpakConfig, PackagingConfig, PackagingConfigError, PreflightConfig,
     ReleaseConfig, VersionConfig, VersionSource,
 };
+pub use schema::{
+    ConfigKeySpec, KeySource, ValueSource, config_schema, value_sources,
+};
 pub use validation::{ConfigGuardRailError, ConfigWarning, ConfigWarnings};
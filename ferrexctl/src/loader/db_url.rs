@@ -4,6 +4,7 @@ use url::Url;
 
 use crate::{
     Config, ConfigLoadError,
+    constants::DEFAULT_DATABASE_PORT,
     models::sources::{EnvConfig, FileDatabaseConfig},
 };
 
@@ -67,7 +68,7 @@ pub fn resolve_database_url(
         .filter(|value| !value.trim().is_empty());
 
     if let (Some(host), Some(user), Some(name)) = (host, user, name) {
-        let port = env.database_port.unwrap_or(5432);
+        let port = env.database_port.unwrap_or(DEFAULT_DATABASE_PORT);
         let mut url = Url::parse(&format!("postgresql://{host}:{port}/{name}"))
             .map_err(|source| ConfigLoadError::InvalidDatabaseUrl { source })?;
         url.set_username(&user).map_err(|_| {
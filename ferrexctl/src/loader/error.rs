@@ -20,6 +20,29 @@ pub enum ConfigLoadError {
         #[source]
         source: std::io::Error,
     },
+    #[error("{key}_FILE points at {path}, which could not be read")]
+    SecretFileUnreadable {
+        key: String,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{key}_FILE points at {path}, which is empty")]
+    EmptySecretFile { key: String, path: PathBuf },
+    #[error("failed to read config directory {path}")]
+    ConfigDirIo {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("invalid config fragment {path}: {reason}")]
+    InvalidFragment { path: PathBuf, reason: String },
+    #[error("invalid LOG_FORMAT: {0}")]
+    InvalidLogFormat(String),
+    #[error("invalid locale: {0}")]
+    InvalidLocale(String),
+    #[error("invalid image store configuration: {0}")]
+    InvalidImageStore(String),
     #[error("failed to load scanner configuration: {0}")]
     Scanner(#[source] anyhow::Error),
     #[error("failed to load rate limiter configuration: {0}")]
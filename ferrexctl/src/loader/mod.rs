@@ -1,27 +1,55 @@
 pub mod db_url;
+pub mod overlay;
+pub mod secret_files;
 
 use super::{
     models::{
         AuthConfig, CacheConfig, Config, ConfigMetadata, CorsConfig,
-        DatabaseConfig, FfmpegConfig, HstsSettings, MediaConfig,
-        RateLimiterSettings, RedisConfig, SecurityConfig, ServerConfig,
+        DatabaseConfig, FfmpegConfig, HstsSettings, ImageStoreBackend,
+        LibraryListingDefaults, MediaConfig, RateLimiterSettings, RedisConfig,
+        S3StoreConfig, SecurityConfig, ServerConfig,
+        locale::{self, LocaleConfig},
         scanner::ScannerConfig,
         sources::{EnvConfig, FileConfig, FileDatabaseConfig},
+        trusted_proxies::default_trusted_proxies,
     },
     validation::{self, ConfigWarnings},
 };
 use crate::{
-    constants::{DEFAULT_PASSWORD_PEPPER, DEFAULT_TOKEN_KEY},
-    loader::db_url::resolve_database_url,
+    constants::{
+        DEFAULT_CACHE_DIR, DEFAULT_CACHE_MIN_FREE_BYTES,
+        DEFAULT_CLOCK_SKEW_LEEWAY_SECS, DEFAULT_COMPRESSION_MIN_BYTES,
+        DEFAULT_DEVICE_CHALLENGE_TTL_SECS, DEFAULT_FFMPEG_PATH,
+        DEFAULT_FFPROBE_PATH, DEFAULT_HLS_CACHE_MAX_BYTES,
+        DEFAULT_HSTS_MAX_AGE_SECS, DEFAULT_IMAGE_FAILURE_TTL_SECS,
+        DEFAULT_LOCALE_LANGUAGE, DEFAULT_MAX_CONCURRENT_STREAMS,
+        DEFAULT_MAX_CONCURRENT_TRANSCODES, DEFAULT_PASSWORD_PEPPER,
+        DEFAULT_REDIS_COMMAND_TIMEOUT_MS, DEFAULT_REDIS_CONNECT_TIMEOUT_SECS,
+        DEFAULT_REDIS_POOL_SIZE, DEFAULT_REFRESH_TTL_SECS,
+        DEFAULT_REQUEST_ID_HEADER, DEFAULT_REQUEST_TIMEOUT_SECS,
+        DEFAULT_SERVER_HOST, DEFAULT_SERVER_PORT, DEFAULT_SESSION_TTL_SECS,
+        DEFAULT_SLOW_REQUEST_TIMEOUT_SECS, DEFAULT_THUMBNAIL_QUALITY,
+        DEFAULT_THUMBNAIL_WIDTH, DEFAULT_TOKEN_KEY,
+    },
+    loader::{
+        db_url::resolve_database_url,
+        overlay::ConfigOverlay,
+        secret_files::{resolve_secret_files, resolve_secret_files_with},
+    },
 };
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tracing::error;
 
 #[derive(Debug, Default, Clone)]
 pub struct ConfigLoaderOptions {
     pub config_path: Option<PathBuf>,
     pub env_file: Option<PathBuf>,
+    /// Directory of ordered `*.env` / `*.toml` overlay fragments. Falls back
+    /// to `$FERREX_CONFIG_DIR`; without either, no overlay is applied.
+    pub config_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Default)]
@@ -43,6 +71,11 @@ impl ConfigLoader {
         self
     }
 
+    pub fn with_config_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.options.config_dir = Some(dir.into());
+        self
+    }
+
     pub fn load(&self) -> Result<ConfigLoad, error::ConfigLoadError> {
         // Load .env with the following precedence:
         // 1) Explicit file path provided via options.env_file
@@ -78,17 +111,142 @@ impl ConfigLoader {
             })?
         };
 
-        let env_config = EnvConfig::gather();
+        // Overlay fragments are applied after .env so that both the env file
+        // and the inherited process environment take precedence over them.
+        let config_dir = self.config_dir();
+        let mut warnings = ConfigWarnings::default();
+        let fragment_sources = match &config_dir {
+            Some(dir) => {
+                let overlay = ConfigOverlay::load(dir)?;
+                let applied = overlay.apply();
+                overlay.conflict_warnings(&applied, &mut warnings);
+                applied
+            }
+            None => BTreeMap::new(),
+        };
+
+        let mut env_config = EnvConfig::gather();
+        let secret_sources =
+            resolve_secret_files(&mut env_config, &mut warnings)?;
+
+        self.finish(
+            env_config,
+            env_file_loaded,
+            config_dir,
+            fragment_sources,
+            secret_sources,
+            warnings,
+        )
+    }
+
+    /// Load the configuration again for a running server whose current
+    /// configuration is `active`.
+    ///
+    /// Unlike [`Self::load`], a discovered `.env` replaces the values it set
+    /// the first time, and overlay fragments replace the values they filled
+    /// in, so edits to either are picked up. Both are read into a copy of
+    /// the process environment; the environment itself is left alone since
+    /// the server's other threads may be reading it.
+    pub fn reload(
+        &self,
+        active: &ConfigMetadata,
+    ) -> Result<ConfigLoad, error::ConfigLoadError> {
+        let mut vars: BTreeMap<String, String> = std::env::vars_os()
+            .filter_map(|(key, value)| {
+                Some((key.into_string().ok()?, value.into_string().ok()?))
+            })
+            .collect();
+        // Fragments filled these in at startup; let them do so again.
+        for key in active.fragment_sources.keys() {
+            vars.remove(key);
+        }
+
+        let env_file_loaded = match self.read_env_file()? {
+            Some(entries) => {
+                vars.extend(entries);
+                true
+            }
+            None => false,
+        };
+
+        let config_dir = self.config_dir();
+        let mut warnings = ConfigWarnings::default();
+        let fragment_sources = match &config_dir {
+            Some(dir) => {
+                let overlay = ConfigOverlay::load(dir)?;
+                let applied = overlay.apply_to(&mut vars);
+                overlay.conflict_warnings(&applied, &mut warnings);
+                applied
+            }
+            None => BTreeMap::new(),
+        };
+
+        let lookup = |key: &str| vars.get(key).cloned();
+        let mut env_config = EnvConfig::gather_from(lookup);
+        let secret_sources =
+            resolve_secret_files_with(&mut env_config, &mut warnings, lookup)?;
+
+        self.finish(
+            env_config,
+            env_file_loaded,
+            config_dir,
+            fragment_sources,
+            secret_sources,
+            warnings,
+        )
+    }
+
+    /// Entries of the env file [`Self::load`] applies, in the same order of
+    /// precedence, without applying them. `None` when there is no such file.
+    fn read_env_file(
+        &self,
+    ) -> Result<Option<Vec<(String, String)>>, error::ConfigLoadError> {
+        let entries = if let Some(path) = &self.options.env_file {
+            dotenvy::from_path_iter(path)
+        } else if let Ok(path) = std::env::var("FERREX_ENV_FILE") {
+            dotenvy::from_path_iter(Path::new(&path)).inspect_err(|err| {
+                if let dotenvy::Error::Io(e) = err {
+                    error!("{}", e);
+                }
+            })
+        } else {
+            dotenvy::dotenv_iter()
+        };
+        match entries {
+            Ok(entries) => Ok(Some(entries.collect::<Result<_, _>>()?)),
+            Err(dotenvy::Error::Io(_)) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn config_dir(&self) -> Option<PathBuf> {
+        self.options.config_dir.clone().or_else(|| {
+            std::env::var_os("FERREX_CONFIG_DIR").map(PathBuf::from)
+        })
+    }
 
+    fn finish(
+        &self,
+        env_config: EnvConfig,
+        env_file_loaded: bool,
+        config_dir: Option<PathBuf>,
+        fragment_sources: BTreeMap<String, PathBuf>,
+        secret_sources: BTreeMap<String, PathBuf>,
+        mut warnings: ConfigWarnings,
+    ) -> Result<ConfigLoad, error::ConfigLoadError> {
         let (file_config, config_path, config_present) = (None, None, false);
 
-        let (config, warnings) = self.compose_config(
+        let (mut config, mut compose_warnings) = self.compose_config(
             file_config,
             env_config,
             config_path.clone(),
             env_file_loaded,
             config_present,
         )?;
+        config.metadata.config_dir = config_dir;
+        config.metadata.fragment_sources = fragment_sources;
+        config.metadata.secret_files = secret_sources;
+        warnings.items.append(&mut compose_warnings.items);
 
         Ok(ConfigLoad { config, warnings })
     }
@@ -123,6 +281,7 @@ impl ConfigLoader {
             database: file_database,
             redis: file_redis,
             media: file_media,
+            locale: file_locale,
             cache: file_cache,
             ffmpeg: file_ffmpeg,
             cors: file_cors,
@@ -133,39 +292,188 @@ impl ConfigLoader {
             dev_mode: file_dev_mode,
         } = file;
         let file_media_root = file_media.root;
+        let file_metadata_languages = file_media.metadata_languages;
+        let filename_rules = file_media.filename_rules.unwrap_or_default();
+        let file_movie_defaults = file_media.movie_defaults.unwrap_or_default();
+        let file_series_defaults =
+            file_media.series_defaults.unwrap_or_default();
 
         let env = env.clone();
 
+        let log_format = match env.log_format.as_deref() {
+            Some(raw) => raw
+                .parse()
+                .map_err(error::ConfigLoadError::InvalidLogFormat)?,
+            None => file_server.log_format.unwrap_or_default(),
+        };
         let server = ServerConfig {
             host: env
                 .server_host
                 .clone()
                 .or(file_server.host.clone())
-                .unwrap_or_else(|| "0.0.0.0".to_string()),
-            port: env.server_port.or(file_server.port).unwrap_or(3000),
+                .unwrap_or_else(|| DEFAULT_SERVER_HOST.to_string()),
+            port: env
+                .server_port
+                .or(file_server.port)
+                .unwrap_or(DEFAULT_SERVER_PORT),
+            request_id_header: env
+                .request_id_header
+                .clone()
+                .or(file_server.request_id_header.clone())
+                .map(|name| name.trim().to_ascii_lowercase())
+                .unwrap_or_else(|| DEFAULT_REQUEST_ID_HEADER.to_string()),
+            request_timeout: request_timeout(
+                env.request_timeout_secs
+                    .or(file_server.request_timeout_secs)
+                    .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS),
+            ),
+            slow_request_timeout: request_timeout(
+                env.slow_request_timeout_secs
+                    .or(file_server.slow_request_timeout_secs)
+                    .unwrap_or(DEFAULT_SLOW_REQUEST_TIMEOUT_SECS),
+            ),
+            max_concurrent_streams: Some(
+                env.max_concurrent_streams
+                    .or(file_server.max_concurrent_streams)
+                    .unwrap_or(DEFAULT_MAX_CONCURRENT_STREAMS),
+            )
+            .filter(|limit| *limit > 0),
+            max_concurrent_transcodes: Some(
+                env.max_concurrent_transcodes
+                    .or(file_server.max_concurrent_transcodes)
+                    .unwrap_or(DEFAULT_MAX_CONCURRENT_TRANSCODES),
+            )
+            .filter(|limit| *limit > 0),
+            stream_bandwidth_limit_kbps: env
+                .stream_bandwidth_limit_kbps
+                .or(file_server.stream_bandwidth_limit_kbps)
+                .filter(|limit| *limit > 0),
+            remote_media_redirect_hosts: env
+                .remote_media_redirect_hosts
+                .clone()
+                .or(file_server.remote_media_redirect_hosts.clone())
+                .unwrap_or_default(),
+            compression_enabled: env
+                .compression_enabled
+                .or(file_server.compression_enabled)
+                .unwrap_or(true),
+            compression_min_bytes: env
+                .compression_min_bytes
+                .or(file_server.compression_min_bytes)
+                .unwrap_or(DEFAULT_COMPRESSION_MIN_BYTES),
+            log_format,
+            log_level: env
+                .log_level
+                .clone()
+                .or(file_server.log_level.clone())
+                .map(|level| level.trim().to_string())
+                .filter(|level| !level.is_empty()),
         };
 
         let database = DatabaseConfig {
             primary_url: self.database_url(&env, &file_database)?,
         };
 
-        let redis = env
-            .redis_url
-            .clone()
-            .map(|url| RedisConfig { url })
-            .or_else(|| file_redis.map(|r| RedisConfig { url: r.url }));
+        let redis = match (env.redis_url.clone(), file_redis) {
+            (None, None) => None,
+            (url, file_redis) => {
+                let file_redis = file_redis.as_ref();
+                let outage_policy = match env.redis_outage_policy.as_deref() {
+                    Some(raw) => raw.parse().unwrap_or_else(|err| {
+                        warnings.push_with_hint(
+                            format!("ignoring REDIS_OUTAGE_POLICY: {err}"),
+                            "falling back to the config file or fail_open",
+                        );
+                        file_redis
+                            .and_then(|r| r.outage_policy)
+                            .unwrap_or_default()
+                    }),
+                    None => file_redis
+                        .and_then(|r| r.outage_policy)
+                        .unwrap_or_default(),
+                };
+                Some(RedisConfig {
+                    url: url
+                        .or_else(|| file_redis.map(|r| r.url.clone()))
+                        .unwrap_or_default(),
+                    pool_size: env
+                        .redis_pool_size
+                        .or(file_redis.and_then(|r| r.pool_size))
+                        .unwrap_or(DEFAULT_REDIS_POOL_SIZE),
+                    connect_timeout: Duration::from_secs(
+                        env.redis_connect_timeout_secs
+                            .or(file_redis.and_then(|r| r.connect_timeout_secs))
+                            .unwrap_or(DEFAULT_REDIS_CONNECT_TIMEOUT_SECS),
+                    ),
+                    command_timeout: Duration::from_millis(
+                        env.redis_command_timeout_ms
+                            .or(file_redis.and_then(|r| r.command_timeout_ms))
+                            .unwrap_or(DEFAULT_REDIS_COMMAND_TIMEOUT_MS),
+                    ),
+                    outage_policy,
+                })
+            }
+        };
 
         let media_root = match (file_media_root, env.media_root.clone()) {
             (Some(file_root), _) => Some(file_root),
             (None, env_root) => env_root,
         };
-        let media = MediaConfig { root: media_root };
+        let metadata_languages = env
+            .metadata_languages
+            .clone()
+            .or(file_metadata_languages)
+            .unwrap_or_default();
+        let locale = LocaleConfig {
+            language: match env
+                .default_language
+                .clone()
+                .or(file_locale.language)
+            {
+                Some(raw) => locale::parse_language(&raw)
+                    .map_err(error::ConfigLoadError::InvalidLocale)?,
+                None => DEFAULT_LOCALE_LANGUAGE.to_string(),
+            },
+            region: env
+                .default_region
+                .clone()
+                .or(file_locale.region)
+                .filter(|raw| !raw.trim().is_empty())
+                .map(|raw| locale::parse_region(&raw))
+                .transpose()
+                .map_err(error::ConfigLoadError::InvalidLocale)?,
+        };
+        let media = MediaConfig {
+            root: media_root,
+            metadata_languages,
+            filename_rules,
+            movie_defaults: LibraryListingDefaults {
+                sort: env
+                    .movie_default_sort
+                    .clone()
+                    .or(file_movie_defaults.sort),
+                filter: env
+                    .movie_default_filter
+                    .clone()
+                    .or(file_movie_defaults.filter),
+            },
+            series_defaults: LibraryListingDefaults {
+                sort: env
+                    .series_default_sort
+                    .clone()
+                    .or(file_series_defaults.sort),
+                filter: env
+                    .series_default_filter
+                    .clone()
+                    .or(file_series_defaults.filter),
+            },
+        };
 
         let cache_root = env
             .cache_root
             .clone()
             .or(file_cache.root.clone())
-            .unwrap_or_else(|| PathBuf::from("./cache"));
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR));
         let images = env
             .cache_images
             .clone()
@@ -181,11 +489,76 @@ impl ConfigLoader {
             .clone()
             .or(file_cache.thumbnails.clone())
             .unwrap_or_else(|| cache_root.join("thumbnails"));
+        let image_store = match env
+            .image_store
+            .as_deref()
+            .or(file_cache.image_store.as_deref())
+        {
+            Some(raw) => raw
+                .parse()
+                .map_err(error::ConfigLoadError::InvalidImageStore)?,
+            None => ImageStoreBackend::default(),
+        };
+        let image_store_s3 = match image_store {
+            ImageStoreBackend::Local => None,
+            ImageStoreBackend::S3 => {
+                let file_s3 =
+                    file_cache.image_store_s3.clone().unwrap_or_default();
+                let setting = |env: &Option<String>, file: Option<String>| {
+                    env.clone()
+                        .or(file)
+                        .map(|value| value.trim().to_string())
+                        .filter(|value| !value.is_empty())
+                };
+                let bucket =
+                    setting(&env.image_store_s3_bucket, file_s3.bucket)
+                        .ok_or_else(|| {
+                            error::ConfigLoadError::InvalidImageStore(
+                            "the s3 image store needs IMAGE_STORE_S3_BUCKET"
+                                .to_string(),
+                        )
+                        })?;
+                Some(S3StoreConfig {
+                    bucket,
+                    region: setting(&env.image_store_s3_region, file_s3.region),
+                    endpoint: setting(
+                        &env.image_store_s3_endpoint,
+                        file_s3.endpoint,
+                    ),
+                    prefix: setting(&env.image_store_s3_prefix, file_s3.prefix),
+                    public_url: setting(
+                        &env.image_store_s3_public_url,
+                        file_s3.public_url,
+                    ),
+                })
+            }
+        };
         let cache = CacheConfig {
             root: cache_root,
             images,
             transcode,
             thumbnails,
+            hls_max_bytes: file_cache
+                .hls_max_bytes
+                .unwrap_or(DEFAULT_HLS_CACHE_MAX_BYTES),
+            min_free_bytes: file_cache
+                .min_free_bytes
+                .unwrap_or(DEFAULT_CACHE_MIN_FREE_BYTES),
+            image_failure_ttl_secs: env
+                .image_failure_ttl_secs
+                .or(file_cache.image_failure_ttl_secs)
+                .unwrap_or(DEFAULT_IMAGE_FAILURE_TTL_SECS),
+            encrypt_at_rest: env
+                .cache_encryption
+                .or(file_cache.encrypt_at_rest)
+                .unwrap_or(false),
+            encryption_key: env
+                .cache_encryption_key
+                .clone()
+                .or(file_cache.encryption_key.clone())
+                .filter(|key| !key.trim().is_empty()),
+            image_store,
+            image_store_s3,
         };
 
         let ffmpeg = FfmpegConfig {
@@ -193,12 +566,33 @@ impl ConfigLoader {
                 .ffmpeg_path
                 .clone()
                 .or(file_ffmpeg.ffmpeg_path.clone())
-                .unwrap_or_else(|| "ffmpeg".into()),
+                .unwrap_or_else(|| DEFAULT_FFMPEG_PATH.into()),
             ffprobe_path: env
                 .ffprobe_path
                 .clone()
                 .or(file_ffmpeg.ffprobe_path.clone())
-                .unwrap_or_else(|| "ffprobe".into()),
+                .unwrap_or_else(|| DEFAULT_FFPROBE_PATH.into()),
+            thumbnail_strategy: match env.thumbnail_strategy.as_deref() {
+                Some(raw) => raw.parse().unwrap_or_else(|err| {
+                    warnings.push_with_hint(
+                        format!("ignoring THUMBNAIL_STRATEGY: {err}"),
+                        "falling back to the config file or the 30% default",
+                    );
+                    file_ffmpeg.thumbnail_strategy.unwrap_or_default()
+                }),
+                None => file_ffmpeg.thumbnail_strategy.unwrap_or_default(),
+            },
+            thumbnail_width: env
+                .thumbnail_width
+                .or(file_ffmpeg.thumbnail_width)
+                .unwrap_or(DEFAULT_THUMBNAIL_WIDTH),
+            thumbnail_max_height: env
+                .thumbnail_max_height
+                .or(file_ffmpeg.thumbnail_max_height),
+            thumbnail_quality: env
+                .thumbnail_quality
+                .or(file_ffmpeg.thumbnail_quality)
+                .unwrap_or(DEFAULT_THUMBNAIL_QUALITY),
         };
 
         let cors = CorsConfig {
@@ -235,11 +629,20 @@ impl ConfigLoader {
                 .trust_proxy_headers
                 .or(file_security.trust_proxy_headers)
                 .unwrap_or(false),
+            trusted_proxies: env
+                .trusted_proxies
+                .clone()
+                .or(file_security.trusted_proxies.clone())
+                .unwrap_or_else(default_trusted_proxies),
+            require_auth_for_browsing: env
+                .require_auth_for_browsing
+                .or(file_security.require_auth_for_browsing)
+                .unwrap_or(true),
             hsts: HstsSettings {
                 max_age: env
                     .hsts_max_age
                     .or(file_security.hsts.max_age)
-                    .unwrap_or(31_536_000),
+                    .unwrap_or(DEFAULT_HSTS_MAX_AGE_SECS),
                 include_subdomains: env
                     .hsts_include_subdomains
                     .or(file_security.hsts.include_subdomains)
@@ -263,9 +666,39 @@ impl ConfigLoader {
                 .or(file_auth.token_key.clone())
                 .unwrap_or_else(|| DEFAULT_TOKEN_KEY.to_string()),
             setup_token: env.setup_token.or(file_auth.setup_token),
+            session_ttl: Duration::from_secs(
+                env.auth_session_ttl_secs
+                    .or(file_auth.session_ttl_secs)
+                    .unwrap_or(DEFAULT_SESSION_TTL_SECS),
+            ),
+            refresh_ttl: Duration::from_secs(
+                env.auth_refresh_ttl_secs
+                    .or(file_auth.refresh_ttl_secs)
+                    .unwrap_or(DEFAULT_REFRESH_TTL_SECS),
+            ),
+            device_challenge_ttl: Duration::from_secs(
+                env.auth_device_challenge_ttl_secs
+                    .or(file_auth.device_challenge_ttl_secs)
+                    .unwrap_or(DEFAULT_DEVICE_CHALLENGE_TTL_SECS),
+            ),
+            clock_skew_leeway: Duration::from_secs(
+                env.auth_clock_skew_leeway_secs
+                    .or(file_auth.clock_skew_leeway_secs)
+                    .unwrap_or(DEFAULT_CLOCK_SKEW_LEEWAY_SECS),
+            ),
+            strict_device_ids: env
+                .auth_strict_device_ids
+                .or(file_auth.strict_device_ids)
+                .unwrap_or(false),
         };
 
-        let (scanner, scanner_source) = ScannerConfig::load_from_env()
+        let (mut scanner, scanner_source) = ScannerConfig::load_from(
+            env.scanner_config_path.clone(),
+            env.scanner_config_json.clone(),
+        )
+        .map_err(error::ConfigLoadError::Scanner)?;
+        scanner
+            .normalize_file_rules()
             .map_err(error::ConfigLoadError::Scanner)?;
 
         let (rate_limiter, rate_limit_source) =
@@ -285,6 +718,9 @@ impl ConfigLoader {
         let metadata = ConfigMetadata {
             config_path: None,
             env_file_loaded,
+            config_dir: None,
+            fragment_sources: BTreeMap::new(),
+            secret_files: BTreeMap::new(),
             scanner_source,
             rate_limit_source,
         };
@@ -294,6 +730,7 @@ impl ConfigLoader {
             database,
             redis,
             media,
+            locale,
             cache,
             ffmpeg,
             cors,
@@ -327,6 +764,11 @@ impl ConfigLoader {
     }
 }
 
+/// A request time limit in seconds; `0` turns it off.
+fn request_timeout(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -348,6 +790,14 @@ mod tests {
             }
             Self { key, prev }
         }
+
+        fn unset(key: &'static str) -> Self {
+            let prev = std::env::var(key).ok();
+            unsafe {
+                std::env::remove_var(key);
+            }
+            Self { key, prev }
+        }
     }
 
     impl Drop for EnvGuard {
@@ -381,18 +831,192 @@ mod tests {
             Some("postgresql://new")
         );
     }
+
+    #[test]
+    fn unknown_log_format_fails_the_load() {
+        let _format = EnvGuard::unset("LOG_FORMAT");
+
+        let dir = tempdir().expect("tempdir");
+        let env_file = dir.path().join(".env");
+        std::fs::write(&env_file, "DEV_MODE=true\nLOG_FORMAT=logfmt\n")
+            .expect("write .env");
+
+        let err = ConfigLoader::new()
+            .with_path(&env_file)
+            .load()
+            .expect_err("logfmt is not a supported format");
+        assert!(matches!(err, error::ConfigLoadError::InvalidLogFormat(_)));
+    }
+
+    #[test]
+    fn s3_image_store_needs_a_bucket() {
+        let _store = EnvGuard::unset("IMAGE_STORE");
+        let _bucket = EnvGuard::unset("IMAGE_STORE_S3_BUCKET");
+        let _public_url = EnvGuard::unset("IMAGE_STORE_S3_PUBLIC_URL");
+
+        let dir = tempdir().expect("tempdir");
+        let env_file = dir.path().join(".env");
+        std::fs::write(&env_file, "DEV_MODE=true\nIMAGE_STORE=s3\n")
+            .expect("write .env");
+        let err = ConfigLoader::new()
+            .with_path(&env_file)
+            .load()
+            .expect_err("s3 without a bucket");
+        assert!(matches!(err, error::ConfigLoadError::InvalidImageStore(_)));
+
+        let _bucket = EnvGuard::set("IMAGE_STORE_S3_BUCKET", "ferrex-images");
+        let _public_url =
+            EnvGuard::set("IMAGE_STORE_S3_PUBLIC_URL", "https://cdn.test/");
+        let loaded = ConfigLoader::new()
+            .with_path(&env_file)
+            .load()
+            .expect("config load");
+        assert_eq!(loaded.config.cache.image_store, ImageStoreBackend::S3);
+        let s3 = loaded.config.cache.image_store_s3.expect("s3 settings");
+        assert_eq!(s3.bucket, "ferrex-images");
+        assert_eq!(s3.public_url.as_deref(), Some("https://cdn.test/"));
+        assert_eq!(s3.region, None);
+    }
+
+    #[test]
+    fn locale_codes_are_validated_and_feed_metadata_languages() {
+        let _language = EnvGuard::unset("DEFAULT_LANGUAGE");
+        let _region = EnvGuard::unset("DEFAULT_REGION");
+        let _tmdb_lang = EnvGuard::unset("TMDB_LANG");
+
+        let dir = tempdir().expect("tempdir");
+        let env_file = dir.path().join(".env");
+        std::fs::write(&env_file, "DEV_MODE=true\nDEFAULT_REGION=UK\n")
+            .expect("write .env");
+        let err = ConfigLoader::new()
+            .with_path(&env_file)
+            .load()
+            .expect_err("UK is not an ISO 3166-1 code");
+        assert!(matches!(err, error::ConfigLoadError::InvalidLocale(_)));
+
+        let _language = EnvGuard::set("DEFAULT_LANGUAGE", "DE");
+        let _region = EnvGuard::set("DEFAULT_REGION", "at");
+        let loaded = ConfigLoader::new()
+            .with_path(&env_file)
+            .load()
+            .expect("config load");
+        assert_eq!(loaded.config.locale.language, "de");
+        assert_eq!(loaded.config.locale.region.as_deref(), Some("AT"));
+        assert_eq!(loaded.config.metadata_languages(), ["de-AT"]);
+
+        let _tmdb_lang = EnvGuard::set("TMDB_LANG", "fr-FR,en-US");
+        let loaded = ConfigLoader::new()
+            .with_path(&env_file)
+            .load()
+            .expect("config load");
+        assert_eq!(loaded.config.metadata_languages(), ["fr-FR", "en-US"]);
+    }
+
+    #[test]
+    fn overlay_fragments_sit_beneath_the_env_file() {
+        let _ffmpeg = EnvGuard::unset("FFMPEG_PATH");
+        let _ffprobe = EnvGuard::unset("FFPROBE_PATH");
+
+        let dir = tempdir().expect("tempdir");
+        let env_file = dir.path().join(".env");
+        std::fs::write(&env_file, "DEV_MODE=true\nFFMPEG_PATH=/env/ffmpeg\n")
+            .expect("write .env");
+        let config_dir = dir.path().join("config.d");
+        std::fs::create_dir(&config_dir).expect("config.d");
+        std::fs::write(
+            config_dir.join("10-base.toml"),
+            "ffmpeg_path = \"/base/ffmpeg\"\nffprobe_path = \"/base/ffprobe\"\n",
+        )
+        .expect("write base fragment");
+        std::fs::write(
+            config_dir.join("20-site.env"),
+            "FFPROBE_PATH=/site/ffprobe\n",
+        )
+        .expect("write site fragment");
+
+        let loaded = ConfigLoader::new()
+            .with_path(&env_file)
+            .with_config_dir(&config_dir)
+            .load()
+            .expect("config load");
+
+        assert_eq!(loaded.config.ffmpeg.ffmpeg_path, "/env/ffmpeg");
+        assert_eq!(loaded.config.ffmpeg.ffprobe_path, "/site/ffprobe");
+        assert_eq!(
+            loaded.config.metadata.fragment_sources.get("FFPROBE_PATH"),
+            Some(&config_dir.join("20-site.env"))
+        );
+        assert!(
+            !loaded
+                .config
+                .metadata
+                .fragment_sources
+                .contains_key("FFMPEG_PATH")
+        );
+        assert!(loaded.warnings.items.iter().any(|w| {
+            w.message.contains("FFPROBE_PATH")
+                && w.message.contains("10-base.toml -> 20-site.env")
+        }));
+    }
+
+    #[test]
+    fn reload_picks_up_edits_without_touching_the_environment() {
+        let _host = EnvGuard::unset("SERVER_HOST");
+        let _header = EnvGuard::unset("REQUEST_ID_HEADER");
+
+        let dir = tempdir().expect("tempdir");
+        let env_file = dir.path().join(".env");
+        std::fs::write(&env_file, "DEV_MODE=true\nSERVER_HOST=10.0.0.1\n")
+            .expect("write .env");
+        let config_dir = dir.path().join("config.d");
+        std::fs::create_dir(&config_dir).expect("config.d");
+        let fragment = config_dir.join("10-site.env");
+        std::fs::write(&fragment, "REQUEST_ID_HEADER=x-first-id\n")
+            .expect("write fragment");
+
+        let loader = ConfigLoader::new()
+            .with_path(&env_file)
+            .with_config_dir(&config_dir);
+        let loaded = loader.load().expect("config load");
+        assert_eq!(loaded.config.server.host, "10.0.0.1");
+        assert_eq!(loaded.config.server.request_id_header, "x-first-id");
+
+        std::fs::write(&env_file, "DEV_MODE=true\nSERVER_HOST=10.0.0.2\n")
+            .expect("rewrite .env");
+        std::fs::write(&fragment, "REQUEST_ID_HEADER=x-second-id\n")
+            .expect("rewrite fragment");
+        let reloaded = loader
+            .reload(&loaded.config.metadata)
+            .expect("config reload");
+
+        assert_eq!(reloaded.config.server.host, "10.0.0.2");
+        assert_eq!(reloaded.config.server.request_id_header, "x-second-id");
+        assert_eq!(
+            reloaded
+                .config
+                .metadata
+                .fragment_sources
+                .get("REQUEST_ID_HEADER"),
+            Some(&fragment)
+        );
+        assert_eq!(std::env::var("SERVER_HOST").as_deref(), Ok("10.0.0.1"));
+        assert_eq!(
+            std::env::var("REQUEST_ID_HEADER").as_deref(),
+            Ok("x-first-id")
+        );
+    }
 }
 
 pub mod error;
 
-fn default_cors_origins() -> Vec<String> {
+pub(crate) fn default_cors_origins() -> Vec<String> {
     vec![
         "http://localhost:3000".to_string(),
         "http://localhost:5173".to_string(),
     ]
 }
 
-fn default_cors_methods() -> Vec<String> {
+pub(crate) fn default_cors_methods() -> Vec<String> {
     vec![
         "GET".to_string(),
         "POST".to_string(),
@@ -403,7 +1027,7 @@ fn default_cors_methods() -> Vec<String> {
     ]
 }
 
-fn default_cors_headers() -> Vec<String> {
+pub(crate) fn default_cors_headers() -> Vec<String> {
     vec![
         "Authorization".to_string(),
         "Content-Type".to_string(),
@@ -0,0 +1,308 @@
+//! Ordered `config.d` overlay fragments.
+//!
+//! A config directory holds `*.env` and `*.toml` fragments that are applied
+//! in file-name order with last-wins precedence. Fragments sit beneath the
+//! `.env` file and the process environment: a key that is already set when
+//! the overlay is applied keeps its value.
+//!
+//! TOML fragments are flattened into environment-style keys, so
+//! `[server] port = 3000` sets `SERVER_PORT` and `TMDB_LANG = ["de", "en"]`
+//! sets `TMDB_LANG=de,en`.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use super::error::ConfigLoadError;
+use crate::validation::ConfigWarnings;
+
+/// Value of one key after all fragments were merged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlayValue {
+    pub value: String,
+    /// Fragment that set the winning value.
+    pub source: PathBuf,
+    /// Earlier fragments that set the same key, in application order.
+    pub overridden: Vec<PathBuf>,
+}
+
+/// Merged contents of a config directory.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverlay {
+    pub fragments: Vec<PathBuf>,
+    pub values: BTreeMap<String, OverlayValue>,
+}
+
+impl ConfigOverlay {
+    /// Read every `*.env` / `*.toml` fragment in `dir`, sorted by file name.
+    /// Other files are ignored.
+    pub fn load(dir: &Path) -> Result<Self, ConfigLoadError> {
+        let io_err = |source| ConfigLoadError::ConfigDirIo {
+            path: dir.to_path_buf(),
+            source,
+        };
+        let mut fragments = Vec::new();
+        for entry in std::fs::read_dir(dir).map_err(io_err)? {
+            let path = entry.map_err(io_err)?.path();
+            if path.is_file() && fragment_kind(&path).is_some() {
+                fragments.push(path);
+            }
+        }
+        fragments.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+
+        let mut overlay = Self::default();
+        for path in fragments {
+            for (key, value) in read_fragment(&path)? {
+                overlay.set(key, value, &path);
+            }
+            overlay.fragments.push(path);
+        }
+        Ok(overlay)
+    }
+
+    fn set(&mut self, key: String, value: String, source: &Path) {
+        match self.values.get_mut(&key) {
+            Some(existing) => {
+                let previous =
+                    std::mem::replace(&mut existing.source, source.into());
+                // A fragment repeating its own key is not an override chain.
+                if previous != existing.source {
+                    existing.overridden.push(previous);
+                }
+                existing.value = value;
+            }
+            None => {
+                self.values.insert(
+                    key,
+                    OverlayValue {
+                        value,
+                        source: source.into(),
+                        overridden: Vec::new(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Export merged values into the process environment, skipping keys that
+    /// are already set. Returns the fragment that supplied each applied key.
+    pub fn apply(&self) -> BTreeMap<String, PathBuf> {
+        let mut applied = BTreeMap::new();
+        for (key, entry) in &self.values {
+            if std::env::var_os(key).is_some() {
+                continue;
+            }
+            // SAFETY: configuration is loaded once during startup before
+            // worker threads are spawned, like the dotenv load above it.
+            unsafe {
+                std::env::set_var(key, &entry.value);
+            }
+            applied.insert(key.clone(), entry.source.clone());
+        }
+        applied
+    }
+
+    /// Like [`Self::apply`], filling `vars` instead of the process
+    /// environment.
+    pub fn apply_to(
+        &self,
+        vars: &mut BTreeMap<String, String>,
+    ) -> BTreeMap<String, PathBuf> {
+        let mut applied = BTreeMap::new();
+        for (key, entry) in &self.values {
+            if vars.contains_key(key) {
+                continue;
+            }
+            vars.insert(key.clone(), entry.value.clone());
+            applied.insert(key.clone(), entry.source.clone());
+        }
+        applied
+    }
+
+    /// Warn about keys set by more than one fragment.
+    pub fn conflict_warnings(
+        &self,
+        applied: &BTreeMap<String, PathBuf>,
+        warnings: &mut ConfigWarnings,
+    ) {
+        for (key, entry) in &self.values {
+            if entry.overridden.is_empty() {
+                continue;
+            }
+            let chain = entry
+                .overridden
+                .iter()
+                .chain(std::iter::once(&entry.source))
+                .map(|path| display_name(path))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            let outcome = if applied.contains_key(key) {
+                format!("{} wins", display_name(&entry.source))
+            } else {
+                "the environment wins".to_string()
+            };
+            warnings.push_with_hint(
+                format!(
+                    "{key} is set by multiple config fragments: {chain} ({outcome})"
+                ),
+                "keep each key in a single fragment unless the override is intentional",
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FragmentKind {
+    Env,
+    Toml,
+}
+
+fn fragment_kind(path: &Path) -> Option<FragmentKind> {
+    match path.extension()?.to_str()? {
+        "env" => Some(FragmentKind::Env),
+        "toml" => Some(FragmentKind::Toml),
+        _ => None,
+    }
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn read_fragment(
+    path: &Path,
+) -> Result<Vec<(String, String)>, ConfigLoadError> {
+    let invalid = |reason: String| ConfigLoadError::InvalidFragment {
+        path: path.to_path_buf(),
+        reason,
+    };
+    match fragment_kind(path) {
+        Some(FragmentKind::Env) => dotenvy::from_path_iter(path)
+            .map_err(|err| invalid(err.to_string()))?
+            .map(|item| item.map_err(|err| invalid(err.to_string())))
+            .collect(),
+        Some(FragmentKind::Toml) => {
+            let raw = std::fs::read_to_string(path).map_err(|source| {
+                ConfigLoadError::ConfigDirIo {
+                    path: path.to_path_buf(),
+                    source,
+                }
+            })?;
+            let table: toml::Table =
+                toml::from_str(&raw).map_err(|err| invalid(err.to_string()))?;
+            let mut out = Vec::new();
+            flatten_toml(None, &table, &mut out).map_err(invalid)?;
+            Ok(out)
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+fn flatten_toml(
+    prefix: Option<&str>,
+    table: &toml::Table,
+    out: &mut Vec<(String, String)>,
+) -> Result<(), String> {
+    for (name, value) in table {
+        let key = match prefix {
+            Some(prefix) => format!("{prefix}_{name}"),
+            None => name.clone(),
+        }
+        .to_ascii_uppercase();
+        match value {
+            toml::Value::Table(nested) => {
+                flatten_toml(Some(&key), nested, out)?
+            }
+            other => out.push((key.clone(), scalar_to_env(&key, other)?)),
+        }
+    }
+    Ok(())
+}
+
+fn scalar_to_env(key: &str, value: &toml::Value) -> Result<String, String> {
+    match value {
+        toml::Value::String(s) => Ok(s.clone()),
+        toml::Value::Integer(i) => Ok(i.to_string()),
+        toml::Value::Float(f) => Ok(f.to_string()),
+        toml::Value::Boolean(b) => Ok(b.to_string()),
+        toml::Value::Datetime(dt) => Ok(dt.to_string()),
+        toml::Value::Array(items) => items
+            .iter()
+            .map(|item| match item {
+                toml::Value::Array(_) | toml::Value::Table(_) => {
+                    Err(format!("{key} must be an array of scalars"))
+                }
+                scalar => scalar_to_env(key, scalar),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|items| items.join(",")),
+        toml::Value::Table(_) => unreachable!("tables are flattened"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn fragments_merge_in_file_name_order() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("10-base.toml"),
+            "tmdb_lang = [\"de-DE\", \"en-US\"]\n[server]\nport = 3000\nhost = \"0.0.0.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("20-prod.env"), "SERVER_PORT=8443\n")
+            .unwrap();
+        std::fs::write(dir.path().join("README.md"), "ignored").unwrap();
+
+        let overlay = ConfigOverlay::load(dir.path()).expect("overlay");
+
+        assert_eq!(overlay.fragments.len(), 2);
+        assert_eq!(overlay.values["TMDB_LANG"].value, "de-DE,en-US");
+        assert_eq!(overlay.values["SERVER_HOST"].value, "0.0.0.0");
+
+        let port = &overlay.values["SERVER_PORT"];
+        assert_eq!(port.value, "8443");
+        assert_eq!(port.source, dir.path().join("20-prod.env"));
+        assert_eq!(port.overridden, vec![dir.path().join("10-base.toml")]);
+    }
+
+    #[test]
+    fn conflicts_report_the_override_chain() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("a.env"), "FERREX_OVERLAY_TEST=1\n")
+            .unwrap();
+        std::fs::write(dir.path().join("b.env"), "FERREX_OVERLAY_TEST=2\n")
+            .unwrap();
+
+        let overlay = ConfigOverlay::load(dir.path()).expect("overlay");
+        let applied = BTreeMap::from([(
+            "FERREX_OVERLAY_TEST".to_string(),
+            "b.env".into(),
+        )]);
+        let mut warnings = ConfigWarnings::default();
+        overlay.conflict_warnings(&applied, &mut warnings);
+
+        assert_eq!(warnings.items.len(), 1);
+        assert!(warnings.items[0].message.contains("a.env -> b.env"));
+        assert!(warnings.items[0].message.contains("b.env wins"));
+    }
+
+    #[test]
+    fn invalid_toml_names_the_fragment() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("bad.toml"), "port = ").unwrap();
+
+        let err = ConfigOverlay::load(dir.path()).expect_err("invalid toml");
+        assert!(matches!(
+            err,
+            ConfigLoadError::InvalidFragment { ref path, .. }
+                if path.ends_with("bad.toml")
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+//! `<KEY>_FILE` indirection for secrets.
+//!
+//! Container platforms mount secrets as files (`/run/secrets/token_key`)
+//! and point at them with `AUTH_TOKEN_KEY_FILE`. For every key in
+//! [`SECRET_FILE_KEYS`] the referenced file's trimmed contents stand in for
+//! the key itself. A value set directly wins over its file; setting both
+//! is reported as a warning.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use super::error::ConfigLoadError;
+use crate::{
+    constants::SECRET_FILE_KEYS, models::sources::EnvConfig,
+    validation::ConfigWarnings,
+};
+
+/// Fill the secrets of `env` from their `<KEY>_FILE` variables. Returns the
+/// file each resolved key was read from.
+pub fn resolve_secret_files(
+    env: &mut EnvConfig,
+    warnings: &mut ConfigWarnings,
+) -> Result<BTreeMap<String, PathBuf>, ConfigLoadError> {
+    resolve_secret_files_with(env, warnings, |key| std::env::var(key).ok())
+}
+
+/// Like [`resolve_secret_files`], reading the `<KEY>_FILE` variables
+/// through `lookup`.
+pub fn resolve_secret_files_with(
+    env: &mut EnvConfig,
+    warnings: &mut ConfigWarnings,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<BTreeMap<String, PathBuf>, ConfigLoadError> {
+    let mut sources = BTreeMap::new();
+
+    for key in SECRET_FILE_KEYS {
+        let file_key = format!("{key}_FILE");
+        let Some(path) = lookup(&file_key)
+            .map(|raw| raw.trim().to_string())
+            .filter(|raw| !raw.is_empty())
+            .map(PathBuf::from)
+        else {
+            continue;
+        };
+        let Some(slot) = secret_slot(env, key) else {
+            continue;
+        };
+
+        if slot
+            .as_deref()
+            .is_some_and(|value| !value.trim().is_empty())
+        {
+            warnings.push_with_hint(
+                format!("both {key} and {file_key} are set; using {key}"),
+                format!("unset one of them ({file_key} is ignored)"),
+            );
+            continue;
+        }
+
+        *slot = Some(read_secret(key, &path)?);
+        sources.insert((*key).to_string(), path);
+    }
+
+    Ok(sources)
+}
+
+/// Field of `env` holding the value of `key`.
+fn secret_slot<'a>(
+    env: &'a mut EnvConfig,
+    key: &str,
+) -> Option<&'a mut Option<String>> {
+    match key {
+        "AUTH_PASSWORD_PEPPER" => Some(&mut env.auth_password_pepper),
+        "AUTH_TOKEN_KEY" => Some(&mut env.auth_token_key),
+        "FERREX_SETUP_TOKEN" => Some(&mut env.setup_token),
+        "DATABASE_URL" => Some(&mut env.database_url),
+        "DATABASE_PASSWORD" => Some(&mut env.database_password),
+        "REDIS_URL" => Some(&mut env.redis_url),
+        "CACHE_ENCRYPTION_KEY" => Some(&mut env.cache_encryption_key),
+        _ => None,
+    }
+}
+
+fn read_secret(key: &str, path: &Path) -> Result<String, ConfigLoadError> {
+    let contents = std::fs::read_to_string(path).map_err(|source| {
+        ConfigLoadError::SecretFileUnreadable {
+            key: key.to_string(),
+            path: path.to_path_buf(),
+            source,
+        }
+    })?;
+    let trimmed = contents.trim();
+    if trimmed.is_empty() {
+        return Err(ConfigLoadError::EmptySecretFile {
+            key: key.to_string(),
+            path: path.to_path_buf(),
+        });
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    fn lookup(
+        vars: &HashMap<&'static str, String>,
+    ) -> impl Fn(&str) -> Option<String> + '_ {
+        |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn file_contents_fill_unset_secrets() {
+        let dir = tempdir().expect("tempdir");
+        let token_key = dir.path().join("token_key");
+        std::fs::write(&token_key, "  s3cret-key\n").expect("write secret");
+
+        let vars = HashMap::from([(
+            "AUTH_TOKEN_KEY_FILE",
+            token_key.display().to_string(),
+        )]);
+        let mut env = EnvConfig::default();
+        let mut warnings = ConfigWarnings::default();
+        let sources =
+            resolve_secret_files_with(&mut env, &mut warnings, lookup(&vars))
+                .expect("resolve");
+
+        assert_eq!(env.auth_token_key.as_deref(), Some("s3cret-key"));
+        assert_eq!(sources.get("AUTH_TOKEN_KEY"), Some(&token_key));
+        assert!(warnings.items.is_empty());
+    }
+
+    #[test]
+    fn direct_values_win_with_a_warning() {
+        let dir = tempdir().expect("tempdir");
+        let pepper = dir.path().join("pepper");
+        std::fs::write(&pepper, "from-file").expect("write secret");
+
+        let vars = HashMap::from([(
+            "AUTH_PASSWORD_PEPPER_FILE",
+            pepper.display().to_string(),
+        )]);
+        let mut env = EnvConfig {
+            auth_password_pepper: Some("from-env".into()),
+            ..EnvConfig::default()
+        };
+        let mut warnings = ConfigWarnings::default();
+        let sources =
+            resolve_secret_files_with(&mut env, &mut warnings, lookup(&vars))
+                .expect("resolve");
+
+        assert_eq!(env.auth_password_pepper.as_deref(), Some("from-env"));
+        assert!(sources.is_empty());
+        assert!(
+            warnings.items[0]
+                .message
+                .contains("AUTH_PASSWORD_PEPPER_FILE")
+        );
+    }
+
+    #[test]
+    fn missing_or_empty_files_fail_the_load() {
+        let dir = tempdir().expect("tempdir");
+        let missing = dir.path().join("missing");
+        let vars = HashMap::from([(
+            "FERREX_SETUP_TOKEN_FILE",
+            missing.display().to_string(),
+        )]);
+        let err = resolve_secret_files_with(
+            &mut EnvConfig::default(),
+            &mut ConfigWarnings::default(),
+            lookup(&vars),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigLoadError::SecretFileUnreadable { ref key, .. }
+                if key == "FERREX_SETUP_TOKEN"
+        ));
+
+        let empty = dir.path().join("empty");
+        std::fs::write(&empty, "\n").expect("write secret");
+        let vars =
+            HashMap::from([("REDIS_URL_FILE", empty.display().to_string())]);
+        let err = resolve_secret_files_with(
+            &mut EnvConfig::default(),
+            &mut ConfigWarnings::default(),
+            lookup(&vars),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ConfigLoadError::EmptySecretFile { .. }));
+    }
+}
@@ -0,0 +1,135 @@
+use serde::Serialize;
+
+use crate::constants::DEFAULT_LOCALE_LANGUAGE;
+
+/// Deployment-wide language and region.
+///
+/// More specific settings win over these: `media.metadata_languages`
+/// (`TMDB_LANG`) over `language` for metadata requests, and a user's own
+/// language preference over both in clients. `language` and `region` are
+/// what everything falls back to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LocaleConfig {
+    /// ISO 639-1 code, lowercase (`de`).
+    pub language: String,
+    /// ISO 3166-1 alpha-2 code, uppercase (`DE`). Release dates and
+    /// certifications of this country are preferred; `None` keeps the
+    /// provider's own order.
+    pub region: Option<String>,
+}
+
+impl Default for LocaleConfig {
+    fn default() -> Self {
+        Self {
+            language: DEFAULT_LOCALE_LANGUAGE.to_string(),
+            region: None,
+        }
+    }
+}
+
+impl LocaleConfig {
+    /// `language-REGION` (`de-DE`), or the bare language without a region.
+    pub fn language_tag(&self) -> String {
+        match &self.region {
+            Some(region) => format!("{}-{region}", self.language),
+            None => self.language.clone(),
+        }
+    }
+}
+
+/// Normalize an ISO 639-1 language code to lowercase.
+pub fn parse_language(raw: &str) -> Result<String, String> {
+    let code = raw.trim().to_ascii_lowercase();
+    if ISO_639_1.contains(&code.as_str()) {
+        Ok(code)
+    } else {
+        Err(format!(
+            "`{}` is not an ISO 639-1 language code (e.g. en, de)",
+            raw.trim()
+        ))
+    }
+}
+
+/// Normalize an ISO 3166-1 alpha-2 region code to uppercase.
+pub fn parse_region(raw: &str) -> Result<String, String> {
+    let code = raw.trim().to_ascii_uppercase();
+    if ISO_3166_1.contains(&code.as_str()) {
+        Ok(code)
+    } else {
+        Err(format!(
+            "`{}` is not an ISO 3166-1 alpha-2 region code (e.g. US, DE)",
+            raw.trim()
+        ))
+    }
+}
+
+#[rustfmt::skip]
+const ISO_639_1: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az",
+    "ba", "be", "bg", "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch",
+    "co", "cr", "cs", "cu", "cv", "cy", "da", "de", "dv", "dz", "ee", "el",
+    "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr", "fy",
+    "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht",
+    "hu", "hy", "hz", "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it",
+    "iu", "ja", "jv", "ka", "kg", "ki", "kj", "kk", "kl", "km", "kn", "ko",
+    "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln", "lo",
+    "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt",
+    "my", "na", "nb", "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny",
+    "oc", "oj", "om", "or", "os", "pa", "pi", "pl", "ps", "pt", "qu", "rm",
+    "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk", "sl",
+    "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te",
+    "tg", "th", "ti", "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty",
+    "ug", "uk", "ur", "uz", "ve", "vi", "vo", "wa", "wo", "xh", "yi", "yo",
+    "za", "zh", "zu",
+];
+
+#[rustfmt::skip]
+const ISO_3166_1: &[&str] = &[
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT",
+    "AU", "AW", "AX", "AZ", "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI",
+    "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS", "BT", "BV", "BW", "BY",
+    "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM",
+    "DO", "DZ", "EC", "EE", "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK",
+    "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF", "GG", "GH", "GI", "GL",
+    "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR",
+    "IS", "IT", "JE", "JM", "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN",
+    "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC", "LI", "LK", "LR", "LS",
+    "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW",
+    "MX", "MY", "MZ", "NA", "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP",
+    "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG", "PH", "PK", "PL", "PM",
+    "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM",
+    "SN", "SO", "SR", "SS", "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF",
+    "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO", "TR", "TT", "TV", "TW",
+    "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_normalized_and_checked_against_iso_lists() {
+        assert_eq!(parse_language(" DE ").as_deref(), Ok("de"));
+        assert_eq!(parse_region("gb").as_deref(), Ok("GB"));
+
+        assert!(parse_language("xx").is_err());
+        assert!(parse_language("de-DE").is_err());
+        assert!(parse_language("eng").is_err());
+        assert!(parse_region("UK").is_err());
+        assert!(parse_region("").is_err());
+    }
+
+    #[test]
+    fn the_language_tag_includes_the_region_when_set() {
+        let mut locale = LocaleConfig::default();
+        assert_eq!(locale.language_tag(), "en");
+        locale.language = "pt".into();
+        locale.region = Some("BR".into());
+        assert_eq!(locale.language_tag(), "pt-BR");
+    }
+}
@@ -1,20 +1,31 @@
+pub mod locale;
 pub mod rate_limits;
+mod redact;
 pub mod scanner;
 pub mod sources;
+pub mod trusted_proxies;
 
 use crate::constants::{DEFAULT_PASSWORD_PEPPER, DEFAULT_TOKEN_KEY};
+use ferrex_model::image::ThumbnailStrategy;
 
+use locale::LocaleConfig;
 use rate_limits::{RateLimitSource, RateLimiterConfig};
 use scanner::{ScannerConfig, ScannerConfigSource};
+use trusted_proxies::IpRange;
 
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Config {
     pub server: ServerConfig,
     pub database: DatabaseConfig,
     pub redis: Option<RedisConfig>,
     pub media: MediaConfig,
+    pub locale: LocaleConfig,
     pub cache: CacheConfig,
     pub ffmpeg: FfmpegConfig,
     pub cors: CorsConfig,
@@ -35,6 +46,19 @@ impl Config {
         self.cache.normalize_paths()
     }
 
+    /// Languages metadata is requested in: `media.metadata_languages` when
+    /// set, else the deployment locale when it was configured. Empty leaves
+    /// the provider's default.
+    pub fn metadata_languages(&self) -> Vec<String> {
+        if !self.media.metadata_languages.is_empty() {
+            self.media.metadata_languages.clone()
+        } else if self.locale != LocaleConfig::default() {
+            vec![self.locale.language_tag()]
+        } else {
+            Vec::new()
+        }
+    }
+
     pub fn cache_root(&self) -> &Path {
         &self.cache.root
     }
@@ -50,35 +74,271 @@ impl Config {
     pub fn image_cache_dir(&self) -> &Path {
         &self.cache.images
     }
+
+    /// Key cached artifacts are sealed with, when encryption is enabled.
+    pub fn cache_encryption_key(&self) -> Option<&str> {
+        self.cache
+            .encryption_key
+            .as_deref()
+            .filter(|_| self.cache.encrypt_at_rest)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    /// Header an inbound correlation id is read from and echoed back in.
+    pub request_id_header: String,
+    /// Time limit for ordinary API requests; `None` disables it.
+    #[serde(serialize_with = "redact::optional_secs")]
+    pub request_timeout: Option<Duration>,
+    /// Time limit for admin, scan and other heavy requests; `None`
+    /// disables it. Streaming, SSE and WebSocket routes are never limited.
+    #[serde(serialize_with = "redact::optional_secs")]
+    pub slow_request_timeout: Option<Duration>,
+    /// Simultaneous direct-play streams allowed before new ones are refused
+    /// with `503`; `None` removes the cap.
+    pub max_concurrent_streams: Option<usize>,
+    /// Simultaneous live transcodes for clients that cannot play a file
+    /// directly, on top of their stream slot; `None` removes the cap.
+    pub max_concurrent_transcodes: Option<usize>,
+    /// Default bandwidth cap of each stream in kilobits per second; `None`
+    /// streams unthrottled.
+    pub stream_bandwidth_limit_kbps: Option<u64>,
+    /// Hosts of remote media URLs that clients are redirected to instead of
+    /// having the file proxied through the server.
+    pub remote_media_redirect_hosts: Vec<String>,
+    /// Compress JSON and plain-text API responses when the client accepts
+    /// gzip or deflate.
+    pub compression_enabled: bool,
+    /// Bodies smaller than this are sent uncompressed.
+    pub compression_min_bytes: u16,
+    /// Output format of the log subscriber.
+    pub log_format: LogFormat,
+    /// Default level filter, used when `RUST_LOG` is not set.
+    pub log_level: Option<String>,
+}
+
+/// How log lines are written to stdout.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Human-readable lines for terminals.
+    #[default]
+    Pretty,
+    /// One JSON object per line, carrying the fields of the enclosing spans
+    /// (including the request id) for log aggregators.
+    Json,
 }
 
-#[derive(Debug, Clone)]
+impl LogFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogFormat::Pretty => "pretty",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "pretty" | "text" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!(
+                "unknown log format `{other}`; expected pretty or json"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct DatabaseConfig {
+    #[serde(serialize_with = "redact::optional_secret")]
     pub primary_url: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RedisConfig {
+    #[serde(serialize_with = "redact::secret")]
     pub url: String,
+    /// Multiplexed connections opened at startup; commands are spread
+    /// across them round-robin.
+    pub pool_size: usize,
+    /// Limit for establishing each connection.
+    #[serde(serialize_with = "redact::secs")]
+    pub connect_timeout: Duration,
+    /// Redis counts as unreachable when a command takes longer than this.
+    #[serde(serialize_with = "redact::millis")]
+    pub command_timeout: Duration,
+    /// What the auth rate limiter does while Redis is unreachable.
+    pub outage_policy: RedisOutagePolicy,
+}
+
+/// Behaviour of the auth rate limiter while Redis cannot be reached.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RedisOutagePolicy {
+    /// Let requests through unthrottled. Logins keep working during a Redis
+    /// outage, at the cost of brute-force protection.
+    #[default]
+    FailOpen,
+    /// Refuse rate-limited requests with `503` until Redis is back.
+    FailClosed,
+}
+
+impl RedisOutagePolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RedisOutagePolicy::FailOpen => "fail_open",
+            RedisOutagePolicy::FailClosed => "fail_closed",
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+impl FromStr for RedisOutagePolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "fail_open" | "open" => Ok(RedisOutagePolicy::FailOpen),
+            "fail_closed" | "closed" => Ok(RedisOutagePolicy::FailClosed),
+            other => Err(format!(
+                "unknown Redis outage policy `{other}`; expected fail_open or fail_closed"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct MediaConfig {
     pub root: Option<PathBuf>,
+    /// Preferred TMDB metadata languages, most preferred first. Empty means
+    /// the provider default (`TMDB_LANG`, else `en-US`).
+    pub metadata_languages: Vec<String>,
+    /// Regexes tried in order before the built-in filename heuristics,
+    /// using the named groups in [`FILENAME_RULE_GROUPS`].
+    ///
+    /// [`FILENAME_RULE_GROUPS`]: crate::constants::FILENAME_RULE_GROUPS
+    pub filename_rules: Vec<String>,
+    /// Listing defaults for movie libraries.
+    pub movie_defaults: LibraryListingDefaults,
+    /// Listing defaults for series libraries.
+    pub series_defaults: LibraryListingDefaults,
+}
+
+/// Order and filter a library listing uses when the client does not ask
+/// for one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LibraryListingDefaults {
+    /// One of [`LIBRARY_SORT_FIELDS`], optionally followed by `:asc` or
+    /// `:desc` (`date_added:desc`).
+    ///
+    /// [`LIBRARY_SORT_FIELDS`]: crate::constants::LIBRARY_SORT_FIELDS
+    pub sort: Option<String>,
+    /// One of [`LIBRARY_LISTING_FILTERS`].
+    ///
+    /// [`LIBRARY_LISTING_FILTERS`]: crate::constants::LIBRARY_LISTING_FILTERS
+    pub filter: Option<String>,
+}
+
+impl LibraryListingDefaults {
+    /// The sort field and, when given, its direction.
+    pub fn sort_parts(&self) -> Option<(&str, Option<&str>)> {
+        let sort = self.sort.as_deref()?;
+        Some(match sort.split_once(':') {
+            Some((field, order)) => (field, Some(order)),
+            None => (sort, None),
+        })
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CacheConfig {
     pub root: PathBuf,
     pub images: PathBuf,
     pub transcode: PathBuf,
     pub thumbnails: PathBuf,
+    /// Upper bound for on-demand HLS segments kept under `transcode`;
+    /// least recently used segments are evicted first.
+    pub hls_max_bytes: u64,
+    /// Scans and image downloads are refused while the cache volume has
+    /// fewer free bytes than this; `0` disables the check.
+    pub min_free_bytes: u64,
+    /// Seal cached images and HLS segments on disk with a key derived from
+    /// `encryption_key`.
+    pub encrypt_at_rest: bool,
+    #[serde(serialize_with = "redact::optional_secret")]
+    pub encryption_key: Option<String>,
+    /// Seconds an image size whose download failed permanently (a 404
+    /// from TMDB) is reported unavailable instead of being fetched again;
+    /// `0` retries on every request. Refreshing the media's images clears
+    /// it early.
+    pub image_failure_ttl_secs: u64,
+    /// Where materialized image blobs are kept. The integrity-checked
+    /// working copy under `images` stays on local disk either way.
+    pub image_store: ImageStoreBackend,
+    /// Bucket settings, present when `image_store` is `s3`.
+    pub image_store_s3: Option<S3StoreConfig>,
+}
+
+/// Storage for materialized image blobs.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageStoreBackend {
+    /// Files under the image cache directory.
+    #[default]
+    Local,
+    /// Objects in an S3-compatible bucket. Needs a server built with the
+    /// `s3` feature.
+    S3,
+}
+
+impl ImageStoreBackend {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImageStoreBackend::Local => "local",
+            ImageStoreBackend::S3 => "s3",
+        }
+    }
+}
+
+impl FromStr for ImageStoreBackend {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "local" | "fs" => Ok(ImageStoreBackend::Local),
+            "s3" => Ok(ImageStoreBackend::S3),
+            other => Err(format!(
+                "unknown image store `{other}`; expected local or s3"
+            )),
+        }
+    }
+}
+
+/// Bucket image blobs are kept in when the image store is `s3`.
+/// Credentials are read from the standard `AWS_*` environment variables.
+#[derive(Debug, Clone, Serialize)]
+pub struct S3StoreConfig {
+    pub bucket: String,
+    pub region: Option<String>,
+    /// Endpoint of an S3-compatible service such as MinIO or R2.
+    pub endpoint: Option<String>,
+    /// Key prefix blobs are stored under.
+    pub prefix: Option<String>,
+    /// Public base URL of the bucket or a CDN in front of it. Clients are
+    /// redirected there for blobs; without it the server proxies them.
+    pub public_url: Option<String>,
 }
 
 impl CacheConfig {
@@ -99,13 +359,23 @@ impl CacheConfig {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FfmpegConfig {
     pub ffmpeg_path: String,
     pub ffprobe_path: String,
+    /// Frame selection for generated episode thumbnails.
+    pub thumbnail_strategy: ThumbnailStrategy,
+    /// Width extracted video thumbnails are scaled to; the height follows
+    /// the source aspect ratio. Sources are never upscaled.
+    pub thumbnail_width: u32,
+    /// Cap on the height of extracted video thumbnails, which mainly
+    /// matters for portrait sources.
+    pub thumbnail_max_height: Option<u32>,
+    /// JPEG quality (1-100) of generated thumbnails.
+    pub thumbnail_quality: u8,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CorsConfig {
     pub allowed_origins: Vec<String>,
     pub allowed_methods: Vec<String>,
@@ -121,14 +391,31 @@ impl CorsConfig {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SecurityConfig {
     pub enforce_https: bool,
     pub trust_proxy_headers: bool,
+    /// Peers (CIDR ranges) whose `X-Forwarded-*` headers are honoured when
+    /// `trust_proxy_headers` is set.
+    pub trusted_proxies: Vec<String>,
+    /// Require a session for library listings, queries and images. When
+    /// off, those routes are open to anonymous clients; playback always
+    /// requires a session.
+    pub require_auth_for_browsing: bool,
     pub hsts: HstsSettings,
 }
 
-#[derive(Debug, Clone)]
+impl SecurityConfig {
+    /// Parsed `trusted_proxies`; entries rejected by validation are left out.
+    pub fn trusted_proxy_ranges(&self) -> Vec<IpRange> {
+        self.trusted_proxies
+            .iter()
+            .filter_map(|range| range.parse().ok())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct HstsSettings {
     pub max_age: u64,
     pub include_subdomains: bool,
@@ -152,11 +439,29 @@ impl From<&HstsSettings> for HstsLayerConfig {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AuthConfig {
+    #[serde(serialize_with = "redact::secret")]
     pub password_pepper: String,
+    #[serde(serialize_with = "redact::secret")]
     pub token_key: String,
+    #[serde(serialize_with = "redact::optional_secret")]
     pub setup_token: Option<String>,
+    /// Lifetime of newly issued access tokens.
+    #[serde(serialize_with = "redact::secs")]
+    pub session_ttl: Duration,
+    /// Lifetime of newly issued and rotated refresh tokens.
+    #[serde(serialize_with = "redact::secs")]
+    pub refresh_ttl: Duration,
+    /// Lifetime of device PIN challenge nonces.
+    #[serde(serialize_with = "redact::secs")]
+    pub device_challenge_ttl: Duration,
+    /// How far past expiry session and refresh tokens are still accepted.
+    #[serde(serialize_with = "redact::secs")]
+    pub clock_skew_leeway: Duration,
+    /// Refuse device logins whose client-supplied device id is already
+    /// claimed by another user's device, instead of assigning a new id.
+    pub strict_device_ids: bool,
 }
 
 impl AuthConfig {
@@ -169,16 +474,23 @@ impl AuthConfig {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RateLimiterSettings {
     pub config: RateLimiterConfig,
     pub source: RateLimitSource,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ConfigMetadata {
     pub config_path: Option<PathBuf>,
     pub env_file_loaded: bool,
+    /// Overlay directory applied beneath `.env`, if one was configured.
+    pub config_dir: Option<PathBuf>,
+    /// Overlay fragment that supplied each key not already set by `.env`
+    /// or the process environment.
+    pub fragment_sources: BTreeMap<String, PathBuf>,
+    /// Secrets read through `<KEY>_FILE`, with the file each came from.
+    pub secret_files: BTreeMap<String, PathBuf>,
     pub scanner_source: ScannerConfigSource,
     pub rate_limit_source: Option<RateLimitSource>,
 }
@@ -188,6 +500,9 @@ impl Default for ConfigMetadata {
         Self {
             config_path: None,
             env_file_loaded: false,
+            config_dir: None,
+            fragment_sources: BTreeMap::new(),
+            secret_files: BTreeMap::new(),
             scanner_source: ScannerConfigSource::Default,
             rate_limit_source: None,
         }
@@ -8,8 +8,12 @@ use ferrex_model::rate_limit::{EndpointLimits, TrustedSources};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::time::Duration;
+use uuid::Uuid;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use super::trusted_proxies::IpRange;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "path", rename_all = "snake_case")]
 pub enum RateLimitSource {
     EnvPath(PathBuf),
     EnvInline,
@@ -32,6 +36,10 @@ pub struct RateLimiterConfig {
     /// Trusted sources that bypass rate limiting
     pub trusted_sources: TrustedSources,
 
+    /// Clients that skip rate limiting before any counter is checked
+    #[serde(default)]
+    pub allowlist: RateLimitAllowlist,
+
     /// Cache TTL for decisions
     pub cache_ttl: Duration,
 
@@ -54,6 +62,7 @@ impl Default for RateLimiterConfig {
                 user_ids: vec![],
                 device_ids: vec![],
             },
+            allowlist: RateLimitAllowlist::default(),
             cache_ttl: Duration::from_millis(100),
             enable_sync: true,
             key_prefix: "ferrex:ratelimit".to_string(),
@@ -62,6 +71,52 @@ impl Default for RateLimiterConfig {
     }
 }
 
+/// Clients exempt from rate limiting, such as internal dashboards or the
+/// operator's own player.
+///
+/// Unlike `trusted_sources`, which is matched against the rate-limit key,
+/// the allowlist is checked by the middleware against both the
+/// `X-Device-ID` header and the client address. Device ids are asserted by
+/// the client, so a listed id is a bearer secret: it is only honoured on
+/// requests that also carry a live session of that device, and anyone who
+/// learns it can reuse it from such a session. Allowlisting an IP range is
+/// a security tradeoff too: everyone behind it, including other users on a
+/// shared network or NAT, can then brute-force logins and PINs unthrottled.
+/// Prefer single addresses.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitAllowlist {
+    /// Device ids (UUIDs) sent in `X-Device-ID` alongside a session of that
+    /// device
+    #[serde(default)]
+    pub device_ids: Vec<String>,
+
+    /// Client addresses or CIDR ranges (`10.0.0.0/8`, `fd00::/8`)
+    #[serde(default)]
+    pub ip_ranges: Vec<String>,
+}
+
+impl RateLimitAllowlist {
+    pub fn is_empty(&self) -> bool {
+        self.device_ids.is_empty() && self.ip_ranges.is_empty()
+    }
+
+    /// Parsed `device_ids`; entries rejected by validation are left out.
+    pub fn device_uuids(&self) -> Vec<Uuid> {
+        self.device_ids
+            .iter()
+            .filter_map(|id| Uuid::parse_str(id.trim()).ok())
+            .collect()
+    }
+
+    /// Parsed `ip_ranges`; entries rejected by validation are left out.
+    pub fn ranges(&self) -> Vec<IpRange> {
+        self.ip_ranges
+            .iter()
+            .filter_map(|range| range.parse().ok())
+            .collect()
+    }
+}
+
 impl RateLimitSpec {
     pub fn load_from_env(
         &self,
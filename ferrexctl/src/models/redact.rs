@@ -0,0 +1,90 @@
+//! Serializers used when [`Config`](super::Config) is written out for
+//! inspection. Secrets are reduced to whether they are set and their
+//! length, and durations are written as whole seconds.
+
+use std::time::Duration;
+
+use serde::{Serialize, Serializer};
+
+#[derive(Serialize)]
+struct Redacted {
+    set: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    length: Option<usize>,
+}
+
+impl Redacted {
+    fn of(value: Option<&str>) -> Self {
+        Self {
+            set: value.is_some(),
+            length: value.map(str::len),
+        }
+    }
+}
+
+pub(crate) fn secret<S: Serializer>(
+    value: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    Redacted::of(Some(value)).serialize(serializer)
+}
+
+pub(crate) fn optional_secret<S: Serializer>(
+    value: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    Redacted::of(value.as_deref()).serialize(serializer)
+}
+
+pub(crate) fn secs<S: Serializer>(
+    value: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(value.as_secs())
+}
+
+pub(crate) fn optional_secs<S: Serializer>(
+    value: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.map(|value| value.as_secs()).serialize(serializer)
+}
+
+pub(crate) fn millis<S: Serializer>(
+    value: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(value.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::AuthConfig;
+
+    #[test]
+    fn secrets_are_written_as_set_and_length_only() {
+        let auth = AuthConfig {
+            password_pepper: "s3cret-pepper".into(),
+            token_key: "k".repeat(32),
+            setup_token: None,
+            session_ttl: Duration::from_secs(900),
+            refresh_ttl: Duration::from_secs(86_400),
+            device_challenge_ttl: Duration::from_secs(300),
+            clock_skew_leeway: Duration::from_secs(5),
+            strict_device_ids: false,
+        };
+
+        let value = serde_json::to_value(&auth).unwrap();
+        assert!(!value.to_string().contains("s3cret"));
+        assert_eq!(
+            value["password_pepper"],
+            json!({"set": true, "length": 13})
+        );
+        assert_eq!(value["token_key"], json!({"set": true, "length": 32}));
+        assert_eq!(value["setup_token"], json!({"set": false}));
+        assert_eq!(value["session_ttl"], json!(900));
+    }
+}
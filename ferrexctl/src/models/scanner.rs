@@ -4,8 +4,10 @@ use ferrex_model::scan::{
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     env, fs,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 fn default_video_extensions() -> Vec<String> {
@@ -13,7 +15,8 @@ fn default_video_extensions() -> Vec<String> {
 }
 
 /// Source that produced the scanner configuration.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(tag = "kind", content = "path", rename_all = "snake_case")]
 pub enum ScannerConfigSource {
     #[default]
     Default,
@@ -41,11 +44,43 @@ pub struct ScannerConfig {
     /// declares the bulk scan complete. Shorter windows flip to maintenance
     /// faster; longer windows help when the filesystem reports changes slowly.
     pub quiescence_window_ms: u64,
-    /// File extensions treated as video assets by the filesystem watcher.
-    /// Defaults mirror the core's built-in allow-list so future user overrides
-    /// can flow through without diverging behaviour.
+    /// Per-library `quiescence_window_ms`, keyed by library id or name.
+    /// Libraries not listed use the global window; a fast local disk can
+    /// settle sooner than a network mount that reports changes late.
+    pub library_quiescence_window_ms: HashMap<String, u64>,
+    /// File extensions treated as video assets by scans and the filesystem
+    /// watcher. Setting this replaces the built-in allow-list; use
+    /// `extra_video_extensions` to only add to it. Case and a leading dot
+    /// are ignored (`.MKV` is `mkv`).
     #[serde(default = "default_video_extensions")]
     pub video_extensions: Vec<String>,
+    /// Extensions added to `video_extensions`.
+    pub extra_video_extensions: Vec<String>,
+    /// Files smaller than this many bytes are skipped, e.g. to keep
+    /// `sample.mkv` clips out of the library. Unset admits any size.
+    pub min_file_size_bytes: Option<u64>,
+    /// Files larger than this many bytes are skipped. Unset admits any size.
+    pub max_file_size_bytes: Option<u64>,
+    /// Download the primary poster of every newly added movie and series once
+    /// a scan completes, so the first library view is not a grid of blanks.
+    /// Off by default; the pass fetches one poster at a time to leave the
+    /// shared image download permits free for on-demand requests.
+    pub prewarm_posters: bool,
+    /// Library media events kept in memory so reconnecting players can replay
+    /// what they missed via `Last-Event-ID`. Players that fall further behind
+    /// are told to reload the library instead.
+    pub media_event_history: usize,
+    /// Run a deletion reconcile over every enabled library this often
+    /// (seconds), dropping records and cached images of files deleted from
+    /// disk between scans. Unset leaves it to `POST
+    /// /libraries/{id}/reconcile`.
+    pub deletion_reconcile_interval_secs: Option<u64>,
+    /// Library roots pinned to a named device, keyed by path prefix. Roots
+    /// are otherwise grouped by the filesystem device they live on, and each
+    /// group shares `orchestrator.queue.max_parallel_scans_per_device`. Use
+    /// this when detection is wrong, e.g. a symlink farm whose targets sit
+    /// on one NAS.
+    pub device_overrides: HashMap<PathBuf, String>,
 }
 
 impl Default for ScannerConfig {
@@ -63,27 +98,116 @@ impl Default for ScannerConfig {
             // Should make this num_cpus?
             library_actor_max_outstanding_jobs: 32,
             quiescence_window_ms: 5_000,
+            library_quiescence_window_ms: HashMap::new(),
             video_extensions: default_video_extensions(),
+            extra_video_extensions: Vec::new(),
+            min_file_size_bytes: None,
+            max_file_size_bytes: None,
+            prewarm_posters: false,
+            media_event_history: 512,
+            deletion_reconcile_interval_secs: None,
+            device_overrides: HashMap::new(),
         }
     }
 }
 
 impl ScannerConfig {
+    /// Normalize the extension lists (lowercase, no leading dot, no
+    /// duplicates) and check them and the size bounds.
+    pub fn normalize_file_rules(&mut self) -> anyhow::Result<()> {
+        fn normalize(
+            list: &[String],
+            field: &str,
+        ) -> anyhow::Result<Vec<String>> {
+            let mut out: Vec<String> = Vec::with_capacity(list.len());
+            for raw in list {
+                let ext =
+                    raw.trim().trim_start_matches('.').to_ascii_lowercase();
+                if ext.is_empty()
+                    || !ext.chars().all(|c| c.is_ascii_alphanumeric())
+                {
+                    return Err(anyhow!(
+                        "scanner.{field} entry `{raw}` is not a file extension"
+                    ));
+                }
+                if !out.contains(&ext) {
+                    out.push(ext);
+                }
+            }
+            Ok(out)
+        }
+
+        self.video_extensions =
+            normalize(&self.video_extensions, "video_extensions")?;
+        self.extra_video_extensions =
+            normalize(&self.extra_video_extensions, "extra_video_extensions")?;
+        if self.effective_video_extensions().is_empty() {
+            return Err(anyhow!(
+                "scanner.video_extensions is empty; no file would be scanned"
+            ));
+        }
+        if let (Some(min), Some(max)) =
+            (self.min_file_size_bytes, self.max_file_size_bytes)
+            && min > max
+        {
+            return Err(anyhow!(
+                "scanner.min_file_size_bytes ({min}) exceeds scanner.max_file_size_bytes ({max})"
+            ));
+        }
+        Ok(())
+    }
+
+    /// `video_extensions` followed by any extra extensions not already
+    /// listed.
+    pub fn effective_video_extensions(&self) -> Vec<String> {
+        let mut all = self.video_extensions.clone();
+        for ext in &self.extra_video_extensions {
+            if !all.contains(ext) {
+                all.push(ext.clone());
+            }
+        }
+        all
+    }
+
+    /// The quiescence window configured for one library, looked up by id
+    /// first and then by name. `None` means the global window applies.
+    pub fn library_quiescence_window(
+        &self,
+        library_id: &str,
+        library_name: &str,
+    ) -> Option<Duration> {
+        self.library_quiescence_window_ms
+            .get(library_id)
+            .or_else(|| self.library_quiescence_window_ms.get(library_name))
+            .map(|ms| Duration::from_millis(*ms))
+    }
+
     /// Load scanner configuration overrides using environment variables.
     /// Evaluation order:
     /// 1) `$SCANNER_CONFIG_PATH` (TOML or JSON file),
     /// 2) `$SCANNER_CONFIG_JSON` (inline JSON),
     /// 3) defaults if neither is set.
     pub fn load_from_env() -> anyhow::Result<(Self, ScannerConfigSource)> {
-        if let Ok(path_str) = env::var("SCANNER_CONFIG_PATH")
-            && !path_str.trim().is_empty()
+        Self::load_from(
+            env::var("SCANNER_CONFIG_PATH").ok().map(PathBuf::from),
+            env::var("SCANNER_CONFIG_JSON").ok(),
+        )
+    }
+
+    /// Like [`Self::load_from_env`], with the values of
+    /// `$SCANNER_CONFIG_PATH` and `$SCANNER_CONFIG_JSON` passed in.
+    pub fn load_from(
+        config_path: Option<PathBuf>,
+        config_json: Option<String>,
+    ) -> anyhow::Result<(Self, ScannerConfigSource)> {
+        if let Some(path) = config_path
+            && !path.to_string_lossy().trim().is_empty()
         {
-            let path = PathBuf::from(path_str);
             let config = Self::load_from_file(&path)?;
             return Ok((config, ScannerConfigSource::EnvPath(path)));
         }
 
-        if let Ok(raw) = env::var("SCANNER_CONFIG_JSON")
+        if let Some(raw) = config_json
             && !raw.trim().is_empty()
         {
             let parsed = Self::parse_json(&raw)
@@ -158,3 +282,54 @@ impl ScannerConfig {
             .map(|path| path.to_path_buf())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_rules_are_normalized_and_checked() {
+        let mut config = ScannerConfig {
+            video_extensions: vec![".MKV".into(), "mp4".into(), "mkv".into()],
+            extra_video_extensions: vec!["TS".into(), "mp4".into()],
+            ..ScannerConfig::default()
+        };
+        config.normalize_file_rules().unwrap();
+        assert_eq!(config.video_extensions, ["mkv", "mp4"]);
+        assert_eq!(config.effective_video_extensions(), ["mkv", "mp4", "ts"]);
+
+        let mut bad = ScannerConfig {
+            extra_video_extensions: vec!["m k v".into()],
+            ..ScannerConfig::default()
+        };
+        assert!(bad.normalize_file_rules().is_err());
+
+        let mut inverted = ScannerConfig {
+            min_file_size_bytes: Some(10),
+            max_file_size_bytes: Some(5),
+            ..ScannerConfig::default()
+        };
+        assert!(inverted.normalize_file_rules().is_err());
+    }
+
+    #[test]
+    fn library_quiescence_prefers_id_over_name() {
+        let config = ScannerConfig {
+            library_quiescence_window_ms: HashMap::from([
+                ("Movies".to_string(), 1_000),
+                ("0190-movies".to_string(), 500),
+                ("NAS Shows".to_string(), 30_000),
+            ]),
+            ..ScannerConfig::default()
+        };
+        assert_eq!(
+            config.library_quiescence_window("0190-movies", "Movies"),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            config.library_quiescence_window("0190-shows", "NAS Shows"),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(config.library_quiescence_window("0190-x", "Music"), None);
+    }
+}
@@ -1,9 +1,13 @@
+use ferrex_model::image::ThumbnailStrategy;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use crate::util::{parse_bool_var, parse_csv_var, rate_limit_spec_from_env};
+use crate::util::{parse_bool, parse_csv, rate_limit_spec_from};
 
-use super::{rate_limits::RateLimitSpec, scanner::ScannerConfig};
+use super::{
+    LogFormat, RedisOutagePolicy, rate_limits::RateLimitSpec,
+    scanner::ScannerConfig,
+};
 
 /// Raw configuration as defined in a TOML file.
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
@@ -17,6 +21,8 @@ pub struct FileConfig {
     #[serde(default)]
     pub media: FileMediaConfig,
     #[serde(default)]
+    pub locale: FileLocaleConfig,
+    #[serde(default)]
     pub cache: FileCacheConfig,
     #[serde(default)]
     pub ffmpeg: FileFfmpegConfig,
@@ -37,6 +43,28 @@ pub struct FileServerConfig {
     pub host: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id_header: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slow_request_timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_streams: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_transcodes: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_bandwidth_limit_kbps: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_media_redirect_hosts: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression_min_bytes: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_format: Option<LogFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
@@ -50,12 +78,44 @@ pub struct FileDatabaseConfig {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FileRedisConfig {
     pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command_timeout_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outage_policy: Option<RedisOutagePolicy>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct FileLocaleConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct FileMediaConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata_languages: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename_rules: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub movie_defaults: Option<FileLibraryListingDefaults>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series_defaults: Option<FileLibraryListingDefaults>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct FileLibraryListingDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
@@ -68,6 +128,34 @@ pub struct FileCacheConfig {
     pub transcode: Option<PathBuf>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thumbnails: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hls_max_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_free_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_failure_ttl_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypt_at_rest: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_store: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_store_s3: Option<FileS3StoreConfig>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct FileS3StoreConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
@@ -76,6 +164,14 @@ pub struct FileFfmpegConfig {
     pub ffmpeg_path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ffprobe_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_strategy: Option<ThumbnailStrategy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_max_height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_quality: Option<u8>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
@@ -96,6 +192,10 @@ pub struct FileSecurityConfig {
     pub enforce_https: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trust_proxy_headers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trusted_proxies: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_auth_for_browsing: Option<bool>,
     #[serde(default)]
     pub hsts: FileHstsConfig,
 }
@@ -118,6 +218,16 @@ pub struct FileAuthConfig {
     pub token_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub setup_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_ttl_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_ttl_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_challenge_ttl_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock_skew_leeway_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_device_ids: Option<bool>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
@@ -133,6 +243,17 @@ pub struct FileRateLimiterConfig {
 pub struct EnvConfig {
     pub server_host: Option<String>,
     pub server_port: Option<u16>,
+    pub request_id_header: Option<String>,
+    pub request_timeout_secs: Option<u64>,
+    pub slow_request_timeout_secs: Option<u64>,
+    pub max_concurrent_streams: Option<usize>,
+    pub max_concurrent_transcodes: Option<usize>,
+    pub stream_bandwidth_limit_kbps: Option<u64>,
+    pub remote_media_redirect_hosts: Option<Vec<String>>,
+    pub compression_enabled: Option<bool>,
+    pub compression_min_bytes: Option<u16>,
+    pub log_format: Option<String>,
+    pub log_level: Option<String>,
     pub database_url: Option<String>,
     pub database_url_file: Option<PathBuf>,
     pub database_host: Option<String>,
@@ -144,13 +265,37 @@ pub struct EnvConfig {
     pub ferrex_app_password: Option<String>,
     pub ferrex_app_password_file: Option<PathBuf>,
     pub redis_url: Option<String>,
+    pub redis_pool_size: Option<usize>,
+    pub redis_connect_timeout_secs: Option<u64>,
+    pub redis_command_timeout_ms: Option<u64>,
+    pub redis_outage_policy: Option<String>,
     pub media_root: Option<PathBuf>,
+    pub metadata_languages: Option<Vec<String>>,
+    pub default_language: Option<String>,
+    pub default_region: Option<String>,
+    pub movie_default_sort: Option<String>,
+    pub movie_default_filter: Option<String>,
+    pub series_default_sort: Option<String>,
+    pub series_default_filter: Option<String>,
     pub cache_root: Option<PathBuf>,
     pub cache_images: Option<PathBuf>,
     pub cache_transcode: Option<PathBuf>,
     pub cache_thumbnails: Option<PathBuf>,
+    pub cache_encryption: Option<bool>,
+    pub cache_encryption_key: Option<String>,
+    pub image_failure_ttl_secs: Option<u64>,
+    pub image_store: Option<String>,
+    pub image_store_s3_bucket: Option<String>,
+    pub image_store_s3_region: Option<String>,
+    pub image_store_s3_endpoint: Option<String>,
+    pub image_store_s3_prefix: Option<String>,
+    pub image_store_s3_public_url: Option<String>,
     pub ffmpeg_path: Option<String>,
     pub ffprobe_path: Option<String>,
+    pub thumbnail_strategy: Option<String>,
+    pub thumbnail_width: Option<u32>,
+    pub thumbnail_max_height: Option<u32>,
+    pub thumbnail_quality: Option<u8>,
     pub cors_allowed_origins: Option<Vec<String>>,
     pub cors_allowed_methods: Option<Vec<String>>,
     pub cors_allowed_headers: Option<Vec<String>>,
@@ -158,12 +303,19 @@ pub struct EnvConfig {
     pub dev_mode: Option<bool>,
     pub enforce_https: Option<bool>,
     pub trust_proxy_headers: Option<bool>,
+    pub trusted_proxies: Option<Vec<String>>,
+    pub require_auth_for_browsing: Option<bool>,
     pub hsts_max_age: Option<u64>,
     pub hsts_include_subdomains: Option<bool>,
     pub hsts_preload: Option<bool>,
     pub auth_password_pepper: Option<String>,
     pub auth_token_key: Option<String>,
     pub setup_token: Option<String>,
+    pub auth_session_ttl_secs: Option<u64>,
+    pub auth_refresh_ttl_secs: Option<u64>,
+    pub auth_device_challenge_ttl_secs: Option<u64>,
+    pub auth_clock_skew_leeway_secs: Option<u64>,
+    pub auth_strict_device_ids: Option<bool>,
     pub rate_limits: Option<RateLimitSpec>,
     pub scanner_config_path: Option<PathBuf>,
     pub scanner_config_json: Option<String>,
@@ -171,68 +323,122 @@ pub struct EnvConfig {
 
 impl EnvConfig {
     pub fn gather() -> Self {
+        Self::gather_from(|key| std::env::var(key).ok())
+    }
+
+    /// Like [`Self::gather`], reading variables through `var` instead of
+    /// the process environment.
+    pub fn gather_from(var: impl Fn(&str) -> Option<String>) -> Self {
+        let csv = |name: &str| var(name).map(|raw| parse_csv(&raw));
+        let flag = |name: &str| var(name).and_then(|raw| parse_bool(&raw));
         Self {
-            server_host: std::env::var("SERVER_HOST").ok(),
-            server_port: std::env::var("SERVER_PORT")
-                .ok()
+            server_host: var("SERVER_HOST"),
+            server_port: var("SERVER_PORT").and_then(|s| s.parse().ok()),
+            request_id_header: var("REQUEST_ID_HEADER"),
+            request_timeout_secs: var("REQUEST_TIMEOUT_SECS")
                 .and_then(|s| s.parse().ok()),
-            database_url: std::env::var("DATABASE_URL").ok(),
-            database_url_file: std::env::var("DATABASE_URL_FILE")
-                .ok()
-                .map(PathBuf::from),
-            database_host: std::env::var("DATABASE_HOST").ok(),
-            database_port: std::env::var("DATABASE_PORT")
-                .ok()
-                .and_then(|s| s.parse().ok()),
-            database_user: std::env::var("DATABASE_USER").ok(),
-            database_name: std::env::var("DATABASE_NAME").ok(),
-            database_password: std::env::var("DATABASE_PASSWORD").ok(),
-            database_password_file: std::env::var("DATABASE_PASSWORD_FILE")
-                .ok()
-                .map(PathBuf::from),
-            ferrex_app_password: std::env::var("FERREX_APP_PASSWORD").ok(),
-            ferrex_app_password_file: std::env::var("FERREX_APP_PASSWORD_FILE")
-                .ok()
-                .map(PathBuf::from),
-            redis_url: std::env::var("REDIS_URL").ok(),
-            media_root: std::env::var("MEDIA_ROOT").ok().map(PathBuf::from),
-            cache_root: std::env::var("CACHE_DIR").ok().map(PathBuf::from),
-            cache_images: std::env::var("IMAGE_CACHE_DIR")
-                .ok()
-                .map(PathBuf::from),
-            cache_transcode: std::env::var("TRANSCODE_CACHE_DIR")
-                .ok()
+            slow_request_timeout_secs: var("SLOW_REQUEST_TIMEOUT_SECS")
+                .and_then(|s| s.parse().ok()),
+            max_concurrent_streams: var("MAX_CONCURRENT_STREAMS")
+                .and_then(|s| s.parse().ok()),
+            max_concurrent_transcodes: var("MAX_CONCURRENT_TRANSCODES")
+                .and_then(|s| s.parse().ok()),
+            stream_bandwidth_limit_kbps: var("STREAM_BANDWIDTH_LIMIT_KBPS")
+                .and_then(|s| s.parse().ok()),
+            remote_media_redirect_hosts: csv("REMOTE_MEDIA_REDIRECT_HOSTS"),
+            compression_enabled: flag("COMPRESSION_ENABLED"),
+            compression_min_bytes: var("COMPRESSION_MIN_BYTES")
+                .and_then(|s| s.parse().ok()),
+            log_format: var("LOG_FORMAT"),
+            log_level: var("LOG_LEVEL")
+                .filter(|level| !level.trim().is_empty()),
+            database_url: var("DATABASE_URL"),
+            database_url_file: var("DATABASE_URL_FILE").map(PathBuf::from),
+            database_host: var("DATABASE_HOST"),
+            database_port: var("DATABASE_PORT").and_then(|s| s.parse().ok()),
+            database_user: var("DATABASE_USER"),
+            database_name: var("DATABASE_NAME"),
+            database_password: var("DATABASE_PASSWORD"),
+            database_password_file: var("DATABASE_PASSWORD_FILE")
                 .map(PathBuf::from),
-            cache_thumbnails: std::env::var("THUMBNAIL_CACHE_DIR")
-                .ok()
+            ferrex_app_password: var("FERREX_APP_PASSWORD"),
+            ferrex_app_password_file: var("FERREX_APP_PASSWORD_FILE")
                 .map(PathBuf::from),
-            ffmpeg_path: std::env::var("FFMPEG_PATH").ok(),
-            ffprobe_path: std::env::var("FFPROBE_PATH").ok(),
+            redis_url: var("REDIS_URL"),
+            redis_pool_size: var("REDIS_POOL_SIZE")
+                .and_then(|s| s.parse().ok()),
+            redis_connect_timeout_secs: var("REDIS_CONNECT_TIMEOUT_SECS")
+                .and_then(|s| s.parse().ok()),
+            redis_command_timeout_ms: var("REDIS_COMMAND_TIMEOUT_MS")
+                .and_then(|s| s.parse().ok()),
+            redis_outage_policy: var("REDIS_OUTAGE_POLICY"),
+            media_root: var("MEDIA_ROOT").map(PathBuf::from),
+            metadata_languages: csv("TMDB_LANG"),
+            default_language: var("DEFAULT_LANGUAGE")
+                .filter(|value| !value.trim().is_empty()),
+            default_region: var("DEFAULT_REGION")
+                .filter(|value| !value.trim().is_empty()),
+            movie_default_sort: var("MOVIE_DEFAULT_SORT"),
+            movie_default_filter: var("MOVIE_DEFAULT_FILTER"),
+            series_default_sort: var("SERIES_DEFAULT_SORT"),
+            series_default_filter: var("SERIES_DEFAULT_FILTER"),
+            cache_root: var("CACHE_DIR").map(PathBuf::from),
+            cache_images: var("IMAGE_CACHE_DIR").map(PathBuf::from),
+            cache_transcode: var("TRANSCODE_CACHE_DIR").map(PathBuf::from),
+            cache_thumbnails: var("THUMBNAIL_CACHE_DIR").map(PathBuf::from),
+            cache_encryption: flag("CACHE_ENCRYPTION"),
+            cache_encryption_key: var("CACHE_ENCRYPTION_KEY"),
+            image_failure_ttl_secs: var("IMAGE_FAILURE_TTL_SECS")
+                .and_then(|s| s.parse().ok()),
+            image_store: var("IMAGE_STORE"),
+            image_store_s3_bucket: var("IMAGE_STORE_S3_BUCKET"),
+            image_store_s3_region: var("IMAGE_STORE_S3_REGION"),
+            image_store_s3_endpoint: var("IMAGE_STORE_S3_ENDPOINT"),
+            image_store_s3_prefix: var("IMAGE_STORE_S3_PREFIX"),
+            image_store_s3_public_url: var("IMAGE_STORE_S3_PUBLIC_URL"),
+            ffmpeg_path: var("FFMPEG_PATH"),
+            ffprobe_path: var("FFPROBE_PATH"),
+            thumbnail_strategy: var("THUMBNAIL_STRATEGY"),
+            thumbnail_width: var("THUMBNAIL_WIDTH")
+                .and_then(|s| s.parse().ok()),
+            thumbnail_max_height: var("THUMBNAIL_MAX_HEIGHT")
+                .and_then(|s| s.parse().ok()),
+            thumbnail_quality: var("THUMBNAIL_QUALITY")
+                .and_then(|s| s.parse().ok()),
 
-            cors_allowed_origins: parse_csv_var("CORS_ALLOWED_ORIGINS"),
-            cors_allowed_methods: parse_csv_var("CORS_ALLOWED_METHODS"),
-            cors_allowed_headers: parse_csv_var("CORS_ALLOWED_HEADERS"),
-            cors_allow_credentials: parse_bool_var("CORS_ALLOW_CREDENTIALS"),
+            cors_allowed_origins: csv("CORS_ALLOWED_ORIGINS"),
+            cors_allowed_methods: csv("CORS_ALLOWED_METHODS"),
+            cors_allowed_headers: csv("CORS_ALLOWED_HEADERS"),
+            cors_allow_credentials: flag("CORS_ALLOW_CREDENTIALS"),
 
-            dev_mode: parse_bool_var("DEV_MODE"),
-            enforce_https: parse_bool_var("ENFORCE_HTTPS"),
-            trust_proxy_headers: parse_bool_var("TRUST_PROXY_HEADERS"),
-            hsts_max_age: std::env::var("HSTS_MAX_AGE")
-                .ok()
-                .and_then(|s| s.parse().ok()),
-            hsts_include_subdomains: parse_bool_var("HSTS_INCLUDE_SUBDOMAINS"),
-            hsts_preload: parse_bool_var("HSTS_PRELOAD"),
+            dev_mode: flag("DEV_MODE"),
+            enforce_https: flag("ENFORCE_HTTPS"),
+            trust_proxy_headers: flag("TRUST_PROXY_HEADERS"),
+            trusted_proxies: csv("TRUSTED_PROXIES"),
+            require_auth_for_browsing: flag("REQUIRE_AUTH_FOR_BROWSING"),
+            hsts_max_age: var("HSTS_MAX_AGE").and_then(|s| s.parse().ok()),
+            hsts_include_subdomains: flag("HSTS_INCLUDE_SUBDOMAINS"),
+            hsts_preload: flag("HSTS_PRELOAD"),
 
-            auth_password_pepper: std::env::var("AUTH_PASSWORD_PEPPER").ok(),
-            auth_token_key: std::env::var("AUTH_TOKEN_KEY").ok(),
-            setup_token: std::env::var("FERREX_SETUP_TOKEN").ok(),
+            auth_password_pepper: var("AUTH_PASSWORD_PEPPER"),
+            auth_token_key: var("AUTH_TOKEN_KEY"),
+            setup_token: var("FERREX_SETUP_TOKEN"),
+            auth_session_ttl_secs: var("AUTH_SESSION_TTL_SECS")
+                .and_then(|s| s.parse().ok()),
+            auth_refresh_ttl_secs: var("AUTH_REFRESH_TTL_SECS")
+                .and_then(|s| s.parse().ok()),
+            auth_device_challenge_ttl_secs: var(
+                "AUTH_DEVICE_CHALLENGE_TTL_SECS",
+            )
+            .and_then(|s| s.parse().ok()),
+            auth_clock_skew_leeway_secs: var("AUTH_CLOCK_SKEW_LEEWAY_SECS")
+                .and_then(|s| s.parse().ok()),
+            auth_strict_device_ids: flag("AUTH_STRICT_DEVICE_IDS"),
 
-            rate_limits: rate_limit_spec_from_env(),
+            rate_limits: rate_limit_spec_from(&var),
 
-            scanner_config_path: std::env::var("SCANNER_CONFIG_PATH")
-                .ok()
-                .map(PathBuf::from),
-            scanner_config_json: std::env::var("SCANNER_CONFIG_JSON").ok(),
+            scanner_config_path: var("SCANNER_CONFIG_PATH").map(PathBuf::from),
+            scanner_config_json: var("SCANNER_CONFIG_JSON"),
         }
     }
 }
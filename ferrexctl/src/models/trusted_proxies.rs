@@ -0,0 +1,128 @@
+use std::{fmt, net::IpAddr, str::FromStr};
+
+/// Ranges trusted when `security.trusted_proxies` is not set: loopback and
+/// the private networks reverse proxies usually run on.
+pub const DEFAULT_TRUSTED_PROXIES: &[&str] = &[
+    "127.0.0.0/8",
+    "::1/128",
+    "10.0.0.0/8",
+    "172.16.0.0/12",
+    "192.168.0.0/16",
+    "fc00::/7",
+];
+
+pub fn default_trusted_proxies() -> Vec<String> {
+    DEFAULT_TRUSTED_PROXIES
+        .iter()
+        .map(|range| range.to_string())
+        .collect()
+}
+
+/// An address range in CIDR notation (`10.0.0.0/8`, `fd00::/8`). A bare
+/// address is a range of one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        // IPv4 peers on a dual-stack socket arrive as `::ffff:a.b.c.d`.
+        let addr = match addr {
+            IpAddr::V6(v6) => v6
+                .to_ipv4_mapped()
+                .map(IpAddr::V4)
+                .unwrap_or(IpAddr::V6(v6)),
+            v4 => v4,
+        };
+        match (self.network, addr) {
+            (IpAddr::V4(net), IpAddr::V4(addr)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(net) & mask == u32::from(addr) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(addr)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(net) & mask == u128::from(addr) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let raw = raw.trim();
+        let (addr, prefix) = match raw.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (raw, None),
+        };
+        let network: IpAddr = addr
+            .parse()
+            .map_err(|_| format!("`{addr}` is not an IP address"))?;
+        let max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max)
+                .ok_or_else(|| {
+                    format!("prefix length `{prefix}` must be 0..={max}")
+                })?,
+            None => max,
+        };
+        Ok(Self { network, prefix })
+    }
+}
+
+impl fmt::Display for IpRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(raw: &str) -> IpAddr {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn ranges_match_their_members() {
+        let lan: IpRange = "192.168.0.0/16".parse().unwrap();
+        assert!(lan.contains(ip("192.168.4.2")));
+        assert!(lan.contains(ip("::ffff:192.168.4.2")));
+        assert!(!lan.contains(ip("192.169.0.1")));
+        assert!(!lan.contains(ip("fd00::1")));
+
+        let single: IpRange = "203.0.113.7".parse().unwrap();
+        assert_eq!(single.to_string(), "203.0.113.7/32");
+        assert!(single.contains(ip("203.0.113.7")));
+        assert!(!single.contains(ip("203.0.113.8")));
+
+        let ula: IpRange = "fc00::/7".parse().unwrap();
+        assert!(ula.contains(ip("fd12::1")));
+        assert!(!ula.contains(ip("2001:db8::1")));
+
+        let any: IpRange = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains(ip("8.8.8.8")));
+    }
+
+    #[test]
+    fn malformed_ranges_are_rejected() {
+        for raw in ["10.0.0.0/33", "::/129", "10.0.0/8", "proxy", "10.0.0.0/"] {
+            assert!(raw.parse::<IpRange>().is_err(), "{raw}");
+        }
+        for raw in DEFAULT_TRUSTED_PROXIES {
+            assert!(raw.parse::<IpRange>().is_ok(), "{raw}");
+        }
+    }
+}
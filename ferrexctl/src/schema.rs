@@ -0,0 +1,907 @@
+//! Typed description of every configuration key.
+//!
+//! [`config_schema`] lists each key the loader reads with its environment
+//! variable, value type, default, and whether it is secret, managed by
+//! `ferrexctl init`, or required. Defaults come from the same constants the
+//! loader falls back to, and the managed/secret-file flags from
+//! [`MANAGED_KEYS`] and [`SECRET_FILE_KEYS`], so guided setup and external
+//! validators see what the server will actually do.
+
+use std::path::PathBuf;
+
+use ferrex_model::image::ThumbnailStrategy;
+use serde::Serialize;
+
+use crate::{
+    constants::{
+        DEFAULT_CACHE_DIR, DEFAULT_CLOCK_SKEW_LEEWAY_SECS,
+        DEFAULT_COMPRESSION_MIN_BYTES, DEFAULT_DATABASE_PORT,
+        DEFAULT_DEVICE_CHALLENGE_TTL_SECS, DEFAULT_FFMPEG_PATH,
+        DEFAULT_FFPROBE_PATH, DEFAULT_HSTS_MAX_AGE_SECS,
+        DEFAULT_IMAGE_FAILURE_TTL_SECS, DEFAULT_LOCALE_LANGUAGE,
+        DEFAULT_MAX_CONCURRENT_STREAMS, DEFAULT_MAX_CONCURRENT_TRANSCODES,
+        DEFAULT_REDIS_COMMAND_TIMEOUT_MS, DEFAULT_REDIS_CONNECT_TIMEOUT_SECS,
+        DEFAULT_REDIS_POOL_SIZE, DEFAULT_REFRESH_TTL_SECS,
+        DEFAULT_REQUEST_ID_HEADER, DEFAULT_REQUEST_TIMEOUT_SECS,
+        DEFAULT_SERVER_HOST, DEFAULT_SERVER_PORT, DEFAULT_SESSION_TTL_SECS,
+        DEFAULT_SLOW_REQUEST_TIMEOUT_SECS, DEFAULT_THUMBNAIL_QUALITY,
+        DEFAULT_THUMBNAIL_WIDTH, LIBRARY_LISTING_FILTERS, LIBRARY_SORT_FIELDS,
+        MANAGED_KEYS, SECRET_FILE_KEYS,
+    },
+    loader::{
+        default_cors_headers, default_cors_methods, default_cors_origins,
+    },
+    models::{
+        ConfigMetadata, ImageStoreBackend, LogFormat, RedisOutagePolicy,
+        trusted_proxies::DEFAULT_TRUSTED_PROXIES,
+    },
+};
+
+/// Secrets whose `<KEY>_FILE` variant is read by the database URL resolver
+/// rather than the generic secret-file pass.
+const DATABASE_FILE_KEYS: &[&str] = &["FERREX_APP_PASSWORD"];
+
+/// Part of [`Config`](crate::Config) a key feeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSection {
+    General,
+    Server,
+    Database,
+    Redis,
+    Media,
+    Locale,
+    Cache,
+    Ffmpeg,
+    Cors,
+    Security,
+    Auth,
+    RateLimiter,
+    Scanner,
+}
+
+/// Shape of a key's value as written in the environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueType {
+    String,
+    Integer,
+    /// `1/true/yes/on` or `0/false/no/off`.
+    Bool,
+    Path,
+    Url,
+    /// Comma-separated list.
+    List,
+    /// Inline JSON document.
+    Json,
+}
+
+/// When a key must be set for the server to start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Requirement {
+    Optional,
+    /// Guard rails reject the default outside `DEV_MODE`.
+    OutsideDevMode,
+}
+
+/// One configuration key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigKeySpec {
+    /// Dotted path of the value in [`Config`](crate::Config).
+    pub key: &'static str,
+    pub env: &'static str,
+    pub section: ConfigSection,
+    pub value_type: ValueType,
+    /// Value used when the key is unset, as it would be written in `.env`.
+    pub default: Option<String>,
+    pub secret: bool,
+    /// Written and rotated by `ferrexctl init`.
+    pub managed: bool,
+    /// Also read from the file named by `<ENV>_FILE`.
+    pub accepts_file: bool,
+    pub required: Requirement,
+    /// Accepted values or value forms; empty when any value of the type is
+    /// accepted.
+    pub allowed_values: Vec<&'static str>,
+    pub description: &'static str,
+}
+
+impl ConfigKeySpec {
+    fn new(
+        key: &'static str,
+        env: &'static str,
+        section: ConfigSection,
+        value_type: ValueType,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            key,
+            env,
+            section,
+            value_type,
+            default: None,
+            secret: false,
+            managed: MANAGED_KEYS.contains(&env),
+            accepts_file: SECRET_FILE_KEYS.contains(&env)
+                || DATABASE_FILE_KEYS.contains(&env),
+            required: Requirement::Optional,
+            allowed_values: Vec::new(),
+            description,
+        }
+    }
+
+    fn with_default(mut self, value: impl ToString) -> Self {
+        self.default = Some(value.to_string());
+        self
+    }
+
+    fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+
+    fn required_outside_dev_mode(mut self) -> Self {
+        self.required = Requirement::OutsideDevMode;
+        self
+    }
+
+    fn allowed(mut self, values: &[&'static str]) -> Self {
+        self.allowed_values = values.to_vec();
+        self
+    }
+
+    /// Name of the `<ENV>_FILE` variable, when the key accepts one.
+    pub fn file_env(&self) -> Option<String> {
+        self.accepts_file.then(|| format!("{}_FILE", self.env))
+    }
+
+    /// Where the loaded value of this key came from, given the metadata of
+    /// that load and whether a variable is set in the environment.
+    pub fn source(
+        &self,
+        metadata: &ConfigMetadata,
+        is_set: impl Fn(&str) -> bool,
+    ) -> ValueSource {
+        if let Some(path) = metadata.secret_files.get(self.env) {
+            ValueSource::SecretFile { path: path.clone() }
+        } else if let Some(path) = metadata.fragment_sources.get(self.env) {
+            ValueSource::Fragment { path: path.clone() }
+        } else if is_set(self.env) {
+            ValueSource::Env
+        } else {
+            ValueSource::Default
+        }
+    }
+}
+
+/// Origin of a loaded value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValueSource {
+    /// Unset; the loader's default (or a value derived from other keys)
+    /// applies.
+    Default,
+    /// `.env` or the process environment.
+    Env,
+    /// An overlay fragment from `FERREX_CONFIG_DIR`.
+    Fragment { path: PathBuf },
+    /// The file named by `<ENV>_FILE`.
+    SecretFile { path: PathBuf },
+}
+
+/// Source of one key, as reported by [`value_sources`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeySource {
+    pub key: &'static str,
+    pub env: &'static str,
+    pub source: ValueSource,
+}
+
+/// Source of every key in [`config_schema`] for a load described by
+/// `metadata`, checked against the current process environment.
+pub fn value_sources(metadata: &ConfigMetadata) -> Vec<KeySource> {
+    config_schema()
+        .into_iter()
+        .map(|spec| KeySource {
+            key: spec.key,
+            env: spec.env,
+            source: spec
+                .source(metadata, |env| std::env::var_os(env).is_some()),
+        })
+        .collect()
+}
+
+/// Every key read by [`ConfigLoader`](crate::ConfigLoader), grouped by
+/// section in load order.
+pub fn config_schema() -> Vec<ConfigKeySpec> {
+    use ConfigSection as S;
+    use ValueType as T;
+
+    let spec = ConfigKeySpec::new;
+
+    vec![
+        spec(
+            "dev_mode",
+            "DEV_MODE",
+            S::General,
+            T::Bool,
+            "Relax production guard rails (default secrets, HTTP, wildcard CORS).",
+        )
+        .with_default(false),
+        spec(
+            "server.host",
+            "SERVER_HOST",
+            S::Server,
+            T::String,
+            "Address the HTTP server binds to.",
+        )
+        .with_default(DEFAULT_SERVER_HOST),
+        spec(
+            "server.port",
+            "SERVER_PORT",
+            S::Server,
+            T::Integer,
+            "Port the HTTP server binds to.",
+        )
+        .with_default(DEFAULT_SERVER_PORT),
+        spec(
+            "server.request_id_header",
+            "REQUEST_ID_HEADER",
+            S::Server,
+            T::String,
+            "Header an inbound correlation id is read from and echoed back in.",
+        )
+        .with_default(DEFAULT_REQUEST_ID_HEADER),
+        spec(
+            "server.request_timeout_secs",
+            "REQUEST_TIMEOUT_SECS",
+            S::Server,
+            T::Integer,
+            "Seconds an ordinary API request may run before it is answered with 504 Gateway Timeout; 0 disables the limit.",
+        )
+        .with_default(DEFAULT_REQUEST_TIMEOUT_SECS),
+        spec(
+            "server.slow_request_timeout_secs",
+            "SLOW_REQUEST_TIMEOUT_SECS",
+            S::Server,
+            T::Integer,
+            "Seconds an admin, scan or other heavy request may run before it is answered with 504 Gateway Timeout; 0 disables the limit. Streaming, SSE and WebSocket routes are never limited.",
+        )
+        .with_default(DEFAULT_SLOW_REQUEST_TIMEOUT_SECS),
+        spec(
+            "server.max_concurrent_streams",
+            "MAX_CONCURRENT_STREAMS",
+            S::Server,
+            T::Integer,
+            "Simultaneous direct-play streams (full files and range requests) before new ones are answered with 503 Service Unavailable and Retry-After; 0 removes the cap.",
+        )
+        .with_default(DEFAULT_MAX_CONCURRENT_STREAMS),
+        spec(
+            "server.max_concurrent_transcodes",
+            "MAX_CONCURRENT_TRANSCODES",
+            S::Server,
+            T::Integer,
+            "Simultaneous live transcodes for clients whose capability hint rules out direct play; further ones are answered with 503 Service Unavailable and Retry-After. 0 removes the cap.",
+        )
+        .with_default(DEFAULT_MAX_CONCURRENT_TRANSCODES),
+        spec(
+            "server.stream_bandwidth_limit_kbps",
+            "STREAM_BANDWIDTH_LIMIT_KBPS",
+            S::Server,
+            T::Integer,
+            "Best-effort bandwidth cap of each stream in kilobits per second, range requests included. Admins can override it per request with max_kbps. 0 streams unthrottled.",
+        )
+        .with_default(0),
+        spec(
+            "server.remote_media_redirect_hosts",
+            "REMOTE_MEDIA_REDIRECT_HOSTS",
+            S::Server,
+            T::List,
+            "Hosts of remote (http/https) media files that players are redirected to with 307 instead of having the file proxied. Redirected streams bypass the bandwidth cap.",
+        ),
+        spec(
+            "server.compression_enabled",
+            "COMPRESSION_ENABLED",
+            S::Server,
+            T::Bool,
+            "Compress JSON and plain-text responses with gzip or deflate when the client's Accept-Encoding allows it. Media, image, subtitle and event-stream bodies are never compressed.",
+        )
+        .with_default(true),
+        spec(
+            "server.compression_min_bytes",
+            "COMPRESSION_MIN_BYTES",
+            S::Server,
+            T::Integer,
+            "Smallest response body, in bytes, that is compressed.",
+        )
+        .with_default(DEFAULT_COMPRESSION_MIN_BYTES),
+        spec(
+            "server.log_format",
+            "LOG_FORMAT",
+            S::Server,
+            T::String,
+            "Log output format: pretty for terminals, json for one object per line carrying span fields such as the request id. Unknown values stop the server at startup.",
+        )
+        .with_default(LogFormat::default().as_str())
+        .allowed(&["pretty", "json"]),
+        spec(
+            "server.log_level",
+            "LOG_LEVEL",
+            S::Server,
+            T::String,
+            "Default log level filter, in RUST_LOG syntax (e.g. info,scan=debug). RUST_LOG takes precedence when set.",
+        ),
+        spec(
+            "database.primary_url",
+            "DATABASE_URL",
+            S::Database,
+            T::Url,
+            "PostgreSQL connection URL. When unset it is assembled from DATABASE_HOST, DATABASE_PORT, DATABASE_USER and DATABASE_NAME.",
+        )
+        .secret(),
+        spec(
+            "database.host",
+            "DATABASE_HOST",
+            S::Database,
+            T::String,
+            "Database host used to assemble DATABASE_URL.",
+        ),
+        spec(
+            "database.port",
+            "DATABASE_PORT",
+            S::Database,
+            T::Integer,
+            "Database port used to assemble DATABASE_URL.",
+        )
+        .with_default(DEFAULT_DATABASE_PORT),
+        spec(
+            "database.user",
+            "DATABASE_USER",
+            S::Database,
+            T::String,
+            "Database role used to assemble DATABASE_URL.",
+        ),
+        spec(
+            "database.name",
+            "DATABASE_NAME",
+            S::Database,
+            T::String,
+            "Database name used to assemble DATABASE_URL.",
+        ),
+        spec(
+            "database.password",
+            "DATABASE_PASSWORD",
+            S::Database,
+            T::String,
+            "Password added to an assembled or password-less DATABASE_URL.",
+        )
+        .secret(),
+        spec(
+            "database.app_password",
+            "FERREX_APP_PASSWORD",
+            S::Database,
+            T::String,
+            "Fallback database password when DATABASE_PASSWORD is unset.",
+        )
+        .secret(),
+        spec(
+            "redis.url",
+            "REDIS_URL",
+            S::Redis,
+            T::Url,
+            "Redis URL for rate limiting and caching; required for the rate limiter outside dev mode.",
+        )
+        .secret(),
+        spec(
+            "redis.pool_size",
+            "REDIS_POOL_SIZE",
+            S::Redis,
+            T::Integer,
+            "Multiplexed Redis connections opened at startup; commands are spread across them. Must be between 1 and 64.",
+        )
+        .with_default(DEFAULT_REDIS_POOL_SIZE),
+        spec(
+            "redis.connect_timeout_secs",
+            "REDIS_CONNECT_TIMEOUT_SECS",
+            S::Redis,
+            T::Integer,
+            "Seconds allowed for establishing a Redis connection.",
+        )
+        .with_default(DEFAULT_REDIS_CONNECT_TIMEOUT_SECS),
+        spec(
+            "redis.command_timeout_ms",
+            "REDIS_COMMAND_TIMEOUT_MS",
+            S::Redis,
+            T::Integer,
+            "Milliseconds a single Redis command may take before Redis is treated as unreachable.",
+        )
+        .with_default(DEFAULT_REDIS_COMMAND_TIMEOUT_MS),
+        spec(
+            "redis.outage_policy",
+            "REDIS_OUTAGE_POLICY",
+            S::Redis,
+            T::String,
+            "What the auth rate limiter does while Redis is unreachable: fail_open lets requests through unthrottled, fail_closed answers them with 503.",
+        )
+        .with_default(RedisOutagePolicy::default().as_str())
+        .allowed(&["fail_open", "fail_closed"]),
+        spec(
+            "media.root",
+            "MEDIA_ROOT",
+            S::Media,
+            T::Path,
+            "Directory libraries are created under.",
+        ),
+        spec(
+            "media.metadata_languages",
+            "TMDB_LANG",
+            S::Media,
+            T::List,
+            "Preferred TMDB metadata languages, most preferred first. Takes precedence over locale.language for metadata; when unset, metadata is requested in the deployment locale.",
+        )
+        .with_default("en-US"),
+        spec(
+            "media.movie_defaults.sort",
+            "MOVIE_DEFAULT_SORT",
+            S::Media,
+            T::String,
+            "Order of movie library listings when the client does not pick one, optionally suffixed with `:asc` or `:desc` (`date_added:desc`).",
+        )
+        .with_default("title")
+        .allowed(LIBRARY_SORT_FIELDS),
+        spec(
+            "media.movie_defaults.filter",
+            "MOVIE_DEFAULT_FILTER",
+            S::Media,
+            T::String,
+            "Watch-status filter applied to unfiltered movie library listings when the client does not pick one.",
+        )
+        .allowed(LIBRARY_LISTING_FILTERS),
+        spec(
+            "media.series_defaults.sort",
+            "SERIES_DEFAULT_SORT",
+            S::Media,
+            T::String,
+            "Order of series library listings when the client does not pick one, optionally suffixed with `:asc` or `:desc`.",
+        )
+        .with_default("title")
+        .allowed(LIBRARY_SORT_FIELDS),
+        spec(
+            "media.series_defaults.filter",
+            "SERIES_DEFAULT_FILTER",
+            S::Media,
+            T::String,
+            "Watch-status filter applied to unfiltered series library listings when the client does not pick one.",
+        )
+        .allowed(LIBRARY_LISTING_FILTERS),
+        spec(
+            "locale.language",
+            "DEFAULT_LANGUAGE",
+            S::Locale,
+            T::String,
+            "ISO 639-1 code of the deployment's language, reported to clients for formatting. A user's own language preference wins in clients; media.metadata_languages wins for metadata requests.",
+        )
+        .with_default(DEFAULT_LOCALE_LANGUAGE),
+        spec(
+            "locale.region",
+            "DEFAULT_REGION",
+            S::Locale,
+            T::String,
+            "ISO 3166-1 alpha-2 code of the deployment's region. Release dates and certifications of this country are preferred and it is sent with TMDB requests, replacing TMDB_REGION. Reported to clients for date formatting.",
+        ),
+        spec(
+            "cache.root",
+            "CACHE_DIR",
+            S::Cache,
+            T::Path,
+            "Root of the image, transcode and thumbnail caches.",
+        )
+        .with_default(DEFAULT_CACHE_DIR),
+        spec(
+            "cache.images",
+            "IMAGE_CACHE_DIR",
+            S::Cache,
+            T::Path,
+            "Image cache directory.",
+        )
+        .with_default(format!("{DEFAULT_CACHE_DIR}/images")),
+        spec(
+            "cache.transcode",
+            "TRANSCODE_CACHE_DIR",
+            S::Cache,
+            T::Path,
+            "Transcode and HLS segment cache directory.",
+        )
+        .with_default(format!("{DEFAULT_CACHE_DIR}/transcode")),
+        spec(
+            "cache.thumbnails",
+            "THUMBNAIL_CACHE_DIR",
+            S::Cache,
+            T::Path,
+            "Generated thumbnail cache directory.",
+        )
+        .with_default(format!("{DEFAULT_CACHE_DIR}/thumbnails")),
+        spec(
+            "cache.encrypt_at_rest",
+            "CACHE_ENCRYPTION",
+            S::Cache,
+            T::Bool,
+            "Encrypt cached images and HLS segments on disk; requires CACHE_ENCRYPTION_KEY.",
+        )
+        .with_default(false),
+        spec(
+            "cache.encryption_key",
+            "CACHE_ENCRYPTION_KEY",
+            S::Cache,
+            T::String,
+            "Key cached images and HLS segments are encrypted with; at least 32 characters.",
+        )
+        .secret(),
+        spec(
+            "cache.image_failure_ttl_secs",
+            "IMAGE_FAILURE_TTL_SECS",
+            S::Cache,
+            T::Integer,
+            "Seconds an image that failed to download permanently (e.g. a 404 from TMDB) is reported unavailable before it is tried again; 0 retries every request. Refreshing the media's images clears it.",
+        )
+        .with_default(DEFAULT_IMAGE_FAILURE_TTL_SECS),
+        spec(
+            "cache.image_store",
+            "IMAGE_STORE",
+            S::Cache,
+            T::String,
+            "Where materialized image blobs are kept: local files under the image cache, or an S3-compatible bucket (needs a server built with the s3 feature). The integrity-checked working copy always stays on local disk.",
+        )
+        .with_default(ImageStoreBackend::default().as_str())
+        .allowed(&["local", "s3"]),
+        spec(
+            "cache.image_store_s3.bucket",
+            "IMAGE_STORE_S3_BUCKET",
+            S::Cache,
+            T::String,
+            "Bucket image blobs are stored in; required when IMAGE_STORE=s3. Credentials come from the standard AWS_* variables.",
+        ),
+        spec(
+            "cache.image_store_s3.region",
+            "IMAGE_STORE_S3_REGION",
+            S::Cache,
+            T::String,
+            "Region of the image blob bucket; falls back to AWS_REGION.",
+        ),
+        spec(
+            "cache.image_store_s3.endpoint",
+            "IMAGE_STORE_S3_ENDPOINT",
+            S::Cache,
+            T::Url,
+            "Endpoint of an S3-compatible service such as MinIO or R2.",
+        ),
+        spec(
+            "cache.image_store_s3.prefix",
+            "IMAGE_STORE_S3_PREFIX",
+            S::Cache,
+            T::String,
+            "Key prefix image blobs are stored under.",
+        ),
+        spec(
+            "cache.image_store_s3.public_url",
+            "IMAGE_STORE_S3_PUBLIC_URL",
+            S::Cache,
+            T::Url,
+            "Public base URL of the bucket or a CDN in front of it. Image requests are redirected there; without it, or with cache encryption on, the server proxies blobs.",
+        ),
+        spec(
+            "ffmpeg.ffmpeg_path",
+            "FFMPEG_PATH",
+            S::Ffmpeg,
+            T::Path,
+            "ffmpeg binary.",
+        )
+        .with_default(DEFAULT_FFMPEG_PATH),
+        spec(
+            "ffmpeg.ffprobe_path",
+            "FFPROBE_PATH",
+            S::Ffmpeg,
+            T::Path,
+            "ffprobe binary.",
+        )
+        .with_default(DEFAULT_FFPROBE_PATH),
+        spec(
+            "ffmpeg.thumbnail_strategy",
+            "THUMBNAIL_STRATEGY",
+            S::Ffmpeg,
+            T::String,
+            "Frame selection for generated episode thumbnails.",
+        )
+        .with_default(ThumbnailStrategy::default())
+        .allowed(&[
+            "percentage:<0..1>",
+            "timestamp:<seconds>",
+            "best_frame[:<candidates>]",
+        ]),
+        spec(
+            "ffmpeg.thumbnail_width",
+            "THUMBNAIL_WIDTH",
+            S::Ffmpeg,
+            T::Integer,
+            "Width extracted video thumbnails are scaled to, keeping the aspect ratio; smaller sources are not upscaled. Between 64 and 1920.",
+        )
+        .with_default(DEFAULT_THUMBNAIL_WIDTH),
+        spec(
+            "ffmpeg.thumbnail_max_height",
+            "THUMBNAIL_MAX_HEIGHT",
+            S::Ffmpeg,
+            T::Integer,
+            "Optional cap on thumbnail height; taller results are scaled down further. Between 64 and 1920.",
+        ),
+        spec(
+            "ffmpeg.thumbnail_quality",
+            "THUMBNAIL_QUALITY",
+            S::Ffmpeg,
+            T::Integer,
+            "JPEG quality of generated thumbnails, between 30 and 100. Existing thumbnails keep their encoding until they are invalidated.",
+        )
+        .with_default(DEFAULT_THUMBNAIL_QUALITY),
+        spec(
+            "cors.allowed_origins",
+            "CORS_ALLOWED_ORIGINS",
+            S::Cors,
+            T::List,
+            "Origins allowed to call the API. `*` is rejected outside dev mode.",
+        )
+        .with_default(default_cors_origins().join(",")),
+        spec(
+            "cors.allowed_methods",
+            "CORS_ALLOWED_METHODS",
+            S::Cors,
+            T::List,
+            "HTTP methods allowed in CORS requests.",
+        )
+        .with_default(default_cors_methods().join(",")),
+        spec(
+            "cors.allowed_headers",
+            "CORS_ALLOWED_HEADERS",
+            S::Cors,
+            T::List,
+            "Request headers allowed in CORS requests.",
+        )
+        .with_default(default_cors_headers().join(",")),
+        spec(
+            "cors.allow_credentials",
+            "CORS_ALLOW_CREDENTIALS",
+            S::Cors,
+            T::Bool,
+            "Allow credentialed CORS requests.",
+        )
+        .with_default(false),
+        spec(
+            "security.enforce_https",
+            "ENFORCE_HTTPS",
+            S::Security,
+            T::Bool,
+            "Redirect plain HTTP to HTTPS. Defaults to false in dev mode.",
+        )
+        .with_default(true),
+        spec(
+            "security.trust_proxy_headers",
+            "TRUST_PROXY_HEADERS",
+            S::Security,
+            T::Bool,
+            "Honour X-Forwarded-* headers from a TLS-terminating proxy.",
+        )
+        .with_default(false),
+        spec(
+            "security.trusted_proxies",
+            "TRUSTED_PROXIES",
+            S::Security,
+            T::List,
+            "CIDR ranges of proxies whose X-Forwarded-* headers are honoured.",
+        )
+        .with_default(DEFAULT_TRUSTED_PROXIES.join(",")),
+        spec(
+            "security.require_auth_for_browsing",
+            "REQUIRE_AUTH_FOR_BROWSING",
+            S::Security,
+            T::Bool,
+            "Require a session to list libraries, query media and fetch images. Playback always requires one.",
+        )
+        .with_default(true),
+        spec(
+            "security.hsts.max_age",
+            "HSTS_MAX_AGE",
+            S::Security,
+            T::Integer,
+            "Strict-Transport-Security max-age in seconds.",
+        )
+        .with_default(DEFAULT_HSTS_MAX_AGE_SECS),
+        spec(
+            "security.hsts.include_subdomains",
+            "HSTS_INCLUDE_SUBDOMAINS",
+            S::Security,
+            T::Bool,
+            "Add includeSubDomains to the HSTS header.",
+        )
+        .with_default(false),
+        spec(
+            "security.hsts.preload",
+            "HSTS_PRELOAD",
+            S::Security,
+            T::Bool,
+            "Add preload to the HSTS header. Requires includeSubDomains and a max-age of at least one year.",
+        )
+        .with_default(false),
+        spec(
+            "auth.password_pepper",
+            "AUTH_PASSWORD_PEPPER",
+            S::Auth,
+            T::String,
+            "Pepper mixed into password hashes; at least 32 characters.",
+        )
+        .secret()
+        .required_outside_dev_mode(),
+        spec(
+            "auth.token_key",
+            "AUTH_TOKEN_KEY",
+            S::Auth,
+            T::String,
+            "HMAC key for session tokens; at least 32 characters.",
+        )
+        .secret()
+        .required_outside_dev_mode(),
+        spec(
+            "auth.setup_token",
+            "FERREX_SETUP_TOKEN",
+            S::Auth,
+            T::String,
+            "Token required to claim the first admin account.",
+        )
+        .secret(),
+        spec(
+            "auth.session_ttl",
+            "AUTH_SESSION_TTL_SECS",
+            S::Auth,
+            T::Integer,
+            "Lifetime of access tokens in seconds.",
+        )
+        .with_default(DEFAULT_SESSION_TTL_SECS),
+        spec(
+            "auth.refresh_ttl",
+            "AUTH_REFRESH_TTL_SECS",
+            S::Auth,
+            T::Integer,
+            "Lifetime of refresh tokens in seconds.",
+        )
+        .with_default(DEFAULT_REFRESH_TTL_SECS),
+        spec(
+            "auth.device_challenge_ttl",
+            "AUTH_DEVICE_CHALLENGE_TTL_SECS",
+            S::Auth,
+            T::Integer,
+            "Lifetime of device PIN challenge nonces in seconds.",
+        )
+        .with_default(DEFAULT_DEVICE_CHALLENGE_TTL_SECS),
+        spec(
+            "auth.clock_skew_leeway",
+            "AUTH_CLOCK_SKEW_LEEWAY_SECS",
+            S::Auth,
+            T::Integer,
+            "Seconds past expiry that session and refresh tokens are still accepted, to absorb clock drift. At most 300.",
+        )
+        .with_default(DEFAULT_CLOCK_SKEW_LEEWAY_SECS),
+        spec(
+            "auth.strict_device_ids",
+            "AUTH_STRICT_DEVICE_IDS",
+            S::Auth,
+            T::Bool,
+            "Reject a device login whose X-Device-ID is already claimed by another user's device, rather than issuing the client a new id.",
+        )
+        .with_default(false),
+        spec(
+            "rate_limiter.path",
+            "RATE_LIMITS_PATH",
+            S::RateLimiter,
+            T::Path,
+            "Rate limit rules file. Takes precedence over RATE_LIMITS_JSON.",
+        ),
+        spec(
+            "rate_limiter.inline_json",
+            "RATE_LIMITS_JSON",
+            S::RateLimiter,
+            T::Json,
+            "Inline rate limit rules.",
+        ),
+        spec(
+            "scanner.path",
+            "SCANNER_CONFIG_PATH",
+            S::Scanner,
+            T::Path,
+            "Scanner settings file. Falls back to scanner.toml when present.",
+        ),
+        spec(
+            "scanner.inline_json",
+            "SCANNER_CONFIG_JSON",
+            S::Scanner,
+            T::Json,
+            "Inline scanner settings.",
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn schema_covers_every_env_var_the_loader_reads() {
+        let schema = config_schema();
+        let envs: BTreeSet<&str> = schema.iter().map(|s| s.env).collect();
+        assert_eq!(envs.len(), schema.len(), "duplicate env var in schema");
+
+        let file_envs: BTreeSet<String> =
+            schema.iter().filter_map(ConfigKeySpec::file_env).collect();
+        let read = regex::Regex::new(r#""([A-Z][A-Z0-9_]+)""#).unwrap();
+        let sources = concat!(
+            include_str!("models/sources.rs"),
+            include_str!("util.rs"),
+        );
+        for name in read.captures_iter(sources).map(|c| c[1].to_string()) {
+            assert!(
+                envs.contains(name.as_str()) || file_envs.contains(&name),
+                "{name} is read by the loader but missing from config_schema()"
+            );
+        }
+    }
+
+    #[test]
+    fn secret_file_keys_are_marked_secret() {
+        let schema = config_schema();
+        for key in SECRET_FILE_KEYS {
+            let spec = schema
+                .iter()
+                .find(|s| s.env == *key)
+                .unwrap_or_else(|| panic!("{key} missing from schema"));
+            assert!(spec.secret && spec.accepts_file, "{key}");
+        }
+        assert!(
+            schema
+                .iter()
+                .filter(|s| s.required == Requirement::OutsideDevMode)
+                .all(|s| s.secret)
+        );
+    }
+
+    #[test]
+    fn sources_prefer_files_over_the_environment() {
+        let mut metadata = ConfigMetadata::default();
+        metadata
+            .secret_files
+            .insert("AUTH_TOKEN_KEY".into(), "/run/secrets/token".into());
+        metadata
+            .fragment_sources
+            .insert("SERVER_PORT".into(), "config.d/10-base.toml".into());
+        let schema = config_schema();
+        let source = |env: &str| {
+            schema
+                .iter()
+                .find(|s| s.env == env)
+                .unwrap()
+                .source(&metadata, |env| env != "SERVER_HOST")
+        };
+
+        assert_eq!(
+            source("AUTH_TOKEN_KEY"),
+            ValueSource::SecretFile {
+                path: "/run/secrets/token".into()
+            }
+        );
+        assert_eq!(
+            source("SERVER_PORT"),
+            ValueSource::Fragment {
+                path: "config.d/10-base.toml".into()
+            }
+        );
+        assert_eq!(source("REDIS_URL"), ValueSource::Env);
+        assert_eq!(source("SERVER_HOST"), ValueSource::Default);
+    }
+}
@@ -3,28 +3,38 @@ use std::path::PathBuf;
 use crate::models::rate_limits::RateLimitSpec;
 
 pub fn parse_csv_var(name: &str) -> Option<Vec<String>> {
-    std::env::var(name).ok().map(|raw| {
-        raw.split(',')
-            .filter_map(|part| {
-                let trimmed = part.trim();
-                if trimmed.is_empty() {
-                    None
-                } else {
-                    Some(trimmed.to_string())
-                }
-            })
-            .collect()
-    })
+    std::env::var(name).ok().map(|raw| parse_csv(&raw))
+}
+
+/// Split a comma-separated value, dropping empty entries.
+pub fn parse_csv(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .filter_map(|part| {
+            let trimmed = part.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            }
+        })
+        .collect()
 }
 
 pub fn rate_limit_spec_from_env() -> Option<RateLimitSpec> {
-    if let Ok(path) = std::env::var("RATE_LIMITS_PATH")
+    rate_limit_spec_from(|key| std::env::var(key).ok())
+}
+
+/// Like [`rate_limit_spec_from_env`], reading variables through `var`.
+pub fn rate_limit_spec_from(
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<RateLimitSpec> {
+    if let Some(path) = var("RATE_LIMITS_PATH")
         && !path.trim().is_empty()
     {
         return Some(RateLimitSpec::Path(PathBuf::from(path)));
     }
 
-    if let Ok(raw) = std::env::var("RATE_LIMITS_JSON") {
+    if let Some(raw) = var("RATE_LIMITS_JSON") {
         if raw.trim().is_empty() {
             None
         } else {
@@ -1,7 +1,20 @@
+use std::time::Duration;
+
 use axum::http::{Method, header::HeaderName};
+use regex::Regex;
+use serde::Serialize;
 use thiserror::Error;
 
-use super::models::{AuthConfig, Config, CorsConfig, RateLimiterSettings};
+use super::models::{
+    AuthConfig, CacheConfig, Config, CorsConfig, FfmpegConfig,
+    LibraryListingDefaults, MediaConfig, RateLimiterSettings, RedisConfig,
+    SecurityConfig, ServerConfig, rate_limits::RateLimitAllowlist,
+    scanner::ScannerConfig, trusted_proxies::IpRange,
+};
+use crate::constants::{
+    FILENAME_RULE_GROUPS, LIBRARY_LISTING_FILTERS, LIBRARY_SORT_FIELDS,
+    MAX_REDIS_POOL_SIZE,
+};
 
 #[derive(Debug, Error)]
 pub enum ConfigGuardRailError {
@@ -15,9 +28,90 @@ pub enum ConfigGuardRailError {
         "rate limiter configured but no supported backend is available in non-dev mode"
     )]
     MissingRateLimiterBackend,
+    #[error("invalid token lifetime: {reason}")]
+    InvalidTokenTtl { reason: String },
+    #[error("REQUEST_ID_HEADER `{0}` is not a valid HTTP header name")]
+    InvalidRequestIdHeader(String),
+    #[error("LOG_LEVEL `{value}` is not a valid level filter: {reason}")]
+    InvalidLogLevel { value: String, reason: String },
+    #[error("media.filename_rules[{index}] `{pattern}` is invalid: {reason}")]
+    InvalidFilenameRule {
+        index: usize,
+        pattern: String,
+        reason: String,
+    },
+    #[error("media.{key} `{value}` is invalid: {reason}")]
+    InvalidLibraryDefault {
+        key: &'static str,
+        value: String,
+        reason: String,
+    },
+    #[error("invalid Redis configuration: {reason}")]
+    InvalidRedisConfig { reason: String },
+    #[error("security.trusted_proxies[{index}] `{range}` is invalid: {reason}")]
+    InvalidTrustedProxy {
+        index: usize,
+        range: String,
+        reason: String,
+    },
+    #[error(
+        "rate_limiter.allowlist.{field}[{index}] `{value}` is invalid: {reason}"
+    )]
+    InvalidRateLimitAllowlist {
+        field: &'static str,
+        index: usize,
+        value: String,
+        reason: String,
+    },
+    #[error("cache encryption is enabled but CACHE_ENCRYPTION_KEY {reason}")]
+    InvalidCacheEncryptionKey { reason: String },
+    #[error("ffmpeg.{key} is {value} but must be between {min} and {max}")]
+    ThumbnailSettingOutOfRange {
+        key: &'static str,
+        value: u32,
+        min: u32,
+        max: u32,
+    },
+    #[error(
+        "HSTS_PRELOAD is set but the header is not preload-eligible: {reason}"
+    )]
+    HstsPreloadIneligible { reason: String },
+    #[error(
+        "scanner.library_quiescence_window_ms `{library}` = {value} is invalid: {reason}"
+    )]
+    InvalidLibraryQuiescence {
+        library: String,
+        value: u64,
+        reason: String,
+    },
 }
 
-#[derive(Debug, Clone)]
+/// Shortest accepted authentication secret or cache encryption key.
+const MIN_SECRET_LENGTH: usize = 32;
+
+/// Accepted thumbnail widths and height caps, in pixels.
+const THUMBNAIL_DIMENSION_RANGE: (u32, u32) = (64, 1920);
+/// Accepted thumbnail JPEG qualities; lower settings produce visible
+/// artefacts at thumbnail sizes.
+const THUMBNAIL_QUALITY_RANGE: (u32, u32) = (30, 100);
+
+/// Accepted per-library quiescence windows, in milliseconds. Shorter
+/// windows complete scans before the watcher has flushed its batch; longer
+/// ones leave a scan looking stuck for minutes.
+const LIBRARY_QUIESCENCE_RANGE_MS: (u64, u64) = (250, 600_000);
+
+/// Shortest HSTS max-age the browser preload lists accept (one year).
+const HSTS_PRELOAD_MIN_MAX_AGE: u64 = 31_536_000;
+
+/// Shortest lifetime accepted for any issued token or challenge.
+const MIN_TOKEN_TTL: Duration = Duration::from_secs(60);
+/// Refresh tokens living longer than this trigger a warning (90 days).
+const MAX_SAFE_REFRESH_TTL: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+/// Largest clock skew leeway accepted; beyond this it stops being drift
+/// and starts extending every token's lifetime.
+const MAX_CLOCK_SKEW_LEEWAY: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ConfigWarning {
     pub message: String,
     pub hint: Option<String>,
@@ -69,6 +163,25 @@ pub fn apply_guard_rails(
     }
 
     validate_cors(&config.cors)?;
+    validate_request_id_header(&config.server)?;
+    validate_log_level(&config.server)?;
+    validate_filename_rules(&config.media)?;
+    validate_library_defaults(&config.media)?;
+    validate_trusted_proxies(&config.security)?;
+    if let Some(rate_limiter) = &config.rate_limiter {
+        validate_rate_limit_allowlist(
+            &rate_limiter.config.allowlist,
+            &mut warnings,
+        )?;
+    }
+    validate_hsts(&config.security, &mut warnings)?;
+    validate_token_ttls(&config.auth, &mut warnings)?;
+    validate_cache_encryption(&config.cache)?;
+    validate_thumbnails(&config.ffmpeg)?;
+    validate_library_quiescence(&config.scanner)?;
+    if let Some(redis) = &config.redis {
+        validate_redis(redis)?;
+    }
 
     if config.redis.is_none() {
         if !config.dev_mode && rate_limiter_configured(&config.rate_limiter) {
@@ -106,8 +219,6 @@ fn enforce_secret(
     auth: &AuthConfig,
     warnings: &mut ConfigWarnings,
 ) -> Result<(), ConfigGuardRailError> {
-    const MIN_SECRET_LENGTH: usize = 32;
-
     if auth.is_default_pepper() {
         return Err(ConfigGuardRailError::WeakSecret {
             field: "AUTH_PASSWORD_PEPPER",
@@ -146,10 +257,370 @@ fn enforce_secret(
     Ok(())
 }
 
+/// Enabling encryption without a usable key would leave the cache in the
+/// clear while the operator believes otherwise, so it refuses to start.
+fn validate_cache_encryption(
+    cache: &CacheConfig,
+) -> Result<(), ConfigGuardRailError> {
+    if !cache.encrypt_at_rest {
+        return Ok(());
+    }
+    match cache.encryption_key.as_deref() {
+        None => Err(ConfigGuardRailError::InvalidCacheEncryptionKey {
+            reason: "is not set".into(),
+        }),
+        Some(key) if key.len() < MIN_SECRET_LENGTH => {
+            Err(ConfigGuardRailError::InvalidCacheEncryptionKey {
+                reason: format!(
+                    "must be at least {MIN_SECRET_LENGTH} characters"
+                ),
+            })
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+fn validate_token_ttls(
+    auth: &AuthConfig,
+    warnings: &mut ConfigWarnings,
+) -> Result<(), ConfigGuardRailError> {
+    for (field, ttl) in [
+        ("AUTH_SESSION_TTL_SECS", auth.session_ttl),
+        ("AUTH_REFRESH_TTL_SECS", auth.refresh_ttl),
+        ("AUTH_DEVICE_CHALLENGE_TTL_SECS", auth.device_challenge_ttl),
+    ] {
+        if ttl < MIN_TOKEN_TTL {
+            return Err(ConfigGuardRailError::InvalidTokenTtl {
+                reason: format!(
+                    "{field} is {}s; it must be at least {}s",
+                    ttl.as_secs(),
+                    MIN_TOKEN_TTL.as_secs()
+                ),
+            });
+        }
+    }
+
+    if auth.session_ttl > auth.refresh_ttl {
+        return Err(ConfigGuardRailError::InvalidTokenTtl {
+            reason: format!(
+                "AUTH_SESSION_TTL_SECS ({}s) must not exceed AUTH_REFRESH_TTL_SECS ({}s)",
+                auth.session_ttl.as_secs(),
+                auth.refresh_ttl.as_secs()
+            ),
+        });
+    }
+
+    if auth.clock_skew_leeway > MAX_CLOCK_SKEW_LEEWAY {
+        return Err(ConfigGuardRailError::InvalidTokenTtl {
+            reason: format!(
+                "AUTH_CLOCK_SKEW_LEEWAY_SECS is {}s; it must be at most {}s",
+                auth.clock_skew_leeway.as_secs(),
+                MAX_CLOCK_SKEW_LEEWAY.as_secs()
+            ),
+        });
+    }
+
+    if auth.refresh_ttl > MAX_SAFE_REFRESH_TTL {
+        warnings.push_with_hint(
+            format!(
+                "AUTH_REFRESH_TTL_SECS is {} days; stolen refresh tokens stay usable for that long",
+                auth.refresh_ttl.as_secs() / 86_400
+            ),
+            format!(
+                "Keep refresh tokens at or below {} days unless clients cannot re-authenticate",
+                MAX_SAFE_REFRESH_TTL.as_secs() / 86_400
+            ),
+        );
+    }
+
+    Ok(())
+}
+
 fn rate_limiter_configured(rate_limiter: &Option<RateLimiterSettings>) -> bool {
     rate_limiter.as_ref().map(|_| true).unwrap_or(false)
 }
 
+fn validate_request_id_header(
+    server: &ServerConfig,
+) -> Result<(), ConfigGuardRailError> {
+    HeaderName::from_bytes(server.request_id_header.as_bytes())
+        .map(|_| ())
+        .map_err(|_| {
+            ConfigGuardRailError::InvalidRequestIdHeader(
+                server.request_id_header.clone(),
+            )
+        })
+}
+
+/// Accepts anything `RUST_LOG` would, e.g. `info,scan=debug`.
+fn validate_log_level(
+    server: &ServerConfig,
+) -> Result<(), ConfigGuardRailError> {
+    let Some(level) = &server.log_level else {
+        return Ok(());
+    };
+    tracing_subscriber::EnvFilter::try_new(level)
+        .map(|_| ())
+        .map_err(|err| ConfigGuardRailError::InvalidLogLevel {
+            value: level.clone(),
+            reason: err.to_string(),
+        })
+}
+
+fn validate_redis(redis: &RedisConfig) -> Result<(), ConfigGuardRailError> {
+    let invalid =
+        |reason: String| ConfigGuardRailError::InvalidRedisConfig { reason };
+    if !(1..=MAX_REDIS_POOL_SIZE).contains(&redis.pool_size) {
+        return Err(invalid(format!(
+            "REDIS_POOL_SIZE must be between 1 and {MAX_REDIS_POOL_SIZE}, got {}",
+            redis.pool_size
+        )));
+    }
+    if redis.connect_timeout.is_zero() {
+        return Err(invalid(
+            "REDIS_CONNECT_TIMEOUT_SECS must be positive".into(),
+        ));
+    }
+    if redis.command_timeout.is_zero() {
+        return Err(invalid(
+            "REDIS_COMMAND_TIMEOUT_MS must be positive".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_trusted_proxies(
+    security: &SecurityConfig,
+) -> Result<(), ConfigGuardRailError> {
+    for (index, range) in security.trusted_proxies.iter().enumerate() {
+        range.parse::<IpRange>().map_err(|reason| {
+            ConfigGuardRailError::InvalidTrustedProxy {
+                index,
+                range: range.clone(),
+                reason,
+            }
+        })?;
+    }
+    Ok(())
+}
+
+fn validate_rate_limit_allowlist(
+    allowlist: &RateLimitAllowlist,
+    warnings: &mut ConfigWarnings,
+) -> Result<(), ConfigGuardRailError> {
+    for (index, id) in allowlist.device_ids.iter().enumerate() {
+        uuid::Uuid::parse_str(id.trim()).map_err(|err| {
+            ConfigGuardRailError::InvalidRateLimitAllowlist {
+                field: "device_ids",
+                index,
+                value: id.clone(),
+                reason: err.to_string(),
+            }
+        })?;
+    }
+    for (index, range) in allowlist.ip_ranges.iter().enumerate() {
+        range.parse::<IpRange>().map_err(|reason| {
+            ConfigGuardRailError::InvalidRateLimitAllowlist {
+                field: "ip_ranges",
+                index,
+                value: range.clone(),
+                reason,
+            }
+        })?;
+    }
+    if !allowlist.ip_ranges.is_empty() {
+        warnings.push_with_hint(
+            format!(
+                "Rate limiting is bypassed for {} allowlisted IP range(s)",
+                allowlist.ip_ranges.len()
+            ),
+            "Everyone behind an allowlisted range can retry logins and PINs unthrottled; prefer single addresses",
+        );
+    }
+    Ok(())
+}
+
+/// Preload lists reject headers without `includeSubDomains` or with a
+/// max-age under a year, so such a header is refused up front.
+fn validate_hsts(
+    security: &SecurityConfig,
+    warnings: &mut ConfigWarnings,
+) -> Result<(), ConfigGuardRailError> {
+    let hsts = &security.hsts;
+    if hsts.preload {
+        if hsts.max_age < HSTS_PRELOAD_MIN_MAX_AGE {
+            return Err(ConfigGuardRailError::HstsPreloadIneligible {
+                reason: format!(
+                    "HSTS_MAX_AGE is {} but must be at least {HSTS_PRELOAD_MIN_MAX_AGE}",
+                    hsts.max_age
+                ),
+            });
+        }
+        if !hsts.include_subdomains {
+            return Err(ConfigGuardRailError::HstsPreloadIneligible {
+                reason: "HSTS_INCLUDE_SUBDOMAINS must be true".into(),
+            });
+        }
+    }
+
+    if hsts.max_age > 0 && !security.enforce_https {
+        warnings.push_with_hint(
+            "HSTS is configured but ENFORCE_HTTPS is false",
+            "The Strict-Transport-Security header is only sent on HTTPS responses; plain HTTP clients never see it",
+        );
+    }
+    Ok(())
+}
+
+fn validate_thumbnails(
+    ffmpeg: &FfmpegConfig,
+) -> Result<(), ConfigGuardRailError> {
+    let check = |key: &'static str, value: u32, (min, max): (u32, u32)| {
+        if (min..=max).contains(&value) {
+            Ok(())
+        } else {
+            Err(ConfigGuardRailError::ThumbnailSettingOutOfRange {
+                key,
+                value,
+                min,
+                max,
+            })
+        }
+    };
+
+    check(
+        "thumbnail_width",
+        ffmpeg.thumbnail_width,
+        THUMBNAIL_DIMENSION_RANGE,
+    )?;
+    if let Some(max_height) = ffmpeg.thumbnail_max_height {
+        check(
+            "thumbnail_max_height",
+            max_height,
+            THUMBNAIL_DIMENSION_RANGE,
+        )?;
+    }
+    check(
+        "thumbnail_quality",
+        u32::from(ffmpeg.thumbnail_quality),
+        THUMBNAIL_QUALITY_RANGE,
+    )
+}
+
+fn validate_library_quiescence(
+    scanner: &ScannerConfig,
+) -> Result<(), ConfigGuardRailError> {
+    let (min, max) = LIBRARY_QUIESCENCE_RANGE_MS;
+    for (library, &value) in &scanner.library_quiescence_window_ms {
+        let invalid =
+            |reason: String| ConfigGuardRailError::InvalidLibraryQuiescence {
+                library: library.clone(),
+                value,
+                reason,
+            };
+        if library.trim().is_empty() {
+            return Err(invalid("library key is empty".into()));
+        }
+        if !(min..=max).contains(&value) {
+            return Err(invalid(format!("must be between {min} and {max} ms")));
+        }
+    }
+    Ok(())
+}
+
+fn validate_filename_rules(
+    media: &MediaConfig,
+) -> Result<(), ConfigGuardRailError> {
+    for (index, pattern) in media.filename_rules.iter().enumerate() {
+        let invalid =
+            |reason: String| ConfigGuardRailError::InvalidFilenameRule {
+                index,
+                pattern: pattern.clone(),
+                reason,
+            };
+        let regex = Regex::new(pattern).map_err(|e| invalid(e.to_string()))?;
+        let names: Vec<&str> = regex.capture_names().flatten().collect();
+        if let Some(unknown) = names
+            .iter()
+            .find(|name| !FILENAME_RULE_GROUPS.contains(name))
+        {
+            return Err(invalid(format!(
+                "unknown group `{}` (expected one of {})",
+                unknown,
+                FILENAME_RULE_GROUPS.join(", ")
+            )));
+        }
+        if !names.contains(&"episode") && !names.contains(&"title") {
+            return Err(invalid(
+                "needs an `episode` group (episodes) or a `title` group (movies)"
+                    .into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_library_defaults(
+    media: &MediaConfig,
+) -> Result<(), ConfigGuardRailError> {
+    validate_listing_defaults(
+        ("movie_defaults.sort", "movie_defaults.filter"),
+        &media.movie_defaults,
+    )?;
+    validate_listing_defaults(
+        ("series_defaults.sort", "series_defaults.filter"),
+        &media.series_defaults,
+    )?;
+    Ok(())
+}
+
+fn validate_listing_defaults(
+    (sort_key, filter_key): (&'static str, &'static str),
+    defaults: &LibraryListingDefaults,
+) -> Result<(), ConfigGuardRailError> {
+    let invalid = |key: &'static str, value: &str, reason: String| {
+        ConfigGuardRailError::InvalidLibraryDefault {
+            key,
+            value: value.to_string(),
+            reason,
+        }
+    };
+
+    if let Some((field, order)) = defaults.sort_parts() {
+        let sort = defaults.sort.as_deref().unwrap_or_default();
+        if !LIBRARY_SORT_FIELDS.contains(&field) {
+            return Err(invalid(
+                sort_key,
+                sort,
+                format!(
+                    "unknown sort field (expected one of {})",
+                    LIBRARY_SORT_FIELDS.join(", ")
+                ),
+            ));
+        }
+        if order.is_some_and(|order| !matches!(order, "asc" | "desc")) {
+            return Err(invalid(
+                sort_key,
+                sort,
+                "order must be `asc` or `desc`".into(),
+            ));
+        }
+    }
+    if let Some(filter) = defaults.filter.as_deref()
+        && !LIBRARY_LISTING_FILTERS.contains(&filter)
+    {
+        return Err(invalid(
+            filter_key,
+            filter,
+            format!(
+                "unknown filter (expected one of {})",
+                LIBRARY_LISTING_FILTERS.join(", ")
+            ),
+        ));
+    }
+    Ok(())
+}
+
 fn validate_cors(cors: &CorsConfig) -> Result<(), ConfigGuardRailError> {
     if cors.allowed_methods.is_empty() {
         return Err(ConfigGuardRailError::InvalidCorsConfig {
@@ -191,3 +662,440 @@ fn validate_cors(cors: &CorsConfig) -> Result<(), ConfigGuardRailError> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        DEFAULT_CLOCK_SKEW_LEEWAY_SECS, DEFAULT_DEVICE_CHALLENGE_TTL_SECS,
+        DEFAULT_REFRESH_TTL_SECS, DEFAULT_SESSION_TTL_SECS,
+    };
+    use crate::models::HstsSettings;
+    use std::collections::HashMap;
+
+    fn auth(session: u64, refresh: u64, challenge: u64) -> AuthConfig {
+        AuthConfig {
+            password_pepper: "pepper".into(),
+            token_key: "key".into(),
+            setup_token: None,
+            session_ttl: Duration::from_secs(session),
+            refresh_ttl: Duration::from_secs(refresh),
+            device_challenge_ttl: Duration::from_secs(challenge),
+            clock_skew_leeway: Duration::from_secs(
+                DEFAULT_CLOCK_SKEW_LEEWAY_SECS,
+            ),
+            strict_device_ids: false,
+        }
+    }
+
+    #[test]
+    fn default_token_ttls_pass_without_warnings() {
+        let mut warnings = ConfigWarnings::default();
+        validate_token_ttls(
+            &auth(
+                DEFAULT_SESSION_TTL_SECS,
+                DEFAULT_REFRESH_TTL_SECS,
+                DEFAULT_DEVICE_CHALLENGE_TTL_SECS,
+            ),
+            &mut warnings,
+        )
+        .expect("defaults are valid");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn sub_minute_ttls_are_rejected() {
+        let mut warnings = ConfigWarnings::default();
+        for config in [
+            auth(30, 86_400, 120),
+            auth(3_600, 59, 120),
+            auth(3_600, 86_400, 10),
+        ] {
+            let err = validate_token_ttls(&config, &mut warnings)
+                .expect_err("sub-minute ttl");
+            assert!(matches!(
+                err,
+                ConfigGuardRailError::InvalidTokenTtl { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn session_ttl_must_not_exceed_refresh_ttl() {
+        let mut warnings = ConfigWarnings::default();
+        let err = validate_token_ttls(&auth(7_200, 3_600, 120), &mut warnings)
+            .expect_err("session outlives refresh");
+        assert!(err.to_string().contains("must not exceed"));
+
+        validate_token_ttls(&auth(3_600, 3_600, 120), &mut warnings)
+            .expect("equal lifetimes are allowed");
+    }
+
+    #[test]
+    fn clock_skew_leeway_is_bounded() {
+        let mut warnings = ConfigWarnings::default();
+        let mut config = auth(3_600, 86_400, 120);
+
+        config.clock_skew_leeway = Duration::from_secs(300);
+        validate_token_ttls(&config, &mut warnings)
+            .expect("five minutes of leeway is allowed");
+
+        config.clock_skew_leeway = Duration::from_secs(86_400);
+        let err = validate_token_ttls(&config, &mut warnings)
+            .expect_err("a day of leeway");
+        assert!(err.to_string().contains("AUTH_CLOCK_SKEW_LEEWAY_SECS"));
+    }
+
+    #[test]
+    fn long_refresh_ttl_only_warns() {
+        let mut warnings = ConfigWarnings::default();
+        validate_token_ttls(&auth(3_600, 365 * 86_400, 120), &mut warnings)
+            .expect("long refresh ttl is allowed");
+        assert_eq!(warnings.items.len(), 1);
+        assert!(warnings.items[0].message.contains("365 days"));
+    }
+
+    #[test]
+    fn request_id_header_must_be_a_header_name() {
+        let server = |name: &str| ServerConfig {
+            host: "0.0.0.0".into(),
+            port: 3000,
+            request_id_header: name.into(),
+            request_timeout: None,
+            slow_request_timeout: None,
+            max_concurrent_streams: None,
+            max_concurrent_transcodes: None,
+            stream_bandwidth_limit_kbps: None,
+            remote_media_redirect_hosts: Vec::new(),
+            compression_enabled: false,
+            compression_min_bytes: 0,
+            log_format: Default::default(),
+            log_level: None,
+        };
+        validate_request_id_header(&server("x-correlation-id"))
+            .expect("valid header name");
+        let err = validate_request_id_header(&server("x request id"))
+            .expect_err("spaces are not allowed");
+        assert!(matches!(
+            err,
+            ConfigGuardRailError::InvalidRequestIdHeader(_)
+        ));
+    }
+
+    #[test]
+    fn log_level_accepts_env_filter_directives() {
+        let server = |level: Option<&str>| ServerConfig {
+            host: "0.0.0.0".into(),
+            port: 3000,
+            request_id_header: "x-request-id".into(),
+            request_timeout: None,
+            slow_request_timeout: None,
+            max_concurrent_streams: None,
+            max_concurrent_transcodes: None,
+            stream_bandwidth_limit_kbps: None,
+            remote_media_redirect_hosts: Vec::new(),
+            compression_enabled: false,
+            compression_min_bytes: 0,
+            log_format: Default::default(),
+            log_level: level.map(str::to_string),
+        };
+        validate_log_level(&server(None)).expect("unset level");
+        validate_log_level(&server(Some("debug"))).expect("plain level");
+        validate_log_level(&server(Some("info,scan::summary=debug")))
+            .expect("directive list");
+        let err = validate_log_level(&server(Some("info,scan=loud")))
+            .expect_err("unknown level");
+        assert!(matches!(err, ConfigGuardRailError::InvalidLogLevel { .. }));
+    }
+
+    #[test]
+    fn log_format_parses_known_values() {
+        use crate::models::LogFormat;
+        assert_eq!(" JSON ".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert_eq!("pretty".parse::<LogFormat>(), Ok(LogFormat::Pretty));
+        assert!("logfmt".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn redis_pool_size_is_bounded() {
+        let redis = |pool_size: usize| RedisConfig {
+            url: "redis://127.0.0.1:6379".into(),
+            pool_size,
+            connect_timeout: Duration::from_secs(5),
+            command_timeout: Duration::from_millis(500),
+            outage_policy: Default::default(),
+        };
+        validate_redis(&redis(1)).expect("smallest pool");
+        validate_redis(&redis(MAX_REDIS_POOL_SIZE)).expect("largest pool");
+        for size in [0, MAX_REDIS_POOL_SIZE + 1] {
+            let err = validate_redis(&redis(size)).expect_err("out of bounds");
+            assert!(err.to_string().contains("REDIS_POOL_SIZE"), "{err}");
+        }
+
+        let mut zero_timeout = redis(4);
+        zero_timeout.command_timeout = Duration::ZERO;
+        assert!(validate_redis(&zero_timeout).is_err());
+    }
+
+    #[test]
+    fn redis_outage_policy_parses_both_spellings() {
+        use crate::models::RedisOutagePolicy;
+        assert_eq!(
+            "fail-closed".parse::<RedisOutagePolicy>(),
+            Ok(RedisOutagePolicy::FailClosed)
+        );
+        assert_eq!(
+            " FAIL_OPEN ".parse::<RedisOutagePolicy>(),
+            Ok(RedisOutagePolicy::FailOpen)
+        );
+        assert!("maybe".parse::<RedisOutagePolicy>().is_err());
+    }
+
+    #[test]
+    fn filename_rules_report_the_bad_pattern() {
+        let media = |rules: &[&str]| MediaConfig {
+            root: None,
+            metadata_languages: Vec::new(),
+            filename_rules: rules.iter().map(|rule| rule.to_string()).collect(),
+            movie_defaults: LibraryListingDefaults::default(),
+            series_defaults: LibraryListingDefaults::default(),
+        };
+        validate_filename_rules(&media(&[
+            r"^\[[^\]]+\]\s*(?P<show>.+?) - (?P<episode>\d+)",
+        ]))
+        .expect("valid rule");
+
+        let err = validate_filename_rules(&media(&[
+            r"(?P<title>.+)",
+            r"(?P<show>.+) (?P<episode>\d+",
+        ]))
+        .expect_err("unclosed group");
+        assert!(matches!(
+            err,
+            ConfigGuardRailError::InvalidFilenameRule { index: 1, .. }
+        ));
+
+        let err = validate_filename_rules(&media(&[r"(?P<ep>\d+)"]))
+            .expect_err("unknown group");
+        assert!(err.to_string().contains("unknown group `ep`"), "{err}");
+    }
+
+    #[test]
+    fn library_defaults_must_name_known_sorts_and_filters() {
+        let media = |sort: &str, filter: Option<&str>| MediaConfig {
+            root: None,
+            metadata_languages: Vec::new(),
+            filename_rules: Vec::new(),
+            movie_defaults: LibraryListingDefaults {
+                sort: Some(sort.to_string()),
+                filter: filter.map(str::to_string),
+            },
+            series_defaults: LibraryListingDefaults::default(),
+        };
+        validate_library_defaults(&media("date_added:desc", Some("unwatched")))
+            .expect("valid defaults");
+        validate_library_defaults(&media("title", None)).expect("bare field");
+
+        let err = validate_library_defaults(&media("newest", None))
+            .expect_err("unknown field");
+        assert!(err.to_string().contains("movie_defaults.sort"), "{err}");
+        assert!(
+            validate_library_defaults(&media("title:sideways", None)).is_err()
+        );
+        let err = validate_library_defaults(&media("title", Some("seen")))
+            .expect_err("unknown filter");
+        assert!(err.to_string().contains("movie_defaults.filter"), "{err}");
+    }
+
+    #[test]
+    fn trusted_proxies_must_be_cidr_ranges() {
+        let security = |ranges: &[&str]| SecurityConfig {
+            enforce_https: true,
+            trust_proxy_headers: true,
+            trusted_proxies: ranges.iter().map(|r| r.to_string()).collect(),
+            require_auth_for_browsing: true,
+            hsts: HstsSettings {
+                max_age: 0,
+                include_subdomains: false,
+                preload: false,
+            },
+        };
+        validate_trusted_proxies(&security(&["10.0.0.0/8", "fd00::/8"]))
+            .expect("valid ranges");
+
+        let err =
+            validate_trusted_proxies(&security(&["10.0.0.0/8", "10.0.0.0/40"]))
+                .expect_err("prefix too long");
+        assert!(matches!(
+            err,
+            ConfigGuardRailError::InvalidTrustedProxy { index: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn rate_limit_allowlist_entries_must_parse() {
+        let allowlist =
+            |device_ids: &[&str], ip_ranges: &[&str]| RateLimitAllowlist {
+                device_ids: device_ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect(),
+                ip_ranges: ip_ranges.iter().map(|r| r.to_string()).collect(),
+            };
+        let mut warnings = ConfigWarnings::default();
+        validate_rate_limit_allowlist(
+            &allowlist(&["7a1e3c52-1f0b-4a8e-9d61-2b9f1c4e8a10"], &[]),
+            &mut warnings,
+        )
+        .expect("valid device id");
+        assert!(warnings.is_empty());
+
+        validate_rate_limit_allowlist(
+            &allowlist(&[], &["192.168.1.20", "10.0.0.0/8"]),
+            &mut warnings,
+        )
+        .expect("valid ranges");
+        assert_eq!(warnings.items.len(), 1, "IP ranges are a tradeoff");
+
+        let err = validate_rate_limit_allowlist(
+            &allowlist(&["living-room-tv"], &[]),
+            &mut warnings,
+        )
+        .expect_err("not a uuid");
+        assert!(matches!(
+            err,
+            ConfigGuardRailError::InvalidRateLimitAllowlist {
+                field: "device_ids",
+                index: 0,
+                ..
+            }
+        ));
+        let err = validate_rate_limit_allowlist(
+            &allowlist(&[], &["10.0.0.0/8", "10.0.0.0/33"]),
+            &mut warnings,
+        )
+        .expect_err("prefix too long");
+        assert!(matches!(
+            err,
+            ConfigGuardRailError::InvalidRateLimitAllowlist {
+                field: "ip_ranges",
+                index: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn hsts_preload_requires_an_eligible_header() {
+        let security =
+            |max_age: u64, include_subdomains: bool| SecurityConfig {
+                enforce_https: true,
+                trust_proxy_headers: true,
+                trusted_proxies: Vec::new(),
+                require_auth_for_browsing: true,
+                hsts: HstsSettings {
+                    max_age,
+                    include_subdomains,
+                    preload: true,
+                },
+            };
+        let mut warnings = ConfigWarnings::default();
+        validate_hsts(&security(HSTS_PRELOAD_MIN_MAX_AGE, true), &mut warnings)
+            .expect("eligible");
+        assert!(warnings.is_empty());
+
+        for (max_age, include_subdomains, field) in [
+            (HSTS_PRELOAD_MIN_MAX_AGE - 1, true, "HSTS_MAX_AGE"),
+            (HSTS_PRELOAD_MIN_MAX_AGE, false, "HSTS_INCLUDE_SUBDOMAINS"),
+        ] {
+            let err = validate_hsts(
+                &security(max_age, include_subdomains),
+                &mut warnings,
+            )
+            .expect_err("ineligible");
+            assert!(err.to_string().contains(field), "{err}");
+        }
+
+        let plain_http = SecurityConfig {
+            enforce_https: false,
+            ..security(HSTS_PRELOAD_MIN_MAX_AGE, true)
+        };
+        validate_hsts(&plain_http, &mut warnings).expect("only warns");
+        assert_eq!(warnings.items.len(), 1);
+        assert!(warnings.items[0].message.contains("ENFORCE_HTTPS"));
+    }
+
+    #[test]
+    fn thumbnail_settings_must_be_within_bounds() {
+        let ffmpeg =
+            |width: u32, max_height: Option<u32>, quality: u8| FfmpegConfig {
+                ffmpeg_path: "ffmpeg".into(),
+                ffprobe_path: "ffprobe".into(),
+                thumbnail_strategy: Default::default(),
+                thumbnail_width: width,
+                thumbnail_max_height: max_height,
+                thumbnail_quality: quality,
+            };
+        validate_thumbnails(&ffmpeg(320, None, 85)).expect("defaults");
+        validate_thumbnails(&ffmpeg(1920, Some(64), 100)).expect("bounds");
+
+        for (config, key) in [
+            (ffmpeg(32, None, 85), "thumbnail_width"),
+            (ffmpeg(3840, None, 85), "thumbnail_width"),
+            (ffmpeg(320, Some(0), 85), "thumbnail_max_height"),
+            (ffmpeg(320, None, 0), "thumbnail_quality"),
+            (ffmpeg(320, None, 101), "thumbnail_quality"),
+        ] {
+            let err = validate_thumbnails(&config).expect_err(key);
+            assert!(err.to_string().contains(key), "{err}");
+        }
+    }
+
+    #[test]
+    fn library_quiescence_overrides_must_be_within_bounds() {
+        let scanner = |key: &str, ms: u64| ScannerConfig {
+            library_quiescence_window_ms: HashMap::from([(key.into(), ms)]),
+            ..ScannerConfig::default()
+        };
+        validate_library_quiescence(&ScannerConfig::default())
+            .expect("no overrides");
+        validate_library_quiescence(&scanner("Movies", 250)).expect("min");
+        validate_library_quiescence(&scanner("Movies", 600_000)).expect("max");
+
+        for (config, reason) in [
+            (scanner("Movies", 0), "between"),
+            (scanner("Movies", 600_001), "between"),
+            (scanner("  ", 1_000), "empty"),
+        ] {
+            let err = validate_library_quiescence(&config).expect_err(reason);
+            assert!(err.to_string().contains(reason), "{err}");
+        }
+    }
+
+    #[test]
+    fn cache_encryption_needs_a_strong_key() {
+        let cache = |enabled: bool, key: Option<&str>| CacheConfig {
+            root: "cache".into(),
+            images: "cache/images".into(),
+            transcode: "cache/transcode".into(),
+            thumbnails: "cache/thumbnails".into(),
+            hls_max_bytes: 0,
+            min_free_bytes: 0,
+            image_failure_ttl_secs: 0,
+            encrypt_at_rest: enabled,
+            encryption_key: key.map(str::to_string),
+            image_store: Default::default(),
+            image_store_s3: None,
+        };
+        validate_cache_encryption(&cache(false, None)).expect("disabled");
+        validate_cache_encryption(&cache(true, Some(&"k".repeat(32))))
+            .expect("strong key");
+
+        for config in [cache(true, None), cache(true, Some("short"))] {
+            assert!(matches!(
+                validate_cache_encryption(&config),
+                Err(ConfigGuardRailError::InvalidCacheEncryptionKey { .. })
+            ));
+        }
+    }
+}